@@ -166,7 +166,7 @@ pub async fn context_query(
         .collect();
         
     // Query for security policies in this project
-    let mut stmt = db.prepare("SELECT id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, created_at FROM security_policies WHERE project_id = ?1").unwrap();
+    let mut stmt = db.prepare("SELECT id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, severity, classification, created_at FROM security_policies WHERE project_id = ?1").unwrap();
     let security_policies = stmt
         .query_map(rusqlite::params![query.project_id], |row| {
             Ok(SecurityPolicy {
@@ -178,7 +178,9 @@ pub async fn context_query(
                 implementation_pattern: row.get(5).ok(),
                 forbidden_patterns: row.get(6).ok(),
                 compliance_notes: row.get(7).ok(),
-                created_at: row.get(8).ok(),
+                severity: row.get(8).ok(),
+                classification: row.get(9).ok(),
+                created_at: row.get(10).ok(),
             })
         })
         .unwrap()
@@ -186,7 +188,7 @@ pub async fn context_query(
         .collect();
         
     // Query for project conventions in this project
-    let mut stmt = db.prepare("SELECT id, project_id, convention_type, convention_rule, good_examples, bad_examples, rationale, created_at FROM project_conventions WHERE project_id = ?1").unwrap();
+    let mut stmt = db.prepare("SELECT id, project_id, convention_type, convention_rule, good_examples, bad_examples, rationale, created_at, pattern, enforcement_level FROM project_conventions WHERE project_id = ?1").unwrap();
     let conventions = stmt
         .query_map(rusqlite::params![query.project_id], |row| {
             Ok(ProjectConvention {
@@ -198,6 +200,8 @@ pub async fn context_query(
                 bad_examples: row.get(5).ok(),
                 rationale: row.get(6).ok(),
                 created_at: row.get(7).ok(),
+                pattern: row.get(8).ok(),
+                enforcement_level: row.get(9).ok(),
             })
         })
         .unwrap()
@@ -300,7 +304,7 @@ async fn get_context_query(
         .collect();
         
     // Get security policies for this project
-    let mut stmt = db.prepare("SELECT id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, created_at FROM security_policies WHERE project_id = ?1").unwrap();
+    let mut stmt = db.prepare("SELECT id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, severity, classification, created_at FROM security_policies WHERE project_id = ?1").unwrap();
     let security_policies = stmt
         .query_map([project_id], |row| {
             Ok(SecurityPolicy {
@@ -312,7 +316,9 @@ async fn get_context_query(
                 implementation_pattern: row.get(5).ok(),
                 forbidden_patterns: row.get(6).ok(),
                 compliance_notes: row.get(7).ok(),
-                created_at: row.get(8).ok(),
+                severity: row.get(8).ok(),
+                classification: row.get(9).ok(),
+                created_at: row.get(10).ok(),
             })
         })
         .unwrap()
@@ -320,7 +326,7 @@ async fn get_context_query(
         .collect();
         
     // Get project conventions for this project
-    let mut stmt = db.prepare("SELECT id, project_id, convention_type, convention_rule, good_examples, bad_examples, rationale, created_at FROM project_conventions WHERE project_id = ?1").unwrap();
+    let mut stmt = db.prepare("SELECT id, project_id, convention_type, convention_rule, good_examples, bad_examples, rationale, created_at, pattern, enforcement_level FROM project_conventions WHERE project_id = ?1").unwrap();
     let conventions = stmt
         .query_map([project_id], |row| {
             Ok(ProjectConvention {
@@ -332,6 +338,8 @@ async fn get_context_query(
                 bad_examples: row.get(5).ok(),
                 rationale: row.get(6).ok(),
                 created_at: row.get(7).ok(),
+                pattern: row.get(8).ok(),
+                enforcement_level: row.get(9).ok(),
             })
         })
         .unwrap()
@@ -434,7 +442,7 @@ async fn create_performance_requirement(State(state): State<AppState>, Json(item
 // Security Policies CRUD (corrected)
 async fn list_security_policies(State(state): State<AppState>) -> Json<Vec<SecurityPolicy>> {
     let db = state.db.lock().unwrap();
-    let mut stmt = db.prepare("SELECT id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, created_at FROM security_policies").unwrap();
+    let mut stmt = db.prepare("SELECT id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, severity, classification, created_at FROM security_policies").unwrap();
     let items = stmt
         .query_map([], |row| {
             Ok(SecurityPolicy {
@@ -446,7 +454,9 @@ async fn list_security_policies(State(state): State<AppState>) -> Json<Vec<Secur
                 implementation_pattern: row.get(5).ok(),
                 forbidden_patterns: row.get(6).ok(),
                 compliance_notes: row.get(7).ok(),
-                created_at: row.get(8).ok(),
+                severity: row.get(8).ok(),
+                classification: row.get(9).ok(),
+                created_at: row.get(10).ok(),
             })
         })
         .unwrap()
@@ -458,7 +468,7 @@ async fn list_security_policies(State(state): State<AppState>) -> Json<Vec<Secur
 async fn create_security_policy(State(state): State<AppState>, Json(item): Json<SecurityPolicy>) -> Json<serde_json::Value> {
     let db = state.db.lock().unwrap();
     let _ = db.execute(
-        "INSERT INTO security_policies (id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        "INSERT INTO security_policies (id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, severity, classification, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
         rusqlite::params![
             item.id,
             item.project_id,
@@ -468,6 +478,8 @@ async fn create_security_policy(State(state): State<AppState>, Json(item): Json<
             item.implementation_pattern,
             item.forbidden_patterns,
             item.compliance_notes,
+            item.severity,
+            item.classification,
             item.created_at
         ]
     );
@@ -477,7 +489,7 @@ async fn create_security_policy(State(state): State<AppState>, Json(item): Json<
 // Project Conventions CRUD (corrected)
 async fn list_project_conventions(State(state): State<AppState>) -> Json<Vec<ProjectConvention>> {
     let db = state.db.lock().unwrap();
-    let mut stmt = db.prepare("SELECT id, project_id, convention_type, convention_rule, good_examples, bad_examples, rationale, created_at FROM project_conventions").unwrap();
+    let mut stmt = db.prepare("SELECT id, project_id, convention_type, convention_rule, good_examples, bad_examples, rationale, created_at, pattern, enforcement_level FROM project_conventions").unwrap();
     let items = stmt
         .query_map([], |row| {
             Ok(ProjectConvention {
@@ -489,6 +501,8 @@ async fn list_project_conventions(State(state): State<AppState>) -> Json<Vec<Pro
                 bad_examples: row.get(5).ok(),
                 rationale: row.get(6).ok(),
                 created_at: row.get(7).ok(),
+                pattern: row.get(8).ok(),
+                enforcement_level: row.get(9).ok(),
             })
         })
         .unwrap()
@@ -500,15 +514,17 @@ async fn list_project_conventions(State(state): State<AppState>) -> Json<Vec<Pro
 async fn create_project_convention(State(state): State<AppState>, Json(item): Json<ProjectConvention>) -> Json<serde_json::Value> {
     let db = state.db.lock().unwrap();
     let _ = db.execute(
-        "INSERT INTO project_conventions (id, project_id, convention_type, convention_rule, good_examples, bad_examples, rationale, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        "INSERT INTO project_conventions (id, project_id, convention_type, convention_rule, pattern, good_examples, bad_examples, rationale, enforcement_level, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
         rusqlite::params![
             item.id,
             item.project_id,
             item.convention_type,
             item.convention_rule,
+            item.pattern,
             item.good_examples,
             item.bad_examples,
             item.rationale,
+            item.enforcement_level,
             item.created_at
         ]
     );
@@ -518,7 +534,7 @@ async fn create_project_convention(State(state): State<AppState>, Json(item): Js
 // Feature Context CRUD (corrected)
 async fn list_feature_contexts(State(state): State<AppState>) -> Json<Vec<FeatureContext>> {
     let db = state.db.lock().unwrap();
-    let mut stmt = db.prepare("SELECT id, project_id, feature_name, business_purpose, user_personas, key_workflows, integration_points, edge_cases, created_at FROM feature_context").unwrap();
+    let mut stmt = db.prepare("SELECT id, project_id, feature_name, business_purpose, user_personas, key_workflows, integration_points, edge_cases, created_at, constraints, related_components, status, owner FROM feature_context").unwrap();
     let items = stmt
         .query_map([], |row| {
             Ok(FeatureContext {
@@ -531,6 +547,10 @@ async fn list_feature_contexts(State(state): State<AppState>) -> Json<Vec<Featur
                 integration_points: row.get(6).ok(),
                 edge_cases: row.get(7).ok(),
                 created_at: row.get(8).ok(),
+                constraints: row.get(9).ok(),
+                related_components: row.get(10).ok(),
+                status: row.get(11).ok(),
+                owner: row.get(12).ok(),
             })
         })
         .unwrap()
@@ -542,7 +562,7 @@ async fn list_feature_contexts(State(state): State<AppState>) -> Json<Vec<Featur
 async fn create_feature_context(State(state): State<AppState>, Json(item): Json<FeatureContext>) -> Json<serde_json::Value> {
     let db = state.db.lock().unwrap();
     let _ = db.execute(
-        "INSERT INTO feature_context (id, project_id, feature_name, business_purpose, user_personas, key_workflows, integration_points, edge_cases, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        "INSERT INTO feature_context (id, project_id, feature_name, business_purpose, user_personas, key_workflows, integration_points, edge_cases, constraints, related_components, status, owner, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
         rusqlite::params![
             item.id,
             item.project_id,
@@ -552,6 +572,10 @@ async fn create_feature_context(State(state): State<AppState>, Json(item): Json<
             item.key_workflows,
             item.integration_points,
             item.edge_cases,
+            item.constraints,
+            item.related_components,
+            item.status,
+            item.owner,
             item.created_at
         ]
     );
@@ -704,7 +728,7 @@ async fn list_security_policies_by_project(
     axum::extract::Path(project_id): axum::extract::Path<String>
 ) -> Json<Vec<SecurityPolicy>> {
     let db = state.db.lock().unwrap();
-    let mut stmt = db.prepare("SELECT id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, created_at FROM security_policies WHERE project_id = ?1").unwrap();
+    let mut stmt = db.prepare("SELECT id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, severity, classification, created_at FROM security_policies WHERE project_id = ?1").unwrap();
     let policies = stmt
         .query_map([project_id], |row| {
             Ok(SecurityPolicy {
@@ -716,7 +740,9 @@ async fn list_security_policies_by_project(
                 implementation_pattern: row.get(5).ok(),
                 forbidden_patterns: row.get(6).ok(),
                 compliance_notes: row.get(7).ok(),
-                created_at: row.get(8).ok(),
+                severity: row.get(8).ok(),
+                classification: row.get(9).ok(),
+                created_at: row.get(10).ok(),
             })
         })
         .unwrap()
@@ -730,7 +756,7 @@ async fn list_project_conventions_by_project(
     axum::extract::Path(project_id): axum::extract::Path<String>
 ) -> Json<Vec<ProjectConvention>> {
     let db = state.db.lock().unwrap();
-    let mut stmt = db.prepare("SELECT id, project_id, convention_type, convention_rule, good_examples, bad_examples, rationale, created_at FROM project_conventions WHERE project_id = ?1").unwrap();
+    let mut stmt = db.prepare("SELECT id, project_id, convention_type, convention_rule, good_examples, bad_examples, rationale, created_at, pattern, enforcement_level FROM project_conventions WHERE project_id = ?1").unwrap();
     let conventions = stmt
         .query_map([project_id], |row| {
             Ok(ProjectConvention {
@@ -742,6 +768,8 @@ async fn list_project_conventions_by_project(
                 bad_examples: row.get(5).ok(),
                 rationale: row.get(6).ok(),
                 created_at: row.get(7).ok(),
+                pattern: row.get(8).ok(),
+                enforcement_level: row.get(9).ok(),
             })
         })
         .unwrap()
@@ -755,7 +783,7 @@ async fn list_feature_contexts_by_project(
     axum::extract::Path(project_id): axum::extract::Path<String>
 ) -> Json<Vec<FeatureContext>> {
     let db = state.db.lock().unwrap();
-    let mut stmt = db.prepare("SELECT id, project_id, feature_name, business_purpose, user_personas, key_workflows, integration_points, edge_cases, created_at FROM feature_context WHERE project_id = ?1").unwrap();
+    let mut stmt = db.prepare("SELECT id, project_id, feature_name, business_purpose, user_personas, key_workflows, integration_points, edge_cases, created_at, constraints, related_components, status, owner FROM feature_context WHERE project_id = ?1").unwrap();
     let features = stmt
         .query_map([project_id], |row| {
             Ok(FeatureContext {
@@ -768,6 +796,10 @@ async fn list_feature_contexts_by_project(
                 integration_points: row.get(6).ok(),
                 edge_cases: row.get(7).ok(),
                 created_at: row.get(8).ok(),
+                constraints: row.get(9).ok(),
+                related_components: row.get(10).ok(),
+                status: row.get(11).ok(),
+                owner: row.get(12).ok(),
             })
         })
         .unwrap()
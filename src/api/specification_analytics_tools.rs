@@ -63,7 +63,7 @@ impl SpecificationAnalyticsTools {
             },
             Tool {
                 name: "calculate_development_velocity".into(),
-                description: Some("Calculate development velocity metrics based on task and requirement completion over a specified time period".into()),
+                description: Some("Calculate development velocity metrics based on task and requirement completion over a specified time period, with daily open/closed task counts and weekly requirement completions for charting".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
@@ -357,6 +357,8 @@ mod tests {
                 velocity_trend: VelocityTrend::Increasing,
                 bottlenecks: vec!["Task dependencies causing delays".to_string()],
                 productivity_score: 72.5,
+                task_flow: Vec::new(),
+                requirements_completed_per_week: Vec::new(),
             })
         }
 
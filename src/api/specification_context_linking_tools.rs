@@ -187,6 +187,45 @@ impl SpecificationContextLinkingTools {
                 ))]))
             }
 
+            "accept_suggested_links" => {
+                let arguments = request.params.arguments.as_ref();
+                let spec_id = arguments
+                    .and_then(|args| args.get("spec_id"))
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        McpError::invalid_params("Missing or invalid spec_id parameter", None)
+                    })?;
+                let links: Vec<(String, String)> = arguments
+                    .and_then(|args| args.get("links"))
+                    .and_then(|v| v.as_array())
+                    .map(|links| {
+                        links
+                            .iter()
+                            .filter_map(|link| {
+                                Some((
+                                    link.get("requirement_id")?.as_str()?.to_string(),
+                                    link.get("context_id")?.as_str()?.to_string(),
+                                ))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let min_confidence = arguments
+                    .and_then(|args| args.get("min_confidence"))
+                    .and_then(|v| v.as_f64());
+
+                let accepted = self
+                    .service
+                    .accept_suggested_links(spec_id, &links, min_confidence)
+                    .await?;
+
+                Ok(CallToolResult::success(vec![Content::text(format!(
+                    "Accepted {} suggested context links for specification {}",
+                    accepted.len(),
+                    spec_id
+                ))]))
+            }
+
             _ => Err(McpError::method_not_found::<
                 rmcp::model::CallToolRequestMethod,
             >()),
@@ -197,6 +236,7 @@ impl SpecificationContextLinkingTools {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::specification::RequirementContextLink;
     use crate::services::specification_context_linking_service::{
         ContextImpactAnalysis, ContextLink, ContextSuggestion, SpecificationChanges,
         SpecificationContextLinkingService, SyncResult,
@@ -284,6 +324,30 @@ mod tests {
         ) -> Result<(), McpError> {
             Ok(())
         }
+
+        async fn suggest_links_for_specification(
+            &self,
+            _spec_id: &str,
+            _context_project_id: Option<&str>,
+        ) -> Result<Vec<RequirementContextLink>, McpError> {
+            Ok(vec![])
+        }
+
+        async fn get_suggested_links(
+            &self,
+            _spec_id: &str,
+        ) -> Result<Vec<RequirementContextLink>, McpError> {
+            Ok(vec![])
+        }
+
+        async fn accept_suggested_links(
+            &self,
+            _spec_id: &str,
+            _links: &[(String, String)],
+            _min_confidence: Option<f64>,
+        ) -> Result<Vec<RequirementContextLink>, McpError> {
+            Ok(vec![])
+        }
     }
 
     #[tokio::test]
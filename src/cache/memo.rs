@@ -0,0 +1,106 @@
+//! Request-scoped memoization
+//! Lookups made while serving one tool call are remembered for the rest of that call only,
+//! so independent services asking for the same data don't each hit SQLite
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+
+tokio::task_local! {
+    static CURRENT: Arc<RequestMemo>;
+}
+
+/// Lookup counters for one request
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MemoStats {
+    /// Memoized lookups made during the request
+    pub lookups: u64,
+    /// Lookups answered from memory
+    pub hits: u64,
+    /// Time spent in loaders (database round trips), in microseconds
+    pub load_time_us: u64,
+}
+
+/// Values loaded during one request, keyed like `CacheKeyBuilder` keys
+#[derive(Default)]
+pub struct RequestMemo {
+    values: Mutex<HashMap<String, Arc<dyn Any + Send + Sync>>>,
+    stats: Mutex<MemoStats>,
+}
+
+impl RequestMemo {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Run `future` with this memo active; `memoize` calls inside it share the memo
+    pub async fn scope<F: Future>(self: Arc<Self>, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+
+    /// Return the value loaded earlier in this request for `key`, or run `load` and
+    /// remember its successful result. Outside a `scope`, `load` always runs.
+    pub async fn memoize<T, E, F, Fut>(key: String, load: F) -> Result<T, E>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let Ok(memo) = CURRENT.try_with(Arc::clone) else {
+            return load().await;
+        };
+
+        memo.stats.lock().lookups += 1;
+        let cached = memo.values.lock().get(&key).cloned();
+        if let Some(value) = cached.and_then(|v| v.downcast_ref::<T>().cloned()) {
+            memo.stats.lock().hits += 1;
+            return Ok(value);
+        }
+
+        let started = Instant::now();
+        let result = load().await;
+        memo.stats.lock().load_time_us += started.elapsed().as_micros() as u64;
+        if let Ok(value) = &result {
+            memo.values.lock().insert(key, Arc::new(value.clone()));
+        }
+        result
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats.lock().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_memoize_within_scope() {
+        let loads = AtomicUsize::new(0);
+        let load = || async {
+            loads.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, ()>(vec![1, 2, 3])
+        };
+
+        let memo = RequestMemo::new();
+        memo.clone()
+            .scope(async {
+                assert_eq!(RequestMemo::memoize("rules:p1".into(), load).await, Ok(vec![1, 2, 3]));
+                assert_eq!(RequestMemo::memoize("rules:p1".into(), load).await, Ok(vec![1, 2, 3]));
+            })
+            .await;
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        let stats = memo.stats();
+        assert_eq!((stats.lookups, stats.hits), (2, 1));
+
+        // Outside a scope nothing is remembered
+        RequestMemo::memoize("rules:p1".into(), load).await.unwrap();
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+    }
+}
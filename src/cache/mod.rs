@@ -1,13 +1,27 @@
-/// Query caching module for performance optimization
-/// Provides LRU and TTL-based caching for frequently accessed queries
+//! Query caching module for performance optimization
+//! Provides LRU and TTL-based caching for frequently accessed queries
 
+use anyhow::Result;
 use lru::LruCache;
 use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
-use tracing::{debug, trace};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, trace, warn};
+
+use crate::config::CacheConfig;
+
+pub mod memo;
+
+pub use memo::RequestMemo;
+
+/// Re-check interval for the sweeper while `sweep_interval_secs` is 0
+const IDLE_SWEEP_CHECK: Duration = Duration::from_secs(60);
 
 /// Cache entry with optional TTL
 #[derive(Clone)]
@@ -28,10 +42,52 @@ impl CacheEntry {
     }
 }
 
+/// Cache entry as written to a snapshot file
+#[derive(Serialize, Deserialize)]
+struct PersistedEntry {
+    key: String,
+    data: Value,
+    /// Unix time in milliseconds after which the entry is stale
+    expires_at_ms: Option<u64>,
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+/// Caching rules for one key namespace (see `CacheKeyBuilder::namespace`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachePolicy {
+    /// When false, values in this namespace are never stored
+    pub enabled: bool,
+    /// TTL used when `set` is called without one; falls back to the cache-wide default
+    pub ttl: Option<Duration>,
+    /// Upper bound on entries of this namespace; its least recently used entry is evicted first
+    pub max_entries: Option<usize>,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl: None,
+            max_entries: None,
+        }
+    }
+}
+
 /// Query result cache with LRU eviction and TTL support
 pub struct QueryCache {
     cache: Arc<RwLock<LruCache<String, CacheEntry>>>,
     max_size: usize,
+    default_ttl: RwLock<Option<Duration>>,
+    negative_ttl: RwLock<Option<Duration>>,
+    sweep_interval: RwLock<Option<Duration>>,
+    policies: RwLock<HashMap<String, CachePolicy>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    expirations: AtomicU64,
 }
 
 impl QueryCache {
@@ -41,65 +97,334 @@ impl QueryCache {
         Self {
             cache: Arc::new(RwLock::new(LruCache::new(non_zero_size))),
             max_size,
+            default_ttl: RwLock::new(None),
+            negative_ttl: RwLock::new(None),
+            sweep_interval: RwLock::new(None),
+            policies: RwLock::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            expirations: AtomicU64::new(0),
+        }
+    }
+
+    /// Create a cache sized and configured by the `[cache]` config section
+    pub fn from_config(config: &CacheConfig) -> Self {
+        let cache = Self::new(config.max_size);
+        cache.apply_config(config);
+        cache
+    }
+
+    /// Replace the TTLs, sweep interval and namespace policies (`max_size` is fixed at creation)
+    pub fn apply_config(&self, config: &CacheConfig) {
+        *self.default_ttl.write() =
+            (config.default_ttl_secs > 0).then(|| Duration::from_secs(config.default_ttl_secs));
+        *self.negative_ttl.write() =
+            (config.negative_ttl_secs > 0).then(|| Duration::from_secs(config.negative_ttl_secs));
+        *self.sweep_interval.write() = (config.sweep_interval_secs > 0)
+            .then(|| Duration::from_secs(config.sweep_interval_secs));
+
+        let mut policies = HashMap::new();
+        for (namespace, policy) in &config.policies {
+            if !CacheKeyBuilder::NAMESPACES.contains(&namespace.as_str()) {
+                warn!("Cache policy for unknown namespace '{}' will never match", namespace);
+            }
+            policies.insert(
+                namespace.clone(),
+                CachePolicy {
+                    enabled: policy.enabled,
+                    ttl: policy.ttl_secs.map(Duration::from_secs),
+                    max_entries: policy.max_entries,
+                },
+            );
         }
+        *self.policies.write() = policies;
+    }
+
+    /// Set the policy for one namespace
+    pub fn set_policy(&self, namespace: &str, policy: CachePolicy) {
+        self.policies.write().insert(namespace.to_string(), policy);
+    }
+
+    /// Policy applying to `key`
+    pub fn policy_for(&self, key: &str) -> CachePolicy {
+        self.policies
+            .read()
+            .get(CacheKeyBuilder::namespace(key))
+            .cloned()
+            .unwrap_or_default()
     }
 
     /// Get a cached value if it exists and hasn't expired
     pub fn get(&self, key: &str) -> Option<Value> {
+        if !self.policy_for(key).enabled {
+            return None;
+        }
         let mut cache = self.cache.write();
         if let Some(entry) = cache.get_mut(key) {
             if entry.is_expired() {
                 trace!("Cache entry expired for key: {}", key);
                 cache.pop(key);
+                self.expirations.fetch_add(1, Ordering::Relaxed);
+                self.misses.fetch_add(1, Ordering::Relaxed);
                 return None;
             }
             debug!("Cache hit for key: {}", key);
+            self.hits.fetch_add(1, Ordering::Relaxed);
             return Some(entry.data.clone());
         }
         trace!("Cache miss for key: {}", key);
+        self.misses.fetch_add(1, Ordering::Relaxed);
         None
     }
 
-    /// Store a value in cache with optional TTL
+    /// Store a value in cache with optional TTL.
+    /// Without a TTL the namespace policy's TTL applies, then the cache-wide default.
     pub fn set(&self, key: String, value: Value, ttl: Option<Duration>) {
+        let policy = self.policy_for(&key);
+        if !policy.enabled {
+            trace!("Caching disabled for key: {}", key);
+            return;
+        }
+
         let entry = CacheEntry {
             data: value,
             created_at: Instant::now(),
-            ttl,
+            ttl: ttl.or(policy.ttl).or(*self.default_ttl.read()),
         };
         let mut cache = self.cache.write();
-        cache.put(key.clone(), entry);
+
+        if let Some(max_entries) = policy.max_entries {
+            let namespace = CacheKeyBuilder::namespace(&key);
+            let in_namespace: Vec<&String> = cache
+                .iter()
+                .map(|(k, _)| k)
+                .filter(|k| CacheKeyBuilder::namespace(k) == namespace && **k != key)
+                .collect();
+            // iter() runs from most to least recently used
+            let excess = (in_namespace.len() + 1).saturating_sub(max_entries);
+            let evict: Vec<String> = in_namespace
+                .into_iter()
+                .rev()
+                .take(excess)
+                .cloned()
+                .collect();
+            for old_key in evict {
+                cache.pop(&old_key);
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+                trace!("Evicted {} (namespace limit {})", old_key, max_entries);
+            }
+        }
+
+        // push hands back the replaced value for an existing key, or the evicted LRU entry
+        if let Some((old_key, _)) = cache.push(key.clone(), entry) {
+            if old_key != key {
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+                trace!("Evicted {} (cache full)", old_key);
+            }
+        }
         debug!("Cached value for key: {}", key);
     }
 
-    /// Clear a specific cache entry
-    pub fn invalidate(&self, key: &str) {
+    /// Remember that `key` doesn't exist, for the short negative TTL.
+    /// `get` then returns `Some(Value::Null)` until the entry expires or is invalidated.
+    pub fn set_not_found(&self, key: String) {
+        let Some(ttl) = *self.negative_ttl.read() else {
+            return;
+        };
+        self.set(key, Value::Null, Some(ttl));
+    }
+
+    /// Clear a specific cache entry, returning whether it was cached
+    pub fn invalidate(&self, key: &str) -> bool {
         let mut cache = self.cache.write();
-        if cache.pop(key).is_some() {
+        let removed = cache.pop(key).is_some();
+        if removed {
             debug!("Invalidated cache entry for key: {}", key);
         }
+        removed
+    }
+
+    /// Clear all cache entries whose key matches a glob pattern, returning how many were removed.
+    /// `*` matches any run of characters (including `:`) and `?` exactly one, so a pattern
+    /// without wildcards matches only that exact key and `rules:*` is a prefix match.
+    pub fn invalidate_pattern(&self, pattern: &str) -> usize {
+        let matcher = match glob::Pattern::new(pattern) {
+            Ok(matcher) => matcher,
+            Err(e) => {
+                warn!("Invalid cache invalidation pattern '{}': {}", pattern, e);
+                return 0;
+            }
+        };
+
+        let mut cache = self.cache.write();
+        let keys_to_remove: Vec<String> = cache
+            .iter()
+            .filter(|(k, _)| matcher.matches(k))
+            .map(|(k, _)| k.clone())
+            .collect();
+
+        let removed = keys_to_remove.len();
+        for key in keys_to_remove {
+            cache.pop(&key);
+        }
+        debug!("Invalidated {} cache entries matching pattern: {}", removed, pattern);
+        removed
     }
 
-    /// Clear all cache entries matching a pattern
-    pub fn invalidate_pattern(&self, pattern: &str) {
+    /// Clear every entry of a key namespace (see `CacheKeyBuilder::namespace`)
+    pub fn invalidate_namespace(&self, namespace: &str) {
         let mut cache = self.cache.write();
         let keys_to_remove: Vec<String> = cache
             .iter()
-            .filter(|(k, _)| k.contains(pattern))
+            .filter(|(k, _)| CacheKeyBuilder::namespace(k) == namespace)
             .map(|(k, _)| k.clone())
             .collect();
 
+        let removed = keys_to_remove.len();
         for key in keys_to_remove {
             cache.pop(&key);
         }
-        debug!("Invalidated {} cache entries matching pattern: {}", cache.len(), pattern);
+        debug!("Invalidated {} cache entries in namespace: {}", removed, namespace);
+    }
+
+    /// Clear the project record and every project-scoped listing or query of `project_id`.
+    /// Single-entity keys (`rule:<id>`, ...) don't carry the project and are left alone.
+    /// Returns the number of entries removed.
+    pub fn invalidate_project(&self, project_id: &str) -> usize {
+        self.invalidate(&CacheKeyBuilder::project(project_id)) as usize
+            + self.invalidate_pattern(&CacheKeyBuilder::project_invalidation_pattern(project_id))
+    }
+
+    /// Remove every expired entry, returning how many were reclaimed
+    pub fn purge_expired(&self) -> usize {
+        let mut cache = self.cache.write();
+        let expired: Vec<String> = cache
+            .iter()
+            .filter(|(_, entry)| entry.is_expired())
+            .map(|(k, _)| k.clone())
+            .collect();
+
+        let removed = expired.len();
+        for key in expired {
+            cache.pop(&key);
+        }
+        self.expirations.fetch_add(removed as u64, Ordering::Relaxed);
+        removed
     }
 
-    /// Clear all cache entries
-    pub fn clear(&self) {
+    /// Periodically purge expired entries on the current tokio runtime.
+    /// The interval is re-read after every sweep so config reloads apply; the task
+    /// stops once the cache is dropped. Does nothing outside a runtime.
+    pub fn spawn_sweeper(self: &Arc<Self>) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            debug!("No tokio runtime; cache sweeper not started");
+            return;
+        };
+
+        let cache = Arc::downgrade(self);
+        runtime.spawn(async move {
+            loop {
+                let interval = match cache.upgrade() {
+                    Some(cache) => *cache.sweep_interval.read(),
+                    None => break,
+                };
+                // While sweeping is disabled, check back for a config change at this pace
+                tokio::time::sleep(interval.unwrap_or(IDLE_SWEEP_CHECK)).await;
+                if interval.is_none() {
+                    continue;
+                }
+
+                let Some(cache) = cache.upgrade() else { break };
+                let reclaimed = cache.purge_expired();
+                if reclaimed > 0 {
+                    debug!(
+                        "Cache sweep reclaimed {} expired entries ({} still cached)",
+                        reclaimed,
+                        cache.stats().size
+                    );
+                }
+            }
+        });
+    }
+
+    /// Write live entries of `namespaces` to `path`, returning how many were saved.
+    /// Entries keep their absolute expiry, so time spent offline counts against the TTL.
+    pub fn save_snapshot(&self, path: &Path, namespaces: &[String]) -> Result<usize> {
+        let now = unix_millis(SystemTime::now());
+        let entries: Vec<PersistedEntry> = {
+            let cache = self.cache.read();
+            // iter() runs from most to least recently used; store the reverse so
+            // loading in file order restores the recency order
+            let mut entries: Vec<PersistedEntry> = cache
+                .iter()
+                .filter(|(k, entry)| {
+                    !entry.is_expired()
+                        && namespaces.iter().any(|ns| ns == CacheKeyBuilder::namespace(k))
+                })
+                .map(|(k, entry)| PersistedEntry {
+                    key: k.clone(),
+                    data: entry.data.clone(),
+                    expires_at_ms: entry.ttl.map(|ttl| {
+                        let remaining = ttl.saturating_sub(entry.created_at.elapsed());
+                        now + remaining.as_millis() as u64
+                    }),
+                })
+                .collect();
+            entries.reverse();
+            entries
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Write then rename so a crash mid-write never leaves a truncated snapshot
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(&entries)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(entries.len())
+    }
+
+    /// Pre-warm the cache from a snapshot written by `save_snapshot`, returning how many
+    /// entries were loaded. A missing file loads nothing; stale entries are skipped.
+    pub fn load_snapshot(&self, path: &Path) -> Result<usize> {
+        if !path.exists() {
+            return Ok(0);
+        }
+        let entries: Vec<PersistedEntry> = serde_json::from_slice(&std::fs::read(path)?)?;
+
+        let now = unix_millis(SystemTime::now());
+        let mut loaded = 0;
+        for entry in entries {
+            let ttl = match entry.expires_at_ms {
+                Some(expires_at) if expires_at <= now => continue,
+                Some(expires_at) => Some(Duration::from_millis(expires_at - now)),
+                None => None,
+            };
+            if !self.policy_for(&entry.key).enabled {
+                continue;
+            }
+            // Insert directly: `set` would replace a missing TTL with the default
+            self.cache.write().put(
+                entry.key,
+                CacheEntry {
+                    data: entry.data,
+                    created_at: Instant::now(),
+                    ttl,
+                },
+            );
+            loaded += 1;
+        }
+        Ok(loaded)
+    }
+
+    /// Clear all cache entries, returning how many were removed
+    pub fn clear(&self) -> usize {
         let mut cache = self.cache.write();
+        let removed = cache.len();
         cache.clear();
         debug!("Cleared all cache entries");
+        removed
     }
 
     /// Get cache statistics
@@ -108,19 +433,31 @@ impl QueryCache {
         CacheStats {
             size: cache.len(),
             max_size: self.max_size,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            expirations: self.expirations.load(Ordering::Relaxed),
         }
     }
 }
 
 /// Cache statistics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CacheStats {
     pub size: usize,
     pub max_size: usize,
+    /// Lookups answered from the cache since startup
+    pub hits: u64,
+    /// Lookups that found no live entry (absent or expired)
+    pub misses: u64,
+    /// Entries dropped to make room, either for `max_size` or a namespace's `max_entries`
+    pub evictions: u64,
+    /// Entries dropped because their TTL ran out
+    pub expirations: u64,
 }
 
 impl CacheStats {
-    /// Calculate cache hit ratio
+    /// Calculate cache utilization
     pub fn utilization_percent(&self) -> f64 {
         if self.max_size == 0 {
             0.0
@@ -128,12 +465,71 @@ impl CacheStats {
             (self.size as f64 / self.max_size as f64) * 100.0
         }
     }
+
+    /// Share of lookups served from the cache
+    pub fn hit_rate_percent(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            (self.hits as f64 / lookups as f64) * 100.0
+        }
+    }
+
+    /// Render in the Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let metrics: [(&str, &str, &str, u64); 6] = [
+            ("cache_entries", "gauge", "Entries currently cached", self.size as u64),
+            ("cache_max_entries", "gauge", "Configured cache capacity", self.max_size as u64),
+            ("cache_hits_total", "counter", "Lookups answered from the cache", self.hits),
+            ("cache_misses_total", "counter", "Lookups that found no live entry", self.misses),
+            ("cache_evictions_total", "counter", "Entries evicted to make room", self.evictions),
+            ("cache_expirations_total", "counter", "Entries dropped after their TTL", self.expirations),
+        ];
+
+        let mut out = String::new();
+        for (name, kind, help, value) in metrics {
+            out.push_str(&format!(
+                "# HELP context_server_{name} {help}\n# TYPE context_server_{name} {kind}\ncontext_server_{name} {value}\n"
+            ));
+        }
+        out
+    }
 }
 
 /// Build cache keys for different query types
 pub struct CacheKeyBuilder;
 
 impl CacheKeyBuilder {
+    /// Key namespaces produced by this builder, usable as `[cache.policies.<namespace>]`
+    pub const NAMESPACES: &'static [&'static str] = &[
+        "project",
+        "projects",
+        "rule",
+        "rules",
+        "decision",
+        "decisions",
+        "perf_req",
+        "perf_reqs",
+        "policy",
+        "policies",
+        "convention",
+        "conventions",
+        "feature",
+        "features",
+        "component",
+        "components",
+        "phase",
+        "phases",
+        "context",
+        "analytics",
+    ];
+
+    /// Namespace of a cache key: the part before the first `:`
+    pub fn namespace(key: &str) -> &str {
+        key.split(':').next().unwrap_or(key)
+    }
+
     /// Build cache key for project queries
     pub fn project(project_id: &str) -> String {
         format!("project:{}", project_id)
@@ -149,6 +545,11 @@ impl CacheKeyBuilder {
         format!("rules:project:{}", project_id)
     }
 
+    /// Build cache key for business rules of one domain area
+    pub fn business_rules_by_domain(project_id: &str, domain_area: &str) -> String {
+        format!("rules:domain:{}:project:{}", domain_area, project_id)
+    }
+
     /// Build cache key for architectural decision queries
     pub fn architectural_decision(decision_id: &str) -> String {
         format!("decision:{}", decision_id)
@@ -179,6 +580,16 @@ impl CacheKeyBuilder {
         format!("policies:project:{}", project_id)
     }
 
+    /// Build cache key for project convention queries
+    pub fn project_convention(convention_id: &str) -> String {
+        format!("convention:{}", convention_id)
+    }
+
+    /// Build cache key for project conventions by project
+    pub fn project_conventions_by_project(project_id: &str) -> String {
+        format!("conventions:project:{}", project_id)
+    }
+
     /// Build cache key for feature context queries
     pub fn feature_context(context_id: &str) -> String {
         format!("feature:{}", context_id)
@@ -199,14 +610,102 @@ impl CacheKeyBuilder {
         format!("components:project:{}", project_id)
     }
 
+    /// Build cache key for framework components of one architecture layer
+    pub fn framework_components_by_layer(project_id: &str, layer: &str) -> String {
+        format!("components:layer:{}:project:{}", layer, project_id)
+    }
+
+    /// Build cache key for development phase queries
+    pub fn development_phase(phase_id: &str) -> String {
+        format!("phase:{}", phase_id)
+    }
+
+    /// Build cache key for development phases by project
+    pub fn development_phases_by_project(project_id: &str) -> String {
+        format!("phases:project:{}", project_id)
+    }
+
+    /// Build cache key for a `query_context` result
+    pub fn context_query(
+        project_id: &str,
+        feature_area: &str,
+        task_type: &str,
+        components: &[String],
+    ) -> String {
+        format!(
+            "context:{}:{}:{}:project:{}",
+            feature_area,
+            task_type,
+            components.join(","),
+            project_id
+        )
+    }
+
+    /// Build cache key for a single entity given its MCP `entity_type` name
+    pub fn entity(entity_type: &str, id: &str) -> Option<String> {
+        match entity_type {
+            "project" => Some(Self::project(id)),
+            "business_rule" => Some(Self::business_rule(id)),
+            "architectural_decision" => Some(Self::architectural_decision(id)),
+            "performance_requirement" => Some(Self::performance_requirement(id)),
+            "security_policy" => Some(Self::security_policy(id)),
+            "project_convention" => Some(Self::project_convention(id)),
+            "feature_context" => Some(Self::feature_context(id)),
+            "framework_component" => Some(Self::framework_component(id)),
+            "development_phase" => Some(Self::development_phase(id)),
+            _ => None,
+        }
+    }
+
+    /// Build cache key for a project-scoped entity listing given its MCP `entity_type` name
+    pub fn entity_list(entity_type: &str, project_id: &str) -> Option<String> {
+        match entity_type {
+            "project" => Some(Self::all_projects()),
+            "business_rule" => Some(Self::business_rules_by_project(project_id)),
+            "architectural_decision" => Some(Self::architectural_decisions_by_project(project_id)),
+            "performance_requirement" => Some(Self::performance_requirements_by_project(project_id)),
+            "security_policy" => Some(Self::security_policies_by_project(project_id)),
+            "project_convention" => Some(Self::project_conventions_by_project(project_id)),
+            "feature_context" => Some(Self::feature_contexts_by_project(project_id)),
+            "framework_component" => Some(Self::framework_components_by_project(project_id)),
+            "development_phase" => Some(Self::development_phases_by_project(project_id)),
+            _ => None,
+        }
+    }
+
+    /// Namespace of the listing keys for an MCP `entity_type`
+    pub fn list_namespace(entity_type: &str) -> Option<&'static str> {
+        match entity_type {
+            "project" => Some("projects"),
+            "business_rule" => Some("rules"),
+            "architectural_decision" => Some("decisions"),
+            "performance_requirement" => Some("perf_reqs"),
+            "security_policy" => Some("policies"),
+            "project_convention" => Some("conventions"),
+            "feature_context" => Some("features"),
+            "framework_component" => Some("components"),
+            "development_phase" => Some("phases"),
+            _ => None,
+        }
+    }
+
     /// Build cache key for all projects
     pub fn all_projects() -> String {
         "projects:all".to_string()
     }
 
-    /// Invalidation pattern for a project's data
+    /// Build cache key for an analytics query, optionally scoped to a project
+    pub fn analytics(query: &str, project_id: Option<&str>) -> String {
+        match project_id {
+            Some(project_id) => format!("analytics:{}:project:{}", query, project_id),
+            None => format!("analytics:{}", query),
+        }
+    }
+
+    /// Invalidation pattern for a project's listings and queries (the project key itself
+    /// is `project:<id>` and not covered)
     pub fn project_invalidation_pattern(project_id: &str) -> String {
-        format!("*:project:{}", project_id)
+        format!("*:project:{}", glob::Pattern::escape(project_id))
     }
 }
 
@@ -253,10 +752,264 @@ mod tests {
         assert_eq!(cache.get(&key), None);
     }
 
+    #[test]
+    fn test_namespace_policy() {
+        let cache = QueryCache::new(100);
+        cache.set_policy(
+            "analytics",
+            CachePolicy {
+                enabled: false,
+                ..Default::default()
+            },
+        );
+        cache.set_policy(
+            "rule",
+            CachePolicy {
+                max_entries: Some(2),
+                ..Default::default()
+            },
+        );
+
+        let analytics_key = CacheKeyBuilder::analytics("insights", Some("p1"));
+        cache.set(analytics_key.clone(), serde_json::json!(1), None);
+        assert_eq!(cache.get(&analytics_key), None);
+
+        for id in ["r1", "r2", "r3"] {
+            cache.set(CacheKeyBuilder::business_rule(id), serde_json::json!(id), None);
+        }
+        // r1 was the least recently used rule and made room for r3
+        assert_eq!(cache.get("rule:r1"), None);
+        assert!(cache.get("rule:r2").is_some());
+        assert!(cache.get("rule:r3").is_some());
+        assert_eq!(cache.stats().size, 2);
+    }
+
+    #[test]
+    fn test_policy_ttl_applies_without_explicit_ttl() {
+        let cache = QueryCache::new(100);
+        cache.set_policy(
+            "project",
+            CachePolicy {
+                ttl: Some(Duration::from_millis(1)),
+                ..Default::default()
+            },
+        );
+
+        cache.set(CacheKeyBuilder::project("p1"), serde_json::json!({}), None);
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(cache.get("project:p1"), None);
+    }
+
     #[test]
     fn test_cache_key_builder() {
         assert_eq!(CacheKeyBuilder::project("p1"), "project:p1");
         assert_eq!(CacheKeyBuilder::business_rule("r1"), "rule:r1");
         assert_eq!(CacheKeyBuilder::business_rules_by_project("p1"), "rules:project:p1");
+        assert_eq!(CacheKeyBuilder::namespace("rules:project:p1"), "rules");
+        assert_eq!(CacheKeyBuilder::entity("business_rule", "r1").as_deref(), Some("rule:r1"));
+        assert_eq!(
+            CacheKeyBuilder::entity_list("business_rule", "p1").as_deref(),
+            Some("rules:project:p1")
+        );
+
+        // Every key the builder makes can be given a policy
+        let keys = [
+            CacheKeyBuilder::project("p1"),
+            CacheKeyBuilder::all_projects(),
+            CacheKeyBuilder::business_rule("r1"),
+            CacheKeyBuilder::business_rules_by_domain("p1", "billing"),
+            CacheKeyBuilder::architectural_decisions_by_project("p1"),
+            CacheKeyBuilder::performance_requirement("q1"),
+            CacheKeyBuilder::security_policies_by_project("p1"),
+            CacheKeyBuilder::project_convention("c1"),
+            CacheKeyBuilder::project_conventions_by_project("p1"),
+            CacheKeyBuilder::feature_context("f1"),
+            CacheKeyBuilder::framework_components_by_layer("p1", "domain"),
+            CacheKeyBuilder::development_phase("d1"),
+            CacheKeyBuilder::context_query("p1", "auth", "implement", &[]),
+            CacheKeyBuilder::analytics("insights", None),
+        ];
+        for key in &keys {
+            let namespace = CacheKeyBuilder::namespace(key);
+            assert!(CacheKeyBuilder::NAMESPACES.contains(&namespace), "{namespace} is not a known namespace");
+        }
+    }
+
+    #[test]
+    fn test_invalidate_namespace() {
+        let cache = QueryCache::new(100);
+        cache.set(CacheKeyBuilder::business_rules_by_project("p1"), serde_json::json!([]), None);
+        cache.set(CacheKeyBuilder::business_rules_by_project("p2"), serde_json::json!([]), None);
+        cache.set(CacheKeyBuilder::business_rule("r1"), serde_json::json!({}), None);
+
+        cache.invalidate_namespace("rules");
+        assert_eq!(cache.get("rules:project:p1"), None);
+        assert_eq!(cache.get("rules:project:p2"), None);
+        assert!(cache.get("rule:r1").is_some());
+    }
+
+    #[test]
+    fn test_invalidate_project_and_hit_counters() {
+        let cache = QueryCache::new(100);
+        cache.set(CacheKeyBuilder::project("p1"), serde_json::json!({}), None);
+        cache.set(CacheKeyBuilder::business_rules_by_project("p1"), serde_json::json!([]), None);
+        cache.set(CacheKeyBuilder::business_rules_by_project("p10"), serde_json::json!([]), None);
+
+        assert_eq!(cache.invalidate_project("p1"), 2);
+        assert_eq!(cache.get("project:p1"), None);
+        assert!(cache.get("rules:project:p10").is_some());
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+        assert_eq!(stats.hit_rate_percent(), 50.0);
+        assert_eq!(cache.clear(), 1);
+    }
+
+    #[test]
+    fn test_invalidate_pattern_is_a_glob() {
+        let cache = QueryCache::new(100);
+        for key in ["rule:r1", "rules:project:p1", "rules:project:p10", "a:b:p1", "a:b:p12"] {
+            cache.set(key.to_string(), serde_json::json!({}), None);
+        }
+
+        assert_eq!(cache.invalidate_pattern("rules:project:p1"), 1);
+        assert!(cache.get("rules:project:p10").is_some());
+        assert_eq!(cache.invalidate_pattern("rule:*"), 1);
+        assert_eq!(cache.invalidate_pattern("*:p?"), 1);
+        assert!(cache.get("a:b:p12").is_some());
+        assert_eq!(cache.invalidate_pattern("rule?:*"), 1);
+        assert_eq!(cache.invalidate_pattern("[unclosed"), 0);
+        assert_eq!(cache.stats().size, 1);
+    }
+
+    #[test]
+    fn test_project_pattern_covers_project_scoped_namespaces() {
+        let cache = QueryCache::new(100);
+        let components = vec!["auth".to_string()];
+        let scoped = [
+            CacheKeyBuilder::business_rules_by_project("p1"),
+            CacheKeyBuilder::architectural_decisions_by_project("p1"),
+            CacheKeyBuilder::performance_requirements_by_project("p1"),
+            CacheKeyBuilder::security_policies_by_project("p1"),
+            CacheKeyBuilder::project_conventions_by_project("p1"),
+            CacheKeyBuilder::feature_contexts_by_project("p1"),
+            CacheKeyBuilder::framework_components_by_project("p1"),
+            CacheKeyBuilder::framework_components_by_layer("p1", "domain"),
+            CacheKeyBuilder::development_phases_by_project("p1"),
+            CacheKeyBuilder::context_query("p1", "auth", "implement", &components),
+            CacheKeyBuilder::analytics("insights", Some("p1")),
+        ];
+        let untouched = [
+            CacheKeyBuilder::project("p1"),
+            CacheKeyBuilder::business_rules_by_project("p10"),
+            CacheKeyBuilder::context_query("p10", "project:p1", "x", &[]),
+            CacheKeyBuilder::business_rule("p1"),
+            CacheKeyBuilder::all_projects(),
+        ];
+        for key in scoped.iter().chain(untouched.iter()) {
+            cache.set(key.clone(), serde_json::json!({}), None);
+        }
+
+        let removed = cache.invalidate_pattern(&CacheKeyBuilder::project_invalidation_pattern("p1"));
+        assert_eq!(removed, scoped.len());
+        for key in &untouched {
+            assert!(cache.get(key).is_some(), "{key} should survive");
+        }
+    }
+
+    #[test]
+    fn test_project_pattern_matches_glob_characters_in_the_id_literally() {
+        let cache = QueryCache::new(100);
+        let scoped = CacheKeyBuilder::business_rules_by_project("proj[1]");
+        let untouched = CacheKeyBuilder::business_rules_by_project("proj1");
+        cache.set(scoped.clone(), serde_json::json!({}), None);
+        cache.set(untouched.clone(), serde_json::json!({}), None);
+
+        assert_eq!(cache.invalidate_pattern(&CacheKeyBuilder::project_invalidation_pattern("proj[1]")), 1);
+        assert!(cache.get(&scoped).is_none());
+        assert!(cache.get(&untouched).is_some());
+    }
+
+    #[test]
+    fn test_eviction_and_expiration_counters() {
+        let cache = QueryCache::new(2);
+        cache.set("a".to_string(), serde_json::json!(1), Some(Duration::from_millis(1)));
+        cache.set("b".to_string(), serde_json::json!(2), None);
+        cache.set("b".to_string(), serde_json::json!(3), None);
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(cache.get("a"), None);
+
+        cache.set("c".to_string(), serde_json::json!(4), None);
+        cache.set("d".to_string(), serde_json::json!(5), None);
+
+        let stats = cache.stats();
+        assert_eq!(stats.expirations, 1);
+        // Overwriting "b" is not an eviction; "d" pushed out "b"
+        assert_eq!(stats.evictions, 1);
+        assert!(stats
+            .to_prometheus()
+            .contains("context_server_cache_evictions_total 1\n"));
+    }
+
+    #[test]
+    fn test_negative_caching() {
+        let cache = QueryCache::from_config(&CacheConfig::default());
+        cache.set_not_found(CacheKeyBuilder::business_rule("missing"));
+        assert_eq!(cache.get("rule:missing"), Some(Value::Null));
+
+        // A create in the namespace drops the negative entry
+        cache.invalidate_namespace("rule");
+        assert_eq!(cache.get("rule:missing"), None);
+
+        let disabled = QueryCache::from_config(&CacheConfig {
+            negative_ttl_secs: 0,
+            ..Default::default()
+        });
+        disabled.set_not_found("rule:missing".to_string());
+        assert_eq!(disabled.get("rule:missing"), None);
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("query_cache.json");
+
+        let cache = QueryCache::new(100);
+        cache.set(CacheKeyBuilder::project("p1"), serde_json::json!({"name": "p1"}), None);
+        cache.set(CacheKeyBuilder::business_rules_by_project("p1"), serde_json::json!([1]), None);
+        cache.set(CacheKeyBuilder::business_rule("r1"), serde_json::json!({}), None);
+        cache.set(
+            CacheKeyBuilder::all_projects(),
+            serde_json::json!([]),
+            Some(Duration::from_millis(1)),
+        );
+        std::thread::sleep(Duration::from_millis(10));
+
+        let namespaces = vec!["project".to_string(), "projects".to_string(), "rules".to_string()];
+        assert_eq!(cache.save_snapshot(&path, &namespaces).unwrap(), 2);
+
+        let warmed = QueryCache::new(100);
+        assert_eq!(warmed.load_snapshot(&path).unwrap(), 2);
+        assert_eq!(warmed.get("project:p1"), Some(serde_json::json!({"name": "p1"})));
+        assert_eq!(warmed.get("rules:project:p1"), Some(serde_json::json!([1])));
+        assert_eq!(warmed.get("rule:r1"), None);
+
+        assert_eq!(warmed.load_snapshot(&dir.path().join("missing.json")).unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_sweeper_purges_expired_entries() {
+        let cache = Arc::new(QueryCache::from_config(&CacheConfig {
+            sweep_interval_secs: 1,
+            ..Default::default()
+        }));
+        cache.set("short".to_string(), serde_json::json!(1), Some(Duration::from_millis(1)));
+        cache.set("long".to_string(), serde_json::json!(2), None);
+        cache.spawn_sweeper();
+
+        tokio::time::sleep(Duration::from_millis(1200)).await;
+        let stats = cache.stats();
+        assert_eq!(stats.size, 1);
+        assert_eq!(stats.expirations, 1);
     }
 }
@@ -0,0 +1,65 @@
+/// Shell completion and alias generation
+/// Single Responsibility: emit shell scripts describing the CLI surface
+use clap::CommandFactory;
+use clap_complete::{generate, Shell};
+use std::io::{self, Write};
+
+use crate::cli::router::{tool_names, Cli, ENTITY_TYPES};
+
+const BIN_NAME: &str = "context-server-rs";
+
+/// Short aliases for the CLI commands, as (alias, subcommand) pairs
+const ALIASES: &[(&str, &str)] = &[
+    ("csq", "query"),
+    ("csl", "list"),
+    ("css", "search"),
+    ("csg", "get"),
+];
+
+/// Write the completion script (and optionally aliases) for `shell` to stdout
+pub fn print_completions(shell: Shell, with_aliases: bool) {
+    let mut stdout = io::stdout();
+    write_completions(shell, with_aliases, &mut stdout);
+}
+
+/// Write the completion script (and optionally aliases) for `shell` to `out`
+pub fn write_completions(shell: Shell, with_aliases: bool, out: &mut dyn Write) {
+    let mut cmd = Cli::command();
+    generate(shell, &mut cmd, BIN_NAME, out);
+    if shell == Shell::Fish {
+        let _ = out.write_all(fish_positionals().as_bytes());
+    }
+
+    if with_aliases {
+        let _ = out.write_all(alias_script(shell).as_bytes());
+    }
+}
+
+/// Values of the `list` and `call` arguments, which clap_complete leaves out of fish scripts
+fn fish_positionals() -> String {
+    let tools = tool_names();
+    let values = [("list", ENTITY_TYPES.join(" ")), ("call", tools.join(" "))];
+    values
+        .iter()
+        .map(|(subcommand, values)| {
+            format!("complete -c {BIN_NAME} -n \"__fish_context_server_rs_using_subcommand {subcommand}\" -f -a \"{values}\"\n")
+        })
+        .collect()
+}
+
+/// Render alias definitions in the syntax of the given shell
+pub fn alias_script(shell: Shell) -> String {
+    let mut script = String::from("\n# context-server-rs aliases\n");
+    for (alias, subcommand) in ALIASES {
+        let line = match shell {
+            Shell::Fish => format!("alias {alias} '{BIN_NAME} {subcommand}'\n"),
+            Shell::PowerShell => {
+                format!("function {alias} {{ {BIN_NAME} {subcommand} @args }}\n")
+            }
+            Shell::Elvish => format!("fn {alias} {{|@a| {BIN_NAME} {subcommand} $@a }}\n"),
+            _ => format!("alias {alias}='{BIN_NAME} {subcommand}'\n"),
+        };
+        script.push_str(&line);
+    }
+    script
+}
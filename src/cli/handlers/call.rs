@@ -0,0 +1,47 @@
+/// Call command handler - Run one MCP tool against the local database
+/// Goes through the same server as `serve`, so results match what MCP clients get
+use anyhow::{Result, anyhow, bail};
+use rmcp::model::CallToolRequestParam;
+use serde_json::Value;
+use crate::config::AppConfig;
+use crate::enhanced_context_server::EnhancedContextMcpServer;
+
+/// Caller recorded in the audit log for tools run from the command line
+const CLI_CALLER: &str = "context-server-rs-cli";
+
+pub struct CallCommand {
+    pub config: AppConfig,
+    pub tool: String,
+    pub arguments: Option<String>,
+}
+
+impl CallCommand {
+    pub fn new(config: AppConfig, tool: String, arguments: Option<String>) -> Self {
+        Self { config, tool, arguments }
+    }
+
+    /// Run the tool with `--args`, a JSON object; the result is parsed when it is JSON
+    pub async fn execute(&self) -> Result<Value> {
+        let arguments = match &self.arguments {
+            Some(json) => match serde_json::from_str(json)? {
+                Value::Object(arguments) => Some(arguments),
+                _ => bail!("--args must be a JSON object"),
+            },
+            None => None,
+        };
+
+        let server = EnhancedContextMcpServer::from_config(self.config.clone())?;
+        let request = CallToolRequestParam { name: self.tool.clone().into(), arguments };
+        let result = server
+            .call_tool_directly(request, CLI_CALLER)
+            .await
+            .map_err(|e| anyhow!("{} failed: {}", self.tool, e.message))?;
+
+        let text: Vec<&str> = result.content.iter().filter_map(|content| content.as_text()).map(|text| text.text.as_str()).collect();
+        let text = text.join("\n");
+        if result.is_error == Some(true) {
+            bail!("{} failed: {}", self.tool, text);
+        }
+        Ok(serde_json::from_str(&text).unwrap_or(Value::String(text)))
+    }
+}
@@ -0,0 +1,54 @@
+/// Dashboard command handler - Render a project's analytics dashboard to an HTML file
+/// Needs the analytics services, so it runs against a full container rather than raw SQL
+use anyhow::{Result, anyhow};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use crate::config::AppConfig;
+use crate::container::AppContainer;
+use crate::services::{DashboardData, DashboardRenderer};
+
+pub struct DashboardCommand {
+    pub config: AppConfig,
+    pub project: Option<String>,
+    pub days: u32,
+    pub output: Option<PathBuf>,
+}
+
+impl DashboardCommand {
+    pub fn new(config: AppConfig, project: Option<String>, days: u32, output: Option<PathBuf>) -> Self {
+        Self { config, project, days, output }
+    }
+
+    /// Write the dashboard of the project named or identified by `--project`
+    pub async fn execute(&self) -> Result<Value> {
+        let container = AppContainer::from_config(self.config.clone())?;
+        let wanted = self.project.as_deref().unwrap_or("default");
+        let project = container
+            .project_service
+            .list_projects()
+            .await
+            .map_err(|e| anyhow!("Failed to list projects: {}", e.message))?
+            .into_iter()
+            .find(|project| project.id == wanted || project.name == wanted)
+            .ok_or_else(|| anyhow!("Project not found: {}", wanted))?;
+
+        let data = DashboardData::collect(
+            container.analytics_service.as_ref(),
+            container.specification_analytics_service.as_ref(),
+            &project,
+            self.days,
+            chrono::Utc::now(),
+        )
+        .await?;
+        let path = self.output.clone().unwrap_or_else(|| data.default_file_name());
+        let bytes = DashboardRenderer::write(&data, &path).await?;
+
+        Ok(json!({
+            "status": "success",
+            "project_id": project.id,
+            "days": self.days,
+            "path": path,
+            "bytes": bytes
+        }))
+    }
+}
@@ -5,8 +5,12 @@ pub mod query;
 pub mod list;
 pub mod search;
 pub mod get;
+pub mod dashboard;
+pub mod call;
 
 pub use query::QueryCommand;
 pub use list::ListCommand;
 pub use search::SearchCommand;
 pub use get::GetCommand;
+pub use dashboard::DashboardCommand;
+pub use call::CallCommand;
@@ -1,6 +1,7 @@
 // CLI module following SOLID principles
 
 pub mod commands;
+pub mod completions;
 pub mod handlers;
 pub mod output;
 pub mod router;
@@ -2,23 +2,61 @@
 /// Follows Dependency Inversion: depends on CliCommand abstraction, not concrete types
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 use crate::cli::commands::CliCommand;
-use crate::cli::handlers::{QueryCommand, ListCommand, SearchCommand, GetCommand};
+use crate::cli::completions;
+use crate::cli::handlers::{QueryCommand, ListCommand, SearchCommand, GetCommand, DashboardCommand, CallCommand};
 use crate::cli::output::get_formatter;
+use crate::config::AppConfig;
+use crate::enhanced_context_server::EnhancedContextMcpServer;
+
+/// Entity types accepted by the `list` command (also offered by shell completion)
+pub const ENTITY_TYPES: &[&str] = &[
+    "business_rule",
+    "architectural_decision",
+    "performance_requirement",
+    "security_policy",
+    "feature",
+];
+
+/// Names of the MCP tools, accepted by the `call` command (also offered by shell completion)
+pub fn tool_names() -> Vec<String> {
+    EnhancedContextMcpServer::tools().into_iter().map(|tool| tool.name.into_owned()).collect()
+}
 
 #[derive(Parser)]
 #[command(name = "context-server-rs")]
 #[command(about = "Context Server for AI Agents and IDEs", long_about = None)]
 #[command(version)]
-#[command(after_help = "EXAMPLES:\n  # Query all contexts for a project\n  context-server-rs query -p myproject\n\n  # List business rules for a project\n  context-server-rs list business_rule -p myproject\n\n  # Search across all contexts\n  context-server-rs search payment -p myproject\n\n  # Get specific context by ID\n  context-server-rs get rule-001 -p myproject\n\n  # Output in different formats\n  context-server-rs query -f yaml -p myproject\n  context-server-rs list security_policy -f text -p myproject")]
+#[command(after_help = "EXAMPLES:\n  # Query all contexts for a project\n  context-server-rs query -p myproject\n\n  # List business rules for a project\n  context-server-rs list business_rule -p myproject\n\n  # Search across all contexts\n  context-server-rs search payment -p myproject\n\n  # Get specific context by ID\n  context-server-rs get rule-001 -p myproject\n\n  # Write a 30-day analytics dashboard to an HTML file\n  context-server-rs dashboard -p myproject -o dashboard.html\n\n  # Call an MCP tool with JSON arguments\n  context-server-rs call query_context --args '{\"project_id\": \"p1\", \"feature_area\": \"auth\", \"task_type\": \"implement\"}'\n\n  # Output in different formats\n  context-server-rs query -f yaml -p myproject\n  context-server-rs list security_policy -f text -p myproject\n\n  # Install bash completion and short aliases\n  context-server-rs completions bash --aliases > ~/.local/share/bash-completion/completions/context-server-rs")]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
 
-    #[arg(global = true, short, long, help = "Database path")]
+    #[arg(global = true, short, long, env = "CONTEXT_SERVER_DB", help = "Database path")]
     pub db: Option<String>,
 
+    #[arg(global = true, long, env = "CONTEXT_SERVER_CONFIG", help = "Path to config.toml")]
+    pub config: Option<PathBuf>,
+
+    #[arg(
+        global = true,
+        long,
+        env = "CONTEXT_SERVER_CONFIG_DIR",
+        help = "Directory for config.toml and the default database (overrides XDG locations)"
+    )]
+    pub config_dir: Option<PathBuf>,
+
+    #[arg(
+        global = true,
+        long,
+        env = "CONTEXT_SERVER_PROFILE",
+        help = "Config profile: local, team, or a [profiles.<name>] section of config.toml"
+    )]
+    pub profile: Option<String>,
+
     #[arg(global = true, short, long, default_value = "json", help = "Output format: json, text, yaml")]
     pub format: String,
 
@@ -31,8 +69,16 @@ pub enum Commands {
     /// Serve as HTTP/MCP server
     #[command(about = "Start MCP server (default mode)")]
     Serve {
-        #[arg(short, long, default_value = "9000")]
+        // Long-only: `-p` is taken by the global --project option
+        #[arg(long, default_value = "9000")]
         port: u16,
+
+        #[arg(
+            long,
+            value_name = "ADDR",
+            help = "Also serve WebSocket sync on ADDR, e.g. 127.0.0.1:8080 (overrides [websocket])"
+        )]
+        ws: Option<SocketAddr>,
     },
 
     /// Query all contexts for a project
@@ -45,7 +91,11 @@ pub enum Commands {
     /// List contexts by type
     #[command(about = "List contexts of a specific type")]
     List {
-        #[arg(help = "Entity type: business_rule | architectural_decision | performance_requirement | security_policy | feature")]
+        #[arg(
+            value_parser = clap::builder::PossibleValuesParser::new(ENTITY_TYPES),
+            ignore_case = true,
+            help = "Entity type: business_rule | architectural_decision | performance_requirement | security_policy | feature"
+        )]
         r#type: String,
     },
 
@@ -62,17 +112,57 @@ pub enum Commands {
         #[arg(help = "Entity ID (e.g., rule-001, ad-002, perf-003)")]
         id: String,
     },
+
+    /// Render an analytics dashboard to a self-contained HTML file
+    #[command(about = "Write a static HTML dashboard of usage, velocity and quality for a project")]
+    Dashboard {
+        #[arg(long, default_value = "30", help = "Days of usage to chart")]
+        days: u32,
+
+        #[arg(short, long, help = "Output file (defaults to context-dashboard-<project>-<date>.html)")]
+        output: Option<PathBuf>,
+    },
+
+    /// Run an MCP tool from the command line
+    #[command(about = "Call an MCP tool with JSON arguments, as an MCP client would")]
+    Call {
+        #[arg(
+            value_parser = clap::builder::PossibleValuesParser::new(tool_names()),
+            help = "Tool name, e.g. query_context"
+        )]
+        tool: String,
+
+        #[arg(short, long, value_name = "JSON", help = "Tool arguments as a JSON object")]
+        args: Option<String>,
+    },
+
+    /// Generate shell completion scripts
+    #[command(about = "Print a shell completion script (bash, zsh, fish, elvish, powershell)")]
+    Completions {
+        #[arg(value_enum, help = "Target shell")]
+        shell: clap_complete::Shell,
+
+        #[arg(long, help = "Also print short aliases (csq, csl, css, csg) for the CLI commands")]
+        aliases: bool,
+    },
 }
 
 pub struct CliRouter {
     db_path: String,
     format: String,
     project: Option<String>,
+    config: AppConfig,
 }
 
 impl CliRouter {
     pub fn new(db_path: String, format: String, project: Option<String>) -> Self {
-        Self { db_path, format, project }
+        Self { db_path, format, project, config: AppConfig::default() }
+    }
+
+    /// Use the loaded configuration for commands that build the full service container
+    pub fn with_config(mut self, config: AppConfig) -> Self {
+        self.config = config;
+        self
     }
 
     /// Route command to appropriate handler - Dependency Inversion
@@ -92,10 +182,28 @@ impl CliRouter {
             Commands::Get { id } => Arc::new(
                 GetCommand::new(self.db_path.clone(), id)
             ),
-            Commands::Serve { port: _ } => {
+            Commands::Dashboard { days, output } => {
+                // Needs the async analytics services rather than a CliCommand over raw SQL
+                let result = DashboardCommand::new(self.config.clone(), self.project.clone(), days, output)
+                    .execute()
+                    .await?;
+                println!("{}", get_formatter(&self.format).format(result));
+                return Ok(());
+            }
+            Commands::Call { tool, args } => {
+                let result = CallCommand::new(self.config.clone(), tool, args).execute().await?;
+                println!("{}", get_formatter(&self.format).format(result));
+                return Ok(());
+            }
+            Commands::Serve { .. } => {
                 // Serve mode handled separately in main
                 return Ok(());
             }
+            Commands::Completions { shell, aliases } => {
+                // Completion scripts are raw shell code, not formatted results
+                completions::print_completions(shell, aliases);
+                return Ok(());
+            }
         };
 
         // Execute through abstraction
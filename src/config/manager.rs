@@ -0,0 +1,341 @@
+//! Runtime configuration shared through `AppContainer`
+//! Watches `config.toml` and applies changes that don't need a restart
+
+use anyhow::Result;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use super::AppConfig;
+
+/// Settings that can change while the server is running
+pub const HOT_RELOADABLE: &[&str] = &[
+    "cache.default_ttl_secs",
+    "cache.negative_ttl_secs",
+    "cache.sweep_interval_secs",
+    "cache.policies",
+    "search",
+    "logging.level",
+    "logging.modules",
+    "analytics.enabled",
+    "analytics.retention",
+    "analytics.rollup_interval_minutes",
+    "analytics.anonymization",
+    "reports",
+    "audit",
+    "digests",
+    "quality",
+    "tagging",
+    "freshness",
+    "shared_context",
+    "attachments.limits",
+    "webhooks.targets",
+];
+
+/// Outcome of a reload
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ReloadReport {
+    /// Hot-reloadable settings that changed and are now in effect
+    pub applied: Vec<String>,
+    /// Sections that changed on disk but only take effect after a restart
+    pub requires_restart: Vec<String>,
+}
+
+type ChangeListener = Box<dyn Fn(&AppConfig) + Send + Sync>;
+
+/// Holds the effective configuration and notifies subscribers when it changes
+pub struct ConfigManager {
+    current: RwLock<AppConfig>,
+    source: Option<PathBuf>,
+    sender: watch::Sender<AppConfig>,
+    listeners: Mutex<Vec<ChangeListener>>,
+    watcher: Mutex<Option<RecommendedWatcher>>,
+}
+
+impl ConfigManager {
+    /// `source` is the config file to reload from; `None` disables reloading
+    pub fn new(config: AppConfig, source: Option<PathBuf>) -> Self {
+        let (sender, _) = watch::channel(config.clone());
+        Self {
+            current: RwLock::new(config),
+            source,
+            sender,
+            listeners: Mutex::new(Vec::new()),
+            watcher: Mutex::new(None),
+        }
+    }
+
+    /// Snapshot of the effective configuration
+    pub fn current(&self) -> AppConfig {
+        self.current.read().clone()
+    }
+
+    /// Config file this manager reloads from
+    pub fn source(&self) -> Option<&Path> {
+        self.source.as_deref()
+    }
+
+    /// Receive the configuration every time a reload applies changes
+    pub fn subscribe(&self) -> watch::Receiver<AppConfig> {
+        self.sender.subscribe()
+    }
+
+    /// Run `listener` synchronously with the new configuration after each applied reload
+    pub fn on_change<F>(&self, listener: F)
+    where
+        F: Fn(&AppConfig) + Send + Sync + 'static,
+    {
+        self.listeners.lock().push(Box::new(listener));
+    }
+
+    /// Re-read the config file (plus environment overrides) and apply hot-reloadable changes
+    pub fn reload(&self) -> Result<ReloadReport> {
+        let Some(source) = &self.source else {
+            return Ok(ReloadReport::default());
+        };
+
+        // Keep the profile chosen at startup
+        let profile = self.current.read().profile.clone();
+        let loaded = if source.exists() {
+            AppConfig::from_file(source, profile.as_deref())?
+        } else {
+            AppConfig::parse("", profile.as_deref())?
+        };
+        let loaded = loaded.apply_env_overrides(std::env::vars())?;
+
+        Ok(self.apply(loaded))
+    }
+
+    /// Merge hot-reloadable settings from `new` into the effective config.
+    /// Everything else keeps its startup value and is reported as requiring a restart.
+    pub fn apply(&self, new: AppConfig) -> ReloadReport {
+        let mut report = ReloadReport::default();
+        let mut merged = self.current();
+
+        if merged.cache.default_ttl_secs != new.cache.default_ttl_secs {
+            merged.cache.default_ttl_secs = new.cache.default_ttl_secs;
+            report.applied.push("cache.default_ttl_secs".to_string());
+        }
+        if merged.cache.negative_ttl_secs != new.cache.negative_ttl_secs {
+            merged.cache.negative_ttl_secs = new.cache.negative_ttl_secs;
+            report.applied.push("cache.negative_ttl_secs".to_string());
+        }
+        if merged.cache.sweep_interval_secs != new.cache.sweep_interval_secs {
+            merged.cache.sweep_interval_secs = new.cache.sweep_interval_secs;
+            report.applied.push("cache.sweep_interval_secs".to_string());
+        }
+        if merged.cache.policies != new.cache.policies {
+            merged.cache.policies = new.cache.policies.clone();
+            report.applied.push("cache.policies".to_string());
+        }
+        if merged.search != new.search {
+            merged.search = new.search.clone();
+            report.applied.push("search".to_string());
+        }
+        if merged.logging.level != new.logging.level {
+            merged.logging.level = new.logging.level.clone();
+            report.applied.push("logging.level".to_string());
+        }
+        if merged.logging.modules != new.logging.modules {
+            merged.logging.modules = new.logging.modules.clone();
+            report.applied.push("logging.modules".to_string());
+        }
+        if merged.analytics.enabled != new.analytics.enabled {
+            report.applied.push("analytics.enabled".to_string());
+        }
+        if merged.analytics.retention_days != new.analytics.retention_days
+            || merged.analytics.prune_interval_hours != new.analytics.prune_interval_hours
+        {
+            report.applied.push("analytics.retention".to_string());
+        }
+        if merged.analytics.rollup_interval_minutes != new.analytics.rollup_interval_minutes {
+            report.applied.push("analytics.rollup_interval_minutes".to_string());
+        }
+        if merged.analytics.anonymize_identifiers != new.analytics.anonymize_identifiers
+            || merged.analytics.anonymization_salt != new.analytics.anonymization_salt
+        {
+            report.applied.push("analytics.anonymization".to_string());
+        }
+        merged.analytics = new.analytics.clone();
+        if merged.reports != new.reports {
+            merged.reports = new.reports.clone();
+            report.applied.push("reports".to_string());
+        }
+        if merged.audit != new.audit {
+            merged.audit = new.audit.clone();
+            report.applied.push("audit".to_string());
+        }
+        if merged.digests != new.digests {
+            merged.digests = new.digests.clone();
+            report.applied.push("digests".to_string());
+        }
+        if merged.quality != new.quality {
+            merged.quality = new.quality.clone();
+            report.applied.push("quality".to_string());
+        }
+        if merged.tagging != new.tagging {
+            merged.tagging = new.tagging.clone();
+            report.applied.push("tagging".to_string());
+        }
+        if merged.freshness != new.freshness {
+            merged.freshness = new.freshness.clone();
+            report.applied.push("freshness".to_string());
+        }
+        if merged.shared_context != new.shared_context {
+            merged.shared_context = new.shared_context.clone();
+            report.applied.push("shared_context".to_string());
+        }
+        if merged.attachments.max_size_bytes != new.attachments.max_size_bytes
+            || merged.attachments.max_entity_bytes != new.attachments.max_entity_bytes
+        {
+            merged.attachments.max_size_bytes = new.attachments.max_size_bytes;
+            merged.attachments.max_entity_bytes = new.attachments.max_entity_bytes;
+            report.applied.push("attachments.limits".to_string());
+        }
+        if merged.webhooks != new.webhooks {
+            merged.webhooks = new.webhooks.clone();
+            report.applied.push("webhooks.targets".to_string());
+        }
+
+        // The database path usually comes from the command line, so only flag explicit changes
+        if new.database.path.is_some() && merged.database != new.database {
+            report.requires_restart.push("database".to_string());
+        }
+        if merged.logging.format != new.logging.format {
+            report.requires_restart.push("logging.format".to_string());
+        }
+        if merged.logging.file != new.logging.file {
+            report.requires_restart.push("logging.file".to_string());
+        }
+        if merged.cache.max_size != new.cache.max_size {
+            report.requires_restart.push("cache.max_size".to_string());
+        }
+        if merged.cache.persistence != new.cache.persistence {
+            report.requires_restart.push("cache.persistence".to_string());
+        }
+        if merged.embedding != new.embedding {
+            report.requires_restart.push("embedding".to_string());
+        }
+        if merged.auth != new.auth {
+            report.requires_restart.push("auth".to_string());
+        }
+        if merged.websocket != new.websocket {
+            report.requires_restart.push("websocket".to_string());
+        }
+        if merged.specs != new.specs {
+            report.requires_restart.push("specs".to_string());
+        }
+        if merged.architecture != new.architecture {
+            report.requires_restart.push("architecture".to_string());
+        }
+        if merged.plugins != new.plugins {
+            report.requires_restart.push("plugins".to_string());
+        }
+        // The directory defaults to one in the config directory, so only flag explicit changes
+        if merged.attachments.storage != new.attachments.storage
+            || (new.attachments.directory.is_some() && merged.attachments.directory != new.attachments.directory)
+        {
+            report.requires_restart.push("attachments.storage".to_string());
+        }
+        if new.exports.directory.is_some() && merged.exports != new.exports {
+            report.requires_restart.push("exports.directory".to_string());
+        }
+
+        if !report.applied.is_empty() {
+            *self.current.write() = merged.clone();
+            for listener in self.listeners.lock().iter() {
+                listener(&merged);
+            }
+            self.sender.send_replace(merged);
+            info!("Applied configuration changes: {}", report.applied.join(", "));
+        }
+        if !report.requires_restart.is_empty() {
+            warn!(
+                "Configuration changes need a restart to take effect: {}",
+                report.requires_restart.join(", ")
+            );
+        }
+
+        report
+    }
+
+    /// Start watching the config file; changes are applied as they are saved
+    pub fn watch(self: &Arc<Self>) -> Result<()> {
+        let Some(source) = self.source.clone() else {
+            return Ok(());
+        };
+        // Watch the directory: editors often replace the file instead of writing in place
+        let Some(dir) = source.parent().map(Path::to_path_buf) else {
+            return Ok(());
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+        *self.watcher.lock() = Some(watcher);
+
+        info!("Watching {} for configuration changes", source.display());
+
+        let manager = Arc::downgrade(self);
+        std::thread::spawn(move || {
+            while let Ok(event) = rx.recv() {
+                let Ok(event) = event else { continue };
+                let touches_source = event.paths.iter().any(|p| p == &source);
+                if !touches_source || !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                    continue;
+                }
+                let Some(manager) = manager.upgrade() else { break };
+                if let Err(e) = manager.reload() {
+                    warn!("Ignoring invalid config file {}: {e:#}", source.display());
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_hot_settings_and_flag_restart_ones() {
+        let manager = ConfigManager::new(AppConfig::default(), None);
+        let receiver = manager.subscribe();
+
+        let mut new = AppConfig::default();
+        new.cache.default_ttl_secs = 30;
+        new.analytics.enabled = false;
+        new.websocket.port = 9999;
+
+        let report = manager.apply(new);
+        assert_eq!(report.applied, vec!["cache.default_ttl_secs", "analytics.enabled"]);
+        assert_eq!(report.requires_restart, vec!["websocket"]);
+
+        let current = manager.current();
+        assert_eq!(current.cache.default_ttl_secs, 30);
+        assert!(!current.analytics.enabled);
+        assert_eq!(current.websocket.port, 8080);
+        assert!(receiver.has_changed().unwrap());
+    }
+
+    #[test]
+    fn test_reload_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[webhooks]\ntargets = [\"http://localhost/hook\"]\n").unwrap();
+
+        let manager = ConfigManager::new(AppConfig::default(), Some(path));
+        let report = manager.reload().unwrap();
+        assert_eq!(report.applied, vec!["webhooks.targets"]);
+        assert_eq!(manager.current().webhooks.targets, vec!["http://localhost/hook"]);
+
+        // Reloading unchanged content is a no-op
+        assert_eq!(manager.reload().unwrap(), ReloadReport::default());
+    }
+}
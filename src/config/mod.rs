@@ -0,0 +1,1461 @@
+//! Layered configuration for the context server
+//! Built-in defaults are overlaid by `config.toml`, then by the selected profile,
+//! then by `CONTEXT_SERVER__*` environment variables, then by command-line flags
+
+use crate::models::scheduled_report::ScheduledReportKind;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+pub mod manager;
+
+pub use manager::ConfigManager;
+
+/// Name of the directory holding server configuration and data
+pub const APP_DIR_NAME: &str = "context-server-rs";
+
+/// File name of the configuration file inside the config directory
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// File name of the SQLite database inside the data directory
+pub const DB_FILE_NAME: &str = "context.db";
+
+/// File name of the persisted query cache inside the data directory
+pub const CACHE_SNAPSHOT_FILE_NAME: &str = "query_cache.json";
+
+/// Prefix of environment variables overriding config keys,
+/// e.g. `CONTEXT_SERVER__CACHE__MAX_SIZE=5000` sets `[cache] max_size`
+pub const ENV_PREFIX: &str = "CONTEXT_SERVER__";
+
+/// Profiles available without being declared in `config.toml`.
+/// A `[profiles.<name>]` table in the file is applied on top of the built-in one.
+pub const BUILTIN_PROFILES: &[(&str, &str)] = &[
+    // Single developer on one machine: the defaults
+    ("local", ""),
+    // Shared service: require auth, accept remote websocket clients
+    (
+        "team",
+        r#"
+        [auth]
+        required = true
+
+        [websocket]
+        enabled = true
+        host = "0.0.0.0"
+        "#,
+    ),
+];
+
+/// Top-level server configuration, mirroring the sections of `config.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    /// Active profile, selected by `--profile` or a top-level `profile = "..."` key
+    pub profile: Option<String>,
+    pub database: DatabaseConfig,
+    pub cache: CacheConfig,
+    pub embedding: EmbeddingSettings,
+    pub search: SearchSettings,
+    pub websocket: WebSocketSettings,
+    pub conflicts: ConflictSettings,
+    pub specs: SpecsConfig,
+    pub architecture: ArchitectureSettings,
+    pub plugins: PluginsConfig,
+    pub logging: LoggingConfig,
+    pub analytics: AnalyticsSettings,
+    pub reports: ReportsConfig,
+    pub audit: AuditConfig,
+    pub digests: DigestSettings,
+    pub quality: QualitySettings,
+    pub tagging: TaggingSettings,
+    pub freshness: FreshnessSettings,
+    pub shared_context: SharedContextSettings,
+    pub attachments: AttachmentSettings,
+    pub exports: ExportSettings,
+    pub webhooks: WebhooksConfig,
+    pub auth: AuthConfig,
+}
+
+/// `[database]` section
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    /// SQLite database file; defaults to `context.db` in the data directory
+    pub path: Option<PathBuf>,
+}
+
+/// `[cache]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Maximum number of entries held by the query cache
+    pub max_size: usize,
+    /// TTL applied to cached query results, in seconds (0 disables expiry)
+    pub default_ttl_secs: u64,
+    /// How long a "not found" lookup is remembered, in seconds (0 disables negative caching)
+    pub negative_ttl_secs: u64,
+    /// How often expired entries are swept out in the background, in seconds (0 disables sweeping)
+    pub sweep_interval_secs: u64,
+    /// Per-namespace overrides keyed by `CacheKeyBuilder` namespace (`project`, `rules`, ...)
+    pub policies: BTreeMap<String, CachePolicyConfig>,
+    pub persistence: CachePersistenceConfig,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 1000,
+            default_ttl_secs: 300,
+            negative_ttl_secs: 30,
+            sweep_interval_secs: 60,
+            policies: BTreeMap::new(),
+            persistence: CachePersistenceConfig::default(),
+        }
+    }
+}
+
+/// `[cache.persistence]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CachePersistenceConfig {
+    /// Save hot entries when `serve` stops and load them on the next start
+    pub enabled: bool,
+    /// Defaults to `query_cache.json` in the data directory
+    pub path: Option<PathBuf>,
+    /// Namespaces worth keeping across restarts
+    pub namespaces: Vec<String>,
+}
+
+impl Default for CachePersistenceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            namespaces: vec![
+                "project".to_string(),
+                "projects".to_string(),
+                "rules".to_string(),
+            ],
+        }
+    }
+}
+
+/// `[cache.policies.<namespace>]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CachePolicyConfig {
+    pub enabled: bool,
+    /// Overrides `default_ttl_secs` for this namespace
+    pub ttl_secs: Option<u64>,
+    pub max_entries: Option<usize>,
+}
+
+impl Default for CachePolicyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_secs: None,
+            max_entries: None,
+        }
+    }
+}
+
+/// `[embedding]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmbeddingSettings {
+    /// Embedding backend: "simple", "openai", "ollama" or "local"
+    pub provider: String,
+    pub model: String,
+    /// Directory of the model run by the "local" provider (`config.json`, `tokenizer.json`,
+    /// `model.safetensors`)
+    pub model_path: Option<PathBuf>,
+    pub dimension: usize,
+    /// Base URL for HTTP-based providers
+    pub endpoint: Option<String>,
+    /// Token for HTTP providers; `openai` falls back to `OPENAI_API_KEY`
+    pub api_key: Option<String>,
+    /// Texts sent per embedding request
+    pub batch_size: usize,
+    /// Retries for timeouts, rate limits and server errors
+    pub max_retries: u32,
+    pub timeout_secs: u64,
+    /// Characters per embedded chunk of long entity text; 0 embeds texts whole
+    pub chunk_size: usize,
+    /// Characters shared by consecutive chunks
+    pub chunk_overlap: usize,
+}
+
+impl Default for EmbeddingSettings {
+    fn default() -> Self {
+        Self {
+            provider: "simple".to_string(),
+            model: "all-MiniLM-L6-v2".to_string(),
+            model_path: None,
+            dimension: 384,
+            endpoint: None,
+            api_key: None,
+            batch_size: 32,
+            max_retries: 3,
+            timeout_secs: 30,
+            chunk_size: 2000,
+            chunk_overlap: 200,
+        }
+    }
+}
+
+/// `[search]` section: how `search_context` fuses and ranks results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchSettings {
+    /// Weight of the embedding similarity leg
+    pub vector_weight: f32,
+    /// Weight of the keyword match leg
+    pub keyword_weight: f32,
+    /// "weighted_sum" of leg scores, or "rrf" (reciprocal rank fusion) of leg ranks
+    pub fusion: String,
+    /// RRF damping constant; larger values flatten the difference between ranks
+    pub rrf_k: f32,
+    /// Minimum cosine similarity for a vector hit
+    pub similarity_threshold: f32,
+    pub max_results: usize,
+    /// Let query wording ("similar to ...", "business rule ...") pick a single leg
+    pub intent_routing: bool,
+    /// Let keyword terms match words with a typo or two ("authetication")
+    pub fuzzy_matching: bool,
+    /// Score multipliers keyed by entity type, e.g. `business_rule = 1.2`
+    pub boosts: BTreeMap<String, f32>,
+    /// Largest extra score multiplier for results agents often open after searching; 0 disables
+    pub click_boost: f32,
+    /// Days after which a search click counts half as much
+    pub click_half_life_days: f32,
+    pub rerank: RerankSettings,
+}
+
+impl Default for SearchSettings {
+    fn default() -> Self {
+        Self {
+            vector_weight: 0.7,
+            keyword_weight: 0.3,
+            fusion: "weighted_sum".to_string(),
+            rrf_k: 60.0,
+            similarity_threshold: 0.3,
+            max_results: 20,
+            intent_routing: true,
+            fuzzy_matching: true,
+            boosts: BTreeMap::new(),
+            click_boost: 0.2,
+            click_half_life_days: 14.0,
+            rerank: RerankSettings::default(),
+        }
+    }
+}
+
+/// `[search.rerank]` section: optional second pass over the top fused results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RerankSettings {
+    /// "none", "cross_encoder" (a `/rerank` endpoint) or "llm" (OpenAI-compatible chat API)
+    pub provider: String,
+    /// Base URL; required for `cross_encoder`, `llm` defaults to api.openai.com/v1
+    pub endpoint: Option<String>,
+    pub model: String,
+    /// `llm` falls back to `OPENAI_API_KEY`
+    pub api_key: Option<String>,
+    /// How many of the best fused results are reranked
+    pub top_k: usize,
+    pub timeout_secs: u64,
+    pub max_retries: u32,
+}
+
+impl Default for RerankSettings {
+    fn default() -> Self {
+        Self {
+            provider: "none".to_string(),
+            endpoint: None,
+            model: String::new(),
+            api_key: None,
+            top_k: 10,
+            timeout_secs: 10,
+            max_retries: 1,
+        }
+    }
+}
+
+/// `[websocket]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebSocketSettings {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    /// How long broadcast changes are kept for clients resuming with `resume_from`, in hours
+    /// (0 disables the change log)
+    pub replay_retention_hours: u64,
+    /// Seconds between keepalive pings to each client
+    pub ping_interval_secs: u64,
+    /// Clients silent for this many seconds are disconnected
+    pub pong_timeout_secs: u64,
+    /// Messages queued for one client before `slow_client_policy` applies
+    pub max_queue_depth: usize,
+    pub slow_client_policy: SlowClientPolicy,
+    /// Milliseconds changes are collected and coalesced before being sent as one batch
+    /// (0 sends each change immediately)
+    pub batch_window_ms: u64,
+    /// Most changes sent in one batch
+    pub max_batch_size: usize,
+}
+
+/// What happens to a client whose send queue is full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SlowClientPolicy {
+    /// Drop the messages that do not fit; the client can catch up with `resume_from`
+    Drop,
+    /// Close the connection; the client reconnects and resumes
+    #[default]
+    Disconnect,
+}
+
+impl Default for WebSocketSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "127.0.0.1".to_string(),
+            port: 8080,
+            replay_retention_hours: 72,
+            ping_interval_secs: 30,
+            pong_timeout_secs: 90,
+            max_queue_depth: 1000,
+            slow_client_policy: SlowClientPolicy::Disconnect,
+            batch_window_ms: 50,
+            max_batch_size: 100,
+        }
+    }
+}
+
+impl WebSocketSettings {
+    /// Address the WebSocket server listens on; `host` may be an IP address or a hostname
+    pub fn bind_address(&self) -> Result<SocketAddr> {
+        (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .with_context(|| format!("Invalid [websocket] address {}:{}", self.host, self.port))?
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("[websocket] host {} did not resolve", self.host))
+    }
+
+    /// Enable the server on `address`, as `serve --ws <addr>` does
+    pub fn listen_on(&mut self, address: SocketAddr) {
+        self.enabled = true;
+        self.host = address.ip().to_string();
+        self.port = address.port();
+    }
+}
+
+/// `[conflicts]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConflictSettings {
+    /// Most concurrent changes to one entity for which auto-merge is recommended
+    pub max_auto_merge_changes: usize,
+    /// Auto-merges less confident than this (0 to 1) are left for manual resolution
+    pub min_merge_confidence: f64,
+    /// String fields, such as `description`, whose concurrent edits are merged as text
+    /// rather than treated as conflicting
+    pub text_fields: Vec<String>,
+    /// Overrides per entity type, e.g. `[conflicts.policies.specification]`
+    pub policies: BTreeMap<String, ConflictPolicySettings>,
+}
+
+impl Default for ConflictSettings {
+    fn default() -> Self {
+        Self {
+            max_auto_merge_changes: 2,
+            min_merge_confidence: 0.0,
+            text_fields: Vec::new(),
+            policies: BTreeMap::new(),
+        }
+    }
+}
+
+/// `[conflicts.policies.<entity_type>]` section; unset fields keep the `[conflicts]` values
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConflictPolicySettings {
+    /// Strategy for every conflict on the entity type, in place of the recommendation by
+    /// conflict type
+    pub strategy: Option<ResolutionStrategy>,
+    pub max_auto_merge_changes: Option<usize>,
+    pub min_merge_confidence: Option<f64>,
+}
+
+/// Conflict resolution strategy named in a policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolutionStrategy {
+    LastWriterWins,
+    AutoMerge,
+    ManualResolution,
+    Reject,
+}
+
+/// `[specs]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpecsConfig {
+    /// Directories scanned for specifications when no path is given
+    pub roots: Vec<PathBuf>,
+    /// Directories scanned for architecture decision records (`NNNN-*.md`) when no path is given
+    pub adr_roots: Vec<PathBuf>,
+    /// Milliseconds a monitored specification file must stay unchanged before it is re-imported
+    pub debounce_ms: u64,
+    /// Further directories, each in its own convention, e.g. one `[[specs.sources]]` per
+    /// service of a monorepo
+    pub sources: Vec<SpecSource>,
+    /// Approvers every specification and decision put in review needs, unless the review names others
+    pub required_approvers: Vec<String>,
+    /// Approvals needed when no approvers are required by name
+    pub min_approvals: usize,
+    /// How many stored versions of each specification are kept
+    pub versions: VersionRetentionSettings,
+}
+
+/// `[specs.versions]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VersionRetentionSettings {
+    /// Squash a specification's versions whenever a new one is stored. Off by default, since
+    /// squashing deletes versions; set `auto_squash = true` under `[specs.versions]` to opt in
+    pub auto_squash: bool,
+    /// Newest versions of each specification that are never squashed
+    pub keep_recent: usize,
+    /// Of older versions, the last of every this many days is kept as a milestone; 0 keeps none
+    pub milestone_interval_days: u32,
+    /// Versions older than this many days are deleted outright, except tagged milestones and the
+    /// newest version; 0 keeps them
+    pub max_age_days: u32,
+}
+
+impl Default for VersionRetentionSettings {
+    fn default() -> Self {
+        Self {
+            auto_squash: false,
+            keep_recent: 20,
+            milestone_interval_days: 1,
+            max_age_days: 0,
+        }
+    }
+}
+
+/// `[[specs.sources]]` entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpecSource {
+    pub path: PathBuf,
+    pub format: SpecSourceFormat,
+}
+
+/// Convention the documents of a spec source follow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecSourceFormat {
+    /// `requirements.md`, `design.md` and `tasks.md` per directory, as under `.kiro/specs`
+    Kiro,
+    /// Architecture decision records, `NNNN-title.md`
+    Adr,
+    /// Gherkin `.feature` files
+    Gherkin,
+    /// Any other markdown files, imported as documents without requirements
+    Markdown,
+}
+
+impl SpecSourceFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpecSourceFormat::Kiro => "kiro",
+            SpecSourceFormat::Adr => "adr",
+            SpecSourceFormat::Gherkin => "gherkin",
+            SpecSourceFormat::Markdown => "markdown",
+        }
+    }
+}
+
+impl SpecsConfig {
+    /// Every configured directory with its format: `roots` as Kiro, `adr_roots` as ADR, then `sources`
+    pub fn all_sources(&self) -> Vec<SpecSource> {
+        let source = |format: SpecSourceFormat| move |path: &PathBuf| SpecSource { path: path.clone(), format };
+        self.roots
+            .iter()
+            .map(source(SpecSourceFormat::Kiro))
+            .chain(self.adr_roots.iter().map(source(SpecSourceFormat::Adr)))
+            .chain(self.sources.iter().cloned())
+            .collect()
+    }
+
+    /// Configured directories of one format
+    pub fn roots_of(&self, format: SpecSourceFormat) -> Vec<PathBuf> {
+        self.all_sources()
+            .into_iter()
+            .filter(|source| source.format == format)
+            .map(|source| source.path)
+            .collect()
+    }
+}
+
+impl Default for SpecsConfig {
+    fn default() -> Self {
+        Self {
+            roots: vec![PathBuf::from(".kiro/specs")],
+            adr_roots: vec![PathBuf::from("docs/adr"), PathBuf::from("docs/decisions")],
+            debounce_ms: 500,
+            sources: Vec::new(),
+            required_approvers: Vec::new(),
+            min_approvals: 1,
+            versions: VersionRetentionSettings::default(),
+        }
+    }
+}
+
+/// `[architecture]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArchitectureSettings {
+    /// Violations acknowledged with `validate_architecture` `update_baseline`, left out of later
+    /// validations
+    pub baseline_file: PathBuf,
+}
+
+impl Default for ArchitectureSettings {
+    fn default() -> Self {
+        Self {
+            baseline_file: PathBuf::from("architecture-baseline.json"),
+        }
+    }
+}
+
+/// `[plugins]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PluginsConfig {
+    pub install_dir: PathBuf,
+    pub data_dir: PathBuf,
+    pub marketplace_url: Option<String>,
+}
+
+impl Default for PluginsConfig {
+    fn default() -> Self {
+        Self {
+            install_dir: PathBuf::from("plugins"),
+            data_dir: PathBuf::from("plugin_data"),
+            marketplace_url: None,
+        }
+    }
+}
+
+/// `[logging]` section
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Filter directive such as "info" or "context_server_rs=debug"; `RUST_LOG` still wins
+    pub level: Option<String>,
+    pub format: LogFormat,
+    /// Per-module levels, e.g. `"context_server_rs::services" = "trace"`
+    pub modules: BTreeMap<String, String>,
+    pub file: LogFileConfig,
+}
+
+/// Log line format
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// `[logging.file]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogFileConfig {
+    pub enabled: bool,
+    /// Defaults to `logs/` in the config directory
+    pub directory: Option<PathBuf>,
+    pub rotation: LogRotation,
+    /// Number of rotated files kept (0 keeps all)
+    pub max_files: usize,
+}
+
+impl Default for LogFileConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: None,
+            rotation: LogRotation::Daily,
+            max_files: 7,
+        }
+    }
+}
+
+/// How often log files are rotated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    Minutely,
+    Hourly,
+    #[default]
+    Daily,
+    Never,
+}
+
+/// `[analytics]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnalyticsSettings {
+    /// Record usage events; when false `track_event` is a no-op
+    pub enabled: bool,
+    /// Days raw events and hourly rollups are kept; daily rollups are kept for good
+    /// (0 keeps everything)
+    pub retention_days: u32,
+    /// Hours between background pruning runs (0 leaves pruning to `prune_analytics`)
+    pub prune_interval_hours: u64,
+    /// Minutes between background runs folding new events into the hourly and daily rollups
+    /// (0 leaves it to pruning)
+    pub rollup_interval_minutes: u64,
+    /// Store user and client identifiers as salted hashes instead of as they were given
+    pub anonymize_identifiers: bool,
+    /// Mixed into identifier hashes so they can't be matched against hashes of known names
+    pub anonymization_salt: String,
+}
+
+impl Default for AnalyticsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            retention_days: 90,
+            prune_interval_hours: 24,
+            rollup_interval_minutes: 10,
+            anonymize_identifiers: false,
+            anonymization_salt: String::new(),
+        }
+    }
+}
+
+/// `[reports]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportsConfig {
+    /// Hours between the reports `serve` generates for every project (0 disables scheduling)
+    pub interval_hours: u64,
+    /// Reports generated on schedule
+    pub kinds: Vec<ScheduledReportKind>,
+    /// Reports of each kind kept per project (0 keeps all)
+    pub keep: usize,
+    /// POST each scheduled report to the `[webhooks]` targets
+    pub notify_webhooks: bool,
+    /// Slack incoming webhook URL sent a summary of each scheduled report
+    pub slack_webhook_url: Option<String>,
+}
+
+impl Default for ReportsConfig {
+    fn default() -> Self {
+        Self {
+            interval_hours: 24,
+            kinds: ScheduledReportKind::ALL.to_vec(),
+            keep: 30,
+            notify_webhooks: false,
+            slack_webhook_url: None,
+        }
+    }
+}
+
+/// `[audit]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuditConfig {
+    /// Record every tool call in the audit log; independent of `[analytics] enabled`
+    pub enabled: bool,
+    /// Days audit log entries are kept (0 keeps everything)
+    pub retention_days: u32,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            retention_days: 365,
+        }
+    }
+}
+
+/// `[digests]` section: the language model `generate_context_digest` writes summaries with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DigestSettings {
+    /// "sampling" (the calling MCP client's model), "llm" (OpenAI-compatible chat API) or "none"
+    pub provider: String,
+    /// Base URL for `llm`; defaults to api.openai.com/v1
+    pub endpoint: Option<String>,
+    /// Model for `llm`, or the model hint sent with sampling requests when set
+    pub model: String,
+    /// `llm` falls back to `OPENAI_API_KEY`
+    pub api_key: Option<String>,
+    /// Longest summary requested, in tokens
+    pub max_tokens: u32,
+    pub timeout_secs: u64,
+    /// Regenerate digests in the background when an entity they summarize changes
+    pub refresh_on_change: bool,
+}
+
+impl Default for DigestSettings {
+    fn default() -> Self {
+        Self {
+            provider: "sampling".to_string(),
+            endpoint: None,
+            model: String::new(),
+            api_key: None,
+            max_tokens: 400,
+            timeout_secs: 60,
+            refresh_on_change: true,
+        }
+    }
+}
+
+/// `[quality]` section: the quality rubric of projects without their own (`manage_quality_rubric`).
+/// Without any checks, quality scores use the built-in heuristics.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QualitySettings {
+    /// Fields each entity type must fill, by entity type
+    pub required_fields: BTreeMap<String, Vec<String>>,
+    /// Fewest characters in a description (0 for no minimum)
+    pub min_description_length: usize,
+    /// Business rules must give at least one example
+    pub require_examples: bool,
+    /// Entities must be linked to a specification requirement
+    pub require_requirement_link: bool,
+}
+
+/// `[tagging]` section: tags suggested for entities as they are created or updated
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaggingSettings {
+    pub enabled: bool,
+    /// Suggestions at least this confident (0-1) are applied; the rest wait for review
+    pub auto_apply_threshold: f64,
+    /// Suggestions less confident than this are dropped
+    pub min_confidence: f64,
+    /// Compare embeddings of entities and tags as well as keywords
+    pub use_embeddings: bool,
+    /// Keywords of each domain area, in addition to the areas' names
+    pub domain_areas: BTreeMap<String, Vec<String>>,
+    /// Keywords of each layer, in addition to the built-in ones
+    pub layers: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for TaggingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            auto_apply_threshold: 0.8,
+            min_confidence: 0.3,
+            use_embeddings: true,
+            domain_areas: BTreeMap::new(),
+            layers: BTreeMap::new(),
+        }
+    }
+}
+
+/// `[freshness]` section: how fast confidence in an entity decays after it was last verified
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FreshnessSettings {
+    /// Days after which an entity's freshness halves
+    pub half_life_days: f64,
+    /// Half-lives of particular entity types (`{"architectural_decision": 365}`)
+    pub half_life_by_type: BTreeMap<String, f64>,
+    /// Entities less fresh than this (0-1) are listed by `list_stale_context`
+    pub stale_below: f64,
+}
+
+impl Default for FreshnessSettings {
+    fn default() -> Self {
+        Self {
+            half_life_days: 180.0,
+            half_life_by_type: BTreeMap::new(),
+            stale_below: 0.5,
+        }
+    }
+}
+
+impl FreshnessSettings {
+    /// Half-life of `entity_type`, never below a day
+    pub fn half_life_of(&self, entity_type: &str) -> f64 {
+        self.half_life_by_type
+            .get(entity_type)
+            .copied()
+            .unwrap_or(self.half_life_days)
+            .max(1.0)
+    }
+}
+
+/// `[shared_context]` section: the organization project whose context applies to every project
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SharedContextSettings {
+    /// Project that `promote_to_shared` copies entities to and `query_context` merges with
+    /// `include_shared`
+    pub project_id: Option<String>,
+}
+
+/// `[attachments]` section: files such as diagrams and sample payloads attached to entities
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AttachmentSettings {
+    /// "sqlite" keeps attachment contents in the database, "filesystem" as files in `directory`
+    pub storage: String,
+    /// Directory of "filesystem" storage; defaults to `attachments` in the config directory
+    pub directory: Option<PathBuf>,
+    /// Largest attachment accepted, in bytes
+    pub max_size_bytes: u64,
+    /// Largest total size of one entity's attachments, in bytes (0 for no limit)
+    pub max_entity_bytes: u64,
+}
+
+impl Default for AttachmentSettings {
+    fn default() -> Self {
+        Self {
+            storage: "sqlite".to_string(),
+            directory: None,
+            max_size_bytes: 5 * 1024 * 1024,
+            max_entity_bytes: 50 * 1024 * 1024,
+        }
+    }
+}
+
+/// `[exports]` section: files written by `generate_dashboard` and Parquet analytics exports
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportSettings {
+    /// Directory that tool output paths are relative to; defaults to `exports` in the config directory
+    pub directory: Option<PathBuf>,
+}
+
+/// `[webhooks]` section
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhooksConfig {
+    /// URLs notified of context changes
+    pub targets: Vec<String>,
+}
+
+/// `[auth]` section
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    /// Reject network clients that don't present one of `tokens` or `project_tokens`
+    pub required: bool,
+    /// Tokens with access to every project
+    pub tokens: Vec<String>,
+    /// Tokens limited to some projects (`[[auth.project_tokens]]`)
+    pub project_tokens: Vec<ProjectToken>,
+}
+
+impl AuthConfig {
+    /// Whether any token is configured
+    pub fn has_tokens(&self) -> bool {
+        !self.tokens.is_empty() || !self.project_tokens.is_empty()
+    }
+}
+
+/// A token granting access to `projects` only
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectToken {
+    pub token: String,
+    /// Project ids the token may subscribe to
+    pub projects: Vec<String>,
+    /// Subscribe only; changes published with this token are rejected
+    pub read_only: bool,
+}
+
+impl AppConfig {
+    /// Load configuration from `path`, or from `config.toml` in `dirs` when `path` is `None`.
+    /// An explicitly given file must exist; a missing default file yields built-in defaults.
+    /// `CONTEXT_SERVER__*` environment variables are applied on top of the file.
+    pub fn load(path: Option<&Path>, dirs: &AppDirs, profile: Option<&str>) -> Result<Self> {
+        let config = match path {
+            Some(path) => Self::from_file(path, profile)?,
+            None => {
+                let path = dirs.config_file();
+                if path.exists() {
+                    Self::from_file(&path, profile)?
+                } else {
+                    Self::parse("", profile)?
+                }
+            }
+        };
+        config.apply_env_overrides(std::env::vars())
+    }
+
+    /// Overlay `CONTEXT_SERVER__SECTION__KEY=value` pairs from `vars` onto this config.
+    /// Values are parsed as TOML literals (numbers, booleans, arrays) unless the key holds a string.
+    pub fn apply_env_overrides<I>(self, vars: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut root = toml::Value::try_from(&self)?;
+        let mut applied = false;
+
+        for (name, raw) in vars {
+            let Some(key) = name.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            let segments: Vec<String> = key.split("__").map(|s| s.to_lowercase()).collect();
+            if segments.iter().any(|s| s.is_empty()) {
+                continue;
+            }
+
+            set_path(&mut root, &segments, &raw);
+            debug!("Config override from {}", name);
+            applied = true;
+        }
+
+        if !applied {
+            return Ok(self);
+        }
+        root.try_into()
+            .context("Invalid value in CONTEXT_SERVER__* environment override")
+    }
+
+    /// Parse a configuration file, applying `profile` (or the file's own `profile` key)
+    pub fn from_file(path: &Path, profile: Option<&str>) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        let config = Self::parse(&content, profile)
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+        debug!("Loaded configuration from {}", path.display());
+        Ok(config)
+    }
+
+    /// Parse configuration from TOML text without selecting a profile
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        Self::parse(content, None)
+    }
+
+    /// Parse configuration from TOML text and overlay the selected profile
+    pub fn parse(content: &str, profile: Option<&str>) -> Result<Self> {
+        let mut root: toml::Table = toml::from_str(content)?;
+        let declared = match root.remove("profiles") {
+            Some(toml::Value::Table(profiles)) => profiles,
+            Some(_) => anyhow::bail!("`profiles` must be a table of [profiles.<name>] sections"),
+            None => toml::Table::new(),
+        };
+
+        let selected = profile
+            .map(str::to_string)
+            .or_else(|| root.get("profile").and_then(|v| v.as_str()).map(str::to_string));
+
+        if let Some(name) = selected {
+            let builtin = BUILTIN_PROFILES.iter().find(|(n, _)| *n == name);
+            let custom = declared.get(&name);
+            if builtin.is_none() && custom.is_none() {
+                let mut known: Vec<&str> = BUILTIN_PROFILES.iter().map(|(n, _)| *n).collect();
+                known.extend(declared.keys().map(String::as_str));
+                anyhow::bail!("Unknown profile '{}' (available: {})", name, known.join(", "));
+            }
+
+            if let Some((_, overlay)) = builtin {
+                merge_tables(&mut root, toml::from_str(overlay)?);
+            }
+            if let Some(overlay) = custom {
+                let toml::Value::Table(overlay) = overlay else {
+                    anyhow::bail!("[profiles.{}] must be a table", name);
+                };
+                merge_tables(&mut root, overlay.clone());
+            }
+            root.insert("profile".to_string(), toml::Value::String(name));
+        }
+
+        Ok(toml::Value::Table(root).try_into()?)
+    }
+
+    /// Database path from the config, falling back to `context.db` in the data directory
+    /// (`--config-dir` when given, the default one otherwise)
+    pub fn resolve_db_path(&self, config_dir_override: Option<&Path>) -> Result<PathBuf> {
+        match &self.database.path {
+            Some(path) => Ok(path.clone()),
+            None => Ok(AppDirs::resolve(config_dir_override)?.default_db_path()),
+        }
+    }
+}
+
+/// Recursively merge `overlay` into `base`; overlay values win, nested tables are merged
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Set the value at `segments` in `root`, creating intermediate tables as needed
+fn set_path(root: &mut toml::Value, segments: &[String], raw: &str) {
+    let mut current = root;
+    for segment in &segments[..segments.len() - 1] {
+        let table = match current {
+            toml::Value::Table(table) => table,
+            other => {
+                *other = toml::Value::Table(toml::map::Map::new());
+                other.as_table_mut().expect("just replaced with a table")
+            }
+        };
+        current = table
+            .entry(segment.clone())
+            .or_insert_with(|| toml::Value::Table(toml::map::Map::new()));
+    }
+
+    let Some(table) = current.as_table_mut() else {
+        return;
+    };
+    let key = &segments[segments.len() - 1];
+    let value = match table.get(key) {
+        Some(toml::Value::String(_)) => toml::Value::String(raw.to_string()),
+        _ => parse_env_value(raw),
+    };
+    table.insert(key.clone(), value);
+}
+
+/// Parse an environment value as a TOML literal, falling back to a plain string
+fn parse_env_value(raw: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("v = {raw}"))
+        .ok()
+        .and_then(|mut t| t.remove("v"))
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()))
+}
+
+/// Directories holding the server's configuration and data
+#[derive(Debug, Clone)]
+pub struct AppDirs {
+    pub config_dir: PathBuf,
+    pub data_dir: PathBuf,
+}
+
+impl AppDirs {
+    /// Resolve the directories, creating them if needed.
+    /// With an override (`--config-dir`), configuration and data share that directory;
+    /// otherwise they follow the XDG base directories (`$XDG_CONFIG_HOME`, `$XDG_DATA_HOME`).
+    pub fn resolve(config_dir_override: Option<&Path>) -> Result<Self> {
+        let app_dirs = match config_dir_override {
+            Some(dir) => Self {
+                config_dir: dir.to_path_buf(),
+                data_dir: dir.to_path_buf(),
+            },
+            None => {
+                let config_base = dirs::config_dir()
+                    .ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?;
+                let data_base = dirs::data_dir()
+                    .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+                Self {
+                    config_dir: config_base.join(APP_DIR_NAME),
+                    data_dir: data_base.join(APP_DIR_NAME),
+                }
+            }
+        };
+
+        for dir in [&app_dirs.config_dir, &app_dirs.data_dir] {
+            if !dir.exists() {
+                fs::create_dir_all(dir)?;
+                debug!("Created directory: {}", dir.display());
+            }
+        }
+
+        Ok(app_dirs)
+    }
+
+    /// Location of `config.toml`
+    pub fn config_file(&self) -> PathBuf {
+        self.config_dir.join(CONFIG_FILE_NAME)
+    }
+
+    /// Location of the database when none is configured
+    pub fn default_db_path(&self) -> PathBuf {
+        self.data_dir.join(DB_FILE_NAME)
+    }
+
+    /// Location of the persisted query cache when `[cache.persistence] path` is not set
+    pub fn default_cache_snapshot_path(&self) -> PathBuf {
+        self.data_dir.join(CACHE_SNAPSHOT_FILE_NAME)
+    }
+
+    /// Location of rotated log files when `[logging.file] directory` is not set
+    pub fn default_log_dir(&self) -> PathBuf {
+        self.config_dir.join("logs")
+    }
+
+    /// Location of attachment files when `[attachments] directory` is not set
+    pub fn default_attachments_dir(&self) -> PathBuf {
+        self.config_dir.join("attachments")
+    }
+
+    /// Location of files written by tools when `[exports] directory` is not set
+    pub fn default_exports_dir(&self) -> PathBuf {
+        self.config_dir.join("exports")
+    }
+}
+
+/// Database locations used by earlier releases, most recent first
+pub fn legacy_db_paths() -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    vec![
+        home.join(".config").join(APP_DIR_NAME).join(DB_FILE_NAME),
+        home.join("config").join(APP_DIR_NAME).join(DB_FILE_NAME),
+    ]
+}
+
+/// Move the first existing database from `legacy_paths` to `target` if `target` does not exist yet.
+/// Returns the path that was migrated, if any.
+pub fn migrate_legacy_db(target: &Path, legacy_paths: &[PathBuf]) -> Result<Option<PathBuf>> {
+    if target.exists() {
+        return Ok(None);
+    }
+
+    let Some(legacy) = legacy_paths.iter().find(|p| p.exists() && p.as_path() != target) else {
+        return Ok(None);
+    };
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // Fold the write-ahead log into the database so its committed pages are not left behind
+    rusqlite::Connection::open(legacy)
+        .and_then(|conn| conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(())))
+        .with_context(|| format!("Failed to checkpoint database {}", legacy.display()))?;
+
+    // Any log left over moves first, so an interrupted migration is retried with it in place
+    for suffix in ["-wal", "-shm", ""] {
+        let from = with_suffix(legacy, suffix);
+        if from.exists() {
+            move_file(&from, &with_suffix(target, suffix))?;
+        }
+    }
+
+    Ok(Some(legacy.clone()))
+}
+
+/// `path` with `suffix` appended to its file name, as SQLite names its `-wal` and `-shm` files
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    // rename fails across filesystems, so fall back to copy + remove
+    if fs::rename(from, to).is_err() {
+        fs::copy(from, to).with_context(|| format!("Failed to migrate database from {}", from.display()))?;
+        fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_config_uses_defaults() {
+        let config = AppConfig::from_toml_str("").unwrap();
+        assert_eq!(config.cache.max_size, 1000);
+        assert_eq!(config.websocket.port, 8080);
+        assert_eq!(config.specs.roots, vec![PathBuf::from(".kiro/specs")]);
+        assert!(config.database.path.is_none());
+    }
+
+    #[test]
+    fn test_websocket_bind_address() {
+        let mut settings = WebSocketSettings::default();
+        assert_eq!(settings.bind_address().unwrap(), "127.0.0.1:8080".parse().unwrap());
+
+        settings.listen_on("[::1]:9100".parse().unwrap());
+        assert!(settings.enabled);
+        assert_eq!(settings.host, "::1");
+        assert_eq!(settings.bind_address().unwrap(), "[::1]:9100".parse().unwrap());
+
+        let config = AppConfig::from_toml_str(
+            r#"
+            [websocket]
+            max_queue_depth = 50
+            slow_client_policy = "drop"
+            batch_window_ms = 0
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.websocket.max_queue_depth, 50);
+        assert_eq!(config.websocket.batch_window_ms, 0);
+        assert_eq!(config.websocket.max_batch_size, 100);
+        assert_eq!(config.websocket.slow_client_policy, SlowClientPolicy::Drop);
+        assert_eq!(config.websocket.ping_interval_secs, 30);
+    }
+
+    #[test]
+    fn test_conflict_policies() {
+        let config = AppConfig::from_toml_str(
+            r#"
+            [conflicts]
+            max_auto_merge_changes = 3
+
+            [conflicts.policies.specification]
+            strategy = "manual_resolution"
+
+            [conflicts.policies.business_rule]
+            min_merge_confidence = 0.9
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.conflicts.max_auto_merge_changes, 3);
+        assert_eq!(config.conflicts.min_merge_confidence, 0.0);
+        assert_eq!(
+            config.conflicts.policies["specification"].strategy,
+            Some(ResolutionStrategy::ManualResolution)
+        );
+        assert_eq!(config.conflicts.policies["business_rule"].min_merge_confidence, Some(0.9));
+        assert!(config.conflicts.policies["business_rule"].strategy.is_none());
+    }
+
+    #[test]
+    fn test_spec_sources() {
+        let config = AppConfig::from_toml_str(
+            r#"
+            [specs]
+            adr_roots = []
+
+            [[specs.sources]]
+            path = "services/billing/features"
+            format = "gherkin"
+
+            [[specs.sources]]
+            path = "services/search/.kiro/specs"
+            format = "kiro"
+            "#,
+        )
+        .unwrap();
+        let sources = config.specs.all_sources();
+        assert_eq!(sources.len(), 3);
+        assert_eq!(sources[0], SpecSource { path: PathBuf::from(".kiro/specs"), format: SpecSourceFormat::Kiro });
+        assert_eq!(sources[1].format, SpecSourceFormat::Gherkin);
+        assert_eq!(
+            config.specs.roots_of(SpecSourceFormat::Kiro),
+            vec![PathBuf::from(".kiro/specs"), PathBuf::from("services/search/.kiro/specs")]
+        );
+        assert!(config.specs.roots_of(SpecSourceFormat::Adr).is_empty());
+
+        assert!(AppConfig::from_toml_str("[[specs.sources]]\npath = \"docs\"\nformat = \"asciidoc\"\n").is_err());
+    }
+
+    #[test]
+    fn test_partial_sections_keep_other_defaults() {
+        let config = AppConfig::from_toml_str(
+            r#"
+            [database]
+            path = "/tmp/ctx.db"
+
+            [cache]
+            max_size = 5000
+
+            [specs]
+            roots = ["docs/specs", ".kiro/specs"]
+
+            [specs.versions]
+            keep_recent = 5
+
+            [architecture]
+            baseline_file = ".context/baseline.json"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.database.path, Some(PathBuf::from("/tmp/ctx.db")));
+        assert_eq!(config.cache.max_size, 5000);
+        assert_eq!(config.cache.default_ttl_secs, 300);
+        assert_eq!(config.specs.roots.len(), 2);
+        assert_eq!(config.specs.adr_roots, vec![PathBuf::from("docs/adr"), PathBuf::from("docs/decisions")]);
+        assert_eq!(config.specs.versions.keep_recent, 5);
+        assert_eq!(config.specs.versions.milestone_interval_days, 1);
+        assert_eq!(config.architecture.baseline_file, PathBuf::from(".context/baseline.json"));
+        assert_eq!(config.embedding.provider, "simple");
+    }
+
+    #[test]
+    fn test_logging_section() {
+        let config = AppConfig::from_toml_str(
+            r#"
+            [logging]
+            format = "json"
+
+            [logging.modules]
+            "context_server_rs::services" = "trace"
+
+            [logging.file]
+            enabled = true
+            rotation = "hourly"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.logging.format, LogFormat::Json);
+        assert_eq!(config.logging.modules["context_server_rs::services"], "trace");
+        assert!(config.logging.file.enabled);
+        assert_eq!(config.logging.file.rotation, LogRotation::Hourly);
+        assert_eq!(config.logging.file.max_files, 7);
+    }
+
+    #[test]
+    fn test_explicit_missing_file_is_an_error() {
+        let dirs = AppDirs {
+            config_dir: std::env::temp_dir(),
+            data_dir: std::env::temp_dir(),
+        };
+        let result = AppConfig::load(Some(Path::new("/nonexistent/context-server.toml")), &dirs, None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_builtin_team_profile() {
+        let config = AppConfig::parse("", Some("team")).unwrap();
+        assert_eq!(config.profile.as_deref(), Some("team"));
+        assert!(config.auth.required);
+        assert!(config.websocket.enabled);
+        assert_eq!(config.websocket.host, "0.0.0.0");
+
+        let local = AppConfig::parse("", Some("local")).unwrap();
+        assert!(!local.auth.required);
+        assert!(!local.websocket.enabled);
+    }
+
+    #[test]
+    fn test_file_profiles_overlay_builtin_and_base() {
+        let content = r#"
+            profile = "team"
+
+            [cache]
+            max_size = 300
+
+            [profiles.team.database]
+            path = "/srv/context/team.db"
+
+            [profiles.team.analytics]
+            enabled = false
+
+            [profiles.ci.websocket]
+            port = 9100
+        "#;
+
+        let team = AppConfig::parse(content, None).unwrap();
+        assert_eq!(team.database.path, Some(PathBuf::from("/srv/context/team.db")));
+        assert!(!team.analytics.enabled);
+        assert!(team.auth.required);
+        assert_eq!(team.cache.max_size, 300);
+
+        // An explicit profile wins over the file's `profile` key
+        let ci = AppConfig::parse(content, Some("ci")).unwrap();
+        assert_eq!(ci.websocket.port, 9100);
+        assert!(!ci.auth.required);
+
+        assert!(AppConfig::parse(content, Some("staging")).is_err());
+    }
+
+    #[test]
+    fn test_project_tokens() {
+        let config = AppConfig::from_toml_str(
+            r#"
+            [auth]
+            required = true
+
+            [[auth.project_tokens]]
+            token = "ide"
+            projects = ["p1", "p2"]
+            read_only = true
+            "#,
+        )
+        .unwrap();
+        assert!(config.auth.has_tokens());
+        assert!(config.auth.tokens.is_empty());
+        assert_eq!(config.auth.project_tokens[0].projects, vec!["p1", "p2"]);
+        assert!(config.auth.project_tokens[0].read_only);
+    }
+
+    #[test]
+    fn test_env_overrides_apply_on_top_of_file() {
+        let config = AppConfig::from_toml_str("[cache]\nmax_size = 200\n").unwrap();
+        let vars = vec![
+            ("CONTEXT_SERVER__CACHE__MAX_SIZE".to_string(), "5000".to_string()),
+            ("CONTEXT_SERVER__WEBSOCKET__ENABLED".to_string(), "true".to_string()),
+            ("CONTEXT_SERVER__EMBEDDING__MODEL".to_string(), "1234".to_string()),
+            ("CONTEXT_SERVER__DATABASE__PATH".to_string(), "/data/ctx.db".to_string()),
+            ("CONTEXT_SERVER__SPECS__ROOTS".to_string(), r#"["a", "b"]"#.to_string()),
+            ("UNRELATED".to_string(), "1".to_string()),
+        ];
+
+        let config = config.apply_env_overrides(vars).unwrap();
+        assert_eq!(config.cache.max_size, 5000);
+        assert!(config.websocket.enabled);
+        assert_eq!(config.embedding.model, "1234");
+        assert_eq!(config.database.path, Some(PathBuf::from("/data/ctx.db")));
+        assert_eq!(config.specs.roots, vec![PathBuf::from("a"), PathBuf::from("b")]);
+    }
+
+    #[test]
+    fn test_env_override_with_wrong_type_is_an_error() {
+        let vars = vec![("CONTEXT_SERVER__CACHE__MAX_SIZE".to_string(), "lots".to_string())];
+        assert!(AppConfig::default().apply_env_overrides(vars).is_err());
+    }
+
+    #[test]
+    fn test_config_dir_override_holds_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let dirs = AppDirs::resolve(Some(dir.path())).unwrap();
+        assert_eq!(dirs.config_file(), dir.path().join(CONFIG_FILE_NAME));
+        assert_eq!(dirs.default_db_path(), dir.path().join(DB_FILE_NAME));
+    }
+
+    #[test]
+    fn test_migrate_legacy_db() {
+        let dir = tempfile::tempdir().unwrap();
+        let legacy = dir.path().join("old").join(DB_FILE_NAME);
+        let target = dir.path().join("new").join(DB_FILE_NAME);
+        fs::create_dir_all(legacy.parent().unwrap()).unwrap();
+
+        // A process that died with rows only in the write-ahead log
+        let conn = rusqlite::Connection::open(&legacy).unwrap();
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL; PRAGMA wal_autocheckpoint = 0;
+             CREATE TABLE projects (name TEXT); INSERT INTO projects VALUES ('kept');",
+        )
+        .unwrap();
+        std::mem::forget(conn);
+
+        let migrated = migrate_legacy_db(&target, std::slice::from_ref(&legacy)).unwrap();
+        assert_eq!(migrated, Some(legacy.clone()));
+        assert!(!legacy.exists());
+        assert!(!with_suffix(&legacy, "-wal").exists());
+        let name: String = rusqlite::Connection::open(&target)
+            .unwrap()
+            .query_row("SELECT name FROM projects", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(name, "kept");
+
+        // A second run finds the target in place and does nothing
+        assert_eq!(migrate_legacy_db(&target, &[legacy]).unwrap(), None);
+    }
+}
@@ -3,33 +3,96 @@ use anyhow::Result;
 use rusqlite::Connection;
 use std::sync::{Arc, Mutex};
 
+use crate::cache::QueryCache;
+use crate::config::{AppConfig, ConfigManager};
+use crate::models::plugin::ContextPlugin;
+
 // Infrastructure layer
 use crate::infrastructure::{
+    FileBlobStore,
     SqliteAnalyticsRepository,
+    SqliteApiContractRepository,
+    SqliteApprovalWorkflowRepository,
+    SqliteArchitectureRuleSetRepository,
     SqliteArchitecturalDecisionRepository,
+    SqliteAttachmentRepository,
+    SqliteBlobStore,
     SqliteBusinessRuleRepository,
+    SqliteChangeLogRepository,
+    SqliteConflictRepository,
+    SqliteContextDigestRepository,
+    DependencyRepository,
+    SqliteDependencyRepository,
+    SqliteCustomEntityRepository,
+    SqliteDecisionLinkRepository,
     SqliteDevelopmentPhaseRepository,
     SqliteEnhancedContextRepository,
+    SqliteFeatureContextRepository,
+    SqliteEntityReviewRepository,
+    SqliteEntityVerificationRepository,
+    SqliteFitnessMeasurementRepository,
     SqliteFrameworkRepository,
+    SqliteGlossaryRepository,
     // Note: SqliteComponentRepository removed as it was identical to SqliteFrameworkRepository
     SqlitePerformanceRequirementRepository,
+    SqliteProjectConventionRepository,
     SqliteProjectRepository,
+    SqliteQualityRubricRepository,
+    SqliteSavedSearchRepository,
+    SqliteScheduledReportRepository,
+    SqliteSecurityPolicyRepository,
+    SqliteSharedContextLinkRepository,
     SqliteSpecificationRepository,
+    SqliteTagRepository,
+    SqliteToolCallAuditRepository,
+    SqliteTraceabilityReportRepository,
+};
+use crate::repositories::embedding_repository::{EmbeddingRepository, SqliteEmbeddingRepository};
+use crate::repositories::{
+    ApiContractRepository, ArchitectureRuleSetRepository, BlobStore, CustomEntityRepository, DecisionLinkRepository, EntityReviewRepository,
+    EntityVerificationRepository, FeatureContextRepository, ProjectConventionRepository, QualityRubricRepository,
+    SecurityPolicyRepository, SharedContextLinkRepository, TagRepository,
 };
 
 // Service layer
 use crate::services::{
+    analytics_policy::AnalyticsPolicy,
     analytics_service::{AnalyticsService, DefaultAnalyticsService},
     architecture_validation_service::ArchitectureValidationServiceImpl,
+    attachment_service::{AttachmentService, AttachmentServiceImpl},
+    change_broadcaster::ChangeBroadcaster,
+    change_detection_service::ChangeDetectionService,
+    codebase_scanner::{CodebaseScanService, DefaultCodebaseScanService},
+    manifest_importer::{DefaultManifestImportService, ManifestImportService},
+    conflict_resolution_engine::{ConflictResolutionConfig, ConflictResolutionEngine},
+    conflict_resolution_ui::ConflictResolutionUI,
     context_crud_service::{ContextCrudService, ContextCrudServiceImpl},
     context_query_service::ContextQueryServiceImpl,
     development_phase_service::DevelopmentPhaseServiceImpl,
+    embedding_migration::{EmbeddingMigrator, SwappableEmbeddingService},
+    embedding_queue::EmbeddingQueue,
+    embedding_service::{EmbeddingService, EmbeddingServiceFactory},
     framework_service::FrameworkServiceImpl,
+    glossary_service::{GlossaryService, GlossaryServiceImpl},
+    hybrid_search_service::{HybridSearchConfig, HybridSearchService, HybridSearchServiceImpl},
     // Note: ComponentService removed as it was identical to FrameworkService
     project_service::ProjectServiceImpl,
+    reranker::RerankerFactory,
+    saved_search_service::{SavedSearchService, SavedSearchServiceImpl},
+    search_feedback::SearchClickTracker,
+    search_index_manager::{IndexManagerConfig, SearchIndexManager, SearchIndexManagerImpl},
+    semantic_search_service::{SemanticSearchConfig, SemanticSearchService, SemanticSearchServiceImpl},
     specification_analytics_service::{SpecificationAnalyticsService, DefaultSpecificationAnalyticsService},
+    vector_embedding_integration::ContentChunker,
+    websocket_auth::WebSocketAuth,
+    websocket_manager::WebSocketManager,
+    websocket_server::WebSocketConfig,
     ArchitectureValidationService,
+    ContextIntelligenceService,
+    ContextQualityService,
     ContextQueryService,
+    DefaultContextIntelligenceService,
+    DefaultContextQualityService,
     DevelopmentPhaseService,
     FrameworkService,
     ProjectService,
@@ -37,12 +100,25 @@ use crate::services::{
     SpecificationService,
     DefaultSpecificationImportService,
     SpecificationImportService,
+    DefaultAdrImportService,
+    AdrImportService,
+    DefaultOpenApiImportService,
+    OpenApiImportService,
+    DefaultTraceabilityService,
+    TraceabilityService,
+    DefaultReportSchedulerService,
+    ReportSchedulerService,
+    DefaultToolAuditService,
+    ToolAuditService,
+    DefaultApprovalWorkflowService,
+    ApprovalWorkflowService,
     SqliteSpecificationVersioningService,
     SpecificationVersioningService,
     DefaultSpecificationContextLinkingService,
     SpecificationContextLinkingService,
     PluginService,
     DefaultPluginService,
+    plugins::{ArchitectureValidatorRegistry, DependencyGuardPlugin},
 };
 
 /// Application container holding all dependencies
@@ -52,23 +128,116 @@ pub struct AppContainer {
     #[allow(dead_code)]
     pub development_phase_service: Box<dyn DevelopmentPhaseService>,
     pub context_query_service: Box<dyn ContextQueryService>,
+    /// Feature area digests written by a language model per `[digests]`
+    pub context_intelligence_service: Arc<dyn ContextIntelligenceService>,
+    /// Quality scores of context items, measured against each project's rubric
+    pub context_quality_service: Arc<dyn ContextQualityService>,
+    /// Quality rubrics projects define in place of `[quality]`
+    pub quality_rubric_repository: Arc<dyn QualityRubricRepository>,
     pub architecture_validation_service: Box<dyn ArchitectureValidationService>,
+    /// Architecture rules projects define in place of the Clean Architecture defaults
+    pub architecture_rule_set_repository: Arc<dyn ArchitectureRuleSetRepository>,
+    /// Creates and updates framework components from a repository's source files
+    pub codebase_scan_service: Arc<dyn CodebaseScanService>,
+    /// External dependencies recorded between components and packages, including those
+    /// imported from package manifests
+    pub dependency_repository: Arc<dyn DependencyRepository>,
+    /// Records the external dependencies declared in Cargo.toml, pubspec.yaml and package.json
+    pub manifest_import_service: Arc<dyn ManifestImportService>,
+    /// Architecture validators contributed by plugins, run alongside the built-in rules
+    pub architecture_validator_registry: Arc<ArchitectureValidatorRegistry>,
     pub context_crud_service: Box<dyn ContextCrudService>,
     pub framework_service: Box<dyn FrameworkService>,
-    pub analytics_service: Box<dyn AnalyticsService>,
+    pub analytics_service: Arc<dyn AnalyticsService>,
+    /// What usage tracking may record; shared by the analytics services and the server
+    pub analytics_policy: Arc<AnalyticsPolicy>,
     pub specification_service: Arc<dyn SpecificationService>,
     pub specification_import_service: Arc<dyn SpecificationImportService>,
+    pub adr_import_service: Arc<dyn AdrImportService>,
+    pub openapi_import_service: Arc<dyn OpenApiImportService>,
+    pub feature_context_repository: Arc<dyn FeatureContextRepository>,
+    pub project_convention_repository: Arc<dyn ProjectConventionRepository>,
+    pub security_policy_repository: Arc<dyn SecurityPolicyRepository>,
     pub specification_versioning_service: Arc<dyn SpecificationVersioningService>,
     pub specification_context_linking_service: Arc<dyn SpecificationContextLinkingService>,
     pub specification_analytics_service: Arc<dyn SpecificationAnalyticsService>,
+    /// Requirement to code matrices saved as traceability reports
+    pub traceability_service: Arc<dyn TraceabilityService>,
+    /// Quality and specification health reports generated for every project on `[reports]` schedule
+    pub report_scheduler: Arc<dyn ReportSchedulerService>,
+    /// Every tool call, with caller and outcome, kept on `[audit]` retention
+    pub tool_audit_service: Arc<dyn ToolAuditService>,
+    /// Review states and approvals of specifications and architectural decisions
+    pub approval_workflow_service: Arc<dyn ApprovalWorkflowService>,
     pub plugin_service: Arc<dyn PluginService>,
     // Note: component_service removed as it was identical to framework_service
+    /// Effective configuration, updated when config.toml is reloaded
+    pub config_manager: Arc<ConfigManager>,
+    /// Shared cache for entity and context query results
+    pub query_cache: Arc<QueryCache>,
+    /// Embedding backend selected by `[embedding] provider`, until `migrate_embeddings` switches it
+    pub embedding_service: Arc<dyn EmbeddingService>,
+    pub embedding_repository: Arc<dyn EmbeddingRepository>,
+    /// Background indexer fed by entity writes
+    pub embedding_queue: Arc<EmbeddingQueue>,
+    /// Moves the index to another embedding model in the background
+    pub embedding_migrator: Arc<EmbeddingMigrator>,
+    /// Entity writes are reported here; it broadcasts them and updates the search index
+    pub change_detection_service: ChangeDetectionService,
+    /// WebSocket clients and their presence; connections arrive only while `serve --ws` runs
+    pub websocket_manager: Arc<WebSocketManager>,
+    /// Conflicts between concurrent changes and the sessions resolving them, both kept
+    /// in SQLite
+    pub conflict_resolution_engine: Arc<tokio::sync::Mutex<ConflictResolutionEngine>>,
+    pub conflict_resolution_ui: Arc<tokio::sync::Mutex<ConflictResolutionUI>>,
+    /// Vector search over stored embeddings
+    pub semantic_search_service: Arc<dyn SemanticSearchService>,
+    /// Index statistics and drift for `search_index_status`
+    pub search_index_manager: Arc<dyn SearchIndexManager>,
+    /// Per-project synonyms that expand search queries
+    pub glossary_service: Arc<dyn GlossaryService>,
+    /// Keyword + semantic search ranked per `[search]`
+    pub hybrid_search_service: Arc<dyn HybridSearchService>,
+    /// Named searches re-run through `run_saved_search`
+    pub saved_search_service: Box<dyn SavedSearchService>,
+    /// Recent search results, to log `get_entity` calls on them as clicks
+    pub search_click_tracker: SearchClickTracker,
+    /// Tags such as `stale` attached to entities of a project
+    pub tag_repository: Arc<dyn TagRepository>,
+    /// When entities were last verified, which their freshness decays from
+    pub entity_verification_repository: Arc<dyn EntityVerificationRepository>,
+    /// Review requests and the verdicts giving entities their verification status
+    pub entity_review_repository: Arc<dyn EntityReviewRepository>,
+    /// Which entities of the shared organization project were promoted from which projects
+    pub shared_context_link_repository: Arc<dyn SharedContextLinkRepository>,
+    /// Team-defined entity types and the entities stored under them
+    pub custom_entity_repository: Arc<dyn CustomEntityRepository>,
+    /// Diagrams, sequence charts and sample payloads attached to decisions and feature contexts
+    pub attachment_service: Arc<dyn AttachmentService>,
+    /// Which decisions supersede, amend or relate to which
+    pub decision_link_repository: Arc<dyn DecisionLinkRepository>,
+    /// Endpoint request/response shapes linked to feature contexts and components
+    pub api_contract_repository: Arc<dyn ApiContractRepository>,
 }
 
 impl AppContainer {
     /// Create a new application container with all dependencies injected
     pub fn new(db_path: &str) -> Result<Self> {
-        let conn = Connection::open(db_path)?;
+        let mut config = AppConfig::default();
+        config.database.path = Some(db_path.into());
+        Self::from_config(config)
+    }
+
+    /// Create an application container from a loaded configuration
+    pub fn from_config(config: AppConfig) -> Result<Self> {
+        Self::with_config_manager(Arc::new(ConfigManager::new(config, None)))
+    }
+
+    /// Create an application container sharing a (possibly hot-reloading) configuration
+    pub fn with_config_manager(config_manager: Arc<ConfigManager>) -> Result<Self> {
+        let config = config_manager.current();
+        let db_path = config.resolve_db_path(None)?;
+        let conn = Connection::open(&db_path)?;
         let db = Arc::new(Mutex::new(conn));
 
         // Create repositories (infrastructure layer)
@@ -87,27 +256,38 @@ impl AppContainer {
             development_phase_repository,
         ));
 
-        let context_query_service = Box::new(ContextQueryServiceImpl::new(
-            business_rule_repository,
-            architectural_decision_repository,
-            performance_requirement_repository,
-        ));
-
         // Create framework service for architecture validation
         // Note: In a real application, you might want to use Arc<dyn FrameworkService> instead
         let framework_repository_for_validation = SqliteFrameworkRepository::new(db.clone());
         let framework_service_for_validation =
             FrameworkServiceImpl::new(framework_repository_for_validation);
-        let architecture_validation_service = Box::new(ArchitectureValidationServiceImpl::new(
-            framework_service_for_validation,
+        let architecture_rule_set_repository: Arc<dyn ArchitectureRuleSetRepository> =
+            Arc::new(SqliteArchitectureRuleSetRepository::new(db.clone()));
+        let codebase_scan_service: Arc<dyn CodebaseScanService> = Arc::new(DefaultCodebaseScanService::new(
+            Arc::new(FrameworkServiceImpl::new(SqliteFrameworkRepository::new(db.clone()))),
+            architecture_rule_set_repository.clone(),
         ));
+        let dependency_repository = SqliteDependencyRepository::new(db.clone());
+        dependency_repository.init_table()?;
+        let dependency_repository: Arc<dyn DependencyRepository> = Arc::new(dependency_repository);
+        let manifest_import_service: Arc<dyn ManifestImportService> =
+            Arc::new(DefaultManifestImportService::new(dependency_repository.clone()));
+
+        let security_policy_repository: Arc<dyn SecurityPolicyRepository> =
+            Arc::new(SqliteSecurityPolicyRepository::new(db.clone()));
+        let feature_context_repository: Arc<dyn FeatureContextRepository> =
+            Arc::new(SqliteFeatureContextRepository::new(db.clone()));
 
         // Create CRUD services with their repositories
-        let context_crud_service = Box::new(ContextCrudServiceImpl::new(
-            SqliteBusinessRuleRepository::new(db.clone()),
-            SqliteArchitecturalDecisionRepository::new(db.clone()),
-            SqlitePerformanceRequirementRepository::new(db.clone()),
-        ));
+        let context_crud_service = Box::new(
+            ContextCrudServiceImpl::new(
+                SqliteBusinessRuleRepository::new(db.clone()),
+                SqliteArchitecturalDecisionRepository::new(db.clone()),
+                SqlitePerformanceRequirementRepository::new(db.clone()),
+            )
+            .with_security_policies(security_policy_repository.clone())
+            .with_feature_contexts(feature_context_repository.clone()),
+        );
 
         // Create framework service
         let framework_repository = SqliteFrameworkRepository::new(db.clone());
@@ -117,72 +297,389 @@ impl AppContainer {
         let analytics_repository = SqliteAnalyticsRepository::new(db.clone());
         // Initialize analytics tables
         analytics_repository.init_tables()?;
-        let analytics_service = Box::new(DefaultAnalyticsService::new(Box::new(analytics_repository)));
+        let analytics_policy = Arc::new(AnalyticsPolicy::with_config(config_manager.clone()));
+        let analytics_service: Arc<dyn AnalyticsService> = Arc::new(DefaultAnalyticsService::with_policy(
+            Box::new(analytics_repository),
+            analytics_policy.clone(),
+        ));
+        DefaultAnalyticsService::spawn_maintenance(analytics_service.clone(), config_manager.clone());
 
         // Create specification services
         let specification_repository = Arc::new(SqliteSpecificationRepository::new(db.clone()));
         specification_repository.initialize_tables()?;
         
         let specification_service = Arc::new(DefaultSpecificationService::new(specification_repository.clone()));
+
+        let project_convention_repository: Arc<dyn ProjectConventionRepository> =
+            Arc::new(SqliteProjectConventionRepository::new(db.clone()));
+
+        // Validators plugins contribute, starting with the built-in dependency guard
+        let architecture_validator_registry = Arc::new(ArchitectureValidatorRegistry::new());
+        architecture_validator_registry.register(
+            "dependency_guard",
+            DependencyGuardPlugin::new().architecture_validators(),
+        );
+
+        let change_log = Arc::new(SqliteChangeLogRepository::new(db.clone()));
+        let architecture_validation_service = Box::new(
+            ArchitectureValidationServiceImpl::new(framework_service_for_validation)
+                .with_change_log(change_log.clone())
+                .with_validators(architecture_validator_registry.clone())
+                .with_conventions(project_convention_repository.clone())
+                .with_external_dependencies(dependency_repository.clone())
+                .with_fitness_history(Arc::new(SqliteFitnessMeasurementRepository::new(db.clone())))
+                .with_rule_sets(architecture_rule_set_repository.clone())
+                .with_development_phases(Arc::new(DevelopmentPhaseServiceImpl::new(
+                    SqliteDevelopmentPhaseRepository::new(db.clone()),
+                )))
+                .with_specifications(specification_service.clone())
+                .with_baseline_file(config.architecture.baseline_file.clone()),
+        );
         
-        let specification_import_service = Arc::new(DefaultSpecificationImportService::new(
-            specification_service.clone(),
-            specification_repository.clone(),
-        ));
-        
-        let specification_versioning_service = Arc::new(SqliteSpecificationVersioningService::new(db.clone()));
+        let specification_versioning_service = Arc::new(
+            SqliteSpecificationVersioningService::new(db.clone()).with_retention(config.specs.versions.clone()),
+        );
         specification_versioning_service.initialize_tables()?;
 
         // Create enhanced context repository and service
         let enhanced_context_repository = Arc::new(SqliteEnhancedContextRepository::new(db.clone()));
         enhanced_context_repository.initialize_tables()?;
         
-        let specification_context_linking_service = Arc::new(DefaultSpecificationContextLinkingService::new(
+        // Create specification analytics service
+        let specification_analytics_service = Arc::new(DefaultSpecificationAnalyticsService::new(
             specification_repository.clone(),
-            enhanced_context_repository,
-            Arc::new(ContextQueryServiceImpl::new(
-                SqliteBusinessRuleRepository::new(db.clone()),
-                SqliteArchitecturalDecisionRepository::new(db.clone()),
-                SqlitePerformanceRequirementRepository::new(db.clone()),
+            Arc::new(DefaultAnalyticsService::with_policy(
+                Box::new(SqliteAnalyticsRepository::new(db.clone())),
+                analytics_policy.clone(),
             )),
+        ).with_version_history(specification_versioning_service.clone()));
+
+        let report_scheduler: Arc<dyn ReportSchedulerService> = Arc::new(DefaultReportSchedulerService::new(
+            Arc::new(SqliteProjectRepository::new(db.clone())),
+            Arc::new(SqliteScheduledReportRepository::new(db.clone())),
+            analytics_service.clone(),
+            specification_analytics_service.clone(),
+            config_manager.clone(),
         ));
+        DefaultReportSchedulerService::spawn(report_scheduler.clone(), config_manager.clone());
 
-        // Create specification analytics service
-        let specification_analytics_service = Arc::new(DefaultSpecificationAnalyticsService::new(
-            specification_repository.clone(),
-            Arc::new(DefaultAnalyticsService::new(Box::new(SqliteAnalyticsRepository::new(db.clone())))),
+        let tool_audit_service: Arc<dyn ToolAuditService> = Arc::new(DefaultToolAuditService::new(
+            Arc::new(SqliteToolCallAuditRepository::new(db.clone())),
+            config_manager.clone(),
         ));
+        DefaultToolAuditService::spawn_pruning(tool_audit_service.clone());
 
         // Create plugin service
-        let plugin_install_dir = std::env::current_dir()?.join("plugins");
-        let plugin_data_dir = std::env::current_dir()?.join("plugin_data");
+        let plugin_install_dir = std::env::current_dir()?.join(&config.plugins.install_dir);
+        let plugin_data_dir = std::env::current_dir()?.join(&config.plugins.data_dir);
         let temp_dir = std::env::temp_dir().join("context_server_plugins");
-        let marketplace_url = std::env::var("PLUGIN_MARKETPLACE_URL").ok();
+        let marketplace_url = config
+            .plugins
+            .marketplace_url
+            .clone()
+            .or_else(|| std::env::var("PLUGIN_MARKETPLACE_URL").ok());
         
-        let plugin_service = Arc::new(DefaultPluginService::new(
-            plugin_install_dir,
-            plugin_data_dir,
-            temp_dir,
-            marketplace_url,
-        ));
+        let plugin_service = Arc::new(
+            DefaultPluginService::new(plugin_install_dir, plugin_data_dir, temp_dir, marketplace_url)
+                .with_validator_registry(architecture_validator_registry.clone()),
+        );
 
         // Note: component_service removed as it was identical to framework_service
 
+        // Create query cache; TTLs and namespace policies follow config reloads
+        let query_cache = Arc::new(QueryCache::from_config(&config.cache));
+        query_cache.spawn_sweeper();
+        let cache_for_reload = Arc::downgrade(&query_cache);
+        config_manager.on_change(move |config| {
+            if let Some(cache) = cache_for_reload.upgrade() {
+                cache.apply_config(&config.cache);
+            }
+        });
+
+        // ADR files are imported as architectural decisions, so monitored imports drop cached ones
+        let approval_workflow_repository = Arc::new(SqliteApprovalWorkflowRepository::new(db.clone()));
+        let adr_import_service: Arc<dyn AdrImportService> = Arc::new(
+            DefaultAdrImportService::new(Arc::new(SqliteArchitecturalDecisionRepository::new(db.clone())))
+                .with_query_cache(query_cache.clone())
+                .with_workflow_repository(approval_workflow_repository.clone()),
+        );
+        let approval_workflow_service: Arc<dyn ApprovalWorkflowService> = Arc::new(
+            DefaultApprovalWorkflowService::new(
+                specification_service.clone(),
+                Arc::new(SqliteArchitecturalDecisionRepository::new(db.clone())),
+                approval_workflow_repository,
+            )
+            .with_approvers(config.specs.required_approvers.clone(), config.specs.min_approvals),
+        );
+
+        let openapi_import_service: Arc<dyn OpenApiImportService> = Arc::new(DefaultOpenApiImportService::new(
+            feature_context_repository.clone(),
+            Arc::new(SqliteFrameworkRepository::new(db.clone())),
+            Arc::new(SqliteBusinessRuleRepository::new(db.clone())),
+        ));
+
+        let traceability_service: Arc<dyn TraceabilityService> = Arc::new(DefaultTraceabilityService::new(
+            specification_service.clone(),
+            Arc::new(SqliteFrameworkRepository::new(db.clone())),
+            Arc::new(SqliteTraceabilityReportRepository::new(db.clone())),
+        ));
+
+        // Create embedding services; the repository locks asynchronously, so it gets its
+        // own connection rather than sharing the std mutex above
+        let embedding_config = EmbeddingServiceFactory::config_from_settings(&config.embedding)?;
+        let active_embedding_service = Arc::new(SwappableEmbeddingService::new(Arc::from(
+            EmbeddingServiceFactory::create_service(embedding_config.clone())?,
+        )));
+        let embedding_service: Arc<dyn EmbeddingService> = active_embedding_service.clone();
+        let embedding_conn = Connection::open(&db_path)?;
+        SqliteEmbeddingRepository::create_tables(&embedding_conn)?;
+        let embedding_repository: Arc<dyn EmbeddingRepository> = Arc::new(
+            SqliteEmbeddingRepository::new(Arc::new(tokio::sync::Mutex::new(embedding_conn))),
+        );
+        let embedding_queue = Arc::new(
+            EmbeddingQueue::new(embedding_service.clone(), embedding_repository.clone())
+                .with_chunker(ContentChunker::from_settings(&config.embedding)),
+        );
+        embedding_queue.spawn_worker();
+        let embedding_migrator = Arc::new(EmbeddingMigrator::new(
+            active_embedding_service,
+            embedding_queue.clone(),
+            embedding_repository.clone(),
+        ));
+        // query_context ranks by similarity to the stored embeddings and by freshness
+        let entity_verification_repository: Arc<dyn EntityVerificationRepository> =
+            Arc::new(SqliteEntityVerificationRepository::new(db.clone()));
+        let context_query_service = Box::new(
+            ContextQueryServiceImpl::new(
+                business_rule_repository,
+                architectural_decision_repository,
+                performance_requirement_repository,
+            )
+            .with_embeddings(embedding_service.clone(), embedding_repository.clone())
+            .with_freshness(entity_verification_repository.clone(), config_manager.clone())
+            .with_security_policies(security_policy_repository.clone())
+            .with_conventions(project_convention_repository.clone())
+            .with_feature_contexts(feature_context_repository.clone()),
+        );
+        let quality_rubric_repository: Arc<dyn QualityRubricRepository> =
+            Arc::new(SqliteQualityRubricRepository::new(db.clone()));
+        let rubric_quality_service = || {
+            DefaultContextQualityService::new().with_rubrics(
+                quality_rubric_repository.clone(),
+                specification_repository.clone(),
+                config_manager.clone(),
+            )
+        };
+        let context_quality_service: Arc<dyn ContextQualityService> = Arc::new(rubric_quality_service());
+        let tag_repository: Arc<dyn TagRepository> = Arc::new(SqliteTagRepository::new(db.clone()));
+        let context_intelligence_service: Arc<dyn ContextIntelligenceService> = Arc::new(
+            DefaultContextIntelligenceService::new()
+                .with_quality_service(Box::new(rubric_quality_service()))
+                .with_digests(
+                    Arc::new(SqliteContextDigestRepository::new(db.clone())),
+                    Arc::new(
+                        ContextQueryServiceImpl::new(
+                            SqliteBusinessRuleRepository::new(db.clone()),
+                            SqliteArchitecturalDecisionRepository::new(db.clone()),
+                            SqlitePerformanceRequirementRepository::new(db.clone()),
+                        )
+                        .with_embeddings(embedding_service.clone(), embedding_repository.clone())
+                        .with_freshness(entity_verification_repository.clone(), config_manager.clone())
+                        .with_security_policies(security_policy_repository.clone())
+                        .with_conventions(project_convention_repository.clone())
+                        .with_feature_contexts(feature_context_repository.clone()),
+                    ),
+                    feature_context_repository.clone(),
+                    config_manager.clone(),
+                )
+                .with_tagging(
+                    tag_repository.clone(),
+                    feature_context_repository.clone(),
+                    Some(embedding_service.clone()),
+                    config_manager.clone(),
+                ),
+        );
+        // Changes are logged for replay only when WebSocket clients can connect
+        let mut change_broadcaster = ChangeBroadcaster::new()
+            .with_max_queue_depth(config.websocket.max_queue_depth)
+            .with_batching(
+                std::time::Duration::from_millis(config.websocket.batch_window_ms),
+                config.websocket.max_batch_size,
+            );
+        if config.websocket.enabled && config.websocket.replay_retention_hours > 0 {
+            change_broadcaster = change_broadcaster.with_change_log(change_log.clone());
+            change_broadcaster.spawn_log_pruning(chrono::Duration::hours(config.websocket.replay_retention_hours as i64));
+        }
+        let change_broadcaster = Arc::new(change_broadcaster);
+        let change_detection_service = ChangeDetectionService::new(change_broadcaster.clone())
+            .with_index_queue(embedding_queue.clone());
+        let conflict_repository = Arc::new(SqliteConflictRepository::new(db.clone()));
+        let conflict_config = ConflictResolutionConfig::from_settings(&config.conflicts);
+        let conflict_resolution_engine = Arc::new(tokio::sync::Mutex::new(
+            ConflictResolutionEngine::with_config(conflict_config.clone())
+                .with_repository(conflict_repository.clone())
+                .with_change_log(change_log),
+        ));
+        let websocket_manager = Arc::new(
+            WebSocketManager::with_broadcaster(change_broadcaster)
+                .with_auth(WebSocketAuth::from_config(&config.auth))
+                .with_config(WebSocketConfig::from_settings(&config.websocket))
+                .with_conflict_resolver(conflict_resolution_engine.clone()),
+        );
+        let conflict_resolution_ui = Arc::new(tokio::sync::Mutex::new(
+            ConflictResolutionUI::new()
+                .with_repository(conflict_repository)
+                .with_config(conflict_config),
+        ));
+        ConflictResolutionUI::spawn_session_cleanup(conflict_resolution_ui.clone(), std::time::Duration::from_secs(60));
+
+        // Create hybrid search; ranking follows `[search]` reloads
+        let semantic_search_service: Arc<dyn SemanticSearchService> = Arc::new(SemanticSearchServiceImpl::new(
+            embedding_service.clone(),
+            embedding_repository.clone(),
+            SemanticSearchConfig {
+                embedding_config,
+                ..Default::default()
+            },
+        ));
+        let search_index_manager: Arc<dyn SearchIndexManager> = Arc::new(SearchIndexManagerImpl::new(
+            semantic_search_service.clone(),
+            embedding_repository.clone(),
+            embedding_service.clone(),
+            IndexManagerConfig::default(),
+        ));
+        let glossary_service: Arc<dyn GlossaryService> =
+            Arc::new(GlossaryServiceImpl::new(SqliteGlossaryRepository::new(db.clone())));
+        let hybrid_search_service = Arc::new(
+            HybridSearchServiceImpl::new(
+                semantic_search_service.clone(),
+                Arc::new(ContextQueryServiceImpl::new(
+                    SqliteBusinessRuleRepository::new(db.clone()),
+                    SqliteArchitecturalDecisionRepository::new(db.clone()),
+                    SqlitePerformanceRequirementRepository::new(db.clone()),
+                )),
+                HybridSearchConfig::from_settings(&config.search)?,
+            )
+            .with_click_history(analytics_service.clone())
+            .with_glossary(glossary_service.clone()),
+        );
+        hybrid_search_service.set_reranker(RerankerFactory::from_settings(&config.search.rerank)?);
+        let search_for_reload = Arc::downgrade(&hybrid_search_service);
+        config_manager.on_change(move |config| {
+            let Some(search) = search_for_reload.upgrade() else {
+                return;
+            };
+            match HybridSearchConfig::from_settings(&config.search) {
+                Ok(search_config) => search.set_config(search_config),
+                Err(e) => tracing::warn!("Keeping previous search ranking: {}", e),
+            }
+            match RerankerFactory::from_settings(&config.search.rerank) {
+                Ok(reranker) => search.set_reranker(reranker),
+                Err(e) => tracing::warn!("Keeping previous reranker: {}", e),
+            }
+        });
+
+        // Imported specifications get links to existing context suggested from hybrid search
+        let specification_context_linking_service = Arc::new(
+            DefaultSpecificationContextLinkingService::new(
+                specification_repository.clone(),
+                enhanced_context_repository,
+                Arc::new(ContextQueryServiceImpl::new(
+                    SqliteBusinessRuleRepository::new(db.clone()),
+                    SqliteArchitecturalDecisionRepository::new(db.clone()),
+                    SqlitePerformanceRequirementRepository::new(db.clone()),
+                )),
+            )
+            .with_hybrid_search(hybrid_search_service.clone()),
+        );
+        let specification_import_service = Arc::new(
+            DefaultSpecificationImportService::new(specification_service.clone(), specification_repository.clone())
+                .with_conflict_engine(conflict_resolution_engine.clone())
+                .with_debounce(std::time::Duration::from_millis(config.specs.debounce_ms))
+                .with_link_suggestions(specification_context_linking_service.clone())
+                .with_version_history(specification_versioning_service.clone()),
+        );
+
+        let saved_search_service = Box::new(SavedSearchServiceImpl::new(
+            SqliteSavedSearchRepository::new(db.clone()),
+            hybrid_search_service.clone(),
+        ));
+
+        let attachment_blobs: Arc<dyn BlobStore> = match config.attachments.storage.as_str() {
+            "sqlite" => Arc::new(SqliteBlobStore::new(db.clone())),
+            "filesystem" => {
+                let directory = config
+                    .attachments
+                    .directory
+                    .clone()
+                    .ok_or_else(|| anyhow::anyhow!("[attachments] storage = \"filesystem\" needs a directory"))?;
+                Arc::new(FileBlobStore::new(directory))
+            }
+            other => anyhow::bail!("Unknown [attachments] storage \"{other}\"; expected sqlite or filesystem"),
+        };
+        let attachment_service: Arc<dyn AttachmentService> = Arc::new(AttachmentServiceImpl::new(
+            Arc::new(SqliteAttachmentRepository::new(db.clone())),
+            attachment_blobs,
+            config_manager.clone(),
+        ));
+
         Ok(AppContainer {
             project_service,
             development_phase_service,
             context_query_service,
+            context_intelligence_service,
+            context_quality_service,
+            quality_rubric_repository,
             architecture_validation_service,
+            architecture_rule_set_repository,
+            codebase_scan_service,
+            dependency_repository,
+            manifest_import_service,
+            architecture_validator_registry,
             context_crud_service,
             framework_service,
             analytics_service,
+            analytics_policy,
             specification_service,
             specification_import_service,
+            adr_import_service,
+            openapi_import_service,
+            feature_context_repository,
+            project_convention_repository,
+            security_policy_repository,
             specification_versioning_service,
             specification_context_linking_service,
             specification_analytics_service,
+            traceability_service,
+            report_scheduler,
+            tool_audit_service,
+            approval_workflow_service,
             plugin_service,
             // Note: component_service removed
+            config_manager,
+            query_cache,
+            embedding_service,
+            embedding_repository,
+            embedding_queue,
+            embedding_migrator,
+            change_detection_service,
+            websocket_manager,
+            conflict_resolution_engine,
+            conflict_resolution_ui,
+            semantic_search_service,
+            search_index_manager,
+            glossary_service,
+            hybrid_search_service,
+            saved_search_service,
+            search_click_tracker: SearchClickTracker::new(),
+            tag_repository,
+            entity_verification_repository,
+            entity_review_repository: Arc::new(SqliteEntityReviewRepository::new(db.clone())),
+            shared_context_link_repository: Arc::new(SqliteSharedContextLinkRepository::new(db.clone())),
+            custom_entity_repository: Arc::new(SqliteCustomEntityRepository::new(db.clone())),
+            attachment_service,
+            decision_link_repository: Arc::new(SqliteDecisionLinkRepository::new(db.clone())),
+            api_contract_repository: Arc::new(SqliteApiContractRepository::new(db.clone())),
         })
     }
 }
@@ -451,7 +451,7 @@ impl ContextMcpServer {
                 name: "context-server-rs".to_string(),
                 version: "0.1.0".to_string(),
                 description: "Flutter-specific MCP Context Server for AI-assisted development".to_string(),
-                config_directory: "~/config/context-server-rs/".to_string(),
+                config_directory: "~/.config/context-server-rs/".to_string(),
             },
             features: vec![
                 FeatureInfo {
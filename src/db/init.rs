@@ -64,6 +64,8 @@ pub fn init_db(db_path: &str) -> Result<Connection> {
             implementation_pattern TEXT,
             forbidden_patterns TEXT,
             compliance_notes TEXT,
+            severity TEXT,
+            classification TEXT,
             created_at TEXT DEFAULT (datetime('now')),
             FOREIGN KEY (project_id) REFERENCES projects(id)
         );
@@ -75,6 +77,8 @@ pub fn init_db(db_path: &str) -> Result<Connection> {
             good_examples TEXT,
             bad_examples TEXT,
             rationale TEXT,
+            pattern TEXT,
+            enforcement_level TEXT,
             created_at TEXT DEFAULT (datetime('now')),
             FOREIGN KEY (project_id) REFERENCES projects(id)
         );
@@ -87,6 +91,10 @@ pub fn init_db(db_path: &str) -> Result<Connection> {
             key_workflows TEXT,
             integration_points TEXT,
             edge_cases TEXT,
+            constraints TEXT,
+            related_components TEXT,
+            status TEXT,
+            owner TEXT,
             created_at TEXT DEFAULT (datetime('now')),
             FOREIGN KEY (project_id) REFERENCES projects(id)
         );
@@ -227,6 +235,7 @@ pub fn init_db(db_path: &str) -> Result<Connection> {
             custom_metadata TEXT, -- JSON object
             created_at TEXT NOT NULL,
             updated_at TEXT DEFAULT (datetime('now')),
+            embedding_dimension INTEGER,
             FOREIGN KEY (project_id) REFERENCES projects(id),
             UNIQUE(context_id, embedding_model, embedding_version)
         );
@@ -237,6 +246,308 @@ pub fn init_db(db_path: &str) -> Result<Connection> {
         CREATE INDEX IF NOT EXISTS idx_embeddings_content_hash ON context_embeddings(content_hash);
         CREATE INDEX IF NOT EXISTS idx_embeddings_created_at ON context_embeddings(created_at);
         
+        -- Named searches re-run through run_saved_search
+        CREATE TABLE IF NOT EXISTS saved_searches (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            query TEXT NOT NULL,
+            feature_area TEXT,
+            filters TEXT, -- JSON object of facet -> values
+            result_limit INTEGER,
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id),
+            UNIQUE(project_id, name)
+        );
+
+        -- Requirement to code matrices saved by generate_traceability_matrix
+        CREATE TABLE IF NOT EXISTS traceability_reports (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            spec_project TEXT NOT NULL,
+            report TEXT NOT NULL, -- JSON rows, untraced tasks and summary
+            created_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_traceability_reports_project ON traceability_reports(project_id, created_at);
+
+        -- Quality and specification health reports generated by the report scheduler
+        CREATE TABLE IF NOT EXISTS scheduled_reports (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            period_start TEXT NOT NULL,
+            period_end TEXT NOT NULL,
+            report TEXT NOT NULL, -- JSON, as the report tools return it
+            delivered_to TEXT NOT NULL DEFAULT '[]', -- JSON webhook targets that accepted it
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_scheduled_reports_project ON scheduled_reports(project_id, kind, created_at);
+
+        -- Summaries of a feature area's context written by a language model (generate_context_digest)
+        CREATE TABLE IF NOT EXISTS context_digests (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            feature_area TEXT NOT NULL COLLATE NOCASE,
+            summary TEXT NOT NULL,
+            generated_by TEXT NOT NULL, -- sampling:<model> or llm:<model>
+            entity_ids TEXT NOT NULL DEFAULT '[]', -- JSON ids of the summarized entities
+            source_hash TEXT NOT NULL,
+            stale INTEGER NOT NULL DEFAULT 0,
+            generated_at TEXT NOT NULL,
+            UNIQUE(project_id, feature_area),
+            FOREIGN KEY (project_id) REFERENCES projects(id)
+        );
+
+        -- Every call_tool invocation, for accountability; separate from usage analytics
+        CREATE TABLE IF NOT EXISTS tool_call_audit (
+            id TEXT PRIMARY KEY,
+            tool TEXT NOT NULL,
+            caller TEXT, -- MCP client name/version
+            project_id TEXT,
+            arguments_hash TEXT NOT NULL, -- MD5 of the arguments as JSON
+            outcome TEXT NOT NULL, -- success or error
+            error_code INTEGER,
+            error_message TEXT,
+            duration_ms INTEGER NOT NULL,
+            created_at TEXT NOT NULL -- RFC 3339 UTC with microseconds
+        );
+        CREATE INDEX IF NOT EXISTS idx_tool_call_audit_created ON tool_call_audit(created_at);
+        CREATE INDEX IF NOT EXISTS idx_tool_call_audit_caller ON tool_call_audit(caller, created_at);
+        CREATE INDEX IF NOT EXISTS idx_tool_call_audit_project ON tool_call_audit(project_id, created_at);
+
+        -- Review state of specifications and architectural decisions (transition_workflow)
+        CREATE TABLE IF NOT EXISTS approval_workflows (
+            entity_type TEXT NOT NULL,
+            entity_id TEXT NOT NULL,
+            project_id TEXT NOT NULL,
+            state TEXT NOT NULL,
+            workflow TEXT NOT NULL, -- JSON required approvers, approvals and history
+            updated_at TEXT DEFAULT (datetime('now')),
+            PRIMARY KEY (entity_type, entity_id)
+        );
+
+        -- Per-project architecture rules checked by validate_architecture (manage_architecture_rules)
+        CREATE TABLE IF NOT EXISTS architecture_rule_sets (
+            project_id TEXT PRIMARY KEY,
+            rules TEXT NOT NULL, -- JSON layers, forbidden imports and naming patterns
+            updated_at TEXT DEFAULT (datetime('now'))
+        );
+
+        -- Quality rubrics projects score their context against (manage_quality_rubric)
+        CREATE TABLE IF NOT EXISTS quality_rubrics (
+            project_id TEXT PRIMARY KEY,
+            rubric TEXT NOT NULL, -- JSON required fields, minimum description length and required links
+            updated_at TEXT DEFAULT (datetime('now'))
+        );
+
+        -- Values of architecture fitness functions recorded by validate_architecture (get_fitness_trend)
+        CREATE TABLE IF NOT EXISTS fitness_measurements (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            project_id TEXT NOT NULL,
+            function TEXT NOT NULL,
+            metric TEXT NOT NULL,
+            value REAL NOT NULL,
+            threshold REAL NOT NULL,
+            subject TEXT,
+            passed INTEGER NOT NULL,
+            measured_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_fitness_measurements_function ON fitness_measurements(project_id, function);
+
+        -- Synonym groups used to expand search queries (manage_glossary), defined as glossary_term entities
+        CREATE TABLE IF NOT EXISTS glossary_terms (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            term TEXT NOT NULL,
+            synonyms TEXT NOT NULL, -- JSON array
+            definition TEXT,
+            domain_area TEXT,
+            created_at TEXT DEFAULT (datetime('now')),
+            updated_at TEXT DEFAULT (datetime('now')),
+            FOREIGN KEY (project_id) REFERENCES projects(id),
+            UNIQUE(project_id, term)
+        );
+
+        -- Tags of a project and the entities carrying them
+        CREATE TABLE IF NOT EXISTS context_tags (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            tag_name TEXT NOT NULL,
+            category TEXT NOT NULL,
+            color TEXT,
+            description TEXT,
+            created_at TEXT NOT NULL,
+            UNIQUE(project_id, tag_name)
+        );
+
+        CREATE TABLE IF NOT EXISTS tagged_entities (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            entity_id TEXT NOT NULL,
+            entity_type TEXT NOT NULL,
+            tag_id TEXT NOT NULL,
+            tagged_at TEXT NOT NULL,
+            FOREIGN KEY (tag_id) REFERENCES context_tags(id) ON DELETE CASCADE,
+            UNIQUE(entity_id, tag_id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_tagged_entities_tag ON tagged_entities(tag_id);
+
+        -- Tags proposed for entities on create/update, applied or waiting for review
+        CREATE TABLE IF NOT EXISTS tag_suggestions (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            entity_id TEXT NOT NULL,
+            entity_type TEXT NOT NULL,
+            tag_name TEXT NOT NULL,
+            category TEXT NOT NULL,
+            confidence REAL NOT NULL,
+            sources TEXT NOT NULL, -- JSON array
+            status TEXT NOT NULL, -- applied, pending, accepted, rejected
+            suggested_at TEXT NOT NULL,
+            reviewed_at TEXT,
+            UNIQUE(entity_id, tag_name)
+        );
+        CREATE INDEX IF NOT EXISTS idx_tag_suggestions_project ON tag_suggestions(project_id, status);
+
+        -- When each entity was last confirmed to still hold (verify_context), for freshness scores
+        CREATE TABLE IF NOT EXISTS entity_verifications (
+            entity_id TEXT PRIMARY KEY,
+            entity_type TEXT NOT NULL,
+            project_id TEXT NOT NULL,
+            verified_at TEXT NOT NULL,
+            verified_by TEXT,
+            note TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_entity_verifications_project ON entity_verifications(project_id);
+
+        -- Review requests and verdicts (verified or disputed) on entities, e.g. AI-written context
+        CREATE TABLE IF NOT EXISTS entity_reviews (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            entity_type TEXT NOT NULL,
+            entity_id TEXT NOT NULL,
+            status TEXT NOT NULL,
+            requested_by TEXT,
+            requested_at TEXT,
+            note TEXT,
+            reviewer TEXT,
+            reviewed_at TEXT,
+            comment TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_entity_reviews_project ON entity_reviews(project_id);
+        CREATE INDEX IF NOT EXISTS idx_entity_reviews_entity ON entity_reviews(entity_id);
+
+        -- Entities promoted to a shared organization project and the entities they were copied from
+        CREATE TABLE IF NOT EXISTS shared_context_links (
+            shared_project_id TEXT NOT NULL,
+            shared_entity_id TEXT PRIMARY KEY,
+            entity_type TEXT NOT NULL,
+            source_project_id TEXT NOT NULL,
+            source_entity_id TEXT NOT NULL,
+            promoted_at TEXT NOT NULL,
+            promoted_by TEXT,
+            UNIQUE(shared_project_id, source_entity_id)
+        );
+
+        -- Team-defined entity types (name, JSON Schema, searchable fields) and their entities
+        CREATE TABLE IF NOT EXISTS custom_entity_types (
+            name TEXT PRIMARY KEY,
+            description TEXT,
+            schema TEXT NOT NULL, -- JSON Schema
+            searchable_fields TEXT NOT NULL, -- JSON array
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS custom_entities (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            custom_type TEXT NOT NULL,
+            fields TEXT NOT NULL, -- JSON object valid against the type's schema
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_custom_entities_project ON custom_entities(project_id, custom_type);
+
+        -- Endpoint contracts: method, path, JSON Schemas and error conventions, linked by ID
+        -- to feature contexts and framework components
+        CREATE TABLE IF NOT EXISTS api_contracts (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            method TEXT NOT NULL,
+            path TEXT NOT NULL,
+            description TEXT,
+            request_schema TEXT, -- JSON Schema
+            response_schema TEXT, -- JSON Schema
+            error_conventions TEXT,
+            feature_context_ids TEXT NOT NULL DEFAULT '[]', -- JSON array
+            component_ids TEXT NOT NULL DEFAULT '[]', -- JSON array
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            UNIQUE(project_id, method, path)
+        );
+
+        -- Typed links between architectural decisions (supersedes, amends, relates_to)
+        CREATE TABLE IF NOT EXISTS decision_links (
+            project_id TEXT NOT NULL,
+            from_decision_id TEXT NOT NULL,
+            to_decision_id TEXT NOT NULL,
+            link_type TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            created_by TEXT,
+            PRIMARY KEY (from_decision_id, to_decision_id, link_type)
+        );
+        CREATE INDEX IF NOT EXISTS idx_decision_links_project ON decision_links(project_id);
+
+        -- Files attached to entities, and their contents when [attachments] storage = "sqlite"
+        CREATE TABLE IF NOT EXISTS attachments (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            entity_type TEXT NOT NULL,
+            entity_id TEXT NOT NULL,
+            file_name TEXT NOT NULL,
+            media_type TEXT NOT NULL,
+            size_bytes INTEGER NOT NULL,
+            content_hash TEXT NOT NULL, -- hex SHA-256
+            description TEXT,
+            created_at TEXT NOT NULL,
+            created_by TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_attachments_entity ON attachments(entity_id);
+        CREATE TABLE IF NOT EXISTS attachment_blobs (
+            content_hash TEXT PRIMARY KEY,
+            content BLOB NOT NULL
+        );
+
+        -- Broadcast changes kept for WebSocket clients resuming with resume_from
+        CREATE TABLE IF NOT EXISTS change_log (
+            sequence INTEGER PRIMARY KEY AUTOINCREMENT,
+            change_id TEXT NOT NULL,
+            project_id TEXT NOT NULL,
+            change TEXT NOT NULL, -- JSON ContextChange
+            recorded_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_change_log_recorded_at ON change_log(recorded_at);
+
+        -- Conflicts between concurrent changes, and the sessions resolving them step by step
+        CREATE TABLE IF NOT EXISTS conflicts (
+            conflict_id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            conflict TEXT NOT NULL, -- JSON ConflictInfo
+            detected_at TEXT NOT NULL,
+            resolved_at TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_conflicts_project ON conflicts(project_id, detected_at);
+
+        CREATE TABLE IF NOT EXISTS conflict_sessions (
+            session_id TEXT PRIMARY KEY,
+            conflict_id TEXT NOT NULL,
+            session TEXT NOT NULL, -- JSON ConflictResolutionSession
+            timeout_at TEXT
+        );
+
         -- Analytics events table for usage tracking
         CREATE TABLE IF NOT EXISTS analytics_events (
             id TEXT PRIMARY KEY,
@@ -257,5 +568,54 @@ pub fn init_db(db_path: &str) -> Result<Connection> {
         CREATE INDEX IF NOT EXISTS idx_analytics_events_entity ON analytics_events(entity_type, entity_id);
         CREATE INDEX IF NOT EXISTS idx_analytics_events_timestamp ON analytics_events(timestamp);
     "#)?;
+
+    // Columns added after a table first shipped; the tables created above already have them
+    add_missing_columns(&conn, "security_policies", &["severity", "classification"])?;
+    add_missing_columns(&conn, "project_conventions", &["pattern", "enforcement_level"])?;
+    add_missing_columns(&conn, "feature_context", &["constraints", "related_components", "status", "owner"])?;
+    add_missing_columns(&conn, "glossary_terms", &["definition", "domain_area"])?;
+
     Ok(conn)
 }
+
+/// Add the nullable TEXT `columns` an older database's `table` lacks
+fn add_missing_columns(conn: &Connection, table: &str, columns: &[&str]) -> Result<()> {
+    for column in columns {
+        let exists: bool = conn.query_row(
+            &format!("SELECT COUNT(*) > 0 FROM pragma_table_info('{table}') WHERE name = ?1"),
+            [column],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            conn.execute_batch(&format!("ALTER TABLE {table} ADD COLUMN {column} TEXT"))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_db_adds_columns_to_older_tables() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let path = file.path().to_str().unwrap();
+        Connection::open(path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE security_policies (id TEXT PRIMARY KEY, project_id TEXT NOT NULL, policy_name TEXT NOT NULL);
+                 INSERT INTO security_policies VALUES ('sp1', 'p1', 'Encrypt at rest');",
+            )
+            .unwrap();
+
+        let conn = init_db(path).unwrap();
+        let severity: Option<String> = conn
+            .query_row("SELECT severity FROM security_policies WHERE id = 'sp1'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(severity, None);
+
+        // A second run finds the columns in place
+        init_db(path).unwrap();
+    }
+}
@@ -1,15 +1,147 @@
 use crate::api::SpecificationAnalyticsTools;
+use crate::cache::{CacheKeyBuilder, QueryCache, RequestMemo};
+use crate::config::{AppConfig, AppDirs, ConfigManager, SpecSourceFormat};
 use crate::container::AppContainer;
 use crate::models::framework::{
     FeatureInfo, FeatureStatus, ServerCapabilitiesInfo, ServerMetadata, TableInfo, ToolInfo,
     UsageExample,
 };
+use crate::services::conflict_resolution_engine::ConflictInfo;
+use crate::services::conflict_resolution_ui::{StartResolutionRequest, UpdateUIStateRequest};
+use crate::services::context_query_service::{estimate_tokens, ContextQueryResult, DEFAULT_TOKEN_BUDGET};
+use crate::services::digest_generator::{DigestGenerator, DigestGeneratorFactory};
+use crate::services::embedding_queue::EmbeddingJob;
+use crate::services::embedding_service::EmbeddingServiceFactory;
+use crate::services::glossary_service::terms_in;
+use crate::services::hybrid_search_service::FacetFilters;
+use crate::services::report_scheduler::quality_report;
+use crate::services::saved_search_service::subscribe_filters;
+use crate::services::traceability_service::DEFAULT_COMMIT_LIMIT;
+use crate::services::vector_embedding_integration::parent_id;
+use crate::services::websocket_manager::WebSocketManager;
+use crate::services::websocket_types::{ClientId, ConflictStrategy};
 use crate::services::AnalyticsHelper;
+use crate::models::architecture::{ArchitectureRuleSet, BaselineComparison, Severity};
+use crate::models::approval::{WorkflowEntity, WorkflowState};
+use crate::services::{AnalyticsEvent, AnalyticsEventType, AnalyticsExporter, ContextGapReport, DashboardData, DashboardRenderer, DependencyGraph, EntityFreshness, EntityUseSource, GraphFormat, SarifExporter, SpecTemplateGenerator, SpecificationExporter, SpecificationParser, StaleContextReport, SyncMetrics, TemplateContext, UnusedContextReport, STALE_TAG};
+use crate::services::hybrid_search_service::RankedSearchResult;
+use crate::models::context::{FeatureContext, ProjectConvention, SecurityPolicy};
+use crate::models::context_conversion::ContextConverter;
+use crate::models::api_contract::ApiContract;
+use crate::models::attachment::{media_type_for, Attachment};
+use crate::models::custom_entity::{CustomEntity, CustomEntityType};
+use crate::models::decision_link::{chain_head, superseded_ids, supersedes_transitively, DecisionLink, DecisionLinkType};
+use crate::models::enhanced_context::{ContextRelationship, EnhancedContextItem, RelationshipType};
+use crate::models::freshness::EntityVerification;
+use crate::models::glossary::GlossaryEntry;
+use crate::models::review::{verification_states, EntityReview, EntityVerificationState, VerificationStatus};
+use crate::models::quality_rubric::QualityRubric;
+use crate::models::scheduled_report::ScheduledReportKind;
+use crate::models::tool_call_audit::{ToolCallAudit, ToolCallAuditFilter, ToolCallOutcome};
+use crate::models::tagging::{ContextTag, TagSuggestionStatus, TaggedEntity};
+use crate::services::context_freshness::FRESHNESS_ENTITY_TYPES;
+use crate::services::context_gaps::specification_entity;
+use crate::models::shared_context::SharedContextLink;
+use crate::services::shared_context::{merge_shared, PROMOTABLE_ENTITY_TYPES};
+use crate::services::context_recommendations::{query_history, recommend, ContextRecommendation, QueryProfile};
+use crate::services::tag_classifier;
+use crate::services::unused_context::entity_title;
 use anyhow::Result;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
 use rmcp::{handler::server::ServerHandler, model::ErrorData as McpError, model::*};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Instant;
 
+/// Client id recorded on changes made through MCP tool calls
+const MCP_CLIENT_ID: ClientId = ClientId::nil();
+/// How long a conflict resolution session started through MCP lasts by default
+const CONFLICT_SESSION_TIMEOUT_SECS: u64 = 1800;
+/// Similarity at which `find_similar_entities` flags a result as a likely duplicate
+const DUPLICATE_SIMILARITY: f32 = 0.9;
+/// Neighbours fetched before `find_similar_entities` applies its entity type filter
+const SIMILAR_CANDIDATES: usize = 100;
+/// Entity types that `submit_for_review` and `record_review` cover and that report a
+/// verification status
+const REVIEWABLE_ENTITY_TYPES: &[&str] = &[
+    "business_rule",
+    "architectural_decision",
+    "performance_requirement",
+    "framework_component",
+    "development_phase",
+    "security_policy",
+    "project_convention",
+    "feature_context",
+];
+
+/// Tools that never write; every other tool call drops cached `query_context` results
+/// (tools with an `action` only when it writes)
+const READ_ONLY_TOOLS: &[&str] = &[
+    "query_context",
+    "search_context",
+    "get_related_context",
+    "find_similar_entities",
+    "run_saved_search",
+    "get_attachment",
+    "list_projects",
+    "get_entity",
+    "list_entities",
+    "export_dependency_graph",
+    "detect_dependency_cycles",
+    "get_fitness_trend",
+    "assess_context_quality",
+    "get_server_capabilities",
+    "get_effective_config",
+    "get_indexing_status",
+    "search_index_status",
+    "get_active_clients",
+    "list_conflicts",
+    "get_conflict",
+    "get_usage_analytics",
+    "get_context_insights",
+    "get_context_digest",
+    "get_tag_suggestions",
+    "list_reviews",
+    "list_stale_context",
+    "analyze_context_gaps",
+    "generate_quality_report",
+    "export_analytics_data",
+    "get_audit_log",
+    "suggest_context",
+    "validate_specification",
+    "export_specification",
+    "generate_spec_template",
+    "get_workflow_state",
+    "analyze_requirement_coverage",
+    "get_specification_versions",
+    "compare_specification_versions",
+    "analyze_specification_completeness",
+    "calculate_development_velocity",
+    "generate_specification_health_report",
+];
+
+/// `query_context` gives the sections whose size doesn't depend on the ranked items (feature
+/// context, API contracts, specifications) at most 1/`SECTION_BUDGET_SHARE` of its budget
+const SECTION_BUDGET_SHARE: usize = 4;
+
+/// A `query_context` result filtered by review state, with the sections added besides the
+/// ranked items, before fitting to the token budget
+struct FilteredContext {
+    result: ContextQueryResult,
+    /// Sections in the order they get room in the budget
+    sections: Vec<(String, Vec<serde_json::Value>)>,
+    /// Links between the project's decisions; those of the decisions returned are added
+    decision_links: Vec<DecisionLink>,
+}
+
+tokio::task_local! {
+    /// `name/version` of the MCP client whose tool call is running, recorded on its events
+    static CLIENT_AGENT: Option<String>;
+    /// The MCP client whose tool call is running, which sampling requests go to
+    static CLIENT_PEER: rmcp::service::Peer<rmcp::service::RoleServer>;
+}
+
 /// Enhanced MCP Context Server with SOLID principles and comprehensive CRUD operations
 #[derive(Clone)]
 pub struct EnhancedContextMcpServer {
@@ -17,54 +149,184 @@ pub struct EnhancedContextMcpServer {
 }
 
 impl EnhancedContextMcpServer {
+    #[allow(dead_code)]
     pub fn new(db_path: &str) -> Result<Self> {
         let container = AppContainer::new(db_path)?;
         Ok(Self {
             container: Arc::new(container),
         })
     }
-}
 
-impl ServerHandler for EnhancedContextMcpServer {
-    fn get_info(&self) -> ServerInfo {
-        ServerInfo {
-            protocol_version: ProtocolVersion::default(),
-            capabilities: ServerCapabilities::builder()
-                .enable_tools()
-                .enable_resources()
-                .build(),
-            server_info: Implementation {
-                name: "enhanced-context-server-rs".to_string(),
-                version: "0.2.0".to_string(),
-            },
-            instructions: Some("Enhanced Context Server with comprehensive CRUD operations for AI Code Generation. Provides curated project context including business rules, architectural decisions, security policies, project conventions, feature contexts, and framework-agnostic components.".to_string()),
-        }
+    #[allow(dead_code)]
+    pub fn from_config(config: AppConfig) -> Result<Self> {
+        let container = AppContainer::from_config(config)?;
+        Ok(Self {
+            container: Arc::new(container),
+        })
     }
 
-    async fn list_tools(
-        &self,
-        _request: Option<PaginatedRequestParam>,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
-    ) -> Result<ListToolsResult, McpError> {
-        tracing::debug!("Received list_tools request for enhanced server");
+    pub fn with_config_manager(config_manager: Arc<ConfigManager>) -> Result<Self> {
+        let container = AppContainer::with_config_manager(config_manager)?;
+        Ok(Self {
+            container: Arc::new(container),
+        })
+    }
 
-        let tools = vec![
+    /// Every MCP tool the server offers, as `list_tools` returns them
+    pub fn tools() -> Vec<Tool> {
+        vec![
             // Core Context Query Tool
             Tool {
                 name: "query_context".into(),
-                description: Some("Query project context based on feature area, task type, and components".into()),
+                description: Some("Query project context based on feature area, task type, and components, ranked by relevance (embedding similarity), freshness and completeness and cut to a token budget; each item in `ranking` says why it was included. Architectural decisions and specifications are limited to approved ones unless include_drafts is set, and superseded decisions are left out unless include_superseded is set. glossary_terms defines the project's terms the result uses, and api_contracts gives the endpoints linked to the feature area's feature context or the components".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
                         "project_id": {"type": "string", "description": "The ID of the project"},
                         "feature_area": {"type": "string", "description": "The feature area (e.g., 'authentication', 'user_interface', 'payments')"},
                         "task_type": {"type": "string", "description": "The type of task ('implement', 'fix', 'optimize')"},
-                        "components": {"type": "array", "items": {"type": "string"}, "description": "List of components involved"}
+                        "components": {"type": "array", "items": {"type": "string"}, "description": "List of components involved"},
+                        "include_drafts": {"type": "boolean", "description": "Also return draft and in-review decisions and specifications (default: only approved ones)"},
+                        "include_superseded": {"type": "boolean", "description": "Also return decisions a newer decision supersedes (default: only the head of each chain)", "default": false},
+                        "exclude_disputed": {"type": "boolean", "description": "Leave out items whose latest review disputed them", "default": false},
+                        "include_shared": {"type": "boolean", "description": "Also return the rules and conventions of the shared organization project ([shared_context] project_id)", "default": false},
+                        "max_tokens": {"type": "integer", "minimum": 1, "description": "Estimated tokens the whole result may take; items are added best ranked first (default: 8000)"}
                     },
                     "required": ["project_id", "feature_area", "task_type", "components"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
+            Tool {
+                name: "search_context".into(),
+                description: Some("Search a project's context, or every project's with all_projects, with hybrid vector and keyword ranking; each result carries its project_id, score breakdown and facets, and facet_counts support drill-down. Weights, fusion method and per-type boosts come from the [search] config section".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The ID of the project (required unless all_projects is true)"},
+                        "all_projects": {"type": "boolean", "description": "Search across all projects instead of one (default: false)"},
+                        "query": {"type": "string", "description": "Free-text search query"},
+                        "feature_area": {"type": "string", "description": "Optional feature area to narrow the keyword leg"},
+                        "filters": {
+                            "type": "object",
+                            "description": "Facet filters: entity_type, domain_area, architecture_layer, status or tags mapped to a value or list of values. Results must match one value of every facet given",
+                            "additionalProperties": {"oneOf": [{"type": "string"}, {"type": "array", "items": {"type": "string"}}]}
+                        },
+                        "limit": {"type": "integer", "description": "Maximum number of results (default: search.max_results)"}
+                    },
+                    "required": ["query"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "get_related_context".into(),
+                description: Some("Walk the relationship graph from an entity up to max_hops (recorded links such as component dependencies and requirement links, plus relationships the engine infers from shared tags and similar descriptions) and return its neighborhood ranked by strength; what to read before touching it".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The ID of the project"},
+                        "entity_id": {"type": "string", "description": "ID of the entity to start from (rule, decision, requirement, component, ...)"},
+                        "max_hops": {"type": "integer", "description": "Relationships to follow from the entity, 1-4 (default: 2)"},
+                        "include_inferred": {"type": "boolean", "description": "Follow relationships the engine infers as well as recorded ones (default: true)"},
+                        "limit": {"type": "integer", "description": "Maximum number of results (default: 20)"}
+                    },
+                    "required": ["project_id", "entity_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "find_similar_entities".into(),
+                description: Some("Find the entities in the same project whose stored embeddings are closest to an entity's, across all entity types; flags likely duplicates".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "entity_id": {"type": "string", "description": "ID of the entity to compare against"},
+                        "entity_types": {"type": "array", "items": {"type": "string"}, "description": "Only return these entity types"},
+                        "min_similarity": {"type": "number", "description": "Minimum cosine similarity, 0.5-1.0 (default: 0.5)"},
+                        "limit": {"type": "integer", "description": "Maximum number of results (default: 10)"}
+                    },
+                    "required": ["entity_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "run_saved_search".into(),
+                description: Some("Re-run a saved_search entity (created with create_entity) with the current ranking; also returns WebSocket subscription filters for changes that may alter its results".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "saved_search_id": {"type": "string", "description": "ID of the saved search"},
+                        "project_id": {"type": "string", "description": "Project of the saved search, when looking it up by name"},
+                        "name": {"type": "string", "description": "Name of the saved search, with project_id"},
+                        "limit": {"type": "integer", "description": "Maximum number of results (default: the saved search's limit)"}
+                    }
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "manage_glossary".into(),
+                description: Some("Maintain a project's glossary of synonyms (\"auth\" = \"authentication\", \"ADR\" = \"architectural decision\"); search_context expands queries with them in both keyword and semantic matching".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "action": {"type": "string", "enum": ["list", "add", "remove", "expand"], "description": "List the glossary, add or remove synonyms, or preview how a query is expanded"},
+                        "project_id": {"type": "string", "description": "The project whose glossary to use"},
+                        "term": {"type": "string", "description": "A term of the group to change (add, remove)"},
+                        "synonyms": {"type": "array", "items": {"type": "string"}, "description": "Synonyms to add, or to remove; remove without synonyms deletes the whole group"},
+                        "query": {"type": "string", "description": "Query to expand (expand)"}
+                    },
+                    "required": ["action", "project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+
+            Tool {
+                name: "manage_custom_entity_types".into(),
+                description: Some("Register entity types of your own (\"data_contract\", \"slo\") with a JSON Schema and searchable fields; their entities are stored through create/get/update/delete/list_entity as custom_entity and included in search_context".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "action": {"type": "string", "enum": ["define", "get", "list", "delete"], "description": "Define (or redefine) a type, show one, list them, or delete one without entities"},
+                        "name": {"type": "string", "description": "Name of the type, given as the custom_type of its entities (define, get, delete)"},
+                        "description": {"type": "string", "description": "What entities of the type describe (define)"},
+                        "schema": {"type": "object", "description": "JSON Schema with \"type\": \"object\" that the fields of its entities must satisfy; type, properties, required, enum and items are checked (define)"},
+                        "searchable_fields": {"type": "array", "items": {"type": "string"}, "description": "Properties whose text search_context indexes (define)"}
+                    },
+                    "required": ["action"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+
+            Tool {
+                name: "add_attachment".into(),
+                description: Some("Attach a file (diagram, sequence chart, sample payload) to an architectural decision or feature context; identical contents are stored once and sizes are limited by [attachments]".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "Project the entity belongs to"},
+                        "entity_type": {"type": "string", "enum": ["architectural_decision", "feature_context"], "description": "Type of the entity to attach to"},
+                        "entity_id": {"type": "string", "description": "ID of the entity to attach to"},
+                        "file_name": {"type": "string", "description": "Name of the attachment, e.g. checkout-sequence.mmd"},
+                        "media_type": {"type": "string", "description": "Media type; guessed from the file name's extension if omitted"},
+                        "content": {"type": "string", "description": "Text content, e.g. a Mermaid diagram or a JSON payload"},
+                        "content_base64": {"type": "string", "description": "Binary content, base64 encoded"},
+                        "description": {"type": "string", "description": "What the attachment shows"}
+                    },
+                    "required": ["project_id", "entity_type", "entity_id", "file_name"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "get_attachment".into(),
+                description: Some("Get an attachment with its content, or list the attachments of an entity".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "attachment_id": {"type": "string", "description": "ID of the attachment to get with its content"},
+                        "entity_id": {"type": "string", "description": "ID of an entity whose attachments to list, without content"}
+                    }
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
 
             // Project Management (kept for convenience)
             Tool {
@@ -81,7 +343,7 @@ impl ServerHandler for EnhancedContextMcpServer {
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "entity_type": {"type": "string", "enum": ["project", "business_rule", "architectural_decision", "performance_requirement", "security_policy", "framework_component", "development_phase", "feature_context"], "description": "The type of entity to retrieve"},
+                        "entity_type": {"type": "string", "enum": ["project", "business_rule", "architectural_decision", "performance_requirement", "security_policy", "project_convention", "framework_component", "development_phase", "feature_context", "saved_search", "traceability_report", "scheduled_report", "custom_entity", "glossary_term", "api_contract"], "description": "The type of entity to retrieve"},
                         "id": {"type": "string", "description": "The ID of the entity"}
                     },
                     "required": ["entity_type", "id"]
@@ -94,7 +356,7 @@ impl ServerHandler for EnhancedContextMcpServer {
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "entity_type": {"type": "string", "enum": ["project", "business_rule", "architectural_decision", "performance_requirement", "security_policy", "framework_component", "development_phase", "feature_context"], "description": "The type of entity to create"},
+                        "entity_type": {"type": "string", "enum": ["project", "business_rule", "architectural_decision", "performance_requirement", "security_policy", "project_convention", "framework_component", "development_phase", "feature_context", "saved_search", "custom_entity", "glossary_term", "api_contract"], "description": "The type of entity to create"},
                         "data": {"type": "object", "description": "The entity data as JSON object"}
                     },
                     "required": ["entity_type", "data"]
@@ -107,7 +369,7 @@ impl ServerHandler for EnhancedContextMcpServer {
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "entity_type": {"type": "string", "enum": ["project", "business_rule", "architectural_decision", "performance_requirement", "security_policy", "framework_component", "development_phase", "feature_context"], "description": "The type of entity to update"},
+                        "entity_type": {"type": "string", "enum": ["project", "business_rule", "architectural_decision", "performance_requirement", "security_policy", "project_convention", "framework_component", "development_phase", "feature_context", "saved_search", "custom_entity", "glossary_term", "api_contract"], "description": "The type of entity to update"},
                         "id": {"type": "string", "description": "The ID of the entity"},
                         "data": {"type": "object", "description": "The updated entity data as JSON object"}
                     },
@@ -121,7 +383,7 @@ impl ServerHandler for EnhancedContextMcpServer {
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "entity_type": {"type": "string", "enum": ["project", "business_rule", "architectural_decision", "performance_requirement", "security_policy", "framework_component", "development_phase", "feature_context"], "description": "The type of entity to delete"},
+                        "entity_type": {"type": "string", "enum": ["project", "business_rule", "architectural_decision", "performance_requirement", "security_policy", "project_convention", "framework_component", "development_phase", "feature_context", "saved_search", "traceability_report", "scheduled_report", "custom_entity", "glossary_term", "api_contract"], "description": "The type of entity to delete"},
                         "id": {"type": "string", "description": "The ID of the entity to delete"}
                     },
                     "required": ["entity_type", "id"]
@@ -134,9 +396,10 @@ impl ServerHandler for EnhancedContextMcpServer {
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "entity_type": {"type": "string", "enum": ["project", "business_rule", "architectural_decision", "performance_requirement", "security_policy", "framework_component", "development_phase", "feature_context"], "description": "The type of entities to list"},
+                        "entity_type": {"type": "string", "enum": ["project", "business_rule", "architectural_decision", "performance_requirement", "security_policy", "project_convention", "framework_component", "development_phase", "feature_context", "saved_search", "traceability_report", "scheduled_report", "custom_entity", "glossary_term", "api_contract"], "description": "The type of entities to list"},
                         "project_id": {"type": "string", "description": "Optional project ID to filter by"},
-                        "architecture_layer": {"type": "string", "description": "Optional architecture layer to filter framework components by (only applies to framework_component entity type)"}
+                        "architecture_layer": {"type": "string", "description": "Optional architecture layer to filter framework components by (only applies to framework_component entity type)"},
+                        "custom_type": {"type": "string", "description": "Optional custom entity type to filter by (only applies to custom_entity entity type)"}
                     },
                     "required": ["entity_type"]
                 }).as_object().unwrap().clone()),
@@ -252,258 +515,3309 @@ impl ServerHandler for EnhancedContextMcpServer {
             },
             Tool {
                 name: "validate_architecture".into(),
-                description: Some("Validate Clean Architecture rules and detect violations".into()),
+                description: Some("Validate the project's architecture rules (Clean Architecture unless set with manage_architecture_rules) and detect violations, including dependency cycles among components. Findings carry a severity and those acknowledged in the baseline file are left out, so only new regressions are reported. Fitness functions of the rule set are computed and recorded with their trend. With since or component_ids only those components are validated, which is cheap enough to run after every edit. Findings carry suggested fixes as create_entity and update_entity calls to apply in order".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "project_id": {"type": "string", "description": "The ID of the project to validate"}
+                        "project_id": {"type": "string", "description": "The ID of the project to validate"},
+                        "min_severity": {"type": "string", "enum": ["info", "warning", "error"], "description": "Leave out findings below this severity", "default": "info"},
+                        "baseline": {"type": "boolean", "description": "Leave out findings acknowledged in the baseline file", "default": true},
+                        "update_baseline": {"type": "boolean", "description": "Acknowledge every current finding, replacing the project's baseline", "default": false},
+                        "record_fitness": {"type": "boolean", "description": "Record the fitness function values for get_fitness_trend", "default": true},
+                        "format": {"type": "string", "enum": ["json", "sarif"], "description": "json for the summary with findings and fitness values, sarif for a SARIF 2.1.0 log to upload to code scanning", "default": "json"},
+                        "since": {"type": "string", "description": "RFC 3339 timestamp; validate only the components created or changed since then, e.g. after an edit"},
+                        "component_ids": {"type": "array", "items": {"type": "string"}, "description": "Validate only these components, by id or name, along with any changed since `since`"}
                     },
                     "required": ["project_id"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
             Tool {
-                name: "get_server_capabilities".into(),
-                description: Some("Get comprehensive information about server features, database tables, and available tools".into()),
-                input_schema: Arc::new(serde_json::json!({"type": "object", "properties": {}}).as_object().unwrap().clone()),
+                name: "scan_codebase".into(),
+                description: Some("Scan a Rust, Dart or TypeScript repository and create or update framework components from its source files, with layers inferred from the architecture rules and import dependencies".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The ID of the project"},
+                        "path": {"type": "string", "description": "Repository root to scan (default: the working directory)"},
+                        "dry_run": {"type": "boolean", "description": "Report the components a scan would create or update without writing them", "default": false}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
                 annotations: None,
             },
-
-            // Cache Management Tools
             Tool {
-                name: "cache_management".into(),
-                description: Some("Manage cache and temporary data (clear project, clear all)".into()),
+                name: "import_manifests".into(),
+                description: Some("Record the external crates and packages declared in a repository's Cargo.toml, pubspec.yaml and package.json files as component dependencies, so validate_architecture can flag layers that pull in forbidden ones".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "action": {"type": "string", "enum": ["clear_project", "clear_all"], "description": "The cache action to perform"},
-                        "project_id": {"type": "string", "description": "Project ID (required for clear_project action)"}
+                        "project_id": {"type": "string", "description": "The ID of the project"},
+                        "path": {"type": "string", "description": "Repository root to search for manifests (default: the working directory)"},
+                        "dry_run": {"type": "boolean", "description": "Report the dependencies an import would record or remove without writing them", "default": false}
                     },
-                    "required": ["action"]
+                    "required": ["project_id"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
-
-            // Analytics MCP Tools
             Tool {
-                name: "get_usage_analytics".into(),
-                description: Some("Retrieve usage statistics for entities or global analytics".into()),
+                name: "export_dependency_graph".into(),
+                description: Some("Render a project's framework components, their layers and dependencies as a Graphviz DOT or Mermaid graph, optionally colored by architecture violations".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "scope": {"type": "string", "enum": ["global", "entity"], "description": "Scope of analytics to retrieve"},
-                        "entity_type": {"type": "string", "description": "Entity type (required for entity scope)"},
-                        "entity_id": {"type": "string", "description": "Entity ID (required for entity scope)"}
+                        "project_id": {"type": "string", "description": "The ID of the project"},
+                        "format": {"type": "string", "enum": ["mermaid", "dot"], "description": "Graph format", "default": "mermaid"},
+                        "show_violations": {"type": "boolean", "description": "Color components and dependencies that break the architecture rules", "default": true},
+                        "include_external": {"type": "boolean", "description": "Also show dependencies that are not components of the project", "default": false}
                     },
-                    "required": ["scope"]
+                    "required": ["project_id"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
             Tool {
-                name: "get_context_insights".into(),
-                description: Some("Get project-level analytics and insights".into()),
+                name: "detect_dependency_cycles".into(),
+                description: Some("Find dependency cycles among a project's framework components, development phases and specification tasks, with each cycle's members and the fewest dependencies to remove to break it".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "project_id": {"type": "string", "description": "The ID of the project to analyze"}
+                        "project_id": {"type": "string", "description": "The ID of the project"},
+                        "spec_project": {"type": "string", "description": "Name the specifications were imported under, i.e. their .kiro/specs directory (defaults to project_id)"}
                     },
                     "required": ["project_id"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
             Tool {
-                name: "generate_quality_report".into(),
-                description: Some("Generate a context health assessment and quality report".into()),
+                name: "get_fitness_trend".into(),
+                description: Some("Show how a project's architecture fitness functions developed over the validate_architecture runs that recorded them".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "start_date": {"type": "string", "format": "date-time", "description": "Start date for the report (ISO 8601 format)"},
-                        "end_date": {"type": "string", "format": "date-time", "description": "End date for the report (ISO 8601 format)"},
-                        "project_id": {"type": "string", "description": "Optional project ID to filter the report"}
+                        "project_id": {"type": "string", "description": "The ID of the project"},
+                        "function": {"type": "string", "description": "Name of one fitness function (default: all of them)"},
+                        "limit": {"type": "integer", "description": "Measurements to return, newest first", "default": 20}
                     },
-                    "required": ["start_date", "end_date"]
+                    "required": ["project_id"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
             Tool {
-                name: "export_analytics_data".into(),
-                description: Some("Export analytics data for data portability and external analysis".into()),
+                name: "manage_architecture_rules".into(),
+                description: Some("Get, set or reset a project's architecture rules: layers and the layers each may depend on, forbidden imports, component naming patterns and fitness functions".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "format": {"type": "string", "enum": ["json", "csv"], "description": "Export format", "default": "json"},
-                        "start_date": {"type": "string", "format": "date-time", "description": "Start date for export (ISO 8601 format)"},
-                        "end_date": {"type": "string", "format": "date-time", "description": "End date for export (ISO 8601 format)"},
-                        "project_id": {"type": "string", "description": "Optional project ID to filter the export"},
-                        "event_types": {"type": "array", "items": {"type": "string"}, "description": "Optional array of event types to include"}
+                        "project_id": {"type": "string", "description": "The ID of the project"},
+                        "action": {"type": "string", "enum": ["get", "set", "reset"], "description": "get the rules in effect, set new ones, or reset to Clean Architecture"},
+                        "layers": {
+                            "type": "array",
+                            "description": "For set: layers as {name, path_patterns, may_depend_on}; a layer without may_depend_on may depend on any layer",
+                            "items": {"type": "object"}
+                        },
+                        "forbidden_imports": {
+                            "type": "array",
+                            "description": "For set: {pattern (regex), layers, reason} dependencies the given layers (all when empty) may not have",
+                            "items": {"type": "object"}
+                        },
+                        "naming_patterns": {
+                            "type": "array",
+                            "description": "For set: {pattern (regex), layer, component_type, description} component names must match",
+                            "items": {"type": "object"}
+                        },
+                        "fitness_functions": {
+                            "type": "array",
+                            "description": "For set: {name, metric (components_per_layer, fan_in, fan_out or instability), threshold, layer} metrics that must stay at or below their threshold",
+                            "items": {"type": "object"}
+                        }
                     },
-                    "required": ["start_date", "end_date"]
+                    "required": ["project_id", "action"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
-
-            // Specification Import and Management Tools
             Tool {
-                name: "scan_specifications".into(),
-                description: Some("Scan and import all Kiro specifications from .kiro/specs directory".into()),
+                name: "manage_quality_rubric".into(),
+                description: Some("Get, set or reset the quality rubric a project's context is scored against: required fields per entity type, minimum description length, required examples and required links to specification requirements".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "base_path": {"type": "string", "description": "Base path to scan for specifications (defaults to .kiro/specs)", "default": ".kiro/specs"}
-                    }
+                        "project_id": {"type": "string", "description": "The ID of the project"},
+                        "action": {"type": "string", "enum": ["get", "set", "reset"], "description": "get the rubric in effect, set the project's own, or reset to the [quality] rubric"},
+                        "required_fields": {
+                            "type": "object",
+                            "description": "For set: fields each entity type must fill, e.g. {\"business_rule\": [\"domain_area\", \"implementation_pattern\"]}",
+                            "additionalProperties": {"type": "array", "items": {"type": "string"}}
+                        },
+                        "min_description_length": {"type": "integer", "minimum": 0, "description": "For set: fewest characters in a description (0 for no minimum)"},
+                        "require_examples": {"type": "boolean", "description": "For set: business rules must give at least one example"},
+                        "require_requirement_link": {"type": "boolean", "description": "For set: entities must be linked to a specification requirement"}
+                    },
+                    "required": ["project_id", "action"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
             Tool {
-                name: "import_specification".into(),
-                description: Some("Import a single specification file".into()),
+                name: "assess_context_quality".into(),
+                description: Some("Score a project's rules, decisions, requirements, conventions and feature contexts, lowest first, with the rubric checks each fails and suggested improvements".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "file_path": {"type": "string", "description": "Path to the specification file to import"}
+                        "project_id": {"type": "string", "description": "The ID of the project"},
+                        "entity_type": {"type": "string", "enum": ["business_rule", "architectural_decision", "performance_requirement", "project_convention", "feature_context"], "description": "Only assess entities of this type"},
+                        "limit": {"type": "integer", "minimum": 1, "description": "Most entities to return (default: 20)"}
                     },
-                    "required": ["file_path"]
+                    "required": ["project_id"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
             Tool {
-                name: "validate_specification".into(),
-                description: Some("Validate a specification file and return validation issues".into()),
+                name: "get_server_capabilities".into(),
+                description: Some("Get comprehensive information about server features, database tables, and available tools".into()),
+                input_schema: Arc::new(serde_json::json!({"type": "object", "properties": {}}).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "get_effective_config".into(),
+                description: Some("Show the configuration currently in effect (config.toml + environment overrides + flags), optionally reloading config.toml first".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "file_path": {"type": "string", "description": "Path to the specification file to validate"}
+                        "reload": {"type": "boolean", "description": "Re-read config.toml before reporting (default: false)"}
+                    }
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+
+            Tool {
+                name: "reindex_project".into(),
+                description: Some("Re-embed every entity of a project and drop embeddings of deleted entities, e.g. after changing the embedding provider".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The ID of the project to reindex"}
                     },
-                    "required": ["file_path"]
+                    "required": ["project_id"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
             Tool {
-                name: "start_spec_monitoring".into(),
-                description: Some("Start monitoring .kiro/specs directory for changes".into()),
+                name: "get_indexing_status".into(),
+                description: Some("Report the background embedding backlog: pending, retrying and failed jobs, stored models and any running migration".into()),
+                input_schema: Arc::new(serde_json::json!({"type": "object", "properties": {}}).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "search_index_status".into(),
+                description: Some("Report what the search index holds: documents per entity type, vectors stored, index size, last rebuild and drift between database entities and index entries".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "base_path": {"type": "string", "description": "Base path to monitor (defaults to .kiro/specs)", "default": ".kiro/specs"}
+                        "project_id": {"type": "string", "description": "Limit the report to one project (default: all projects)"}
                     }
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
             Tool {
-                name: "get_specification_versions".into(),
-                description: Some("Get all versions of a specification".into()),
+                name: "get_active_clients".into(),
+                description: Some("List the IDEs, agents and other clients connected over WebSocket, who uses them and which entities they are editing".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "spec_id": {"type": "string", "description": "ID of the specification"}
+                        "project_id": {"type": "string", "description": "Only clients connected to this project (default: all projects)"},
+                        "entity_type": {"type": "string", "description": "With entity_id, only clients editing this entity"},
+                        "entity_id": {"type": "string", "description": "With entity_type, only clients editing this entity"}
+                    }
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "list_conflicts".into(),
+                description: Some("List conflicts detected between concurrent changes to a project's entities, newest first".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The project whose conflicts to list"},
+                        "status": {"type": "string", "enum": ["unresolved", "resolved", "all"], "description": "Which conflicts to list (default: unresolved)"}
                     },
-                    "required": ["spec_id"]
+                    "required": ["project_id"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
             Tool {
-                name: "compare_specification_versions".into(),
-                description: Some("Compare two versions of a specification".into()),
+                name: "get_conflict".into(),
+                description: Some("Get a conflict with the competing changes and, once resolved, how it was resolved".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "version1_id": {"type": "string", "description": "ID of the first version"},
-                        "version2_id": {"type": "string", "description": "ID of the second version"}
+                        "conflict_id": {"type": "string", "description": "The ID of the conflict"}
                     },
-                    "required": ["version1_id", "version2_id"]
+                    "required": ["conflict_id"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
-
-            // Specification Analytics Tools
             Tool {
-                name: "track_requirements_progress".into(),
-                description: Some("Track progress for all requirements in a project, including completion percentages, linked tasks, and acceptance criteria status".into()),
+                name: "start_conflict_resolution".into(),
+                description: Some("Start resolving an unresolved conflict; returns a session ID, the available strategies and a recommended one".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "project_id": {"type": "string", "description": "The ID of the project to track requirements progress for"}
+                        "conflict_id": {"type": "string", "description": "The ID of the conflict to resolve"},
+                        "user_id": {"type": "string", "description": "Who is resolving the conflict (default: mcp)"},
+                        "preferred_strategy": {"type": "string", "enum": ["LastWriterWins", "ManualResolution", "AutoMerge", "Reject"], "description": "Strategy to start with"},
+                        "timeout_seconds": {"type": "integer", "minimum": 1, "description": "Seconds before the session expires (default: 1800)"}
                     },
-                    "required": ["project_id"]
+                    "required": ["conflict_id"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
             Tool {
-                name: "track_tasks_progress".into(),
-                description: Some("Track progress for all tasks in a project, including status, completion percentage, time tracking, and dependencies".into()),
+                name: "update_conflict_resolution".into(),
+                description: Some("Move a conflict resolution session to another step, choosing a strategy or the values to keep; returns validation errors and whether the session can proceed".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "project_id": {"type": "string", "description": "The ID of the project to track tasks progress for"}
+                        "session_id": {"type": "string", "description": "The session from start_conflict_resolution"},
+                        "step": {"type": "string", "enum": ["ConflictPresentation", "StrategySelection", "ManualResolution", "PreviewConfirmation"], "description": "The step to move to"},
+                        "selected_strategy": {"type": "string", "enum": ["LastWriterWins", "ManualResolution", "AutoMerge", "Reject"], "description": "Strategy to resolve with"},
+                        "user_selections": {"type": "object", "description": "Choices for the step, such as the field values to keep for manual resolution"}
                     },
-                    "required": ["project_id"]
+                    "required": ["session_id", "step"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
             Tool {
-                name: "analyze_specification_completeness".into(),
-                description: Some("Analyze completeness of specifications in a project, including content quality, missing sections, and recommendations".into()),
+                name: "complete_conflict_resolution".into(),
+                description: Some("Resolve the conflict of a session with its selected strategy and return the resolved entity".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "project_id": {"type": "string", "description": "The ID of the project to analyze specification completeness for"}
+                        "session_id": {"type": "string", "description": "The session from start_conflict_resolution"},
+                        "resolution_notes": {"type": "string", "description": "Why the conflict was resolved this way, recorded with manual resolutions"}
                     },
-                    "required": ["project_id"]
+                    "required": ["session_id"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
             Tool {
-                name: "calculate_development_velocity".into(),
-                description: Some("Calculate development velocity metrics based on task and requirement completion over a specified time period".into()),
+                name: "migrate_embeddings".into(),
+                description: Some("Re-embed all content with another embedding model in the background; search keeps using the current model until the new index is complete".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "project_id": {"type": "string", "description": "The ID of the project to calculate velocity for"},
-                        "days": {"type": "integer", "description": "Number of days to look back for velocity calculation", "default": 30, "minimum": 1, "maximum": 365}
+                        "action": {"type": "string", "enum": ["start", "status"], "description": "Start a migration or report progress (default: status)"},
+                        "provider": {"type": "string", "enum": ["simple", "openai", "ollama", "local"], "description": "Embedding provider to migrate to (default: [embedding] provider)"},
+                        "model": {"type": "string", "description": "Model to migrate to (default: [embedding] model)"},
+                        "dimension": {"type": "integer", "minimum": 1, "description": "Vector length of the new model (default: [embedding] dimension)"},
+                        "endpoint": {"type": "string", "description": "Base URL for openai and ollama (default: [embedding] endpoint)"}
+                    }
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+
+            // Cache Management Tools
+            Tool {
+                name: "cache_management".into(),
+                description: Some("Manage the query cache (clear project, clear all, stats)".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "action": {"type": "string", "enum": ["clear_project", "clear_all", "stats"], "description": "The cache action to perform"},
+                        "project_id": {"type": "string", "description": "Project ID (required for clear_project action)"},
+                        "format": {"type": "string", "enum": ["json", "prometheus"], "description": "Output format for the stats action (default: json)"}
                     },
-                    "required": ["project_id"]
+                    "required": ["action"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
+
+            // Analytics MCP Tools
             Tool {
-                name: "generate_specification_health_report".into(),
-                description: Some("Generate a comprehensive health report for all specifications in a project, including progress, completeness, velocity, and recommendations".into()),
+                name: "get_usage_analytics".into(),
+                description: Some("Retrieve usage statistics for entities or global analytics, or realtime sync metrics".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
-                        "project_id": {"type": "string", "description": "The ID of the project to generate health report for"}
+                        "scope": {"type": "string", "enum": ["global", "entity", "sync"], "description": "Scope of analytics to retrieve; sync reports realtime sync queues, deliveries and conflicts"},
+                        "entity_type": {"type": "string", "description": "Entity type (required for entity scope)"},
+                        "entity_id": {"type": "string", "description": "Entity ID (required for entity scope)"},
+                        "format": {"type": "string", "enum": ["json", "prometheus"], "description": "Output format for the sync scope (default: json)"}
+                    },
+                    "required": ["scope"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "get_context_insights".into(),
+                description: Some("Get project-level analytics and insights".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The ID of the project to analyze"}
                     },
                     "required": ["project_id"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
             },
-        ];
+            Tool {
+                name: "generate_context_digest".into(),
+                description: Some("Write a narrative summary of a feature area's rules, decisions, requirements and feature description with a language model ([digests]: the client's model via sampling, or a configured API) and store it; it is refreshed when those entities change".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The ID of the project"},
+                        "feature_area": {"type": "string", "description": "The feature area to summarize (e.g., 'authentication', 'payments')"},
+                        "force": {"type": "boolean", "description": "Regenerate even if the summarized entities are unchanged (default: false)"}
+                    },
+                    "required": ["project_id", "feature_area"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "get_context_digest".into(),
+                description: Some("Read the stored digests of a project, or of one feature area, with whether they are stale".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The ID of the project"},
+                        "feature_area": {"type": "string", "description": "Only this feature area's digest"}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "get_tag_suggestions".into(),
+                description: Some("List tags suggested for entities as they were created or updated (domain area, layer, feature area), with confidence; confident ones were applied, the rest wait for review".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The ID of the project"},
+                        "status": {"type": "string", "enum": ["pending", "applied", "accepted", "rejected", "all"], "description": "Suggestions in this state (default: pending)"}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "review_tag_suggestions".into(),
+                description: Some("Accept suggested tags, tagging their entities, or reject them, removing tags that were applied automatically".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "suggestion_ids": {"type": "array", "items": {"type": "string"}, "description": "Suggestions to review"},
+                        "action": {"type": "string", "enum": ["accept", "reject"]}
+                    },
+                    "required": ["suggestion_ids", "action"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "verify_context".into(),
+                description: Some("Confirm that business rules, architectural decisions or performance requirements still hold, resetting their freshness; updating an entity confirms it too".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The project the entities belong to"},
+                        "entity_ids": {"type": "array", "items": {"type": "string"}, "description": "Entities to confirm"},
+                        "note": {"type": "string", "description": "Optional note on how they were confirmed"}
+                    },
+                    "required": ["project_id", "entity_ids"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "promote_to_shared".into(),
+                description: Some("Copy business rules and conventions that apply beyond one project to the shared organization project, which query_context merges in with include_shared; promoting again refreshes the copy".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The project the entities belong to"},
+                        "entity_ids": {"type": "array", "items": {"type": "string"}, "description": "Business rules and conventions to promote"},
+                        "shared_project_id": {"type": "string", "description": "The shared project (default: [shared_context] project_id)"}
+                    },
+                    "required": ["project_id", "entity_ids"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "submit_for_review".into(),
+                description: Some("Ask people to review entities, e.g. context an AI agent wrote; they stay unverified until record_review gives a verdict".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The project the entities belong to"},
+                        "entity_ids": {"type": "array", "items": {"type": "string"}, "description": "Entities to review"},
+                        "requested_by": {"type": "string", "description": "Who asks for the review (default: the calling client)"},
+                        "note": {"type": "string", "description": "What reviewers should check"}
+                    },
+                    "required": ["project_id", "entity_ids"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "record_review".into(),
+                description: Some("Record a reviewer's verdict on an entity, verified or disputed, completing its pending review; verifying also resets its freshness".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The project the entity belongs to"},
+                        "entity_id": {"type": "string", "description": "The reviewed entity"},
+                        "review_id": {"type": "string", "description": "The review request to complete, instead of entity_id"},
+                        "verdict": {"type": "string", "enum": ["verified", "disputed"], "description": "Whether the entity is right"},
+                        "reviewer": {"type": "string", "description": "Who reviewed it"},
+                        "comment": {"type": "string", "description": "Why, especially when disputed"}
+                    },
+                    "required": ["project_id", "verdict", "reviewer"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "list_reviews".into(),
+                description: Some("List a project's review requests and verdicts, newest first; by default the pending ones".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The project to list reviews of"},
+                        "status": {"type": "string", "enum": ["pending", "verified", "disputed", "all"], "description": "Reviews to list", "default": "pending"},
+                        "entity_id": {"type": "string", "description": "Only reviews of this entity"}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "list_stale_context".into(),
+                description: Some("List a project's business rules, architectural decisions and performance requirements whose freshness (halving every [freshness] half-life since they were last verified or created) fell below the stale threshold, least fresh first".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The project to report on"},
+                        "entity_type": {"type": "string", "enum": ["business_rule", "architectural_decision", "performance_requirement"], "description": "Only report this entity type"},
+                        "stale_below": {"type": "number", "minimum": 0, "maximum": 1, "description": "Freshness threshold (default: [freshness] stale_below)"},
+                        "limit": {"type": "integer", "minimum": 0, "description": "Most entities to list", "default": 50}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "analyze_context_gaps".into(),
+                description: Some("List the feature areas a project's specifications, framework components and feature contexts reference that its business rules, security policies and conventions cover thinly or not at all, with suggestions for what to document".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The project to analyze"},
+                        "spec_projects": {"type": "array", "items": {"type": "string"}, "description": "Projects the specifications were imported under besides project_id, e.g. Kiro spec directories"},
+                        "min_items": {"type": "integer", "minimum": 1, "description": "Rules, policies and conventions an area needs to count as covered", "default": 3},
+                        "limit": {"type": "integer", "minimum": 0, "description": "Most areas to list", "default": 50}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "generate_quality_report".into(),
+                description: Some("Generate a context health assessment and quality report".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "start_date": {"type": "string", "format": "date-time", "description": "Start date for the report (ISO 8601 format)"},
+                        "end_date": {"type": "string", "format": "date-time", "description": "End date for the report (ISO 8601 format)"},
+                        "project_id": {"type": "string", "description": "Optional project ID to filter the report"}
+                    },
+                    "required": ["start_date", "end_date"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "export_analytics_data".into(),
+                description: Some("Export analytics events for data portability and external analysis: a JSON report with the events, CSV with one row per event, or a Parquet file for DuckDB, BigQuery and other data warehouses".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "format": {"type": "string", "enum": ["json", "csv", "parquet"], "description": "Export format; parquet is written to output_path", "default": "json"},
+                        "output_path": {"type": "string", "description": "Parquet file to write, relative to the export directory (defaults to analytics-events-<start>-<end>.parquet)"},
+                        "start_date": {"type": "string", "format": "date-time", "description": "Start date for export (ISO 8601 format)"},
+                        "end_date": {"type": "string", "format": "date-time", "description": "End date for export (ISO 8601 format)"},
+                        "project_id": {"type": "string", "description": "Optional project ID to filter the export"},
+                        "event_types": {"type": "array", "items": {"type": "string"}, "description": "Optional array of event types to include"}
+                    },
+                    "required": ["start_date", "end_date"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "prune_analytics".into(),
+                description: Some("Fold analytics events older than the retention period into daily aggregates and delete them, reporting rows removed and space reclaimed".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "retention_days": {"type": "integer", "minimum": 1, "description": "Days of raw events to keep (defaults to retention_days in [analytics] of config.toml)"},
+                        "vacuum": {"type": "boolean", "description": "Also VACUUM the database to return the freed space to the file system", "default": false}
+                    }
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "run_scheduled_reports".into(),
+                description: Some("Generate a project's scheduled quality and specification health reports now and save them as scheduled_report entities, delivering them to the webhooks and Slack configured in [reports]".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The project to report on"},
+                        "kinds": {"type": "array", "items": {"type": "string", "enum": ["quality", "specification_health"]}, "description": "Reports to generate (defaults to kinds in [reports] of config.toml)"}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "generate_dashboard".into(),
+                description: Some("Render a project's usage, velocity and quality data into a self-contained HTML dashboard with embedded charts and write it to disk as a shareable snapshot".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The project to chart"},
+                        "days": {"type": "integer", "minimum": 1, "maximum": 366, "description": "Days of usage to chart", "default": 30},
+                        "output_path": {"type": "string", "description": "File to write, relative to the export directory (defaults to context-dashboard-<project>-<date>.html)"}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "get_audit_log".into(),
+                description: Some("Read the audit log of tool calls, newest first: tool, calling client, project, arguments hash, outcome and duration of each call. Kept apart from usage analytics, so [analytics] settings don't affect it".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "tool": {"type": "string", "description": "Only calls of this tool"},
+                        "caller": {"type": "string", "description": "Only calls from this client, as name/version"},
+                        "project_id": {"type": "string", "description": "Only calls with this project_id argument"},
+                        "outcome": {"type": "string", "enum": ["success", "error"], "description": "Only successful or failed calls"},
+                        "since": {"type": "string", "format": "date-time", "description": "Only calls at or after this time (ISO 8601)"},
+                        "until": {"type": "string", "format": "date-time", "description": "Only calls at or before this time (ISO 8601)"},
+                        "limit": {"type": "integer", "minimum": 1, "maximum": 1000, "description": "Most entries to return", "default": 100}
+                    }
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "get_unused_context".into(),
+                description: Some("List a project's entities that query_context, search_context and get_entity have not returned in the last N days, and the least used ones, as candidates for pruning; can tag the unused ones `stale`".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The project to report on"},
+                        "days": {"type": "integer", "minimum": 1, "description": "Length of the window in days", "default": 30},
+                        "limit": {"type": "integer", "minimum": 0, "description": "Most least-used entities to list", "default": 20},
+                        "tag_stale": {"type": "boolean", "description": "Tag the unused entities `stale` and untag listed entities used again", "default": false}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+
+            Tool {
+                name: "suggest_context".into(),
+                description: Some("Recommend entities that past query_context calls similar to the given feature area and components returned, or led their client to open from a search, beyond what similarity search finds".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The project to recommend from"},
+                        "feature_area": {"type": "string", "description": "Feature area of the new query"},
+                        "task_type": {"type": "string", "description": "Task type of the new query; past queries of the same type count more"},
+                        "components": {"type": "array", "items": {"type": "string"}, "description": "Components of the new query"},
+                        "days": {"type": "integer", "minimum": 1, "description": "How far back to look at past queries", "default": 90},
+                        "min_similarity": {"type": "number", "minimum": 0, "maximum": 1, "description": "Least similarity of a past query to the new one", "default": 0.3},
+                        "limit": {"type": "integer", "minimum": 0, "description": "Most entities to recommend", "default": 10}
+                    },
+                    "required": ["project_id", "feature_area"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+
+            // Specification Import and Management Tools
+            Tool {
+                name: "scan_specifications".into(),
+                description: Some("Scan and import all Kiro specifications from .kiro/specs directory".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "base_path": {"type": "string", "description": "Base path to scan for specifications (defaults to every Kiro root and source in [specs] of config.toml)"}
+                    }
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "import_specification".into(),
+                description: Some("Import a single specification file and suggest links from its requirements to existing business rules, architectural decisions and components".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "file_path": {"type": "string", "description": "Path to the specification file to import"},
+                        "project_id": {"type": "string", "description": "Project whose business rules, decisions and components are searched for suggested links (defaults to the specification's .kiro/specs directory name)"}
+                    },
+                    "required": ["file_path"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "accept_suggested_links".into(),
+                description: Some("Accept links from a specification's requirements to business rules, architectural decisions and components that were suggested by hybrid search when it was imported".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "spec_id": {"type": "string", "description": "ID of the specification"},
+                        "links": {
+                            "type": "array",
+                            "description": "Suggestions to accept (defaults to every suggestion of the specification)",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "requirement_id": {"type": "string"},
+                                    "context_id": {"type": "string"}
+                                },
+                                "required": ["requirement_id", "context_id"]
+                            }
+                        },
+                        "min_confidence": {"type": "number", "description": "Skip suggestions scored below this, from 0 to 1"},
+                        "dry_run": {"type": "boolean", "description": "List the suggestions without accepting any (default: false)"}
+                    },
+                    "required": ["spec_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "validate_specification".into(),
+                description: Some("Validate a specification file and return validation issues, including acceptance criteria that do not follow EARS patterns with suggested rewrites, and a quality score for requirements".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "file_path": {"type": "string", "description": "Path to the specification file to validate"}
+                    },
+                    "required": ["file_path"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "export_specification".into(),
+                description: Some("Write a stored specification back to Kiro markdown, with its requirements, acceptance criteria and current task statuses".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "spec_id": {"type": "string", "description": "ID of the specification"},
+                        "output_path": {"type": "string", "description": "File to write, relative to the export directory (defaults to specifications/<spec_id>/<imported file name>)"},
+                        "overwrite_source": {"type": "boolean", "description": "Write to the file the specification was imported from instead, if it is inside a configured specification directory (default: false)"},
+                        "dry_run": {"type": "boolean", "description": "Return the markdown without writing it (default: false)"}
+                    },
+                    "required": ["spec_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "generate_spec_template".into(),
+                description: Some("Scaffold requirements.md, design.md and tasks.md for a new feature, pre-filled from the project's components, conventions, phases and feature context".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "Project whose context fills the templates"},
+                        "feature_name": {"type": "string", "description": "Name of the feature; a feature context with this name supplies personas, workflows and edge cases"},
+                        "description": {"type": "string", "description": "Introduction for the documents"},
+                        "output_dir": {"type": "string", "description": "Directory to write to, relative to the first Kiro root (defaults to <feature-name>)"},
+                        "overwrite": {"type": "boolean", "description": "Replace documents that already exist (default: false)"},
+                        "dry_run": {"type": "boolean", "description": "Return the documents without writing them (default: false)"}
+                    },
+                    "required": ["project_id", "feature_name"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "get_workflow_state".into(),
+                description: Some("Show a specification's or architectural decision's review state, required approvers, approvals and transition history".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "entity_type": {"type": "string", "enum": ["specification", "architectural_decision"]},
+                        "entity_id": {"type": "string", "description": "ID of the specification or decision"}
+                    },
+                    "required": ["entity_type", "entity_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "transition_workflow".into(),
+                description: Some("Move a specification or architectural decision through review: draft -> in_review -> approved -> deprecated, or in_review back to draft. Approval requires every required approver to have approved".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "entity_type": {"type": "string", "enum": ["specification", "architectural_decision"]},
+                        "entity_id": {"type": "string", "description": "ID of the specification or decision"},
+                        "to_state": {"type": "string", "enum": ["draft", "in_review", "approved", "deprecated"]},
+                        "actor": {"type": "string", "description": "Who makes the transition"},
+                        "comment": {"type": "string"},
+                        "approvers": {"type": "array", "items": {"type": "string"}, "description": "Required approvers when moving to in_review (defaults to [specs] required_approvers)"}
+                    },
+                    "required": ["entity_type", "entity_id", "to_state", "actor"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "approve_workflow_item".into(),
+                description: Some("Approve a specification or architectural decision in review; it becomes approved once every required approver has approved".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "entity_type": {"type": "string", "enum": ["specification", "architectural_decision"]},
+                        "entity_id": {"type": "string", "description": "ID of the specification or decision"},
+                        "approver": {"type": "string"},
+                        "comment": {"type": "string"}
+                    },
+                    "required": ["entity_type", "entity_id", "approver"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "manage_decision_links".into(),
+                description: Some("Link architectural decisions: a decision that supersedes another marks it superseded, one that amends another leaves it current, relates_to only records the relation. query_context returns the head of each supersede chain".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "action": {"type": "string", "enum": ["link", "unlink", "list"]},
+                        "project_id": {"type": "string", "description": "Project of the decisions"},
+                        "from_decision_id": {"type": "string", "description": "The newer decision (link, unlink)"},
+                        "to_decision_id": {"type": "string", "description": "The decision it supersedes, amends or relates to (link, unlink)"},
+                        "link_type": {"type": "string", "enum": ["supersedes", "amends", "relates_to"]},
+                        "decision_id": {"type": "string", "description": "Only list the links of this decision, with the head of its chain (list)"}
+                    },
+                    "required": ["action", "project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "generate_traceability_matrix".into(),
+                description: Some("Trace requirements to the tasks implementing them, the framework components they touch and the git commits changing them, highlight coverage gaps and save the matrix as a traceability_report entity".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "Project whose framework components are traced and that owns the report"},
+                        "spec_project": {"type": "string", "description": "Name the specifications were imported under, i.e. their .kiro/specs directory (defaults to project_id)"},
+                        "repository_path": {"type": "string", "description": "Git repository to read commits from (default: the working directory)"},
+                        "commit_limit": {"type": "integer", "description": "Number of recent commits to read (default: 500)"}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "analyze_requirement_coverage".into(),
+                description: Some("List requirements that have no linked tasks, no linked framework components, or that name components (in backticks, e.g. `PaymentService`) the project does not have".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "Project whose framework components are checked"},
+                        "spec_project": {"type": "string", "description": "Name the specifications were imported under, i.e. their .kiro/specs directory (defaults to project_id)"}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "scan_spec_sources".into(),
+                description: Some("Import every [specs] source in config.toml in its format (kiro, adr, gherkin or markdown) into one project".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "Project that ADR, Gherkin and markdown documents are imported into; Kiro specifications keep their .kiro/specs directory name"}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "start_spec_monitoring".into(),
+                description: Some("Start monitoring .kiro/specs directory for changes".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "base_path": {"type": "string", "description": "Base path to monitor (defaults to the first Kiro root in [specs] of config.toml)"}
+                    }
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "scan_adrs".into(),
+                description: Some("Scan architecture decision records (MADR or Nygard markdown, NNNN-*.md) and import them as architectural decisions".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "Project the decisions belong to"},
+                        "base_path": {"type": "string", "description": "Directory to scan for ADRs (defaults to every ADR root and source in [specs] of config.toml)"}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "start_adr_monitoring".into(),
+                description: Some("Start monitoring an ADR directory, re-importing decisions as their files change".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "Project the decisions belong to"},
+                        "base_path": {"type": "string", "description": "Directory to monitor (defaults to the first ADR root in [specs] of config.toml)"}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "import_openapi".into(),
+                description: Some("Import an OpenAPI 3 or Swagger 2 document (JSON or YAML) as a feature context and framework component per endpoint group, linked to related business rules".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "Project the API belongs to"},
+                        "file_path": {"type": "string", "description": "Path to the OpenAPI document"}
+                    },
+                    "required": ["project_id", "file_path"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "get_specification_versions".into(),
+                description: Some("Get all versions of a specification".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "spec_id": {"type": "string", "description": "ID of the specification"}
+                    },
+                    "required": ["spec_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "compare_specification_versions".into(),
+                description: Some("Compare two versions of a specification".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "version1_id": {"type": "string", "description": "ID of the first version"},
+                        "version2_id": {"type": "string", "description": "ID of the second version"}
+                    },
+                    "required": ["version1_id", "version2_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "squash_spec_versions".into(),
+                description: Some("Keep the milestone versions of a specification, or of every specification of a project, fold the versions between them into the next kept one with their diffs, and delete versions past the retention age".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "spec_id": {"type": "string", "description": "ID of the specification"},
+                        "project_id": {"type": "string", "description": "Squash every specification of this project instead"},
+                        "keep_recent": {"type": "integer", "description": "Newest versions that are never squashed (default from [specs.versions])"},
+                        "milestone_interval_days": {"type": "integer", "description": "Keep the last version of every this many days; 0 keeps none"},
+                        "max_age_days": {"type": "integer", "description": "Delete versions older than this many days; 0 keeps them"},
+                        "dry_run": {"type": "boolean", "description": "Only report what would be kept, squashed and deleted"}
+                    }
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+
+            // Specification Analytics Tools
+            Tool {
+                name: "track_requirements_progress".into(),
+                description: Some("Track progress for all requirements in a project, including completion percentages, linked tasks, and acceptance criteria status".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The ID of the project to track requirements progress for"}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "track_tasks_progress".into(),
+                description: Some("Track progress for all tasks in a project, including status, completion percentage, time tracking, and dependencies".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The ID of the project to track tasks progress for"}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "analyze_specification_completeness".into(),
+                description: Some("Analyze completeness of specifications in a project, including content quality, missing sections, and recommendations".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The ID of the project to analyze specification completeness for"}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "calculate_development_velocity".into(),
+                description: Some("Calculate development velocity metrics based on task and requirement completion over a specified time period, with daily open/closed task counts and weekly requirement completions for charting".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The ID of the project to calculate velocity for"},
+                        "days": {"type": "integer", "description": "Number of days to look back for velocity calculation", "default": 30, "minimum": 1, "maximum": 365}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+            Tool {
+                name: "generate_specification_health_report".into(),
+                description: Some("Generate a comprehensive health report for all specifications in a project, including progress, completeness, velocity, and recommendations".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "project_id": {"type": "string", "description": "The ID of the project to generate health report for"}
+                    },
+                    "required": ["project_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+            },
+        ]
+    }
+
+    /// Query cache shared by all tool calls
+    pub fn query_cache(&self) -> Arc<QueryCache> {
+        self.container.query_cache.clone()
+    }
+
+    /// WebSocket clients, delivered every change made through tool calls
+    pub fn websocket_manager(&self) -> Arc<WebSocketManager> {
+        self.container.websocket_manager.clone()
+    }
+}
+
+impl ServerHandler for EnhancedContextMcpServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            protocol_version: ProtocolVersion::default(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_resources()
+                .build(),
+            server_info: Implementation {
+                name: "enhanced-context-server-rs".to_string(),
+                version: "0.2.0".to_string(),
+            },
+            instructions: Some("Enhanced Context Server with comprehensive CRUD operations for AI Code Generation. Provides curated project context including business rules, architectural decisions, security policies, project conventions, feature contexts, and framework-agnostic components.".to_string()),
+        }
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        tracing::debug!("Received list_tools request for enhanced server");
+
+        Ok(ListToolsResult {
+            tools: Self::tools(),
+            next_cursor: None,
+        })
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        tracing::debug!("Received call_tool request: {}", request.name);
+
+        let tool = request.name.clone();
+        let cache_writes = Self::cache_writes(&request);
+        let reads_only = Self::reads_only(&request);
+        let client_agent = context
+            .peer
+            .peer_info()
+            .map(|info| format!("{}/{}", info.client_info.name, info.client_info.version));
+        let mut audit = Self::tool_call_audit(&request, client_agent.clone());
+        let started = Instant::now();
+        let result = CLIENT_AGENT
+            .scope(client_agent, CLIENT_PEER.scope(context.peer.clone(), self.dispatch_tool(request)))
+            .await;
+        audit.duration_ms = started.elapsed().as_millis() as u64;
+        self.audit_tool_call(audit, &result).await;
+        if let Ok(result) = &result {
+            self.invalidate_writes(&cache_writes, reads_only);
+            self.notify_changes(&tool, &cache_writes, result).await;
+            self.refresh_digests(&tool, &cache_writes, result, context.peer).await;
+            self.suggest_tags(&tool, &cache_writes, result);
+            self.verify_updated(&tool, &cache_writes, result).await;
+        }
+        result
+    }
+}
+
+impl EnhancedContextMcpServer {
+    /// Run a tool without an MCP client, as the `call` CLI command does: audited, and
+    /// reported to change listeners like a client's call
+    pub async fn call_tool_directly(&self, request: CallToolRequestParam, caller: &str) -> Result<CallToolResult, McpError> {
+        let tool = request.name.clone();
+        let cache_writes = Self::cache_writes(&request);
+        let reads_only = Self::reads_only(&request);
+        let mut audit = Self::tool_call_audit(&request, Some(caller.to_string()));
+        let started = Instant::now();
+        let result = CLIENT_AGENT.scope(Some(caller.to_string()), self.dispatch_tool(request)).await;
+        audit.duration_ms = started.elapsed().as_millis() as u64;
+        self.audit_tool_call(audit, &result).await;
+        if let Ok(result) = &result {
+            self.invalidate_writes(&cache_writes, reads_only);
+            self.notify_changes(&tool, &cache_writes, result).await;
+        }
+        result
+    }
+
+    /// Audit record of a tool call by `caller`, before it runs
+    fn tool_call_audit(request: &CallToolRequestParam, caller: Option<String>) -> ToolCallAudit {
+        ToolCallAudit {
+            id: uuid::Uuid::new_v4().to_string(),
+            tool: request.name.to_string(),
+            caller,
+            project_id: request
+                .arguments
+                .as_ref()
+                .and_then(|args| args.get("project_id"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            arguments_hash: ToolCallAudit::hash_arguments(request.arguments.as_ref()),
+            outcome: ToolCallOutcome::Success,
+            error_code: None,
+            error_message: None,
+            duration_ms: 0,
+            created_at: ToolCallAudit::timestamp(chrono::Utc::now()),
+        }
+    }
+
+    /// Entities written by a tool call, as (entity_type, id) pairs, so cached reads can be dropped
+    fn cache_writes(request: &CallToolRequestParam) -> Vec<(String, Option<String>)> {
+        let Some(args) = &request.arguments else {
+            return Vec::new();
+        };
+        let arg = |name: &str| args.get(name).and_then(|v| v.as_str()).map(str::to_string);
+
+        match request.name.as_ref() {
+            "create_entity" | "update_entity" | "delete_entity" => match arg("entity_type") {
+                Some(entity_type) => vec![(entity_type, arg("id"))],
+                None => Vec::new(),
+            },
+            "bulk_operations" => match arg("entity_type") {
+                Some(entity_type) if arg("operation").is_some() => vec![(entity_type, None)],
+                _ => Vec::new(),
+            },
+            "manage_project" => match arg("action").as_deref() {
+                Some("create" | "update" | "delete") => vec![("project".to_string(), arg("id"))],
+                _ => Vec::new(),
+            },
+            "bulk_create_components" | "bulk_update_components" | "bulk_delete_components" | "scan_codebase" => {
+                vec![("framework_component".to_string(), None)]
+            }
+            "scan_adrs" | "scan_spec_sources" => vec![("architectural_decision".to_string(), None)],
+            "generate_traceability_matrix" => vec![("traceability_report".to_string(), None)],
+            "run_scheduled_reports" => vec![("scheduled_report".to_string(), None)],
+            "transition_workflow" | "approve_workflow_item" => match arg("entity_type") {
+                Some(entity_type) => vec![(entity_type, arg("entity_id"))],
+                None => Vec::new(),
+            },
+            "manage_decision_links" => match arg("action").as_deref() {
+                Some("link" | "unlink") => vec![
+                    ("architectural_decision".to_string(), arg("from_decision_id")),
+                    ("architectural_decision".to_string(), arg("to_decision_id")),
+                ],
+                _ => Vec::new(),
+            },
+            "manage_glossary" => match arg("action").as_deref() {
+                Some("add" | "remove") => vec![("glossary_term".to_string(), None)],
+                _ => Vec::new(),
+            },
+            "manage_custom_entity_types" => match arg("action").as_deref() {
+                Some("define" | "delete") => vec![("custom_entity".to_string(), None)],
+                _ => Vec::new(),
+            },
+            "import_openapi" => vec![
+                ("feature_context".to_string(), None),
+                ("framework_component".to_string(), None),
+            ],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Whether a tool call only reads. Any other call may change what `query_context`
+    /// assembles (tags, reviews, links, glossary, shared context), so it drops those results
+    fn reads_only(request: &CallToolRequestParam) -> bool {
+        let arg = |name: &str| request.arguments.as_ref().and_then(|args| args.get(name));
+        match request.name.as_ref() {
+            "get_unused_context" => arg("tag_stale").and_then(|v| v.as_bool()) != Some(true),
+            name if READ_ONLY_TOOLS.contains(&name) => true,
+            _ => matches!(arg("action").and_then(|v| v.as_str()), Some("get" | "list" | "expand" | "stats")),
+        }
+    }
+
+    /// Drop the cached reads a successful tool call may have made stale
+    fn invalidate_writes(&self, writes: &[(String, Option<String>)], reads_only: bool) {
+        for (entity_type, id) in writes {
+            self.invalidate_cached(entity_type, id.as_deref());
+        }
+        if !reads_only {
+            self.container.query_cache.invalidate_namespace("context");
+        }
+    }
+
+    /// Drop cached reads affected by a write to `entity_type`; without an id (creates, bulk
+    /// writes) every cached entity of that type is dropped, including remembered misses
+    fn invalidate_cached(&self, entity_type: &str, id: Option<&str>) {
+        let cache = &self.container.query_cache;
+        match id {
+            Some(id) => {
+                if let Some(key) = CacheKeyBuilder::entity(entity_type, id) {
+                    cache.invalidate(&key);
+                }
+            }
+            None => {
+                if let Some(key) = CacheKeyBuilder::entity(entity_type, "") {
+                    cache.invalidate_namespace(CacheKeyBuilder::namespace(&key));
+                }
+            }
+        }
+        if let Some(namespace) = CacheKeyBuilder::list_namespace(entity_type) {
+            cache.invalidate_namespace(namespace);
+        }
+        // query_context results aggregate several entity types
+        cache.invalidate_namespace("context");
+    }
+
+    /// Report entities written by create/update/delete_entity to change detection, which
+    /// broadcasts them and keeps the search index current
+    async fn notify_changes(&self, tool: &str, writes: &[(String, Option<String>)], result: &CallToolResult) {
+        let detector = &self.container.change_detection_service;
+        for (entity_type, id) in writes {
+            let outcome = match (tool, id) {
+                ("create_entity" | "update_entity", _) => {
+                    let Some(entity) = result
+                        .content
+                        .first()
+                        .and_then(|content| content.as_text())
+                        .and_then(|text| serde_json::from_str::<serde_json::Value>(&text.text).ok())
+                    else {
+                        continue;
+                    };
+                    let Some(entity_id) = entity.get("id").and_then(|v| v.as_str()).map(str::to_string) else {
+                        continue;
+                    };
+                    let project_id = match entity_type.as_str() {
+                        "project" => entity_id.clone(),
+                        _ => entity.get("project_id").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                    };
+                    if tool == "create_entity" {
+                        detector
+                            .notify_entity_created(entity_type, &entity_id, &project_id, entity, MCP_CLIENT_ID, None)
+                            .await
+                    } else {
+                        detector
+                            .notify_entity_updated(
+                                entity_type,
+                                &entity_id,
+                                &project_id,
+                                serde_json::Value::Null,
+                                entity,
+                                MCP_CLIENT_ID,
+                                None,
+                            )
+                            .await
+                    }
+                }
+                ("delete_entity", Some(id)) => {
+                    detector
+                        .notify_entity_deleted(entity_type, id, "", serde_json::Value::Null, MCP_CLIENT_ID, None)
+                        .await
+                }
+                _ => Ok(()),
+            };
+            if let Err(e) = outcome {
+                tracing::warn!("Failed to report change to {} {:?}: {}", entity_type, id, e);
+            }
+        }
+    }
+
+    /// The `[digests]` model; sampling goes to the client whose tool call is running.
+    /// `None` when digests are off
+    fn digest_generator(&self) -> Result<Option<Arc<dyn DigestGenerator>>, McpError> {
+        let peer = CLIENT_PEER.try_with(Clone::clone).ok();
+        DigestGeneratorFactory::from_settings(&self.container.config_manager.current().digests, peer)
+            .map_err(|e| McpError::invalid_request(e.to_string(), None))
+    }
+
+    /// Mark the digests summarizing entities written by create/update/delete_entity stale and,
+    /// with `[digests] refresh_on_change`, regenerate them in the background through `peer`
+    async fn refresh_digests(
+        &self,
+        tool: &str,
+        writes: &[(String, Option<String>)],
+        result: &CallToolResult,
+        peer: rmcp::service::Peer<rmcp::service::RoleServer>,
+    ) {
+        let written = result
+            .content
+            .first()
+            .and_then(|content| content.as_text())
+            .and_then(|text| serde_json::from_str::<serde_json::Value>(&text.text).ok());
+        let service = self.container.context_intelligence_service.clone();
+
+        let mut stale: Vec<crate::models::context_digest::ContextDigest> = Vec::new();
+        for (entity_type, id) in writes {
+            let (entity_id, project_id, feature_area) = match (tool, id, &written) {
+                ("create_entity" | "update_entity", _, Some(entity)) if entity_type != "project" => {
+                    let field = |name: &str| entity.get(name).and_then(|v| v.as_str()).map(str::to_string);
+                    let Some(entity_id) = field("id") else {
+                        continue;
+                    };
+                    let area = field("domain_area").or_else(|| field("component_area")).or_else(|| field("feature_name"));
+                    (entity_id, field("project_id").unwrap_or_default(), area)
+                }
+                ("delete_entity", Some(id), _) => (id.clone(), String::new(), None),
+                _ => continue,
+            };
+            match service.mark_digests_stale(&entity_id, &project_id, feature_area.as_deref()).await {
+                Ok(digests) => {
+                    for digest in digests {
+                        if !stale.iter().any(|known| known.id == digest.id) {
+                            stale.push(digest);
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to mark digests of {} {} stale: {}", entity_type, entity_id, e),
+            }
+        }
+        if stale.is_empty() {
+            return;
+        }
+
+        let settings = self.container.config_manager.current().digests;
+        if !settings.refresh_on_change {
+            return;
+        }
+        let generator = match DigestGeneratorFactory::from_settings(&settings, Some(peer)) {
+            Ok(Some(generator)) => generator,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::debug!("Leaving {} digests stale: {}", stale.len(), e);
+                return;
+            }
+        };
+        tokio::spawn(async move {
+            for digest in stale {
+                if let Err(e) = service
+                    .generate_context_digest(&digest.project_id, &digest.feature_area, generator.as_ref(), false)
+                    .await
+                {
+                    tracing::warn!(
+                        "Failed to refresh the digest of {} in project {}: {}",
+                        digest.feature_area,
+                        digest.project_id,
+                        e
+                    );
+                }
+            }
+        });
+    }
+
+    /// Classify entities written by create/update_entity into tags in the background
+    fn suggest_tags(&self, tool: &str, writes: &[(String, Option<String>)], result: &CallToolResult) {
+        if !matches!(tool, "create_entity" | "update_entity") {
+            return;
+        }
+        let Some(entity) = result
+            .content
+            .first()
+            .and_then(|content| content.as_text())
+            .and_then(|text| serde_json::from_str::<serde_json::Value>(&text.text).ok())
+        else {
+            return;
+        };
+        let Some((entity_type, _)) = writes.first().filter(|(entity_type, _)| entity_type != "project") else {
+            return;
+        };
+        let Some(project_id) = entity.get("project_id").and_then(|v| v.as_str()).map(str::to_string) else {
+            return;
+        };
+
+        let entity_type = entity_type.clone();
+        let service = self.container.context_intelligence_service.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service.suggest_tags(&project_id, &entity_type, &entity).await {
+                tracing::warn!("Failed to suggest tags for a {} in project {}: {}", entity_type, project_id, e);
+            }
+        });
+    }
+
+    /// Updating a rule, decision or requirement confirms it, which resets its freshness
+    async fn verify_updated(&self, tool: &str, writes: &[(String, Option<String>)], result: &CallToolResult) {
+        if tool != "update_entity" {
+            return;
+        }
+        let Some((entity_type, Some(id))) = writes
+            .first()
+            .filter(|(entity_type, _)| FRESHNESS_ENTITY_TYPES.contains(&entity_type.as_str()))
+        else {
+            return;
+        };
+        let Some(project_id) = result
+            .content
+            .first()
+            .and_then(|content| content.as_text())
+            .and_then(|text| serde_json::from_str::<serde_json::Value>(&text.text).ok())
+            .and_then(|entity| entity.get("project_id").and_then(|v| v.as_str()).map(str::to_string))
+        else {
+            return;
+        };
+
+        let mut verification = EntityVerification::new(&project_id, entity_type, id);
+        verification.verified_by = CLIENT_AGENT.try_with(Clone::clone).ok().flatten();
+        if let Err(e) = self.container.entity_verification_repository.record(&verification).await {
+            tracing::warn!("Failed to record the verification of {} {}: {}", entity_type, id, e);
+        }
+    }
+
+    /// Reject a `severity` or `classification` outside the known values
+    fn validate_security_policy_fields(data: &serde_json::Map<String, serde_json::Value>) -> Result<(), McpError> {
+        let checks = [
+            ("severity", SecurityPolicy::SEVERITIES),
+            ("classification", SecurityPolicy::CLASSIFICATIONS),
+        ];
+        for (field, allowed) in checks {
+            if let Some(value) = data.get(field).and_then(|v| v.as_str()) {
+                if !allowed.contains(&value) {
+                    return Err(McpError::invalid_params(
+                        format!("{field} must be one of {}", allowed.join(", ")),
+                        None,
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the fields of a security policy that `data` gives, leaving the others as they are
+    fn apply_security_policy_fields(
+        policy: &mut SecurityPolicy,
+        data: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), McpError> {
+        Self::validate_security_policy_fields(data)?;
+        let text = |field: &str| data.get(field).and_then(|v| v.as_str()).map(str::to_string);
+        if let Some(policy_name) = text("policy_name") {
+            policy.policy_name = policy_name;
+        }
+        let optional = [
+            ("policy_area", &mut policy.policy_area),
+            ("requirements", &mut policy.requirements),
+            ("implementation_pattern", &mut policy.implementation_pattern),
+            ("forbidden_patterns", &mut policy.forbidden_patterns),
+            ("compliance_notes", &mut policy.compliance_notes),
+            ("severity", &mut policy.severity),
+            ("classification", &mut policy.classification),
+        ];
+        for (field, value) in optional {
+            if data.contains_key(field) {
+                *value = text(field);
+            }
+        }
+        Ok(())
+    }
+
+    /// Reject a `convention_type` or `enforcement_level` outside the known values
+    fn validate_convention_fields(data: &serde_json::Map<String, serde_json::Value>) -> Result<(), McpError> {
+        let checks = [
+            ("convention_type", ProjectConvention::CONVENTION_TYPES),
+            ("enforcement_level", ProjectConvention::ENFORCEMENT_LEVELS),
+        ];
+        for (field, allowed) in checks {
+            if let Some(value) = data.get(field).and_then(|v| v.as_str()) {
+                if !allowed.contains(&value) {
+                    return Err(McpError::invalid_params(
+                        format!("{field} must be one of {}", allowed.join(", ")),
+                        None,
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// A text field of `data`; arrays and objects are given as JSON
+    fn json_text(data: &serde_json::Map<String, serde_json::Value>, field: &str) -> Option<String> {
+        match data.get(field) {
+            Some(serde_json::Value::String(value)) => Some(value.clone()),
+            Some(serde_json::Value::Null) | None => None,
+            Some(value) => Some(value.to_string()),
+        }
+    }
+
+    /// Set the fields of a convention that `data` gives, leaving the others as they are;
+    /// examples may be given as arrays and are stored as JSON
+    fn apply_convention_fields(
+        convention: &mut ProjectConvention,
+        data: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), McpError> {
+        Self::validate_convention_fields(data)?;
+        let optional = [
+            ("convention_type", &mut convention.convention_type),
+            ("convention_rule", &mut convention.convention_rule),
+            ("pattern", &mut convention.pattern),
+            ("good_examples", &mut convention.good_examples),
+            ("bad_examples", &mut convention.bad_examples),
+            ("rationale", &mut convention.rationale),
+            ("enforcement_level", &mut convention.enforcement_level),
+        ];
+        for (field, value) in optional {
+            if data.contains_key(field) {
+                *value = Self::json_text(data, field);
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the fields of a glossary term that `data` gives; `aliases` is another name for `synonyms`
+    fn apply_glossary_term_fields(
+        entry: &mut GlossaryEntry,
+        data: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), McpError> {
+        if let Some(term) = data.get("term") {
+            entry.term = term
+                .as_str()
+                .ok_or_else(|| McpError::invalid_params("term must be a string", None))?
+                .to_string();
+        }
+        if let Some(aliases) = data.get("aliases").or_else(|| data.get("synonyms")) {
+            entry.synonyms = aliases
+                .as_array()
+                .and_then(|aliases| aliases.iter().map(|a| a.as_str().map(str::to_string)).collect())
+                .ok_or_else(|| McpError::invalid_params("aliases must be an array of strings", None))?;
+        }
+        let optional = [
+            ("definition", &mut entry.definition),
+            ("domain_area", &mut entry.domain_area),
+        ];
+        for (field, value) in optional {
+            if data.contains_key(field) {
+                *value = Self::json_text(data, field);
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the fields of an API contract that `data` gives; schemas are JSON objects, or null
+    /// to remove them, and the linked IDs arrays of strings
+    fn apply_api_contract_fields(
+        contract: &mut ApiContract,
+        data: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), McpError> {
+        if let Some(method) = data.get("method").and_then(|v| v.as_str()) {
+            contract.method = method.to_uppercase();
+        }
+        if let Some(path) = data.get("path").and_then(|v| v.as_str()) {
+            contract.path = path.to_string();
+        }
+        let schemas = [
+            ("request_schema", &mut contract.request_schema),
+            ("response_schema", &mut contract.response_schema),
+        ];
+        for (field, schema) in schemas {
+            if let Some(value) = data.get(field) {
+                *schema = (!value.is_null()).then(|| value.clone());
+            }
+        }
+        let optional = [
+            ("description", &mut contract.description),
+            ("error_conventions", &mut contract.error_conventions),
+        ];
+        for (field, value) in optional {
+            if data.contains_key(field) {
+                *value = Self::json_text(data, field);
+            }
+        }
+        let links = [
+            ("feature_context_ids", &mut contract.feature_context_ids),
+            ("component_ids", &mut contract.component_ids),
+        ];
+        for (field, ids) in links {
+            if let Some(value) = data.get(field) {
+                *ids = value
+                    .as_array()
+                    .and_then(|values| values.iter().map(|v| v.as_str().map(str::to_string)).collect())
+                    .ok_or_else(|| McpError::invalid_params(format!("{field} must be an array of IDs"), None))?;
+            }
+        }
+        contract
+            .check()
+            .map_err(|e| McpError::invalid_params(format!("Invalid API contract: {e}"), None))
+    }
+
+    /// Reject a contract for an endpoint the project already has one for, or linked to
+    /// feature contexts or components outside its project
+    async fn check_api_contract(&self, contract: &ApiContract) -> Result<(), McpError> {
+        let container = &self.container;
+        let existing = container.api_contract_repository.list_by_project(&contract.project_id).await?;
+        if let Some(other) = existing.iter().find(|other| other.id != contract.id && other.signature() == contract.signature()) {
+            return Err(McpError::invalid_params(
+                format!("{} already has the API contract {}", contract.signature(), other.id),
+                None,
+            ));
+        }
+        for id in &contract.feature_context_ids {
+            let feature = container.feature_context_repository.get_by_id(id).await?;
+            if feature.is_none_or(|feature| feature.project_id != contract.project_id) {
+                return Err(McpError::invalid_params(
+                    format!("No feature context {id} in project {}", contract.project_id),
+                    None,
+                ));
+            }
+        }
+        for id in &contract.component_ids {
+            let component = container.framework_service.get_component(id).await?;
+            if component.is_none_or(|component| component.project_id != contract.project_id) {
+                return Err(McpError::invalid_params(
+                    format!("No framework component {id} in project {}", contract.project_id),
+                    None,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reject a feature `status` outside the known values
+    fn validate_feature_context_fields(data: &serde_json::Map<String, serde_json::Value>) -> Result<(), McpError> {
+        if let Some(status) = data.get("status").and_then(|v| v.as_str()) {
+            if !FeatureContext::STATUSES.contains(&status) {
+                return Err(McpError::invalid_params(
+                    format!("status must be one of {}", FeatureContext::STATUSES.join(", ")),
+                    None,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the fields of a feature context that `data` gives, leaving the others as they are;
+    /// list fields such as `constraints` may be given as arrays and are stored as JSON
+    fn apply_feature_context_fields(
+        feature: &mut FeatureContext,
+        data: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), McpError> {
+        Self::validate_feature_context_fields(data)?;
+        if let Some(feature_name) = data.get("feature_name").and_then(|v| v.as_str()) {
+            feature.feature_name = feature_name.to_string();
+        }
+        let optional = [
+            ("business_purpose", &mut feature.business_purpose),
+            ("user_personas", &mut feature.user_personas),
+            ("key_workflows", &mut feature.key_workflows),
+            ("integration_points", &mut feature.integration_points),
+            ("edge_cases", &mut feature.edge_cases),
+            ("constraints", &mut feature.constraints),
+            ("related_components", &mut feature.related_components),
+            ("status", &mut feature.status),
+            ("owner", &mut feature.owner),
+        ];
+        for (field, value) in optional {
+            if data.contains_key(field) {
+                *value = Self::json_text(data, field);
+            }
+        }
+        Ok(())
+    }
+
+    /// The registered custom entity type called `name`
+    async fn custom_entity_type(&self, name: &str) -> Result<CustomEntityType, McpError> {
+        self.container.custom_entity_repository.get_type(name).await?.ok_or_else(|| {
+            McpError::invalid_params(
+                format!("Unknown custom entity type: {name}; define it with manage_custom_entity_types"),
+                None,
+            )
+        })
+    }
+
+    /// Check a custom entity's fields against its type's schema and fill in the text search indexes
+    fn check_custom_entity(entity: &mut CustomEntity, entity_type: &CustomEntityType) -> Result<(), McpError> {
+        entity_type
+            .validate(&entity.fields)
+            .map_err(|e| McpError::invalid_params(format!("Invalid {}: {e}", entity_type.name), None))?;
+        entity.search_text = entity_type.search_text(&entity.fields);
+        Ok(())
+    }
+
+    /// Custom entities as the CRUD tools return them, with the text of their types' searchable fields
+    async fn with_search_text(&self, entities: Vec<CustomEntity>) -> Result<Vec<CustomEntity>, McpError> {
+        let types: HashMap<String, CustomEntityType> = self
+            .container
+            .custom_entity_repository
+            .list_types()
+            .await?
+            .into_iter()
+            .map(|entity_type| (entity_type.name.clone(), entity_type))
+            .collect();
+        Ok(entities
+            .into_iter()
+            .map(|mut entity| {
+                if let Some(entity_type) = types.get(&entity.custom_type) {
+                    entity.search_text = entity_type.search_text(&entity.fields);
+                }
+                entity
+            })
+            .collect())
+    }
+
+    /// Every entity of a project a review can cover: its rules, decisions, requirements,
+    /// components, phases and custom entities, security policies, conventions and feature contexts
+    async fn reviewable_entities(&self, project_id: &str) -> Result<Vec<(&'static str, serde_json::Value)>, McpError> {
+        let container = &self.container;
+        let to_value = |value: serde_json::Result<serde_json::Value>| {
+            value.map_err(|e| McpError::internal_error(format!("Serialization error: {e}"), None))
+        };
+        let mut entities: Vec<(&'static str, serde_json::Value)> = self
+            .project_entities(project_id)
+            .await?
+            .into_iter()
+            .filter(|(entity_type, _)| *entity_type != "project")
+            .collect();
+        for policy in container.security_policy_repository.list_by_project(project_id).await? {
+            entities.push(("security_policy", to_value(serde_json::to_value(policy))?));
+        }
+        for convention in container.project_convention_repository.list_by_project(project_id).await? {
+            entities.push(("project_convention", to_value(serde_json::to_value(convention))?));
+        }
+        for feature in container.feature_context_repository.list_by_project(project_id).await? {
+            entities.push(("feature_context", to_value(serde_json::to_value(feature))?));
+        }
+        Ok(entities)
+    }
+
+    /// Add `verification_status`, `reviewed_by`, `reviewed_at` and `review_pending` to an
+    /// entity, or to each entity of a list
+    fn add_verification(value: &mut serde_json::Value, states: &HashMap<String, EntityVerificationState>) {
+        let unreviewed = EntityVerificationState::default();
+        let entities: Vec<&mut serde_json::Value> = match value {
+            serde_json::Value::Array(items) => items.iter_mut().collect(),
+            entity => vec![entity],
+        };
+        for entity in entities {
+            let Some(id) = entity.get("id").and_then(|v| v.as_str()).map(str::to_string) else {
+                continue;
+            };
+            if let (Some(fields), Ok(serde_json::Value::Object(state))) =
+                (entity.as_object_mut(), serde_json::to_value(states.get(&id).unwrap_or(&unreviewed)))
+            {
+                fields.extend(state);
+            }
+        }
+    }
+
+    /// A `get_entity` result with its verification status, for entity types reviews cover
+    async fn with_entity_verification(
+        &self,
+        entity_type: &str,
+        id: &str,
+        mut entity: serde_json::Value,
+    ) -> Result<serde_json::Value, McpError> {
+        if REVIEWABLE_ENTITY_TYPES.contains(&entity_type) && !entity.is_null() {
+            let reviews = self.container.entity_review_repository.find_by_entity(id).await?;
+            Self::add_verification(&mut entity, &verification_states(&reviews));
+        }
+        Ok(entity)
+    }
+
+    /// A `list_entities` result with the verification status of each entity, for entity types
+    /// reviews cover
+    async fn with_project_verification(
+        &self,
+        entity_type: &str,
+        project_id: Option<&str>,
+        mut entities: serde_json::Value,
+    ) -> Result<serde_json::Value, McpError> {
+        if let Some(project_id) = project_id.filter(|_| REVIEWABLE_ENTITY_TYPES.contains(&entity_type)) {
+            let reviews = self.container.entity_review_repository.find_by_project(project_id).await?;
+            Self::add_verification(&mut entities, &verification_states(&reviews));
+        }
+        Ok(entities)
+    }
+
+    /// Merge the rules and conventions of the shared organization project into a `query_context`
+    /// result, leaving out the copies of the project's own entities; unchanged when no shared
+    /// project is configured or the project is the shared one
+    async fn with_shared_context(
+        &self,
+        result: serde_json::Value,
+        project_id: &str,
+        feature_area: &str,
+        task_type: &str,
+        components: &[String],
+    ) -> Result<serde_json::Value, McpError> {
+        let Some(shared_project_id) = self.container.config_manager.current().shared_context.project_id else {
+            return Ok(result);
+        };
+        if shared_project_id == project_id {
+            return Ok(result);
+        }
+        let container = &self.container;
+        let serialization_error = |e: serde_json::Error| McpError::internal_error(format!("Serialization error: {e}"), None);
+
+        let cache_key = CacheKeyBuilder::context_query(&shared_project_id, feature_area, task_type, components);
+        let shared = match container.query_cache.get(&cache_key) {
+            Some(cached) => cached,
+            None => {
+                let shared = container
+                    .context_query_service
+                    .query_context(&shared_project_id, feature_area, task_type, components, None)
+                    .await?;
+                let shared = serde_json::to_value(shared).map_err(serialization_error)?;
+                container.query_cache.set(cache_key, shared.clone(), None);
+                shared
+            }
+        };
+        let shared: ContextQueryResult = serde_json::from_value(shared).map_err(serialization_error)?;
+
+        let promoted_from_here: HashSet<String> = container
+            .shared_context_link_repository
+            .find_by_shared_project(&shared_project_id)
+            .await?
+            .into_iter()
+            .filter(|link| link.source_project_id == project_id)
+            .map(|link| link.shared_entity_id)
+            .collect();
+        let mut result: ContextQueryResult = serde_json::from_value(result).map_err(serialization_error)?;
+        merge_shared(&mut result, shared, &promoted_from_here);
+        serde_json::to_value(result).map_err(serialization_error)
+    }
+
+    /// Copy a business rule or convention of a project to the shared project, or refresh the
+    /// copy made earlier, and link the copy to its source
+    async fn promote_entity(
+        &self,
+        project_id: &str,
+        entity_id: &str,
+        shared_project_id: &str,
+    ) -> Result<Option<SharedContextLink>, McpError> {
+        let container = &self.container;
+        let earlier = container
+            .shared_context_link_repository
+            .find_by_source(shared_project_id, entity_id)
+            .await?;
+        let copy_id = earlier
+            .as_ref()
+            .map(|link| link.shared_entity_id.clone())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let now = chrono::Utc::now().to_rfc3339();
+
+        let crud = &container.context_crud_service;
+        let entity_type = if let Some(rule) = crud.get_business_rule(entity_id).await? {
+            if rule.project_id != project_id {
+                return Ok(None);
+            }
+            let mut copy = rule;
+            copy.id = copy_id.clone();
+            copy.project_id = shared_project_id.to_string();
+            match crud.get_business_rule(&copy_id).await? {
+                Some(_) => {
+                    crud.update_business_rule(&copy).await?;
+                }
+                None => {
+                    copy.created_at = Some(now);
+                    crud.bulk_create_business_rules(&[copy]).await?;
+                }
+            }
+            "business_rule"
+        } else if let Some(convention) = container.project_convention_repository.get_by_id(entity_id).await? {
+            if convention.project_id != project_id {
+                return Ok(None);
+            }
+            let conventions = &container.project_convention_repository;
+            let mut copy = convention;
+            copy.id = copy_id.clone();
+            copy.project_id = shared_project_id.to_string();
+            match conventions.get_by_id(&copy_id).await? {
+                Some(_) => {
+                    conventions.update(&copy).await?;
+                }
+                None => {
+                    copy.created_at = Some(now);
+                    conventions.create(&copy).await?;
+                }
+            }
+            "project_convention"
+        } else {
+            return Ok(None);
+        };
+        self.invalidate_cached(entity_type, Some(&copy_id));
+        self.invalidate_cached(entity_type, None);
+
+        let mut link = SharedContextLink::new(shared_project_id, &copy_id, entity_type, project_id, entity_id);
+        link.promoted_by = CLIENT_AGENT.try_with(Clone::clone).ok().flatten();
+        container.shared_context_link_repository.save(&link).await?;
+        Ok(Some(link))
+    }
+
+    /// Limit the decisions of a `query_context` result to approved ones (drafts and reviews too
+    /// with `include_drafts`, deprecated never) that no other decision supersedes (superseded
+    /// ones too with `include_superseded`), drop disputed items with `exclude_disputed`, and add
+    /// the project's specifications likewise
+    async fn with_review_states(
+        &self,
+        result: serde_json::Value,
+        project_id: &str,
+        include_drafts: bool,
+        include_superseded: bool,
+        exclude_disputed: bool,
+    ) -> Result<FilteredContext, McpError> {
+        let visible = |state: WorkflowState| match state {
+            WorkflowState::Approved => true,
+            WorkflowState::Draft | WorkflowState::InReview => include_drafts,
+            WorkflowState::Deprecated => false,
+        };
+
+        let mut result: ContextQueryResult = serde_json::from_value(result)
+            .map_err(|e| McpError::internal_error(format!("Serialization error: {e}"), None))?;
+        let decision_links = self.container.decision_link_repository.find_by_project(project_id).await?;
+        let superseded = superseded_ids(&decision_links);
+        result.architectural_decisions.retain(|decision| {
+            let status = decision.status.as_deref();
+            if superseded.contains(decision.id.as_str())
+                || status.is_some_and(|status| status.trim().to_lowercase().starts_with("superseded"))
+            {
+                include_superseded
+            } else {
+                visible(WorkflowState::from_decision_status(status))
+            }
+        });
+        if exclude_disputed {
+            let reviews = self.container.entity_review_repository.find_by_project(project_id).await?;
+            let disputed: HashSet<String> = verification_states(&reviews)
+                .into_iter()
+                .filter(|(_, state)| state.verification_status == VerificationStatus::Disputed)
+                .map(|(entity_id, _)| entity_id)
+                .collect();
+            result.business_rules.retain(|rule| !disputed.contains(&rule.id));
+            result.architectural_decisions.retain(|decision| !disputed.contains(&decision.id));
+            result.performance_requirements.retain(|requirement| !disputed.contains(&requirement.id));
+            result.security_policies.retain(|policy| !disputed.contains(&policy.id));
+            result.project_conventions.retain(|convention| !disputed.contains(&convention.id));
+        }
+
+        let specifications: Vec<serde_json::Value> = self
+            .container
+            .specification_service
+            .get_specifications_by_project(project_id)
+            .await?
+            .into_iter()
+            .filter(|spec| visible(WorkflowState::from_spec_status(&spec.status)))
+            .map(|spec| {
+                serde_json::json!({
+                    "id": spec.id,
+                    "title": spec.title,
+                    "spec_type": spec.spec_type,
+                    "status": spec.status,
+                    "file_path": spec.file_path
+                })
+            })
+            .collect();
+        Ok(FilteredContext {
+            result,
+            sections: vec![("specifications".to_string(), specifications)],
+            decision_links,
+        })
+    }
+
+    /// Add the API contracts linked to the feature context of a `query_context` result or to
+    /// the named components as `api_contracts`
+    async fn with_api_contracts(
+        &self,
+        mut filtered: FilteredContext,
+        project_id: &str,
+        components: &[String],
+    ) -> Result<FilteredContext, McpError> {
+        let contracts = self.container.api_contract_repository.list_by_project(project_id).await?;
+        if contracts.is_empty() {
+            return Ok(filtered);
+        }
+
+        let mut linked_ids: HashSet<String> =
+            filtered.result.feature_context.iter().map(|feature| feature.id.clone()).collect();
+        if !components.is_empty() {
+            linked_ids.extend(
+                self.container
+                    .framework_service
+                    .list_components(project_id)
+                    .await?
+                    .into_iter()
+                    .filter(|component| components.iter().any(|name| name.eq_ignore_ascii_case(&component.component_name)))
+                    .map(|component| component.id),
+            );
+        }
+        let contracts = contracts
+            .into_iter()
+            .filter(|contract| linked_ids.iter().any(|id| contract.links_to(id)))
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| McpError::internal_error(format!("Serialization error: {e}"), None))?;
+        // Contracts describe the endpoints to implement, so they get room before specifications
+        filtered.sections.insert(0, ("api_contracts".to_string(), contracts));
+        Ok(filtered)
+    }
+
+    /// Fit a filtered `query_context` result into `max_tokens` as a whole. The feature context
+    /// and the other sections that don't depend on which items are returned get up to a
+    /// `SECTION_BUDGET_SHARE` of the budget, entry by entry; the ranked items get the rest,
+    /// with the links between the decisions returned and the glossary terms they use counted
+    /// against it, so the lowest ranked items are dropped until everything fits
+    async fn fit_query_result(
+        &self,
+        filtered: FilteredContext,
+        project_id: &str,
+        feature_area: &str,
+        max_tokens: usize,
+    ) -> Result<serde_json::Value, McpError> {
+        let glossary = self.container.glossary_service.list_entries(project_id).await?;
+        let FilteredContext { mut result, mut sections, decision_links } = filtered;
+
+        let section_budget = max_tokens / SECTION_BUDGET_SHARE;
+        let mut section_tokens = 0;
+        let mut omitted_sections = BTreeMap::new();
+        if let Some(feature) = &result.feature_context {
+            let tokens = estimate_tokens(feature);
+            if tokens > section_budget {
+                result.feature_context = None;
+                omitted_sections.insert("feature_context".to_string(), 1);
+            } else {
+                section_tokens += tokens;
+            }
+        }
+        for (name, entries) in &mut sections {
+            let before = entries.len();
+            entries.retain(|entry| {
+                let tokens = estimate_tokens(entry);
+                let fits = section_tokens + tokens <= section_budget;
+                if fits {
+                    section_tokens += tokens;
+                }
+                fits
+            });
+            if entries.len() < before {
+                omitted_sections.insert(name.clone(), before - entries.len());
+            }
+        }
+
+        // Rankings, links and glossary uses grow with the items, so bisect for the largest
+        // item budget whose whole result fits
+        let fit = |item_budget: usize| -> Result<(serde_json::Value, usize, usize), McpError> {
+            let mut fitted = result.clone();
+            fitted.fit_to_budget(Some(item_budget));
+            let item_tokens = fitted.assembly.as_ref().map_or(0, |assembly| assembly.estimated_tokens);
+            if let Some(assembly) = fitted.assembly.as_mut() {
+                assembly.token_budget = Some(max_tokens);
+                assembly.omitted_sections = omitted_sections.clone();
+            }
+            let value = Self::with_item_sections(fitted, &sections, &decision_links, &glossary, feature_area)?;
+            let result_tokens = estimate_tokens(&value);
+            Ok((value, item_tokens, result_tokens))
+        };
+        let mut item_budget = max_tokens.saturating_sub(section_tokens);
+        let mut fitting = None;
+        let mut too_large = None;
+        loop {
+            let (value, item_tokens, result_tokens) = fit(item_budget)?;
+            if result_tokens <= max_tokens {
+                fitting = Some((item_budget, value, result_tokens));
+            } else {
+                too_large = Some(item_tokens);
+            }
+            let low = fitting.as_ref().map_or(0, |(budget, _, _)| *budget);
+            let high = too_large.unwrap_or(item_budget + 1);
+            if high <= low + 1 {
+                break;
+            }
+            item_budget = (low + high) / 2;
+        }
+        let (mut value, result_tokens) = match fitting {
+            Some((_, value, result_tokens)) => (value, result_tokens),
+            None => {
+                let (value, _, result_tokens) = fit(0)?;
+                (value, result_tokens)
+            }
+        };
+        if let Some(assembly) = value.get_mut("assembly").and_then(|a| a.as_object_mut()) {
+            assembly.insert("result_tokens".to_string(), result_tokens.into());
+        }
+        Ok(value)
+    }
+
+    /// A fitted `query_context` result with its `sections`, the `decision_links` between the
+    /// decisions returned, and the project's glossary terms that the result mentions or that
+    /// belong to its feature area as `glossary_terms`, with the IDs of the items using them
+    fn with_item_sections(
+        result: ContextQueryResult,
+        sections: &[(String, Vec<serde_json::Value>)],
+        decision_links: &[DecisionLink],
+        glossary: &[GlossaryEntry],
+        feature_area: &str,
+    ) -> Result<serde_json::Value, McpError> {
+        let serialization_error = |e: serde_json::Error| McpError::internal_error(format!("Serialization error: {e}"), None);
+        let returned: HashSet<&str> = result.architectural_decisions.iter().map(|d| d.id.as_str()).collect();
+        let links: Vec<&DecisionLink> = decision_links
+            .iter()
+            .filter(|link| returned.contains(link.from_decision_id.as_str()) || returned.contains(link.to_decision_id.as_str()))
+            .collect();
+        let links = serde_json::to_value(links).map_err(serialization_error)?;
+
+        let mut value = serde_json::to_value(result).map_err(serialization_error)?;
+        let Some(result) = value.as_object_mut() else {
+            return Ok(value);
+        };
+        for (name, entries) in sections {
+            result.insert(name.clone(), serde_json::Value::Array(entries.clone()));
+        }
+        result.insert("decision_links".to_string(), links);
+        if glossary.is_empty() {
+            return Ok(value);
+        }
+
+        let mut texts = Vec::new();
+        // `ranking` repeats the items' titles under the same IDs
+        let sections = result.iter().filter(|(name, _)| name.as_str() != "ranking").map(|(_, section)| section);
+        let items = sections.flat_map(|section| match section {
+            serde_json::Value::Array(items) => items.iter().collect(),
+            item @ serde_json::Value::Object(_) => vec![item],
+            _ => Vec::new(),
+        });
+        for item in items.filter_map(|item| item.as_object()) {
+            let Some(id) = item.get("id").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let text: Vec<&str> = item
+                .iter()
+                .filter(|(field, _)| field.as_str() != "id" && !field.ends_with("_id"))
+                .flat_map(|(_, value)| match value {
+                    serde_json::Value::String(text) => vec![text.as_str()],
+                    serde_json::Value::Array(values) => values.iter().filter_map(|v| v.as_str()).collect(),
+                    _ => Vec::new(),
+                })
+                .collect();
+            texts.push((id.to_string(), text.join("\n")));
+        }
+        let terms = serde_json::to_value(terms_in(glossary, &texts, feature_area)).map_err(serialization_error)?;
+        result.insert("glossary_terms".to_string(), terms);
+        Ok(value)
+    }
+
+    /// Where a tool writes its output: `output_path`, or `default_name`, under `[exports] directory`.
+    /// Clients can't name files outside it, so absolute paths and `..` are rejected.
+    fn export_path(&self, output_path: Option<&str>, default_name: std::path::PathBuf) -> Result<std::path::PathBuf, McpError> {
+        let relative = match output_path {
+            Some(output_path) => Self::relative_path("output_path", output_path)?,
+            None => default_name,
+        };
+        let path = self.export_directory()?.join(relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                McpError::internal_error(format!("Failed to create {}: {e}", parent.display()), None)
+            })?;
+        }
+        Ok(path)
+    }
+
+    /// `[exports] directory`, or the default one when it isn't configured
+    fn export_directory(&self) -> Result<std::path::PathBuf, McpError> {
+        match self.container.config_manager.current().exports.directory {
+            Some(directory) => Ok(directory),
+            None => Ok(AppDirs::resolve(None)
+                .map_err(|e| McpError::internal_error(format!("No export directory: {e}"), None))?
+                .default_exports_dir()),
+        }
+    }
+
+    /// A path argument that must stay inside the directory it is joined to: absolute paths and
+    /// `..` are rejected
+    fn relative_path(argument: &str, value: &str) -> Result<std::path::PathBuf, McpError> {
+        let path = std::path::PathBuf::from(value);
+        if path.as_os_str().is_empty()
+            || !path.components().all(|component| matches!(component, std::path::Component::Normal(_)))
+        {
+            return Err(McpError::invalid_params(
+                format!("{argument} must be a relative path without '..': {value}"),
+                None,
+            ));
+        }
+        Ok(path)
+    }
+
+    /// `path`, resolved, when it lies under one of the configured specification directories
+    fn spec_source_path(&self, path: &str) -> Result<std::path::PathBuf, McpError> {
+        let outside = || {
+            McpError::invalid_params(format!("{path} is not inside a configured specification directory"), None)
+        };
+        let resolved = std::fs::canonicalize(path).map_err(|_| outside())?;
+        let inside = self
+            .container
+            .config_manager
+            .current()
+            .specs
+            .all_sources()
+            .iter()
+            .filter_map(|source| std::fs::canonicalize(&source.path).ok())
+            .any(|root| resolved.starts_with(root));
+        if inside {
+            Ok(resolved)
+        } else {
+            Err(outside())
+        }
+    }
+
+    /// Parse a `filters` argument; each facet takes a string or an array of strings
+    fn facet_filters(value: Option<&serde_json::Value>) -> Result<FacetFilters, McpError> {
+        let mut filters = FacetFilters::new();
+        for (facet, value) in value.and_then(|v| v.as_object()).into_iter().flatten() {
+            let values = match value {
+                serde_json::Value::String(value) => vec![value.clone()],
+                serde_json::Value::Array(values) => {
+                    values.iter().filter_map(|v| v.as_str()).map(str::to_string).collect()
+                }
+                _ => {
+                    return Err(McpError::invalid_params(
+                        format!("filters.{facet} must be a string or an array of strings"),
+                        None,
+                    ))
+                }
+            };
+            filters.insert(facet.clone(), values);
+        }
+        Ok(filters)
+    }
+
+    /// Embedding jobs for every indexed entity of a project, with their entity types
+    async fn project_index_jobs(&self, project_id: &str) -> Result<Vec<(&'static str, EmbeddingJob)>, McpError> {
+        Ok(self
+            .project_entities(project_id)
+            .await?
+            .into_iter()
+            .filter_map(|(entity_type, entity)| {
+                EmbeddingJob::from_entity(entity_type, &entity).map(|job| (entity_type, job))
+            })
+            .collect())
+    }
+
+    /// The project and its rules, decisions, requirements, components, phases and custom
+    /// entities as the CRUD tools return them
+    async fn project_entities(&self, project_id: &str) -> Result<Vec<(&'static str, serde_json::Value)>, McpError> {
+        let container = &self.container;
+        let project = container
+            .project_service
+            .get_project(project_id)
+            .await?
+            .ok_or_else(|| McpError::invalid_params(format!("Project not found: {project_id}"), None))?;
+
+        let to_value = |value: serde_json::Result<serde_json::Value>| {
+            value.map_err(|e| McpError::internal_error(format!("Serialization error: {e}"), None))
+        };
+        let mut entities = vec![("project", to_value(serde_json::to_value(&project))?)];
+        for rule in container.context_crud_service.list_business_rules(project_id).await? {
+            entities.push(("business_rule", to_value(serde_json::to_value(rule))?));
+        }
+        for decision in container.context_crud_service.list_architectural_decisions(project_id).await? {
+            entities.push(("architectural_decision", to_value(serde_json::to_value(decision))?));
+        }
+        for requirement in container.context_crud_service.list_performance_requirements(project_id).await? {
+            entities.push(("performance_requirement", to_value(serde_json::to_value(requirement))?));
+        }
+        for component in container.framework_service.list_components(project_id).await? {
+            entities.push(("framework_component", to_value(serde_json::to_value(component))?));
+        }
+        for phase in container.development_phase_service.list_phases(project_id).await? {
+            entities.push(("development_phase", to_value(serde_json::to_value(phase))?));
+        }
+        let custom_entities = container.custom_entity_repository.list_by_project(project_id, None).await?;
+        for entity in self.with_search_text(custom_entities).await? {
+            entities.push(("custom_entity", to_value(serde_json::to_value(entity))?));
+        }
+        Ok(entities)
+    }
+
+    /// A project's rules, decisions, requirements, conventions and feature contexts as scored by
+    /// the quality service, optionally of one entity type
+    async fn quality_items(&self, project_id: &str, entity_type: Option<&str>) -> Result<Vec<EnhancedContextItem>, McpError> {
+        const ASSESSED_TYPES: [&str; 5] = [
+            "business_rule",
+            "architectural_decision",
+            "performance_requirement",
+            "project_convention",
+            "feature_context",
+        ];
+        if let Some(entity_type) = entity_type.filter(|name| !ASSESSED_TYPES.contains(name)) {
+            return Err(McpError::invalid_params(
+                format!("Unknown entity type: {entity_type}; expected one of {}", ASSESSED_TYPES.join(", ")),
+                None,
+            ));
+        }
+        let container = &self.container;
+        container
+            .project_service
+            .get_project(project_id)
+            .await?
+            .ok_or_else(|| McpError::invalid_params(format!("Project not found: {project_id}"), None))?;
+
+        let wanted = |name: &str| entity_type.is_none_or(|entity_type| entity_type == name);
+        let mut items = Vec::new();
+        if wanted("business_rule") {
+            let rules = container.context_crud_service.list_business_rules(project_id).await?;
+            items.extend(rules.into_iter().map(ContextConverter::from_business_rule));
+        }
+        if wanted("architectural_decision") {
+            let decisions = container.context_crud_service.list_architectural_decisions(project_id).await?;
+            items.extend(decisions.into_iter().map(ContextConverter::from_architectural_decision));
+        }
+        if wanted("performance_requirement") {
+            let requirements = container.context_crud_service.list_performance_requirements(project_id).await?;
+            items.extend(requirements.into_iter().map(ContextConverter::from_performance_requirement));
+        }
+        if wanted("project_convention") {
+            let conventions = container.project_convention_repository.list_by_project(project_id).await?;
+            items.extend(conventions.into_iter().map(ContextConverter::from_project_convention));
+        }
+        if wanted("feature_context") {
+            let features = container.feature_context_repository.list_by_project(project_id).await?;
+            items.extend(features.into_iter().map(ContextConverter::from_feature_context));
+        }
+        Ok(items)
+    }
+
+    /// A project's context as nodes of the relationship graph: the quality-scored entity types,
+    /// framework components and specification requirements, carrying the relationships
+    /// recorded between them (component dependencies, requirement links and dependencies).
+    /// Each item's tags are the ones assigned to its entity, so the engine relates entities
+    /// sharing tags rather than every entity of a type.
+    async fn related_items(&self, project_id: &str) -> Result<Vec<EnhancedContextItem>, McpError> {
+        let container = &self.container;
+        let mut items = self.quality_items(project_id, None).await?;
+
+        let components = container.framework_service.list_components(project_id).await?;
+        for component in &components {
+            let mut item = ContextConverter::from_framework_component(component.clone());
+            for dependency in &component.dependencies {
+                if let Some(target) = DependencyGraph::resolve(&components, dependency).map(|index| &components[index]) {
+                    if target.id != component.id {
+                        item.relationships
+                            .push(ContextRelationship::new(target.id.clone(), RelationshipType::DependsOn, 1.0, false));
+                    }
+                }
+            }
+            items.push(item);
+        }
+
+        let mut implemented: Vec<(String, String)> = Vec::new();
+        for spec in container.specification_service.get_specifications_by_project(project_id).await? {
+            for requirement in container.specification_service.get_requirements_by_spec(&spec.id).await? {
+                implemented.extend(requirement.linked_context.iter().map(|context_id| (context_id.clone(), requirement.id.clone())));
+                let dependencies = requirement.dependencies.clone();
+                let mut item = ContextConverter::from_requirement(requirement, project_id);
+                item.relationships.extend(
+                    dependencies
+                        .into_iter()
+                        .map(|target| ContextRelationship::new(target, RelationshipType::DependsOn, 1.0, false)),
+                );
+                items.push(item);
+            }
+        }
+        for (context_id, requirement_id) in implemented {
+            if let Some(item) = items.iter_mut().find(|item| item.id == context_id) {
+                item.relationships
+                    .push(ContextRelationship::new(requirement_id, RelationshipType::Implements, 1.0, false));
+            }
+        }
+
+        let mut entity_tags: HashMap<String, Vec<String>> = HashMap::new();
+        for tag in container.tag_repository.list_tags(project_id).await? {
+            if tag.tag_name == STALE_TAG {
+                continue;
+            }
+            for tagged in container.tag_repository.find_tagged_entities(&tag.id).await? {
+                entity_tags.entry(tagged.entity_id).or_default().push(tag.tag_name.clone());
+            }
+        }
+        for item in &mut items {
+            item.metadata.tags = entity_tags.remove(&item.id).unwrap_or_default();
+        }
+        Ok(items)
+    }
+
+    /// Add a finished call to the audit log; unlike analytics this records the caller as given
+    async fn audit_tool_call(&self, mut audit: ToolCallAudit, result: &Result<CallToolResult, McpError>) {
+        match result {
+            Ok(result) if result.is_error == Some(true) => {
+                audit.outcome = ToolCallOutcome::Error;
+                audit.error_message = result
+                    .content
+                    .iter()
+                    .find_map(|content| content.as_text())
+                    .map(|text| text.text.clone());
+            }
+            Ok(_) => {}
+            Err(e) => {
+                audit.outcome = ToolCallOutcome::Error;
+                audit.error_code = Some(e.code.0);
+                audit.error_message = Some(e.message.to_string());
+            }
+        }
+        if let Err(e) = self.container.tool_audit_service.record(audit).await {
+            tracing::warn!("Failed to record tool call in the audit log: {}", e);
+        }
+    }
+
+    /// Record a usage event from the calling client, as the analytics policy allows
+    async fn track_event(&self, mut event: AnalyticsEvent) {
+        if !self.container.analytics_policy.is_enabled() {
+            return;
+        }
+        if event.user_agent.is_none() {
+            event.user_agent = CLIENT_AGENT.try_with(Clone::clone).ok().flatten();
+        }
+        if let Err(e) = self.container.analytics_service.track_event(event).await {
+            tracing::warn!("Failed to track analytics event: {}", e);
+        }
+    }
+
+    /// Count entities handed to a client for `get_unused_context`
+    async fn track_entity_uses(&self, project_id: &str, source: EntityUseSource, entities: &[(String, String)]) {
+        if !self.container.analytics_policy.is_enabled() {
+            return;
+        }
+        if let Err(e) = self.container.analytics_service.record_entity_uses(project_id, source, entities).await {
+            tracing::warn!("Failed to record entity uses: {}", e);
+        }
+    }
+
+    /// Search results are counted per project; those without one belong to `project_id`
+    async fn track_search_results(&self, project_id: Option<&str>, results: &[RankedSearchResult]) {
+        let mut by_project: HashMap<&str, Vec<(String, String)>> = HashMap::new();
+        for result in results {
+            if let Some(project_id) = result.project_id.as_deref().or(project_id) {
+                by_project
+                    .entry(project_id)
+                    .or_default()
+                    .push((result.entity_type.clone(), result.entity_id.clone()));
+            }
+        }
+        for (project_id, entities) in by_project {
+            self.track_entity_uses(project_id, EntityUseSource::Search, &entities).await;
+        }
+    }
+
+    /// Entities in a `query_context` result, with the entity type of the list holding them
+    fn query_result_entities(result: &serde_json::Value) -> Vec<(String, String)> {
+        const LISTS: [(&str, &str); 6] = [
+            ("business_rules", "business_rule"),
+            ("architectural_decisions", "architectural_decision"),
+            ("performance_requirements", "performance_requirement"),
+            ("security_policies", "security_policy"),
+            ("project_conventions", "project_convention"),
+            ("api_contracts", "api_contract"),
+        ];
+        LISTS
+            .iter()
+            .flat_map(|(list, entity_type)| {
+                result
+                    .get(*list)
+                    .and_then(|items| items.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(move |item| item.get("id").and_then(|id| id.as_str()))
+                    .map(move |id| (entity_type.to_string(), id.to_string()))
+            })
+            .collect()
+    }
+
+    /// Tag the entities of a report `stale` and untag those no longer unused
+    async fn tag_stale_entities(&self, report: &UnusedContextReport) -> Result<serde_json::Value, McpError> {
+        let tags = &self.container.tag_repository;
+        let mut tag = ContextTag::new(report.project_id.clone(), STALE_TAG.to_string(), "status".to_string());
+        tag.description = Some("Not returned by query_context, search or get_entity recently".to_string());
+        let tag = tags.find_or_create_tag(&tag).await?;
+
+        let mut tagged = Vec::new();
+        for usage in &report.never_used {
+            let entity = TaggedEntity::new(
+                report.project_id.clone(),
+                usage.entity_id.clone(),
+                usage.entity_type.clone(),
+                tag.id.clone(),
+            );
+            if tags.tag_entity(&entity).await? {
+                tagged.push(usage.entity_id.clone());
+            }
+        }
+
+        let mut untagged = Vec::new();
+        for entity in tags.find_tagged_entities(&tag.id).await? {
+            let still_unused = report.never_used.iter().any(|usage| usage.entity_id == entity.entity_id);
+            if !still_unused && tags.untag_entity(&entity.entity_id, &tag.id).await? {
+                untagged.push(entity.entity_id);
+            }
+        }
+        Ok(serde_json::json!({
+            "tag": tag.tag_name,
+            "tagged": tagged,
+            "untagged": untagged,
+        }))
+    }
+
+    /// Queue every entity of a project for re-embedding and drop embeddings of entities
+    /// that no longer exist; recovers an index that missed writes or used another model
+    async fn reindex_project(&self, project_id: &str) -> Result<serde_json::Value, McpError> {
+        let container = &self.container;
+        let mut queued: HashMap<&str, usize> = HashMap::new();
+        let mut live_ids = HashSet::new();
+        for (entity_type, job) in self.project_index_jobs(project_id).await? {
+            live_ids.insert(job.entity_id().to_string());
+            container.embedding_queue.enqueue(job);
+            *queued.entry(entity_type).or_default() += 1;
+        }
+
+        let stored = container
+            .embedding_repository
+            .get_embeddings_by_project(project_id)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to read embeddings: {e}"), None))?;
+        let mut removed = HashSet::new();
+        for embedding in &stored {
+            let entity_id = parent_id(&embedding.context_id);
+            if !live_ids.contains(entity_id) && removed.insert(entity_id) {
+                container.embedding_queue.enqueue(EmbeddingJob::Remove {
+                    entity_id: entity_id.to_string(),
+                });
+            }
+        }
+
+        container.search_index_manager.record_rebuild(project_id).await;
+
+        Ok(serde_json::json!({
+            "project_id": project_id,
+            "queued": queued.values().sum::<usize>(),
+            "queued_by_type": queued,
+            "removed": removed.len(),
+            "status": container.embedding_queue.status(),
+        }))
+    }
+
+    /// Log a fetch of an entity that a recent search returned as a click on that result,
+    /// which raises the entity in later searches
+    async fn track_search_click(&self, entity_id: &str) {
+        let Some(hit) = self.container.search_click_tracker.take_click(entity_id) else {
+            return;
+        };
+        let event = AnalyticsHelper::create_search_click_event(
+            hit.project_id,
+            hit.entity_type,
+            entity_id.to_string(),
+            hit.query,
+            hit.rank,
+        );
+        self.track_event(event).await;
+    }
+
+    /// Compare the search index of one project, or of all projects, with the entities that
+    /// should be in it
+    async fn search_index_status(&self, project_id: Option<&str>) -> Result<serde_json::Value, McpError> {
+        let container = &self.container;
+        let project_ids = match project_id {
+            Some(project_id) => vec![project_id.to_string()],
+            None => container
+                .project_service
+                .list_projects()
+                .await?
+                .into_iter()
+                .map(|project| project.id)
+                .collect(),
+        };
+        let mut expected = HashMap::new();
+        for project_id in &project_ids {
+            for (entity_type, job) in self.project_index_jobs(project_id).await? {
+                expected.insert(job.entity_id().to_string(), entity_type.to_string());
+            }
+        }
+
+        let status = container
+            .search_index_manager
+            .get_index_status(project_id, &expected)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to read search index: {e}"), None))?;
+        let mut result = serde_json::to_value(status)
+            .map_err(|e| McpError::internal_error(format!("Serialization error: {e}"), None))?;
+        result["project_id"] = serde_json::json!(project_id);
+        result["model"] = serde_json::json!(container.embedding_service.get_model_info().model_name);
+        result["pending_jobs"] = serde_json::json!(container.embedding_queue.status().pending);
+        Ok(result)
+    }
+
+    /// Build the embedding service described by the `migrate_embeddings` arguments over the
+    /// `[embedding]` settings and start re-embedding every project with it
+    async fn start_embedding_migration(
+        &self,
+        args: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<serde_json::Value, McpError> {
+        let container = &self.container;
+        let mut settings = container.config_manager.current().embedding;
+        if let Some(provider) = args.get("provider").and_then(|v| v.as_str()) {
+            settings.provider = provider.to_string();
+        }
+        if let Some(model) = args.get("model").and_then(|v| v.as_str()) {
+            settings.model = model.to_string();
+        }
+        if let Some(dimension) = args.get("dimension").and_then(|v| v.as_u64()) {
+            settings.dimension = dimension as usize;
+        }
+        if let Some(endpoint) = args.get("endpoint").and_then(|v| v.as_str()) {
+            settings.endpoint = Some(endpoint.to_string());
+        }
+        let target = EmbeddingServiceFactory::config_from_settings(&settings)
+            .and_then(EmbeddingServiceFactory::create_service)
+            .map_err(|e| McpError::invalid_params(format!("Invalid embedding settings: {e}"), None))?;
+
+        let mut jobs = Vec::new();
+        for project in container.project_service.list_projects().await? {
+            jobs.extend(self.project_index_jobs(&project.id).await?.into_iter().map(|(_, job)| job));
+        }
+        let status = container
+            .embedding_migrator
+            .start(Arc::from(target), jobs)
+            .await
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+        let note = format!(
+            "Search switches to {} once every entity is embedded. Set [embedding] provider = \"{}\", model = \"{}\" and dimension = {} in config.toml so restarts keep the new model.",
+            status.to_model.as_deref().unwrap_or(&settings.model),
+            settings.provider,
+            settings.model,
+            settings.dimension
+        );
+        Ok(serde_json::json!({
+            "migration": status,
+            "note": note,
+        }))
+    }
+
+    /// A string argument naming a variant of `T`, such as a conflict strategy
+    fn enum_arg<T: serde::de::DeserializeOwned>(
+        args: &serde_json::Map<String, serde_json::Value>,
+        name: &str,
+    ) -> Result<Option<T>, McpError> {
+        args.get(name)
+            .map(|value| {
+                serde_json::from_value(value.clone())
+                    .map_err(|_| McpError::invalid_params(format!("Invalid {name}: {value}"), None))
+            })
+            .transpose()
+    }
+
+    async fn find_conflict(&self, conflict_id: &str) -> Result<ConflictInfo, McpError> {
+        self.container
+            .conflict_resolution_engine
+            .lock()
+            .await
+            .find_conflict(conflict_id)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to read conflict: {e}"), None))?
+            .ok_or_else(|| McpError::invalid_params(format!("Conflict not found: {conflict_id}"), None))
+    }
+
+    /// Open a resolution session for an unresolved conflict
+    async fn start_conflict_resolution(
+        &self,
+        args: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<serde_json::Value, McpError> {
+        let conflict_id = args.get("conflict_id").and_then(|v| v.as_str()).ok_or_else(|| {
+            McpError::invalid_params("Missing required parameter: conflict_id", None)
+        })?;
+        let conflict = self.find_conflict(conflict_id).await?;
+        if conflict.resolved_at.is_some() {
+            return Err(McpError::invalid_params(format!("Conflict {conflict_id} is already resolved"), None));
+        }
+
+        let request = StartResolutionRequest {
+            conflict_id: conflict_id.to_string(),
+            user_id: args.get("user_id").and_then(|v| v.as_str()).unwrap_or("mcp").to_string(),
+            client_id: MCP_CLIENT_ID,
+            preferred_strategy: Self::enum_arg(args, "preferred_strategy")?,
+            timeout_seconds: Some(args.get("timeout_seconds").and_then(|v| v.as_u64()).unwrap_or(CONFLICT_SESSION_TIMEOUT_SECS)),
+        };
+        let response = self
+            .container
+            .conflict_resolution_ui
+            .lock()
+            .await
+            .start_resolution_session(request, conflict)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to start resolution: {e}"), None))?;
+        serde_json::to_value(response).map_err(|e| McpError::internal_error(format!("Serialization error: {e}"), None))
+    }
+
+    /// Resolve a session's conflict: with the entity the user built for manual resolution,
+    /// otherwise by letting the engine apply the selected strategy
+    async fn complete_conflict_resolution(
+        &self,
+        args: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<serde_json::Value, McpError> {
+        let session_id = args.get("session_id").and_then(|v| v.as_str()).ok_or_else(|| {
+            McpError::invalid_params("Missing required parameter: session_id", None)
+        })?;
+        let notes = args.get("resolution_notes").and_then(|v| v.as_str()).map(str::to_string);
+
+        let request = self
+            .container
+            .conflict_resolution_ui
+            .lock()
+            .await
+            .complete_resolution(session_id, notes)
+            .await
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+        let conflict_id = request.conflict_id.clone();
+        let mut engine = self.container.conflict_resolution_engine.lock().await;
+        let conflict = engine
+            .find_conflict(&conflict_id)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to read conflict: {e}"), None))?;
+        if conflict.is_some_and(|conflict| conflict.resolved_at.is_some()) {
+            return Err(McpError::invalid_params(format!("Conflict {conflict_id} is already resolved"), None));
+        }
+        let result = match request.resolution_strategy {
+            ConflictStrategy::ManualResolution => engine.resolve_conflict_manually(request).await,
+            strategy => engine.resolve_conflict(&conflict_id, strategy, Some(request.resolved_by)).await,
+        }
+        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+        Ok(serde_json::json!({
+            "conflict_id": conflict_id,
+            "session_id": session_id,
+            "resolution": result,
+        }))
+    }
+
+    /// Drop cached results scoped to `project_id`
+    fn clear_project_cache(&self, project_id: &str) -> serde_json::Value {
+        let removed = self.container.query_cache.invalidate_project(project_id);
+        serde_json::json!({
+            "message": "Project cache cleared successfully",
+            "project_id": project_id,
+            "cleared": true,
+            "entries_removed": removed
+        })
+    }
+
+    /// Drop every cached query result; stored context is not touched
+    fn clear_all_cache(&self) -> serde_json::Value {
+        let removed = self.container.query_cache.clear();
+        serde_json::json!({
+            "message": "All cache cleared successfully",
+            "cleared": true,
+            "entries_removed": removed
+        })
+    }
+
+    fn cache_stats(&self) -> serde_json::Value {
+        let stats = self.container.query_cache.stats();
+        serde_json::json!({
+            "stats": stats,
+            "hit_rate_percent": stats.hit_rate_percent(),
+            "utilization_percent": stats.utilization_percent()
+        })
+    }
+
+    async fn dispatch_tool(
+        &self,
+        request: CallToolRequestParam,
+    ) -> Result<CallToolResult, McpError> {
+        match request.name.as_ref() {
+            // Core operations (kept for convenience)
+            "list_projects" => {
+                let projects = self.container.project_service.list_projects().await?;
+                let content = serde_json::to_string_pretty(&projects).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            // Context Query
+            "query_context" => {
+                let start_time = Instant::now();
+                let args = request.arguments.unwrap_or_default();
+                let project_id =
+                    args.get("project_id")
+                        .and_then(|v| v.as_str())
+                        .ok_or_else(|| {
+                            McpError::invalid_params("Missing required parameter: project_id", None)
+                        })?;
+                let feature_area = args
+                    .get("feature_area")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        McpError::invalid_params("Missing required parameter: feature_area", None)
+                    })?;
+                let task_type =
+                    args.get("task_type")
+                        .and_then(|v| v.as_str())
+                        .ok_or_else(|| {
+                            McpError::invalid_params("Missing required parameter: task_type", None)
+                        })?;
+                let components: Vec<String> = args
+                    .get("components")
+                    .and_then(|v| v.as_array())
+                    .map(|arr| {
+                        arr.iter()
+                            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let include_drafts = args.get("include_drafts").and_then(|v| v.as_bool()).unwrap_or(false);
+                let include_superseded = args.get("include_superseded").and_then(|v| v.as_bool()).unwrap_or(false);
+                let exclude_disputed = args.get("exclude_disputed").and_then(|v| v.as_bool()).unwrap_or(false);
+                let include_shared = args.get("include_shared").and_then(|v| v.as_bool()).unwrap_or(false);
+                let max_tokens = match args.get("max_tokens") {
+                    None => DEFAULT_TOKEN_BUDGET,
+                    Some(value) => value
+                        .as_u64()
+                        .filter(|&tokens| tokens >= 1)
+                        .ok_or_else(|| McpError::invalid_params("max_tokens must be a positive integer", None))?
+                        as usize,
+                };
+
+                // The full ranking is cached; review states and the budget are applied per call
+                let cache_key =
+                    CacheKeyBuilder::context_query(project_id, feature_area, task_type, &components);
+                let memo = RequestMemo::new();
+                let query_result = match self.container.query_cache.get(&cache_key) {
+                    Some(cached) => Ok(cached),
+                    None => memo
+                        .clone()
+                        .scope(self.container.context_query_service.query_context(
+                            project_id,
+                            feature_area,
+                            task_type,
+                            &components,
+                            None,
+                        ))
+                        .await
+                        .and_then(|result| {
+                            serde_json::to_value(result).map_err(|e| {
+                                McpError::internal_error(format!("Serialization error: {e}"), None)
+                            })
+                        })
+                        .inspect(|value| {
+                            self.container.query_cache.set(cache_key.clone(), value.clone(), None)
+                        }),
+                };
+
+                let duration_ms = start_time.elapsed().as_millis() as u64;
+                let memo_stats = memo.stats();
+                tracing::debug!(
+                    "query_context took {}ms: {} lookups, {} memoized, {}us in the database",
+                    duration_ms,
+                    memo_stats.lookups,
+                    memo_stats.hits,
+                    memo_stats.load_time_us
+                );
+
+                let query_result = match query_result {
+                    Ok(result) if include_shared => {
+                        self.with_shared_context(result, project_id, feature_area, task_type, &components).await
+                    }
+                    result => result,
+                };
+                let query_result = match query_result {
+                    Ok(result) => {
+                        self.with_review_states(result, project_id, include_drafts, include_superseded, exclude_disputed)
+                            .await
+                    }
+                    Err(e) => Err(e),
+                };
+                let query_result = match query_result {
+                    Ok(filtered) => self.with_api_contracts(filtered, project_id, &components).await,
+                    Err(e) => Err(e),
+                };
+                let query_result = match query_result {
+                    Ok(filtered) => self.fit_query_result(filtered, project_id, feature_area, max_tokens).await,
+                    Err(e) => Err(e),
+                };
+
+                match query_result {
+                    Ok(result) => {
+                        let entities = Self::query_result_entities(&result);
+                        self.track_entity_uses(project_id, EntityUseSource::Query, &entities).await;
+
+                        // Track successful query
+                        let mut analytics_event = AnalyticsHelper::create_context_query_event(
+                            Some(project_id.to_string()),
+                            Some(feature_area.to_string()),
+                            Some(task_type.to_string()),
+                            Some(components),
+                            Some(duration_ms),
+                            true,
+                            None,
+                        );
+                        if let Ok(stats) = serde_json::to_value(&memo_stats) {
+                            analytics_event.metadata.insert("memo".to_string(), stats);
+                        }
+                        // suggest_context recommends what similar queries returned
+                        analytics_event.metadata.insert(
+                            "entities".to_string(),
+                            entities
+                                .iter()
+                                .map(|(entity_type, entity_id)| {
+                                    serde_json::json!({"entity_type": entity_type, "entity_id": entity_id})
+                                })
+                                .collect(),
+                        );
+                        
+                        self.track_event(analytics_event).await;
+
+                        let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {e}"), None)
+                        })?;
+                        Ok(CallToolResult::success(vec![Content::text(content)]))
+                    }
+                    Err(e) => {
+                        // Track failed query
+                        let analytics_event = AnalyticsHelper::create_context_query_event(
+                            Some(project_id.to_string()),
+                            Some(feature_area.to_string()),
+                            Some(task_type.to_string()),
+                            Some(components),
+                            Some(duration_ms),
+                            false,
+                            Some(e.to_string()),
+                        );
+                        
+                        self.track_event(analytics_event).await;
+
+                        Err(McpError::internal_error(format!("Query failed: {e}"), None))
+                    }
+                }
+            }
+
+            "search_context" => {
+                let args = request.arguments.unwrap_or_default();
+                let all_projects = args.get("all_projects").and_then(|v| v.as_bool()).unwrap_or(false);
+                let project_id = args.get("project_id").and_then(|v| v.as_str());
+                if project_id.is_none() && !all_projects {
+                    return Err(McpError::invalid_params(
+                        "Missing required parameter: project_id (or set all_projects)",
+                        None,
+                    ));
+                }
+                let query = args.get("query").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: query", None)
+                })?;
+                let feature_area = args.get("feature_area").and_then(|v| v.as_str());
+                let limit = args.get("limit").and_then(|v| v.as_u64()).map(|l| l as usize);
+                let filters = Self::facet_filters(args.get("filters"))?;
+
+                let search = &self.container.hybrid_search_service;
+                let (result, project_names) = match project_id {
+                    Some(project_id) if !all_projects => {
+                        (search.faceted_search(project_id, query, feature_area, &filters).await, None)
+                    }
+                    _ => {
+                        let projects = self.container.project_service.list_projects().await?;
+                        let project_ids: Vec<String> = projects.iter().map(|p| p.id.clone()).collect();
+                        let names: HashMap<String, String> =
+                            projects.into_iter().map(|p| (p.id, p.name)).collect();
+                        (
+                            search.cross_project_search(&project_ids, query, feature_area, &filters).await,
+                            Some(names),
+                        )
+                    }
+                };
+                let result = result.map_err(|e| McpError::internal_error(format!("Search failed: {e}"), None))?;
+
+                let mut results = result.ranked_results;
+                if let Some(limit) = limit {
+                    results.truncate(limit);
+                }
+                self.container.search_click_tracker.record_results(query, &results);
+                self.track_search_results(project_id.filter(|_| !all_projects), &results).await;
+                let response = serde_json::json!({
+                    "query": query,
+                    "project_id": project_id.filter(|_| !all_projects),
+                    "all_projects": all_projects,
+                    "project_names": project_names,
+                    "strategy": result.search_strategy,
+                    "fusion": self.container.config_manager.current().search.fusion,
+                    "total_results": results.len(),
+                    "results": results,
+                    "facet_counts": result.facet_counts,
+                    "query_expansions": result.query_expansions,
+                });
+                let content = serde_json::to_string_pretty(&response).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "find_similar_entities" => {
+                let args = request.arguments.unwrap_or_default();
+                let entity_id = args.get("entity_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: entity_id", None)
+                })?;
+                let entity_types: Option<HashSet<&str>> = args
+                    .get("entity_types")
+                    .and_then(|v| v.as_array())
+                    .map(|types| types.iter().filter_map(|t| t.as_str()).collect());
+                let min_similarity = args.get("min_similarity").and_then(|v| v.as_f64()).unwrap_or(0.5) as f32;
+                let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(10) as usize;
+
+                let source = self
+                    .container
+                    .embedding_repository
+                    .get_embedding_by_context_id(entity_id)
+                    .await
+                    .map_err(|e| McpError::internal_error(format!("Failed to load embedding: {e}"), None))?
+                    .ok_or_else(|| {
+                        McpError::invalid_params(
+                            format!("No embedding stored for {entity_id}; it may still be queued (see get_indexing_status) or need reindex_project"),
+                            None,
+                        )
+                    })?;
+
+                let candidates = if entity_types.is_some() { SIMILAR_CANDIDATES } else { limit };
+                let neighbours = self
+                    .container
+                    .semantic_search_service
+                    .find_similar_contexts(entity_id, candidates)
+                    .await
+                    .map_err(|e| McpError::internal_error(format!("Similarity search failed: {e}"), None))?;
+
+                let similar: Vec<serde_json::Value> = neighbours
+                    .into_iter()
+                    .map(|neighbour| neighbour.vector_result)
+                    .filter(|result| result.similarity_score >= min_similarity)
+                    .filter(|result| {
+                        entity_types
+                            .as_ref()
+                            .is_none_or(|types| types.contains(result.metadata.content_type.as_str()))
+                    })
+                    .take(limit)
+                    .map(|result| {
+                        let preview = result.metadata.content_preview;
+                        serde_json::json!({
+                            "entity_id": result.context_id,
+                            "entity_type": result.metadata.content_type,
+                            "similarity": result.similarity_score,
+                            "likely_duplicate": result.similarity_score >= DUPLICATE_SIMILARITY,
+                            "preview": (preview != "Preview not available").then_some(preview),
+                        })
+                    })
+                    .collect();
+
+                let response = serde_json::json!({
+                    "entity_id": entity_id,
+                    "entity_type": source.metadata.content_type,
+                    "project_id": source.metadata.custom_fields.get("project_id"),
+                    "total_results": similar.len(),
+                    "similar": similar,
+                });
+                let content = serde_json::to_string_pretty(&response).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "manage_glossary" => {
+                let args = request.arguments.unwrap_or_default();
+                let arg = |name: &str| args.get(name).and_then(|v| v.as_str());
+                let action = arg("action").ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: action", None)
+                })?;
+                let project_id = arg("project_id").ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let synonyms: Vec<String> = args
+                    .get("synonyms")
+                    .and_then(|v| v.as_array())
+                    .map(|values| values.iter().filter_map(|v| v.as_str()).map(str::to_string).collect())
+                    .unwrap_or_default();
+                let term = || {
+                    arg("term").ok_or_else(|| McpError::invalid_params("Missing required parameter: term", None))
+                };
+
+                let glossary = &self.container.glossary_service;
+                let result = match action {
+                    "list" => serde_json::json!({
+                        "project_id": project_id,
+                        "entries": glossary.list_entries(project_id).await?,
+                    }),
+                    "add" => serde_json::json!({
+                        "entry": glossary.add_synonyms(project_id, term()?, &synonyms).await?,
+                    }),
+                    "remove" => {
+                        let entry = glossary.remove_synonyms(project_id, term()?, &synonyms).await?;
+                        serde_json::json!({
+                            "deleted": entry.is_none(),
+                            "entry": entry,
+                        })
+                    }
+                    "expand" => {
+                        let query = arg("query").ok_or_else(|| {
+                            McpError::invalid_params("Missing required parameter: query", None)
+                        })?;
+                        serde_json::json!({
+                            "query": query,
+                            "query_expansions": glossary.expand(&[project_id.to_string()], query).await?,
+                        })
+                    }
+                    other => {
+                        return Err(McpError::invalid_params(
+                            format!("Unknown action: {other}; expected list, add, remove or expand"),
+                            None,
+                        ))
+                    }
+                };
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "manage_custom_entity_types" => {
+                let args = request.arguments.unwrap_or_default();
+                let arg = |name: &str| args.get(name).and_then(|v| v.as_str());
+                let action = arg("action").ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: action", None)
+                })?;
+                let name = || {
+                    arg("name").ok_or_else(|| McpError::invalid_params("Missing required parameter: name", None))
+                };
 
-        Ok(ListToolsResult {
-            tools,
-            next_cursor: None,
-        })
-    }
+                let repository = &self.container.custom_entity_repository;
+                let result = match action {
+                    "list" => serde_json::json!({"types": repository.list_types().await?}),
+                    "get" => {
+                        let name = name()?;
+                        serde_json::json!({
+                            "type": self.custom_entity_type(name).await?,
+                            "entity_count": repository.count_of_type(name).await?,
+                        })
+                    }
+                    "define" => {
+                        let name = name()?;
+                        let schema = args.get("schema").filter(|v| v.is_object()).cloned().ok_or_else(|| {
+                            McpError::invalid_params("Missing required parameter: schema", None)
+                        })?;
+                        let searchable_fields: Vec<String> = args
+                            .get("searchable_fields")
+                            .and_then(|v| v.as_array())
+                            .map(|values| values.iter().filter_map(|v| v.as_str()).map(str::to_string).collect())
+                            .unwrap_or_default();
 
-    async fn call_tool(
-        &self,
-        request: CallToolRequestParam,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
-    ) -> Result<CallToolResult, McpError> {
-        tracing::debug!("Received call_tool request: {}", request.name);
+                        let mut definition = CustomEntityType::new(name, schema, searchable_fields);
+                        definition.description = arg("description").map(str::to_string);
+                        if let Some(existing) = repository.get_type(name).await? {
+                            definition.created_at = existing.created_at;
+                        }
+                        definition
+                            .check_definition()
+                            .map_err(|e| McpError::invalid_params(format!("Invalid custom entity type: {e}"), None))?;
+                        repository.save_type(&definition).await?;
+                        serde_json::json!({"type": definition})
+                    }
+                    "delete" => {
+                        let name = name()?;
+                        let entity_count = repository.count_of_type(name).await?;
+                        if entity_count > 0 {
+                            return Err(McpError::invalid_request(
+                                format!("Custom entity type {name} still has {entity_count} entities; delete them first"),
+                                None,
+                            ));
+                        }
+                        serde_json::json!({"deleted": repository.delete_type(name).await?, "name": name})
+                    }
+                    other => {
+                        return Err(McpError::invalid_params(
+                            format!("Unknown action: {other}; expected define, get, list or delete"),
+                            None,
+                        ))
+                    }
+                };
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
 
-        match request.name.as_ref() {
-            // Core operations (kept for convenience)
-            "list_projects" => {
-                let projects = self.container.project_service.list_projects().await?;
-                let content = serde_json::to_string_pretty(&projects).map_err(|e| {
+            "add_attachment" => {
+                let args = request.arguments.unwrap_or_default();
+                let arg = |name: &str| args.get(name).and_then(|v| v.as_str());
+                let required = |name: &str| {
+                    arg(name).ok_or_else(|| McpError::invalid_params(format!("Missing required parameter: {name}"), None))
+                };
+                let project_id = required("project_id")?;
+                let entity_type = required("entity_type")?;
+                let entity_id = required("entity_id")?;
+                let file_name = required("file_name")?;
+                if file_name.trim().is_empty() {
+                    return Err(McpError::invalid_params("file_name must not be empty", None));
+                }
+
+                let entity_project = match entity_type {
+                    "architectural_decision" => self
+                        .container
+                        .context_crud_service
+                        .get_architectural_decision(entity_id)
+                        .await?
+                        .map(|decision| decision.project_id),
+                    "feature_context" => self
+                        .container
+                        .feature_context_repository
+                        .get_by_id(entity_id)
+                        .await?
+                        .map(|feature| feature.project_id),
+                    other => {
+                        return Err(McpError::invalid_params(
+                            format!(
+                                "Attachments can't be added to {other}; expected one of {}",
+                                Attachment::ENTITY_TYPES.join(", ")
+                            ),
+                            None,
+                        ))
+                    }
+                };
+                if entity_project.as_deref() != Some(project_id) {
+                    return Err(McpError::resource_not_found(
+                        format!("No {entity_type} {entity_id} in project {project_id}"),
+                        None,
+                    ));
+                }
+
+                let content = match (arg("content"), arg("content_base64")) {
+                    (Some(text), None) => text.as_bytes().to_vec(),
+                    (None, Some(encoded)) => BASE64.decode(encoded.trim()).map_err(|e| {
+                        McpError::invalid_params(format!("content_base64 is not valid base64: {e}"), None)
+                    })?,
+                    _ => {
+                        return Err(McpError::invalid_params(
+                            "Provide exactly one of content or content_base64",
+                            None,
+                        ))
+                    }
+                };
+
+                let attachment = Attachment {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    project_id: project_id.to_string(),
+                    entity_type: entity_type.to_string(),
+                    entity_id: entity_id.to_string(),
+                    file_name: file_name.to_string(),
+                    media_type: arg("media_type").unwrap_or_else(|| media_type_for(file_name)).to_string(),
+                    size_bytes: 0,
+                    content_hash: String::new(),
+                    description: arg("description").map(str::to_string),
+                    created_at: chrono::Utc::now().to_rfc3339(),
+                    created_by: CLIENT_AGENT.try_with(Clone::clone).ok().flatten(),
+                };
+                let attachment = self.container.attachment_service.add_attachment(attachment, &content).await?;
+                let content = serde_json::to_string_pretty(&serde_json::json!({"attachment": attachment})).map_err(|e| {
                     McpError::internal_error(format!("Serialization error: {e}"), None)
                 })?;
                 Ok(CallToolResult::success(vec![Content::text(content)]))
             }
 
-            // Context Query
-            "query_context" => {
+            "get_attachment" => {
+                let args = request.arguments.unwrap_or_default();
+                let arg = |name: &str| args.get(name).and_then(|v| v.as_str());
+                let attachments = &self.container.attachment_service;
+                let (result, image) = match (arg("attachment_id"), arg("entity_id")) {
+                    (Some(id), _) => {
+                        let (attachment, content) = attachments.get_attachment(id).await?.ok_or_else(|| {
+                            McpError::resource_not_found(format!("Attachment not found: {id}"), None)
+                        })?;
+                        let encoded = BASE64.encode(&content);
+                        let image = (attachment.media_type.starts_with("image/") && !attachment.is_text())
+                            .then(|| Content::image(encoded.clone(), attachment.media_type.clone()));
+                        let mut result = serde_json::json!({"attachment": attachment});
+                        match String::from_utf8(content) {
+                            Ok(text) if attachment.is_text() => result["content"] = serde_json::json!(text),
+                            _ => result["content_base64"] = serde_json::json!(encoded),
+                        }
+                        (result, image)
+                    }
+                    (None, Some(entity_id)) => {
+                        let attachments = attachments.list_attachments(entity_id).await?;
+                        (serde_json::json!({"entity_id": entity_id, "attachments": attachments}), None)
+                    }
+                    (None, None) => {
+                        return Err(McpError::invalid_params("Provide attachment_id or entity_id", None));
+                    }
+                };
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                let mut contents = vec![Content::text(content)];
+                contents.extend(image);
+                Ok(CallToolResult::success(contents))
+            }
+
+            "run_saved_search" => {
+                let args = request.arguments.unwrap_or_default();
+                let arg = |name: &str| args.get(name).and_then(|v| v.as_str());
+                let saved_searches = &self.container.saved_search_service;
+                let search = match (arg("saved_search_id"), arg("project_id"), arg("name")) {
+                    (Some(id), _, _) => saved_searches.get_saved_search(id).await?,
+                    (None, Some(project_id), Some(name)) => {
+                        saved_searches.find_saved_search(project_id, name).await?
+                    }
+                    _ => {
+                        return Err(McpError::invalid_params(
+                            "Provide saved_search_id, or project_id and name",
+                            None,
+                        ))
+                    }
+                }
+                .ok_or_else(|| McpError::invalid_params("Saved search not found", None))?;
+
+                let mut search_to_run = search.clone();
+                if let Some(limit) = args.get("limit").and_then(|v| v.as_u64()) {
+                    search_to_run.limit = Some(limit as usize);
+                }
+                let result = saved_searches.run_saved_search(&search_to_run).await?;
+                self.container.search_click_tracker.record_results(&search.query, &result.ranked_results);
+                self.track_search_results(Some(&search.project_id), &result.ranked_results).await;
+
+                let response = serde_json::json!({
+                    "saved_search": search,
+                    "strategy": result.search_strategy,
+                    "fusion": self.container.config_manager.current().search.fusion,
+                    "total_results": result.total_results,
+                    "results": result.ranked_results,
+                    "facet_counts": result.facet_counts,
+                    "query_expansions": result.query_expansions,
+                    // Send as {"type": "Subscribe", "filters": ...} to be told when to re-run
+                    "subscribe_filters": subscribe_filters(&search),
+                });
+                let content = serde_json::to_string_pretty(&response).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            // Architecture validation
+            "validate_architecture" => {
                 let start_time = Instant::now();
                 let args = request.arguments.unwrap_or_default();
                 let project_id =
@@ -512,135 +3826,474 @@ impl ServerHandler for EnhancedContextMcpServer {
                         .ok_or_else(|| {
                             McpError::invalid_params("Missing required parameter: project_id", None)
                         })?;
-                let feature_area = args
-                    .get("feature_area")
-                    .and_then(|v| v.as_str())
-                    .ok_or_else(|| {
-                        McpError::invalid_params("Missing required parameter: feature_area", None)
-                    })?;
-                let task_type =
-                    args.get("task_type")
-                        .and_then(|v| v.as_str())
-                        .ok_or_else(|| {
-                            McpError::invalid_params("Missing required parameter: task_type", None)
-                        })?;
-                let components: Vec<String> = args
-                    .get("components")
-                    .and_then(|v| v.as_array())
-                    .map(|arr| {
-                        arr.iter()
-                            .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                            .collect()
-                    })
-                    .unwrap_or_default();
 
-                let query_result = self
-                    .container
-                    .context_query_service
-                    .query_context(project_id, feature_area, task_type, &components)
-                    .await;
+                let min_severity = match args.get("min_severity").and_then(|v| v.as_str()) {
+                    Some(severity) => severity
+                        .parse::<Severity>()
+                        .map_err(|e| McpError::invalid_params(e, None))?,
+                    None => Severity::Info,
+                };
+                let use_baseline = args.get("baseline").and_then(|v| v.as_bool()).unwrap_or(true);
+                let update_baseline = args.get("update_baseline").and_then(|v| v.as_bool()).unwrap_or(false);
+                let record_fitness = args.get("record_fitness").and_then(|v| v.as_bool()).unwrap_or(true);
+                let sarif = match args.get("format").and_then(|v| v.as_str()).unwrap_or("json") {
+                    "json" => false,
+                    "sarif" => true,
+                    other => {
+                        return Err(McpError::invalid_params(
+                            format!("Unknown format: {other}; expected json or sarif"),
+                            None,
+                        ))
+                    }
+                };
+
+                let since = match args.get("since").and_then(|v| v.as_str()) {
+                    Some(since) => Some(
+                        chrono::DateTime::parse_from_rfc3339(since)
+                            .map_err(|e| McpError::invalid_params(format!("Invalid since timestamp {since}: {e}"), None))?
+                            .with_timezone(&chrono::Utc),
+                    ),
+                    None => None,
+                };
+                let component_ids: Option<Vec<String>> = args.get("component_ids").and_then(|v| v.as_array()).map(|ids| {
+                    ids.iter().filter_map(|id| id.as_str().map(str::to_string)).collect()
+                });
+                let incremental = since.is_some() || component_ids.is_some();
+                if incremental && update_baseline {
+                    return Err(McpError::invalid_params(
+                        "update_baseline needs a full validation; leave out since and component_ids",
+                        None,
+                    ));
+                }
+
+                let service = &self.container.architecture_validation_service;
+                let validation_result = async {
+                    if update_baseline {
+                        service.acknowledge_violations(project_id).await?;
+                    }
+                    if incremental {
+                        let mut selection = component_ids.clone().unwrap_or_default();
+                        if let Some(since) = since {
+                            for id in service.changed_components(project_id, since).await? {
+                                if !selection.contains(&id) {
+                                    selection.push(id);
+                                }
+                            }
+                        }
+                        // Fitness functions measure the whole project, so they wait for a full run
+                        let comparison = if use_baseline {
+                            service.compare_with_baseline_in(project_id, &selection).await?
+                        } else {
+                            BaselineComparison {
+                                new: service.find_violations_in(project_id, &selection).await?,
+                                ..Default::default()
+                            }
+                        };
+                        let validated: Vec<String> = self
+                            .container
+                            .framework_service
+                            .list_components(project_id)
+                            .await?
+                            .into_iter()
+                            .filter(|c| selection.contains(&c.id) || selection.contains(&c.component_name))
+                            .map(|c| c.component_name)
+                            .collect();
+                        return Ok::<_, McpError>((comparison, Vec::new(), Some(validated)));
+                    }
+                    let comparison = if use_baseline {
+                        service.compare_with_baseline(project_id).await?
+                    } else {
+                        BaselineComparison {
+                            new: service.find_violations(project_id).await?,
+                            ..Default::default()
+                        }
+                    };
+                    Ok((comparison, service.evaluate_fitness(project_id, record_fitness).await?, None))
+                }
+                .await;
 
                 let duration_ms = start_time.elapsed().as_millis() as u64;
-                
-                match query_result {
-                    Ok(result) => {
-                        // Track successful query
-                        let analytics_event = AnalyticsHelper::create_context_query_event(
-                            Some(project_id.to_string()),
-                            Some(feature_area.to_string()),
-                            Some(task_type.to_string()),
-                            Some(components),
+
+                match validation_result {
+                    Ok((comparison, fitness, validated_components)) => {
+                        let findings: Vec<_> = comparison
+                            .new
+                            .into_iter()
+                            .filter(|finding| finding.severity >= min_severity)
+                            .collect();
+
+                        // Track successful validation
+                        let analytics_event = AnalyticsHelper::create_architecture_validation_event(
+                            project_id.to_string(),
+                            findings.len(),
                             Some(duration_ms),
                             true,
                             None,
                         );
                         
-                        if let Err(e) = self.container.analytics_service.track_event(analytics_event).await {
-                            tracing::warn!("Failed to track analytics event: {}", e);
+                        self.track_event(analytics_event).await;
+
+                        if sarif {
+                            let suppressed: Vec<_> = comparison
+                                .suppressed
+                                .into_iter()
+                                .filter(|finding| finding.severity >= min_severity)
+                                .collect();
+                            let components = self.container.framework_service.list_components(project_id).await?;
+                            let log = SarifExporter::export(&findings, &suppressed, &components);
+                            let content = serde_json::to_string_pretty(&log).map_err(|e| {
+                                McpError::internal_error(format!("Serialization error: {e}"), None)
+                            })?;
+                            return Ok(CallToolResult::success(vec![Content::text(content)]));
                         }
 
+                        let count = |severity: Severity| findings.iter().filter(|f| f.severity == severity).count();
+                        let result = serde_json::json!({
+                            "project_id": project_id,
+                            "errors": count(Severity::Error),
+                            "warnings": count(Severity::Warning),
+                            "infos": count(Severity::Info),
+                            "suppressed": comparison.suppressed.len(),
+                            "fixed": comparison.fixed.iter().map(|entry| &entry.message).collect::<Vec<_>>(),
+                            "baseline_file": use_baseline.then(|| self.container.config_manager.current().architecture.baseline_file),
+                            "baseline_updated": update_baseline,
+                            "validators": self.container.architecture_validator_registry.names(),
+                            "incremental": incremental,
+                            "validated_components": validated_components,
+                            "fitness": fitness,
+                            "findings": findings,
+                        });
                         let content = serde_json::to_string_pretty(&result).map_err(|e| {
                             McpError::internal_error(format!("Serialization error: {e}"), None)
                         })?;
                         Ok(CallToolResult::success(vec![Content::text(content)]))
                     }
                     Err(e) => {
-                        // Track failed query
-                        let analytics_event = AnalyticsHelper::create_context_query_event(
-                            Some(project_id.to_string()),
-                            Some(feature_area.to_string()),
-                            Some(task_type.to_string()),
-                            Some(components),
+                        // Track failed validation
+                        let analytics_event = AnalyticsHelper::create_architecture_validation_event(
+                            project_id.to_string(),
+                            0,
                             Some(duration_ms),
                             false,
                             Some(e.to_string()),
                         );
                         
-                        if let Err(analytics_err) = self.container.analytics_service.track_event(analytics_event).await {
-                            tracing::warn!("Failed to track analytics event: {}", analytics_err);
+                        self.track_event(analytics_event).await;
+
+                        Err(McpError::internal_error(format!("Validation failed: {e}"), None))
+                    }
+                }
+            }
+
+            "scan_codebase" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+                let dry_run = args.get("dry_run").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                let report = self
+                    .container
+                    .codebase_scan_service
+                    .scan_codebase(project_id, std::path::Path::new(path), dry_run)
+                    .await?;
+                let content = serde_json::to_string_pretty(&report).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "import_manifests" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+                let dry_run = args.get("dry_run").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                let report = self
+                    .container
+                    .manifest_import_service
+                    .import_manifests(project_id, std::path::Path::new(path), dry_run)
+                    .await?;
+                let content = serde_json::to_string_pretty(&report).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "export_dependency_graph" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let format_name = args.get("format").and_then(|v| v.as_str()).unwrap_or("mermaid");
+                let format = GraphFormat::parse(format_name).ok_or_else(|| {
+                    McpError::invalid_params(format!("Unknown format: {format_name}; expected dot or mermaid"), None)
+                })?;
+                let show_violations = args.get("show_violations").and_then(|v| v.as_bool()).unwrap_or(true);
+                let include_external = args.get("include_external").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                let components = self.container.framework_service.list_components(project_id).await?;
+                let mut violations = HashMap::new();
+                if show_violations {
+                    for component in &components {
+                        let found = self
+                            .container
+                            .architecture_validation_service
+                            .validate_component_dependencies(component)
+                            .await?;
+                        if !found.is_empty() {
+                            violations.insert(component.id.clone(), found);
                         }
+                    }
+                }
+                let graph = DependencyGraph::build(&components, show_violations.then_some(&violations), include_external);
+
+                let result = serde_json::json!({
+                    "project_id": project_id,
+                    "format": format.as_str(),
+                    "components": components.len(),
+                    "dependencies": graph.edges.len(),
+                    "violations": violations.values().map(Vec::len).sum::<usize>(),
+                    "graph": graph.render(format),
+                });
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "detect_dependency_cycles" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let spec_project = args.get("spec_project").and_then(|v| v.as_str());
+
+                let cycles = self
+                    .container
+                    .architecture_validation_service
+                    .detect_cycles(project_id, spec_project)
+                    .await?;
+                let count = |kind: &str| cycles.iter().filter(|cycle| cycle.kind == kind).count();
+                let result = serde_json::json!({
+                    "project_id": project_id,
+                    "component_cycles": count("component"),
+                    "phase_cycles": count("phase"),
+                    "task_cycles": count("task"),
+                    "cycles": cycles,
+                });
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "get_fitness_trend" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let function = args.get("function").and_then(|v| v.as_str());
+                let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(20) as usize;
+
+                let measurements = self
+                    .container
+                    .architecture_validation_service
+                    .fitness_history(project_id, function, limit)
+                    .await?;
+                let result = serde_json::json!({
+                    "project_id": project_id,
+                    "function": function,
+                    "count": measurements.len(),
+                    "measurements": measurements,
+                });
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "manage_architecture_rules" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let action = args.get("action").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: action", None)
+                })?;
+                let validation = &self.container.architecture_validation_service;
+                let repository = &self.container.architecture_rule_set_repository;
 
-                        Err(McpError::internal_error(format!("Query failed: {e}"), None))
+                let result = match action {
+                    "get" => serde_json::json!({
+                        "rules": validation.rule_set(project_id).await?,
+                        "custom": repository.find_by_project_id(project_id).await?.is_some(),
+                    }),
+                    "set" => {
+                        let field = |name: &str| args.get(name).cloned().unwrap_or_else(|| serde_json::json!([]));
+                        let rules: ArchitectureRuleSet = serde_json::from_value(serde_json::json!({
+                            "project_id": project_id,
+                            "layers": field("layers"),
+                            "forbidden_imports": field("forbidden_imports"),
+                            "naming_patterns": field("naming_patterns"),
+                            "fitness_functions": field("fitness_functions"),
+                            "updated_at": chrono::Utc::now().to_rfc3339(),
+                        }))
+                        .map_err(|e| McpError::invalid_params(format!("Invalid architecture rules: {e}"), None))?;
+                        if rules.layers.is_empty() {
+                            return Err(McpError::invalid_params("Architecture rules need at least one layer", None));
+                        }
+                        let problems = rules.problems();
+                        if !problems.is_empty() {
+                            return Err(McpError::invalid_params(
+                                format!("Invalid architecture rules: {}", problems.join("; ")),
+                                None,
+                            ));
+                        }
+                        let rules = repository.save(&rules).await?;
+                        serde_json::json!({
+                            "rules": rules,
+                            "custom": true,
+                            "violations": validation.validate_architecture(project_id).await?,
+                        })
                     }
-                }
+                    "reset" => {
+                        repository.delete(project_id).await?;
+                        serde_json::json!({
+                            "rules": ArchitectureRuleSet::clean_architecture(project_id),
+                            "custom": false,
+                        })
+                    }
+                    _ => {
+                        return Err(McpError::invalid_params(
+                            format!("Unknown action: {action}; expected get, set or reset"),
+                            None,
+                        ));
+                    }
+                };
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
             }
 
-            // Architecture validation
-            "validate_architecture" => {
-                let start_time = Instant::now();
+            "manage_quality_rubric" => {
                 let args = request.arguments.unwrap_or_default();
-                let project_id =
-                    args.get("project_id")
-                        .and_then(|v| v.as_str())
-                        .ok_or_else(|| {
-                            McpError::invalid_params("Missing required parameter: project_id", None)
-                        })?;
-
-                let validation_result = self
-                    .container
-                    .architecture_validation_service
-                    .validate_architecture(project_id)
-                    .await;
-
-                let duration_ms = start_time.elapsed().as_millis() as u64;
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let action = args.get("action").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: action", None)
+                })?;
+                let quality = &self.container.context_quality_service;
+                let repository = &self.container.quality_rubric_repository;
+                let rubric_error = |e: anyhow::Error| McpError::internal_error(format!("Failed to read the quality rubric: {e}"), None);
 
-                match validation_result {
-                    Ok(violations) => {
-                        // Track successful validation
-                        let analytics_event = AnalyticsHelper::create_architecture_validation_event(
-                            project_id.to_string(),
-                            violations.len(),
-                            Some(duration_ms),
-                            true,
-                            None,
-                        );
-                        
-                        if let Err(e) = self.container.analytics_service.track_event(analytics_event).await {
-                            tracing::warn!("Failed to track analytics event: {}", e);
+                let result = match action {
+                    "get" => serde_json::json!({
+                        "rubric": quality.rubric(project_id).await.map_err(rubric_error)?,
+                        "custom": repository.find_by_project_id(project_id).await?.is_some(),
+                    }),
+                    "set" => {
+                        let rubric: QualityRubric = serde_json::from_value(serde_json::json!({
+                            "project_id": project_id,
+                            "required_fields": args.get("required_fields").cloned().unwrap_or_else(|| serde_json::json!({})),
+                            "min_description_length": args.get("min_description_length").cloned().unwrap_or_else(|| serde_json::json!(0)),
+                            "require_examples": args.get("require_examples").cloned().unwrap_or(serde_json::Value::Bool(false)),
+                            "require_requirement_link": args.get("require_requirement_link").cloned().unwrap_or(serde_json::Value::Bool(false)),
+                            "updated_at": chrono::Utc::now().to_rfc3339(),
+                        }))
+                        .map_err(|e| McpError::invalid_params(format!("Invalid quality rubric: {e}"), None))?;
+                        let problems = rubric.problems();
+                        if !problems.is_empty() {
+                            return Err(McpError::invalid_params(
+                                format!("Invalid quality rubric: {}", problems.join("; ")),
+                                None,
+                            ));
                         }
-
-                        let content = serde_json::to_string_pretty(&violations).map_err(|e| {
-                            McpError::internal_error(format!("Serialization error: {e}"), None)
-                        })?;
-                        Ok(CallToolResult::success(vec![Content::text(content)]))
+                        serde_json::json!({
+                            "rubric": repository.save(&rubric).await?,
+                            "custom": true,
+                        })
                     }
-                    Err(e) => {
-                        // Track failed validation
-                        let analytics_event = AnalyticsHelper::create_architecture_validation_event(
-                            project_id.to_string(),
-                            0,
-                            Some(duration_ms),
-                            false,
-                            Some(e.to_string()),
-                        );
-                        
-                        if let Err(analytics_err) = self.container.analytics_service.track_event(analytics_event).await {
-                            tracing::warn!("Failed to track analytics event: {}", analytics_err);
-                        }
-
-                        Err(McpError::internal_error(format!("Validation failed: {e}"), None))
+                    "reset" => {
+                        repository.delete(project_id).await?;
+                        serde_json::json!({
+                            "rubric": quality.rubric(project_id).await.map_err(rubric_error)?,
+                            "custom": false,
+                        })
+                    }
+                    _ => {
+                        return Err(McpError::invalid_params(
+                            format!("Unknown action: {action}; expected get, set or reset"),
+                            None,
+                        ));
                     }
+                };
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "assess_context_quality" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let entity_type = args.get("entity_type").and_then(|v| v.as_str());
+                let limit = match args.get("limit") {
+                    None => 20,
+                    Some(v) => v.as_u64().filter(|limit| *limit > 0).ok_or_else(|| {
+                        McpError::invalid_params("limit must be a positive integer", None)
+                    })? as usize,
+                };
+
+                let items = self.quality_items(project_id, entity_type).await?;
+                let quality = &self.container.context_quality_service;
+                let quality_error = |e: anyhow::Error| McpError::internal_error(format!("Quality assessment failed: {e}"), None);
+                let assessments = quality.assess_context_batch(&items).await.map_err(quality_error)?;
+
+                let mut assessed = Vec::new();
+                for (item, assessment) in items.iter().zip(assessments) {
+                    let checks = quality.check_rubric(item).await.map_err(quality_error)?;
+                    let metrics = assessment.quality_metrics;
+                    assessed.push(serde_json::json!({
+                        "id": item.id,
+                        "entity_type": item.content.content_type.as_str(),
+                        "title": item.content.title,
+                        "overall_score": metrics.overall_score,
+                        "completeness_score": metrics.completeness_score,
+                        "rubric_checks": checks,
+                        "issues": metrics.issues,
+                        "suggestions": assessment.improvement_suggestions,
+                    }));
                 }
+                let score = |entry: &serde_json::Value| entry["overall_score"].as_f64().unwrap_or(0.0);
+                let average_score = if assessed.is_empty() {
+                    0.0
+                } else {
+                    assessed.iter().map(score).sum::<f64>() / assessed.len() as f64
+                };
+                assessed.sort_by(|a, b| score(a).total_cmp(&score(b)));
+                let total = assessed.len();
+                assessed.truncate(limit);
+
+                let result = serde_json::json!({
+                    "project_id": project_id,
+                    "rubric": quality.rubric(project_id).await.map_err(quality_error)?,
+                    "custom_rubric": self.container.quality_rubric_repository.find_by_project_id(project_id).await?.is_some(),
+                    "assessed": total,
+                    "average_score": average_score,
+                    "items": assessed,
+                });
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
             }
 
             // Server capabilities
@@ -667,6 +4320,9 @@ impl ServerHandler for EnhancedContextMcpServer {
                                 "get_entity".to_string(),
                                 "list_entities".to_string(),
                                 "bulk_*".to_string(),
+                                "manage_custom_entity_types".to_string(),
+                                "add_attachment".to_string(),
+                                "get_attachment".to_string(),
                             ],
                         },
                         FeatureInfo {
@@ -683,11 +4339,25 @@ impl ServerHandler for EnhancedContextMcpServer {
                             status: FeatureStatus::Implemented,
                             tools: vec![
                                 "scan_specifications".to_string(),
+                                "scan_spec_sources".to_string(),
                                 "import_specification".to_string(),
+                                "accept_suggested_links".to_string(),
                                 "validate_specification".to_string(),
+                                "export_specification".to_string(),
+                                "generate_spec_template".to_string(),
+                                "get_workflow_state".to_string(),
+                                "transition_workflow".to_string(),
+                                "approve_workflow_item".to_string(),
+                                "manage_decision_links".to_string(),
+                                "generate_traceability_matrix".to_string(),
+                                "analyze_requirement_coverage".to_string(),
                                 "get_specification_versions".to_string(),
                                 "compare_specification_versions".to_string(),
+                                "squash_spec_versions".to_string(),
                                 "start_spec_monitoring".to_string(),
+                                "scan_adrs".to_string(),
+                                "start_adr_monitoring".to_string(),
+                                "import_openapi".to_string(),
                             ],
                         },
                         FeatureInfo {
@@ -711,7 +4381,27 @@ impl ServerHandler for EnhancedContextMcpServer {
                             tools: vec![
                                 "query_context".to_string(),
                                 "validate_architecture".to_string(),
+                                "manage_architecture_rules".to_string(),
+                                "manage_quality_rubric".to_string(),
+                                "assess_context_quality".to_string(),
+                                "scan_codebase".to_string(),
+                                "import_manifests".to_string(),
+                                "export_dependency_graph".to_string(),
+                                "detect_dependency_cycles".to_string(),
+                                "get_fitness_trend".to_string(),
                                 "get_context_insights".to_string(),
+                                "generate_context_digest".to_string(),
+                                "get_context_digest".to_string(),
+                                "get_related_context".to_string(),
+                                "get_tag_suggestions".to_string(),
+                                "review_tag_suggestions".to_string(),
+                                "verify_context".to_string(),
+                                "promote_to_shared".to_string(),
+                                "submit_for_review".to_string(),
+                                "record_review".to_string(),
+                                "list_reviews".to_string(),
+                                "list_stale_context".to_string(),
+                                "analyze_context_gaps".to_string(),
                                 "generate_quality_report".to_string(),
                             ],
                         },
@@ -725,6 +4415,12 @@ impl ServerHandler for EnhancedContextMcpServer {
                                 "get_context_insights".to_string(),
                                 "generate_quality_report".to_string(),
                                 "export_analytics_data".to_string(),
+                                "prune_analytics".to_string(),
+                                "get_unused_context".to_string(),
+                                "suggest_context".to_string(),
+                                "run_scheduled_reports".to_string(),
+                                "generate_dashboard".to_string(),
+                                "get_audit_log".to_string(),
                             ],
                         },
                         FeatureInfo {
@@ -848,6 +4544,153 @@ impl ServerHandler for EnhancedContextMcpServer {
                             ],
                             example_use: "Get curated context for implementing authentication features".to_string(),
                         },
+                        ToolInfo {
+                            name: "generate_context_digest".to_string(),
+                            description: "Language model summary of a feature area, stored and refreshed on change".to_string(),
+                            category: "Core".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                                "feature_area".to_string(),
+                            ],
+                            example_use: "Brief an agent on the payments area in a few paragraphs instead of every rule".to_string(),
+                        },
+                        ToolInfo {
+                            name: "get_context_digest".to_string(),
+                            description: "Stored feature area digests of a project".to_string(),
+                            category: "Core".to_string(),
+                            required_params: vec!["project_id".to_string()],
+                            example_use: "Read the authentication digest before starting a task".to_string(),
+                        },
+                        ToolInfo {
+                            name: "get_tag_suggestions".to_string(),
+                            description: "Tags suggested for new and updated entities, with confidence".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec!["project_id".to_string()],
+                            example_use: "See which domain area tags are waiting for review".to_string(),
+                        },
+                        ToolInfo {
+                            name: "review_tag_suggestions".to_string(),
+                            description: "Accept or reject suggested tags".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec!["suggestion_ids".to_string(), "action".to_string()],
+                            example_use: "Confirm the 'payments' tag suggested for a new business rule".to_string(),
+                        },
+                        ToolInfo {
+                            name: "verify_context".to_string(),
+                            description: "Confirm rules, decisions or requirements still hold".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec!["project_id".to_string(), "entity_ids".to_string()],
+                            example_use: "Re-confirm an ADR after reviewing it with the team".to_string(),
+                        },
+                        ToolInfo {
+                            name: "promote_to_shared".to_string(),
+                            description: "Copy rules and conventions to the shared organization project".to_string(),
+                            category: "Core".to_string(),
+                            required_params: vec!["project_id".to_string(), "entity_ids".to_string()],
+                            example_use: "Share a team's password policy rule with every project".to_string(),
+                        },
+                        ToolInfo {
+                            name: "submit_for_review".to_string(),
+                            description: "Ask people to review entities".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec!["project_id".to_string(), "entity_ids".to_string()],
+                            example_use: "Queue the business rules an agent extracted from a spec for human review".to_string(),
+                        },
+                        ToolInfo {
+                            name: "record_review".to_string(),
+                            description: "Mark an entity verified or disputed".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                                "verdict".to_string(),
+                                "reviewer".to_string(),
+                            ],
+                            example_use: "Dispute a generated rule that contradicts the refund policy".to_string(),
+                        },
+                        ToolInfo {
+                            name: "list_reviews".to_string(),
+                            description: "List pending review requests and past verdicts".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec!["project_id".to_string()],
+                            example_use: "See what is waiting for review in a project".to_string(),
+                        },
+                        ToolInfo {
+                            name: "list_stale_context".to_string(),
+                            description: "List context whose freshness decayed below the stale threshold".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec!["project_id".to_string()],
+                            example_use: "Find the business rules nobody has confirmed in a year".to_string(),
+                        },
+                        ToolInfo {
+                            name: "analyze_context_gaps".to_string(),
+                            description: "List feature areas with thin or missing rules, policies and conventions".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec!["project_id".to_string()],
+                            example_use: "Find the features specified and built without any documented business rules".to_string(),
+                        },
+                        ToolInfo {
+                            name: "search_context".to_string(),
+                            description: "Hybrid vector and keyword search with per-result score breakdowns, in one project or all".to_string(),
+                            category: "Core".to_string(),
+                            required_params: vec![
+                                "query".to_string(),
+                            ],
+                            example_use: "Find rules and decisions about token refresh, and see why each ranked where it did".to_string(),
+                        },
+                        ToolInfo {
+                            name: "get_related_context".to_string(),
+                            description: "Ranked neighborhood of an entity in the relationship graph, up to N hops".to_string(),
+                            category: "Core".to_string(),
+                            required_params: vec!["project_id".to_string(), "entity_id".to_string()],
+                            example_use: "List the rules, decisions and components to read before changing PaymentService".to_string(),
+                        },
+                        ToolInfo {
+                            name: "find_similar_entities".to_string(),
+                            description: "Entities closest to a given one by embedding, across types".to_string(),
+                            category: "Core".to_string(),
+                            required_params: vec!["entity_id".to_string()],
+                            example_use: "Check for duplicate business rules before adding a new one".to_string(),
+                        },
+                        ToolInfo {
+                            name: "run_saved_search".to_string(),
+                            description: "Re-run a named search saved as a saved_search entity".to_string(),
+                            category: "Core".to_string(),
+                            required_params: vec![],
+                            example_use: "Pull the latest payment rules with the team's stored query and filters".to_string(),
+                        },
+                        ToolInfo {
+                            name: "manage_glossary".to_string(),
+                            description: "Maintain per-project synonyms used to expand search queries".to_string(),
+                            category: "Core".to_string(),
+                            required_params: vec!["action".to_string(), "project_id".to_string()],
+                            example_use: "Make searches for \"ADR\" find architectural decisions".to_string(),
+                        },
+                        ToolInfo {
+                            name: "manage_custom_entity_types".to_string(),
+                            description: "Register team-defined entity types stored and searched as custom_entity".to_string(),
+                            category: "Core".to_string(),
+                            required_params: vec!["action".to_string()],
+                            example_use: "Define an \"slo\" type so service level objectives are kept as context".to_string(),
+                        },
+                        ToolInfo {
+                            name: "add_attachment".to_string(),
+                            description: "Attach diagrams and sample payloads to decisions and feature contexts".to_string(),
+                            category: "Core".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                                "entity_type".to_string(),
+                                "entity_id".to_string(),
+                                "file_name".to_string(),
+                            ],
+                            example_use: "Keep the sequence diagram of a checkout flow with its ADR".to_string(),
+                        },
+                        ToolInfo {
+                            name: "get_attachment".to_string(),
+                            description: "Read an attachment, or list an entity's attachments".to_string(),
+                            category: "Core".to_string(),
+                            required_params: vec![],
+                            example_use: "Fetch the sample payload attached to a feature context".to_string(),
+                        },
                         // Universal CRUD Operations
                         ToolInfo {
                             name: "create_entity".to_string(),
@@ -887,22 +4730,140 @@ impl ServerHandler for EnhancedContextMcpServer {
                             example_use: "Import all specifications from .kiro/specs directory".to_string(),
                         },
                         ToolInfo {
-                            name: "import_specification".to_string(),
-                            description: "Import a single specification file with parsing".to_string(),
+                            name: "import_specification".to_string(),
+                            description: "Import a single specification file with parsing".to_string(),
+                            category: "Specifications".to_string(),
+                            required_params: vec![
+                                "file_path".to_string(),
+                            ],
+                            example_use: "Import specific requirements.md or tasks.md file".to_string(),
+                        },
+                        ToolInfo {
+                            name: "accept_suggested_links".to_string(),
+                            description: "Accept context links suggested when a specification was imported".to_string(),
+                            category: "Specifications".to_string(),
+                            required_params: vec![
+                                "spec_id".to_string(),
+                            ],
+                            example_use: "Link requirements to the business rules found for them on import".to_string(),
+                        },
+                        ToolInfo {
+                            name: "validate_specification".to_string(),
+                            description: "Validate specification format and content".to_string(),
+                            category: "Specifications".to_string(),
+                            required_params: vec![
+                                "file_path".to_string(),
+                            ],
+                            example_use: "Check specification file for format issues and completeness".to_string(),
+                        },
+                        ToolInfo {
+                            name: "export_specification".to_string(),
+                            description: "Render a stored specification back to Kiro markdown".to_string(),
+                            category: "Specifications".to_string(),
+                            required_params: vec![
+                                "spec_id".to_string(),
+                            ],
+                            example_use: "Write task statuses updated through the server back to tasks.md".to_string(),
+                        },
+                        ToolInfo {
+                            name: "get_workflow_state".to_string(),
+                            description: "Show where a specification or decision stands in review".to_string(),
+                            category: "Specifications".to_string(),
+                            required_params: vec!["entity_type".to_string(), "entity_id".to_string()],
+                            example_use: "See which approvers a specification still waits for".to_string(),
+                        },
+                        ToolInfo {
+                            name: "transition_workflow".to_string(),
+                            description: "Move a specification or decision between draft, in_review, approved and deprecated".to_string(),
+                            category: "Specifications".to_string(),
+                            required_params: vec![
+                                "entity_type".to_string(),
+                                "entity_id".to_string(),
+                                "to_state".to_string(),
+                                "actor".to_string(),
+                            ],
+                            example_use: "Put a new ADR in review with two named approvers".to_string(),
+                        },
+                        ToolInfo {
+                            name: "approve_workflow_item".to_string(),
+                            description: "Approve a specification or decision in review".to_string(),
+                            category: "Specifications".to_string(),
+                            required_params: vec![
+                                "entity_type".to_string(),
+                                "entity_id".to_string(),
+                                "approver".to_string(),
+                            ],
+                            example_use: "Sign off a requirements specification so query_context returns it".to_string(),
+                        },
+                        ToolInfo {
+                            name: "manage_decision_links".to_string(),
+                            description: "Record that a decision supersedes, amends or relates to another".to_string(),
+                            category: "Specifications".to_string(),
+                            required_params: vec!["action".to_string(), "project_id".to_string()],
+                            example_use: "Mark ADR-0004 superseded by ADR-0012 so agents only see the newer one".to_string(),
+                        },
+                        ToolInfo {
+                            name: "generate_spec_template".to_string(),
+                            description: "Scaffold Kiro spec documents for a new feature from the project's context".to_string(),
+                            category: "Specifications".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                                "feature_name".to_string(),
+                            ],
+                            example_use: "Start a new feature's spec with the project's layers, conventions and phases already listed".to_string(),
+                        },
+                        ToolInfo {
+                            name: "generate_traceability_matrix".to_string(),
+                            description: "Trace requirements to tasks, components and commits and save the matrix as a report".to_string(),
+                            category: "Specifications".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                            ],
+                            example_use: "Find requirements without tasks, code or commits before a release".to_string(),
+                        },
+                        ToolInfo {
+                            name: "analyze_requirement_coverage".to_string(),
+                            description: "List requirements without tasks or components, or naming components that do not exist".to_string(),
+                            category: "Specifications".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                            ],
+                            example_use: "Give a lead the gaps to plan before a sprint".to_string(),
+                        },
+                        ToolInfo {
+                            name: "squash_spec_versions".to_string(),
+                            description: "Collapse intermediate specification versions into milestones and prune old ones".to_string(),
+                            category: "Specifications".to_string(),
+                            required_params: vec![],
+                            example_use: "Trim the version history of a long-running spec while keeping daily milestones".to_string(),
+                        },
+                        ToolInfo {
+                            name: "scan_spec_sources".to_string(),
+                            description: "Import every configured spec directory (Kiro, ADR, Gherkin, markdown) into one project".to_string(),
+                            category: "Specifications".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                            ],
+                            example_use: "Feed the differently organised specs of a monorepo's services into one project".to_string(),
+                        },
+                        ToolInfo {
+                            name: "scan_adrs".to_string(),
+                            description: "Import MADR or Nygard architecture decision records as architectural decisions".to_string(),
                             category: "Specifications".to_string(),
                             required_params: vec![
-                                "file_path".to_string(),
+                                "project_id".to_string(),
                             ],
-                            example_use: "Import specific requirements.md or tasks.md file".to_string(),
+                            example_use: "Import docs/adr/0001-record-architecture-decisions.md and its siblings".to_string(),
                         },
                         ToolInfo {
-                            name: "validate_specification".to_string(),
-                            description: "Validate specification format and content".to_string(),
+                            name: "import_openapi".to_string(),
+                            description: "Import an OpenAPI document as feature contexts and framework components per endpoint group".to_string(),
                             category: "Specifications".to_string(),
                             required_params: vec![
+                                "project_id".to_string(),
                                 "file_path".to_string(),
                             ],
-                            example_use: "Check specification file for format issues and completeness".to_string(),
+                            example_use: "Give agents the request and response shapes of api/openapi.yaml".to_string(),
                         },
                         // Specification Analytics
                         ToolInfo {
@@ -961,16 +4922,139 @@ impl ServerHandler for EnhancedContextMcpServer {
                             ],
                             example_use: "Export usage data in JSON or CSV format for reporting".to_string(),
                         },
+                        ToolInfo {
+                            name: "prune_analytics".to_string(),
+                            description: "Aggregate and delete analytics events past the retention period".to_string(),
+                            category: "Analytics".to_string(),
+                            required_params: vec![],
+                            example_use: "Keep 30 days of raw events and vacuum the database".to_string(),
+                        },
+                        ToolInfo {
+                            name: "run_scheduled_reports".to_string(),
+                            description: "Generate and save a project's scheduled reports now".to_string(),
+                            category: "Analytics".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                            ],
+                            example_use: "Produce this week's quality report before a planning meeting".to_string(),
+                        },
+                        ToolInfo {
+                            name: "generate_dashboard".to_string(),
+                            description: "Write a static HTML dashboard of usage, velocity and quality".to_string(),
+                            category: "Analytics".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                            ],
+                            example_use: "Share a month of project activity without running extra services".to_string(),
+                        },
+                        ToolInfo {
+                            name: "get_audit_log".to_string(),
+                            description: "Read who called which tools, when, and with what outcome".to_string(),
+                            category: "Analytics".to_string(),
+                            required_params: vec![],
+                            example_use: "Review the failed calls of one client on a shared server".to_string(),
+                        },
+                        ToolInfo {
+                            name: "get_unused_context".to_string(),
+                            description: "List context not returned by queries, searches or reads in N days".to_string(),
+                            category: "Analytics".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                            ],
+                            example_use: "Find rules and decisions nobody used in 90 days and tag them stale".to_string(),
+                        },
+                        ToolInfo {
+                            name: "suggest_context".to_string(),
+                            description: "Recommend the context similar past queries ended up using".to_string(),
+                            category: "Analytics".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                                "feature_area".to_string(),
+                            ],
+                            example_use: "See which decisions agents working on checkout usually needed".to_string(),
+                        },
                         // Architecture & Quality
                         ToolInfo {
                             name: "validate_architecture".to_string(),
-                            description: "Validate Clean Architecture rules and detect violations".to_string(),
+                            description: "Validate the project's architecture rules and report violations not in the baseline".to_string(),
                             category: "Quality".to_string(),
                             required_params: vec![
                                 "project_id".to_string(),
                             ],
                             example_use: "Check for architecture layer violations and dependency issues".to_string(),
                         },
+                        ToolInfo {
+                            name: "manage_architecture_rules".to_string(),
+                            description: "Define a project's layers, forbidden imports and naming patterns".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                                "action".to_string(),
+                            ],
+                            example_use: "Replace Clean Architecture with a hexagonal layout before validating".to_string(),
+                        },
+                        ToolInfo {
+                            name: "manage_quality_rubric".to_string(),
+                            description: "Define the fields, description length, examples and requirement links a project's context needs".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                                "action".to_string(),
+                            ],
+                            example_use: "Require every business rule to name its domain area and link to a requirement".to_string(),
+                        },
+                        ToolInfo {
+                            name: "assess_context_quality".to_string(),
+                            description: "Quality scores of a project's context against its rubric, weakest first".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec!["project_id".to_string()],
+                            example_use: "Find the decisions that miss the team's standards before a review".to_string(),
+                        },
+                        ToolInfo {
+                            name: "scan_codebase".to_string(),
+                            description: "Create or update framework components from a repository's source files".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                            ],
+                            example_use: "Populate components and their imports before validate_architecture".to_string(),
+                        },
+                        ToolInfo {
+                            name: "import_manifests".to_string(),
+                            description: "Record external dependencies from Cargo.toml, pubspec.yaml and package.json".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                            ],
+                            example_use: "Flag a presentation crate that depends on rusqlite".to_string(),
+                        },
+                        ToolInfo {
+                            name: "export_dependency_graph".to_string(),
+                            description: "Render component dependencies and layers as Graphviz DOT or Mermaid".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                            ],
+                            example_use: "Paste a Mermaid diagram of layer violations into a design review".to_string(),
+                        },
+                        ToolInfo {
+                            name: "detect_dependency_cycles".to_string(),
+                            description: "Find dependency cycles among components, phases and tasks".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                            ],
+                            example_use: "See which import to drop to untangle two services that use each other".to_string(),
+                        },
+                        ToolInfo {
+                            name: "get_fitness_trend".to_string(),
+                            description: "Recorded values of architecture fitness functions over time".to_string(),
+                            category: "Quality".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                            ],
+                            example_use: "Check whether the fan-out of services keeps growing between releases".to_string(),
+                        },
                         ToolInfo {
                             name: "generate_quality_report".to_string(),
                             description: "Generate context health assessment and quality report".to_string(),
@@ -1001,14 +5085,104 @@ impl ServerHandler for EnhancedContextMcpServer {
                             required_params: vec![],
                             example_use: "Discover available features, tools, and database schema".to_string(),
                         },
+                        ToolInfo {
+                            name: "get_effective_config".to_string(),
+                            description: "Inspect the effective configuration and reload config.toml".to_string(),
+                            category: "Management".to_string(),
+                            required_params: vec![],
+                            example_use: "Check which cache, analytics and logging settings are active".to_string(),
+                        },
+                        ToolInfo {
+                            name: "reindex_project".to_string(),
+                            description: "Rebuild a project's embeddings in the background".to_string(),
+                            category: "Management".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                            ],
+                            example_use: "Recover search after switching embedding provider or a crash".to_string(),
+                        },
+                        ToolInfo {
+                            name: "get_indexing_status".to_string(),
+                            description: "Report the background embedding backlog and failures".to_string(),
+                            category: "Management".to_string(),
+                            required_params: vec![],
+                            example_use: "Check whether recent writes are searchable yet".to_string(),
+                        },
+                        ToolInfo {
+                            name: "search_index_status".to_string(),
+                            description: "Report search index contents, size and drift from the database".to_string(),
+                            category: "Management".to_string(),
+                            required_params: vec![],
+                            example_use: "Find entities missing from search, then run reindex_project".to_string(),
+                        },
+                        ToolInfo {
+                            name: "get_active_clients".to_string(),
+                            description: "List connected WebSocket clients and what they are editing".to_string(),
+                            category: "Management".to_string(),
+                            required_params: vec![],
+                            example_use: "Check whether someone else is editing a business rule before changing it".to_string(),
+                        },
+                        ToolInfo {
+                            name: "list_conflicts".to_string(),
+                            description: "List conflicts between concurrent changes in a project".to_string(),
+                            category: "Management".to_string(),
+                            required_params: vec![
+                                "project_id".to_string(),
+                            ],
+                            example_use: "Find edits from two IDEs that still need a decision".to_string(),
+                        },
+                        ToolInfo {
+                            name: "get_conflict".to_string(),
+                            description: "Get the competing changes of a conflict".to_string(),
+                            category: "Management".to_string(),
+                            required_params: vec![
+                                "conflict_id".to_string(),
+                            ],
+                            example_use: "Compare both versions of a business rule before choosing one".to_string(),
+                        },
+                        ToolInfo {
+                            name: "start_conflict_resolution".to_string(),
+                            description: "Open a resolution session for a conflict".to_string(),
+                            category: "Management".to_string(),
+                            required_params: vec![
+                                "conflict_id".to_string(),
+                            ],
+                            example_use: "Get the recommended strategy for a conflict".to_string(),
+                        },
+                        ToolInfo {
+                            name: "update_conflict_resolution".to_string(),
+                            description: "Choose a strategy or the values to keep in a resolution session".to_string(),
+                            category: "Management".to_string(),
+                            required_params: vec![
+                                "session_id".to_string(),
+                                "step".to_string(),
+                            ],
+                            example_use: "Select AutoMerge, then preview the merged entity".to_string(),
+                        },
+                        ToolInfo {
+                            name: "complete_conflict_resolution".to_string(),
+                            description: "Resolve a conflict with the session's strategy".to_string(),
+                            category: "Management".to_string(),
+                            required_params: vec![
+                                "session_id".to_string(),
+                            ],
+                            example_use: "Finish a resolution started by an agent or an IDE".to_string(),
+                        },
+                        ToolInfo {
+                            name: "migrate_embeddings".to_string(),
+                            description: "Switch to another embedding model without downtime".to_string(),
+                            category: "Management".to_string(),
+                            required_params: vec![],
+                            example_use: "Move from the local provider to an Ollama model, then poll with action=status".to_string(),
+                        },
                         ToolInfo {
                             name: "cache_management".to_string(),
-                            description: "Manage cache and temporary data".to_string(),
+                            description: "Clear cached query results or report cache statistics".to_string(),
                             category: "Management".to_string(),
                             required_params: vec![
                                 "action".to_string(),
                             ],
-                            example_use: "Clear project cache or global cache for performance optimization".to_string(),
+                            example_use: "Check hit rates with the stats action, clear a project's cache after bulk edits".to_string(),
                         },
                     ],
                     usage_examples: vec![
@@ -1135,39 +5309,263 @@ impl ServerHandler for EnhancedContextMcpServer {
                         })?;
                     let file_path = obj.get("file_path").and_then(|v| v.as_str());
 
-                    let component = self
-                        .container
-                        .framework_service
-                        .create_component(
-                            project_id,
-                            component_name,
-                            component_type,
-                            architecture_layer,
-                            file_path,
-                            None,
-                        )
-                        .await?;
-                    components.push(component);
-                }
+                    let component = self
+                        .container
+                        .framework_service
+                        .create_component(
+                            project_id,
+                            component_name,
+                            component_type,
+                            architecture_layer,
+                            file_path,
+                            None,
+                        )
+                        .await?;
+                    components.push(component);
+                }
+
+                let duration_ms = start_time.elapsed().as_millis() as u64;
+                
+                // Track successful bulk operation
+                let analytics_event = AnalyticsHelper::create_bulk_operation_event(
+                    Some(project_id.to_string()),
+                    "framework_component".to_string(),
+                    "bulk_create".to_string(),
+                    components.len(),
+                    Some(duration_ms),
+                    true,
+                    None,
+                );
+                
+                self.track_event(analytics_event).await;
+
+                let content = serde_json::to_string_pretty(&components).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "get_effective_config" => {
+                let args = request.arguments.unwrap_or_default();
+                let reload = args.get("reload").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                let config_manager = &self.container.config_manager;
+                let reload_report = if reload {
+                    let report = config_manager.reload().map_err(|e| {
+                        McpError::internal_error(format!("Failed to reload configuration: {e:#}"), None)
+                    })?;
+                    Some(report)
+                } else {
+                    None
+                };
+
+                let result = serde_json::json!({
+                    "source": config_manager.source().map(|p| p.display().to_string()),
+                    "config": config_manager.current(),
+                    "hot_reloadable": crate::config::manager::HOT_RELOADABLE,
+                    "reload": reload_report,
+                });
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "reindex_project" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+
+                let result = self.reindex_project(project_id).await?;
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "get_indexing_status" => {
+                let container = &self.container;
+                let active = container.embedding_service.get_model_info();
+                let stored_models = container
+                    .embedding_repository
+                    .count_embeddings_by_model()
+                    .await
+                    .map_err(|e| McpError::internal_error(format!("Failed to read embeddings: {e}"), None))?;
+                let mismatched: u64 = stored_models
+                    .iter()
+                    .filter(|m| m.model != active.model_name || m.dimension != active.embedding_dimension)
+                    .map(|m| m.count)
+                    .sum();
+                let mut status = serde_json::to_value(container.embedding_queue.status()).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                status["dimension"] = serde_json::json!(active.embedding_dimension);
+                status["stored_models"] = serde_json::json!(stored_models);
+                status["mismatched_embeddings"] = serde_json::json!(mismatched);
+                status["migration"] = serde_json::json!(container.embedding_migrator.status());
+                let content = serde_json::to_string_pretty(&status).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "search_index_status" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str());
+
+                let result = self.search_index_status(project_id).await?;
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "get_active_clients" => {
+                let args = request.arguments.unwrap_or_default();
+                let arg = |name: &str| args.get(name).and_then(|v| v.as_str());
+
+                let mut clients = self.container.websocket_manager.active_clients(arg("project_id"));
+                if let (Some(entity_type), Some(entity_id)) = (arg("entity_type"), arg("entity_id")) {
+                    clients.retain(|client| {
+                        client
+                            .editing
+                            .iter()
+                            .any(|activity| activity.entity.entity_type == entity_type && activity.entity.entity_id == entity_id)
+                    });
+                }
+                let result = serde_json::json!({ "count": clients.len(), "clients": clients });
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "list_conflicts" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let resolved = match args.get("status").and_then(|v| v.as_str()).unwrap_or("unresolved") {
+                    "unresolved" => Some(false),
+                    "resolved" => Some(true),
+                    "all" => None,
+                    other => {
+                        return Err(McpError::invalid_params(
+                            format!("Unknown status: {other}; expected unresolved, resolved or all"),
+                            None,
+                        ))
+                    }
+                };
+
+                let conflicts = self
+                    .container
+                    .conflict_resolution_engine
+                    .lock()
+                    .await
+                    .find_conflicts(project_id, resolved)
+                    .await
+                    .map_err(|e| McpError::internal_error(format!("Failed to list conflicts: {e}"), None))?;
+                let summaries: Vec<serde_json::Value> = conflicts
+                    .iter()
+                    .map(|conflict| {
+                        serde_json::json!({
+                            "conflict_id": conflict.conflict_id,
+                            "entity_type": conflict.entity_type,
+                            "entity_id": conflict.entity_id,
+                            "conflict_type": conflict.conflict_type,
+                            "changes": conflict.conflicting_changes.len(),
+                            "detected_at": conflict.detected_at,
+                            "resolved_at": conflict.resolved_at,
+                            "resolved_by": conflict.resolved_by,
+                            "resolution_strategy": conflict.resolution_strategy,
+                        })
+                    })
+                    .collect();
+                let result = serde_json::json!({ "count": summaries.len(), "conflicts": summaries });
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "get_conflict" => {
+                let args = request.arguments.unwrap_or_default();
+                let conflict_id = args.get("conflict_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: conflict_id", None)
+                })?;
+
+                let conflict = self.find_conflict(conflict_id).await?;
+                let content = serde_json::to_string_pretty(&conflict).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "start_conflict_resolution" => {
+                let args = request.arguments.unwrap_or_default();
+                let result = self.start_conflict_resolution(&args).await?;
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
 
-                let duration_ms = start_time.elapsed().as_millis() as u64;
-                
-                // Track successful bulk operation
-                let analytics_event = AnalyticsHelper::create_bulk_operation_event(
-                    Some(project_id.to_string()),
-                    "framework_component".to_string(),
-                    "bulk_create".to_string(),
-                    components.len(),
-                    Some(duration_ms),
-                    true,
-                    None,
-                );
-                
-                if let Err(e) = self.container.analytics_service.track_event(analytics_event).await {
-                    tracing::warn!("Failed to track analytics event: {}", e);
-                }
+            "update_conflict_resolution" => {
+                let args = request.arguments.unwrap_or_default();
+                let session_id = args.get("session_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: session_id", None)
+                })?;
+                let step = Self::enum_arg(&args, "step")?
+                    .ok_or_else(|| McpError::invalid_params("Missing required parameter: step", None))?;
+                let user_selections = args
+                    .get("user_selections")
+                    .and_then(|v| v.as_object())
+                    .map(|selections| selections.clone().into_iter().collect())
+                    .unwrap_or_default();
 
-                let content = serde_json::to_string_pretty(&components).map_err(|e| {
+                let response = self
+                    .container
+                    .conflict_resolution_ui
+                    .lock()
+                    .await
+                    .update_ui_state(UpdateUIStateRequest {
+                        session_id: session_id.to_string(),
+                        step,
+                        user_selections,
+                        selected_strategy: Self::enum_arg(&args, "selected_strategy")?,
+                    })
+                    .await
+                    .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+                let content = serde_json::to_string_pretty(&response).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "complete_conflict_resolution" => {
+                let args = request.arguments.unwrap_or_default();
+                let result = self.complete_conflict_resolution(&args).await?;
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "migrate_embeddings" => {
+                let args = request.arguments.unwrap_or_default();
+                let action = args.get("action").and_then(|v| v.as_str()).unwrap_or("status");
+                let result = match action {
+                    "status" => serde_json::json!(self.container.embedding_migrator.status()),
+                    "start" => self.start_embedding_migration(&args).await?,
+                    other => {
+                        return Err(McpError::invalid_params(
+                            format!("Unknown action: {other}; expected start or status"),
+                            None,
+                        ))
+                    }
+                };
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
                     McpError::internal_error(format!("Serialization error: {e}"), None)
                 })?;
                 Ok(CallToolResult::success(vec![Content::text(content)]))
@@ -1183,26 +5581,14 @@ impl ServerHandler for EnhancedContextMcpServer {
                             McpError::invalid_params("Missing required parameter: project_id", None)
                         })?;
 
-                // Clear project-related data - for now, just return a success message
-                // In a real implementation, you might want to clear cached data, temporary files, etc.
-                let result = serde_json::json!({
-                    "message": "Project cache cleared successfully",
-                    "project_id": project_id,
-                    "cleared": true,
-                    "note": "Cache clearing implementation can be customized based on your needs"
-                });
+                let result = self.clear_project_cache(project_id);
                 let content = serde_json::to_string_pretty(&result).map_err(|e| {
                     McpError::internal_error(format!("Serialization error: {e}"), None)
                 })?;
                 Ok(CallToolResult::success(vec![Content::text(content)]))
             }
             "clear_all_cache" => {
-                // This would be a nuclear option - clear everything
-                let result = serde_json::json!({
-                    "message": "All cache cleared successfully",
-                    "warning": "This operation removes all stored data",
-                    "cleared": true
-                });
+                let result = self.clear_all_cache();
                 let content = serde_json::to_string_pretty(&result).map_err(|e| {
                     McpError::internal_error(format!("Serialization error: {}", e), None)
                 })?;
@@ -1221,6 +5607,23 @@ impl ServerHandler for EnhancedContextMcpServer {
                 let id = args.get("id").and_then(|v| v.as_str()).ok_or_else(|| {
                     McpError::invalid_params("Missing required parameter: id", None)
                 })?;
+                self.track_search_click(id).await;
+
+                let cache_key = CacheKeyBuilder::entity(entity_type, id);
+                if let Some(cached) = cache_key
+                    .as_deref()
+                    .and_then(|key| self.container.query_cache.get(key))
+                {
+                    if let Some(project_id) = cached.get("project_id").and_then(|v| v.as_str()) {
+                        self.track_entity_uses(project_id, EntityUseSource::Read, &[(entity_type.to_string(), id.to_string())])
+                            .await;
+                    }
+                    let cached = self.with_entity_verification(entity_type, id, cached).await?;
+                    let content = serde_json::to_string_pretty(&cached).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?;
+                    return Ok(CallToolResult::success(vec![Content::text(content)]));
+                }
 
                 let result = match entity_type {
                     "project" => {
@@ -1251,6 +5654,14 @@ impl ServerHandler for EnhancedContextMcpServer {
                             .await?;
                         serde_json::to_value(requirement)
                     }
+                    "security_policy" => {
+                        let policy = self.container.context_crud_service.get_security_policy(id).await?;
+                        serde_json::to_value(policy)
+                    }
+                    "project_convention" => {
+                        let convention = self.container.project_convention_repository.get_by_id(id).await?;
+                        serde_json::to_value(convention)
+                    }
                     "framework_component" => {
                         let component = self.container.framework_service.get_component(id).await?;
                         serde_json::to_value(component)
@@ -1263,10 +5674,58 @@ impl ServerHandler for EnhancedContextMcpServer {
                             .await?;
                         serde_json::to_value(phase)
                     }
+                    "saved_search" => {
+                        let search = self.container.saved_search_service.get_saved_search(id).await?;
+                        serde_json::to_value(search)
+                    }
+                    "feature_context" => {
+                        let feature = self.container.context_crud_service.get_feature_context(id).await?;
+                        serde_json::to_value(feature)
+                    }
+                    "traceability_report" => {
+                        let report = self.container.traceability_service.get_report(id).await?;
+                        serde_json::to_value(report)
+                    }
+                    "glossary_term" => {
+                        let term = self.container.glossary_service.get_entry(id).await?;
+                        serde_json::to_value(term)
+                    }
+                    "api_contract" => {
+                        let contract = self.container.api_contract_repository.get_by_id(id).await?;
+                        serde_json::to_value(contract)
+                    }
+                    "custom_entity" => {
+                        let entity = self.container.custom_entity_repository.get_by_id(id).await?;
+                        let entity = match entity {
+                            Some(entity) => self.with_search_text(vec![entity]).await?.pop(),
+                            None => None,
+                        };
+                        serde_json::to_value(entity)
+                    }
+                    "scheduled_report" => {
+                        let report = self.container.report_scheduler.get_report(id).await?;
+                        serde_json::to_value(report)
+                    }
                     _ => return Err(McpError::invalid_params("Invalid entity_type", None)),
                 }
                 .map_err(|e| McpError::internal_error(format!("Serialization error: {e}"), None))?;
 
+                if let Some(project_id) = result.get("project_id").and_then(|v| v.as_str()) {
+                    self.track_entity_uses(project_id, EntityUseSource::Read, &[(entity_type.to_string(), id.to_string())])
+                        .await;
+                }
+
+                if let Some(key) = cache_key {
+                    // Agents often probe ids that don't exist; remember misses briefly too
+                    if result.is_null() {
+                        self.container.query_cache.set_not_found(key);
+                    } else {
+                        self.container.query_cache.set(key, result.clone(), None);
+                    }
+                }
+
+                // Review verdicts change without the entity, so they are added after caching
+                let result = self.with_entity_verification(entity_type, id, result).await?;
                 let content = serde_json::to_string_pretty(&result).map_err(|e| {
                     McpError::internal_error(format!("Serialization error: {e}"), None)
                 })?;
@@ -1358,20 +5817,14 @@ impl ServerHandler for EnhancedContextMcpServer {
                                     None,
                                 )
                             })?;
-                        serde_json::json!({
-                            "message": "Project cache cleared successfully",
-                            "project_id": project_id,
-                            "cleared": true,
-                            "note": "Cache clearing implementation can be customized based on your needs"
-                        })
+                        self.clear_project_cache(project_id)
                     }
-                    "clear_all" => {
-                        serde_json::json!({
-                            "message": "All cache cleared successfully",
-                            "warning": "This operation removes all stored data",
-                            "cleared": true
-                        })
+                    "clear_all" => self.clear_all_cache(),
+                    "stats" if args.get("format").and_then(|v| v.as_str()) == Some("prometheus") => {
+                        let metrics = self.container.query_cache.stats().to_prometheus();
+                        return Ok(CallToolResult::success(vec![Content::text(metrics)]));
                     }
+                    "stats" => self.cache_stats(),
                     _ => return Err(McpError::invalid_params("Unsupported cache action", None)),
                 };
 
@@ -1389,6 +5842,23 @@ impl ServerHandler for EnhancedContextMcpServer {
                     McpError::invalid_params("Missing required parameter: scope", None)
                 })?;
 
+                if scope == "sync" {
+                    let conflicts = self.container.conflict_resolution_engine.lock().await.metrics();
+                    let metrics = SyncMetrics::collect(
+                        &self.container.websocket_manager.broadcaster().get_metrics(),
+                        self.container.websocket_manager.delivery_stats(),
+                        &conflicts,
+                    );
+                    let content = if args.get("format").and_then(|v| v.as_str()) == Some("prometheus") {
+                        metrics.to_prometheus()
+                    } else {
+                        serde_json::to_string_pretty(&metrics).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {e}"), None)
+                        })?
+                    };
+                    return Ok(CallToolResult::success(vec![Content::text(content)]));
+                }
+
                 let analytics_result = match scope {
                     "global" => {
                         self.container.analytics_service.get_global_statistics().await
@@ -1406,7 +5876,7 @@ impl ServerHandler for EnhancedContextMcpServer {
                             Err(e) => Err(e),
                         }
                     }
-                    _ => return Err(McpError::invalid_params("Invalid scope. Must be 'global' or 'entity'", None)),
+                    _ => return Err(McpError::invalid_params("Invalid scope. Must be 'global', 'entity' or 'sync'", None)),
                 };
 
                 let duration_ms = start_time.elapsed().as_millis() as u64;
@@ -1422,32 +5892,602 @@ impl ServerHandler for EnhancedContextMcpServer {
                             None,
                         );
                         
-                        if let Err(e) = self.container.analytics_service.track_event(analytics_event).await {
-                            tracing::warn!("Failed to track analytics event: {}", e);
-                        }
+                        self.track_event(analytics_event).await;
 
                         let content = serde_json::to_string_pretty(&result).map_err(|e| {
                             McpError::internal_error(format!("Serialization error: {e}"), None)
                         })?;
                         Ok(CallToolResult::success(vec![Content::text(content)]))
                     }
-                    Err(e) => {
-                        // Track failed analytics query
-                        let analytics_event = AnalyticsHelper::create_analytics_event(
-                            "get_usage_analytics".to_string(),
-                            Some(scope.to_string()),
-                            Some(duration_ms),
-                            false,
-                            Some(e.to_string()),
-                        );
-                        
-                        if let Err(analytics_err) = self.container.analytics_service.track_event(analytics_event).await {
-                            tracing::warn!("Failed to track analytics event: {}", analytics_err);
-                        }
+                    Err(e) => {
+                        // Track failed analytics query
+                        let analytics_event = AnalyticsHelper::create_analytics_event(
+                            "get_usage_analytics".to_string(),
+                            Some(scope.to_string()),
+                            Some(duration_ms),
+                            false,
+                            Some(e.to_string()),
+                        );
+                        
+                        self.track_event(analytics_event).await;
+
+                        Err(McpError::internal_error(format!("Analytics query failed: {e}"), None))
+                    }
+                }
+            }
+
+            "generate_context_digest" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let feature_area = args
+                    .get("feature_area")
+                    .and_then(|v| v.as_str())
+                    .filter(|area| !area.trim().is_empty())
+                    .ok_or_else(|| McpError::invalid_params("Missing required parameter: feature_area", None))?;
+                let force = args.get("force").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                let generator = self.digest_generator()?.ok_or_else(|| {
+                    McpError::invalid_request("Context digests are disabled ([digests] provider = \"none\")", None)
+                })?;
+                let digest = self
+                    .container
+                    .context_intelligence_service
+                    .generate_context_digest(project_id, feature_area, generator.as_ref(), force)
+                    .await
+                    .map_err(|e| McpError::internal_error(format!("Digest generation failed: {e}"), None))?;
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&digest).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "get_related_context" => {
+                let args = request.arguments.unwrap_or_default();
+                let text = |name: &str| {
+                    args.get(name)
+                        .and_then(|v| v.as_str())
+                        .ok_or_else(|| McpError::invalid_params(format!("Missing required parameter: {name}"), None))
+                };
+                let project_id = text("project_id")?;
+                let entity_id = text("entity_id")?;
+                let max_hops = match args.get("max_hops") {
+                    Some(value) => value
+                        .as_u64()
+                        .filter(|hops| (1..=4).contains(hops))
+                        .ok_or_else(|| McpError::invalid_params("max_hops must be between 1 and 4", None))?
+                        as usize,
+                    None => 2,
+                };
+                let include_inferred = args.get("include_inferred").and_then(|v| v.as_bool()).unwrap_or(true);
+                let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(20) as usize;
+
+                let items = self.related_items(project_id).await?;
+                let Some(entity) = items.iter().find(|item| item.id == entity_id) else {
+                    return Err(McpError::invalid_params(format!("Entity {entity_id} not found in project {project_id}"), None));
+                };
+                let entity = serde_json::json!({
+                    "id": entity.id,
+                    "entity_type": entity.content.content_type.as_str(),
+                    "title": entity.content.title,
+                });
+                let related = self
+                    .container
+                    .context_intelligence_service
+                    .find_related_context(entity_id, &items, max_hops, include_inferred)
+                    .await
+                    .map_err(|e| McpError::internal_error(format!("Failed to walk the relationship graph: {e}"), None))?;
+
+                let neighbors: Vec<serde_json::Value> = related
+                    .iter()
+                    .take(limit)
+                    .map(|neighbor| {
+                        serde_json::json!({
+                            "id": neighbor.context.id,
+                            "entity_type": neighbor.context.content.content_type.as_str(),
+                            "title": neighbor.context.content.title,
+                            "score": (neighbor.strength * 1000.0).round() / 1000.0,
+                            "hops": neighbor.depth,
+                            "relationship": neighbor.relationship_type.as_str(),
+                            "direction": if neighbor.incoming { "incoming" } else { "outgoing" },
+                            "inferred": neighbor.inferred,
+                            "path": neighbor.path,
+                        })
+                    })
+                    .collect();
+                let result = serde_json::json!({
+                    "entity": entity,
+                    "max_hops": max_hops,
+                    "total": related.len(),
+                    "related": neighbors,
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "get_tag_suggestions" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let status = match args.get("status").and_then(|v| v.as_str()).unwrap_or("pending") {
+                    "all" => None,
+                    status => Some(TagSuggestionStatus::parse(status).ok_or_else(|| {
+                        McpError::invalid_params(format!("Unknown status: {status}"), None)
+                    })?),
+                };
+
+                let suggestions = self
+                    .container
+                    .context_intelligence_service
+                    .get_tag_suggestions(project_id, status)
+                    .await
+                    .map_err(|e| McpError::internal_error(format!("Failed to read tag suggestions: {e}"), None))?;
+                let result = serde_json::json!({
+                    "count": suggestions.len(),
+                    "suggestions": suggestions
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "review_tag_suggestions" => {
+                let args = request.arguments.unwrap_or_default();
+                let suggestion_ids: Vec<String> = args
+                    .get("suggestion_ids")
+                    .and_then(|v| v.as_array())
+                    .ok_or_else(|| McpError::invalid_params("Missing required parameter: suggestion_ids", None))?
+                    .iter()
+                    .filter_map(|id| id.as_str().map(str::to_string))
+                    .collect();
+                let accept = match args.get("action").and_then(|v| v.as_str()) {
+                    Some("accept") => true,
+                    Some("reject") => false,
+                    _ => return Err(McpError::invalid_params("action must be \"accept\" or \"reject\"", None)),
+                };
+
+                let reviewed = self
+                    .container
+                    .context_intelligence_service
+                    .review_tag_suggestions(&suggestion_ids, accept)
+                    .await
+                    .map_err(|e| McpError::invalid_request(format!("Failed to review tag suggestions: {e}"), None))?;
+                let result = serde_json::json!({
+                    "count": reviewed.len(),
+                    "suggestions": reviewed
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "verify_context" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let entity_ids: Vec<&str> = args
+                    .get("entity_ids")
+                    .and_then(|v| v.as_array())
+                    .ok_or_else(|| McpError::invalid_params("Missing required parameter: entity_ids", None))?
+                    .iter()
+                    .filter_map(|id| id.as_str())
+                    .collect();
+                let note = args.get("note").and_then(|v| v.as_str());
+
+                let entities = self.project_entities(project_id).await?;
+                let settings = self.container.config_manager.current().freshness.clone();
+                let now = chrono::Utc::now();
+                let mut verified = Vec::new();
+                let mut not_found = Vec::new();
+                for entity_id in entity_ids {
+                    let Some((entity_type, entity)) = entities.iter().find(|(entity_type, entity)| {
+                        FRESHNESS_ENTITY_TYPES.contains(entity_type)
+                            && entity.get("id").and_then(|v| v.as_str()) == Some(entity_id)
+                    }) else {
+                        not_found.push(entity_id);
+                        continue;
+                    };
+                    let mut verification = EntityVerification::new(project_id, entity_type, entity_id);
+                    verification.verified_by = CLIENT_AGENT.try_with(Clone::clone).ok().flatten();
+                    verification.note = note.map(str::to_string);
+                    self.container.entity_verification_repository.record(&verification).await?;
+                    let mut review = EntityReview::request(project_id, entity_type, entity_id);
+                    review.requested_at = None;
+                    review.record(
+                        VerificationStatus::Verified,
+                        verification.verified_by.clone(),
+                        verification.note.clone(),
+                    );
+                    self.container.entity_review_repository.save(&review).await?;
+                    verified.extend(EntityFreshness::of(entity_type, entity, Some(&verification), &settings, now));
+                }
+                // Rankings cached by query_context include freshness
+                self.container.query_cache.invalidate_namespace("context");
+
+                let result = serde_json::json!({
+                    "count": verified.len(),
+                    "verified": verified,
+                    "not_found": not_found
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "promote_to_shared" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let entity_ids: Vec<&str> = args
+                    .get("entity_ids")
+                    .and_then(|v| v.as_array())
+                    .ok_or_else(|| McpError::invalid_params("Missing required parameter: entity_ids", None))?
+                    .iter()
+                    .filter_map(|id| id.as_str())
+                    .collect();
+                let shared_project_id = args
+                    .get("shared_project_id")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+                    .or_else(|| self.container.config_manager.current().shared_context.project_id)
+                    .ok_or_else(|| {
+                        McpError::invalid_params(
+                            "No shared project: pass shared_project_id or set [shared_context] project_id",
+                            None,
+                        )
+                    })?;
+                if shared_project_id == project_id {
+                    return Err(McpError::invalid_params("The project is the shared project", None));
+                }
+                if self.container.project_service.get_project(&shared_project_id).await?.is_none() {
+                    return Err(McpError::resource_not_found(
+                        format!("Shared project not found: {shared_project_id}"),
+                        None,
+                    ));
+                }
+
+                let mut promoted = Vec::new();
+                let mut not_found = Vec::new();
+                for entity_id in entity_ids {
+                    match self.promote_entity(project_id, entity_id, &shared_project_id).await? {
+                        Some(link) => promoted.push(link),
+                        None => not_found.push(entity_id),
+                    }
+                }
+
+                let result = serde_json::json!({
+                    "shared_project_id": shared_project_id,
+                    "promotable_entity_types": PROMOTABLE_ENTITY_TYPES,
+                    "count": promoted.len(),
+                    "promoted": promoted,
+                    "not_found": not_found
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "submit_for_review" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let entity_ids: Vec<&str> = args
+                    .get("entity_ids")
+                    .and_then(|v| v.as_array())
+                    .ok_or_else(|| McpError::invalid_params("Missing required parameter: entity_ids", None))?
+                    .iter()
+                    .filter_map(|id| id.as_str())
+                    .collect();
+                let requested_by = args
+                    .get("requested_by")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+                    .or_else(|| CLIENT_AGENT.try_with(Clone::clone).ok().flatten());
+                let note = args.get("note").and_then(|v| v.as_str());
+
+                let entities = self.reviewable_entities(project_id).await?;
+                let reviews = self.container.entity_review_repository.find_by_project(project_id).await?;
+                let mut submitted = Vec::new();
+                let mut not_found = Vec::new();
+                for entity_id in entity_ids {
+                    let Some((entity_type, _)) = entities
+                        .iter()
+                        .find(|(_, entity)| entity.get("id").and_then(|v| v.as_str()) == Some(entity_id))
+                    else {
+                        not_found.push(entity_id);
+                        continue;
+                    };
+                    // An entity has one pending review at a time; submitting again updates it
+                    let mut review = reviews
+                        .iter()
+                        .find(|review| review.entity_id == entity_id && review.is_pending())
+                        .cloned()
+                        .unwrap_or_else(|| EntityReview::request(project_id, entity_type, entity_id));
+                    review.requested_by = requested_by.clone();
+                    review.note = note.map(str::to_string);
+                    self.container.entity_review_repository.save(&review).await?;
+                    submitted.push(review);
+                }
+
+                let result = serde_json::json!({
+                    "count": submitted.len(),
+                    "submitted": submitted,
+                    "not_found": not_found
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "record_review" => {
+                let args = request.arguments.unwrap_or_default();
+                let required = |name: &str| {
+                    args.get(name)
+                        .and_then(|v| v.as_str())
+                        .ok_or_else(|| McpError::invalid_params(format!("Missing required parameter: {name}"), None))
+                };
+                let project_id = required("project_id")?;
+                let reviewer = required("reviewer")?;
+                let verdict = match VerificationStatus::parse(required("verdict")?) {
+                    Some(verdict @ (VerificationStatus::Verified | VerificationStatus::Disputed)) => verdict,
+                    _ => return Err(McpError::invalid_params("verdict must be verified or disputed", None)),
+                };
+                let comment = args.get("comment").and_then(|v| v.as_str()).map(str::to_string);
+
+                let container = &self.container;
+                let (entity_id, pending) = match args.get("review_id").and_then(|v| v.as_str()) {
+                    Some(review_id) => {
+                        let review = container
+                            .entity_review_repository
+                            .find(review_id)
+                            .await?
+                            .filter(|review| review.project_id == project_id)
+                            .ok_or_else(|| {
+                                McpError::resource_not_found(format!("Review not found: {review_id}"), None)
+                            })?;
+                        (review.entity_id.clone(), Some(review))
+                    }
+                    None => {
+                        let entity_id = args.get("entity_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                            McpError::invalid_params("Missing required parameter: entity_id or review_id", None)
+                        })?;
+                        let pending = container
+                            .entity_review_repository
+                            .find_by_entity(entity_id)
+                            .await?
+                            .into_iter()
+                            .find(|review| review.project_id == project_id && review.is_pending());
+                        (entity_id.to_string(), pending)
+                    }
+                };
+                let entity_id = entity_id.as_str();
+                let mut review = match pending {
+                    Some(review) => review,
+                    None => {
+                        let (entity_type, _) = self
+                            .reviewable_entities(project_id)
+                            .await?
+                            .into_iter()
+                            .find(|(_, entity)| entity.get("id").and_then(|v| v.as_str()) == Some(entity_id))
+                            .ok_or_else(|| {
+                                McpError::resource_not_found(format!("Entity not found: {entity_id}"), None)
+                            })?;
+                        let mut review = EntityReview::request(project_id, entity_type, entity_id);
+                        review.requested_at = None;
+                        review
+                    }
+                };
+                review.record(verdict, Some(reviewer.to_string()), comment.clone());
+                container.entity_review_repository.save(&review).await?;
+                if verdict == VerificationStatus::Verified {
+                    let mut verification = EntityVerification::new(project_id, &review.entity_type, entity_id);
+                    verification.verified_by = Some(reviewer.to_string());
+                    verification.note = comment;
+                    container.entity_verification_repository.record(&verification).await?;
+                    // Rankings cached by query_context include freshness
+                    container.query_cache.invalidate_namespace("context");
+                }
+
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&review).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "list_reviews" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let status = args.get("status").and_then(|v| v.as_str()).unwrap_or("pending");
+                let wanted: Box<dyn Fn(&EntityReview) -> bool + Send> = match status {
+                    "all" => Box::new(|_| true),
+                    "pending" => Box::new(|review| review.is_pending()),
+                    other => {
+                        let status = VerificationStatus::parse(other)
+                            .filter(|status| *status != VerificationStatus::Unverified)
+                            .ok_or_else(|| {
+                                McpError::invalid_params("status must be pending, verified, disputed or all", None)
+                            })?;
+                        Box::new(move |review| !review.is_pending() && review.status == status)
+                    }
+                };
+                let entity_id = args.get("entity_id").and_then(|v| v.as_str());
+
+                let reviews: Vec<EntityReview> = self
+                    .container
+                    .entity_review_repository
+                    .find_by_project(project_id)
+                    .await?
+                    .into_iter()
+                    .filter(|review| entity_id.is_none_or(|entity_id| review.entity_id == entity_id))
+                    .filter(|review| wanted(review))
+                    .collect();
+                let result = serde_json::json!({
+                    "count": reviews.len(),
+                    "reviews": reviews
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "list_stale_context" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let entity_type = args.get("entity_type").and_then(|v| v.as_str());
+                if entity_type.is_some_and(|entity_type| !FRESHNESS_ENTITY_TYPES.contains(&entity_type)) {
+                    return Err(McpError::invalid_params(
+                        format!("entity_type must be one of {}", FRESHNESS_ENTITY_TYPES.join(", ")),
+                        None,
+                    ));
+                }
+                let mut settings = self.container.config_manager.current().freshness.clone();
+                if let Some(value) = args.get("stale_below") {
+                    settings.stale_below = value
+                        .as_f64()
+                        .filter(|threshold| (0.0..=1.0).contains(threshold))
+                        .ok_or_else(|| McpError::invalid_params("stale_below must be between 0 and 1", None))?;
+                }
+                let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(50) as usize;
+
+                let entities: Vec<(&str, serde_json::Value)> = self
+                    .project_entities(project_id)
+                    .await?
+                    .into_iter()
+                    .filter(|(candidate, _)| entity_type.is_none_or(|entity_type| entity_type == *candidate))
+                    .collect();
+                let verifications = self
+                    .container
+                    .entity_verification_repository
+                    .find_by_project(project_id)
+                    .await?
+                    .into_iter()
+                    .map(|verification| (verification.entity_id.clone(), verification))
+                    .collect();
+                let report =
+                    StaleContextReport::build(project_id, &settings, chrono::Utc::now(), &entities, &verifications, limit);
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&report).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "analyze_context_gaps" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let min_items = args.get("min_items").and_then(|v| v.as_u64()).unwrap_or(3).max(1) as usize;
+                let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(50) as usize;
+                let mut spec_projects = vec![project_id];
+                spec_projects.extend(
+                    args.get("spec_projects")
+                        .and_then(|v| v.as_array())
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|v| v.as_str()),
+                );
+
+                let container = &self.container;
+                container
+                    .project_service
+                    .get_project(project_id)
+                    .await?
+                    .ok_or_else(|| McpError::invalid_params(format!("Project not found: {project_id}"), None))?;
+                let to_value = |value: serde_json::Result<serde_json::Value>| {
+                    value.map_err(|e| McpError::internal_error(format!("Serialization error: {e}"), None))
+                };
+
+                let mut referencing = Vec::new();
+                for spec_project in spec_projects {
+                    for spec in container.specification_service.get_specifications_by_project(spec_project).await? {
+                        referencing.push(("specification", specification_entity(&spec)));
+                    }
+                }
+                for component in container.framework_service.list_components(project_id).await? {
+                    referencing.push(("framework_component", to_value(serde_json::to_value(component))?));
+                }
+                for feature in container.feature_context_repository.list_by_project(project_id).await? {
+                    referencing.push(("feature_context", to_value(serde_json::to_value(feature))?));
+                }
 
-                        Err(McpError::internal_error(format!("Analytics query failed: {e}"), None))
+                let mut documenting = Vec::new();
+                for rule in container.context_crud_service.list_business_rules(project_id).await? {
+                    documenting.push(("business_rule", to_value(serde_json::to_value(rule))?));
+                }
+                for policy in container.security_policy_repository.list_by_project(project_id).await? {
+                    documenting.push(("security_policy", to_value(serde_json::to_value(policy))?));
+                }
+                for convention in container.project_convention_repository.list_by_project(project_id).await? {
+                    documenting.push(("project_convention", to_value(serde_json::to_value(convention))?));
+                }
+
+                let mut area_tags: HashMap<String, Vec<String>> = HashMap::new();
+                for tag in container.tag_repository.list_tags(project_id).await? {
+                    if tag.category != tag_classifier::DOMAIN_AREA && tag.category != tag_classifier::FEATURE_AREA {
+                        continue;
+                    }
+                    for tagged in container.tag_repository.find_tagged_entities(&tag.id).await? {
+                        area_tags.entry(tagged.entity_id).or_default().push(tag.tag_name.clone());
                     }
                 }
+
+                let report = ContextGapReport::build(project_id, &referencing, &documenting, &area_tags, min_items, limit);
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&report).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "get_context_digest" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let feature_area = args.get("feature_area").and_then(|v| v.as_str());
+
+                let digests = self
+                    .container
+                    .context_intelligence_service
+                    .get_context_digests(project_id, feature_area)
+                    .await
+                    .map_err(|e| McpError::internal_error(format!("Failed to read digests: {e}"), None))?;
+                let result = serde_json::json!({
+                    "count": digests.len(),
+                    "digests": digests
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
             }
 
             "get_context_insights" => {
@@ -1471,9 +6511,7 @@ impl ServerHandler for EnhancedContextMcpServer {
                             None,
                         );
                         
-                        if let Err(e) = self.container.analytics_service.track_event(analytics_event).await {
-                            tracing::warn!("Failed to track analytics event: {}", e);
-                        }
+                        self.track_event(analytics_event).await;
 
                         let content = serde_json::to_string_pretty(&insights).map_err(|e| {
                             McpError::internal_error(format!("Serialization error: {e}"), None)
@@ -1490,9 +6528,7 @@ impl ServerHandler for EnhancedContextMcpServer {
                             Some(e.to_string()),
                         );
                         
-                        if let Err(analytics_err) = self.container.analytics_service.track_event(analytics_event).await {
-                            tracing::warn!("Failed to track analytics event: {}", analytics_err);
-                        }
+                        self.track_event(analytics_event).await;
 
                         Err(McpError::internal_error(format!("Context insights query failed: {e}"), None))
                     }
@@ -1519,24 +6555,12 @@ impl ServerHandler for EnhancedContextMcpServer {
 
                 let project_id = args.get("project_id").and_then(|v| v.as_str());
 
-                let report_result = self.container.analytics_service.generate_usage_report(start_date, end_date).await;
+                let report_result =
+                    quality_report(self.container.analytics_service.as_ref(), start_date, end_date, project_id).await;
                 let duration_ms = start_time.elapsed().as_millis() as u64;
 
                 match report_result {
-                    Ok(mut report) => {
-                        // Add quality assessment to the report
-                        if let Some(pid) = project_id {
-                            if let Ok(insights) = self.container.analytics_service.get_project_insights(pid).await {
-                                if let Some(report_obj) = report.as_object_mut() {
-                                    report_obj.insert("quality_assessment".to_string(), serde_json::json!({
-                                        "context_health_score": insights.context_health_score,
-                                        "recommendations": insights.recommendations,
-                                        "most_active_entity_types": insights.most_active_entity_types
-                                    }));
-                                }
-                            }
-                        }
-
+                    Ok(report) => {
                         // Track successful report generation
                         let analytics_event = AnalyticsHelper::create_analytics_event(
                             "generate_quality_report".to_string(),
@@ -1546,9 +6570,7 @@ impl ServerHandler for EnhancedContextMcpServer {
                             None,
                         );
                         
-                        if let Err(e) = self.container.analytics_service.track_event(analytics_event).await {
-                            tracing::warn!("Failed to track analytics event: {}", e);
-                        }
+                        self.track_event(analytics_event).await;
 
                         let content = serde_json::to_string_pretty(&report).map_err(|e| {
                             McpError::internal_error(format!("Serialization error: {e}"), None)
@@ -1565,9 +6587,7 @@ impl ServerHandler for EnhancedContextMcpServer {
                             Some(e.to_string()),
                         );
                         
-                        if let Err(analytics_err) = self.container.analytics_service.track_event(analytics_event).await {
-                            tracing::warn!("Failed to track analytics event: {}", analytics_err);
-                        }
+                        self.track_event(analytics_event).await;
 
                         Err(McpError::internal_error(format!("Quality report generation failed: {e}"), None))
                     }
@@ -1594,37 +6614,76 @@ impl ServerHandler for EnhancedContextMcpServer {
                     .with_timezone(&chrono::Utc);
 
                 let project_id = args.get("project_id").and_then(|v| v.as_str());
-                let event_types: Vec<String> = args.get("event_types")
-                    .and_then(|v| v.as_array())
-                    .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-                    .unwrap_or_default();
+                let event_types = match args.get("event_types").and_then(|v| v.as_array()) {
+                    Some(names) => names
+                        .iter()
+                        .filter_map(|v| v.as_str())
+                        .map(|name| {
+                            AnalyticsEventType::from_name(name).ok_or_else(|| {
+                                let known: Vec<&str> = AnalyticsEventType::ALL.iter().map(|t| t.as_str()).collect();
+                                McpError::invalid_params(
+                                    format!("Unknown event type: {name}; expected one of {}", known.join(", ")),
+                                    None,
+                                )
+                            })
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                    None => Vec::new(),
+                };
+                if !matches!(format, "json" | "csv" | "parquet") {
+                    return Err(McpError::invalid_params(
+                        format!("Unknown format: {format}; expected json, csv or parquet"),
+                        None,
+                    ));
+                }
 
-                let export_result = self.container.analytics_service.generate_usage_report(start_date, end_date).await;
+                let export_result = self
+                    .container
+                    .analytics_service
+                    .get_events(start_date, end_date, project_id, &event_types)
+                    .await;
                 let duration_ms = start_time.elapsed().as_millis() as u64;
 
                 match export_result {
-                    Ok(mut export_data) => {
-                        // Add export metadata
-                        if let Some(export_obj) = export_data.as_object_mut() {
-                            export_obj.insert("export_metadata".to_string(), serde_json::json!({
+                    Ok(events) => {
+                        let content = if format == "csv" {
+                            AnalyticsExporter::to_csv(&events)
+                        } else if format == "parquet" {
+                            let default_name = format!(
+                                "analytics-events-{}-{}.parquet",
+                                start_date.format("%Y%m%d"),
+                                end_date.format("%Y%m%d")
+                            );
+                            let path = self.export_path(args.get("output_path").and_then(|v| v.as_str()), default_name.into())?;
+                            let parquet = AnalyticsExporter::to_parquet(&events).map_err(|e| {
+                                McpError::internal_error(format!("Parquet export failed: {e}"), None)
+                            })?;
+                            tokio::fs::write(&path, &parquet).await.map_err(|e| {
+                                McpError::internal_error(format!("Failed to write {}: {e}", path.display()), None)
+                            })?;
+                            serde_json::to_string_pretty(&serde_json::json!({
+                                "format": format,
+                                "output_path": path,
+                                "bytes": parquet.len(),
+                                "total_records": events.len()
+                            }))
+                            .map_err(|e| McpError::internal_error(format!("Serialization error: {e}"), None))?
+                        } else {
+                            let mut export_data = AnalyticsExporter::to_json(start_date, end_date, &events);
+                            export_data["export_metadata"] = serde_json::json!({
                                 "format": format,
                                 "exported_at": chrono::Utc::now().to_rfc3339(),
                                 "project_filter": project_id,
-                                "event_type_filter": if event_types.is_empty() { None } else { Some(event_types) },
-                                "total_records": export_obj.get("summary").and_then(|s| s.get("total_events")).unwrap_or(&serde_json::Value::Number(0.into()))
-                            }));
-                        }
-
-                        // For CSV format, we would need to implement CSV conversion
-                        // For now, we'll return JSON with a note about CSV format
-                        let final_content = if format == "csv" {
-                            serde_json::json!({
-                                "note": "CSV export format requested but not yet implemented. Returning JSON format.",
-                                "format": "json",
-                                "data": export_data
-                            })
-                        } else {
-                            export_data
+                                "event_type_filter": if event_types.is_empty() {
+                                    None
+                                } else {
+                                    Some(event_types.iter().map(|t| t.as_str()).collect::<Vec<_>>())
+                                },
+                                "total_records": events.len()
+                            });
+                            serde_json::to_string_pretty(&export_data).map_err(|e| {
+                                McpError::internal_error(format!("Serialization error: {e}"), None)
+                            })?
                         };
 
                         // Track successful export
@@ -1636,13 +6695,8 @@ impl ServerHandler for EnhancedContextMcpServer {
                             None,
                         );
                         
-                        if let Err(e) = self.container.analytics_service.track_event(analytics_event).await {
-                            tracing::warn!("Failed to track analytics event: {}", e);
-                        }
+                        self.track_event(analytics_event).await;
 
-                        let content = serde_json::to_string_pretty(&final_content).map_err(|e| {
-                            McpError::internal_error(format!("Serialization error: {e}"), None)
-                        })?;
                         Ok(CallToolResult::success(vec![Content::text(content)]))
                     }
                     Err(e) => {
@@ -1655,15 +6709,293 @@ impl ServerHandler for EnhancedContextMcpServer {
                             Some(e.to_string()),
                         );
                         
-                        if let Err(analytics_err) = self.container.analytics_service.track_event(analytics_event).await {
-                            tracing::warn!("Failed to track analytics event: {}", analytics_err);
-                        }
+                        self.track_event(analytics_event).await;
 
                         Err(McpError::internal_error(format!("Analytics data export failed: {e}"), None))
                     }
                 }
             }
 
+            "get_unused_context" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let days = match args.get("days") {
+                    Some(value) => value
+                        .as_u64()
+                        .filter(|days| (1..=36500).contains(days))
+                        .ok_or_else(|| McpError::invalid_params("days must be a positive integer", None))?
+                        as u32,
+                    None => 30,
+                };
+                let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(20) as usize;
+                let tag_stale = args.get("tag_stale").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                let now = chrono::Utc::now();
+                let entities: Vec<(&str, serde_json::Value)> = self
+                    .project_entities(project_id)
+                    .await?
+                    .into_iter()
+                    .filter(|(entity_type, _)| *entity_type != "project")
+                    .collect();
+                let uses = self
+                    .container
+                    .analytics_service
+                    .get_entity_uses(project_id, now - chrono::Duration::days(days as i64))
+                    .await
+                    .map_err(|e| McpError::internal_error(format!("Failed to read entity uses: {e}"), None))?;
+                let report = UnusedContextReport::build(project_id, days, now, &entities, &uses, limit);
+
+                let mut result = serde_json::to_value(&report).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                if tag_stale {
+                    result["tagging"] = self.tag_stale_entities(&report).await?;
+                }
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "suggest_context" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let feature_area = args.get("feature_area").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: feature_area", None)
+                })?;
+                let task_type = args.get("task_type").and_then(|v| v.as_str());
+                let components: Vec<String> = args
+                    .get("components")
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect();
+                let days = match args.get("days") {
+                    Some(value) => value
+                        .as_u64()
+                        .filter(|days| (1..=36500).contains(days))
+                        .ok_or_else(|| McpError::invalid_params("days must be a positive integer", None))?,
+                    None => 90,
+                };
+                let min_similarity = match args.get("min_similarity") {
+                    Some(value) => value
+                        .as_f64()
+                        .filter(|similarity| (0.0..=1.0).contains(similarity))
+                        .ok_or_else(|| McpError::invalid_params("min_similarity must be between 0 and 1", None))?
+                        as f32,
+                    None => 0.3,
+                };
+                let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(10) as usize;
+
+                let now = chrono::Utc::now();
+                let events = self
+                    .container
+                    .analytics_service
+                    .get_events(
+                        now - chrono::Duration::days(days as i64),
+                        now,
+                        Some(project_id),
+                        &[AnalyticsEventType::ContextQuery, AnalyticsEventType::SearchClick],
+                    )
+                    .await
+                    .map_err(|e| McpError::internal_error(format!("Failed to read query history: {e}"), None))?;
+                let history = query_history(&events);
+                let query = QueryProfile::new(feature_area, task_type, &components);
+
+                // Recommend only entities that still exist, named as they are now
+                let mut titles: HashMap<String, Option<String>> = self
+                    .project_entities(project_id)
+                    .await?
+                    .iter()
+                    .filter_map(|(_, entity)| {
+                        Some((entity.get("id")?.as_str()?.to_string(), entity_title(entity)))
+                    })
+                    .collect();
+                for policy in self.container.security_policy_repository.list_by_project(project_id).await? {
+                    titles.insert(policy.id, Some(policy.policy_name));
+                }
+                for convention in self.container.project_convention_repository.list_by_project(project_id).await? {
+                    titles.insert(convention.id, convention.convention_type);
+                }
+                let recommendations: Vec<ContextRecommendation> = recommend(&query, &history, min_similarity, usize::MAX)
+                    .into_iter()
+                    .filter_map(|mut recommendation| {
+                        recommendation.title = titles.get(&recommendation.entity_id)?.clone();
+                        Some(recommendation)
+                    })
+                    .take(limit)
+                    .collect();
+
+                let result = serde_json::json!({
+                    "project_id": project_id,
+                    "queries_considered": history.len(),
+                    "recommendations": recommendations
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "get_audit_log" => {
+                let args = request.arguments.unwrap_or_default();
+                let text = |name: &str| args.get(name).and_then(|v| v.as_str()).map(str::to_string);
+                let time = |name: &str| -> Result<Option<chrono::DateTime<chrono::Utc>>, McpError> {
+                    text(name)
+                        .map(|value| {
+                            chrono::DateTime::parse_from_rfc3339(&value)
+                                .map(|at| at.with_timezone(&chrono::Utc))
+                                .map_err(|_| McpError::invalid_params(format!("Invalid {name} format. Use ISO 8601 format"), None))
+                        })
+                        .transpose()
+                };
+                let outcome = text("outcome")
+                    .map(|name| {
+                        ToolCallOutcome::from_name(&name).ok_or_else(|| {
+                            McpError::invalid_params(format!("Unknown outcome: {name}; expected success or error"), None)
+                        })
+                    })
+                    .transpose()?;
+                let limit = match args.get("limit") {
+                    Some(value) => value
+                        .as_u64()
+                        .filter(|limit| (1..=1000).contains(limit))
+                        .ok_or_else(|| McpError::invalid_params("limit must be between 1 and 1000", None))?
+                        as usize,
+                    None => 100,
+                };
+                let filter = ToolCallAuditFilter {
+                    tool: text("tool"),
+                    caller: text("caller"),
+                    project_id: text("project_id"),
+                    outcome,
+                    since: time("since")?,
+                    until: time("until")?,
+                    limit,
+                };
+
+                let entries = self.container.tool_audit_service.find(&filter).await?;
+                let result = serde_json::json!({
+                    "count": entries.len(),
+                    "entries": entries
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "generate_dashboard" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let days = match args.get("days") {
+                    Some(value) => value
+                        .as_u64()
+                        .filter(|days| (1..=366).contains(days))
+                        .ok_or_else(|| McpError::invalid_params("days must be between 1 and 366", None))?
+                        as u32,
+                    None => 30,
+                };
+                let project = self.container.project_service.get_project(project_id).await?.ok_or_else(|| {
+                    McpError::invalid_params(format!("Project not found: {project_id}"), None)
+                })?;
+
+                let data = DashboardData::collect(
+                    self.container.analytics_service.as_ref(),
+                    self.container.specification_analytics_service.as_ref(),
+                    &project,
+                    days,
+                    chrono::Utc::now(),
+                )
+                .await
+                .map_err(|e| McpError::internal_error(format!("Failed to collect dashboard data: {e}"), None))?;
+                let path = self.export_path(args.get("output_path").and_then(|v| v.as_str()), data.default_file_name())?;
+                let bytes = DashboardRenderer::write(&data, &path).await.map_err(|e| {
+                    McpError::internal_error(format!("Failed to write {}: {e}", path.display()), None)
+                })?;
+
+                let result = serde_json::json!({
+                    "project_id": project_id,
+                    "path": path,
+                    "bytes": bytes,
+                    "days": days,
+                    "events": data.daily_events.iter().map(|day| day.successful + day.failed).sum::<u64>()
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "run_scheduled_reports" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args.get("project_id").and_then(|v| v.as_str()).ok_or_else(|| {
+                    McpError::invalid_params("Missing required parameter: project_id", None)
+                })?;
+                let kinds = args
+                    .get("kinds")
+                    .and_then(|v| v.as_array())
+                    .map(|kinds| {
+                        kinds
+                            .iter()
+                            .map(|kind| {
+                                kind.as_str().and_then(ScheduledReportKind::from_name).ok_or_else(|| {
+                                    McpError::invalid_params(format!("Unknown report kind: {kind}"), None)
+                                })
+                            })
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .transpose()?
+                    .unwrap_or_default();
+
+                let reports = self.container.report_scheduler.run_project(project_id, &kinds).await?;
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&reports).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
+            "prune_analytics" => {
+                let args = request.arguments.unwrap_or_default();
+                let retention_days = match args.get("retention_days") {
+                    Some(value) => value
+                        .as_u64()
+                        .filter(|days| (1..=u32::MAX as u64).contains(days))
+                        .ok_or_else(|| McpError::invalid_params("retention_days must be a positive integer", None))?
+                        as u32,
+                    None => self.container.config_manager.current().analytics.retention_days,
+                };
+                let vacuum = args.get("vacuum").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                let report = self
+                    .container
+                    .analytics_service
+                    .prune_events(retention_days, vacuum)
+                    .await
+                    .map_err(|e| McpError::internal_error(format!("Analytics pruning failed: {e}"), None))?;
+                let result = serde_json::json!({
+                    "retention_days": retention_days,
+                    "report": report
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {e}"), None)
+                    })?,
+                )]))
+            }
+
             // Universal CRUD Operations
             // First universal handler for create_entity
             "create_entity" => {
@@ -1899,20 +7231,198 @@ impl ServerHandler for EnhancedContextMcpServer {
                             McpError::internal_error(format!("Serialization error: {}", e), None)
                         })?
                     }
-                    "security_policy" => {
-                        // Security Policy operations - placeholder
-                        return Err(McpError::invalid_params(
-                            "Security policy operations not yet fully integrated. Please use the ExtendedContextCrudService.",
-                            None,
-                        ));
-                    }
-                    "feature_context" => {
-                        // Feature Context operations - placeholder
-                        return Err(McpError::invalid_params(
-                            "Feature context operations not yet fully integrated. Please use the ExtendedContextCrudService.",
-                            None,
-                        ));
-                    }
+                    "saved_search" => {
+                        let project_id = data
+                            .get("project_id")
+                            .and_then(|v| v.as_str())
+                            .ok_or_else(|| {
+                                McpError::invalid_params(
+                                    "Missing required parameter: project_id",
+                                    None,
+                                )
+                            })?;
+                        let name = data.get("name").and_then(|v| v.as_str()).ok_or_else(|| {
+                            McpError::invalid_params("Missing required parameter: name", None)
+                        })?;
+                        let query = data.get("query").and_then(|v| v.as_str()).ok_or_else(|| {
+                            McpError::invalid_params("Missing required parameter: query", None)
+                        })?;
+                        let feature_area = data.get("feature_area").and_then(|v| v.as_str());
+                        let filters = Self::facet_filters(data.get("filters"))?;
+                        let limit = data.get("limit").and_then(|v| v.as_u64()).map(|l| l as usize);
+
+                        let search = self
+                            .container
+                            .saved_search_service
+                            .create_saved_search(project_id, name, query, feature_area, filters, limit)
+                            .await?;
+                        serde_json::to_value(search).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {}", e), None)
+                        })?
+                    }
+                    "security_policy" => {
+                        let project_id = data
+                            .get("project_id")
+                            .and_then(|v| v.as_str())
+                            .ok_or_else(|| {
+                                McpError::invalid_params(
+                                    "Missing required parameter: project_id",
+                                    None,
+                                )
+                            })?;
+                        let policy_name = data
+                            .get("policy_name")
+                            .and_then(|v| v.as_str())
+                            .ok_or_else(|| {
+                                McpError::invalid_params(
+                                    "Missing required parameter: policy_name",
+                                    None,
+                                )
+                            })?;
+                        let policy_area = data.get("policy_area").and_then(|v| v.as_str());
+                        Self::validate_security_policy_fields(data)?;
+
+                        let crud = &self.container.context_crud_service;
+                        let mut policy = crud.create_security_policy(project_id, policy_name, policy_area).await?;
+                        Self::apply_security_policy_fields(&mut policy, data)?;
+                        let policy = crud.update_security_policy(&policy).await?;
+                        serde_json::to_value(policy).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {}", e), None)
+                        })?
+                    }
+                    "project_convention" => {
+                        let project_id = data
+                            .get("project_id")
+                            .and_then(|v| v.as_str())
+                            .ok_or_else(|| {
+                                McpError::invalid_params(
+                                    "Missing required parameter: project_id",
+                                    None,
+                                )
+                            })?;
+                        if !data.get("convention_rule").is_some_and(|v| v.is_string()) {
+                            return Err(McpError::invalid_params(
+                                "Missing required parameter: convention_rule",
+                                None,
+                            ));
+                        }
+
+                        let mut convention = ProjectConvention {
+                            id: uuid::Uuid::new_v4().to_string(),
+                            project_id: project_id.to_string(),
+                            convention_type: None,
+                            convention_rule: None,
+                            pattern: None,
+                            good_examples: None,
+                            bad_examples: None,
+                            rationale: None,
+                            enforcement_level: None,
+                            created_at: Some(chrono::Utc::now().to_rfc3339()),
+                        };
+                        Self::apply_convention_fields(&mut convention, data)?;
+                        let convention = self.container.project_convention_repository.create(&convention).await?;
+                        serde_json::to_value(convention).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {}", e), None)
+                        })?
+                    }
+                    "feature_context" => {
+                        let project_id = data
+                            .get("project_id")
+                            .and_then(|v| v.as_str())
+                            .ok_or_else(|| {
+                                McpError::invalid_params(
+                                    "Missing required parameter: project_id",
+                                    None,
+                                )
+                            })?;
+                        let feature_name = data
+                            .get("feature_name")
+                            .and_then(|v| v.as_str())
+                            .ok_or_else(|| {
+                                McpError::invalid_params(
+                                    "Missing required parameter: feature_name",
+                                    None,
+                                )
+                            })?;
+                        Self::validate_feature_context_fields(data)?;
+
+                        let crud = &self.container.context_crud_service;
+                        let mut feature = crud.create_feature_context(project_id, feature_name, None).await?;
+                        Self::apply_feature_context_fields(&mut feature, data)?;
+                        let feature = crud.update_feature_context(&feature).await?;
+                        serde_json::to_value(feature).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {}", e), None)
+                        })?
+                    }
+                    "glossary_term" => {
+                        let project_id = data
+                            .get("project_id")
+                            .and_then(|v| v.as_str())
+                            .ok_or_else(|| {
+                                McpError::invalid_params(
+                                    "Missing required parameter: project_id",
+                                    None,
+                                )
+                            })?;
+                        let term = data.get("term").and_then(|v| v.as_str()).ok_or_else(|| {
+                            McpError::invalid_params("Missing required parameter: term", None)
+                        })?;
+
+                        let mut entry = GlossaryEntry {
+                            id: uuid::Uuid::new_v4().to_string(),
+                            project_id: project_id.to_string(),
+                            term: term.to_string(),
+                            synonyms: Vec::new(),
+                            definition: None,
+                            domain_area: None,
+                            created_at: None,
+                            updated_at: None,
+                        };
+                        Self::apply_glossary_term_fields(&mut entry, data)?;
+                        let entry = self.container.glossary_service.save_entry(entry).await?;
+                        serde_json::to_value(entry).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {}", e), None)
+                        })?
+                    }
+                    "api_contract" => {
+                        let required = |field: &str| {
+                            data.get(field).and_then(|v| v.as_str()).ok_or_else(|| {
+                                McpError::invalid_params(format!("Missing required parameter: {field}"), None)
+                            })
+                        };
+                        let mut contract = ApiContract::new(required("project_id")?, required("method")?, required("path")?);
+                        Self::apply_api_contract_fields(&mut contract, data)?;
+                        self.check_api_contract(&contract).await?;
+                        let contract = self.container.api_contract_repository.create(&contract).await?;
+                        serde_json::to_value(contract).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {}", e), None)
+                        })?
+                    }
+                    "custom_entity" => {
+                        let project_id = data
+                            .get("project_id")
+                            .and_then(|v| v.as_str())
+                            .ok_or_else(|| {
+                                McpError::invalid_params(
+                                    "Missing required parameter: project_id",
+                                    None,
+                                )
+                            })?;
+                        let custom_type = data.get("custom_type").and_then(|v| v.as_str()).ok_or_else(|| {
+                            McpError::invalid_params("Missing required parameter: custom_type", None)
+                        })?;
+                        let fields = data.get("fields").filter(|v| v.is_object()).cloned().ok_or_else(|| {
+                            McpError::invalid_params("Missing required parameter: fields", None)
+                        })?;
+                        let entity_type = self.custom_entity_type(custom_type).await?;
+
+                        let mut entity = CustomEntity::new(project_id, custom_type, fields);
+                        Self::check_custom_entity(&mut entity, &entity_type)?;
+                        let entity = self.container.custom_entity_repository.create(&entity).await?;
+                        serde_json::to_value(entity).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {}", e), None)
+                        })?
+                    }
                     // Add more entity types as needed
                     _ => {
                         return Err(McpError::invalid_params(
@@ -1942,9 +7452,7 @@ impl ServerHandler for EnhancedContextMcpServer {
                     None,
                 );
                 
-                if let Err(e) = self.container.analytics_service.track_event(analytics_event).await {
-                    tracing::warn!("Failed to track analytics event: {}", e);
-                }
+                self.track_event(analytics_event).await;
 
                 let content = serde_json::to_string_pretty(&result).map_err(|e| {
                     McpError::internal_error(format!("Serialization error: {}", e), None)
@@ -2115,6 +7623,12 @@ impl ServerHandler for EnhancedContextMcpServer {
                         if let Some(fp) = file_path {
                             component.file_path = Some(fp.to_string());
                         }
+                        if let Some(dependencies) = data.get("dependencies").and_then(|v| v.as_array()) {
+                            component.dependencies = dependencies
+                                .iter()
+                                .filter_map(|dep| dep.as_str().map(str::to_string))
+                                .collect();
+                        }
 
                         // Update the component
                         let updated_component = self
@@ -2170,6 +7684,17 @@ impl ServerHandler for EnhancedContextMcpServer {
                                 .map(|s| s.to_string()),
                             created_at: None,
                         };
+                        let mut decision = decision;
+                        // Review states change through transition_workflow; without a status the stored one is kept
+                        if decision.status.is_none() {
+                            decision.status = self
+                                .container
+                                .context_crud_service
+                                .get_architectural_decision(id)
+                                .await?
+                                .and_then(|d| d.status);
+                        }
+                        self.container.approval_workflow_service.check_decision_update(&decision).await?;
 
                         let updated_decision = self
                             .container
@@ -2223,52 +7748,93 @@ impl ServerHandler for EnhancedContextMcpServer {
                         })?
                     }
                     "security_policy" => {
-                        use crate::models::context::SecurityPolicy;
-
-                        let security_policy = SecurityPolicy {
-                            id: id.to_string(),
-                            project_id: data
-                                .get("project_id")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("")
-                                .to_string(),
-                            policy_name: data
-                                .get("policy_name")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("")
-                                .to_string(),
-                            policy_area: data
-                                .get("policy_area")
-                                .and_then(|v| v.as_str())
-                                .map(|s| s.to_string()),
-                            requirements: data
-                                .get("requirements")
-                                .and_then(|v| v.as_str())
-                                .map(|s| s.to_string()),
-                            implementation_pattern: data
-                                .get("implementation_pattern")
-                                .and_then(|v| v.as_str())
-                                .map(|s| s.to_string()),
-                            forbidden_patterns: data
-                                .get("forbidden_patterns")
-                                .and_then(|v| v.as_str())
-                                .map(|s| s.to_string()),
-                            compliance_notes: data
-                                .get("compliance_notes")
-                                .and_then(|v| v.as_str())
-                                .map(|s| s.to_string()),
-                            created_at: None,
-                        };
+                        let crud = &self.container.context_crud_service;
+                        let mut policy = crud.get_security_policy(id).await?.ok_or_else(|| {
+                            McpError::resource_not_found(format!("Security policy not found: {id}"), None)
+                        })?;
+                        Self::apply_security_policy_fields(&mut policy, data)?;
 
-                        let updated_policy = self
-                            .container
-                            .context_crud_service
-                            .update_security_policy(&security_policy)
-                            .await?;
+                        let updated_policy = crud.update_security_policy(&policy).await?;
                         serde_json::to_value(updated_policy).map_err(|e| {
                             McpError::internal_error(format!("Serialization error: {}", e), None)
                         })?
                     }
+                    "project_convention" => {
+                        let conventions = &self.container.project_convention_repository;
+                        let mut convention = conventions.get_by_id(id).await?.ok_or_else(|| {
+                            McpError::resource_not_found(format!("Project convention not found: {id}"), None)
+                        })?;
+                        Self::apply_convention_fields(&mut convention, data)?;
+
+                        let updated_convention = conventions.update(&convention).await?;
+                        serde_json::to_value(updated_convention).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {}", e), None)
+                        })?
+                    }
+                    "feature_context" => {
+                        let crud = &self.container.context_crud_service;
+                        let mut feature = crud.get_feature_context(id).await?.ok_or_else(|| {
+                            McpError::resource_not_found(format!("Feature context not found: {id}"), None)
+                        })?;
+                        Self::apply_feature_context_fields(&mut feature, data)?;
+
+                        let updated_feature = crud.update_feature_context(&feature).await?;
+                        serde_json::to_value(updated_feature).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {}", e), None)
+                        })?
+                    }
+                    "glossary_term" => {
+                        let glossary = &self.container.glossary_service;
+                        let mut entry = glossary.get_entry(id).await?.ok_or_else(|| {
+                            McpError::resource_not_found(format!("Glossary term not found: {id}"), None)
+                        })?;
+                        Self::apply_glossary_term_fields(&mut entry, data)?;
+
+                        let updated_entry = glossary.save_entry(entry).await?;
+                        serde_json::to_value(updated_entry).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {}", e), None)
+                        })?
+                    }
+                    "api_contract" => {
+                        let contracts = &self.container.api_contract_repository;
+                        let mut contract = contracts.get_by_id(id).await?.ok_or_else(|| {
+                            McpError::resource_not_found(format!("API contract not found: {id}"), None)
+                        })?;
+                        Self::apply_api_contract_fields(&mut contract, data)?;
+                        self.check_api_contract(&contract).await?;
+                        contract.updated_at = chrono::Utc::now().to_rfc3339();
+
+                        let updated_contract = contracts.update(&contract).await?;
+                        serde_json::to_value(updated_contract).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {}", e), None)
+                        })?
+                    }
+                    "custom_entity" => {
+                        let entities = &self.container.custom_entity_repository;
+                        let mut entity = entities.get_by_id(id).await?.ok_or_else(|| {
+                            McpError::resource_not_found(format!("Custom entity not found: {id}"), None)
+                        })?;
+                        // Given fields replace the stored ones; null removes a field
+                        if let (Some(changes), Some(fields)) =
+                            (data.get("fields").and_then(|v| v.as_object()), entity.fields.as_object_mut())
+                        {
+                            for (name, value) in changes {
+                                if value.is_null() {
+                                    fields.remove(name);
+                                } else {
+                                    fields.insert(name.clone(), value.clone());
+                                }
+                            }
+                        }
+                        let entity_type = self.custom_entity_type(&entity.custom_type).await?;
+                        Self::check_custom_entity(&mut entity, &entity_type)?;
+                        entity.updated_at = chrono::Utc::now().to_rfc3339();
+
+                        let updated_entity = entities.update(&entity).await?;
+                        serde_json::to_value(updated_entity).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {}", e), None)
+                        })?
+                    }
                     "development_phase" => {
                         use crate::models::development::{DevelopmentPhase, PhaseStatus};
 
@@ -2337,6 +7903,41 @@ impl ServerHandler for EnhancedContextMcpServer {
                             McpError::internal_error(format!("Serialization error: {}", e), None)
                         })?
                     }
+                    "saved_search" => {
+                        let mut search = self
+                            .container
+                            .saved_search_service
+                            .get_saved_search(id)
+                            .await?
+                            .ok_or_else(|| {
+                                McpError::invalid_params(format!("Saved search not found: {id}"), None)
+                            })?;
+                        // Fields left out of `data` keep their saved values
+                        if let Some(name) = data.get("name").and_then(|v| v.as_str()) {
+                            search.name = name.to_string();
+                        }
+                        if let Some(query) = data.get("query").and_then(|v| v.as_str()) {
+                            search.query = query.to_string();
+                        }
+                        if let Some(feature_area) = data.get("feature_area") {
+                            search.feature_area = feature_area.as_str().map(|s| s.to_string());
+                        }
+                        if data.contains_key("filters") {
+                            search.filters = Self::facet_filters(data.get("filters"))?;
+                        }
+                        if let Some(limit) = data.get("limit") {
+                            search.limit = limit.as_u64().map(|l| l as usize);
+                        }
+
+                        let updated_search = self
+                            .container
+                            .saved_search_service
+                            .update_saved_search(&search)
+                            .await?;
+                        serde_json::to_value(updated_search).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {}", e), None)
+                        })?
+                    }
                     // Add more entity types as needed
                     _ => {
                         return Err(McpError::invalid_params(
@@ -2383,6 +7984,12 @@ impl ServerHandler for EnhancedContextMcpServer {
                             .context_crud_service
                             .delete_architectural_decision(id)
                             .await?;
+                        self.container
+                            .approval_workflow_service
+                            .remove_workflow(WorkflowEntity::ArchitecturalDecision, id)
+                            .await?;
+                        self.container.attachment_service.delete_entity_attachments(id).await?;
+                        self.container.decision_link_repository.delete_by_decision(id).await?;
                         serde_json::json!({"deleted": deleted, "decision_id": id})
                     }
                     "framework_component" => {
@@ -2409,19 +8016,62 @@ impl ServerHandler for EnhancedContextMcpServer {
                             .await?;
                         serde_json::json!({"deleted": deleted, "requirement_id": id})
                     }
+                    "saved_search" => {
+                        let deleted = self
+                            .container
+                            .saved_search_service
+                            .delete_saved_search(id)
+                            .await?;
+                        serde_json::json!({"deleted": deleted, "saved_search_id": id})
+                    }
+                    "traceability_report" => {
+                        let deleted = self
+                            .container
+                            .traceability_service
+                            .delete_report(id)
+                            .await?;
+                        serde_json::json!({"deleted": deleted, "report_id": id})
+                    }
+                    "scheduled_report" => {
+                        let deleted = self
+                            .container
+                            .report_scheduler
+                            .delete_report(id)
+                            .await?;
+                        serde_json::json!({"deleted": deleted, "report_id": id})
+                    }
                     "security_policy" => {
-                        // Security Policy operations - placeholder
-                        return Err(McpError::invalid_params(
-                            "Security policy operations not yet fully integrated",
-                            None,
-                        ));
+                        let deleted = self
+                            .container
+                            .context_crud_service
+                            .delete_security_policy(id)
+                            .await?;
+                        serde_json::json!({"deleted": deleted, "policy_id": id})
+                    }
+                    "project_convention" => {
+                        let deleted = self.container.project_convention_repository.delete(id).await?;
+                        serde_json::json!({"deleted": deleted, "convention_id": id})
                     }
                     "feature_context" => {
-                        // Feature Context operations - placeholder
-                        return Err(McpError::invalid_params(
-                            "Feature context operations not yet fully integrated",
-                            None,
-                        ));
+                        let deleted = self
+                            .container
+                            .context_crud_service
+                            .delete_feature_context(id)
+                            .await?;
+                        self.container.attachment_service.delete_entity_attachments(id).await?;
+                        serde_json::json!({"deleted": deleted, "feature_context_id": id})
+                    }
+                    "glossary_term" => {
+                        let deleted = self.container.glossary_service.delete_entry(id).await?;
+                        serde_json::json!({"deleted": deleted, "glossary_term_id": id})
+                    }
+                    "api_contract" => {
+                        let deleted = self.container.api_contract_repository.delete(id).await?;
+                        serde_json::json!({"deleted": deleted, "api_contract_id": id})
+                    }
+                    "custom_entity" => {
+                        let deleted = self.container.custom_entity_repository.delete(id).await?;
+                        serde_json::json!({"deleted": deleted, "custom_entity_id": id})
                     }
                     // Add more entity types as needed
                     _ => {
@@ -2450,6 +8100,25 @@ impl ServerHandler for EnhancedContextMcpServer {
                 let project_id = args.get("project_id").and_then(|v| v.as_str());
                 let architecture_layer = args.get("architecture_layer").and_then(|v| v.as_str());
 
+                let cache_key = match (entity_type, project_id, architecture_layer) {
+                    ("project", _, _) => Some(CacheKeyBuilder::all_projects()),
+                    ("framework_component", Some(pid), Some(layer)) => {
+                        Some(CacheKeyBuilder::framework_components_by_layer(pid, layer))
+                    }
+                    (_, Some(pid), _) => CacheKeyBuilder::entity_list(entity_type, pid),
+                    _ => None,
+                };
+                if let Some(cached) = cache_key
+                    .as_deref()
+                    .and_then(|key| self.container.query_cache.get(key))
+                {
+                    let cached = self.with_project_verification(entity_type, project_id, cached).await?;
+                    let content = serde_json::to_string_pretty(&cached).map_err(|e| {
+                        McpError::internal_error(format!("Serialization error: {}", e), None)
+                    })?;
+                    return Ok(CallToolResult::success(vec![Content::text(content)]));
+                }
+
                 let result = match entity_type {
                     "project" => {
                         let projects = self.container.project_service.list_projects().await?;
@@ -2477,90 +8146,228 @@ impl ServerHandler for EnhancedContextMcpServer {
                             ));
                         }
                     }
-                    "architectural_decision" => {
+                    "architectural_decision" => {
+                        if let Some(pid) = project_id {
+                            let decisions = self
+                                .container
+                                .context_crud_service
+                                .list_architectural_decisions(pid)
+                                .await?;
+                            serde_json::to_value(decisions).map_err(|e| {
+                                McpError::internal_error(
+                                    format!("Serialization error: {}", e),
+                                    None,
+                                )
+                            })?
+                        } else {
+                            return Err(McpError::invalid_params("Missing required parameter: project_id for architectural_decision listing", None));
+                        }
+                    }
+                    "framework_component" => {
+                        if let Some(pid) = project_id {
+                            let components = if let Some(layer) = architecture_layer {
+                                // Use list_components_by_layer if architecture_layer is specified
+                                self.container
+                                    .framework_service
+                                    .list_components_by_layer(pid, layer)
+                                    .await?
+                            } else {
+                                // Use list_components if no layer is specified
+                                self.container
+                                    .framework_service
+                                    .list_components(pid)
+                                    .await?
+                            };
+                            serde_json::to_value(components).map_err(|e| {
+                                McpError::internal_error(
+                                    format!("Serialization error: {}", e),
+                                    None,
+                                )
+                            })?
+                        } else {
+                            return Err(McpError::invalid_params("Missing required parameter: project_id for framework_component listing", None));
+                        }
+                    }
+                    "development_phase" => {
+                        if let Some(pid) = project_id {
+                            let phases = self
+                                .container
+                                .development_phase_service
+                                .list_phases(pid)
+                                .await?;
+                            serde_json::to_value(phases).map_err(|e| {
+                                McpError::internal_error(
+                                    format!("Serialization error: {}", e),
+                                    None,
+                                )
+                            })?
+                        } else {
+                            return Err(McpError::invalid_params("Missing required parameter: project_id for development_phase listing", None));
+                        }
+                    }
+                    "performance_requirement" => {
+                        if let Some(pid) = project_id {
+                            let requirements = self
+                                .container
+                                .context_crud_service
+                                .list_performance_requirements(pid)
+                                .await?;
+                            serde_json::to_value(requirements).map_err(|e| {
+                                McpError::internal_error(
+                                    format!("Serialization error: {}", e),
+                                    None,
+                                )
+                            })?
+                        } else {
+                            return Err(McpError::invalid_params("Missing required parameter: project_id for performance_requirement listing", None));
+                        }
+                    }
+                    "saved_search" => {
+                        if let Some(pid) = project_id {
+                            let searches = self
+                                .container
+                                .saved_search_service
+                                .list_saved_searches(pid)
+                                .await?;
+                            serde_json::to_value(searches).map_err(|e| {
+                                McpError::internal_error(
+                                    format!("Serialization error: {}", e),
+                                    None,
+                                )
+                            })?
+                        } else {
+                            return Err(McpError::invalid_params("Missing required parameter: project_id for saved_search listing", None));
+                        }
+                    }
+                    "traceability_report" => {
+                        if let Some(pid) = project_id {
+                            let reports = self
+                                .container
+                                .traceability_service
+                                .list_reports(pid)
+                                .await?;
+                            serde_json::to_value(reports).map_err(|e| {
+                                McpError::internal_error(
+                                    format!("Serialization error: {}", e),
+                                    None,
+                                )
+                            })?
+                        } else {
+                            return Err(McpError::invalid_params("Missing required parameter: project_id for traceability_report listing", None));
+                        }
+                    }
+                    "scheduled_report" => {
+                        if let Some(pid) = project_id {
+                            let reports = self
+                                .container
+                                .report_scheduler
+                                .list_reports(pid, None)
+                                .await?;
+                            serde_json::to_value(reports).map_err(|e| {
+                                McpError::internal_error(
+                                    format!("Serialization error: {}", e),
+                                    None,
+                                )
+                            })?
+                        } else {
+                            return Err(McpError::invalid_params("Missing required parameter: project_id for scheduled_report listing", None));
+                        }
+                    }
+                    "security_policy" => {
+                        if let Some(pid) = project_id {
+                            let policies = self
+                                .container
+                                .context_crud_service
+                                .list_security_policies(pid)
+                                .await?;
+                            serde_json::to_value(policies).map_err(|e| {
+                                McpError::internal_error(
+                                    format!("Serialization error: {}", e),
+                                    None,
+                                )
+                            })?
+                        } else {
+                            return Err(McpError::invalid_params("Missing required parameter: project_id for security_policy listing", None));
+                        }
+                    }
+                    "project_convention" => {
+                        if let Some(pid) = project_id {
+                            let conventions = self
+                                .container
+                                .project_convention_repository
+                                .list_by_project(pid)
+                                .await?;
+                            serde_json::to_value(conventions).map_err(|e| {
+                                McpError::internal_error(
+                                    format!("Serialization error: {}", e),
+                                    None,
+                                )
+                            })?
+                        } else {
+                            return Err(McpError::invalid_params("Missing required parameter: project_id for project_convention listing", None));
+                        }
+                    }
+                    "feature_context" => {
                         if let Some(pid) = project_id {
-                            let decisions = self
+                            let features = self
                                 .container
                                 .context_crud_service
-                                .list_architectural_decisions(pid)
+                                .list_feature_contexts(pid)
                                 .await?;
-                            serde_json::to_value(decisions).map_err(|e| {
+                            serde_json::to_value(features).map_err(|e| {
                                 McpError::internal_error(
                                     format!("Serialization error: {}", e),
                                     None,
                                 )
                             })?
                         } else {
-                            return Err(McpError::invalid_params("Missing required parameter: project_id for architectural_decision listing", None));
+                            return Err(McpError::invalid_params("Missing required parameter: project_id for feature_context listing", None));
                         }
                     }
-                    "framework_component" => {
+                    "glossary_term" => {
                         if let Some(pid) = project_id {
-                            let components = if let Some(layer) = architecture_layer {
-                                // Use list_components_by_layer if architecture_layer is specified
-                                self.container
-                                    .framework_service
-                                    .list_components_by_layer(pid, layer)
-                                    .await?
-                            } else {
-                                // Use list_components if no layer is specified
-                                self.container
-                                    .framework_service
-                                    .list_components(pid)
-                                    .await?
-                            };
-                            serde_json::to_value(components).map_err(|e| {
+                            let terms = self.container.glossary_service.list_entries(pid).await?;
+                            serde_json::to_value(terms).map_err(|e| {
                                 McpError::internal_error(
                                     format!("Serialization error: {}", e),
                                     None,
                                 )
                             })?
                         } else {
-                            return Err(McpError::invalid_params("Missing required parameter: project_id for framework_component listing", None));
+                            return Err(McpError::invalid_params("Missing required parameter: project_id for glossary_term listing", None));
                         }
                     }
-                    "development_phase" => {
+                    "api_contract" => {
                         if let Some(pid) = project_id {
-                            let phases = self
-                                .container
-                                .development_phase_service
-                                .list_phases(pid)
-                                .await?;
-                            serde_json::to_value(phases).map_err(|e| {
+                            let contracts = self.container.api_contract_repository.list_by_project(pid).await?;
+                            serde_json::to_value(contracts).map_err(|e| {
                                 McpError::internal_error(
                                     format!("Serialization error: {}", e),
                                     None,
                                 )
                             })?
                         } else {
-                            return Err(McpError::invalid_params("Missing required parameter: project_id for development_phase listing", None));
+                            return Err(McpError::invalid_params("Missing required parameter: project_id for api_contract listing", None));
                         }
                     }
-                    "performance_requirement" => {
+                    "custom_entity" => {
                         if let Some(pid) = project_id {
-                            let requirements = self
+                            let custom_type = args.get("custom_type").and_then(|v| v.as_str());
+                            let entities = self
                                 .container
-                                .context_crud_service
-                                .list_performance_requirements(pid)
+                                .custom_entity_repository
+                                .list_by_project(pid, custom_type)
                                 .await?;
-                            serde_json::to_value(requirements).map_err(|e| {
+                            serde_json::to_value(self.with_search_text(entities).await?).map_err(|e| {
                                 McpError::internal_error(
                                     format!("Serialization error: {}", e),
                                     None,
                                 )
                             })?
                         } else {
-                            return Err(McpError::invalid_params("Missing required parameter: project_id for performance_requirement listing", None));
+                            return Err(McpError::invalid_params("Missing required parameter: project_id for custom_entity listing", None));
                         }
                     }
-                    "security_policy" => {
-                        // Security Policy operations - placeholder
-                        serde_json::json!({"error": "Security policy operations not yet fully integrated"})
-                    }
-                    "feature_context" => {
-                        // Feature Context operations - placeholder
-                        serde_json::json!({"error": "Feature context operations not yet fully integrated"})
-                    }
                     // Add more entity types as needed
                     _ => {
                         return Err(McpError::invalid_params(
@@ -2570,6 +8377,11 @@ impl ServerHandler for EnhancedContextMcpServer {
                     }
                 };
 
+                if let Some(key) = cache_key {
+                    self.container.query_cache.set(key, result.clone(), None);
+                }
+
+                let result = self.with_project_verification(entity_type, project_id, result).await?;
                 let content = serde_json::to_string_pretty(&result).map_err(|e| {
                     McpError::internal_error(format!("Serialization error: {}", e), None)
                 })?;
@@ -2829,143 +8641,714 @@ impl ServerHandler for EnhancedContextMcpServer {
                                 component.file_path = Some(file_path.to_string());
                             }
 
-                            // Update the component
-                            let updated_component = self
+                            // Update the component
+                            let updated_component = self
+                                .container
+                                .framework_service
+                                .update_component(&component)
+                                .await?;
+                            results.push(updated_component);
+                        }
+                        let content = serde_json::to_string_pretty(&results).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {}", e), None)
+                        })?;
+                        Ok(CallToolResult::success(vec![Content::text(content)]))
+                    }
+                    ("delete", "framework_component") => {
+                        let mut ids = Vec::new();
+                        for item in data {
+                            if let Some(obj) = item.as_object() {
+                                if let Some(id) = obj.get("id").and_then(|v| v.as_str()) {
+                                    ids.push(id.to_string());
+                                }
+                            }
+                        }
+
+                        let mut deleted_count = 0;
+                        let mut failed_ids = Vec::new();
+
+                        for id in &ids {
+                            match self.container.framework_service.delete_component(id).await {
+                                Ok(true) => deleted_count += 1,
+                                Ok(false) => failed_ids.push(id.clone()),
+                                Err(e) => {
+                                    tracing::error!("Error deleting component {}: {}", id, e);
+                                    failed_ids.push(id.clone());
+                                }
+                            }
+                        }
+
+                        let result = serde_json::json!({
+                            "deleted_count": deleted_count,
+                            "component_ids": ids,
+                            "failed_ids": failed_ids,
+                            "success": deleted_count == ids.len()
+                        });
+                        let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {}", e), None)
+                        })?;
+                        Ok(CallToolResult::success(vec![Content::text(content)]))
+                    }
+                    _ => Err(McpError::invalid_params(
+                        format!(
+                            "Unsupported operation '{}' or entity type '{}'",
+                            operation, entity_type
+                        ),
+                        None,
+                    )),
+                }
+            }
+
+            // Project Management
+            // Removed duplicate manage_project handler
+
+            // Specification Import and Management Tools
+            "scan_specifications" => {
+                let args = request.arguments.unwrap_or_default();
+                // Without an explicit base_path every configured spec root is scanned
+                let roots: Vec<std::path::PathBuf> = match args.get("base_path").and_then(|v| v.as_str()) {
+                    Some(base_path) => vec![base_path.into()],
+                    None => self.container.config_manager.current().specs.roots_of(SpecSourceFormat::Kiro),
+                };
+
+                let mut specs = Vec::new();
+                for root in &roots {
+                    match self.container.specification_import_service.scan_and_import_specifications(root).await {
+                        Ok(found) => specs.extend(found),
+                        Err(e) => {
+                            return Err(McpError::internal_error(
+                                format!("Failed to scan specifications in {}: {e}", root.display()),
+                                None,
+                            ))
+                        }
+                    }
+                }
+
+                let content = serde_json::to_string_pretty(&specs).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "import_specification" => {
+                let args = request.arguments.unwrap_or_default();
+                let file_path = args
+                    .get("file_path")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        McpError::invalid_params("Missing required parameter: file_path", None)
+                    })?;
+
+                let path = std::path::Path::new(file_path);
+                match self.container.specification_import_service.import_specification_file(path).await {
+                    Ok(spec) => {
+                        let linking = &self.container.specification_context_linking_service;
+                        if let Some(project_id) = args.get("project_id").and_then(|v| v.as_str()) {
+                            linking.suggest_links_for_specification(&spec.id, Some(project_id)).await?;
+                        }
+                        let suggested_links = linking.get_suggested_links(&spec.id).await?;
+                        let mut response = serde_json::to_value(&spec).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {e}"), None)
+                        })?;
+                        response["suggested_links"] = serde_json::json!(suggested_links);
+                        let content = serde_json::to_string_pretty(&response).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {e}"), None)
+                        })?;
+                        Ok(CallToolResult::success(vec![Content::text(content)]))
+                    }
+                    Err(e) => Err(McpError::internal_error(format!("Failed to import specification: {e}"), None)),
+                }
+            }
+
+            "accept_suggested_links" => {
+                let args = request.arguments.unwrap_or_default();
+                let spec_id = args
+                    .get("spec_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| McpError::invalid_params("Missing required parameter: spec_id", None))?;
+                let links: Vec<(String, String)> = args
+                    .get("links")
+                    .and_then(|v| v.as_array())
+                    .map(|links| {
+                        links
+                            .iter()
+                            .filter_map(|link| {
+                                Some((
+                                    link.get("requirement_id")?.as_str()?.to_string(),
+                                    link.get("context_id")?.as_str()?.to_string(),
+                                ))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let min_confidence = args.get("min_confidence").and_then(|v| v.as_f64());
+                let dry_run = args.get("dry_run").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                let linking = &self.container.specification_context_linking_service;
+                let accepted = if dry_run {
+                    Vec::new()
+                } else {
+                    linking.accept_suggested_links(spec_id, &links, min_confidence).await?
+                };
+                let remaining = linking.get_suggested_links(spec_id).await?;
+                let content = serde_json::to_string_pretty(&serde_json::json!({
+                    "spec_id": spec_id,
+                    "accepted": accepted,
+                    "remaining": remaining,
+                }))
+                .map_err(|e| McpError::internal_error(format!("Serialization error: {e}"), None))?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "validate_specification" => {
+                let args = request.arguments.unwrap_or_default();
+                let file_path = args
+                    .get("file_path")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        McpError::invalid_params("Missing required parameter: file_path", None)
+                    })?;
+
+                let path = std::path::Path::new(file_path);
+                match self.container.specification_import_service.validate_specification_file(path).await {
+                    Ok(issues) => {
+                        let quality = match tokio::fs::read_to_string(path).await {
+                            Ok(content) => SpecificationParser::parse_specification(String::new(), file_path, &content)
+                                .and_then(|spec| SpecificationParser::criteria_quality(&spec))
+                                .ok()
+                                .flatten(),
+                            Err(_) => None,
+                        };
+                        let result = serde_json::json!({
+                            "file_path": file_path,
+                            "validation_issues": issues,
+                            "is_valid": issues.is_empty(),
+                            "quality": quality
+                        });
+                        let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                            McpError::internal_error(format!("Serialization error: {e}"), None)
+                        })?;
+                        Ok(CallToolResult::success(vec![Content::text(content)]))
+                    }
+                    Err(e) => Err(McpError::internal_error(format!("Failed to validate specification: {e}"), None)),
+                }
+            }
+
+            "export_specification" => {
+                let args = request.arguments.unwrap_or_default();
+                let spec_id = args
+                    .get("spec_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        McpError::invalid_params("Missing required parameter: spec_id", None)
+                    })?;
+                let dry_run = args.get("dry_run").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                let service = &self.container.specification_service;
+                let spec = service.get_specification(spec_id).await?.ok_or_else(|| {
+                    McpError::invalid_params(format!("Specification not found: {spec_id}"), None)
+                })?;
+                let requirements = service.get_requirements_by_spec(spec_id).await?;
+                let tasks = service.get_tasks_by_spec(spec_id).await?;
+                let markdown = SpecificationExporter::render_markdown(&spec, &requirements, &tasks);
+
+                // The imported file is only replaced when asked for, and only inside a spec directory
+                let overwrite_source = args.get("overwrite_source").and_then(|v| v.as_bool()).unwrap_or(false);
+                let output_path = if overwrite_source {
+                    let source = spec.file_path.as_deref().ok_or_else(|| {
+                        McpError::invalid_params("Specification was not imported from a file", None)
+                    })?;
+                    self.spec_source_path(source)?
+                } else {
+                    let file_name = spec
+                        .file_path
+                        .as_deref()
+                        .and_then(|path| std::path::Path::new(path).file_name())
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| format!("{}.md", spec.spec_type.as_str()));
+                    let default_name = std::path::Path::new("specifications").join(spec_id).join(file_name);
+                    match args.get("output_path").and_then(|v| v.as_str()) {
+                        Some(output_path) => self.export_directory()?.join(Self::relative_path("output_path", output_path)?),
+                        None => self.export_directory()?.join(default_name),
+                    }
+                };
+                if !dry_run {
+                    if let Some(parent) = output_path.parent() {
+                        tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                            McpError::internal_error(format!("Failed to create {}: {e}", parent.display()), None)
+                        })?;
+                    }
+                    tokio::fs::write(&output_path, &markdown).await.map_err(|e| {
+                        McpError::internal_error(format!("Failed to write {}: {e}", output_path.display()), None)
+                    })?;
+                }
+
+                let result = serde_json::json!({
+                    "spec_id": spec_id,
+                    "spec_type": spec.spec_type.as_str(),
+                    "output_path": output_path,
+                    "written": !dry_run,
+                    "requirements": requirements.len(),
+                    "tasks": tasks.len(),
+                    "content": markdown
+                });
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "generate_spec_template" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args
+                    .get("project_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        McpError::invalid_params("Missing required parameter: project_id", None)
+                    })?;
+                let feature_name = args
+                    .get("feature_name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        McpError::invalid_params("Missing required parameter: feature_name", None)
+                    })?;
+                let overwrite = args.get("overwrite").and_then(|v| v.as_bool()).unwrap_or(false);
+                let dry_run = args.get("dry_run").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                let context = TemplateContext {
+                    feature_name: feature_name.to_string(),
+                    description: args.get("description").and_then(|v| v.as_str()).map(str::to_string),
+                    feature_context: self
+                        .container
+                        .feature_context_repository
+                        .get_by_feature_name(project_id, feature_name)
+                        .await?,
+                    components: self.container.framework_service.list_components(project_id).await?,
+                    conventions: self.container.project_convention_repository.list_by_project(project_id).await?,
+                    phases: self.container.development_phase_service.list_phases(project_id).await?,
+                };
+                let template = SpecTemplateGenerator::generate(&context);
+
+                // Templates are only written under the first Kiro spec root
+                let relative = match args.get("output_dir").and_then(|v| v.as_str()) {
+                    Some(dir) => Self::relative_path("output_dir", dir)?,
+                    None if template.directory.is_empty() => {
+                        return Err(McpError::invalid_params(
+                            format!("feature_name has no letters or digits to name a directory: {feature_name}"),
+                            None,
+                        ))
+                    }
+                    None => std::path::PathBuf::from(&template.directory),
+                };
+                let output_dir = self
+                    .container
+                    .config_manager
+                    .current()
+                    .specs
+                    .roots_of(SpecSourceFormat::Kiro)
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| ".kiro/specs".into())
+                    .join(relative);
+                if !dry_run {
+                    tokio::fs::create_dir_all(&output_dir).await.map_err(|e| {
+                        McpError::internal_error(format!("Failed to create {}: {e}", output_dir.display()), None)
+                    })?;
+                }
+
+                let mut files = Vec::new();
+                for (file_name, content) in template.documents() {
+                    let path = output_dir.join(file_name);
+                    // Existing documents are kept unless overwrite is set
+                    let written = !dry_run && (overwrite || !path.exists());
+                    if written {
+                        tokio::fs::write(&path, content).await.map_err(|e| {
+                            McpError::internal_error(format!("Failed to write {}: {e}", path.display()), None)
+                        })?;
+                    }
+                    files.push(serde_json::json!({
+                        "file": file_name,
+                        "path": path,
+                        "written": written,
+                        "content": content
+                    }));
+                }
+
+                let result = serde_json::json!({
+                    "feature_name": feature_name,
+                    "directory": output_dir,
+                    "files": files
+                });
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "get_workflow_state" | "transition_workflow" | "approve_workflow_item" => {
+                let args = request.arguments.unwrap_or_default();
+                let arg = |name: &str| {
+                    args.get(name).and_then(|v| v.as_str()).ok_or_else(|| {
+                        McpError::invalid_params(format!("Missing required parameter: {name}"), None)
+                    })
+                };
+                let entity_type = arg("entity_type")?;
+                let entity = WorkflowEntity::parse(entity_type).ok_or_else(|| {
+                    McpError::invalid_params(
+                        format!("entity_type must be specification or architectural_decision, not {entity_type}"),
+                        None,
+                    )
+                })?;
+                let entity_id = arg("entity_id")?;
+                let comment = args.get("comment").and_then(|v| v.as_str());
+
+                let service = &self.container.approval_workflow_service;
+                let workflow = match request.name.as_ref() {
+                    "get_workflow_state" => service.get_workflow(entity, entity_id).await?,
+                    "transition_workflow" => {
+                        let to_state = arg("to_state")?;
+                        let to = WorkflowState::parse(to_state).ok_or_else(|| {
+                            McpError::invalid_params(format!("Unknown workflow state: {to_state}"), None)
+                        })?;
+                        let approvers = args.get("approvers").and_then(|v| v.as_array()).map(|approvers| {
+                            approvers.iter().filter_map(|a| a.as_str()).map(str::to_string).collect()
+                        });
+                        service.transition(entity, entity_id, to, arg("actor")?, comment, approvers).await?
+                    }
+                    _ => service.approve(entity, entity_id, arg("approver")?, comment).await?,
+                };
+
+                let result = serde_json::json!({
+                    "workflow": workflow,
+                    "pending_approvers": workflow.pending_approvers(),
+                    "next_states": workflow.state.next_states()
+                });
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "manage_decision_links" => {
+                let args = request.arguments.unwrap_or_default();
+                let arg = |name: &str| args.get(name).and_then(|v| v.as_str());
+                let required = |name: &str| {
+                    arg(name).ok_or_else(|| McpError::invalid_params(format!("Missing required parameter: {name}"), None))
+                };
+                let action = required("action")?;
+                let project_id = required("project_id")?;
+                let link_type = || {
+                    let link_type = required("link_type")?;
+                    DecisionLinkType::parse(link_type).ok_or_else(|| {
+                        McpError::invalid_params(
+                            format!("Unknown link_type: {link_type}; expected supersedes, amends or relates_to"),
+                            None,
+                        )
+                    })
+                };
+
+                let repository = &self.container.decision_link_repository;
+                let links = repository.find_by_project(project_id).await?;
+                let result = match action {
+                    "list" => match arg("decision_id") {
+                        Some(decision_id) => {
+                            let decision_links: Vec<&DecisionLink> =
+                                links.iter().filter(|link| link.involves(decision_id)).collect();
+                            serde_json::json!({
+                                "decision_id": decision_id,
+                                "chain_head": chain_head(&links, decision_id),
+                                "links": decision_links
+                            })
+                        }
+                        None => serde_json::json!({"links": links}),
+                    },
+                    "link" => {
+                        let link_type = link_type()?;
+                        let from_id = required("from_decision_id")?;
+                        let to_id = required("to_decision_id")?;
+                        if from_id == to_id {
+                            return Err(McpError::invalid_params("A decision can't be linked to itself", None));
+                        }
+                        let mut decisions = Vec::new();
+                        for id in [from_id, to_id] {
+                            let decision = self
                                 .container
-                                .framework_service
-                                .update_component(&component)
-                                .await?;
-                            results.push(updated_component);
+                                .context_crud_service
+                                .get_architectural_decision(id)
+                                .await?
+                                .filter(|decision| decision.project_id == project_id)
+                                .ok_or_else(|| {
+                                    McpError::resource_not_found(
+                                        format!("No architectural decision {id} in project {project_id}"),
+                                        None,
+                                    )
+                                })?;
+                            decisions.push(decision);
                         }
-                        let content = serde_json::to_string_pretty(&results).map_err(|e| {
-                            McpError::internal_error(format!("Serialization error: {}", e), None)
-                        })?;
-                        Ok(CallToolResult::success(vec![Content::text(content)]))
-                    }
-                    ("delete", "framework_component") => {
-                        let mut ids = Vec::new();
-                        for item in data {
-                            if let Some(obj) = item.as_object() {
-                                if let Some(id) = obj.get("id").and_then(|v| v.as_str()) {
-                                    ids.push(id.to_string());
-                                }
-                            }
+                        if link_type == DecisionLinkType::Supersedes && supersedes_transitively(&links, to_id, from_id) {
+                            return Err(McpError::invalid_params(
+                                format!("{to_id} already supersedes {from_id}; superseding it back would make a cycle"),
+                                None,
+                            ));
                         }
 
-                        let mut deleted_count = 0;
-                        let mut failed_ids = Vec::new();
+                        let mut link = DecisionLink::new(project_id, from_id, to_id, link_type);
+                        link.created_by = CLIENT_AGENT.try_with(Clone::clone).ok().flatten();
+                        repository.save(&link).await?;
 
-                        for id in &ids {
-                            match self.container.framework_service.delete_component(id).await {
-                                Ok(true) => deleted_count += 1,
-                                Ok(false) => failed_ids.push(id.clone()),
-                                Err(e) => {
-                                    tracing::error!("Error deleting component {}: {}", id, e);
-                                    failed_ids.push(id.clone());
-                                }
-                            }
+                        let mut result = serde_json::json!({"link": link});
+                        if link_type == DecisionLinkType::Supersedes {
+                            let actor = link.created_by.as_deref().unwrap_or("decision_links");
+                            self.container
+                                .approval_workflow_service
+                                .supersede_decision(to_id, &decisions[0], actor)
+                                .await?;
+                            result["superseded"] = serde_json::to_value(
+                                self.container.context_crud_service.get_architectural_decision(to_id).await?,
+                            )
+                            .map_err(|e| McpError::internal_error(format!("Serialization error: {e}"), None))?;
                         }
-
-                        let result = serde_json::json!({
-                            "deleted_count": deleted_count,
-                            "component_ids": ids,
-                            "failed_ids": failed_ids,
-                            "success": deleted_count == ids.len()
-                        });
-                        let content = serde_json::to_string_pretty(&result).map_err(|e| {
-                            McpError::internal_error(format!("Serialization error: {}", e), None)
-                        })?;
-                        Ok(CallToolResult::success(vec![Content::text(content)]))
+                        result
                     }
-                    _ => Err(McpError::invalid_params(
-                        format!(
-                            "Unsupported operation '{}' or entity type '{}'",
-                            operation, entity_type
-                        ),
-                        None,
-                    )),
-                }
+                    "unlink" => {
+                        let link_type = link_type()?;
+                        let from_id = required("from_decision_id")?;
+                        let to_id = required("to_decision_id")?;
+                        serde_json::json!({"deleted": repository.delete(from_id, to_id, link_type).await?})
+                    }
+                    other => {
+                        return Err(McpError::invalid_params(
+                            format!("Unknown action: {other}; expected link, unlink or list"),
+                            None,
+                        ))
+                    }
+                };
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
             }
 
-            // Project Management
-            // Removed duplicate manage_project handler
+            "generate_traceability_matrix" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args
+                    .get("project_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        McpError::invalid_params("Missing required parameter: project_id", None)
+                    })?;
+                let spec_project = args.get("spec_project").and_then(|v| v.as_str()).unwrap_or(project_id);
+                let repository_path = args.get("repository_path").and_then(|v| v.as_str()).unwrap_or(".");
+                let commit_limit = args
+                    .get("commit_limit")
+                    .and_then(|v| v.as_u64())
+                    .map_or(DEFAULT_COMMIT_LIMIT, |limit| limit as usize);
+
+                let report = self
+                    .container
+                    .traceability_service
+                    .generate_matrix(project_id, spec_project, std::path::Path::new(repository_path), commit_limit)
+                    .await?;
+                let result = serde_json::json!({
+                    "report": report,
+                    "markdown": report.to_markdown()
+                });
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
 
-            // Specification Import and Management Tools
-            "scan_specifications" => {
+            "analyze_requirement_coverage" => {
                 let args = request.arguments.unwrap_or_default();
-                let base_path = args
-                    .get("base_path")
+                let project_id = args
+                    .get("project_id")
                     .and_then(|v| v.as_str())
-                    .unwrap_or(".kiro/specs");
+                    .ok_or_else(|| {
+                        McpError::invalid_params("Missing required parameter: project_id", None)
+                    })?;
+                let spec_project = args.get("spec_project").and_then(|v| v.as_str()).unwrap_or(project_id);
 
-                let path = std::path::Path::new(base_path);
-                match self.container.specification_import_service.scan_and_import_specifications(path).await {
-                    Ok(specs) => {
-                        let content = serde_json::to_string_pretty(&specs).map_err(|e| {
-                            McpError::internal_error(format!("Serialization error: {e}"), None)
-                        })?;
-                        Ok(CallToolResult::success(vec![Content::text(content)]))
-                    }
-                    Err(e) => Err(McpError::internal_error(format!("Failed to scan specifications: {e}"), None)),
-                }
+                let analysis = self
+                    .container
+                    .traceability_service
+                    .analyze_coverage(project_id, spec_project)
+                    .await?;
+                let result = serde_json::json!({
+                    "coverage": analysis,
+                    "markdown": analysis.to_markdown()
+                });
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
             }
 
-            "import_specification" => {
+            "scan_spec_sources" => {
                 let args = request.arguments.unwrap_or_default();
-                let file_path = args
-                    .get("file_path")
+                let project_id = args
+                    .get("project_id")
                     .and_then(|v| v.as_str())
                     .ok_or_else(|| {
-                        McpError::invalid_params("Missing required parameter: file_path", None)
+                        McpError::invalid_params("Missing required parameter: project_id", None)
                     })?;
 
-                let path = std::path::Path::new(file_path);
-                match self.container.specification_import_service.import_specification_file(path).await {
-                    Ok(spec) => {
-                        let content = serde_json::to_string_pretty(&spec).map_err(|e| {
-                            McpError::internal_error(format!("Serialization error: {e}"), None)
-                        })?;
-                        Ok(CallToolResult::success(vec![Content::text(content)]))
+                let mut sources = Vec::new();
+                for source in self.container.config_manager.current().specs.all_sources() {
+                    let root = source.path.as_path();
+                    if !root.is_dir() {
+                        sources.push(serde_json::json!({
+                            "path": root,
+                            "format": source.format.as_str(),
+                            "skipped": "not a directory"
+                        }));
+                        continue;
                     }
-                    Err(e) => Err(McpError::internal_error(format!("Failed to import specification: {e}"), None)),
+
+                    let import_service = &self.container.specification_import_service;
+                    let imported: Result<Vec<String>, McpError> = match source.format {
+                        SpecSourceFormat::Kiro => import_service
+                            .scan_and_import_specifications(root)
+                            .await
+                            .map(|specs| specs.into_iter().map(|s| s.id).collect()),
+                        SpecSourceFormat::Adr => self
+                            .container
+                            .adr_import_service
+                            .scan_and_import_adrs(project_id, root)
+                            .await
+                            .map(|decisions| decisions.into_iter().map(|d| d.id).collect()),
+                        SpecSourceFormat::Gherkin => import_service
+                            .scan_and_import_features(project_id, root)
+                            .await
+                            .map(|specs| specs.into_iter().map(|s| s.id).collect()),
+                        SpecSourceFormat::Markdown => import_service
+                            .scan_and_import_markdown(project_id, root)
+                            .await
+                            .map(|specs| specs.into_iter().map(|s| s.id).collect()),
+                    };
+                    sources.push(match imported {
+                        Ok(ids) => serde_json::json!({
+                            "path": root,
+                            "format": source.format.as_str(),
+                            "imported": ids.len(),
+                            "ids": ids
+                        }),
+                        Err(e) => serde_json::json!({
+                            "path": root,
+                            "format": source.format.as_str(),
+                            "error": e.message
+                        }),
+                    });
                 }
+
+                let result = serde_json::json!({
+                    "project_id": project_id,
+                    "sources": sources
+                });
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
             }
 
-            "validate_specification" => {
+            "start_spec_monitoring" => {
                 let args = request.arguments.unwrap_or_default();
-                let file_path = args
-                    .get("file_path")
+                let default_root = self
+                    .container
+                    .config_manager
+                    .current()
+                    .specs
+                    .roots_of(SpecSourceFormat::Kiro)
+                    .first()
+                    .map(|p| p.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| ".kiro/specs".to_string());
+                let base_path = args
+                    .get("base_path")
                     .and_then(|v| v.as_str())
-                    .ok_or_else(|| {
-                        McpError::invalid_params("Missing required parameter: file_path", None)
-                    })?;
+                    .unwrap_or(&default_root);
 
-                let path = std::path::Path::new(file_path);
-                match self.container.specification_import_service.validate_specification_file(path).await {
-                    Ok(issues) => {
+                let path = std::path::Path::new(base_path);
+                match self.container.specification_import_service.start_file_monitoring(path).await {
+                    Ok(()) => {
                         let result = serde_json::json!({
-                            "file_path": file_path,
-                            "validation_issues": issues,
-                            "is_valid": issues.is_empty()
+                            "status": "success",
+                            "message": format!("Started monitoring {}", base_path),
+                            "monitoring_path": base_path
                         });
                         let content = serde_json::to_string_pretty(&result).map_err(|e| {
                             McpError::internal_error(format!("Serialization error: {e}"), None)
                         })?;
                         Ok(CallToolResult::success(vec![Content::text(content)]))
                     }
-                    Err(e) => Err(McpError::internal_error(format!("Failed to validate specification: {e}"), None)),
+                    Err(e) => Err(McpError::internal_error(format!("Failed to start monitoring: {e}"), None)),
                 }
             }
 
-            "start_spec_monitoring" => {
+            "scan_adrs" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args
+                    .get("project_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        McpError::invalid_params("Missing required parameter: project_id", None)
+                    })?;
+                // Without an explicit base_path every configured ADR root that exists is scanned
+                let roots: Vec<std::path::PathBuf> = match args.get("base_path").and_then(|v| v.as_str()) {
+                    Some(base_path) => vec![base_path.into()],
+                    None => self
+                        .container
+                        .config_manager
+                        .current()
+                        .specs
+                        .roots_of(SpecSourceFormat::Adr)
+                        .into_iter()
+                        .filter(|root| root.is_dir())
+                        .collect(),
+                };
+
+                let mut decisions = Vec::new();
+                for root in &roots {
+                    match self.container.adr_import_service.scan_and_import_adrs(project_id, root).await {
+                        Ok(found) => decisions.extend(found),
+                        Err(e) => {
+                            return Err(McpError::internal_error(
+                                format!("Failed to scan ADRs in {}: {e}", root.display()),
+                                None,
+                            ))
+                        }
+                    }
+                }
+
+                let content = serde_json::to_string_pretty(&decisions).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
+            "start_adr_monitoring" => {
                 let args = request.arguments.unwrap_or_default();
+                let project_id = args
+                    .get("project_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        McpError::invalid_params("Missing required parameter: project_id", None)
+                    })?;
+                let default_root = self
+                    .container
+                    .config_manager
+                    .current()
+                    .specs
+                    .roots_of(SpecSourceFormat::Adr)
+                    .first()
+                    .map(|p| p.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "docs/adr".to_string());
                 let base_path = args
                     .get("base_path")
                     .and_then(|v| v.as_str())
-                    .unwrap_or(".kiro/specs");
+                    .unwrap_or(&default_root);
 
                 let path = std::path::Path::new(base_path);
-                match self.container.specification_import_service.start_file_monitoring(path).await {
+                match self.container.adr_import_service.start_file_monitoring(project_id, path).await {
                     Ok(()) => {
                         let result = serde_json::json!({
                             "status": "success",
@@ -2981,6 +9364,32 @@ impl ServerHandler for EnhancedContextMcpServer {
                 }
             }
 
+            "import_openapi" => {
+                let args = request.arguments.unwrap_or_default();
+                let project_id = args
+                    .get("project_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        McpError::invalid_params("Missing required parameter: project_id", None)
+                    })?;
+                let file_path = args
+                    .get("file_path")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        McpError::invalid_params("Missing required parameter: file_path", None)
+                    })?;
+
+                let report = self
+                    .container
+                    .openapi_import_service
+                    .import_openapi_file(project_id, std::path::Path::new(file_path))
+                    .await?;
+                let content = serde_json::to_string_pretty(&report).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
             "get_specification_versions" => {
                 let args = request.arguments.unwrap_or_default();
                 let spec_id = args
@@ -3027,6 +9436,59 @@ impl ServerHandler for EnhancedContextMcpServer {
                 }
             }
 
+            "squash_spec_versions" => {
+                let args = request.arguments.unwrap_or_default();
+                let mut retention = self.container.config_manager.current().specs.versions;
+                if let Some(keep_recent) = args.get("keep_recent").and_then(|v| v.as_u64()) {
+                    retention.keep_recent = keep_recent as usize;
+                }
+                if let Some(days) = args.get("milestone_interval_days").and_then(|v| v.as_u64()) {
+                    retention.milestone_interval_days = days as u32;
+                }
+                if let Some(days) = args.get("max_age_days").and_then(|v| v.as_u64()) {
+                    retention.max_age_days = days as u32;
+                }
+                let dry_run = args.get("dry_run").and_then(|v| v.as_bool()).unwrap_or(false);
+
+                let spec_ids = match (
+                    args.get("spec_id").and_then(|v| v.as_str()),
+                    args.get("project_id").and_then(|v| v.as_str()),
+                ) {
+                    (Some(spec_id), _) => vec![spec_id.to_string()],
+                    (None, Some(project_id)) => self
+                        .container
+                        .specification_service
+                        .get_specifications_by_project(project_id)
+                        .await?
+                        .into_iter()
+                        .map(|spec| spec.id)
+                        .collect(),
+                    (None, None) => {
+                        return Err(McpError::invalid_params("Either spec_id or project_id is required", None));
+                    }
+                };
+
+                let mut reports = Vec::new();
+                for spec_id in &spec_ids {
+                    reports.push(
+                        self.container
+                            .specification_versioning_service
+                            .squash_versions(spec_id, &retention, dry_run)
+                            .await?,
+                    );
+                }
+                let result = serde_json::json!({
+                    "retention": retention,
+                    "squashed": reports.iter().map(|r| r.squashed.len()).sum::<usize>(),
+                    "pruned": reports.iter().map(|r| r.pruned.len()).sum::<usize>(),
+                    "specifications": reports,
+                });
+                let content = serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Serialization error: {e}"), None)
+                })?;
+                Ok(CallToolResult::success(vec![Content::text(content)]))
+            }
+
             // Specification Analytics Tools
             "track_requirements_progress" | "track_tasks_progress" | "analyze_specification_completeness" | 
             "calculate_development_velocity" | "generate_specification_health_report" => {
@@ -3040,3 +9502,321 @@ impl ServerHandler for EnhancedContextMcpServer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(name: &'static str, arguments: serde_json::Value) -> CallToolRequestParam {
+        CallToolRequestParam {
+            name: name.into(),
+            arguments: arguments.as_object().cloned(),
+        }
+    }
+
+    /// What `call_tool` does with a request, minus the client it needs
+    async fn call(server: &EnhancedContextMcpServer, name: &'static str, arguments: serde_json::Value) -> serde_json::Value {
+        let request = request(name, arguments);
+        let writes = EnhancedContextMcpServer::cache_writes(&request);
+        let reads_only = EnhancedContextMcpServer::reads_only(&request);
+        let result = server.dispatch_tool(request).await.unwrap_or_else(|e| panic!("{name} failed: {e:?}"));
+        server.invalidate_writes(&writes, reads_only);
+        result
+            .content
+            .first()
+            .and_then(|content| content.as_text())
+            .and_then(|text| serde_json::from_str(&text.text).ok())
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_write_tools_drop_cached_reads() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("context.db");
+        let db_path = db_path.to_str().unwrap();
+        crate::db::init::init_db(db_path).unwrap();
+        let server = EnhancedContextMcpServer::new(db_path).unwrap();
+        let cache = server.container.query_cache.clone();
+
+        let project = call(&server, "create_entity", json!({"entity_type": "project", "data": {"name": "Shop"}})).await;
+        let project_id = project["id"].as_str().unwrap().to_string();
+        let mut decisions = Vec::new();
+        for title in ["Use Postgres", "Use SQLite"] {
+            let decision = call(
+                &server,
+                "create_entity",
+                json!({"entity_type": "architectural_decision", "data": {"project_id": project_id, "decision_title": title}}),
+            )
+            .await;
+            decisions.push(decision["id"].as_str().unwrap().to_string());
+        }
+
+        let query = json!({"project_id": project_id, "feature_area": "checkout", "task_type": "implement"});
+        let context_key = CacheKeyBuilder::context_query(&project_id, "checkout", "implement", &[]);
+        let decision_key = |id: &str| CacheKeyBuilder::architectural_decision(id);
+
+        let writes = [
+            ("manage_glossary", json!({"action": "add", "project_id": project_id, "term": "checkout", "synonyms": ["cart"]})),
+            ("review_tag_suggestions", json!({"suggestion_ids": [], "action": "accept"})),
+            ("submit_for_review", json!({"project_id": project_id, "entity_ids": [decisions[0]]})),
+            (
+                "manage_custom_entity_types",
+                json!({"action": "define", "name": "slo", "schema": {"type": "object"}}),
+            ),
+            ("get_unused_context", json!({"project_id": project_id, "tag_stale": true})),
+            (
+                "manage_decision_links",
+                json!({"action": "link", "project_id": project_id, "from_decision_id": decisions[1], "to_decision_id": decisions[0], "link_type": "relates_to"}),
+            ),
+            (
+                "manage_decision_links",
+                json!({"action": "unlink", "project_id": project_id, "from_decision_id": decisions[1], "to_decision_id": decisions[0], "link_type": "relates_to"}),
+            ),
+        ];
+        for (tool, arguments) in writes {
+            call(&server, "query_context", query.clone()).await;
+            for id in &decisions {
+                call(&server, "get_entity", json!({"entity_type": "architectural_decision", "id": id})).await;
+                assert!(cache.get(&decision_key(id)).is_some());
+            }
+            assert!(cache.get(&context_key).is_some(), "query_context should be cached before {tool}");
+
+            let is_link = tool == "manage_decision_links";
+            call(&server, tool, arguments).await;
+            assert!(cache.get(&context_key).is_none(), "{tool} should drop cached query_context results");
+            for id in &decisions {
+                assert_eq!(cache.get(&decision_key(id)).is_none(), is_link, "{tool} and cached decision {id}");
+            }
+        }
+
+        // The result read after a write reflects it
+        call(&server, "query_context", query.clone()).await;
+        call(
+            &server,
+            "manage_decision_links",
+            json!({"action": "link", "project_id": project_id, "from_decision_id": decisions[1], "to_decision_id": decisions[0], "link_type": "supersedes"}),
+        )
+        .await;
+        let superseded = call(&server, "get_entity", json!({"entity_type": "architectural_decision", "id": decisions[0]})).await;
+        assert!(superseded["status"].as_str().unwrap_or_default().starts_with("superseded"));
+
+        // Reads keep the cache
+        call(&server, "query_context", query.clone()).await;
+        call(&server, "list_entities", json!({"entity_type": "architectural_decision", "project_id": project_id})).await;
+        call(&server, "manage_glossary", json!({"action": "list", "project_id": project_id})).await;
+        call(&server, "get_unused_context", json!({"project_id": project_id})).await;
+        assert!(cache.get(&context_key).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_query_context_fits_every_section_in_the_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("context.db");
+        let db_path = db_path.to_str().unwrap();
+        crate::db::init::init_db(db_path).unwrap();
+        let server = EnhancedContextMcpServer::new(db_path).unwrap();
+
+        let project = call(&server, "create_entity", json!({"entity_type": "project", "data": {"name": "Shop"}})).await;
+        let project_id = project["id"].as_str().unwrap();
+        let mut decisions = Vec::new();
+        for i in 0..12 {
+            let decision = call(
+                &server,
+                "create_entity",
+                json!({"entity_type": "architectural_decision", "data": {
+                    "project_id": project_id,
+                    "decision_title": format!("Checkout decision {i}"),
+                    "context": "The checkout cart keeps orders consistent. ".repeat(10),
+                    "status": "accepted"
+                }}),
+            )
+            .await;
+            decisions.push(decision["id"].as_str().unwrap().to_string());
+        }
+        for i in 1..4 {
+            call(
+                &server,
+                "manage_decision_links",
+                json!({"action": "link", "project_id": project_id, "from_decision_id": decisions[i], "to_decision_id": decisions[0], "link_type": "relates_to"}),
+            )
+            .await;
+        }
+        call(&server, "manage_glossary", json!({"action": "add", "project_id": project_id, "term": "cart", "synonyms": ["basket"]})).await;
+        call(
+            &server,
+            "create_entity",
+            json!({"entity_type": "feature_context", "data": {
+                "project_id": project_id,
+                "feature_name": "Checkout",
+                "business_purpose": "Customers pay for the cart in one step. ".repeat(20)
+            }}),
+        )
+        .await;
+
+        let max_tokens = 600;
+        let result = call(
+            &server,
+            "query_context",
+            json!({"project_id": project_id, "feature_area": "checkout", "task_type": "implement", "include_drafts": true, "max_tokens": max_tokens}),
+        )
+        .await;
+        assert!(estimate_tokens(&result) <= max_tokens);
+        assert!(result["assembly"]["result_tokens"].as_u64().unwrap() <= max_tokens as u64);
+        let returned = result["architectural_decisions"].as_array().unwrap().len();
+        assert!(returned > 0 && returned < decisions.len());
+        assert!(!result["assembly"]["omitted_ids"].as_array().unwrap().is_empty());
+        assert!(!result["glossary_terms"].as_array().unwrap().is_empty());
+        // The feature context alone would take more than its share
+        assert!(result.get("feature_context").is_none());
+        assert_eq!(result["assembly"]["omitted_sections"]["feature_context"], 1);
+
+        let roomy = call(
+            &server,
+            "query_context",
+            json!({"project_id": project_id, "feature_area": "checkout", "task_type": "implement", "include_drafts": true, "max_tokens": 100_000}),
+        )
+        .await;
+        assert_eq!(roomy["architectural_decisions"].as_array().unwrap().len(), decisions.len());
+        assert_eq!(roomy["decision_links"].as_array().unwrap().len(), 3);
+        assert_eq!(roomy["feature_context"]["feature_name"], "Checkout");
+    }
+
+    #[tokio::test]
+    async fn test_generate_dashboard_writes_inside_the_export_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("context.db");
+        crate::db::init::init_db(db_path.to_str().unwrap()).unwrap();
+        let exports = dir.path().join("exports");
+        let mut config = AppConfig::default();
+        config.database.path = Some(db_path);
+        config.exports.directory = Some(exports.clone());
+        let server = EnhancedContextMcpServer::from_config(config).unwrap();
+
+        let project = call(&server, "create_entity", json!({"entity_type": "project", "data": {"name": "Shop"}})).await;
+        let project_id = project["id"].as_str().unwrap();
+        let outside = dir.path().join("outside.html");
+        for output_path in [outside.to_str().unwrap(), "../outside.html", "reports/../../outside.html"] {
+            let result = server
+                .dispatch_tool(request("generate_dashboard", json!({"project_id": project_id, "output_path": output_path})))
+                .await;
+            assert!(result.is_err(), "{output_path} should be rejected");
+        }
+        assert!(!outside.exists());
+
+        let written = call(&server, "generate_dashboard", json!({"project_id": project_id, "output_path": "reports/shop.html"})).await;
+        assert_eq!(written["path"], json!(exports.join("reports/shop.html")));
+        assert!(exports.join("reports/shop.html").is_file());
+        let default = call(&server, "generate_dashboard", json!({"project_id": project_id})).await;
+        assert!(std::path::Path::new(default["path"].as_str().unwrap()).starts_with(&exports));
+    }
+
+    #[tokio::test]
+    async fn test_parquet_export_writes_inside_the_export_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("context.db");
+        crate::db::init::init_db(db_path.to_str().unwrap()).unwrap();
+        let exports = dir.path().join("exports");
+        let mut config = AppConfig::default();
+        config.database.path = Some(db_path);
+        config.exports.directory = Some(exports.clone());
+        let server = EnhancedContextMcpServer::from_config(config).unwrap();
+
+        let export = |output_path: &str| {
+            request(
+                "export_analytics_data",
+                json!({
+                    "format": "parquet",
+                    "output_path": output_path,
+                    "start_date": "2026-01-01T00:00:00Z",
+                    "end_date": "2026-02-01T00:00:00Z"
+                }),
+            )
+        };
+        let outside = dir.path().join("events.parquet");
+        assert!(server.dispatch_tool(export(outside.to_str().unwrap())).await.is_err());
+        assert!(server.dispatch_tool(export("../events.parquet")).await.is_err());
+        assert!(!outside.exists());
+
+        server.dispatch_tool(export("events.parquet")).await.unwrap();
+        assert!(exports.join("events.parquet").is_file());
+    }
+
+    #[tokio::test]
+    async fn test_generate_spec_template_writes_under_the_kiro_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("context.db");
+        crate::db::init::init_db(db_path.to_str().unwrap()).unwrap();
+        let specs = dir.path().join("specs");
+        let mut config = AppConfig::default();
+        config.database.path = Some(db_path);
+        config.specs.roots = vec![specs.clone()];
+        let server = EnhancedContextMcpServer::from_config(config).unwrap();
+
+        let generate = |extra: serde_json::Value| {
+            let mut arguments = json!({"project_id": "shop", "feature_name": "User Login"});
+            arguments.as_object_mut().unwrap().extend(extra.as_object().cloned().unwrap());
+            request("generate_spec_template", arguments)
+        };
+        let outside = dir.path().join("outside");
+        let absolute = json!({"output_dir": outside.to_str().unwrap()});
+        assert!(server.dispatch_tool(generate(absolute)).await.is_err());
+        assert!(server.dispatch_tool(generate(json!({"output_dir": "../outside"}))).await.is_err());
+        assert!(!outside.exists());
+
+        let requirements = specs.join("user-login").join("requirements.md");
+        server.dispatch_tool(generate(json!({}))).await.unwrap();
+        assert!(requirements.is_file());
+
+        // Edited documents are kept unless overwrite is set
+        std::fs::write(&requirements, "edited").unwrap();
+        server.dispatch_tool(generate(json!({}))).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&requirements).unwrap(), "edited");
+        server.dispatch_tool(generate(json!({"overwrite": true}))).await.unwrap();
+        assert_ne!(std::fs::read_to_string(&requirements).unwrap(), "edited");
+
+        server.dispatch_tool(generate(json!({"output_dir": "auth/login"}))).await.unwrap();
+        assert!(specs.join("auth").join("login").join("tasks.md").is_file());
+    }
+
+    #[tokio::test]
+    async fn test_export_specification_only_overwrites_its_source_when_asked() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("context.db");
+        crate::db::init::init_db(db_path.to_str().unwrap()).unwrap();
+        let specs = dir.path().join("specs");
+        let source = specs.join("login").join("requirements.md");
+        std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+        let original = "# Requirements Document\n\n## Requirements\n\n### Requirement 1\n\n**User Story:** As a user, I want to log in, so that I can see my projects.\n\n#### Acceptance Criteria\n\n1. WHEN the password is wrong THEN the system SHALL refuse the login\n";
+        std::fs::write(&source, original).unwrap();
+        let exports = dir.path().join("exports");
+        let mut config = AppConfig::default();
+        config.database.path = Some(db_path);
+        config.exports.directory = Some(exports.clone());
+        config.specs.roots = vec![specs.clone()];
+        let server = EnhancedContextMcpServer::from_config(config).unwrap();
+
+        let imported = call(&server, "import_specification", json!({"file_path": source.to_str().unwrap()})).await;
+        let spec_id = imported["id"].as_str().unwrap();
+
+        // By default the export goes to the export directory and the source is left alone
+        server.dispatch_tool(request("export_specification", json!({"spec_id": spec_id}))).await.unwrap();
+        assert!(exports.join("specifications").join(spec_id).join("requirements.md").is_file());
+        assert_eq!(std::fs::read_to_string(&source).unwrap(), original);
+        let outside = json!({"spec_id": spec_id, "output_path": "../requirements.md"});
+        assert!(server.dispatch_tool(request("export_specification", outside)).await.is_err());
+
+        std::fs::write(&source, "stale").unwrap();
+        let overwrite = json!({"spec_id": spec_id, "overwrite_source": true});
+        server.dispatch_tool(request("export_specification", overwrite.clone())).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&source).unwrap(), original);
+
+        // A source outside every configured spec directory is never written
+        let mut moved = server.container.config_manager.current();
+        moved.specs.roots = vec![dir.path().join("elsewhere")];
+        let server = EnhancedContextMcpServer::from_config(moved).unwrap();
+        std::fs::write(&source, "stale").unwrap();
+        assert!(server.dispatch_tool(request("export_specification", overwrite)).await.is_err());
+        assert_eq!(std::fs::read_to_string(&source).unwrap(), "stale");
+    }
+}
@@ -0,0 +1,67 @@
+use crate::repositories::BlobStore;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use std::path::PathBuf;
+
+/// BlobStore keeping each content as a file named by its hash, under a subdirectory named
+/// by the hash's first two characters
+pub struct FileBlobStore {
+    directory: PathBuf,
+}
+
+impl FileBlobStore {
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    fn path(&self, content_hash: &str) -> Result<PathBuf, McpError> {
+        // Hashes become file names, so anything but hex digits could escape the directory
+        if content_hash.len() < 3 || !content_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(McpError::invalid_params(format!("Invalid content hash: {content_hash}"), None));
+        }
+        Ok(self.directory.join(&content_hash[..2]).join(content_hash))
+    }
+}
+
+#[async_trait]
+impl BlobStore for FileBlobStore {
+    async fn put(&self, content_hash: &str, content: &[u8]) -> Result<(), McpError> {
+        let path = self.path(content_hash)?;
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(());
+        }
+        let write_error = |e: std::io::Error| {
+            McpError::internal_error(format!("Failed to write attachment {}: {}", path.display(), e), None)
+        };
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(write_error)?;
+        }
+        // Write aside and rename so a crash never leaves a truncated blob under its hash
+        let partial = path.with_extension("partial");
+        tokio::fs::write(&partial, content).await.map_err(write_error)?;
+        tokio::fs::rename(&partial, &path).await.map_err(write_error)
+    }
+
+    async fn get(&self, content_hash: &str) -> Result<Option<Vec<u8>>, McpError> {
+        let path = self.path(content_hash)?;
+        match tokio::fs::read(&path).await {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(McpError::internal_error(
+                format!("Failed to read attachment {}: {}", path.display(), e),
+                None,
+            )),
+        }
+    }
+
+    async fn delete(&self, content_hash: &str) -> Result<(), McpError> {
+        let path = self.path(content_hash)?;
+        match tokio::fs::remove_file(&path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(McpError::internal_error(
+                format!("Failed to delete attachment {}: {}", path.display(), e),
+                None,
+            )),
+            _ => Ok(()),
+        }
+    }
+}
@@ -1,36 +1,80 @@
 // Infrastructure layer - SQLite implementations of repositories
 
+pub mod file_blob_store;
 pub mod sqlite_analytics_repository;
+pub mod sqlite_approval_workflow_repository;
+pub mod sqlite_architecture_rule_set_repository;
 pub mod sqlite_architectural_decision_repository;
+pub mod sqlite_api_contract_repository;
+pub mod sqlite_attachment_repository;
 pub mod sqlite_audit_trail_repository;
 pub mod sqlite_business_rule_repository;
+pub mod sqlite_change_log_repository;
+pub mod sqlite_conflict_repository;
 pub mod sqlite_constraint_repository;
+pub mod sqlite_context_digest_repository;
+pub mod sqlite_custom_entity_repository;
+pub mod sqlite_decision_link_repository;
 pub mod sqlite_development_phase_repository;
 pub mod sqlite_enhanced_context_repository;
+pub mod sqlite_entity_review_repository;
+pub mod sqlite_entity_verification_repository;
+pub mod sqlite_feature_context_repository;
+pub mod sqlite_fitness_measurement_repository;
 pub mod sqlite_framework_repository;
+pub mod sqlite_glossary_repository;
 pub mod sqlite_performance_requirement_repository;
+pub mod sqlite_project_convention_repository;
 pub mod sqlite_project_repository;
+pub mod sqlite_quality_rubric_repository;
+pub mod sqlite_saved_search_repository;
+pub mod sqlite_scheduled_report_repository;
+pub mod sqlite_security_policy_repository;
+pub mod sqlite_shared_context_link_repository;
 pub mod sqlite_specification_repository;
+pub mod sqlite_tag_repository;
+pub mod sqlite_tool_call_audit_repository;
+pub mod sqlite_traceability_report_repository;
 // Note: sqlite_component_repository was removed as it was identical to sqlite_framework_repository
 // TODO: Fix error handling in these files
-// pub mod sqlite_security_policy_repository;
 // pub mod sqlite_extended_repositories;
 
 // Re-export implementations
+pub use file_blob_store::FileBlobStore;
 pub use sqlite_analytics_repository::SqliteAnalyticsRepository;
+pub use sqlite_approval_workflow_repository::SqliteApprovalWorkflowRepository;
+pub use sqlite_architecture_rule_set_repository::SqliteArchitectureRuleSetRepository;
 pub use sqlite_architectural_decision_repository::SqliteArchitecturalDecisionRepository;
+pub use sqlite_api_contract_repository::SqliteApiContractRepository;
+pub use sqlite_attachment_repository::{SqliteAttachmentRepository, SqliteBlobStore};
 pub use sqlite_audit_trail_repository::{AuditTrailRepository, SqliteAuditTrailRepository};
 pub use sqlite_business_rule_repository::SqliteBusinessRuleRepository;
+pub use sqlite_change_log_repository::SqliteChangeLogRepository;
+pub use sqlite_conflict_repository::SqliteConflictRepository;
 pub use sqlite_constraint_repository::{
     ConstraintRepository, DependencyRepository, SqliteConstraintRepository, SqliteDependencyRepository,
 };
+pub use sqlite_context_digest_repository::SqliteContextDigestRepository;
+pub use sqlite_custom_entity_repository::SqliteCustomEntityRepository;
+pub use sqlite_decision_link_repository::SqliteDecisionLinkRepository;
 pub use sqlite_development_phase_repository::SqliteDevelopmentPhaseRepository;
 pub use sqlite_enhanced_context_repository::SqliteEnhancedContextRepository;
+pub use sqlite_entity_review_repository::SqliteEntityReviewRepository;
+pub use sqlite_entity_verification_repository::SqliteEntityVerificationRepository;
+pub use sqlite_feature_context_repository::SqliteFeatureContextRepository;
+pub use sqlite_fitness_measurement_repository::SqliteFitnessMeasurementRepository;
 pub use sqlite_framework_repository::SqliteFrameworkRepository;
+pub use sqlite_glossary_repository::SqliteGlossaryRepository;
 pub use sqlite_performance_requirement_repository::SqlitePerformanceRequirementRepository;
+pub use sqlite_project_convention_repository::SqliteProjectConventionRepository;
 pub use sqlite_project_repository::SqliteProjectRepository;
+pub use sqlite_quality_rubric_repository::SqliteQualityRubricRepository;
+pub use sqlite_saved_search_repository::SqliteSavedSearchRepository;
+pub use sqlite_scheduled_report_repository::SqliteScheduledReportRepository;
+pub use sqlite_security_policy_repository::SqliteSecurityPolicyRepository;
+pub use sqlite_shared_context_link_repository::SqliteSharedContextLinkRepository;
 pub use sqlite_specification_repository::SqliteSpecificationRepository;
+pub use sqlite_tag_repository::SqliteTagRepository;
+pub use sqlite_tool_call_audit_repository::SqliteToolCallAuditRepository;
+pub use sqlite_traceability_report_repository::SqliteTraceabilityReportRepository;
 // Note: SqliteComponentRepository removed - use SqliteFrameworkRepository instead
-// TODO: Re-enable when fixed
-// pub use sqlite_security_policy_repository::SqliteSecurityPolicyRepository;
-// pub use sqlite_extended_repositories::{SqliteProjectConventionRepository, SqliteFeatureContextRepository};
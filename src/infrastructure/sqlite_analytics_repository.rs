@@ -1,4 +1,7 @@
-use crate::services::analytics_service::{AnalyticsEvent, AnalyticsEventType, AnalyticsRepository, UsageStatistics};
+use crate::services::analytics_service::{
+    AnalyticsEvent, AnalyticsEventType, AnalyticsPruneReport, AnalyticsRepository, DailyUsage, EntityUseSource,
+    EntityUses, SearchClick, UsageStatistics,
+};
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -34,11 +37,25 @@ impl SqliteAnalyticsRepository {
                 timestamp TEXT NOT NULL,
                 duration_ms INTEGER,
                 success BOOLEAN NOT NULL,
-                error_message TEXT
+                error_message TEXT,
+                rolled_up INTEGER NOT NULL DEFAULT 0
             )",
             [],
         )?;
 
+        // Databases created before rollups hold no rolled-up events
+        let has_rolled_up: bool = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('analytics_events') WHERE name = 'rolled_up'",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_rolled_up {
+            conn.execute(
+                "ALTER TABLE analytics_events ADD COLUMN rolled_up INTEGER NOT NULL DEFAULT 0",
+                [],
+            )?;
+        }
+
         // Create indexes for better query performance
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_analytics_events_project_id ON analytics_events(project_id)",
@@ -55,21 +72,139 @@ impl SqliteAnalyticsRepository {
             [],
         )?;
 
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_analytics_events_pending ON analytics_events(rolled_up) WHERE rolled_up = 0",
+            [],
+        )?;
+
+        // Rolled-up events as per-hour and per-day counts; project_id is '' for events without
+        // one. Hourly rollups are pruned with the raw events, daily ones are kept; `hourly_from`
+        // in analytics_rollup_state is the first day hourly rollups are kept for.
+        // Entities handed to clients per day, for finding context nobody uses
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS analytics_entity_uses (
+                day TEXT NOT NULL,
+                entity_id TEXT NOT NULL,
+                entity_type TEXT NOT NULL,
+                project_id TEXT NOT NULL,
+                queries INTEGER NOT NULL DEFAULT 0,
+                searches INTEGER NOT NULL DEFAULT 0,
+                reads INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (day, entity_id)
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_analytics_entity_uses_project ON analytics_entity_uses(project_id, entity_id)",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS analytics_rollup_state (
+                name TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS analytics_hourly_aggregates (
+                hour TEXT NOT NULL,
+                project_id TEXT NOT NULL,
+                event_type TEXT NOT NULL,
+                event_count INTEGER NOT NULL,
+                success_count INTEGER NOT NULL,
+                total_duration_ms INTEGER NOT NULL,
+                timed_count INTEGER NOT NULL,
+                PRIMARY KEY (hour, project_id, event_type)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS analytics_daily_aggregates (
+                day TEXT NOT NULL,
+                project_id TEXT NOT NULL,
+                event_type TEXT NOT NULL,
+                event_count INTEGER NOT NULL,
+                success_count INTEGER NOT NULL,
+                total_duration_ms INTEGER NOT NULL,
+                timed_count INTEGER NOT NULL,
+                PRIMARY KEY (day, project_id, event_type)
+            )",
+            [],
+        )?;
+
         Ok(())
     }
 
+    /// First day with hourly rollups; '' until pruning removes some
+    const HOURLY_FROM: &'static str =
+        "(SELECT COALESCE(MAX(value), '') FROM analytics_rollup_state WHERE name = 'hourly_from')";
+
+    /// Counts per project and event type of the events between ?1 and ?2, to the hour: hourly
+    /// rollups of the hours the range touches, daily rollups of the days whose hourly rollups
+    /// were pruned, and the events not rolled up yet
+    fn counts_in_range() -> String {
+        format!(
+            "SELECT project_id, event_type, event_count, success_count FROM analytics_hourly_aggregates
+             WHERE hour >= substr(?1, 1, 13) AND hour <= substr(?2, 1, 13)
+             UNION ALL
+             SELECT project_id, event_type, event_count, success_count FROM analytics_daily_aggregates
+             WHERE day >= substr(?1, 1, 10) AND day <= substr(?2, 1, 10) AND day < {}
+             UNION ALL
+             SELECT COALESCE(project_id, ''), event_type, 1, success FROM analytics_events
+             WHERE rolled_up = 0 AND timestamp >= ?1 AND timestamp <= ?2",
+            Self::HOURLY_FROM
+        )
+    }
+
+    /// Counts per event type of all events: daily rollups and the events not rolled up yet
+    const ALL_COUNTS: &'static str = "
+        SELECT event_type, event_count, success_count, total_duration_ms, timed_count
+        FROM analytics_daily_aggregates
+        UNION ALL
+        SELECT event_type, 1, success, COALESCE(duration_ms, 0), duration_ms IS NOT NULL
+        FROM analytics_events WHERE rolled_up = 0";
+
+    /// Adds the events not rolled up yet to the hourly and daily rollups and marks them;
+    /// returns the events and the rollup rows written. Events arriving for days whose hourly
+    /// rollups were pruned only go into the daily ones.
+    fn roll_up(conn: &Connection) -> Result<(u64, u64)> {
+        let mut rows_updated = 0;
+        let hourly_filter = format!("AND substr(timestamp, 1, 10) >= {}", Self::HOURLY_FROM);
+        for (table, key, length, filter) in [
+            ("analytics_hourly_aggregates", "hour", 13, hourly_filter.as_str()),
+            ("analytics_daily_aggregates", "day", 10, ""),
+        ] {
+            rows_updated += conn.execute(
+                &format!(
+                    "INSERT INTO {table}
+                        ({key}, project_id, event_type, event_count, success_count, total_duration_ms, timed_count)
+                     SELECT substr(timestamp, 1, {length}), COALESCE(project_id, ''), event_type, COUNT(*), SUM(success),
+                            COALESCE(SUM(duration_ms), 0), COUNT(duration_ms)
+                     FROM analytics_events WHERE rolled_up = 0 {filter}
+                     GROUP BY substr(timestamp, 1, {length}), COALESCE(project_id, ''), event_type
+                     ON CONFLICT ({key}, project_id, event_type) DO UPDATE SET
+                        event_count = event_count + excluded.event_count,
+                        success_count = success_count + excluded.success_count,
+                        total_duration_ms = total_duration_ms + excluded.total_duration_ms,
+                        timed_count = timed_count + excluded.timed_count"
+                ),
+                [],
+            )? as u64;
+        }
+        let events = conn.execute("UPDATE analytics_events SET rolled_up = 1 WHERE rolled_up = 0", [])? as u64;
+        Ok((events, rows_updated))
+    }
+
+    fn pragma(conn: &Connection, name: &str) -> Result<u64> {
+        Ok(conn.query_row(&format!("PRAGMA {}", name), [], |row| row.get::<_, i64>(0))? as u64)
+    }
+
     fn row_to_analytics_event(row: &Row) -> Result<AnalyticsEvent, rusqlite::Error> {
         let event_type_str: String = row.get("event_type")?;
-        let event_type = match event_type_str.as_str() {
-            "ContextQuery" => AnalyticsEventType::ContextQuery,
-            "EntityCreate" => AnalyticsEventType::EntityCreate,
-            "EntityUpdate" => AnalyticsEventType::EntityUpdate,
-            "EntityDelete" => AnalyticsEventType::EntityDelete,
-            "BulkOperation" => AnalyticsEventType::BulkOperation,
-            "ArchitectureValidation" => AnalyticsEventType::ArchitectureValidation,
-            "CacheOperation" => AnalyticsEventType::CacheOperation,
-            _ => AnalyticsEventType::ContextQuery, // Default fallback
-        };
+        let event_type = AnalyticsEventType::from_name(&event_type_str).unwrap_or(AnalyticsEventType::ContextQuery); // Default fallback
 
         let metadata_str: Option<String> = row.get("metadata")?;
         let metadata = if let Some(meta_str) = metadata_str {
@@ -104,15 +239,7 @@ impl AnalyticsRepository for SqliteAnalyticsRepository {
     async fn store_event(&self, event: AnalyticsEvent) -> Result<()> {
         let conn = self.db.lock().unwrap();
         
-        let event_type_str = match event.event_type {
-            AnalyticsEventType::ContextQuery => "ContextQuery",
-            AnalyticsEventType::EntityCreate => "EntityCreate",
-            AnalyticsEventType::EntityUpdate => "EntityUpdate",
-            AnalyticsEventType::EntityDelete => "EntityDelete",
-            AnalyticsEventType::BulkOperation => "BulkOperation",
-            AnalyticsEventType::ArchitectureValidation => "ArchitectureValidation",
-            AnalyticsEventType::CacheOperation => "CacheOperation",
-        };
+        let event_type_str = event.event_type.as_str();
 
         let metadata_json = serde_json::to_string(&event.metadata)?;
         let timestamp_str = event.timestamp.to_rfc3339();
@@ -232,20 +359,20 @@ impl AnalyticsRepository for SqliteAnalyticsRepository {
         
         let mut stats = HashMap::new();
         
-        // Total events
-        let total_events: u64 = conn.query_row(
-            "SELECT COUNT(*) FROM analytics_events",
+        // Total events, successful ones and average response time
+        let (total_events, successful_events, avg_response_time): (u64, u64, Option<f64>) = conn.query_row(
+            &format!(
+                "SELECT COALESCE(SUM(event_count), 0), COALESCE(SUM(success_count), 0),
+                        SUM(total_duration_ms) * 1.0 / NULLIF(SUM(timed_count), 0)
+                 FROM ({})",
+                Self::ALL_COUNTS
+            ),
             [],
-            |row| row.get(0),
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
         )?;
         stats.insert("total_events".to_string(), serde_json::Value::Number(total_events.into()));
         
         // Success rate
-        let successful_events: u64 = conn.query_row(
-            "SELECT COUNT(*) FROM analytics_events WHERE success = 1",
-            [],
-            |row| row.get(0),
-        )?;
         let success_rate = if total_events > 0 {
             successful_events as f64 / total_events as f64
         } else {
@@ -254,17 +381,13 @@ impl AnalyticsRepository for SqliteAnalyticsRepository {
         stats.insert("success_rate".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(success_rate).unwrap_or(serde_json::Number::from(0))));
         
         // Average response time
-        let avg_response_time: Option<f64> = conn.query_row(
-            "SELECT AVG(duration_ms) FROM analytics_events WHERE duration_ms IS NOT NULL",
-            [],
-            |row| row.get(0),
-        ).optional()?;
         stats.insert("average_response_time_ms".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(avg_response_time.unwrap_or(0.0)).unwrap_or(serde_json::Number::from(0))));
         
         // Event type distribution
-        let mut stmt = conn.prepare(
-            "SELECT event_type, COUNT(*) as count FROM analytics_events GROUP BY event_type ORDER BY count DESC"
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT event_type, SUM(event_count) as count FROM ({}) GROUP BY event_type ORDER BY count DESC",
+            Self::ALL_COUNTS
+        ))?;
         
         let event_type_rows = stmt.query_map([], |row| {
             Ok((row.get::<_, String>(0)?, row.get::<_, u64>(1)?))
@@ -286,18 +409,14 @@ impl AnalyticsRepository for SqliteAnalyticsRepository {
         let start_str = start_date.to_rfc3339();
         let end_str = end_date.to_rfc3339();
         
-        // Events in time range
-        let events_in_range: u64 = conn.query_row(
-            "SELECT COUNT(*) FROM analytics_events WHERE timestamp >= ?1 AND timestamp <= ?2",
+        // Events in time range and successful ones
+        let (events_in_range, successful_events_in_range): (u64, u64) = conn.query_row(
+            &format!(
+                "SELECT COALESCE(SUM(event_count), 0), COALESCE(SUM(success_count), 0) FROM ({})",
+                Self::counts_in_range()
+            ),
             params![start_str, end_str],
-            |row| row.get(0),
-        )?;
-        
-        // Success rate in time range
-        let successful_events_in_range: u64 = conn.query_row(
-            "SELECT COUNT(*) FROM analytics_events WHERE timestamp >= ?1 AND timestamp <= ?2 AND success = 1",
-            params![start_str, end_str],
-            |row| row.get(0),
+            |row| Ok((row.get(0)?, row.get(1)?)),
         )?;
         
         let success_rate = if events_in_range > 0 {
@@ -307,11 +426,11 @@ impl AnalyticsRepository for SqliteAnalyticsRepository {
         };
         
         // Most active projects
-        let mut stmt = conn.prepare(
-            "SELECT project_id, COUNT(*) as count FROM analytics_events 
-             WHERE timestamp >= ?1 AND timestamp <= ?2 AND project_id IS NOT NULL
-             GROUP BY project_id ORDER BY count DESC LIMIT 10"
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT project_id, SUM(event_count) as count FROM ({}) WHERE project_id != ''
+             GROUP BY project_id ORDER BY count DESC LIMIT 10",
+            Self::counts_in_range()
+        ))?;
         
         let project_rows = stmt.query_map(params![start_str, end_str], |row| {
             Ok((row.get::<_, String>(0)?, row.get::<_, u64>(1)?))
@@ -339,4 +458,296 @@ impl AnalyticsRepository for SqliteAnalyticsRepository {
             "most_active_projects": most_active_projects
         }))
     }
-}
\ No newline at end of file
+
+    async fn get_search_clicks(&self, since: DateTime<Utc>) -> Result<Vec<SearchClick>> {
+        let conn = self.db.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT entity_id, project_id, timestamp FROM analytics_events
+             WHERE event_type = 'SearchClick' AND entity_id IS NOT NULL AND timestamp >= ?1"
+        )?;
+
+        let click_rows = stmt.query_map(params![since.to_rfc3339()], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?, row.get::<_, String>(2)?))
+        })?;
+
+        let mut clicks = Vec::new();
+        for row in click_rows {
+            let (entity_id, project_id, timestamp_str) = row?;
+            // Skip rows written with another timestamp format rather than failing the search
+            if let Ok(timestamp) = DateTime::parse_from_rfc3339(&timestamp_str) {
+                clicks.push(SearchClick {
+                    entity_id,
+                    project_id,
+                    timestamp: timestamp.with_timezone(&Utc),
+                });
+            }
+        }
+
+        Ok(clicks)
+    }
+
+    async fn prune_events(&self, before: DateTime<Utc>, vacuum: bool) -> Result<AnalyticsPruneReport> {
+        let mut conn = self.db.lock().unwrap();
+        let page_size = Self::pragma(&conn, "page_size")?;
+        let (pages_before, free_before) = (Self::pragma(&conn, "page_count")?, Self::pragma(&conn, "freelist_count")?);
+        let cutoff = before.to_rfc3339();
+
+        let tx = conn.transaction()?;
+        let (_, aggregates_updated) = Self::roll_up(&tx)?;
+        let events_removed = tx.execute("DELETE FROM analytics_events WHERE timestamp < ?1", params![cutoff])?;
+        // Whole days only, so the days reports read from hourly rollups are complete
+        tx.execute(
+            "INSERT INTO analytics_rollup_state (name, value) VALUES ('hourly_from', substr(?1, 1, 10))
+             ON CONFLICT (name) DO UPDATE SET value = MAX(value, excluded.value)",
+            params![cutoff],
+        )?;
+        tx.execute(
+            &format!("DELETE FROM analytics_hourly_aggregates WHERE hour < {}", Self::HOURLY_FROM),
+            [],
+        )?;
+        tx.commit()?;
+
+        let space_reclaimed_bytes = if vacuum {
+            conn.execute("VACUUM", [])?;
+            pages_before.saturating_sub(Self::pragma(&conn, "page_count")?) * page_size
+        } else {
+            Self::pragma(&conn, "freelist_count")?.saturating_sub(free_before) * page_size
+        };
+
+        Ok(AnalyticsPruneReport {
+            cutoff: Some(before),
+            events_removed: events_removed as u64,
+            aggregates_updated,
+            space_reclaimed_bytes,
+            vacuumed: vacuum,
+        })
+    }
+
+    async fn roll_up_events(&self) -> Result<u64> {
+        let mut conn = self.db.lock().unwrap();
+        let tx = conn.transaction()?;
+        let (events, _) = Self::roll_up(&tx)?;
+        tx.commit()?;
+        Ok(events)
+    }
+
+    async fn record_entity_uses(
+        &self,
+        project_id: &str,
+        source: EntityUseSource,
+        entities: &[(String, String)],
+        at: DateTime<Utc>,
+    ) -> Result<()> {
+        let column = match source {
+            EntityUseSource::Query => "queries",
+            EntityUseSource::Search => "searches",
+            EntityUseSource::Read => "reads",
+        };
+        let day = at.format("%Y-%m-%d").to_string();
+        let mut conn = self.db.lock().unwrap();
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(&format!(
+                "INSERT INTO analytics_entity_uses (day, entity_id, entity_type, project_id, {column})
+                 VALUES (?1, ?2, ?3, ?4, 1)
+                 ON CONFLICT (day, entity_id) DO UPDATE SET {column} = {column} + 1"
+            ))?;
+            for (entity_type, entity_id) in entities {
+                stmt.execute(params![day, entity_id, entity_type, project_id])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    async fn get_entity_uses(&self, project_id: &str, since: DateTime<Utc>) -> Result<HashMap<String, EntityUses>> {
+        let conn = self.db.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT entity_id,
+                    COALESCE(SUM(CASE WHEN day >= ?2 THEN queries END), 0),
+                    COALESCE(SUM(CASE WHEN day >= ?2 THEN searches END), 0),
+                    COALESCE(SUM(CASE WHEN day >= ?2 THEN reads END), 0),
+                    MAX(day)
+             FROM analytics_entity_uses WHERE project_id = ?1 GROUP BY entity_id",
+        )?;
+        let rows = stmt.query_map(params![project_id, since.format("%Y-%m-%d").to_string()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                EntityUses {
+                    queries: row.get(1)?,
+                    searches: row.get(2)?,
+                    reads: row.get(3)?,
+                    last_used: row.get(4)?,
+                },
+            ))
+        })?;
+
+        let mut uses = HashMap::new();
+        for row in rows {
+            let (entity_id, entity_uses) = row?;
+            uses.insert(entity_id, entity_uses);
+        }
+        Ok(uses)
+    }
+
+    async fn get_daily_usage(
+        &self,
+        project_id: Option<&str>,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> Result<Vec<DailyUsage>> {
+        let conn = self.db.lock().unwrap();
+        // Daily rollups hold every rolled up event, pruned or not
+        let mut stmt = conn.prepare(
+            "SELECT day, event_type, SUM(event_count), SUM(success_count) FROM (
+                 SELECT day, project_id, event_type, event_count, success_count FROM analytics_daily_aggregates
+                 UNION ALL
+                 SELECT substr(timestamp, 1, 10), COALESCE(project_id, ''), event_type, 1, success
+                 FROM analytics_events WHERE rolled_up = 0
+             )
+             WHERE day >= ?1 AND day <= ?2 AND (?3 IS NULL OR project_id = ?3)
+             GROUP BY day, event_type ORDER BY day, event_type",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                start_date.format("%Y-%m-%d").to_string(),
+                end_date.format("%Y-%m-%d").to_string(),
+                project_id
+            ],
+            |row| {
+                Ok(DailyUsage {
+                    day: row.get(0)?,
+                    event_type: row.get(1)?,
+                    events: row.get(2)?,
+                    successful: row.get(3)?,
+                })
+            },
+        )?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    async fn get_events(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        project_id: Option<&str>,
+        event_types: &[AnalyticsEventType],
+    ) -> Result<Vec<AnalyticsEvent>> {
+        let conn = self.db.lock().unwrap();
+
+        let mut sql = String::from(
+            "SELECT id, event_type, project_id, entity_type, entity_id,
+                    user_agent, metadata, timestamp, duration_ms, success, error_message
+             FROM analytics_events WHERE timestamp >= ? AND timestamp <= ?",
+        );
+        let mut values: Vec<String> = vec![start_date.to_rfc3339(), end_date.to_rfc3339()];
+        if let Some(project_id) = project_id {
+            sql.push_str(" AND project_id = ?");
+            values.push(project_id.to_string());
+        }
+        if !event_types.is_empty() {
+            sql.push_str(&format!(" AND event_type IN ({})", vec!["?"; event_types.len()].join(", ")));
+            values.extend(event_types.iter().map(|event_type| event_type.as_str().to_string()));
+        }
+        sql.push_str(" ORDER BY timestamp");
+
+        let mut stmt = conn.prepare(&sql)?;
+        let event_rows = stmt.query_map(rusqlite::params_from_iter(values.iter()), Self::row_to_analytics_event)?;
+
+        let mut events = Vec::new();
+        for event_result in event_rows {
+            events.push(event_result?);
+        }
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(timestamp).unwrap().with_timezone(&Utc)
+    }
+
+    fn event(id: &str, project_id: Option<&str>, timestamp: &str, success: bool) -> AnalyticsEvent {
+        AnalyticsEvent {
+            id: id.to_string(),
+            event_type: AnalyticsEventType::EntityCreate,
+            project_id: project_id.map(str::to_string),
+            entity_type: None,
+            entity_id: None,
+            user_agent: None,
+            metadata: HashMap::new(),
+            timestamp: at(timestamp),
+            duration_ms: Some(10),
+            success,
+            error_message: None,
+        }
+    }
+
+    async fn summary(repository: &SqliteAnalyticsRepository, start: &str, end: &str) -> (u64, u64) {
+        let report = repository.generate_usage_report(at(start), at(end)).await.unwrap();
+        (
+            report["summary"]["total_events"].as_u64().unwrap(),
+            report["summary"]["successful_events"].as_u64().unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_rollups_and_pruning() {
+        let repository = SqliteAnalyticsRepository::new(Arc::new(Mutex::new(Connection::open_in_memory().unwrap())));
+        repository.init_tables().unwrap();
+        for event in [
+            event("old1", Some("p1"), "2024-01-10T08:15:00Z", true),
+            event("old2", Some("p1"), "2024-01-10T08:45:00Z", false),
+            event("old3", None, "2024-01-10T09:05:00Z", true),
+            event("new", Some("p1"), "2024-05-01T12:00:00Z", true),
+        ] {
+            repository.store_event(event).await.unwrap();
+        }
+        let everything = ("2024-01-01T00:00:00Z", "2024-12-31T23:59:59Z");
+        assert_eq!(summary(&repository, everything.0, everything.1).await, (4, 3));
+
+        // Rolled-up events are counted once, to the hour of the range
+        assert_eq!(repository.roll_up_events().await.unwrap(), 4);
+        assert_eq!(repository.roll_up_events().await.unwrap(), 0);
+        repository.store_event(event("late", Some("p2"), "2024-01-10T08:30:00Z", true)).await.unwrap();
+        assert_eq!(summary(&repository, everything.0, everything.1).await, (5, 4));
+        assert_eq!(summary(&repository, "2024-01-10T08:20:00Z", "2024-01-10T08:59:00Z").await, (3, 2));
+        let stats = repository.get_global_statistics().await.unwrap();
+        assert_eq!(stats["total_events"], 5);
+        assert_eq!(stats["average_response_time_ms"], 10.0);
+
+        let report = repository.prune_events(at("2024-03-01T00:00:00Z"), false).await.unwrap();
+        assert_eq!(report.events_removed, 4);
+        let remaining = repository.get_events(at(everything.0), at(everything.1), None, &[]).await.unwrap();
+        assert_eq!(remaining.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(), vec!["new"]);
+
+        // Pruned days are counted from the daily rollups, whole days at a time
+        assert_eq!(summary(&repository, everything.0, everything.1).await, (5, 4));
+        assert_eq!(summary(&repository, "2024-01-10T08:20:00Z", "2024-01-10T08:59:00Z").await, (4, 3));
+        repository.store_event(event("later", None, "2024-01-11T10:00:00Z", false)).await.unwrap();
+        repository.roll_up_events().await.unwrap();
+        assert_eq!(summary(&repository, everything.0, everything.1).await, (6, 4));
+        let report = repository.generate_usage_report(at(everything.0), at(everything.1)).await.unwrap();
+        assert_eq!(report["most_active_projects"][0]["project_id"], "p1");
+        assert_eq!(report["most_active_projects"][0]["event_count"], 3);
+
+        let again = repository.prune_events(at("2024-03-01T00:00:00Z"), true).await.unwrap();
+        assert_eq!(again.events_removed, 1);
+        assert!(again.vacuumed);
+        assert_eq!(summary(&repository, everything.0, everything.1).await, (6, 4));
+
+        // Daily usage adds events not rolled up yet to the daily rollups
+        repository.store_event(event("newer", Some("p1"), "2024-05-01T18:00:00Z", false)).await.unwrap();
+        let daily = repository.get_daily_usage(Some("p1"), at(everything.0), at(everything.1)).await.unwrap();
+        let days: Vec<(&str, u64, u64)> = daily.iter().map(|d| (d.day.as_str(), d.events, d.successful)).collect();
+        assert_eq!(days, vec![("2024-01-10", 2, 1), ("2024-05-01", 2, 1)]);
+        assert_eq!(daily[0].event_type, "EntityCreate");
+        let all = repository.get_daily_usage(None, at("2024-01-10T23:00:00Z"), at("2024-01-11T00:00:00Z")).await.unwrap();
+        assert_eq!(all.iter().map(|d| d.events).sum::<u64>(), 5);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::models::api_contract::ApiContract;
+use crate::repositories::ApiContractRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::sync::{Arc, Mutex};
+
+const COLUMNS: &str = "id, project_id, method, path, description, request_schema, response_schema, error_conventions, feature_context_ids, component_ids, created_at, updated_at";
+
+/// SQLite implementation of ApiContractRepository; schemas and links are kept as JSON
+pub struct SqliteApiContractRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+fn json_column<T: serde::de::DeserializeOwned>(row: &Row, index: usize) -> rusqlite::Result<Option<T>> {
+    let Some(text) = row.get::<_, Option<String>>(index)? else {
+        return Ok(None);
+    };
+    serde_json::from_str(&text)
+        .map(Some)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+impl SqliteApiContractRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<ApiContract> {
+        Ok(ApiContract {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            method: row.get(2)?,
+            path: row.get(3)?,
+            description: row.get(4)?,
+            request_schema: json_column(row, 5)?,
+            response_schema: json_column(row, 6)?,
+            error_conventions: row.get(7)?,
+            feature_context_ids: json_column(row, 8)?.unwrap_or_default(),
+            component_ids: json_column(row, 9)?.unwrap_or_default(),
+            created_at: row.get(10)?,
+            updated_at: row.get(11)?,
+        })
+    }
+
+    fn ids_json(ids: &[String]) -> String {
+        serde_json::to_string(ids).unwrap_or_else(|_| "[]".to_string())
+    }
+}
+
+#[async_trait]
+impl ApiContractRepository for SqliteApiContractRepository {
+    async fn create(&self, contract: &ApiContract) -> Result<ApiContract, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        db.execute(
+            &format!("INSERT INTO api_contracts ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)"),
+            params![
+                contract.id,
+                contract.project_id,
+                contract.method,
+                contract.path,
+                contract.description,
+                contract.request_schema.as_ref().map(|schema| schema.to_string()),
+                contract.response_schema.as_ref().map(|schema| schema.to_string()),
+                contract.error_conventions,
+                Self::ids_json(&contract.feature_context_ids),
+                Self::ids_json(&contract.component_ids),
+                contract.created_at,
+                contract.updated_at
+            ],
+        )
+        .map_err(|e| McpError::internal_error(format!("Failed to create API contract: {}", e), None))?;
+
+        Ok(contract.clone())
+    }
+
+    async fn get_by_id(&self, id: &str) -> Result<Option<ApiContract>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        db.query_row(
+            &format!("SELECT {COLUMNS} FROM api_contracts WHERE id = ?1"),
+            params![id],
+            Self::from_row,
+        )
+        .optional()
+        .map_err(|e| McpError::internal_error(format!("Failed to get API contract: {}", e), None))
+    }
+
+    async fn update(&self, contract: &ApiContract) -> Result<ApiContract, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let rows_affected = db
+            .execute(
+                "UPDATE api_contracts SET method = ?2, path = ?3, description = ?4, request_schema = ?5,
+                 response_schema = ?6, error_conventions = ?7, feature_context_ids = ?8, component_ids = ?9,
+                 updated_at = ?10 WHERE id = ?1",
+                params![
+                    contract.id,
+                    contract.method,
+                    contract.path,
+                    contract.description,
+                    contract.request_schema.as_ref().map(|schema| schema.to_string()),
+                    contract.response_schema.as_ref().map(|schema| schema.to_string()),
+                    contract.error_conventions,
+                    Self::ids_json(&contract.feature_context_ids),
+                    Self::ids_json(&contract.component_ids),
+                    contract.updated_at
+                ],
+            )
+            .map_err(|e| McpError::internal_error(format!("Failed to update API contract: {}", e), None))?;
+        if rows_affected == 0 {
+            return Err(McpError::resource_not_found(format!("API contract not found: {}", contract.id), None));
+        }
+
+        Ok(contract.clone())
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let rows_affected = db
+            .execute("DELETE FROM api_contracts WHERE id = ?1", params![id])
+            .map_err(|e| McpError::internal_error(format!("Failed to delete API contract: {}", e), None))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn list_by_project(&self, project_id: &str) -> Result<Vec<ApiContract>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let mut stmt = db
+            .prepare(&format!("SELECT {COLUMNS} FROM api_contracts WHERE project_id = ?1 ORDER BY path, method"))
+            .map_err(|e| McpError::internal_error(format!("Failed to prepare statement: {}", e), None))?;
+        let contracts = stmt
+            .query_map(params![project_id], Self::from_row)
+            .map_err(|e| McpError::internal_error(format!("Failed to query API contracts: {}", e), None))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| McpError::internal_error(format!("Failed to process API contract row: {}", e), None))?;
+
+        Ok(contracts)
+    }
+}
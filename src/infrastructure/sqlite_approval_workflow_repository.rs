@@ -0,0 +1,102 @@
+use crate::models::approval::{Approval, ApprovalWorkflow, WorkflowEntity, WorkflowState, WorkflowTransition};
+use crate::repositories::ApprovalWorkflowRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+const COLUMNS: &str = "entity_type, entity_id, project_id, state, workflow, updated_at";
+
+/// The parts of a workflow stored as JSON in the `workflow` column
+#[derive(Serialize, Deserialize)]
+struct StoredWorkflow {
+    required_approvers: Vec<String>,
+    approvals: Vec<Approval>,
+    history: Vec<WorkflowTransition>,
+}
+
+/// SQLite implementation of ApprovalWorkflowRepository
+pub struct SqliteApprovalWorkflowRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteApprovalWorkflowRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<ApprovalWorkflow> {
+        let invalid = |column: usize, e: Box<dyn std::error::Error + Send + Sync>| {
+            rusqlite::Error::FromSqlConversionFailure(column, rusqlite::types::Type::Text, e)
+        };
+        let entity_type: String = row.get(0)?;
+        let state: String = row.get(3)?;
+        let workflow: String = row.get(4)?;
+        let stored: StoredWorkflow = serde_json::from_str(&workflow).map_err(|e| invalid(4, Box::new(e)))?;
+        Ok(ApprovalWorkflow {
+            entity_type: WorkflowEntity::parse(&entity_type)
+                .ok_or_else(|| invalid(0, format!("unknown entity type {entity_type}").into()))?,
+            entity_id: row.get(1)?,
+            project_id: row.get(2)?,
+            state: WorkflowState::parse(&state).ok_or_else(|| invalid(3, format!("unknown state {state}").into()))?,
+            required_approvers: stored.required_approvers,
+            approvals: stored.approvals,
+            history: stored.history,
+            updated_at: row.get(5)?,
+        })
+    }
+}
+
+#[async_trait]
+impl ApprovalWorkflowRepository for SqliteApprovalWorkflowRepository {
+    async fn find(&self, entity_type: WorkflowEntity, entity_id: &str) -> Result<Option<ApprovalWorkflow>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.query_row(
+            &format!("SELECT {COLUMNS} FROM approval_workflows WHERE entity_type = ? AND entity_id = ?"),
+            [entity_type.as_str(), entity_id],
+            Self::from_row,
+        )
+        .optional()
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))
+    }
+
+    async fn save(&self, workflow: &ApprovalWorkflow) -> Result<ApprovalWorkflow, McpError> {
+        let json = serde_json::to_string(&StoredWorkflow {
+            required_approvers: workflow.required_approvers.clone(),
+            approvals: workflow.approvals.clone(),
+            history: workflow.history.clone(),
+        })
+        .map_err(|e| McpError::internal_error(format!("Serialization error: {}", e), None))?;
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            &format!("INSERT OR REPLACE INTO approval_workflows ({COLUMNS}) VALUES (?, ?, ?, ?, ?, ?)"),
+            (
+                workflow.entity_type.as_str(),
+                &workflow.entity_id,
+                &workflow.project_id,
+                workflow.state.as_str(),
+                json,
+                workflow.updated_at.as_deref(),
+            ),
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(workflow.clone())
+    }
+
+    async fn delete(&self, entity_type: WorkflowEntity, entity_id: &str) -> Result<bool, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let rows_affected = db
+            .execute(
+                "DELETE FROM approval_workflows WHERE entity_type = ? AND entity_id = ?",
+                [entity_type.as_str(), entity_id],
+            )
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(rows_affected > 0)
+    }
+}
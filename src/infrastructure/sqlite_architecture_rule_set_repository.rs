@@ -0,0 +1,62 @@
+use crate::models::architecture::ArchitectureRuleSet;
+use crate::repositories::ArchitectureRuleSetRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{Connection, OptionalExtension};
+use std::sync::{Arc, Mutex};
+
+/// SQLite implementation of ArchitectureRuleSetRepository
+pub struct SqliteArchitectureRuleSetRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteArchitectureRuleSetRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl ArchitectureRuleSetRepository for SqliteArchitectureRuleSetRepository {
+    async fn find_by_project_id(&self, project_id: &str) -> Result<Option<ArchitectureRuleSet>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let rules: Option<String> = db
+            .query_row(
+                "SELECT rules FROM architecture_rule_sets WHERE project_id = ?",
+                [project_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        rules
+            .map(|rules| serde_json::from_str(&rules))
+            .transpose()
+            .map_err(|e| McpError::internal_error(format!("Invalid stored architecture rules: {}", e), None))
+    }
+
+    async fn save(&self, rule_set: &ArchitectureRuleSet) -> Result<ArchitectureRuleSet, McpError> {
+        let rules = serde_json::to_string(rule_set)
+            .map_err(|e| McpError::internal_error(format!("Serialization error: {}", e), None))?;
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            "INSERT OR REPLACE INTO architecture_rule_sets (project_id, rules, updated_at) VALUES (?, ?, ?)",
+            (&rule_set.project_id, rules, rule_set.updated_at.as_deref()),
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(rule_set.clone())
+    }
+
+    async fn delete(&self, project_id: &str) -> Result<bool, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let rows_affected = db
+            .execute("DELETE FROM architecture_rule_sets WHERE project_id = ?", [project_id])
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(rows_affected > 0)
+    }
+}
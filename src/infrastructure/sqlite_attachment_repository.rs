@@ -0,0 +1,157 @@
+use crate::models::attachment::Attachment;
+use crate::repositories::{AttachmentRepository, BlobStore};
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::sync::{Arc, Mutex};
+
+const COLUMNS: &str =
+    "id, project_id, entity_type, entity_id, file_name, media_type, size_bytes, content_hash, description, created_at, created_by";
+
+/// SQLite implementation of AttachmentRepository
+pub struct SqliteAttachmentRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteAttachmentRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Attachment> {
+        Ok(Attachment {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            entity_type: row.get(2)?,
+            entity_id: row.get(3)?,
+            file_name: row.get(4)?,
+            media_type: row.get(5)?,
+            size_bytes: row.get(6)?,
+            content_hash: row.get(7)?,
+            description: row.get(8)?,
+            created_at: row.get(9)?,
+            created_by: row.get(10)?,
+        })
+    }
+}
+
+#[async_trait]
+impl AttachmentRepository for SqliteAttachmentRepository {
+    async fn save(&self, attachment: &Attachment) -> Result<(), McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            &format!("INSERT OR REPLACE INTO attachments ({COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"),
+            params![
+                attachment.id,
+                attachment.project_id,
+                attachment.entity_type,
+                attachment.entity_id,
+                attachment.file_name,
+                attachment.media_type,
+                attachment.size_bytes,
+                attachment.content_hash,
+                attachment.description,
+                attachment.created_at,
+                attachment.created_by,
+            ],
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(())
+    }
+
+    async fn find(&self, id: &str) -> Result<Option<Attachment>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.query_row(&format!("SELECT {COLUMNS} FROM attachments WHERE id = ?"), [id], Self::from_row)
+            .optional()
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))
+    }
+
+    async fn find_by_entity(&self, entity_id: &str) -> Result<Vec<Attachment>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let mut stmt = db
+            .prepare(&format!("SELECT {COLUMNS} FROM attachments WHERE entity_id = ? ORDER BY created_at"))
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+        let attachments = stmt
+            .query_map([entity_id], Self::from_row)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(attachments)
+    }
+
+    async fn delete_by_entity(&self, entity_id: &str) -> Result<(usize, Vec<String>), McpError> {
+        let mut db = self.db.lock().unwrap();
+        let db_error = |e: rusqlite::Error| McpError::internal_error(format!("Database error: {}", e), None);
+
+        let tx = db.transaction().map_err(db_error)?;
+        let hashes = tx
+            .prepare("SELECT DISTINCT content_hash FROM attachments WHERE entity_id = ?")
+            .and_then(|mut stmt| stmt.query_map([entity_id], |row| row.get(0))?.collect::<Result<Vec<String>, _>>())
+            .map_err(db_error)?;
+        let removed = tx.execute("DELETE FROM attachments WHERE entity_id = ?", [entity_id]).map_err(db_error)?;
+        let mut orphaned = Vec::new();
+        for hash in hashes {
+            let remaining: usize = tx
+                .query_row("SELECT COUNT(*) FROM attachments WHERE content_hash = ?", [&hash], |row| row.get(0))
+                .map_err(db_error)?;
+            if remaining == 0 {
+                orphaned.push(hash);
+            }
+        }
+        tx.commit().map_err(db_error)?;
+
+        Ok((removed, orphaned))
+    }
+}
+
+/// BlobStore keeping contents in the `attachment_blobs` table
+pub struct SqliteBlobStore {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteBlobStore {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl BlobStore for SqliteBlobStore {
+    async fn put(&self, content_hash: &str, content: &[u8]) -> Result<(), McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            "INSERT OR IGNORE INTO attachment_blobs (content_hash, content) VALUES (?, ?)",
+            params![content_hash, content],
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(())
+    }
+
+    async fn get(&self, content_hash: &str) -> Result<Option<Vec<u8>>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.query_row(
+            "SELECT content FROM attachment_blobs WHERE content_hash = ?",
+            [content_hash],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))
+    }
+
+    async fn delete(&self, content_hash: &str) -> Result<(), McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.execute("DELETE FROM attachment_blobs WHERE content_hash = ?", [content_hash])
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(())
+    }
+}
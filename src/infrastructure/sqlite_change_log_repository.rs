@@ -0,0 +1,133 @@
+use crate::repositories::ChangeLogRepository;
+use crate::services::websocket_types::ContextChange;
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{Connection, OptionalExtension, Row};
+use std::sync::{Arc, Mutex};
+
+/// SQLite implementation of ChangeLogRepository
+pub struct SqliteChangeLogRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteChangeLogRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    /// Fixed-width timestamps, so `recorded_at` compares correctly as text
+    fn timestamp(time: &DateTime<Utc>) -> String {
+        time.to_rfc3339_opts(SecondsFormat::Micros, true)
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<(i64, String)> {
+        Ok((row.get(0)?, row.get(1)?))
+    }
+}
+
+#[async_trait]
+impl ChangeLogRepository for SqliteChangeLogRepository {
+    async fn append(&self, change: &ContextChange) -> Result<u64, McpError> {
+        let json = serde_json::to_string(change)
+            .map_err(|e| McpError::internal_error(format!("Serialization error: {}", e), None))?;
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            "INSERT INTO change_log (change_id, project_id, change, recorded_at) VALUES (?, ?, ?, ?)",
+            (
+                change.change_id.to_string(),
+                &change.project_id,
+                json,
+                Self::timestamp(&change.metadata.timestamp),
+            ),
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(db.last_insert_rowid() as u64)
+    }
+
+    async fn find_since(&self, sequence: u64, limit: usize) -> Result<Vec<ContextChange>, McpError> {
+        let db = self.db.lock().unwrap();
+        let mut changes = Vec::new();
+
+        let mut stmt = db
+            .prepare("SELECT sequence, change FROM change_log WHERE sequence > ? ORDER BY sequence LIMIT ?")
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let rows = stmt
+            .query_map((sequence as i64, limit as i64), Self::from_row)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        for row in rows {
+            let (sequence, json) = row.map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+            match serde_json::from_str::<ContextChange>(&json) {
+                Ok(mut change) => {
+                    change.metadata.sequence = Some(sequence as u64);
+                    changes.push(change);
+                }
+                Err(e) => tracing::warn!("Skipping unreadable change log entry {}: {}", sequence, e),
+            }
+        }
+
+        Ok(changes)
+    }
+
+    async fn find_latest_before(
+        &self,
+        entity_type: &str,
+        entity_id: &str,
+        before: DateTime<Utc>,
+    ) -> Result<Option<ContextChange>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let row = db
+            .query_row(
+                "SELECT sequence, change FROM change_log
+                 WHERE json_extract(change, '$.entity_type') = ? AND json_extract(change, '$.entity_id') = ? AND recorded_at < ?
+                 ORDER BY sequence DESC LIMIT 1",
+                (entity_type, entity_id, Self::timestamp(&before)),
+                Self::from_row,
+            )
+            .optional()
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let Some((sequence, json)) = row else {
+            return Ok(None);
+        };
+        let mut change = serde_json::from_str::<ContextChange>(&json)
+            .map_err(|e| McpError::internal_error(format!("Deserialization error: {}", e), None))?;
+        change.metadata.sequence = Some(sequence as u64);
+        Ok(Some(change))
+    }
+
+    async fn find_changed_entities(
+        &self,
+        project_id: &str,
+        entity_type: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<String>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let mut stmt = db
+            .prepare(
+                "SELECT DISTINCT json_extract(change, '$.entity_id') FROM change_log
+                 WHERE project_id = ? AND json_extract(change, '$.entity_type') = ? AND recorded_at >= ?",
+            )
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let ids = stmt
+            .query_map((project_id, entity_type, Self::timestamp(&since)), |row| row.get(0))
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?
+            .collect::<rusqlite::Result<Vec<String>>>()
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+        Ok(ids)
+    }
+
+    async fn delete_before(&self, cutoff: DateTime<Utc>) -> Result<usize, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.execute("DELETE FROM change_log WHERE recorded_at < ?", [Self::timestamp(&cutoff)])
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))
+    }
+}
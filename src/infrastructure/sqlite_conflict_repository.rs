@@ -0,0 +1,133 @@
+use crate::repositories::ConflictRepository;
+use crate::services::conflict_resolution_engine::ConflictInfo;
+use crate::services::conflict_resolution_ui::ConflictResolutionSession;
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+
+/// SQLite implementation of ConflictRepository; conflicts and sessions are stored as JSON
+pub struct SqliteConflictRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteConflictRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    /// Fixed-width timestamps, so they compare correctly as text
+    fn timestamp(time: &DateTime<Utc>) -> String {
+        time.to_rfc3339_opts(SecondsFormat::Micros, true)
+    }
+
+    fn to_json<T: Serialize>(value: &T) -> Result<String, McpError> {
+        serde_json::to_string(value)
+            .map_err(|e| McpError::internal_error(format!("Serialization error: {}", e), None))
+    }
+
+    fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, McpError> {
+        serde_json::from_str(json)
+            .map_err(|e| McpError::internal_error(format!("Deserialization error: {}", e), None))
+    }
+}
+
+#[async_trait]
+impl ConflictRepository for SqliteConflictRepository {
+    async fn save_conflict(&self, conflict: &ConflictInfo) -> Result<(), McpError> {
+        let json = Self::to_json(conflict)?;
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            "INSERT OR REPLACE INTO conflicts (conflict_id, project_id, conflict, detected_at, resolved_at) VALUES (?, ?, ?, ?, ?)",
+            (
+                &conflict.conflict_id,
+                &conflict.project_id,
+                json,
+                Self::timestamp(&conflict.detected_at),
+                conflict.resolved_at.as_ref().map(Self::timestamp),
+            ),
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(())
+    }
+
+    async fn find_conflict(&self, conflict_id: &str) -> Result<Option<ConflictInfo>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let json: Option<String> = db
+            .query_row("SELECT conflict FROM conflicts WHERE conflict_id = ?", [conflict_id], |row| row.get(0))
+            .optional()
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        json.as_deref().map(Self::from_json).transpose()
+    }
+
+    async fn find_conflicts(&self, project_id: &str, resolved: Option<bool>) -> Result<Vec<ConflictInfo>, McpError> {
+        let db = self.db.lock().unwrap();
+        let query = match resolved {
+            None => "SELECT conflict FROM conflicts WHERE project_id = ? ORDER BY detected_at DESC",
+            Some(true) => "SELECT conflict FROM conflicts WHERE project_id = ? AND resolved_at IS NOT NULL ORDER BY detected_at DESC",
+            Some(false) => "SELECT conflict FROM conflicts WHERE project_id = ? AND resolved_at IS NULL ORDER BY detected_at DESC",
+        };
+
+        let mut stmt = db
+            .prepare(query)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let rows = stmt
+            .query_map([project_id], |row| row.get::<_, String>(0))
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let mut conflicts = Vec::new();
+        for row in rows {
+            let json = row.map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+            conflicts.push(Self::from_json(&json)?);
+        }
+
+        Ok(conflicts)
+    }
+
+    async fn save_session(&self, session: &ConflictResolutionSession) -> Result<(), McpError> {
+        let json = Self::to_json(session)?;
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            "INSERT OR REPLACE INTO conflict_sessions (session_id, conflict_id, session, timeout_at) VALUES (?, ?, ?, ?)",
+            (
+                &session.session_id,
+                &session.conflict_id,
+                json,
+                session.timeout_at.as_ref().map(Self::timestamp),
+            ),
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(())
+    }
+
+    async fn find_session(&self, session_id: &str) -> Result<Option<ConflictResolutionSession>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let json: Option<String> = db
+            .query_row("SELECT session FROM conflict_sessions WHERE session_id = ?", [session_id], |row| row.get(0))
+            .optional()
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        json.as_deref().map(Self::from_json).transpose()
+    }
+
+    async fn delete_expired_sessions(&self, now: DateTime<Utc>) -> Result<usize, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            "DELETE FROM conflict_sessions WHERE timeout_at IS NOT NULL AND timeout_at <= ?",
+            [Self::timestamp(&now)],
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))
+    }
+}
@@ -370,7 +370,7 @@ impl ConstraintRepository for SqliteConstraintRepository {
 }
 
 // Dependency Repository
-pub trait DependencyRepository: Send {
+pub trait DependencyRepository: Send + Sync {
     fn create_dependency(&self, dependency: &ComponentDependency) -> anyhow::Result<()>;
     fn get_dependency(&self, id: &str) -> anyhow::Result<Option<ComponentDependency>>;
     fn list_dependencies(&self, project_id: &str) -> anyhow::Result<Vec<ComponentDependency>>;
@@ -0,0 +1,130 @@
+use crate::models::context_digest::ContextDigest;
+use crate::repositories::ContextDigestRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{Connection, OptionalExtension, Row};
+use std::sync::{Arc, Mutex};
+
+const COLUMNS: &str = "id, project_id, feature_area, summary, generated_by, entity_ids, source_hash, stale, generated_at";
+
+/// SQLite implementation of ContextDigestRepository
+pub struct SqliteContextDigestRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteContextDigestRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<ContextDigest> {
+        let entity_ids: String = row.get(5)?;
+        Ok(ContextDigest {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            feature_area: row.get(2)?,
+            summary: row.get(3)?,
+            generated_by: row.get(4)?,
+            entity_ids: serde_json::from_str(&entity_ids).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(5, rusqlite::types::Type::Text, Box::new(e))
+            })?,
+            source_hash: row.get(6)?,
+            stale: row.get(7)?,
+            generated_at: row.get(8)?,
+        })
+    }
+
+    fn collect(rows: impl Iterator<Item = rusqlite::Result<ContextDigest>>) -> Vec<ContextDigest> {
+        let mut digests = Vec::new();
+        for digest in rows {
+            match digest {
+                Ok(digest) => digests.push(digest),
+                Err(e) => tracing::warn!("Failed to parse context digest: {}", e),
+            }
+        }
+        digests
+    }
+
+    fn db_error(e: rusqlite::Error) -> McpError {
+        McpError::internal_error(format!("Database error: {}", e), None)
+    }
+}
+
+#[async_trait]
+impl ContextDigestRepository for SqliteContextDigestRepository {
+    async fn save(&self, digest: &ContextDigest) -> Result<(), McpError> {
+        let db = self.db.lock().unwrap();
+        let entity_ids = serde_json::to_string(&digest.entity_ids)
+            .map_err(|e| McpError::internal_error(format!("Serialization error: {}", e), None))?;
+
+        db.execute(
+            &format!(
+                "INSERT INTO context_digests ({COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                 ON CONFLICT (project_id, feature_area) DO UPDATE SET
+                     summary = excluded.summary, generated_by = excluded.generated_by,
+                     entity_ids = excluded.entity_ids, source_hash = excluded.source_hash,
+                     stale = excluded.stale, generated_at = excluded.generated_at"
+            ),
+            rusqlite::params![
+                digest.id,
+                digest.project_id,
+                digest.feature_area,
+                digest.summary,
+                digest.generated_by,
+                entity_ids,
+                digest.source_hash,
+                digest.stale,
+                digest.generated_at,
+            ],
+        )
+        .map_err(Self::db_error)?;
+
+        Ok(())
+    }
+
+    async fn find(&self, project_id: &str, feature_area: &str) -> Result<Option<ContextDigest>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.query_row(
+            &format!("SELECT {COLUMNS} FROM context_digests WHERE project_id = ? AND feature_area = ?"),
+            [project_id, feature_area],
+            Self::from_row,
+        )
+        .optional()
+        .map_err(Self::db_error)
+    }
+
+    async fn list_by_project(&self, project_id: &str) -> Result<Vec<ContextDigest>, McpError> {
+        let db = self.db.lock().unwrap();
+        let mut stmt = db
+            .prepare(&format!(
+                "SELECT {COLUMNS} FROM context_digests WHERE project_id = ? ORDER BY feature_area"
+            ))
+            .map_err(Self::db_error)?;
+
+        let rows = stmt.query_map([project_id], Self::from_row).map_err(Self::db_error)?;
+        Ok(Self::collect(rows))
+    }
+
+    async fn list_containing(&self, entity_id: &str) -> Result<Vec<ContextDigest>, McpError> {
+        let db = self.db.lock().unwrap();
+        let mut stmt = db
+            .prepare(&format!(
+                "SELECT {COLUMNS} FROM context_digests
+                 WHERE EXISTS (SELECT 1 FROM json_each(context_digests.entity_ids) WHERE json_each.value = ?)
+                 ORDER BY project_id, feature_area"
+            ))
+            .map_err(Self::db_error)?;
+
+        let rows = stmt.query_map([entity_id], Self::from_row).map_err(Self::db_error)?;
+        Ok(Self::collect(rows))
+    }
+
+    async fn set_stale(&self, id: &str, stale: bool) -> Result<(), McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.execute("UPDATE context_digests SET stale = ? WHERE id = ?", rusqlite::params![stale, id])
+            .map_err(Self::db_error)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+use crate::models::custom_entity::{CustomEntity, CustomEntityType};
+use crate::repositories::CustomEntityRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::sync::{Arc, Mutex};
+
+const TYPE_COLUMNS: &str = "name, description, schema, searchable_fields, created_at, updated_at";
+const COLUMNS: &str = "id, project_id, custom_type, fields, created_at, updated_at";
+
+/// SQLite implementation of CustomEntityRepository; entity fields are kept as a JSON document
+pub struct SqliteCustomEntityRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+fn json_column<T: serde::de::DeserializeOwned>(row: &Row, index: usize) -> rusqlite::Result<T> {
+    let text: String = row.get(index)?;
+    serde_json::from_str(&text)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+impl SqliteCustomEntityRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn type_from_row(row: &Row) -> rusqlite::Result<CustomEntityType> {
+        Ok(CustomEntityType {
+            name: row.get(0)?,
+            description: row.get(1)?,
+            schema: json_column(row, 2)?,
+            searchable_fields: json_column(row, 3)?,
+            created_at: row.get(4)?,
+            updated_at: row.get(5)?,
+        })
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<CustomEntity> {
+        Ok(CustomEntity {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            custom_type: row.get(2)?,
+            fields: json_column(row, 3)?,
+            search_text: Vec::new(),
+            created_at: row.get(4)?,
+            updated_at: row.get(5)?,
+        })
+    }
+}
+
+#[async_trait]
+impl CustomEntityRepository for SqliteCustomEntityRepository {
+    async fn save_type(&self, entity_type: &CustomEntityType) -> Result<(), McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        db.execute(
+            &format!(
+                "INSERT INTO custom_entity_types ({TYPE_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                 ON CONFLICT(name) DO UPDATE SET description = ?2, schema = ?3, searchable_fields = ?4, updated_at = ?6"
+            ),
+            params![
+                entity_type.name,
+                entity_type.description,
+                entity_type.schema.to_string(),
+                serde_json::to_string(&entity_type.searchable_fields).unwrap_or_else(|_| "[]".to_string()),
+                entity_type.created_at,
+                entity_type.updated_at
+            ],
+        )
+        .map_err(|e| McpError::internal_error(format!("Failed to save custom entity type: {}", e), None))?;
+
+        Ok(())
+    }
+
+    async fn get_type(&self, name: &str) -> Result<Option<CustomEntityType>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        db.query_row(
+            &format!("SELECT {TYPE_COLUMNS} FROM custom_entity_types WHERE name = ?1"),
+            params![name],
+            Self::type_from_row,
+        )
+        .optional()
+        .map_err(|e| McpError::internal_error(format!("Failed to get custom entity type: {}", e), None))
+    }
+
+    async fn list_types(&self) -> Result<Vec<CustomEntityType>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let mut stmt = db
+            .prepare(&format!("SELECT {TYPE_COLUMNS} FROM custom_entity_types ORDER BY name"))
+            .map_err(|e| McpError::internal_error(format!("Failed to prepare statement: {}", e), None))?;
+        let types = stmt
+            .query_map([], Self::type_from_row)
+            .map_err(|e| McpError::internal_error(format!("Failed to query custom entity types: {}", e), None))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| McpError::internal_error(format!("Failed to process custom entity type row: {}", e), None))?;
+
+        Ok(types)
+    }
+
+    async fn delete_type(&self, name: &str) -> Result<bool, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let rows_affected = db
+            .execute("DELETE FROM custom_entity_types WHERE name = ?1", params![name])
+            .map_err(|e| McpError::internal_error(format!("Failed to delete custom entity type: {}", e), None))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn count_of_type(&self, name: &str) -> Result<usize, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        db.query_row(
+            "SELECT COUNT(*) FROM custom_entities WHERE custom_type = ?1",
+            params![name],
+            |row| row.get(0),
+        )
+        .map_err(|e| McpError::internal_error(format!("Failed to count custom entities: {}", e), None))
+    }
+
+    async fn create(&self, entity: &CustomEntity) -> Result<CustomEntity, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        db.execute(
+            &format!("INSERT INTO custom_entities ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6)"),
+            params![
+                entity.id,
+                entity.project_id,
+                entity.custom_type,
+                entity.fields.to_string(),
+                entity.created_at,
+                entity.updated_at
+            ],
+        )
+        .map_err(|e| McpError::internal_error(format!("Failed to create custom entity: {}", e), None))?;
+
+        Ok(entity.clone())
+    }
+
+    async fn get_by_id(&self, id: &str) -> Result<Option<CustomEntity>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        db.query_row(
+            &format!("SELECT {COLUMNS} FROM custom_entities WHERE id = ?1"),
+            params![id],
+            Self::from_row,
+        )
+        .optional()
+        .map_err(|e| McpError::internal_error(format!("Failed to get custom entity: {}", e), None))
+    }
+
+    async fn update(&self, entity: &CustomEntity) -> Result<CustomEntity, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let rows_affected = db
+            .execute(
+                "UPDATE custom_entities SET project_id = ?2, custom_type = ?3, fields = ?4, updated_at = ?5 WHERE id = ?1",
+                params![
+                    entity.id,
+                    entity.project_id,
+                    entity.custom_type,
+                    entity.fields.to_string(),
+                    entity.updated_at
+                ],
+            )
+            .map_err(|e| McpError::internal_error(format!("Failed to update custom entity: {}", e), None))?;
+        if rows_affected == 0 {
+            return Err(McpError::resource_not_found(format!("Custom entity not found: {}", entity.id), None));
+        }
+
+        Ok(entity.clone())
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let rows_affected = db
+            .execute("DELETE FROM custom_entities WHERE id = ?1", params![id])
+            .map_err(|e| McpError::internal_error(format!("Failed to delete custom entity: {}", e), None))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn list_by_project(&self, project_id: &str, custom_type: Option<&str>) -> Result<Vec<CustomEntity>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let mut stmt = db
+            .prepare(&format!(
+                "SELECT {COLUMNS} FROM custom_entities
+                 WHERE project_id = ?1 AND (?2 IS NULL OR custom_type = ?2)
+                 ORDER BY created_at DESC"
+            ))
+            .map_err(|e| McpError::internal_error(format!("Failed to prepare statement: {}", e), None))?;
+        let entities = stmt
+            .query_map(params![project_id, custom_type], Self::from_row)
+            .map_err(|e| McpError::internal_error(format!("Failed to query custom entities: {}", e), None))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| McpError::internal_error(format!("Failed to process custom entity row: {}", e), None))?;
+
+        Ok(entities)
+    }
+}
@@ -0,0 +1,97 @@
+use crate::models::decision_link::{DecisionLink, DecisionLinkType};
+use crate::repositories::DecisionLinkRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{params, Connection, Row};
+use std::sync::{Arc, Mutex};
+
+const COLUMNS: &str = "project_id, from_decision_id, to_decision_id, link_type, created_at, created_by";
+
+/// SQLite implementation of DecisionLinkRepository
+pub struct SqliteDecisionLinkRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteDecisionLinkRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<DecisionLink> {
+        let link_type: String = row.get(3)?;
+        Ok(DecisionLink {
+            project_id: row.get(0)?,
+            from_decision_id: row.get(1)?,
+            to_decision_id: row.get(2)?,
+            link_type: DecisionLinkType::parse(&link_type).ok_or_else(|| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    3,
+                    rusqlite::types::Type::Text,
+                    format!("Unknown decision link type: {link_type}").into(),
+                )
+            })?,
+            created_at: row.get(4)?,
+            created_by: row.get(5)?,
+        })
+    }
+}
+
+#[async_trait]
+impl DecisionLinkRepository for SqliteDecisionLinkRepository {
+    async fn save(&self, link: &DecisionLink) -> Result<(), McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        db.execute(
+            &format!("INSERT OR REPLACE INTO decision_links ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6)"),
+            params![
+                link.project_id,
+                link.from_decision_id,
+                link.to_decision_id,
+                link.link_type.as_str(),
+                link.created_at,
+                link.created_by
+            ],
+        )
+        .map_err(|e| McpError::internal_error(format!("Failed to save decision link: {}", e), None))?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, from_decision_id: &str, to_decision_id: &str, link_type: DecisionLinkType) -> Result<bool, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let rows_affected = db
+            .execute(
+                "DELETE FROM decision_links WHERE from_decision_id = ?1 AND to_decision_id = ?2 AND link_type = ?3",
+                params![from_decision_id, to_decision_id, link_type.as_str()],
+            )
+            .map_err(|e| McpError::internal_error(format!("Failed to delete decision link: {}", e), None))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn find_by_project(&self, project_id: &str) -> Result<Vec<DecisionLink>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let mut stmt = db
+            .prepare(&format!("SELECT {COLUMNS} FROM decision_links WHERE project_id = ?1 ORDER BY created_at"))
+            .map_err(|e| McpError::internal_error(format!("Failed to prepare statement: {}", e), None))?;
+        let links = stmt
+            .query_map(params![project_id], Self::from_row)
+            .map_err(|e| McpError::internal_error(format!("Failed to query decision links: {}", e), None))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| McpError::internal_error(format!("Failed to process decision link row: {}", e), None))?;
+
+        Ok(links)
+    }
+
+    async fn delete_by_decision(&self, decision_id: &str) -> Result<usize, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        db.execute(
+            "DELETE FROM decision_links WHERE from_decision_id = ?1 OR to_decision_id = ?1",
+            params![decision_id],
+        )
+        .map_err(|e| McpError::internal_error(format!("Failed to delete decision links: {}", e), None))
+    }
+}
@@ -0,0 +1,102 @@
+use crate::models::review::{EntityReview, VerificationStatus};
+use crate::repositories::EntityReviewRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{Connection, OptionalExtension, Row};
+use std::sync::{Arc, Mutex};
+
+const COLUMNS: &str =
+    "id, project_id, entity_type, entity_id, status, requested_by, requested_at, note, reviewer, reviewed_at, comment";
+
+/// SQLite implementation of EntityReviewRepository
+pub struct SqliteEntityReviewRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteEntityReviewRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<EntityReview> {
+        let status: String = row.get(4)?;
+        Ok(EntityReview {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            entity_type: row.get(2)?,
+            entity_id: row.get(3)?,
+            status: VerificationStatus::parse(&status).unwrap_or(VerificationStatus::Unverified),
+            requested_by: row.get(5)?,
+            requested_at: row.get(6)?,
+            note: row.get(7)?,
+            reviewer: row.get(8)?,
+            reviewed_at: row.get(9)?,
+            comment: row.get(10)?,
+        })
+    }
+
+    fn select(&self, filter: &str, value: &str) -> Result<Vec<EntityReview>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let mut stmt = db
+            .prepare(&format!(
+                "SELECT {COLUMNS} FROM entity_reviews WHERE {filter} = ?
+                 ORDER BY COALESCE(reviewed_at, requested_at) DESC"
+            ))
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+        let reviews = stmt
+            .query_map([value], Self::from_row)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(reviews)
+    }
+}
+
+#[async_trait]
+impl EntityReviewRepository for SqliteEntityReviewRepository {
+    async fn save(&self, review: &EntityReview) -> Result<(), McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            &format!("INSERT OR REPLACE INTO entity_reviews ({COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"),
+            rusqlite::params![
+                review.id,
+                review.project_id,
+                review.entity_type,
+                review.entity_id,
+                review.status.as_str(),
+                review.requested_by,
+                review.requested_at,
+                review.note,
+                review.reviewer,
+                review.reviewed_at,
+                review.comment,
+            ],
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(())
+    }
+
+    async fn find(&self, id: &str) -> Result<Option<EntityReview>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.query_row(
+            &format!("SELECT {COLUMNS} FROM entity_reviews WHERE id = ?"),
+            [id],
+            Self::from_row,
+        )
+        .optional()
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))
+    }
+
+    async fn find_by_entity(&self, entity_id: &str) -> Result<Vec<EntityReview>, McpError> {
+        self.select("entity_id", entity_id)
+    }
+
+    async fn find_by_project(&self, project_id: &str) -> Result<Vec<EntityReview>, McpError> {
+        self.select("project_id", project_id)
+    }
+}
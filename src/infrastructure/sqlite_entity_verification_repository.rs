@@ -0,0 +1,79 @@
+use crate::models::freshness::EntityVerification;
+use crate::repositories::EntityVerificationRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{Connection, OptionalExtension, Row};
+use std::sync::{Arc, Mutex};
+
+const COLUMNS: &str = "entity_id, entity_type, project_id, verified_at, verified_by, note";
+
+/// SQLite implementation of EntityVerificationRepository
+pub struct SqliteEntityVerificationRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteEntityVerificationRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<EntityVerification> {
+        Ok(EntityVerification {
+            entity_id: row.get(0)?,
+            entity_type: row.get(1)?,
+            project_id: row.get(2)?,
+            verified_at: row.get(3)?,
+            verified_by: row.get(4)?,
+            note: row.get(5)?,
+        })
+    }
+}
+
+#[async_trait]
+impl EntityVerificationRepository for SqliteEntityVerificationRepository {
+    async fn record(&self, verification: &EntityVerification) -> Result<(), McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            &format!("INSERT OR REPLACE INTO entity_verifications ({COLUMNS}) VALUES (?, ?, ?, ?, ?, ?)"),
+            rusqlite::params![
+                verification.entity_id,
+                verification.entity_type,
+                verification.project_id,
+                verification.verified_at,
+                verification.verified_by,
+                verification.note,
+            ],
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(())
+    }
+
+    async fn find(&self, entity_id: &str) -> Result<Option<EntityVerification>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.query_row(
+            &format!("SELECT {COLUMNS} FROM entity_verifications WHERE entity_id = ?"),
+            [entity_id],
+            Self::from_row,
+        )
+        .optional()
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))
+    }
+
+    async fn find_by_project(&self, project_id: &str) -> Result<Vec<EntityVerification>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let mut stmt = db
+            .prepare(&format!("SELECT {COLUMNS} FROM entity_verifications WHERE project_id = ?"))
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+        let verifications = stmt
+            .query_map([project_id], Self::from_row)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(verifications)
+    }
+}
@@ -0,0 +1,205 @@
+use crate::models::context::FeatureContext;
+use crate::repositories::FeatureContextRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{params, Connection, Row};
+use std::sync::{Arc, Mutex};
+
+const COLUMNS: &str = "id, project_id, feature_name, business_purpose, user_personas, key_workflows, integration_points, edge_cases, constraints, related_components, status, owner, created_at";
+
+/// SQLite implementation of FeatureContextRepository
+pub struct SqliteFeatureContextRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteFeatureContextRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+
+    fn from_row(row: &Row) -> rusqlite::Result<FeatureContext> {
+        Ok(FeatureContext {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            feature_name: row.get(2)?,
+            business_purpose: row.get(3)?,
+            user_personas: row.get(4)?,
+            key_workflows: row.get(5)?,
+            integration_points: row.get(6)?,
+            edge_cases: row.get(7)?,
+            constraints: row.get(8)?,
+            related_components: row.get(9)?,
+            status: row.get(10)?,
+            owner: row.get(11)?,
+            created_at: row.get(12)?,
+        })
+    }
+
+    fn insert(db: &Connection, feature_context: &FeatureContext) -> rusqlite::Result<usize> {
+        db.execute(
+            &format!("INSERT INTO feature_context ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"),
+            params![
+                feature_context.id,
+                feature_context.project_id,
+                feature_context.feature_name,
+                feature_context.business_purpose,
+                feature_context.user_personas,
+                feature_context.key_workflows,
+                feature_context.integration_points,
+                feature_context.edge_cases,
+                feature_context.constraints,
+                feature_context.related_components,
+                feature_context.status,
+                feature_context.owner,
+                feature_context.created_at
+            ],
+        )
+    }
+
+    /// Updates everything but the creation time
+    fn update_row(db: &Connection, feature_context: &FeatureContext) -> rusqlite::Result<usize> {
+        db.execute(
+            "UPDATE feature_context SET project_id = ?2, feature_name = ?3, business_purpose = ?4, user_personas = ?5, key_workflows = ?6, integration_points = ?7, edge_cases = ?8, constraints = ?9, related_components = ?10, status = ?11, owner = ?12 WHERE id = ?1",
+            params![
+                feature_context.id,
+                feature_context.project_id,
+                feature_context.feature_name,
+                feature_context.business_purpose,
+                feature_context.user_personas,
+                feature_context.key_workflows,
+                feature_context.integration_points,
+                feature_context.edge_cases,
+                feature_context.constraints,
+                feature_context.related_components,
+                feature_context.status,
+                feature_context.owner
+            ],
+        )
+    }
+}
+
+#[async_trait]
+impl FeatureContextRepository for SqliteFeatureContextRepository {
+    async fn create(&self, feature_context: &FeatureContext) -> Result<FeatureContext, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        Self::insert(&db, feature_context)
+            .map_err(|e| McpError::internal_error(format!("Failed to create feature context: {}", e), None))?;
+
+        Ok(feature_context.clone())
+    }
+
+    async fn get_by_id(&self, id: &str) -> Result<Option<FeatureContext>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let mut stmt = db.prepare(&format!("SELECT {COLUMNS} FROM feature_context WHERE id = ?1"))
+            .map_err(|e| McpError::internal_error(format!("Failed to prepare statement: {}", e), None))?;
+
+        match stmt.query_row(params![id], Self::from_row) {
+            Ok(feature_context) => Ok(Some(feature_context)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(McpError::internal_error(format!("Failed to get feature context: {}", e), None)),
+        }
+    }
+
+    async fn update(&self, feature_context: &FeatureContext) -> Result<FeatureContext, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let rows_affected = Self::update_row(&db, feature_context)
+            .map_err(|e| McpError::internal_error(format!("Failed to update feature context: {}", e), None))?;
+        if rows_affected == 0 {
+            return Err(McpError::resource_not_found(format!("Feature context not found: {}", feature_context.id), None));
+        }
+
+        Ok(feature_context.clone())
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let rows_affected = db.execute("DELETE FROM feature_context WHERE id = ?1", params![id])
+            .map_err(|e| McpError::internal_error(format!("Failed to delete feature context: {}", e), None))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn list_by_project(&self, project_id: &str) -> Result<Vec<FeatureContext>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let mut stmt = db.prepare(&format!(
+            "SELECT {COLUMNS} FROM feature_context WHERE project_id = ?1 ORDER BY created_at DESC"
+        )).map_err(|e| McpError::internal_error(format!("Failed to prepare statement: {}", e), None))?;
+
+        let feature_iter = stmt.query_map(params![project_id], Self::from_row)
+            .map_err(|e| McpError::internal_error(format!("Failed to query feature contexts: {}", e), None))?;
+
+        let mut features = Vec::new();
+        for feature in feature_iter {
+            features.push(feature.map_err(|e| McpError::internal_error(format!("Failed to process feature context row: {}", e), None))?);
+        }
+
+        Ok(features)
+    }
+
+    async fn get_by_feature_name(&self, project_id: &str, feature_name: &str) -> Result<Option<FeatureContext>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let mut stmt = db.prepare(&format!(
+            "SELECT {COLUMNS} FROM feature_context WHERE project_id = ?1 AND feature_name = ?2"
+        )).map_err(|e| McpError::internal_error(format!("Failed to prepare statement: {}", e), None))?;
+
+        match stmt.query_row(params![project_id, feature_name], Self::from_row) {
+            Ok(feature_context) => Ok(Some(feature_context)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(McpError::internal_error(format!("Failed to get feature context by name: {}", e), None)),
+        }
+    }
+
+    async fn bulk_create(&self, feature_contexts: &[FeatureContext]) -> Result<Vec<FeatureContext>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let tx = db.unchecked_transaction().map_err(|e| McpError::internal_error(format!("Failed to start transaction: {}", e), None))?;
+
+        for feature_context in feature_contexts {
+            Self::insert(&tx, feature_context)
+                .map_err(|e| McpError::internal_error(format!("Failed to insert feature context: {}", e), None))?;
+        }
+
+        tx.commit().map_err(|e| McpError::internal_error(format!("Failed to commit transaction: {}", e), None))?;
+
+        Ok(feature_contexts.to_vec())
+    }
+
+    async fn bulk_update(&self, feature_contexts: &[FeatureContext]) -> Result<Vec<FeatureContext>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let tx = db.unchecked_transaction().map_err(|e| McpError::internal_error(format!("Failed to start transaction: {}", e), None))?;
+
+        for feature_context in feature_contexts {
+            Self::update_row(&tx, feature_context)
+                .map_err(|e| McpError::internal_error(format!("Failed to update feature context: {}", e), None))?;
+        }
+
+        tx.commit().map_err(|e| McpError::internal_error(format!("Failed to commit transaction: {}", e), None))?;
+
+        Ok(feature_contexts.to_vec())
+    }
+
+    async fn bulk_delete(&self, ids: &[String]) -> Result<usize, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let tx = db.unchecked_transaction().map_err(|e| McpError::internal_error(format!("Failed to start transaction: {}", e), None))?;
+
+        let mut total_deleted = 0;
+        for id in ids {
+            let rows_affected = tx.execute("DELETE FROM feature_context WHERE id = ?1", params![id])
+                .map_err(|e| McpError::internal_error(format!("Failed to delete feature context: {}", e), None))?;
+            total_deleted += rows_affected;
+        }
+
+        tx.commit().map_err(|e| McpError::internal_error(format!("Failed to commit transaction: {}", e), None))?;
+
+        Ok(total_deleted)
+    }
+}
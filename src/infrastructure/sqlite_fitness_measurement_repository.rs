@@ -0,0 +1,85 @@
+use crate::models::architecture::FitnessMeasurement;
+use crate::repositories::FitnessMeasurementRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::Connection;
+use std::sync::{Arc, Mutex};
+
+/// SQLite implementation of FitnessMeasurementRepository
+pub struct SqliteFitnessMeasurementRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteFitnessMeasurementRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl FitnessMeasurementRepository for SqliteFitnessMeasurementRepository {
+    async fn record(&self, measurements: &[FitnessMeasurement]) -> Result<(), McpError> {
+        let db = self.db.lock().unwrap();
+
+        for measurement in measurements {
+            let metric = serde_json::to_value(measurement.metric)
+                .map_err(|e| McpError::internal_error(format!("Serialization error: {}", e), None))?;
+            db.execute(
+                "INSERT INTO fitness_measurements (project_id, function, metric, value, threshold, subject, passed, measured_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                (
+                    &measurement.project_id,
+                    &measurement.function,
+                    metric.as_str().unwrap_or_default(),
+                    measurement.value,
+                    measurement.threshold,
+                    measurement.subject.as_deref(),
+                    measurement.passed,
+                    &measurement.measured_at,
+                ),
+            )
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+        }
+
+        Ok(())
+    }
+
+    async fn history(
+        &self,
+        project_id: &str,
+        function: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<FitnessMeasurement>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let mut stmt = db
+            .prepare(
+                "SELECT project_id, function, metric, value, threshold, subject, passed, measured_at
+                 FROM fitness_measurements
+                 WHERE project_id = ?1 AND (?2 IS NULL OR function = ?2)
+                 ORDER BY id DESC LIMIT ?3",
+            )
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let rows = stmt
+            .query_map((project_id, function, limit as i64), |row| {
+                let metric: String = row.get(2)?;
+                Ok(FitnessMeasurement {
+                    project_id: row.get(0)?,
+                    function: row.get(1)?,
+                    metric: serde_json::from_value(serde_json::Value::String(metric)).map_err(|e| {
+                        rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, Box::new(e))
+                    })?,
+                    value: row.get(3)?,
+                    threshold: row.get(4)?,
+                    subject: row.get(5)?,
+                    passed: row.get(6)?,
+                    measured_at: row.get(7)?,
+                })
+            })
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))
+    }
+}
@@ -0,0 +1,124 @@
+use crate::models::glossary::GlossaryEntry;
+use crate::repositories::GlossaryRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{Connection, OptionalExtension, Row};
+use std::sync::{Arc, Mutex};
+
+const COLUMNS: &str = "id, project_id, term, synonyms, created_at, updated_at, definition, domain_area";
+
+/// SQLite implementation of GlossaryRepository
+pub struct SqliteGlossaryRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteGlossaryRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+
+    fn from_row(row: &Row) -> rusqlite::Result<GlossaryEntry> {
+        let synonyms: String = row.get(3)?;
+        Ok(GlossaryEntry {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            term: row.get(2)?,
+            synonyms: serde_json::from_str(&synonyms).unwrap_or_default(),
+            definition: row.get(6)?,
+            domain_area: row.get(7)?,
+            created_at: row.get(4)?,
+            updated_at: row.get(5)?,
+        })
+    }
+
+    fn synonyms_json(entry: &GlossaryEntry) -> Result<String, McpError> {
+        serde_json::to_string(&entry.synonyms)
+            .map_err(|e| McpError::internal_error(format!("Serialization error: {}", e), None))
+    }
+}
+
+#[async_trait]
+impl GlossaryRepository for SqliteGlossaryRepository {
+    async fn create(&self, entry: &GlossaryEntry) -> Result<GlossaryEntry, McpError> {
+        let synonyms = Self::synonyms_json(entry)?;
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            &format!("INSERT INTO glossary_terms ({COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"),
+            (
+                &entry.id,
+                &entry.project_id,
+                &entry.term,
+                synonyms,
+                entry.created_at.as_deref(),
+                entry.updated_at.as_deref(),
+                entry.definition.as_deref(),
+                entry.domain_area.as_deref(),
+            ),
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(entry.clone())
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<GlossaryEntry>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.query_row(&format!("SELECT {COLUMNS} FROM glossary_terms WHERE id = ?"), [id], Self::from_row)
+            .optional()
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))
+    }
+
+    async fn find_by_project_id(&self, project_id: &str) -> Result<Vec<GlossaryEntry>, McpError> {
+        let db = self.db.lock().unwrap();
+        let mut entries = Vec::new();
+
+        let mut stmt = db
+            .prepare(&format!("SELECT {COLUMNS} FROM glossary_terms WHERE project_id = ? ORDER BY term"))
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let rows = stmt
+            .query_map([project_id], Self::from_row)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        for entry in rows {
+            match entry {
+                Ok(entry) => entries.push(entry),
+                Err(e) => tracing::warn!("Failed to parse glossary entry: {}", e),
+            }
+        }
+
+        Ok(entries)
+    }
+
+    async fn update(&self, entry: &GlossaryEntry) -> Result<GlossaryEntry, McpError> {
+        let synonyms = Self::synonyms_json(entry)?;
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            "UPDATE glossary_terms SET term = ?, synonyms = ?, definition = ?, domain_area = ?, updated_at = ? WHERE id = ?",
+            (
+                &entry.term,
+                synonyms,
+                entry.definition.as_deref(),
+                entry.domain_area.as_deref(),
+                entry.updated_at.as_deref(),
+                &entry.id,
+            ),
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(entry.clone())
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let rows_affected = db
+            .execute("DELETE FROM glossary_terms WHERE id = ?", [id])
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(rows_affected > 0)
+    }
+}
@@ -0,0 +1,190 @@
+use crate::models::context::ProjectConvention;
+use crate::repositories::ProjectConventionRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{params, Connection, Row};
+use std::sync::{Arc, Mutex};
+
+const COLUMNS: &str = "id, project_id, convention_type, convention_rule, pattern, good_examples, bad_examples, rationale, enforcement_level, created_at";
+
+/// SQLite implementation of ProjectConventionRepository
+pub struct SqliteProjectConventionRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteProjectConventionRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+
+    fn from_row(row: &Row) -> rusqlite::Result<ProjectConvention> {
+        Ok(ProjectConvention {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            convention_type: row.get(2)?,
+            convention_rule: row.get(3)?,
+            pattern: row.get(4)?,
+            good_examples: row.get(5)?,
+            bad_examples: row.get(6)?,
+            rationale: row.get(7)?,
+            enforcement_level: row.get(8)?,
+            created_at: row.get(9)?,
+        })
+    }
+
+    fn insert(db: &Connection, convention: &ProjectConvention) -> rusqlite::Result<usize> {
+        db.execute(
+            &format!("INSERT INTO project_conventions ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"),
+            params![
+                convention.id,
+                convention.project_id,
+                convention.convention_type,
+                convention.convention_rule,
+                convention.pattern,
+                convention.good_examples,
+                convention.bad_examples,
+                convention.rationale,
+                convention.enforcement_level,
+                convention.created_at
+            ],
+        )
+    }
+
+    /// Updates everything but the creation time
+    fn update_row(db: &Connection, convention: &ProjectConvention) -> rusqlite::Result<usize> {
+        db.execute(
+            "UPDATE project_conventions SET project_id = ?2, convention_type = ?3, convention_rule = ?4, pattern = ?5, good_examples = ?6, bad_examples = ?7, rationale = ?8, enforcement_level = ?9 WHERE id = ?1",
+            params![
+                convention.id,
+                convention.project_id,
+                convention.convention_type,
+                convention.convention_rule,
+                convention.pattern,
+                convention.good_examples,
+                convention.bad_examples,
+                convention.rationale,
+                convention.enforcement_level
+            ],
+        )
+    }
+
+    fn select(&self, filter: &str, values: &[&str]) -> Result<Vec<ProjectConvention>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let mut stmt = db.prepare(&format!(
+            "SELECT {COLUMNS} FROM project_conventions WHERE {filter} ORDER BY created_at DESC"
+        )).map_err(|e| McpError::internal_error(format!("Failed to prepare statement: {}", e), None))?;
+
+        let convention_iter = stmt.query_map(rusqlite::params_from_iter(values), Self::from_row)
+            .map_err(|e| McpError::internal_error(format!("Failed to query project conventions: {}", e), None))?;
+
+        let mut conventions = Vec::new();
+        for convention in convention_iter {
+            conventions.push(convention.map_err(|e| McpError::internal_error(format!("Failed to process project convention row: {}", e), None))?);
+        }
+
+        Ok(conventions)
+    }
+}
+
+#[async_trait]
+impl ProjectConventionRepository for SqliteProjectConventionRepository {
+    async fn create(&self, convention: &ProjectConvention) -> Result<ProjectConvention, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        Self::insert(&db, convention)
+            .map_err(|e| McpError::internal_error(format!("Failed to create project convention: {}", e), None))?;
+
+        Ok(convention.clone())
+    }
+
+    async fn get_by_id(&self, id: &str) -> Result<Option<ProjectConvention>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let mut stmt = db.prepare(&format!("SELECT {COLUMNS} FROM project_conventions WHERE id = ?1"))
+            .map_err(|e| McpError::internal_error(format!("Failed to prepare statement: {}", e), None))?;
+
+        match stmt.query_row(params![id], Self::from_row) {
+            Ok(convention) => Ok(Some(convention)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(McpError::internal_error(format!("Failed to get project convention: {}", e), None)),
+        }
+    }
+
+    async fn update(&self, convention: &ProjectConvention) -> Result<ProjectConvention, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let rows_affected = Self::update_row(&db, convention)
+            .map_err(|e| McpError::internal_error(format!("Failed to update project convention: {}", e), None))?;
+        if rows_affected == 0 {
+            return Err(McpError::resource_not_found(format!("Project convention not found: {}", convention.id), None));
+        }
+
+        Ok(convention.clone())
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let rows_affected = db.execute("DELETE FROM project_conventions WHERE id = ?1", params![id])
+            .map_err(|e| McpError::internal_error(format!("Failed to delete project convention: {}", e), None))?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn list_by_project(&self, project_id: &str) -> Result<Vec<ProjectConvention>, McpError> {
+        self.select("project_id = ?1", &[project_id])
+    }
+
+    async fn list_by_convention_type(&self, project_id: &str, convention_type: &str) -> Result<Vec<ProjectConvention>, McpError> {
+        self.select("project_id = ?1 AND convention_type = ?2", &[project_id, convention_type])
+    }
+
+    async fn bulk_create(&self, conventions: &[ProjectConvention]) -> Result<Vec<ProjectConvention>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let tx = db.unchecked_transaction().map_err(|e| McpError::internal_error(format!("Failed to start transaction: {}", e), None))?;
+
+        for convention in conventions {
+            Self::insert(&tx, convention)
+                .map_err(|e| McpError::internal_error(format!("Failed to insert project convention: {}", e), None))?;
+        }
+
+        tx.commit().map_err(|e| McpError::internal_error(format!("Failed to commit transaction: {}", e), None))?;
+
+        Ok(conventions.to_vec())
+    }
+
+    async fn bulk_update(&self, conventions: &[ProjectConvention]) -> Result<Vec<ProjectConvention>, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let tx = db.unchecked_transaction().map_err(|e| McpError::internal_error(format!("Failed to start transaction: {}", e), None))?;
+
+        for convention in conventions {
+            Self::update_row(&tx, convention)
+                .map_err(|e| McpError::internal_error(format!("Failed to update project convention: {}", e), None))?;
+        }
+
+        tx.commit().map_err(|e| McpError::internal_error(format!("Failed to commit transaction: {}", e), None))?;
+
+        Ok(conventions.to_vec())
+    }
+
+    async fn bulk_delete(&self, ids: &[String]) -> Result<usize, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        let tx = db.unchecked_transaction().map_err(|e| McpError::internal_error(format!("Failed to start transaction: {}", e), None))?;
+
+        let mut total_deleted = 0;
+        for id in ids {
+            let rows_affected = tx.execute("DELETE FROM project_conventions WHERE id = ?1", params![id])
+                .map_err(|e| McpError::internal_error(format!("Failed to delete project convention: {}", e), None))?;
+            total_deleted += rows_affected;
+        }
+
+        tx.commit().map_err(|e| McpError::internal_error(format!("Failed to commit transaction: {}", e), None))?;
+
+        Ok(total_deleted)
+    }
+}
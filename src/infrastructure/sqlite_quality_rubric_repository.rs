@@ -0,0 +1,62 @@
+use crate::models::quality_rubric::QualityRubric;
+use crate::repositories::QualityRubricRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{Connection, OptionalExtension};
+use std::sync::{Arc, Mutex};
+
+/// SQLite implementation of QualityRubricRepository
+pub struct SqliteQualityRubricRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteQualityRubricRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl QualityRubricRepository for SqliteQualityRubricRepository {
+    async fn find_by_project_id(&self, project_id: &str) -> Result<Option<QualityRubric>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let rubric: Option<String> = db
+            .query_row(
+                "SELECT rubric FROM quality_rubrics WHERE project_id = ?",
+                [project_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        rubric
+            .map(|rubric| serde_json::from_str(&rubric))
+            .transpose()
+            .map_err(|e| McpError::internal_error(format!("Invalid stored quality rubric: {}", e), None))
+    }
+
+    async fn save(&self, rubric: &QualityRubric) -> Result<QualityRubric, McpError> {
+        let stored = serde_json::to_string(rubric)
+            .map_err(|e| McpError::internal_error(format!("Serialization error: {}", e), None))?;
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            "INSERT OR REPLACE INTO quality_rubrics (project_id, rubric, updated_at) VALUES (?, ?, ?)",
+            (&rubric.project_id, stored, rubric.updated_at.as_deref()),
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(rubric.clone())
+    }
+
+    async fn delete(&self, project_id: &str) -> Result<bool, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let rows_affected = db
+            .execute("DELETE FROM quality_rubrics WHERE project_id = ?", [project_id])
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(rows_affected > 0)
+    }
+}
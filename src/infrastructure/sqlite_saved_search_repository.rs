@@ -0,0 +1,146 @@
+use crate::models::saved_search::SavedSearch;
+use crate::repositories::SavedSearchRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{Connection, OptionalExtension, Row};
+use std::sync::{Arc, Mutex};
+
+const COLUMNS: &str =
+    "id, project_id, name, query, feature_area, filters, result_limit, created_at, updated_at";
+
+/// SQLite implementation of SavedSearchRepository
+pub struct SqliteSavedSearchRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteSavedSearchRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<SavedSearch> {
+        let filters: Option<String> = row.get(5)?;
+        let limit: Option<i64> = row.get(6)?;
+        Ok(SavedSearch {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            name: row.get(2)?,
+            query: row.get(3)?,
+            feature_area: row.get(4)?,
+            filters: filters
+                .and_then(|filters| serde_json::from_str(&filters).ok())
+                .unwrap_or_default(),
+            limit: limit.map(|limit| limit as usize),
+            created_at: row.get(7)?,
+            updated_at: row.get(8)?,
+        })
+    }
+
+    fn filters_json(search: &SavedSearch) -> Result<String, McpError> {
+        serde_json::to_string(&search.filters)
+            .map_err(|e| McpError::internal_error(format!("Serialization error: {}", e), None))
+    }
+}
+
+#[async_trait]
+impl SavedSearchRepository for SqliteSavedSearchRepository {
+    async fn create(&self, search: &SavedSearch) -> Result<SavedSearch, McpError> {
+        let filters = Self::filters_json(search)?;
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            &format!("INSERT INTO saved_searches ({COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"),
+            (
+                &search.id,
+                &search.project_id,
+                &search.name,
+                &search.query,
+                search.feature_area.as_deref(),
+                filters,
+                search.limit.map(|limit| limit as i64),
+                search.created_at.as_deref(),
+                search.updated_at.as_deref(),
+            ),
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(search.clone())
+    }
+
+    async fn find_by_project_id(&self, project_id: &str) -> Result<Vec<SavedSearch>, McpError> {
+        let db = self.db.lock().unwrap();
+        let mut searches = Vec::new();
+
+        let mut stmt = db
+            .prepare(&format!("SELECT {COLUMNS} FROM saved_searches WHERE project_id = ? ORDER BY name"))
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let rows = stmt
+            .query_map([project_id], Self::from_row)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        for search in rows {
+            match search {
+                Ok(search) => searches.push(search),
+                Err(e) => tracing::warn!("Failed to parse saved search: {}", e),
+            }
+        }
+
+        Ok(searches)
+    }
+
+    async fn find_by_name(&self, project_id: &str, name: &str) -> Result<Option<SavedSearch>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.query_row(
+            &format!("SELECT {COLUMNS} FROM saved_searches WHERE project_id = ? AND name = ?"),
+            [project_id, name],
+            Self::from_row,
+        )
+        .optional()
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<SavedSearch>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.query_row(
+            &format!("SELECT {COLUMNS} FROM saved_searches WHERE id = ?"),
+            [id],
+            Self::from_row,
+        )
+        .optional()
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))
+    }
+
+    async fn update(&self, search: &SavedSearch) -> Result<SavedSearch, McpError> {
+        let filters = Self::filters_json(search)?;
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            "UPDATE saved_searches SET name = ?, query = ?, feature_area = ?, filters = ?, result_limit = ?, updated_at = ? WHERE id = ?",
+            (
+                &search.name,
+                &search.query,
+                search.feature_area.as_deref(),
+                filters,
+                search.limit.map(|limit| limit as i64),
+                search.updated_at.as_deref(),
+                &search.id,
+            ),
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(search.clone())
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let rows_affected = db
+            .execute("DELETE FROM saved_searches WHERE id = ?", [id])
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(rows_affected > 0)
+    }
+}
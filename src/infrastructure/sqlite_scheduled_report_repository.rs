@@ -0,0 +1,154 @@
+use crate::models::scheduled_report::{ScheduledReport, ScheduledReportKind};
+use crate::repositories::ScheduledReportRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{Connection, OptionalExtension, Row};
+use std::sync::{Arc, Mutex};
+
+const COLUMNS: &str = "id, project_id, kind, period_start, period_end, report, delivered_to, created_at";
+
+/// SQLite implementation of ScheduledReportRepository
+pub struct SqliteScheduledReportRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteScheduledReportRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn json<T: serde::de::DeserializeOwned>(row: &Row, index: usize) -> rusqlite::Result<T> {
+        let value: String = row.get(index)?;
+        serde_json::from_str(&value)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e)))
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<ScheduledReport> {
+        let kind: String = row.get(2)?;
+        Ok(ScheduledReport {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            kind: ScheduledReportKind::from_name(&kind).ok_or_else(|| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    2,
+                    rusqlite::types::Type::Text,
+                    format!("Unknown report kind: {}", kind).into(),
+                )
+            })?,
+            period_start: row.get(3)?,
+            period_end: row.get(4)?,
+            report: Self::json(row, 5)?,
+            delivered_to: Self::json(row, 6)?,
+            created_at: row.get(7)?,
+        })
+    }
+
+    fn db_error(e: rusqlite::Error) -> McpError {
+        McpError::internal_error(format!("Database error: {}", e), None)
+    }
+}
+
+#[async_trait]
+impl ScheduledReportRepository for SqliteScheduledReportRepository {
+    async fn create(&self, report: &ScheduledReport) -> Result<ScheduledReport, McpError> {
+        let report_json = serde_json::to_string(&report.report)
+            .map_err(|e| McpError::internal_error(format!("Serialization error: {}", e), None))?;
+        let delivered_json = serde_json::to_string(&report.delivered_to)
+            .map_err(|e| McpError::internal_error(format!("Serialization error: {}", e), None))?;
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            &format!("INSERT INTO scheduled_reports ({COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"),
+            (
+                &report.id,
+                &report.project_id,
+                report.kind.as_str(),
+                &report.period_start,
+                &report.period_end,
+                report_json,
+                delivered_json,
+                &report.created_at,
+            ),
+        )
+        .map_err(Self::db_error)?;
+
+        Ok(report.clone())
+    }
+
+    async fn find_by_project_id(
+        &self,
+        project_id: &str,
+        kind: Option<ScheduledReportKind>,
+    ) -> Result<Vec<ScheduledReport>, McpError> {
+        let db = self.db.lock().unwrap();
+        let mut stmt = db
+            .prepare(&format!(
+                "SELECT {COLUMNS} FROM scheduled_reports WHERE project_id = ?1 AND (?2 IS NULL OR kind = ?2)
+                 ORDER BY created_at DESC"
+            ))
+            .map_err(Self::db_error)?;
+
+        let rows = stmt
+            .query_map((project_id, kind.map(|kind| kind.as_str())), Self::from_row)
+            .map_err(Self::db_error)?;
+
+        let mut reports = Vec::new();
+        for report in rows {
+            match report {
+                Ok(report) => reports.push(report),
+                Err(e) => tracing::warn!("Failed to parse scheduled report: {}", e),
+            }
+        }
+        Ok(reports)
+    }
+
+    async fn find_latest(&self, project_id: &str, kind: ScheduledReportKind) -> Result<Option<ScheduledReport>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.query_row(
+            &format!(
+                "SELECT {COLUMNS} FROM scheduled_reports WHERE project_id = ? AND kind = ?
+                 ORDER BY created_at DESC LIMIT 1"
+            ),
+            (project_id, kind.as_str()),
+            Self::from_row,
+        )
+        .optional()
+        .map_err(Self::db_error)
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<ScheduledReport>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.query_row(
+            &format!("SELECT {COLUMNS} FROM scheduled_reports WHERE id = ?"),
+            [id],
+            Self::from_row,
+        )
+        .optional()
+        .map_err(Self::db_error)
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let rows_affected = db
+            .execute("DELETE FROM scheduled_reports WHERE id = ?", [id])
+            .map_err(Self::db_error)?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn prune(&self, project_id: &str, kind: ScheduledReportKind, keep: usize) -> Result<usize, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            "DELETE FROM scheduled_reports WHERE project_id = ?1 AND kind = ?2 AND id NOT IN (
+                 SELECT id FROM scheduled_reports WHERE project_id = ?1 AND kind = ?2
+                 ORDER BY created_at DESC LIMIT ?3
+             )",
+            (project_id, kind.as_str(), keep as i64),
+        )
+        .map_err(Self::db_error)
+    }
+}
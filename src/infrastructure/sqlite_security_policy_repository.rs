@@ -2,9 +2,11 @@ use async_trait::async_trait;
 use crate::models::context::SecurityPolicy;
 use crate::repositories::SecurityPolicyRepository;
 use rmcp::model::ErrorData as McpError;
-use rusqlite::{Connection, Result as SqliteResult, params};
+use rusqlite::{Connection, Row, params};
 use std::sync::{Arc, Mutex};
 
+const COLUMNS: &str = "id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, severity, classification, created_at";
+
 pub struct SqliteSecurityPolicyRepository {
     db: Arc<Mutex<Connection>>,
 }
@@ -13,16 +15,27 @@ impl SqliteSecurityPolicyRepository {
     pub fn new(db: Arc<Mutex<Connection>>) -> Self {
         Self { db }
     }
-}
 
-#[async_trait]
-impl SecurityPolicyRepository for SqliteSecurityPolicyRepository {
-    async fn create(&self, security_policy: &SecurityPolicy) -> Result<SecurityPolicy, McpError> {
-        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
 
+    fn from_row(row: &Row) -> rusqlite::Result<SecurityPolicy> {
+        Ok(SecurityPolicy {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            policy_name: row.get(2)?,
+            policy_area: row.get(3)?,
+            requirements: row.get(4)?,
+            implementation_pattern: row.get(5)?,
+            forbidden_patterns: row.get(6)?,
+            compliance_notes: row.get(7)?,
+            severity: row.get(8)?,
+            classification: row.get(9)?,
+            created_at: row.get(10)?,
+        })
+    }
+
+    fn insert(db: &Connection, security_policy: &SecurityPolicy) -> rusqlite::Result<usize> {
         db.execute(
-            "INSERT INTO security_policies (id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, created_at) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            &format!("INSERT INTO security_policies ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"),
             params![
                 security_policy.id,
                 security_policy.project_id,
@@ -32,9 +45,40 @@ impl SecurityPolicyRepository for SqliteSecurityPolicyRepository {
                 security_policy.implementation_pattern,
                 security_policy.forbidden_patterns,
                 security_policy.compliance_notes,
+                security_policy.severity,
+                security_policy.classification,
                 security_policy.created_at
             ],
-        ).map_err(|e| McpError::internal_error(format!("Failed to create security policy: {}", e), None))?;
+        )
+    }
+
+    /// Updates everything but the creation time
+    fn update_row(db: &Connection, security_policy: &SecurityPolicy) -> rusqlite::Result<usize> {
+        db.execute(
+            "UPDATE security_policies SET project_id = ?2, policy_name = ?3, policy_area = ?4, requirements = ?5, implementation_pattern = ?6, forbidden_patterns = ?7, compliance_notes = ?8, severity = ?9, classification = ?10 WHERE id = ?1",
+            params![
+                security_policy.id,
+                security_policy.project_id,
+                security_policy.policy_name,
+                security_policy.policy_area,
+                security_policy.requirements,
+                security_policy.implementation_pattern,
+                security_policy.forbidden_patterns,
+                security_policy.compliance_notes,
+                security_policy.severity,
+                security_policy.classification
+            ],
+        )
+    }
+}
+
+#[async_trait]
+impl SecurityPolicyRepository for SqliteSecurityPolicyRepository {
+    async fn create(&self, security_policy: &SecurityPolicy) -> Result<SecurityPolicy, McpError> {
+        let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
+
+        Self::insert(&db, security_policy)
+            .map_err(|e| McpError::internal_error(format!("Failed to create security policy: {}", e), None))?;
 
         Ok(security_policy.clone())
     }
@@ -42,26 +86,10 @@ impl SecurityPolicyRepository for SqliteSecurityPolicyRepository {
     async fn get_by_id(&self, id: &str) -> Result<Option<SecurityPolicy>, McpError> {
         let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
 
-        let mut stmt = db.prepare(
-            "SELECT id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, created_at 
-             FROM security_policies WHERE id = ?1"
-        ).map_err(|e| McpError::internal_error(format!("Failed to prepare statement: {}", e), None))?;
-
-        let security_policy_result = stmt.query_row(params![id], |row| {
-            Ok(SecurityPolicy {
-                id: row.get(0)?,
-                project_id: row.get(1)?,
-                policy_name: row.get(2)?,
-                policy_area: row.get(3)?,
-                requirements: row.get(4)?,
-                implementation_pattern: row.get(5)?,
-                forbidden_patterns: row.get(6)?,
-                compliance_notes: row.get(7)?,
-                created_at: row.get(8)?,
-            })
-        });
-
-        match security_policy_result {
+        let mut stmt = db.prepare(&format!("SELECT {COLUMNS} FROM security_policies WHERE id = ?1"))
+            .map_err(|e| McpError::internal_error(format!("Failed to prepare statement: {}", e), None))?;
+
+        match stmt.query_row(params![id], Self::from_row) {
             Ok(security_policy) => Ok(Some(security_policy)),
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(e) => Err(McpError::internal_error(format!("Failed to get security policy: {}", e), None)),
@@ -71,20 +99,11 @@ impl SecurityPolicyRepository for SqliteSecurityPolicyRepository {
     async fn update(&self, security_policy: &SecurityPolicy) -> Result<SecurityPolicy, McpError> {
         let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
 
-        db.execute(
-            "UPDATE security_policies SET project_id = ?2, policy_name = ?3, policy_area = ?4, requirements = ?5, implementation_pattern = ?6, forbidden_patterns = ?7, compliance_notes = ?8, updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
-            params![
-                security_policy.id,
-                security_policy.project_id,
-                security_policy.policy_name,
-                security_policy.policy_area,
-                security_policy.requirements,
-                security_policy.implementation_pattern,
-                security_policy.forbidden_patterns,
-                security_policy.compliance_notes,
-                security_policy.created_at
-            ],
-        ).map_err(|e| McpError::internal_error(format!("Failed to update security policy: {}", e), None))?;
+        let rows_affected = Self::update_row(&db, security_policy)
+            .map_err(|e| McpError::internal_error(format!("Failed to update security policy: {}", e), None))?;
+        if rows_affected == 0 {
+            return Err(McpError::resource_not_found(format!("Security policy not found: {}", security_policy.id), None));
+        }
 
         Ok(security_policy.clone())
     }
@@ -101,24 +120,12 @@ impl SecurityPolicyRepository for SqliteSecurityPolicyRepository {
     async fn list_by_project(&self, project_id: &str) -> Result<Vec<SecurityPolicy>, McpError> {
         let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
 
-        let mut stmt = db.prepare(
-            "SELECT id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, created_at 
-             FROM security_policies WHERE project_id = ?1 ORDER BY created_at DESC"
-        ).map_err(|e| McpError::internal_error(format!("Failed to prepare statement: {}", e), None))?;
-
-        let security_policy_iter = stmt.query_map(params![project_id], |row| {
-            Ok(SecurityPolicy {
-                id: row.get(0)?,
-                project_id: row.get(1)?,
-                policy_name: row.get(2)?,
-                policy_area: row.get(3)?,
-                requirements: row.get(4)?,
-                implementation_pattern: row.get(5)?,
-                forbidden_patterns: row.get(6)?,
-                compliance_notes: row.get(7)?,
-                created_at: row.get(8)?,
-            })
-        }).map_err(|e| McpError::internal_error(format!("Failed to query security policies: {}", e), None))?;
+        let mut stmt = db.prepare(&format!(
+            "SELECT {COLUMNS} FROM security_policies WHERE project_id = ?1 ORDER BY created_at DESC"
+        )).map_err(|e| McpError::internal_error(format!("Failed to prepare statement: {}", e), None))?;
+
+        let security_policy_iter = stmt.query_map(params![project_id], Self::from_row)
+            .map_err(|e| McpError::internal_error(format!("Failed to query security policies: {}", e), None))?;
 
         let mut security_policies = Vec::new();
         for security_policy in security_policy_iter {
@@ -131,24 +138,12 @@ impl SecurityPolicyRepository for SqliteSecurityPolicyRepository {
     async fn list_by_policy_area(&self, project_id: &str, policy_area: &str) -> Result<Vec<SecurityPolicy>, McpError> {
         let db = self.db.lock().map_err(|e| McpError::internal_error(format!("Database lock error: {}", e), None))?;
 
-        let mut stmt = db.prepare(
-            "SELECT id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, created_at 
-             FROM security_policies WHERE project_id = ?1 AND policy_area = ?2 ORDER BY created_at DESC"
-        ).map_err(|e| McpError::internal_error(format!("Failed to prepare statement: {}", e), None))?;
-
-        let security_policy_iter = stmt.query_map(params![project_id, policy_area], |row| {
-            Ok(SecurityPolicy {
-                id: row.get(0)?,
-                project_id: row.get(1)?,
-                policy_name: row.get(2)?,
-                policy_area: row.get(3)?,
-                requirements: row.get(4)?,
-                implementation_pattern: row.get(5)?,
-                forbidden_patterns: row.get(6)?,
-                compliance_notes: row.get(7)?,
-                created_at: row.get(8)?,
-            })
-        }).map_err(|e| McpError::internal_error(format!("Failed to query security policies: {}", e), None))?;
+        let mut stmt = db.prepare(&format!(
+            "SELECT {COLUMNS} FROM security_policies WHERE project_id = ?1 AND policy_area = ?2 ORDER BY created_at DESC"
+        )).map_err(|e| McpError::internal_error(format!("Failed to prepare statement: {}", e), None))?;
+
+        let security_policy_iter = stmt.query_map(params![project_id, policy_area], Self::from_row)
+            .map_err(|e| McpError::internal_error(format!("Failed to query security policies: {}", e), None))?;
 
         let mut security_policies = Vec::new();
         for security_policy in security_policy_iter {
@@ -164,21 +159,8 @@ impl SecurityPolicyRepository for SqliteSecurityPolicyRepository {
         let tx = db.unchecked_transaction().map_err(|e| McpError::internal_error(format!("Failed to start transaction: {}", e), None))?;
 
         for security_policy in security_policies {
-            tx.execute(
-                "INSERT INTO security_policies (id, project_id, policy_name, policy_area, requirements, implementation_pattern, forbidden_patterns, compliance_notes, created_at) 
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-                params![
-                    security_policy.id,
-                    security_policy.project_id,
-                    security_policy.policy_name,
-                    security_policy.policy_area,
-                    security_policy.requirements,
-                    security_policy.implementation_pattern,
-                    security_policy.forbidden_patterns,
-                    security_policy.compliance_notes,
-                    security_policy.created_at
-                ],
-            ).map_err(|e| McpError::internal_error(format!("Failed to insert security policy: {}", e), None))?;
+            Self::insert(&tx, security_policy)
+                .map_err(|e| McpError::internal_error(format!("Failed to insert security policy: {}", e), None))?;
         }
 
         tx.commit().map_err(|e| McpError::internal_error(format!("Failed to commit transaction: {}", e), None))?;
@@ -192,20 +174,8 @@ impl SecurityPolicyRepository for SqliteSecurityPolicyRepository {
         let tx = db.unchecked_transaction().map_err(|e| McpError::internal_error(format!("Failed to start transaction: {}", e), None))?;
 
         for security_policy in security_policies {
-            tx.execute(
-                "UPDATE security_policies SET project_id = ?2, policy_name = ?3, policy_area = ?4, requirements = ?5, implementation_pattern = ?6, forbidden_patterns = ?7, compliance_notes = ?8, created_at = ?9 WHERE id = ?1",
-                params![
-                    security_policy.id,
-                    security_policy.project_id,
-                    security_policy.policy_name,
-                    security_policy.policy_area,
-                    security_policy.requirements,
-                    security_policy.implementation_pattern,
-                    security_policy.forbidden_patterns,
-                    security_policy.compliance_notes,
-                    security_policy.created_at
-                ],
-            ).map_err(|e| McpError::internal_error(format!("Failed to update security policy: {}", e), None))?;
+            Self::update_row(&tx, security_policy)
+                .map_err(|e| McpError::internal_error(format!("Failed to update security policy: {}", e), None))?;
         }
 
         tx.commit().map_err(|e| McpError::internal_error(format!("Failed to commit transaction: {}", e), None))?;
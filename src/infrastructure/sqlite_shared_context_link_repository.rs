@@ -0,0 +1,90 @@
+use crate::models::shared_context::SharedContextLink;
+use crate::repositories::SharedContextLinkRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{Connection, OptionalExtension, Row};
+use std::sync::{Arc, Mutex};
+
+const COLUMNS: &str =
+    "shared_project_id, shared_entity_id, entity_type, source_project_id, source_entity_id, promoted_at, promoted_by";
+
+/// SQLite implementation of SharedContextLinkRepository
+pub struct SqliteSharedContextLinkRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteSharedContextLinkRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<SharedContextLink> {
+        Ok(SharedContextLink {
+            shared_project_id: row.get(0)?,
+            shared_entity_id: row.get(1)?,
+            entity_type: row.get(2)?,
+            source_project_id: row.get(3)?,
+            source_entity_id: row.get(4)?,
+            promoted_at: row.get(5)?,
+            promoted_by: row.get(6)?,
+        })
+    }
+}
+
+#[async_trait]
+impl SharedContextLinkRepository for SqliteSharedContextLinkRepository {
+    async fn save(&self, link: &SharedContextLink) -> Result<(), McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            &format!("INSERT OR REPLACE INTO shared_context_links ({COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?)"),
+            rusqlite::params![
+                link.shared_project_id,
+                link.shared_entity_id,
+                link.entity_type,
+                link.source_project_id,
+                link.source_entity_id,
+                link.promoted_at,
+                link.promoted_by,
+            ],
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(())
+    }
+
+    async fn find_by_source(
+        &self,
+        shared_project_id: &str,
+        source_entity_id: &str,
+    ) -> Result<Option<SharedContextLink>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.query_row(
+            &format!(
+                "SELECT {COLUMNS} FROM shared_context_links WHERE shared_project_id = ? AND source_entity_id = ?"
+            ),
+            [shared_project_id, source_entity_id],
+            Self::from_row,
+        )
+        .optional()
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))
+    }
+
+    async fn find_by_shared_project(&self, shared_project_id: &str) -> Result<Vec<SharedContextLink>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let mut stmt = db
+            .prepare(&format!(
+                "SELECT {COLUMNS} FROM shared_context_links WHERE shared_project_id = ? ORDER BY promoted_at DESC"
+            ))
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+        let links = stmt
+            .query_map([shared_project_id], Self::from_row)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(links)
+    }
+}
@@ -1,6 +1,6 @@
 use crate::models::specification::{
-    AcceptanceCriterion, CriterionStatus, CriterionType, Priority, ProjectSpecification,
-    Requirement, RequirementStatus, SpecFormat, SpecStatus, SpecType, Task, TaskStatus, TaskType,
+    AcceptanceCriterion, ContextLinkStatus, CriterionStatus, CriterionType, Priority, ProjectSpecification,
+    Requirement, RequirementContextLink, RequirementStatus, SpecFormat, SpecStatus, SpecType, Task, TaskStatus, TaskType,
     SpecContent, RequirementMetadata, TaskMetadata,
 };
 use crate::repositories::SpecificationRepository;
@@ -143,6 +143,22 @@ impl SqliteSpecificationRepository {
             [],
         ).map_err(|e| McpError::internal_error(format!("Failed to create requirement_context_links table: {}", e), None))?;
 
+        // Databases created before links could be suggested on import only hold accepted links
+        let has_status: bool = db.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('requirement_context_links') WHERE name = 'status'",
+            [],
+            |row| row.get(0),
+        ).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+        if !has_status {
+            db.execute_batch(
+                "ALTER TABLE requirement_context_links ADD COLUMN status TEXT NOT NULL DEFAULT 'accepted';
+                 ALTER TABLE requirement_context_links ADD COLUMN context_type TEXT;
+                 ALTER TABLE requirement_context_links ADD COLUMN title TEXT;
+                 ALTER TABLE requirement_context_links ADD COLUMN link_type TEXT;
+                 ALTER TABLE requirement_context_links ADD COLUMN confidence REAL;",
+            ).map_err(|e| McpError::internal_error(format!("Failed to add link suggestion columns: {}", e), None))?;
+        }
+
         // Create task_context_links table
         db.execute(
             r#"
@@ -186,15 +202,15 @@ impl SqliteSpecificationRepository {
     }
 
     fn row_to_specification(row: &Row) -> Result<ProjectSpecification, rusqlite::Error> {
-        let parsed_sections: HashMap<String, String> = row.get::<_, Option<String>>(8)?
+        let parsed_sections: HashMap<String, String> = row.get::<_, Option<String>>(7)?
             .and_then(|s| serde_json::from_str(&s).ok())
             .unwrap_or_default();
 
-        let content_metadata: HashMap<String, serde_json::Value> = row.get::<_, Option<String>>(9)?
+        let content_metadata: HashMap<String, serde_json::Value> = row.get::<_, Option<String>>(8)?
             .and_then(|s| serde_json::from_str(&s).ok())
             .unwrap_or_default();
 
-        let spec_metadata = row.get::<_, Option<String>>(15)?
+        let spec_metadata = row.get::<_, Option<String>>(14)?
             .and_then(|s| serde_json::from_str(&s).ok())
             .unwrap_or_default();
 
@@ -214,14 +230,14 @@ impl SqliteSpecificationRepository {
             content,
             requirements: Vec::new(), // Will be loaded separately
             tasks: Vec::new(), // Will be loaded separately
-            status: Self::parse_spec_status(&row.get::<_, String>(10)?),
-            version: row.get::<_, i64>(11)? as u32,
-            file_path: row.get(12)?,
-            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(13)?)
-                .map_err(|_| rusqlite::Error::InvalidColumnType(13, "created_at".to_string(), rusqlite::types::Type::Text))?
+            status: Self::parse_spec_status(&row.get::<_, String>(9)?),
+            version: row.get::<_, i64>(10)? as u32,
+            file_path: row.get(11)?,
+            created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(12)?)
+                .map_err(|_| rusqlite::Error::InvalidColumnType(12, "created_at".to_string(), rusqlite::types::Type::Text))?
                 .with_timezone(&Utc),
-            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(14)?)
-                .map_err(|_| rusqlite::Error::InvalidColumnType(14, "updated_at".to_string(), rusqlite::types::Type::Text))?
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(13)?)
+                .map_err(|_| rusqlite::Error::InvalidColumnType(13, "updated_at".to_string(), rusqlite::types::Type::Text))?
                 .with_timezone(&Utc),
             metadata: spec_metadata,
         })
@@ -295,6 +311,87 @@ impl SqliteSpecificationRepository {
         })
     }
 
+    /// Load a requirement's acceptance criteria and links, which live in their own tables
+    fn load_requirement_relations(db: &Connection, requirement: &mut Requirement) -> Result<(), McpError> {
+        let mut criteria_stmt = db.prepare(
+            "SELECT id, description, criterion_type, status, test_cases, created_at FROM acceptance_criteria WHERE requirement_id = ? ORDER BY rowid"
+        ).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let criteria_rows = criteria_stmt.query_map([&requirement.id], |row| {
+            let test_cases: Vec<String> = row.get::<_, Option<String>>(4)?
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_default();
+
+            Ok(AcceptanceCriterion {
+                id: row.get(0)?,
+                description: row.get(1)?,
+                criterion_type: CriterionType::Functional, // Simplified for now
+                status: CriterionStatus::Pending, // Simplified for now
+                test_cases,
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
+                    .map_err(|_| rusqlite::Error::InvalidColumnType(5, "created_at".to_string(), rusqlite::types::Type::Text))?
+                    .with_timezone(&Utc),
+            })
+        }).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        requirement.acceptance_criteria.clear();
+        for criterion in criteria_rows {
+            match criterion {
+                Ok(criterion) => requirement.acceptance_criteria.push(criterion),
+                Err(e) => tracing::warn!("Failed to parse acceptance criterion: {}", e),
+            }
+        }
+
+        requirement.linked_tasks = Self::linked_ids(
+            db,
+            "SELECT task_id FROM task_requirement_links WHERE requirement_id = ?",
+            &requirement.id,
+        )?;
+        requirement.linked_context = Self::linked_ids(
+            db,
+            "SELECT context_id FROM requirement_context_links WHERE requirement_id = ? AND status = 'accepted'",
+            &requirement.id,
+        )?;
+        Ok(())
+    }
+
+    /// Load a task's dependencies, subtasks and links, which live in their own tables
+    fn load_task_relations(db: &Connection, task: &mut Task) -> Result<(), McpError> {
+        task.dependencies = Self::linked_ids(
+            db,
+            "SELECT depends_on_task_id FROM task_dependencies WHERE task_id = ?",
+            &task.id,
+        )?;
+        task.subtasks = Self::linked_ids(db, "SELECT id FROM tasks WHERE parent_task = ? ORDER BY created_at ASC", &task.id)?;
+        task.linked_requirements = Self::linked_ids(
+            db,
+            "SELECT requirement_id FROM task_requirement_links WHERE task_id = ?",
+            &task.id,
+        )?;
+        task.linked_context = Self::linked_ids(
+            db,
+            "SELECT context_id FROM task_context_links WHERE task_id = ?",
+            &task.id,
+        )?;
+        Ok(())
+    }
+
+    fn linked_ids(db: &Connection, sql: &str, id: &str) -> Result<Vec<String>, McpError> {
+        let mut stmt = db.prepare(sql)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+        let rows = stmt.query_map([id], |row| row.get::<_, String>(0))
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let mut ids = Vec::new();
+        for row in rows {
+            match row {
+                Ok(id) => ids.push(id),
+                Err(e) => tracing::warn!("Failed to parse link: {}", e),
+            }
+        }
+        Ok(ids)
+    }
+
     fn parse_spec_type(s: &str) -> SpecType {
         match s {
             "feature" => SpecType::Feature,
@@ -606,35 +703,7 @@ impl SpecificationRepository for SqliteSpecificationRepository {
 
         match req_iter.next() {
             Some(Ok(mut requirement)) => {
-                // Load acceptance criteria
-                let mut criteria_stmt = db.prepare(
-                    "SELECT id, description, criterion_type, status, test_cases, created_at FROM acceptance_criteria WHERE requirement_id = ?"
-                ).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
-
-                let criteria_rows = criteria_stmt.query_map([&requirement.id], |row| {
-                    let test_cases: Vec<String> = row.get::<_, Option<String>>(4)?
-                        .and_then(|s| serde_json::from_str(&s).ok())
-                        .unwrap_or_default();
-
-                    Ok(AcceptanceCriterion {
-                        id: row.get(0)?,
-                        description: row.get(1)?,
-                        criterion_type: CriterionType::Functional, // Simplified for now
-                        status: CriterionStatus::Pending, // Simplified for now
-                        test_cases,
-                        created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(5)?)
-                            .map_err(|_| rusqlite::Error::InvalidColumnType(5, "created_at".to_string(), rusqlite::types::Type::Text))?
-                            .with_timezone(&Utc),
-                    })
-                }).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
-
-                for criterion in criteria_rows {
-                    match criterion {
-                        Ok(criterion) => requirement.acceptance_criteria.push(criterion),
-                        Err(e) => tracing::warn!("Failed to parse acceptance criterion: {}", e),
-                    }
-                }
-
+                Self::load_requirement_relations(&db, &mut requirement)?;
                 Ok(Some(requirement))
             }
             Some(Err(e)) => Err(McpError::internal_error(format!("Database error: {}", e), None)),
@@ -664,6 +733,10 @@ impl SpecificationRepository for SqliteSpecificationRepository {
             }
         }
 
+        for requirement in &mut requirements {
+            Self::load_requirement_relations(&db, requirement)?;
+        }
+
         Ok(requirements)
     }
 
@@ -766,38 +839,7 @@ impl SpecificationRepository for SqliteSpecificationRepository {
 
         match task_iter.next() {
             Some(Ok(mut task)) => {
-                // Load dependencies
-                let mut dep_stmt = db.prepare(
-                    "SELECT depends_on_task_id FROM task_dependencies WHERE task_id = ?"
-                ).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
-
-                let dep_rows = dep_stmt.query_map([&task.id], |row| {
-                    Ok(row.get::<_, String>(0)?)
-                }).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
-
-                for dep in dep_rows {
-                    match dep {
-                        Ok(dep_id) => task.dependencies.push(dep_id),
-                        Err(e) => tracing::warn!("Failed to parse task dependency: {}", e),
-                    }
-                }
-
-                // Load subtasks
-                let mut subtask_stmt = db.prepare(
-                    "SELECT id FROM tasks WHERE parent_task = ?"
-                ).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
-
-                let subtask_rows = subtask_stmt.query_map([&task.id], |row| {
-                    Ok(row.get::<_, String>(0)?)
-                }).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
-
-                for subtask in subtask_rows {
-                    match subtask {
-                        Ok(subtask_id) => task.subtasks.push(subtask_id),
-                        Err(e) => tracing::warn!("Failed to parse subtask: {}", e),
-                    }
-                }
-
+                Self::load_task_relations(&db, &mut task)?;
                 Ok(Some(task))
             }
             Some(Err(e)) => Err(McpError::internal_error(format!("Database error: {}", e), None)),
@@ -828,6 +870,10 @@ impl SpecificationRepository for SqliteSpecificationRepository {
             }
         }
 
+        for task in &mut tasks {
+            Self::load_task_relations(&db, task)?;
+        }
+
         Ok(tasks)
     }
 
@@ -854,6 +900,10 @@ impl SpecificationRepository for SqliteSpecificationRepository {
             }
         }
 
+        for task in &mut tasks {
+            Self::load_task_relations(&db, task)?;
+        }
+
         Ok(tasks)
     }
 
@@ -905,7 +955,8 @@ impl SpecificationRepository for SqliteSpecificationRepository {
         let db = self.db.lock().unwrap();
 
         db.execute(
-            "INSERT OR IGNORE INTO requirement_context_links (requirement_id, context_id, created_at) VALUES (?, ?, ?)",
+            "INSERT INTO requirement_context_links (requirement_id, context_id, created_at) VALUES (?, ?, ?)
+             ON CONFLICT (requirement_id, context_id) DO UPDATE SET status = 'accepted'",
             params![requirement_id, context_id, Utc::now().to_rfc3339()],
         ).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
@@ -966,4 +1017,77 @@ impl SpecificationRepository for SqliteSpecificationRepository {
 
         Ok(())
     }
+
+    async fn find_requirement_ids_by_context(&self, context_id: &str) -> Result<Vec<String>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        Self::linked_ids(
+            &db,
+            "SELECT requirement_id FROM requirement_context_links WHERE context_id = ? AND status = 'accepted'",
+            context_id,
+        )
+    }
+
+    async fn suggest_requirement_context_link(&self, link: &RequirementContextLink) -> Result<(), McpError> {
+        let db = self.db.lock().unwrap();
+
+        // Accepted links are kept as they are; earlier suggestions take the new score
+        db.execute(
+            "INSERT INTO requirement_context_links
+                (requirement_id, context_id, created_at, status, context_type, title, link_type, confidence)
+             VALUES (?, ?, ?, 'suggested', ?, ?, ?, ?)
+             ON CONFLICT (requirement_id, context_id) DO UPDATE SET
+                context_type = excluded.context_type,
+                title = excluded.title,
+                link_type = excluded.link_type,
+                confidence = excluded.confidence
+             WHERE status = 'suggested'",
+            params![
+                link.requirement_id,
+                link.context_id,
+                link.created_at.to_rfc3339(),
+                link.context_type,
+                link.title,
+                link.link_type,
+                link.confidence,
+            ],
+        ).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(())
+    }
+
+    async fn find_suggested_context_links(&self, spec_id: &str) -> Result<Vec<RequirementContextLink>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let mut stmt = db.prepare(
+            "SELECT l.requirement_id, l.context_id, l.context_type, l.title, l.link_type, l.confidence, l.status, l.created_at
+             FROM requirement_context_links l JOIN requirements r ON r.id = l.requirement_id
+             WHERE r.spec_id = ? AND l.status = 'suggested'
+             ORDER BY l.confidence DESC, l.requirement_id, l.context_id"
+        ).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let rows = stmt.query_map([spec_id], |row| {
+            Ok(RequirementContextLink {
+                requirement_id: row.get(0)?,
+                context_id: row.get(1)?,
+                context_type: row.get(2)?,
+                title: row.get(3)?,
+                link_type: row.get(4)?,
+                confidence: row.get(5)?,
+                status: ContextLinkStatus::parse(&row.get::<_, String>(6)?),
+                created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(7)?)
+                    .map_err(|_| rusqlite::Error::InvalidColumnType(7, "created_at".to_string(), rusqlite::types::Type::Text))?
+                    .with_timezone(&Utc),
+            })
+        }).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let mut links = Vec::new();
+        for row in rows {
+            match row {
+                Ok(link) => links.push(link),
+                Err(e) => tracing::warn!("Failed to parse suggested link: {}", e),
+            }
+        }
+        Ok(links)
+    }
 }
\ No newline at end of file
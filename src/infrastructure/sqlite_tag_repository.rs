@@ -0,0 +1,222 @@
+use crate::models::tagging::{ContextTag, TagSuggestion, TagSuggestionStatus, TaggedEntity};
+use crate::repositories::TagRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{Connection, OptionalExtension, Row};
+use std::sync::{Arc, Mutex};
+
+const TAG_COLUMNS: &str = "id, project_id, tag_name, category, color, description, created_at";
+const TAGGED_COLUMNS: &str = "id, project_id, entity_id, entity_type, tag_id, tagged_at";
+const SUGGESTION_COLUMNS: &str =
+    "id, project_id, entity_id, entity_type, tag_name, category, confidence, sources, status, suggested_at, reviewed_at";
+
+/// SQLite implementation of TagRepository
+pub struct SqliteTagRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteTagRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn tag_from_row(row: &Row) -> rusqlite::Result<ContextTag> {
+        Ok(ContextTag {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            tag_name: row.get(2)?,
+            category: row.get(3)?,
+            color: row.get(4)?,
+            description: row.get(5)?,
+            created_at: row.get(6)?,
+        })
+    }
+
+    fn tagged_from_row(row: &Row) -> rusqlite::Result<TaggedEntity> {
+        Ok(TaggedEntity {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            entity_id: row.get(2)?,
+            entity_type: row.get(3)?,
+            tag_id: row.get(4)?,
+            tagged_at: row.get(5)?,
+        })
+    }
+
+    fn suggestion_from_row(row: &Row) -> rusqlite::Result<TagSuggestion> {
+        let sources: String = row.get(7)?;
+        let status: String = row.get(8)?;
+        Ok(TagSuggestion {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            entity_id: row.get(2)?,
+            entity_type: row.get(3)?,
+            tag_name: row.get(4)?,
+            category: row.get(5)?,
+            confidence: row.get(6)?,
+            sources: serde_json::from_str(&sources).unwrap_or_default(),
+            status: TagSuggestionStatus::parse(&status).unwrap_or(TagSuggestionStatus::Pending),
+            suggested_at: row.get(9)?,
+            reviewed_at: row.get(10)?,
+        })
+    }
+
+    fn db_error(e: rusqlite::Error) -> McpError {
+        McpError::internal_error(format!("Database error: {}", e), None)
+    }
+}
+
+#[async_trait]
+impl TagRepository for SqliteTagRepository {
+    async fn find_or_create_tag(&self, tag: &ContextTag) -> Result<ContextTag, McpError> {
+        {
+            let db = self.db.lock().unwrap();
+            db.execute(
+                &format!("INSERT OR IGNORE INTO context_tags ({TAG_COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?)"),
+                (
+                    &tag.id,
+                    &tag.project_id,
+                    &tag.tag_name,
+                    &tag.category,
+                    tag.color.as_deref(),
+                    tag.description.as_deref(),
+                    &tag.created_at,
+                ),
+            )
+            .map_err(Self::db_error)?;
+        }
+        self.find_tag(&tag.project_id, &tag.tag_name)
+            .await?
+            .ok_or_else(|| McpError::internal_error(format!("Tag {} was not stored", tag.tag_name), None))
+    }
+
+    async fn find_tag(&self, project_id: &str, tag_name: &str) -> Result<Option<ContextTag>, McpError> {
+        let db = self.db.lock().unwrap();
+        db.query_row(
+            &format!("SELECT {TAG_COLUMNS} FROM context_tags WHERE project_id = ? AND tag_name = ?"),
+            (project_id, tag_name),
+            Self::tag_from_row,
+        )
+        .optional()
+        .map_err(Self::db_error)
+    }
+
+    async fn tag_entity(&self, tagged: &TaggedEntity) -> Result<bool, McpError> {
+        let db = self.db.lock().unwrap();
+        let inserted = db
+            .execute(
+                &format!("INSERT OR IGNORE INTO tagged_entities ({TAGGED_COLUMNS}) VALUES (?, ?, ?, ?, ?, ?)"),
+                (
+                    &tagged.id,
+                    &tagged.project_id,
+                    &tagged.entity_id,
+                    &tagged.entity_type,
+                    &tagged.tag_id,
+                    &tagged.tagged_at,
+                ),
+            )
+            .map_err(Self::db_error)?;
+        Ok(inserted > 0)
+    }
+
+    async fn untag_entity(&self, entity_id: &str, tag_id: &str) -> Result<bool, McpError> {
+        let db = self.db.lock().unwrap();
+        let removed = db
+            .execute(
+                "DELETE FROM tagged_entities WHERE entity_id = ? AND tag_id = ?",
+                (entity_id, tag_id),
+            )
+            .map_err(Self::db_error)?;
+        Ok(removed > 0)
+    }
+
+    async fn find_tagged_entities(&self, tag_id: &str) -> Result<Vec<TaggedEntity>, McpError> {
+        let db = self.db.lock().unwrap();
+        let mut stmt = db
+            .prepare(&format!("SELECT {TAGGED_COLUMNS} FROM tagged_entities WHERE tag_id = ? ORDER BY tagged_at"))
+            .map_err(Self::db_error)?;
+        let rows = stmt.query_map([tag_id], Self::tagged_from_row).map_err(Self::db_error)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(Self::db_error)
+    }
+
+    async fn list_tags(&self, project_id: &str) -> Result<Vec<ContextTag>, McpError> {
+        let db = self.db.lock().unwrap();
+        let mut stmt = db
+            .prepare(&format!("SELECT {TAG_COLUMNS} FROM context_tags WHERE project_id = ? ORDER BY tag_name"))
+            .map_err(Self::db_error)?;
+        let rows = stmt.query_map([project_id], Self::tag_from_row).map_err(Self::db_error)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(Self::db_error)
+    }
+
+    async fn save_suggestion(&self, suggestion: &TagSuggestion) -> Result<(), McpError> {
+        let sources = serde_json::to_string(&suggestion.sources)
+            .map_err(|e| McpError::internal_error(format!("Serialization error: {}", e), None))?;
+        let db = self.db.lock().unwrap();
+        db.execute(
+            &format!("INSERT OR REPLACE INTO tag_suggestions ({SUGGESTION_COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"),
+            rusqlite::params![
+                suggestion.id,
+                suggestion.project_id,
+                suggestion.entity_id,
+                suggestion.entity_type,
+                suggestion.tag_name,
+                suggestion.category,
+                suggestion.confidence,
+                sources,
+                suggestion.status.as_str(),
+                suggestion.suggested_at,
+                suggestion.reviewed_at,
+            ],
+        )
+        .map_err(Self::db_error)?;
+        Ok(())
+    }
+
+    async fn find_suggestion(&self, id: &str) -> Result<Option<TagSuggestion>, McpError> {
+        let db = self.db.lock().unwrap();
+        db.query_row(
+            &format!("SELECT {SUGGESTION_COLUMNS} FROM tag_suggestions WHERE id = ?"),
+            [id],
+            Self::suggestion_from_row,
+        )
+        .optional()
+        .map_err(Self::db_error)
+    }
+
+    async fn find_suggestions(
+        &self,
+        project_id: &str,
+        status: Option<TagSuggestionStatus>,
+    ) -> Result<Vec<TagSuggestion>, McpError> {
+        let db = self.db.lock().unwrap();
+        let mut stmt = db
+            .prepare(&format!(
+                "SELECT {SUGGESTION_COLUMNS} FROM tag_suggestions WHERE project_id = ?1 AND (?2 IS NULL OR status = ?2) \
+                 ORDER BY confidence DESC, suggested_at"
+            ))
+            .map_err(Self::db_error)?;
+        let rows = stmt
+            .query_map((project_id, status.map(|status| status.as_str())), Self::suggestion_from_row)
+            .map_err(Self::db_error)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(Self::db_error)
+    }
+
+    async fn find_entity_suggestions(&self, entity_id: &str) -> Result<Vec<TagSuggestion>, McpError> {
+        let db = self.db.lock().unwrap();
+        let mut stmt = db
+            .prepare(&format!(
+                "SELECT {SUGGESTION_COLUMNS} FROM tag_suggestions WHERE entity_id = ? ORDER BY confidence DESC"
+            ))
+            .map_err(Self::db_error)?;
+        let rows = stmt.query_map([entity_id], Self::suggestion_from_row).map_err(Self::db_error)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(Self::db_error)
+    }
+
+    async fn delete_suggestion(&self, id: &str) -> Result<bool, McpError> {
+        let db = self.db.lock().unwrap();
+        let removed = db
+            .execute("DELETE FROM tag_suggestions WHERE id = ?", [id])
+            .map_err(Self::db_error)?;
+        Ok(removed > 0)
+    }
+}
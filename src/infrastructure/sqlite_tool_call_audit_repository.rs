@@ -0,0 +1,116 @@
+use crate::models::tool_call_audit::{ToolCallAudit, ToolCallAuditFilter, ToolCallOutcome};
+use crate::repositories::ToolCallAuditRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{Connection, Row};
+use std::sync::{Arc, Mutex};
+
+const COLUMNS: &str =
+    "id, tool, caller, project_id, arguments_hash, outcome, error_code, error_message, duration_ms, created_at";
+
+/// SQLite implementation of ToolCallAuditRepository
+pub struct SqliteToolCallAuditRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteToolCallAuditRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<ToolCallAudit> {
+        let outcome: String = row.get(5)?;
+        Ok(ToolCallAudit {
+            id: row.get(0)?,
+            tool: row.get(1)?,
+            caller: row.get(2)?,
+            project_id: row.get(3)?,
+            arguments_hash: row.get(4)?,
+            outcome: ToolCallOutcome::from_name(&outcome).ok_or_else(|| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    5,
+                    rusqlite::types::Type::Text,
+                    format!("Unknown outcome: {}", outcome).into(),
+                )
+            })?,
+            error_code: row.get(6)?,
+            error_message: row.get(7)?,
+            duration_ms: row.get::<_, i64>(8)? as u64,
+            created_at: row.get(9)?,
+        })
+    }
+
+    fn db_error(e: rusqlite::Error) -> McpError {
+        McpError::internal_error(format!("Database error: {}", e), None)
+    }
+}
+
+#[async_trait]
+impl ToolCallAuditRepository for SqliteToolCallAuditRepository {
+    async fn record(&self, entry: &ToolCallAudit) -> Result<(), McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            &format!("INSERT INTO tool_call_audit ({COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"),
+            rusqlite::params![
+                entry.id,
+                entry.tool,
+                entry.caller,
+                entry.project_id,
+                entry.arguments_hash,
+                entry.outcome.as_str(),
+                entry.error_code,
+                entry.error_message,
+                entry.duration_ms as i64,
+                entry.created_at,
+            ],
+        )
+        .map_err(Self::db_error)?;
+
+        Ok(())
+    }
+
+    async fn find(&self, filter: &ToolCallAuditFilter) -> Result<Vec<ToolCallAudit>, McpError> {
+        let db = self.db.lock().unwrap();
+        let mut stmt = db
+            .prepare(&format!(
+                "SELECT {COLUMNS} FROM tool_call_audit
+                 WHERE (?1 IS NULL OR tool = ?1) AND (?2 IS NULL OR caller = ?2) AND (?3 IS NULL OR project_id = ?3)
+                   AND (?4 IS NULL OR outcome = ?4) AND (?5 IS NULL OR created_at >= ?5) AND (?6 IS NULL OR created_at <= ?6)
+                 ORDER BY created_at DESC LIMIT ?7"
+            ))
+            .map_err(Self::db_error)?;
+
+        let rows = stmt
+            .query_map(
+                rusqlite::params![
+                    filter.tool,
+                    filter.caller,
+                    filter.project_id,
+                    filter.outcome.map(|outcome| outcome.as_str()),
+                    filter.since.map(ToolCallAudit::timestamp),
+                    filter.until.map(ToolCallAudit::timestamp),
+                    filter.limit as i64,
+                ],
+                Self::from_row,
+            )
+            .map_err(Self::db_error)?;
+
+        let mut entries = Vec::new();
+        for entry in rows {
+            match entry {
+                Ok(entry) => entries.push(entry),
+                Err(e) => tracing::warn!("Failed to parse audit log entry: {}", e),
+            }
+        }
+        Ok(entries)
+    }
+
+    async fn prune(&self, before: DateTime<Utc>) -> Result<usize, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.execute("DELETE FROM tool_call_audit WHERE created_at < ?", [ToolCallAudit::timestamp(before)])
+            .map_err(Self::db_error)
+    }
+}
@@ -0,0 +1,125 @@
+use crate::models::traceability::{TraceabilityReport, TraceabilityRow, TraceabilitySummary, TracedTask};
+use crate::repositories::TraceabilityReportRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+const COLUMNS: &str = "id, project_id, spec_project, report, created_at";
+
+/// The parts of a report stored as JSON in the `report` column
+#[derive(Serialize, Deserialize)]
+struct StoredReport {
+    rows: Vec<TraceabilityRow>,
+    #[serde(default)]
+    untraced_tasks: Vec<TracedTask>,
+    summary: TraceabilitySummary,
+    commits_scanned: Option<usize>,
+}
+
+/// SQLite implementation of TraceabilityReportRepository
+pub struct SqliteTraceabilityReportRepository {
+    db: Arc<Mutex<Connection>>,
+}
+
+impl SqliteTraceabilityReportRepository {
+    pub fn new(db: Arc<Mutex<Connection>>) -> Self {
+        Self { db }
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<TraceabilityReport> {
+        let report: String = row.get(3)?;
+        let stored: StoredReport = serde_json::from_str(&report).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+        Ok(TraceabilityReport {
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            spec_project: row.get(2)?,
+            rows: stored.rows,
+            untraced_tasks: stored.untraced_tasks,
+            summary: stored.summary,
+            commits_scanned: stored.commits_scanned,
+            created_at: row.get(4)?,
+        })
+    }
+
+    fn report_json(report: &TraceabilityReport) -> Result<String, McpError> {
+        serde_json::to_string(&StoredReport {
+            rows: report.rows.clone(),
+            untraced_tasks: report.untraced_tasks.clone(),
+            summary: report.summary.clone(),
+            commits_scanned: report.commits_scanned,
+        })
+        .map_err(|e| McpError::internal_error(format!("Serialization error: {}", e), None))
+    }
+}
+
+#[async_trait]
+impl TraceabilityReportRepository for SqliteTraceabilityReportRepository {
+    async fn create(&self, report: &TraceabilityReport) -> Result<TraceabilityReport, McpError> {
+        let json = Self::report_json(report)?;
+        let db = self.db.lock().unwrap();
+
+        db.execute(
+            &format!("INSERT INTO traceability_reports ({COLUMNS}) VALUES (?, ?, ?, ?, ?)"),
+            (
+                &report.id,
+                &report.project_id,
+                &report.spec_project,
+                json,
+                report.created_at.as_deref(),
+            ),
+        )
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(report.clone())
+    }
+
+    async fn find_by_project_id(&self, project_id: &str) -> Result<Vec<TraceabilityReport>, McpError> {
+        let db = self.db.lock().unwrap();
+        let mut reports = Vec::new();
+
+        let mut stmt = db
+            .prepare(&format!(
+                "SELECT {COLUMNS} FROM traceability_reports WHERE project_id = ? ORDER BY created_at DESC"
+            ))
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        let rows = stmt
+            .query_map([project_id], Self::from_row)
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        for report in rows {
+            match report {
+                Ok(report) => reports.push(report),
+                Err(e) => tracing::warn!("Failed to parse traceability report: {}", e),
+            }
+        }
+
+        Ok(reports)
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<TraceabilityReport>, McpError> {
+        let db = self.db.lock().unwrap();
+
+        db.query_row(
+            &format!("SELECT {COLUMNS} FROM traceability_reports WHERE id = ?"),
+            [id],
+            Self::from_row,
+        )
+        .optional()
+        .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, McpError> {
+        let db = self.db.lock().unwrap();
+
+        let rows_affected = db
+            .execute("DELETE FROM traceability_reports WHERE id = ?", [id])
+            .map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
+
+        Ok(rows_affected > 0)
+    }
+}
@@ -1,10 +1,12 @@
 pub mod api;
 pub mod cache;
 pub mod cli;
+pub mod config;
 pub mod container;
 pub mod db;
 pub mod enhanced_context_server;
 pub mod infrastructure;
+pub mod logging;
 pub mod models;
 pub mod repositories;
 pub mod services;
@@ -12,6 +14,7 @@ pub mod services;
 // Re-export common types
 pub use cache::QueryCache;
 pub use cli::CliRouter;
+pub use config::AppConfig;
 pub use container::AppContainer;
 pub use db::connection_pool::{ConnectionPool, PoolConfig, PoolStats};
 pub use enhanced_context_server::EnhancedContextMcpServer;
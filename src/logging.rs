@@ -0,0 +1,121 @@
+//! Logging setup driven by the `[logging]` config section
+//! Always logs to stderr (stdout carries MCP traffic); optionally to rotating files as well
+
+use anyhow::Result;
+use std::path::Path;
+use tracing::Subscriber;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{fmt, prelude::*, reload, EnvFilter, Layer, Registry};
+
+use crate::config::{LogFormat, LogRotation, LoggingConfig};
+
+/// File name prefix of rotated log files
+const LOG_FILE_PREFIX: &str = "context-server";
+
+/// Installed logging; keep it alive for the lifetime of the process so file output is flushed
+pub struct Logging {
+    handle: reload::Handle<EnvFilter, Registry>,
+    is_cli_mode: bool,
+    _file_guard: Option<WorkerGuard>,
+}
+
+impl Logging {
+    /// Install the global subscriber. `default_log_dir` is used when file logging
+    /// is enabled without an explicit directory.
+    pub fn init(config: &LoggingConfig, default_log_dir: &Path, is_cli_mode: bool) -> Result<Self> {
+        let (filter_layer, handle) = reload::Layer::new(build_filter(config, is_cli_mode));
+
+        let mut file_guard = None;
+        let file_layer = if config.file.enabled && !is_cli_mode {
+            let directory = config.file.directory.as_deref().unwrap_or(default_log_dir);
+            std::fs::create_dir_all(directory)?;
+            let mut builder = RollingFileAppender::builder()
+                .rotation(rotation(config.file.rotation))
+                .filename_prefix(LOG_FILE_PREFIX)
+                .filename_suffix("log");
+            if config.file.max_files > 0 {
+                builder = builder.max_log_files(config.file.max_files);
+            }
+            let appender = builder.build(directory)?;
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            file_guard = Some(guard);
+            Some(fmt_layer(config.format, writer))
+        } else {
+            None
+        };
+
+        tracing_subscriber::registry()
+            .with(filter_layer)
+            .with(fmt_layer(config.format, std::io::stderr))
+            .with(file_layer)
+            .try_init()?;
+
+        Ok(Self {
+            handle,
+            is_cli_mode,
+            _file_guard: file_guard,
+        })
+    }
+
+    /// Apply changed level and per-module filters without reinstalling the subscriber
+    pub fn reload(&self, config: &LoggingConfig) {
+        if let Err(e) = self.handle.reload(build_filter(config, self.is_cli_mode)) {
+            tracing::warn!("Failed to apply new log filter: {}", e);
+        }
+    }
+}
+
+/// Build the filter from `[logging]`; `RUST_LOG` takes precedence over `level` when set
+pub fn build_filter(config: &LoggingConfig, is_cli_mode: bool) -> EnvFilter {
+    let mut filter = match &config.level {
+        Some(level) if std::env::var_os(EnvFilter::DEFAULT_ENV).is_none() => {
+            EnvFilter::try_new(level).unwrap_or_else(|e| {
+                eprintln!("Ignoring invalid [logging] level '{level}': {e}");
+                mode_default_filter(is_cli_mode)
+            })
+        }
+        _ => mode_default_filter(is_cli_mode),
+    };
+
+    for (module, level) in &config.modules {
+        match format!("{module}={level}").parse() {
+            Ok(directive) => filter = filter.add_directive(directive),
+            Err(e) => eprintln!("Ignoring invalid [logging.modules] entry '{module}': {e}"),
+        }
+    }
+
+    filter
+}
+
+fn mode_default_filter(is_cli_mode: bool) -> EnvFilter {
+    if is_cli_mode {
+        // Quiet logging for CLI mode
+        EnvFilter::from_default_env().add_directive(tracing::Level::WARN.into())
+    } else {
+        // Verbose logging for server mode
+        EnvFilter::from_default_env().add_directive(tracing::Level::DEBUG.into())
+    }
+}
+
+fn fmt_layer<S, W>(format: LogFormat, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => fmt::layer().with_writer(writer).with_ansi(false).boxed(),
+        LogFormat::Json => fmt::layer().json().with_writer(writer).boxed(),
+    }
+}
+
+fn rotation(rotation: LogRotation) -> Rotation {
+    match rotation {
+        LogRotation::Minutely => Rotation::MINUTELY,
+        LogRotation::Hourly => Rotation::HOURLY,
+        LogRotation::Daily => Rotation::DAILY,
+        LogRotation::Never => Rotation::NEVER,
+    }
+}
@@ -1,4 +1,5 @@
 mod api;
+mod cache;
 mod container;
 mod context_server;
 mod context_server_solid;
@@ -9,46 +10,19 @@ mod models;
 mod repositories;
 mod services;
 mod cli;
+mod config;
+mod logging;
 
 use anyhow::Result;
 use clap::Parser;
 use cli::router::{Cli, Commands, CliRouter};
+use config::{AppConfig, AppDirs, ConfigManager};
 use db::init::init_db;
 use enhanced_context_server::EnhancedContextMcpServer;
 use rmcp::{transport::stdio, ServiceExt};
-use std::fs;
-use std::path::PathBuf;
-use tracing_subscriber::{self, EnvFilter};
-
-/// Get the config directory path for the context server
-fn get_config_dir() -> Result<PathBuf> {
-    let home_dir =
-        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
-
-    let config_dir = home_dir.join(".config").join("context-server-rs");
-
-    // Create the config directory if it doesn't exist
-    if !config_dir.exists() {
-        fs::create_dir_all(&config_dir)?;
-        tracing::debug!("Created config directory: {}", config_dir.display());
-    }
-
-    Ok(config_dir)
-}
-
-/// Get the database path, either from CLI argument or default location
-fn get_db_path(cli_db: Option<String>) -> Result<String> {
-    if let Some(db_path) = cli_db {
-        Ok(db_path)
-    } else {
-        let config_dir = get_config_dir()?;
-        let db_path = config_dir.join("context.db");
-        db_path
-            .to_str()
-            .map(|s| s.to_string())
-            .ok_or_else(|| anyhow::anyhow!("Invalid database path"))
-    }
-}
+use services::WebSocketServer;
+use logging::Logging;
+use std::sync::Arc;
 
 /// Enhanced MCP Context Server for AI Code Generation with SOLID Architecture
 ///
@@ -61,30 +35,62 @@ fn get_db_path(cli_db: Option<String>) -> Result<String> {
 /// high-value context to help AI agents generate better production-quality code.
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize logging - adjust level based on mode (query is CLI, serve is server)
+    // Logging defaults depend on mode (query is CLI, serve is server)
     let is_cli_mode = std::env::args().any(|arg| 
-        arg == "query" || arg == "list" || arg == "search" || arg == "get"
+        arg == "query" || arg == "list" || arg == "search" || arg == "get" || arg == "dashboard" || arg == "call" || arg == "completions"
     );
-    
-    let env_filter = if is_cli_mode {
-        // Quiet logging for CLI mode
-        EnvFilter::from_default_env().add_directive(tracing::Level::WARN.into())
-    } else {
-        // Verbose logging for server mode
-        EnvFilter::from_default_env().add_directive(tracing::Level::DEBUG.into())
-    };
-
-    tracing_subscriber::fmt()
-        .with_env_filter(env_filter)
-        .with_writer(std::io::stderr)
-        .with_ansi(false)
-        .init();
 
     // Parse CLI arguments
     let cli = Cli::parse();
+
+    // Completion scripts don't need the database
+    if let Commands::Completions { shell, aliases } = cli.command {
+        cli::completions::print_completions(shell, aliases);
+        return Ok(());
+    }
     
-    // Get database path
-    let db_path = get_db_path(cli.db)?;
+    // Load config.toml; --db / CONTEXT_SERVER_DB take precedence over [database] path
+    let dirs = AppDirs::resolve(cli.config_dir.as_deref())?;
+    let mut config = AppConfig::load(cli.config.as_deref(), &dirs, cli.profile.as_deref())?;
+    if let Some(db) = cli.db {
+        config.database.path = Some(db.into());
+    }
+    if config.attachments.directory.is_none() {
+        config.attachments.directory = Some(dirs.default_attachments_dir());
+    }
+    if config.exports.directory.is_none() {
+        config.exports.directory = Some(dirs.default_exports_dir());
+    }
+    if let Commands::Serve { ws: Some(address), .. } = &cli.command {
+        config.websocket.listen_on(*address);
+    }
+
+    // Initialize logging as configured by [logging]
+    let logging = Logging::init(&config.logging, &dirs.default_log_dir(), is_cli_mode)?;
+
+    if let Some(profile) = &config.profile {
+        tracing::debug!("Using config profile: {}", profile);
+    }
+    if config.auth.required && !config.auth.has_tokens() {
+        tracing::warn!("[auth] required is set but no tokens are configured; network clients will be rejected");
+    }
+
+    let db_path = config.resolve_db_path(cli.config_dir.as_deref())?;
+    // Earlier releases' databases only move into the default location, never into a --config-dir
+    if config.database.path.is_none() && cli.config_dir.is_none() {
+        if let Some(legacy) = config::migrate_legacy_db(&db_path, &config::legacy_db_paths())? {
+            tracing::info!(
+                "Migrated database from {} to {}",
+                legacy.display(),
+                db_path.display()
+            );
+        }
+    }
+    let db_path = db_path
+        .to_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow::anyhow!("Invalid database path"))?;
+    config.database.path = Some(db_path.clone().into());
 
     tracing::debug!("Using database: {}", db_path);
 
@@ -101,11 +107,54 @@ async fn main() -> Result<()> {
 
     // Route based on command
     match &cli.command {
-        Commands::Serve { port: _ } => {
+        Commands::Serve { .. } => {
             // Run MCP server mode
             tracing::info!("Starting MCP Context Server");
             
-            let service = EnhancedContextMcpServer::new(&db_path)?
+            // Optionally pre-warm the query cache from the previous run
+            let persistence = config.cache.persistence.clone();
+            let snapshot_path = persistence
+                .path
+                .clone()
+                .unwrap_or_else(|| dirs.default_cache_snapshot_path());
+
+            let websocket = config.websocket.clone();
+
+            // Reload config.toml on save; apply log filter changes as they arrive
+            let config_source = cli.config.clone().unwrap_or_else(|| dirs.config_file());
+            let config_manager = Arc::new(ConfigManager::new(config, Some(config_source)));
+            if let Err(e) = config_manager.watch() {
+                tracing::warn!("Config hot-reload disabled: {}", e);
+            }
+            let mut config_updates = config_manager.subscribe();
+            tokio::spawn(async move {
+                while config_updates.changed().await.is_ok() {
+                    let logging_config = config_updates.borrow_and_update().logging.clone();
+                    logging.reload(&logging_config);
+                }
+            });
+
+            let server = EnhancedContextMcpServer::with_config_manager(config_manager)?;
+            let query_cache = server.query_cache();
+            if persistence.enabled {
+                match query_cache.load_snapshot(&snapshot_path) {
+                    Ok(loaded) => tracing::info!("Pre-warmed query cache with {} entries", loaded),
+                    Err(e) => tracing::warn!("Ignoring unreadable cache snapshot {}: {}", snapshot_path.display(), e),
+                }
+            }
+
+            // WebSocket clients share the container, so they see changes made by MCP tool calls
+            if websocket.enabled {
+                let ws_server = WebSocketServer::with_manager(websocket.bind_address()?, server.websocket_manager());
+                let listener = ws_server.bind().await?;
+                tokio::spawn(async move {
+                    if let Err(e) = ws_server.serve(listener).await {
+                        tracing::error!("WebSocket server stopped: {}", e);
+                    }
+                });
+            }
+
+            let service = server
                 .serve(stdio())
                 .await
                 .inspect_err(|e| {
@@ -114,14 +163,28 @@ async fn main() -> Result<()> {
 
             tracing::info!("Enhanced MCP Context Server started successfully");
 
-            // Wait for the service to complete
-            service.waiting().await?;
+            // Wait for the client to disconnect or for Ctrl-C
+            tokio::select! {
+                result = service.waiting() => {
+                    result?;
+                }
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Interrupted, shutting down");
+                }
+            }
+
+            if persistence.enabled {
+                match query_cache.save_snapshot(&snapshot_path, &persistence.namespaces) {
+                    Ok(saved) => tracing::info!("Saved {} cache entries to {}", saved, snapshot_path.display()),
+                    Err(e) => tracing::warn!("Failed to save cache snapshot: {}", e),
+                }
+            }
 
             Ok(())
         }
         _ => {
-            // Run CLI mode: Query, List, Search, Get
-            let router = CliRouter::new(db_path, cli.format, cli.project);
+            // Run CLI mode: Query, List, Search, Get, Dashboard
+            let router = CliRouter::new(db_path, cli.format, cli.project).with_config(config);
             router.route(cli.command).await?;
             
             Ok(())
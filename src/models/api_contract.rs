@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The authoritative shape of one endpoint: its method and path, the JSON Schemas of its
+/// request and response, and how it reports errors
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiContract {
+    pub id: String,
+    pub project_id: String,
+    /// One of `ApiContract::METHODS`
+    pub method: String,
+    /// Path template, e.g. `/orders/{order_id}`
+    pub path: String,
+    pub description: Option<String>,
+    /// JSON Schema of the request body
+    pub request_schema: Option<Value>,
+    /// JSON Schema of the successful response body
+    pub response_schema: Option<Value>,
+    /// Status codes, error bodies and retry behaviour callers should expect
+    pub error_conventions: Option<String>,
+    /// Feature contexts the endpoint serves
+    #[serde(default)]
+    pub feature_context_ids: Vec<String>,
+    /// Framework components handling or calling the endpoint
+    #[serde(default)]
+    pub component_ids: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl ApiContract {
+    pub const METHODS: &'static [&'static str] = &["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+    pub fn new(project_id: &str, method: &str, path: &str) -> Self {
+        let now = chrono::Utc::now().to_rfc3339();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            project_id: project_id.to_string(),
+            method: method.to_uppercase(),
+            path: path.to_string(),
+            description: None,
+            request_schema: None,
+            response_schema: None,
+            error_conventions: None,
+            feature_context_ids: Vec::new(),
+            component_ids: Vec::new(),
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+
+    /// `POST /orders`
+    pub fn signature(&self) -> String {
+        format!("{} {}", self.method, self.path)
+    }
+
+    /// Reject an unknown method, a path not starting with `/` and schemas that aren't objects
+    pub fn check(&self) -> Result<(), String> {
+        if !Self::METHODS.contains(&self.method.as_str()) {
+            return Err(format!("method must be one of {}", Self::METHODS.join(", ")));
+        }
+        if !self.path.starts_with('/') {
+            return Err("path must start with /".to_string());
+        }
+        for (field, schema) in [("request_schema", &self.request_schema), ("response_schema", &self.response_schema)] {
+            if schema.as_ref().is_some_and(|schema| !schema.is_object()) {
+                return Err(format!("{field} must be a JSON Schema object"));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn links_to(&self, entity_id: &str) -> bool {
+        self.feature_context_ids.iter().chain(&self.component_ids).any(|id| id == entity_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_contract_checks() {
+        let mut contract = ApiContract::new("p1", "post", "/orders");
+        assert_eq!(contract.signature(), "POST /orders");
+        contract.response_schema = Some(json!({"type": "object", "properties": {"id": {"type": "string"}}}));
+        contract.component_ids.push("c1".to_string());
+        assert!(contract.check().is_ok());
+        assert!(contract.links_to("c1"));
+        assert!(!contract.links_to("f1"));
+
+        contract.request_schema = Some(json!("order"));
+        assert_eq!(contract.check().unwrap_err(), "request_schema must be a JSON Schema object");
+        assert!(ApiContract::new("p1", "FETCH", "/orders").check().is_err());
+        assert!(ApiContract::new("p1", "GET", "orders").check().is_err());
+    }
+}
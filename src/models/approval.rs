@@ -0,0 +1,192 @@
+use crate::models::specification::SpecStatus;
+use serde::{Deserialize, Serialize};
+
+/// Review state of a specification or architectural decision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowState {
+    Draft,
+    InReview,
+    Approved,
+    Deprecated,
+}
+
+impl WorkflowState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkflowState::Draft => "draft",
+            WorkflowState::InReview => "in_review",
+            WorkflowState::Approved => "approved",
+            WorkflowState::Deprecated => "deprecated",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "draft" => Some(WorkflowState::Draft),
+            "in_review" => Some(WorkflowState::InReview),
+            "approved" => Some(WorkflowState::Approved),
+            "deprecated" => Some(WorkflowState::Deprecated),
+            _ => None,
+        }
+    }
+
+    /// Draft goes to review, review ends in approval or back in draft, and approved items are
+    /// eventually deprecated
+    pub fn can_transition_to(&self, to: WorkflowState) -> bool {
+        matches!(
+            (self, to),
+            (WorkflowState::Draft, WorkflowState::InReview)
+                | (WorkflowState::InReview, WorkflowState::Draft)
+                | (WorkflowState::InReview, WorkflowState::Approved)
+                | (WorkflowState::Approved, WorkflowState::Deprecated)
+        )
+    }
+
+    /// States a transition from this one may go to
+    pub fn next_states(&self) -> Vec<WorkflowState> {
+        [
+            WorkflowState::Draft,
+            WorkflowState::InReview,
+            WorkflowState::Approved,
+            WorkflowState::Deprecated,
+        ]
+        .into_iter()
+        .filter(|to| self.can_transition_to(*to))
+        .collect()
+    }
+
+    /// Specifications being worked on or done were approved first
+    pub fn from_spec_status(status: &SpecStatus) -> Self {
+        match status {
+            SpecStatus::Draft => WorkflowState::Draft,
+            SpecStatus::InReview => WorkflowState::InReview,
+            SpecStatus::Approved | SpecStatus::InProgress | SpecStatus::Completed => WorkflowState::Approved,
+            SpecStatus::Archived | SpecStatus::Deprecated => WorkflowState::Deprecated,
+        }
+    }
+
+    pub fn spec_status(&self) -> SpecStatus {
+        match self {
+            WorkflowState::Draft => SpecStatus::Draft,
+            WorkflowState::InReview => SpecStatus::InReview,
+            WorkflowState::Approved => SpecStatus::Approved,
+            WorkflowState::Deprecated => SpecStatus::Deprecated,
+        }
+    }
+
+    /// ADR statuses (`proposed`, `accepted`, `superseded by …`) in workflow terms. Decisions
+    /// without a status predate the workflow and count as approved.
+    pub fn from_decision_status(status: Option<&str>) -> Self {
+        let Some(status) = status.map(|s| s.trim().to_lowercase()) else {
+            return WorkflowState::Approved;
+        };
+        match status.as_str() {
+            "draft" => WorkflowState::Draft,
+            "proposed" | "in_review" => WorkflowState::InReview,
+            "deprecated" | "rejected" => WorkflowState::Deprecated,
+            s if s.starts_with("superseded") => WorkflowState::Deprecated,
+            _ => WorkflowState::Approved,
+        }
+    }
+
+    /// Status written to a decision entering this state
+    pub fn decision_status(&self) -> &'static str {
+        match self {
+            WorkflowState::Draft => "draft",
+            WorkflowState::InReview => "proposed",
+            WorkflowState::Approved => "accepted",
+            WorkflowState::Deprecated => "deprecated",
+        }
+    }
+}
+
+/// Kind of item that goes through the approval workflow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowEntity {
+    Specification,
+    ArchitecturalDecision,
+}
+
+impl WorkflowEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkflowEntity::Specification => "specification",
+            WorkflowEntity::ArchitecturalDecision => "architectural_decision",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "specification" => Some(WorkflowEntity::Specification),
+            "architectural_decision" => Some(WorkflowEntity::ArchitecturalDecision),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Approval {
+    pub approver: String,
+    pub comment: Option<String>,
+    pub approved_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowTransition {
+    pub from: WorkflowState,
+    pub to: WorkflowState,
+    pub actor: String,
+    pub comment: Option<String>,
+    pub at: String,
+}
+
+/// Where a specification or decision stands in review, who has to approve it and who has
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalWorkflow {
+    pub entity_type: WorkflowEntity,
+    pub entity_id: String,
+    pub project_id: String,
+    pub state: WorkflowState,
+    /// Approvers who must all approve before the item is approved; when empty, the
+    /// configured number of approvals from anyone is enough
+    pub required_approvers: Vec<String>,
+    /// Approvals of the current review round
+    pub approvals: Vec<Approval>,
+    pub history: Vec<WorkflowTransition>,
+    pub updated_at: Option<String>,
+}
+
+impl ApprovalWorkflow {
+    pub fn new(entity_type: WorkflowEntity, entity_id: String, project_id: String, state: WorkflowState) -> Self {
+        Self {
+            entity_type,
+            entity_id,
+            project_id,
+            state,
+            required_approvers: Vec::new(),
+            approvals: Vec::new(),
+            history: Vec::new(),
+            updated_at: None,
+        }
+    }
+
+    /// Required approvers who have not approved yet
+    pub fn pending_approvers(&self) -> Vec<String> {
+        self.required_approvers
+            .iter()
+            .filter(|approver| !self.approvals.iter().any(|a| &a.approver == *approver))
+            .cloned()
+            .collect()
+    }
+
+    /// Whether every required approver, or `min_approvals` approvers when none are named, approved
+    pub fn is_fully_approved(&self, min_approvals: usize) -> bool {
+        if self.required_approvers.is_empty() {
+            self.approvals.len() >= min_approvals.max(1)
+        } else {
+            self.pending_approvers().is_empty()
+        }
+    }
+}
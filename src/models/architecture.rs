@@ -1,3 +1,5 @@
+use crate::models::context::ProjectConvention;
+use crate::models::framework::FrameworkComponent;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
@@ -79,3 +81,523 @@ impl fmt::Display for ComponentType {
         }
     }
 }
+
+/// A layer of a project's architecture and the layers it may depend on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayerRule {
+    /// Matched case-insensitively against a component's `architecture_layer`
+    pub name: String,
+    /// Fragments, such as `data/`, that mark a dependency path as belonging to this layer
+    #[serde(default)]
+    pub path_patterns: Vec<String>,
+    /// Layers this layer may depend on besides itself; `None` allows any layer
+    #[serde(default)]
+    pub may_depend_on: Option<Vec<String>>,
+    /// Severity of dependencies on other layers; `error` unless set
+    #[serde(default)]
+    pub severity: Option<Severity>,
+}
+
+/// A dependency no component, or no component of the given layers, may have
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForbiddenImport {
+    /// Regular expression matched against each dependency
+    pub pattern: String,
+    /// Layers the rule applies to; empty for every layer
+    #[serde(default)]
+    pub layers: Vec<String>,
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// `error` unless set
+    #[serde(default)]
+    pub severity: Option<Severity>,
+}
+
+/// A pattern the names of components of a layer or type must match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamingPattern {
+    /// Regular expression matched against the component name
+    pub pattern: String,
+    #[serde(default)]
+    pub layer: Option<String>,
+    #[serde(default)]
+    pub component_type: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// `warning` unless set
+    #[serde(default)]
+    pub severity: Option<Severity>,
+}
+
+/// Quantity a fitness function measures; lower is better for each
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FitnessMetric {
+    /// Components in a layer
+    ComponentsPerLayer,
+    /// Components depending on one component
+    FanIn,
+    /// Components one component depends on
+    FanOut,
+    /// Outgoing share of a layer's dependencies on other layers, from 0 (stable) to 1
+    Instability,
+}
+
+impl fmt::Display for FitnessMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FitnessMetric::ComponentsPerLayer => write!(f, "components_per_layer"),
+            FitnessMetric::FanIn => write!(f, "fan_in"),
+            FitnessMetric::FanOut => write!(f, "fan_out"),
+            FitnessMetric::Instability => write!(f, "instability"),
+        }
+    }
+}
+
+/// A metric that must stay at or below a threshold, for every layer or component or only
+/// those of one layer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FitnessFunction {
+    pub name: String,
+    pub metric: FitnessMetric,
+    pub threshold: f64,
+    #[serde(default)]
+    pub layer: Option<String>,
+    /// `error` unless set
+    #[serde(default)]
+    pub severity: Option<Severity>,
+}
+
+/// Architecture rules `validate_architecture` checks a project's components against
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchitectureRuleSet {
+    pub project_id: String,
+    #[serde(default)]
+    pub layers: Vec<LayerRule>,
+    #[serde(default)]
+    pub forbidden_imports: Vec<ForbiddenImport>,
+    #[serde(default)]
+    pub naming_patterns: Vec<NamingPattern>,
+    #[serde(default)]
+    pub fitness_functions: Vec<FitnessFunction>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+impl ArchitectureRuleSet {
+    /// Clean Architecture as validated before projects could define their own rules:
+    /// presentation must not reach into data and domain depends on nothing outside itself and core
+    pub fn clean_architecture(project_id: &str) -> Self {
+        let layer = |name: &str, may_depend_on: Option<&[&str]>| LayerRule {
+            name: name.to_string(),
+            path_patterns: vec![format!("{name}/")],
+            may_depend_on: may_depend_on.map(|layers| layers.iter().map(|l| l.to_string()).collect()),
+            severity: None,
+        };
+        Self {
+            project_id: project_id.to_string(),
+            layers: vec![
+                layer("presentation", Some(&["domain", "core"])),
+                layer("domain", Some(&["core"])),
+                layer("data", None),
+                layer("core", None),
+            ],
+            forbidden_imports: Vec::new(),
+            naming_patterns: Vec::new(),
+            fitness_functions: Vec::new(),
+            updated_at: None,
+        }
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&LayerRule> {
+        self.layers.iter().find(|layer| layer.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Layers a dependency path belongs to by their path patterns
+    pub fn layers_of_dependency(&self, dependency: &str) -> Vec<&LayerRule> {
+        self.layers
+            .iter()
+            .filter(|layer| layer.path_patterns.iter().any(|pattern| dependency.contains(pattern.as_str())))
+            .collect()
+    }
+
+    /// Problems that keep the rule set from being evaluated: unknown or duplicate layers and
+    /// invalid regular expressions
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (index, layer) in self.layers.iter().enumerate() {
+            if layer.name.trim().is_empty() {
+                problems.push(format!("Layer {} has no name", index + 1));
+            } else if self.layers[..index].iter().any(|other| other.name.eq_ignore_ascii_case(&layer.name)) {
+                problems.push(format!("Layer {} is defined more than once", layer.name));
+            }
+            for target in layer.may_depend_on.iter().flatten() {
+                if self.layer(target).is_none() {
+                    problems.push(format!("Layer {} may depend on unknown layer {}", layer.name, target));
+                }
+            }
+        }
+        let mut check_layer = |layer: &str, rule: &str| {
+            if self.layer(layer).is_none() {
+                problems.push(format!("{} refers to unknown layer {}", rule, layer));
+            }
+        };
+        for rule in &self.forbidden_imports {
+            for layer in &rule.layers {
+                check_layer(layer, &format!("Forbidden import {}", rule.pattern));
+            }
+        }
+        for rule in &self.naming_patterns {
+            if let Some(layer) = &rule.layer {
+                check_layer(layer, &format!("Naming pattern {}", rule.pattern));
+            }
+        }
+        for function in &self.fitness_functions {
+            if let Some(layer) = &function.layer {
+                check_layer(layer, &format!("Fitness function {}", function.name));
+            }
+        }
+        for (index, function) in self.fitness_functions.iter().enumerate() {
+            if function.name.trim().is_empty() {
+                problems.push(format!("Fitness function {} has no name", index + 1));
+            } else if self.fitness_functions[..index].iter().any(|other| other.name == function.name) {
+                problems.push(format!("Fitness function {} is defined more than once", function.name));
+            }
+            let upper = if function.metric == FitnessMetric::Instability { 1.0 } else { f64::INFINITY };
+            if !(0.0..=upper).contains(&function.threshold) {
+                problems.push(format!("Fitness function {} has an out of range threshold {}", function.name, function.threshold));
+            }
+        }
+        let patterns = self
+            .forbidden_imports
+            .iter()
+            .map(|rule| &rule.pattern)
+            .chain(self.naming_patterns.iter().map(|rule| &rule.pattern));
+        for pattern in patterns {
+            if let Err(e) = regex::Regex::new(pattern) {
+                problems.push(format!("Invalid pattern {}: {}", pattern, e));
+            }
+        }
+        problems
+    }
+}
+
+/// What a naming convention constrains
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConventionTarget {
+    ComponentName,
+    /// The last segment of the component's file path
+    FileName,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConventionMatcher {
+    Regex(String),
+    Prefix(String),
+    Suffix(String),
+}
+
+impl ConventionMatcher {
+    pub fn matches(&self, name: &str) -> Result<bool, regex::Error> {
+        Ok(match self {
+            ConventionMatcher::Regex(pattern) => regex::Regex::new(pattern)?.is_match(name),
+            ConventionMatcher::Prefix(prefix) => name.starts_with(prefix.as_str()),
+            ConventionMatcher::Suffix(suffix) => name.ends_with(suffix.as_str()),
+        })
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            ConventionMatcher::Regex(pattern) => format!("match {}", pattern),
+            ConventionMatcher::Prefix(prefix) => format!("start with {}", prefix),
+            ConventionMatcher::Suffix(suffix) => format!("end with {}", suffix),
+        }
+    }
+}
+
+/// The checkable part of a `naming` or `file_naming` project convention. Its rule is
+/// `regex: <pattern>`, `prefix: <text>`, `suffix: <text>` or `/<pattern>/`, optionally scoped
+/// to a layer or component type by naming it first, as in `service suffix: Service`; other
+/// rules are prose and are not checked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConventionPattern {
+    pub convention_id: String,
+    pub target: ConventionTarget,
+    /// Layer or component type the convention applies to; every component when `None`
+    pub scope: Option<String>,
+    pub matcher: ConventionMatcher,
+    pub rationale: Option<String>,
+}
+
+impl ConventionPattern {
+    pub fn from_convention(convention: &ProjectConvention) -> Option<Self> {
+        let kind = convention.convention_type.as_deref()?.trim().to_lowercase().replace(['-', ' '], "_");
+        let target = match kind.as_str() {
+            "naming" | "component_naming" | "naming_convention" => ConventionTarget::ComponentName,
+            "file_naming" | "file_name" | "file_names" => ConventionTarget::FileName,
+            _ => return None,
+        };
+        let rule = convention.convention_rule.as_deref()?.trim();
+
+        let (scope, matcher) = if rule.len() > 2 && rule.starts_with('/') && rule.ends_with('/') {
+            (None, ConventionMatcher::Regex(rule[1..rule.len() - 1].to_string()))
+        } else {
+            let (head, value) = rule.split_once(':')?;
+            let value = value.trim().to_string();
+            let mut words: Vec<&str> = head.split_whitespace().collect();
+            let matcher = match words.pop()?.to_lowercase().as_str() {
+                "regex" | "pattern" => ConventionMatcher::Regex(value),
+                "prefix" => ConventionMatcher::Prefix(value),
+                "suffix" => ConventionMatcher::Suffix(value),
+                _ => return None,
+            };
+            let scope = (!words.is_empty()).then(|| words.join(" "));
+            (scope, matcher)
+        };
+
+        Some(Self {
+            convention_id: convention.id.clone(),
+            target,
+            scope,
+            matcher,
+            rationale: convention.rationale.clone().filter(|rationale| !rationale.trim().is_empty()),
+        })
+    }
+
+    /// Name of the component the convention checks, if it applies to the component at all
+    pub fn subject<'a>(&self, component: &'a FrameworkComponent) -> Option<&'a str> {
+        if let Some(scope) = &self.scope {
+            if !scope.eq_ignore_ascii_case(&component.architecture_layer)
+                && !scope.eq_ignore_ascii_case(&component.component_type)
+            {
+                return None;
+            }
+        }
+        match self.target {
+            ConventionTarget::ComponentName => Some(component.component_name.as_str()),
+            ConventionTarget::FileName => {
+                let path = component.file_path.as_deref()?;
+                path.rsplit(['/', '\\']).next().filter(|name| !name.is_empty())
+            }
+        }
+    }
+
+    /// A name that follows a prefix or suffix convention; file suffixes go before the
+    /// extension unless they carry one, as in `_service.dart`
+    pub fn fixed_name(&self, name: &str) -> Option<String> {
+        match (&self.matcher, self.target) {
+            (ConventionMatcher::Regex(_), _) => None,
+            (ConventionMatcher::Prefix(prefix), _) => Some(format!("{}{}", prefix, name)),
+            (ConventionMatcher::Suffix(suffix), ConventionTarget::ComponentName) => Some(format!("{}{}", name, suffix)),
+            (ConventionMatcher::Suffix(suffix), ConventionTarget::FileName) => match name.rsplit_once('.') {
+                Some((stem, _)) if suffix.contains('.') => Some(format!("{}{}", stem, suffix)),
+                Some((stem, extension)) => Some(format!("{}{}.{}", stem, suffix, extension)),
+                None => Some(format!("{}{}", name, suffix)),
+            },
+        }
+    }
+
+    /// Message for a component whose name breaks the convention
+    pub fn violation(&self, component: &FrameworkComponent, name: &str) -> String {
+        let subject = match self.target {
+            ConventionTarget::ComponentName => component.component_name.clone(),
+            ConventionTarget::FileName => format!("file {} of {}", name, component.component_name),
+        };
+        let mut message = format!(
+            "Convention violation: {} does not {} (convention {})",
+            subject,
+            self.matcher.describe(),
+            self.convention_id
+        );
+        if let Some(rationale) = &self.rationale {
+            message.push_str(&format!(": {}", rationale));
+        }
+        message
+    }
+}
+
+/// How much a validation finding matters; orders from `info` up to `error`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "info" => Ok(Severity::Info),
+            "warning" => Ok(Severity::Warning),
+            "error" => Ok(Severity::Error),
+            _ => Err(format!("Unknown severity: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Kind of rule a validation finding breaks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViolationRule {
+    LayerDependency,
+    ForbiddenImport,
+    Naming,
+    UnknownLayer,
+    DependencyCycle,
+    /// Reported by a validator a plugin registered
+    Plugin,
+    /// Breaks a naming convention stored for the project
+    Convention,
+    /// A fitness function is above its threshold
+    Fitness,
+}
+
+impl ViolationRule {
+    /// Severity when the rule set does not give one
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            ViolationRule::LayerDependency
+            | ViolationRule::ForbiddenImport
+            | ViolationRule::DependencyCycle
+            | ViolationRule::Fitness => {
+                Severity::Error
+            }
+            ViolationRule::Naming | ViolationRule::UnknownLayer | ViolationRule::Plugin | ViolationRule::Convention => {
+                Severity::Warning
+            }
+        }
+    }
+}
+
+/// A rule a component breaks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchitectureFinding {
+    pub rule: ViolationRule,
+    pub severity: Severity,
+    pub component: String,
+    /// Names the component and what it breaks; findings are matched to the baseline by it
+    pub message: String,
+    /// Plugin validator that reported the finding; `None` for built-in rules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validator: Option<String>,
+    /// Dependency the finding is about, for layer and import findings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependency: Option<String>,
+    /// Ways to resolve the finding, most targeted first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixes: Vec<FixSuggestion>,
+}
+
+/// What a suggested fix changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FixKind {
+    /// Depend on an interface in a layer both sides may use, implemented by the dependency
+    IntroduceInterface,
+    /// Put the component, and its file, in another layer
+    MoveToLayer,
+    /// Let the dependency depend on the component instead
+    InvertDependency,
+    RemoveDependency,
+    Rename,
+}
+
+/// A tool call that applies part of a fix, e.g. `update_entity` with the changed component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FixAction {
+    pub tool: String,
+    pub arguments: serde_json::Value,
+}
+
+/// A concrete way to resolve a finding; its actions apply it when called in order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FixSuggestion {
+    pub kind: FixKind,
+    pub description: String,
+    pub actions: Vec<FixAction>,
+}
+
+/// A finding acknowledged so later validations leave it out
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub project_id: String,
+    pub rule: ViolationRule,
+    pub severity: Severity,
+    pub component: String,
+    pub message: String,
+    pub acknowledged_at: String,
+}
+
+/// Acknowledged findings of every project, as kept in the baseline file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArchitectureBaseline {
+    #[serde(default)]
+    pub entries: Vec<BaselineEntry>,
+}
+
+impl ArchitectureBaseline {
+    /// Replaces the project's entries with `findings`
+    pub fn acknowledge(&mut self, project_id: &str, findings: &[ArchitectureFinding]) {
+        let acknowledged_at = chrono::Utc::now().to_rfc3339();
+        self.entries.retain(|entry| entry.project_id != project_id);
+        self.entries.extend(findings.iter().map(|finding| BaselineEntry {
+            project_id: project_id.to_string(),
+            rule: finding.rule,
+            severity: finding.severity,
+            component: finding.component.clone(),
+            message: finding.message.clone(),
+            acknowledged_at: acknowledged_at.clone(),
+        }));
+    }
+
+    /// Splits findings into new ones and ones acknowledged before, and lists acknowledged
+    /// findings that no longer occur
+    pub fn compare(&self, project_id: &str, findings: Vec<ArchitectureFinding>) -> BaselineComparison {
+        let entries: Vec<&BaselineEntry> = self.entries.iter().filter(|entry| entry.project_id == project_id).collect();
+        let (suppressed, new): (Vec<ArchitectureFinding>, Vec<ArchitectureFinding>) = findings
+            .into_iter()
+            .partition(|finding| entries.iter().any(|entry| entry.message == finding.message));
+        let fixed = entries
+            .into_iter()
+            .filter(|entry| !suppressed.iter().any(|finding| finding.message == entry.message))
+            .cloned()
+            .collect();
+        BaselineComparison { new, suppressed, fixed }
+    }
+}
+
+/// Current findings against a project's baseline
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BaselineComparison {
+    pub new: Vec<ArchitectureFinding>,
+    pub suppressed: Vec<ArchitectureFinding>,
+    /// Acknowledged findings that are gone
+    pub fixed: Vec<BaselineEntry>,
+}
+
+/// The value a fitness function had when architecture was validated
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FitnessMeasurement {
+    pub project_id: String,
+    pub function: String,
+    pub metric: FitnessMetric,
+    pub value: f64,
+    pub threshold: f64,
+    /// Layer or component the value was reached by
+    pub subject: Option<String>,
+    pub passed: bool,
+    pub measured_at: String,
+}
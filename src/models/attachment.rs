@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+/// A file attached to an entity, e.g. a diagram on an architectural decision; the content
+/// is stored once per distinct `content_hash`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: String,
+    pub project_id: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub file_name: String,
+    pub media_type: String,
+    pub size_bytes: u64,
+    /// Hex SHA-256 of the content
+    pub content_hash: String,
+    pub description: Option<String>,
+    pub created_at: String,
+    pub created_by: Option<String>,
+}
+
+impl Attachment {
+    /// Entity types attachments can be added to
+    pub const ENTITY_TYPES: &'static [&'static str] = &["architectural_decision", "feature_context"];
+
+    /// Whether the content reads as text rather than as binary data
+    pub fn is_text(&self) -> bool {
+        self.media_type.starts_with("text/")
+            || ["application/json", "application/yaml", "application/xml", "image/svg+xml"]
+                .contains(&self.media_type.as_str())
+    }
+}
+
+/// Media type for a file name, by its extension
+pub fn media_type_for(file_name: &str) -> &'static str {
+    let extension = file_name.rsplit_once('.').map(|(_, ext)| ext.to_lowercase()).unwrap_or_default();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "json" => "application/json",
+        "yaml" | "yml" => "application/yaml",
+        "xml" => "application/xml",
+        "md" => "text/markdown",
+        "mmd" | "mermaid" => "text/vnd.mermaid",
+        "puml" | "plantuml" => "text/vnd.plantuml",
+        "dot" | "gv" => "text/vnd.graphviz",
+        "csv" => "text/csv",
+        "txt" | "log" => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_media_types_by_extension() {
+        assert_eq!(media_type_for("flow.MMD"), "text/vnd.mermaid");
+        assert_eq!(media_type_for("order.sample.json"), "application/json");
+        assert_eq!(media_type_for("Makefile"), "application/octet-stream");
+
+        let attachment = |media_type: &str| Attachment {
+            id: "a1".to_string(),
+            project_id: "p1".to_string(),
+            entity_type: "architectural_decision".to_string(),
+            entity_id: "adr-1".to_string(),
+            file_name: "diagram".to_string(),
+            media_type: media_type.to_string(),
+            size_bytes: 0,
+            content_hash: String::new(),
+            description: None,
+            created_at: String::new(),
+            created_by: None,
+        };
+        assert!(attachment(media_type_for("a.svg")).is_text());
+        assert!(attachment("text/vnd.mermaid").is_text());
+        assert!(!attachment(media_type_for("a.png")).is_text());
+    }
+}
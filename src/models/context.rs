@@ -58,21 +58,44 @@ pub struct SecurityPolicy {
     pub implementation_pattern: Option<String>,
     pub forbidden_patterns: Option<String>, // JSON array
     pub compliance_notes: Option<String>,
+    /// One of `SecurityPolicy::SEVERITIES`
+    #[serde(default)]
+    pub severity: Option<String>,
+    /// Sensitivity of the data the policy protects, one of `SecurityPolicy::CLASSIFICATIONS`
+    #[serde(default)]
+    pub classification: Option<String>,
     pub created_at: Option<String>,
 }
 
+impl SecurityPolicy {
+    pub const SEVERITIES: &'static [&'static str] = &["critical", "high", "medium", "low"];
+    pub const CLASSIFICATIONS: &'static [&'static str] = &["public", "internal", "confidential", "restricted"];
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectConvention {
     pub id: String,
     pub project_id: String,
+    /// One of `ProjectConvention::CONVENTION_TYPES`
     pub convention_type: Option<String>,
     pub convention_rule: Option<String>,
+    /// What conforming code looks like, e.g. a naming regex or a commit message template
+    #[serde(default)]
+    pub pattern: Option<String>,
     pub good_examples: Option<String>, // JSON array
     pub bad_examples: Option<String>,  // JSON array
     pub rationale: Option<String>,
+    /// One of `ProjectConvention::ENFORCEMENT_LEVELS`
+    #[serde(default)]
+    pub enforcement_level: Option<String>,
     pub created_at: Option<String>,
 }
 
+impl ProjectConvention {
+    pub const CONVENTION_TYPES: &'static [&'static str] = &["naming", "formatting", "git", "testing"];
+    pub const ENFORCEMENT_LEVELS: &'static [&'static str] = &["required", "recommended", "optional"];
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeatureContext {
     pub id: String,
@@ -83,5 +106,21 @@ pub struct FeatureContext {
     pub key_workflows: Option<String>,      // JSON array
     pub integration_points: Option<String>, // JSON array
     pub edge_cases: Option<String>,         // JSON array
+    /// Limits the feature must stay within, e.g. supported browsers
+    #[serde(default)]
+    pub constraints: Option<String>, // JSON array
+    /// Names of the framework components that implement the feature
+    #[serde(default)]
+    pub related_components: Option<String>, // JSON array
+    /// One of `FeatureContext::STATUSES`
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Person or team responsible for the feature
+    #[serde(default)]
+    pub owner: Option<String>,
     pub created_at: Option<String>,
 }
+
+impl FeatureContext {
+    pub const STATUSES: &'static [&'static str] = &["planned", "in_progress", "shipped", "deprecated"];
+}
@@ -1,5 +1,7 @@
 use crate::models::context::*;
 use crate::models::enhanced_context::*;
+use crate::models::framework::FrameworkComponent;
+use crate::models::specification::Requirement;
 use chrono::{DateTime, Utc};
 use serde_json::json;
 
@@ -148,7 +150,9 @@ impl ContextConverter {
                 "requirements": policy.requirements,
                 "implementation_pattern": policy.implementation_pattern,
                 "forbidden_patterns": policy.forbidden_patterns,
-                "compliance_notes": policy.compliance_notes
+                "compliance_notes": policy.compliance_notes,
+                "severity": policy.severity,
+                "classification": policy.classification
             }),
             source_file: None,
             source_line: None,
@@ -189,9 +193,11 @@ impl ContextConverter {
             data: json!({
                 "convention_type": convention.convention_type,
                 "convention_rule": convention.convention_rule,
+                "pattern": convention.pattern,
                 "good_examples": convention.good_examples,
                 "bad_examples": convention.bad_examples,
-                "rationale": convention.rationale
+                "rationale": convention.rationale,
+                "enforcement_level": convention.enforcement_level
             }),
             source_file: None,
             source_line: None,
@@ -232,7 +238,11 @@ impl ContextConverter {
                 "user_personas": feature.user_personas,
                 "key_workflows": feature.key_workflows,
                 "integration_points": feature.integration_points,
-                "edge_cases": feature.edge_cases
+                "edge_cases": feature.edge_cases,
+                "constraints": feature.constraints,
+                "related_components": feature.related_components,
+                "status": feature.status,
+                "owner": feature.owner
             }),
             source_file: None,
             source_line: None,
@@ -259,6 +269,67 @@ impl ContextConverter {
         item
     }
 
+    /// Convert a FrameworkComponent to an EnhancedContextItem described by its file path, so
+    /// only components of one file read alike; its dependencies are not resolved to other
+    /// components here
+    pub fn from_framework_component(component: FrameworkComponent) -> EnhancedContextItem {
+        let content = ContextContent {
+            content_type: ContextType::Custom("framework_component".to_string()),
+            title: component.component_name.clone(),
+            description: component.file_path.clone().unwrap_or_default(),
+            data: json!({
+                "component_type": component.component_type,
+                "architecture_layer": component.architecture_layer,
+                "file_path": component.file_path,
+                "dependencies": component.dependencies,
+                "metadata": component.metadata
+            }),
+            source_file: component.file_path.clone(),
+            source_line: None,
+        };
+
+        let mut item = EnhancedContextItem::new(component.project_id, content);
+        item.id = component.id;
+        if let Some(created_at) = component.created_at.and_then(|at| DateTime::parse_from_rfc3339(&at).ok()) {
+            item.created_at = created_at.with_timezone(&Utc);
+        }
+        if let Some(updated_at) = component.updated_at.and_then(|at| DateTime::parse_from_rfc3339(&at).ok()) {
+            item.updated_at = updated_at.with_timezone(&Utc);
+        }
+
+        item.metadata.tags.push("component".to_string());
+        item.semantic_tags.push(SemanticTag::new(
+            format!("layer:{}", component.architecture_layer),
+            0.9,
+            TagSource::Manual,
+        ));
+        item
+    }
+
+    /// Convert a specification Requirement to an EnhancedContextItem of `project_id`
+    pub fn from_requirement(requirement: Requirement, project_id: &str) -> EnhancedContextItem {
+        let content = ContextContent {
+            content_type: ContextType::Custom("requirement".to_string()),
+            title: requirement.title.clone(),
+            description: requirement.description.clone(),
+            data: json!({
+                "spec_id": requirement.spec_id,
+                "user_story": requirement.user_story,
+                "linked_context": requirement.linked_context,
+                "dependencies": requirement.dependencies
+            }),
+            source_file: None,
+            source_line: None,
+        };
+
+        let mut item = EnhancedContextItem::new(project_id.to_string(), content);
+        item.id = requirement.id;
+        item.created_at = requirement.created_at;
+        item.updated_at = requirement.updated_at;
+        item.metadata.tags.push("requirement".to_string());
+        item
+    }
+
     /// Convert an EnhancedContextItem back to a BusinessRule (if applicable)
     pub fn to_business_rule(item: &EnhancedContextItem) -> Option<BusinessRule> {
         if item.content.content_type != ContextType::BusinessRule {
@@ -334,6 +405,8 @@ impl ContextConverter {
             implementation_pattern: data.get("implementation_pattern").and_then(|v| v.as_str()).map(String::from),
             forbidden_patterns: data.get("forbidden_patterns").and_then(|v| v.as_str()).map(String::from),
             compliance_notes: data.get("compliance_notes").and_then(|v| v.as_str()).map(String::from),
+            severity: data.get("severity").and_then(|v| v.as_str()).map(String::from),
+            classification: data.get("classification").and_then(|v| v.as_str()).map(String::from),
             created_at: Some(item.created_at.to_rfc3339()),
         })
     }
@@ -350,9 +423,11 @@ impl ContextConverter {
             project_id: item.project_id.clone(),
             convention_type: data.get("convention_type").and_then(|v| v.as_str()).map(String::from),
             convention_rule: Some(item.content.description.clone()),
+            pattern: data.get("pattern").and_then(|v| v.as_str()).map(String::from),
             good_examples: data.get("good_examples").and_then(|v| v.as_str()).map(String::from),
             bad_examples: data.get("bad_examples").and_then(|v| v.as_str()).map(String::from),
             rationale: data.get("rationale").and_then(|v| v.as_str()).map(String::from),
+            enforcement_level: data.get("enforcement_level").and_then(|v| v.as_str()).map(String::from),
             created_at: Some(item.created_at.to_rfc3339()),
         })
     }
@@ -373,6 +448,10 @@ impl ContextConverter {
             key_workflows: data.get("key_workflows").and_then(|v| v.as_str()).map(String::from),
             integration_points: data.get("integration_points").and_then(|v| v.as_str()).map(String::from),
             edge_cases: data.get("edge_cases").and_then(|v| v.as_str()).map(String::from),
+            constraints: data.get("constraints").and_then(|v| v.as_str()).map(String::from),
+            related_components: data.get("related_components").and_then(|v| v.as_str()).map(String::from),
+            status: data.get("status").and_then(|v| v.as_str()).map(String::from),
+            owner: data.get("owner").and_then(|v| v.as_str()).map(String::from),
             created_at: Some(item.created_at.to_rfc3339()),
         })
     }
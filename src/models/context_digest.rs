@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// Narrative summary of a feature area's context, written by a language model and kept
+/// until the entities it summarizes change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextDigest {
+    pub id: String,
+    pub project_id: String,
+    pub feature_area: String,
+    pub summary: String,
+    /// `sampling:<model>` or `llm:<model>`
+    pub generated_by: String,
+    /// Entities the summary was written from
+    pub entity_ids: Vec<String>,
+    /// MD5 of those entities as JSON; regenerating with the same hash keeps the summary
+    pub source_hash: String,
+    /// An entity it summarizes changed since the summary was written
+    pub stale: bool,
+    pub generated_at: String,
+}
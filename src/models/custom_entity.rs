@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A kind of context a team defines for itself, e.g. a data contract or an SLO, whose
+/// entities go through the universal CRUD tools as `custom_entity`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomEntityType {
+    /// Unique name, given as the `custom_type` of its entities
+    pub name: String,
+    pub description: Option<String>,
+    /// JSON Schema the `fields` of its entities must satisfy; `type`, `properties`,
+    /// `required`, `enum` and `items` are checked
+    pub schema: Value,
+    /// Properties of `fields` whose text is indexed for search
+    pub searchable_fields: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl CustomEntityType {
+    pub fn new(name: &str, schema: Value, searchable_fields: Vec<String>) -> Self {
+        let now = chrono::Utc::now().to_rfc3339();
+        Self {
+            name: name.to_string(),
+            description: None,
+            schema,
+            searchable_fields,
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+
+    /// Reject a schema whose entities wouldn't be objects, or searchable fields it doesn't declare
+    pub fn check_definition(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("name must not be empty".to_string());
+        }
+        if self.schema.get("type").and_then(|t| t.as_str()) != Some("object") {
+            return Err("schema must have \"type\": \"object\"".to_string());
+        }
+        if let Some(properties) = self.schema.get("properties").and_then(|p| p.as_object()) {
+            if let Some(field) = self.searchable_fields.iter().find(|field| !properties.contains_key(*field)) {
+                return Err(format!("searchable field {field} is not a property of the schema"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check entity fields against the schema; the error names the first offending path
+    pub fn validate(&self, fields: &Value) -> Result<(), String> {
+        validate_value(&self.schema, fields, "fields")
+    }
+
+    /// Text of the searchable properties of `fields`
+    pub fn search_text(&self, fields: &Value) -> Vec<String> {
+        let mut text = Vec::new();
+        for field in &self.searchable_fields {
+            match fields.get(field) {
+                Some(Value::String(value)) if !value.trim().is_empty() => text.push(value.clone()),
+                Some(Value::Array(items)) => text.extend(items.iter().filter_map(|item| item.as_str()).map(str::to_string)),
+                Some(value @ (Value::Number(_) | Value::Bool(_))) => text.push(value.to_string()),
+                _ => {}
+            }
+        }
+        text
+    }
+}
+
+/// An entity of a custom type, stored with its fields as JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomEntity {
+    pub id: String,
+    pub project_id: String,
+    pub custom_type: String,
+    pub fields: Value,
+    /// Text of the type's searchable fields, which is what search indexes
+    #[serde(default)]
+    pub search_text: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl CustomEntity {
+    pub fn new(project_id: &str, custom_type: &str, fields: Value) -> Self {
+        let now = chrono::Utc::now().to_rfc3339();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            project_id: project_id.to_string(),
+            custom_type: custom_type.to_string(),
+            fields,
+            search_text: Vec::new(),
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn validate_value(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    match schema.get("type") {
+        Some(Value::String(expected)) if !type_matches(expected, value) => {
+            return Err(format!("{path} must be of type {expected}"));
+        }
+        Some(Value::Array(types)) if !types.iter().filter_map(|t| t.as_str()).any(|t| type_matches(t, value)) => {
+            return Err(format!("{path} must be one of the types {}", Value::Array(types.clone())));
+        }
+        _ => {}
+    }
+    if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array()) {
+        if !allowed.contains(value) {
+            return Err(format!("{path} must be one of {}", Value::Array(allowed.clone())));
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+            if let Some(missing) = required.iter().filter_map(|r| r.as_str()).find(|name| !object.contains_key(*name)) {
+                return Err(format!("{path}.{missing} is required"));
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+            for (name, property) in properties {
+                if let Some(field) = object.get(name) {
+                    validate_value(property, field, &format!("{path}.{name}"))?;
+                }
+            }
+        }
+    }
+    if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+        for (index, item) in array.iter().enumerate() {
+            validate_value(items, item, &format!("{path}[{index}]"))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_schema_validation_and_search_text() {
+        let slo = CustomEntityType::new(
+            "slo",
+            json!({
+                "type": "object",
+                "required": ["service", "objective"],
+                "properties": {
+                    "service": {"type": "string"},
+                    "objective": {"type": "number"},
+                    "window": {"type": "string", "enum": ["7d", "28d"]},
+                    "owners": {"type": "array", "items": {"type": "string"}}
+                }
+            }),
+            vec!["service".to_string(), "owners".to_string()],
+        );
+        assert!(slo.check_definition().is_ok());
+
+        let fields = json!({"service": "checkout", "objective": 99.9, "window": "28d", "owners": ["payments"]});
+        assert!(slo.validate(&fields).is_ok());
+        assert_eq!(slo.search_text(&fields), vec!["checkout", "payments"]);
+
+        assert_eq!(slo.validate(&json!({"service": "checkout"})).unwrap_err(), "fields.objective is required");
+        assert_eq!(
+            slo.validate(&json!({"service": "checkout", "objective": "high"})).unwrap_err(),
+            "fields.objective must be of type number"
+        );
+        assert!(slo.validate(&json!({"service": "checkout", "objective": 99, "window": "1y"})).is_err());
+        assert_eq!(
+            slo.validate(&json!({"service": "checkout", "objective": 99, "owners": [1]})).unwrap_err(),
+            "fields.owners[0] must be of type string"
+        );
+
+        let mut undeclared = slo.clone();
+        undeclared.searchable_fields.push("notes".to_string());
+        assert!(undeclared.check_definition().is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// How one architectural decision relates to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionLinkType {
+    /// The newer decision replaces the older one, which is then marked superseded
+    Supersedes,
+    /// The newer decision changes part of the older one, which stays current
+    Amends,
+    RelatesTo,
+}
+
+impl DecisionLinkType {
+    pub const ALL: [DecisionLinkType; 3] =
+        [DecisionLinkType::Supersedes, DecisionLinkType::Amends, DecisionLinkType::RelatesTo];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DecisionLinkType::Supersedes => "supersedes",
+            DecisionLinkType::Amends => "amends",
+            DecisionLinkType::RelatesTo => "relates_to",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|link_type| link_type.as_str() == value)
+    }
+}
+
+/// A typed link from one decision of a project to another, e.g. ADR-0012 `supersedes` ADR-0004
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionLink {
+    pub project_id: String,
+    pub from_decision_id: String,
+    pub to_decision_id: String,
+    pub link_type: DecisionLinkType,
+    pub created_at: String,
+    /// Client that linked the decisions, when known
+    pub created_by: Option<String>,
+}
+
+impl DecisionLink {
+    pub fn new(project_id: &str, from_decision_id: &str, to_decision_id: &str, link_type: DecisionLinkType) -> Self {
+        Self {
+            project_id: project_id.to_string(),
+            from_decision_id: from_decision_id.to_string(),
+            to_decision_id: to_decision_id.to_string(),
+            link_type,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            created_by: None,
+        }
+    }
+
+    pub fn involves(&self, decision_id: &str) -> bool {
+        self.from_decision_id == decision_id || self.to_decision_id == decision_id
+    }
+}
+
+/// Decisions another decision supersedes
+pub fn superseded_ids(links: &[DecisionLink]) -> HashSet<&str> {
+    links
+        .iter()
+        .filter(|link| link.link_type == DecisionLinkType::Supersedes)
+        .map(|link| link.to_decision_id.as_str())
+        .collect()
+}
+
+/// The current decision of `decision_id`'s chain: the one reached by following
+/// `supersedes` links from older to newer decisions
+pub fn chain_head(links: &[DecisionLink], decision_id: &str) -> String {
+    let mut head = decision_id.to_string();
+    let mut seen = HashSet::from([head.clone()]);
+    while let Some(newer) = links
+        .iter()
+        .find(|link| link.link_type == DecisionLinkType::Supersedes && link.to_decision_id == head)
+    {
+        if !seen.insert(newer.from_decision_id.clone()) {
+            break;
+        }
+        head = newer.from_decision_id.clone();
+    }
+    head
+}
+
+/// Whether `newer` already supersedes `older`, directly or through decisions in between
+pub fn supersedes_transitively(links: &[DecisionLink], newer: &str, older: &str) -> bool {
+    let mut pending = vec![newer];
+    let mut seen = HashSet::new();
+    while let Some(current) = pending.pop() {
+        if current == older {
+            return true;
+        }
+        if seen.insert(current) {
+            pending.extend(
+                links
+                    .iter()
+                    .filter(|link| link.link_type == DecisionLinkType::Supersedes && link.from_decision_id == current)
+                    .map(|link| link.to_decision_id.as_str()),
+            );
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supersede_chains() {
+        let links = vec![
+            DecisionLink::new("p1", "adr-2", "adr-1", DecisionLinkType::Supersedes),
+            DecisionLink::new("p1", "adr-3", "adr-2", DecisionLinkType::Supersedes),
+            DecisionLink::new("p1", "adr-4", "adr-3", DecisionLinkType::Amends),
+            DecisionLink::new("p1", "adr-5", "adr-1", DecisionLinkType::RelatesTo),
+        ];
+        assert_eq!(superseded_ids(&links), HashSet::from(["adr-1", "adr-2"]));
+        assert_eq!(chain_head(&links, "adr-1"), "adr-3");
+        assert_eq!(chain_head(&links, "adr-4"), "adr-4");
+        assert!(supersedes_transitively(&links, "adr-3", "adr-1"));
+        assert!(!supersedes_transitively(&links, "adr-1", "adr-3"));
+        assert!(!supersedes_transitively(&links, "adr-4", "adr-3"));
+        assert_eq!(DecisionLinkType::parse("relates_to"), Some(DecisionLinkType::RelatesTo));
+    }
+}
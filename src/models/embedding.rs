@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Vector embedding for context content
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -149,6 +149,9 @@ pub struct SearchFilters {
     pub date_range: Option<DateRange>,
     pub quality_threshold: Option<f32>,
     pub tags: Option<Vec<String>>,
+    /// Only compare vectors from this model; others are in a different vector space
+    #[serde(default)]
+    pub embedding_model: Option<String>,
 }
 
 impl Default for SearchFilters {
@@ -159,6 +162,7 @@ impl Default for SearchFilters {
             date_range: None,
             quality_threshold: None,
             tags: None,
+            embedding_model: None,
         }
     }
 }
@@ -207,6 +211,12 @@ pub struct ResultMetadata {
     pub content_preview: String,
     pub match_explanation: String,
     pub quality_indicators: Vec<String>,
+    /// Facet values of the matched entity, keyed by facet name (`status`, `tags`, ...)
+    #[serde(default)]
+    pub facets: BTreeMap<String, Vec<String>>,
+    /// Project the matched entity belongs to, when the indexer recorded one
+    #[serde(default)]
+    pub project_id: Option<String>,
 }
 
 /// Batch of embeddings for efficient processing
@@ -251,6 +261,8 @@ pub struct BatchStats {
 /// Configuration for embedding generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbeddingConfig {
+    #[serde(default)]
+    pub provider: EmbeddingProvider,
     pub model_name: String,
     pub model_path: Option<String>,
     pub embedding_dimension: usize,
@@ -259,11 +271,26 @@ pub struct EmbeddingConfig {
     pub tokenization_method: TokenizationMethod,
     pub preprocessing_enabled: bool,
     pub cache_embeddings: bool,
+    /// Base URL of HTTP providers; each provider has its own default
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// Bearer token for HTTP providers; never serialized
+    #[serde(default, skip_serializing)]
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+}
+
+fn default_request_timeout_secs() -> u64 {
+    30
 }
 
 impl Default for EmbeddingConfig {
     fn default() -> Self {
         Self {
+            provider: EmbeddingProvider::default(),
             model_name: "all-MiniLM-L6-v2".to_string(),
             model_path: None,
             embedding_dimension: 384,
@@ -272,6 +299,70 @@ impl Default for EmbeddingConfig {
             tokenization_method: TokenizationMethod::Hybrid,
             preprocessing_enabled: true,
             cache_embeddings: true,
+            endpoint: None,
+            api_key: None,
+            retry: RetryPolicy::default(),
+            request_timeout_secs: default_request_timeout_secs(),
+        }
+    }
+}
+
+/// Backend that turns text into vectors
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbeddingProvider {
+    /// Hash-based vectors computed in-process; no semantic quality, no dependencies
+    #[default]
+    Simple,
+    /// Any OpenAI-compatible `/embeddings` API
+    OpenAi,
+    /// A local Ollama server (`/api/embed`)
+    Ollama,
+    /// A model run in-process
+    Local,
+}
+
+impl std::str::FromStr for EmbeddingProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "simple" => Ok(Self::Simple),
+            "openai" => Ok(Self::OpenAi),
+            "ollama" => Ok(Self::Ollama),
+            "local" => Ok(Self::Local),
+            other => Err(format!(
+                "Unknown embedding provider '{other}' (expected simple, openai, ollama or local)"
+            )),
+        }
+    }
+}
+
+/// Retries for transient backend failures (timeouts, HTTP 429 and 5xx)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Attempts after the first one
+    pub max_retries: u32,
+    /// Delay before the first retry; doubles on every further attempt
+    pub initial_backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff_ms: 500,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            initial_backoff_ms: 0,
         }
     }
 }
\ No newline at end of file
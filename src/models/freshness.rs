@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// The last time someone confirmed an entity still holds, with `verify_context` or by updating it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityVerification {
+    pub entity_id: String,
+    pub entity_type: String,
+    pub project_id: String,
+    /// RFC 3339
+    pub verified_at: String,
+    /// Client that confirmed the entity, when known
+    pub verified_by: Option<String>,
+    pub note: Option<String>,
+}
+
+impl EntityVerification {
+    pub fn new(project_id: &str, entity_type: &str, entity_id: &str) -> Self {
+        Self {
+            entity_id: entity_id.to_string(),
+            entity_type: entity_type.to_string(),
+            project_id: project_id.to_string(),
+            verified_at: chrono::Utc::now().to_rfc3339(),
+            verified_by: None,
+            note: None,
+        }
+    }
+}
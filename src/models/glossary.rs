@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// A domain term and the words a project uses interchangeably with it, e.g. "ADR" for
+/// "architectural decision"; search treats every member of the group as equivalent. As the
+/// `glossary_term` entity it also defines the term, so agents use the project's language
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GlossaryEntry {
+    pub id: String,
+    pub project_id: String,
+    pub term: String,
+    /// Aliases of the term
+    pub synonyms: Vec<String>,
+    #[serde(default)]
+    pub definition: Option<String>,
+    /// Feature area the term belongs to, when it is not used project-wide
+    #[serde(default)]
+    pub domain_area: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+impl GlossaryEntry {
+    /// The term followed by its synonyms
+    pub fn members(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.term).chain(self.synonyms.iter())
+    }
+}
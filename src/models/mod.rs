@@ -1,17 +1,32 @@
 pub mod api;
+pub mod api_contract;
+pub mod approval;
 pub mod architecture;
+pub mod attachment;
 pub mod audit_log;
 pub mod constraint;
 pub mod context;
+pub mod context_digest;
 pub mod context_conversion;
+pub mod custom_entity;
+pub mod decision_link;
 pub mod development;
 pub mod embedding;
 pub mod enhanced_context;
 pub mod flutter;
 pub mod framework;
+pub mod freshness;
+pub mod glossary;
 pub mod plugin;
+pub mod quality_rubric;
+pub mod review;
+pub mod saved_search;
+pub mod shared_context;
+pub mod scheduled_report;
 pub mod specification;
 pub mod tagging;
+pub mod tool_call_audit;
+pub mod traceability;
 
 // Re-export commonly used types
 pub use audit_log::{AuditEventType, AuditTrail};
@@ -5,6 +5,9 @@ use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use async_trait::async_trait;
 use anyhow::Result;
+use std::sync::Arc;
+use crate::models::architecture::{ArchitectureFinding, ArchitectureRuleSet};
+use crate::models::framework::FrameworkComponent;
 
 /// Unique identifier for a plugin
 pub type PluginId = Uuid;
@@ -242,6 +245,25 @@ pub trait ContextPlugin: Send + Sync {
     
     /// Health check for the plugin
     async fn health_check(&self) -> Result<PluginHealth>;
+
+    /// Validators run by `validate_architecture` alongside the built-in rules while the plugin is active
+    fn architecture_validators(&self) -> Vec<Arc<dyn ArchitectureValidator>> {
+        Vec::new()
+    }
+}
+
+/// An architecture check contributed by a plugin
+#[async_trait]
+pub trait ArchitectureValidator: Send + Sync {
+    /// Name the validator's findings are reported under
+    fn name(&self) -> &str;
+
+    /// Findings for the components of one project, validated against its rule set
+    async fn validate(
+        &self,
+        rules: &ArchitectureRuleSet,
+        components: &[FrameworkComponent],
+    ) -> Result<Vec<ArchitectureFinding>>;
 }
 
 /// Plugin health status
@@ -0,0 +1,148 @@
+use crate::config::QualitySettings;
+use crate::models::enhanced_context::EnhancedContextItem;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Entity types `required_fields` can name
+pub const RUBRIC_ENTITY_TYPES: &[&str] = &[
+    "business_rule",
+    "architectural_decision",
+    "performance_requirement",
+    "security_policy",
+    "project_convention",
+    "feature_context",
+];
+
+/// A team's standard for its context, which quality scores measure entities against instead of
+/// the built-in heuristics. A rubric without checks leaves the heuristics in place.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QualityRubric {
+    pub project_id: String,
+    /// Fields each entity type must fill, by entity type (`{"business_rule": ["domain_area"]}`)
+    #[serde(default)]
+    pub required_fields: BTreeMap<String, Vec<String>>,
+    /// Fewest characters in a description (0 for no minimum)
+    #[serde(default)]
+    pub min_description_length: usize,
+    /// Entity types that record examples (business rules) must give at least one
+    #[serde(default)]
+    pub require_examples: bool,
+    /// Every entity must be linked to a specification requirement
+    #[serde(default)]
+    pub require_requirement_link: bool,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+/// Outcome of one rubric check on an entity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RubricCheck {
+    /// `required.<field>`, `min_description_length`, `examples` or `requirement_link`
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+impl QualityRubric {
+    /// The `[quality]` rubric, for projects without one of their own
+    pub fn from_settings(project_id: &str, settings: &QualitySettings) -> Self {
+        Self {
+            project_id: project_id.to_string(),
+            required_fields: settings.required_fields.clone(),
+            min_description_length: settings.min_description_length,
+            require_examples: settings.require_examples,
+            require_requirement_link: settings.require_requirement_link,
+            updated_at: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.required_fields.values().all(|fields| fields.is_empty())
+            && self.min_description_length == 0
+            && !self.require_examples
+            && !self.require_requirement_link
+    }
+
+    /// Unknown entity types and blank field names
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (entity_type, fields) in &self.required_fields {
+            if !RUBRIC_ENTITY_TYPES.contains(&entity_type.as_str()) {
+                problems.push(format!(
+                    "Unknown entity type {}; expected one of {}",
+                    entity_type,
+                    RUBRIC_ENTITY_TYPES.join(", ")
+                ));
+            }
+            if fields.iter().any(|field| field.trim().is_empty()) {
+                problems.push(format!("Required fields of {} include a blank name", entity_type));
+            }
+        }
+        problems
+    }
+
+    /// Check `item` against the rubric; `linked_requirements` is how many specification
+    /// requirements it is linked to
+    pub fn evaluate(&self, item: &EnhancedContextItem, linked_requirements: usize) -> Vec<RubricCheck> {
+        let mut checks = Vec::new();
+        let content = &item.content;
+
+        for field in self.required_fields.get(content.content_type.as_str()).into_iter().flatten() {
+            let passed = match field.as_str() {
+                "title" => !content.title.trim().is_empty(),
+                "description" => !content.description.trim().is_empty(),
+                _ => content.data.get(field).is_some_and(is_filled),
+            };
+            checks.push(RubricCheck {
+                name: format!("required.{field}"),
+                passed,
+                detail: format!("{} must fill {}", content.content_type.as_str(), field),
+            });
+        }
+
+        if self.min_description_length > 0 {
+            let length = content.description.trim().chars().count();
+            checks.push(RubricCheck {
+                name: "min_description_length".to_string(),
+                passed: length >= self.min_description_length,
+                detail: format!(
+                    "description has {} of at least {} characters",
+                    length, self.min_description_length
+                ),
+            });
+        }
+
+        if self.require_examples && content.data.get("examples").is_some() {
+            checks.push(RubricCheck {
+                name: "examples".to_string(),
+                passed: content.data.get("examples").is_some_and(is_filled),
+                detail: "at least one example is required".to_string(),
+            });
+        }
+
+        if self.require_requirement_link {
+            checks.push(RubricCheck {
+                name: "requirement_link".to_string(),
+                passed: linked_requirements > 0,
+                detail: format!("linked to {} specification requirements", linked_requirements),
+            });
+        }
+
+        checks
+    }
+}
+
+/// Non-empty text, a non-empty list, or text holding a non-empty JSON list; examples and
+/// constraints are stored as JSON text
+fn is_filled(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => false,
+        serde_json::Value::String(text) => match serde_json::from_str::<serde_json::Value>(text) {
+            Ok(serde_json::Value::Array(items)) => !items.is_empty(),
+            _ => !text.trim().is_empty(),
+        },
+        serde_json::Value::Array(items) => !items.is_empty(),
+        serde_json::Value::Object(fields) => !fields.is_empty(),
+        _ => true,
+    }
+}
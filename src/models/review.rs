@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Whether people have confirmed an entity is right, e.g. context an AI agent wrote
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationStatus {
+    Unverified,
+    Verified,
+    Disputed,
+}
+
+impl VerificationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unverified => "unverified",
+            Self::Verified => "verified",
+            Self::Disputed => "disputed",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "unverified" => Some(Self::Unverified),
+            "verified" => Some(Self::Verified),
+            "disputed" => Some(Self::Disputed),
+            _ => None,
+        }
+    }
+}
+
+/// A request to review an entity and, once reviewed, the verdict
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityReview {
+    pub id: String,
+    pub project_id: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    /// `Unverified` while the review is pending
+    pub status: VerificationStatus,
+    pub requested_by: Option<String>,
+    /// RFC 3339; None for reviews recorded without a request
+    pub requested_at: Option<String>,
+    pub note: Option<String>,
+    pub reviewer: Option<String>,
+    /// RFC 3339; None while pending
+    pub reviewed_at: Option<String>,
+    pub comment: Option<String>,
+}
+
+impl EntityReview {
+    /// A pending review of the entity
+    pub fn request(project_id: &str, entity_type: &str, entity_id: &str) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            project_id: project_id.to_string(),
+            entity_type: entity_type.to_string(),
+            entity_id: entity_id.to_string(),
+            status: VerificationStatus::Unverified,
+            requested_by: None,
+            requested_at: Some(chrono::Utc::now().to_rfc3339()),
+            note: None,
+            reviewer: None,
+            reviewed_at: None,
+            comment: None,
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.reviewed_at.is_none()
+    }
+
+    /// Record the verdict now
+    pub fn record(&mut self, status: VerificationStatus, reviewer: Option<String>, comment: Option<String>) {
+        self.status = status;
+        self.reviewer = reviewer;
+        self.comment = comment;
+        self.reviewed_at = Some(chrono::Utc::now().to_rfc3339());
+    }
+}
+
+/// Where an entity stands: the verdict of its latest review, and whether another is pending
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EntityVerificationState {
+    pub verification_status: VerificationStatus,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<String>,
+    pub review_pending: bool,
+}
+
+impl Default for EntityVerificationState {
+    fn default() -> Self {
+        Self {
+            verification_status: VerificationStatus::Unverified,
+            reviewed_by: None,
+            reviewed_at: None,
+            review_pending: false,
+        }
+    }
+}
+
+/// The state of every entity `reviews` cover, by entity id
+pub fn verification_states(reviews: &[EntityReview]) -> HashMap<String, EntityVerificationState> {
+    let mut states: HashMap<String, EntityVerificationState> = HashMap::new();
+    for review in reviews {
+        let state = states.entry(review.entity_id.clone()).or_default();
+        if review.is_pending() {
+            state.review_pending = true;
+        } else if state.reviewed_at.is_none() || review.reviewed_at > state.reviewed_at {
+            state.verification_status = review.status;
+            state.reviewed_by = review.reviewer.clone();
+            state.reviewed_at = review.reviewed_at.clone();
+        }
+    }
+    states
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_verdict_wins_and_pending_requests_show() {
+        let reviewed = |entity_id: &str, status: VerificationStatus, reviewer: &str, reviewed_at: &str| {
+            let mut review = EntityReview::request("p1", "business_rule", entity_id);
+            review.record(status, Some(reviewer.to_string()), None);
+            review.reviewed_at = Some(reviewed_at.to_string());
+            review
+        };
+        let reviews = vec![
+            EntityReview::request("p1", "business_rule", "rule-1"),
+            reviewed("rule-1", VerificationStatus::Disputed, "bob", "2026-03-01T00:00:00+00:00"),
+            reviewed("rule-1", VerificationStatus::Verified, "alice", "2026-01-01T00:00:00+00:00"),
+            EntityReview::request("p1", "business_rule", "rule-2"),
+        ];
+
+        let states = verification_states(&reviews);
+        assert_eq!(states["rule-1"].verification_status, VerificationStatus::Disputed);
+        assert_eq!(states["rule-1"].reviewed_by.as_deref(), Some("bob"));
+        assert!(states["rule-1"].review_pending);
+        assert_eq!(
+            states["rule-2"],
+            EntityVerificationState {
+                review_pending: true,
+                ..Default::default()
+            }
+        );
+        assert!(!states.contains_key("rule-3"));
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A named hybrid search that agents re-run instead of rebuilding the query each time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub id: String,
+    pub project_id: String,
+    pub name: String,
+    pub query: String,
+    pub feature_area: Option<String>,
+    /// Facet filters as accepted by `search_context`, e.g. `{"tags": ["payments"]}`
+    #[serde(default)]
+    pub filters: BTreeMap<String, Vec<String>>,
+    /// Results returned per run; `search.max_results` when unset
+    pub limit: Option<usize>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+/// Report the scheduler generates for each project
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledReportKind {
+    /// What `generate_quality_report` returns for the project
+    Quality,
+    /// What `generate_specification_health_report` returns for the project
+    SpecificationHealth,
+}
+
+impl ScheduledReportKind {
+    pub const ALL: [ScheduledReportKind; 2] = [ScheduledReportKind::Quality, ScheduledReportKind::SpecificationHealth];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScheduledReportKind::Quality => "quality",
+            ScheduledReportKind::SpecificationHealth => "specification_health",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == name)
+    }
+}
+
+/// A report generated on schedule, kept so a project's reports can be compared over time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledReport {
+    pub id: String,
+    pub project_id: String,
+    pub kind: ScheduledReportKind,
+    /// Period the report covers, RFC 3339
+    pub period_start: String,
+    pub period_end: String,
+    pub report: serde_json::Value,
+    /// Webhook targets that accepted the report
+    #[serde(default)]
+    pub delivered_to: Vec<String>,
+    pub created_at: String,
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// Where an entity of the shared organization project was promoted from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedContextLink {
+    pub shared_project_id: String,
+    /// The copy in the shared project
+    pub shared_entity_id: String,
+    pub entity_type: String,
+    pub source_project_id: String,
+    pub source_entity_id: String,
+    /// RFC 3339; the last time the copy was refreshed from its source
+    pub promoted_at: String,
+    /// Client that promoted the entity, when known
+    pub promoted_by: Option<String>,
+}
+
+impl SharedContextLink {
+    pub fn new(
+        shared_project_id: &str,
+        shared_entity_id: &str,
+        entity_type: &str,
+        source_project_id: &str,
+        source_entity_id: &str,
+    ) -> Self {
+        Self {
+            shared_project_id: shared_project_id.to_string(),
+            shared_entity_id: shared_entity_id.to_string(),
+            entity_type: entity_type.to_string(),
+            source_project_id: source_project_id.to_string(),
+            source_entity_id: source_entity_id.to_string(),
+            promoted_at: chrono::Utc::now().to_rfc3339(),
+            promoted_by: None,
+        }
+    }
+}
@@ -473,6 +473,45 @@ impl Default for RequirementMetadata {
     }
 }
 
+/// Whether a requirement's link to a context item was proposed or confirmed
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextLinkStatus {
+    Suggested,
+    Accepted,
+}
+
+impl ContextLinkStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContextLinkStatus::Suggested => "suggested",
+            ContextLinkStatus::Accepted => "accepted",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "suggested" => ContextLinkStatus::Suggested,
+            _ => ContextLinkStatus::Accepted,
+        }
+    }
+}
+
+/// Link between a requirement and a business rule, decision or component, as stored in
+/// `requirement_context_links`. Only accepted links show up in `Requirement::linked_context`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RequirementContextLink {
+    pub requirement_id: RequirementId,
+    pub context_id: ContextId,
+    pub context_type: Option<String>,
+    pub title: Option<String>,
+    pub link_type: Option<String>,
+    /// How well the context item matched the requirement, from 0 to 1
+    pub confidence: Option<f64>,
+    pub status: ContextLinkStatus,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Individual task within a specification
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
@@ -641,9 +680,9 @@ impl TaskStatus {
 
     pub fn from_checkbox_status(status: &str) -> Self {
         match status.trim() {
-            "[ ]" | "-" => TaskStatus::NotStarted,
+            "[ ]" | "" => TaskStatus::NotStarted,
             "[x]" | "x" => TaskStatus::Completed,
-            "[-]" => TaskStatus::InProgress,
+            "[-]" | "-" => TaskStatus::InProgress,
             _ => TaskStatus::NotStarted,
         }
     }
@@ -876,6 +915,8 @@ mod tests {
     fn test_task_status_from_checkbox_status() {
         assert_eq!(TaskStatus::from_checkbox_status("[ ]"), TaskStatus::NotStarted);
         assert_eq!(TaskStatus::from_checkbox_status("[x]"), TaskStatus::Completed);
+        // The task parser passes the bare checkbox character
+        assert_eq!(TaskStatus::from_checkbox_status("-"), TaskStatus::InProgress);
         assert_eq!(TaskStatus::from_checkbox_status("[-]"), TaskStatus::InProgress);
         assert_eq!(TaskStatus::from_checkbox_status("unknown"), TaskStatus::NotStarted);
     }
@@ -55,3 +55,53 @@ impl TaggedEntity {
         }
     }
 }
+
+/// Where a suggested tag stands: applied automatically, waiting for review, or reviewed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagSuggestionStatus {
+    Applied,
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+impl TagSuggestionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Applied => "applied",
+            Self::Pending => "pending",
+            Self::Accepted => "accepted",
+            Self::Rejected => "rejected",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "applied" => Some(Self::Applied),
+            "pending" => Some(Self::Pending),
+            "accepted" => Some(Self::Accepted),
+            "rejected" => Some(Self::Rejected),
+            _ => None,
+        }
+    }
+}
+
+/// A tag classification proposed for an entity, with how confident it is
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagSuggestion {
+    pub id: String,
+    pub project_id: String,
+    pub entity_id: String,
+    pub entity_type: String,
+    pub tag_name: String,
+    /// "domain_area", "layer" or "feature_area"
+    pub category: String,
+    /// 0-1
+    pub confidence: f64,
+    /// Signals behind the suggestion: "field", "keyword" and/or "embedding"
+    pub sources: Vec<String>,
+    pub status: TagSuggestionStatus,
+    pub suggested_at: String,
+    pub reviewed_at: Option<String>,
+}
@@ -0,0 +1,74 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Whether an audited tool call returned a result
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolCallOutcome {
+    Success,
+    /// The call failed or returned a result flagged as an error
+    Error,
+}
+
+impl ToolCallOutcome {
+    pub const ALL: [ToolCallOutcome; 2] = [ToolCallOutcome::Success, ToolCallOutcome::Error];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ToolCallOutcome::Success => "success",
+            ToolCallOutcome::Error => "error",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|outcome| outcome.as_str() == name)
+    }
+}
+
+/// One `call_tool` invocation, kept apart from usage analytics for accountability on shared servers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolCallAudit {
+    pub id: String,
+    pub tool: String,
+    /// MCP client as `name/version` from its `initialize` request
+    pub caller: Option<String>,
+    /// `project_id` argument of the call, if any
+    pub project_id: Option<String>,
+    /// MD5 of the arguments as JSON, so repeated calls can be matched without storing their content
+    pub arguments_hash: String,
+    pub outcome: ToolCallOutcome,
+    /// MCP error code of a failed call
+    pub error_code: Option<i32>,
+    pub error_message: Option<String>,
+    pub duration_ms: u64,
+    /// RFC 3339 in UTC with microseconds, so timestamps sort as text
+    pub created_at: String,
+}
+
+impl ToolCallAudit {
+    pub fn timestamp(at: DateTime<Utc>) -> String {
+        at.to_rfc3339_opts(SecondsFormat::Micros, true)
+    }
+
+    /// Hash of a call's arguments; serde_json keeps object keys sorted, so argument order doesn't matter
+    pub fn hash_arguments(arguments: Option<&serde_json::Map<String, serde_json::Value>>) -> String {
+        let json = match arguments {
+            Some(arguments) => serde_json::Value::Object(arguments.clone()).to_string(),
+            None => "{}".to_string(),
+        };
+        format!("{:x}", md5::compute(json))
+    }
+}
+
+/// Criteria for reading the audit log; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ToolCallAuditFilter {
+    pub tool: Option<String>,
+    pub caller: Option<String>,
+    pub project_id: Option<String>,
+    pub outcome: Option<ToolCallOutcome>,
+    /// Bounds on `created_at`, inclusive
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: usize,
+}
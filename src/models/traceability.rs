@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+
+/// Requirements traced to the tasks implementing them, the framework components those
+/// touch and the commits that changed them, kept as a report of a project at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceabilityReport {
+    pub id: String,
+    pub project_id: String,
+    /// Name the specifications were imported under (their `.kiro/specs` directory)
+    pub spec_project: String,
+    pub rows: Vec<TraceabilityRow>,
+    /// Tasks not linked to any requirement
+    #[serde(default)]
+    pub untraced_tasks: Vec<TracedTask>,
+    pub summary: TraceabilitySummary,
+    /// Commits read from the repository; `None` when it could not be read
+    pub commits_scanned: Option<usize>,
+    pub created_at: Option<String>,
+}
+
+/// One requirement and everything traced to it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceabilityRow {
+    pub spec_id: String,
+    pub requirement_id: String,
+    pub requirement_title: String,
+    pub user_story: Option<String>,
+    pub acceptance_criteria: usize,
+    pub tasks: Vec<TracedTask>,
+    pub components: Vec<TracedComponent>,
+    /// Components named in the requirement or its tasks that the project does not have
+    #[serde(default)]
+    pub missing_components: Vec<String>,
+    pub commits: Vec<TracedCommit>,
+    /// Empty when the requirement is fully covered
+    pub gaps: Vec<CoverageGap>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TracedTask {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TracedComponent {
+    pub id: String,
+    pub name: String,
+    pub file_path: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TracedCommit {
+    pub hash: String,
+    pub message: String,
+    pub author: String,
+    pub timestamp: String,
+}
+
+/// What a requirement is missing on its way from specification to code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverageGap {
+    NoAcceptanceCriteria,
+    NoTasks,
+    TasksIncomplete,
+    NoComponents,
+    MissingComponents,
+    NoCommits,
+}
+
+impl CoverageGap {
+    pub fn as_str(&self) -> &str {
+        match self {
+            CoverageGap::NoAcceptanceCriteria => "no acceptance criteria",
+            CoverageGap::NoTasks => "no tasks",
+            CoverageGap::TasksIncomplete => "tasks incomplete",
+            CoverageGap::NoComponents => "no components",
+            CoverageGap::MissingComponents => "missing components",
+            CoverageGap::NoCommits => "no commits",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TraceabilitySummary {
+    pub requirements: usize,
+    /// Requirements without gaps
+    pub fully_traced: usize,
+    pub with_tasks: usize,
+    pub with_components: usize,
+    pub with_commits: usize,
+    /// Share of requirements without gaps, 0.0 to 1.0
+    pub coverage: f64,
+}
+
+impl TraceabilityReport {
+    /// The matrix as a markdown table, gaps in bold
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# Traceability Matrix: {}\n\n{} of {} requirements fully traced ({:.0}%)\n\n",
+            self.spec_project,
+            self.summary.fully_traced,
+            self.summary.requirements,
+            self.summary.coverage * 100.0
+        );
+        out.push_str("| Requirement | Tasks | Components | Commits | Gaps |\n");
+        out.push_str("|---|---|---|---|---|\n");
+
+        for row in &self.rows {
+            let tasks: Vec<String> = row.tasks.iter().map(|t| format!("{} ({})", t.title, t.status)).collect();
+            let components: Vec<&str> = row.components.iter().map(|c| c.name.as_str()).collect();
+            let commits: Vec<&str> = row.commits.iter().map(|c| &c.hash[..c.hash.len().min(8)]).collect();
+            let gaps: Vec<String> = row.gaps.iter().map(|g| format!("**{}**", g.as_str())).collect();
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                Self::cell(&row.requirement_title),
+                Self::cell(&tasks.join("<br>")),
+                Self::cell(&components.join(", ")),
+                commits.join(", "),
+                gaps.join(", ")
+            ));
+        }
+
+        if !self.untraced_tasks.is_empty() {
+            out.push_str("\n## Tasks without a requirement\n\n");
+            for task in &self.untraced_tasks {
+                out.push_str(&format!("- {} ({})\n", task.title, task.status));
+            }
+        }
+        out
+    }
+
+    fn cell(text: &str) -> String {
+        text.replace('|', "\\|")
+    }
+}
+
+/// Requirements lacking tasks or components, or naming components that do not exist, as
+/// lists a lead can work through
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CoverageAnalysis {
+    pub project_id: String,
+    pub spec_project: String,
+    pub requirements: usize,
+    /// Requirements with tasks and components, all of which exist
+    pub covered: usize,
+    pub no_tasks: Vec<CoverageItem>,
+    pub no_components: Vec<CoverageItem>,
+    pub missing_components: Vec<CoverageItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoverageItem {
+    pub spec_id: String,
+    pub requirement_id: String,
+    pub requirement_title: String,
+    /// Component names that were not found, for `missing_components`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub component_names: Vec<String>,
+}
+
+impl CoverageAnalysis {
+    /// The task and component gaps of a matrix; acceptance criteria, task progress and commits
+    /// are left to the matrix itself
+    pub fn from_report(report: &TraceabilityReport) -> Self {
+        let item = |row: &TraceabilityRow, component_names: Vec<String>| CoverageItem {
+            spec_id: row.spec_id.clone(),
+            requirement_id: row.requirement_id.clone(),
+            requirement_title: row.requirement_title.clone(),
+            component_names,
+        };
+        let mut analysis = CoverageAnalysis {
+            project_id: report.project_id.clone(),
+            spec_project: report.spec_project.clone(),
+            requirements: report.rows.len(),
+            ..Default::default()
+        };
+        for row in &report.rows {
+            let mut covered = true;
+            if row.gaps.contains(&CoverageGap::NoTasks) {
+                analysis.no_tasks.push(item(row, Vec::new()));
+                covered = false;
+            }
+            if row.gaps.contains(&CoverageGap::NoComponents) {
+                analysis.no_components.push(item(row, Vec::new()));
+                covered = false;
+            }
+            if row.gaps.contains(&CoverageGap::MissingComponents) {
+                analysis.missing_components.push(item(row, row.missing_components.clone()));
+                covered = false;
+            }
+            if covered {
+                analysis.covered += 1;
+            }
+        }
+        analysis
+    }
+
+    /// The gap lists as markdown sections, skipping empty ones
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# Requirement Coverage: {}\n\n{} of {} requirements covered\n",
+            self.spec_project, self.covered, self.requirements
+        );
+        let sections = [
+            ("Requirements without tasks", &self.no_tasks),
+            ("Requirements without components", &self.no_components),
+            ("Requirements naming missing components", &self.missing_components),
+        ];
+        for (heading, items) in sections {
+            if items.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n## {}\n\n", heading));
+            for item in items {
+                if item.component_names.is_empty() {
+                    out.push_str(&format!("- {}\n", item.requirement_title));
+                } else {
+                    out.push_str(&format!("- {}: {}\n", item.requirement_title, item.component_names.join(", ")));
+                }
+            }
+        }
+        out
+    }
+}
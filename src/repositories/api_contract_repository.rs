@@ -0,0 +1,14 @@
+use crate::models::api_contract::ApiContract;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for API contracts
+#[async_trait]
+pub trait ApiContractRepository: Send + Sync {
+    async fn create(&self, contract: &ApiContract) -> Result<ApiContract, McpError>;
+    async fn get_by_id(&self, id: &str) -> Result<Option<ApiContract>, McpError>;
+    async fn update(&self, contract: &ApiContract) -> Result<ApiContract, McpError>;
+    async fn delete(&self, id: &str) -> Result<bool, McpError>;
+    /// A project's contracts ordered by path and method
+    async fn list_by_project(&self, project_id: &str) -> Result<Vec<ApiContract>, McpError>;
+}
@@ -0,0 +1,12 @@
+use crate::models::approval::{ApprovalWorkflow, WorkflowEntity};
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for approval workflows of specifications and architectural decisions
+#[async_trait]
+pub trait ApprovalWorkflowRepository: Send + Sync {
+    async fn find(&self, entity_type: WorkflowEntity, entity_id: &str) -> Result<Option<ApprovalWorkflow>, McpError>;
+    /// Inserts the workflow or replaces the stored one of the same item
+    async fn save(&self, workflow: &ApprovalWorkflow) -> Result<ApprovalWorkflow, McpError>;
+    async fn delete(&self, entity_type: WorkflowEntity, entity_id: &str) -> Result<bool, McpError>;
+}
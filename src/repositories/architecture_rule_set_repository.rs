@@ -0,0 +1,12 @@
+use crate::models::architecture::ArchitectureRuleSet;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for the architecture rules each project defines
+#[async_trait]
+pub trait ArchitectureRuleSetRepository: Send + Sync {
+    async fn find_by_project_id(&self, project_id: &str) -> Result<Option<ArchitectureRuleSet>, McpError>;
+    /// Inserts the rule set or replaces the project's stored one
+    async fn save(&self, rule_set: &ArchitectureRuleSet) -> Result<ArchitectureRuleSet, McpError>;
+    async fn delete(&self, project_id: &str) -> Result<bool, McpError>;
+}
@@ -0,0 +1,24 @@
+use crate::models::attachment::Attachment;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for what is known about attachments; their contents live in a `BlobStore`
+#[async_trait]
+pub trait AttachmentRepository: Send + Sync {
+    async fn save(&self, attachment: &Attachment) -> Result<(), McpError>;
+    async fn find(&self, id: &str) -> Result<Option<Attachment>, McpError>;
+    /// Attachments of an entity, oldest first
+    async fn find_by_entity(&self, entity_id: &str) -> Result<Vec<Attachment>, McpError>;
+    /// Remove an entity's attachments in one transaction, returning how many there were and
+    /// the hashes of the contents no remaining attachment uses
+    async fn delete_by_entity(&self, entity_id: &str) -> Result<(usize, Vec<String>), McpError>;
+}
+
+/// Content-addressed storage of attachment contents, keyed by their hex SHA-256
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Store the content unless content with this hash is already stored
+    async fn put(&self, content_hash: &str, content: &[u8]) -> Result<(), McpError>;
+    async fn get(&self, content_hash: &str) -> Result<Option<Vec<u8>>, McpError>;
+    async fn delete(&self, content_hash: &str) -> Result<(), McpError>;
+}
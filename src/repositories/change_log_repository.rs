@@ -0,0 +1,29 @@
+use crate::services::websocket_types::ContextChange;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for the log of broadcast changes
+#[async_trait]
+pub trait ChangeLogRepository: Send + Sync {
+    /// Store a change, returning its sequence number
+    async fn append(&self, change: &ContextChange) -> Result<u64, McpError>;
+    /// Up to `limit` changes logged after `sequence`, oldest first, with `metadata.sequence` set
+    async fn find_since(&self, sequence: u64, limit: usize) -> Result<Vec<ContextChange>, McpError>;
+    /// Latest change to an entity recorded before `before`, with `metadata.sequence` set
+    async fn find_latest_before(
+        &self,
+        entity_type: &str,
+        entity_id: &str,
+        before: DateTime<Utc>,
+    ) -> Result<Option<ContextChange>, McpError>;
+    /// Distinct ids of a project's entities of one type changed at or after `since`
+    async fn find_changed_entities(
+        &self,
+        project_id: &str,
+        entity_type: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<String>, McpError>;
+    /// Drop changes recorded before `cutoff`, returning how many were removed
+    async fn delete_before(&self, cutoff: DateTime<Utc>) -> Result<usize, McpError>;
+}
@@ -0,0 +1,20 @@
+use crate::services::conflict_resolution_engine::ConflictInfo;
+use crate::services::conflict_resolution_ui::ConflictResolutionSession;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for detected conflicts and the sessions resolving them
+#[async_trait]
+pub trait ConflictRepository: Send + Sync {
+    /// Insert or replace a conflict
+    async fn save_conflict(&self, conflict: &ConflictInfo) -> Result<(), McpError>;
+    async fn find_conflict(&self, conflict_id: &str) -> Result<Option<ConflictInfo>, McpError>;
+    /// Conflicts of a project, newest first; only unresolved or resolved ones when `resolved` is set
+    async fn find_conflicts(&self, project_id: &str, resolved: Option<bool>) -> Result<Vec<ConflictInfo>, McpError>;
+    /// Insert or replace a session
+    async fn save_session(&self, session: &ConflictResolutionSession) -> Result<(), McpError>;
+    async fn find_session(&self, session_id: &str) -> Result<Option<ConflictResolutionSession>, McpError>;
+    /// Drop sessions that timed out before `now`, returning how many were removed
+    async fn delete_expired_sessions(&self, now: DateTime<Utc>) -> Result<usize, McpError>;
+}
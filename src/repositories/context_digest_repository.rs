@@ -0,0 +1,16 @@
+use crate::models::context_digest::ContextDigest;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for context digests, one per project and feature area
+#[async_trait]
+pub trait ContextDigestRepository: Send + Sync {
+    /// Insert, or replace the digest of the same project and feature area
+    async fn save(&self, digest: &ContextDigest) -> Result<(), McpError>;
+    /// Feature areas compare ignoring case
+    async fn find(&self, project_id: &str, feature_area: &str) -> Result<Option<ContextDigest>, McpError>;
+    async fn list_by_project(&self, project_id: &str) -> Result<Vec<ContextDigest>, McpError>;
+    /// Digests in any project that summarize `entity_id`
+    async fn list_containing(&self, entity_id: &str) -> Result<Vec<ContextDigest>, McpError>;
+    async fn set_stale(&self, id: &str, stale: bool) -> Result<(), McpError>;
+}
@@ -0,0 +1,22 @@
+use crate::models::custom_entity::{CustomEntity, CustomEntityType};
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for the custom entity type registry and the entities of those types
+#[async_trait]
+pub trait CustomEntityRepository: Send + Sync {
+    /// Inserts the type or replaces the one with its name, keeping its creation time
+    async fn save_type(&self, entity_type: &CustomEntityType) -> Result<(), McpError>;
+    async fn get_type(&self, name: &str) -> Result<Option<CustomEntityType>, McpError>;
+    async fn list_types(&self) -> Result<Vec<CustomEntityType>, McpError>;
+    async fn delete_type(&self, name: &str) -> Result<bool, McpError>;
+    /// Entities of the type across all projects
+    async fn count_of_type(&self, name: &str) -> Result<usize, McpError>;
+
+    async fn create(&self, entity: &CustomEntity) -> Result<CustomEntity, McpError>;
+    async fn get_by_id(&self, id: &str) -> Result<Option<CustomEntity>, McpError>;
+    async fn update(&self, entity: &CustomEntity) -> Result<CustomEntity, McpError>;
+    async fn delete(&self, id: &str) -> Result<bool, McpError>;
+    /// A project's custom entities, optionally of one type, newest first
+    async fn list_by_project(&self, project_id: &str, custom_type: Option<&str>) -> Result<Vec<CustomEntity>, McpError>;
+}
@@ -0,0 +1,14 @@
+use crate::models::decision_link::{DecisionLink, DecisionLinkType};
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for typed links between architectural decisions
+#[async_trait]
+pub trait DecisionLinkRepository: Send + Sync {
+    /// Inserts the link or replaces the one of the same decisions and type
+    async fn save(&self, link: &DecisionLink) -> Result<(), McpError>;
+    async fn delete(&self, from_decision_id: &str, to_decision_id: &str, link_type: DecisionLinkType) -> Result<bool, McpError>;
+    async fn find_by_project(&self, project_id: &str) -> Result<Vec<DecisionLink>, McpError>;
+    /// Remove every link to or from a deleted decision
+    async fn delete_by_decision(&self, decision_id: &str) -> Result<usize, McpError>;
+}
@@ -1,4 +1,5 @@
 use crate::models::embedding::{ContextEmbedding, VectorSearchQuery, VectorSearchResult};
+use crate::services::vector_embedding_integration::chunk_id_prefix;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, Result as SqliteResult, Row};
@@ -58,7 +59,7 @@ pub trait EmbeddingRepository: Send + Sync {
     /// Update embedding
     async fn update_embedding(&self, embedding: &ContextEmbedding) -> Result<(), EmbeddingRepositoryError>;
     
-    /// Delete embedding by context ID
+    /// Delete embedding by context ID, with any extra chunks stored for it
     async fn delete_embedding(&self, context_id: &str) -> Result<(), EmbeddingRepositoryError>;
     
     /// Delete all embeddings for a project
@@ -69,6 +70,43 @@ pub trait EmbeddingRepository: Send + Sync {
     
     /// Check if embedding exists for context
     async fn embedding_exists(&self, context_id: &str) -> Result<bool, EmbeddingRepositoryError>;
+
+    /// Retrieve the embedding `model` produced for a context at `dimension`
+    async fn get_embedding_for_model(&self, context_id: &str, model: &str, dimension: usize) -> Result<Option<ContextEmbedding>, EmbeddingRepositoryError>;
+
+    /// Stored vectors per model and dimension
+    async fn count_embeddings_by_model(&self) -> Result<Vec<ModelEmbeddingCount>, EmbeddingRepositoryError>;
+
+    /// Delete every embedding not produced by `model` at `dimension`; returns how many were removed
+    async fn delete_embeddings_except(&self, model: &str, dimension: usize) -> Result<u64, EmbeddingRepositoryError>;
+
+    /// Delete the extra chunks stored for a chunked entity, only `model`'s when given;
+    /// the first chunk, stored under the entity's id, is kept
+    async fn delete_chunks(&self, entity_id: &str, model: Option<&str>) -> Result<u64, EmbeddingRepositoryError>;
+
+    /// Every stored vector without its data, in one project when given
+    async fn list_index_entries(&self, project_id: Option<&str>) -> Result<Vec<IndexEntry>, EmbeddingRepositoryError>;
+}
+
+/// A stored vector's bookkeeping, for index statistics
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexEntry {
+    pub context_id: String,
+    pub project_id: Option<String>,
+    pub content_type: Option<String>,
+    pub model: String,
+    pub dimension: usize,
+    /// Bytes of the stored vector and its metadata
+    pub size_bytes: u64,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// Number of stored vectors from one model
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ModelEmbeddingCount {
+    pub model: String,
+    pub dimension: usize,
+    pub count: u64,
 }
 
 /// Statistics about embeddings
@@ -94,7 +132,13 @@ impl SqliteEmbeddingRepository {
     /// Initialize the embeddings table
     pub async fn initialize(&self) -> Result<(), EmbeddingRepositoryError> {
         let conn = self.connection.lock().await;
-        
+        Self::create_tables(&conn)?;
+        info!("Embedding repository initialized successfully");
+        Ok(())
+    }
+
+    /// Create the embeddings table on a connection before it is shared
+    pub fn create_tables(conn: &Connection) -> Result<(), EmbeddingRepositoryError> {
         conn.execute_batch(
             r#"
             CREATE TABLE IF NOT EXISTS context_embeddings (
@@ -113,6 +157,7 @@ impl SqliteEmbeddingRepository {
                 custom_metadata TEXT, -- JSON object
                 created_at TEXT NOT NULL,
                 updated_at TEXT DEFAULT (datetime('now')),
+                embedding_dimension INTEGER,
                 FOREIGN KEY (project_id) REFERENCES projects(id),
                 UNIQUE(context_id, embedding_model, embedding_version)
             );
@@ -124,11 +169,33 @@ impl SqliteEmbeddingRepository {
             CREATE INDEX IF NOT EXISTS idx_embeddings_created_at ON context_embeddings(created_at);
             "#,
         )?;
-        
-        info!("Embedding repository initialized successfully");
+
+        // Databases created before vectors recorded their dimension
+        let has_dimension: bool = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('context_embeddings') WHERE name = 'embedding_dimension'",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_dimension {
+            conn.execute_batch(
+                "ALTER TABLE context_embeddings ADD COLUMN embedding_dimension INTEGER;
+                 UPDATE context_embeddings SET embedding_dimension = json_array_length(embedding_vector);",
+            )?;
+            info!("Added embedding_dimension to stored embeddings");
+        }
         Ok(())
     }
     
+    /// Project recorded by the indexer in `custom_fields["project_id"]`
+    fn project_id(embedding: &ContextEmbedding) -> Option<String> {
+        embedding
+            .metadata
+            .custom_fields
+            .get("project_id")
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+    }
+
     /// Convert database row to ContextEmbedding
     fn row_to_embedding(&self, row: &Row) -> SqliteResult<ContextEmbedding> {
         let embedding_vector_json: String = row.get("embedding_vector")?;
@@ -212,9 +279,7 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
         let preprocessing_steps_json = serde_json::to_string(&embedding.metadata.preprocessing_steps)?;
         let custom_metadata_json = serde_json::to_string(&embedding.metadata.custom_fields)?;
         
-        // Extract project_id from context (this would need to be passed or looked up)
-        // For now, we'll leave it as None and handle it in the service layer
-        let project_id: Option<String> = None;
+        let project_id = Self::project_id(embedding);
         
         conn.execute(
             r#"
@@ -222,8 +287,8 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
                 id, context_id, project_id, embedding_vector, embedding_model, 
                 embedding_version, content_hash, content_type, content_length,
                 tokenization_method, preprocessing_steps, quality_score, 
-                custom_metadata, created_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+                custom_metadata, created_at, embedding_dimension
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
             "#,
             params![
                 embedding.id,
@@ -240,6 +305,7 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
                 embedding.metadata.quality_score,
                 custom_metadata_json,
                 embedding.created_at.to_rfc3339(),
+                embedding.embedding_vector.len() as i64,
             ],
         )?;
         
@@ -257,7 +323,7 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
             let preprocessing_steps_json = serde_json::to_string(&embedding.metadata.preprocessing_steps)?;
             let custom_metadata_json = serde_json::to_string(&embedding.metadata.custom_fields)?;
             
-            let project_id: Option<String> = None; // Would be determined by service layer
+            let project_id = Self::project_id(embedding);
             
             tx.execute(
                 r#"
@@ -265,8 +331,8 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
                     id, context_id, project_id, embedding_vector, embedding_model, 
                     embedding_version, content_hash, content_type, content_length,
                     tokenization_method, preprocessing_steps, quality_score, 
-                    custom_metadata, created_at
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+                    custom_metadata, created_at, embedding_dimension
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
                 "#,
                 params![
                     embedding.id,
@@ -283,6 +349,7 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
                     embedding.metadata.quality_score,
                     custom_metadata_json,
                     embedding.created_at.to_rfc3339(),
+                    embedding.embedding_vector.len() as i64,
                 ],
             )?;
         }
@@ -385,13 +452,26 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
                 message: "Query embedding not provided".to_string()
             })?;
         
+        // Vectors from another model (or the same model at another dimension) live in a
+        // different space; comparing them would produce meaningless scores
+        let mut mismatched = 0;
         for embedding in embeddings {
-            
+            let other_model = query
+                .filters
+                .embedding_model
+                .as_ref()
+                .is_some_and(|model| *model != embedding.embedding_model);
+            if other_model || embedding.embedding_vector.len() != query_embedding.len() {
+                mismatched += 1;
+                continue;
+            }
+
             // Calculate similarity
             let similarity = self.cosine_similarity(query_embedding, &embedding.embedding_vector);
             
             if similarity >= query.similarity_threshold {
                 let distance = 1.0 - similarity;
+                let project_id = Self::project_id(&embedding);
                 
                 results.push(VectorSearchResult {
                     context_id: embedding.context_id,
@@ -399,13 +479,26 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
                     distance,
                     rank: 0, // Will be set after sorting
                     metadata: crate::models::embedding::ResultMetadata {
+                        content_preview: embedding
+                            .metadata
+                            .custom_fields
+                            .get("preview")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("Preview not available")
+                            .to_string(),
                         content_type: embedding.metadata.content_type,
-                        content_preview: "Preview not available".to_string(),
                         match_explanation: format!("Cosine similarity: {:.3}", similarity),
                         quality_indicators: vec![
                             format!("Quality: {:.2}", embedding.metadata.quality_score),
                             format!("Model: {}", embedding.embedding_model),
                         ],
+                        facets: embedding
+                            .metadata
+                            .custom_fields
+                            .get("facets")
+                            .and_then(|v| serde_json::from_value(v.clone()).ok())
+                            .unwrap_or_default(),
+                        project_id,
                     },
                 });
             }
@@ -420,7 +513,15 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
         }
         
         results.truncate(query.max_results);
-        
+
+        if mismatched > 0 {
+            warn!(
+                "Skipped {} stored embeddings from a different model or dimension than the query ({}, {} dimensions); run migrate_embeddings or reindex_project",
+                mismatched,
+                query.filters.embedding_model.as_deref().unwrap_or("unknown model"),
+                query_embedding.len()
+            );
+        }
         debug!("Found {} similar embeddings", results.len());
         Ok(results)
     }
@@ -434,8 +535,9 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
         let conn = self.connection.lock().await;
         
         let rows_affected = conn.execute(
-            "DELETE FROM context_embeddings WHERE context_id = ?1",
-            [context_id],
+            "DELETE FROM context_embeddings
+             WHERE context_id = ?1 OR substr(context_id, 1, length(?2)) = ?2",
+            params![context_id, chunk_id_prefix(context_id)],
         )?;
         
         if rows_affected == 0 {
@@ -535,8 +637,19 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
         let newest_embedding = newest_str.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
             .map(|dt| dt.with_timezone(&Utc));
         
-        // Calculate average dimension (this is a rough estimate)
-        let average_vector_dimension = 384.0; // Default for most sentence transformers
+        let average_vector_dimension: f64 = if let Some(pid) = project_id {
+            conn.query_row(
+                "SELECT COALESCE(AVG(embedding_dimension), 0) FROM context_embeddings WHERE project_id = ?1",
+                [pid],
+                |row| row.get(0),
+            )?
+        } else {
+            conn.query_row(
+                "SELECT COALESCE(AVG(embedding_dimension), 0) FROM context_embeddings",
+                [],
+                |row| row.get(0),
+            )?
+        };
         
         Ok(EmbeddingStats {
             total_embeddings,
@@ -558,4 +671,96 @@ impl EmbeddingRepository for SqliteEmbeddingRepository {
         
         Ok(count > 0)
     }
+
+    async fn get_embedding_for_model(&self, context_id: &str, model: &str, dimension: usize) -> Result<Option<ContextEmbedding>, EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT id, context_id, embedding_vector, embedding_model, embedding_version,
+                   content_hash, content_type, content_length, tokenization_method,
+                   preprocessing_steps, quality_score, custom_metadata, created_at
+            FROM context_embeddings
+            WHERE context_id = ?1 AND embedding_model = ?2 AND embedding_dimension = ?3
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )?;
+
+        let mut embeddings = stmt.query_map(params![context_id, model, dimension as i64], |row| {
+            self.row_to_embedding(row)
+        })?;
+        Ok(embeddings.next().transpose()?)
+    }
+
+    async fn count_embeddings_by_model(&self) -> Result<Vec<ModelEmbeddingCount>, EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+
+        let mut stmt = conn.prepare(
+            "SELECT embedding_model, COALESCE(embedding_dimension, 0), COUNT(*) FROM context_embeddings
+             GROUP BY embedding_model, embedding_dimension ORDER BY COUNT(*) DESC",
+        )?;
+        let counts = stmt.query_map([], |row| {
+            Ok(ModelEmbeddingCount {
+                model: row.get(0)?,
+                dimension: row.get::<_, i64>(1)? as usize,
+                count: row.get::<_, i64>(2)? as u64,
+            })
+        })?;
+        Ok(counts.collect::<Result<_, _>>()?)
+    }
+
+    async fn delete_embeddings_except(&self, model: &str, dimension: usize) -> Result<u64, EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+
+        let rows_affected = conn.execute(
+            "DELETE FROM context_embeddings
+             WHERE embedding_model != ?1 OR embedding_dimension IS NULL OR embedding_dimension != ?2",
+            params![model, dimension as i64],
+        )?;
+
+        info!("Deleted {} embeddings not produced by {} ({} dimensions)", rows_affected, model, dimension);
+        Ok(rows_affected as u64)
+    }
+
+    async fn delete_chunks(&self, entity_id: &str, model: Option<&str>) -> Result<u64, EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+
+        let prefix = chunk_id_prefix(entity_id);
+        let rows_affected = conn.execute(
+            "DELETE FROM context_embeddings
+             WHERE substr(context_id, 1, length(?1)) = ?1 AND (?2 IS NULL OR embedding_model = ?2)",
+            params![prefix, model],
+        )?;
+
+        debug!("Deleted {} chunks of {}", rows_affected, entity_id);
+        Ok(rows_affected as u64)
+    }
+
+    async fn list_index_entries(&self, project_id: Option<&str>) -> Result<Vec<IndexEntry>, EmbeddingRepositoryError> {
+        let conn = self.connection.lock().await;
+
+        let mut stmt = conn.prepare(
+            "SELECT context_id, project_id, content_type, embedding_model, COALESCE(embedding_dimension, 0),
+                    length(embedding_vector) + COALESCE(length(custom_metadata), 0), created_at
+             FROM context_embeddings
+             WHERE ?1 IS NULL OR project_id = ?1",
+        )?;
+        let entries = stmt.query_map([project_id], |row| {
+            Ok(IndexEntry {
+                context_id: row.get(0)?,
+                project_id: row.get(1)?,
+                content_type: row.get(2)?,
+                model: row.get(3)?,
+                dimension: row.get::<_, i64>(4)? as usize,
+                size_bytes: row.get::<_, i64>(5)? as u64,
+                created_at: row
+                    .get::<_, String>(6)
+                    .ok()
+                    .and_then(|created_at| DateTime::parse_from_rfc3339(&created_at).ok())
+                    .map(|created_at| created_at.with_timezone(&Utc)),
+            })
+        })?;
+        Ok(entries.collect::<Result<_, _>>()?)
+    }
 }
\ No newline at end of file
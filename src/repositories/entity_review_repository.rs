@@ -0,0 +1,15 @@
+use crate::models::review::EntityReview;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for review requests and verdicts on entities
+#[async_trait]
+pub trait EntityReviewRepository: Send + Sync {
+    /// Inserts the review or replaces the one with its id
+    async fn save(&self, review: &EntityReview) -> Result<(), McpError>;
+    async fn find(&self, id: &str) -> Result<Option<EntityReview>, McpError>;
+    /// Reviews of an entity, newest request or verdict first
+    async fn find_by_entity(&self, entity_id: &str) -> Result<Vec<EntityReview>, McpError>;
+    /// Reviews of a project's entities, newest request or verdict first
+    async fn find_by_project(&self, project_id: &str) -> Result<Vec<EntityReview>, McpError>;
+}
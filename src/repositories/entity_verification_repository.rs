@@ -0,0 +1,12 @@
+use crate::models::freshness::EntityVerification;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for when entities were last verified
+#[async_trait]
+pub trait EntityVerificationRepository: Send + Sync {
+    /// Inserts the verification or replaces the entity's earlier one
+    async fn record(&self, verification: &EntityVerification) -> Result<(), McpError>;
+    async fn find(&self, entity_id: &str) -> Result<Option<EntityVerification>, McpError>;
+    async fn find_by_project(&self, project_id: &str) -> Result<Vec<EntityVerification>, McpError>;
+}
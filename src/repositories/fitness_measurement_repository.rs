@@ -0,0 +1,16 @@
+use crate::models::architecture::FitnessMeasurement;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for the recorded values of architecture fitness functions
+#[async_trait]
+pub trait FitnessMeasurementRepository: Send + Sync {
+    async fn record(&self, measurements: &[FitnessMeasurement]) -> Result<(), McpError>;
+    /// Newest first, of one function or of all the project's functions
+    async fn history(
+        &self,
+        project_id: &str,
+        function: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<FitnessMeasurement>, McpError>;
+}
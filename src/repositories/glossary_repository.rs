@@ -0,0 +1,13 @@
+use crate::models::glossary::GlossaryEntry;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for Glossary operations
+#[async_trait]
+pub trait GlossaryRepository: Send + Sync {
+    async fn create(&self, entry: &GlossaryEntry) -> Result<GlossaryEntry, McpError>;
+    async fn find_by_id(&self, id: &str) -> Result<Option<GlossaryEntry>, McpError>;
+    async fn find_by_project_id(&self, project_id: &str) -> Result<Vec<GlossaryEntry>, McpError>;
+    async fn update(&self, entry: &GlossaryEntry) -> Result<GlossaryEntry, McpError>;
+    async fn delete(&self, id: &str) -> Result<bool, McpError>;
+}
@@ -1,31 +1,71 @@
 // Repository layer interfaces following Dependency Inversion Principle
 
+pub mod approval_workflow_repository;
+pub mod api_contract_repository;
+pub mod attachment_repository;
+pub mod architecture_rule_set_repository;
 pub mod architectural_decision_repository;
 pub mod business_rule_repository;
+pub mod change_log_repository;
+pub mod conflict_repository;
+pub mod context_digest_repository;
+pub mod custom_entity_repository;
+pub mod decision_link_repository;
 pub mod development_phase_repository;
 pub mod embedding_repository;
 pub mod enhanced_context_repository;
+pub mod entity_review_repository;
+pub mod entity_verification_repository;
 pub mod extended_repositories;
 pub mod feature_context_repository;
+pub mod fitness_measurement_repository;
 pub mod framework_repository;
+pub mod glossary_repository;
 pub mod performance_requirement_repository;
 pub mod project_convention_repository;
 pub mod project_repository;
+pub mod quality_rubric_repository;
+pub mod saved_search_repository;
+pub mod scheduled_report_repository;
 pub mod security_policy_repository;
+pub mod shared_context_link_repository;
 pub mod specification_repository;
+pub mod tag_repository;
+pub mod tool_call_audit_repository;
+pub mod traceability_report_repository;
 // Note: component_repository was removed as it was identical to framework_repository
 
 // Re-export repository traits
+pub use approval_workflow_repository::ApprovalWorkflowRepository;
+pub use api_contract_repository::ApiContractRepository;
+pub use attachment_repository::{AttachmentRepository, BlobStore};
+pub use architecture_rule_set_repository::ArchitectureRuleSetRepository;
 pub use architectural_decision_repository::ArchitecturalDecisionRepository;
 pub use business_rule_repository::BusinessRuleRepository;
+pub use change_log_repository::ChangeLogRepository;
+pub use conflict_repository::ConflictRepository;
+pub use context_digest_repository::ContextDigestRepository;
+pub use custom_entity_repository::CustomEntityRepository;
+pub use decision_link_repository::DecisionLinkRepository;
 pub use development_phase_repository::DevelopmentPhaseRepository;
 pub use embedding_repository::EmbeddingRepository;
 pub use enhanced_context_repository::EnhancedContextRepository;
+pub use entity_review_repository::EntityReviewRepository;
+pub use entity_verification_repository::EntityVerificationRepository;
 pub use feature_context_repository::FeatureContextRepository;
+pub use fitness_measurement_repository::FitnessMeasurementRepository;
+pub use glossary_repository::GlossaryRepository;
 pub use performance_requirement_repository::PerformanceRequirementRepository;
 pub use project_convention_repository::ProjectConventionRepository;
 pub use project_repository::ProjectRepository;
+pub use quality_rubric_repository::QualityRubricRepository;
+pub use saved_search_repository::SavedSearchRepository;
+pub use scheduled_report_repository::ScheduledReportRepository;
 pub use security_policy_repository::SecurityPolicyRepository;
+pub use shared_context_link_repository::SharedContextLinkRepository;
 pub use specification_repository::SpecificationRepository;
+pub use tag_repository::TagRepository;
+pub use tool_call_audit_repository::ToolCallAuditRepository;
+pub use traceability_report_repository::TraceabilityReportRepository;
 // pub use extended_repositories::{}; // Uncomment when needed
 pub use framework_repository::FrameworkRepository;
@@ -0,0 +1,12 @@
+use crate::models::quality_rubric::QualityRubric;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for the quality rubrics projects define
+#[async_trait]
+pub trait QualityRubricRepository: Send + Sync {
+    async fn find_by_project_id(&self, project_id: &str) -> Result<Option<QualityRubric>, McpError>;
+    /// Inserts the rubric or replaces the project's stored one
+    async fn save(&self, rubric: &QualityRubric) -> Result<QualityRubric, McpError>;
+    async fn delete(&self, project_id: &str) -> Result<bool, McpError>;
+}
@@ -0,0 +1,14 @@
+use crate::models::saved_search::SavedSearch;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for Saved Search operations
+#[async_trait]
+pub trait SavedSearchRepository: Send + Sync {
+    async fn create(&self, search: &SavedSearch) -> Result<SavedSearch, McpError>;
+    async fn find_by_project_id(&self, project_id: &str) -> Result<Vec<SavedSearch>, McpError>;
+    async fn find_by_name(&self, project_id: &str, name: &str) -> Result<Option<SavedSearch>, McpError>;
+    async fn find_by_id(&self, id: &str) -> Result<Option<SavedSearch>, McpError>;
+    async fn update(&self, search: &SavedSearch) -> Result<SavedSearch, McpError>;
+    async fn delete(&self, id: &str) -> Result<bool, McpError>;
+}
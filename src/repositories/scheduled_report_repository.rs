@@ -0,0 +1,20 @@
+use crate::models::scheduled_report::{ScheduledReport, ScheduledReportKind};
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for reports generated by the report scheduler
+#[async_trait]
+pub trait ScheduledReportRepository: Send + Sync {
+    async fn create(&self, report: &ScheduledReport) -> Result<ScheduledReport, McpError>;
+    /// Newest first, optionally of one kind
+    async fn find_by_project_id(
+        &self,
+        project_id: &str,
+        kind: Option<ScheduledReportKind>,
+    ) -> Result<Vec<ScheduledReport>, McpError>;
+    async fn find_latest(&self, project_id: &str, kind: ScheduledReportKind) -> Result<Option<ScheduledReport>, McpError>;
+    async fn find_by_id(&self, id: &str) -> Result<Option<ScheduledReport>, McpError>;
+    async fn delete(&self, id: &str) -> Result<bool, McpError>;
+    /// Delete all but the newest `keep` reports of a kind, returning how many went
+    async fn prune(&self, project_id: &str, kind: ScheduledReportKind, keep: usize) -> Result<usize, McpError>;
+}
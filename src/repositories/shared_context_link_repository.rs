@@ -0,0 +1,17 @@
+use crate::models::shared_context::SharedContextLink;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for the provenance of entities promoted to a shared project
+#[async_trait]
+pub trait SharedContextLinkRepository: Send + Sync {
+    /// Inserts the link or replaces the one of the same shared entity
+    async fn save(&self, link: &SharedContextLink) -> Result<(), McpError>;
+    /// The copy of `source_entity_id` in `shared_project_id`, if it was promoted there
+    async fn find_by_source(
+        &self,
+        shared_project_id: &str,
+        source_entity_id: &str,
+    ) -> Result<Option<SharedContextLink>, McpError>;
+    async fn find_by_shared_project(&self, shared_project_id: &str) -> Result<Vec<SharedContextLink>, McpError>;
+}
@@ -1,4 +1,4 @@
-use crate::models::specification::{ProjectSpecification, Requirement, Task};
+use crate::models::specification::{ProjectSpecification, Requirement, RequirementContextLink, Task};
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
 
@@ -34,4 +34,10 @@ pub trait SpecificationRepository: Send + Sync {
     async fn unlink_requirement_from_context(&self, requirement_id: &str, context_id: &str) -> Result<(), McpError>;
     async fn unlink_task_from_context(&self, task_id: &str, context_id: &str) -> Result<(), McpError>;
     async fn unlink_task_from_requirement(&self, task_id: &str, requirement_id: &str) -> Result<(), McpError>;
+    /// Requirements with an accepted link to the context item
+    async fn find_requirement_ids_by_context(&self, context_id: &str) -> Result<Vec<String>, McpError>;
+
+    // Suggested links, which `link_requirement_to_context` accepts
+    async fn suggest_requirement_context_link(&self, link: &RequirementContextLink) -> Result<(), McpError>;
+    async fn find_suggested_context_links(&self, spec_id: &str) -> Result<Vec<RequirementContextLink>, McpError>;
 }
\ No newline at end of file
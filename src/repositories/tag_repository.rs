@@ -0,0 +1,27 @@
+use crate::models::tagging::{ContextTag, TagSuggestion, TagSuggestionStatus, TaggedEntity};
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for tags, the entities carrying them and suggested tags
+#[async_trait]
+pub trait TagRepository: Send + Sync {
+    /// The project's tag with this name, created from `tag` when there is none
+    async fn find_or_create_tag(&self, tag: &ContextTag) -> Result<ContextTag, McpError>;
+    async fn find_tag(&self, project_id: &str, tag_name: &str) -> Result<Option<ContextTag>, McpError>;
+    /// False when the entity already had the tag
+    async fn tag_entity(&self, tagged: &TaggedEntity) -> Result<bool, McpError>;
+    async fn untag_entity(&self, entity_id: &str, tag_id: &str) -> Result<bool, McpError>;
+    async fn find_tagged_entities(&self, tag_id: &str) -> Result<Vec<TaggedEntity>, McpError>;
+    async fn list_tags(&self, project_id: &str) -> Result<Vec<ContextTag>, McpError>;
+    /// Insert or replace
+    async fn save_suggestion(&self, suggestion: &TagSuggestion) -> Result<(), McpError>;
+    async fn find_suggestion(&self, id: &str) -> Result<Option<TagSuggestion>, McpError>;
+    /// Most confident first
+    async fn find_suggestions(
+        &self,
+        project_id: &str,
+        status: Option<TagSuggestionStatus>,
+    ) -> Result<Vec<TagSuggestion>, McpError>;
+    async fn find_entity_suggestions(&self, entity_id: &str) -> Result<Vec<TagSuggestion>, McpError>;
+    async fn delete_suggestion(&self, id: &str) -> Result<bool, McpError>;
+}
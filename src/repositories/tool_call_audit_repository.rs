@@ -0,0 +1,14 @@
+use crate::models::tool_call_audit::{ToolCallAudit, ToolCallAuditFilter};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for the tool call audit log
+#[async_trait]
+pub trait ToolCallAuditRepository: Send + Sync {
+    async fn record(&self, entry: &ToolCallAudit) -> Result<(), McpError>;
+    /// Newest first, at most `filter.limit`
+    async fn find(&self, filter: &ToolCallAuditFilter) -> Result<Vec<ToolCallAudit>, McpError>;
+    /// Delete entries created before `before`, returning how many went
+    async fn prune(&self, before: DateTime<Utc>) -> Result<usize, McpError>;
+}
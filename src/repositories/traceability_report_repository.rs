@@ -0,0 +1,13 @@
+use crate::models::traceability::TraceabilityReport;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+
+/// Repository interface for Traceability Report operations
+#[async_trait]
+pub trait TraceabilityReportRepository: Send + Sync {
+    async fn create(&self, report: &TraceabilityReport) -> Result<TraceabilityReport, McpError>;
+    /// Newest first
+    async fn find_by_project_id(&self, project_id: &str) -> Result<Vec<TraceabilityReport>, McpError>;
+    async fn find_by_id(&self, id: &str) -> Result<Option<TraceabilityReport>, McpError>;
+    async fn delete(&self, id: &str) -> Result<bool, McpError>;
+}
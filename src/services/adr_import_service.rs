@@ -0,0 +1,307 @@
+use crate::cache::{CacheKeyBuilder, QueryCache};
+use crate::models::approval::WorkflowEntity;
+use crate::models::context::ArchitecturalDecision;
+use crate::repositories::{ApprovalWorkflowRepository, ArchitecturalDecisionRepository};
+use crate::services::adr_parser::{AdrParser, ParsedAdr};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use parking_lot::Mutex;
+use rmcp::model::ErrorData as McpError;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tracing::{debug, info, warn};
+
+/// Service for importing and monitoring architecture decision records kept as markdown
+/// files (`docs/adr/NNNN-*.md`)
+#[async_trait]
+pub trait AdrImportService: Send + Sync {
+    /// Import every ADR file under `base_path` as an architectural decision of the project
+    async fn scan_and_import_adrs(&self, project_id: &str, base_path: &Path) -> Result<Vec<ArchitecturalDecision>, McpError>;
+
+    /// Import a single ADR file, updating the decision imported from it before
+    async fn import_adr_file(&self, project_id: &str, file_path: &Path) -> Result<ArchitecturalDecision, McpError>;
+
+    /// Re-import ADR files under `base_path` as they change and remove decisions whose file is deleted
+    async fn start_file_monitoring(&self, project_id: &str, base_path: &Path) -> Result<(), McpError>;
+
+    /// Stop every file monitor
+    async fn stop_file_monitoring(&self) -> Result<(), McpError>;
+}
+
+/// Default implementation of AdrImportService
+pub struct DefaultAdrImportService {
+    importer: AdrImporter,
+    watchers: Mutex<HashMap<PathBuf, RecommendedWatcher>>,
+}
+
+/// What a monitor needs to import files in the background
+#[derive(Clone)]
+struct AdrImporter {
+    repository: Arc<dyn ArchitecturalDecisionRepository>,
+    query_cache: Option<Arc<QueryCache>>,
+    workflow_repository: Option<Arc<dyn ApprovalWorkflowRepository>>,
+}
+
+impl DefaultAdrImportService {
+    pub fn new(repository: Arc<dyn ArchitecturalDecisionRepository>) -> Self {
+        Self {
+            importer: AdrImporter {
+                repository,
+                query_cache: None,
+                workflow_repository: None,
+            },
+            watchers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Drop cached decisions when a monitored file is imported; tool calls are invalidated by the server
+    pub fn with_query_cache(mut self, query_cache: Arc<QueryCache>) -> Self {
+        self.importer.query_cache = Some(query_cache);
+        self
+    }
+
+    /// Keep the status of decisions in the approval workflow when their file is re-imported
+    pub fn with_workflow_repository(mut self, workflow_repository: Arc<dyn ApprovalWorkflowRepository>) -> Self {
+        self.importer.workflow_repository = Some(workflow_repository);
+        self
+    }
+
+    /// Decisions imported from an ADR keep the same id across imports: the project and the ADR number
+    pub fn decision_id(project_id: &str, file_path: &Path, adr: &ParsedAdr) -> String {
+        match adr.number {
+            Some(number) => format!("adr-{}-{:04}", project_id, number),
+            None => format!(
+                "adr-{}-{}",
+                project_id,
+                file_path.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default()
+            ),
+        }
+    }
+
+    /// Scan directory recursively for ADR files
+    fn scan_directory(dir_path: &Path) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Vec<PathBuf>>> + Send + '_>> {
+        Box::pin(async move {
+            let mut adr_files = Vec::new();
+            let mut entries = fs::read_dir(dir_path).await
+                .map_err(|e| anyhow!("Failed to read directory {}: {}", dir_path.display(), e))?;
+
+            while let Some(entry) = entries.next_entry().await
+                .map_err(|e| anyhow!("Failed to read directory entry: {}", e))? {
+                let path = entry.path();
+                if path.is_dir() {
+                    adr_files.append(&mut Self::scan_directory(&path).await?);
+                } else if AdrParser::is_adr_file(&path) {
+                    adr_files.push(path);
+                }
+            }
+
+            adr_files.sort();
+            Ok(adr_files)
+        })
+    }
+}
+
+impl AdrImporter {
+    async fn import(&self, project_id: &str, file_path: &Path) -> Result<ArchitecturalDecision, McpError> {
+        debug!("Importing ADR file: {}", file_path.display());
+
+        let content = fs::read_to_string(file_path).await
+            .map_err(|e| McpError::internal_error(format!("Failed to read file {}: {}", file_path.display(), e), None))?;
+        let adr = AdrParser::parse(file_path, &content)
+            .map_err(|e| McpError::invalid_params(format!("Failed to parse ADR: {}", e), None))?;
+
+        let id = DefaultAdrImportService::decision_id(project_id, file_path, &adr);
+        let existing = self.repository.find_by_id(&id).await?;
+        let created_at = existing
+            .as_ref()
+            .and_then(|d| d.created_at.clone())
+            .or_else(|| adr.date.clone())
+            .unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+        // Once reviewed through the workflow, a decision's status no longer comes from its file
+        let status = match (&existing, &self.workflow_repository) {
+            (Some(existing), Some(workflows))
+                if workflows.find(WorkflowEntity::ArchitecturalDecision, &id).await?.is_some() =>
+            {
+                existing.status.clone()
+            }
+            _ => adr.status,
+        };
+
+        let decision = ArchitecturalDecision {
+            id,
+            project_id: project_id.to_string(),
+            decision_title: adr.title,
+            context: adr.context,
+            decision: adr.decision,
+            consequences: adr.consequences,
+            alternatives_considered: adr.alternatives_considered,
+            status,
+            created_at: Some(created_at),
+        };
+
+        match existing {
+            Some(_) => self.repository.update(&decision).await,
+            None => self.repository.create(&decision).await,
+        }
+    }
+
+    /// Apply a file system event from a monitor
+    async fn handle_event(&self, project_id: &str, event: Event) {
+        if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) {
+            return;
+        }
+
+        for path in event.paths.iter().filter(|p| AdrParser::is_adr_file(p)) {
+            let outcome = if path.exists() {
+                self.import(project_id, path).await.map(|decision| {
+                    info!("Re-imported ADR {} from {}", decision.id, path.display());
+                    decision.id
+                })
+            } else {
+                let id = DefaultAdrImportService::decision_id(project_id, path, &ParsedAdr {
+                    number: AdrParser::file_number(path),
+                    ..Default::default()
+                });
+                let removed = match &self.workflow_repository {
+                    Some(workflows) => workflows.delete(WorkflowEntity::ArchitecturalDecision, &id).await,
+                    None => Ok(false),
+                };
+                match removed {
+                    Ok(_) => self.repository.delete(&id).await.map(|_| {
+                        info!("Removed ADR {} after {} was deleted", id, path.display());
+                        id
+                    }),
+                    Err(e) => Err(e),
+                }
+            };
+
+            match outcome {
+                Ok(id) => self.invalidate_cached(&id),
+                Err(e) => warn!("Failed to sync ADR {}: {}", path.display(), e),
+            }
+        }
+    }
+
+    fn invalidate_cached(&self, id: &str) {
+        let Some(cache) = &self.query_cache else {
+            return;
+        };
+        cache.invalidate(&CacheKeyBuilder::architectural_decision(id));
+        if let Some(namespace) = CacheKeyBuilder::list_namespace("architectural_decision") {
+            cache.invalidate_namespace(namespace);
+        }
+        cache.invalidate_namespace("context");
+    }
+}
+
+#[async_trait]
+impl AdrImportService for DefaultAdrImportService {
+    async fn scan_and_import_adrs(&self, project_id: &str, base_path: &Path) -> Result<Vec<ArchitecturalDecision>, McpError> {
+        info!("Scanning for ADRs in: {}", base_path.display());
+
+        let adr_files = Self::scan_directory(base_path).await
+            .map_err(|e| McpError::internal_error(format!("Failed to scan directory: {}", e), None))?;
+
+        let mut decisions = Vec::new();
+        for file_path in adr_files {
+            match self.importer.import(project_id, &file_path).await {
+                Ok(decision) => decisions.push(decision),
+                Err(e) => warn!("Failed to import ADR from {}: {}", file_path.display(), e),
+            }
+        }
+
+        info!("Imported {} ADRs", decisions.len());
+        Ok(decisions)
+    }
+
+    async fn import_adr_file(&self, project_id: &str, file_path: &Path) -> Result<ArchitecturalDecision, McpError> {
+        self.importer.import(project_id, file_path).await
+    }
+
+    async fn start_file_monitoring(&self, project_id: &str, base_path: &Path) -> Result<(), McpError> {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            if let Ok(event) = event {
+                let _ = tx.send(event);
+            }
+        })
+        .map_err(|e| McpError::internal_error(format!("Failed to create file watcher: {}", e), None))?;
+        watcher
+            .watch(base_path, RecursiveMode::Recursive)
+            .map_err(|e| McpError::internal_error(format!("Failed to watch {}: {}", base_path.display(), e), None))?;
+
+        // Replacing a monitor of the same directory drops its watcher, which ends its task
+        self.watchers.lock().insert(base_path.to_path_buf(), watcher);
+
+        let importer = self.importer.clone();
+        let project_id = project_id.to_string();
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                importer.handle_event(&project_id, event).await;
+            }
+        });
+
+        info!("Monitoring {} for ADR changes", base_path.display());
+        Ok(())
+    }
+
+    async fn stop_file_monitoring(&self) -> Result<(), McpError> {
+        info!("Stopping ADR file monitoring");
+        self.watchers.lock().clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::SqliteArchitecturalDecisionRepository;
+
+    fn service() -> DefaultAdrImportService {
+        let conn = crate::db::init::init_db(":memory:").unwrap();
+        conn.execute("INSERT INTO projects (id, name) VALUES ('p1', 'Payments')", []).unwrap();
+        let db = Arc::new(std::sync::Mutex::new(conn));
+        DefaultAdrImportService::new(Arc::new(SqliteArchitecturalDecisionRepository::new(db)))
+    }
+
+    #[tokio::test]
+    async fn test_scan_imports_adrs_and_updates_on_rescan() {
+        let dir = tempfile::tempdir().unwrap();
+        let adr_dir = dir.path().join("docs/adr");
+        std::fs::create_dir_all(&adr_dir).unwrap();
+        std::fs::write(
+            adr_dir.join("0001-record-architecture-decisions.md"),
+            "# 1. Record architecture decisions\n\n## Status\n\nAccepted\n\n## Context\n\nWhy.\n\n## Decision\n\nADRs.\n",
+        )
+        .unwrap();
+        std::fs::write(
+            adr_dir.join("0002-use-sqlite.md"),
+            "---\nstatus: proposed\n---\n# Use SQLite\n\n## Decision Outcome\n\nSQLite.\n",
+        )
+        .unwrap();
+        std::fs::write(adr_dir.join("template.md"), "# Title\n").unwrap();
+
+        let service = service();
+        let decisions = service.scan_and_import_adrs("p1", dir.path()).await.unwrap();
+        assert_eq!(decisions.len(), 2);
+        assert_eq!(decisions[0].id, "adr-p1-0001");
+        assert_eq!(decisions[0].decision_title, "Record architecture decisions");
+        assert_eq!(decisions[0].status.as_deref(), Some("accepted"));
+        assert_eq!(decisions[1].decision.as_deref(), Some("SQLite."));
+
+        std::fs::write(
+            adr_dir.join("0002-use-sqlite.md"),
+            "---\nstatus: accepted\n---\n# Use SQLite\n\n## Decision Outcome\n\nSQLite.\n",
+        )
+        .unwrap();
+        service.scan_and_import_adrs("p1", dir.path()).await.unwrap();
+
+        let stored = service.importer.repository.find_by_project_id("p1").await.unwrap();
+        assert_eq!(stored.len(), 2);
+        let updated = stored.iter().find(|d| d.id == "adr-p1-0002").unwrap();
+        assert_eq!(updated.status.as_deref(), Some("accepted"));
+    }
+}
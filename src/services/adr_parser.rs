@@ -0,0 +1,305 @@
+use anyhow::{anyhow, Result};
+use std::path::Path;
+
+/// An architecture decision record read from a markdown file
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ParsedAdr {
+    /// Sequence number from the file name (`0007-use-postgres.md`) or the title (`7. Use Postgres`)
+    pub number: Option<u32>,
+    pub title: String,
+    pub status: Option<String>,
+    pub date: Option<String>,
+    pub context: Option<String>,
+    pub decision: Option<String>,
+    pub consequences: Option<String>,
+    pub alternatives_considered: Option<String>,
+}
+
+/// Parser for ADR files in the Nygard (`## Status`, `## Context`, `## Decision`,
+/// `## Consequences`) and MADR (front matter or `* Status:` lines, `## Context and Problem
+/// Statement`, `## Considered Options`, `## Decision Outcome`) formats
+pub struct AdrParser;
+
+struct Section {
+    /// Lowercased heading without `#` or a trailing colon
+    key: String,
+    heading: String,
+    body: String,
+}
+
+impl AdrParser {
+    /// ADR files are markdown files whose name starts with their number, e.g. `0001-record-decisions.md`;
+    /// this leaves out templates and READMEs kept next to them
+    pub fn is_adr_file(file_path: &Path) -> bool {
+        let is_markdown = file_path.extension().and_then(|e| e.to_str()) == Some("md");
+        is_markdown && Self::file_number(file_path).is_some()
+    }
+
+    /// Number at the start of the file name
+    pub fn file_number(file_path: &Path) -> Option<u32> {
+        let stem = file_path.file_stem()?.to_str()?;
+        let digits: String = stem.chars().take_while(|c| c.is_ascii_digit()).collect();
+        let rest = &stem[digits.len()..];
+        if digits.is_empty() || !(rest.is_empty() || rest.starts_with(['-', '_', '.'])) {
+            return None;
+        }
+        digits.parse().ok()
+    }
+
+    pub fn parse(file_path: &Path, content: &str) -> Result<ParsedAdr> {
+        let (front_matter, body) = Self::split_front_matter(content);
+        let (title, sections) = Self::parse_sections(body);
+
+        let title_number = title.as_deref().and_then(Self::title_number);
+        let title = match title {
+            Some(title) => Self::strip_title_number(&title),
+            None => Self::title_from_file_name(file_path)
+                .ok_or_else(|| anyhow!("No title found in {}", file_path.display()))?,
+        };
+
+        // Text between the title and the first section, where MADR 2 lists `* Status: ...`
+        let preamble = sections.iter().find(|s| s.key.is_empty()).map(|s| s.body.as_str()).unwrap_or("");
+
+        let status = Self::front_matter_value(&front_matter, "status")
+            .or_else(|| Self::section(&sections, &["status"]).and_then(|s| s.lines().next().map(str::to_string)))
+            .or_else(|| Self::labelled_value(preamble, "status"))
+            .map(|s| Self::normalize_status(&s));
+        let date = Self::front_matter_value(&front_matter, "date").or_else(|| Self::labelled_value(preamble, "date"));
+
+        Ok(ParsedAdr {
+            number: Self::file_number(file_path).or(title_number),
+            title,
+            status,
+            date,
+            context: Self::section(&sections, &["context", "context and problem statement", "problem statement"]),
+            decision: Self::section(&sections, &["decision", "decision outcome"]),
+            consequences: Self::consequences(&sections),
+            alternatives_considered: Self::section(
+                &sections,
+                &["considered options", "options considered", "alternatives", "alternatives considered"],
+            ),
+        })
+    }
+
+    /// Split off a `---` delimited YAML front matter block
+    fn split_front_matter(content: &str) -> (Vec<(String, String)>, &str) {
+        let Some(rest) = content.strip_prefix("---\n").or_else(|| content.strip_prefix("---\r\n")) else {
+            return (Vec::new(), content);
+        };
+        let Some(end) = rest.find("\n---") else {
+            return (Vec::new(), content);
+        };
+
+        let values = rest[..end]
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim().to_lowercase(), Self::unquote(value.trim()).to_string()))
+            .filter(|(_, value)| !value.is_empty())
+            .collect();
+        let body = rest[end + 4..].split_once('\n').map_or("", |(_, body)| body);
+        (values, body)
+    }
+
+    /// The `# ` title and every section under it, in file order; text before the first
+    /// section gets an empty key
+    fn parse_sections(body: &str) -> (Option<String>, Vec<Section>) {
+        let mut title = None;
+        let mut sections = vec![Section {
+            key: String::new(),
+            heading: String::new(),
+            body: String::new(),
+        }];
+        let mut in_code_block = false;
+
+        for line in body.lines() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") {
+                in_code_block = !in_code_block;
+            }
+            let heading_level = trimmed.chars().take_while(|&c| c == '#').count();
+            let is_heading = !in_code_block && heading_level > 0 && trimmed[heading_level..].starts_with(' ');
+
+            if is_heading && heading_level == 1 && title.is_none() {
+                title = Some(trimmed[1..].trim().to_string());
+            } else if is_heading && heading_level > 1 {
+                let heading = trimmed[heading_level..].trim().trim_end_matches(':').to_string();
+                sections.push(Section {
+                    key: heading.to_lowercase(),
+                    heading,
+                    body: String::new(),
+                });
+            } else if let Some(section) = sections.last_mut() {
+                section.body.push_str(line);
+                section.body.push('\n');
+            }
+        }
+
+        for section in &mut sections {
+            section.body = section.body.trim().to_string();
+        }
+        (title, sections)
+    }
+
+    /// Body of the first non-empty section with one of `keys`
+    fn section(sections: &[Section], keys: &[&str]) -> Option<String> {
+        sections
+            .iter()
+            .find(|s| keys.contains(&s.key.as_str()) && !s.body.is_empty())
+            .map(|s| s.body.clone())
+    }
+
+    /// Nygard's single `## Consequences` section, or MADR's consequences under the decision
+    /// outcome, including MADR 2's separate positive and negative lists
+    fn consequences(sections: &[Section]) -> Option<String> {
+        let parts: Vec<String> = sections
+            .iter()
+            .filter(|s| s.key.ends_with("consequences") && !s.body.is_empty())
+            .map(|s| {
+                if s.key == "consequences" {
+                    s.body.clone()
+                } else {
+                    format!("{}:\n{}", s.heading, s.body)
+                }
+            })
+            .collect();
+        (!parts.is_empty()).then(|| parts.join("\n\n"))
+    }
+
+    fn front_matter_value(front_matter: &[(String, String)], key: &str) -> Option<String> {
+        front_matter.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    }
+
+    /// Value of a `Status: accepted` or `* Status: accepted` line
+    fn labelled_value(text: &str, label: &str) -> Option<String> {
+        text.lines().find_map(|line| {
+            let line = line.trim().trim_start_matches(['*', '-']).trim();
+            let (key, value) = line.split_once(':')?;
+            let value = value.trim();
+            (key.trim().eq_ignore_ascii_case(label) && !value.is_empty()).then(|| value.to_string())
+        })
+    }
+
+    /// `Accepted` becomes `accepted`; `Superseded by [ADR-0009](0009-x.md)` keeps its reference
+    fn normalize_status(status: &str) -> String {
+        let status = status.trim();
+        match status.split_once(char::is_whitespace) {
+            Some((word, rest)) => format!("{} {}", word.to_lowercase(), rest.trim()),
+            None => status.to_lowercase(),
+        }
+    }
+
+    fn title_number(title: &str) -> Option<u32> {
+        let rest = Self::strip_adr_prefix(title);
+        let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+        let after = rest[digits.len()..].trim_start();
+        if digits.is_empty() || !after.starts_with(['.', ':', '-']) {
+            return None;
+        }
+        digits.parse().ok()
+    }
+
+    /// `1. Record decisions` and `ADR-0001: Record decisions` become `Record decisions`
+    fn strip_title_number(title: &str) -> String {
+        if Self::title_number(title).is_none() {
+            return title.to_string();
+        }
+        let rest = Self::strip_adr_prefix(title).trim_start_matches(|c: char| c.is_ascii_digit()).trim_start();
+        rest[1..].trim().to_string()
+    }
+
+    fn strip_adr_prefix(title: &str) -> &str {
+        match title.get(..3) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("adr") => title[3..].trim_start_matches(['-', ' ']),
+            _ => title,
+        }
+    }
+
+    /// `0004-use-event-sourcing.md` becomes `Use event sourcing`
+    fn title_from_file_name(file_path: &Path) -> Option<String> {
+        let stem = file_path.file_stem()?.to_str()?;
+        let words = stem.trim_start_matches(|c: char| c.is_ascii_digit()).replace(['-', '_'], " ");
+        let words = words.trim();
+        let mut chars = words.chars();
+        let first = chars.next()?;
+        Some(first.to_uppercase().chain(chars).collect())
+    }
+
+    fn unquote(value: &str) -> &str {
+        value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+            .unwrap_or(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nygard_adr() {
+        let content = "# 1. Record architecture decisions\n\nDate: 2024-03-01\n\n## Status\n\nAccepted\n\n\
+            ## Context\n\nWe need to record decisions.\n\n## Decision\n\nWe will use ADRs.\n\n\
+            ```sh\n# not a heading\n```\n\n## Consequences\n\nSee Nygard's article.\n";
+        let adr = AdrParser::parse(Path::new("docs/adr/0001-record-architecture-decisions.md"), content).unwrap();
+
+        assert_eq!(adr.number, Some(1));
+        assert_eq!(adr.title, "Record architecture decisions");
+        assert_eq!(adr.status.as_deref(), Some("accepted"));
+        assert_eq!(adr.date.as_deref(), Some("2024-03-01"));
+        assert_eq!(adr.context.as_deref(), Some("We need to record decisions."));
+        assert_eq!(
+            adr.decision.as_deref(),
+            Some("We will use ADRs.\n\n```sh\n# not a heading\n```")
+        );
+        assert_eq!(adr.consequences.as_deref(), Some("See Nygard's article."));
+        assert_eq!(adr.alternatives_considered, None);
+    }
+
+    #[test]
+    fn test_parse_madr_adr() {
+        let content = "---\nstatus: \"superseded by ADR-0012\"\ndate: 2024-05-02\n---\n\
+            # Use PostgreSQL for persistence\n\n## Context and Problem Statement\n\nWhich database?\n\n\
+            ## Considered Options\n\n* PostgreSQL\n* SQLite\n\n## Decision Outcome\n\n\
+            Chosen option: \"PostgreSQL\", because it scales.\n\n### Consequences\n\n* Good, because replicas\n";
+        let adr = AdrParser::parse(Path::new("0003-use-postgresql.md"), content).unwrap();
+
+        assert_eq!(adr.number, Some(3));
+        assert_eq!(adr.title, "Use PostgreSQL for persistence");
+        assert_eq!(adr.status.as_deref(), Some("superseded by ADR-0012"));
+        assert_eq!(adr.date.as_deref(), Some("2024-05-02"));
+        assert_eq!(adr.context.as_deref(), Some("Which database?"));
+        assert_eq!(adr.alternatives_considered.as_deref(), Some("* PostgreSQL\n* SQLite"));
+        assert_eq!(adr.decision.as_deref(), Some("Chosen option: \"PostgreSQL\", because it scales."));
+        assert_eq!(adr.consequences.as_deref(), Some("* Good, because replicas"));
+
+        // MADR 2 lists the status under the title and splits the consequences
+        let content = "# ADR-0005: Use Kafka\n\n* Status: Proposed\n* Date: 2023-01-09\n\n\
+            ## Decision Outcome\n\nKafka.\n\n### Positive Consequences\n\n* Replay\n\n\
+            ### Negative Consequences\n\n* Operations\n";
+        let adr = AdrParser::parse(Path::new("notes.md"), content).unwrap();
+        assert_eq!(adr.number, Some(5));
+        assert_eq!(adr.title, "Use Kafka");
+        assert_eq!(adr.status.as_deref(), Some("proposed"));
+        assert_eq!(adr.date.as_deref(), Some("2023-01-09"));
+        assert_eq!(
+            adr.consequences.as_deref(),
+            Some("Positive Consequences:\n* Replay\n\nNegative Consequences:\n* Operations")
+        );
+    }
+
+    #[test]
+    fn test_is_adr_file() {
+        assert!(AdrParser::is_adr_file(Path::new("docs/adr/0001-record-decisions.md")));
+        assert!(AdrParser::is_adr_file(Path::new("docs/decisions/12_use-kafka.md")));
+        assert!(!AdrParser::is_adr_file(Path::new("docs/adr/adr-template.md")));
+        assert!(!AdrParser::is_adr_file(Path::new("docs/adr/README.md")));
+        assert!(!AdrParser::is_adr_file(Path::new("docs/adr/2024plan.md")));
+        assert!(!AdrParser::is_adr_file(Path::new("docs/adr/0001-record-decisions.txt")));
+        assert_eq!(
+            AdrParser::title_from_file_name(Path::new("0004-use-event-sourcing.md")).as_deref(),
+            Some("Use event sourcing")
+        );
+    }
+}
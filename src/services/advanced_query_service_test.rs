@@ -60,6 +60,7 @@ impl HybridSearchService for MockHybridSearchService {
                 performance_requirements: Vec::new(),
                 security_policies: Vec::new(),
                 project_conventions: Vec::new(),
+                ..Default::default()
             },
             combined_score: 0.85,
             search_strategy: SearchStrategy::Hybrid,
@@ -87,6 +88,7 @@ impl HybridSearchService for MockHybridSearchService {
             performance_requirements: Vec::new(),
             security_policies: Vec::new(),
             project_conventions: Vec::new(),
+            ..Default::default()
         })
     }
 
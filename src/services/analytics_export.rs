@@ -0,0 +1,267 @@
+use crate::services::analytics_service::AnalyticsEvent;
+use arrow_array::{ArrayRef, BooleanArray, Int64Array, RecordBatch, StringArray, TimestampMicrosecondArray};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use chrono::{DateTime, SecondsFormat, Utc};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// Writes analytics events for use outside the server: one CSV row per event, a JSON report
+/// with the events and a summary of them, or a Parquet file for data warehouses
+pub struct AnalyticsExporter;
+
+impl AnalyticsExporter {
+    /// Columns every row has; metadata keys follow as `metadata.<key>` columns
+    pub const COLUMNS: [&'static str; 10] = [
+        "id",
+        "timestamp",
+        "event_type",
+        "project_id",
+        "entity_type",
+        "entity_id",
+        "user_agent",
+        "duration_ms",
+        "success",
+        "error_message",
+    ];
+
+    /// RFC 4180 CSV with a header row and CRLF line breaks. Metadata is flattened into a column
+    /// per key across all events; string values are written as they are, others as JSON.
+    pub fn to_csv(events: &[AnalyticsEvent]) -> String {
+        let metadata_keys: BTreeSet<&str> = events
+            .iter()
+            .flat_map(|event| event.metadata.keys().map(String::as_str))
+            .collect();
+
+        let mut header: Vec<Cow<str>> = Self::COLUMNS.iter().map(|column| Cow::Borrowed(*column)).collect();
+        header.extend(metadata_keys.iter().map(|key| Cow::Owned(format!("metadata.{}", key))));
+        let mut csv = Self::row(&header);
+
+        for event in events {
+            let mut fields: Vec<Cow<str>> = vec![
+                Cow::Borrowed(event.id.as_str()),
+                Cow::Owned(event.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)),
+                Cow::Borrowed(event.event_type.as_str()),
+                Cow::Borrowed(event.project_id.as_deref().unwrap_or_default()),
+                Cow::Borrowed(event.entity_type.as_deref().unwrap_or_default()),
+                Cow::Borrowed(event.entity_id.as_deref().unwrap_or_default()),
+                Cow::Borrowed(event.user_agent.as_deref().unwrap_or_default()),
+                Cow::Owned(event.duration_ms.map(|ms| ms.to_string()).unwrap_or_default()),
+                Cow::Borrowed(if event.success { "true" } else { "false" }),
+                Cow::Borrowed(event.error_message.as_deref().unwrap_or_default()),
+            ];
+            fields.extend(metadata_keys.iter().map(|key| match event.metadata.get(*key) {
+                None | Some(serde_json::Value::Null) => Cow::Borrowed(""),
+                Some(serde_json::Value::String(value)) => Cow::Borrowed(value.as_str()),
+                Some(value) => Cow::Owned(value.to_string()),
+            }));
+            csv.push_str(&Self::row(&fields));
+        }
+        csv
+    }
+
+    /// Events per Parquet row group
+    const PARQUET_ROW_GROUP: usize = 8192;
+
+    /// Snappy-compressed Parquet with the CSV columns, `timestamp` as UTC microseconds and
+    /// `metadata` as one JSON column, so every export has the same schema whatever keys the
+    /// events carry. `duration_ms` is signed because BigQuery has no unsigned integers.
+    pub fn to_parquet(events: &[AnalyticsEvent]) -> Result<Vec<u8>, ParquetError> {
+        let schema = Arc::new(Self::parquet_schema());
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .set_max_row_group_size(Self::PARQUET_ROW_GROUP)
+            .build();
+        let mut writer = ArrowWriter::try_new(Vec::new(), schema.clone(), Some(properties))?;
+
+        for chunk in events.chunks(Self::PARQUET_ROW_GROUP) {
+            let text = |value: fn(&AnalyticsEvent) -> Option<&str>| -> ArrayRef {
+                Arc::new(chunk.iter().map(value).collect::<StringArray>())
+            };
+            let columns: Vec<ArrayRef> = vec![
+                text(|event| Some(event.id.as_str())),
+                Arc::new(
+                    TimestampMicrosecondArray::from_iter_values(chunk.iter().map(|event| event.timestamp.timestamp_micros()))
+                        .with_timezone("UTC"),
+                ),
+                text(|event| Some(event.event_type.as_str())),
+                text(|event| event.project_id.as_deref()),
+                text(|event| event.entity_type.as_deref()),
+                text(|event| event.entity_id.as_deref()),
+                text(|event| event.user_agent.as_deref()),
+                Arc::new(
+                    chunk
+                        .iter()
+                        .map(|event| event.duration_ms.map(|ms| ms as i64))
+                        .collect::<Int64Array>(),
+                ),
+                Arc::new(chunk.iter().map(|event| Some(event.success)).collect::<BooleanArray>()),
+                text(|event| event.error_message.as_deref()),
+                Arc::new(
+                    chunk
+                        .iter()
+                        .map(|event| {
+                            Some(serde_json::Value::Object(event.metadata.clone().into_iter().collect()).to_string())
+                        })
+                        .collect::<StringArray>(),
+                ),
+            ];
+            writer.write(&RecordBatch::try_new(schema.clone(), columns)?)?;
+        }
+        writer.into_inner()
+    }
+
+    fn parquet_schema() -> Schema {
+        let text = |name: &str, nullable: bool| Field::new(name, DataType::Utf8, nullable);
+        Schema::new(vec![
+            text("id", false),
+            Field::new("timestamp", DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())), false),
+            text("event_type", false),
+            text("project_id", true),
+            text("entity_type", true),
+            text("entity_id", true),
+            text("user_agent", true),
+            Field::new("duration_ms", DataType::Int64, true),
+            Field::new("success", DataType::Boolean, false),
+            text("error_message", true),
+            text("metadata", false),
+        ])
+    }
+
+    /// The events with a summary, in the shape of the usage report
+    pub fn to_json(start_date: DateTime<Utc>, end_date: DateTime<Utc>, events: &[AnalyticsEvent]) -> serde_json::Value {
+        let successful_events = events.iter().filter(|event| event.success).count();
+        let success_rate = if events.is_empty() {
+            0.0
+        } else {
+            successful_events as f64 / events.len() as f64
+        };
+
+        let mut project_counts: HashMap<&str, u64> = HashMap::new();
+        for project_id in events.iter().filter_map(|event| event.project_id.as_deref()) {
+            *project_counts.entry(project_id).or_insert(0) += 1;
+        }
+        let mut most_active_projects: Vec<(&str, u64)> = project_counts.into_iter().collect();
+        most_active_projects.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        most_active_projects.truncate(10);
+
+        serde_json::json!({
+            "report_period": {
+                "start": start_date.to_rfc3339(),
+                "end": end_date.to_rfc3339()
+            },
+            "summary": {
+                "total_events": events.len(),
+                "successful_events": successful_events,
+                "success_rate": success_rate
+            },
+            "most_active_projects": most_active_projects
+                .into_iter()
+                .map(|(project_id, count)| serde_json::json!({"project_id": project_id, "event_count": count}))
+                .collect::<Vec<_>>(),
+            "events": events
+        })
+    }
+
+    fn row(fields: &[Cow<str>]) -> String {
+        let mut row = fields.iter().map(|field| Self::field(field)).collect::<Vec<_>>().join(",");
+        row.push_str("\r\n");
+        row
+    }
+
+    /// Quoted when it holds a comma, quote or line break, with quotes doubled
+    fn field(value: &str) -> Cow<'_, str> {
+        if value.contains([',', '"', '\r', '\n']) {
+            Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+        } else {
+            Cow::Borrowed(value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::analytics_service::AnalyticsEventType;
+
+    fn event(id: &str, event_type: AnalyticsEventType, success: bool) -> AnalyticsEvent {
+        AnalyticsEvent {
+            id: id.to_string(),
+            event_type,
+            project_id: Some("p1".to_string()),
+            entity_type: None,
+            entity_id: None,
+            user_agent: None,
+            metadata: HashMap::new(),
+            timestamp: DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z").unwrap().with_timezone(&Utc),
+            duration_ms: Some(12),
+            success,
+            error_message: None,
+        }
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let mut query = event("e1", AnalyticsEventType::ContextQuery, true);
+        query.metadata.insert("tool_name".to_string(), serde_json::json!("query_context"));
+        query.metadata.insert("tags".to_string(), serde_json::json!(["a", "b"]));
+        let mut failed = event("e2", AnalyticsEventType::EntityCreate, false);
+        failed.error_message = Some("Missing \"name\",\nretry".to_string());
+        failed.project_id = None;
+
+        let csv = AnalyticsExporter::to_csv(&[query, failed]);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(
+            lines[0],
+            "id,timestamp,event_type,project_id,entity_type,entity_id,user_agent,duration_ms,success,error_message,metadata.tags,metadata.tool_name"
+        );
+        assert_eq!(lines[1], r#"e1,2024-05-01T12:00:00.000Z,ContextQuery,p1,,,,12,true,,"[""a"",""b""]",query_context"#);
+        assert_eq!(lines[2], "e2,2024-05-01T12:00:00.000Z,EntityCreate,,,,,12,false,\"Missing \"\"name\"\",\nretry\",,");
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[3], "");
+
+        assert_eq!(AnalyticsEventType::from_name("entity_create"), Some(AnalyticsEventType::EntityCreate));
+        assert_eq!(AnalyticsEventType::from_name("SearchClick"), Some(AnalyticsEventType::SearchClick));
+        assert_eq!(AnalyticsEventType::from_name("click"), None);
+    }
+
+    #[test]
+    fn test_parquet_export() {
+        use arrow_array::Array;
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let mut query = event("e1", AnalyticsEventType::ContextQuery, true);
+        query.metadata.insert("tool_name".to_string(), serde_json::json!("query_context"));
+        let mut failed = event("e2", AnalyticsEventType::EntityCreate, false);
+        failed.project_id = None;
+        failed.duration_ms = None;
+
+        let bytes = AnalyticsExporter::to_parquet(&[query, failed]).unwrap();
+        let mut file = tempfile::tempfile().unwrap();
+        std::io::Write::write_all(&mut file, &bytes).unwrap();
+        let batches: Vec<RecordBatch> = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.schema().as_ref(), &AnalyticsExporter::parquet_schema());
+        assert_eq!(batch.num_rows(), 2);
+        let text = |name: &str| batch.column_by_name(name).unwrap().as_any().downcast_ref::<StringArray>().unwrap().clone();
+        assert_eq!(text("event_type").value(1), "EntityCreate");
+        assert!(text("project_id").is_null(1));
+        assert_eq!(text("metadata").value(0), r#"{"tool_name":"query_context"}"#);
+        assert_eq!(text("metadata").value(1), "{}");
+        let timestamps = batch.column(1).as_any().downcast_ref::<TimestampMicrosecondArray>().unwrap();
+        assert_eq!(timestamps.value(0), 1_714_564_800_000_000);
+        let durations = batch.column_by_name("duration_ms").unwrap().as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!((durations.value(0), durations.is_null(1)), (12, true));
+    }
+}
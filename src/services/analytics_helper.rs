@@ -208,6 +208,34 @@ impl AnalyticsHelper {
         }
     }
 
+    /// Create a search click event: `entity_id` was fetched after appearing at `rank`
+    /// (1-based) in the results for `query`
+    pub fn create_search_click_event(
+        project_id: Option<String>,
+        entity_type: String,
+        entity_id: String,
+        query: String,
+        rank: usize,
+    ) -> AnalyticsEvent {
+        let mut metadata = HashMap::new();
+        metadata.insert("query".to_string(), serde_json::Value::String(query));
+        metadata.insert("rank".to_string(), serde_json::Value::Number(rank.into()));
+
+        AnalyticsEvent {
+            id: Uuid::new_v4().to_string(),
+            event_type: AnalyticsEventType::SearchClick,
+            project_id,
+            entity_type: Some(entity_type),
+            entity_id: Some(entity_id),
+            user_agent: None,
+            metadata,
+            timestamp: Utc::now(),
+            duration_ms: None,
+            success: true,
+            error_message: None,
+        }
+    }
+
     /// Create a general analytics event for analytics operations
     pub fn create_analytics_event(
         operation: String,
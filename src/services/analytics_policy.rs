@@ -0,0 +1,99 @@
+use crate::config::{AnalyticsSettings, ConfigManager};
+use crate::services::analytics_service::AnalyticsEvent;
+use std::sync::Arc;
+
+/// Metadata keys holding who made a request rather than what it was about
+pub const IDENTIFIER_KEYS: &[&str] = &["user_id", "client_id", "user_agent", "approver"];
+
+/// What usage tracking may record, following `[analytics]` as currently configured: nothing
+/// when it is disabled, and identifiers only as salted hashes when anonymization is on. The
+/// analytics service and the server's tracking helpers share one.
+#[derive(Default)]
+pub struct AnalyticsPolicy {
+    config: Option<Arc<ConfigManager>>,
+}
+
+impl AnalyticsPolicy {
+    /// Records everything, as an unconfigured analytics service does
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Follow the given configuration, including reloads
+    pub fn with_config(config: Arc<ConfigManager>) -> Self {
+        Self { config: Some(config) }
+    }
+
+    fn settings(&self) -> AnalyticsSettings {
+        self.config
+            .as_ref()
+            .map(|config| config.current().analytics)
+            .unwrap_or_default()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.is_none() || self.settings().enabled
+    }
+
+    /// The event as it may be stored, or `None` when tracking is disabled
+    pub fn apply(&self, mut event: AnalyticsEvent) -> Option<AnalyticsEvent> {
+        let settings = self.settings();
+        if !settings.enabled {
+            return None;
+        }
+        if settings.anonymize_identifiers {
+            let hash = |value: &str| Self::hash(&settings.anonymization_salt, value);
+            event.user_agent = event.user_agent.as_deref().map(hash);
+            for key in IDENTIFIER_KEYS {
+                if let Some(serde_json::Value::String(value)) = event.metadata.get_mut(*key) {
+                    *value = hash(value);
+                }
+            }
+        }
+        Some(event)
+    }
+
+    /// Stable for a salt, so events of one client can still be told apart from another's
+    fn hash(salt: &str, value: &str) -> String {
+        format!("anon:{:x}", md5::compute(format!("{salt}\0{value}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::services::analytics_helper::AnalyticsHelper;
+
+    #[test]
+    fn test_analytics_policy() {
+        let mut event = AnalyticsHelper::create_search_click_event(None, "business_rule".to_string(), "r1".to_string(), "refunds".to_string(), 1);
+        event.user_agent = Some("claude-desktop/1.2".to_string());
+        event.metadata.insert("user_id".to_string(), serde_json::json!("alice"));
+
+        let stored = AnalyticsPolicy::new().apply(event.clone()).unwrap();
+        assert_eq!(stored.user_agent.as_deref(), Some("claude-desktop/1.2"));
+
+        let mut config = AppConfig::default();
+        config.analytics.anonymize_identifiers = true;
+        config.analytics.anonymization_salt = "pepper".to_string();
+        let manager = Arc::new(ConfigManager::new(config.clone(), None));
+        let policy = AnalyticsPolicy::with_config(manager.clone());
+        let stored = policy.apply(event.clone()).unwrap();
+        let user_agent = stored.user_agent.clone().unwrap();
+        assert!(user_agent.starts_with("anon:"));
+        assert_eq!(policy.apply(event.clone()).unwrap().user_agent, Some(user_agent.clone()));
+        assert_ne!(stored.metadata["user_id"], "alice");
+        assert_eq!(stored.metadata["query"], "refunds");
+        assert_eq!(stored.entity_id.as_deref(), Some("r1"));
+
+        config.analytics.anonymization_salt = "salt".to_string();
+        manager.apply(config.clone());
+        assert_ne!(policy.apply(event.clone()).unwrap().user_agent, Some(user_agent));
+
+        config.analytics.enabled = false;
+        manager.apply(config);
+        assert!(!policy.is_enabled());
+        assert!(policy.apply(event).is_none());
+    }
+}
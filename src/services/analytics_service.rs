@@ -3,9 +3,14 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use crate::config::ConfigManager;
+use crate::services::analytics_policy::AnalyticsPolicy;
 
 /// Analytics event types for tracking context usage
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AnalyticsEventType {
     ContextQuery,
     EntityCreate,
@@ -14,6 +19,43 @@ pub enum AnalyticsEventType {
     BulkOperation,
     ArchitectureValidation,
     CacheOperation,
+    /// An entity fetched shortly after a search returned it
+    SearchClick,
+}
+
+impl AnalyticsEventType {
+    pub const ALL: [AnalyticsEventType; 8] = [
+        AnalyticsEventType::ContextQuery,
+        AnalyticsEventType::EntityCreate,
+        AnalyticsEventType::EntityUpdate,
+        AnalyticsEventType::EntityDelete,
+        AnalyticsEventType::BulkOperation,
+        AnalyticsEventType::ArchitectureValidation,
+        AnalyticsEventType::CacheOperation,
+        AnalyticsEventType::SearchClick,
+    ];
+
+    /// Name the event type is stored under
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnalyticsEventType::ContextQuery => "ContextQuery",
+            AnalyticsEventType::EntityCreate => "EntityCreate",
+            AnalyticsEventType::EntityUpdate => "EntityUpdate",
+            AnalyticsEventType::EntityDelete => "EntityDelete",
+            AnalyticsEventType::BulkOperation => "BulkOperation",
+            AnalyticsEventType::ArchitectureValidation => "ArchitectureValidation",
+            AnalyticsEventType::CacheOperation => "CacheOperation",
+            AnalyticsEventType::SearchClick => "SearchClick",
+        }
+    }
+
+    /// Accepts the stored name or its snake_case form, in any case: `EntityCreate`, `entity_create`
+    pub fn from_name(name: &str) -> Option<Self> {
+        let wanted = name.replace('_', "");
+        Self::ALL
+            .into_iter()
+            .find(|event_type| event_type.as_str().eq_ignore_ascii_case(&wanted))
+    }
 }
 
 /// Analytics event data structure
@@ -32,6 +74,65 @@ pub struct AnalyticsEvent {
     pub error_message: Option<String>,
 }
 
+/// A search result that was opened, read back to boost its ranking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchClick {
+    pub entity_id: String,
+    pub project_id: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// How an entity was handed to a client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityUseSource {
+    /// Returned by `query_context`
+    Query,
+    /// Returned by `search_context` or `run_saved_search`
+    Search,
+    /// Fetched with `get_entity`
+    Read,
+}
+
+/// How often an entity was handed out in a period, and the last day it was at all
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntityUses {
+    pub queries: u64,
+    pub searches: u64,
+    pub reads: u64,
+    /// `YYYY-MM-DD`, possibly before the period
+    pub last_used: Option<String>,
+}
+
+impl EntityUses {
+    pub fn total(&self) -> u64 {
+        self.queries + self.searches + self.reads
+    }
+}
+
+/// Events of one type on one day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyUsage {
+    /// `YYYY-MM-DD`
+    pub day: String,
+    pub event_type: String,
+    pub events: u64,
+    pub successful: u64,
+}
+
+/// Outcome of deleting events past the retention period
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnalyticsPruneReport {
+    /// Events before this were pruned; `None` when retention is off
+    pub cutoff: Option<DateTime<Utc>>,
+    pub events_removed: u64,
+    /// Hourly and daily rollup rows created or added to by rolling up before deleting
+    pub aggregates_updated: u64,
+    /// Bytes freed for reuse inside the database, or returned to the file system when vacuumed
+    pub space_reclaimed_bytes: u64,
+    pub vacuumed: bool,
+}
+
 /// Usage statistics for a specific context or entity
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UsageStatistics {
@@ -73,23 +174,118 @@ pub trait AnalyticsService: Send + Sync {
     
     /// Generate a usage report for a time period
     async fn generate_usage_report(&self, start_date: DateTime<Utc>, end_date: DateTime<Utc>) -> Result<serde_json::Value>;
+
+    /// Search result clicks recorded since `since`
+    async fn get_search_clicks(&self, since: DateTime<Utc>) -> Result<Vec<SearchClick>>;
+
+    /// Events in the period, oldest first, of one project and of the given types when set
+    async fn get_events(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        project_id: Option<&str>,
+        event_types: &[AnalyticsEventType],
+    ) -> Result<Vec<AnalyticsEvent>>;
+
+    /// Rolls up new events, then deletes events and hourly rollups older than `retention_days`;
+    /// 0 prunes nothing. `vacuum` also shrinks the database file.
+    async fn prune_events(&self, retention_days: u32, vacuum: bool) -> Result<AnalyticsPruneReport>;
+
+    /// Adds the events not rolled up yet to the hourly and daily rollups that statistics and
+    /// reports read; returns how many there were
+    async fn roll_up_events(&self) -> Result<u64>;
+
+    /// Counts `(entity_type, entity_id)` pairs of a project as handed out today. Kept per day
+    /// and entity, apart from events, so retention does not forget them.
+    async fn record_entity_uses(
+        &self,
+        project_id: &str,
+        source: EntityUseSource,
+        entities: &[(String, String)],
+    ) -> Result<()>;
+
+    /// Uses of a project's entities since `since`, by entity id; entities never used are absent
+    async fn get_entity_uses(&self, project_id: &str, since: DateTime<Utc>) -> Result<HashMap<String, EntityUses>>;
+
+    /// Events per day and type over the days from `start_date` to `end_date`, of one project
+    /// when set, by day. Read from the daily rollups, so retention does not shorten it.
+    async fn get_daily_usage(
+        &self,
+        project_id: Option<&str>,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> Result<Vec<DailyUsage>>;
 }
 
 /// Default implementation of the analytics service
 pub struct DefaultAnalyticsService {
     repository: Box<dyn AnalyticsRepository>,
+    policy: Arc<AnalyticsPolicy>,
 }
 
 impl DefaultAnalyticsService {
+    #[allow(dead_code)]
     pub fn new(repository: Box<dyn AnalyticsRepository>) -> Self {
-        Self { repository }
+        Self { repository, policy: Arc::new(AnalyticsPolicy::new()) }
+    }
+
+    /// Store only what the policy allows: nothing while tracking is disabled, and identifiers
+    /// hashed while anonymization is on
+    pub fn with_policy(repository: Box<dyn AnalyticsRepository>, policy: Arc<AnalyticsPolicy>) -> Self {
+        Self { repository, policy }
+    }
+
+    /// Rolls up and prunes events in the background as `[analytics]` says: both once at startup,
+    /// then every `rollup_interval_minutes` and `prune_interval_hours`, re-read on every check
+    /// so reloads apply
+    pub fn spawn_maintenance(service: Arc<dyn AnalyticsService>, config: Arc<ConfigManager>) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            debug!("No tokio runtime; analytics maintenance not started");
+            return;
+        };
+
+        runtime.spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            let (mut last_rollup, mut last_prune): (Option<std::time::Instant>, Option<std::time::Instant>) = (None, None);
+            let due = |last: Option<std::time::Instant>, every_secs: u64| {
+                every_secs > 0 && last.is_none_or(|at| at.elapsed().as_secs() >= every_secs)
+            };
+            loop {
+                interval.tick().await;
+                let settings = config.current().analytics;
+
+                if settings.retention_days > 0 && due(last_prune, settings.prune_interval_hours * 3600) {
+                    last_prune = Some(std::time::Instant::now());
+                    // Pruning rolls up first, so it also counts as a rollup
+                    last_rollup = last_prune;
+                    match service.prune_events(settings.retention_days, false).await {
+                        Ok(report) if report.events_removed == 0 => {}
+                        Ok(report) => debug!(
+                            "Pruned {} analytics events, updating {} rollups",
+                            report.events_removed, report.aggregates_updated
+                        ),
+                        Err(e) => warn!("Failed to prune analytics events: {}", e),
+                    }
+                } else if due(last_rollup, settings.rollup_interval_minutes * 60) {
+                    last_rollup = Some(std::time::Instant::now());
+                    match service.roll_up_events().await {
+                        Ok(0) => {}
+                        Ok(events) => debug!("Rolled up {} analytics events", events),
+                        Err(e) => warn!("Failed to roll up analytics events: {}", e),
+                    }
+                }
+            }
+        });
     }
 }
 
 #[async_trait]
 impl AnalyticsService for DefaultAnalyticsService {
     async fn track_event(&self, event: AnalyticsEvent) -> Result<()> {
-        self.repository.store_event(event).await
+        match self.policy.apply(event) {
+            Some(event) => self.repository.store_event(event).await,
+            None => Ok(()),
+        }
     }
     
     async fn get_entity_usage(&self, entity_type: &str, entity_id: &str) -> Result<UsageStatistics> {
@@ -166,6 +362,57 @@ impl AnalyticsService for DefaultAnalyticsService {
     async fn generate_usage_report(&self, start_date: DateTime<Utc>, end_date: DateTime<Utc>) -> Result<serde_json::Value> {
         self.repository.generate_usage_report(start_date, end_date).await
     }
+
+    async fn get_search_clicks(&self, since: DateTime<Utc>) -> Result<Vec<SearchClick>> {
+        self.repository.get_search_clicks(since).await
+    }
+
+    async fn get_events(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        project_id: Option<&str>,
+        event_types: &[AnalyticsEventType],
+    ) -> Result<Vec<AnalyticsEvent>> {
+        self.repository.get_events(start_date, end_date, project_id, event_types).await
+    }
+
+    async fn prune_events(&self, retention_days: u32, vacuum: bool) -> Result<AnalyticsPruneReport> {
+        if retention_days == 0 {
+            return Ok(AnalyticsPruneReport::default());
+        }
+        let cutoff = Utc::now() - chrono::Duration::days(retention_days as i64);
+        self.repository.prune_events(cutoff, vacuum).await
+    }
+
+    async fn roll_up_events(&self) -> Result<u64> {
+        self.repository.roll_up_events().await
+    }
+
+    async fn record_entity_uses(
+        &self,
+        project_id: &str,
+        source: EntityUseSource,
+        entities: &[(String, String)],
+    ) -> Result<()> {
+        if !self.policy.is_enabled() || entities.is_empty() {
+            return Ok(());
+        }
+        self.repository.record_entity_uses(project_id, source, entities, Utc::now()).await
+    }
+
+    async fn get_entity_uses(&self, project_id: &str, since: DateTime<Utc>) -> Result<HashMap<String, EntityUses>> {
+        self.repository.get_entity_uses(project_id, since).await
+    }
+
+    async fn get_daily_usage(
+        &self,
+        project_id: Option<&str>,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> Result<Vec<DailyUsage>> {
+        self.repository.get_daily_usage(project_id, start_date, end_date).await
+    }
 }
 
 /// Repository trait for analytics data persistence
@@ -176,4 +423,28 @@ pub trait AnalyticsRepository: Send + Sync {
     async fn get_project_events(&self, project_id: &str) -> Result<Vec<AnalyticsEvent>>;
     async fn get_global_statistics(&self) -> Result<HashMap<String, serde_json::Value>>;
     async fn generate_usage_report(&self, start_date: DateTime<Utc>, end_date: DateTime<Utc>) -> Result<serde_json::Value>;
+    async fn get_search_clicks(&self, since: DateTime<Utc>) -> Result<Vec<SearchClick>>;
+    async fn get_events(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        project_id: Option<&str>,
+        event_types: &[AnalyticsEventType],
+    ) -> Result<Vec<AnalyticsEvent>>;
+    async fn prune_events(&self, before: DateTime<Utc>, vacuum: bool) -> Result<AnalyticsPruneReport>;
+    async fn roll_up_events(&self) -> Result<u64>;
+    async fn record_entity_uses(
+        &self,
+        project_id: &str,
+        source: EntityUseSource,
+        entities: &[(String, String)],
+        at: DateTime<Utc>,
+    ) -> Result<()>;
+    async fn get_entity_uses(&self, project_id: &str, since: DateTime<Utc>) -> Result<HashMap<String, EntityUses>>;
+    async fn get_daily_usage(
+        &self,
+        project_id: Option<&str>,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> Result<Vec<DailyUsage>>;
 }
\ No newline at end of file
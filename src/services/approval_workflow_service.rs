@@ -0,0 +1,476 @@
+use crate::models::approval::{Approval, ApprovalWorkflow, WorkflowEntity, WorkflowState, WorkflowTransition};
+use crate::models::context::ArchitecturalDecision;
+use crate::models::specification::ProjectSpecification;
+use crate::repositories::{ApprovalWorkflowRepository, ArchitecturalDecisionRepository};
+use crate::services::SpecificationService;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use std::sync::Arc;
+use tracing::info;
+
+/// Service moving specifications and architectural decisions through review
+/// (draft → in_review → approved → deprecated) and collecting the approvals it needs
+#[async_trait]
+pub trait ApprovalWorkflowService: Send + Sync {
+    /// The stored workflow, or one in the state the item's status implies when it has none yet
+    async fn get_workflow(&self, entity: WorkflowEntity, entity_id: &str) -> Result<ApprovalWorkflow, McpError>;
+
+    /// Move an item to `to`. Entering review sets its required approvers (`approvers`, or the
+    /// configured ones) and starts a new round of approvals; `approved` is only reached once
+    /// every required approver has approved
+    async fn transition(
+        &self,
+        entity: WorkflowEntity,
+        entity_id: &str,
+        to: WorkflowState,
+        actor: &str,
+        comment: Option<&str>,
+        approvers: Option<Vec<String>>,
+    ) -> Result<ApprovalWorkflow, McpError>;
+
+    /// Record an approval of an item in review, approving the item once it has all it needs
+    async fn approve(
+        &self,
+        entity: WorkflowEntity,
+        entity_id: &str,
+        approver: &str,
+        comment: Option<&str>,
+    ) -> Result<ApprovalWorkflow, McpError>;
+
+    /// Reject a decision update whose status would change its review state, which only
+    /// `transition` and `approve` may do
+    async fn check_decision_update(&self, decision: &ArchitecturalDecision) -> Result<(), McpError>;
+
+    /// Deprecate a decision `successor` supersedes, giving it the status `superseded by <title>`
+    /// whatever its review state; a decision already superseded by `successor` is left as is
+    async fn supersede_decision(
+        &self,
+        decision_id: &str,
+        successor: &ArchitecturalDecision,
+        actor: &str,
+    ) -> Result<ApprovalWorkflow, McpError>;
+
+    /// Forget the workflow of a deleted item
+    async fn remove_workflow(&self, entity: WorkflowEntity, entity_id: &str) -> Result<bool, McpError>;
+}
+
+/// The item a workflow belongs to
+enum Reviewed {
+    Specification(Box<ProjectSpecification>),
+    Decision(Box<ArchitecturalDecision>),
+}
+
+impl Reviewed {
+    fn state(&self) -> WorkflowState {
+        match self {
+            Reviewed::Specification(spec) => WorkflowState::from_spec_status(&spec.status),
+            Reviewed::Decision(decision) => WorkflowState::from_decision_status(decision.status.as_deref()),
+        }
+    }
+
+    fn project_id(&self) -> &str {
+        match self {
+            Reviewed::Specification(spec) => &spec.project_id,
+            Reviewed::Decision(decision) => &decision.project_id,
+        }
+    }
+}
+
+/// Default implementation of ApprovalWorkflowService
+pub struct DefaultApprovalWorkflowService {
+    specification_service: Arc<dyn SpecificationService>,
+    decision_repository: Arc<dyn ArchitecturalDecisionRepository>,
+    workflow_repository: Arc<dyn ApprovalWorkflowRepository>,
+    required_approvers: Vec<String>,
+    min_approvals: usize,
+}
+
+impl DefaultApprovalWorkflowService {
+    pub fn new(
+        specification_service: Arc<dyn SpecificationService>,
+        decision_repository: Arc<dyn ArchitecturalDecisionRepository>,
+        workflow_repository: Arc<dyn ApprovalWorkflowRepository>,
+    ) -> Self {
+        Self {
+            specification_service,
+            decision_repository,
+            workflow_repository,
+            required_approvers: Vec::new(),
+            min_approvals: 1,
+        }
+    }
+
+    /// Approvers required when a review names none, and the approvals needed when nobody is required
+    pub fn with_approvers(mut self, required_approvers: Vec<String>, min_approvals: usize) -> Self {
+        self.required_approvers = required_approvers;
+        self.min_approvals = min_approvals.max(1);
+        self
+    }
+
+    async fn load(&self, entity: WorkflowEntity, entity_id: &str) -> Result<Reviewed, McpError> {
+        let item = match entity {
+            WorkflowEntity::Specification => self
+                .specification_service
+                .get_specification(entity_id)
+                .await?
+                .map(|spec| Reviewed::Specification(Box::new(spec))),
+            WorkflowEntity::ArchitecturalDecision => self
+                .decision_repository
+                .find_by_id(entity_id)
+                .await?
+                .map(|decision| Reviewed::Decision(Box::new(decision))),
+        };
+        item.ok_or_else(|| {
+            McpError::resource_not_found(format!("{} not found: {}", entity.as_str(), entity_id), None)
+        })
+    }
+
+    async fn workflow_of(&self, entity: WorkflowEntity, entity_id: &str, item: &Reviewed) -> Result<ApprovalWorkflow, McpError> {
+        Ok(self
+            .workflow_repository
+            .find(entity, entity_id)
+            .await?
+            .unwrap_or_else(|| {
+                ApprovalWorkflow::new(entity, entity_id.to_string(), item.project_id().to_string(), item.state())
+            }))
+    }
+
+    /// Moves the workflow and its item to `to`, saving both
+    async fn apply(
+        &self,
+        mut workflow: ApprovalWorkflow,
+        item: Reviewed,
+        to: WorkflowState,
+        actor: &str,
+        comment: Option<&str>,
+    ) -> Result<ApprovalWorkflow, McpError> {
+        let now = chrono::Utc::now().to_rfc3339();
+        workflow.history.push(WorkflowTransition {
+            from: workflow.state,
+            to,
+            actor: actor.to_string(),
+            comment: comment.map(str::to_string),
+            at: now.clone(),
+        });
+        workflow.state = to;
+        workflow.updated_at = Some(now);
+
+        match item {
+            Reviewed::Specification(mut spec) => {
+                spec.status = to.spec_status();
+                spec.updated_at = chrono::Utc::now();
+                self.specification_service.update_specification(*spec).await?;
+            }
+            Reviewed::Decision(mut decision) => {
+                // A status such as `superseded by …` is kept when it already means `to`
+                if WorkflowState::from_decision_status(decision.status.as_deref()) != to {
+                    decision.status = Some(to.decision_status().to_string());
+                }
+                self.decision_repository.update(&decision).await?;
+            }
+        }
+        info!(
+            "{} {} moved to {} by {}",
+            workflow.entity_type.as_str(),
+            workflow.entity_id,
+            to.as_str(),
+            actor
+        );
+        self.workflow_repository.save(&workflow).await
+    }
+
+    fn missing_approvals(&self, workflow: &ApprovalWorkflow) -> String {
+        match workflow.pending_approvers().as_slice() {
+            [] => format!(
+                "{} of {} approvals",
+                workflow.approvals.len(),
+                self.min_approvals
+            ),
+            pending => format!("approval from {}", pending.join(", ")),
+        }
+    }
+}
+
+#[async_trait]
+impl ApprovalWorkflowService for DefaultApprovalWorkflowService {
+    async fn get_workflow(&self, entity: WorkflowEntity, entity_id: &str) -> Result<ApprovalWorkflow, McpError> {
+        let item = self.load(entity, entity_id).await?;
+        self.workflow_of(entity, entity_id, &item).await
+    }
+
+    async fn transition(
+        &self,
+        entity: WorkflowEntity,
+        entity_id: &str,
+        to: WorkflowState,
+        actor: &str,
+        comment: Option<&str>,
+        approvers: Option<Vec<String>>,
+    ) -> Result<ApprovalWorkflow, McpError> {
+        let item = self.load(entity, entity_id).await?;
+        let mut workflow = self.workflow_of(entity, entity_id, &item).await?;
+
+        if !workflow.state.can_transition_to(to) {
+            let allowed: Vec<&str> = workflow.state.next_states().iter().map(|s| s.as_str()).collect();
+            return Err(McpError::invalid_params(
+                format!(
+                    "Cannot move {} {} from {} to {}; allowed: {}",
+                    entity.as_str(),
+                    entity_id,
+                    workflow.state.as_str(),
+                    to.as_str(),
+                    allowed.join(", ")
+                ),
+                None,
+            ));
+        }
+
+        match to {
+            WorkflowState::InReview => {
+                workflow.required_approvers = approvers.unwrap_or_else(|| self.required_approvers.clone());
+                workflow.approvals.clear();
+            }
+            WorkflowState::Draft => workflow.approvals.clear(),
+            WorkflowState::Approved if !workflow.is_fully_approved(self.min_approvals) => {
+                return Err(McpError::invalid_params(
+                    format!(
+                        "{} {} cannot be approved yet: it still needs {}",
+                        entity.as_str(),
+                        entity_id,
+                        self.missing_approvals(&workflow)
+                    ),
+                    None,
+                ));
+            }
+            _ => {}
+        }
+
+        self.apply(workflow, item, to, actor, comment).await
+    }
+
+    async fn approve(
+        &self,
+        entity: WorkflowEntity,
+        entity_id: &str,
+        approver: &str,
+        comment: Option<&str>,
+    ) -> Result<ApprovalWorkflow, McpError> {
+        let item = self.load(entity, entity_id).await?;
+        let mut workflow = self.workflow_of(entity, entity_id, &item).await?;
+
+        if workflow.state != WorkflowState::InReview {
+            return Err(McpError::invalid_params(
+                format!(
+                    "{} {} is {}, only items in review can be approved",
+                    entity.as_str(),
+                    entity_id,
+                    workflow.state.as_str()
+                ),
+                None,
+            ));
+        }
+        if !workflow.required_approvers.is_empty() && !workflow.required_approvers.iter().any(|a| a == approver) {
+            return Err(McpError::invalid_params(
+                format!(
+                    "{} is not a required approver of {} {}; required: {}",
+                    approver,
+                    entity.as_str(),
+                    entity_id,
+                    workflow.required_approvers.join(", ")
+                ),
+                None,
+            ));
+        }
+        if workflow.approvals.iter().any(|a| a.approver == approver) {
+            return Err(McpError::invalid_params(
+                format!("{} already approved {} {}", approver, entity.as_str(), entity_id),
+                None,
+            ));
+        }
+
+        let now = chrono::Utc::now().to_rfc3339();
+        workflow.approvals.push(Approval {
+            approver: approver.to_string(),
+            comment: comment.map(str::to_string),
+            approved_at: now.clone(),
+        });
+
+        if workflow.is_fully_approved(self.min_approvals) {
+            self.apply(workflow, item, WorkflowState::Approved, approver, comment).await
+        } else {
+            workflow.updated_at = Some(now);
+            self.workflow_repository.save(&workflow).await
+        }
+    }
+
+    async fn check_decision_update(&self, decision: &ArchitecturalDecision) -> Result<(), McpError> {
+        let Some(stored) = self.decision_repository.find_by_id(&decision.id).await? else {
+            return Ok(());
+        };
+        let from = WorkflowState::from_decision_status(stored.status.as_deref());
+        let to = WorkflowState::from_decision_status(decision.status.as_deref());
+        if from != to {
+            return Err(McpError::invalid_params(
+                format!(
+                    "Changing the status of decision {} from {} to {} is a review transition; use transition_workflow or approve_workflow_item",
+                    decision.id,
+                    from.as_str(),
+                    to.as_str()
+                ),
+                None,
+            ));
+        }
+        Ok(())
+    }
+
+    async fn supersede_decision(
+        &self,
+        decision_id: &str,
+        successor: &ArchitecturalDecision,
+        actor: &str,
+    ) -> Result<ApprovalWorkflow, McpError> {
+        let entity = WorkflowEntity::ArchitecturalDecision;
+        let item = self.load(entity, decision_id).await?;
+        let workflow = self.workflow_of(entity, decision_id, &item).await?;
+        let Reviewed::Decision(mut decision) = item else {
+            unreachable!("decisions load as Reviewed::Decision")
+        };
+
+        let status = format!("superseded by {}", successor.decision_title);
+        if decision.status.as_deref() == Some(status.as_str()) {
+            return Ok(workflow);
+        }
+        decision.status = Some(status);
+        let comment = format!("Superseded by decision {}", successor.id);
+        self.apply(workflow, Reviewed::Decision(decision), WorkflowState::Deprecated, actor, Some(&comment))
+            .await
+    }
+
+    async fn remove_workflow(&self, entity: WorkflowEntity, entity_id: &str) -> Result<bool, McpError> {
+        self.workflow_repository.delete(entity, entity_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::{
+        SqliteApprovalWorkflowRepository, SqliteArchitecturalDecisionRepository, SqliteSpecificationRepository,
+    };
+    use crate::models::specification::{SpecContent, SpecFormat, SpecStatus, SpecType};
+    use crate::repositories::SpecificationRepository;
+    use crate::services::specification_service::DefaultSpecificationService;
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    type Fixture = (
+        DefaultApprovalWorkflowService,
+        Arc<SqliteSpecificationRepository>,
+        Arc<dyn SpecificationService>,
+        Arc<SqliteArchitecturalDecisionRepository>,
+    );
+
+    async fn service() -> Fixture {
+        let conn = crate::db::init::init_db(":memory:").unwrap();
+        conn.execute("INSERT INTO projects (id, name) VALUES ('p1', 'Payments')", []).unwrap();
+        let db = Arc::new(Mutex::new(conn));
+        let spec_repository = Arc::new(SqliteSpecificationRepository::new(db.clone()));
+        spec_repository.initialize_tables().unwrap();
+        let specification_service: Arc<dyn SpecificationService> =
+            Arc::new(DefaultSpecificationService::new(spec_repository.clone()));
+        let decisions = Arc::new(SqliteArchitecturalDecisionRepository::new(db.clone()));
+        let service = DefaultApprovalWorkflowService::new(
+            specification_service.clone(),
+            decisions.clone(),
+            Arc::new(SqliteApprovalWorkflowRepository::new(db)),
+        )
+        .with_approvers(Vec::new(), 2);
+        (service, spec_repository, specification_service, decisions)
+    }
+
+    #[tokio::test]
+    async fn test_specification_review_needs_required_approvers() {
+        let (service, spec_repository, specs, _) = service().await;
+        let spec = ProjectSpecification::new(
+            "login".to_string(),
+            SpecType::Requirements,
+            "Login".to_string(),
+            SpecContent::new(SpecFormat::Markdown, "# Requirements".to_string()),
+        );
+        let id = spec_repository.create_specification(&spec).await.unwrap().id;
+        let entity = WorkflowEntity::Specification;
+
+        assert_eq!(service.get_workflow(entity, &id).await.unwrap().state, WorkflowState::Draft);
+        assert!(service.transition(entity, &id, WorkflowState::Approved, "ana", None, None).await.is_err());
+        assert!(service.approve(entity, &id, "ana", None).await.is_err());
+
+        let workflow = service
+            .transition(entity, &id, WorkflowState::InReview, "ana", None, Some(vec!["bo".to_string(), "cy".to_string()]))
+            .await
+            .unwrap();
+        assert_eq!(workflow.required_approvers, vec!["bo", "cy"]);
+        assert_eq!(specs.get_specification(&id).await.unwrap().unwrap().status, SpecStatus::InReview);
+
+        assert!(service.approve(entity, &id, "ana", None).await.is_err());
+        let workflow = service.approve(entity, &id, "bo", Some("looks good")).await.unwrap();
+        assert_eq!(workflow.state, WorkflowState::InReview);
+        assert!(service.approve(entity, &id, "bo", None).await.is_err());
+        let error = service.transition(entity, &id, WorkflowState::Approved, "ana", None, None).await.unwrap_err();
+        assert!(error.message.contains("approval from cy"));
+
+        let workflow = service.approve(entity, &id, "cy", None).await.unwrap();
+        assert_eq!(workflow.state, WorkflowState::Approved);
+        assert_eq!(workflow.history.len(), 2);
+        assert_eq!(specs.get_specification(&id).await.unwrap().unwrap().status, SpecStatus::Approved);
+
+        assert!(service.transition(entity, &id, WorkflowState::Draft, "ana", None, None).await.is_err());
+        let workflow = service.transition(entity, &id, WorkflowState::Deprecated, "ana", None, None).await.unwrap();
+        assert_eq!(workflow.state, WorkflowState::Deprecated);
+        assert_eq!(service.get_workflow(entity, &id).await.unwrap().history.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_decision_status_follows_workflow() {
+        let (service, _, _, decisions) = service().await;
+        let decision = ArchitecturalDecision {
+            id: "adr-1".to_string(),
+            project_id: "p1".to_string(),
+            decision_title: "Use SQLite".to_string(),
+            context: None,
+            decision: None,
+            consequences: None,
+            alternatives_considered: None,
+            status: Some("proposed".to_string()),
+            created_at: None,
+        };
+        decisions.create(&decision).await.unwrap();
+        let entity = WorkflowEntity::ArchitecturalDecision;
+
+        let mut accepted = decision.clone();
+        accepted.status = Some("accepted".to_string());
+        assert!(service.check_decision_update(&accepted).await.is_err());
+        let mut reworded = decision.clone();
+        reworded.decision = Some("SQLite with WAL".to_string());
+        assert!(service.check_decision_update(&reworded).await.is_ok());
+
+        // Two approvals from anyone are configured
+        service.approve(entity, "adr-1", "bo", None).await.unwrap();
+        let workflow = service.approve(entity, "adr-1", "cy", None).await.unwrap();
+        assert_eq!(workflow.state, WorkflowState::Approved);
+        assert_eq!(decisions.find_by_id("adr-1").await.unwrap().unwrap().status.as_deref(), Some("accepted"));
+
+        let successor = ArchitecturalDecision {
+            id: "adr-2".to_string(),
+            decision_title: "Use Postgres".to_string(),
+            ..decision.clone()
+        };
+        let workflow = service.supersede_decision("adr-1", &successor, "ana").await.unwrap();
+        assert_eq!(workflow.state, WorkflowState::Deprecated);
+        assert_eq!(
+            decisions.find_by_id("adr-1").await.unwrap().unwrap().status.as_deref(),
+            Some("superseded by Use Postgres")
+        );
+        let workflow = service.supersede_decision("adr-1", &successor, "ana").await.unwrap();
+        assert_eq!(workflow.history.len(), 2);
+    }
+}
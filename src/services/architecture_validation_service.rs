@@ -1,114 +1,852 @@
-use crate::models::architecture::ArchitectureLayer;
+use crate::models::architecture::{
+    ArchitectureBaseline, ArchitectureFinding, ArchitectureRuleSet, BaselineComparison, ConventionPattern,
+    FitnessMeasurement, Severity, ViolationRule,
+};
+use crate::infrastructure::DependencyRepository;
+use crate::models::constraint::ComponentDependency;
 use crate::models::framework::FrameworkComponent;
-use crate::services::FrameworkService;
+use crate::repositories::{
+    ArchitectureRuleSetRepository, ChangeLogRepository, FitnessMeasurementRepository, ProjectConventionRepository,
+};
+use crate::services::dependency_cycles::{CycleDetector, DependencyCycle};
+use crate::services::fitness_functions::{FitnessEvaluator, FitnessTrend};
+use crate::services::manifest_importer::{ManifestKind, ParsedManifest};
+use crate::services::plugins::ArchitectureValidatorRegistry;
+use crate::services::{DevelopmentPhaseService, FixSuggester, FrameworkService, SpecificationService};
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use regex::Regex;
 use rmcp::model::ErrorData as McpError;
-use std::str::FromStr;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Service for validating architecture rules following Single Responsibility Principle
 #[async_trait]
 pub trait ArchitectureValidationService: Send + Sync {
     async fn validate_architecture(&self, project_id: &str) -> Result<Vec<String>, McpError>;
+    /// Every rule the project's components break, with its severity
+    async fn find_violations(&self, project_id: &str) -> Result<Vec<ArchitectureFinding>, McpError>;
+    /// Findings of the given components, by id or name. Cycles count when they pass through
+    /// one of them; manifest and fitness findings, which concern the whole project, are left out
+    async fn find_violations_in(
+        &self,
+        project_id: &str,
+        components: &[String],
+    ) -> Result<Vec<ArchitectureFinding>, McpError>;
+    /// Current findings split by the baseline file; without one every finding is new
+    async fn compare_with_baseline(&self, project_id: &str) -> Result<BaselineComparison, McpError>;
+    /// `find_violations_in` split by the baseline file; only acknowledged findings of the
+    /// given components can show up as fixed
+    async fn compare_with_baseline_in(
+        &self,
+        project_id: &str,
+        components: &[String],
+    ) -> Result<BaselineComparison, McpError>;
+    /// Ids of the components created or updated at or after `since`, by their timestamps
+    /// and the change log
+    async fn changed_components(&self, project_id: &str, since: DateTime<Utc>) -> Result<Vec<String>, McpError>;
+    /// Records the current findings as the project's baseline and returns them
+    async fn acknowledge_violations(&self, project_id: &str) -> Result<Vec<ArchitectureFinding>, McpError>;
     async fn validate_component_dependencies(
         &self,
         component: &FrameworkComponent,
     ) -> Result<Vec<String>, McpError>;
+    /// The project's own rule set, or Clean Architecture when it has none
+    async fn rule_set(&self, project_id: &str) -> Result<ArchitectureRuleSet, McpError>;
+    /// Dependency cycles among the project's components, development phases and the tasks of
+    /// the specifications imported under `spec_project` (defaults to the project id)
+    async fn detect_cycles(
+        &self,
+        project_id: &str,
+        spec_project: Option<&str>,
+    ) -> Result<Vec<DependencyCycle>, McpError>;
+    /// Current values of the project's fitness functions against the last recorded ones;
+    /// with `record` they are stored for later trends
+    async fn evaluate_fitness(&self, project_id: &str, record: bool) -> Result<Vec<FitnessTrend>, McpError>;
+    /// Recorded values, newest first
+    async fn fitness_history(
+        &self,
+        project_id: &str,
+        function: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<FitnessMeasurement>, McpError>;
 }
 
 /// Implementation of ArchitectureValidationService
 pub struct ArchitectureValidationServiceImpl<FS: FrameworkService> {
     framework_service: FS,
+    rule_sets: Option<Arc<dyn ArchitectureRuleSetRepository>>,
+    phases: Option<Arc<dyn DevelopmentPhaseService>>,
+    specifications: Option<Arc<dyn SpecificationService>>,
+    baseline_file: Option<PathBuf>,
+    validators: Option<Arc<ArchitectureValidatorRegistry>>,
+    conventions: Option<Arc<dyn ProjectConventionRepository>>,
+    external_dependencies: Option<Arc<dyn DependencyRepository>>,
+    fitness_history: Option<Arc<dyn FitnessMeasurementRepository>>,
+    change_log: Option<Arc<dyn ChangeLogRepository>>,
 }
 
 impl<FS: FrameworkService> ArchitectureValidationServiceImpl<FS> {
     pub fn new(framework_service: FS) -> Self {
-        Self { framework_service }
+        Self {
+            framework_service,
+            rule_sets: None,
+            phases: None,
+            specifications: None,
+            baseline_file: None,
+            validators: None,
+            conventions: None,
+            external_dependencies: None,
+            fitness_history: None,
+            change_log: None,
+        }
     }
 
-    /// Validate presentation layer dependencies (OCP - can be extended with new rules)
-    fn validate_presentation_layer(&self, component: &FrameworkComponent) -> Vec<String> {
-        let mut violations = Vec::new();
+    /// Validate projects against the rule sets stored for them
+    pub fn with_rule_sets(mut self, rule_sets: Arc<dyn ArchitectureRuleSetRepository>) -> Self {
+        self.rule_sets = Some(rule_sets);
+        self
+    }
 
-        // Presentation layer should not directly import from data layer
-        for dep in &component.dependencies {
-            if dep.contains("data/") && !dep.contains("domain/") {
-                violations.push(format!(
-                    "Architecture violation: {} (presentation) directly imports from data layer: {}",
-                    component.component_name, dep
-                ));
-            }
+    /// Also look for cycles among development phases
+    pub fn with_development_phases(mut self, phases: Arc<dyn DevelopmentPhaseService>) -> Self {
+        self.phases = Some(phases);
+        self
+    }
+
+    /// Also look for cycles among specification tasks
+    pub fn with_specifications(mut self, specifications: Arc<dyn SpecificationService>) -> Self {
+        self.specifications = Some(specifications);
+        self
+    }
+
+    /// Keep acknowledged findings in this file and leave them out of comparisons
+    pub fn with_baseline_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.baseline_file = Some(path.into());
+        self
+    }
+
+    /// Also run the validators plugins register
+    pub fn with_validators(mut self, validators: Arc<ArchitectureValidatorRegistry>) -> Self {
+        self.validators = Some(validators);
+        self
+    }
+
+    /// Also check component and file names against the project's naming conventions
+    pub fn with_conventions(mut self, conventions: Arc<dyn ProjectConventionRepository>) -> Self {
+        self.conventions = Some(conventions);
+        self
+    }
+
+    /// Also check the external dependencies imported from package manifests against the
+    /// forbidden imports of the layer each manifest belongs to
+    pub fn with_external_dependencies(mut self, dependencies: Arc<dyn DependencyRepository>) -> Self {
+        self.external_dependencies = Some(dependencies);
+        self
+    }
+
+    /// Record fitness function values so their trend can be followed
+    pub fn with_fitness_history(mut self, history: Arc<dyn FitnessMeasurementRepository>) -> Self {
+        self.fitness_history = Some(history);
+        self
+    }
+
+    /// Also find changed components in the change log, which sees changes made by other clients
+    pub fn with_change_log(mut self, change_log: Arc<dyn ChangeLogRepository>) -> Self {
+        self.change_log = Some(change_log);
+        self
+    }
+
+    /// Findings of the selected components, or of the whole project without a selection
+    async fn collect_findings(
+        &self,
+        project_id: &str,
+        selection: Option<&[String]>,
+    ) -> Result<Vec<ArchitectureFinding>, McpError> {
+        let rules = self.rule_set(project_id).await?;
+
+        // Get all components for the project; cycles need every one of them
+        let components = self.framework_service.list_components(project_id).await?;
+        let selected = match selection {
+            Some(selection) => Self::select(&components, selection),
+            None => components.clone(),
+        };
+
+        let mut violations: Vec<ArchitectureFinding> = selected
+            .iter()
+            .flat_map(|component| Self::check_component(&rules, component))
+            .collect();
+        violations.extend(
+            CycleDetector::component_cycles(&components)
+                .iter()
+                .filter(|cycle| {
+                    selection.is_none()
+                        || cycle.members.iter().any(|member| selected.iter().any(|c| c.component_name == *member))
+                })
+                .map(|cycle| ArchitectureFinding {
+                    rule: ViolationRule::DependencyCycle,
+                    severity: ViolationRule::DependencyCycle.default_severity(),
+                    component: cycle.members[0].clone(),
+                    message: cycle.describe(),
+                    validator: None,
+                    dependency: None,
+                    fixes: FixSuggester::new(&rules, &components).break_cycle(cycle),
+                }),
+        );
+        violations.extend(self.convention_findings(project_id, &selected).await?);
+        if selection.is_none() {
+            violations.extend(self.manifest_findings(&rules, project_id, &components).await?);
+            let measurements = FitnessEvaluator::new(&components).evaluate(&rules);
+            violations.extend(
+                rules
+                    .fitness_functions
+                    .iter()
+                    .zip(&measurements)
+                    .filter_map(|(function, measurement)| FitnessEvaluator::finding(function, measurement)),
+            );
         }
+        violations.extend(self.plugin_findings(&rules, &selected).await);
 
-        violations
+        let suggester = FixSuggester::new(&rules, &components);
+        for finding in violations.iter_mut().filter(|finding| finding.fixes.is_empty()) {
+            finding.fixes = suggester.suggest(finding);
+        }
+        Ok(violations)
     }
 
-    /// Validate domain layer dependencies (OCP - can be extended with new rules)
-    fn validate_domain_layer(&self, component: &FrameworkComponent) -> Vec<String> {
-        let mut violations = Vec::new();
+    /// Components named by id or by name
+    fn select(components: &[FrameworkComponent], selection: &[String]) -> Vec<FrameworkComponent> {
+        components
+            .iter()
+            .filter(|component| {
+                selection.iter().any(|wanted| *wanted == component.id || *wanted == component.component_name)
+            })
+            .cloned()
+            .collect()
+    }
 
-        // Domain layer should not import from presentation or data layers
-        for dep in &component.dependencies {
-            if dep.contains("presentation/") || dep.contains("data/") {
-                violations.push(format!(
-                    "Architecture violation: {} (domain) imports from {}: {}",
-                    component.component_name,
-                    if dep.contains("presentation/") {
-                        "presentation"
-                    } else {
-                        "data"
-                    },
-                    dep
-                ));
+    /// Whether a component timestamp, RFC 3339 or SQLite's `datetime('now')`, is at or after `since`
+    fn changed_since(timestamp: Option<&str>, since: DateTime<Utc>) -> bool {
+        let Some(timestamp) = timestamp else {
+            return false;
+        };
+        DateTime::parse_from_rfc3339(timestamp)
+            .map(|time| time.with_timezone(&Utc))
+            .or_else(|_| NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S").map(|time| time.and_utc()))
+            .is_ok_and(|time| time >= since)
+    }
+
+    async fn manifest_findings(
+        &self,
+        rules: &ArchitectureRuleSet,
+        project_id: &str,
+        components: &[FrameworkComponent],
+    ) -> Result<Vec<ArchitectureFinding>, McpError> {
+        let Some(dependencies) = &self.external_dependencies else {
+            return Ok(Vec::new());
+        };
+        let rows: Vec<ComponentDependency> = dependencies
+            .list_dependencies(project_id)
+            .map_err(|e| McpError::internal_error(format!("Dependency storage error: {}", e), None))?
+            .into_iter()
+            .filter(|row| ManifestKind::from_source_type(&row.source_type).is_some())
+            .collect();
+        Ok(Self::check_manifest_dependencies(rules, &rows, components))
+    }
+
+    /// Layer of a manifest: the one its path patterns put it in, or else the layer all
+    /// components under its directory share
+    fn manifest_layer<'a>(rules: &'a ArchitectureRuleSet, manifest: &str, components: &[FrameworkComponent]) -> Option<&'a str> {
+        if let Some(layer) = rules.layers_of_dependency(manifest).first() {
+            return Some(layer.name.as_str());
+        }
+        let directory = ParsedManifest::directory(manifest);
+        let mut layers = components
+            .iter()
+            .filter(|component| component.file_path.as_deref().is_some_and(|path| path.starts_with(directory)))
+            .filter_map(|component| rules.layer(&component.architecture_layer));
+        let layer = layers.next()?;
+        layers.all(|other| other.name == layer.name).then_some(layer.name.as_str())
+    }
+
+    /// External dependencies declared by a manifest that a forbidden import of its layer, or
+    /// of every layer, matches
+    fn check_manifest_dependencies(
+        rules: &ArchitectureRuleSet,
+        rows: &[ComponentDependency],
+        components: &[FrameworkComponent],
+    ) -> Vec<ArchitectureFinding> {
+        let mut findings = Vec::new();
+        for row in rows {
+            let layer = Self::manifest_layer(rules, &row.source_component, components);
+            for rule in &rules.forbidden_imports {
+                let applies = rule.layers.is_empty()
+                    || layer.is_some_and(|layer| rule.layers.iter().any(|name| name.eq_ignore_ascii_case(layer)));
+                let Some(pattern) = applies.then(|| Regex::new(&rule.pattern).ok()).flatten() else {
+                    continue;
+                };
+                if !pattern.is_match(&row.target_component) {
+                    continue;
+                }
+                let mut message = match layer {
+                    Some(layer) => format!(
+                        "Architecture violation: {} ({}) declares forbidden dependency {}",
+                        row.source_component, layer, row.target_component
+                    ),
+                    None => format!(
+                        "Architecture violation: {} declares forbidden dependency {}",
+                        row.source_component, row.target_component
+                    ),
+                };
+                if let Some(reason) = &rule.reason {
+                    message.push_str(&format!(": {}", reason));
+                }
+                findings.push(ArchitectureFinding {
+                    rule: ViolationRule::ForbiddenImport,
+                    severity: rule.severity.unwrap_or_else(|| ViolationRule::ForbiddenImport.default_severity()),
+                    component: row.source_component.clone(),
+                    message,
+                    validator: None,
+                    dependency: Some(row.target_component.clone()),
+                    fixes: Vec::new(),
+                });
             }
         }
+        findings
+    }
 
-        violations
+    /// Components whose names break a checkable naming convention of the project; a
+    /// convention with an invalid pattern is logged and skipped
+    async fn convention_findings(
+        &self,
+        project_id: &str,
+        components: &[FrameworkComponent],
+    ) -> Result<Vec<ArchitectureFinding>, McpError> {
+        let Some(conventions) = &self.conventions else {
+            return Ok(Vec::new());
+        };
+        let patterns: Vec<ConventionPattern> = conventions
+            .list_by_project(project_id)
+            .await?
+            .iter()
+            .filter_map(ConventionPattern::from_convention)
+            .collect();
+        Ok(Self::check_conventions(&patterns, components))
     }
 
-    /// Validate data layer dependencies (OCP - can be extended with new rules)
-    fn validate_data_layer(&self, _component: &FrameworkComponent) -> Vec<String> {
-        // Data layer validation rules would go here
-        // For now, data layer has fewer restrictions
-        Vec::new()
+    fn check_conventions(patterns: &[ConventionPattern], components: &[FrameworkComponent]) -> Vec<ArchitectureFinding> {
+        let mut findings = Vec::new();
+        for pattern in patterns {
+            for component in components {
+                let Some(name) = pattern.subject(component) else {
+                    continue;
+                };
+                match pattern.matcher.matches(name) {
+                    Ok(true) => {}
+                    Ok(false) => findings.push(ArchitectureFinding {
+                        rule: ViolationRule::Convention,
+                        severity: ViolationRule::Convention.default_severity(),
+                        component: component.component_name.clone(),
+                        message: pattern.violation(component, name),
+                        validator: None,
+                        dependency: None,
+                        fixes: FixSuggester::rename(component, pattern, name).into_iter().collect(),
+                    }),
+                    Err(e) => {
+                        tracing::warn!("Convention {} has an invalid pattern: {}", pattern.convention_id, e);
+                        break;
+                    }
+                }
+            }
+        }
+        findings
     }
 
-    /// Validate core layer dependencies (OCP - can be extended with new rules)
-    fn validate_core_layer(&self, _component: &FrameworkComponent) -> Vec<String> {
-        // Core layer validation rules would go here
-        // Core layer should be independent of all other layers
-        Vec::new()
+    /// Findings of the registered plugin validators; a failing validator is logged and skipped
+    /// so one broken plugin does not hide the other findings
+    async fn plugin_findings(&self, rules: &ArchitectureRuleSet, components: &[FrameworkComponent]) -> Vec<ArchitectureFinding> {
+        let Some(registry) = &self.validators else {
+            return Vec::new();
+        };
+        let mut findings = Vec::new();
+        for validator in registry.validators() {
+            match validator.validate(rules, components).await {
+                Ok(found) => findings.extend(found.into_iter().map(|mut finding| {
+                    finding.validator.get_or_insert_with(|| validator.name().to_string());
+                    finding
+                })),
+                Err(e) => tracing::warn!("Architecture validator {} failed: {}", validator.name(), e),
+            }
+        }
+        findings
+    }
+
+    async fn load_baseline(&self) -> Result<ArchitectureBaseline, McpError> {
+        let Some(path) = &self.baseline_file else {
+            return Ok(ArchitectureBaseline::default());
+        };
+        match tokio::fs::read_to_string(path).await {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| {
+                McpError::internal_error(format!("Invalid baseline file {}: {}", path.display(), e), None)
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ArchitectureBaseline::default()),
+            Err(e) => Err(McpError::internal_error(format!("Cannot read {}: {}", path.display(), e), None)),
+        }
+    }
+
+    /// Violations of one component: dependencies on layers its layer may not use, forbidden
+    /// imports and names not matching the naming patterns of its layer or type
+    fn check_component(rules: &ArchitectureRuleSet, component: &FrameworkComponent) -> Vec<ArchitectureFinding> {
+        let finding = |rule: ViolationRule, severity: Option<Severity>, message: String| ArchitectureFinding {
+            rule,
+            severity: severity.unwrap_or_else(|| rule.default_severity()),
+            component: component.component_name.clone(),
+            message,
+            validator: None,
+            dependency: None,
+            fixes: Vec::new(),
+        };
+        let Some(layer) = rules.layer(&component.architecture_layer) else {
+            return vec![finding(
+                ViolationRule::UnknownLayer,
+                None,
+                format!("Unknown architecture layer: {}", component.architecture_layer),
+            )];
+        };
+        let mut violations = Vec::new();
+
+        if let Some(allowed) = &layer.may_depend_on {
+            for dep in &component.dependencies {
+                let dep_layers = rules.layers_of_dependency(dep);
+                let permitted = dep_layers.iter().any(|dep_layer| {
+                    dep_layer.name.eq_ignore_ascii_case(&layer.name)
+                        || allowed.iter().any(|name| name.eq_ignore_ascii_case(&dep_layer.name))
+                });
+                if let Some(dep_layer) = dep_layers.first().filter(|_| !permitted) {
+                    violations.push(ArchitectureFinding {
+                        dependency: Some(dep.clone()),
+                        ..finding(
+                            ViolationRule::LayerDependency,
+                            layer.severity,
+                            format!(
+                                "Architecture violation: {} ({}) imports from {} layer: {}",
+                                component.component_name, layer.name, dep_layer.name, dep
+                            ),
+                        )
+                    });
+                }
+            }
+        }
+
+        for rule in &rules.forbidden_imports {
+            let applies = rule.layers.is_empty() || rule.layers.iter().any(|name| name.eq_ignore_ascii_case(&layer.name));
+            let Some(pattern) = applies.then(|| Regex::new(&rule.pattern).ok()).flatten() else {
+                continue;
+            };
+            for dep in component.dependencies.iter().filter(|dep| pattern.is_match(dep)) {
+                let mut violation = format!(
+                    "Architecture violation: {} ({}) has forbidden import {}",
+                    component.component_name, layer.name, dep
+                );
+                if let Some(reason) = &rule.reason {
+                    violation.push_str(&format!(": {}", reason));
+                }
+                violations.push(ArchitectureFinding {
+                    dependency: Some(dep.clone()),
+                    ..finding(ViolationRule::ForbiddenImport, rule.severity, violation)
+                });
+            }
+        }
+
+        for rule in &rules.naming_patterns {
+            let applies = rule.layer.as_ref().is_none_or(|name| name.eq_ignore_ascii_case(&layer.name))
+                && rule
+                    .component_type
+                    .as_ref()
+                    .is_none_or(|component_type| component_type.eq_ignore_ascii_case(&component.component_type));
+            let Some(pattern) = applies.then(|| Regex::new(&rule.pattern).ok()).flatten() else {
+                continue;
+            };
+            if !pattern.is_match(&component.component_name) {
+                let mut violation = format!(
+                    "Naming violation: {} ({} {}) does not match {}",
+                    component.component_name, layer.name, component.component_type, rule.pattern
+                );
+                if let Some(description) = &rule.description {
+                    violation.push_str(&format!(" ({})", description));
+                }
+                violations.push(finding(ViolationRule::Naming, rule.severity, violation));
+            }
+        }
+
+        violations
     }
 }
 
 #[async_trait]
 impl<FS: FrameworkService> ArchitectureValidationService for ArchitectureValidationServiceImpl<FS> {
     async fn validate_architecture(&self, project_id: &str) -> Result<Vec<String>, McpError> {
-        let mut violations = Vec::new();
+        Ok(self
+            .find_violations(project_id)
+            .await?
+            .into_iter()
+            .map(|finding| finding.message)
+            .collect())
+    }
+
+    async fn find_violations(&self, project_id: &str) -> Result<Vec<ArchitectureFinding>, McpError> {
+        self.collect_findings(project_id, None).await
+    }
+
+    async fn find_violations_in(
+        &self,
+        project_id: &str,
+        components: &[String],
+    ) -> Result<Vec<ArchitectureFinding>, McpError> {
+        self.collect_findings(project_id, Some(components)).await
+    }
 
-        // Get all components for the project
+    async fn compare_with_baseline(&self, project_id: &str) -> Result<BaselineComparison, McpError> {
+        let findings = self.find_violations(project_id).await?;
+        Ok(self.load_baseline().await?.compare(project_id, findings))
+    }
+
+    async fn compare_with_baseline_in(
+        &self,
+        project_id: &str,
+        components: &[String],
+    ) -> Result<BaselineComparison, McpError> {
+        let names: Vec<String> = Self::select(&self.framework_service.list_components(project_id).await?, components)
+            .into_iter()
+            .map(|component| component.component_name)
+            .collect();
+        let findings = self.find_violations_in(project_id, components).await?;
+        let mut comparison = self.load_baseline().await?.compare(project_id, findings);
+        comparison.fixed.retain(|entry| names.contains(&entry.component));
+        Ok(comparison)
+    }
+
+    async fn changed_components(&self, project_id: &str, since: DateTime<Utc>) -> Result<Vec<String>, McpError> {
         let components = self.framework_service.list_components(project_id).await?;
+        let logged = match &self.change_log {
+            Some(change_log) => change_log.find_changed_entities(project_id, "framework_component", since).await?,
+            None => Vec::new(),
+        };
+        Ok(components
+            .into_iter()
+            .filter(|component| {
+                logged.contains(&component.id)
+                    || Self::changed_since(component.updated_at.as_deref(), since)
+                    || Self::changed_since(component.created_at.as_deref(), since)
+            })
+            .map(|component| component.id)
+            .collect())
+    }
 
-        // Validate each component's dependencies
-        for component in &components {
-            let component_violations = self.validate_component_dependencies(component).await?;
-            violations.extend(component_violations);
-        }
+    async fn acknowledge_violations(&self, project_id: &str) -> Result<Vec<ArchitectureFinding>, McpError> {
+        let path = self
+            .baseline_file
+            .as_ref()
+            .ok_or_else(|| McpError::invalid_params("No baseline file is configured", None))?;
+        let findings = self.find_violations(project_id).await?;
+        let mut baseline = self.load_baseline().await?;
+        baseline.acknowledge(project_id, &findings);
 
-        Ok(violations)
+        let content = serde_json::to_string_pretty(&baseline)
+            .map_err(|e| McpError::internal_error(format!("Serialization error: {}", e), None))?;
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| McpError::internal_error(format!("Cannot create {}: {}", parent.display(), e), None))?;
+        }
+        tokio::fs::write(path, content)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Cannot write {}: {}", path.display(), e), None))?;
+        Ok(findings)
     }
 
     async fn validate_component_dependencies(
         &self,
         component: &FrameworkComponent,
     ) -> Result<Vec<String>, McpError> {
-        let violations = match ArchitectureLayer::from_str(&component.architecture_layer) {
-            Ok(ArchitectureLayer::Presentation) => self.validate_presentation_layer(component),
-            Ok(ArchitectureLayer::Domain) => self.validate_domain_layer(component),
-            Ok(ArchitectureLayer::Data) => self.validate_data_layer(component),
-            Ok(ArchitectureLayer::Core) => self.validate_core_layer(component),
-            Err(err) => vec![err],
+        let rules = self.rule_set(&component.project_id).await?;
+        let mut findings = Self::check_component(&rules, component);
+        findings.extend(
+            self.convention_findings(&component.project_id, std::slice::from_ref(component))
+                .await?,
+        );
+        findings.extend(self.plugin_findings(&rules, std::slice::from_ref(component)).await);
+        Ok(findings.into_iter().map(|finding| finding.message).collect())
+    }
+
+    async fn rule_set(&self, project_id: &str) -> Result<ArchitectureRuleSet, McpError> {
+        let stored = match &self.rule_sets {
+            Some(rule_sets) => rule_sets.find_by_project_id(project_id).await?,
+            None => None,
         };
+        Ok(stored.unwrap_or_else(|| ArchitectureRuleSet::clean_architecture(project_id)))
+    }
 
-        Ok(violations)
+    async fn detect_cycles(
+        &self,
+        project_id: &str,
+        spec_project: Option<&str>,
+    ) -> Result<Vec<DependencyCycle>, McpError> {
+        let components = self.framework_service.list_components(project_id).await?;
+        let mut cycles = CycleDetector::component_cycles(&components);
+
+        if let Some(phases) = &self.phases {
+            cycles.extend(CycleDetector::phase_cycles(&phases.list_phases(project_id).await?));
+        }
+        if let Some(specifications) = &self.specifications {
+            let mut tasks = Vec::new();
+            for spec in specifications
+                .get_specifications_by_project(spec_project.unwrap_or(project_id))
+                .await?
+            {
+                tasks.extend(specifications.get_tasks_by_spec(&spec.id).await?);
+            }
+            cycles.extend(CycleDetector::task_cycles(&tasks));
+        }
+        Ok(cycles)
+    }
+
+    async fn evaluate_fitness(&self, project_id: &str, record: bool) -> Result<Vec<FitnessTrend>, McpError> {
+        let rules = self.rule_set(project_id).await?;
+        let components = self.framework_service.list_components(project_id).await?;
+        let measurements = FitnessEvaluator::new(&components).evaluate(&rules);
+
+        let mut trends = Vec::new();
+        for measurement in &measurements {
+            let previous = self.fitness_history(project_id, Some(&measurement.function), 1).await?;
+            trends.push(FitnessTrend::new(measurement.clone(), previous.first()));
+        }
+        if let Some(history) = self.fitness_history.as_ref().filter(|_| record) {
+            history.record(&measurements).await?;
+        }
+        Ok(trends)
+    }
+
+    async fn fitness_history(
+        &self,
+        project_id: &str,
+        function: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<FitnessMeasurement>, McpError> {
+        match &self.fitness_history {
+            Some(history) => history.history(project_id, function, limit).await,
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::architecture::{ForbiddenImport, LayerRule, NamingPattern};
+    use crate::models::context::ProjectConvention;
+    use crate::infrastructure::SqliteFrameworkRepository;
+    use crate::services::framework_service::FrameworkServiceImpl;
+
+    type Service = ArchitectureValidationServiceImpl<FrameworkServiceImpl<SqliteFrameworkRepository>>;
+
+    fn component(name: &str, component_type: &str, layer: &str, dependencies: &[&str]) -> FrameworkComponent {
+        FrameworkComponent {
+            id: name.to_string(),
+            project_id: "p1".to_string(),
+            component_name: name.to_string(),
+            component_type: component_type.to_string(),
+            architecture_layer: layer.to_string(),
+            file_path: None,
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            metadata: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn check(rules: &ArchitectureRuleSet, component: &FrameworkComponent) -> Vec<String> {
+        Service::check_component(rules, component).into_iter().map(|finding| finding.message).collect()
+    }
+
+    #[test]
+    fn test_clean_architecture_by_default() {
+        let rules = ArchitectureRuleSet::clean_architecture("p1");
+        let violations = check(
+            &rules,
+            &component("LoginPage", "widget", "presentation", &["domain/login.dart", "data/api.dart", "domain/data/user.dart"]),
+        );
+        assert_eq!(violations, vec!["Architecture violation: LoginPage (presentation) imports from data layer: data/api.dart"]);
+        assert_eq!(check(&rules, &component("Api", "service", "data", &["presentation/x"])).len(), 0);
+        assert_eq!(
+            check(&rules, &component("X", "service", "infra", &[])),
+            vec!["Unknown architecture layer: infra"]
+        );
+    }
+
+    #[test]
+    fn test_custom_rule_set() {
+        let rules = ArchitectureRuleSet {
+            project_id: "p1".to_string(),
+            layers: vec![
+                LayerRule { name: "api".to_string(), path_patterns: vec!["src/api/".to_string()], may_depend_on: Some(vec!["services".to_string()]), severity: None },
+                LayerRule { name: "services".to_string(), path_patterns: vec!["src/services/".to_string()], may_depend_on: Some(vec!["storage".to_string()]), severity: None },
+                LayerRule { name: "storage".to_string(), path_patterns: vec!["src/infrastructure/".to_string()], may_depend_on: Some(Vec::new()), severity: None },
+            ],
+            forbidden_imports: vec![ForbiddenImport {
+                pattern: r"^std::process".to_string(),
+                layers: vec!["services".to_string()],
+                reason: Some("services must not spawn processes".to_string()),
+                severity: None,
+            }],
+            naming_patterns: vec![NamingPattern {
+                pattern: "Service$".to_string(),
+                layer: Some("services".to_string()),
+                component_type: None,
+                description: Some("services end in Service".to_string()),
+                severity: Some(Severity::Info),
+            }],
+            fitness_functions: Vec::new(),
+            updated_at: None,
+        };
+        assert!(rules.problems().is_empty());
+
+        let api = component("Routes", "controller", "API", &["src/services/user.rs", "src/infrastructure/db.rs"]);
+        assert_eq!(
+            check(&rules, &api),
+            vec!["Architecture violation: Routes (api) imports from storage layer: src/infrastructure/db.rs"]
+        );
+        let service = component("UserManager", "service", "services", &["src/infrastructure/db.rs", "std::process::Command"]);
+        assert_eq!(
+            check(&rules, &service),
+            vec![
+                "Architecture violation: UserManager (services) has forbidden import std::process::Command: services must not spawn processes",
+                "Naming violation: UserManager (services service) does not match Service$ (services end in Service)",
+            ]
+        );
+        let severities: Vec<Severity> = Service::check_component(&rules, &service).iter().map(|f| f.severity).collect();
+        assert_eq!(severities, vec![Severity::Error, Severity::Info]);
+
+        let mut broken = rules.clone();
+        broken.layers[0].may_depend_on = Some(vec!["ui".to_string()]);
+        broken.naming_patterns[0].pattern = "(".to_string();
+        assert_eq!(broken.problems().len(), 2);
+    }
+
+    #[test]
+    fn test_naming_conventions() {
+        let convention = |id: &str, kind: &str, rule: &str| ProjectConvention {
+            id: id.to_string(),
+            project_id: "p1".to_string(),
+            convention_type: Some(kind.to_string()),
+            convention_rule: Some(rule.to_string()),
+            pattern: None,
+            good_examples: None,
+            bad_examples: None,
+            rationale: None,
+            enforcement_level: None,
+            created_at: None,
+        };
+        let mut services = convention("c1", "naming", "service suffix: Service");
+        services.rationale = Some("services are easy to find".to_string());
+        let conventions = [
+            services,
+            convention("c2", "File Naming", r"/^[a-z0-9_]+\.dart$/"),
+            convention("c3", "naming", "Widgets should read well"),
+            convention("c4", "testing", "prefix: test_"),
+        ];
+        let patterns: Vec<ConventionPattern> = conventions.iter().filter_map(ConventionPattern::from_convention).collect();
+        assert_eq!(patterns.len(), 2);
+
+        let mut page = component("LoginPage", "widget", "presentation", &[]);
+        page.file_path = Some("lib/ui/LoginPage.dart".to_string());
+        let mut manager = component("UserManager", "service", "domain", &[]);
+        manager.file_path = Some("lib/domain/user_manager.dart".to_string());
+        let findings = Service::check_conventions(&patterns, &[page, manager, component("AuthService", "service", "domain", &[])]);
+        let messages: Vec<&str> = findings.iter().map(|finding| finding.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Convention violation: UserManager does not end with Service (convention c1): services are easy to find",
+                r"Convention violation: file LoginPage.dart of LoginPage does not match ^[a-z0-9_]+\.dart$ (convention c2)",
+            ]
+        );
+        assert!(findings.iter().all(|finding| finding.rule == ViolationRule::Convention && finding.severity == Severity::Warning));
+        assert_eq!(findings[0].fixes[0].description, "Rename UserManager to UserManagerService");
+        assert_eq!(findings[0].fixes[0].actions[0].arguments["data"]["component_name"], "UserManagerService");
+        assert!(findings[1].fixes.is_empty());
+    }
+
+    #[test]
+    fn test_forbidden_manifest_dependencies() {
+        let mut rules = ArchitectureRuleSet::clean_architecture("p1");
+        rules.forbidden_imports.push(ForbiddenImport {
+            pattern: "^(rusqlite|sqflite)$".to_string(),
+            layers: vec!["presentation".to_string()],
+            reason: Some("no storage in the UI".to_string()),
+            severity: None,
+        });
+        let manifest = |path: &str, kind: ManifestKind, body: &str| {
+            crate::services::manifest_importer::ManifestParser::parse(kind, path, body)
+                .unwrap()
+                .component_dependencies("p1")
+        };
+        let mut rows = manifest("crates/presentation/Cargo.toml", ManifestKind::Cargo, "[dependencies]\nrusqlite = \"0.31\"\n");
+        rows.extend(manifest("crates/store/Cargo.toml", ManifestKind::Cargo, "[dependencies]\nrusqlite = \"0.31\"\n"));
+        rows.extend(manifest("app/pubspec.yaml", ManifestKind::Pubspec, "name: app\ndependencies:\n  sqflite: ^2.0.0\n"));
+
+        let mut page = component("LoginPage", "widget", "presentation", &[]);
+        page.file_path = Some("app/lib/login_page.dart".to_string());
+        let findings = Service::check_manifest_dependencies(&rules, &rows, &[page]);
+        let messages: Vec<&str> = findings.iter().map(|finding| finding.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Architecture violation: crates/presentation/Cargo.toml (presentation) declares forbidden dependency rusqlite: no storage in the UI",
+                "Architecture violation: app/pubspec.yaml (presentation) declares forbidden dependency sqflite: no storage in the UI",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_baseline_suppresses_acknowledged_findings() {
+        let rules = ArchitectureRuleSet::clean_architecture("p1");
+        let page = component("LoginPage", "widget", "presentation", &["data/api.dart"]);
+        let mut baseline = ArchitectureBaseline::default();
+        baseline.acknowledge("p1", &Service::check_component(&rules, &page));
+        baseline.acknowledge("p2", &Service::check_component(&rules, &component("Other", "widget", "ui", &[])));
+
+        let page = component("LoginPage", "widget", "presentation", &["data/cache.dart"]);
+        let comparison = baseline.compare("p1", Service::check_component(&rules, &page));
+        assert!(comparison.suppressed.is_empty());
+        assert_eq!(comparison.new.len(), 1);
+        assert_eq!(comparison.new[0].rule, ViolationRule::LayerDependency);
+        assert_eq!(comparison.fixed.len(), 1);
+        assert_eq!(comparison.fixed[0].message, "Architecture violation: LoginPage (presentation) imports from data layer: data/api.dart");
+
+        let page = component("LoginPage", "widget", "presentation", &["data/api.dart", "data/cache.dart"]);
+        let comparison = baseline.compare("p1", Service::check_component(&rules, &page));
+        assert_eq!((comparison.new.len(), comparison.suppressed.len(), comparison.fixed.len()), (1, 1, 0));
+        assert_eq!(baseline.entries.len(), 2);
+    }
+
+    #[test]
+    fn test_select_changed_components() {
+        let mut page = component("LoginPage", "widget", "presentation", &[]);
+        page.id = "c1".to_string();
+        let components = vec![page, component("AuthService", "service", "domain", &[])];
+        let names = |selection: &[&str]| -> Vec<String> {
+            let selection: Vec<String> = selection.iter().map(|s| s.to_string()).collect();
+            Service::select(&components, &selection).into_iter().map(|c| c.component_name).collect()
+        };
+        assert_eq!(names(&["c1", "AuthService", "missing"]), vec!["LoginPage", "AuthService"]);
+        assert!(names(&[]).is_empty());
+
+        let since = DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        assert!(Service::changed_since(Some("2024-05-01T12:00:00+00:00"), since));
+        assert!(Service::changed_since(Some("2024-05-02 08:30:00"), since));
+        assert!(!Service::changed_since(Some("2024-05-01T13:59:59+02:00"), since));
+        assert!(!Service::changed_since(Some("yesterday"), since));
+        assert!(!Service::changed_since(None, since));
     }
 }
@@ -0,0 +1,189 @@
+//! Files attached to entities: diagrams, sequence charts and sample payloads kept with the
+//! decisions and features they illustrate. Contents are stored once per SHA-256 in a
+//! `BlobStore` and limited in size by `[attachments]`
+
+use crate::config::ConfigManager;
+use crate::models::attachment::Attachment;
+use crate::repositories::{AttachmentRepository, BlobStore};
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+/// Service for attachment operations
+#[async_trait]
+pub trait AttachmentService: Send + Sync {
+    /// Store `content` as `attachment`, filling in its size and hash; rejected when it would
+    /// exceed `[attachments] max_size_bytes` or the entity's `max_entity_bytes`
+    async fn add_attachment(&self, attachment: Attachment, content: &[u8]) -> Result<Attachment, McpError>;
+    /// The attachment and its content
+    async fn get_attachment(&self, id: &str) -> Result<Option<(Attachment, Vec<u8>)>, McpError>;
+    async fn list_attachments(&self, entity_id: &str) -> Result<Vec<Attachment>, McpError>;
+    /// Remove an entity's attachments, and the contents no other attachment shares
+    async fn delete_entity_attachments(&self, entity_id: &str) -> Result<usize, McpError>;
+}
+
+pub struct AttachmentServiceImpl {
+    repository: Arc<dyn AttachmentRepository>,
+    blobs: Arc<dyn BlobStore>,
+    config: Arc<ConfigManager>,
+    /// Held while contents are stored or removed, so a content is never deleted between
+    /// another attachment storing it and saving its row
+    content_lock: tokio::sync::Mutex<()>,
+}
+
+impl AttachmentServiceImpl {
+    pub fn new(
+        repository: Arc<dyn AttachmentRepository>,
+        blobs: Arc<dyn BlobStore>,
+        config: Arc<ConfigManager>,
+    ) -> Self {
+        Self {
+            repository,
+            blobs,
+            config,
+            content_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    fn content_hash(content: &[u8]) -> String {
+        Sha256::digest(content).iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
+
+#[async_trait]
+impl AttachmentService for AttachmentServiceImpl {
+    async fn add_attachment(&self, mut attachment: Attachment, content: &[u8]) -> Result<Attachment, McpError> {
+        let limits = self.config.current().attachments;
+        let size_bytes = content.len() as u64;
+        if size_bytes > limits.max_size_bytes {
+            return Err(McpError::invalid_params(
+                format!(
+                    "{} is {size_bytes} bytes; attachments may be at most {} bytes",
+                    attachment.file_name, limits.max_size_bytes
+                ),
+                None,
+            ));
+        }
+        if limits.max_entity_bytes > 0 {
+            let attached: u64 = self
+                .repository
+                .find_by_entity(&attachment.entity_id)
+                .await?
+                .iter()
+                .map(|existing| existing.size_bytes)
+                .sum();
+            if attached + size_bytes > limits.max_entity_bytes {
+                return Err(McpError::invalid_params(
+                    format!(
+                        "{} already has {attached} bytes of attachments; adding {size_bytes} would exceed {} bytes",
+                        attachment.entity_id, limits.max_entity_bytes
+                    ),
+                    None,
+                ));
+            }
+        }
+
+        attachment.size_bytes = size_bytes;
+        attachment.content_hash = Self::content_hash(content);
+        let _content_guard = self.content_lock.lock().await;
+        self.blobs.put(&attachment.content_hash, content).await?;
+        self.repository.save(&attachment).await?;
+        Ok(attachment)
+    }
+
+    async fn get_attachment(&self, id: &str) -> Result<Option<(Attachment, Vec<u8>)>, McpError> {
+        let Some(attachment) = self.repository.find(id).await? else {
+            return Ok(None);
+        };
+        let content = self.blobs.get(&attachment.content_hash).await?.ok_or_else(|| {
+            McpError::internal_error(format!("Content of attachment {id} is missing"), None)
+        })?;
+        Ok(Some((attachment, content)))
+    }
+
+    async fn list_attachments(&self, entity_id: &str) -> Result<Vec<Attachment>, McpError> {
+        self.repository.find_by_entity(entity_id).await
+    }
+
+    async fn delete_entity_attachments(&self, entity_id: &str) -> Result<usize, McpError> {
+        let _content_guard = self.content_lock.lock().await;
+        let (removed, orphaned) = self.repository.delete_by_entity(entity_id).await?;
+        for content_hash in &orphaned {
+            self.blobs.delete(content_hash).await?;
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::infrastructure::{SqliteAttachmentRepository, SqliteBlobStore};
+    use rusqlite::Connection;
+    use std::sync::Mutex;
+
+    fn attachment(entity_id: &str, file_name: &str) -> Attachment {
+        Attachment {
+            id: uuid::Uuid::new_v4().to_string(),
+            project_id: "p1".to_string(),
+            entity_type: "architectural_decision".to_string(),
+            entity_id: entity_id.to_string(),
+            file_name: file_name.to_string(),
+            media_type: "text/plain".to_string(),
+            size_bytes: 0,
+            content_hash: String::new(),
+            description: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            created_by: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_contents_are_shared_and_limited() {
+        let db = Arc::new(Mutex::new(Connection::open_in_memory().unwrap()));
+        db.lock()
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE attachments (id TEXT PRIMARY KEY, project_id TEXT, entity_type TEXT, entity_id TEXT,
+                    file_name TEXT, media_type TEXT, size_bytes INTEGER, content_hash TEXT, description TEXT,
+                    created_at TEXT, created_by TEXT);
+                 CREATE TABLE attachment_blobs (content_hash TEXT PRIMARY KEY, content BLOB NOT NULL);",
+            )
+            .unwrap();
+        let mut config = AppConfig::default();
+        config.attachments.max_size_bytes = 10;
+        config.attachments.max_entity_bytes = 12;
+        let service = AttachmentServiceImpl::new(
+            Arc::new(SqliteAttachmentRepository::new(db.clone())),
+            Arc::new(SqliteBlobStore::new(db.clone())),
+            Arc::new(ConfigManager::new(config, None)),
+        );
+
+        let first = service.add_attachment(attachment("adr-1", "a.txt"), b"sequence").await.unwrap();
+        assert_eq!(first.size_bytes, 8);
+        assert_eq!(first.content_hash.len(), 64);
+        let copy = service.add_attachment(attachment("adr-2", "b.txt"), b"sequence").await.unwrap();
+        assert_eq!(copy.content_hash, first.content_hash);
+
+        assert!(service.add_attachment(attachment("adr-3", "big.txt"), b"more than ten").await.is_err());
+        assert!(service.add_attachment(attachment("adr-1", "c.txt"), b"12345").await.is_err());
+
+        let (stored, content) = service.get_attachment(&first.id).await.unwrap().unwrap();
+        assert_eq!(stored.file_name, "a.txt");
+        assert_eq!(content, b"sequence");
+
+        // The content stays while another attachment shares it
+        assert_eq!(service.delete_entity_attachments("adr-1").await.unwrap(), 1);
+        assert!(service.get_attachment(&first.id).await.unwrap().is_none());
+        assert_eq!(service.get_attachment(&copy.id).await.unwrap().unwrap().1, b"sequence");
+        service.delete_entity_attachments("adr-2").await.unwrap();
+        let blobs: i64 = db
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM attachment_blobs", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(blobs, 0);
+    }
+}
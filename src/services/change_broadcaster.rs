@@ -1,9 +1,14 @@
+use crate::repositories::ChangeLogRepository;
+use crate::services::json_patch;
 use crate::services::websocket_types::*;
 use anyhow::{anyhow, Result};
 use chrono::Utc;
 use dashmap::DashMap;
 use serde_json::Value;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::broadcast;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
@@ -13,6 +18,8 @@ use uuid::Uuid;
 pub struct ChangeBroadcaster {
     /// Broadcast channel for sending changes to all subscribers
     change_sender: broadcast::Sender<ContextChange>,
+    /// The same changes grouped as they should be sent to a client in one frame
+    batch_sender: broadcast::Sender<Vec<ContextChange>>,
     /// Client subscriptions with filters
     pub subscriptions: Arc<DashMap<ClientId, Vec<SyncFilters>>>,
     /// Change queue for reliable delivery
@@ -21,6 +28,17 @@ pub struct ChangeBroadcaster {
     pub change_history: Arc<DashMap<String, ChangeHistory>>,
     /// Metrics for monitoring
     metrics: Arc<BroadcastMetrics>,
+    /// Durable, sequence-numbered record of broadcast changes for replay after a reconnect
+    change_log: Option<Arc<dyn ChangeLogRepository>>,
+    /// Most changes queued per client; older ones are dropped first
+    max_queue_depth: usize,
+    /// How long changes are collected before being coalesced and sent; `None` sends
+    /// each change as it happens
+    batch_window: Option<Duration>,
+    /// Most changes in one batch; larger flushes are split
+    max_batch_size: usize,
+    /// Changes collected during the current batch window
+    pending_changes: Arc<Mutex<Vec<ContextChange>>>,
 }
 
 /// Queued change for reliable delivery
@@ -47,7 +65,6 @@ pub struct ChangeHistory {
 struct VersionedChange {
     version: u32,
     change: ContextChange,
-    timestamp: chrono::DateTime<chrono::Utc>,
 }
 
 /// Broadcasting metrics
@@ -58,6 +75,24 @@ pub struct BroadcastMetrics {
     pub failed_deliveries: std::sync::atomic::AtomicU64,
     pub delta_calculations: std::sync::atomic::AtomicU64,
     pub queue_size: std::sync::atomic::AtomicU64,
+    /// Batches published, including single changes sent without batching
+    pub batches_sent: std::sync::atomic::AtomicU64,
+    /// Changes published across all batches
+    pub batched_changes: std::sync::atomic::AtomicU64,
+    /// Changes merged into a later change to the same entity before publishing
+    pub coalesced_changes: std::sync::atomic::AtomicU64,
+    pub largest_batch: std::sync::atomic::AtomicU64,
+}
+
+impl BroadcastMetrics {
+    /// Mean number of changes per published batch
+    pub fn average_batch_size(&self) -> f64 {
+        let batches = self.batches_sent.load(Ordering::Relaxed);
+        if batches == 0 {
+            return 0.0;
+        }
+        self.batched_changes.load(Ordering::Relaxed) as f64 / batches as f64
+    }
 }
 
 /// Change event for internal processing
@@ -73,20 +108,117 @@ pub struct ChangeEvent {
     pub feature_area: Option<String>,
 }
 
+/// Entity fields naming the area of the product an entity belongs to, in order of preference
+const FEATURE_AREA_FIELDS: &[&str] = &["feature_area", "domain_area", "component_area", "policy_area"];
+
+/// Feature area of an entity for `SyncFilters::feature_areas`: its first non-empty
+/// area field, or the feature name of a feature context
+pub fn entity_feature_area(entity_type: &str, entity: &Value) -> Option<String> {
+    let fields: &[&str] = if entity_type == "feature_context" { &["feature_name"] } else { FEATURE_AREA_FIELDS };
+    fields
+        .iter()
+        .filter_map(|field| entity.get(*field).and_then(Value::as_str))
+        .map(str::trim)
+        .find(|area| !area.is_empty())
+        .map(str::to_string)
+}
+
 impl ChangeBroadcaster {
     /// Create a new change broadcaster
     pub fn new() -> Self {
         let (change_sender, _) = broadcast::channel(1000);
+        let (batch_sender, _) = broadcast::channel(1000);
         
         Self {
             change_sender,
+            batch_sender,
             subscriptions: Arc::new(DashMap::new()),
             change_queue: Arc::new(DashMap::new()),
             change_history: Arc::new(DashMap::new()),
             metrics: Arc::new(BroadcastMetrics::default()),
+            change_log: None,
+            max_queue_depth: 1000,
+            batch_window: None,
+            max_batch_size: 100,
+            pending_changes: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
+    /// Collect changes for `window` and send them as batches of at most `max_batch_size`,
+    /// with rapid changes to the same entity merged into one. A zero window, or no tokio
+    /// runtime to flush on, leaves batching off.
+    pub fn with_batching(mut self, window: Duration, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size.max(1);
+        if window.is_zero() {
+            return self;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            debug!("No tokio runtime; change batching not started");
+            return self;
+        };
+
+        self.batch_window = Some(window);
+        let broadcaster = self.clone();
+        runtime.spawn(async move {
+            let mut interval = tokio::time::interval(window);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                broadcaster.flush_pending().await;
+            }
+        });
+        self
+    }
+
+    /// Queue at most `depth` undelivered changes per client
+    pub fn with_max_queue_depth(mut self, depth: usize) -> Self {
+        self.max_queue_depth = depth.max(1);
+        self
+    }
+
+    /// Number every broadcast change and store it in `change_log`, so clients can resume
+    pub fn with_change_log(mut self, change_log: Arc<dyn ChangeLogRepository>) -> Self {
+        self.change_log = Some(change_log);
+        self
+    }
+
+    /// Whether changes are logged for replay
+    pub fn has_change_log(&self) -> bool {
+        self.change_log.is_some()
+    }
+
+    /// Up to `limit` logged changes after sequence `after`, oldest first
+    pub async fn changes_since(&self, after: u64, limit: usize) -> Result<Vec<ContextChange>> {
+        let change_log = self
+            .change_log
+            .as_ref()
+            .ok_or_else(|| anyhow!("Change replay is not enabled on this server"))?;
+        Ok(change_log.find_since(after, limit).await?)
+    }
+
+    /// Periodically drop logged changes older than `retention`
+    pub fn spawn_log_pruning(&self, retention: chrono::Duration) {
+        let Some(change_log) = self.change_log.clone() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            debug!("No tokio runtime; change log pruning not started");
+            return;
+        };
+
+        runtime.spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+            loop {
+                interval.tick().await;
+                match change_log.delete_before(Utc::now() - retention).await {
+                    Ok(0) => {}
+                    Ok(removed) => debug!("Pruned {} changes from the change log", removed),
+                    Err(e) => warn!("Failed to prune the change log: {}", e),
+                }
+            }
+        });
+    }
+
     /// Start the change broadcaster with background processing
     pub async fn start(&self) -> Result<()> {
         info!("Starting change broadcaster");
@@ -136,8 +268,26 @@ impl ChangeBroadcaster {
     }
 
     /// Broadcast a change event to all subscribed clients
-    pub async fn broadcast_change(&self, event: ChangeEvent) -> Result<()> {
+    pub async fn broadcast_change(&self, mut event: ChangeEvent) -> Result<()> {
         debug!("Broadcasting change for entity {}/{}", event.entity_type, event.entity_id);
+
+        // Scope the change for filtering: deletions are often reported without the
+        // project, so fall back to what the entity's previous change recorded
+        if event.feature_area.is_none() {
+            event.feature_area = event
+                .new_value
+                .as_ref()
+                .or(event.old_value.as_ref())
+                .and_then(|entity| entity_feature_area(&event.entity_type, entity));
+        }
+        if event.project_id.is_empty() || event.feature_area.is_none() {
+            if let Some(previous) = self.last_change(&event.entity_type, &event.entity_id) {
+                if event.project_id.is_empty() {
+                    event.project_id = previous.project_id;
+                }
+                event.feature_area = event.feature_area.or(previous.feature_area);
+            }
+        }
         
         // Calculate delta if this is an update
         let delta = if event.change_type == ChangeType::Update {
@@ -147,7 +297,7 @@ impl ChangeBroadcaster {
         };
 
         // Create context change
-        let context_change = ContextChange {
+        let mut context_change = ContextChange {
             change_id: Uuid::new_v4(),
             change_type: event.change_type.clone(),
             entity_type: event.entity_type.clone(),
@@ -162,9 +312,18 @@ impl ChangeBroadcaster {
                 timestamp: Utc::now(),
                 version: self.get_next_version(&event.entity_id).await,
                 conflict_resolution: None,
+                sequence: None,
             },
         };
 
+        // Log before matching subscribers, so clients offline right now can replay it
+        if let Some(change_log) = &self.change_log {
+            match change_log.append(&context_change).await {
+                Ok(sequence) => context_change.metadata.sequence = Some(sequence),
+                Err(e) => warn!("Failed to log change {}: {}", context_change.change_id, e),
+            }
+        }
+
         // Update change history
         self.update_change_history(&context_change).await;
 
@@ -176,11 +335,11 @@ impl ChangeBroadcaster {
             return Ok(());
         }
 
-        // Try to broadcast immediately
-        let immediate_success = self.try_immediate_broadcast(&context_change).await;
-        
-        // Queue for clients that couldn't receive immediately
-        if !immediate_success {
+        if self.batch_window.is_some() {
+            // Sent with the rest of the window's changes by `flush_pending`
+            self.pending_changes.lock().unwrap().push(context_change);
+        } else if !self.publish(vec![context_change.clone()]) {
+            // Queue for clients that couldn't receive immediately
             self.queue_change(&context_change, &matching_clients).await?;
         }
 
@@ -191,61 +350,46 @@ impl ChangeBroadcaster {
         Ok(())
     }
 
-    /// Calculate delta between old and new values
+    /// RFC 6902 patch from the old to the new value of an update; `None` when the
+    /// previous state is unknown
     pub async fn calculate_delta(&self, event: &ChangeEvent) -> Result<Option<Value>> {
         self.metrics.delta_calculations.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        
-        let old_value = match &event.old_value {
-            Some(val) => val,
-            None => return Ok(None),
-        };
 
-        let new_value = match &event.new_value {
-            Some(val) => val,
-            None => return Ok(None),
-        };
+        match (&event.old_value, &event.new_value) {
+            (Some(old_value), Some(new_value)) if !old_value.is_null() => {
+                Ok(Some(json_patch::to_delta(&json_patch::diff(old_value, new_value))))
+            }
+            _ => Ok(None),
+        }
+    }
 
-        // Simple delta calculation - in production, use a more sophisticated diff algorithm
-        let delta = serde_json::json!({
-            "old": old_value,
-            "new": new_value,
-            "changed_fields": self.find_changed_fields(old_value, new_value)
-        });
+    /// Latest change recorded for an entity
+    fn last_change(&self, entity_type: &str, entity_id: &str) -> Option<ContextChange> {
+        let history = self.change_history.get(&format!("{}:{}", entity_type, entity_id))?;
+        history.versions.last().map(|versioned| versioned.change.clone())
+    }
 
-        Ok(Some(delta))
+    /// Version of an entity's latest broadcast change
+    pub fn current_version(&self, entity_type: &str, entity_id: &str) -> Option<u32> {
+        self.last_change(entity_type, entity_id).map(|change| change.metadata.version)
     }
 
-    /// Find changed fields between two JSON values
-    fn find_changed_fields(&self, old: &Value, new: &Value) -> Vec<String> {
-        let mut changed_fields = Vec::new();
-        
-        match (old, new) {
-            (Value::Object(old_obj), Value::Object(new_obj)) => {
-                // Check for changed or new fields
-                for (key, new_val) in new_obj {
-                    if let Some(old_val) = old_obj.get(key) {
-                        if old_val != new_val {
-                            changed_fields.push(key.clone());
-                        }
-                    } else {
-                        changed_fields.push(key.clone());
-                    }
-                }
-                
-                // Check for removed fields
-                for key in old_obj.keys() {
-                    if !new_obj.contains_key(key) {
-                        changed_fields.push(format!("removed_{}", key));
-                    }
-                }
-            }
-            _ => {
-                // For non-object values, consider the entire value changed
-                changed_fields.push("value".to_string());
-            }
-        }
-        
-        changed_fields
+    /// The change that made `version` of an entity, while it is among the recent changes
+    /// kept per entity
+    pub fn change_at_version(&self, entity_type: &str, entity_id: &str, version: u32) -> Option<ContextChange> {
+        let history = self.change_history.get(&format!("{}:{}", entity_type, entity_id))?;
+        history
+            .versions
+            .iter()
+            .find(|versioned| versioned.version == version)
+            .map(|versioned| versioned.change.clone())
+    }
+
+    /// Entity as of its latest broadcast change, unless that change deleted it
+    pub fn last_entity(&self, entity_type: &str, entity_id: &str) -> Option<Value> {
+        self.last_change(entity_type, entity_id)
+            .filter(|change| change.change_type != ChangeType::Delete)?
+            .full_entity
     }
 
     /// Find clients that match the change filters
@@ -267,20 +411,38 @@ impl ChangeBroadcaster {
         matching_clients
     }
 
-    /// Try to broadcast change immediately
-    async fn try_immediate_broadcast(&self, change: &ContextChange) -> bool {
-        match self.change_sender.send(change.clone()) {
-            Ok(receiver_count) => {
-                debug!("Immediately broadcast change {} to {} receivers", change.change_id, receiver_count);
-                true
+    /// Coalesce the changes collected so far and publish them in batches
+    pub async fn flush_pending(&self) {
+        let pending = std::mem::take(&mut *self.pending_changes.lock().unwrap());
+        if pending.is_empty() {
+            return;
+        }
+
+        let collected = pending.len();
+        let changes = coalesce_changes(pending);
+        self.metrics
+            .coalesced_changes
+            .fetch_add((collected - changes.len()) as u64, Ordering::Relaxed);
+
+        for batch in changes.chunks(self.max_batch_size) {
+            if self.publish(batch.to_vec()) {
+                continue;
             }
-            Err(_) => {
-                warn!("Failed to immediately broadcast change {}", change.change_id);
-                false
+            for change in batch {
+                let matching_clients = self.find_matching_clients(change).await;
+                if let Err(e) = self.queue_change(change, &matching_clients).await {
+                    warn!("Failed to queue change {}: {}", change.change_id, e);
+                }
             }
         }
     }
 
+    /// Send `changes` to change subscribers one by one and to batch subscribers as one
+    /// batch; false when nobody is listening
+    fn publish(&self, changes: Vec<ContextChange>) -> bool {
+        publish_changes(&self.change_sender, &self.batch_sender, &self.metrics, changes)
+    }
+
     /// Queue change for reliable delivery
     pub async fn queue_change(&self, change: &ContextChange, target_clients: &[ClientId]) -> Result<()> {
         let queued_change = QueuedChange {
@@ -293,8 +455,14 @@ impl ChangeBroadcaster {
 
         for &client_id in target_clients {
             if let Some(mut queue) = self.change_queue.get_mut(&client_id) {
+                if queue.len() >= self.max_queue_depth {
+                    let dropped = queue.remove(0);
+                    self.metrics.failed_deliveries.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    debug!("Queue of client {} is full; dropped change {}", client_id, dropped.change_id);
+                } else {
+                    self.metrics.queue_size.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                }
                 queue.push(queued_change.clone());
-                self.metrics.queue_size.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
             }
         }
 
@@ -309,7 +477,6 @@ impl ChangeBroadcaster {
         let versioned_change = VersionedChange {
             version: change.metadata.version,
             change: change.clone(),
-            timestamp: change.metadata.timestamp,
         };
 
         if let Some(mut history) = self.change_history.get_mut(&history_key) {
@@ -353,6 +520,12 @@ impl ChangeBroadcaster {
         self.change_sender.subscribe()
     }
 
+    /// Receiver for changes grouped into batches, one per frame sent to clients. Without
+    /// batching every batch holds a single change.
+    pub fn subscribe_to_batches(&self) -> broadcast::Receiver<Vec<ContextChange>> {
+        self.batch_sender.subscribe()
+    }
+
     /// Get queued changes for a client
     pub async fn get_queued_changes(&self, client_id: ClientId) -> Vec<QueuedChange> {
         self.change_queue.get(&client_id)
@@ -393,6 +566,10 @@ impl ChangeBroadcaster {
             queue_size: std::sync::atomic::AtomicU64::new(
                 self.metrics.queue_size.load(std::sync::atomic::Ordering::Relaxed)
             ),
+            batches_sent: std::sync::atomic::AtomicU64::new(self.metrics.batches_sent.load(Ordering::Relaxed)),
+            batched_changes: std::sync::atomic::AtomicU64::new(self.metrics.batched_changes.load(Ordering::Relaxed)),
+            coalesced_changes: std::sync::atomic::AtomicU64::new(self.metrics.coalesced_changes.load(Ordering::Relaxed)),
+            largest_batch: std::sync::atomic::AtomicU64::new(self.metrics.largest_batch.load(Ordering::Relaxed)),
         }
     }
 
@@ -400,6 +577,7 @@ impl ChangeBroadcaster {
     async fn start_queue_processing(&self) {
         let change_queue = self.change_queue.clone();
         let change_sender = self.change_sender.clone();
+        let batch_sender = self.batch_sender.clone();
         let metrics = self.metrics.clone();
 
         tokio::spawn(async move {
@@ -416,13 +594,13 @@ impl ChangeBroadcaster {
 
                     for (index, queued_change) in queue.iter_mut().enumerate() {
                         // Try to resend the change
-                        match change_sender.send(queued_change.change.clone()) {
-                            Ok(_) => {
+                        match publish_changes(&change_sender, &batch_sender, &metrics, vec![queued_change.change.clone()]) {
+                            true => {
                                 to_remove.push(index);
                                 debug!("Successfully resent queued change {} to client {}", 
                                       queued_change.change_id, client_id);
                             }
-                            Err(_) => {
+                            false => {
                                 queued_change.retry_count += 1;
                                 
                                 // Remove changes that have been retried too many times
@@ -473,16 +651,114 @@ impl ChangeBroadcaster {
                 }
 
                 // Log metrics
-                info!("Broadcast metrics - Changes: {}, Clients notified: {}, Failed: {}, Queue size: {}",
+                info!("Broadcast metrics - Changes: {}, Clients notified: {}, Failed: {}, Queue size: {}, Batches: {} (avg {:.1}, max {}), Coalesced: {}",
                       metrics.total_changes_broadcast.load(std::sync::atomic::Ordering::Relaxed),
                       metrics.total_clients_notified.load(std::sync::atomic::Ordering::Relaxed),
                       metrics.failed_deliveries.load(std::sync::atomic::Ordering::Relaxed),
-                      metrics.queue_size.load(std::sync::atomic::Ordering::Relaxed));
+                      metrics.queue_size.load(std::sync::atomic::Ordering::Relaxed),
+                      metrics.batches_sent.load(Ordering::Relaxed),
+                      metrics.average_batch_size(),
+                      metrics.largest_batch.load(Ordering::Relaxed),
+                      metrics.coalesced_changes.load(Ordering::Relaxed));
             }
         });
     }
 }
 
+fn publish_changes(
+    change_sender: &broadcast::Sender<ContextChange>,
+    batch_sender: &broadcast::Sender<Vec<ContextChange>>,
+    metrics: &BroadcastMetrics,
+    changes: Vec<ContextChange>,
+) -> bool {
+    let mut delivered = false;
+    for change in &changes {
+        delivered |= change_sender.send(change.clone()).is_ok();
+    }
+    let size = changes.len() as u64;
+    match batch_sender.send(changes) {
+        Ok(receiver_count) => {
+            debug!("Broadcast a batch of {} changes to {} receivers", size, receiver_count);
+            delivered = true;
+        }
+        Err(_) if !delivered => warn!("Failed to broadcast a batch of {} changes", size),
+        Err(_) => {}
+    }
+    if delivered {
+        metrics.batches_sent.fetch_add(1, Ordering::Relaxed);
+        metrics.batched_changes.fetch_add(size, Ordering::Relaxed);
+        metrics.largest_batch.fetch_max(size, Ordering::Relaxed);
+    }
+    delivered
+}
+
+/// `changes` with each run of rapid changes to one entity merged into a single change at
+/// the position of the first. An update folds into the create or update before it, and a
+/// delete replaces the updates before it or, after a create, cancels it out. Changes after
+/// a delete, creates, and bulk changes start a new run.
+pub fn coalesce_changes(changes: Vec<ContextChange>) -> Vec<ContextChange> {
+    let mut merged: Vec<Option<ContextChange>> = Vec::with_capacity(changes.len());
+    let mut latest: HashMap<(String, String), usize> = HashMap::new();
+
+    for change in changes {
+        let key = (change.entity_type.clone(), change.entity_id.clone());
+        let previous = latest.get(&key).copied().filter(|&index| {
+            merged[index].as_ref().is_some_and(|earlier| {
+                !matches!(earlier.change_type, ChangeType::Delete | ChangeType::Bulk)
+                    && !matches!(change.change_type, ChangeType::Create | ChangeType::Bulk)
+            })
+        });
+        let Some(index) = previous else {
+            latest.insert(key, merged.len());
+            merged.push(Some(change));
+            continue;
+        };
+        merged[index] = merged[index].take().and_then(|earlier| merge_changes(earlier, change));
+        if merged[index].is_none() {
+            latest.remove(&key);
+        }
+    }
+
+    merged.into_iter().flatten().collect()
+}
+
+/// `later` folded into the create or update `earlier` it directly follows; `None` when
+/// the two cancel out
+fn merge_changes(earlier: ContextChange, later: ContextChange) -> Option<ContextChange> {
+    let patch = later.delta.as_ref().and_then(json_patch::from_delta);
+    let full_entity = later.full_entity.clone().or_else(|| {
+        json_patch::apply(earlier.full_entity.as_ref()?, patch.as_ref()?).ok()
+    });
+
+    match (&earlier.change_type, &later.change_type) {
+        (ChangeType::Create, ChangeType::Delete) => None,
+        (ChangeType::Create, ChangeType::Update) => Some(ContextChange {
+            change_type: ChangeType::Create,
+            delta: None,
+            full_entity,
+            ..later
+        }),
+        (ChangeType::Update, ChangeType::Update) => {
+            // The patches applied one after the other; unknown if either is not a patch
+            let delta = earlier
+                .delta
+                .as_ref()
+                .and_then(json_patch::from_delta)
+                .zip(patch)
+                .map(|(mut first, second)| {
+                    first.extend(second);
+                    json_patch::to_delta(&first)
+                });
+            Some(ContextChange {
+                delta,
+                full_entity,
+                ..later
+            })
+        }
+        _ => Some(later),
+    }
+}
+
 impl Default for ChangeBroadcaster {
     fn default() -> Self {
         Self::new()
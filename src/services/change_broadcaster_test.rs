@@ -1,4 +1,5 @@
 use super::change_broadcaster::*;
+use super::json_patch::{self, PatchOperation};
 use super::websocket_types::*;
 use chrono::Utc;
 use serde_json::json;
@@ -157,17 +158,24 @@ async fn test_delta_calculation() {
     assert!(delta.is_some());
     let delta_value = delta.unwrap();
     
-    // Check that delta contains old and new values
-    assert!(delta_value.get("old").is_some());
-    assert!(delta_value.get("new").is_some());
-    assert!(delta_value.get("changed_fields").is_some());
-    
-    // Check changed fields
-    let changed_fields = delta_value.get("changed_fields").unwrap().as_array().unwrap();
-    assert!(changed_fields.contains(&json!("name")));
-    assert!(changed_fields.contains(&json!("description")));
-    assert!(changed_fields.contains(&json!("status")));
-    assert!(changed_fields.contains(&json!("removed_priority")));
+    // The delta is a JSON Patch from the old to the new value
+    let patch = json_patch::from_delta(&delta_value).expect("delta should be a JSON Patch");
+    assert_eq!(patch.len(), 4);
+    assert!(patch.contains(&PatchOperation::Replace {
+        path: "/name".to_string(),
+        value: json!("New Rule Name")
+    }));
+    assert!(patch.contains(&PatchOperation::Replace {
+        path: "/description".to_string(),
+        value: json!("New description")
+    }));
+    assert!(patch.contains(&PatchOperation::Add {
+        path: "/status".to_string(),
+        value: json!("active")
+    }));
+    assert!(patch.contains(&PatchOperation::Remove {
+        path: "/priority".to_string()
+    }));
 }
 
 #[tokio::test]
@@ -215,6 +223,7 @@ async fn test_change_filtering() {
             timestamp: Utc::now(),
             version: 1,
             conflict_resolution: None,
+            sequence: None,
         },
     };
     
@@ -227,6 +236,94 @@ async fn test_change_filtering() {
     assert!(!matching_clients.contains(&client3));
 }
 
+#[tokio::test]
+async fn test_changes_are_scoped_for_feature_area_filters() {
+    let broadcaster = ChangeBroadcaster::new();
+    let mut receiver = broadcaster.subscribe_to_changes();
+    let payments_watcher = Uuid::new_v4();
+    broadcaster.subscribe(payments_watcher, vec![SyncFilters {
+        project_ids: Some(vec!["project1".to_string()]),
+        feature_areas: Some(vec!["payments".to_string()]),
+        ..SyncFilters::default()
+    }]).await.unwrap();
+
+    let event = |entity_id: &str, project_id: &str, change_type: ChangeType, entity: Option<serde_json::Value>| ChangeEvent {
+        entity_type: "business_rule".to_string(),
+        entity_id: entity_id.to_string(),
+        project_id: project_id.to_string(),
+        change_type,
+        old_value: None,
+        new_value: entity,
+        client_id: Uuid::nil(),
+        feature_area: None,
+    };
+
+    // The feature area comes from the rule's domain area and matches regardless of case
+    broadcaster
+        .broadcast_change(event("rule-1", "project1", ChangeType::Create, Some(json!({"domain_area": "Payments"}))))
+        .await
+        .unwrap();
+    let change = receiver.try_recv().unwrap();
+    assert_eq!(change.feature_area.as_deref(), Some("Payments"));
+    assert_eq!(broadcaster.find_matching_clients(&change).await, vec![payments_watcher]);
+
+    // A deletion reported without its project inherits the scope of the earlier change
+    broadcaster.broadcast_change(event("rule-1", "", ChangeType::Delete, None)).await.unwrap();
+    let change = receiver.try_recv().unwrap();
+    assert_eq!(change.project_id, "project1");
+    assert_eq!(change.feature_area.as_deref(), Some("Payments"));
+
+    // Changes in other areas are not broadcast at all
+    broadcaster
+        .broadcast_change(event("rule-2", "project1", ChangeType::Create, Some(json!({"domain_area": "onboarding"}))))
+        .await
+        .unwrap();
+    assert!(receiver.try_recv().is_err());
+
+    assert_eq!(
+        entity_feature_area("feature_context", &json!({"feature_name": "Checkout", "domain_area": "payments"})).as_deref(),
+        Some("Checkout")
+    );
+    assert_eq!(entity_feature_area("security_policy", &json!({"policy_area": " "})), None);
+}
+
+#[tokio::test]
+async fn test_logged_changes_are_numbered_and_replayable() {
+    use crate::infrastructure::SqliteChangeLogRepository;
+    use crate::repositories::ChangeLogRepository;
+    use std::sync::{Arc, Mutex};
+
+    let conn = crate::db::init::init_db(":memory:").unwrap();
+    let change_log = Arc::new(SqliteChangeLogRepository::new(Arc::new(Mutex::new(conn))));
+    let broadcaster = ChangeBroadcaster::new().with_change_log(change_log.clone());
+    assert!(ChangeBroadcaster::new().changes_since(0, 10).await.is_err());
+
+    // Changes are logged even while nobody is subscribed
+    let mut receiver = broadcaster.subscribe_to_changes();
+    for entity_id in ["rule-1", "rule-2", "rule-3"] {
+        broadcaster.broadcast_change(ChangeEvent {
+            entity_type: "business_rule".to_string(),
+            entity_id: entity_id.to_string(),
+            project_id: "project1".to_string(),
+            change_type: ChangeType::Create,
+            old_value: None,
+            new_value: Some(json!({"id": entity_id})),
+            client_id: Uuid::nil(),
+            feature_area: None,
+        }).await.unwrap();
+    }
+    assert!(receiver.try_recv().is_err());
+
+    let missed = broadcaster.changes_since(1, 10).await.unwrap();
+    let ids: Vec<_> = missed.iter().map(|change| change.entity_id.as_str()).collect();
+    assert_eq!(ids, vec!["rule-2", "rule-3"]);
+    assert_eq!(missed[0].metadata.sequence, Some(2));
+    assert_eq!(broadcaster.changes_since(0, 1).await.unwrap().len(), 1);
+
+    assert_eq!(change_log.delete_before(Utc::now() + chrono::Duration::seconds(1)).await.unwrap(), 3);
+    assert!(broadcaster.changes_since(0, 10).await.unwrap().is_empty());
+}
+
 #[tokio::test]
 async fn test_change_queue_management() {
     let broadcaster = ChangeBroadcaster::new();
@@ -250,6 +347,7 @@ async fn test_change_queue_management() {
             timestamp: Utc::now(),
             version: 1,
             conflict_resolution: None,
+            sequence: None,
         },
     };
     
@@ -337,4 +435,107 @@ async fn test_change_receiver() {
     assert_eq!(received_change.entity_type, "business_rule");
     assert_eq!(received_change.entity_id, "rule-1");
     assert_eq!(received_change.change_type, ChangeType::Create);
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_rapid_changes_are_coalesced_into_batches() {
+    // A long window, flushed by hand
+    let broadcaster = ChangeBroadcaster::new().with_batching(std::time::Duration::from_secs(3600), 2);
+    let mut batches = broadcaster.subscribe_to_batches();
+    let client_id = Uuid::new_v4();
+    broadcaster.subscribe(client_id, vec![SyncFilters::default()]).await.unwrap();
+
+    let event = |entity_id: &str, change_type: ChangeType, old_value: Option<serde_json::Value>, new_value: Option<serde_json::Value>| ChangeEvent {
+        entity_type: "business_rule".to_string(),
+        entity_id: entity_id.to_string(),
+        project_id: "test-project".to_string(),
+        change_type,
+        old_value,
+        new_value,
+        client_id,
+        feature_area: None,
+    };
+    let v1 = json!({"name": "Rule", "status": "draft"});
+    let v2 = json!({"name": "Rule", "status": "active"});
+    let v3 = json!({"name": "Rule v3", "status": "active"});
+    let events = vec![
+        event("rule-1", ChangeType::Create, None, Some(v1.clone())),
+        event("rule-2", ChangeType::Update, Some(v1.clone()), Some(v2.clone())),
+        event("rule-1", ChangeType::Update, Some(v1.clone()), Some(v2.clone())),
+        event("rule-2", ChangeType::Update, Some(v2.clone()), Some(v3.clone())),
+        event("rule-3", ChangeType::Create, None, Some(v1.clone())),
+        event("rule-3", ChangeType::Delete, Some(v1.clone()), None),
+        event("rule-4", ChangeType::Delete, Some(v1.clone()), None),
+    ];
+    for event in events {
+        broadcaster.broadcast_change(event).await.unwrap();
+    }
+    assert!(batches.try_recv().is_err(), "nothing is sent before the window closes");
+
+    broadcaster.flush_pending().await;
+    let first = batches.try_recv().unwrap();
+    let second = batches.try_recv().unwrap();
+    assert!(batches.try_recv().is_err());
+    assert_eq!(first.len(), 2);
+    assert_eq!(second.len(), 1);
+
+    // A create and the update after it arrive as a create of the latest version
+    assert_eq!(first[0].entity_id, "rule-1");
+    assert_eq!(first[0].change_type, ChangeType::Create);
+    assert_eq!(first[0].full_entity, Some(v2.clone()));
+    assert!(first[0].delta.is_none());
+
+    // Consecutive updates carry both patches, in order
+    assert_eq!(first[1].entity_id, "rule-2");
+    assert_eq!(first[1].change_type, ChangeType::Update);
+    let patch = json_patch::from_delta(first[1].delta.as_ref().unwrap()).unwrap();
+    assert_eq!(json_patch::apply(&v1, &patch).unwrap(), v3);
+
+    // rule-3 was created and deleted within the window
+    assert_eq!(second[0].entity_id, "rule-4");
+
+    let metrics = broadcaster.get_metrics();
+    assert_eq!(metrics.total_changes_broadcast.load(std::sync::atomic::Ordering::Relaxed), 7);
+    assert_eq!(metrics.coalesced_changes.load(std::sync::atomic::Ordering::Relaxed), 4);
+    assert_eq!(metrics.batches_sent.load(std::sync::atomic::Ordering::Relaxed), 2);
+    assert_eq!(metrics.largest_batch.load(std::sync::atomic::Ordering::Relaxed), 2);
+    assert_eq!(metrics.average_batch_size(), 1.5);
+}
+
+#[test]
+fn test_changes_after_a_delete_are_not_coalesced() {
+    let change = |change_type: ChangeType, full_entity: Option<serde_json::Value>| ContextChange {
+        change_id: Uuid::new_v4(),
+        change_type,
+        entity_type: "business_rule".to_string(),
+        entity_id: "rule-1".to_string(),
+        project_id: "test-project".to_string(),
+        feature_area: None,
+        delta: None,
+        full_entity,
+        metadata: ChangeMetadata {
+            user_id: None,
+            client_id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            version: 1,
+            conflict_resolution: None,
+            sequence: None,
+        },
+    };
+
+    let coalesced = coalesce_changes(vec![
+        change(ChangeType::Update, Some(json!({"name": "a"}))),
+        change(ChangeType::Delete, None),
+        change(ChangeType::Create, Some(json!({"name": "b"}))),
+        change(ChangeType::Update, Some(json!({"name": "c"}))),
+    ]);
+    let types: Vec<ChangeType> = coalesced.iter().map(|change| change.change_type.clone()).collect();
+    assert_eq!(types, vec![ChangeType::Delete, ChangeType::Create]);
+    assert_eq!(coalesced[1].full_entity, Some(json!({"name": "c"})));
+    // Without patches on both sides the combined delta is unknown
+    assert!(coalesce_changes(vec![
+        change(ChangeType::Update, Some(json!({"name": "a"}))),
+        change(ChangeType::Update, Some(json!({"name": "b"}))),
+    ])[0]
+        .delta
+        .is_none());
+}
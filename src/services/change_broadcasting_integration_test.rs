@@ -1,5 +1,6 @@
 use super::change_broadcaster::ChangeBroadcaster;
 use super::change_detection_service::ChangeDetectionService;
+use super::json_patch;
 use super::sync_engine::SyncEngine;
 use super::websocket_types::*;
 use chrono::Utc;
@@ -211,16 +212,23 @@ async fn test_delta_calculation_in_updates() {
     assert!(received_change.delta.is_some(), "Delta should be calculated for updates");
     
     let delta = received_change.delta.unwrap();
-    assert!(delta.get("old").is_some());
-    assert!(delta.get("new").is_some());
-    assert!(delta.get("changed_fields").is_some());
-    
-    let changed_fields = delta.get("changed_fields").unwrap().as_array().unwrap();
-    assert!(changed_fields.contains(&json!("name")));
-    assert!(changed_fields.contains(&json!("description")));
-    assert!(changed_fields.contains(&json!("priority")));
-    assert!(changed_fields.contains(&json!("category")));
-    assert!(changed_fields.contains(&json!("removed_status")));
+    let patch = json_patch::from_delta(&delta).expect("delta should be a JSON Patch");
+    let paths: Vec<&str> = patch.iter().map(|op| op.path()).collect();
+    assert_eq!(paths.len(), 5);
+    for path in ["/name", "/description", "/priority", "/category", "/status"] {
+        assert!(paths.contains(&path), "missing {}", path);
+    }
+    assert!(!paths.contains(&"/id"));
+
+    // Applying the patch to the old entity yields the full entity sent alongside it
+    let old_data = json!({
+        "id": "rule-1",
+        "name": "Old Rule Name",
+        "description": "Old description",
+        "priority": "high",
+        "status": "draft"
+    });
+    assert_eq!(json_patch::apply(&old_data, &patch).unwrap(), received_change.full_entity.unwrap());
 }
 
 #[tokio::test]
@@ -301,6 +309,7 @@ async fn test_change_queue_and_acknowledgment() {
             timestamp: Utc::now(),
             version: 1,
             conflict_resolution: None,
+            sequence: None,
         },
     };
     
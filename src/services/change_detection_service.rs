@@ -1,4 +1,5 @@
 use crate::services::change_broadcaster::{ChangeBroadcaster, ChangeEvent};
+use crate::services::embedding_queue::{EmbeddingJob, EmbeddingQueue};
 use crate::services::websocket_types::{ChangeType, ClientId};
 use anyhow::Result;
 use serde_json::Value;
@@ -10,12 +11,39 @@ use uuid::Uuid;
 #[derive(Clone)]
 pub struct ChangeDetectionService {
     broadcaster: Arc<ChangeBroadcaster>,
+    index_queue: Option<Arc<EmbeddingQueue>>,
 }
 
 impl ChangeDetectionService {
     /// Create a new change detection service
     pub fn new(broadcaster: Arc<ChangeBroadcaster>) -> Self {
-        Self { broadcaster }
+        Self {
+            broadcaster,
+            index_queue: None,
+        }
+    }
+
+    /// Also keep the search index in step with every create, update and delete
+    pub fn with_index_queue(mut self, index_queue: Arc<EmbeddingQueue>) -> Self {
+        self.index_queue = Some(index_queue);
+        self
+    }
+
+    /// Queue the embedding update for a changed entity; `None` means it was deleted
+    fn update_index(&self, entity_type: &str, entity_id: &str, entity_data: Option<&Value>) {
+        let Some(queue) = &self.index_queue else {
+            return;
+        };
+        match entity_data {
+            Some(data) => {
+                if let Some(job) = EmbeddingJob::from_entity(entity_type, data) {
+                    queue.enqueue(job);
+                }
+            }
+            None => queue.enqueue(EmbeddingJob::Remove {
+                entity_id: entity_id.to_string(),
+            }),
+        }
     }
 
     /// Notify about a context entity creation
@@ -29,6 +57,7 @@ impl ChangeDetectionService {
         feature_area: Option<String>,
     ) -> Result<()> {
         debug!("Notifying entity created: {}/{}", entity_type, entity_id);
+        self.update_index(entity_type, entity_id, Some(&entity_data));
 
         let change_event = ChangeEvent {
             entity_type: entity_type.to_string(),
@@ -57,6 +86,17 @@ impl ChangeDetectionService {
         feature_area: Option<String>,
     ) -> Result<()> {
         debug!("Notifying entity updated: {}/{}", entity_type, entity_id);
+        self.update_index(entity_type, entity_id, Some(&new_data));
+
+        // Callers that did not load the entity before updating it pass `Null`; the last
+        // broadcast state stands in, so the delta still names only the changed fields
+        let old_data = match old_data {
+            Value::Null => self
+                .broadcaster
+                .last_entity(entity_type, entity_id)
+                .unwrap_or(Value::Null),
+            old_data => old_data,
+        };
 
         let change_event = ChangeEvent {
             entity_type: entity_type.to_string(),
@@ -84,6 +124,7 @@ impl ChangeDetectionService {
         feature_area: Option<String>,
     ) -> Result<()> {
         debug!("Notifying entity deleted: {}/{}", entity_type, entity_id);
+        self.update_index(entity_type, entity_id, None);
 
         let change_event = ChangeEvent {
             entity_type: entity_type.to_string(),
@@ -299,6 +340,36 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_update_delta_falls_back_to_last_broadcast_entity() {
+        let broadcaster = Arc::new(ChangeBroadcaster::new());
+        let detector = ChangeDetectionService::new(broadcaster.clone());
+        let client_id = Uuid::new_v4();
+        broadcaster
+            .subscribe(client_id, vec![crate::services::websocket_types::SyncFilters::default()])
+            .await
+            .unwrap();
+        let mut changes = broadcaster.subscribe_to_changes();
+
+        let created = json!({"id": "rule-1", "name": "Rule", "priority": "high"});
+        detector
+            .notify_entity_created("business_rule", "rule-1", "p1", created, client_id, None)
+            .await
+            .unwrap();
+        let updated = json!({"id": "rule-1", "name": "Rule", "priority": "low"});
+        detector
+            .notify_entity_updated("business_rule", "rule-1", "p1", Value::Null, updated, client_id, None)
+            .await
+            .unwrap();
+
+        changes.recv().await.unwrap();
+        let update = changes.recv().await.unwrap();
+        assert_eq!(
+            update.delta,
+            Some(json!([{"op": "replace", "path": "/priority", "value": "low"}]))
+        );
+    }
+
     #[tokio::test]
     async fn test_notify_entity_deleted() {
         let broadcaster = Arc::new(ChangeBroadcaster::new());
@@ -349,4 +420,39 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_changes_update_index_queue() {
+        use crate::models::embedding::EmbeddingConfig;
+        use crate::repositories::embedding_repository::SqliteEmbeddingRepository;
+        use crate::services::embedding_service::SimpleEmbeddingService;
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        SqliteEmbeddingRepository::create_tables(&conn).unwrap();
+        let queue = Arc::new(EmbeddingQueue::new(
+            Arc::new(SimpleEmbeddingService::new(EmbeddingConfig::default())),
+            Arc::new(SqliteEmbeddingRepository::new(Arc::new(tokio::sync::Mutex::new(conn)))),
+        ));
+        let detector = ChangeDetectionService::new(Arc::new(ChangeBroadcaster::new()))
+            .with_index_queue(queue.clone());
+        let client_id = Uuid::new_v4();
+
+        let entity_data = json!({"id": "rule-1", "rule_name": "Passwords expire"});
+        detector
+            .notify_entity_created("business_rule", "rule-1", "p1", entity_data.clone(), client_id, None)
+            .await
+            .unwrap();
+        detector
+            .notify_entity_updated("business_rule", "rule-1", "p1", entity_data.clone(), entity_data, client_id, None)
+            .await
+            .unwrap();
+        assert_eq!(queue.status().pending, 1);
+
+        detector
+            .notify_entity_deleted("business_rule", "rule-1", "p1", json!({}), client_id, None)
+            .await
+            .unwrap();
+        assert_eq!(queue.status().pending, 1);
+        assert_eq!(queue.drain().await, 1);
+    }
 }
\ No newline at end of file
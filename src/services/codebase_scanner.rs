@@ -0,0 +1,600 @@
+use crate::models::architecture::ArchitectureRuleSet;
+use crate::models::framework::FrameworkComponent;
+use crate::repositories::ArchitectureRuleSetRepository;
+use crate::services::FrameworkService;
+use async_trait::async_trait;
+use regex::Regex;
+use rmcp::model::ErrorData as McpError;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+
+/// Value of `metadata.source` on components created by a scan
+pub const SCAN_SOURCE: &str = "scan_codebase";
+
+/// Directories never descended into
+pub(crate) const SKIPPED_DIRS: &[&str] = &["target", "node_modules", "build", "dist", "out", "vendor", "coverage", "tests", "test"];
+
+/// Layer assumed from directory names when the rule set's path patterns match nothing
+const LAYER_DIRECTORIES: &[(&str, &[&str])] = &[
+    ("presentation", &["presentation", "ui", "widgets", "pages", "screens", "views", "components"]),
+    ("domain", &["domain", "models", "entities", "services", "usecases", "use_cases"]),
+    ("data", &["data", "repositories", "infrastructure", "db", "database", "datasources"]),
+    ("core", &["core", "utils", "util", "shared", "common", "config"]),
+];
+
+/// Layer of files no layer could be inferred for
+pub const UNCLASSIFIED_LAYER: &str = "unclassified";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceLanguage {
+    Rust,
+    Dart,
+    TypeScript,
+}
+
+impl SourceLanguage {
+    fn of(path: &str) -> Option<Self> {
+        let skipped_suffixes = [".d.ts", ".test.ts", ".spec.ts", ".test.tsx", ".spec.tsx", "_test.dart", ".g.dart", ".freezed.dart"];
+        if skipped_suffixes.iter().any(|suffix| path.ends_with(suffix)) {
+            return None;
+        }
+        match Path::new(path).extension()?.to_str()? {
+            "rs" => Some(SourceLanguage::Rust),
+            "dart" => Some(SourceLanguage::Dart),
+            "ts" | "tsx" => Some(SourceLanguage::TypeScript),
+            _ => None,
+        }
+    }
+}
+
+/// A source file read as a framework component
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScannedComponent {
+    pub component_name: String,
+    pub component_type: String,
+    pub architecture_layer: String,
+    /// Relative to the scanned root, with `/` separators
+    pub file_path: String,
+    pub language: SourceLanguage,
+    /// Files of the repository the component imports, relative to the root, followed by
+    /// external imports as written (`std::process::Command`, `package:http/http.dart`, `react`)
+    pub dependencies: Vec<String>,
+}
+
+/// What a scan created, updated and left alone
+#[derive(Debug, Clone, Serialize)]
+pub struct CodebaseScanReport {
+    pub project_id: String,
+    pub root: String,
+    pub files_scanned: usize,
+    pub created: Vec<ScannedComponent>,
+    pub updated: Vec<ScannedComponent>,
+    pub unchanged: usize,
+    /// Files whose layer could not be inferred from the architecture rules or directory names
+    pub unclassified: Vec<String>,
+    /// Components from an earlier scan whose file no longer declares them
+    pub stale: Vec<String>,
+    pub dry_run: bool,
+}
+
+/// Reads Rust, Dart and TypeScript sources with regular expressions: one component per file,
+/// named after its first public type, with the layer taken from the architecture rules
+pub struct CodebaseScanner {
+    root: PathBuf,
+    rules: ArchitectureRuleSet,
+    rust_type: Regex,
+    rust_use: Regex,
+    dart_type: Regex,
+    dart_import: Regex,
+    ts_type: Regex,
+    ts_import: Regex,
+}
+
+impl CodebaseScanner {
+    pub fn new(root: impl Into<PathBuf>, rules: ArchitectureRuleSet) -> Self {
+        Self {
+            root: root.into(),
+            rules,
+            rust_type: Regex::new(r"(?m)^\s*pub(?:\([^)]*\))?\s+(?:struct|trait|enum)\s+([A-Za-z_][A-Za-z0-9_]*)").unwrap(),
+            rust_use: Regex::new(r"(?ms)^\s*(?:pub(?:\([^)]*\))?\s+)?use\s+([^;]+);").unwrap(),
+            dart_type: Regex::new(r"(?m)^\s*(?:abstract\s+|sealed\s+|base\s+|final\s+)*(?:class|mixin|enum)\s+([A-Za-z_][A-Za-z0-9_]*)(?:[^{\n]*\bextends\s+(\w+))?").unwrap(),
+            dart_import: Regex::new(r#"(?m)^\s*import\s+['"]([^'"]+)['"]"#).unwrap(),
+            ts_type: Regex::new(r"(?m)^\s*export\s+(?:default\s+)?(?:abstract\s+)?(?:class|interface|function|const|enum)\s+([A-Za-z_$][A-Za-z0-9_$]*)").unwrap(),
+            ts_import: Regex::new(r#"(?m)(?:^\s*import\s+(?:[^'";]*?\s+from\s+)?|^\s*export\s+[^'";]*?\s+from\s+|\brequire\()['"]([^'"]+)['"]"#).unwrap(),
+        }
+    }
+
+    /// Source files under the root, relative and sorted, skipping hidden, build and test directories
+    pub async fn source_files(&self) -> Result<Vec<String>, McpError> {
+        let mut files = Vec::new();
+        let mut pending = vec![self.root.clone()];
+        while let Some(dir) = pending.pop() {
+            let mut entries = fs::read_dir(&dir)
+                .await
+                .map_err(|e| McpError::invalid_params(format!("Cannot read {}: {}", dir.display(), e), None))?;
+            while let Some(entry) = entries
+                .next_entry()
+                .await
+                .map_err(|e| McpError::internal_error(format!("Cannot read {}: {}", dir.display(), e), None))?
+            {
+                let path = entry.path();
+                let name = entry.file_name().to_string_lossy().to_string();
+                let Ok(file_type) = entry.file_type().await else {
+                    continue;
+                };
+                if file_type.is_dir() {
+                    if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str()) {
+                        pending.push(path);
+                    }
+                } else if let Some(relative) = self.relative(&path) {
+                    if SourceLanguage::of(&relative).is_some() {
+                        files.push(relative);
+                    }
+                }
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    /// Reads every source file; files declaring no type are not components
+    pub async fn scan(&self) -> Result<(usize, Vec<ScannedComponent>), McpError> {
+        let files = self.source_files().await?;
+        let known: HashSet<String> = files.iter().cloned().collect();
+        let dart_package = fs::read_to_string(self.root.join("pubspec.yaml"))
+            .await
+            .ok()
+            .and_then(|pubspec| {
+                pubspec
+                    .lines()
+                    .find_map(|line| line.strip_prefix("name:").map(|name| name.trim().to_string()))
+            });
+
+        let mut components = Vec::new();
+        for file in &files {
+            let Ok(content) = fs::read_to_string(self.root.join(file)).await else {
+                continue;
+            };
+            if let Some(component) = self.parse_file(file, &content, &known, dart_package.as_deref()) {
+                components.push(component);
+            }
+        }
+        Ok((files.len(), components))
+    }
+
+    pub fn parse_file(&self, file: &str, content: &str, known: &HashSet<String>, dart_package: Option<&str>) -> Option<ScannedComponent> {
+        let language = SourceLanguage::of(file)?;
+        let (name, extends, dependencies) = match language {
+            SourceLanguage::Rust => {
+                let name = self.rust_type.captures(content)?[1].to_string();
+                (name, None, self.rust_dependencies(file, content, known))
+            }
+            SourceLanguage::Dart => {
+                let captures = self.dart_type.captures(content)?;
+                let extends = captures.get(2).map(|m| m.as_str().to_string());
+                (captures[1].to_string(), extends, self.dart_dependencies(file, content, known, dart_package))
+            }
+            SourceLanguage::TypeScript => {
+                let name = self.ts_type.captures(content)?[1].to_string();
+                (name, None, self.ts_dependencies(file, content, known))
+            }
+        };
+
+        Some(ScannedComponent {
+            component_type: Self::component_type(&name, file, extends.as_deref()),
+            architecture_layer: self.layer(file),
+            component_name: name,
+            file_path: file.to_string(),
+            language,
+            dependencies,
+        })
+    }
+
+    /// The first layer whose path patterns the file matches, else one named like a directory
+    /// of the path
+    pub fn layer(&self, file: &str) -> String {
+        if let Some(layer) = self.rules.layers_of_dependency(file).first() {
+            return layer.name.clone();
+        }
+        let directories: Vec<String> = file.to_lowercase().split('/').rev().skip(1).map(str::to_string).collect();
+        for directory in &directories {
+            for (layer, names) in LAYER_DIRECTORIES {
+                if names.contains(&directory.as_str()) {
+                    if let Some(rule) = self.rules.layer(layer) {
+                        return rule.name.clone();
+                    }
+                }
+            }
+        }
+        UNCLASSIFIED_LAYER.to_string()
+    }
+
+    fn component_type(name: &str, file: &str, extends: Option<&str>) -> String {
+        if extends.is_some_and(|base| base.ends_with("Widget") || base == "State") {
+            return "widget".to_string();
+        }
+        let name = name.to_lowercase();
+        let file = file.to_lowercase();
+        let by_suffix = [
+            ("repository", "repository"),
+            ("repo", "repository"),
+            ("service", "service"),
+            ("controller", "controller"),
+            ("handler", "controller"),
+            ("provider", "provider"),
+            ("notifier", "provider"),
+            ("bloc", "provider"),
+            ("widget", "widget"),
+            ("page", "view"),
+            ("screen", "view"),
+            ("view", "view"),
+            ("model", "model"),
+            ("entity", "model"),
+            ("dto", "model"),
+            ("util", "utility"),
+            ("utils", "utility"),
+            ("helper", "utility"),
+        ];
+        by_suffix
+            .iter()
+            .find(|(suffix, _)| name.ends_with(suffix))
+            .or_else(|| by_suffix.iter().find(|(suffix, _)| file.contains(&format!("/{}s/", suffix))))
+            .map(|(_, component_type)| component_type.to_string())
+            .unwrap_or_else(|| "module".to_string())
+    }
+
+    fn rust_dependencies(&self, file: &str, content: &str, known: &HashSet<String>) -> Vec<String> {
+        // The crate's source root, e.g. `src` or `crates/core/src`
+        let crate_root = file.rfind("src/").map(|i| &file[..i + 3]).unwrap_or("src");
+        let module_dir = Self::rust_module_dir(file);
+        let mut dependencies = BTreeSet::new();
+        for captures in self.rust_use.captures_iter(content) {
+            for path in Self::expand_use(&captures[1].split_whitespace().collect::<Vec<_>>().join(" ")) {
+                let segments: Vec<&str> = path.split("::").filter(|s| !s.is_empty() && *s != "*" && *s != "self").collect();
+                let (base, rest) = match segments.first() {
+                    Some(&"crate") => (crate_root.to_string(), &segments[1..]),
+                    Some(&"super") => {
+                        let ups = segments.iter().take_while(|s| **s == "super").count();
+                        let mut base = module_dir.clone();
+                        for _ in 0..ups {
+                            base = base.rsplit_once('/').map(|(parent, _)| parent.to_string()).unwrap_or_default();
+                        }
+                        (base, &segments[ups..])
+                    }
+                    Some(_) => {
+                        dependencies.insert(path.trim_end_matches("::*").to_string());
+                        continue;
+                    }
+                    None => continue,
+                };
+                if let Some(found) = Self::resolve_rust_module(&base, rest, known) {
+                    if found != file {
+                        dependencies.insert(found);
+                    }
+                }
+            }
+        }
+        dependencies.into_iter().collect()
+    }
+
+    /// Directory a file's child modules live in: `src/a/b.rs` → `src/a/b`, `src/a/mod.rs` → `src/a`
+    fn rust_module_dir(file: &str) -> String {
+        let (dir, name) = file.rsplit_once('/').unwrap_or(("", file));
+        match name {
+            "mod.rs" | "lib.rs" | "main.rs" => dir.to_string(),
+            _ => format!("{}/{}", dir, name.trim_end_matches(".rs")),
+        }
+    }
+
+    /// Longest module path that is a file: `a/b/C` tries `a/b/C.rs`, `a/b/C/mod.rs`, `a/b.rs`, …
+    fn resolve_rust_module(base: &str, segments: &[&str], known: &HashSet<String>) -> Option<String> {
+        (1..=segments.len()).rev().find_map(|len| {
+            let module = format!("{}/{}", base, segments[..len].join("/"));
+            [format!("{}.rs", module), format!("{}/mod.rs", module)]
+                .into_iter()
+                .find(|candidate| known.contains(candidate))
+        })
+    }
+
+    /// `a::{b, c::{D, E}}` → `a::b`, `a::c::D`, `a::c::E`; aliases are dropped
+    fn expand_use(tree: &str) -> Vec<String> {
+        let tree = tree.trim().trim_start_matches("::");
+        let Some(open) = tree.find('{') else {
+            let path = tree.split(" as ").next().unwrap_or(tree);
+            return vec![path.replace(' ', "")];
+        };
+        let prefix = &tree[..open];
+        let inner = tree[open + 1..].strip_suffix('}').unwrap_or(&tree[open + 1..]);
+        let mut items = Vec::new();
+        let (mut depth, mut start) = (0, 0);
+        for (i, c) in inner.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                ',' if depth == 0 => {
+                    items.push(&inner[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        items.push(&inner[start..]);
+        items
+            .into_iter()
+            .filter(|item| !item.trim().is_empty())
+            .flat_map(|item| Self::expand_use(&format!("{}{}", prefix, item.trim())))
+            .collect()
+    }
+
+    fn dart_dependencies(&self, file: &str, content: &str, known: &HashSet<String>, dart_package: Option<&str>) -> Vec<String> {
+        let dir = file.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+        let own_package = dart_package.map(|name| format!("package:{}/", name));
+        let mut dependencies = BTreeSet::new();
+        for captures in self.dart_import.captures_iter(content) {
+            let import = &captures[1];
+            let resolved = match &own_package {
+                Some(prefix) if import.starts_with(prefix.as_str()) => Some(format!("lib/{}", &import[prefix.len()..])),
+                _ if import.contains(':') => None,
+                _ => Some(Self::join(dir, import)),
+            };
+            dependencies.insert(resolved.filter(|path| known.contains(path)).unwrap_or_else(|| import.to_string()));
+        }
+        dependencies.into_iter().collect()
+    }
+
+    fn ts_dependencies(&self, file: &str, content: &str, known: &HashSet<String>) -> Vec<String> {
+        let dir = file.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+        let mut dependencies = BTreeSet::new();
+        for captures in self.ts_import.captures_iter(content) {
+            let import = &captures[1];
+            if !import.starts_with('.') {
+                dependencies.insert(import.to_string());
+                continue;
+            }
+            let base = Self::join(dir, import);
+            let resolved = ["", ".ts", ".tsx", "/index.ts", "/index.tsx"]
+                .iter()
+                .map(|suffix| format!("{}{}", base, suffix))
+                .find(|candidate| known.contains(candidate));
+            dependencies.insert(resolved.unwrap_or(base));
+        }
+        dependencies.into_iter().collect()
+    }
+
+    /// Joins a relative import onto a directory, resolving `.` and `..`
+    fn join(dir: &str, relative: &str) -> String {
+        let mut parts: Vec<&str> = dir.split('/').filter(|p| !p.is_empty()).collect();
+        for part in relative.split('/') {
+            match part {
+                "" | "." => {}
+                ".." => {
+                    parts.pop();
+                }
+                _ => parts.push(part),
+            }
+        }
+        parts.join("/")
+    }
+
+    fn relative(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        Some(relative.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/"))
+    }
+}
+
+/// Keeps framework components in step with the source code of a repository
+#[async_trait]
+pub trait CodebaseScanService: Send + Sync {
+    /// Creates a component for each new source file that declares a type and updates the
+    /// components of files scanned before; with `dry_run` nothing is written
+    async fn scan_codebase(&self, project_id: &str, root: &Path, dry_run: bool) -> Result<CodebaseScanReport, McpError>;
+}
+
+pub struct DefaultCodebaseScanService {
+    framework_service: Arc<dyn FrameworkService>,
+    rule_sets: Arc<dyn ArchitectureRuleSetRepository>,
+}
+
+impl DefaultCodebaseScanService {
+    pub fn new(framework_service: Arc<dyn FrameworkService>, rule_sets: Arc<dyn ArchitectureRuleSetRepository>) -> Self {
+        Self {
+            framework_service,
+            rule_sets,
+        }
+    }
+
+    fn scanned(component: &FrameworkComponent) -> bool {
+        component
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("source"))
+            .and_then(|source| source.as_str())
+            == Some(SCAN_SOURCE)
+    }
+
+    /// The stored component updated from a scan. Components created by hand only take the
+    /// file path and dependencies; their name, type and layer stay as they were.
+    fn merge(existing: &FrameworkComponent, scanned: &ScannedComponent) -> FrameworkComponent {
+        let mut component = existing.clone();
+        component.file_path = Some(scanned.file_path.clone());
+        component.dependencies = scanned.dependencies.clone();
+        if Self::scanned(existing) {
+            component.component_name = scanned.component_name.clone();
+            component.component_type = scanned.component_type.clone();
+            component.architecture_layer = scanned.architecture_layer.clone();
+            component.metadata = Some(Self::metadata(scanned));
+        }
+        component
+    }
+
+    fn metadata(scanned: &ScannedComponent) -> serde_json::Value {
+        serde_json::json!({ "source": SCAN_SOURCE, "language": scanned.language })
+    }
+}
+
+#[async_trait]
+impl CodebaseScanService for DefaultCodebaseScanService {
+    async fn scan_codebase(&self, project_id: &str, root: &Path, dry_run: bool) -> Result<CodebaseScanReport, McpError> {
+        let rules = self
+            .rule_sets
+            .find_by_project_id(project_id)
+            .await?
+            .unwrap_or_else(|| ArchitectureRuleSet::clean_architecture(project_id));
+        let (files_scanned, scanned) = CodebaseScanner::new(root, rules).scan().await?;
+        let existing = self.framework_service.list_components(project_id).await?;
+
+        let mut report = CodebaseScanReport {
+            project_id: project_id.to_string(),
+            root: root.display().to_string(),
+            files_scanned,
+            created: Vec::new(),
+            updated: Vec::new(),
+            unchanged: 0,
+            unclassified: scanned
+                .iter()
+                .filter(|component| component.architecture_layer == UNCLASSIFIED_LAYER)
+                .map(|component| component.file_path.clone())
+                .collect(),
+            stale: Vec::new(),
+            dry_run,
+        };
+
+        let mut matched = HashSet::new();
+        for component in scanned {
+            // Components are matched by file, or by name when they have no file yet
+            let stored = existing
+                .iter()
+                .find(|c| c.file_path.as_deref() == Some(component.file_path.as_str()))
+                .or_else(|| existing.iter().find(|c| c.file_path.is_none() && c.component_name == component.component_name));
+            match stored {
+                Some(stored) => {
+                    matched.insert(stored.id.clone());
+                    let merged = Self::merge(stored, &component);
+                    let changed = merged.file_path != stored.file_path
+                        || merged.dependencies != stored.dependencies
+                        || merged.component_name != stored.component_name
+                        || merged.component_type != stored.component_type
+                        || merged.architecture_layer != stored.architecture_layer;
+                    if !changed {
+                        report.unchanged += 1;
+                        continue;
+                    }
+                    if !dry_run {
+                        self.framework_service.update_component(&merged).await?;
+                    }
+                    report.updated.push(component);
+                }
+                None => {
+                    if !dry_run {
+                        let mut created = self
+                            .framework_service
+                            .create_component(
+                                project_id,
+                                &component.component_name,
+                                &component.component_type,
+                                &component.architecture_layer,
+                                Some(&component.file_path),
+                                Some(Self::metadata(&component)),
+                            )
+                            .await?;
+                        created.dependencies = component.dependencies.clone();
+                        self.framework_service.update_component(&created).await?;
+                    }
+                    report.created.push(component);
+                }
+            }
+        }
+
+        report.stale = existing
+            .iter()
+            .filter(|component| Self::scanned(component) && !matched.contains(&component.id))
+            .map(|component| component.component_name.clone())
+            .collect();
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::architecture::LayerRule;
+
+    fn scanner() -> CodebaseScanner {
+        CodebaseScanner::new("/repo", ArchitectureRuleSet::clean_architecture("p1"))
+    }
+
+    fn known(files: &[&str]) -> HashSet<String> {
+        files.iter().map(|f| f.to_string()).collect()
+    }
+
+    #[test]
+    fn test_rust_file() {
+        let files = known(&["src/services/user_service.rs", "src/repositories/mod.rs", "src/repositories/user_repository.rs", "src/models/user.rs"]);
+        let content = r#"
+use crate::models::user::User;
+use crate::repositories::{UserRepository, user_repository::SqliteUserRepository};
+use super::helpers::*;
+use std::process::{Command as Cmd, Stdio};
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
+/// Users
+pub struct UserService {
+    repository: Arc<dyn UserRepository>,
+}
+"#;
+        let component = scanner().parse_file("src/services/user_service.rs", content, &files, None).unwrap();
+        assert_eq!(component.component_name, "UserService");
+        assert_eq!(component.component_type, "service");
+        assert_eq!(component.architecture_layer, "domain");
+        assert_eq!(
+            component.dependencies,
+            vec![
+                "src/models/user.rs",
+                "src/repositories/mod.rs",
+                "src/repositories/user_repository.rs",
+                "std::collections::HashMap",
+                "std::collections::HashSet",
+                "std::process::Command",
+                "std::process::Stdio",
+            ]
+        );
+        assert!(scanner().parse_file("src/lib.rs", "pub mod services;\n", &files, None).is_none());
+    }
+
+    #[test]
+    fn test_dart_and_typescript_files() {
+        let files = known(&["lib/data/api_client.dart", "lib/presentation/login_page.dart", "web/src/api/client.ts", "web/src/ui/index.ts"]);
+        let dart = "import 'package:flutter/material.dart';\nimport 'package:shop/data/api_client.dart';\n\nclass LoginPage extends StatelessWidget {\n}\n";
+        let page = scanner().parse_file("lib/presentation/login_page.dart", dart, &files, Some("shop")).unwrap();
+        assert_eq!(page.component_type, "widget");
+        assert_eq!(page.architecture_layer, "presentation");
+        assert_eq!(page.dependencies, vec!["lib/data/api_client.dart", "package:flutter/material.dart"]);
+
+        let ts = "import React from 'react';\nimport { get } from '../api/client';\nexport * from '../ui';\n\nexport function CartView() {}\n";
+        let view = scanner().parse_file("web/src/cart/cart_view.tsx", ts, &files, None).unwrap();
+        assert_eq!(view.component_name, "CartView");
+        assert_eq!(view.component_type, "view");
+        assert_eq!(view.dependencies, vec!["react", "web/src/api/client.ts", "web/src/ui/index.ts"]);
+        assert_eq!(view.architecture_layer, UNCLASSIFIED_LAYER);
+    }
+
+    #[test]
+    fn test_layers_follow_the_rule_set() {
+        let mut rules = ArchitectureRuleSet::clean_architecture("p1");
+        rules.layers = vec![
+            LayerRule { name: "Handlers".to_string(), path_patterns: vec!["src/api/".to_string()], may_depend_on: None, severity: None },
+            LayerRule { name: "Data".to_string(), path_patterns: Vec::new(), may_depend_on: None, severity: None },
+        ];
+        let scanner = CodebaseScanner::new("/repo", rules);
+        assert_eq!(scanner.layer("src/api/routes.rs"), "Handlers");
+        assert_eq!(scanner.layer("src/repositories/user.rs"), "Data");
+        assert_eq!(scanner.layer("src/services/user.rs"), UNCLASSIFIED_LAYER);
+    }
+}
@@ -1,10 +1,16 @@
+use crate::config::{ConflictSettings, ResolutionStrategy};
 use crate::models::enhanced_context::{EnhancedContextItem, ContextId, ProjectId};
-use crate::services::websocket_types::{ContextChange, ConflictStrategy, ConflictResolution, ChangeMetadata, ClientId};
+use crate::repositories::{ChangeLogRepository, ConflictRepository};
+use crate::services::json_patch;
+use crate::services::sync_engine::merge_text_field;
+use crate::services::websocket_types::{ChangeType, ContextChange, ConflictStrategy, ConflictResolution, ChangeMetadata, ClientId};
 use anyhow::{Result, anyhow};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::{debug, warn, error};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::{debug, warn};
 use uuid::Uuid;
 
 /// Conflict resolution engine for handling concurrent context modifications
@@ -14,10 +20,42 @@ pub struct ConflictResolutionEngine {
     active_conflicts: HashMap<String, ConflictInfo>,
     /// Configuration for conflict resolution strategies
     config: ConflictResolutionConfig,
+    /// Durable copy of every conflict, shared with other engines on the same database
+    repository: Option<Arc<dyn ConflictRepository>>,
+    /// Earlier changes, from which auto-merge takes the common base of conflicting edits
+    change_log: Option<Arc<dyn ChangeLogRepository>>,
+    /// Shared by clones of the engine
+    metrics: Arc<ConflictMetrics>,
+}
+
+/// Conflicts detected and how they ended, since the server started
+#[derive(Debug, Default)]
+pub struct ConflictMetrics {
+    pub conflicts_detected: AtomicU64,
+    pub resolved_last_writer_wins: AtomicU64,
+    pub resolved_auto_merge: AtomicU64,
+    pub resolved_reject: AtomicU64,
+    /// Resolved by a person through manual resolution
+    pub resolved_manually: AtomicU64,
+    /// Left open for a person because of policy, overlapping edits or a doubtful merge
+    pub escalated_to_manual: AtomicU64,
+}
+
+impl ConflictMetrics {
+    fn record_resolution(&self, strategy: &ConflictStrategy) {
+        let counter = match strategy {
+            ConflictStrategy::LastWriterWins => &self.resolved_last_writer_wins,
+            ConflictStrategy::AutoMerge => &self.resolved_auto_merge,
+            ConflictStrategy::Reject => &self.resolved_reject,
+            ConflictStrategy::ManualResolution => &self.resolved_manually,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
 }
 
 /// Configuration for conflict resolution behavior
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ConflictResolutionConfig {
     /// Default strategy to use when no specific strategy is configured
     pub default_strategy: ConflictStrategy,
@@ -29,6 +67,23 @@ pub struct ConflictResolutionConfig {
     pub auto_detect_content_conflicts: bool,
     /// Minimum time between changes to consider them concurrent (in seconds)
     pub concurrent_change_threshold_seconds: u64,
+    /// Most concurrent changes for which auto-merge is recommended
+    pub max_auto_merge_changes: usize,
+    /// Auto-merges less confident than this are left for manual resolution
+    pub min_merge_confidence: f64,
+    /// String fields whose overlapping edits auto-merge combines as text
+    pub text_fields: Vec<String>,
+    /// Overrides per entity type
+    pub policies: HashMap<String, ConflictPolicy>,
+}
+
+/// How conflicts on one entity type are resolved; unset fields keep the engine-wide values
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConflictPolicy {
+    /// Strategy for every conflict, in place of the recommendation by conflict type
+    pub strategy: Option<ConflictStrategy>,
+    pub max_auto_merge_changes: Option<usize>,
+    pub min_merge_confidence: Option<f64>,
 }
 
 impl Default for ConflictResolutionConfig {
@@ -39,10 +94,77 @@ impl Default for ConflictResolutionConfig {
             auto_detect_version_conflicts: true,
             auto_detect_content_conflicts: true,
             concurrent_change_threshold_seconds: 30, // 30 seconds
+            max_auto_merge_changes: 2,
+            min_merge_confidence: 0.0,
+            text_fields: Vec::new(),
+            policies: HashMap::new(),
         }
     }
 }
 
+impl ConflictResolutionConfig {
+    /// Defaults with the thresholds and policies of `[conflicts]`
+    pub fn from_settings(settings: &ConflictSettings) -> Self {
+        let policies = settings
+            .policies
+            .iter()
+            .map(|(entity_type, policy)| {
+                let policy = ConflictPolicy {
+                    strategy: policy.strategy.map(|strategy| match strategy {
+                        ResolutionStrategy::LastWriterWins => ConflictStrategy::LastWriterWins,
+                        ResolutionStrategy::AutoMerge => ConflictStrategy::AutoMerge,
+                        ResolutionStrategy::ManualResolution => ConflictStrategy::ManualResolution,
+                        ResolutionStrategy::Reject => ConflictStrategy::Reject,
+                    }),
+                    max_auto_merge_changes: policy.max_auto_merge_changes,
+                    min_merge_confidence: policy.min_merge_confidence,
+                };
+                (entity_type.clone(), policy)
+            })
+            .collect();
+        Self {
+            max_auto_merge_changes: settings.max_auto_merge_changes,
+            min_merge_confidence: settings.min_merge_confidence,
+            text_fields: settings.text_fields.clone(),
+            policies,
+            ..Self::default()
+        }
+    }
+
+    /// The strategy for a conflict: its entity type's policy strategy when there is one,
+    /// otherwise chosen by conflict type, with auto-merge only for a few changes
+    pub fn recommend_strategy(&self, conflict_info: &ConflictInfo) -> ConflictStrategy {
+        let policy = self.policies.get(&conflict_info.entity_type);
+        if let Some(strategy) = policy.and_then(|policy| policy.strategy.clone()) {
+            return strategy;
+        }
+        match conflict_info.conflict_type {
+            ConflictType::VersionConflict => ConflictStrategy::LastWriterWins,
+            ConflictType::ContentConflict => {
+                let max_changes = policy
+                    .and_then(|policy| policy.max_auto_merge_changes)
+                    .unwrap_or(self.max_auto_merge_changes);
+                if conflict_info.conflicting_changes.len() <= max_changes {
+                    ConflictStrategy::AutoMerge
+                } else {
+                    ConflictStrategy::ManualResolution
+                }
+            }
+            ConflictType::SemanticConflict | ConflictType::DependencyConflict => {
+                ConflictStrategy::ManualResolution
+            }
+        }
+    }
+
+    /// Confidence an auto-merge of `entity_type` needs to resolve a conflict
+    pub fn min_merge_confidence(&self, entity_type: &str) -> f64 {
+        self.policies
+            .get(entity_type)
+            .and_then(|policy| policy.min_merge_confidence)
+            .unwrap_or(self.min_merge_confidence)
+    }
+}
+
 /// Information about a detected conflict
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConflictInfo {
@@ -107,6 +229,9 @@ pub struct MergeDetails {
     pub conflicts_resolved: u32,
     pub manual_interventions: u32,
     pub confidence_score: f64,
+    /// Fields both sides edited differently, which need a manual decision
+    #[serde(default)]
+    pub overlapping_paths: Vec<String>,
 }
 
 /// Request for manual conflict resolution
@@ -122,10 +247,7 @@ pub struct ManualResolutionRequest {
 impl ConflictResolutionEngine {
     /// Create a new conflict resolution engine
     pub fn new() -> Self {
-        Self {
-            active_conflicts: HashMap::new(),
-            config: ConflictResolutionConfig::default(),
-        }
+        Self::with_config(ConflictResolutionConfig::default())
     }
 
     /// Create a new conflict resolution engine with custom configuration
@@ -133,9 +255,29 @@ impl ConflictResolutionEngine {
         Self {
             active_conflicts: HashMap::new(),
             config,
+            repository: None,
+            change_log: None,
+            metrics: Arc::new(ConflictMetrics::default()),
         }
     }
 
+    /// Persist conflicts in `repository`, which then holds the current state of each one
+    pub fn with_repository(mut self, repository: Arc<dyn ConflictRepository>) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Auto-merge three ways, against the version the conflicting changes started from
+    pub fn with_change_log(mut self, change_log: Arc<dyn ChangeLogRepository>) -> Self {
+        self.change_log = Some(change_log);
+        self
+    }
+
+    /// Counters of detected and resolved conflicts
+    pub fn metrics(&self) -> Arc<ConflictMetrics> {
+        self.metrics.clone()
+    }
+
     /// Detect conflicts between concurrent changes
     pub async fn detect_conflict(
         &mut self,
@@ -163,6 +305,8 @@ impl ConflictResolutionEngine {
         if conflicts.is_empty() && self.config.auto_detect_content_conflicts {
             if let Some(content_conflict) = self.detect_content_conflict(incoming_change, recent_changes)? {
                 conflicts.push(content_conflict);
+                // Both sides, so a merge sees each edit
+                conflicts.push(Self::conflicting_change(incoming_change));
                 detected_conflict_type = ConflictType::ContentConflict;
             }
         }
@@ -194,12 +338,42 @@ impl ConflictResolutionEngine {
         };
 
         // Store the conflict
-        self.active_conflicts.insert(conflict_id.clone(), conflict_info.clone());
+        self.store_conflict(conflict_info.clone()).await?;
+        self.metrics.conflicts_detected.fetch_add(1, Ordering::Relaxed);
 
         debug!("Conflict detected: {}", conflict_id);
         Ok(Some(conflict_info))
     }
 
+    /// Record a conflict found outside change detection, such as a file edited while the
+    /// copy imported from it was edited too; `recent` is the stored side, `incoming` the
+    /// change that would replace it
+    pub async fn record_conflict(
+        &mut self,
+        conflict_type: ConflictType,
+        recent: &ContextChange,
+        incoming: &ContextChange,
+    ) -> Result<ConflictInfo> {
+        let conflict_info = ConflictInfo {
+            conflict_id: Uuid::new_v4().to_string(),
+            entity_type: incoming.entity_type.clone(),
+            entity_id: incoming.entity_id.clone(),
+            project_id: incoming.project_id.clone(),
+            conflicting_changes: vec![Self::conflicting_change(recent), Self::conflicting_change(incoming)],
+            conflict_type,
+            detected_at: Utc::now(),
+            resolution_strategy: None,
+            resolved_at: None,
+            resolved_by: None,
+            resolution_result: None,
+        };
+
+        self.store_conflict(conflict_info.clone()).await?;
+        self.metrics.conflicts_detected.fetch_add(1, Ordering::Relaxed);
+        debug!("Conflict recorded: {}", conflict_info.conflict_id);
+        Ok(conflict_info)
+    }
+
     /// Resolve a conflict using the specified strategy
     pub async fn resolve_conflict(
         &mut self,
@@ -207,10 +381,9 @@ impl ConflictResolutionEngine {
         strategy: ConflictStrategy,
         resolver: Option<String>,
     ) -> Result<ConflictResolutionResult> {
-        let mut conflict = self.active_conflicts
-            .get(conflict_id)
-            .ok_or_else(|| anyhow!("Conflict not found: {}", conflict_id))?
-            .clone();
+        let mut conflict = self.find_conflict(conflict_id)
+            .await?
+            .ok_or_else(|| anyhow!("Conflict not found: {}", conflict_id))?;
 
         debug!("Resolving conflict {} using strategy {:?}", conflict_id, strategy);
 
@@ -229,6 +402,32 @@ impl ConflictResolutionEngine {
             }
         };
 
+        let min_confidence = self.config.min_merge_confidence(&conflict.entity_type);
+        let below_confidence = resolution_result.strategy_used == ConflictStrategy::AutoMerge
+            && resolution_result
+                .merge_details
+                .as_ref()
+                .is_some_and(|details| details.confidence_score < min_confidence);
+        let resolution_result = if below_confidence {
+            ConflictResolutionResult {
+                strategy_used: ConflictStrategy::ManualResolution,
+                resolved_entity: None,
+                resolution_notes: Some(format!(
+                    "Auto-merge is less confident than the {} required for {}; resolve manually",
+                    min_confidence, conflict.entity_type
+                )),
+                ..resolution_result
+            }
+        } else {
+            resolution_result
+        };
+
+        // Auto-merge hands overlapping or doubtful merges back for a person to resolve
+        if resolution_result.strategy_used == ConflictStrategy::ManualResolution {
+            self.leave_for_manual_resolution(conflict, &resolution_result).await?;
+            return Ok(resolution_result);
+        }
+
         // Update conflict info
         conflict.resolution_strategy = Some(strategy);
         conflict.resolved_at = Some(Utc::now());
@@ -236,21 +435,71 @@ impl ConflictResolutionEngine {
         conflict.resolution_result = Some(resolution_result.clone());
 
         // Store updated conflict
-        self.active_conflicts.insert(conflict_id.to_string(), conflict);
+        self.store_conflict(conflict).await?;
+        self.metrics.record_resolution(&resolution_result.strategy_used);
 
         debug!("Conflict {} resolved successfully", conflict_id);
         Ok(resolution_result)
     }
 
+    /// Resolve a conflict with the strategy recommended for it. When that is manual
+    /// resolution, the conflict is left open and marked for a person to resolve.
+    pub async fn resolve_by_policy(
+        &mut self,
+        conflict_id: &str,
+        resolver: Option<String>,
+    ) -> Result<ConflictResolutionResult> {
+        let conflict = self.find_conflict(conflict_id)
+            .await?
+            .ok_or_else(|| anyhow!("Conflict not found: {}", conflict_id))?;
+
+        match self.config.recommend_strategy(&conflict) {
+            ConflictStrategy::ManualResolution => {
+                let resolution_result = ConflictResolutionResult {
+                    strategy_used: ConflictStrategy::ManualResolution,
+                    resolved_entity: None,
+                    discarded_changes: Vec::new(),
+                    merge_details: None,
+                    resolution_notes: Some(format!("Conflicts on {} are resolved manually", conflict.entity_type)),
+                };
+                self.leave_for_manual_resolution(conflict, &resolution_result).await?;
+                Ok(resolution_result)
+            }
+            strategy => self.resolve_conflict(conflict_id, strategy, resolver).await,
+        }
+    }
+
+    pub fn config(&self) -> &ConflictResolutionConfig {
+        &self.config
+    }
+
+    /// The strategy the configuration recommends for a conflict
+    pub fn recommend_strategy(&self, conflict_info: &ConflictInfo) -> ConflictStrategy {
+        self.config.recommend_strategy(conflict_info)
+    }
+
+    /// Keep a conflict unresolved, recording why it needs manual resolution
+    async fn leave_for_manual_resolution(
+        &mut self,
+        mut conflict: ConflictInfo,
+        resolution_result: &ConflictResolutionResult,
+    ) -> Result<()> {
+        debug!("Conflict {} needs manual resolution", conflict.conflict_id);
+        conflict.resolution_strategy = Some(ConflictStrategy::ManualResolution);
+        conflict.resolution_result = Some(resolution_result.clone());
+        self.store_conflict(conflict).await?;
+        self.metrics.escalated_to_manual.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
     /// Resolve a conflict manually with provided resolution data
     pub async fn resolve_conflict_manually(
         &mut self,
         request: ManualResolutionRequest,
     ) -> Result<ConflictResolutionResult> {
-        let mut conflict = self.active_conflicts
-            .get(&request.conflict_id)
-            .ok_or_else(|| anyhow!("Conflict not found: {}", request.conflict_id))?
-            .clone();
+        let mut conflict = self.find_conflict(&request.conflict_id)
+            .await?
+            .ok_or_else(|| anyhow!("Conflict not found: {}", request.conflict_id))?;
 
         debug!("Manually resolving conflict {}", request.conflict_id);
 
@@ -272,12 +521,57 @@ impl ConflictResolutionEngine {
         conflict.resolution_result = Some(resolution_result.clone());
 
         // Store updated conflict
-        self.active_conflicts.insert(request.conflict_id.clone(), conflict);
+        let conflict_id = conflict.conflict_id.clone();
+        self.store_conflict(conflict).await?;
+        self.metrics.resolved_manually.fetch_add(1, Ordering::Relaxed);
 
-        debug!("Conflict {} manually resolved", request.conflict_id);
+        debug!("Conflict {} manually resolved", conflict_id);
         Ok(resolution_result)
     }
 
+    /// A conflict as currently stored, which with a repository may include changes made
+    /// by other engines; refreshes this engine's copy
+    pub async fn find_conflict(&mut self, conflict_id: &str) -> Result<Option<ConflictInfo>> {
+        let Some(repository) = &self.repository else {
+            return Ok(self.active_conflicts.get(conflict_id).cloned());
+        };
+        let conflict = repository.find_conflict(conflict_id).await?;
+        match &conflict {
+            Some(conflict) => {
+                self.active_conflicts.insert(conflict_id.to_string(), conflict.clone());
+            }
+            None => {
+                self.active_conflicts.remove(conflict_id);
+            }
+        }
+        Ok(conflict)
+    }
+
+    /// Conflicts of a project, newest first, from the repository when there is one;
+    /// only unresolved or resolved ones when `resolved` is set
+    pub async fn find_conflicts(&self, project_id: &str, resolved: Option<bool>) -> Result<Vec<ConflictInfo>> {
+        if let Some(repository) = &self.repository {
+            return Ok(repository.find_conflicts(project_id, resolved).await?);
+        }
+        let mut conflicts: Vec<ConflictInfo> = self
+            .active_conflicts
+            .values()
+            .filter(|c| c.project_id == project_id && resolved.is_none_or(|resolved| c.resolved_at.is_some() == resolved))
+            .cloned()
+            .collect();
+        conflicts.sort_by_key(|c| std::cmp::Reverse(c.detected_at));
+        Ok(conflicts)
+    }
+
+    /// Record a new or updated conflict, persisting it first
+    async fn store_conflict(&mut self, conflict: ConflictInfo) -> Result<()> {
+        if let Some(repository) = &self.repository {
+            repository.save_conflict(&conflict).await?;
+        }
+        self.active_conflicts.insert(conflict.conflict_id.clone(), conflict);
+        Ok(())
+    }
+
     /// Get information about an active conflict
     pub fn get_conflict_info(&self, conflict_id: &str) -> Option<&ConflictInfo> {
         self.active_conflicts.get(conflict_id)
@@ -326,17 +620,7 @@ impl ConflictResolutionEngine {
                     incoming_version, current_version
                 );
 
-                return Ok(Some(ConflictingChange {
-                    change_id: incoming_change.change_id,
-                    change: incoming_change.clone(),
-                    base_version: incoming_version,
-                    client_info: ClientInfo {
-                        client_id: incoming_change.metadata.client_id,
-                        user_id: incoming_change.metadata.user_id.clone(),
-                        client_type: "unknown".to_string(),
-                        timestamp: incoming_change.metadata.timestamp,
-                    },
-                }));
+                return Ok(Some(Self::conflicting_change(incoming_change)));
             }
         }
 
@@ -357,23 +641,13 @@ impl ConflictResolutionEngine {
             {
                 let time_diff = incoming_change.metadata.timestamp - recent_change.metadata.timestamp;
                 
-                if time_diff.abs() < threshold {
+                if time_diff.abs() < threshold && !Self::touch_separate_fields(incoming_change, recent_change) {
                     debug!(
                         "Content conflict detected: concurrent changes within {} seconds",
                         self.config.concurrent_change_threshold_seconds
                     );
 
-                    return Ok(Some(ConflictingChange {
-                        change_id: recent_change.change_id,
-                        change: recent_change.clone(),
-                        base_version: recent_change.metadata.version,
-                        client_info: ClientInfo {
-                            client_id: recent_change.metadata.client_id,
-                            user_id: recent_change.metadata.user_id.clone(),
-                            client_type: "unknown".to_string(),
-                            timestamp: recent_change.metadata.timestamp,
-                        },
-                    }));
+                    return Ok(Some(Self::conflicting_change(recent_change)));
                 }
             }
         }
@@ -381,6 +655,29 @@ impl ConflictResolutionEngine {
         Ok(None)
     }
 
+    fn conflicting_change(change: &ContextChange) -> ConflictingChange {
+        ConflictingChange {
+            change_id: change.change_id,
+            change: change.clone(),
+            base_version: change.metadata.version,
+            client_info: ClientInfo {
+                client_id: change.metadata.client_id,
+                user_id: change.metadata.user_id.clone(),
+                client_type: "unknown".to_string(),
+                timestamp: change.metadata.timestamp,
+            },
+        }
+    }
+
+    /// Whether both changes are patches editing different fields, so they can both apply
+    fn touch_separate_fields(a: &ContextChange, b: &ContextChange) -> bool {
+        let patch = |change: &ContextChange| change.delta.as_ref().and_then(json_patch::from_delta);
+        match (patch(a), patch(b)) {
+            (Some(ours), Some(theirs)) => json_patch::conflicting_paths(&ours, &theirs).is_empty(),
+            _ => false,
+        }
+    }
+
     /// Detect semantic conflicts (business rule violations)
     fn detect_semantic_conflict(
         &self,
@@ -442,7 +739,11 @@ impl ConflictResolutionEngine {
     async fn resolve_auto_merge(&self, conflict: &ConflictInfo) -> Result<ConflictResolutionResult> {
         debug!("Resolving conflict using auto-merge strategy");
 
-        // For now, implement a simple merge that combines non-conflicting fields
+        if let Some(base) = self.find_merge_base(conflict).await? {
+            return self.resolve_three_way_merge(conflict, &base);
+        }
+
+        // Without a base, combine the changes field by field, later ones winning
         let merged_entity = self.merge_changes(&conflict.conflicting_changes)?;
         
         let merge_details = MergeDetails {
@@ -450,6 +751,7 @@ impl ConflictResolutionEngine {
             conflicts_resolved: conflict.conflicting_changes.len() as u32,
             manual_interventions: 0,
             confidence_score: 0.8, // Moderate confidence for auto-merge
+            overlapping_paths: Vec::new(),
         };
 
         Ok(ConflictResolutionResult {
@@ -461,6 +763,127 @@ impl ConflictResolutionEngine {
         })
     }
 
+    /// The entity as it was before the first conflicting change, from the change log
+    async fn find_merge_base(&self, conflict: &ConflictInfo) -> Result<Option<serde_json::Value>> {
+        let Some(change_log) = &self.change_log else {
+            return Ok(None);
+        };
+        let Some(earliest) = conflict.conflicting_changes.iter().map(|c| c.change.metadata.timestamp).min() else {
+            return Ok(None);
+        };
+        let base = change_log
+            .find_latest_before(&conflict.entity_type, &conflict.entity_id, earliest)
+            .await?;
+        Ok(base
+            .filter(|change| change.change_type != ChangeType::Delete)
+            .and_then(|change| change.full_entity))
+    }
+
+    /// Apply every change's edits to `base`. Edits to different fields all apply; when
+    /// two changes edit the same field differently nothing is merged and the result asks
+    /// for manual resolution, naming the overlapping fields.
+    fn resolve_three_way_merge(
+        &self,
+        conflict: &ConflictInfo,
+        base: &serde_json::Value,
+    ) -> Result<ConflictResolutionResult> {
+        let mut merged = base.clone();
+        let mut applied: Vec<json_patch::PatchOperation> = Vec::new();
+        let mut overlapping_paths: Vec<String> = Vec::new();
+
+        let mut changes: Vec<&ConflictingChange> = conflict.conflicting_changes.iter().collect();
+        changes.sort_by_key(|c| c.change.metadata.timestamp);
+        for conflicting in changes {
+            let Some(entity) = Self::entity_after(&conflicting.change, base) else {
+                overlapping_paths.push(String::new());
+                continue;
+            };
+            let mut patch = json_patch::diff(base, &entity);
+
+            // Overlapping edits of text fields combine; any other overlap stops the merge
+            let mut texts = Vec::new();
+            let mut overlaps = Vec::new();
+            for path in json_patch::conflicting_paths(&applied, &patch) {
+                match merge_text_field(base, &merged, &entity, &path, &self.config.text_fields) {
+                    Some(text) => texts.push((path, text)),
+                    None => overlaps.push(path),
+                }
+            }
+            if !overlaps.is_empty() {
+                for path in overlaps {
+                    if !overlapping_paths.contains(&path) {
+                        overlapping_paths.push(path);
+                    }
+                }
+                continue;
+            }
+            patch.retain(|operation| !texts.iter().any(|(path, _)| json_patch::paths_overlap(path, operation.path())));
+
+            match json_patch::apply(&merged, &patch) {
+                Ok(mut entity) => {
+                    for (path, text) in texts {
+                        if let Some(target) = entity.pointer_mut(&path) {
+                            *target = serde_json::Value::String(text);
+                        }
+                    }
+                    merged = entity;
+                    applied.extend(patch);
+                }
+                Err(e) => {
+                    warn!("Change {} does not apply to the merge: {}", conflicting.change_id, e);
+                    overlapping_paths.push(String::new());
+                }
+            }
+        }
+
+        if !overlapping_paths.is_empty() {
+            let fields = overlapping_paths
+                .iter()
+                .map(|path| if path.is_empty() { "the whole entity" } else { path.as_str() })
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Ok(ConflictResolutionResult {
+                strategy_used: ConflictStrategy::ManualResolution,
+                resolved_entity: None,
+                discarded_changes: Vec::new(),
+                merge_details: Some(MergeDetails {
+                    merge_algorithm: "three_way_merge".to_string(),
+                    conflicts_resolved: 0,
+                    manual_interventions: overlapping_paths.len() as u32,
+                    confidence_score: 0.0,
+                    overlapping_paths,
+                }),
+                resolution_notes: Some(format!("Changes overlap at {}; resolve manually", fields)),
+            });
+        }
+
+        Ok(ConflictResolutionResult {
+            strategy_used: ConflictStrategy::AutoMerge,
+            resolved_entity: Some(merged),
+            discarded_changes: Vec::new(),
+            merge_details: Some(MergeDetails {
+                merge_algorithm: "three_way_merge".to_string(),
+                conflicts_resolved: conflict.conflicting_changes.len() as u32,
+                manual_interventions: 0,
+                confidence_score: 0.95, // Every edit applied to the common base
+                overlapping_paths: Vec::new(),
+            }),
+            resolution_notes: Some(format!("Merged {} field edits onto the common base", applied.len())),
+        })
+    }
+
+    /// The entity a change leaves behind when made to `base`; `None` for a delete or
+    /// a change carrying neither the entity nor a patch that applies
+    fn entity_after(change: &ContextChange, base: &serde_json::Value) -> Option<serde_json::Value> {
+        if change.change_type == ChangeType::Delete {
+            return None;
+        }
+        change.full_entity.clone().or_else(|| {
+            let patch = json_patch::from_delta(change.delta.as_ref()?)?;
+            json_patch::apply(base, &patch).ok()
+        })
+    }
+
     /// Resolve conflict by rejecting all changes
     async fn resolve_reject(&self, conflict: &ConflictInfo) -> Result<ConflictResolutionResult> {
         debug!("Resolving conflict by rejecting all changes");
@@ -539,7 +962,6 @@ impl Default for ConflictResolutionEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::services::websocket_types::ChangeType;
     use serde_json::json;
 
     fn create_test_change(
@@ -567,6 +989,7 @@ mod tests {
                 timestamp,
                 version,
                 conflict_resolution: None,
+                sequence: None,
             },
         }
     }
@@ -629,6 +1052,24 @@ mod tests {
         assert_eq!(conflict_info.conflict_type, ConflictType::ContentConflict);
     }
 
+    #[tokio::test]
+    async fn test_patches_to_separate_fields_do_not_conflict() {
+        let mut engine = ConflictResolutionEngine::new();
+        let now = Utc::now();
+
+        let mut change1 = create_test_change("rule-1", 2, Uuid::new_v4(), now);
+        change1.delta = Some(json!([{"op": "replace", "path": "/name", "value": "Renamed"}]));
+        let mut change2 = create_test_change("rule-1", 2, Uuid::new_v4(), now + chrono::Duration::seconds(5));
+        change2.delta = Some(json!([{"op": "replace", "path": "/description", "value": "Reworded"}]));
+
+        let conflict = engine.detect_conflict(&change2, None, &[change1.clone()]).await.unwrap();
+        assert!(conflict.is_none());
+
+        change2.delta = Some(json!([{"op": "replace", "path": "/name", "value": "Other"}]));
+        let conflict = engine.detect_conflict(&change2, None, &[change1]).await.unwrap();
+        assert_eq!(conflict.unwrap().conflict_type, ConflictType::ContentConflict);
+    }
+
     #[tokio::test]
     async fn test_last_writer_wins_resolution() {
         let mut engine = ConflictResolutionEngine::new();
@@ -688,6 +1129,9 @@ mod tests {
         assert_eq!(result.strategy_used, ConflictStrategy::LastWriterWins);
         assert!(result.resolved_entity.is_some());
         assert_eq!(result.discarded_changes.len(), 1);
+        let metrics = engine.metrics();
+        assert_eq!(metrics.resolved_last_writer_wins.load(Ordering::Relaxed), 1);
+        assert_eq!(metrics.resolved_auto_merge.load(Ordering::Relaxed), 0);
     }
 
     #[tokio::test]
@@ -762,6 +1206,134 @@ mod tests {
         assert_eq!(result.discarded_changes.len(), 0); // No changes discarded in merge
     }
 
+    #[tokio::test]
+    async fn test_auto_merge_against_logged_base() {
+        use crate::infrastructure::SqliteChangeLogRepository;
+        use std::sync::Mutex;
+
+        let db = Arc::new(Mutex::new(crate::db::init::init_db(":memory:").unwrap()));
+        let change_log = Arc::new(SqliteChangeLogRepository::new(db));
+        let mut engine = ConflictResolutionEngine::new().with_change_log(change_log.clone());
+        let now = Utc::now();
+
+        let base = create_test_change("rule-1", 1, Uuid::new_v4(), now - chrono::Duration::minutes(5));
+        change_log.append(&base).await.unwrap();
+
+        let mut renamed = create_test_change("rule-1", 2, Uuid::new_v4(), now);
+        renamed.full_entity = Some(json!({"id": "rule-1", "name": "Renamed", "description": "A test business rule"}));
+        let mut reworded = create_test_change("rule-1", 2, Uuid::new_v4(), now + chrono::Duration::seconds(5));
+        reworded.full_entity = Some(json!({"id": "rule-1", "name": "Test Rule", "description": "Reworded", "priority": "high"}));
+
+        let conflict = engine.detect_conflict(&reworded, None, &[renamed.clone()]).await.unwrap().unwrap();
+        assert_eq!(conflict.conflicting_changes.len(), 2);
+        let result = engine
+            .resolve_conflict(&conflict.conflict_id, ConflictStrategy::AutoMerge, None)
+            .await
+            .unwrap();
+        assert_eq!(result.strategy_used, ConflictStrategy::AutoMerge);
+        assert_eq!(
+            result.resolved_entity,
+            Some(json!({"id": "rule-1", "name": "Renamed", "description": "Reworded", "priority": "high"}))
+        );
+        assert_eq!(result.merge_details.unwrap().merge_algorithm, "three_way_merge");
+
+        // Both renaming the rule is a real overlap, left for a person to decide
+        let mut also_renamed = reworded.clone();
+        also_renamed.change_id = Uuid::new_v4();
+        also_renamed.full_entity = Some(json!({"id": "rule-1", "name": "Other", "description": "Reworded"}));
+        let conflict = engine.detect_conflict(&also_renamed, None, &[renamed.clone()]).await.unwrap().unwrap();
+        let result = engine
+            .resolve_conflict(&conflict.conflict_id, ConflictStrategy::AutoMerge, None)
+            .await
+            .unwrap();
+        assert_eq!(result.strategy_used, ConflictStrategy::ManualResolution);
+        assert!(result.resolved_entity.is_none());
+        assert_eq!(result.merge_details.unwrap().overlapping_paths, vec!["/name".to_string()]);
+
+        let stored = engine.get_conflict_info(&conflict.conflict_id).unwrap();
+        assert!(stored.resolved_at.is_none());
+        assert_eq!(stored.resolution_strategy, Some(ConflictStrategy::ManualResolution));
+
+        // With the description merged as text, editing it on both sides is no overlap
+        let config = ConflictResolutionConfig {
+            text_fields: vec!["description".to_string()],
+            ..Default::default()
+        };
+        let mut engine = ConflictResolutionEngine::with_config(config).with_change_log(change_log);
+        let mut shortened = renamed.clone();
+        shortened.change_id = Uuid::new_v4();
+        shortened.full_entity = Some(json!({"id": "rule-1", "name": "Renamed", "description": "A short test business rule"}));
+        let mut extended = reworded.clone();
+        extended.change_id = Uuid::new_v4();
+        extended.full_entity = Some(json!({"id": "rule-1", "name": "Test Rule", "description": "A test business rule for refunds"}));
+        let conflict = engine.detect_conflict(&extended, None, &[shortened]).await.unwrap().unwrap();
+        let result = engine
+            .resolve_conflict(&conflict.conflict_id, ConflictStrategy::AutoMerge, None)
+            .await
+            .unwrap();
+        assert_eq!(result.strategy_used, ConflictStrategy::AutoMerge);
+        assert_eq!(
+            result.resolved_entity,
+            Some(json!({"id": "rule-1", "name": "Renamed", "description": "A short test business rule for refunds"}))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_entity_type_policies_override_recommendations() {
+        let mut settings = ConflictSettings::default();
+        settings.policies.insert(
+            "specification".to_string(),
+            crate::config::ConflictPolicySettings {
+                strategy: Some(ResolutionStrategy::ManualResolution),
+                ..Default::default()
+            },
+        );
+        settings.policies.insert(
+            "business_rule".to_string(),
+            crate::config::ConflictPolicySettings {
+                min_merge_confidence: Some(0.9),
+                ..Default::default()
+            },
+        );
+        let mut engine = ConflictResolutionEngine::with_config(ConflictResolutionConfig::from_settings(&settings));
+        let now = Utc::now();
+
+        let rule_change = create_test_change("rule-1", 1, Uuid::new_v4(), now);
+        let conflict = engine
+            .detect_conflict(&create_test_change("rule-1", 1, Uuid::new_v4(), now), None, &[rule_change])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(engine.recommend_strategy(&conflict), ConflictStrategy::AutoMerge);
+
+        // A field-by-field merge is not confident enough for business rules
+        let result = engine.resolve_by_policy(&conflict.conflict_id, None).await.unwrap();
+        assert_eq!(result.strategy_used, ConflictStrategy::ManualResolution);
+        assert!(engine.get_conflict_info(&conflict.conflict_id).unwrap().resolved_at.is_none());
+
+        let mut spec_change = create_test_change("spec-1", 1, Uuid::new_v4(), now);
+        spec_change.entity_type = "specification".to_string();
+        let mut other_spec_change = spec_change.clone();
+        other_spec_change.change_id = Uuid::new_v4();
+        let conflict = engine
+            .detect_conflict(&other_spec_change, None, &[spec_change])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(engine.recommend_strategy(&conflict), ConflictStrategy::ManualResolution);
+        let result = engine.resolve_by_policy(&conflict.conflict_id, None).await.unwrap();
+        assert_eq!(result.strategy_used, ConflictStrategy::ManualResolution);
+        let stored = engine.get_conflict_info(&conflict.conflict_id).unwrap();
+        assert_eq!(stored.resolution_strategy, Some(ConflictStrategy::ManualResolution));
+        assert!(stored.resolved_at.is_none());
+
+        // Without a policy, content conflicts beyond the threshold need a person
+        let mut crowded = conflict.clone();
+        crowded.entity_type = "component".to_string();
+        crowded.conflicting_changes.push(crowded.conflicting_changes[0].clone());
+        assert_eq!(engine.recommend_strategy(&crowded), ConflictStrategy::ManualResolution);
+    }
+
     #[tokio::test]
     async fn test_manual_resolution() {
         let mut engine = ConflictResolutionEngine::new();
@@ -48,6 +48,7 @@ async fn test_complete_conflict_resolution_workflow() -> Result<()> {
             timestamp: now,
             version: 1, // Lower version than existing entity
             conflict_resolution: None,
+            sequence: None,
         },
     };
 
@@ -155,6 +156,7 @@ async fn test_content_conflict_auto_merge() -> Result<()> {
             timestamp: now,
             version: 1,
             conflict_resolution: None,
+            sequence: None,
         },
     };
 
@@ -177,6 +179,7 @@ async fn test_content_conflict_auto_merge() -> Result<()> {
             timestamp: now + chrono::Duration::seconds(10),
             version: 1,
             conflict_resolution: None,
+            sequence: None,
         },
     };
 
@@ -235,6 +238,7 @@ async fn test_manual_conflict_resolution() -> Result<()> {
             timestamp: now,
             version: 1,
             conflict_resolution: None,
+            sequence: None,
         },
     };
 
@@ -257,6 +261,7 @@ async fn test_manual_conflict_resolution() -> Result<()> {
             timestamp: now + chrono::Duration::seconds(5),
             version: 1,
             conflict_resolution: None,
+            sequence: None,
         },
     };
 
@@ -371,6 +376,7 @@ async fn test_conflict_cleanup_and_session_management() -> Result<()> {
             timestamp: now,
             version: 1,
             conflict_resolution: None,
+            sequence: None,
         },
     };
 
@@ -414,7 +420,7 @@ async fn test_conflict_cleanup_and_session_management() -> Result<()> {
     assert_eq!(cancelled_session.unwrap().ui_state.current_step, ConflictResolutionStep::Cancelled);
 
     // Test cleanup of expired sessions
-    conflict_ui.cleanup_expired_sessions();
+    conflict_ui.cleanup_expired_sessions().await?;
 
     // Verify active and resolved conflicts
     let active_conflicts = sync_engine.get_active_conflicts("test-project").await;
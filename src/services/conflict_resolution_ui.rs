@@ -1,10 +1,13 @@
-use crate::services::conflict_resolution_engine::{ConflictInfo, ConflictType, ManualResolutionRequest, ConflictResolutionResult};
+use crate::repositories::ConflictRepository;
+use crate::services::conflict_resolution_engine::{ConflictInfo, ConflictResolutionConfig, ConflictType, ManualResolutionRequest, ConflictResolutionResult};
 use crate::services::websocket_types::{ConflictStrategy, ClientId};
 use anyhow::{Result, anyhow};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::{debug, info};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 /// UI service for conflict resolution workflows
@@ -12,6 +15,11 @@ use uuid::Uuid;
 pub struct ConflictResolutionUI {
     /// Active UI sessions for conflict resolution
     active_sessions: HashMap<String, ConflictResolutionSession>,
+    /// Durable copy of every session, so sessions survive restarts and can be continued
+    /// from another transport
+    repository: Option<Arc<dyn ConflictRepository>>,
+    /// Policies deciding the recommended strategy
+    config: ConflictResolutionConfig,
 }
 
 /// A conflict resolution session with UI state
@@ -187,9 +195,43 @@ impl ConflictResolutionUI {
     pub fn new() -> Self {
         Self {
             active_sessions: HashMap::new(),
+            repository: None,
+            config: ConflictResolutionConfig::default(),
         }
     }
 
+    /// Persist sessions in `repository`, which then holds the current state of each one
+    pub fn with_repository(mut self, repository: Arc<dyn ConflictRepository>) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Recommend strategies by the policies of `config`, as the engine applies them
+    pub fn with_config(mut self, config: ConflictResolutionConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Run `cleanup_expired_sessions` on the shared service every `every`
+    pub fn spawn_session_cleanup(ui: Arc<Mutex<Self>>, every: std::time::Duration) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            debug!("No tokio runtime; conflict session cleanup not started");
+            return;
+        };
+
+        runtime.spawn(async move {
+            let mut interval = tokio::time::interval(every);
+            loop {
+                interval.tick().await;
+                match ui.lock().await.cleanup_expired_sessions().await {
+                    Ok(0) => {}
+                    Ok(removed) => debug!("Removed {} expired conflict resolution sessions", removed),
+                    Err(e) => warn!("Failed to remove expired conflict resolution sessions: {}", e),
+                }
+            }
+        });
+    }
+
     /// Start a new conflict resolution session
     pub async fn start_resolution_session(
         &mut self,
@@ -234,7 +276,7 @@ impl ConflictResolutionUI {
         };
 
         // Store session
-        self.active_sessions.insert(session_id.clone(), session);
+        self.store_session(session).await?;
 
         // Generate response
         let available_strategies = self.get_available_strategies(&conflict_info);
@@ -256,12 +298,10 @@ impl ConflictResolutionUI {
         request: UpdateUIStateRequest,
     ) -> Result<UpdateUIStateResponse> {
         // First, get the session data we need for validation and component generation
-        let conflict_info = {
-            let session = self.active_sessions
-                .get(&request.session_id)
-                .ok_or_else(|| anyhow!("Session not found: {}", request.session_id))?;
-            session.conflict_info.clone()
-        };
+        let conflict_info = self.find_session(&request.session_id)
+            .await?
+            .ok_or_else(|| anyhow!("Session not found: {}", request.session_id))?
+            .conflict_info;
 
         debug!("Updating UI state for session {} to step {:?}", request.session_id, request.step);
 
@@ -293,6 +333,7 @@ impl ConflictResolutionUI {
             &request.step,
             &conflict_info,
             &session.ui_state,
+            &self.config,
         )?;
 
         // Check if can proceed
@@ -303,9 +344,12 @@ impl ConflictResolutionUI {
             session.ui_state.preview_entity = Some(Self::generate_preview_entity_static(&session.ui_state, &conflict_info)?);
         }
 
+        let session = session.clone();
+        self.store_session(session.clone()).await?;
+
         Ok(UpdateUIStateResponse {
             success: true,
-            updated_ui_state: session.ui_state.clone(),
+            updated_ui_state: session.ui_state,
             next_components,
             validation_errors,
             can_proceed,
@@ -318,8 +362,8 @@ impl ConflictResolutionUI {
         session_id: &str,
         resolution_notes: Option<String>,
     ) -> Result<ManualResolutionRequest> {
-        let session = self.active_sessions
-            .get_mut(session_id)
+        let mut session = self.find_session(session_id)
+            .await?
             .ok_or_else(|| anyhow!("Session not found: {}", session_id))?;
 
         info!("Completing conflict resolution session {}", session_id);
@@ -365,27 +409,58 @@ impl ConflictResolutionUI {
             resolution_notes,
             resolved_by: session.user_id.clone(),
         };
+        self.store_session(session).await?;
 
         Ok(manual_request)
     }
 
     /// Cancel a conflict resolution session
     pub async fn cancel_resolution(&mut self, session_id: &str) -> Result<()> {
-        if let Some(session) = self.active_sessions.get_mut(session_id) {
+        if let Some(mut session) = self.find_session(session_id).await? {
             session.ui_state.current_step = ConflictResolutionStep::Cancelled;
+            self.store_session(session).await?;
             info!("Cancelled conflict resolution session {}", session_id);
         }
         Ok(())
     }
 
+    /// A session as currently stored, which with a repository may include steps taken
+    /// through another transport; refreshes this service's copy
+    pub async fn find_session(&mut self, session_id: &str) -> Result<Option<ConflictResolutionSession>> {
+        let Some(repository) = &self.repository else {
+            return Ok(self.active_sessions.get(session_id).cloned());
+        };
+        let session = repository.find_session(session_id).await?;
+        match &session {
+            Some(session) => {
+                self.active_sessions.insert(session_id.to_string(), session.clone());
+            }
+            None => {
+                self.active_sessions.remove(session_id);
+            }
+        }
+        Ok(session)
+    }
+
+    /// Record a new or updated session, persisting it first
+    async fn store_session(&mut self, session: ConflictResolutionSession) -> Result<()> {
+        if let Some(repository) = &self.repository {
+            repository.save_session(&session).await?;
+        }
+        self.active_sessions.insert(session.session_id.clone(), session);
+        Ok(())
+    }
+
     /// Get active session information
     pub fn get_session(&self, session_id: &str) -> Option<&ConflictResolutionSession> {
         self.active_sessions.get(session_id)
     }
 
-    /// Clean up expired sessions
-    pub fn cleanup_expired_sessions(&mut self) {
+    /// Clean up expired sessions, returning how many were removed from the repository,
+    /// or from memory without one
+    pub async fn cleanup_expired_sessions(&mut self) -> Result<usize> {
         let now = Utc::now();
+        let before = self.active_sessions.len();
         self.active_sessions.retain(|_, session| {
             if let Some(timeout_at) = session.timeout_at {
                 timeout_at > now
@@ -393,6 +468,10 @@ impl ConflictResolutionUI {
                 true
             }
         });
+        match &self.repository {
+            Some(repository) => Ok(repository.delete_expired_sessions(now).await?),
+            None => Ok(before - self.active_sessions.len()),
+        }
     }
 
     /// Get available strategies for a conflict
@@ -427,8 +506,11 @@ impl ConflictResolutionUI {
             },
         ];
 
-        // Add manual resolution for complex conflicts
-        if conflict_info.conflicting_changes.len() > 1 || conflict_info.conflict_type == ConflictType::SemanticConflict {
+        // Add manual resolution for complex conflicts and when it is recommended
+        if conflict_info.conflicting_changes.len() > 1
+            || conflict_info.conflict_type == ConflictType::SemanticConflict
+            || self.recommend_strategy(conflict_info) == ConflictStrategy::ManualResolution
+        {
             strategies.push(ConflictStrategyOption {
                 strategy: ConflictStrategy::ManualResolution,
                 name: "Manual Resolution".to_string(),
@@ -445,19 +527,7 @@ impl ConflictResolutionUI {
 
     /// Recommend the best strategy for a conflict
     fn recommend_strategy(&self, conflict_info: &ConflictInfo) -> ConflictStrategy {
-        match conflict_info.conflict_type {
-            ConflictType::VersionConflict => ConflictStrategy::LastWriterWins,
-            ConflictType::ContentConflict => {
-                if conflict_info.conflicting_changes.len() <= 2 {
-                    ConflictStrategy::AutoMerge
-                } else {
-                    ConflictStrategy::ManualResolution
-                }
-            }
-            ConflictType::SemanticConflict | ConflictType::DependencyConflict => {
-                ConflictStrategy::ManualResolution
-            }
-        }
+        self.config.recommend_strategy(conflict_info)
     }
 
     /// Generate initial UI components for conflict presentation
@@ -646,11 +716,12 @@ impl ConflictResolutionUI {
         step: &ConflictResolutionStep,
         conflict_info: &ConflictInfo,
         ui_state: &ConflictUIState,
+        config: &ConflictResolutionConfig,
     ) -> Result<Vec<UIComponent>> {
         match step {
             ConflictResolutionStep::StrategySelection => {
-                let available_strategies = Self::get_available_strategies_static(conflict_info);
-                let recommended_strategy = Self::recommend_strategy_static(conflict_info);
+                let available_strategies = Self::get_available_strategies_static(conflict_info, config);
+                let recommended_strategy = config.recommend_strategy(conflict_info);
                 
                 Ok(vec![
                     UIComponent {
@@ -765,7 +836,7 @@ impl ConflictResolutionUI {
     }
 
     /// Get available strategies for a conflict (static version)
-    fn get_available_strategies_static(conflict_info: &ConflictInfo) -> Vec<ConflictStrategyOption> {
+    fn get_available_strategies_static(conflict_info: &ConflictInfo, config: &ConflictResolutionConfig) -> Vec<ConflictStrategyOption> {
         let mut strategies = vec![
             ConflictStrategyOption {
                 strategy: ConflictStrategy::LastWriterWins,
@@ -796,8 +867,11 @@ impl ConflictResolutionUI {
             },
         ];
 
-        // Add manual resolution for complex conflicts
-        if conflict_info.conflicting_changes.len() > 1 || conflict_info.conflict_type == ConflictType::SemanticConflict {
+        // Add manual resolution for complex conflicts and when it is recommended
+        if conflict_info.conflicting_changes.len() > 1
+            || conflict_info.conflict_type == ConflictType::SemanticConflict
+            || config.recommend_strategy(conflict_info) == ConflictStrategy::ManualResolution
+        {
             strategies.push(ConflictStrategyOption {
                 strategy: ConflictStrategy::ManualResolution,
                 name: "Manual Resolution".to_string(),
@@ -812,23 +886,6 @@ impl ConflictResolutionUI {
         strategies
     }
 
-    /// Recommend the best strategy for a conflict (static version)
-    fn recommend_strategy_static(conflict_info: &ConflictInfo) -> ConflictStrategy {
-        match conflict_info.conflict_type {
-            ConflictType::VersionConflict => ConflictStrategy::LastWriterWins,
-            ConflictType::ContentConflict => {
-                if conflict_info.conflicting_changes.len() <= 2 {
-                    ConflictStrategy::AutoMerge
-                } else {
-                    ConflictStrategy::ManualResolution
-                }
-            }
-            ConflictType::SemanticConflict | ConflictType::DependencyConflict => {
-                ConflictStrategy::ManualResolution
-            }
-        }
-    }
-
     /// Extract conflicting fields from changes (static version)
     fn extract_conflicting_fields_static(changes: &[crate::services::conflict_resolution_engine::ConflictingChange]) -> serde_json::Value {
         let mut conflicting_fields = serde_json::Map::new();
@@ -1045,6 +1102,7 @@ mod tests {
                             timestamp: now,
                             version: 1,
                             conflict_resolution: None,
+                            sequence: None,
                         },
                     },
                     base_version: 1,
@@ -1076,6 +1134,7 @@ mod tests {
                             timestamp: now + chrono::Duration::seconds(10),
                             version: 1,
                             conflict_resolution: None,
+                            sequence: None,
                         },
                     },
                     base_version: 1,
@@ -1229,4 +1288,76 @@ mod tests {
         assert_eq!(errors[0].severity, ValidationSeverity::Error);
         assert_eq!(errors[0].field, "selected_strategy");
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_sessions_and_conflicts_persist_across_instances() {
+        use crate::infrastructure::SqliteConflictRepository;
+        use crate::services::conflict_resolution_engine::ConflictResolutionEngine;
+
+        let conn = crate::db::init::init_db(":memory:").unwrap();
+        let repository = Arc::new(SqliteConflictRepository::new(Arc::new(std::sync::Mutex::new(conn))));
+        let conflict_info = create_test_conflict_info();
+
+        let mut engine = ConflictResolutionEngine::new().with_repository(repository.clone());
+        let detected = engine
+            .detect_conflict(&conflict_info.conflicting_changes[1].change, None, &[conflict_info.conflicting_changes[0].change.clone()])
+            .await
+            .unwrap()
+            .unwrap();
+
+        let mut ui = ConflictResolutionUI::new().with_repository(repository.clone());
+        let start = ui
+            .start_resolution_session(
+                StartResolutionRequest {
+                    conflict_id: detected.conflict_id.clone(),
+                    user_id: "test-user".to_string(),
+                    client_id: Uuid::new_v4(),
+                    preferred_strategy: None,
+                    timeout_seconds: Some(600),
+                },
+                detected.clone(),
+            )
+            .await
+            .unwrap();
+
+        // Another instance, as after a restart or on another transport, continues the session
+        let mut restarted = ConflictResolutionUI::new().with_repository(repository.clone());
+        assert!(restarted.get_session(&start.session_id).is_none());
+        restarted
+            .update_ui_state(UpdateUIStateRequest {
+                session_id: start.session_id.clone(),
+                step: ConflictResolutionStep::StrategySelection,
+                user_selections: HashMap::new(),
+                selected_strategy: Some(ConflictStrategy::LastWriterWins),
+            })
+            .await
+            .unwrap();
+        let session = ui.find_session(&start.session_id).await.unwrap().unwrap();
+        assert_eq!(session.ui_state.selected_strategy, Some(ConflictStrategy::LastWriterWins));
+
+        let request = ui.complete_resolution(&start.session_id, None).await.unwrap();
+        let mut other_engine = ConflictResolutionEngine::new().with_repository(repository.clone());
+        other_engine.resolve_conflict_manually(request).await.unwrap();
+        assert!(engine.find_conflicts("test-project", Some(false)).await.unwrap().is_empty());
+        let resolved = engine.find_conflict(&detected.conflict_id).await.unwrap().unwrap();
+        assert_eq!(resolved.resolved_by.as_deref(), Some("test-user"));
+
+        // Expired sessions are removed from the repository
+        let expired = ui
+            .start_resolution_session(
+                StartResolutionRequest {
+                    conflict_id: detected.conflict_id.clone(),
+                    user_id: "test-user".to_string(),
+                    client_id: Uuid::new_v4(),
+                    preferred_strategy: None,
+                    timeout_seconds: Some(0),
+                },
+                detected,
+            )
+            .await
+            .unwrap();
+        assert_eq!(ui.cleanup_expired_sessions().await.unwrap(), 1);
+        assert!(restarted.find_session(&expired.session_id).await.unwrap().is_none());
+        assert!(restarted.find_session(&start.session_id).await.unwrap().is_some());
+    }
+}
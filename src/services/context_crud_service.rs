@@ -1,9 +1,13 @@
-use crate::models::context::{ArchitecturalDecision, BusinessRule, PerformanceRequirement};
+use crate::models::context::{
+    ArchitecturalDecision, BusinessRule, FeatureContext, PerformanceRequirement, SecurityPolicy,
+};
 use crate::repositories::{
-    ArchitecturalDecisionRepository, BusinessRuleRepository, PerformanceRequirementRepository,
+    ArchitecturalDecisionRepository, BusinessRuleRepository, FeatureContextRepository,
+    PerformanceRequirementRepository, SecurityPolicyRepository,
 };
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
+use std::sync::Arc;
 use uuid::Uuid;
 
 /// Enhanced CRUD service for business rules, architectural decisions, and performance requirements
@@ -83,23 +87,17 @@ pub trait ContextCrudService: Send + Sync {
     ) -> Result<Vec<BusinessRule>, McpError>;
     async fn bulk_delete_business_rules(&self, ids: &[String]) -> Result<usize, McpError>;
 
-    // Security Policy CRUD (from ExtendedContextCrudService)
+    // Security Policy CRUD
     async fn create_security_policy(
         &self,
         project_id: &str,
         policy_name: &str,
         policy_area: Option<&str>,
-    ) -> Result<crate::models::context::SecurityPolicy, McpError>;
-    async fn get_security_policy(&self, id: &str) -> Result<Option<crate::models::context::SecurityPolicy>, McpError>;
-    async fn update_security_policy(
-        &self,
-        policy: &crate::models::context::SecurityPolicy,
-    ) -> Result<crate::models::context::SecurityPolicy, McpError>;
+    ) -> Result<SecurityPolicy, McpError>;
+    async fn get_security_policy(&self, id: &str) -> Result<Option<SecurityPolicy>, McpError>;
+    async fn update_security_policy(&self, policy: &SecurityPolicy) -> Result<SecurityPolicy, McpError>;
     async fn delete_security_policy(&self, id: &str) -> Result<bool, McpError>;
-    async fn list_security_policies(
-        &self,
-        project_id: &str,
-    ) -> Result<Vec<crate::models::context::SecurityPolicy>, McpError>;
+    async fn list_security_policies(&self, project_id: &str) -> Result<Vec<SecurityPolicy>, McpError>;
 
     // Feature Context CRUD (from ExtendedContextCrudService)
     async fn create_feature_context(
@@ -107,17 +105,17 @@ pub trait ContextCrudService: Send + Sync {
         project_id: &str,
         feature_name: &str,
         business_purpose: Option<&str>,
-    ) -> Result<crate::models::context::FeatureContext, McpError>;
-    async fn get_feature_context(&self, id: &str) -> Result<Option<crate::models::context::FeatureContext>, McpError>;
+    ) -> Result<FeatureContext, McpError>;
+    async fn get_feature_context(&self, id: &str) -> Result<Option<FeatureContext>, McpError>;
     async fn update_feature_context(
         &self,
-        feature_context: &crate::models::context::FeatureContext,
-    ) -> Result<crate::models::context::FeatureContext, McpError>;
+        feature_context: &FeatureContext,
+    ) -> Result<FeatureContext, McpError>;
     async fn delete_feature_context(&self, id: &str) -> Result<bool, McpError>;
     async fn list_feature_contexts(
         &self,
         project_id: &str,
-    ) -> Result<Vec<crate::models::context::FeatureContext>, McpError>;
+    ) -> Result<Vec<FeatureContext>, McpError>;
 }
 
 /// Implementation of ContextCrudService
@@ -130,6 +128,8 @@ where
     business_rule_repository: BR,
     architectural_decision_repository: ADR,
     performance_requirement_repository: PR,
+    security_policy_repository: Option<Arc<dyn SecurityPolicyRepository>>,
+    feature_context_repository: Option<Arc<dyn FeatureContextRepository>>,
 }
 
 impl<BR, ADR, PR> ContextCrudServiceImpl<BR, ADR, PR>
@@ -148,8 +148,34 @@ where
             business_rule_repository,
             architectural_decision_repository,
             performance_requirement_repository,
+            security_policy_repository: None,
+            feature_context_repository: None,
         }
     }
+
+    /// Manage security policies too
+    pub fn with_security_policies(mut self, security_policy_repository: Arc<dyn SecurityPolicyRepository>) -> Self {
+        self.security_policy_repository = Some(security_policy_repository);
+        self
+    }
+
+    fn security_policies(&self) -> Result<&dyn SecurityPolicyRepository, McpError> {
+        self.security_policy_repository
+            .as_deref()
+            .ok_or_else(|| McpError::internal_error("Security policies are not configured".to_string(), None))
+    }
+
+    /// Manage feature contexts too
+    pub fn with_feature_contexts(mut self, feature_context_repository: Arc<dyn FeatureContextRepository>) -> Self {
+        self.feature_context_repository = Some(feature_context_repository);
+        self
+    }
+
+    fn feature_contexts(&self) -> Result<&dyn FeatureContextRepository, McpError> {
+        self.feature_context_repository
+            .as_deref()
+            .ok_or_else(|| McpError::internal_error("Feature contexts are not configured".to_string(), None))
+    }
 }
 
 #[async_trait]
@@ -361,96 +387,85 @@ where
         Ok(deleted_count)
     }
 
-    // Extended methods - SecurityPolicy and FeatureContext (stubs for now)
+    // Security Policies CRUD Implementation
     async fn create_security_policy(
         &self,
-        _project_id: &str,
-        _policy_name: &str,
-        _policy_area: Option<&str>,
-    ) -> Result<crate::models::context::SecurityPolicy, McpError> {
-        Err(McpError::internal_error(
-            "SecurityPolicy creation not implemented in ContextCrudServiceImpl".to_string(),
-            None,
-        ))
+        project_id: &str,
+        policy_name: &str,
+        policy_area: Option<&str>,
+    ) -> Result<SecurityPolicy, McpError> {
+        let policy = SecurityPolicy {
+            id: Uuid::new_v4().to_string(),
+            project_id: project_id.to_string(),
+            policy_name: policy_name.to_string(),
+            policy_area: policy_area.map(|s| s.to_string()),
+            requirements: None,
+            implementation_pattern: None,
+            forbidden_patterns: None,
+            compliance_notes: None,
+            severity: None,
+            classification: None,
+            created_at: Some(chrono::Utc::now().to_rfc3339()),
+        };
+
+        self.security_policies()?.create(&policy).await
     }
 
-    async fn get_security_policy(&self, _id: &str) -> Result<Option<crate::models::context::SecurityPolicy>, McpError> {
-        Err(McpError::internal_error(
-            "SecurityPolicy retrieval not implemented".to_string(),
-            None,
-        ))
+    async fn get_security_policy(&self, id: &str) -> Result<Option<SecurityPolicy>, McpError> {
+        self.security_policies()?.get_by_id(id).await
     }
 
-    async fn update_security_policy(
-        &self,
-        _policy: &crate::models::context::SecurityPolicy,
-    ) -> Result<crate::models::context::SecurityPolicy, McpError> {
-        Err(McpError::internal_error(
-            "SecurityPolicy update not implemented".to_string(),
-            None,
-        ))
+    async fn update_security_policy(&self, policy: &SecurityPolicy) -> Result<SecurityPolicy, McpError> {
+        self.security_policies()?.update(policy).await
     }
 
-    async fn delete_security_policy(&self, _id: &str) -> Result<bool, McpError> {
-        Err(McpError::internal_error(
-            "SecurityPolicy deletion not implemented".to_string(),
-            None,
-        ))
+    async fn delete_security_policy(&self, id: &str) -> Result<bool, McpError> {
+        self.security_policies()?.delete(id).await
     }
 
-    async fn list_security_policies(
-        &self,
-        _project_id: &str,
-    ) -> Result<Vec<crate::models::context::SecurityPolicy>, McpError> {
-        Err(McpError::internal_error(
-            "SecurityPolicy listing not implemented".to_string(),
-            None,
-        ))
+    async fn list_security_policies(&self, project_id: &str) -> Result<Vec<SecurityPolicy>, McpError> {
+        self.security_policies()?.list_by_project(project_id).await
     }
 
+    // Feature Context CRUD Implementation
     async fn create_feature_context(
         &self,
-        _project_id: &str,
-        _feature_name: &str,
-        _business_purpose: Option<&str>,
-    ) -> Result<crate::models::context::FeatureContext, McpError> {
-        Err(McpError::internal_error(
-            "FeatureContext creation not implemented".to_string(),
-            None,
-        ))
+        project_id: &str,
+        feature_name: &str,
+        business_purpose: Option<&str>,
+    ) -> Result<FeatureContext, McpError> {
+        let feature_context = FeatureContext {
+            id: Uuid::new_v4().to_string(),
+            project_id: project_id.to_string(),
+            feature_name: feature_name.to_string(),
+            business_purpose: business_purpose.map(|s| s.to_string()),
+            user_personas: None,
+            key_workflows: None,
+            integration_points: None,
+            edge_cases: None,
+            constraints: None,
+            related_components: None,
+            status: Some("planned".to_string()),
+            owner: None,
+            created_at: Some(chrono::Utc::now().to_rfc3339()),
+        };
+
+        self.feature_contexts()?.create(&feature_context).await
     }
 
-    async fn get_feature_context(&self, _id: &str) -> Result<Option<crate::models::context::FeatureContext>, McpError> {
-        Err(McpError::internal_error(
-            "FeatureContext retrieval not implemented".to_string(),
-            None,
-        ))
+    async fn get_feature_context(&self, id: &str) -> Result<Option<FeatureContext>, McpError> {
+        self.feature_contexts()?.get_by_id(id).await
     }
 
-    async fn update_feature_context(
-        &self,
-        _feature_context: &crate::models::context::FeatureContext,
-    ) -> Result<crate::models::context::FeatureContext, McpError> {
-        Err(McpError::internal_error(
-            "FeatureContext update not implemented".to_string(),
-            None,
-        ))
+    async fn update_feature_context(&self, feature_context: &FeatureContext) -> Result<FeatureContext, McpError> {
+        self.feature_contexts()?.update(feature_context).await
     }
 
-    async fn delete_feature_context(&self, _id: &str) -> Result<bool, McpError> {
-        Err(McpError::internal_error(
-            "FeatureContext deletion not implemented".to_string(),
-            None,
-        ))
+    async fn delete_feature_context(&self, id: &str) -> Result<bool, McpError> {
+        self.feature_contexts()?.delete(id).await
     }
 
-    async fn list_feature_contexts(
-        &self,
-        _project_id: &str,
-    ) -> Result<Vec<crate::models::context::FeatureContext>, McpError> {
-        Err(McpError::internal_error(
-            "FeatureContext listing not implemented".to_string(),
-            None,
-        ))
+    async fn list_feature_contexts(&self, project_id: &str) -> Result<Vec<FeatureContext>, McpError> {
+        self.feature_contexts()?.list_by_project(project_id).await
     }
 }
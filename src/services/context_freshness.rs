@@ -0,0 +1,162 @@
+use crate::config::FreshnessSettings;
+use crate::models::freshness::EntityVerification;
+use crate::services::context_query_service::parse_timestamp;
+use crate::services::unused_context::entity_title;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Entity types whose freshness `query_context` ranks by and `list_stale_context` reports
+pub const FRESHNESS_ENTITY_TYPES: &[&str] = &["business_rule", "architectural_decision", "performance_requirement"];
+
+/// 1.0 at `since`, halving every `half_life_days`
+pub fn freshness(since: DateTime<Utc>, now: DateTime<Utc>, half_life_days: f64) -> f64 {
+    let days = (now - since).num_seconds().max(0) as f64 / 86_400.0;
+    0.5f64.powf(days / half_life_days.max(1.0))
+}
+
+/// How fresh one entity is
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EntityFreshness {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub title: Option<String>,
+    /// None when the entity was never verified and freshness decays from its creation
+    pub last_verified_at: Option<String>,
+    pub verified_by: Option<String>,
+    /// Whole days since the entity was verified, or created
+    pub age_days: i64,
+    pub half_life_days: f64,
+    pub freshness: f64,
+}
+
+impl EntityFreshness {
+    /// None for entities without an id or any timestamp to decay from
+    pub fn of(
+        entity_type: &str,
+        entity: &serde_json::Value,
+        verification: Option<&EntityVerification>,
+        settings: &FreshnessSettings,
+        now: DateTime<Utc>,
+    ) -> Option<Self> {
+        let entity_id = entity.get("id")?.as_str()?;
+        let verified_at = verification.and_then(|verification| parse_timestamp(&verification.verified_at));
+        let since = verified_at.or_else(|| {
+            ["updated_at", "created_at"]
+                .iter()
+                .find_map(|field| entity.get(*field).and_then(|v| v.as_str()).and_then(parse_timestamp))
+        })?;
+        let half_life_days = settings.half_life_of(entity_type);
+        Some(Self {
+            entity_type: entity_type.to_string(),
+            entity_id: entity_id.to_string(),
+            title: entity_title(entity),
+            last_verified_at: verification
+                .filter(|_| verified_at.is_some())
+                .map(|verification| verification.verified_at.clone()),
+            verified_by: verification.and_then(|verification| verification.verified_by.clone()),
+            age_days: (now - since).num_days().max(0),
+            half_life_days,
+            freshness: (freshness(since, now, half_life_days) * 1000.0).round() / 1000.0,
+        })
+    }
+}
+
+/// Entities of a project whose freshness fell below `[freshness] stale_below`, so someone
+/// should confirm they still hold
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StaleContextReport {
+    pub project_id: String,
+    pub stale_below: f64,
+    pub entities_checked: usize,
+    /// Checked entities never verified since they were created
+    pub never_verified: usize,
+    /// Least fresh first
+    pub stale: Vec<EntityFreshness>,
+}
+
+impl StaleContextReport {
+    /// `entities` are `(entity_type, entity)` pairs as the CRUD tools return them, of which
+    /// `FRESHNESS_ENTITY_TYPES` are checked; `stale` holds at most `limit` entities
+    pub fn build(
+        project_id: &str,
+        settings: &FreshnessSettings,
+        now: DateTime<Utc>,
+        entities: &[(&str, serde_json::Value)],
+        verifications: &HashMap<String, EntityVerification>,
+        limit: usize,
+    ) -> Self {
+        let mut report = Self {
+            project_id: project_id.to_string(),
+            stale_below: settings.stale_below,
+            entities_checked: 0,
+            never_verified: 0,
+            stale: Vec::new(),
+        };
+
+        let tracked = entities.iter().filter(|(entity_type, _)| FRESHNESS_ENTITY_TYPES.contains(entity_type));
+        for (entity_type, entity) in tracked {
+            let verification = entity
+                .get("id")
+                .and_then(|id| id.as_str())
+                .and_then(|id| verifications.get(id));
+            let Some(entry) = EntityFreshness::of(entity_type, entity, verification, settings, now) else {
+                continue;
+            };
+            report.entities_checked += 1;
+            if entry.last_verified_at.is_none() {
+                report.never_verified += 1;
+            }
+            if entry.freshness < settings.stale_below {
+                report.stale.push(entry);
+            }
+        }
+
+        report.stale.sort_by(|a, b| {
+            a.freshness
+                .total_cmp(&b.freshness)
+                .then_with(|| a.entity_id.cmp(&b.entity_id))
+        });
+        report.stale.truncate(limit);
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stale_context_report() {
+        let now = Utc::now();
+        let days_ago = |days: i64| (now - chrono::Duration::days(days)).to_rfc3339();
+        let mut settings = FreshnessSettings::default();
+        settings.half_life_by_type.insert("architectural_decision".to_string(), 365.0);
+        let entities = vec![
+            ("business_rule", serde_json::json!({"id": "r-old", "rule_name": "Lock accounts", "created_at": days_ago(400)})),
+            ("business_rule", serde_json::json!({"id": "r-verified", "rule_name": "Refunds", "created_at": days_ago(400)})),
+            ("business_rule", serde_json::json!({"id": "r-new", "rule_name": "Coupons", "created_at": days_ago(10)})),
+            ("architectural_decision", serde_json::json!({"id": "adr", "decision_title": "Postgres", "created_at": days_ago(200)})),
+            ("business_rule", serde_json::json!({"id": "r-undated", "rule_name": "Undated"})),
+            ("framework_component", serde_json::json!({"id": "c", "component_name": "Cart", "created_at": days_ago(900)})),
+        ];
+        let mut verification = EntityVerification::new("p1", "business_rule", "r-verified");
+        verification.verified_at = days_ago(30);
+        verification.verified_by = Some("ide/1".to_string());
+        let verifications = HashMap::from([("r-verified".to_string(), verification)]);
+
+        let report = StaleContextReport::build("p1", &settings, now, &entities, &verifications, 10);
+        assert_eq!((report.entities_checked, report.never_verified), (4, 3));
+        let stale: Vec<&str> = report.stale.iter().map(|entry| entry.entity_id.as_str()).collect();
+        assert_eq!(stale, vec!["r-old"]);
+        assert_eq!(report.stale[0].title.as_deref(), Some("Lock accounts"));
+        assert_eq!(report.stale[0].age_days, 400);
+        assert!((report.stale[0].freshness - 0.5f64.powf(400.0 / 180.0)).abs() < 0.001);
+
+        settings.stale_below = 0.9;
+        let report = StaleContextReport::build("p1", &settings, now, &entities, &verifications, 2);
+        let stale: Vec<&str> = report.stale.iter().map(|entry| entry.entity_id.as_str()).collect();
+        assert_eq!(stale, vec!["r-old", "adr"]);
+        assert!((freshness(now - chrono::Duration::days(180), now, 180.0) - 0.5).abs() < 1e-9);
+    }
+}
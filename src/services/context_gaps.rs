@@ -0,0 +1,286 @@
+use crate::models::specification::ProjectSpecification;
+use crate::services::change_broadcaster::entity_feature_area;
+use crate::services::tag_classifier::{entity_text, tag_name};
+use crate::services::unused_context::entity_title;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// How well the stored context covers a feature area
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GapStatus {
+    /// No business rule, security policy or convention mentions the area
+    Missing,
+    /// Some context, but a kind of it is missing or there are fewer than `min_items`
+    Thin,
+    Covered,
+}
+
+/// An entity naming a feature area
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AreaReference {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub title: Option<String>,
+}
+
+/// A feature area referenced by specifications, components or feature contexts, with the
+/// context documenting it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AreaCoverage {
+    /// Tag-style name, e.g. `user-login`
+    pub feature_area: String,
+    pub status: GapStatus,
+    pub referenced_by: Vec<AreaReference>,
+    pub business_rules: usize,
+    pub security_policies: usize,
+    pub conventions: usize,
+    /// What to document next, most important first
+    pub suggestions: Vec<String>,
+}
+
+impl AreaCoverage {
+    fn total(&self) -> usize {
+        self.business_rules + self.security_policies + self.conventions
+    }
+}
+
+/// Feature areas of a project that its specifications, framework components and feature
+/// contexts reference but its business rules, security policies and conventions barely cover
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContextGapReport {
+    pub project_id: String,
+    pub min_items: usize,
+    pub areas_checked: usize,
+    pub missing: usize,
+    pub thin: usize,
+    /// Conventions naming no area, which apply to all of them
+    pub project_wide_conventions: usize,
+    /// Missing areas first, then thin ones, most referenced first
+    pub gaps: Vec<AreaCoverage>,
+}
+
+impl ContextGapReport {
+    /// `referencing` are the specifications (see `specification_entity`), framework components
+    /// and feature contexts, `documenting` the business rules, security policies and
+    /// conventions, both as `(entity_type, entity)` pairs as the CRUD tools return them.
+    /// `area_tags` maps entity ids to the domain and feature area tags assigned to them.
+    /// `gaps` holds at most `limit` areas.
+    pub fn build(
+        project_id: &str,
+        referencing: &[(&str, serde_json::Value)],
+        documenting: &[(&str, serde_json::Value)],
+        area_tags: &HashMap<String, Vec<String>>,
+        min_items: usize,
+        limit: usize,
+    ) -> Self {
+        let mut areas: BTreeMap<String, AreaCoverage> = BTreeMap::new();
+        for (entity_type, entity) in referencing {
+            let Some(entity_id) = entity.get("id").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            for area in entity_areas(entity_type, entity, area_tags) {
+                areas
+                    .entry(area.clone())
+                    .or_insert_with(|| AreaCoverage {
+                        feature_area: area,
+                        status: GapStatus::Missing,
+                        referenced_by: Vec::new(),
+                        business_rules: 0,
+                        security_policies: 0,
+                        conventions: 0,
+                        suggestions: Vec::new(),
+                    })
+                    .referenced_by
+                    .push(AreaReference {
+                        entity_type: entity_type.to_string(),
+                        entity_id: entity_id.to_string(),
+                        title: entity_title(entity),
+                    });
+            }
+        }
+
+        let mut project_wide_conventions = 0;
+        for (entity_type, entity) in documenting {
+            let mut documented = entity_areas(entity_type, entity, area_tags);
+            if *entity_type == "project_convention" {
+                // Conventions have no area field, so the ones mentioning an area document it
+                let text = format!("-{}-", tag_name(&entity_text(entity)));
+                documented.extend(areas.keys().filter(|area| text.contains(&format!("-{area}-"))).cloned());
+                if documented.is_empty() {
+                    project_wide_conventions += 1;
+                }
+            }
+            for area in documented {
+                let Some(coverage) = areas.get_mut(&area) else {
+                    continue;
+                };
+                match *entity_type {
+                    "business_rule" => coverage.business_rules += 1,
+                    "security_policy" => coverage.security_policies += 1,
+                    "project_convention" => coverage.conventions += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        let mut report = Self {
+            project_id: project_id.to_string(),
+            min_items,
+            areas_checked: areas.len(),
+            missing: 0,
+            thin: 0,
+            project_wide_conventions,
+            gaps: Vec::new(),
+        };
+        for mut coverage in areas.into_values() {
+            coverage.suggestions = suggestions(&coverage, min_items);
+            coverage.status = if coverage.total() == 0 {
+                report.missing += 1;
+                GapStatus::Missing
+            } else if coverage.suggestions.is_empty() {
+                GapStatus::Covered
+            } else {
+                report.thin += 1;
+                GapStatus::Thin
+            };
+            if coverage.status != GapStatus::Covered {
+                report.gaps.push(coverage);
+            }
+        }
+
+        report.gaps.sort_by(|a, b| {
+            a.status
+                .cmp(&b.status)
+                .then_with(|| b.referenced_by.len().cmp(&a.referenced_by.len()))
+                .then_with(|| a.feature_area.cmp(&b.feature_area))
+        });
+        report.gaps.truncate(limit);
+        report
+    }
+}
+
+/// A specification as an entity whose feature area is its Kiro feature directory
+/// (`.kiro/specs/<feature>/requirements.md`), or else its title
+pub fn specification_entity(spec: &ProjectSpecification) -> serde_json::Value {
+    let feature = spec
+        .file_path
+        .as_deref()
+        .and_then(|path| {
+            let mut parents = std::path::Path::new(path).ancestors().skip(1);
+            let feature = parents.next()?;
+            (parents.next()?.file_name()? == "specs").then(|| feature.file_name()?.to_str())?
+        })
+        .unwrap_or(&spec.title);
+    serde_json::json!({
+        "id": spec.id,
+        "title": spec.title,
+        "feature_area": feature
+    })
+}
+
+/// The feature areas an entity names in its fields (a component's in its metadata) or
+/// carries as tags, as tag names
+fn entity_areas(
+    entity_type: &str,
+    entity: &serde_json::Value,
+    area_tags: &HashMap<String, Vec<String>>,
+) -> BTreeSet<String> {
+    let mut areas: BTreeSet<String> = area_tags
+        .get(entity.get("id").and_then(|v| v.as_str()).unwrap_or_default())
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect();
+    let fields = if entity_type == "framework_component" { entity.get("metadata") } else { Some(entity) };
+    areas.extend(fields.and_then(|fields| entity_feature_area(entity_type, fields)).map(|area| tag_name(&area)));
+    areas.retain(|area| !area.is_empty());
+    areas
+}
+
+/// What an area lacks: business rules first, then security policies, conventions and, for
+/// areas with each kind, more context up to `min_items`
+fn suggestions(coverage: &AreaCoverage, min_items: usize) -> Vec<String> {
+    let area = &coverage.feature_area;
+    let referenced = match coverage.referenced_by.len() {
+        1 => "1 specification, component or feature context".to_string(),
+        count => format!("{count} specifications, components and feature contexts"),
+    };
+    let mut suggestions = Vec::new();
+    if coverage.business_rules == 0 {
+        suggestions.push(format!(
+            "Document the business rules of '{area}' (domain_area \"{area}\"), which {referenced} reference"
+        ));
+    }
+    if coverage.security_policies == 0 {
+        suggestions.push(format!(
+            "Add a security policy for '{area}' (policy_area \"{area}\") covering the data and access it handles"
+        ));
+    }
+    if coverage.conventions == 0 {
+        suggestions.push(format!("Record the conventions code in '{area}' follows, mentioning the area"));
+    }
+    if suggestions.is_empty() && coverage.total() < min_items {
+        suggestions.push(format!(
+            "Expand the context of '{area}': {} of {min_items} rules, policies and conventions",
+            coverage.total()
+        ));
+    }
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::specification::{SpecContent, SpecFormat, SpecType};
+
+    #[test]
+    fn test_context_gap_report() {
+        let mut spec = ProjectSpecification::new(
+            "login".to_string(),
+            SpecType::Requirements,
+            "Requirements Document".to_string(),
+            SpecContent::new(SpecFormat::Markdown, String::new()),
+        );
+        spec.file_path = Some(".kiro/specs/user-login/requirements.md".to_string());
+        let referencing = vec![
+            ("specification", specification_entity(&spec)),
+            ("framework_component", serde_json::json!({"id": "c1", "component_name": "LoginForm", "metadata": {"feature_area": "User Login"}})),
+            ("framework_component", serde_json::json!({"id": "c2", "component_name": "Cart"})),
+            ("feature_context", serde_json::json!({"id": "f1", "feature_name": "Payments"})),
+            ("feature_context", serde_json::json!({"id": "f2", "feature_name": "Search"})),
+        ];
+        let documenting = vec![
+            ("business_rule", serde_json::json!({"id": "r1", "rule_name": "Lockout", "domain_area": "user-login"})),
+            ("business_rule", serde_json::json!({"id": "r2", "rule_name": "Refunds", "domain_area": "payments"})),
+            ("business_rule", serde_json::json!({"id": "r3", "rule_name": "Coupons"})),
+            ("security_policy", serde_json::json!({"id": "s1", "policy_name": "PCI", "policy_area": "Payments"})),
+            ("project_convention", serde_json::json!({"id": "v1", "convention_rule": "Payments amounts are integer cents"})),
+            ("project_convention", serde_json::json!({"id": "v2", "convention_rule": "Use snake_case"})),
+        ];
+        let area_tags = HashMap::from([
+            ("c2".to_string(), vec!["checkout".to_string()]),
+            ("r3".to_string(), vec!["checkout".to_string()]),
+        ]);
+
+        let report = ContextGapReport::build("p1", &referencing, &documenting, &area_tags, 3, 10);
+        assert_eq!((report.areas_checked, report.missing, report.thin), (4, 1, 2));
+        assert_eq!(report.project_wide_conventions, 1);
+        let gaps: Vec<(&str, GapStatus)> =
+            report.gaps.iter().map(|gap| (gap.feature_area.as_str(), gap.status)).collect();
+        assert_eq!(
+            gaps,
+            vec![("search", GapStatus::Missing), ("user-login", GapStatus::Thin), ("checkout", GapStatus::Thin)]
+        );
+        let login = &report.gaps[1];
+        assert_eq!(login.referenced_by.len(), 2);
+        assert_eq!((login.business_rules, login.security_policies, login.conventions), (1, 0, 0));
+        assert_eq!(login.suggestions.len(), 2);
+        assert!(report.gaps[0].suggestions[0].starts_with("Document the business rules of 'search'"));
+
+        let report = ContextGapReport::build("p1", &referencing, &documenting, &area_tags, 4, 10);
+        let payments = report.gaps.iter().find(|gap| gap.feature_area == "payments").unwrap();
+        assert_eq!(payments.status, GapStatus::Thin);
+        assert!(payments.suggestions[0].contains("3 of 4"));
+    }
+}
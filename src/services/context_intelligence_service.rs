@@ -1,12 +1,29 @@
+use crate::config::ConfigManager;
+use crate::models::context_digest::ContextDigest;
 use crate::models::enhanced_context::*;
+use crate::models::tagging::{ContextTag, TagSuggestion, TagSuggestionStatus, TaggedEntity};
+use crate::repositories::{ContextDigestRepository, FeatureContextRepository, TagRepository};
+use crate::services::digest_generator::DigestGenerator;
+use crate::services::embedding_service::EmbeddingService;
+use crate::services::tag_classifier::{entity_text, field_tags, TagClassifier, DOMAIN_AREA, FEATURE_AREA};
 use crate::services::{
     ContextRelationshipEngine, DefaultContextRelationshipEngine,
-    ContextQualityService, DefaultContextQualityService,
+    ContextQualityService, DefaultContextQualityService, ContextQueryService,
 };
 use async_trait::async_trait;
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use chrono::{DateTime, Utc};
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
+
+/// Estimated tokens of context a digest is written from, best ranked first
+const DIGEST_SOURCE_TOKENS: usize = 6000;
+
+const DIGEST_SYSTEM_PROMPT: &str = "You brief software engineers and coding agents on one feature area of a project. \
+Summarize the business rules, architectural decisions, performance requirements and feature description you are given \
+as a few paragraphs of plain prose: what the feature is for, the rules and constraints code must respect, and the \
+decisions that shape it. Keep names, numbers and limits exact, mention conflicts between items, and add nothing that \
+isn't in the material.";
 
 /// Service that orchestrates context intelligence features
 #[async_trait]
@@ -23,8 +40,51 @@ pub trait ContextIntelligenceService: Send + Sync {
     /// Analyze context gaps and suggest missing information
     async fn analyze_context_gaps(&self, project_contexts: &[EnhancedContextItem]) -> Result<Vec<ContextGap>>;
     
+    /// Items within `max_hops` of `context_id` through the relationships recorded on `contexts`
+    /// and, with `include_inferred`, those the relationship engine infers between them,
+    /// strongest first
+    async fn find_related_context(&self, context_id: &str, contexts: &[EnhancedContextItem], max_hops: usize, include_inferred: bool) -> Result<Vec<RelatedContext>>;
+
     /// Generate context insights for a project
     async fn generate_project_insights(&self, project_id: &str, contexts: &[EnhancedContextItem]) -> Result<ProjectContextInsights>;
+
+    /// Summarize a feature area's context with `generator` and store the digest. A digest
+    /// whose entities haven't changed is returned as stored unless `force` is set.
+    async fn generate_context_digest(&self, project_id: &str, feature_area: &str, generator: &dyn DigestGenerator, force: bool) -> Result<ContextDigest>;
+
+    /// Stored digests of a project, or of one of its feature areas
+    async fn get_context_digests(&self, project_id: &str, feature_area: Option<&str>) -> Result<Vec<ContextDigest>>;
+
+    /// Mark the digests that summarize a changed entity stale, and those of the entity's
+    /// feature area in its project (catching entities new to the area), and return them
+    async fn mark_digests_stale(&self, entity_id: &str, project_id: &str, feature_area: Option<&str>) -> Result<Vec<ContextDigest>>;
+
+    /// Classify a created or updated entity into domain area, layer and feature area tags.
+    /// Suggestions at least `[tagging] auto_apply_threshold` confident are applied, the rest
+    /// wait for review; reviewed suggestions are not made again.
+    async fn suggest_tags(&self, project_id: &str, entity_type: &str, entity: &serde_json::Value) -> Result<Vec<TagSuggestion>>;
+
+    /// Tag suggestions of a project, most confident first
+    async fn get_tag_suggestions(&self, project_id: &str, status: Option<TagSuggestionStatus>) -> Result<Vec<TagSuggestion>>;
+
+    /// Apply (`accept`) or reject suggested tags; rejecting an applied tag removes it
+    async fn review_tag_suggestions(&self, suggestion_ids: &[String], accept: bool) -> Result<Vec<TagSuggestion>>;
+}
+
+/// Where digests are stored and what they are written from
+struct DigestSources {
+    repository: Arc<dyn ContextDigestRepository>,
+    context_query_service: Arc<dyn ContextQueryService>,
+    feature_context_repository: Arc<dyn FeatureContextRepository>,
+    config: Arc<ConfigManager>,
+}
+
+/// Where tags and suggestions are stored and what entities are classified against
+struct TaggingSources {
+    tag_repository: Arc<dyn TagRepository>,
+    feature_context_repository: Arc<dyn FeatureContextRepository>,
+    embedding_service: Option<Arc<dyn EmbeddingService>>,
+    config: Arc<ConfigManager>,
 }
 
 /// Default implementation of the Context Intelligence Service
@@ -33,6 +93,8 @@ pub struct DefaultContextIntelligenceService {
     quality_service: Box<dyn ContextQualityService>,
     suggestion_generators: Vec<Box<dyn SuggestionGenerator>>,
     gap_analyzers: Vec<Box<dyn GapAnalyzer>>,
+    digests: Option<DigestSources>,
+    tagging: Option<TaggingSources>,
 }
 
 impl DefaultContextIntelligenceService {
@@ -50,6 +112,8 @@ impl DefaultContextIntelligenceService {
                 Box::new(RelationshipGapAnalyzer::new()),
                 Box::new(QualityGapAnalyzer::new()),
             ],
+            digests: None,
+            tagging: None,
         }
     }
 
@@ -62,6 +126,97 @@ impl DefaultContextIntelligenceService {
         self.quality_service = service;
         self
     }
+
+    /// Enable context digests, written from `query_context` results and feature contexts
+    /// with `[digests]` settings and stored in `repository`
+    pub fn with_digests(
+        mut self,
+        repository: Arc<dyn ContextDigestRepository>,
+        context_query_service: Arc<dyn ContextQueryService>,
+        feature_context_repository: Arc<dyn FeatureContextRepository>,
+        config: Arc<ConfigManager>,
+    ) -> Self {
+        self.digests = Some(DigestSources {
+            repository,
+            context_query_service,
+            feature_context_repository,
+            config,
+        });
+        self
+    }
+
+    /// Enable tag suggestions, classified with `[tagging]` settings by keyword and, given an
+    /// `embedding_service`, by embedding
+    pub fn with_tagging(
+        mut self,
+        tag_repository: Arc<dyn TagRepository>,
+        feature_context_repository: Arc<dyn FeatureContextRepository>,
+        embedding_service: Option<Arc<dyn EmbeddingService>>,
+        config: Arc<ConfigManager>,
+    ) -> Self {
+        self.tagging = Some(TaggingSources {
+            tag_repository,
+            feature_context_repository,
+            embedding_service,
+            config,
+        });
+        self
+    }
+
+    fn tagging_sources(&self) -> Result<&TaggingSources> {
+        self.tagging.as_ref().ok_or_else(|| anyhow!("Tag suggestions are not configured"))
+    }
+
+    /// Tag the suggestion's entity with its tag, creating the tag in the project if needed
+    async fn apply_tag(&self, sources: &TaggingSources, suggestion: &TagSuggestion) -> Result<()> {
+        let tag = ContextTag::new(suggestion.project_id.clone(), suggestion.tag_name.clone(), suggestion.category.clone());
+        let tag = sources.tag_repository.find_or_create_tag(&tag).await?;
+        let tagged = TaggedEntity::new(
+            suggestion.project_id.clone(),
+            suggestion.entity_id.clone(),
+            suggestion.entity_type.clone(),
+            tag.id,
+        );
+        sources.tag_repository.tag_entity(&tagged).await?;
+        Ok(())
+    }
+
+    fn digest_sources(&self) -> Result<&DigestSources> {
+        self.digests.as_ref().ok_or_else(|| anyhow!("Context digests are not configured"))
+    }
+
+    /// The entities of a feature area a digest summarizes, as `(entity_type, entity)` pairs
+    /// ordered by id: context `query_context` ranks as relevant to the area, within
+    /// `DIGEST_SOURCE_TOKENS`, and the feature context of that name
+    async fn digest_material(&self, sources: &DigestSources, project_id: &str, feature_area: &str) -> Result<Vec<(String, serde_json::Value)>> {
+        let context = sources
+            .context_query_service
+            .query_context(project_id, feature_area, "summarize", &[], Some(DIGEST_SOURCE_TOKENS))
+            .await?;
+        let relevant: HashSet<&str> = context
+            .ranking
+            .iter()
+            .filter(|item| item.relevance > 0.0)
+            .map(|item| item.id.as_str())
+            .collect();
+
+        let mut material = Vec::new();
+        for rule in context.business_rules.iter().filter(|rule| relevant.contains(rule.id.as_str())) {
+            material.push(("business_rule".to_string(), serde_json::to_value(rule)?));
+        }
+        for decision in context.architectural_decisions.iter().filter(|decision| relevant.contains(decision.id.as_str())) {
+            material.push(("architectural_decision".to_string(), serde_json::to_value(decision)?));
+        }
+        for requirement in context.performance_requirements.iter().filter(|requirement| relevant.contains(requirement.id.as_str())) {
+            material.push(("performance_requirement".to_string(), serde_json::to_value(requirement)?));
+        }
+        if let Some(feature) = sources.feature_context_repository.get_by_feature_name(project_id, feature_area).await? {
+            material.push(("feature_context".to_string(), serde_json::to_value(feature)?));
+        }
+
+        material.sort_by(|(_, a), (_, b)| a["id"].as_str().cmp(&b["id"].as_str()));
+        Ok(material)
+    }
 }
 
 #[async_trait]
@@ -202,6 +357,28 @@ impl ContextIntelligenceService for DefaultContextIntelligenceService {
         Ok(gaps)
     }
 
+    async fn find_related_context(&self, context_id: &str, contexts: &[EnhancedContextItem], max_hops: usize, include_inferred: bool) -> Result<Vec<RelatedContext>> {
+        let context_id = context_id.to_string();
+        let mut graph = if include_inferred {
+            self.relationship_engine.build_relationship_graph(contexts).await?
+        } else {
+            let mut graph = RelationshipGraph::new();
+            for context in contexts {
+                graph.add_node(context.id.clone(), context.clone());
+            }
+            graph
+        };
+        if graph.get_node(&context_id).is_none() {
+            bail!("Entity {} not found", context_id);
+        }
+        for context in contexts {
+            for relationship in &context.relationships {
+                graph.add_edge(context.id.clone(), relationship.clone());
+            }
+        }
+        Ok(graph.neighborhood(&context_id, max_hops))
+    }
+
     async fn generate_project_insights(&self, project_id: &str, contexts: &[EnhancedContextItem]) -> Result<ProjectContextInsights> {
         let project_contexts: Vec<_> = contexts.iter()
             .filter(|c| c.project_id == project_id)
@@ -296,6 +473,186 @@ impl ContextIntelligenceService for DefaultContextIntelligenceService {
             generated_at: Utc::now(),
         })
     }
+
+    async fn generate_context_digest(&self, project_id: &str, feature_area: &str, generator: &dyn DigestGenerator, force: bool) -> Result<ContextDigest> {
+        let sources = self.digest_sources()?;
+        // Areas match ignoring case; keep the spelling of a stored digest
+        let existing = sources.repository.find(project_id, feature_area.trim()).await?;
+        let feature_area = existing
+            .as_ref()
+            .map_or_else(|| feature_area.trim().to_string(), |digest| digest.feature_area.clone());
+        let feature_area = feature_area.as_str();
+        let material = self.digest_material(sources, project_id, feature_area).await?;
+        if material.is_empty() {
+            bail!("No context relevant to feature area '{}' in project {}", feature_area, project_id);
+        }
+        let source_hash = format!("{:x}", md5::compute(serde_json::to_string(&material)?));
+
+        if let Some(mut digest) = existing.clone().filter(|digest| !force && digest.source_hash == source_hash) {
+            if digest.stale {
+                sources.repository.set_stale(&digest.id, false).await?;
+                digest.stale = false;
+            }
+            return Ok(digest);
+        }
+
+        let prompt: String = std::iter::once(format!("Feature area: {}\n", feature_area))
+            .chain(material.iter().map(|(entity_type, entity)| format!("\n[{}] {}", entity_type, entity)))
+            .collect();
+        let max_tokens = sources.config.current().digests.max_tokens;
+        let generated = generator.generate(DIGEST_SYSTEM_PROMPT, &prompt, max_tokens).await?;
+
+        let digest = ContextDigest {
+            id: existing.map_or_else(|| uuid::Uuid::new_v4().to_string(), |digest| digest.id),
+            project_id: project_id.to_string(),
+            feature_area: feature_area.to_string(),
+            summary: generated.text,
+            generated_by: format!("{}:{}", generator.kind(), generated.model),
+            entity_ids: material
+                .iter()
+                .filter_map(|(_, entity)| entity["id"].as_str().map(str::to_string))
+                .collect(),
+            source_hash,
+            stale: false,
+            generated_at: Utc::now().to_rfc3339(),
+        };
+        sources.repository.save(&digest).await?;
+        Ok(digest)
+    }
+
+    async fn get_context_digests(&self, project_id: &str, feature_area: Option<&str>) -> Result<Vec<ContextDigest>> {
+        let sources = self.digest_sources()?;
+        Ok(match feature_area {
+            Some(feature_area) => sources.repository.find(project_id, feature_area.trim()).await?.into_iter().collect(),
+            None => sources.repository.list_by_project(project_id).await?,
+        })
+    }
+
+    async fn mark_digests_stale(&self, entity_id: &str, project_id: &str, feature_area: Option<&str>) -> Result<Vec<ContextDigest>> {
+        let sources = self.digest_sources()?;
+        let mut affected = sources.repository.list_containing(entity_id).await?;
+        if let Some(feature_area) = feature_area.filter(|area| !area.trim().is_empty()) {
+            if let Some(digest) = sources.repository.find(project_id, feature_area.trim()).await? {
+                if !affected.iter().any(|known| known.id == digest.id) {
+                    affected.push(digest);
+                }
+            }
+        }
+
+        for digest in affected.iter_mut().filter(|digest| !digest.stale) {
+            sources.repository.set_stale(&digest.id, true).await?;
+            digest.stale = true;
+        }
+        Ok(affected)
+    }
+
+    async fn suggest_tags(&self, project_id: &str, entity_type: &str, entity: &serde_json::Value) -> Result<Vec<TagSuggestion>> {
+        let sources = self.tagging_sources()?;
+        let settings = sources.config.current().tagging;
+        if !settings.enabled {
+            return Ok(Vec::new());
+        }
+        let entity_id = entity
+            .get("id")
+            .and_then(|id| id.as_str())
+            .ok_or_else(|| anyhow!("The {} has no id", entity_type))?;
+
+        // Classify against the areas the project already tags with, and its features
+        let tags = sources.tag_repository.list_tags(project_id).await?;
+        let tagged_areas = |category: &str| -> Vec<String> {
+            tags.iter().filter(|tag| tag.category == category).map(|tag| tag.tag_name.clone()).collect()
+        };
+        let mut feature_areas = tagged_areas(FEATURE_AREA);
+        feature_areas.extend(
+            sources
+                .feature_context_repository
+                .list_by_project(project_id)
+                .await?
+                .into_iter()
+                .map(|feature| feature.feature_name),
+        );
+        let threshold = settings.auto_apply_threshold;
+        let classifier = TagClassifier::new(settings, sources.embedding_service.clone());
+        let labels = classifier.labels(&tagged_areas(DOMAIN_AREA), &feature_areas);
+
+        // Fields naming a tag outright take precedence over classification
+        let mut candidates = field_tags(entity);
+        for candidate in classifier.classify(&entity_text(entity), &labels).await {
+            if !candidates.iter().any(|known| known.category == candidate.category) {
+                candidates.push(candidate);
+            }
+        }
+
+        let existing = sources.tag_repository.find_entity_suggestions(entity_id).await?;
+        let mut suggested = Vec::new();
+        for candidate in candidates {
+            let previous = existing.iter().find(|known| known.tag_name == candidate.name);
+            if previous.is_some_and(|known| known.status != TagSuggestionStatus::Pending) {
+                continue;
+            }
+            let suggestion = TagSuggestion {
+                id: previous.map_or_else(|| uuid::Uuid::new_v4().to_string(), |known| known.id.clone()),
+                project_id: project_id.to_string(),
+                entity_id: entity_id.to_string(),
+                entity_type: entity_type.to_string(),
+                tag_name: candidate.name,
+                category: candidate.category,
+                confidence: candidate.confidence,
+                sources: candidate.sources,
+                status: if candidate.confidence >= threshold {
+                    TagSuggestionStatus::Applied
+                } else {
+                    TagSuggestionStatus::Pending
+                },
+                suggested_at: Utc::now().to_rfc3339(),
+                reviewed_at: None,
+            };
+            if suggestion.status == TagSuggestionStatus::Applied {
+                self.apply_tag(sources, &suggestion).await?;
+            }
+            sources.tag_repository.save_suggestion(&suggestion).await?;
+            suggested.push(suggestion);
+        }
+
+        // Pending suggestions the entity no longer supports
+        for outdated in existing
+            .iter()
+            .filter(|known| known.status == TagSuggestionStatus::Pending && !suggested.iter().any(|s| s.id == known.id))
+        {
+            sources.tag_repository.delete_suggestion(&outdated.id).await?;
+        }
+        Ok(suggested)
+    }
+
+    async fn get_tag_suggestions(&self, project_id: &str, status: Option<TagSuggestionStatus>) -> Result<Vec<TagSuggestion>> {
+        let sources = self.tagging_sources()?;
+        Ok(sources.tag_repository.find_suggestions(project_id, status).await?)
+    }
+
+    async fn review_tag_suggestions(&self, suggestion_ids: &[String], accept: bool) -> Result<Vec<TagSuggestion>> {
+        let sources = self.tagging_sources()?;
+        let mut reviewed = Vec::new();
+        for id in suggestion_ids {
+            let Some(mut suggestion) = sources.tag_repository.find_suggestion(id).await? else {
+                bail!("Tag suggestion {} not found", id);
+            };
+            if accept {
+                self.apply_tag(sources, &suggestion).await?;
+                suggestion.status = TagSuggestionStatus::Accepted;
+            } else {
+                if matches!(suggestion.status, TagSuggestionStatus::Applied | TagSuggestionStatus::Accepted) {
+                    if let Some(tag) = sources.tag_repository.find_tag(&suggestion.project_id, &suggestion.tag_name).await? {
+                        sources.tag_repository.untag_entity(&suggestion.entity_id, &tag.id).await?;
+                    }
+                }
+                suggestion.status = TagSuggestionStatus::Rejected;
+            }
+            suggestion.reviewed_at = Some(Utc::now().to_rfc3339());
+            sources.tag_repository.save_suggestion(&suggestion).await?;
+            reviewed.push(suggestion);
+        }
+        Ok(reviewed)
+    }
 }
 
 /// Comprehensive intelligence analysis for a context item
@@ -755,11 +1112,17 @@ fn calculate_text_similarity(text1: &str, text2: &str) -> f64 {
 
 // Import the missing types from context_quality_service
 use crate::services::context_quality_service::ValidationResult;
-use crate::services::context_relationship_engine::RelatedContext;
+use crate::services::context_relationship_engine::{RelatedContext, RelationshipGraph};
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::AppConfig;
+    use crate::infrastructure::{
+        SqliteArchitecturalDecisionRepository, SqliteBusinessRuleRepository, SqliteContextDigestRepository,
+        SqliteFeatureContextRepository, SqlitePerformanceRequirementRepository, SqliteTagRepository,
+    };
+    use crate::services::digest_generator::GeneratedText;
     use serde_json::json;
 
     fn create_test_context(id: &str, project_id: &str, content_type: ContextType, title: &str, description: &str) -> EnhancedContextItem {
@@ -901,4 +1264,138 @@ mod tests {
         assert_eq!(query.tags, vec!["auth".to_string()]);
         assert_eq!(query.max_results, 5);
     }
-}
\ No newline at end of file
+
+    struct FakeGenerator {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl DigestGenerator for FakeGenerator {
+        async fn generate(&self, _system_prompt: &str, prompt: &str, _max_tokens: u32) -> Result<GeneratedText> {
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            Ok(GeneratedText {
+                text: format!("Digest {call} of {} lines", prompt.lines().count()),
+                model: "fake-1".to_string(),
+            })
+        }
+
+        fn kind(&self) -> &'static str {
+            "llm"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_context_digest_lifecycle() {
+        let conn = crate::db::init::init_db(":memory:").unwrap();
+        conn.execute_batch(
+            "INSERT INTO projects (id, name) VALUES ('p1', 'Shop');
+             INSERT INTO business_rules (id, project_id, rule_name, description, domain_area)
+                 VALUES ('rule-capture', 'p1', 'Capture on shipping', 'Card payments are captured when the order ships', 'payments');
+             INSERT INTO business_rules (id, project_id, rule_name, domain_area)
+                 VALUES ('rule-login', 'p1', 'Lock accounts', 'auth');
+             INSERT INTO feature_context (id, project_id, feature_name, business_purpose)
+                 VALUES ('feature-payments', 'p1', 'payments', 'Take card payments');",
+        )
+        .unwrap();
+        let db = Arc::new(std::sync::Mutex::new(conn));
+        let query_service = crate::services::context_query_service::ContextQueryServiceImpl::new(
+            SqliteBusinessRuleRepository::new(db.clone()),
+            SqliteArchitecturalDecisionRepository::new(db.clone()),
+            SqlitePerformanceRequirementRepository::new(db.clone()),
+        );
+        let service = DefaultContextIntelligenceService::new().with_digests(
+            Arc::new(SqliteContextDigestRepository::new(db.clone())),
+            Arc::new(query_service),
+            Arc::new(SqliteFeatureContextRepository::new(db.clone())),
+            Arc::new(ConfigManager::new(AppConfig::default(), None)),
+        );
+        let generator = FakeGenerator { calls: Default::default() };
+
+        let digest = service.generate_context_digest("p1", " payments ", &generator, false).await.unwrap();
+        assert_eq!(digest.feature_area, "payments");
+        assert_eq!(digest.entity_ids, vec!["feature-payments", "rule-capture"]);
+        assert_eq!(digest.generated_by, "llm:fake-1");
+        assert!(digest.summary.starts_with("Digest 1"));
+
+        // Unchanged entities reuse the stored digest unless forced
+        let again = service.generate_context_digest("p1", "Payments", &generator, false).await.unwrap();
+        assert_eq!((again.id.as_str(), again.summary.as_str()), (digest.id.as_str(), digest.summary.as_str()));
+        let forced = service.generate_context_digest("p1", "payments", &generator, true).await.unwrap();
+        assert_eq!(forced.id, digest.id);
+        assert!(forced.summary.starts_with("Digest 2"));
+
+        // Only digests summarizing the entity, or its area, go stale
+        assert!(service.mark_digests_stale("rule-login", "p1", Some("auth")).await.unwrap().is_empty());
+        let stale = service.mark_digests_stale("rule-new", "p1", Some("PAYMENTS")).await.unwrap();
+        assert_eq!(stale.len(), 1);
+        db.lock()
+            .unwrap()
+            .execute("UPDATE business_rules SET description = 'Captured within a day of shipping' WHERE id = 'rule-capture'", [])
+            .unwrap();
+        let stored = service.get_context_digests("p1", None).await.unwrap();
+        assert!(stored[0].stale);
+
+        let refreshed = service.generate_context_digest("p1", "payments", &generator, false).await.unwrap();
+        assert!(refreshed.summary.starts_with("Digest 3"));
+        assert!(!service.get_context_digests("p1", Some("payments")).await.unwrap()[0].stale);
+        assert!(service.generate_context_digest("p1", "billing", &generator, false).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tag_suggestions() {
+        let conn = crate::db::init::init_db(":memory:").unwrap();
+        conn.execute_batch(
+            "INSERT INTO projects (id, name) VALUES ('p1', 'Shop');
+             INSERT INTO feature_context (id, project_id, feature_name) VALUES ('f1', 'p1', 'Checkout');",
+        )
+        .unwrap();
+        let db = Arc::new(std::sync::Mutex::new(conn));
+        let mut config = AppConfig::default();
+        config.tagging.domain_areas.insert("billing".to_string(), vec!["invoice".to_string()]);
+        let tags = Arc::new(SqliteTagRepository::new(db.clone()));
+        let service = DefaultContextIntelligenceService::new().with_tagging(
+            tags.clone(),
+            Arc::new(SqliteFeatureContextRepository::new(db.clone())),
+            None,
+            Arc::new(ConfigManager::new(config, None)),
+        );
+
+        // A field naming the domain area is applied; a two-keyword layer match waits for review
+        let rule = json!({
+            "id": "r1",
+            "project_id": "p1",
+            "rule_name": "Capture on shipping",
+            "description": "The capture endpoint rejects requests for unshipped orders",
+            "domain_area": "Payments",
+        });
+        let suggested = service.suggest_tags("p1", "business_rule", &rule).await.unwrap();
+        let status_of = |name: &str| suggested.iter().find(|s| s.tag_name == name).map(|s| s.status);
+        assert_eq!(status_of("payments"), Some(TagSuggestionStatus::Applied));
+        assert_eq!(status_of("api"), Some(TagSuggestionStatus::Pending));
+        let payments = tags.find_tag("p1", "payments").await.unwrap().unwrap();
+        assert_eq!(payments.category, "domain_area");
+        assert_eq!(tags.find_tagged_entities(&payments.id).await.unwrap().len(), 1);
+
+        let pending = service.get_tag_suggestions("p1", Some(TagSuggestionStatus::Pending)).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        let accepted = service.review_tag_suggestions(&[pending[0].id.clone()], true).await.unwrap();
+        assert_eq!(accepted[0].status, TagSuggestionStatus::Accepted);
+        let applied_id = suggested.iter().find(|s| s.tag_name == "payments").unwrap().id.clone();
+        service.review_tag_suggestions(&[applied_id], false).await.unwrap();
+        assert!(tags.find_tagged_entities(&payments.id).await.unwrap().is_empty());
+        // Reviewed suggestions are not made again
+        assert!(service.suggest_tags("p1", "business_rule", &rule).await.unwrap().is_empty());
+
+        // Known features and configured areas are matched by name and keyword
+        let mut decision = json!({"id": "d1", "project_id": "p1", "title": "Checkout sends an invoice by email"});
+        let suggested = service.suggest_tags("p1", "architectural_decision", &decision).await.unwrap();
+        let names: HashSet<&str> = suggested.iter().map(|s| s.tag_name.as_str()).collect();
+        assert_eq!(names, HashSet::from(["checkout", "billing"]));
+        assert!(suggested.iter().all(|s| s.status == TagSuggestionStatus::Pending));
+        // Suggestions the entity no longer supports are withdrawn
+        decision["title"] = json!("Send receipts by email");
+        assert!(service.suggest_tags("p1", "architectural_decision", &decision).await.unwrap().is_empty());
+        assert_eq!(service.get_tag_suggestions("p1", None).await.unwrap().len(), 2);
+        assert!(service.review_tag_suggestions(&["missing".to_string()], true).await.is_err());
+    }
+}
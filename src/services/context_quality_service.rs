@@ -1,9 +1,16 @@
+use crate::config::ConfigManager;
 use crate::models::enhanced_context::*;
+use crate::models::quality_rubric::{QualityRubric, RubricCheck};
+use crate::repositories::{QualityRubricRepository, SpecificationRepository};
 use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use anyhow::Result;
 
+/// Prefix of the `field` of quality issues raised by rubric checks
+const RUBRIC_FIELD_PREFIX: &str = "rubric.";
+
 /// Service for evaluating and improving context quality
 #[async_trait]
 pub trait ContextQualityService: Send + Sync {
@@ -21,6 +28,19 @@ pub trait ContextQualityService: Send + Sync {
     
     /// Get quality rules for a specific context type
     fn get_validation_rules(&self, context_type: &ContextType) -> Vec<ValidationRule>;
+
+    /// The rubric a project's context is scored against: its own, or the `[quality]` one
+    async fn rubric(&self, project_id: &str) -> Result<QualityRubric>;
+
+    /// Check a context item against its project's rubric
+    async fn check_rubric(&self, context: &EnhancedContextItem) -> Result<Vec<RubricCheck>>;
+}
+
+/// Where project rubrics come from
+struct RubricSources {
+    repository: Arc<dyn QualityRubricRepository>,
+    specification_repository: Arc<dyn SpecificationRepository>,
+    config: Arc<ConfigManager>,
 }
 
 /// Default implementation of the Context Quality Service
@@ -28,6 +48,7 @@ pub struct DefaultContextQualityService {
     quality_analyzers: Vec<Box<dyn QualityAnalyzer>>,
     validation_rules: HashMap<ContextType, Vec<ValidationRule>>,
     improvement_generators: Vec<Box<dyn ImprovementGenerator>>,
+    rubrics: Option<RubricSources>,
 }
 
 impl DefaultContextQualityService {
@@ -45,13 +66,31 @@ impl DefaultContextQualityService {
                 Box::new(CompletenessImprover::new()),
                 Box::new(ConsistencyImprover::new()),
                 Box::new(ContentImprover::new()),
+                Box::new(RubricImprover::new()),
             ],
+            rubrics: None,
         };
         
         service.initialize_validation_rules();
         service
     }
 
+    /// Score completeness against project rubrics stored in `repository`, falling back to
+    /// `[quality]`; requirement links come from `specification_repository`
+    pub fn with_rubrics(
+        mut self,
+        repository: Arc<dyn QualityRubricRepository>,
+        specification_repository: Arc<dyn SpecificationRepository>,
+        config: Arc<ConfigManager>,
+    ) -> Self {
+        self.rubrics = Some(RubricSources {
+            repository,
+            specification_repository,
+            config,
+        });
+        self
+    }
+
     fn initialize_validation_rules(&mut self) {
         // Business Rule validation rules
         self.validation_rules.insert(
@@ -216,11 +255,18 @@ impl DefaultContextQualityService {
 impl ContextQualityService for DefaultContextQualityService {
     async fn calculate_quality_metrics(&self, context: &EnhancedContextItem, related_contexts: &[EnhancedContextItem]) -> Result<QualityMetrics> {
         let mut metrics = QualityMetrics::new();
+        let checks = self.check_rubric(context).await?;
         
-        // Run all quality analyzers
+        // Run all quality analyzers; a rubric replaces the completeness heuristics
         for analyzer in &self.quality_analyzers {
+            if !checks.is_empty() && analyzer.replaced_by_rubric() {
+                continue;
+            }
             analyzer.analyze(context, related_contexts, &mut metrics).await?;
         }
+        if !checks.is_empty() {
+            apply_rubric_checks(&checks, &mut metrics);
+        }
         
         // Calculate overall score
         metrics.calculate_overall_score();
@@ -316,6 +362,49 @@ impl ContextQualityService for DefaultContextQualityService {
     fn get_validation_rules(&self, context_type: &ContextType) -> Vec<ValidationRule> {
         self.validation_rules.get(context_type).cloned().unwrap_or_default()
     }
+
+    async fn rubric(&self, project_id: &str) -> Result<QualityRubric> {
+        let Some(sources) = &self.rubrics else {
+            return Ok(QualityRubric { project_id: project_id.to_string(), ..Default::default() });
+        };
+        match sources.repository.find_by_project_id(project_id).await? {
+            Some(rubric) => Ok(rubric),
+            None => Ok(QualityRubric::from_settings(project_id, &sources.config.current().quality)),
+        }
+    }
+
+    async fn check_rubric(&self, context: &EnhancedContextItem) -> Result<Vec<RubricCheck>> {
+        let rubric = self.rubric(&context.project_id).await?;
+        if rubric.is_empty() {
+            return Ok(Vec::new());
+        }
+        let linked_requirements = match &self.rubrics {
+            Some(sources) if rubric.require_requirement_link => {
+                sources.specification_repository.find_requirement_ids_by_context(&context.id).await?.len()
+            }
+            _ => 0,
+        };
+        Ok(rubric.evaluate(context, linked_requirements))
+    }
+}
+
+/// Score completeness as the share of rubric checks passed, with an issue per failed check
+fn apply_rubric_checks(checks: &[RubricCheck], metrics: &mut QualityMetrics) {
+    let passed = checks.iter().filter(|check| check.passed).count();
+    metrics.completeness_score = passed as f64 / checks.len() as f64;
+    for check in checks.iter().filter(|check| !check.passed) {
+        metrics.issues.push(QualityIssue {
+            issue_type: QualityIssueType::MissingInformation,
+            severity: if check.name.starts_with("required.") { IssueSeverity::High } else { IssueSeverity::Medium },
+            description: format!("Rubric: {}", check.detail),
+            field: Some(format!("{RUBRIC_FIELD_PREFIX}{}", check.name)),
+            detected_at: Utc::now(),
+        });
+    }
+}
+
+fn is_rubric_issue(issue: &QualityIssue) -> bool {
+    issue.field.as_deref().is_some_and(|field| field.starts_with(RUBRIC_FIELD_PREFIX))
 }
 
 /// Validation rule for context quality
@@ -381,6 +470,11 @@ pub struct ContextQualityAssessment {
 #[async_trait]
 trait QualityAnalyzer: Send + Sync {
     async fn analyze(&self, context: &EnhancedContextItem, related_contexts: &[EnhancedContextItem], metrics: &mut QualityMetrics) -> Result<()>;
+
+    /// Skipped when the project's rubric has checks
+    fn replaced_by_rubric(&self) -> bool {
+        false
+    }
 }
 
 /// Analyzer for content completeness
@@ -459,6 +553,10 @@ impl QualityAnalyzer for CompletenessAnalyzer {
         
         Ok(())
     }
+
+    fn replaced_by_rubric(&self) -> bool {
+        true
+    }
 }
 
 /// Analyzer for content consistency
@@ -674,7 +772,8 @@ impl ImprovementGenerator for CompletenessImprover {
     async fn generate_suggestions(&self, context: &EnhancedContextItem, metrics: &QualityMetrics) -> Result<Vec<QualityImprovement>> {
         let mut suggestions = Vec::new();
         
-        if metrics.completeness_score < 0.7 {
+        // Rubric checks have their own suggestions
+        if metrics.completeness_score < 0.7 && !metrics.issues.iter().any(is_rubric_issue) {
             if context.content.description.len() < 20 {
                 suggestions.push(QualityImprovement {
                     suggestion_type: ImprovementType::ImproveDescription,
@@ -710,6 +809,42 @@ impl ImprovementGenerator for CompletenessImprover {
     }
 }
 
+/// Generator for the checks of a project's rubric that an item fails
+struct RubricImprover;
+
+impl RubricImprover {
+    fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl ImprovementGenerator for RubricImprover {
+    async fn generate_suggestions(&self, _context: &EnhancedContextItem, metrics: &QualityMetrics) -> Result<Vec<QualityImprovement>> {
+        let suggestions = metrics
+            .issues
+            .iter()
+            .filter_map(|issue| {
+                let check = issue.field.as_deref()?.strip_prefix(RUBRIC_FIELD_PREFIX)?;
+                let (suggestion_type, action_required) = match check {
+                    "min_description_length" => (ImprovementType::ImproveDescription, "Expand the description to the length the project's rubric asks for".to_string()),
+                    "examples" => (ImprovementType::AddExamples, "Add at least one example".to_string()),
+                    "requirement_link" => (ImprovementType::AddRelationships, "Link the item to the specification requirement it serves".to_string()),
+                    field => (ImprovementType::AddMissingInfo, format!("Fill in {}", field.trim_start_matches("required."))),
+                };
+                Some(QualityImprovement {
+                    suggestion_type,
+                    description: issue.description.clone(),
+                    priority: if issue.severity == IssueSeverity::High { Priority::High } else { Priority::Medium },
+                    estimated_impact: 0.3,
+                    action_required,
+                })
+            })
+            .collect();
+        Ok(suggestions)
+    }
+}
+
 /// Generator for consistency improvements
 struct ConsistencyImprover;
 
@@ -930,6 +1065,77 @@ mod tests {
         assert!(!security_rules.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_rubric_scoring() {
+        use crate::config::{AppConfig, ConfigManager};
+        use crate::infrastructure::{SqliteQualityRubricRepository, SqliteSpecificationRepository};
+        use crate::models::quality_rubric::QualityRubric;
+
+        let db = Arc::new(std::sync::Mutex::new(crate::db::init::init_db(":memory:").unwrap()));
+        let specifications = Arc::new(SqliteSpecificationRepository::new(db.clone()));
+        specifications.initialize_tables().unwrap();
+        db.lock()
+            .unwrap()
+            .execute_batch(
+                "PRAGMA foreign_keys = OFF;
+                 INSERT INTO requirement_context_links (requirement_id, context_id, created_at) VALUES ('req-1', 'linked', '2026-01-01T00:00:00Z');",
+            )
+            .unwrap();
+        let repository = Arc::new(SqliteQualityRubricRepository::new(db));
+        let mut config = AppConfig::default();
+        config.quality.min_description_length = 10;
+        let service = DefaultContextQualityService::new().with_rubrics(
+            repository.clone(),
+            specifications,
+            Arc::new(ConfigManager::new(config, None)),
+        );
+
+        let mut linked = create_test_context("linked", "p1", ContextType::BusinessRule, "Lock accounts", "Lock after five failed logins");
+        linked.content.data = json!({"domain_area": "auth", "examples": "[\"five failures\"]"});
+        let mut bare = create_test_context("bare", "p1", ContextType::BusinessRule, "Passwords", "Long ones");
+        bare.content.data = json!({"domain_area": null, "examples": "[]"});
+
+        // Projects without a rubric of their own use [quality]
+        let fallback = service.rubric("p1").await.unwrap();
+        assert_eq!((fallback.min_description_length, fallback.require_examples), (10, false));
+        let metrics = service.calculate_quality_metrics(&bare, &[]).await.unwrap();
+        assert_eq!(metrics.completeness_score, 0.0);
+
+        let mut required_fields = std::collections::BTreeMap::new();
+        required_fields.insert("business_rule".to_string(), vec!["domain_area".to_string()]);
+        repository
+            .save(&QualityRubric {
+                project_id: "p1".to_string(),
+                required_fields,
+                min_description_length: 20,
+                require_examples: true,
+                require_requirement_link: true,
+                updated_at: None,
+            })
+            .await
+            .unwrap();
+
+        let checks = service.check_rubric(&linked).await.unwrap();
+        assert_eq!(checks.len(), 4);
+        assert!(checks.iter().all(|check| check.passed));
+        assert_eq!(service.calculate_quality_metrics(&linked, &[]).await.unwrap().completeness_score, 1.0);
+
+        let metrics = service.calculate_quality_metrics(&bare, &[]).await.unwrap();
+        assert_eq!(metrics.completeness_score, 0.0);
+        let failed: Vec<&str> = metrics.issues.iter().filter_map(|issue| issue.field.as_deref()).collect();
+        assert_eq!(
+            failed,
+            vec!["rubric.required.domain_area", "rubric.min_description_length", "rubric.examples", "rubric.requirement_link"]
+        );
+        let suggestions = service.generate_improvement_suggestions(&bare, &metrics).await.unwrap();
+        assert!(suggestions.iter().any(|s| s.suggestion_type == ImprovementType::AddExamples));
+        assert!(!suggestions.iter().any(|s| s.suggestion_type == ImprovementType::UpdateTags));
+
+        // Without a rubric the heuristics still apply
+        let heuristic = DefaultContextQualityService::new().calculate_quality_metrics(&bare, &[]).await.unwrap();
+        assert!(heuristic.completeness_score > 0.0);
+    }
+
     #[test]
     fn test_text_similarity() {
         let similarity = calculate_similarity("hello world", "world hello");
@@ -1,20 +1,164 @@
+use crate::cache::{CacheKeyBuilder, RequestMemo};
+use crate::config::{ConfigManager, FreshnessSettings};
 use crate::models::context::{
-    ArchitecturalDecision, BusinessRule, PerformanceRequirement, ProjectConvention, SecurityPolicy,
+    ArchitecturalDecision, BusinessRule, FeatureContext, PerformanceRequirement, ProjectConvention, SecurityPolicy,
 };
+use crate::models::embedding::ContextEmbedding;
 use crate::repositories::{
-    ArchitecturalDecisionRepository, BusinessRuleRepository, PerformanceRequirementRepository,
+    ArchitecturalDecisionRepository, BusinessRuleRepository, EmbeddingRepository,
+    EntityVerificationRepository, FeatureContextRepository, PerformanceRequirementRepository,
+    ProjectConventionRepository, SecurityPolicyRepository,
 };
+use crate::services::context_freshness::freshness;
+use crate::services::embedding_service::EmbeddingService;
+use crate::services::specification_template::json_list;
+use crate::services::tag_classifier::tag_name;
+use crate::services::vector_embedding_integration::parent_id;
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use rmcp::model::ErrorData as McpError;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+/// Budget `query_context` assembles within when the caller doesn't give one
+pub const DEFAULT_TOKEN_BUDGET: usize = 8000;
+/// Rough characters per token of the JSON an item is returned as
+const CHARS_PER_TOKEN: usize = 4;
+const RELEVANCE_WEIGHT: f32 = 0.6;
+const FRESHNESS_WEIGHT: f32 = 0.2;
+const QUALITY_WEIGHT: f32 = 0.2;
 
 /// Result of context query
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct ContextQueryResult {
     pub business_rules: Vec<BusinessRule>,
     pub architectural_decisions: Vec<ArchitecturalDecision>,
     pub performance_requirements: Vec<PerformanceRequirement>,
     pub security_policies: Vec<SecurityPolicy>,
     pub project_conventions: Vec<ProjectConvention>,
+    /// Returned items best first, with the score each got and why
+    #[serde(default)]
+    pub ranking: Vec<RankedContextItem>,
+    /// How the items were fitted into the token budget; absent for unranked results
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assembly: Option<ContextAssembly>,
+    /// The feature context named like the feature area
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feature_context: Option<FeatureContextSummary>,
+}
+
+/// What a feature context tells an agent working on the feature
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FeatureContextSummary {
+    pub id: String,
+    pub feature_name: String,
+    /// The feature's business purpose
+    pub description: Option<String>,
+    pub constraints: Vec<String>,
+    pub related_components: Vec<String>,
+    pub status: Option<String>,
+    pub owner: Option<String>,
+}
+
+impl From<FeatureContext> for FeatureContextSummary {
+    fn from(feature: FeatureContext) -> Self {
+        Self {
+            constraints: json_list(feature.constraints.as_deref()),
+            related_components: json_list(feature.related_components.as_deref()),
+            id: feature.id,
+            feature_name: feature.feature_name,
+            description: feature.business_purpose,
+            status: feature.status,
+            owner: feature.owner,
+        }
+    }
+}
+
+/// Score of one returned item; the entity lists above hold the items in this order
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RankedContextItem {
+    pub id: String,
+    pub entity_type: String,
+    pub title: String,
+    /// Weighted sum of relevance, freshness and quality
+    pub score: f32,
+    /// Embedding similarity to the feature area and components, or keyword match without embeddings
+    pub relevance: f32,
+    /// 1.0 when verified (or, never verified, created) today, halving every `[freshness]` half-life
+    pub freshness: f32,
+    /// Share of the item's optional fields that are filled
+    pub quality: f32,
+    pub estimated_tokens: usize,
+    pub why_included: String,
+}
+
+/// Outcome of fitting ranked items into a token budget
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ContextAssembly {
+    /// None when every candidate was returned
+    pub token_budget: Option<usize>,
+    pub estimated_tokens: usize,
+    pub candidates: usize,
+    /// Items that didn't fit, best first
+    pub omitted_ids: Vec<String>,
+    /// Estimated tokens of the whole result, sections besides the ranked items included;
+    /// `query_context` keeps it within the budget
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result_tokens: Option<usize>,
+    /// Entries of those other sections left out to fit, by section
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub omitted_sections: BTreeMap<String, usize>,
+}
+
+impl ContextQueryResult {
+    /// Keep the best ranked items that fit in `token_budget`, skipping any too large for the
+    /// room left, and order the entity lists by rank. Items removed from the lists after
+    /// ranking (e.g. by review state) no longer count against the budget.
+    pub fn fit_to_budget(&mut self, token_budget: Option<usize>) {
+        let listed: HashSet<&str> = self
+            .business_rules
+            .iter()
+            .map(|rule| rule.id.as_str())
+            .chain(self.architectural_decisions.iter().map(|decision| decision.id.as_str()))
+            .chain(self.performance_requirements.iter().map(|requirement| requirement.id.as_str()))
+            .chain(self.security_policies.iter().map(|policy| policy.id.as_str()))
+            .chain(self.project_conventions.iter().map(|convention| convention.id.as_str()))
+            .collect();
+        let ranked: Vec<RankedContextItem> = std::mem::take(&mut self.ranking)
+            .into_iter()
+            .filter(|item| listed.contains(item.id.as_str()))
+            .collect();
+
+        let mut assembly = ContextAssembly {
+            token_budget,
+            candidates: ranked.len(),
+            ..Default::default()
+        };
+        for item in ranked {
+            if token_budget.is_some_and(|budget| assembly.estimated_tokens + item.estimated_tokens > budget) {
+                assembly.omitted_ids.push(item.id);
+                continue;
+            }
+            assembly.estimated_tokens += item.estimated_tokens;
+            self.ranking.push(item);
+        }
+
+        let position: HashMap<&str, usize> =
+            self.ranking.iter().enumerate().map(|(i, item)| (item.id.as_str(), i)).collect();
+        let order = |id: &str| position.get(id).copied();
+        self.business_rules.retain(|rule| order(&rule.id).is_some());
+        self.business_rules.sort_by_key(|rule| order(&rule.id));
+        self.architectural_decisions.retain(|decision| order(&decision.id).is_some());
+        self.architectural_decisions.sort_by_key(|decision| order(&decision.id));
+        self.performance_requirements.retain(|requirement| order(&requirement.id).is_some());
+        self.performance_requirements.sort_by_key(|requirement| order(&requirement.id));
+        self.security_policies.retain(|policy| order(&policy.id).is_some());
+        self.security_policies.sort_by_key(|policy| order(&policy.id));
+        self.project_conventions.retain(|convention| order(&convention.id).is_some());
+        self.project_conventions.sort_by_key(|convention| order(&convention.id));
+        self.assembly = Some(assembly);
+    }
 }
 
 /// Service for querying project context following Single Responsibility Principle
@@ -26,10 +170,17 @@ pub trait ContextQueryService: Send + Sync {
         feature_area: &str,
         task_type: &str,
         components: &[String],
+        token_budget: Option<usize>,
     ) -> Result<ContextQueryResult, McpError>;
+
+    /// All context of a project regardless of feature area, e.g. as search candidates
+    async fn project_context(&self, project_id: &str) -> Result<ContextQueryResult, McpError> {
+        self.query_context(project_id, "", "general", &[], None).await
+    }
 }
 
-/// Implementation of ContextQueryService
+/// Implementation of ContextQueryService; results are ranked by relevance to the feature
+/// area and components, freshness and completeness, and cut to the token budget best first
 pub struct ContextQueryServiceImpl<BR, ADR, PR>
 where
     BR: BusinessRuleRepository,
@@ -39,6 +190,11 @@ where
     business_rule_repository: BR,
     architectural_decision_repository: ADR,
     performance_requirement_repository: PR,
+    embeddings: Option<(Arc<dyn EmbeddingService>, Arc<dyn EmbeddingRepository>)>,
+    freshness: Option<(Arc<dyn EntityVerificationRepository>, Arc<ConfigManager>)>,
+    security_policy_repository: Option<Arc<dyn SecurityPolicyRepository>>,
+    project_convention_repository: Option<Arc<dyn ProjectConventionRepository>>,
+    feature_context_repository: Option<Arc<dyn FeatureContextRepository>>,
 }
 
 impl<BR, ADR, PR> ContextQueryServiceImpl<BR, ADR, PR>
@@ -56,8 +212,371 @@ where
             business_rule_repository,
             architectural_decision_repository,
             performance_requirement_repository,
+            embeddings: None,
+            freshness: None,
+            security_policy_repository: None,
+            project_convention_repository: None,
+            feature_context_repository: None,
         }
     }
+
+    /// Rank by similarity of stored embeddings to the query instead of keyword matches
+    pub fn with_embeddings(
+        mut self,
+        embedding_service: Arc<dyn EmbeddingService>,
+        embedding_repository: Arc<dyn EmbeddingRepository>,
+    ) -> Self {
+        self.embeddings = Some((embedding_service, embedding_repository));
+        self
+    }
+
+    /// Let freshness decay from when items were last verified, at the `[freshness]` half-lives,
+    /// instead of from their creation at the default half-life
+    pub fn with_freshness(
+        mut self,
+        verification_repository: Arc<dyn EntityVerificationRepository>,
+        config: Arc<ConfigManager>,
+    ) -> Self {
+        self.freshness = Some((verification_repository, config));
+        self
+    }
+
+    /// Rank the project's security policies along with its other context
+    pub fn with_security_policies(mut self, security_policy_repository: Arc<dyn SecurityPolicyRepository>) -> Self {
+        self.security_policy_repository = Some(security_policy_repository);
+        self
+    }
+
+    /// The project's security policies; none unless configured
+    async fn security_policies(&self, project_id: &str) -> Result<Vec<SecurityPolicy>, McpError> {
+        match &self.security_policy_repository {
+            Some(repository) => {
+                RequestMemo::memoize(CacheKeyBuilder::security_policies_by_project(project_id), || {
+                    repository.list_by_project(project_id)
+                })
+                .await
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Rank the project's conventions along with its other context
+    pub fn with_conventions(mut self, project_convention_repository: Arc<dyn ProjectConventionRepository>) -> Self {
+        self.project_convention_repository = Some(project_convention_repository);
+        self
+    }
+
+    /// The project's conventions; none unless configured
+    async fn conventions(&self, project_id: &str) -> Result<Vec<ProjectConvention>, McpError> {
+        match &self.project_convention_repository {
+            Some(repository) => {
+                RequestMemo::memoize(CacheKeyBuilder::project_conventions_by_project(project_id), || {
+                    repository.list_by_project(project_id)
+                })
+                .await
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Add the feature context named like the feature area to results
+    pub fn with_feature_contexts(mut self, feature_context_repository: Arc<dyn FeatureContextRepository>) -> Self {
+        self.feature_context_repository = Some(feature_context_repository);
+        self
+    }
+
+    /// The project's feature context whose name matches `feature_area` ignoring case and
+    /// punctuation, e.g. `User Login` for `user-login`
+    async fn matching_feature(&self, project_id: &str, feature_area: &str) -> Result<Option<FeatureContext>, McpError> {
+        let (Some(repository), area) = (&self.feature_context_repository, tag_name(feature_area)) else {
+            return Ok(None);
+        };
+        if area.is_empty() {
+            return Ok(None);
+        }
+        let features = RequestMemo::memoize(CacheKeyBuilder::feature_contexts_by_project(project_id), || {
+            repository.list_by_project(project_id)
+        })
+        .await?;
+        Ok(features.into_iter().find(|feature| tag_name(&feature.feature_name) == area))
+    }
+
+    /// When each item of the project was last verified, with the half-lives to decay by
+    async fn verifications(&self, project_id: &str) -> (HashMap<String, DateTime<Utc>>, FreshnessSettings) {
+        let Some((repository, config)) = &self.freshness else {
+            return (HashMap::new(), FreshnessSettings::default());
+        };
+        let settings = config.current().freshness.clone();
+        match repository.find_by_project(project_id).await {
+            Ok(verifications) => (
+                verifications
+                    .into_iter()
+                    .filter_map(|verification| {
+                        parse_timestamp(&verification.verified_at).map(|at| (verification.entity_id, at))
+                    })
+                    .collect(),
+                settings,
+            ),
+            Err(e) => {
+                tracing::warn!("Ranking context by creation dates, verifications are unavailable: {}", e);
+                (HashMap::new(), settings)
+            }
+        }
+    }
+
+    /// Best similarity of each stored entity's chunks to the query; empty when embeddings
+    /// aren't configured or fail, so ranking falls back to keywords
+    async fn semantic_scores(&self, project_id: &str, query: &str) -> HashMap<String, f32> {
+        let mut scores = HashMap::new();
+        let Some((service, repository)) = &self.embeddings else {
+            return scores;
+        };
+        if query.is_empty() {
+            return scores;
+        }
+        let query_embedding = match service.generate_embedding(query, "query").await {
+            Ok(embedding) => embedding,
+            Err(e) => {
+                tracing::warn!("Ranking context by keywords, the query embedding failed: {}", e);
+                return scores;
+            }
+        };
+        let stored: Vec<ContextEmbedding> = match repository.get_embeddings_by_project(project_id).await {
+            Ok(stored) => stored,
+            Err(e) => {
+                tracing::warn!("Ranking context by keywords, stored embeddings are unavailable: {}", e);
+                return scores;
+            }
+        };
+        for embedding in stored.iter().filter(|embedding| {
+            embedding.embedding_model == query_embedding.embedding_model
+                && embedding.embedding_vector.len() == query_embedding.embedding_vector.len()
+        }) {
+            let similarity = service.calculate_similarity(&query_embedding, embedding).clamp(0.0, 1.0);
+            let best = scores.entry(parent_id(&embedding.context_id).to_string()).or_insert(0.0);
+            *best = best.max(similarity);
+        }
+        scores
+    }
+}
+
+/// An entity competing for a place in a `query_context` result
+struct Candidate {
+    entity_type: &'static str,
+    id: String,
+    title: String,
+    /// Domain or component area the entity is filed under
+    area: Option<String>,
+    text: String,
+    created_at: Option<String>,
+    filled_fields: usize,
+    optional_fields: usize,
+    estimated_tokens: usize,
+}
+
+impl Candidate {
+    fn new<T: Serialize>(
+        entity_type: &'static str,
+        id: &str,
+        title: &str,
+        area: Option<&str>,
+        fields: &[&Option<String>],
+        created_at: &Option<String>,
+        item: &T,
+    ) -> Self {
+        let text = std::iter::once(title)
+            .chain(area)
+            .chain(fields.iter().filter_map(|field| field.as_deref()))
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        Self {
+            entity_type,
+            id: id.to_string(),
+            title: title.to_string(),
+            area: area.map(str::to_string),
+            text,
+            created_at: created_at.clone(),
+            filled_fields: fields
+                .iter()
+                .filter(|field| field.as_deref().is_some_and(|value| !value.trim().is_empty()))
+                .count(),
+            optional_fields: fields.len(),
+            estimated_tokens: estimate_tokens(item),
+        }
+    }
+
+    fn rank(
+        &self,
+        query: &RankingQuery,
+        semantic: Option<f32>,
+        verified_at: Option<DateTime<Utc>>,
+        settings: &FreshnessSettings,
+        now: DateTime<Utc>,
+    ) -> RankedContextItem {
+        let mut reasons = Vec::new();
+
+        let area_match = !query.feature_area.is_empty()
+            && self.area.as_deref().is_some_and(|area| area.eq_ignore_ascii_case(query.feature_area));
+        let matched: Vec<&str> = query.terms.iter().map(String::as_str).filter(|term| self.text.contains(term)).collect();
+        let keyword = if area_match {
+            1.0
+        } else if query.terms.is_empty() {
+            0.0
+        } else {
+            matched.len() as f32 / query.terms.len() as f32
+        };
+        let relevance = semantic.unwrap_or(0.0).max(keyword);
+        match semantic {
+            Some(similarity) if similarity >= keyword && similarity > 0.0 => {
+                reasons.push(format!("similarity {:.2} to \"{}\"", similarity, query.text))
+            }
+            _ if area_match => reasons.push(format!("filed under {}", query.feature_area)),
+            _ if !matched.is_empty() => reasons.push(format!("mentions {}", matched.join(", "))),
+            _ => reasons.push("project-wide context with no match to the query".to_string()),
+        }
+
+        let since = match verified_at {
+            Some(verified_at) => Some(("verified", verified_at)),
+            None => self.created_at.as_deref().and_then(parse_timestamp).map(|created_at| ("created", created_at)),
+        };
+        let freshness = match since {
+            Some((event, at)) => {
+                reasons.push(match (now - at).num_days().max(0) {
+                    0 => format!("{event} today"),
+                    1 => format!("{event} yesterday"),
+                    days => format!("{event} {days} days ago"),
+                });
+                freshness(at, now, settings.half_life_of(self.entity_type)) as f32
+            }
+            None => {
+                reasons.push("creation date unknown".to_string());
+                0.0
+            }
+        };
+
+        let quality = match self.optional_fields {
+            0 => 1.0,
+            fields => self.filled_fields as f32 / fields as f32,
+        };
+        reasons.push(format!("{} of {} fields filled", self.filled_fields, self.optional_fields));
+
+        RankedContextItem {
+            id: self.id.clone(),
+            entity_type: self.entity_type.to_string(),
+            title: self.title.clone(),
+            score: RELEVANCE_WEIGHT * relevance + FRESHNESS_WEIGHT * freshness + QUALITY_WEIGHT * quality,
+            relevance,
+            freshness,
+            quality,
+            estimated_tokens: self.estimated_tokens,
+            why_included: reasons.join("; "),
+        }
+    }
+}
+
+/// What candidates are ranked against
+struct RankingQuery<'a> {
+    feature_area: &'a str,
+    /// Feature area and components, as embedded
+    text: String,
+    /// Lowercase words of the text worth matching
+    terms: Vec<String>,
+}
+
+impl<'a> RankingQuery<'a> {
+    fn new(feature_area: &'a str, components: &[String]) -> Self {
+        let text = std::iter::once(feature_area)
+            .chain(components.iter().map(String::as_str))
+            .filter(|part| !part.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut terms: Vec<String> = Vec::new();
+        for term in text.to_lowercase().split(|c: char| !c.is_alphanumeric()).filter(|term| term.len() >= 3) {
+            if !terms.iter().any(|existing| existing == term) {
+                terms.push(term.to_string());
+            }
+        }
+        Self { feature_area, text, terms }
+    }
+}
+
+pub(crate) fn estimate_tokens<T: Serialize>(item: &T) -> usize {
+    serde_json::to_string(item).map_or(0, |json| json.len().div_ceil(CHARS_PER_TOKEN))
+}
+
+/// RFC 3339, or SQLite's `CURRENT_TIMESTAMP` format in UTC
+pub(crate) fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|at| at.with_timezone(&Utc))
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").map(|at| at.and_utc()))
+        .ok()
+}
+
+/// Rank the items of `result` and keep the best that fit in `token_budget`
+fn assemble(
+    mut result: ContextQueryResult,
+    query: &RankingQuery,
+    semantic: &HashMap<String, f32>,
+    verified: &HashMap<String, DateTime<Utc>>,
+    settings: &FreshnessSettings,
+    token_budget: Option<usize>,
+) -> ContextQueryResult {
+    let mut candidates = Vec::new();
+    for rule in &result.business_rules {
+        let fields = [&rule.description, &rule.implementation_pattern, &rule.constraints, &rule.examples];
+        let area = rule.domain_area.as_deref();
+        candidates.push(Candidate::new("business_rule", &rule.id, &rule.rule_name, area, &fields, &rule.created_at, rule));
+    }
+    for decision in &result.architectural_decisions {
+        let fields = [&decision.context, &decision.decision, &decision.consequences, &decision.alternatives_considered];
+        let title = &decision.decision_title;
+        candidates.push(Candidate::new("architectural_decision", &decision.id, title, None, &fields, &decision.created_at, decision));
+    }
+    for requirement in &result.performance_requirements {
+        let title = requirement.requirement_type.as_deref().unwrap_or("performance requirement");
+        let fields = [&requirement.target_value, &requirement.optimization_patterns, &requirement.avoid_patterns];
+        let area = requirement.component_area.as_deref();
+        candidates.push(Candidate::new("performance_requirement", &requirement.id, title, area, &fields, &requirement.created_at, requirement));
+    }
+    for policy in &result.security_policies {
+        let fields = [
+            &policy.requirements,
+            &policy.implementation_pattern,
+            &policy.forbidden_patterns,
+            &policy.compliance_notes,
+            &policy.severity,
+            &policy.classification,
+        ];
+        let area = policy.policy_area.as_deref();
+        candidates.push(Candidate::new("security_policy", &policy.id, &policy.policy_name, area, &fields, &policy.created_at, policy));
+    }
+    for convention in &result.project_conventions {
+        let title = convention.convention_rule.as_deref().unwrap_or("project convention");
+        let fields = [
+            &convention.convention_type,
+            &convention.pattern,
+            &convention.good_examples,
+            &convention.bad_examples,
+            &convention.rationale,
+            &convention.enforcement_level,
+        ];
+        candidates.push(Candidate::new("project_convention", &convention.id, title, None, &fields, &convention.created_at, convention));
+    }
+
+    let now = Utc::now();
+    let mut ranked: Vec<RankedContextItem> = candidates
+        .iter()
+        .map(|candidate| {
+            let verified_at = verified.get(&candidate.id).copied();
+            candidate.rank(query, semantic.get(&candidate.id).copied(), verified_at, settings, now)
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.id.cmp(&b.id)));
+
+    result.ranking = ranked;
+    result.fit_to_budget(token_budget);
+    result
 }
 
 #[async_trait]
@@ -72,32 +591,156 @@ where
         project_id: &str,
         feature_area: &str,
         _task_type: &str,
-        _components: &[String],
+        components: &[String],
+        token_budget: Option<usize>,
     ) -> Result<ContextQueryResult, McpError> {
+        // Lookups are memoized for the current request (see `RequestMemo::scope`)
+
         // Query business rules for the feature area
-        let business_rules = self
-            .business_rule_repository
-            .find_by_domain_area(project_id, feature_area)
-            .await?;
+        let business_rules = RequestMemo::memoize(
+            CacheKeyBuilder::business_rules_by_domain(project_id, feature_area),
+            || self.business_rule_repository.find_by_domain_area(project_id, feature_area),
+        )
+        .await?;
 
         // Query architectural decisions
-        let architectural_decisions = self
-            .architectural_decision_repository
-            .find_by_project_id(project_id)
-            .await?;
+        let architectural_decisions = RequestMemo::memoize(
+            CacheKeyBuilder::architectural_decisions_by_project(project_id),
+            || self.architectural_decision_repository.find_by_project_id(project_id),
+        )
+        .await?;
 
         // Query performance requirements
-        let performance_requirements = self
-            .performance_requirement_repository
-            .find_by_project_id(project_id)
-            .await?;
+        let performance_requirements = RequestMemo::memoize(
+            CacheKeyBuilder::performance_requirements_by_project(project_id),
+            || self.performance_requirement_repository.find_by_project_id(project_id),
+        )
+        .await?;
+
+        let security_policies = self.security_policies(project_id).await?;
+        // Conventions apply whatever the feature area
+        let project_conventions = self.conventions(project_id).await?;
+
+        let result = ContextQueryResult {
+            business_rules,
+            architectural_decisions,
+            performance_requirements,
+            security_policies,
+            project_conventions,
+            ..Default::default()
+        };
+
+        let query = RankingQuery::new(feature_area, components);
+        let semantic = self.semantic_scores(project_id, &query.text).await;
+        let (verified, settings) = self.verifications(project_id).await;
+        let mut result = assemble(result, &query, &semantic, &verified, &settings, token_budget);
+        result.feature_context = self.matching_feature(project_id, feature_area).await?.map(Into::into);
+        Ok(result)
+    }
+
+    async fn project_context(&self, project_id: &str) -> Result<ContextQueryResult, McpError> {
+        let business_rules = RequestMemo::memoize(
+            CacheKeyBuilder::business_rules_by_project(project_id),
+            || self.business_rule_repository.find_by_project_id(project_id),
+        )
+        .await?;
+        let architectural_decisions = RequestMemo::memoize(
+            CacheKeyBuilder::architectural_decisions_by_project(project_id),
+            || self.architectural_decision_repository.find_by_project_id(project_id),
+        )
+        .await?;
+        let performance_requirements = RequestMemo::memoize(
+            CacheKeyBuilder::performance_requirements_by_project(project_id),
+            || self.performance_requirement_repository.find_by_project_id(project_id),
+        )
+        .await?;
+
+        let security_policies = self.security_policies(project_id).await?;
+        let project_conventions = self.conventions(project_id).await?;
 
         Ok(ContextQueryResult {
             business_rules,
             architectural_decisions,
             performance_requirements,
-            security_policies: Vec::new(), // TODO: Implement when security repository is available
-            project_conventions: Vec::new(), // TODO: Implement when convention repository is available
+            security_policies,
+            project_conventions,
+            ..Default::default()
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::{
+        SqliteArchitecturalDecisionRepository, SqliteBusinessRuleRepository, SqliteEntityVerificationRepository,
+        SqlitePerformanceRequirementRepository,
+    };
+    use crate::models::freshness::EntityVerification;
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_ranks_and_fits_to_token_budget() {
+        let conn = crate::db::init::init_db(":memory:").unwrap();
+        conn.execute_batch(
+            "INSERT INTO projects (id, name) VALUES ('p1', 'Shop');
+             INSERT INTO business_rules (id, project_id, rule_name, description, domain_area, implementation_pattern, constraints, examples)
+                 VALUES ('rule-full', 'p1', 'Lock accounts', 'Lock the account for an hour after five failed logins', 'auth', 'Counter per user', '[]', '[]');
+             INSERT INTO business_rules (id, project_id, rule_name, domain_area, created_at)
+                 VALUES ('rule-old', 'p1', 'Passwords need at least twelve characters', 'auth', '2020-01-01 00:00:00');
+             INSERT INTO architectural_decisions (id, project_id, decision_title, decision, status)
+                 VALUES ('adr-login', 'p1', 'Session tokens', 'Opaque login tokens', 'accepted');
+             INSERT INTO architectural_decisions (id, project_id, decision_title, context, decision, consequences, alternatives_considered, status)
+                 VALUES ('adr-other', 'p1', 'Event sourcing for orders', 'Orders change often', 'Store events', 'Replays are slow', 'CRUD tables', 'accepted');",
+        )
+        .unwrap();
+        let db = Arc::new(Mutex::new(conn));
+        let verifications = Arc::new(SqliteEntityVerificationRepository::new(db.clone()));
+        let service = ContextQueryServiceImpl::new(
+            SqliteBusinessRuleRepository::new(db.clone()),
+            SqliteArchitecturalDecisionRepository::new(db.clone()),
+            SqlitePerformanceRequirementRepository::new(db),
+        )
+        .with_freshness(verifications.clone(), Arc::new(ConfigManager::new(Default::default(), None)));
+        let components = vec!["login".to_string()];
+
+        let all = service.query_context("p1", "auth", "implement", &components, None).await.unwrap();
+        let order: Vec<&str> = all.ranking.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(order, vec!["rule-full", "rule-old", "adr-login", "adr-other"]);
+        assert_eq!(all.business_rules[0].id, "rule-full");
+        assert!(all.ranking[0].why_included.starts_with("filed under auth; created today; 4 of 4 fields filled"));
+        assert!(all.ranking[2].why_included.starts_with("mentions login"));
+        assert!(all.ranking[3].why_included.starts_with("project-wide context"));
+        let assembly = all.assembly.clone().unwrap();
+        assert_eq!((assembly.candidates, assembly.token_budget), (4, None));
+        assert!(assembly.omitted_ids.is_empty());
+
+        // The old rule no longer fits once the best one is in, but the smaller decision does
+        assert!(all.ranking[1].estimated_tokens > all.ranking[2].estimated_tokens);
+        let budget = all.ranking[0].estimated_tokens + all.ranking[2].estimated_tokens;
+        let fitted = service.query_context("p1", "auth", "implement", &components, Some(budget)).await.unwrap();
+        let kept: Vec<&str> = fitted.ranking.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(kept, vec!["rule-full", "adr-login"]);
+        assert_eq!(fitted.business_rules.len(), 1);
+        let assembly = fitted.assembly.unwrap();
+        assert_eq!(assembly.estimated_tokens, budget);
+        assert_eq!(assembly.omitted_ids, vec!["rule-old", "adr-other"]);
+
+        // Items dropped after ranking free their share of the budget
+        assert!(all.ranking[1].estimated_tokens <= all.ranking[0].estimated_tokens);
+        let mut filtered = all;
+        filtered.business_rules.retain(|rule| rule.id != "rule-full");
+        filtered.fit_to_budget(Some(budget));
+        let kept: Vec<&str> = filtered.ranking.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(&kept[..2], ["rule-old", "adr-login"]);
+        assert_eq!(filtered.assembly.unwrap().candidates, 3);
+
+        // Verifying the old rule makes it fresh again
+        assert!(filtered.ranking[0].freshness < 0.01);
+        verifications.record(&EntityVerification::new("p1", "business_rule", "rule-old")).await.unwrap();
+        let verified = service.query_context("p1", "auth", "implement", &components, None).await.unwrap();
+        let rule = verified.ranking.iter().find(|item| item.id == "rule-old").unwrap();
+        assert!(rule.freshness > 0.99);
+        assert!(rule.why_included.contains("verified today"));
+    }
+}
@@ -0,0 +1,265 @@
+use crate::services::analytics_service::{AnalyticsEvent, AnalyticsEventType};
+use crate::services::search_feedback::CLICK_WINDOW;
+use crate::services::tag_classifier::tag_name;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+
+/// Weight of an entity a past query returned; one fetched from a search right after the
+/// query, by the same client, counts 1
+const RETURNED_WEIGHT: f32 = 0.5;
+/// Share of a query's similarity that a matching task type adds to shared terms
+const TASK_TYPE_WEIGHT: f32 = 0.2;
+/// Past queries quoted as the reason for a recommendation
+const MAX_REASONS: usize = 3;
+
+/// What a `query_context` call asked for
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryProfile {
+    pub feature_area: String,
+    pub task_type: Option<String>,
+    pub components: Vec<String>,
+}
+
+impl QueryProfile {
+    pub fn new(feature_area: &str, task_type: Option<&str>, components: &[String]) -> Self {
+        Self {
+            feature_area: feature_area.to_string(),
+            task_type: task_type.map(str::to_string),
+            components: components.to_vec(),
+        }
+    }
+
+    /// The words of the feature area and components, e.g. `user`, `login` and `form`
+    fn terms(&self) -> BTreeSet<String> {
+        std::iter::once(&self.feature_area)
+            .chain(&self.components)
+            .flat_map(|text| tag_name(text).split('-').map(str::to_string).collect::<Vec<_>>())
+            .filter(|term| !term.is_empty())
+            .collect()
+    }
+
+    /// Share of terms the queries have in common (Jaccard), with a bonus for the same task
+    /// type when they share any
+    pub fn similarity(&self, other: &QueryProfile) -> f32 {
+        let (terms, other_terms) = (self.terms(), other.terms());
+        let union = terms.union(&other_terms).count();
+        if union == 0 {
+            return 0.0;
+        }
+        let shared = terms.intersection(&other_terms).count() as f32 / union as f32;
+        let same_task = self.task_type.is_some() && self.task_type == other.task_type;
+        if shared > 0.0 && same_task {
+            (1.0 - TASK_TYPE_WEIGHT) * shared + TASK_TYPE_WEIGHT
+        } else {
+            (1.0 - TASK_TYPE_WEIGHT) * shared
+        }
+    }
+
+    /// E.g. `auth [login, session] (implement)`
+    fn describe(&self) -> String {
+        let mut description = self.feature_area.clone();
+        if !self.components.is_empty() {
+            description.push_str(&format!(" [{}]", self.components.join(", ")));
+        }
+        if let Some(task_type) = &self.task_type {
+            description.push_str(&format!(" ({task_type})"));
+        }
+        description
+    }
+}
+
+/// A past `query_context` call with the entities it led to, by `(entity_type, entity_id)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PastQuery {
+    pub profile: QueryProfile,
+    pub timestamp: DateTime<Utc>,
+    pub user_agent: Option<String>,
+    pub used: HashMap<(String, String), f32>,
+}
+
+/// The successful `ContextQuery` events among `events` (oldest first) with the entities they
+/// returned, plus the search results each client opened within `CLICK_WINDOW` after its query
+pub fn query_history(events: &[AnalyticsEvent]) -> Vec<PastQuery> {
+    let mut history: Vec<PastQuery> = Vec::new();
+    for event in events {
+        match event.event_type {
+            AnalyticsEventType::ContextQuery if event.success => {
+                let Some(feature_area) = event.metadata.get("feature_area").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                let components: Vec<String> = event
+                    .metadata
+                    .get("components")
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect();
+                let task_type = event.metadata.get("task_type").and_then(|v| v.as_str());
+                let used = event
+                    .metadata
+                    .get("entities")
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|entity| {
+                        let entity_type = entity.get("entity_type")?.as_str()?;
+                        let entity_id = entity.get("entity_id")?.as_str()?;
+                        Some(((entity_type.to_string(), entity_id.to_string()), RETURNED_WEIGHT))
+                    })
+                    .collect();
+                history.push(PastQuery {
+                    profile: QueryProfile::new(feature_area, task_type, &components),
+                    timestamp: event.timestamp,
+                    user_agent: event.user_agent.clone(),
+                    used,
+                });
+            }
+            AnalyticsEventType::SearchClick => {
+                let (Some(user_agent), Some(entity_type), Some(entity_id)) =
+                    (&event.user_agent, &event.entity_type, &event.entity_id)
+                else {
+                    continue;
+                };
+                let window = chrono::Duration::from_std(CLICK_WINDOW).unwrap_or_default();
+                let query = history.iter_mut().rev().find(|query| {
+                    query.user_agent.as_ref() == Some(user_agent)
+                        && query.timestamp <= event.timestamp
+                        && event.timestamp - query.timestamp < window
+                });
+                if let Some(query) = query {
+                    query.used.insert((entity_type.clone(), entity_id.clone()), 1.0);
+                }
+            }
+            _ => {}
+        }
+    }
+    history
+}
+
+/// An entity that queries like the new one ended up using
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContextRecommendation {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub title: Option<String>,
+    /// Sum over similar past queries of their similarity times the entity's weight in them
+    pub score: f32,
+    pub similar_queries: usize,
+    /// The most similar of those queries, described
+    pub because_of: Vec<String>,
+}
+
+/// Entities used by past queries at least `min_similarity` similar to `query`, best first
+pub fn recommend(
+    query: &QueryProfile,
+    history: &[PastQuery],
+    min_similarity: f32,
+    limit: usize,
+) -> Vec<ContextRecommendation> {
+    let mut similar: Vec<(f32, &PastQuery)> = history
+        .iter()
+        .map(|past| (query.similarity(&past.profile), past))
+        .filter(|(similarity, _)| *similarity > 0.0 && *similarity >= min_similarity)
+        .collect();
+    similar.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| b.1.timestamp.cmp(&a.1.timestamp)));
+
+    let mut recommendations: HashMap<&(String, String), ContextRecommendation> = HashMap::new();
+    for (similarity, past) in similar {
+        for (key, weight) in &past.used {
+            let recommendation = recommendations.entry(key).or_insert_with(|| ContextRecommendation {
+                entity_type: key.0.clone(),
+                entity_id: key.1.clone(),
+                title: None,
+                score: 0.0,
+                similar_queries: 0,
+                because_of: Vec::new(),
+            });
+            recommendation.score += similarity * weight;
+            recommendation.similar_queries += 1;
+            let reason = past.profile.describe();
+            if recommendation.because_of.len() < MAX_REASONS && !recommendation.because_of.contains(&reason) {
+                recommendation.because_of.push(reason);
+            }
+        }
+    }
+
+    let mut recommendations: Vec<ContextRecommendation> = recommendations
+        .into_values()
+        .map(|mut recommendation| {
+            recommendation.score = (recommendation.score * 1000.0).round() / 1000.0;
+            recommendation
+        })
+        .collect();
+    recommendations.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.entity_id.cmp(&b.entity_id)));
+    recommendations.truncate(limit);
+    recommendations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::AnalyticsHelper;
+
+    #[test]
+    fn test_recommends_what_similar_queries_used() {
+        let now = Utc::now();
+        let query = |feature_area: &str, components: &[&str], entities: &[&str], agent: &str, minutes_ago: i64| {
+            let components = components.iter().map(|c| c.to_string()).collect();
+            let mut event = AnalyticsHelper::create_context_query_event(
+                Some("p1".to_string()),
+                Some(feature_area.to_string()),
+                Some("implement".to_string()),
+                Some(components),
+                None,
+                true,
+                None,
+            );
+            let entities: Vec<serde_json::Value> = entities
+                .iter()
+                .map(|id| serde_json::json!({"entity_type": "business_rule", "entity_id": id}))
+                .collect();
+            event.metadata.insert("entities".to_string(), serde_json::Value::Array(entities));
+            event.user_agent = Some(agent.to_string());
+            event.timestamp = now - chrono::Duration::minutes(minutes_ago);
+            event
+        };
+        let click = |entity_id: &str, agent: &str, minutes_ago: i64| {
+            let mut event = AnalyticsHelper::create_search_click_event(
+                Some("p1".to_string()),
+                "architectural_decision".to_string(),
+                entity_id.to_string(),
+                "jwt".to_string(),
+                1,
+            );
+            event.user_agent = Some(agent.to_string());
+            event.timestamp = now - chrono::Duration::minutes(minutes_ago);
+            event
+        };
+        let events = vec![
+            query("authentication", &["login form"], &["rule-lockout"], "ide/1", 120),
+            query("billing", &["invoice"], &["rule-invoice"], "ide/2", 110),
+            click("adr-jwt", "ide/1", 115),
+            click("adr-late", "ide/1", 60),
+            query("authentication", &["session"], &["rule-lockout", "rule-session"], "ide/2", 30),
+        ];
+
+        let history = query_history(&events);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].used[&("architectural_decision".to_string(), "adr-jwt".to_string())], 1.0);
+        assert!(!history.iter().any(|past| past.used.keys().any(|(_, id)| id == "adr-late")));
+
+        let recommendations = recommend(
+            &QueryProfile::new("Authentication", Some("implement"), &["login".to_string()]),
+            &history,
+            0.3,
+            10,
+        );
+        let ids: Vec<&str> = recommendations.iter().map(|r| r.entity_id.as_str()).collect();
+        assert_eq!(ids, vec!["adr-jwt", "rule-lockout", "rule-session"]);
+        assert_eq!(recommendations[1].similar_queries, 2);
+        assert_eq!(recommendations[0].because_of, vec!["authentication [login form] (implement)"]);
+        assert!(recommend(&QueryProfile::new("shipping", None, &[]), &history, 0.3, 10).is_empty());
+    }
+}
@@ -3,6 +3,15 @@ use async_trait::async_trait;
 use std::collections::{HashMap, HashSet};
 use anyhow::Result;
 
+/// Metadata key naming the detector that inferred a relationship
+pub const DETECTOR_KEY: &str = "detector";
+
+/// Detectors relating items by their types or project alone, so every such pair is related
+const GENERIC_DETECTORS: &[&str] = &["type", "project_structure"];
+
+/// Each hop beyond the first multiplies a neighbor's score by this
+const HOP_DECAY: f64 = 0.8;
+
 /// Engine for detecting and managing relationships between context items
 #[async_trait]
 pub trait ContextRelationshipEngine: Send + Sync {
@@ -110,7 +119,8 @@ impl ContextRelationshipEngine for DefaultContextRelationshipEngine {
         let mut relationship_map: HashMap<String, ContextRelationship> = HashMap::new();
         
         for rel in relationships {
-            let key = format!("{}:{}", rel.target_id, rel.relationship_type.as_str());
+            let detector = rel.metadata.get(DETECTOR_KEY).and_then(|v| v.as_str()).unwrap_or_default();
+            let key = format!("{}:{}:{}", rel.target_id, rel.relationship_type.as_str(), detector);
             
             if let Some(existing) = relationship_map.get(&key) {
                 if rel.strength > existing.strength {
@@ -142,12 +152,13 @@ impl ContextRelationshipEngine for DefaultContextRelationshipEngine {
                 
                 // Add bidirectional relationships if applicable
                 if relationship.relationship_type.is_bidirectional() {
-                    let reverse_relationship = ContextRelationship::new(
+                    let mut reverse_relationship = ContextRelationship::new(
                         source.id.clone(),
                         relationship.relationship_type.clone(),
                         relationship.strength,
                         relationship.auto_detected,
                     ).with_confidence(relationship.confidence);
+                    reverse_relationship.metadata = relationship.metadata.clone();
                     
                     graph.add_edge(relationship.target_id.clone(), reverse_relationship);
                 }
@@ -260,6 +271,72 @@ impl RelationshipGraph {
         related
     }
 
+    /// Items within `max_hops` of `context_id`, following relationships either way, each with
+    /// its strongest path: the product of the relationships' strength times confidence, decayed
+    /// per extra hop. Relationships inferred from item types or the project alone are skipped.
+    /// Strongest first, recorded relationships before inferred ones of equal strength.
+    pub fn neighborhood(&self, context_id: &ContextId, max_hops: usize) -> Vec<RelatedContext> {
+        // Adjacency in both directions: (neighbor, relationship, incoming)
+        let mut adjacency: HashMap<&ContextId, Vec<(&ContextId, &ContextRelationship, bool)>> = HashMap::new();
+        for (source_id, relationships) in &self.edges {
+            for relationship in relationships.iter().filter(|r| is_pair_specific(r)) {
+                if !self.nodes.contains_key(&relationship.target_id) || relationship.target_id == *source_id {
+                    continue;
+                }
+                adjacency.entry(source_id).or_default().push((&relationship.target_id, relationship, false));
+                adjacency.entry(&relationship.target_id).or_default().push((source_id, relationship, true));
+            }
+        }
+
+        // Best score, path and last relationship per reached item, relaxed once per hop
+        let mut best: HashMap<&ContextId, (f64, Vec<ContextId>, &ContextRelationship, bool)> = HashMap::new();
+        let mut frontier: Vec<(&ContextId, f64, Vec<ContextId>)> = vec![(context_id, 1.0, vec![context_id.clone()])];
+        for hop in 1..=max_hops {
+            let decay = if hop > 1 { HOP_DECAY } else { 1.0 };
+            let mut next = Vec::new();
+            for (id, score, path) in &frontier {
+                for (neighbor, relationship, incoming) in adjacency.get(id).into_iter().flatten() {
+                    if *neighbor == context_id || path.contains(*neighbor) {
+                        continue;
+                    }
+                    let candidate = score * relationship.strength * relationship.confidence * decay;
+                    if best.get(neighbor).is_some_and(|(known, ..)| *known >= candidate) {
+                        continue;
+                    }
+                    let mut neighbor_path = path.clone();
+                    neighbor_path.push((*neighbor).clone());
+                    best.insert(neighbor, (candidate, neighbor_path.clone(), relationship, *incoming));
+                    next.push((*neighbor, candidate, neighbor_path));
+                }
+            }
+            frontier = next;
+        }
+
+        let mut related: Vec<RelatedContext> = best
+            .into_iter()
+            .filter_map(|(id, (score, path, relationship, incoming))| {
+                Some(RelatedContext {
+                    context: self.get_node(id)?.clone(),
+                    relationship_type: relationship.relationship_type.clone(),
+                    strength: score,
+                    depth: path.len() - 1,
+                    path,
+                    inferred: relationship.auto_detected,
+                    incoming,
+                })
+            })
+            .collect();
+        related.sort_by(|a, b| {
+            b.strength
+                .partial_cmp(&a.strength)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.inferred.cmp(&b.inferred))
+                .then(a.depth.cmp(&b.depth))
+                .then(a.context.id.cmp(&b.context.id))
+        });
+        related
+    }
+
     fn find_related_recursive(
         &self,
         context_id: &ContextId,
@@ -283,6 +360,8 @@ impl RelationshipGraph {
                         strength: relationship.strength,
                         depth: current_depth + 1,
                         path: vec![context_id.clone(), relationship.target_id.clone()],
+                        inferred: relationship.auto_detected,
+                        incoming: false,
                     });
                     
                     // Recursively find related contexts
@@ -307,6 +386,21 @@ pub struct RelatedContext {
     pub strength: f64,
     pub depth: usize,
     pub path: Vec<ContextId>,
+    /// The relationship reaching the item was detected by the engine rather than recorded
+    pub inferred: bool,
+    /// The item is the source of that relationship
+    pub incoming: bool,
+}
+
+/// Whether a relationship says something about its two items in particular: recorded ones
+/// always, inferred ones unless their detector relates items by type or project alone
+pub fn is_pair_specific(relationship: &ContextRelationship) -> bool {
+    !relationship.auto_detected
+        || relationship
+            .metadata
+            .get(DETECTOR_KEY)
+            .and_then(|v| v.as_str())
+            .is_none_or(|detector| !GENERIC_DETECTORS.contains(&detector))
 }
 
 /// Updates to relationships
@@ -413,7 +507,8 @@ impl RelationshipDetector for ContentSimilarityDetector {
                 RelationshipType::Similar,
                 similarity,
                 true,
-            ).with_confidence(similarity))
+            ).with_confidence(similarity)
+            .with_metadata(DETECTOR_KEY.to_string(), "content_similarity".into()))
         } else {
             None
         }
@@ -449,7 +544,8 @@ impl RelationshipDetector for KeywordOverlapDetector {
                 RelationshipType::References,
                 overlap,
                 true,
-            ).with_confidence(overlap))
+            ).with_confidence(overlap)
+            .with_metadata(DETECTOR_KEY.to_string(), "keyword_overlap".into()))
         } else {
             None
         }
@@ -483,7 +579,8 @@ impl RelationshipDetector for TypeBasedDetector {
                 rel_type,
                 0.7,
                 true,
-            ).with_confidence(0.8))
+            ).with_confidence(0.8)
+            .with_metadata(DETECTOR_KEY.to_string(), "type".into()))
         } else {
             None
         }
@@ -508,7 +605,8 @@ impl RelationshipDetector for ProjectStructureDetector {
                 RelationshipType::References,
                 0.4,
                 true,
-            ).with_confidence(0.6))
+            ).with_confidence(0.6)
+            .with_metadata(DETECTOR_KEY.to_string(), "project_structure".into()))
         } else {
             None
         }
@@ -620,6 +718,45 @@ mod tests {
         assert!(related.iter().any(|r| r.context.id == "2" || r.context.id == "3"));
     }
 
+    #[tokio::test]
+    async fn test_neighborhood() {
+        let engine = DefaultContextRelationshipEngine::new();
+        let component = ContextType::Custom("framework_component".to_string());
+        let mut contexts = vec![
+            create_test_context("c1", "project1", component.clone(), "CheckoutPage", "presentation component"),
+            create_test_context("c2", "project1", component, "PaymentService", "domain service"),
+            create_test_context("r1", "project1", ContextType::BusinessRule, "Capture", "Orders are captured when shipped to the customer"),
+            create_test_context("r2", "project1", ContextType::BusinessRule, "Abroad", "Orders are captured when shipped to customers abroad"),
+            create_test_context("d1", "project1", ContextType::ArchitecturalDecision, "Use Postgres", "Store data in Postgres"),
+        ];
+        contexts[0].relationships.push(ContextRelationship::new("c2".to_string(), RelationshipType::DependsOn, 1.0, false));
+        contexts[2].relationships.push(ContextRelationship::new("c2".to_string(), RelationshipType::Implements, 1.0, false));
+
+        let mut graph = engine.build_relationship_graph(&contexts).await.unwrap();
+        for context in &contexts {
+            for relationship in &context.relationships {
+                graph.add_edge(context.id.clone(), relationship.clone());
+            }
+        }
+
+        // Rules relate to decisions by type alone, which the walk ignores
+        let two_hops = graph.neighborhood(&"c1".to_string(), 2);
+        let ids: Vec<&str> = two_hops.iter().map(|r| r.context.id.as_str()).collect();
+        assert_eq!(ids, vec!["c2", "r1"]);
+        assert_eq!((two_hops[0].strength, two_hops[0].incoming, two_hops[0].inferred), (1.0, false, false));
+        assert!((two_hops[1].strength - 0.8).abs() < 1e-9);
+        assert!(two_hops[1].incoming);
+        assert_eq!(two_hops[1].path, vec!["c1", "c2", "r1"]);
+
+        // The similar rule is a third hop away, through an inferred relationship
+        let three_hops = graph.neighborhood(&"c1".to_string(), 3);
+        let similar = three_hops.iter().find(|r| r.context.id == "r2").unwrap();
+        assert_eq!((similar.depth, similar.inferred), (3, true));
+        assert_eq!(similar.relationship_type, RelationshipType::Similar);
+        assert!((similar.strength - 0.8 * 0.36 * 0.8).abs() < 1e-9);
+        assert!(!three_hops.iter().any(|r| r.context.id == "d1"));
+    }
+
     #[test]
     fn test_text_similarity() {
         let similarity = calculate_text_similarity(
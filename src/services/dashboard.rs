@@ -0,0 +1,438 @@
+use crate::models::context::Project;
+use crate::services::analytics_service::{AnalyticsService, DailyUsage, ProjectInsights};
+use crate::services::specification_analytics_service::{
+    DevelopmentVelocity, SpecificationAnalyticsService, SpecificationHealthReport,
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+const SUCCESS_COLOR: &str = "#2f9e44";
+const FAILURE_COLOR: &str = "#e03131";
+const OPEN_COLOR: &str = "#f08c00";
+const CLOSED_COLOR: &str = "#1971c2";
+const CHART_WIDTH: f64 = 640.0;
+const CHART_HEIGHT: f64 = 200.0;
+
+/// Usage, velocity and quality of a project over its last `days`, as a dashboard shows them.
+/// Parts that could not be computed are left out.
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardData {
+    pub project_id: String,
+    pub project_name: String,
+    pub days: u32,
+    pub generated_at: DateTime<Utc>,
+    /// One entry per day of the period, oldest first
+    pub daily_events: Vec<DayCount>,
+    /// Events of the period per type, most first
+    pub events_by_type: Vec<(String, u64)>,
+    pub insights: Option<ProjectInsights>,
+    pub velocity: Option<DevelopmentVelocity>,
+    pub health: Option<SpecificationHealthReport>,
+}
+
+/// Events of a day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DayCount {
+    pub day: NaiveDate,
+    pub successful: u64,
+    pub failed: u64,
+}
+
+impl DashboardData {
+    pub async fn collect(
+        analytics: &dyn AnalyticsService,
+        specification_analytics: &dyn SpecificationAnalyticsService,
+        project: &Project,
+        days: u32,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<Self> {
+        let start = now - Duration::days(days.saturating_sub(1) as i64);
+        let usage = analytics.get_daily_usage(Some(&project.id), start, now).await?;
+
+        let insights = analytics
+            .get_project_insights(&project.id)
+            .await
+            .inspect_err(|e| warn!("Dashboard without usage insights: {}", e))
+            .ok();
+        let velocity = specification_analytics
+            .calculate_development_velocity(&project.id, days as i64)
+            .await
+            .inspect_err(|e| warn!("Dashboard without velocity: {}", e))
+            .ok();
+        let health = specification_analytics
+            .generate_health_report(&project.id)
+            .await
+            .inspect_err(|e| warn!("Dashboard without specification health: {}", e))
+            .ok();
+
+        Ok(Self::from_usage(project, days, now, &usage, insights, velocity, health))
+    }
+
+    pub fn from_usage(
+        project: &Project,
+        days: u32,
+        now: DateTime<Utc>,
+        usage: &[DailyUsage],
+        insights: Option<ProjectInsights>,
+        velocity: Option<DevelopmentVelocity>,
+        health: Option<SpecificationHealthReport>,
+    ) -> Self {
+        let today = now.date_naive();
+        let mut daily: BTreeMap<NaiveDate, DayCount> = (0..days.max(1) as i64)
+            .rev()
+            .map(|ago| today - Duration::days(ago))
+            .map(|day| (day, DayCount { day, successful: 0, failed: 0 }))
+            .collect();
+        let mut by_type: BTreeMap<&str, u64> = BTreeMap::new();
+        for entry in usage {
+            let Ok(day) = NaiveDate::parse_from_str(&entry.day, "%Y-%m-%d") else {
+                continue;
+            };
+            if let Some(count) = daily.get_mut(&day) {
+                count.successful += entry.successful;
+                count.failed += entry.events.saturating_sub(entry.successful);
+                *by_type.entry(entry.event_type.as_str()).or_default() += entry.events;
+            }
+        }
+        let mut events_by_type: Vec<(String, u64)> =
+            by_type.into_iter().map(|(event_type, count)| (event_type.to_string(), count)).collect();
+        events_by_type.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        Self {
+            project_id: project.id.clone(),
+            project_name: project.name.clone(),
+            days,
+            generated_at: now,
+            daily_events: daily.into_values().collect(),
+            events_by_type,
+            insights,
+            velocity,
+            health,
+        }
+    }
+
+    /// `context-dashboard-<project>-<date>.html`, with the project name reduced to a slug
+    pub fn default_file_name(&self) -> PathBuf {
+        let slug: String = self
+            .project_name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+            .collect::<String>()
+            .split('-')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+        let slug = if slug.is_empty() { self.project_id.as_str() } else { slug.as_str() };
+        PathBuf::from(format!("context-dashboard-{}-{}.html", slug, self.generated_at.format("%Y-%m-%d")))
+    }
+}
+
+/// Renders dashboard data as one HTML file with inline CSS and SVG charts, which opens
+/// offline; the data itself is embedded as JSON for reuse
+pub struct DashboardRenderer;
+
+impl DashboardRenderer {
+    /// Renders the dashboard to `path`, creating its directory; returns the bytes written
+    pub async fn write(data: &DashboardData, path: &Path) -> std::io::Result<usize> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let html = Self::render(data);
+        tokio::fs::write(path, &html).await?;
+        Ok(html.len())
+    }
+
+    pub fn render(data: &DashboardData) -> String {
+        let total: u64 = data.daily_events.iter().map(|d| d.successful + d.failed).sum();
+        let successful: u64 = data.daily_events.iter().map(|d| d.successful).sum();
+
+        let mut cards = vec![
+            ("Events".to_string(), total.to_string()),
+            ("Successful".to_string(), percent(successful, total)),
+        ];
+        if let Some(insights) = &data.insights {
+            cards.push(("Avg response".to_string(), format!("{:.0} ms", insights.average_response_time_ms)));
+            cards.push(("Context health".to_string(), format!("{:.0}/100", insights.context_health_score)));
+        }
+        if let Some(health) = &data.health {
+            cards.push(("Spec health".to_string(), format!("{:.0}/100", health.overall_health_score * 100.0)));
+        }
+        if let Some(velocity) = &data.velocity {
+            cards.push(("Tasks done".to_string(), velocity.tasks_completed.to_string()));
+            cards.push(("Requirements done".to_string(), velocity.requirements_completed.to_string()));
+        }
+
+        let mut body = String::new();
+        let _ = write!(
+            body,
+            "<header><h1>{}</h1><p>Last {} days to {} &middot; generated {}</p></header>",
+            escape(&data.project_name),
+            data.days,
+            data.generated_at.format("%Y-%m-%d"),
+            data.generated_at.format("%Y-%m-%d %H:%M UTC"),
+        );
+        body.push_str("<section class=\"cards\">");
+        for (label, value) in &cards {
+            let _ = write!(body, "<div class=\"card\"><span>{}</span><strong>{}</strong></div>", escape(label), escape(value));
+        }
+        body.push_str("</section>");
+
+        let labels: Vec<String> = data.daily_events.iter().map(|d| d.day.format("%m-%d").to_string()).collect();
+        let stacks: Vec<Vec<(f64, &str)>> = data
+            .daily_events
+            .iter()
+            .map(|d| vec![(d.successful as f64, SUCCESS_COLOR), (d.failed as f64, FAILURE_COLOR)])
+            .collect();
+        section(
+            &mut body,
+            "Daily activity",
+            &legend(&[("Successful", SUCCESS_COLOR), ("Failed", FAILURE_COLOR)]),
+            &bar_chart(&labels, &stacks),
+        );
+        section(&mut body, "Events by type", "", &horizontal_bars(&data.events_by_type));
+
+        if let Some(velocity) = &data.velocity {
+            if !velocity.task_flow.is_empty() {
+                let labels: Vec<String> = velocity.task_flow.iter().map(|p| p.date.format("%m-%d").to_string()).collect();
+                let open: Vec<f64> = velocity.task_flow.iter().map(|p| p.open as f64).collect();
+                let closed: Vec<f64> = velocity.task_flow.iter().map(|p| p.closed as f64).collect();
+                section(
+                    &mut body,
+                    "Task flow",
+                    &legend(&[("Open", OPEN_COLOR), ("Closed", CLOSED_COLOR)]),
+                    &line_chart(&labels, &[(open, OPEN_COLOR), (closed, CLOSED_COLOR)]),
+                );
+            }
+            if !velocity.requirements_completed_per_week.is_empty() {
+                let labels: Vec<String> = velocity
+                    .requirements_completed_per_week
+                    .iter()
+                    .map(|w| w.week_start.format("%m-%d").to_string())
+                    .collect();
+                let stacks: Vec<Vec<(f64, &str)>> = velocity
+                    .requirements_completed_per_week
+                    .iter()
+                    .map(|w| vec![(w.completed as f64, CLOSED_COLOR)])
+                    .collect();
+                section(&mut body, "Requirements completed per week", "", &bar_chart(&labels, &stacks));
+            }
+        }
+
+        let mut notes: Vec<(&str, &[String])> = Vec::new();
+        if let Some(health) = &data.health {
+            notes.push(("Critical issues", &health.critical_issues));
+            notes.push(("Specification recommendations", &health.recommendations));
+        }
+        if let Some(velocity) = &data.velocity {
+            notes.push(("Bottlenecks", &velocity.bottlenecks));
+        }
+        if let Some(insights) = &data.insights {
+            notes.push(("Usage recommendations", &insights.recommendations));
+        }
+        for (title, items) in notes.into_iter().filter(|(_, items)| !items.is_empty()) {
+            let list: String = items.iter().map(|item| format!("<li>{}</li>", escape(item))).collect();
+            section(&mut body, title, "", &format!("<ul>{}</ul>", list));
+        }
+
+        // `</` would end the script element early
+        let json = serde_json::to_string(data).unwrap_or_default().replace("</", "<\\/");
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+             <title>{} dashboard</title>\n<style>{}</style>\n</head>\n<body>\n{}\n\
+             <script type=\"application/json\" id=\"dashboard-data\">{}</script>\n</body>\n</html>\n",
+            escape(&data.project_name),
+            STYLE,
+            body,
+            json
+        )
+    }
+}
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem auto;max-width:60rem;color:#212529;padding:0 1rem}\
+header p{color:#868e96;margin-top:0}\
+.cards{display:flex;flex-wrap:wrap;gap:.75rem}\
+.card{border:1px solid #dee2e6;border-radius:.5rem;padding:.75rem 1rem;min-width:8rem}\
+.card span{display:block;font-size:.8rem;color:#868e96}.card strong{font-size:1.4rem}\
+section{margin-top:2rem}h2{font-size:1.1rem}\
+.legend span{margin-right:1rem;font-size:.8rem}.legend i{display:inline-block;width:.7rem;height:.7rem;margin-right:.3rem}\
+svg{max-width:100%;height:auto}svg text{font-size:10px;fill:#868e96}\
+.bar{display:flex;align-items:center;gap:.5rem;font-size:.85rem;margin:.2rem 0}\
+.bar span{width:14rem}.bar div{background:#1971c2;height:.8rem}";
+
+fn section(body: &mut String, title: &str, legend: &str, content: &str) {
+    let _ = write!(body, "<section><h2>{}</h2>{}{}</section>", escape(title), legend, content);
+}
+
+fn legend(entries: &[(&str, &str)]) -> String {
+    let items: String = entries
+        .iter()
+        .map(|(label, color)| format!("<span><i style=\"background:{}\"></i>{}</span>", color, escape(label)))
+        .collect();
+    format!("<div class=\"legend\">{}</div>", items)
+}
+
+/// Stacked vertical bars, one per label
+fn bar_chart(labels: &[String], stacks: &[Vec<(f64, &str)>]) -> String {
+    let max = stacks.iter().map(|stack| stack.iter().map(|(v, _)| v).sum::<f64>()).fold(0.0, f64::max);
+    let slot = CHART_WIDTH / labels.len().max(1) as f64;
+    let mut svg = svg_open(labels, max);
+    for (index, stack) in stacks.iter().enumerate() {
+        let mut top = CHART_HEIGHT;
+        for (value, color) in stack.iter().filter(|(value, _)| *value > 0.0) {
+            let height = value / max * CHART_HEIGHT;
+            top -= height;
+            let _ = write!(
+                svg,
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"><title>{}: {}</title></rect>",
+                index as f64 * slot + slot * 0.15,
+                top,
+                slot * 0.7,
+                height,
+                color,
+                escape(&labels[index]),
+                value
+            );
+        }
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// One polyline per series over the same labels
+fn line_chart(labels: &[String], series: &[(Vec<f64>, &str)]) -> String {
+    let max = series.iter().flat_map(|(values, _)| values.iter().copied()).fold(0.0, f64::max);
+    let step = CHART_WIDTH / labels.len().max(1) as f64;
+    let mut svg = svg_open(labels, max);
+    for (values, color) in series {
+        let points: Vec<String> = values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let y = if max > 0.0 { CHART_HEIGHT - value / max * CHART_HEIGHT } else { CHART_HEIGHT };
+                format!("{:.1},{:.1}", index as f64 * step + step / 2.0, y)
+            })
+            .collect();
+        let _ = write!(
+            svg,
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+            points.join(" "),
+            color
+        );
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// The chart area with a baseline, the maximum and every few labels below it
+fn svg_open(labels: &[String], max: f64) -> String {
+    let slot = CHART_WIDTH / labels.len().max(1) as f64;
+    let mut svg = format!(
+        "<svg viewBox=\"0 -12 {w} {h}\" xmlns=\"http://www.w3.org/2000/svg\" role=\"img\">\
+         <line x1=\"0\" y1=\"{base}\" x2=\"{w}\" y2=\"{base}\" stroke=\"#dee2e6\"/>\
+         <text x=\"0\" y=\"-2\">{max}</text>",
+        w = CHART_WIDTH,
+        h = CHART_HEIGHT + 30.0,
+        base = CHART_HEIGHT,
+        max = max
+    );
+    let every = labels.len().div_ceil(10).max(1);
+    for (index, label) in labels.iter().enumerate().step_by(every) {
+        let _ = write!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{}\" text-anchor=\"middle\">{}</text>",
+            index as f64 * slot + slot / 2.0,
+            CHART_HEIGHT + 14.0,
+            escape(label)
+        );
+    }
+    svg
+}
+
+/// Bars as sized blocks, for a few long labels
+fn horizontal_bars(values: &[(String, u64)]) -> String {
+    let max = values.iter().map(|(_, v)| *v).max().unwrap_or(0).max(1);
+    values
+        .iter()
+        .map(|(label, value)| {
+            format!(
+                "<div class=\"bar\"><span>{}</span><div style=\"width:{:.1}%\"></div>{}</div>",
+                escape(label),
+                *value as f64 / max as f64 * 60.0,
+                value
+            )
+        })
+        .collect()
+}
+
+fn percent(part: u64, total: u64) -> String {
+    if total == 0 {
+        "n/a".to_string()
+    } else {
+        format!("{:.0}%", part as f64 / total as f64 * 100.0)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(day: &str, event_type: &str, events: u64, successful: u64) -> DailyUsage {
+        DailyUsage { day: day.to_string(), event_type: event_type.to_string(), events, successful }
+    }
+
+    #[test]
+    fn test_render_dashboard() {
+        let project = Project {
+            id: "p1".to_string(),
+            name: "Shop <Admin> & Co".to_string(),
+            description: None,
+            repository_url: None,
+            created_at: None,
+            updated_at: None,
+        };
+        let now = DateTime::parse_from_rfc3339("2024-05-10T12:00:00Z").unwrap().with_timezone(&Utc);
+        let data = DashboardData::from_usage(
+            &project,
+            7,
+            now,
+            &[
+                usage("2024-05-01", "ContextQuery", 9, 9),
+                usage("2024-05-04", "ContextQuery", 4, 3),
+                usage("2024-05-04", "EntityCreate", 2, 2),
+                usage("2024-05-10", "EntityCreate", 1, 0),
+            ],
+            None,
+            None,
+            None,
+        );
+        assert_eq!(data.daily_events.len(), 7);
+        assert_eq!(data.daily_events[0].day, NaiveDate::from_ymd_opt(2024, 5, 4).unwrap());
+        assert_eq!((data.daily_events[0].successful, data.daily_events[0].failed), (5, 1));
+        assert_eq!((data.daily_events[6].successful, data.daily_events[6].failed), (0, 1));
+        assert_eq!(data.events_by_type, vec![("ContextQuery".to_string(), 4), ("EntityCreate".to_string(), 3)]);
+        assert_eq!(data.default_file_name(), PathBuf::from("context-dashboard-shop-admin-co-2024-05-10.html"));
+
+        let html = DashboardRenderer::render(&data);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h1>Shop &lt;Admin&gt; &amp; Co</h1>"));
+        assert!(html.contains("<strong>7</strong>"));
+        assert!(html.contains("<strong>71%</strong>"));
+        assert_eq!(html.matches("<svg").count(), 1);
+        assert!(html.contains(r#""project_name":"Shop <Admin> & Co""#));
+        // Nothing is loaded from elsewhere
+        assert!(!html.contains(" src=") && !html.contains("<link"));
+    }
+}
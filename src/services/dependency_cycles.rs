@@ -0,0 +1,381 @@
+use crate::models::development::DevelopmentPhase;
+use crate::models::framework::FrameworkComponent;
+use crate::models::specification::Task;
+use crate::services::dependency_graph::DependencyGraph;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+/// Cycles with at most this many dependencies get an exact smallest break set; larger ones a
+/// heuristic set
+const EXACT_BREAK_EDGE_LIMIT: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CycleEdge {
+    pub from: String,
+    pub to: String,
+}
+
+/// Items that depend on each other in a loop
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DependencyCycle {
+    /// `component`, `phase` or `task`
+    pub kind: String,
+    /// Everything on some loop with the others, in input order
+    pub members: Vec<String>,
+    /// One loop through the members, ending where it starts
+    pub path: Vec<String>,
+    /// Dependencies whose removal leaves no loop among the members
+    pub break_edges: Vec<CycleEdge>,
+    /// Whether `break_edges` is a smallest such set; large cycles get a heuristic one
+    pub minimal: bool,
+}
+
+impl DependencyCycle {
+    pub fn describe(&self) -> String {
+        let breaks: Vec<String> = self.break_edges.iter().map(|edge| format!("{} -> {}", edge.from, edge.to)).collect();
+        format!(
+            "Dependency cycle: {} (remove {} to break it)",
+            self.path.join(" -> "),
+            breaks.join(", ")
+        )
+    }
+}
+
+/// Finds dependency cycles as strongly connected components
+pub struct CycleDetector;
+
+impl CycleDetector {
+    /// Components depend on others named by file path or name, as in the dependency graph
+    pub fn component_cycles(components: &[FrameworkComponent]) -> Vec<DependencyCycle> {
+        let labels: Vec<String> = components.iter().map(|c| c.component_name.clone()).collect();
+        let edges: Vec<(usize, usize)> = components
+            .iter()
+            .enumerate()
+            .flat_map(|(from, component)| {
+                component
+                    .dependencies
+                    .iter()
+                    .filter_map(move |dep| DependencyGraph::resolve(components, dep).map(|to| (from, to)))
+            })
+            .collect();
+        Self::find_cycles("component", &labels, &edges)
+    }
+
+    /// Phases depend on others named by id or phase name
+    pub fn phase_cycles(phases: &[DevelopmentPhase]) -> Vec<DependencyCycle> {
+        let labels: Vec<String> = phases.iter().map(|p| p.phase_name.clone()).collect();
+        let edges: Vec<(usize, usize)> = phases
+            .iter()
+            .enumerate()
+            .flat_map(|(from, phase)| {
+                phase.dependencies.iter().filter_map(move |dep| {
+                    phases
+                        .iter()
+                        .position(|p| &p.id == dep)
+                        .or_else(|| phases.iter().position(|p| &p.phase_name == dep))
+                        .map(|to| (from, to))
+                })
+            })
+            .collect();
+        Self::find_cycles("phase", &labels, &edges)
+    }
+
+    /// Tasks depend on others by task id
+    pub fn task_cycles(tasks: &[Task]) -> Vec<DependencyCycle> {
+        let labels: Vec<String> = tasks.iter().map(|t| t.title.clone()).collect();
+        let edges: Vec<(usize, usize)> = tasks
+            .iter()
+            .enumerate()
+            .flat_map(|(from, task)| {
+                task.dependencies
+                    .iter()
+                    .filter_map(move |dep| tasks.iter().position(|t| &t.id == dep).map(|to| (from, to)))
+            })
+            .collect();
+        Self::find_cycles("task", &labels, &edges)
+    }
+
+    /// Cycles among `labels`, with `edges` as (dependent, dependency) indexes into them
+    pub fn find_cycles(kind: &str, labels: &[String], edges: &[(usize, usize)]) -> Vec<DependencyCycle> {
+        let edges: Vec<(usize, usize)> = edges.iter().copied().collect::<BTreeSet<_>>().into_iter().collect();
+        let mut adjacency = vec![Vec::new(); labels.len()];
+        for &(from, to) in &edges {
+            adjacency[from].push(to);
+        }
+
+        let mut cycles = Vec::new();
+        for mut members in Self::strongly_connected(&adjacency) {
+            members.sort_unstable();
+            let inner: Vec<(usize, usize)> = edges
+                .iter()
+                .copied()
+                .filter(|(from, to)| members.binary_search(from).is_ok() && members.binary_search(to).is_ok())
+                .collect();
+            if inner.is_empty() {
+                continue;
+            }
+            let (break_edges, minimal) = Self::break_set(&members, &inner);
+            let edge = |&(from, to): &(usize, usize)| CycleEdge {
+                from: labels[from].clone(),
+                to: labels[to].clone(),
+            };
+            cycles.push(DependencyCycle {
+                kind: kind.to_string(),
+                members: members.iter().map(|&m| labels[m].clone()).collect(),
+                path: Self::loop_through(members[0], &inner).into_iter().map(|m| labels[m].clone()).collect(),
+                break_edges: break_edges.iter().map(edge).collect(),
+                minimal,
+            });
+        }
+        cycles.sort_by(|a, b| a.members.cmp(&b.members));
+        cycles
+    }
+
+    /// Tarjan's algorithm
+    fn strongly_connected(adjacency: &[Vec<usize>]) -> Vec<Vec<usize>> {
+        struct Tarjan<'a> {
+            adjacency: &'a [Vec<usize>],
+            index: Vec<Option<usize>>,
+            low: Vec<usize>,
+            on_stack: Vec<bool>,
+            stack: Vec<usize>,
+            next: usize,
+            components: Vec<Vec<usize>>,
+        }
+
+        impl Tarjan<'_> {
+            fn visit(&mut self, v: usize) {
+                self.index[v] = Some(self.next);
+                self.low[v] = self.next;
+                self.next += 1;
+                self.stack.push(v);
+                self.on_stack[v] = true;
+
+                let adjacency = self.adjacency;
+                for &w in &adjacency[v] {
+                    match self.index[w] {
+                        None => {
+                            self.visit(w);
+                            self.low[v] = self.low[v].min(self.low[w]);
+                        }
+                        Some(index) if self.on_stack[w] => self.low[v] = self.low[v].min(index),
+                        Some(_) => {}
+                    }
+                }
+
+                if Some(self.low[v]) == self.index[v] {
+                    let mut component = Vec::new();
+                    while let Some(w) = self.stack.pop() {
+                        self.on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    self.components.push(component);
+                }
+            }
+        }
+
+        let n = adjacency.len();
+        let mut tarjan = Tarjan {
+            adjacency,
+            index: vec![None; n],
+            low: vec![0; n],
+            on_stack: vec![false; n],
+            stack: Vec::new(),
+            next: 0,
+            components: Vec::new(),
+        };
+        for v in 0..n {
+            if tarjan.index[v].is_none() {
+                tarjan.visit(v);
+            }
+        }
+        tarjan.components
+    }
+
+    /// Shortest loop from `start` back to itself
+    fn loop_through(start: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+        let mut parent: HashMap<usize, usize> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            for &(_, to) in edges.iter().filter(|(from, _)| *from == node) {
+                if to == start {
+                    let mut path = Vec::new();
+                    let mut current = node;
+                    while current != start {
+                        path.push(current);
+                        current = parent[&current];
+                    }
+                    path.push(start);
+                    path.reverse();
+                    path.push(start);
+                    return path;
+                }
+                if to != start && !parent.contains_key(&to) {
+                    parent.insert(to, node);
+                    queue.push_back(to);
+                }
+            }
+        }
+        vec![start]
+    }
+
+    /// Dependencies to remove so no loop remains: self-dependencies, then the fewest others
+    /// when there are few enough to search, else the back edges of a greedy ordering
+    fn break_set(members: &[usize], edges: &[(usize, usize)]) -> (Vec<(usize, usize)>, bool) {
+        let self_loops: Vec<(usize, usize)> = edges.iter().copied().filter(|(from, to)| from == to).collect();
+        let edges: Vec<(usize, usize)> = edges.iter().copied().filter(|(from, to)| from != to).collect();
+        if Self::is_acyclic(members, &edges) {
+            return (self_loops, true);
+        }
+
+        if edges.len() <= EXACT_BREAK_EDGE_LIMIT {
+            for size in 1..=edges.len() {
+                let mut chosen: Vec<usize> = (0..size).collect();
+                loop {
+                    let remaining: Vec<(usize, usize)> = edges
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| !chosen.contains(i))
+                        .map(|(_, edge)| *edge)
+                        .collect();
+                    if Self::is_acyclic(members, &remaining) {
+                        let mut result = self_loops;
+                        result.extend(chosen.iter().map(|&i| edges[i]));
+                        return (result, true);
+                    }
+                    if !Self::next_combination(&mut chosen, edges.len()) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let order = Self::greedy_order(members, &edges);
+        let position: HashMap<usize, usize> = order.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+        let mut result = self_loops;
+        result.extend(edges.iter().copied().filter(|(from, to)| position[from] > position[to]));
+        (result, false)
+    }
+
+    /// Advances `chosen` to the next `chosen.len()`-subset of `0..n` in lexicographic order
+    fn next_combination(chosen: &mut [usize], n: usize) -> bool {
+        let k = chosen.len();
+        for i in (0..k).rev() {
+            if chosen[i] < n - k + i {
+                chosen[i] += 1;
+                for j in i + 1..k {
+                    chosen[j] = chosen[j - 1] + 1;
+                }
+                return true;
+            }
+        }
+        false
+    }
+
+    fn is_acyclic(nodes: &[usize], edges: &[(usize, usize)]) -> bool {
+        let mut in_degree: HashMap<usize, usize> = nodes.iter().map(|&n| (n, 0)).collect();
+        for (_, to) in edges {
+            *in_degree.entry(*to).or_default() += 1;
+        }
+        let mut ready: Vec<usize> = in_degree.iter().filter(|(_, &d)| d == 0).map(|(&n, _)| n).collect();
+        let mut visited = 0;
+        while let Some(node) = ready.pop() {
+            visited += 1;
+            for (_, to) in edges.iter().filter(|(from, _)| *from == node) {
+                let degree = in_degree.get_mut(to).expect("edge within nodes");
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push(*to);
+                }
+            }
+        }
+        visited == in_degree.len()
+    }
+
+    /// Eades–Lin–Smyth ordering: sinks go last, sources first, otherwise the node with the most
+    /// outgoing over incoming dependencies comes next
+    fn greedy_order(nodes: &[usize], edges: &[(usize, usize)]) -> Vec<usize> {
+        let mut remaining: BTreeSet<usize> = nodes.iter().copied().collect();
+        let (mut first, mut last) = (Vec::new(), Vec::new());
+        let degrees = |remaining: &BTreeSet<usize>, node: usize| {
+            let live = |n: &usize| remaining.contains(n);
+            let out = edges.iter().filter(|(from, to)| *from == node && live(to)).count();
+            let incoming = edges.iter().filter(|(from, to)| *to == node && live(from)).count();
+            (out, incoming)
+        };
+
+        while !remaining.is_empty() {
+            if let Some(sink) = remaining.iter().copied().find(|&n| degrees(&remaining, n).0 == 0) {
+                remaining.remove(&sink);
+                last.push(sink);
+                continue;
+            }
+            if let Some(source) = remaining.iter().copied().find(|&n| degrees(&remaining, n).1 == 0) {
+                remaining.remove(&source);
+                first.push(source);
+                continue;
+            }
+            let next = remaining
+                .iter()
+                .copied()
+                .max_by_key(|&n| {
+                    let (out, incoming) = degrees(&remaining, n);
+                    (out as i64 - incoming as i64, std::cmp::Reverse(n))
+                })
+                .expect("remaining is not empty");
+            remaining.remove(&next);
+            first.push(next);
+        }
+        first.extend(last.into_iter().rev());
+        first
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_cycles_with_smallest_break_sets() {
+        // a -> b -> c -> a and b -> a share the edge a -> b; d -> e is not a cycle; f depends on itself
+        let names = labels(&["a", "b", "c", "d", "e", "f"]);
+        let edges = [(0, 1), (1, 2), (2, 0), (1, 0), (3, 4), (5, 5)];
+        let cycles = CycleDetector::find_cycles("task", &names, &edges);
+        assert_eq!(cycles.len(), 2);
+
+        assert_eq!(cycles[0].members, labels(&["a", "b", "c"]));
+        assert_eq!(cycles[0].path, labels(&["a", "b", "a"]));
+        assert_eq!(cycles[0].break_edges, vec![CycleEdge { from: "a".to_string(), to: "b".to_string() }]);
+        assert!(cycles[0].minimal);
+        assert_eq!(cycles[0].describe(), "Dependency cycle: a -> b -> a (remove a -> b to break it)");
+
+        assert_eq!(cycles[1].path, labels(&["f", "f"]));
+        assert_eq!(cycles[1].break_edges, vec![CycleEdge { from: "f".to_string(), to: "f".to_string() }]);
+    }
+
+    #[test]
+    fn test_large_cycles_get_a_heuristic_break_set() {
+        // A complete graph on six nodes has 30 edges, too many to search
+        let names = labels(&["a", "b", "c", "d", "e", "f"]);
+        let edges: Vec<(usize, usize)> = (0..6).flat_map(|i| (0..6).filter(move |&j| j != i).map(move |j| (i, j))).collect();
+        let cycles = CycleDetector::find_cycles("component", &names, &edges);
+        assert_eq!(cycles.len(), 1);
+        assert!(!cycles[0].minimal);
+        assert_eq!(cycles[0].break_edges.len(), 15);
+
+        let removed: Vec<(usize, usize)> = cycles[0]
+            .break_edges
+            .iter()
+            .map(|e| (names.iter().position(|n| *n == e.from).unwrap(), names.iter().position(|n| *n == e.to).unwrap()))
+            .collect();
+        let rest: Vec<(usize, usize)> = edges.into_iter().filter(|e| !removed.contains(e)).collect();
+        assert!(CycleDetector::is_acyclic(&[0, 1, 2, 3, 4, 5], &rest));
+    }
+}
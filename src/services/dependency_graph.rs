@@ -0,0 +1,302 @@
+use crate::models::framework::FrameworkComponent;
+use std::collections::HashMap;
+
+/// Text format a dependency graph is rendered in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+impl GraphFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format.to_lowercase().as_str() {
+            "dot" | "graphviz" => Some(GraphFormat::Dot),
+            "mermaid" => Some(GraphFormat::Mermaid),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "dot",
+            GraphFormat::Mermaid => "mermaid",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    pub id: String,
+    pub label: String,
+    pub component_type: Option<String>,
+    /// None for dependencies outside the project's components
+    pub layer: Option<String>,
+    pub violations: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    pub violation: bool,
+}
+
+/// Framework components grouped by layer, with an edge for each dependency on another component
+pub struct DependencyGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// Whether nodes and edges are colored by violation status
+    pub colorized: bool,
+}
+
+impl DependencyGraph {
+    /// A dependency names another component by file path or name; other dependencies are left
+    /// out unless `include_external`. With `violations` (component id → violation messages),
+    /// components with violations and the dependencies the messages name are marked.
+    pub fn build(
+        components: &[FrameworkComponent],
+        violations: Option<&HashMap<String, Vec<String>>>,
+        include_external: bool,
+    ) -> Self {
+        let mut nodes: Vec<GraphNode> = components
+            .iter()
+            .enumerate()
+            .map(|(index, component)| GraphNode {
+                id: format!("c{}", index),
+                label: component.component_name.clone(),
+                component_type: Some(component.component_type.clone()),
+                layer: Some(component.architecture_layer.clone()),
+                violations: violations
+                    .and_then(|violations| violations.get(&component.id))
+                    .map_or(0, Vec::len),
+            })
+            .collect();
+
+        let mut external: HashMap<String, String> = HashMap::new();
+        let mut edges = Vec::new();
+        for (index, component) in components.iter().enumerate() {
+            let messages = violations.and_then(|violations| violations.get(&component.id));
+            for dep in &component.dependencies {
+                let to = match Self::resolve(components, dep) {
+                    Some(target) if target == index => continue,
+                    Some(target) => format!("c{}", target),
+                    None if include_external => {
+                        if !external.contains_key(dep) {
+                            let id = format!("x{}", external.len());
+                            nodes.push(GraphNode {
+                                id: id.clone(),
+                                label: dep.clone(),
+                                component_type: None,
+                                layer: None,
+                                violations: 0,
+                            });
+                            external.insert(dep.clone(), id);
+                        }
+                        external[dep].clone()
+                    }
+                    None => continue,
+                };
+                let violation = messages.is_some_and(|messages| {
+                    messages.iter().any(|message| message.contains(&format!(" {}", dep)))
+                });
+                edges.push(GraphEdge { from: format!("c{}", index), to, violation });
+            }
+        }
+
+        Self {
+            nodes,
+            edges,
+            colorized: violations.is_some(),
+        }
+    }
+
+    /// Index of the component a dependency names, by file path or else by name
+    pub fn resolve(components: &[FrameworkComponent], dep: &str) -> Option<usize> {
+        components
+            .iter()
+            .position(|c| c.file_path.as_deref() == Some(dep))
+            .or_else(|| components.iter().position(|c| c.component_name == dep))
+    }
+
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.render_dot(),
+            GraphFormat::Mermaid => self.render_mermaid(),
+        }
+    }
+
+    /// Layers in order of first appearance, with their nodes
+    fn layers(&self) -> Vec<(&str, Vec<&GraphNode>)> {
+        let mut layers: Vec<(&str, Vec<&GraphNode>)> = Vec::new();
+        for node in &self.nodes {
+            let Some(layer) = node.layer.as_deref() else {
+                continue;
+            };
+            match layers.iter_mut().find(|(name, _)| *name == layer) {
+                Some((_, nodes)) => nodes.push(node),
+                None => layers.push((layer, vec![node])),
+            }
+        }
+        layers
+    }
+
+    /// Graphviz DOT with a cluster per layer
+    pub fn render_dot(&self) -> String {
+        let escape = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+        let mut out = String::from("digraph dependencies {\n    rankdir=LR;\n    node [shape=box, style=filled, fillcolor=white];\n");
+
+        for (index, (layer, nodes)) in self.layers().into_iter().enumerate() {
+            out.push_str(&format!("\n    subgraph cluster_{} {{\n        label=\"{}\";\n", index, escape(layer)));
+            for node in nodes {
+                let mut attributes = format!(
+                    "label=\"{}\\n{}\"",
+                    escape(&node.label),
+                    escape(node.component_type.as_deref().unwrap_or_default())
+                );
+                if self.colorized {
+                    attributes.push_str(if node.violations > 0 {
+                        ", fillcolor=\"#f8d7da\", color=\"#c00000\""
+                    } else {
+                        ", fillcolor=\"#d4edda\""
+                    });
+                }
+                out.push_str(&format!("        {} [{}];\n", node.id, attributes));
+            }
+            out.push_str("    }\n");
+        }
+
+        let external: Vec<&GraphNode> = self.nodes.iter().filter(|node| node.layer.is_none()).collect();
+        if !external.is_empty() {
+            out.push('\n');
+            for node in external {
+                out.push_str(&format!("    {} [label=\"{}\", shape=ellipse, style=dashed];\n", node.id, escape(&node.label)));
+            }
+        }
+
+        out.push('\n');
+        for edge in &self.edges {
+            if self.colorized && edge.violation {
+                out.push_str(&format!("    {} -> {} [color=\"#c00000\", penwidth=2];\n", edge.from, edge.to));
+            } else {
+                out.push_str(&format!("    {} -> {};\n", edge.from, edge.to));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Mermaid flowchart with a subgraph per layer
+    pub fn render_mermaid(&self) -> String {
+        let escape = |text: &str| text.replace('"', "#quot;");
+        let mut out = String::from("flowchart LR\n");
+
+        for (index, (layer, nodes)) in self.layers().into_iter().enumerate() {
+            out.push_str(&format!("    subgraph layer_{}[\"{}\"]\n", index, escape(layer)));
+            for node in nodes {
+                out.push_str(&format!(
+                    "        {}[\"{}<br/><small>{}</small>\"]\n",
+                    node.id,
+                    escape(&node.label),
+                    escape(node.component_type.as_deref().unwrap_or_default())
+                ));
+            }
+            out.push_str("    end\n");
+        }
+        for node in self.nodes.iter().filter(|node| node.layer.is_none()) {
+            out.push_str(&format!("    {}([\"{}\"])\n", node.id, escape(&node.label)));
+        }
+
+        for edge in &self.edges {
+            out.push_str(&format!("    {} --> {}\n", edge.from, edge.to));
+        }
+
+        if self.colorized {
+            out.push_str("    classDef violation fill:#f8d7da,stroke:#c00000\n    classDef clean fill:#d4edda\n");
+            let (violating, clean): (Vec<&GraphNode>, Vec<&GraphNode>) = self
+                .nodes
+                .iter()
+                .filter(|node| node.layer.is_some())
+                .partition(|node| node.violations > 0);
+            for (class, nodes) in [("violation", violating), ("clean", clean)] {
+                if !nodes.is_empty() {
+                    let ids: Vec<&str> = nodes.iter().map(|node| node.id.as_str()).collect();
+                    out.push_str(&format!("    class {} {}\n", ids.join(","), class));
+                }
+            }
+            // Links are styled by their position in the order they were declared
+            let violating: Vec<String> = self
+                .edges
+                .iter()
+                .enumerate()
+                .filter(|(_, edge)| edge.violation)
+                .map(|(index, _)| index.to_string())
+                .collect();
+            if !violating.is_empty() {
+                out.push_str(&format!("    linkStyle {} stroke:#c00000,stroke-width:2px\n", violating.join(",")));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(id: &str, layer: &str, file_path: &str, dependencies: &[&str]) -> FrameworkComponent {
+        FrameworkComponent {
+            id: id.to_string(),
+            project_id: "p1".to_string(),
+            component_name: id.to_string(),
+            component_type: "service".to_string(),
+            architecture_layer: layer.to_string(),
+            file_path: Some(file_path.to_string()),
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            metadata: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_render_with_violations() {
+        let components = vec![
+            component("LoginPage", "presentation", "lib/ui/login_page.dart", &["lib/domain/auth.dart", "lib/data/api.dart", "package:http/http.dart"]),
+            component("Auth", "domain", "lib/domain/auth.dart", &[]),
+            component("Api", "data", "lib/data/api.dart", &["Auth"]),
+        ];
+        let violations = HashMap::from([(
+            "LoginPage".to_string(),
+            vec!["Architecture violation: LoginPage (presentation) imports from data layer: lib/data/api.dart".to_string()],
+        )]);
+
+        let graph = DependencyGraph::build(&components, Some(&violations), false);
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(
+            graph.edges,
+            vec![
+                GraphEdge { from: "c0".to_string(), to: "c1".to_string(), violation: false },
+                GraphEdge { from: "c0".to_string(), to: "c2".to_string(), violation: true },
+                GraphEdge { from: "c2".to_string(), to: "c1".to_string(), violation: false },
+            ]
+        );
+
+        let dot = graph.render(GraphFormat::Dot);
+        assert!(dot.contains("subgraph cluster_0 {\n        label=\"presentation\";"));
+        assert!(dot.contains("c0 [label=\"LoginPage\\nservice\", fillcolor=\"#f8d7da\", color=\"#c00000\"];"));
+        assert!(dot.contains("c0 -> c2 [color=\"#c00000\", penwidth=2];"));
+        assert!(dot.contains("    c2 -> c1;\n"));
+
+        let mermaid = graph.render(GraphFormat::Mermaid);
+        assert!(mermaid.starts_with("flowchart LR\n    subgraph layer_0[\"presentation\"]\n"));
+        assert!(mermaid.contains("    class c0 violation\n    class c1,c2 clean\n"));
+        assert!(mermaid.contains("    linkStyle 1 stroke:#c00000,stroke-width:2px\n"));
+
+        let plain = DependencyGraph::build(&components, None, true);
+        assert_eq!(plain.nodes[3].label, "package:http/http.dart");
+        let mermaid = plain.render(GraphFormat::Mermaid);
+        assert!(mermaid.contains("    x0([\"package:http/http.dart\"])\n"));
+        assert!(!mermaid.contains("classDef"));
+    }
+}
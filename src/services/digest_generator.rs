@@ -0,0 +1,258 @@
+//! Language models that write context digests: the calling MCP client's model through
+//! sampling, or an OpenAI-compatible chat API configured under `[digests]`
+
+use crate::config::DigestSettings;
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use rmcp::model::{
+    Content, ContextInclusion, CreateMessageRequestParam, ModelHint, ModelPreferences, Role, SamplingMessage,
+};
+use rmcp::service::{Peer, RoleServer};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+const OPENAI_DEFAULT_ENDPOINT: &str = "https://api.openai.com/v1";
+
+/// Text written by a model, with the model that wrote it
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedText {
+    pub text: String,
+    pub model: String,
+}
+
+/// Writes text from a prompt
+#[async_trait]
+pub trait DigestGenerator: Send + Sync {
+    async fn generate(&self, system_prompt: &str, prompt: &str, max_tokens: u32) -> Result<GeneratedText>;
+
+    /// "sampling" or "llm", recorded with the model in a digest's `generated_by`
+    fn kind(&self) -> &'static str;
+}
+
+/// Asks the connected MCP client to run the prompt on its model (`sampling/createMessage`)
+pub struct SamplingDigestGenerator {
+    peer: Peer<RoleServer>,
+    model_hint: Option<String>,
+}
+
+impl SamplingDigestGenerator {
+    /// Fails when the client did not announce the sampling capability
+    pub fn new(peer: Peer<RoleServer>, model_hint: Option<String>) -> Result<Self> {
+        let supports_sampling = peer
+            .peer_info()
+            .is_some_and(|info| info.capabilities.sampling.is_some());
+        if !supports_sampling {
+            bail!("The MCP client does not support sampling; set [digests] provider = \"llm\" to use an API instead");
+        }
+        Ok(Self { peer, model_hint })
+    }
+}
+
+#[async_trait]
+impl DigestGenerator for SamplingDigestGenerator {
+    async fn generate(&self, system_prompt: &str, prompt: &str, max_tokens: u32) -> Result<GeneratedText> {
+        let result = self
+            .peer
+            .create_message(CreateMessageRequestParam {
+                messages: vec![SamplingMessage {
+                    role: Role::User,
+                    content: Content::text(prompt),
+                }],
+                model_preferences: self.model_hint.clone().map(|name| ModelPreferences {
+                    hints: Some(vec![ModelHint { name: Some(name) }]),
+                    cost_priority: None,
+                    speed_priority: None,
+                    intelligence_priority: None,
+                }),
+                system_prompt: Some(system_prompt.to_string()),
+                include_context: Some(ContextInclusion::None),
+                temperature: Some(0.2),
+                max_tokens,
+                stop_sequences: None,
+                metadata: None,
+            })
+            .await
+            .map_err(|e| anyhow!("Sampling request failed: {e}"))?;
+
+        let text = result
+            .message
+            .content
+            .as_text()
+            .map(|text| text.text.trim().to_string())
+            .ok_or_else(|| anyhow!("The client's model replied without text"))?;
+        Ok(GeneratedText { text, model: result.model })
+    }
+
+    fn kind(&self) -> &'static str {
+        "sampling"
+    }
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    #[serde(default)]
+    model: Option<String>,
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatMessage {
+    content: String,
+}
+
+/// An OpenAI-compatible `/chat/completions` endpoint
+pub struct HttpDigestGenerator {
+    client: reqwest::Client,
+    endpoint: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+impl HttpDigestGenerator {
+    pub fn new(settings: &DigestSettings) -> Result<Self> {
+        if settings.model.is_empty() {
+            bail!("digests.model is required for the llm provider");
+        }
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(settings.timeout_secs))
+            .build()
+            .map_err(|e| anyhow!("Failed to create HTTP client: {e}"))?;
+        let endpoint = settings
+            .endpoint
+            .clone()
+            .unwrap_or_else(|| OPENAI_DEFAULT_ENDPOINT.to_string());
+
+        Ok(Self {
+            client,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            model: settings.model.clone(),
+            api_key: settings
+                .api_key
+                .clone()
+                .or_else(|| std::env::var("OPENAI_API_KEY").ok()),
+        })
+    }
+}
+
+#[async_trait]
+impl DigestGenerator for HttpDigestGenerator {
+    async fn generate(&self, system_prompt: &str, prompt: &str, max_tokens: u32) -> Result<GeneratedText> {
+        let url = format!("{}/chat/completions", self.endpoint);
+        let body = serde_json::json!({
+            "model": self.model,
+            "temperature": 0.2,
+            "max_tokens": max_tokens,
+            "messages": [
+                {"role": "system", "content": system_prompt},
+                {"role": "user", "content": prompt}
+            ],
+        });
+        let mut request = self.client.post(&url).json(&body);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await.map_err(|e| anyhow!("{url}: {e}"))?;
+        let status = response.status();
+        if !status.is_success() {
+            let detail = response.text().await.unwrap_or_default();
+            bail!("{url} returned {status}: {detail}");
+        }
+        let reply = response
+            .json::<ChatResponse>()
+            .await
+            .map_err(|e| anyhow!("Unexpected response from {url}: {e}"))?;
+        let text = reply
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content.trim().to_string())
+            .filter(|text| !text.is_empty())
+            .ok_or_else(|| anyhow!("{url} replied without text"))?;
+        Ok(GeneratedText {
+            text,
+            model: reply.model.unwrap_or_else(|| self.model.clone()),
+        })
+    }
+
+    fn kind(&self) -> &'static str {
+        "llm"
+    }
+}
+
+pub struct DigestGeneratorFactory;
+
+impl DigestGeneratorFactory {
+    /// Translate `[digests]`; `peer` is the client that sampling requests go to. `None` when
+    /// digests are off
+    pub fn from_settings(
+        settings: &DigestSettings,
+        peer: Option<Peer<RoleServer>>,
+    ) -> Result<Option<Arc<dyn DigestGenerator>>> {
+        match settings.provider.to_lowercase().as_str() {
+            "none" | "" => Ok(None),
+            "sampling" => {
+                let peer = peer.ok_or_else(|| anyhow!("Sampling needs a connected MCP client"))?;
+                let hint = Some(settings.model.clone()).filter(|model| !model.is_empty());
+                Ok(Some(Arc::new(SamplingDigestGenerator::new(peer, hint)?)))
+            }
+            "llm" => Ok(Some(Arc::new(HttpDigestGenerator::new(settings)?))),
+            other => bail!("Unknown digests.provider \"{other}\"; expected \"sampling\", \"llm\" or \"none\""),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_http_generator() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buffer = vec![0u8; 64 * 1024];
+            let read = socket.read(&mut buffer).await.unwrap();
+            let body = r#"{"model": "mini-2", "choices": [{"message": {"content": "  Payments are captured on shipping.\n"}}]}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&buffer[..read]).to_string()
+        });
+
+        let settings = DigestSettings {
+            provider: "llm".to_string(),
+            endpoint: Some(format!("http://{address}/v1/")),
+            model: "mini".to_string(),
+            api_key: Some("secret".to_string()),
+            ..Default::default()
+        };
+        let generator = DigestGeneratorFactory::from_settings(&settings, None).unwrap().unwrap();
+        let generated = generator.generate("Summarize", "Rules: ...", 300).await.unwrap();
+        assert_eq!(generated, GeneratedText {
+            text: "Payments are captured on shipping.".to_string(),
+            model: "mini-2".to_string(),
+        });
+        assert_eq!(generator.kind(), "llm");
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /v1/chat/completions"));
+        assert!(request.to_lowercase().contains("authorization: bearer secret"));
+        assert!(request.contains("\"max_tokens\":300"));
+
+        let none = DigestSettings { provider: "none".to_string(), ..Default::default() };
+        assert!(DigestGeneratorFactory::from_settings(&none, None).unwrap().is_none());
+        assert!(DigestGeneratorFactory::from_settings(&DigestSettings::default(), None).is_err());
+    }
+}
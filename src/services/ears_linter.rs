@@ -0,0 +1,388 @@
+use crate::models::specification::Requirement;
+use serde::Serialize;
+
+/// EARS (Easy Approach to Requirements Syntax) template an acceptance criterion follows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EarsPattern {
+    /// `The <system> SHALL <response>`
+    Ubiquitous,
+    /// `WHEN <trigger> THEN the <system> SHALL <response>`
+    EventDriven,
+    /// `WHILE <state> the <system> SHALL <response>`
+    StateDriven,
+    /// `IF <condition> THEN the <system> SHALL <response>`
+    UnwantedBehavior,
+    /// `WHERE <feature is included> the <system> SHALL <response>`
+    Optional,
+    /// More than one of the preconditions above
+    Complex,
+}
+
+/// Findings for one acceptance criterion
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CriterionLint {
+    pub requirement: String,
+    /// 1-based position of the criterion in its requirement
+    pub criterion: usize,
+    pub text: String,
+    pub pattern: Option<EarsPattern>,
+    pub issues: Vec<String>,
+    pub suggestion: Option<String>,
+    /// 0.0 to 1.0
+    pub score: f64,
+}
+
+impl CriterionLint {
+    /// One-line validation issue, e.g. `Requirement 2, criterion 1: vague term "fast"; … Suggested rewrite: …`
+    pub fn describe(&self) -> String {
+        let mut description = format!("{}, criterion {}: {}", self.requirement, self.criterion, self.issues.join("; "));
+        if let Some(suggestion) = &self.suggestion {
+            description.push_str(&format!(". Suggested rewrite: {}", suggestion));
+        }
+        description
+    }
+}
+
+/// EARS quality of a specification's acceptance criteria
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpecQualityReport {
+    /// Mean criterion score as a percentage; `None` when there are no criteria
+    pub score: Option<f64>,
+    pub criteria_count: usize,
+    pub passing: usize,
+    /// Criteria with at least one issue
+    pub flagged: Vec<CriterionLint>,
+}
+
+/// Vague terms and the placeholder a rewrite puts in their place
+const VAGUE_TERMS: &[(&str, &str)] = &[
+    ("fast", "within <N> ms"),
+    ("quickly", "within <N> ms"),
+    ("promptly", "within <N> ms"),
+    ("slow", "after <N> ms"),
+    ("user-friendly", "<observable behaviour>"),
+    ("easy", "<observable behaviour>"),
+    ("easily", "<observable behaviour>"),
+    ("intuitive", "<observable behaviour>"),
+    ("seamless", "<observable behaviour>"),
+    ("seamlessly", "<observable behaviour>"),
+    ("appropriate", "<specific value>"),
+    ("appropriately", "<specific action>"),
+    ("adequate", "<specific value>"),
+    ("reasonable", "<specific value>"),
+    ("sufficient", "<specific value>"),
+    ("efficient", "<measurable limit>"),
+    ("efficiently", "<measurable limit>"),
+    ("optimal", "<measurable limit>"),
+    ("robust", "<failure behaviour>"),
+    ("flexible", "<supported variations>"),
+    ("properly", "<expected result>"),
+    ("correctly", "<expected result>"),
+    ("several", "<N>"),
+    ("many", "<N>"),
+    ("some", "<N>"),
+    ("approximately", "<tolerance>"),
+    ("etc", "<remaining items>"),
+    ("and/or", "<AND | OR>"),
+    ("as needed", "<condition>"),
+    ("if possible", "<condition>"),
+    ("tbd", "<detail>"),
+    ("todo", "<detail>"),
+];
+
+/// Modal verbs that read as SHALL but leave the obligation open
+const WEAK_MODALS: &[&str] = &["should", "must", "will", "may", "might", "can", "could"];
+
+/// Checks acceptance criteria against the EARS templates, flags vague wording and proposes
+/// rewrites in the `WHEN … THEN the system SHALL …` form Kiro specifications use.
+pub struct EarsLinter;
+
+impl EarsLinter {
+    /// Lints every acceptance criterion of the requirements
+    pub fn lint_requirements(requirements: &[Requirement]) -> SpecQualityReport {
+        let lints: Vec<CriterionLint> = requirements
+            .iter()
+            .flat_map(|requirement| {
+                requirement
+                    .acceptance_criteria
+                    .iter()
+                    .enumerate()
+                    .map(|(index, criterion)| Self::lint_criterion(&requirement.title, index + 1, &criterion.description))
+            })
+            .collect();
+
+        let score = (!lints.is_empty()).then(|| {
+            let mean = lints.iter().map(|lint| lint.score).sum::<f64>() / lints.len() as f64;
+            (mean * 1000.0).round() / 10.0
+        });
+        let criteria_count = lints.len();
+        let flagged: Vec<CriterionLint> = lints.into_iter().filter(|lint| !lint.issues.is_empty()).collect();
+        SpecQualityReport {
+            score,
+            criteria_count,
+            passing: criteria_count - flagged.len(),
+            flagged,
+        }
+    }
+
+    pub fn lint_criterion(requirement: &str, criterion: usize, text: &str) -> CriterionLint {
+        let text = text.trim();
+        let words = Self::words(text);
+        let mut issues = Vec::new();
+        let mut score: f64 = 1.0;
+
+        let shall_count = words.iter().filter(|w| w.as_str() == "shall").count();
+        let pattern = if shall_count == 0 { None } else { Self::pattern(&words) };
+        if pattern.is_none() {
+            issues.push("does not follow an EARS pattern such as \"WHEN <trigger> THEN the system SHALL <response>\"".to_string());
+            score -= 0.5;
+        } else if pattern == Some(EarsPattern::UnwantedBehavior) && !words.iter().any(|w| w == "then") {
+            issues.push("IF condition has no THEN".to_string());
+            score -= 0.25;
+        }
+        if shall_count > 1 {
+            issues.push("has more than one SHALL; split it into separate criteria".to_string());
+            score -= 0.25;
+        }
+        if let Some(modal) = words.iter().find(|w| WEAK_MODALS.contains(&w.as_str())) {
+            issues.push(format!("uses \"{}\" where SHALL states the obligation", modal));
+            score -= 0.25;
+        }
+        if shall_count > 0 && Self::response(text).is_empty() {
+            issues.push("has no response after SHALL".to_string());
+            score -= 0.5;
+        }
+        let vague = Self::vague_terms(text);
+        for (term, _) in &vague {
+            issues.push(format!("vague term \"{}\"; replace it with something measurable", term));
+            score -= 0.25;
+        }
+
+        let suggestion = (!issues.is_empty()).then(|| Self::suggest(text, &words, pattern, &vague));
+        CriterionLint {
+            requirement: requirement.to_string(),
+            criterion,
+            text: text.to_string(),
+            pattern,
+            issues,
+            suggestion,
+            score: score.max(0.0),
+        }
+    }
+
+    /// Pattern from the preconditions before SHALL
+    fn pattern(words: &[String]) -> Option<EarsPattern> {
+        let before_shall: Vec<&str> = words
+            .iter()
+            .map(String::as_str)
+            .take_while(|w| *w != "shall")
+            .collect();
+        let preconditions: Vec<EarsPattern> = [
+            ("when", EarsPattern::EventDriven),
+            ("while", EarsPattern::StateDriven),
+            ("if", EarsPattern::UnwantedBehavior),
+            ("where", EarsPattern::Optional),
+        ]
+        .into_iter()
+        .filter(|(keyword, _)| before_shall.contains(keyword))
+        .map(|(_, pattern)| pattern)
+        .collect();
+
+        match preconditions.as_slice() {
+            [] if before_shall.first() == Some(&"the") => Some(EarsPattern::Ubiquitous),
+            [] => None,
+            [single] if Self::starts_with_precondition(&before_shall) => Some(*single),
+            [_] => None,
+            _ => Some(EarsPattern::Complex),
+        }
+    }
+
+    fn starts_with_precondition(words: &[&str]) -> bool {
+        matches!(words.first(), Some(&"when" | &"while" | &"if" | &"where"))
+    }
+
+    /// Lowercased words, with punctuation other than `/` and `-` stripped
+    fn words(text: &str) -> Vec<String> {
+        text.split_whitespace()
+            .map(|word| {
+                word.trim_matches(|c: char| !c.is_alphanumeric() && c != '/' && c != '-')
+                    .to_lowercase()
+            })
+            .filter(|word| !word.is_empty())
+            .collect()
+    }
+
+    fn vague_terms(text: &str) -> Vec<(&'static str, &'static str)> {
+        let lower = format!(" {} ", Self::words(text).join(" "));
+        VAGUE_TERMS
+            .iter()
+            .filter(|(term, _)| lower.contains(&format!(" {} ", term)))
+            .copied()
+            .collect()
+    }
+
+    /// Text after the first SHALL
+    fn response(text: &str) -> String {
+        Self::split_at_keyword(text, "shall")
+            .map(|(_, after)| after.trim().to_string())
+            .unwrap_or_default()
+    }
+
+    fn suggest(text: &str, words: &[String], pattern: Option<EarsPattern>, vague: &[(&str, &str)]) -> String {
+        let mut rewrite = match pattern {
+            Some(_) => text.to_string(),
+            None => Self::rewrite_as_event(text, words),
+        };
+        for modal in WEAK_MODALS {
+            rewrite = Self::replace_word(&rewrite, modal, "SHALL");
+        }
+        for (term, placeholder) in vague {
+            rewrite = Self::replace_word(&rewrite, term, placeholder);
+        }
+        rewrite
+    }
+
+    /// `WHEN … THEN the system SHALL …` built from Gherkin steps, a modal sentence or free text
+    fn rewrite_as_event(text: &str, words: &[String]) -> String {
+        let text = text.trim().trim_end_matches('.');
+        let gherkin = words.first().is_some_and(|w| w == "given") || (words.contains(&"when".to_string()) && words.contains(&"then".to_string()));
+        if gherkin {
+            if let Some((before, outcome)) = Self::split_at_keyword(text, "then") {
+                let trigger = before
+                    .split_whitespace()
+                    .map(|word| match word.to_lowercase().as_str() {
+                        "given" | "when" => "AND",
+                        _ => word,
+                    })
+                    .skip_while(|word| *word == "AND")
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                return format!("WHEN {} THEN {}", trigger, Self::with_shall(outcome.trim()));
+            }
+        }
+        if let Some((trigger, outcome)) = Self::split_at_keyword(text, "when").filter(|(before, _)| !before.trim().is_empty()) {
+            // "The system sends a receipt when the order is paid"
+            return format!("WHEN {} THEN {}", outcome.trim(), Self::with_shall(trigger.trim()));
+        }
+        format!("WHEN <trigger> THEN {}", Self::with_shall(text))
+    }
+
+    /// `the system SHALL …`, reusing the subject and verb of `The API should …` style sentences
+    /// and wrapping statements of outcome in `ensure that`
+    fn with_shall(text: &str) -> String {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        if let Some(position) = words.iter().position(|w| WEAK_MODALS.contains(&w.to_lowercase().as_str())) {
+            if position > 0 {
+                let subject = Self::lowercase_first(&words[..position].join(" "));
+                return format!("{} SHALL {}", subject, words[position + 1..].join(" "));
+            }
+        }
+        format!("the system SHALL ensure that {}", Self::lowercase_first(text))
+    }
+
+    /// Splits around the first whole-word, case-insensitive occurrence of the keyword
+    fn split_at_keyword<'a>(text: &'a str, keyword: &str) -> Option<(&'a str, &'a str)> {
+        let mut offset = 0;
+        for word in text.split_inclusive(char::is_whitespace) {
+            if word.trim().trim_matches(|c: char| !c.is_alphanumeric()).eq_ignore_ascii_case(keyword) {
+                return Some((&text[..offset], &text[offset + word.len()..]));
+            }
+            offset += word.len();
+        }
+        None
+    }
+
+    fn replace_word(text: &str, word: &str, replacement: &str) -> String {
+        if word.contains(' ') {
+            // ASCII lowercasing keeps byte offsets valid in the original text
+            return match text.to_ascii_lowercase().find(word) {
+                Some(start) => format!("{}{}{}", &text[..start], replacement, &text[start + word.len()..]),
+                None => text.to_string(),
+            };
+        }
+        let mut out = String::new();
+        let mut rest = text;
+        while let Some((before, after)) = Self::split_at_keyword(rest, word) {
+            out.push_str(before);
+            out.push_str(replacement);
+            // Keep what followed the word, such as a comma or the space
+            let matched = &rest[before.len()..rest.len() - after.len()];
+            out.push_str(&matched[matched.trim_end_matches(|c: char| !c.is_alphanumeric()).len()..]);
+            rest = after;
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// Lowercases the first letter unless the first word is an acronym such as `API`
+    fn lowercase_first(text: &str) -> String {
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(first), second) if !second.is_some_and(char::is_uppercase) => {
+                first.to_lowercase().chain(second).chain(chars).collect()
+            }
+            _ => text.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::specification::{AcceptanceCriterion, CriterionType};
+
+    #[test]
+    fn test_patterns_and_vague_criteria() {
+        let lint = |text: &str| EarsLinter::lint_criterion("Requirement 1", 1, text);
+
+        let event = lint("WHEN a user submits the form THEN the system SHALL save the order");
+        assert_eq!(event.pattern, Some(EarsPattern::EventDriven));
+        assert!(event.issues.is_empty());
+        assert_eq!(event.score, 1.0);
+        assert_eq!(lint("The API SHALL reject unsigned requests").pattern, Some(EarsPattern::Ubiquitous));
+        assert_eq!(lint("WHILE offline WHEN a change is made THEN the app SHALL queue it").pattern, Some(EarsPattern::Complex));
+
+        let vague = lint("WHEN a search runs THEN the system SHALL respond fast, etc.");
+        assert_eq!(vague.pattern, Some(EarsPattern::EventDriven));
+        assert_eq!(vague.issues.len(), 2);
+        assert_eq!(vague.suggestion.as_deref(), Some("WHEN a search runs THEN the system SHALL respond within <N> ms, <remaining items>."));
+
+        let modal = lint("The API should return errors as JSON when a request fails");
+        assert_eq!(modal.pattern, None);
+        assert_eq!(
+            modal.suggestion.as_deref(),
+            Some("WHEN a request fails THEN the API SHALL return errors as JSON")
+        );
+
+        let gherkin = lint("GIVEN a cart WHEN the user checks out THEN an invoice is issued");
+        assert_eq!(
+            gherkin.suggestion.as_deref(),
+            Some("WHEN a cart AND the user checks out THEN the system SHALL ensure that an invoice is issued")
+        );
+        assert_eq!(
+            lint("Errors are handled properly, as needed").suggestion.as_deref(),
+            Some("WHEN <trigger> THEN the system SHALL ensure that errors are handled <expected result>, <condition>")
+        );
+    }
+
+    #[test]
+    fn test_quality_score() {
+        let mut requirement = Requirement::new("s1".to_string(), "Requirement 1".to_string(), String::new());
+        for text in [
+            "WHEN an order is paid THEN the system SHALL send a receipt",
+            "IF payment fails THEN the system SHALL keep the cart",
+            "The receipt should look nice and be user-friendly",
+        ] {
+            requirement.add_acceptance_criterion(AcceptanceCriterion::new(text.to_string(), CriterionType::Functional));
+        }
+
+        let report = EarsLinter::lint_requirements(&[requirement]);
+        assert_eq!(report.criteria_count, 3);
+        assert_eq!(report.passing, 2);
+        assert_eq!(report.flagged[0].criterion, 3);
+        assert_eq!(report.flagged[0].score, 0.0);
+        assert_eq!(report.score, Some(66.7));
+        assert_eq!(EarsLinter::lint_requirements(&[]).score, None);
+    }
+}
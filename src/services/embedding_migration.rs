@@ -0,0 +1,352 @@
+//! Switching embedding models without downtime
+//! A second queue builds the new model's vectors next to the old ones while search keeps
+//! using the old model; once the new index is complete, search switches over and the old
+//! vectors are dropped
+
+use crate::models::embedding::{
+    ContextEmbedding, EmbeddingConfig, ModelInfo, RetryPolicy, VectorSearchQuery, VectorSearchResult,
+};
+use crate::repositories::EmbeddingRepository;
+use crate::services::embedding_queue::{EmbeddingJob, EmbeddingQueue, FailedJob};
+use crate::services::embedding_service::{EmbeddingError, EmbeddingService};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// How often a running migration checks whether the new index is complete
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The embedding service search and indexing use, replaceable at runtime.
+/// Everything that embeds text holds this rather than a concrete service, so a finished
+/// migration switches them all at once.
+pub struct SwappableEmbeddingService {
+    current: RwLock<Arc<dyn EmbeddingService>>,
+}
+
+impl SwappableEmbeddingService {
+    pub fn new(service: Arc<dyn EmbeddingService>) -> Self {
+        Self {
+            current: RwLock::new(service),
+        }
+    }
+
+    pub fn current(&self) -> Arc<dyn EmbeddingService> {
+        self.current.read().clone()
+    }
+
+    pub fn replace(&self, service: Arc<dyn EmbeddingService>) {
+        *self.current.write() = service;
+    }
+}
+
+#[async_trait]
+impl EmbeddingService for SwappableEmbeddingService {
+    async fn generate_embedding(&self, text: &str, content_type: &str) -> Result<ContextEmbedding, EmbeddingError> {
+        self.current().generate_embedding(text, content_type).await
+    }
+
+    async fn generate_embeddings_batch(&self, texts: Vec<(&str, &str, &str)>) -> Result<Vec<ContextEmbedding>, EmbeddingError> {
+        self.current().generate_embeddings_batch(texts).await
+    }
+
+    fn calculate_similarity(&self, embedding1: &ContextEmbedding, embedding2: &ContextEmbedding) -> f32 {
+        self.current().calculate_similarity(embedding1, embedding2)
+    }
+
+    async fn find_similar(&self, query: &VectorSearchQuery, embeddings: &[ContextEmbedding]) -> Result<Vec<VectorSearchResult>, EmbeddingError> {
+        self.current().find_similar(query, embeddings).await
+    }
+
+    fn get_model_info(&self) -> ModelInfo {
+        self.current().get_model_info()
+    }
+
+    async fn update_config(&mut self, _config: EmbeddingConfig) -> Result<(), EmbeddingError> {
+        Err(EmbeddingError::ConfigurationError {
+            message: "The active embedding model is changed with migrate_embeddings".to_string(),
+        })
+    }
+
+    fn max_batch_size(&self) -> usize {
+        self.current().max_batch_size()
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        self.current().retry_policy()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationState {
+    #[default]
+    Idle,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Progress of the current or last migration, as reported by `migrate_embeddings`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MigrationStatus {
+    pub state: MigrationState,
+    pub from_model: Option<String>,
+    pub from_dimension: Option<usize>,
+    pub to_model: Option<String>,
+    pub to_dimension: Option<usize>,
+    /// Entities queued for the new model when the migration started
+    pub total: usize,
+    /// Vectors stored for the new model, including writes made during the migration
+    pub indexed: u64,
+    pub pending: usize,
+    pub failed: usize,
+    /// Vectors of the previous model dropped after switching
+    pub removed: u64,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+    pub failures: Vec<FailedJob>,
+}
+
+pub struct EmbeddingMigrator {
+    active: Arc<SwappableEmbeddingService>,
+    queue: Arc<EmbeddingQueue>,
+    repository: Arc<dyn EmbeddingRepository>,
+    status: Mutex<MigrationStatus>,
+    /// Builds the new model's vectors while a migration runs
+    target_queue: Mutex<Option<Arc<EmbeddingQueue>>>,
+}
+
+impl EmbeddingMigrator {
+    pub fn new(
+        active: Arc<SwappableEmbeddingService>,
+        queue: Arc<EmbeddingQueue>,
+        repository: Arc<dyn EmbeddingRepository>,
+    ) -> Self {
+        Self {
+            active,
+            queue,
+            repository,
+            status: Mutex::new(MigrationStatus::default()),
+            target_queue: Mutex::new(None),
+        }
+    }
+
+    pub fn status(&self) -> MigrationStatus {
+        let mut status = self.status.lock().clone();
+        if let Some(target_queue) = self.target_queue.lock().as_ref() {
+            Self::record_progress(&mut status, target_queue);
+        }
+        status
+    }
+
+    fn record_progress(status: &mut MigrationStatus, target_queue: &EmbeddingQueue) {
+        let progress = target_queue.status();
+        status.indexed = progress.indexed_total;
+        status.pending = progress.pending + progress.in_flight;
+        status.failed = progress.failed;
+        status.failures = progress.failures;
+    }
+
+    /// Embed `jobs` with `target` next to the current vectors in the background, then
+    /// switch search and indexing to `target`. Entity writes made meanwhile go to both models.
+    pub async fn start(
+        self: &Arc<Self>,
+        target: Arc<dyn EmbeddingService>,
+        jobs: Vec<EmbeddingJob>,
+    ) -> Result<MigrationStatus, EmbeddingError> {
+        let from = self.active.get_model_info();
+        let to = target.get_model_info();
+        if from.model_name == to.model_name && from.embedding_dimension == to.embedding_dimension {
+            return Err(EmbeddingError::ConfigurationError {
+                message: format!(
+                    "Already using {} ({} dimensions); use reindex_project to re-embed with the same model",
+                    to.model_name, to.embedding_dimension
+                ),
+            });
+        }
+
+        let target_queue = {
+            let mut status = self.status.lock();
+            if status.state == MigrationState::Running {
+                return Err(EmbeddingError::ConfigurationError {
+                    message: format!(
+                        "A migration to {} is already running",
+                        status.to_model.as_deref().unwrap_or("another model")
+                    ),
+                });
+            }
+
+            let target_queue = Arc::new(
+                EmbeddingQueue::new(target.clone(), self.repository.clone()).with_chunker(self.queue.chunker()),
+            );
+            let jobs: Vec<EmbeddingJob> = jobs
+                .into_iter()
+                .filter(|job| matches!(job, EmbeddingJob::Index { .. }))
+                .collect();
+            *status = MigrationStatus {
+                state: MigrationState::Running,
+                from_model: Some(from.model_name.clone()),
+                from_dimension: Some(from.embedding_dimension),
+                to_model: Some(to.model_name.clone()),
+                to_dimension: Some(to.embedding_dimension),
+                total: jobs.len(),
+                started_at: Some(Utc::now()),
+                ..MigrationStatus::default()
+            };
+            for job in jobs {
+                target_queue.enqueue(job);
+            }
+            *self.target_queue.lock() = Some(target_queue.clone());
+            target_queue
+        };
+
+        info!(
+            "Migrating embeddings from {} ({}d) to {} ({}d)",
+            from.model_name, from.embedding_dimension, to.model_name, to.embedding_dimension
+        );
+        self.queue.set_mirror(Some(target_queue.clone()));
+        target_queue.spawn_worker();
+
+        let migrator = self.clone();
+        tokio::spawn(async move {
+            while !target_queue.is_idle() {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+            migrator.finish(target, target_queue, &to).await;
+        });
+
+        Ok(self.status())
+    }
+
+    /// Switch to the new model if every entity was embedded; otherwise keep the old
+    /// model and drop the partial new index, since switching would lose results
+    async fn finish(&self, target: Arc<dyn EmbeddingService>, target_queue: Arc<EmbeddingQueue>, to: &ModelInfo) {
+        let failed = target_queue.status().failed;
+        let (state, error, removed) = if failed == 0 {
+            self.active.replace(target);
+            self.queue.set_mirror(None);
+            // Writes mirrored just before the switch
+            target_queue.drain().await;
+            match self
+                .repository
+                .delete_embeddings_except(&to.model_name, to.embedding_dimension)
+                .await
+            {
+                Ok(removed) => (MigrationState::Completed, None, removed),
+                Err(e) => (
+                    MigrationState::Completed,
+                    Some(format!("Switched models but could not drop the previous vectors: {e}")),
+                    0,
+                ),
+            }
+        } else {
+            self.queue.set_mirror(None);
+            let current = self.active.get_model_info();
+            if let Err(e) = self
+                .repository
+                .delete_embeddings_except(&current.model_name, current.embedding_dimension)
+                .await
+            {
+                warn!("Could not drop the partial {} index: {}", to.model_name, e);
+            }
+            (
+                MigrationState::Failed,
+                Some(format!(
+                    "{failed} entities could not be embedded with {}; search keeps using {}",
+                    to.model_name, current.model_name
+                )),
+                0,
+            )
+        };
+
+        let mut status = self.status.lock();
+        Self::record_progress(&mut status, &target_queue);
+        status.state = state;
+        status.error = error;
+        status.removed = removed;
+        status.finished_at = Some(Utc::now());
+        *self.target_queue.lock() = None;
+        match state {
+            MigrationState::Completed => info!("Embedding migration to {} completed", to.model_name),
+            _ => warn!("Embedding migration to {} failed: {:?}", to.model_name, status.error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::embedding_repository::SqliteEmbeddingRepository;
+    use crate::services::embedding_service::SimpleEmbeddingService;
+    use rusqlite::Connection;
+    use std::collections::BTreeMap;
+
+    fn current() -> Arc<dyn EmbeddingService> {
+        Arc::new(SimpleEmbeddingService::new(EmbeddingConfig::default()))
+    }
+
+    fn simple(dimension: usize) -> Arc<dyn EmbeddingService> {
+        Arc::new(SimpleEmbeddingService::new(EmbeddingConfig {
+            model_name: "simple-test".to_string(),
+            embedding_dimension: dimension,
+            ..EmbeddingConfig::default()
+        }))
+    }
+
+    fn job(entity_id: &str, text: &str) -> EmbeddingJob {
+        EmbeddingJob::Index {
+            entity_type: "business_rule".to_string(),
+            entity_id: entity_id.to_string(),
+            project_id: Some("p1".to_string()),
+            text: text.to_string(),
+            facets: BTreeMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migration_switches_model_and_drops_old_vectors() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE projects (id TEXT PRIMARY KEY); INSERT INTO projects VALUES ('p1');")
+            .unwrap();
+        SqliteEmbeddingRepository::create_tables(&conn).unwrap();
+        let repository: Arc<dyn EmbeddingRepository> =
+            Arc::new(SqliteEmbeddingRepository::new(Arc::new(tokio::sync::Mutex::new(conn))));
+
+        let active = Arc::new(SwappableEmbeddingService::new(current()));
+        let queue = Arc::new(EmbeddingQueue::new(active.clone(), repository.clone()));
+        queue.enqueue(job("r1", "Refunds need approval"));
+        queue.drain().await;
+
+        let migrator = Arc::new(EmbeddingMigrator::new(active.clone(), queue.clone(), repository.clone()));
+        assert!(migrator.start(current(), Vec::new()).await.is_err());
+
+        let status = migrator
+            .start(simple(128), vec![job("r1", "Refunds need approval")])
+            .await
+            .unwrap();
+        assert_eq!((status.state, status.total), (MigrationState::Running, 1));
+        // Writes during the migration reach both indexes
+        queue.enqueue(job("r2", "Invoices are immutable"));
+        queue.drain().await;
+
+        for _ in 0..100 {
+            if migrator.status().state != MigrationState::Running {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        let status = migrator.status();
+        assert_eq!(status.state, MigrationState::Completed, "{:?}", status.error);
+        assert_eq!((status.indexed, status.removed), (2, 2));
+        assert_eq!(active.get_model_info().model_name, "simple-test");
+
+        let counts = repository.count_embeddings_by_model().await.unwrap();
+        assert_eq!(counts.len(), 1);
+        assert_eq!((counts[0].dimension, counts[0].count), (128, 2));
+    }
+}
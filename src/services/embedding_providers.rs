@@ -0,0 +1,315 @@
+//! HTTP embedding backends: OpenAI-compatible `/embeddings` APIs and Ollama
+//! Both batch texts per request and retry transient failures with `retry_with_backoff`
+
+use crate::models::embedding::{
+    ContextEmbedding, EmbeddingConfig, EmbeddingMetadata, EmbeddingProvider, ModelInfo, ModelType,
+    RetryPolicy, VectorSearchQuery, VectorSearchResult,
+};
+use crate::services::embedding_service::{
+    rank_by_similarity, retry_with_backoff, EmbeddingError, EmbeddingService,
+};
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{debug, info};
+
+const OPENAI_DEFAULT_ENDPOINT: &str = "https://api.openai.com/v1";
+const OLLAMA_DEFAULT_ENDPOINT: &str = "http://localhost:11434";
+
+#[derive(Deserialize)]
+struct OpenAiResponse {
+    data: Vec<OpenAiEmbedding>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiEmbedding {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+#[derive(Deserialize)]
+struct OllamaResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+/// Embedding service backed by a remote (or local) HTTP API
+pub struct HttpEmbeddingService {
+    config: EmbeddingConfig,
+    client: reqwest::Client,
+    endpoint: String,
+}
+
+impl HttpEmbeddingService {
+    /// `config.provider` must be `OpenAi` or `Ollama`
+    pub fn new(mut config: EmbeddingConfig) -> Result<Self, EmbeddingError> {
+        let default_endpoint = match config.provider {
+            EmbeddingProvider::OpenAi => {
+                if config.api_key.is_none() {
+                    config.api_key = std::env::var("OPENAI_API_KEY").ok();
+                }
+                OPENAI_DEFAULT_ENDPOINT
+            }
+            EmbeddingProvider::Ollama => OLLAMA_DEFAULT_ENDPOINT,
+            other => {
+                return Err(EmbeddingError::ConfigurationError {
+                    message: format!("{other:?} is not an HTTP embedding provider"),
+                })
+            }
+        };
+        let endpoint = config
+            .endpoint
+            .as_deref()
+            .unwrap_or(default_endpoint)
+            .trim_end_matches('/')
+            .to_string();
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .build()
+            .map_err(|e| EmbeddingError::ConfigurationError {
+                message: format!("Failed to create HTTP client: {e}"),
+            })?;
+
+        Ok(Self {
+            config,
+            client,
+            endpoint,
+        })
+    }
+
+    /// Embed `texts` with as few requests as `batch_size` allows
+    async fn embed_texts(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for chunk in texts.chunks(self.max_batch_size()) {
+            let batch = retry_with_backoff(&self.config.retry, || self.request(chunk)).await?;
+            vectors.extend(batch);
+        }
+        Ok(vectors)
+    }
+
+    /// One request for one batch
+    async fn request(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        let (url, body) = match self.config.provider {
+            EmbeddingProvider::Ollama => (
+                format!("{}/api/embed", self.endpoint),
+                serde_json::json!({"model": self.config.model_name, "input": texts}),
+            ),
+            _ => (
+                format!("{}/embeddings", self.endpoint),
+                serde_json::json!({"model": self.config.model_name, "input": texts}),
+            ),
+        };
+
+        let mut request = self.client.post(&url).json(&body);
+        if let Some(api_key) = &self.config.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await.map_err(|e| EmbeddingError::BackendError {
+            message: format!("{url}: {e}"),
+            retryable: e.is_timeout() || e.is_connect() || e.is_request(),
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let detail = response.text().await.unwrap_or_default();
+            return Err(EmbeddingError::BackendError {
+                message: format!("{url} returned {status}: {detail}"),
+                retryable: status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error(),
+            });
+        }
+
+        let invalid = |e: reqwest::Error| EmbeddingError::BackendError {
+            message: format!("Unexpected response from {url}: {e}"),
+            retryable: false,
+        };
+        let vectors = match self.config.provider {
+            EmbeddingProvider::Ollama => {
+                response.json::<OllamaResponse>().await.map_err(invalid)?.embeddings
+            }
+            _ => {
+                let mut data = response.json::<OpenAiResponse>().await.map_err(invalid)?.data;
+                data.sort_by_key(|item| item.index);
+                data.into_iter().map(|item| item.embedding).collect()
+            }
+        };
+
+        if vectors.len() != texts.len() {
+            return Err(EmbeddingError::BackendError {
+                message: format!("{url} returned {} vectors for {} inputs", vectors.len(), texts.len()),
+                retryable: false,
+            });
+        }
+        if let Some(vector) = vectors.iter().find(|v| v.len() != self.config.embedding_dimension) {
+            return Err(EmbeddingError::ConfigurationError {
+                message: format!(
+                    "Model {} returned {} dimensions but embedding.dimension is {}",
+                    self.config.model_name,
+                    vector.len(),
+                    self.config.embedding_dimension
+                ),
+            });
+        }
+        Ok(vectors)
+    }
+
+    fn prepare(&self, text: &str) -> String {
+        // Rough token budget, same approximation as the simple provider
+        text.trim()
+            .chars()
+            .take(self.config.max_sequence_length * 4)
+            .collect()
+    }
+
+    fn to_embedding(&self, context_id: &str, text: &str, content_type: &str, vector: Vec<f32>) -> ContextEmbedding {
+        let mut embedding = ContextEmbedding::new(
+            context_id.to_string(),
+            vector,
+            self.config.model_name.clone(),
+            format!("{:?}", self.config.provider).to_lowercase(),
+            format!("{:x}", md5::compute(text.as_bytes())),
+        );
+        embedding.metadata = EmbeddingMetadata {
+            content_type: content_type.to_string(),
+            content_length: text.len(),
+            tokenization_method: self.config.tokenization_method.clone(),
+            preprocessing_steps: vec!["truncation".to_string()],
+            quality_score: 1.0,
+            custom_fields: HashMap::new(),
+        };
+        embedding
+    }
+}
+
+#[async_trait]
+impl EmbeddingService for HttpEmbeddingService {
+    async fn generate_embedding(&self, text: &str, content_type: &str) -> Result<ContextEmbedding, EmbeddingError> {
+        let mut vectors = self.embed_texts(&[self.prepare(text)]).await?;
+        let vector = vectors.pop().unwrap_or_default();
+        Ok(self.to_embedding("", text, content_type, vector))
+    }
+
+    async fn generate_embeddings_batch(&self, texts: Vec<(&str, &str, &str)>) -> Result<Vec<ContextEmbedding>, EmbeddingError> {
+        debug!("Generating batch of {} embeddings via {}", texts.len(), self.endpoint);
+        let inputs: Vec<String> = texts.iter().map(|(_, text, _)| self.prepare(text)).collect();
+        let vectors = self.embed_texts(&inputs).await?;
+
+        let embeddings: Vec<ContextEmbedding> = texts
+            .into_iter()
+            .zip(vectors)
+            .map(|((context_id, text, content_type), vector)| {
+                self.to_embedding(context_id, text, content_type, vector)
+            })
+            .collect();
+        info!("Generated {} embeddings in batch", embeddings.len());
+        Ok(embeddings)
+    }
+
+    fn calculate_similarity(&self, embedding1: &ContextEmbedding, embedding2: &ContextEmbedding) -> f32 {
+        embedding1.cosine_similarity(embedding2)
+    }
+
+    async fn find_similar(&self, query: &VectorSearchQuery, embeddings: &[ContextEmbedding]) -> Result<Vec<VectorSearchResult>, EmbeddingError> {
+        let query_embedding = match &query.query_embedding {
+            Some(embedding) => embedding.clone(),
+            None => self.generate_embedding(&query.query_text, "query").await?.embedding_vector,
+        };
+        Ok(rank_by_similarity(&query_embedding, query, embeddings))
+    }
+
+    fn get_model_info(&self) -> ModelInfo {
+        ModelInfo {
+            model_name: self.config.model_name.clone(),
+            model_version: format!("{:?}", self.config.provider).to_lowercase(),
+            embedding_dimension: self.config.embedding_dimension,
+            max_sequence_length: self.config.max_sequence_length,
+            model_type: ModelType::SentenceTransformer,
+        }
+    }
+
+    async fn update_config(&mut self, config: EmbeddingConfig) -> Result<(), EmbeddingError> {
+        *self = Self::new(config)?;
+        Ok(())
+    }
+
+    fn max_batch_size(&self) -> usize {
+        self.config.batch_size.max(1)
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        self.config.retry.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one canned HTTP response per connection, in order
+    async fn serve_responses(responses: Vec<(u16, String)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            for (status, body) in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = vec![0u8; 64 * 1024];
+                let _ = socket.read(&mut buffer).await;
+                let response = format!(
+                    "HTTP/1.1 {status} X\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        format!("http://{address}")
+    }
+
+    fn config(provider: EmbeddingProvider, endpoint: String) -> EmbeddingConfig {
+        EmbeddingConfig {
+            provider,
+            endpoint: Some(endpoint),
+            api_key: Some("test".to_string()),
+            embedding_dimension: 2,
+            batch_size: 2,
+            retry: RetryPolicy {
+                max_retries: 2,
+                initial_backoff_ms: 1,
+            },
+            ..EmbeddingConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_openai_batches_and_retries_server_errors() {
+        let endpoint = serve_responses(vec![
+            (503, "{}".to_string()),
+            (200, r#"{"data":[{"index":1,"embedding":[0.0,1.0]},{"index":0,"embedding":[1.0,0.0]}]}"#.to_string()),
+            (200, r#"{"data":[{"index":0,"embedding":[0.5,0.5]}]}"#.to_string()),
+        ])
+        .await;
+        let service = HttpEmbeddingService::new(config(EmbeddingProvider::OpenAi, endpoint)).unwrap();
+
+        let embeddings = service
+            .generate_embeddings_batch(vec![("a", "first", "text"), ("b", "second", "text"), ("c", "third", "text")])
+            .await
+            .unwrap();
+        assert_eq!(embeddings.len(), 3);
+        assert_eq!(embeddings[0].embedding_vector, vec![1.0, 0.0]);
+        assert_eq!(embeddings[1].context_id, "b");
+        assert_eq!(embeddings[1].embedding_vector, vec![0.0, 1.0]);
+        assert_eq!(embeddings[2].embedding_vector, vec![0.5, 0.5]);
+    }
+
+    #[tokio::test]
+    async fn test_ollama_rejects_wrong_dimension() {
+        let endpoint = serve_responses(vec![(200, r#"{"embeddings":[[1.0,0.0,0.0]]}"#.to_string())]).await;
+        let service = HttpEmbeddingService::new(config(EmbeddingProvider::Ollama, endpoint)).unwrap();
+
+        let error = service.generate_embedding("text", "text").await.unwrap_err();
+        assert!(matches!(error, EmbeddingError::ConfigurationError { .. }));
+    }
+}
@@ -0,0 +1,632 @@
+//! Background indexing of entity text into embeddings
+//! Writes enqueue jobs and return immediately; a worker task batches them into
+//! `generate_embeddings_batch` calls, stores the vectors and retries failures
+
+use crate::repositories::EmbeddingRepository;
+use crate::services::embedding_service::{EmbeddingError, EmbeddingService};
+use crate::services::hybrid_search_service::entity_facets;
+use crate::services::vector_embedding_integration::{chunk_id, ContentChunker};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use tracing::{debug, warn};
+
+/// How long the worker waits after a wake-up so a burst of writes shares one batch
+const BATCH_WINDOW: Duration = Duration::from_millis(100);
+/// Longest the worker sleeps without a wake-up
+const IDLE_WAIT: Duration = Duration::from_secs(60);
+/// Delay before the first retry; doubled on each further attempt
+const RETRY_BACKOFF: Duration = Duration::from_secs(5);
+/// Attempts per job before it is reported as failed
+const MAX_ATTEMPTS: u32 = 5;
+/// Characters of the indexed text kept with the vector, for result previews and reranking
+const PREVIEW_CHARS: usize = 500;
+/// Entities that configure the server rather than describe the project; embedding a
+/// saved search would make it a hit for its own query
+const UNINDEXED_ENTITY_TYPES: &[&str] = &["saved_search"];
+
+/// Work for the indexer
+#[derive(Debug, Clone, PartialEq)]
+pub enum EmbeddingJob {
+    /// Embed (or re-embed) an entity's text
+    Index {
+        entity_type: String,
+        entity_id: String,
+        project_id: Option<String>,
+        text: String,
+        /// Stored with the vector so search can filter and count by them
+        facets: BTreeMap<String, Vec<String>>,
+    },
+    /// Drop the stored embedding of a deleted entity
+    Remove { entity_id: String },
+}
+
+impl EmbeddingJob {
+    pub fn entity_id(&self) -> &str {
+        match self {
+            Self::Index { entity_id, .. } | Self::Remove { entity_id } => entity_id,
+        }
+    }
+
+    /// Index job for an entity as returned by the CRUD tools; string fields other than
+    /// ids, id lists and timestamps become the embedded text. `None` if there is nothing to embed.
+    pub fn from_entity(entity_type: &str, entity: &serde_json::Value) -> Option<Self> {
+        if UNINDEXED_ENTITY_TYPES.contains(&entity_type) {
+            return None;
+        }
+        let object = entity.as_object()?;
+        let entity_id = object.get("id")?.as_str()?.to_string();
+        let project_id = match entity_type {
+            "project" => Some(entity_id.clone()),
+            _ => object.get("project_id").and_then(|v| v.as_str()).map(str::to_string),
+        };
+
+        let mut parts = Vec::new();
+        for (key, value) in object {
+            if key == "id" || key.ends_with("_id") || key.ends_with("_ids") || key.ends_with("_at") {
+                continue;
+            }
+            match value {
+                serde_json::Value::String(text) if !text.trim().is_empty() => parts.push(text.clone()),
+                serde_json::Value::Array(items) => {
+                    parts.extend(items.iter().filter_map(|item| item.as_str()).map(str::to_string))
+                }
+                _ => {}
+            }
+        }
+        if parts.is_empty() {
+            return None;
+        }
+
+        Some(Self::Index {
+            entity_type: entity_type.to_string(),
+            entity_id,
+            project_id,
+            text: parts.join("\n"),
+            facets: entity_facets(entity),
+        })
+    }
+}
+
+/// A job that exhausted its attempts or failed permanently
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedJob {
+    pub entity_id: String,
+    pub entity_type: Option<String>,
+    pub attempts: u32,
+    pub error: String,
+    pub failed_at: DateTime<Utc>,
+}
+
+/// Snapshot of the indexer backlog, as reported by `get_indexing_status`
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexingStatus {
+    /// Jobs waiting to be embedded, including scheduled retries
+    pub pending: usize,
+    /// Pending jobs that already failed at least once
+    pub retrying: usize,
+    /// Jobs in the batch currently being embedded
+    pub in_flight: usize,
+    /// Entities whose last job failed for good
+    pub failed: usize,
+    pub indexed_total: u64,
+    pub failed_total: u64,
+    pub last_indexed_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub model: String,
+    pub failures: Vec<FailedJob>,
+}
+
+struct QueuedJob {
+    job: EmbeddingJob,
+    attempts: u32,
+    not_before: Instant,
+}
+
+#[derive(Default)]
+struct QueueState {
+    pending: VecDeque<QueuedJob>,
+    in_flight: usize,
+    failed: HashMap<String, FailedJob>,
+    indexed_total: u64,
+    failed_total: u64,
+    last_indexed_at: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+}
+
+pub struct EmbeddingQueue {
+    service: Arc<dyn EmbeddingService>,
+    repository: Arc<dyn EmbeddingRepository>,
+    state: Mutex<QueueState>,
+    wake: Arc<Notify>,
+    retry_backoff: Duration,
+    chunker: ContentChunker,
+    /// Receives a copy of every job, so an index being built for another model stays current
+    mirror: Mutex<Option<Arc<EmbeddingQueue>>>,
+}
+
+impl EmbeddingQueue {
+    pub fn new(service: Arc<dyn EmbeddingService>, repository: Arc<dyn EmbeddingRepository>) -> Self {
+        Self {
+            service,
+            repository,
+            state: Mutex::new(QueueState::default()),
+            wake: Arc::new(Notify::new()),
+            retry_backoff: RETRY_BACKOFF,
+            chunker: ContentChunker::default(),
+            mirror: Mutex::new(None),
+        }
+    }
+
+    /// Override the delay before the first retry
+    pub fn with_retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// Override how long texts are split before embedding
+    pub fn with_chunker(mut self, chunker: ContentChunker) -> Self {
+        self.chunker = chunker;
+        self
+    }
+
+    pub fn chunker(&self) -> ContentChunker {
+        self.chunker
+    }
+
+    /// Queue `job`, replacing any pending job for the same entity
+    pub fn enqueue(&self, job: EmbeddingJob) {
+        if let Some(mirror) = self.mirror.lock().as_ref() {
+            mirror.enqueue(job.clone());
+        }
+        {
+            let mut state = self.state.lock();
+            state.pending.retain(|queued| queued.job.entity_id() != job.entity_id());
+            state.failed.remove(job.entity_id());
+            state.pending.push_back(QueuedJob {
+                job,
+                attempts: 0,
+                not_before: Instant::now(),
+            });
+        }
+        self.wake.notify_one();
+    }
+
+    /// Forward every job enqueued from now on to `mirror` as well, or stop forwarding
+    pub fn set_mirror(&self, mirror: Option<Arc<EmbeddingQueue>>) {
+        *self.mirror.lock() = mirror;
+    }
+
+    /// Nothing pending, retrying or being embedded
+    pub fn is_idle(&self) -> bool {
+        let state = self.state.lock();
+        state.pending.is_empty() && state.in_flight == 0
+    }
+
+    pub fn status(&self) -> IndexingStatus {
+        let state = self.state.lock();
+        let mut failures: Vec<FailedJob> = state.failed.values().cloned().collect();
+        failures.sort_by_key(|failure| std::cmp::Reverse(failure.failed_at));
+        IndexingStatus {
+            pending: state.pending.len(),
+            retrying: state.pending.iter().filter(|queued| queued.attempts > 0).count(),
+            in_flight: state.in_flight,
+            failed: state.failed.len(),
+            indexed_total: state.indexed_total,
+            failed_total: state.failed_total,
+            last_indexed_at: state.last_indexed_at,
+            last_error: state.last_error.clone(),
+            model: self.service.get_model_info().model_name,
+            failures,
+        }
+    }
+
+    /// Start the background worker on the current tokio runtime, if any.
+    /// The worker stops once the queue is dropped.
+    pub fn spawn_worker(self: &Arc<Self>) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            debug!("No tokio runtime; embedding worker not started");
+            return;
+        };
+
+        let queue = Arc::downgrade(self);
+        let wake = self.wake.clone();
+        runtime.spawn(async move {
+            loop {
+                let wait = match queue.upgrade() {
+                    Some(queue) => {
+                        if queue.process_batch().await > 0 {
+                            continue;
+                        }
+                        queue.next_retry_in().unwrap_or(IDLE_WAIT)
+                    }
+                    None => break,
+                };
+                let _ = tokio::time::timeout(wait, wake.notified()).await;
+                tokio::time::sleep(BATCH_WINDOW).await;
+            }
+        });
+    }
+
+    /// Embed and store up to one batch of due jobs; returns how many were taken
+    pub async fn process_batch(&self) -> usize {
+        let batch = self.take_due(self.service.max_batch_size());
+        let taken = batch.len();
+        if taken == 0 {
+            return 0;
+        }
+
+        let (removals, indexing): (Vec<_>, Vec<_>) = batch
+            .into_iter()
+            .partition(|queued| matches!(queued.job, EmbeddingJob::Remove { .. }));
+
+        for queued in removals {
+            match self.repository.delete_embedding(queued.job.entity_id()).await {
+                Ok(()) => self.finish(1, 0),
+                Err(e) => self.fail(queued, e.to_string(), true),
+            }
+        }
+        if !indexing.is_empty() {
+            self.index(indexing).await;
+        }
+        taken
+    }
+
+    /// Process due jobs until none are left; retries scheduled for later are not awaited
+    pub async fn drain(&self) -> usize {
+        let mut processed = 0;
+        loop {
+            let taken = self.process_batch().await;
+            if taken == 0 {
+                return processed;
+            }
+            processed += taken;
+        }
+    }
+
+    async fn index(&self, batch: Vec<QueuedJob>) {
+        // Every chunk is embedded separately; `owners[i]` is the job, chunk index and
+        // chunk count of `texts[i]`
+        let mut texts = Vec::new();
+        let mut owners = Vec::new();
+        for (job_index, queued) in batch.iter().enumerate() {
+            if let EmbeddingJob::Index { entity_type, entity_id, text, .. } = &queued.job {
+                let chunks = self.chunker.chunk(text);
+                let count = chunks.len();
+                for (chunk_index, chunk) in chunks.into_iter().enumerate() {
+                    texts.push((chunk_id(entity_id, chunk_index), chunk, entity_type.as_str()));
+                    owners.push((job_index, chunk_index, count));
+                }
+            }
+        }
+
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for request in texts.chunks(self.service.max_batch_size().max(1)) {
+            let request: Vec<(&str, &str, &str)> = request
+                .iter()
+                .map(|(id, text, entity_type)| (id.as_str(), text.as_str(), *entity_type))
+                .collect();
+            let expected = request.len();
+            match self.service.generate_embeddings_batch(request).await {
+                Ok(generated) if generated.len() == expected => embeddings.extend(generated),
+                Ok(generated) => {
+                    let error = EmbeddingError::EmbeddingGenerationError {
+                        message: format!("{} embeddings returned for {} texts", generated.len(), expected),
+                    };
+                    return self.fail_all(batch, &error);
+                }
+                Err(e) => return self.fail_all(batch, &e),
+            }
+        }
+
+        let embeddings: Vec<_> = embeddings
+            .into_iter()
+            .zip(texts.iter().zip(&owners))
+            .map(|(mut embedding, ((context_id, chunk, _), &(job_index, chunk_index, count)))| {
+                if let EmbeddingJob::Index { entity_id, entity_type, project_id, facets, .. } = &batch[job_index].job {
+                    embedding.context_id = context_id.clone();
+                    let fields = &mut embedding.metadata.custom_fields;
+                    fields.insert("entity_type".to_string(), entity_type.clone().into());
+                    let preview: String = chunk.chars().take(PREVIEW_CHARS).collect();
+                    fields.insert("preview".to_string(), preview.into());
+                    if !facets.is_empty() {
+                        fields.insert("facets".to_string(), serde_json::json!(facets));
+                    }
+                    if let Some(project_id) = project_id {
+                        fields.insert("project_id".to_string(), project_id.clone().into());
+                    }
+                    if count > 1 {
+                        fields.insert("parent_id".to_string(), entity_id.clone().into());
+                        fields.insert("chunk_index".to_string(), chunk_index.into());
+                        fields.insert("chunk_count".to_string(), count.into());
+                    }
+                }
+                embedding
+            })
+            .collect();
+
+        // Chunks left over from a longer previous version of the entity
+        let model = self.service.get_model_info().model_name;
+        for queued in &batch {
+            if let Err(e) = self.repository.delete_chunks(queued.job.entity_id(), Some(&model)).await {
+                let error = e.to_string();
+                for queued in batch {
+                    self.fail(queued, error.clone(), true);
+                }
+                return;
+            }
+        }
+
+        match self.repository.store_embeddings_batch(&embeddings).await {
+            Ok(()) => self.finish(batch.len(), batch.len()),
+            Err(e) => {
+                let error = e.to_string();
+                for queued in batch {
+                    self.fail(queued, error.clone(), true);
+                }
+            }
+        }
+    }
+
+    fn take_due(&self, max: usize) -> Vec<QueuedJob> {
+        let now = Instant::now();
+        let mut state = self.state.lock();
+        let mut batch = Vec::new();
+        let mut remaining = VecDeque::with_capacity(state.pending.len());
+        while let Some(queued) = state.pending.pop_front() {
+            if batch.len() < max && queued.not_before <= now {
+                batch.push(queued);
+            } else {
+                remaining.push_back(queued);
+            }
+        }
+        state.pending = remaining;
+        state.in_flight = batch.len();
+        batch
+    }
+
+    fn next_retry_in(&self) -> Option<Duration> {
+        let now = Instant::now();
+        self.state
+            .lock()
+            .pending
+            .iter()
+            .map(|queued| queued.not_before.saturating_duration_since(now))
+            .min()
+    }
+
+    fn finish(&self, completed: usize, indexed: usize) {
+        let mut state = self.state.lock();
+        state.in_flight = state.in_flight.saturating_sub(completed);
+        if indexed > 0 {
+            state.indexed_total += indexed as u64;
+            state.last_indexed_at = Some(Utc::now());
+        }
+    }
+
+    fn fail_all(&self, batch: Vec<QueuedJob>, error: &EmbeddingError) {
+        let retryable = error.is_retryable();
+        for queued in batch {
+            self.fail(queued, error.to_string(), retryable);
+        }
+    }
+
+    /// Reschedule `queued` with backoff, or record it as failed once it is out of attempts
+    fn fail(&self, mut queued: QueuedJob, error: String, retryable: bool) {
+        queued.attempts += 1;
+        let mut state = self.state.lock();
+        state.in_flight = state.in_flight.saturating_sub(1);
+        state.last_error = Some(error.clone());
+
+        // A newer write for the same entity supersedes this job
+        if state.pending.iter().any(|pending| pending.job.entity_id() == queued.job.entity_id()) {
+            return;
+        }
+
+        if retryable && queued.attempts < MAX_ATTEMPTS {
+            let delay = self.retry_backoff * 2u32.pow(queued.attempts - 1);
+            debug!(
+                "Embedding {} failed (attempt {}), retrying in {:?}: {}",
+                queued.job.entity_id(),
+                queued.attempts,
+                delay,
+                error
+            );
+            queued.not_before = Instant::now() + delay;
+            state.pending.push_back(queued);
+            return;
+        }
+
+        warn!("Giving up on embedding {}: {}", queued.job.entity_id(), error);
+        let entity_type = match &queued.job {
+            EmbeddingJob::Index { entity_type, .. } => Some(entity_type.clone()),
+            EmbeddingJob::Remove { .. } => None,
+        };
+        state.failed_total += 1;
+        state.failed.insert(
+            queued.job.entity_id().to_string(),
+            FailedJob {
+                entity_id: queued.job.entity_id().to_string(),
+                entity_type,
+                attempts: queued.attempts,
+                error,
+                failed_at: Utc::now(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::embedding::{ContextEmbedding, EmbeddingConfig, ModelInfo, VectorSearchQuery, VectorSearchResult};
+    use crate::repositories::embedding_repository::SqliteEmbeddingRepository;
+    use crate::services::embedding_service::SimpleEmbeddingService;
+    use async_trait::async_trait;
+    use rusqlite::Connection;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn repository() -> Arc<SqliteEmbeddingRepository> {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE projects (id TEXT PRIMARY KEY); INSERT INTO projects VALUES ('p1');")
+            .unwrap();
+        SqliteEmbeddingRepository::create_tables(&conn).unwrap();
+        Arc::new(SqliteEmbeddingRepository::new(Arc::new(tokio::sync::Mutex::new(conn))))
+    }
+
+    fn rule(id: &str, text: &str) -> EmbeddingJob {
+        EmbeddingJob::from_entity(
+            "business_rule",
+            &serde_json::json!({"id": id, "project_id": "p1", "rule_name": text, "created_at": "2024-01-01"}),
+        )
+        .unwrap()
+    }
+
+    /// Fails its first `failures` batches with a retryable error
+    struct FlakyService {
+        inner: SimpleEmbeddingService,
+        failures: AtomicUsize,
+        batches: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EmbeddingService for FlakyService {
+        async fn generate_embedding(&self, text: &str, content_type: &str) -> Result<ContextEmbedding, EmbeddingError> {
+            self.inner.generate_embedding(text, content_type).await
+        }
+
+        async fn generate_embeddings_batch(&self, texts: Vec<(&str, &str, &str)>) -> Result<Vec<ContextEmbedding>, EmbeddingError> {
+            self.batches.fetch_add(1, Ordering::SeqCst);
+            if self.failures.load(Ordering::SeqCst) > 0 {
+                self.failures.fetch_sub(1, Ordering::SeqCst);
+                return Err(EmbeddingError::BackendError {
+                    message: "unavailable".to_string(),
+                    retryable: true,
+                });
+            }
+            self.inner.generate_embeddings_batch(texts).await
+        }
+
+        fn calculate_similarity(&self, a: &ContextEmbedding, b: &ContextEmbedding) -> f32 {
+            self.inner.calculate_similarity(a, b)
+        }
+
+        async fn find_similar(&self, query: &VectorSearchQuery, embeddings: &[ContextEmbedding]) -> Result<Vec<VectorSearchResult>, EmbeddingError> {
+            self.inner.find_similar(query, embeddings).await
+        }
+
+        fn get_model_info(&self) -> ModelInfo {
+            self.inner.get_model_info()
+        }
+
+        async fn update_config(&mut self, config: EmbeddingConfig) -> Result<(), EmbeddingError> {
+            self.inner.update_config(config).await
+        }
+
+        fn max_batch_size(&self) -> usize {
+            2
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batches_store_and_remove() {
+        let repository = repository();
+        let service = Arc::new(SimpleEmbeddingService::new(EmbeddingConfig::default()));
+        let queue = EmbeddingQueue::new(service, repository.clone());
+
+        queue.enqueue(rule("r1", "old text"));
+        queue.enqueue(rule("r2", "Orders need approval"));
+        queue.enqueue(rule("r1", "Users must verify email"));
+        assert_eq!(queue.status().pending, 2);
+
+        assert_eq!(queue.drain().await, 2);
+        let status = queue.status();
+        assert_eq!((status.pending, status.indexed_total, status.failed), (0, 2, 0));
+        assert_eq!(repository.get_embeddings_by_project("p1").await.unwrap().len(), 2);
+        let stored = repository.get_embedding_by_context_id("r1").await.unwrap().unwrap();
+        assert_eq!(stored.content_hash, format!("{:x}", md5::compute("Users must verify email")));
+
+        queue.enqueue(EmbeddingJob::Remove { entity_id: "r1".to_string() });
+        queue.drain().await;
+        assert!(!repository.embedding_exists("r1").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_long_texts_are_indexed_in_chunks() {
+        let repository = repository();
+        let service = Arc::new(SimpleEmbeddingService::new(EmbeddingConfig::default()));
+        let queue = EmbeddingQueue::new(service, repository.clone()).with_chunker(ContentChunker::new(40, 10));
+
+        let long = "Payments are captured after shipping. Refunds need manager approval. Invoices are never edited.";
+        queue.enqueue(rule("r1", long));
+        queue.drain().await;
+        let stored = repository.get_embeddings_by_project("p1").await.unwrap();
+        assert!(stored.len() > 1);
+        assert!(stored.iter().all(|e| crate::services::vector_embedding_integration::parent_id(&e.context_id) == "r1"));
+        let chunk = repository.get_embedding_by_context_id("r1#chunk-1").await.unwrap().unwrap();
+        assert_eq!(chunk.metadata.custom_fields["parent_id"], "r1");
+
+        // A shorter version drops the chunks it no longer has
+        queue.enqueue(rule("r1", "Refunds need approval"));
+        queue.drain().await;
+        assert_eq!(repository.get_embeddings_by_project("p1").await.unwrap().len(), 1);
+
+        queue.enqueue(rule("r1", long));
+        queue.drain().await;
+        queue.enqueue(EmbeddingJob::Remove { entity_id: "r1".to_string() });
+        queue.drain().await;
+        assert!(repository.get_embeddings_by_project("p1").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_batches_are_retried_then_reported() {
+        let service = Arc::new(FlakyService {
+            inner: SimpleEmbeddingService::new(EmbeddingConfig::default()),
+            failures: AtomicUsize::new(1),
+            batches: AtomicUsize::new(0),
+        });
+        let queue = EmbeddingQueue::new(service.clone(), repository()).with_retry_backoff(Duration::ZERO);
+
+        queue.enqueue(rule("r1", "Users must verify email"));
+        queue.drain().await;
+        let status = queue.status();
+        assert_eq!((status.indexed_total, status.failed), (1, 0));
+        assert_eq!(service.batches.load(Ordering::SeqCst), 2);
+        assert!(status.last_error.unwrap().contains("unavailable"));
+
+        service.failures.store(usize::MAX, Ordering::SeqCst);
+        queue.enqueue(rule("r2", "Orders need approval"));
+        queue.drain().await;
+        let status = queue.status();
+        assert_eq!((status.pending, status.failed, status.failed_total), (0, 1, 1));
+        assert_eq!(status.failures[0].attempts, MAX_ATTEMPTS);
+    }
+
+    #[test]
+    fn test_job_text_skips_ids_and_timestamps() {
+        let job = EmbeddingJob::from_entity(
+            "architectural_decision",
+            &serde_json::json!({
+                "id": "d1", "project_id": "p1", "decision_title": "Use SQLite",
+                "context": "Single node", "tags": ["storage"], "created_at": "2024-01-01T00:00:00Z"
+            }),
+        )
+        .unwrap();
+        let EmbeddingJob::Index { text, project_id, facets, .. } = job else { panic!("expected index job") };
+        assert_eq!(project_id.as_deref(), Some("p1"));
+        assert_eq!(facets.get("tags"), Some(&vec!["storage".to_string()]));
+        assert!(text.contains("Use SQLite") && text.contains("Single node") && text.contains("storage"));
+        assert!(!text.contains("2024") && !text.contains("p1"));
+
+        assert!(EmbeddingJob::from_entity("project", &serde_json::json!({"id": "p1"})).is_none());
+        assert!(EmbeddingJob::from_entity(
+            "saved_search",
+            &serde_json::json!({"id": "s1", "project_id": "p1", "name": "Refunds", "query": "refund limits"}),
+        )
+        .is_none());
+    }
+}
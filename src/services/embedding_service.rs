@@ -1,8 +1,14 @@
+use crate::config::EmbeddingSettings;
 use crate::models::embedding::{
-    ContextEmbedding, EmbeddingConfig, EmbeddingMetadata, ModelInfo, ModelType, TokenizationMethod,
-    VectorSearchQuery, VectorSearchResult,
+    ContextEmbedding, EmbeddingConfig, EmbeddingMetadata, EmbeddingProvider, ModelInfo, ModelType,
+    RetryPolicy, TokenizationMethod, VectorSearchQuery, VectorSearchResult,
 };
+use crate::services::embedding_providers::HttpEmbeddingService;
+#[cfg(feature = "local-model")]
+use crate::services::local_embedding::LocalEmbeddingService;
 use async_trait::async_trait;
+use std::future::Future;
+use std::time::Duration;
 use nalgebra::DVector;
 use regex::Regex;
 use std::collections::HashMap;
@@ -29,6 +35,16 @@ pub enum EmbeddingError {
     
     #[error("IO error: {source}")]
     IoError { source: std::io::Error },
+
+    #[error("Embedding backend request failed: {message}")]
+    BackendError { message: String, retryable: bool },
+}
+
+impl EmbeddingError {
+    /// Whether repeating the same request may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::BackendError { retryable: true, .. })
+    }
 }
 
 /// Trait for embedding generation services
@@ -51,6 +67,105 @@ pub trait EmbeddingService: Send + Sync {
     
     /// Update configuration
     async fn update_config(&mut self, config: EmbeddingConfig) -> Result<(), EmbeddingError>;
+
+    /// Length of the vectors this service produces
+    fn dimension(&self) -> usize {
+        self.get_model_info().embedding_dimension
+    }
+
+    /// Largest number of texts sent to the backend in one request
+    fn max_batch_size(&self) -> usize {
+        1
+    }
+
+    /// How transient backend failures are retried
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::none()
+    }
+}
+
+/// Run `attempt` until it succeeds, fails with a non-retryable error or the policy's
+/// retries are used up, doubling the delay between attempts
+pub async fn retry_with_backoff<T, F, Fut>(policy: &RetryPolicy, mut attempt: F) -> Result<T, EmbeddingError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, EmbeddingError>>,
+{
+    let mut backoff = Duration::from_millis(policy.initial_backoff_ms);
+    let mut retries = 0;
+    loop {
+        match attempt().await {
+            Err(e) if e.is_retryable() && retries < policy.max_retries => {
+                retries += 1;
+                warn!("Embedding request failed ({}), retry {}/{} in {:?}", e, retries, policy.max_retries, backoff);
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Cosine similarity of two vectors; 0 when their lengths differ or either is zero
+pub fn cosine_similarity(vec1: &[f32], vec2: &[f32]) -> f32 {
+    if vec1.len() != vec2.len() {
+        return 0.0;
+    }
+
+    let dot_product: f32 = vec1.iter().zip(vec2.iter()).map(|(a, b)| a * b).sum();
+    let norm_a: f32 = vec1.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = vec2.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot_product / (norm_a * norm_b)
+    }
+}
+
+/// Rank `embeddings` against an already embedded query, applying the query's
+/// similarity threshold and result limit
+pub fn rank_by_similarity(
+    query_embedding: &[f32],
+    query: &VectorSearchQuery,
+    embeddings: &[ContextEmbedding],
+) -> Vec<VectorSearchResult> {
+    let mut results = Vec::new();
+
+    for embedding in embeddings {
+        // Calculate similarity
+        let similarity = cosine_similarity(query_embedding, &embedding.embedding_vector);
+
+        if similarity >= query.similarity_threshold {
+            let distance = 1.0 - similarity; // Convert similarity to distance
+
+            results.push(VectorSearchResult {
+                context_id: embedding.context_id.clone(),
+                similarity_score: similarity,
+                distance,
+                rank: 0, // Will be set after sorting
+                metadata: crate::models::embedding::ResultMetadata {
+                    content_type: embedding.metadata.content_type.clone(),
+                    content_preview: "Preview not available".to_string(), // Would be filled by caller
+                    match_explanation: format!("Similarity: {:.3}", similarity),
+                    quality_indicators: vec![format!("Quality: {:.2}", embedding.metadata.quality_score)],
+                    facets: Default::default(),
+                    project_id: None,
+                },
+            });
+        }
+    }
+
+    // Sort by similarity (descending)
+    results.sort_by(|a, b| b.similarity_score.partial_cmp(&a.similarity_score).unwrap());
+
+    // Set ranks and limit results
+    for (i, result) in results.iter_mut().enumerate() {
+        result.rank = i + 1;
+    }
+
+    results.truncate(query.max_results);
+    results
 }
 
 /// Implementation of EmbeddingService using simple hash-based embeddings
@@ -319,39 +434,7 @@ impl EmbeddingService for SimpleEmbeddingService {
             temp_embedding.embedding_vector
         };
         
-        let mut results = Vec::new();
-        
-        for embedding in embeddings {
-            // Calculate similarity
-            let similarity = self.calculate_cosine_similarity(&query_embedding, &embedding.embedding_vector);
-            
-            if similarity >= query.similarity_threshold {
-                let distance = 1.0 - similarity; // Convert similarity to distance
-                
-                results.push(VectorSearchResult {
-                    context_id: embedding.context_id.clone(),
-                    similarity_score: similarity,
-                    distance,
-                    rank: 0, // Will be set after sorting
-                    metadata: crate::models::embedding::ResultMetadata {
-                        content_type: embedding.metadata.content_type.clone(),
-                        content_preview: "Preview not available".to_string(), // Would be filled by caller
-                        match_explanation: format!("Similarity: {:.3}", similarity),
-                        quality_indicators: vec![format!("Quality: {:.2}", embedding.metadata.quality_score)],
-                    },
-                });
-            }
-        }
-        
-        // Sort by similarity (descending)
-        results.sort_by(|a, b| b.similarity_score.partial_cmp(&a.similarity_score).unwrap());
-        
-        // Set ranks and limit results
-        for (i, result) in results.iter_mut().enumerate() {
-            result.rank = i + 1;
-        }
-        
-        results.truncate(query.max_results);
+        let results = rank_by_similarity(&query_embedding, query, embeddings);
         
         debug!("Found {} similar embeddings", results.len());
         Ok(results)
@@ -374,24 +457,9 @@ impl EmbeddingService for SimpleEmbeddingService {
         self.initialize().await?;
         Ok(())
     }
-}
-
-impl SimpleEmbeddingService {
-    /// Calculate cosine similarity between two vectors
-    fn calculate_cosine_similarity(&self, vec1: &[f32], vec2: &[f32]) -> f32 {
-        if vec1.len() != vec2.len() {
-            return 0.0;
-        }
 
-        let dot_product: f32 = vec1.iter().zip(vec2.iter()).map(|(a, b)| a * b).sum();
-        let norm_a: f32 = vec1.iter().map(|x| x * x).sum::<f32>().sqrt();
-        let norm_b: f32 = vec2.iter().map(|x| x * x).sum::<f32>().sqrt();
-
-        if norm_a == 0.0 || norm_b == 0.0 {
-            0.0
-        } else {
-            dot_product / (norm_a * norm_b)
-        }
+    fn max_batch_size(&self) -> usize {
+        self.config.batch_size
     }
 }
 
@@ -399,13 +467,116 @@ impl SimpleEmbeddingService {
 pub struct EmbeddingServiceFactory;
 
 impl EmbeddingServiceFactory {
-    pub fn create_service(config: EmbeddingConfig) -> Box<dyn EmbeddingService> {
-        Box::new(SimpleEmbeddingService::new(config))
+    /// Create the service for `config.provider`
+    pub fn create_service(config: EmbeddingConfig) -> Result<Box<dyn EmbeddingService>, EmbeddingError> {
+        match config.provider {
+            EmbeddingProvider::Simple => Ok(Box::new(SimpleEmbeddingService::new(config))),
+            EmbeddingProvider::OpenAi | EmbeddingProvider::Ollama => {
+                Ok(Box::new(HttpEmbeddingService::new(config)?))
+            }
+            #[cfg(feature = "local-model")]
+            EmbeddingProvider::Local => Ok(Box::new(LocalEmbeddingService::new(config)?)),
+            #[cfg(not(feature = "local-model"))]
+            EmbeddingProvider::Local => Err(EmbeddingError::ConfigurationError {
+                message: "The local embedding provider needs a build with the local-model feature".to_string(),
+            }),
+        }
     }
-    
+
     pub async fn create_initialized_service(config: EmbeddingConfig) -> Result<Box<dyn EmbeddingService>, EmbeddingError> {
-        let mut service = SimpleEmbeddingService::new(config);
-        service.initialize().await?;
-        Ok(Box::new(service))
+        if config.provider == EmbeddingProvider::Simple {
+            let mut service = SimpleEmbeddingService::new(config);
+            service.initialize().await?;
+            return Ok(Box::new(service));
+        }
+        Self::create_service(config)
     }
-}
\ No newline at end of file
+
+    /// Translate the `[embedding]` config section
+    pub fn config_from_settings(settings: &EmbeddingSettings) -> Result<EmbeddingConfig, EmbeddingError> {
+        let provider = settings
+            .provider
+            .parse()
+            .map_err(|message| EmbeddingError::ConfigurationError { message })?;
+        Ok(EmbeddingConfig {
+            provider,
+            model_name: settings.model.clone(),
+            model_path: settings.model_path.as_ref().map(|path| path.display().to_string()),
+            embedding_dimension: settings.dimension,
+            batch_size: settings.batch_size,
+            endpoint: settings.endpoint.clone(),
+            api_key: settings.api_key.clone(),
+            retry: RetryPolicy {
+                max_retries: settings.max_retries,
+                ..RetryPolicy::default()
+            },
+            request_timeout_secs: settings.timeout_secs,
+            ..EmbeddingConfig::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test]
+    async fn test_retry_with_backoff_stops_on_success_or_fatal_error() {
+        let policy = RetryPolicy {
+            max_retries: 3,
+            initial_backoff_ms: 1,
+        };
+        let attempts = AtomicU32::new(0);
+        let result = retry_with_backoff(&policy, || async {
+            if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(EmbeddingError::BackendError { message: "503".into(), retryable: true })
+            } else {
+                Ok(42)
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        attempts.store(0, Ordering::SeqCst);
+        let result: Result<(), _> = retry_with_backoff(&policy, || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(EmbeddingError::BackendError { message: "401".into(), retryable: false })
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_config_from_settings() {
+        let settings = EmbeddingSettings {
+            provider: "ollama".to_string(),
+            model: "nomic-embed-text".to_string(),
+            dimension: 768,
+            ..Default::default()
+        };
+        let config = EmbeddingServiceFactory::config_from_settings(&settings).unwrap();
+        assert_eq!(config.provider, EmbeddingProvider::Ollama);
+        assert_eq!(config.embedding_dimension, 768);
+        assert!(EmbeddingServiceFactory::create_service(config).is_ok());
+
+        let settings = EmbeddingSettings {
+            provider: "word2vec".to_string(),
+            ..Default::default()
+        };
+        assert!(EmbeddingServiceFactory::config_from_settings(&settings).is_err());
+
+        // The local provider needs the local-model feature and a model directory
+        let settings = EmbeddingSettings {
+            provider: "local".to_string(),
+            ..Default::default()
+        };
+        let config = EmbeddingServiceFactory::config_from_settings(&settings).unwrap();
+        assert!(matches!(
+            EmbeddingServiceFactory::create_service(config),
+            Err(EmbeddingError::ConfigurationError { .. })
+        ));
+    }
+}
@@ -167,6 +167,8 @@ where
             implementation_pattern: None,
             forbidden_patterns: None,
             compliance_notes: None,
+            severity: None,
+            classification: None,
             created_at: Some(chrono::Utc::now().to_rfc3339()),
         };
         self.security_policy_repository.create(&policy).await
@@ -218,9 +220,11 @@ where
             project_id: project_id.to_string(),
             convention_type: convention_type.map(|s| s.to_string()),
             convention_rule: convention_rule.map(|s| s.to_string()),
+            pattern: None,
             good_examples: None,
             bad_examples: None,
             rationale: None,
+            enforcement_level: None,
             created_at: Some(chrono::Utc::now().to_rfc3339()),
         };
         self.project_convention_repository.create(&convention).await
@@ -279,6 +283,10 @@ where
             key_workflows: None,
             integration_points: None,
             edge_cases: None,
+            constraints: None,
+            related_components: None,
+            status: Some("planned".to_string()),
+            owner: None,
             created_at: Some(chrono::Utc::now().to_rfc3339()),
         };
         self.feature_context_repository
@@ -0,0 +1,279 @@
+use crate::models::architecture::{
+    ArchitectureFinding, ArchitectureRuleSet, FitnessFunction, FitnessMeasurement, FitnessMetric, ViolationRule,
+};
+use crate::models::framework::FrameworkComponent;
+use crate::services::dependency_graph::DependencyGraph;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Computes the fitness functions of a rule set over a project's components. Dependencies
+/// count when they name another component by file path or name, as in the dependency graph.
+pub struct FitnessEvaluator<'a> {
+    components: &'a [FrameworkComponent],
+    /// Distinct components each component depends on, by index
+    edges: Vec<BTreeSet<usize>>,
+}
+
+impl<'a> FitnessEvaluator<'a> {
+    pub fn new(components: &'a [FrameworkComponent]) -> Self {
+        let edges = components
+            .iter()
+            .enumerate()
+            .map(|(index, component)| {
+                component
+                    .dependencies
+                    .iter()
+                    .filter_map(|dep| DependencyGraph::resolve(components, dep))
+                    .filter(|&target| target != index)
+                    .collect()
+            })
+            .collect();
+        Self { components, edges }
+    }
+
+    pub fn evaluate(&self, rules: &ArchitectureRuleSet) -> Vec<FitnessMeasurement> {
+        let measured_at = chrono::Utc::now().to_rfc3339();
+        rules
+            .fitness_functions
+            .iter()
+            .map(|function| {
+                let (value, subject) = self.measure(function);
+                FitnessMeasurement {
+                    project_id: rules.project_id.clone(),
+                    function: function.name.clone(),
+                    metric: function.metric,
+                    value,
+                    threshold: function.threshold,
+                    subject,
+                    passed: value <= function.threshold,
+                    measured_at: measured_at.clone(),
+                }
+            })
+            .collect()
+    }
+
+    /// Highest value of the metric in the function's scope and the layer or component with it
+    pub fn measure(&self, function: &FitnessFunction) -> (f64, Option<String>) {
+        let in_scope = |component: &FrameworkComponent| {
+            function
+                .layer
+                .as_ref()
+                .is_none_or(|layer| layer.eq_ignore_ascii_case(&component.architecture_layer))
+        };
+        let per_component = |value: &dyn Fn(usize) -> usize| {
+            self.highest(
+                self.components
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, component)| in_scope(component))
+                    .map(|(index, component)| (value(index) as f64, component.component_name.clone())),
+            )
+        };
+
+        match function.metric {
+            FitnessMetric::FanOut => per_component(&|index| self.edges[index].len()),
+            FitnessMetric::FanIn => per_component(&|index| self.edges.iter().filter(|targets| targets.contains(&index)).count()),
+            FitnessMetric::ComponentsPerLayer => self.highest(
+                self.layers()
+                    .into_iter()
+                    .filter(|layer| function.layer.as_ref().is_none_or(|name| name.eq_ignore_ascii_case(layer)))
+                    .map(|layer| {
+                        let count = self.components.iter().filter(|c| c.architecture_layer.eq_ignore_ascii_case(&layer)).count();
+                        (count as f64, layer)
+                    }),
+            ),
+            FitnessMetric::Instability => self.highest(
+                self.layers()
+                    .into_iter()
+                    .filter(|layer| function.layer.as_ref().is_none_or(|name| name.eq_ignore_ascii_case(layer)))
+                    .map(|layer| (self.instability(&layer), layer)),
+            ),
+        }
+    }
+
+    /// Outgoing over all dependencies crossing the layer's boundary; 0 without any
+    fn instability(&self, layer: &str) -> f64 {
+        let inside = |index: usize| self.components[index].architecture_layer.eq_ignore_ascii_case(layer);
+        let (mut efferent, mut afferent) = (0usize, 0usize);
+        for (source, targets) in self.edges.iter().enumerate() {
+            for &target in targets {
+                match (inside(source), inside(target)) {
+                    (true, false) => efferent += 1,
+                    (false, true) => afferent += 1,
+                    _ => {}
+                }
+            }
+        }
+        if efferent + afferent == 0 {
+            0.0
+        } else {
+            efferent as f64 / (efferent + afferent) as f64
+        }
+    }
+
+    /// Layers in order of first appearance, lowercased
+    fn layers(&self) -> Vec<String> {
+        let mut layers: Vec<String> = Vec::new();
+        for component in self.components {
+            let layer = component.architecture_layer.to_lowercase();
+            if !layers.contains(&layer) {
+                layers.push(layer);
+            }
+        }
+        layers
+    }
+
+    /// The first of the highest values, or 0 when there are none
+    fn highest(&self, values: impl Iterator<Item = (f64, String)>) -> (f64, Option<String>) {
+        values.fold((0.0, None), |(best, subject), (value, name)| {
+            if subject.is_none() || value > best {
+                (value, Some(name))
+            } else {
+                (best, subject)
+            }
+        })
+    }
+
+    /// A finding for a measurement above its threshold
+    pub fn finding(function: &FitnessFunction, measurement: &FitnessMeasurement) -> Option<ArchitectureFinding> {
+        if measurement.passed {
+            return None;
+        }
+        let subject = measurement.subject.clone().unwrap_or_default();
+        Some(ArchitectureFinding {
+            rule: ViolationRule::Fitness,
+            severity: function.severity.unwrap_or_else(|| ViolationRule::Fitness.default_severity()),
+            component: subject.clone(),
+            message: format!(
+                "Fitness function {} failed: {} of {} is {}, above {}",
+                function.name,
+                function.metric,
+                subject,
+                number(measurement.value),
+                number(measurement.threshold)
+            ),
+            validator: None,
+            dependency: None,
+            fixes: Vec::new(),
+        })
+    }
+}
+
+fn number(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+/// How a fitness function moved since it was last recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendDirection {
+    /// Not recorded before
+    New,
+    Improving,
+    Stable,
+    Worsening,
+}
+
+/// A measurement with the previously recorded value of the same function
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FitnessTrend {
+    #[serde(flatten)]
+    pub measurement: FitnessMeasurement,
+    pub previous: Option<f64>,
+    pub trend: TrendDirection,
+}
+
+impl FitnessTrend {
+    pub fn new(measurement: FitnessMeasurement, previous: Option<&FitnessMeasurement>) -> Self {
+        let previous = previous.map(|previous| previous.value);
+        // Lower is better for every metric
+        let trend = match previous {
+            None => TrendDirection::New,
+            Some(previous) if (measurement.value - previous).abs() < 1e-9 => TrendDirection::Stable,
+            Some(previous) if measurement.value < previous => TrendDirection::Improving,
+            Some(_) => TrendDirection::Worsening,
+        };
+        Self { measurement, previous, trend }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(name: &str, layer: &str, dependencies: &[&str]) -> FrameworkComponent {
+        FrameworkComponent {
+            id: name.to_string(),
+            project_id: "p1".to_string(),
+            component_name: name.to_string(),
+            component_type: "service".to_string(),
+            architecture_layer: layer.to_string(),
+            file_path: None,
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            metadata: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn function(name: &str, metric: FitnessMetric, threshold: f64, layer: Option<&str>) -> FitnessFunction {
+        FitnessFunction {
+            name: name.to_string(),
+            metric,
+            threshold,
+            layer: layer.map(str::to_string),
+            severity: None,
+        }
+    }
+
+    #[test]
+    fn test_fitness_functions() {
+        let components = vec![
+            component("LoginPage", "presentation", &["AuthService", "UserRepository", "package:http/http.dart"]),
+            component("ProfilePage", "presentation", &["AuthService"]),
+            component("AuthService", "domain", &["UserRepository", "AuthService"]),
+            component("UserRepository", "data", &[]),
+        ];
+        let mut rules = ArchitectureRuleSet::clean_architecture("p1");
+        rules.fitness_functions = vec![
+            function("max_fan_out", FitnessMetric::FanOut, 1.0, None),
+            function("max_fan_in", FitnessMetric::FanIn, 2.0, None),
+            function("presentation_size", FitnessMetric::ComponentsPerLayer, 5.0, Some("Presentation")),
+            function("domain_instability", FitnessMetric::Instability, 0.25, Some("domain")),
+        ];
+        assert!(rules.problems().is_empty());
+
+        let measurements = FitnessEvaluator::new(&components).evaluate(&rules);
+        let values: Vec<(f64, Option<&str>, bool)> = measurements
+            .iter()
+            .map(|m| (m.value, m.subject.as_deref(), m.passed))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                (2.0, Some("LoginPage"), false),
+                (2.0, Some("AuthService"), true),
+                (2.0, Some("presentation"), true),
+                // one outgoing dependency against two incoming ones
+                (1.0 / 3.0, Some("domain"), false),
+            ]
+        );
+
+        let finding = FitnessEvaluator::finding(&rules.fitness_functions[0], &measurements[0]).unwrap();
+        assert_eq!(finding.message, "Fitness function max_fan_out failed: fan_out of LoginPage is 2, above 1");
+        assert!(FitnessEvaluator::finding(&rules.fitness_functions[1], &measurements[1]).is_none());
+
+        let mut earlier = measurements[3].clone();
+        earlier.value = 0.5;
+        assert_eq!(FitnessTrend::new(measurements[3].clone(), Some(&earlier)).trend, TrendDirection::Improving);
+        assert_eq!(FitnessTrend::new(measurements[0].clone(), None).trend, TrendDirection::New);
+
+        rules.fitness_functions[3].threshold = 2.0;
+        rules.fitness_functions.push(function("max_fan_out", FitnessMetric::FanOut, 3.0, Some("ui")));
+        assert_eq!(rules.problems().len(), 3);
+    }
+}
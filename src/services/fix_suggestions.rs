@@ -0,0 +1,389 @@
+use crate::models::architecture::{
+    ArchitectureFinding, ArchitectureRuleSet, ConventionPattern, ConventionTarget, FixAction, FixKind, FixSuggestion,
+    LayerRule, ViolationRule,
+};
+use crate::models::framework::FrameworkComponent;
+use crate::services::dependency_cycles::DependencyCycle;
+use crate::services::dependency_graph::DependencyGraph;
+use serde_json::json;
+
+/// Proposes fixes for architecture findings as `create_entity` and `update_entity` calls an
+/// agent can make as they are. Only the stored components change; moving or renaming the
+/// files themselves is left to whoever applies the fix.
+pub struct FixSuggester<'a> {
+    rules: &'a ArchitectureRuleSet,
+    components: &'a [FrameworkComponent],
+}
+
+impl<'a> FixSuggester<'a> {
+    pub fn new(rules: &'a ArchitectureRuleSet, components: &'a [FrameworkComponent]) -> Self {
+        Self { rules, components }
+    }
+
+    /// Fixes for a finding about one of the components; none for the others
+    pub fn suggest(&self, finding: &ArchitectureFinding) -> Vec<FixSuggestion> {
+        let dependency = finding.dependency.as_deref();
+        let Some(component) = self.components.iter().find(|component| {
+            component.component_name == finding.component
+                && dependency.is_none_or(|dep| component.dependencies.iter().any(|d| d == dep))
+        }) else {
+            return Vec::new();
+        };
+        match (finding.rule, dependency) {
+            (ViolationRule::LayerDependency, Some(dep)) => self.layer_fixes(component, dep),
+            (ViolationRule::ForbiddenImport | ViolationRule::Plugin, Some(dep)) => {
+                vec![Self::remove_dependency(component, dep)]
+            }
+            (ViolationRule::UnknownLayer, _) => self.assign_layer(component).into_iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Removes the dependencies whose removal leaves no loop
+    pub fn break_cycle(&self, cycle: &DependencyCycle) -> Vec<FixSuggestion> {
+        let mut actions = Vec::new();
+        let mut removed = Vec::new();
+        for edge in &cycle.break_edges {
+            let Some(from) = self.components.iter().find(|c| c.component_name == edge.from) else {
+                continue;
+            };
+            let Some(dep) = from.dependencies.iter().find(|dep| {
+                DependencyGraph::resolve(self.components, dep)
+                    .is_some_and(|index| self.components[index].component_name == edge.to)
+            }) else {
+                continue;
+            };
+            let mut changed = from.clone();
+            changed.dependencies.retain(|d| d != dep);
+            actions.push(Self::update(&changed));
+            removed.push(format!("{} -> {}", edge.from, edge.to));
+        }
+        if actions.is_empty() {
+            return Vec::new();
+        }
+        vec![FixSuggestion {
+            kind: FixKind::RemoveDependency,
+            description: format!(
+                "Break the cycle by removing {}, e.g. by depending on an interface instead",
+                removed.join(", ")
+            ),
+            actions,
+        }]
+    }
+
+    /// Renames a component, or its file, to follow a prefix or suffix convention
+    pub fn rename(component: &FrameworkComponent, pattern: &ConventionPattern, name: &str) -> Option<FixSuggestion> {
+        let fixed = pattern.fixed_name(name)?;
+        let mut changed = component.clone();
+        let description = match pattern.target {
+            ConventionTarget::ComponentName => {
+                changed.component_name = fixed.clone();
+                format!("Rename {} to {}", component.component_name, fixed)
+            }
+            ConventionTarget::FileName => {
+                let path = component.file_path.as_deref()?;
+                let directory = &path[..path.len() - name.len()];
+                changed.file_path = Some(format!("{}{}", directory, fixed));
+                format!("Rename file {} of {} to {}", name, component.component_name, fixed)
+            }
+        };
+        Some(FixSuggestion {
+            kind: FixKind::Rename,
+            description,
+            actions: vec![Self::update(&changed)],
+        })
+    }
+
+    /// An interface between the two layers, moving the component and inverting the
+    /// dependency, as far as the rules allow each
+    fn layer_fixes(&self, component: &FrameworkComponent, dep: &str) -> Vec<FixSuggestion> {
+        let (Some(layer), Some(dep_layer)) = (
+            self.rules.layer(&component.architecture_layer),
+            self.rules.layers_of_dependency(dep).first().copied(),
+        ) else {
+            return Vec::new();
+        };
+        let target = DependencyGraph::resolve(self.components, dep)
+            .map(|index| &self.components[index])
+            .filter(|target| target.id != component.id);
+
+        let mut fixes = Vec::new();
+        fixes.extend(self.introduce_interface(component, layer, dep_layer, dep, target));
+        fixes.extend(self.move_to_layer(component, layer));
+        if let Some(target) = target.filter(|_| Self::may_use(dep_layer, layer)) {
+            fixes.extend(Self::invert(component, dep, target));
+        }
+        fixes
+    }
+
+    /// Puts the interface in the innermost layer both may use, the one allowed the fewest
+    /// dependencies, so `presentation` to `data` goes through `domain`
+    fn introduce_interface(
+        &self,
+        component: &FrameworkComponent,
+        layer: &LayerRule,
+        dep_layer: &LayerRule,
+        dep: &str,
+        target: Option<&FrameworkComponent>,
+    ) -> Option<FixSuggestion> {
+        let reach = |candidate: &LayerRule| candidate.may_depend_on.as_ref().map_or(self.rules.layers.len(), Vec::len);
+        let interface_layer = self
+            .rules
+            .layers
+            .iter()
+            .filter(|candidate| {
+                !candidate.name.eq_ignore_ascii_case(&dep_layer.name)
+                    && Self::may_use(layer, candidate)
+                    && Self::may_use(dep_layer, candidate)
+            })
+            .fold(None::<&LayerRule>, |best, candidate| match best {
+                Some(best) if reach(best) <= reach(candidate) => Some(best),
+                _ => Some(candidate),
+            })?;
+
+        let base = match target {
+            Some(target) => target.component_name.clone(),
+            None => Self::type_name(dep)?,
+        };
+        let name = format!("{}Interface", base);
+        let mut actions = Vec::new();
+        if !self.components.iter().any(|c| c.component_name == name) {
+            actions.push(FixAction {
+                tool: "create_entity".to_string(),
+                arguments: json!({
+                    "entity_type": "framework_component",
+                    "data": {
+                        "project_id": component.project_id,
+                        "component_name": name,
+                        "component_type": "interface",
+                        "architecture_layer": interface_layer.name,
+                    }
+                }),
+            });
+        }
+        let mut changed = component.clone();
+        for d in changed.dependencies.iter_mut().filter(|d| *d == dep) {
+            *d = name.clone();
+        }
+        changed.dependencies.dedup();
+        actions.push(Self::update(&changed));
+
+        let mut description = format!(
+            "Introduce {} in the {} layer and let {} depend on it instead of {}",
+            name, interface_layer.name, component.component_name, dep
+        );
+        if let Some(target) = target {
+            if !target.dependencies.contains(&name) {
+                let mut implementation = target.clone();
+                implementation.dependencies.push(name.clone());
+                actions.push(Self::update(&implementation));
+            }
+            description.push_str(&format!("; {} implements it", target.component_name));
+        }
+        Some(FixSuggestion {
+            kind: FixKind::IntroduceInterface,
+            description,
+            actions,
+        })
+    }
+
+    /// The first other layer that allows every dependency of the component
+    fn move_to_layer(&self, component: &FrameworkComponent, layer: &LayerRule) -> Option<FixSuggestion> {
+        let destination = self.rules.layers.iter().find(|candidate| {
+            !candidate.name.eq_ignore_ascii_case(&layer.name)
+                && component.dependencies.iter().all(|dep| self.permits(candidate, dep))
+        })?;
+        let mut changed = component.clone();
+        changed.architecture_layer = destination.name.clone();
+        let mut description = format!(
+            "Move {} to the {} layer, which may use all its dependencies",
+            component.component_name, destination.name
+        );
+        if let Some(path) = Self::moved_path(component.file_path.as_deref(), layer, destination) {
+            description.push_str(&format!(" ({} -> {})", component.file_path.as_deref().unwrap_or_default(), path));
+            changed.file_path = Some(path);
+        }
+        Some(FixSuggestion {
+            kind: FixKind::MoveToLayer,
+            description,
+            actions: vec![Self::update(&changed)],
+        })
+    }
+
+    fn invert(component: &FrameworkComponent, dep: &str, target: &FrameworkComponent) -> Option<FixSuggestion> {
+        let reference = component.file_path.clone().unwrap_or_else(|| component.component_name.clone());
+        if target.dependencies.contains(&reference) || target.dependencies.contains(&component.component_name) {
+            return None;
+        }
+        let mut changed = component.clone();
+        changed.dependencies.retain(|d| d != dep);
+        let mut inverted = target.clone();
+        inverted.dependencies.push(reference);
+        Some(FixSuggestion {
+            kind: FixKind::InvertDependency,
+            description: format!(
+                "Invert the dependency: {} depends on {} instead, e.g. by calling back into it",
+                target.component_name, component.component_name
+            ),
+            actions: vec![Self::update(&changed), Self::update(&inverted)],
+        })
+    }
+
+    fn remove_dependency(component: &FrameworkComponent, dep: &str) -> FixSuggestion {
+        let mut changed = component.clone();
+        changed.dependencies.retain(|d| d != dep);
+        FixSuggestion {
+            kind: FixKind::RemoveDependency,
+            description: format!("Remove the dependency of {} on {}", component.component_name, dep),
+            actions: vec![Self::update(&changed)],
+        }
+    }
+
+    /// The layer the component's file is in by the path patterns
+    fn assign_layer(&self, component: &FrameworkComponent) -> Option<FixSuggestion> {
+        let path = component.file_path.as_deref()?;
+        let layer = self.rules.layers_of_dependency(path).first().copied()?;
+        let mut changed = component.clone();
+        changed.architecture_layer = layer.name.clone();
+        Some(FixSuggestion {
+            kind: FixKind::MoveToLayer,
+            description: format!(
+                "Assign {} to the {} layer its file {} is in",
+                component.component_name, layer.name, path
+            ),
+            actions: vec![Self::update(&changed)],
+        })
+    }
+
+    /// Same check as validation: a dependency outside every layer is always allowed
+    fn permits(&self, layer: &LayerRule, dep: &str) -> bool {
+        let Some(allowed) = &layer.may_depend_on else {
+            return true;
+        };
+        let dep_layers = self.rules.layers_of_dependency(dep);
+        dep_layers.is_empty()
+            || dep_layers.iter().any(|dep_layer| {
+                dep_layer.name.eq_ignore_ascii_case(&layer.name)
+                    || allowed.iter().any(|name| name.eq_ignore_ascii_case(&dep_layer.name))
+            })
+    }
+
+    fn may_use(layer: &LayerRule, other: &LayerRule) -> bool {
+        layer.name.eq_ignore_ascii_case(&other.name)
+            || layer
+                .may_depend_on
+                .as_ref()
+                .is_none_or(|allowed| allowed.iter().any(|name| name.eq_ignore_ascii_case(&other.name)))
+    }
+
+    /// The file path with the old layer's path pattern replaced by the new layer's
+    fn moved_path(path: Option<&str>, from: &LayerRule, to: &LayerRule) -> Option<String> {
+        let path = path?;
+        let old = from.path_patterns.iter().find(|pattern| path.contains(pattern.as_str()))?;
+        let new = to.path_patterns.first()?;
+        Some(path.replacen(old.as_str(), new, 1))
+    }
+
+    /// `data/user_repository.dart` → `UserRepository`
+    fn type_name(dep: &str) -> Option<String> {
+        let file = dep.rsplit(['/', '\\', ':']).next()?;
+        let stem = file.split('.').next()?;
+        let name: String = stem
+            .split(['_', '-'])
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let mut chars = word.chars();
+                chars.next().map(|first| first.to_uppercase().chain(chars).collect::<String>()).unwrap_or_default()
+            })
+            .collect();
+        (!name.is_empty()).then_some(name)
+    }
+
+    /// `update_entity` call storing the changed component
+    fn update(component: &FrameworkComponent) -> FixAction {
+        let mut data = json!({
+            "component_name": component.component_name,
+            "component_type": component.component_type,
+            "architecture_layer": component.architecture_layer,
+            "dependencies": component.dependencies,
+        });
+        if let Some(path) = &component.file_path {
+            data["file_path"] = json!(path);
+        }
+        FixAction {
+            tool: "update_entity".to_string(),
+            arguments: json!({"entity_type": "framework_component", "id": component.id, "data": data}),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::architecture::Severity;
+
+    fn component(name: &str, layer: &str, file_path: &str, dependencies: &[&str]) -> FrameworkComponent {
+        FrameworkComponent {
+            id: name.to_lowercase(),
+            project_id: "p1".to_string(),
+            component_name: name.to_string(),
+            component_type: "class".to_string(),
+            architecture_layer: layer.to_string(),
+            file_path: Some(file_path.to_string()),
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            metadata: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn finding(rule: ViolationRule, component: &str, dependency: Option<&str>) -> ArchitectureFinding {
+        ArchitectureFinding {
+            rule,
+            severity: Severity::Error,
+            component: component.to_string(),
+            message: String::new(),
+            validator: None,
+            dependency: dependency.map(str::to_string),
+            fixes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_layer_dependency_fixes() {
+        let rules = ArchitectureRuleSet::clean_architecture("p1");
+        let components = vec![
+            component("LoginPage", "presentation", "lib/presentation/login_page.dart", &["data/user_repository.dart"]),
+            component("UserRepository", "data", "data/user_repository.dart", &[]),
+        ];
+        let suggester = FixSuggester::new(&rules, &components);
+        let fixes = suggester.suggest(&finding(ViolationRule::LayerDependency, "LoginPage", Some("data/user_repository.dart")));
+        let kinds: Vec<FixKind> = fixes.iter().map(|fix| fix.kind).collect();
+        assert_eq!(kinds, vec![FixKind::IntroduceInterface, FixKind::MoveToLayer, FixKind::InvertDependency]);
+
+        let interface = &fixes[0];
+        assert_eq!(
+            interface.description,
+            "Introduce UserRepositoryInterface in the domain layer and let LoginPage depend on it instead of data/user_repository.dart; UserRepository implements it"
+        );
+        assert_eq!(interface.actions[0].tool, "create_entity");
+        assert_eq!(interface.actions[0].arguments["data"]["architecture_layer"], "domain");
+        assert_eq!(interface.actions[1].arguments["id"], "loginpage");
+        assert_eq!(interface.actions[1].arguments["data"]["dependencies"], json!(["UserRepositoryInterface"]));
+        assert_eq!(interface.actions[2].arguments["data"]["dependencies"], json!(["UserRepositoryInterface"]));
+
+        let moved = &fixes[1].actions[0].arguments["data"];
+        assert_eq!(moved["architecture_layer"], "data");
+        assert_eq!(moved["file_path"], "lib/data/login_page.dart");
+
+        let inverted = &fixes[2].actions;
+        assert_eq!(inverted[0].arguments["data"]["dependencies"], json!([]));
+        assert_eq!(inverted[1].arguments["data"]["dependencies"], json!(["lib/presentation/login_page.dart"]));
+
+        let unknown = component("Cache", "infra", "lib/core/cache.dart", &[]);
+        let components = vec![unknown];
+        let fixes = FixSuggester::new(&rules, &components).suggest(&finding(ViolationRule::UnknownLayer, "Cache", None));
+        assert_eq!(fixes[0].actions[0].arguments["data"]["architecture_layer"], "core");
+        assert_eq!(FixSuggester::type_name("package:app/data/api_client.dart").as_deref(), Some("ApiClient"));
+    }
+}
@@ -0,0 +1,317 @@
+use crate::models::specification::{AcceptanceCriterion, CriterionType, Requirement};
+use anyhow::{anyhow, Result};
+use std::path::Path;
+
+/// A feature read from a Gherkin `.feature` file
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ParsedFeature {
+    pub name: String,
+    /// Free text under `Feature:`, often a user story
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    /// `Background:` steps, which precede every scenario
+    pub background: Vec<Step>,
+    pub scenarios: Vec<ParsedScenario>,
+}
+
+/// A `Scenario:` or `Scenario Outline:` of a feature
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ParsedScenario {
+    pub name: String,
+    pub description: Option<String>,
+    /// The scenario's own tags and those of the `Rule:` it is under
+    pub tags: Vec<String>,
+    pub steps: Vec<Step>,
+    /// `Examples:` rows of an outline, each as `column=value` pairs
+    pub examples: Vec<String>,
+}
+
+/// A step, with `And`/`But`/`*` resolved to the keyword they continue
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub keyword: StepKeyword,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepKeyword {
+    Given,
+    When,
+    Then,
+}
+
+/// Parser for Gherkin feature files, in English keywords
+pub struct GherkinParser;
+
+/// Where the lines being read belong
+enum Block {
+    Feature,
+    Background,
+    Rule,
+    Scenario,
+    Examples,
+}
+
+impl GherkinParser {
+    pub fn is_feature_file(file_path: &Path) -> bool {
+        file_path.extension().and_then(|e| e.to_str()) == Some("feature")
+    }
+
+    pub fn parse(content: &str) -> Result<ParsedFeature> {
+        let mut feature: Option<ParsedFeature> = None;
+        let mut block = Block::Feature;
+        let mut pending_tags: Vec<String> = Vec::new();
+        let mut rule_tags: Vec<String> = Vec::new();
+        let mut last_keyword: Option<StepKeyword> = None;
+        let mut example_header: Option<Vec<String>> = None;
+        let mut in_doc_string = false;
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with("\"\"\"") || line.starts_with("```") {
+                in_doc_string = !in_doc_string;
+                continue;
+            }
+            if in_doc_string || line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line.starts_with('@') {
+                pending_tags.extend(line.split_whitespace().map(|tag| tag.trim_start_matches('@').to_string()));
+                continue;
+            }
+
+            if let Some(name) = Self::keyword_value(line, &["Feature"]) {
+                feature = Some(ParsedFeature {
+                    name: name.to_string(),
+                    tags: std::mem::take(&mut pending_tags),
+                    ..Default::default()
+                });
+                block = Block::Feature;
+                continue;
+            }
+            let Some(feature) = feature.as_mut() else {
+                continue;
+            };
+
+            if Self::keyword_value(line, &["Background"]).is_some() {
+                block = Block::Background;
+                last_keyword = None;
+            } else if Self::keyword_value(line, &["Rule"]).is_some() {
+                rule_tags = std::mem::take(&mut pending_tags);
+                block = Block::Rule;
+            } else if let Some(name) =
+                Self::keyword_value(line, &["Scenario Outline", "Scenario Template", "Scenario", "Example"])
+            {
+                let mut tags = rule_tags.clone();
+                tags.append(&mut pending_tags);
+                feature.scenarios.push(ParsedScenario {
+                    name: name.to_string(),
+                    tags,
+                    ..Default::default()
+                });
+                block = Block::Scenario;
+                last_keyword = None;
+            } else if Self::keyword_value(line, &["Examples", "Scenarios"]).is_some() {
+                pending_tags.clear();
+                example_header = None;
+                block = Block::Examples;
+            } else if let Some(step) = Self::step(line, last_keyword) {
+                last_keyword = Some(step.keyword);
+                match block {
+                    Block::Background => feature.background.push(step),
+                    Block::Scenario => {
+                        if let Some(scenario) = feature.scenarios.last_mut() {
+                            scenario.steps.push(step);
+                        }
+                    }
+                    _ => {}
+                }
+            } else if line.starts_with('|') {
+                // Data tables of steps are left out; example rows become test cases
+                if let (Block::Examples, Some(scenario)) = (&block, feature.scenarios.last_mut()) {
+                    let cells = Self::table_cells(line);
+                    match &example_header {
+                        None => example_header = Some(cells),
+                        Some(header) => scenario.examples.push(
+                            header
+                                .iter()
+                                .zip(&cells)
+                                .map(|(column, value)| format!("{}={}", column, value))
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        ),
+                    }
+                }
+            } else {
+                let description = match block {
+                    Block::Feature => Some(&mut feature.description),
+                    Block::Scenario => feature.scenarios.last_mut().map(|s| &mut s.description),
+                    _ => None,
+                };
+                if let Some(description) = description {
+                    match description {
+                        Some(text) => {
+                            text.push('\n');
+                            text.push_str(line);
+                        }
+                        None => *description = Some(line.to_string()),
+                    }
+                }
+            }
+        }
+
+        feature.ok_or_else(|| anyhow!("No Feature: found"))
+    }
+
+    /// Text after `Keyword:` when the line starts with one of the keywords
+    fn keyword_value<'a>(line: &'a str, keywords: &[&str]) -> Option<&'a str> {
+        keywords.iter().find_map(|keyword| {
+            line.strip_prefix(keyword)
+                .and_then(|rest| rest.trim_start().strip_prefix(':'))
+                .map(str::trim)
+        })
+    }
+
+    fn step(line: &str, previous: Option<StepKeyword>) -> Option<Step> {
+        let (word, text) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let keyword = match word {
+            "Given" => StepKeyword::Given,
+            "When" => StepKeyword::When,
+            "Then" => StepKeyword::Then,
+            "And" | "But" | "*" => previous.unwrap_or(StepKeyword::Given),
+            _ => return None,
+        };
+        Some(Step {
+            keyword,
+            text: text.trim().to_string(),
+        })
+    }
+
+    fn table_cells(line: &str) -> Vec<String> {
+        line.trim_matches('|').split('|').map(|cell| cell.trim().to_string()).collect()
+    }
+}
+
+impl ParsedFeature {
+    /// One requirement per scenario, whose acceptance criteria are its `Then` steps, each
+    /// with the `Given` and `When` steps leading up to it
+    pub fn to_requirements(&self, spec_id: &str) -> Vec<Requirement> {
+        self.scenarios
+            .iter()
+            .map(|scenario| {
+                let mut requirement = Requirement::new(
+                    spec_id.to_string(),
+                    scenario.name.clone(),
+                    scenario.description.clone().unwrap_or_default(),
+                );
+                requirement.metadata.source = "gherkin".to_string();
+                if !scenario.tags.is_empty() {
+                    requirement.metadata.custom_fields.insert("tags".to_string(), serde_json::json!(scenario.tags));
+                }
+
+                let steps: Vec<&Step> = self.background.iter().chain(&scenario.steps).collect();
+                let phrase = |keyword: StepKeyword, upto: usize| {
+                    steps[..upto]
+                        .iter()
+                        .filter(|step| step.keyword == keyword)
+                        .map(|step| step.text.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" AND ")
+                };
+                for (index, step) in steps.iter().enumerate().filter(|(_, step)| step.keyword == StepKeyword::Then) {
+                    let mut description = String::new();
+                    for (keyword, label) in [(StepKeyword::Given, "GIVEN"), (StepKeyword::When, "WHEN")] {
+                        let text = phrase(keyword, index);
+                        if !text.is_empty() {
+                            description.push_str(&format!("{} {} ", label, text));
+                        }
+                    }
+                    description.push_str(&format!("THEN {}", step.text));
+
+                    let mut criterion = AcceptanceCriterion::new(description, CriterionType::Functional);
+                    criterion.test_cases = scenario.examples.clone();
+                    requirement.add_acceptance_criterion(criterion);
+                }
+                requirement
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEATURE: &str = r#"@billing
+Feature: Invoices
+  As an accountant
+  I want invoices generated monthly
+
+  Background:
+    Given a customer on the monthly plan
+
+  @smoke
+  Scenario: Invoice is issued
+    When the billing period ends
+    Then an invoice is issued
+    And it is emailed to the customer
+
+  Rule: Discounts
+    Scenario Outline: Discount applies
+      Given a discount of <percent>
+      When the invoice is issued
+      Then the total is <total>
+      """
+      ignored doc string
+      Then not a step
+      """
+
+      Examples:
+        | percent | total |
+        | 10      | 90    |
+        | 50      | 50    |
+"#;
+
+    #[test]
+    fn test_parse_feature() {
+        let feature = GherkinParser::parse(FEATURE).unwrap();
+        assert_eq!(feature.name, "Invoices");
+        assert_eq!(feature.tags, vec!["billing"]);
+        assert_eq!(feature.description.as_deref(), Some("As an accountant\nI want invoices generated monthly"));
+        assert_eq!(feature.background.len(), 1);
+        assert_eq!(feature.scenarios.len(), 2);
+
+        let issued = &feature.scenarios[0];
+        assert_eq!(issued.tags, vec!["smoke"]);
+        assert_eq!(issued.steps[2], Step { keyword: StepKeyword::Then, text: "it is emailed to the customer".to_string() });
+
+        let outline = &feature.scenarios[1];
+        assert_eq!(outline.name, "Discount applies");
+        assert_eq!(outline.steps.len(), 3);
+        assert_eq!(outline.examples, vec!["percent=10, total=90", "percent=50, total=50"]);
+
+        assert!(GherkinParser::parse("Scenario: no feature\n").is_err());
+    }
+
+    #[test]
+    fn test_scenarios_become_requirements() {
+        let feature = GherkinParser::parse(FEATURE).unwrap();
+        let requirements = feature.to_requirements("spec-1");
+        assert_eq!(requirements.len(), 2);
+
+        let criteria: Vec<&str> = requirements[0].acceptance_criteria.iter().map(|c| c.description.as_str()).collect();
+        assert_eq!(
+            criteria,
+            vec![
+                "GIVEN a customer on the monthly plan WHEN the billing period ends THEN an invoice is issued",
+                "GIVEN a customer on the monthly plan WHEN the billing period ends THEN it is emailed to the customer",
+            ]
+        );
+        assert_eq!(requirements[1].acceptance_criteria[0].test_cases.len(), 2);
+        assert_eq!(
+            requirements[1].acceptance_criteria[0].description,
+            "GIVEN a customer on the monthly plan AND a discount of <percent> WHEN the invoice is issued THEN the total is <total>"
+        );
+    }
+}
@@ -0,0 +1,369 @@
+//! Project glossaries: groups of interchangeable domain terms ("auth", "authentication")
+//! that expand search queries, so a search finds content whichever word it was written with,
+//! and define the project's domain language for `query_context`
+
+use crate::models::glossary::GlossaryEntry;
+use crate::repositories::GlossaryRepository;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A glossary term found in a query and the synonyms searched alongside it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryExpansion {
+    /// Glossary member as it appears in the glossary
+    pub matched: String,
+    pub synonyms: Vec<String>,
+}
+
+/// A glossary term that `query_context` results use or that belongs to the queried feature area
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TermUse {
+    pub term: String,
+    pub synonyms: Vec<String>,
+    pub definition: Option<String>,
+    pub domain_area: Option<String>,
+    /// IDs of the results mentioning the term or one of its synonyms
+    pub found_in: Vec<String>,
+}
+
+/// Service for Glossary operations
+#[async_trait]
+pub trait GlossaryService: Send + Sync {
+    /// Add `synonyms` to the group containing `term` (or one of the synonyms), creating a
+    /// group for `term` when there is none
+    async fn add_synonyms(&self, project_id: &str, term: &str, synonyms: &[String]) -> Result<GlossaryEntry, McpError>;
+    /// Remove `synonyms` from the group containing `term`, or the whole group when
+    /// `synonyms` is empty; `None` when no synonyms are left and the group was deleted
+    async fn remove_synonyms(
+        &self,
+        project_id: &str,
+        term: &str,
+        synonyms: &[String],
+    ) -> Result<Option<GlossaryEntry>, McpError>;
+    async fn list_entries(&self, project_id: &str) -> Result<Vec<GlossaryEntry>, McpError>;
+    /// Expansions of `query` by the glossaries of `project_ids`
+    async fn expand(&self, project_ids: &[String], query: &str) -> Result<Vec<QueryExpansion>, McpError>;
+    async fn get_entry(&self, id: &str) -> Result<Option<GlossaryEntry>, McpError>;
+    /// Create or replace a glossary term; neither it nor its synonyms may belong to another
+    /// term of the project
+    async fn save_entry(&self, entry: GlossaryEntry) -> Result<GlossaryEntry, McpError>;
+    async fn delete_entry(&self, id: &str) -> Result<bool, McpError>;
+}
+
+/// Lowercased words of a query or glossary member
+fn words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether `member` appears in `text_words` as whole words
+fn mentions(text_words: &[String], member: &str) -> bool {
+    let member_words = words(member);
+    !member_words.is_empty() && text_words.windows(member_words.len()).any(|window| window == member_words)
+}
+
+/// For each glossary group with a member in `query` (as whole words, ignoring case),
+/// that member and the rest of its group
+pub fn expand_query(entries: &[GlossaryEntry], query: &str) -> Vec<QueryExpansion> {
+    let query_words = words(query);
+    let mut expansions = Vec::new();
+    for entry in entries {
+        let matched = entry.members().find(|member| mentions(&query_words, member));
+        if let Some(matched) = matched {
+            expansions.push(QueryExpansion {
+                matched: matched.clone(),
+                synonyms: entry.members().filter(|member| *member != matched).cloned().collect(),
+            });
+        }
+    }
+    expansions
+}
+
+/// The glossary terms mentioned in `texts` (result ID and text) as whole words, ignoring case,
+/// and those whose domain area is `feature_area`
+pub fn terms_in(entries: &[GlossaryEntry], texts: &[(String, String)], feature_area: &str) -> Vec<TermUse> {
+    let texts: Vec<(&String, Vec<String>)> = texts.iter().map(|(id, text)| (id, words(text))).collect();
+    let area = words(feature_area);
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let found_in: Vec<String> = texts
+                .iter()
+                .filter(|(_, text_words)| entry.members().any(|member| mentions(text_words, member)))
+                .map(|(id, _)| (*id).clone())
+                .collect();
+            let in_area = entry.domain_area.as_deref().is_some_and(|domain| words(domain) == area);
+            (in_area || !found_in.is_empty()).then(|| TermUse {
+                term: entry.term.clone(),
+                synonyms: entry.synonyms.clone(),
+                definition: entry.definition.clone(),
+                domain_area: entry.domain_area.clone(),
+                found_in,
+            })
+        })
+        .collect()
+}
+
+pub struct GlossaryServiceImpl<R: GlossaryRepository> {
+    repository: R,
+}
+
+impl<R: GlossaryRepository> GlossaryServiceImpl<R> {
+    pub fn new(repository: R) -> Self {
+        Self { repository }
+    }
+
+    fn contains(entry: &GlossaryEntry, word: &str) -> bool {
+        entry.members().any(|member| member.eq_ignore_ascii_case(word))
+    }
+}
+
+#[async_trait]
+impl<R: GlossaryRepository> GlossaryService for GlossaryServiceImpl<R> {
+    async fn add_synonyms(&self, project_id: &str, term: &str, synonyms: &[String]) -> Result<GlossaryEntry, McpError> {
+        let term = term.trim();
+        if term.is_empty() {
+            return Err(McpError::invalid_params("Glossary term must not be empty", None));
+        }
+        let mut additions: Vec<String> = Vec::new();
+        for synonym in synonyms.iter().map(|s| s.trim()).filter(|s| !s.is_empty()) {
+            if !synonym.eq_ignore_ascii_case(term) && !additions.iter().any(|a| a.eq_ignore_ascii_case(synonym)) {
+                additions.push(synonym.to_string());
+            }
+        }
+        if additions.is_empty() {
+            return Err(McpError::invalid_params("Provide at least one synonym other than the term", None));
+        }
+
+        let entries = self.repository.find_by_project_id(project_id).await?;
+        let groups: Vec<&GlossaryEntry> = std::iter::once(term)
+            .chain(additions.iter().map(String::as_str))
+            .filter_map(|word| entries.iter().find(|entry| Self::contains(entry, word)))
+            .collect();
+        let now = chrono::Utc::now().to_rfc3339();
+        // Groups stay disjoint so every word expands to exactly one set of synonyms
+        if let (Some(group), Some(other)) = (groups.first(), groups.iter().find(|other| other.id != groups[0].id)) {
+            return Err(McpError::invalid_params(
+                format!(
+                    "\"{}\" and \"{}\" are separate glossary groups; remove one before joining them",
+                    group.term, other.term
+                ),
+                None,
+            ));
+        }
+        match groups.first() {
+            Some(group) => {
+                let mut entry = (*group).clone();
+                for word in std::iter::once(term).chain(additions.iter().map(String::as_str)) {
+                    if !Self::contains(&entry, word) {
+                        entry.synonyms.push(word.to_string());
+                    }
+                }
+                entry.updated_at = Some(now);
+                self.repository.update(&entry).await
+            }
+            None => {
+                let entry = GlossaryEntry {
+                    id: Uuid::new_v4().to_string(),
+                    project_id: project_id.to_string(),
+                    term: term.to_string(),
+                    synonyms: additions,
+                    definition: None,
+                    domain_area: None,
+                    created_at: Some(now.clone()),
+                    updated_at: Some(now),
+                };
+                self.repository.create(&entry).await
+            }
+        }
+    }
+
+    async fn remove_synonyms(
+        &self,
+        project_id: &str,
+        term: &str,
+        synonyms: &[String],
+    ) -> Result<Option<GlossaryEntry>, McpError> {
+        let entries = self.repository.find_by_project_id(project_id).await?;
+        let mut entry = entries
+            .into_iter()
+            .find(|entry| Self::contains(entry, term.trim()))
+            .ok_or_else(|| McpError::invalid_params(format!("\"{term}\" is not in the glossary"), None))?;
+
+        if !synonyms.is_empty() {
+            entry
+                .synonyms
+                .retain(|member| !synonyms.iter().any(|s| s.trim().eq_ignore_ascii_case(member)));
+            if synonyms.iter().any(|s| s.trim().eq_ignore_ascii_case(&entry.term)) && !entry.synonyms.is_empty() {
+                entry.term = entry.synonyms.remove(0);
+            }
+        }
+        // A defined term stays in the glossary without synonyms
+        if synonyms.is_empty() || (entry.synonyms.is_empty() && entry.definition.is_none()) {
+            self.repository.delete(&entry.id).await?;
+            return Ok(None);
+        }
+        entry.updated_at = Some(chrono::Utc::now().to_rfc3339());
+        self.repository.update(&entry).await.map(Some)
+    }
+
+    async fn list_entries(&self, project_id: &str) -> Result<Vec<GlossaryEntry>, McpError> {
+        self.repository.find_by_project_id(project_id).await
+    }
+
+    async fn expand(&self, project_ids: &[String], query: &str) -> Result<Vec<QueryExpansion>, McpError> {
+        let mut entries = Vec::new();
+        for project_id in project_ids {
+            entries.extend(self.repository.find_by_project_id(project_id).await?);
+        }
+        Ok(expand_query(&entries, query))
+    }
+
+    async fn get_entry(&self, id: &str) -> Result<Option<GlossaryEntry>, McpError> {
+        self.repository.find_by_id(id).await
+    }
+
+    async fn save_entry(&self, mut entry: GlossaryEntry) -> Result<GlossaryEntry, McpError> {
+        entry.term = entry.term.trim().to_string();
+        if entry.term.is_empty() {
+            return Err(McpError::invalid_params("Glossary term must not be empty", None));
+        }
+        let mut synonyms: Vec<String> = Vec::new();
+        for synonym in entry.synonyms.iter().map(|s| s.trim()).filter(|s| !s.is_empty()) {
+            if !synonym.eq_ignore_ascii_case(&entry.term) && !synonyms.iter().any(|s| s.eq_ignore_ascii_case(synonym)) {
+                synonyms.push(synonym.to_string());
+            }
+        }
+        entry.synonyms = synonyms;
+
+        let entries = self.repository.find_by_project_id(&entry.project_id).await?;
+        for other in entries.iter().filter(|other| other.id != entry.id) {
+            if let Some(word) = entry.members().find(|word| Self::contains(other, word)) {
+                return Err(McpError::invalid_params(
+                    format!("\"{word}\" already belongs to the glossary term \"{}\"", other.term),
+                    None,
+                ));
+            }
+        }
+
+        entry.updated_at = Some(chrono::Utc::now().to_rfc3339());
+        if entries.iter().any(|existing| existing.id == entry.id) {
+            self.repository.update(&entry).await
+        } else {
+            entry.created_at = entry.updated_at.clone();
+            self.repository.create(&entry).await
+        }
+    }
+
+    async fn delete_entry(&self, id: &str) -> Result<bool, McpError> {
+        self.repository.delete(id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::SqliteGlossaryRepository;
+    use std::sync::Arc;
+
+    fn service() -> GlossaryServiceImpl<SqliteGlossaryRepository> {
+        let conn = crate::db::init::init_db(":memory:").unwrap();
+        conn.execute("INSERT INTO projects (id, name) VALUES ('p1', 'Payments')", [])
+            .unwrap();
+        GlossaryServiceImpl::new(SqliteGlossaryRepository::new(Arc::new(std::sync::Mutex::new(conn))))
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_glossary_groups_merge_and_shrink() {
+        let service = service();
+        service.add_synonyms("p1", "auth", &strings(&["authentication"])).await.unwrap();
+        // Adding through an existing synonym extends the same group
+        let entry = service
+            .add_synonyms("p1", "Authentication", &strings(&["login", "AUTH"]))
+            .await
+            .unwrap();
+        assert_eq!(entry.term, "auth");
+        assert_eq!(entry.synonyms, strings(&["authentication", "login"]));
+
+        service.add_synonyms("p1", "ADR", &strings(&["architectural decision"])).await.unwrap();
+        assert!(service.add_synonyms("p1", "login", &strings(&["adr"])).await.is_err());
+        assert!(service.add_synonyms("p1", "sso", &[]).await.is_err());
+
+        let entry = service.remove_synonyms("p1", "login", &strings(&["auth"])).await.unwrap().unwrap();
+        assert_eq!(entry.term, "authentication");
+        assert_eq!(entry.synonyms, strings(&["login"]));
+        assert!(service.remove_synonyms("p1", "login", &strings(&["login"])).await.unwrap().is_none());
+        assert_eq!(service.list_entries("p1").await.unwrap().len(), 1);
+        assert!(service.remove_synonyms("p1", "unknown", &[]).await.is_err());
+
+        // Defined terms keep their definition and stay without synonyms
+        let adr = service.list_entries("p1").await.unwrap().remove(0);
+        let defined = service
+            .save_entry(GlossaryEntry {
+                definition: Some("A recorded architecture choice".to_string()),
+                domain_area: Some("architecture".to_string()),
+                ..adr.clone()
+            })
+            .await
+            .unwrap();
+        assert_eq!(defined.created_at, adr.created_at);
+        assert!(service.remove_synonyms("p1", "ADR", &strings(&["architectural decision"])).await.unwrap().is_some());
+        let ledger = GlossaryEntry {
+            id: "ledger".to_string(),
+            term: "Ledger".to_string(),
+            synonyms: strings(&["adr"]),
+            ..defined
+        };
+        assert!(service.save_entry(ledger).await.is_err());
+    }
+
+    #[test]
+    fn test_expand_query_matches_whole_words_and_phrases() {
+        let entry = |term: &str, synonyms: &[&str]| GlossaryEntry {
+            id: term.to_string(),
+            project_id: "p1".to_string(),
+            term: term.to_string(),
+            synonyms: strings(synonyms),
+            definition: None,
+            domain_area: None,
+            created_at: None,
+            updated_at: None,
+        };
+        let entries = [entry("auth", &["authentication"]), entry("ADR", &["architectural decision"])];
+
+        let expansions = expand_query(&entries, "Architectural decisions about auth");
+        assert_eq!(
+            expansions,
+            vec![QueryExpansion {
+                matched: "auth".to_string(),
+                synonyms: strings(&["authentication"]),
+            }]
+        );
+        let expansions = expand_query(&entries, "latest architectural decision on authorization");
+        assert_eq!(expansions.len(), 1);
+        assert_eq!(expansions[0].synonyms, strings(&["ADR"]));
+        assert!(expand_query(&entries, "oauthentication flow").is_empty());
+
+        let mut adr = entry("ADR", &["architectural decision"]);
+        adr.domain_area = Some("Architecture".to_string());
+        let entries = [entry("auth", &["authentication"]), adr];
+        let texts = [
+            ("r1".to_string(), "Every authentication attempt is logged".to_string()),
+            ("r2".to_string(), "Tokens expire after an hour".to_string()),
+        ];
+        let uses = terms_in(&entries, &texts, "architecture");
+        assert_eq!(uses.len(), 2);
+        assert_eq!(uses[0].found_in, vec!["r1"]);
+        assert!(uses[1].found_in.is_empty());
+        assert_eq!(terms_in(&entries, &texts, "payments").len(), 1);
+    }
+}
@@ -1,18 +1,73 @@
+use crate::config::SearchSettings;
 use crate::models::context::{
     ArchitecturalDecision, BusinessRule, PerformanceRequirement,
 };
 use crate::models::enhanced_context::{EnhancedContextItem, ContextType};
 use crate::models::embedding::VectorSearchQuery;
+use crate::services::analytics_service::AnalyticsService;
 use crate::services::context_query_service::{ContextQueryService, ContextQueryResult};
+use crate::services::glossary_service::{GlossaryService, QueryExpansion};
+use crate::services::reranker::Reranker;
+use crate::services::search_feedback::{click_popularity, popularity_boost, CLICK_HISTORY_HALF_LIVES};
 use crate::services::semantic_search_service::{
     SemanticSearchService, EnhancedSearchResult, SemanticSearchError,
 };
 use async_trait::async_trait;
+use chrono::Utc;
+use parking_lot::RwLock;
 use rmcp::model::ErrorData as McpError;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
 use std::sync::Arc;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
+
+/// How many more vector hits to fetch when facet filters may discard some
+const FILTER_OVERFETCH: usize = 5;
+
+/// Credit for a query term that only matched a word approximately
+const FUZZY_MATCH_WEIGHT: f32 = 0.8;
+
+/// Words too common to count as keyword matches
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "for", "how", "in", "is", "of", "on", "or", "the", "to", "what", "with",
+];
+
+/// Alternative spellings of one query term, each a list of words that must all match
+type TermGroup = Vec<Vec<String>>;
+
+/// Entity fields exposed as search facets, besides `entity_type`
+pub const FACET_FIELDS: &[&str] = &["domain_area", "architecture_layer", "status", "tags"];
+
+/// Facet name to accepted values; a result must match one value of every listed facet
+pub type FacetFilters = BTreeMap<String, Vec<String>>;
+
+/// Facet values of an entity as returned by the CRUD tools, lowercased.
+/// Array fields, and strings holding a JSON array (as `tags` often do), give several values.
+pub fn entity_facets(entity: &serde_json::Value) -> BTreeMap<String, Vec<String>> {
+    let mut facets = BTreeMap::new();
+    for field in FACET_FIELDS {
+        let values: Vec<String> = match entity.get(*field) {
+            Some(serde_json::Value::String(text)) => match serde_json::from_str::<Vec<String>>(text) {
+                Ok(items) => items,
+                Err(_) => vec![text.clone()],
+            },
+            Some(serde_json::Value::Array(items)) => {
+                items.iter().filter_map(|item| item.as_str()).map(str::to_string).collect()
+            }
+            _ => Vec::new(),
+        };
+        let values: Vec<String> = values
+            .iter()
+            .map(|value| value.trim().to_lowercase())
+            .filter(|value| !value.is_empty())
+            .collect();
+        if !values.is_empty() {
+            facets.insert(field.to_string(), values);
+        }
+    }
+    facets
+}
 
 /// Hybrid search result combining traditional and semantic search
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +77,86 @@ pub struct HybridSearchResult {
     pub combined_score: f64,
     pub search_strategy: SearchStrategy,
     pub total_results: usize,
+    /// Semantic and keyword hits fused into one ranking, best first
+    #[serde(default)]
+    pub ranked_results: Vec<RankedSearchResult>,
+    /// Matching results per facet value, counted after filtering and before `max_results`
+    #[serde(default)]
+    pub facet_counts: BTreeMap<String, BTreeMap<String, usize>>,
+    /// Glossary synonyms searched alongside the query's terms
+    #[serde(default)]
+    pub query_expansions: Vec<QueryExpansion>,
+}
+
+/// One entity in the fused ranking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankedSearchResult {
+    pub entity_id: String,
+    pub entity_type: String,
+    pub title: Option<String>,
+    pub score: f32,
+    pub breakdown: ScoreBreakdown,
+    /// Project the entity belongs to
+    #[serde(default)]
+    pub project_id: Option<String>,
+    /// `entity_type` and the entity's `FACET_FIELDS` values
+    #[serde(default)]
+    pub facets: BTreeMap<String, Vec<String>>,
+    /// Text the reranker reads; not returned to clients
+    #[serde(skip)]
+    pub content: String,
+}
+
+/// How a result's score was put together, for tuning `[search]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    pub fusion: FusionMethod,
+    /// Cosine similarity, if the entity was a vector hit
+    pub vector_score: Option<f32>,
+    pub vector_rank: Option<usize>,
+    pub vector_contribution: f32,
+    /// Fraction of query terms found in the entity, if it matched any
+    pub keyword_score: Option<f32>,
+    pub keyword_rank: Option<usize>,
+    pub keyword_contribution: f32,
+    /// Multiplier from `[search.boosts]`
+    pub entity_type_boost: f32,
+    /// Multiplier from how often agents opened the entity after earlier searches
+    #[serde(default = "neutral_boost")]
+    pub popularity_boost: f32,
+    /// Reranker relevance, if the result was within `search.rerank.top_k`; reranked
+    /// results are ordered by this instead of `score`
+    #[serde(default)]
+    pub rerank_score: Option<f32>,
+}
+
+fn neutral_boost() -> f32 {
+    1.0
+}
+
+/// How the vector and keyword legs are combined
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FusionMethod {
+    /// `vector_weight * similarity + keyword_weight * keyword_score`
+    #[default]
+    WeightedSum,
+    /// Reciprocal rank fusion: `weight / (rrf_k + rank)` per leg
+    Rrf,
+}
+
+impl FromStr for FusionMethod {
+    type Err = HybridSearchError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "weighted_sum" => Ok(Self::WeightedSum),
+            "rrf" => Ok(Self::Rrf),
+            other => Err(HybridSearchError::ConfigurationError {
+                message: format!("Unknown search.fusion \"{other}\"; expected \"weighted_sum\" or \"rrf\""),
+            }),
+        }
+    }
 }
 
 /// Search strategy used for hybrid search
@@ -42,6 +177,17 @@ pub struct HybridSearchConfig {
     pub max_semantic_results: usize,
     pub similarity_threshold: f32,
     pub enable_result_fusion: bool,
+    pub fusion_method: FusionMethod,
+    pub rrf_k: f32,
+    /// Score multipliers keyed by entity type; missing types use 1.0
+    pub entity_type_boosts: HashMap<String, f32>,
+    /// Number of top fused results passed to the reranker, if one is set
+    pub rerank_top_k: usize,
+    /// Let keyword terms match words a typo or two away
+    pub fuzzy_matching: bool,
+    /// Largest extra multiplier for often-clicked results; 0 disables click feedback
+    pub click_boost: f32,
+    pub click_half_life_days: f32,
 }
 
 impl Default for HybridSearchConfig {
@@ -53,10 +199,54 @@ impl Default for HybridSearchConfig {
             max_semantic_results: 20,
             similarity_threshold: 0.6,
             enable_result_fusion: true,
+            fusion_method: FusionMethod::WeightedSum,
+            rrf_k: 60.0,
+            entity_type_boosts: HashMap::new(),
+            rerank_top_k: 10,
+            fuzzy_matching: true,
+            click_boost: 0.2,
+            click_half_life_days: 14.0,
         }
     }
 }
 
+impl HybridSearchConfig {
+    /// Build the ranking configuration from the `[search]` section
+    pub fn from_settings(settings: &SearchSettings) -> Result<Self, HybridSearchError> {
+        if settings.vector_weight < 0.0 || settings.keyword_weight < 0.0 {
+            return Err(HybridSearchError::ConfigurationError {
+                message: "search.vector_weight and search.keyword_weight must not be negative".to_string(),
+            });
+        }
+        if let Some((entity_type, _)) = settings.boosts.iter().find(|(_, boost)| **boost < 0.0) {
+            return Err(HybridSearchError::ConfigurationError {
+                message: format!("search.boosts.{entity_type} must not be negative"),
+            });
+        }
+        if settings.click_boost < 0.0 || settings.click_half_life_days <= 0.0 {
+            return Err(HybridSearchError::ConfigurationError {
+                message: "search.click_boost must not be negative and search.click_half_life_days must be positive".to_string(),
+            });
+        }
+
+        Ok(Self {
+            semantic_weight: settings.vector_weight,
+            traditional_weight: settings.keyword_weight,
+            enable_intent_routing: settings.intent_routing,
+            max_semantic_results: settings.max_results,
+            similarity_threshold: settings.similarity_threshold,
+            enable_result_fusion: true,
+            fusion_method: settings.fusion.parse()?,
+            rrf_k: settings.rrf_k.max(0.0),
+            entity_type_boosts: settings.boosts.clone().into_iter().collect(),
+            rerank_top_k: settings.rerank.top_k,
+            fuzzy_matching: settings.fuzzy_matching,
+            click_boost: settings.click_boost,
+            click_half_life_days: settings.click_half_life_days,
+        })
+    }
+}
+
 /// Error types for hybrid search operations
 #[derive(Debug, thiserror::Error)]
 pub enum HybridSearchError {
@@ -97,6 +287,25 @@ pub trait HybridSearchService: Send + Sync {
         task_type: Option<&str>,
         components: &[String],
     ) -> Result<HybridSearchResult, HybridSearchError>;
+
+    /// Hybrid search over the whole project, keeping results that match `filters`
+    async fn faceted_search(
+        &self,
+        project_id: &str,
+        query_text: &str,
+        feature_area: Option<&str>,
+        filters: &FacetFilters,
+    ) -> Result<HybridSearchResult, HybridSearchError>;
+
+    /// Faceted search over several projects at once, ranked together; each result
+    /// carries its `project_id`
+    async fn cross_project_search(
+        &self,
+        project_ids: &[String],
+        query_text: &str,
+        feature_area: Option<&str>,
+        filters: &FacetFilters,
+    ) -> Result<HybridSearchResult, HybridSearchError>;
     
     /// Perform semantic-only search
     async fn semantic_search(
@@ -125,7 +334,10 @@ pub trait HybridSearchService: Send + Sync {
 pub struct HybridSearchServiceImpl {
     semantic_search_service: Arc<dyn SemanticSearchService>,
     context_query_service: Arc<dyn ContextQueryService>,
-    config: HybridSearchConfig,
+    config: RwLock<HybridSearchConfig>,
+    reranker: RwLock<Option<Arc<dyn Reranker>>>,
+    click_history: Option<Arc<dyn AnalyticsService>>,
+    glossary: Option<Arc<dyn GlossaryService>>,
 }
 
 impl HybridSearchServiceImpl {
@@ -137,13 +349,38 @@ impl HybridSearchServiceImpl {
         Self {
             semantic_search_service,
             context_query_service,
-            config,
+            config: RwLock::new(config),
+            reranker: RwLock::new(None),
+            click_history: None,
+            glossary: None,
         }
     }
+
+    /// Expand queries with the synonyms in the searched projects' glossaries
+    pub fn with_glossary(mut self, glossary: Arc<dyn GlossaryService>) -> Self {
+        self.glossary = Some(glossary);
+        self
+    }
+
+    /// Boost results by the search clicks recorded in `analytics`
+    pub fn with_click_history(mut self, analytics: Arc<dyn AnalyticsService>) -> Self {
+        self.click_history = Some(analytics);
+        self
+    }
+
+    /// Replace the ranking configuration, e.g. after `[search]` is reloaded
+    pub fn set_config(&self, config: HybridSearchConfig) {
+        *self.config.write() = config;
+    }
+
+    /// Rerank the top fused results with `reranker`, or stop reranking with `None`
+    pub fn set_reranker(&self, reranker: Option<Arc<dyn Reranker>>) {
+        *self.reranker.write() = reranker;
+    }
     
     /// Determine search strategy based on query intent and configuration
     fn determine_search_strategy(&self, query_text: &str) -> SearchStrategy {
-        if !self.config.enable_intent_routing {
+        if !self.config.read().enable_intent_routing {
             return SearchStrategy::Hybrid;
         }
         
@@ -178,14 +415,19 @@ impl HybridSearchServiceImpl {
         semantic_results: Vec<EnhancedSearchResult>,
         traditional_results: ContextQueryResult,
         strategy: SearchStrategy,
+        ranked_results: Vec<RankedSearchResult>,
     ) -> HybridSearchResult {
-        if !self.config.enable_result_fusion {
+        let config = self.config.read().clone();
+        if !config.enable_result_fusion {
             return HybridSearchResult {
                 semantic_results,
                 traditional_results,
                 combined_score: 0.0,
                 search_strategy: strategy,
                 total_results: 0,
+                ranked_results,
+                facet_counts: BTreeMap::new(),
+                query_expansions: Vec::new(),
             };
         }
         
@@ -197,8 +439,8 @@ impl HybridSearchServiceImpl {
             SearchStrategy::SemanticOnly => semantic_score,
             SearchStrategy::TraditionalOnly => traditional_score,
             SearchStrategy::Hybrid | SearchStrategy::IntentBased => {
-                semantic_score * self.config.semantic_weight as f64 +
-                traditional_score * self.config.traditional_weight as f64
+                semantic_score * config.semantic_weight as f64 +
+                traditional_score * config.traditional_weight as f64
             }
         };
         
@@ -215,6 +457,9 @@ impl HybridSearchServiceImpl {
             combined_score,
             search_strategy: strategy,
             total_results,
+            ranked_results,
+            facet_counts: BTreeMap::new(),
+            query_expansions: Vec::new(),
         }
     }
     
@@ -248,6 +493,442 @@ impl HybridSearchServiceImpl {
         }
     }
     
+    /// Lowercased query words worth matching
+    fn query_terms(query_text: &str) -> Vec<String> {
+        let mut seen = HashSet::new();
+        query_text
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| word.len() > 1 && !STOP_WORDS.contains(word))
+            .filter(|word| seen.insert(word.to_string()))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Query terms to match, one group per term. A glossary member found in the query
+    /// becomes a single group that its synonyms can satisfy as well.
+    fn term_groups(query_text: &str, expansions: &[QueryExpansion]) -> Vec<TermGroup> {
+        let mut groups = Vec::new();
+        let mut covered = HashSet::new();
+        for expansion in expansions {
+            let matched = Self::query_terms(&expansion.matched);
+            if matched.is_empty() {
+                continue;
+            }
+            covered.extend(matched.iter().cloned());
+            let mut alternatives = vec![matched];
+            alternatives.extend(
+                expansion.synonyms.iter().map(|synonym| Self::query_terms(synonym)).filter(|words| !words.is_empty()),
+            );
+            groups.push(alternatives);
+        }
+        for term in Self::query_terms(query_text) {
+            if !covered.contains(&term) {
+                groups.push(vec![vec![term]]);
+            }
+        }
+        groups
+    }
+
+    /// Fraction of term `groups` with an alternative whose words all occur in the item's
+    /// title, description or fields. With `fuzzy`, a word missing verbatim earns
+    /// `FUZZY_MATCH_WEIGHT` if a word is within its typo allowance, so "authetication"
+    /// still finds "authentication".
+    fn keyword_score(groups: &[TermGroup], item: &EnhancedContextItem, fuzzy: bool) -> f32 {
+        if groups.is_empty() {
+            return 0.0;
+        }
+        let text = format!(
+            "{} {} {}",
+            item.content.title, item.content.description, item.content.data
+        )
+        .to_lowercase();
+        let words: HashSet<&str> = text.split(|c: char| !c.is_alphanumeric()).collect();
+        let term_score = |term: &String| {
+            if words.contains(term.as_str()) {
+                1.0
+            } else if fuzzy && words.iter().any(|word| Self::within_typos(term, word)) {
+                FUZZY_MATCH_WEIGHT
+            } else {
+                0.0
+            }
+        };
+        let matched: f32 = groups
+            .iter()
+            .map(|alternatives| {
+                alternatives
+                    .iter()
+                    .map(|terms| terms.iter().map(term_score).fold(1.0, f32::min))
+                    .fold(0.0, f32::max)
+            })
+            .sum();
+        matched / groups.len() as f32
+    }
+
+    /// Whether `word` is within `term`'s edit-distance allowance: none for short
+    /// terms, where one edit changes the meaning, one from 4 letters, two from 8
+    fn within_typos(term: &str, word: &str) -> bool {
+        let term: Vec<char> = term.chars().collect();
+        let word: Vec<char> = word.chars().collect();
+        let allowed = match term.len() {
+            0..=3 => return false,
+            4..=7 => 1,
+            _ => 2,
+        };
+        if term.len().abs_diff(word.len()) > allowed {
+            return false;
+        }
+
+        // Levenshtein distance, one row at a time
+        let mut previous: Vec<usize> = (0..=word.len()).collect();
+        for (i, t) in term.iter().enumerate() {
+            let mut current = vec![i + 1; word.len() + 1];
+            for (j, w) in word.iter().enumerate() {
+                let substitution = previous[j] + usize::from(t != w);
+                current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+            }
+            if current.iter().min().is_some_and(|&best| best > allowed) {
+                return false;
+            }
+            previous = current;
+        }
+        previous[word.len()] <= allowed
+    }
+
+    /// Fuse vector hits and keyword matches into one list, scored per `config` and
+    /// boosted by each entity's decayed click count in `popularity`. Keyword matching
+    /// accepts the glossary synonyms in `expansions`.
+    fn rank_results(
+        config: &HybridSearchConfig,
+        query_text: &str,
+        expansions: &[QueryExpansion],
+        semantic_results: &[EnhancedSearchResult],
+        keyword_candidates: &[EnhancedContextItem],
+        popularity: &HashMap<String, f32>,
+    ) -> Vec<RankedSearchResult> {
+        let groups = Self::term_groups(query_text, expansions);
+        let mut keyword_hits: Vec<(&EnhancedContextItem, f32)> = keyword_candidates
+            .iter()
+            .map(|item| (item, Self::keyword_score(&groups, item, config.fuzzy_matching)))
+            .filter(|(_, score)| *score > 0.0)
+            .collect();
+        keyword_hits.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let mut results: Vec<RankedSearchResult> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        let empty_breakdown = ScoreBreakdown {
+            fusion: config.fusion_method,
+            vector_score: None,
+            vector_rank: None,
+            vector_contribution: 0.0,
+            keyword_score: None,
+            keyword_rank: None,
+            keyword_contribution: 0.0,
+            entity_type_boost: 1.0,
+            popularity_boost: 1.0,
+            rerank_score: None,
+        };
+
+        let mut vector_hits: Vec<&EnhancedSearchResult> = semantic_results.iter().collect();
+        vector_hits.sort_by(|a, b| b.vector_result.similarity_score.total_cmp(&a.vector_result.similarity_score));
+        for (index, hit) in vector_hits.into_iter().enumerate() {
+            let vector = &hit.vector_result;
+            if positions.contains_key(&vector.context_id) {
+                continue;
+            }
+            positions.insert(vector.context_id.clone(), results.len());
+            let content = match &hit.context_item {
+                Some(item) => format!("{}\n{}", item.content.title, item.content.description),
+                None if vector.metadata.content_preview != "Preview not available" => {
+                    vector.metadata.content_preview.clone()
+                }
+                None => String::new(),
+            };
+            results.push(RankedSearchResult {
+                entity_id: vector.context_id.clone(),
+                entity_type: vector.metadata.content_type.clone(),
+                title: hit.context_item.as_ref().map(|item| item.content.title.clone()),
+                score: 0.0,
+                breakdown: ScoreBreakdown {
+                    vector_score: Some(vector.similarity_score),
+                    vector_rank: Some(index + 1),
+                    ..empty_breakdown.clone()
+                },
+                project_id: vector.metadata.project_id.clone(),
+                facets: vector.metadata.facets.clone(),
+                content,
+            });
+        }
+
+        for (index, (item, score)) in keyword_hits.into_iter().enumerate() {
+            let position = *positions.entry(item.id.clone()).or_insert_with(|| {
+                results.push(RankedSearchResult {
+                    entity_id: item.id.clone(),
+                    entity_type: item.content.content_type.as_str().to_string(),
+                    title: None,
+                    score: 0.0,
+                    breakdown: empty_breakdown.clone(),
+                    project_id: None,
+                    facets: BTreeMap::new(),
+                    content: String::new(),
+                });
+                results.len() - 1
+            });
+            let result = &mut results[position];
+            result.title.get_or_insert_with(|| item.content.title.clone());
+            result.project_id.get_or_insert_with(|| item.project_id.clone());
+            result.content = format!("{}\n{}", item.content.title, item.content.description);
+            result.facets = entity_facets(&item.content.data);
+            result.breakdown.keyword_score = Some(score);
+            result.breakdown.keyword_rank = Some(index + 1);
+        }
+
+        for result in &mut results {
+            let breakdown = &mut result.breakdown;
+            let (vector, keyword) = match config.fusion_method {
+                FusionMethod::WeightedSum => (
+                    breakdown.vector_score.unwrap_or(0.0),
+                    breakdown.keyword_score.unwrap_or(0.0),
+                ),
+                FusionMethod::Rrf => (
+                    breakdown.vector_rank.map_or(0.0, |rank| 1.0 / (config.rrf_k + rank as f32)),
+                    breakdown.keyword_rank.map_or(0.0, |rank| 1.0 / (config.rrf_k + rank as f32)),
+                ),
+            };
+            breakdown.vector_contribution = vector * config.semantic_weight;
+            breakdown.keyword_contribution = keyword * config.traditional_weight;
+            breakdown.entity_type_boost = config
+                .entity_type_boosts
+                .get(&result.entity_type)
+                .copied()
+                .unwrap_or(1.0);
+            breakdown.popularity_boost = popularity
+                .get(&result.entity_id)
+                .map_or(1.0, |clicks| popularity_boost(*clicks, config.click_boost));
+            result.score = (breakdown.vector_contribution + breakdown.keyword_contribution)
+                * breakdown.entity_type_boost
+                * breakdown.popularity_boost;
+            result.facets.insert("entity_type".to_string(), vec![result.entity_type.clone()]);
+        }
+
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results
+    }
+
+    /// Whether `facets` has one of the accepted values for every filtered facet
+    fn matches_filters(facets: &BTreeMap<String, Vec<String>>, filters: &FacetFilters) -> bool {
+        filters.iter().all(|(facet, accepted)| {
+            accepted.is_empty()
+                || facets.get(facet).is_some_and(|values| {
+                    values.iter().any(|value| accepted.iter().any(|a| a.eq_ignore_ascii_case(value)))
+                })
+        })
+    }
+
+    /// Results per value of each facet
+    fn count_facets(results: &[RankedSearchResult]) -> BTreeMap<String, BTreeMap<String, usize>> {
+        let mut counts: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+        for result in results {
+            for (facet, values) in &result.facets {
+                let facet_counts = counts.entry(facet.clone()).or_default();
+                for value in values.iter().collect::<HashSet<_>>() {
+                    *facet_counts.entry(value.clone()).or_default() += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Reorder the first `rerank_top_k` results by reranker relevance; on failure the
+    /// fused order is kept, since a slow or unavailable reranker shouldn't fail search
+    async fn rerank(
+        reranker: &dyn Reranker,
+        config: &HybridSearchConfig,
+        query_text: &str,
+        results: &mut [RankedSearchResult],
+    ) {
+        let window = config.rerank_top_k.min(results.len());
+        if window < 2 {
+            return;
+        }
+        let documents: Vec<String> = results[..window]
+            .iter()
+            .map(|result| match (&result.title, result.content.is_empty()) {
+                (_, false) => result.content.clone(),
+                (Some(title), true) => title.clone(),
+                (None, true) => result.entity_id.clone(),
+            })
+            .collect();
+
+        match reranker.rerank(query_text, &documents).await {
+            Ok(scores) => {
+                for (result, score) in results[..window].iter_mut().zip(scores) {
+                    result.breakdown.rerank_score = Some(score);
+                }
+                results[..window].sort_by(|a, b| {
+                    let score = |r: &RankedSearchResult| r.breakdown.rerank_score.unwrap_or(f32::NEG_INFINITY);
+                    score(b).total_cmp(&score(a))
+                });
+                debug!("Reranked top {} results with {}", window, reranker.name());
+            }
+            Err(e) => warn!("Reranking with {} failed, keeping fused order: {}", reranker.name(), e),
+        }
+    }
+
+    /// Decayed search clicks per entity of `project_ids`; empty when click feedback is off
+    /// or the history can't be read, since ranking works without it
+    async fn click_popularity(&self, config: &HybridSearchConfig, project_ids: &[String]) -> HashMap<String, f32> {
+        let Some(analytics) = self.click_history.as_ref().filter(|_| config.click_boost > 0.0) else {
+            return HashMap::new();
+        };
+        let now = Utc::now();
+        let window = chrono::Duration::seconds(
+            (config.click_half_life_days * CLICK_HISTORY_HALF_LIVES * 86_400.0) as i64,
+        );
+        match analytics.get_search_clicks(now - window).await {
+            Ok(mut clicks) => {
+                clicks.retain(|click| click.project_id.as_ref().is_none_or(|id| project_ids.contains(id)));
+                click_popularity(&clicks, now, config.click_half_life_days)
+            }
+            Err(e) => {
+                warn!("Ranking without click feedback: {}", e);
+                HashMap::new()
+            }
+        }
+    }
+
+    /// Glossary expansions of `query_text` in `project_ids`; none when the glossary can't be
+    /// read, since the query still works as written
+    async fn query_expansions(&self, project_ids: &[String], query_text: &str) -> Vec<QueryExpansion> {
+        let Some(glossary) = &self.glossary else {
+            return Vec::new();
+        };
+        glossary.expand(project_ids, query_text).await.unwrap_or_else(|e| {
+            warn!("Searching without glossary expansion: {}", e);
+            Vec::new()
+        })
+    }
+
+    /// Run both legs over `project_ids`, fuse, apply facet `filters`, count facets and rerank
+    async fn search(
+        &self,
+        project_ids: &[String],
+        query_text: &str,
+        feature_area: Option<&str>,
+        task_type: Option<&str>,
+        components: &[String],
+        filters: &FacetFilters,
+    ) -> Result<HybridSearchResult, HybridSearchError> {
+        info!("Performing hybrid search for query: {}", query_text);
+        
+        let config = self.config.read().clone();
+        let strategy = self.determine_search_strategy(query_text);
+        debug!("Selected search strategy: {:?}", strategy);
+        let expansions = self.query_expansions(project_ids, query_text).await;
+        
+        let mut semantic_results = Vec::new();
+        let mut keyword_candidates = Vec::new();
+        let mut traditional_results = ContextQueryResult {
+            business_rules: Vec::new(),
+            architectural_decisions: Vec::new(),
+            performance_requirements: Vec::new(),
+            security_policies: Vec::new(),
+            project_conventions: Vec::new(),
+            ..Default::default()
+        };
+        
+        match strategy {
+            SearchStrategy::SemanticOnly | SearchStrategy::Hybrid | SearchStrategy::IntentBased => {
+                // Perform semantic search
+                let mut vector_query_text = query_text.to_string();
+                for synonym in expansions.iter().flat_map(|expansion| &expansion.synonyms) {
+                    vector_query_text.push(' ');
+                    vector_query_text.push_str(synonym);
+                }
+                let query = VectorSearchQuery {
+                    query_text: vector_query_text,
+                    similarity_threshold: config.similarity_threshold,
+                    // Filtered-out hits would otherwise leave the page short
+                    max_results: if filters.is_empty() {
+                        config.max_semantic_results
+                    } else {
+                        config.max_semantic_results * FILTER_OVERFETCH
+                    },
+                    // The vector index filters on a single project; wider scopes are
+                    // searched whole and narrowed below
+                    filters: crate::models::embedding::SearchFilters {
+                        project_ids: (project_ids.len() == 1).then(|| project_ids.to_vec()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                };
+                
+                semantic_results = self.semantic_search_service.search(&query).await?;
+                if project_ids.len() != 1 {
+                    semantic_results.retain(|result| {
+                        result.vector_result.metadata.project_id.as_ref().is_some_and(|id| project_ids.contains(id))
+                    });
+                }
+                debug!("Semantic search returned {} results", semantic_results.len());
+            }
+            _ => {}
+        }
+        
+        match strategy {
+            SearchStrategy::TraditionalOnly | SearchStrategy::Hybrid => {
+                // Narrow to the feature area when given, otherwise match against the whole project
+                for project_id in project_ids {
+                    let project_results = match feature_area {
+                        Some(feature_area) => {
+                            self.context_query_service
+                                .query_context(project_id, feature_area, task_type.unwrap_or("general"), components, None)
+                                .await?
+                        }
+                        None => self.context_query_service.project_context(project_id).await?,
+                    };
+                    keyword_candidates.extend(self.convert_traditional_to_enhanced(&project_results, project_id));
+                    traditional_results.business_rules.extend(project_results.business_rules);
+                    traditional_results.architectural_decisions.extend(project_results.architectural_decisions);
+                    traditional_results.performance_requirements.extend(project_results.performance_requirements);
+                    traditional_results.security_policies.extend(project_results.security_policies);
+                    traditional_results.project_conventions.extend(project_results.project_conventions);
+                }
+
+                debug!("Traditional search returned {} business rules, {} architectural decisions, {} performance requirements",
+                       traditional_results.business_rules.len(),
+                       traditional_results.architectural_decisions.len(),
+                       traditional_results.performance_requirements.len());
+            }
+            _ => {}
+        }
+        
+        // Fuse results
+        let popularity = self.click_popularity(&config, project_ids).await;
+        let mut ranked_results = Self::rank_results(
+            &config,
+            query_text,
+            &expansions,
+            &semantic_results,
+            &keyword_candidates,
+            &popularity,
+        );
+        ranked_results.retain(|result| Self::matches_filters(&result.facets, filters));
+        let facet_counts = Self::count_facets(&ranked_results);
+        ranked_results.truncate(config.max_semantic_results);
+        let reranker = self.reranker.read().clone();
+        if let Some(reranker) = reranker {
+            Self::rerank(reranker.as_ref(), &config, query_text, &mut ranked_results).await;
+        }
+        let mut hybrid_result = self.fuse_results(semantic_results, traditional_results, strategy, ranked_results);
+        hybrid_result.facet_counts = facet_counts;
+        hybrid_result.query_expansions = expansions;
+        
+        info!("Hybrid search completed: {} total results with combined score {:.3}",
+              hybrid_result.total_results, hybrid_result.combined_score);
+        
+        Ok(hybrid_result)
+    }
+
     /// Convert traditional context results to enhanced context items for unified processing
     fn convert_traditional_to_enhanced(&self, traditional_results: &ContextQueryResult, project_id: &str) -> Vec<EnhancedContextItem> {
         let mut enhanced_items = Vec::new();
@@ -314,66 +995,30 @@ impl HybridSearchService for HybridSearchServiceImpl {
         task_type: Option<&str>,
         components: &[String],
     ) -> Result<HybridSearchResult, HybridSearchError> {
-        info!("Performing hybrid search for query: {}", query_text);
-        
-        let strategy = self.determine_search_strategy(query_text);
-        debug!("Selected search strategy: {:?}", strategy);
-        
-        let mut semantic_results = Vec::new();
-        let mut traditional_results = ContextQueryResult {
-            business_rules: Vec::new(),
-            architectural_decisions: Vec::new(),
-            performance_requirements: Vec::new(),
-            security_policies: Vec::new(),
-            project_conventions: Vec::new(),
-        };
-        
-        match strategy {
-            SearchStrategy::SemanticOnly | SearchStrategy::Hybrid | SearchStrategy::IntentBased => {
-                // Perform semantic search
-                let query = VectorSearchQuery {
-                    query_text: query_text.to_string(),
-                    similarity_threshold: self.config.similarity_threshold,
-                    max_results: self.config.max_semantic_results,
-                    filters: crate::models::embedding::SearchFilters {
-                        project_ids: Some(vec![project_id.to_string()]),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                };
-                
-                semantic_results = self.semantic_search_service.search(&query).await?;
-                debug!("Semantic search returned {} results", semantic_results.len());
-            }
-            _ => {}
-        }
-        
-        match strategy {
-            SearchStrategy::TraditionalOnly | SearchStrategy::Hybrid => {
-                // Perform traditional search if we have the required parameters
-                if let (Some(feature_area), Some(task_type)) = (feature_area, task_type) {
-                    traditional_results = self.context_query_service
-                        .query_context(project_id, feature_area, task_type, components)
-                        .await?;
-                    
-                    debug!("Traditional search returned {} business rules, {} architectural decisions, {} performance requirements",
-                           traditional_results.business_rules.len(),
-                           traditional_results.architectural_decisions.len(),
-                           traditional_results.performance_requirements.len());
-                }
-            }
-            _ => {}
-        }
-        
-        // Fuse results
-        let hybrid_result = self.fuse_results(semantic_results, traditional_results, strategy);
-        
-        info!("Hybrid search completed: {} total results with combined score {:.3}",
-              hybrid_result.total_results, hybrid_result.combined_score);
-        
-        Ok(hybrid_result)
+        self.search(&[project_id.to_string()], query_text, feature_area, task_type, components, &FacetFilters::new())
+            .await
     }
-    
+
+    async fn faceted_search(
+        &self,
+        project_id: &str,
+        query_text: &str,
+        feature_area: Option<&str>,
+        filters: &FacetFilters,
+    ) -> Result<HybridSearchResult, HybridSearchError> {
+        self.search(&[project_id.to_string()], query_text, feature_area, None, &[], filters).await
+    }
+
+    async fn cross_project_search(
+        &self,
+        project_ids: &[String],
+        query_text: &str,
+        feature_area: Option<&str>,
+        filters: &FacetFilters,
+    ) -> Result<HybridSearchResult, HybridSearchError> {
+        self.search(project_ids, query_text, feature_area, None, &[], filters).await
+    }
+
     async fn semantic_search(
         &self,
         query: &VectorSearchQuery,
@@ -392,7 +1037,7 @@ impl HybridSearchService for HybridSearchServiceImpl {
     ) -> Result<ContextQueryResult, HybridSearchError> {
         debug!("Performing traditional-only search");
         let results = self.context_query_service
-            .query_context(project_id, feature_area, task_type, components)
+            .query_context(project_id, feature_area, task_type, components, None)
             .await?;
         Ok(results)
     }
@@ -441,6 +1086,7 @@ mod tests {
     use crate::models::embedding::VectorSearchQuery;
     use crate::services::semantic_search_service::{EnhancedSearchResult, SearchMetadata};
     use crate::models::embedding::{VectorSearchResult, ResultMetadata};
+    use crate::services::embedding_service::EmbeddingError;
     use std::sync::Arc;
 
     
@@ -470,6 +1116,8 @@ mod tests {
                         content_preview: "Test business rule".to_string(),
                         match_explanation: "High similarity match".to_string(),
                         quality_indicators: vec!["High quality".to_string()],
+                        facets: Default::default(),
+                        project_id: Some("p1".to_string()),
                     },
                 },
                 context_item: None,
@@ -529,6 +1177,7 @@ mod tests {
             _feature_area: &str,
             _task_type: &str,
             _components: &[String],
+            _token_budget: Option<usize>,
         ) -> Result<ContextQueryResult, McpError> {
             Ok(ContextQueryResult {
                 business_rules: Vec::new(),
@@ -536,6 +1185,7 @@ mod tests {
                 performance_requirements: Vec::new(),
                 security_policies: Vec::new(),
                 project_conventions: Vec::new(),
+                ..Default::default()
             })
         }
     }
@@ -564,6 +1214,275 @@ mod tests {
         assert!(!result.semantic_results.is_empty());
     }
     
+    #[tokio::test]
+    async fn test_cross_project_search_attributes_projects() {
+        let service = HybridSearchServiceImpl::new(
+            Arc::new(MockSemanticSearchService),
+            Arc::new(MockContextQueryService),
+            HybridSearchConfig::default(),
+        );
+        let projects = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+
+        let result = service
+            .cross_project_search(&projects(&["p1", "p2"]), "authentication security", None, &FacetFilters::new())
+            .await
+            .unwrap();
+        assert_eq!(result.ranked_results[0].project_id.as_deref(), Some("p1"));
+
+        let result = service
+            .cross_project_search(&projects(&["p2", "p3"]), "authentication security", None, &FacetFilters::new())
+            .await
+            .unwrap();
+        assert!(result.ranked_results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rank_results_fusion_and_boosts() {
+        let service = HybridSearchServiceImpl::new(
+            Arc::new(MockSemanticSearchService),
+            Arc::new(MockContextQueryService),
+            HybridSearchConfig::default(),
+        );
+        let semantic = MockSemanticSearchService
+            .search(&VectorSearchQuery::default())
+            .await
+            .unwrap();
+        let keyword_candidates = service.convert_traditional_to_enhanced(
+            &ContextQueryResult {
+                business_rules: vec![BusinessRule {
+                    id: "rule-2".to_string(),
+                    project_id: "p1".to_string(),
+                    rule_name: "Token refresh".to_string(),
+                    description: Some("Refresh tokens rotate on every use".to_string()),
+                    domain_area: None,
+                    implementation_pattern: None,
+                    constraints: None,
+                    examples: None,
+                    created_at: None,
+                }],
+                architectural_decisions: Vec::new(),
+                performance_requirements: Vec::new(),
+                security_policies: Vec::new(),
+                project_conventions: Vec::new(),
+                ..Default::default()
+            },
+            "p1",
+        );
+
+        let weighted = HybridSearchServiceImpl::rank_results(
+            &HybridSearchConfig::default(),
+            "token refresh",
+            &[],
+            &semantic,
+            &keyword_candidates,
+            &HashMap::new(),
+        );
+        assert_eq!(weighted.len(), 2);
+        assert_eq!(weighted[0].entity_id, "test-context-1");
+        assert!((weighted[0].score - 0.7 * 0.85).abs() < 1e-6);
+        assert_eq!(weighted[1].entity_id, "rule-2");
+        assert_eq!(weighted[1].breakdown.keyword_score, Some(1.0));
+        assert!((weighted[1].score - 0.3).abs() < 1e-6);
+
+        let rrf = HybridSearchServiceImpl::rank_results(
+            &HybridSearchConfig {
+                fusion_method: FusionMethod::Rrf,
+                entity_type_boosts: HashMap::from([("business_rule".to_string(), 2.0)]),
+                ..HybridSearchConfig::default()
+            },
+            "token refresh",
+            &[],
+            &semantic,
+            &keyword_candidates,
+            &HashMap::new(),
+        );
+        let vector_hit = rrf.iter().find(|r| r.entity_id == "test-context-1").unwrap();
+        assert_eq!(vector_hit.breakdown.vector_rank, Some(1));
+        assert!((vector_hit.score - 2.0 * 0.7 / 61.0).abs() < 1e-6);
+
+        // Three recent clicks lift the keyword hit (0.3 * (1 + 2 * 3/4)) above the vector hit
+        let clicked = HybridSearchServiceImpl::rank_results(
+            &HybridSearchConfig {
+                click_boost: 2.0,
+                ..HybridSearchConfig::default()
+            },
+            "token refresh",
+            &[],
+            &semantic,
+            &keyword_candidates,
+            &HashMap::from([("rule-2".to_string(), 3.0)]),
+        );
+        assert_eq!(clicked[0].entity_id, "rule-2");
+        assert!((clicked[0].breakdown.popularity_boost - 2.5).abs() < 1e-6);
+        assert!((clicked[0].score - 0.3 * 2.5).abs() < 1e-6);
+        assert_eq!(clicked[1].breakdown.popularity_boost, 1.0);
+    }
+
+    struct ReverseReranker;
+
+    #[async_trait]
+    impl Reranker for ReverseReranker {
+        async fn rerank(&self, _query: &str, documents: &[String]) -> Result<Vec<f32>, EmbeddingError> {
+            Ok((0..documents.len()).map(|i| i as f32).collect())
+        }
+
+        fn name(&self) -> String {
+            "reverse".to_string()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rerank_reorders_top_results() {
+        let result = |id: &str, score: f32| RankedSearchResult {
+            entity_id: id.to_string(),
+            entity_type: "business_rule".to_string(),
+            title: Some(id.to_string()),
+            score,
+            breakdown: ScoreBreakdown {
+                fusion: FusionMethod::WeightedSum,
+                vector_score: None,
+                vector_rank: None,
+                vector_contribution: 0.0,
+                keyword_score: None,
+                keyword_rank: None,
+                keyword_contribution: 0.0,
+                entity_type_boost: 1.0,
+                popularity_boost: 1.0,
+                rerank_score: None,
+            },
+            project_id: None,
+            facets: BTreeMap::new(),
+            content: String::new(),
+        };
+        let mut results = vec![result("a", 0.9), result("b", 0.8), result("c", 0.7)];
+        let config = HybridSearchConfig {
+            rerank_top_k: 2,
+            ..HybridSearchConfig::default()
+        };
+
+        HybridSearchServiceImpl::rerank(&ReverseReranker, &config, "query", &mut results).await;
+        let order: Vec<&str> = results.iter().map(|r| r.entity_id.as_str()).collect();
+        assert_eq!(order, vec!["b", "a", "c"]);
+        assert_eq!(results[0].breakdown.rerank_score, Some(1.0));
+        assert_eq!(results[2].breakdown.rerank_score, None);
+    }
+
+    #[test]
+    fn test_fuzzy_keyword_matching() {
+        assert!(HybridSearchServiceImpl::within_typos("authetication", "authentication"));
+        assert!(HybridSearchServiceImpl::within_typos("rules", "rule"));
+        assert!(!HybridSearchServiceImpl::within_typos("api", "apt"));
+        assert!(!HybridSearchServiceImpl::within_typos("payment", "deployment"));
+
+        let mut item = EnhancedContextItem::new(
+            "p1".to_string(),
+            crate::models::enhanced_context::ContextContent {
+                content_type: ContextType::BusinessRule,
+                title: "Authentication rule".to_string(),
+                description: "Users must authenticate with MFA".to_string(),
+                data: serde_json::Value::Null,
+                source_file: None,
+                source_line: None,
+            },
+        );
+        item.id = "rule-1".to_string();
+        let groups = HybridSearchServiceImpl::term_groups("authetication rules", &[]);
+        assert_eq!(HybridSearchServiceImpl::keyword_score(&groups, &item, false), 0.0);
+        let score = HybridSearchServiceImpl::keyword_score(&groups, &item, true);
+        assert!((score - FUZZY_MATCH_WEIGHT).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_glossary_synonyms_satisfy_keyword_terms() {
+        let mut item = EnhancedContextItem::new(
+            "p1".to_string(),
+            crate::models::enhanced_context::ContextContent {
+                content_type: ContextType::ArchitecturalDecision,
+                title: "Architectural decision: token storage".to_string(),
+                description: "Authentication tokens live in the keychain".to_string(),
+                data: serde_json::Value::Null,
+                source_file: None,
+                source_line: None,
+            },
+        );
+        item.id = "adr-1".to_string();
+        let expansions = [
+            QueryExpansion {
+                matched: "ADR".to_string(),
+                synonyms: vec!["architectural decision".to_string()],
+            },
+            QueryExpansion {
+                matched: "auth".to_string(),
+                synonyms: vec!["authentication".to_string()],
+            },
+        ];
+
+        let plain = HybridSearchServiceImpl::term_groups("adr for auth", &[]);
+        assert_eq!(HybridSearchServiceImpl::keyword_score(&plain, &item, false), 0.0);
+        let expanded = HybridSearchServiceImpl::term_groups("adr for auth", &expansions);
+        assert_eq!(expanded.len(), 2);
+        assert_eq!(HybridSearchServiceImpl::keyword_score(&expanded, &item, false), 1.0);
+    }
+
+    #[test]
+    fn test_facets_filter_and_count() {
+        let facets = entity_facets(&serde_json::json!({
+            "domain_area": "Payments", "status": "accepted", "tags": "[\"pci\", \"billing\"]"
+        }));
+        assert_eq!(facets["domain_area"], vec!["payments"]);
+        assert_eq!(facets["tags"], vec!["pci", "billing"]);
+
+        let filters = FacetFilters::from([("domain_area".to_string(), vec!["payments".to_string()])]);
+        assert!(HybridSearchServiceImpl::matches_filters(&facets, &filters));
+        let filters = FacetFilters::from([("status".to_string(), vec!["proposed".to_string()])]);
+        assert!(!HybridSearchServiceImpl::matches_filters(&facets, &filters));
+
+        let result = |facets: BTreeMap<String, Vec<String>>| RankedSearchResult {
+            entity_id: String::new(),
+            entity_type: String::new(),
+            title: None,
+            score: 0.0,
+            breakdown: ScoreBreakdown {
+                fusion: FusionMethod::WeightedSum,
+                vector_score: None,
+                vector_rank: None,
+                vector_contribution: 0.0,
+                keyword_score: None,
+                keyword_rank: None,
+                keyword_contribution: 0.0,
+                entity_type_boost: 1.0,
+                popularity_boost: 1.0,
+                rerank_score: None,
+            },
+            project_id: None,
+            facets,
+            content: String::new(),
+        };
+        let counts = HybridSearchServiceImpl::count_facets(&[
+            result(facets.clone()),
+            result(entity_facets(&serde_json::json!({"tags": ["pci"]}))),
+        ]);
+        assert_eq!(counts["tags"]["pci"], 2);
+        assert_eq!(counts["tags"]["billing"], 1);
+        assert_eq!(counts["domain_area"]["payments"], 1);
+    }
+
+    #[test]
+    fn test_config_from_settings() {
+        let settings = SearchSettings {
+            fusion: "rrf".to_string(),
+            ..SearchSettings::default()
+        };
+        let config = HybridSearchConfig::from_settings(&settings).unwrap();
+        assert_eq!(config.fusion_method, FusionMethod::Rrf);
+
+        let settings = SearchSettings {
+            fusion: "max".to_string(),
+            ..SearchSettings::default()
+        };
+        assert!(HybridSearchConfig::from_settings(&settings).is_err());
+    }
+
     #[tokio::test]
     async fn test_search_suggestions() {
         let semantic_service = Arc::new(MockSemanticSearchService);
@@ -0,0 +1,329 @@
+//! RFC 6902 JSON Patch: the deltas carried by update changes, so clients holding an
+//! entity receive only the fields that changed and concurrent edits can be compared
+//! field by field
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One operation of a JSON Patch; paths are JSON Pointers (RFC 6901)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+impl PatchOperation {
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Add { path, .. }
+            | Self::Remove { path }
+            | Self::Replace { path, .. }
+            | Self::Move { path, .. }
+            | Self::Copy { path, .. }
+            | Self::Test { path, .. } => path,
+        }
+    }
+
+    /// Locations whose value this operation changes; `test` changes nothing
+    fn modified_paths(&self) -> Vec<&str> {
+        match self {
+            Self::Move { from, path } => vec![from, path],
+            Self::Test { .. } => Vec::new(),
+            other => vec![other.path()],
+        }
+    }
+}
+
+/// Escape a key for use as a JSON Pointer token
+pub fn escape_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+/// Operations turning `old` into `new`. Objects are compared key by key and arrays
+/// index by index, so the patch touches only what changed.
+pub fn diff(old: &Value, new: &Value) -> Vec<PatchOperation> {
+    let mut patch = Vec::new();
+    diff_into(old, new, String::new(), &mut patch);
+    patch
+}
+
+fn diff_into(old: &Value, new: &Value, path: String, patch: &mut Vec<PatchOperation>) {
+    match (old, new) {
+        (Value::Object(old_fields), Value::Object(new_fields)) => {
+            for key in old_fields.keys().filter(|key| !new_fields.contains_key(*key)) {
+                patch.push(PatchOperation::Remove {
+                    path: format!("{}/{}", path, escape_token(key)),
+                });
+            }
+            for (key, new_value) in new_fields {
+                let field_path = format!("{}/{}", path, escape_token(key));
+                match old_fields.get(key) {
+                    Some(old_value) => diff_into(old_value, new_value, field_path, patch),
+                    None => patch.push(PatchOperation::Add {
+                        path: field_path,
+                        value: new_value.clone(),
+                    }),
+                }
+            }
+        }
+        (Value::Array(old_items), Value::Array(new_items)) => {
+            let common = old_items.len().min(new_items.len());
+            for index in 0..common {
+                diff_into(&old_items[index], &new_items[index], format!("{}/{}", path, index), patch);
+            }
+            for (index, value) in new_items.iter().enumerate().skip(common) {
+                patch.push(PatchOperation::Add {
+                    path: format!("{}/{}", path, index),
+                    value: value.clone(),
+                });
+            }
+            // From the end, so earlier indices stay valid
+            for index in (common..old_items.len()).rev() {
+                patch.push(PatchOperation::Remove {
+                    path: format!("{}/{}", path, index),
+                });
+            }
+        }
+        _ if old != new => patch.push(PatchOperation::Replace {
+            path,
+            value: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+/// `document` with `patch` applied. Operations apply in order and the patch is atomic:
+/// if any operation fails, the error is returned and nothing is changed.
+pub fn apply(document: &Value, patch: &[PatchOperation]) -> Result<Value> {
+    let mut result = document.clone();
+    for operation in patch {
+        apply_operation(&mut result, operation)?;
+    }
+    Ok(result)
+}
+
+fn apply_operation(document: &mut Value, operation: &PatchOperation) -> Result<()> {
+    match operation {
+        PatchOperation::Add { path, value } => add(document, path, value.clone()),
+        PatchOperation::Remove { path } => remove(document, path).map(|_| ()),
+        PatchOperation::Replace { path, value } => {
+            let target = document
+                .pointer_mut(path)
+                .ok_or_else(|| anyhow!("Cannot replace missing path {}", path))?;
+            *target = value.clone();
+            Ok(())
+        }
+        PatchOperation::Move { from, path } => {
+            if path.starts_with(&format!("{}/", from)) {
+                return Err(anyhow!("Cannot move {} into its own child {}", from, path));
+            }
+            let value = remove(document, from)?;
+            add(document, path, value)
+        }
+        PatchOperation::Copy { from, path } => {
+            let value = document
+                .pointer(from)
+                .cloned()
+                .ok_or_else(|| anyhow!("Cannot copy missing path {}", from))?;
+            add(document, path, value)
+        }
+        PatchOperation::Test { path, value } => match document.pointer(path) {
+            Some(current) if current == value => Ok(()),
+            _ => Err(anyhow!("Test failed at {}", path)),
+        },
+    }
+}
+
+/// Parent pointer and unescaped last token of a non-root `path`
+fn split_path(path: &str) -> Result<(&str, String)> {
+    let index = path
+        .rfind('/')
+        .ok_or_else(|| anyhow!("Invalid JSON Pointer \"{}\"", path))?;
+    Ok((&path[..index], unescape_token(&path[index + 1..])))
+}
+
+fn add(document: &mut Value, path: &str, value: Value) -> Result<()> {
+    if path.is_empty() {
+        *document = value;
+        return Ok(());
+    }
+    let (parent_path, token) = split_path(path)?;
+    match document.pointer_mut(parent_path) {
+        Some(Value::Object(fields)) => {
+            fields.insert(token, value);
+            Ok(())
+        }
+        Some(Value::Array(items)) => {
+            let index = if token == "-" { items.len() } else { array_index(&token, items.len() + 1)? };
+            items.insert(index, value);
+            Ok(())
+        }
+        _ => Err(anyhow!("Cannot add at {}: parent does not exist", path)),
+    }
+}
+
+fn remove(document: &mut Value, path: &str) -> Result<Value> {
+    if path.is_empty() {
+        return Err(anyhow!("Cannot remove the whole document"));
+    }
+    let (parent_path, token) = split_path(path)?;
+    match document.pointer_mut(parent_path) {
+        Some(Value::Object(fields)) => fields
+            .remove(&token)
+            .ok_or_else(|| anyhow!("Cannot remove missing path {}", path)),
+        Some(Value::Array(items)) => {
+            let index = array_index(&token, items.len())?;
+            Ok(items.remove(index))
+        }
+        _ => Err(anyhow!("Cannot remove missing path {}", path)),
+    }
+}
+
+/// Index below `bound`, without the leading zeros RFC 6901 forbids
+fn array_index(token: &str, bound: usize) -> Result<usize> {
+    let index = token
+        .parse::<usize>()
+        .ok()
+        .filter(|_| token == "0" || !token.starts_with('0'))
+        .ok_or_else(|| anyhow!("Invalid array index \"{}\"", token))?;
+    if index >= bound {
+        return Err(anyhow!("Array index {} out of bounds", index));
+    }
+    Ok(index)
+}
+
+/// Whether one path is the other or contains it
+pub fn paths_overlap(a: &str, b: &str) -> bool {
+    let contains = |parent: &str, child: &str| child.starts_with(parent) && child[parent.len()..].starts_with('/');
+    a == b || contains(a, b) || contains(b, a)
+}
+
+/// Paths where two patches made from the same base disagree: both change overlapping
+/// locations, other than by making the same edit. Patches with no conflicting paths
+/// can both be applied.
+pub fn conflicting_paths(ours: &[PatchOperation], theirs: &[PatchOperation]) -> Vec<String> {
+    let mut conflicts: Vec<String> = Vec::new();
+    for our_operation in ours {
+        for their_operation in theirs {
+            if our_operation == their_operation {
+                continue;
+            }
+            for our_path in our_operation.modified_paths() {
+                for their_path in their_operation.modified_paths() {
+                    if paths_overlap(our_path, their_path) {
+                        // Report the enclosing location of the two
+                        let path = if our_path.len() <= their_path.len() { our_path } else { their_path };
+                        if !conflicts.iter().any(|existing| existing == path) {
+                            conflicts.push(path.to_string());
+                        }
+                    }
+                }
+            }
+        }
+    }
+    conflicts
+}
+
+/// A change delta as a patch; `None` for deltas in any other shape
+pub fn from_delta(delta: &Value) -> Option<Vec<PatchOperation>> {
+    if !delta.is_array() {
+        return None;
+    }
+    serde_json::from_value(delta.clone()).ok()
+}
+
+pub fn to_delta(patch: &[PatchOperation]) -> Value {
+    serde_json::to_value(patch).unwrap_or_else(|_| Value::Array(Vec::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_round_trips_through_apply() {
+        let old = json!({
+            "name": "Retry policy",
+            "tags": ["payments", "retries", "legacy"],
+            "owner": {"team": "core", "a/b": 1},
+            "draft": true
+        });
+        let new = json!({
+            "name": "Retry policy v2",
+            "tags": ["payments", "backoff"],
+            "owner": {"team": "core", "a/b": 2},
+            "status": "active"
+        });
+
+        let patch = diff(&old, &new);
+        assert!(patch.contains(&PatchOperation::Remove { path: "/draft".to_string() }));
+        assert!(patch.contains(&PatchOperation::Replace {
+            path: "/owner/a~1b".to_string(),
+            value: json!(2)
+        }));
+        assert!(!patch.iter().any(|op| op.path() == "/owner/team"));
+        assert_eq!(apply(&old, &patch).unwrap(), new);
+        assert!(diff(&new, &new).is_empty());
+
+        let delta = to_delta(&patch);
+        assert_eq!(delta[0]["op"], "remove");
+        assert_eq!(from_delta(&delta).unwrap(), patch);
+        assert!(from_delta(&json!({"old": {}, "new": {}})).is_none());
+    }
+
+    #[test]
+    fn test_apply_operations_and_atomic_failure() {
+        let document = json!({"items": [1, 2], "meta": {"owner": "ana"}});
+        let patch: Vec<PatchOperation> = serde_json::from_value(json!([
+            {"op": "test", "path": "/meta/owner", "value": "ana"},
+            {"op": "add", "path": "/items/-", "value": 3},
+            {"op": "copy", "from": "/meta/owner", "path": "/reviewer"},
+            {"op": "move", "from": "/meta", "path": "/details"}
+        ]))
+        .unwrap();
+        assert_eq!(
+            apply(&document, &patch).unwrap(),
+            json!({"items": [1, 2, 3], "reviewer": "ana", "details": {"owner": "ana"}})
+        );
+
+        let failing = vec![
+            PatchOperation::Remove { path: "/items/0".to_string() },
+            PatchOperation::Replace {
+                path: "/missing".to_string(),
+                value: json!(1),
+            },
+        ];
+        assert!(apply(&document, &failing).is_err());
+        assert!(apply(&document, &[PatchOperation::Remove { path: "/items/01".to_string() }]).is_err());
+    }
+
+    #[test]
+    fn test_conflicting_paths_compare_fields() {
+        let base = json!({"name": "Rule", "description": "Old", "owner": {"team": "core"}});
+        let ours = diff(&base, &json!({"name": "Rule A", "description": "Old", "owner": {"team": "core"}}));
+        let theirs = diff(&base, &json!({"name": "Rule", "description": "New", "owner": {"team": "core"}}));
+        assert!(conflicting_paths(&ours, &theirs).is_empty());
+
+        // The same edit on both sides agrees
+        assert!(conflicting_paths(&ours, &ours.clone()).is_empty());
+
+        let nested = vec![PatchOperation::Replace {
+            path: "/owner/team".to_string(),
+            value: json!("payments"),
+        }];
+        let whole = vec![PatchOperation::Remove { path: "/owner".to_string() }];
+        assert_eq!(conflicting_paths(&nested, &whole), vec!["/owner".to_string()]);
+        assert!(!paths_overlap("/owner", "/owners"));
+    }
+}
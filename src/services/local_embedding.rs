@@ -0,0 +1,262 @@
+//! In-process sentence embeddings from a BERT model such as all-MiniLM-L6-v2, run on the CPU
+//! with candle. Built with the `local-model` cargo feature; `[embedding] model_path` is a
+//! directory holding the model's `config.json`, `tokenizer.json` and `model.safetensors`,
+//! as published on Hugging Face, so no service or network access is needed at runtime
+
+use crate::models::embedding::{
+    ContextEmbedding, EmbeddingConfig, EmbeddingMetadata, ModelInfo, ModelType, VectorSearchQuery,
+    VectorSearchResult,
+};
+use crate::services::embedding_service::{rank_by_similarity, EmbeddingError, EmbeddingService};
+use async_trait::async_trait;
+use candle_core::{Device, Tensor};
+use candle_nn::VarBuilder;
+use candle_transformers::models::bert::{BertModel, Config, DTYPE};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use tokenizers::{PaddingParams, Tokenizer, TruncationParams};
+
+/// A loaded model and its tokenizer; inference runs on blocking threads
+struct SentenceModel {
+    bert: BertModel,
+    tokenizer: Tokenizer,
+    dimension: usize,
+}
+
+impl SentenceModel {
+    fn load(dir: &Path, max_sequence_length: usize) -> Result<Self, EmbeddingError> {
+        let load_error = |message: String| EmbeddingError::ModelLoadError { message };
+        let config = std::fs::read_to_string(dir.join("config.json"))
+            .map_err(|e| load_error(format!("{}: {e}", dir.join("config.json").display())))?;
+        let config: Config = serde_json::from_str(&config)
+            .map_err(|e| load_error(format!("{}: {e}", dir.join("config.json").display())))?;
+
+        let mut tokenizer = Tokenizer::from_file(dir.join("tokenizer.json"))
+            .map_err(|e| load_error(format!("{}: {e}", dir.join("tokenizer.json").display())))?;
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: max_sequence_length.min(config.max_position_embeddings),
+                ..TruncationParams::default()
+            }))
+            .map_err(|e| load_error(format!("Invalid truncation: {e}")))?;
+        tokenizer.with_padding(Some(PaddingParams {
+            pad_id: config.pad_token_id as u32,
+            ..PaddingParams::default()
+        }));
+
+        let weights = dir.join("model.safetensors");
+        // SAFETY: the weights file is only read, and not expected to change while mapped
+        let vb = unsafe { VarBuilder::from_mmaped_safetensors(&[&weights], DTYPE, &Device::Cpu) }
+            .map_err(|e| load_error(format!("{}: {e}", weights.display())))?;
+        let bert = BertModel::load(vb, &config).map_err(|e| load_error(format!("{}: {e}", weights.display())))?;
+        Ok(Self {
+            bert,
+            tokenizer,
+            dimension: config.hidden_size,
+        })
+    }
+
+    /// Mean of each text's token vectors, padding excluded, scaled to unit length
+    fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        let tokenization_error = |message: String| EmbeddingError::TokenizationError { message };
+        let generation_error = |e: candle_core::Error| EmbeddingError::EmbeddingGenerationError { message: e.to_string() };
+        let encodings = self
+            .tokenizer
+            .encode_batch(texts, true)
+            .map_err(|e| tokenization_error(e.to_string()))?;
+
+        let device = &self.bert.device;
+        let rows = |row: fn(&tokenizers::Encoding) -> &[u32]| -> candle_core::Result<Tensor> {
+            let rows = encodings
+                .iter()
+                .map(|encoding| Tensor::new(row(encoding), device))
+                .collect::<candle_core::Result<Vec<_>>>()?;
+            Tensor::stack(&rows, 0)
+        };
+        let input_ids = rows(|encoding| encoding.get_ids()).map_err(generation_error)?;
+        let attention_mask = rows(|encoding| encoding.get_attention_mask()).map_err(generation_error)?;
+        let token_type_ids = input_ids.zeros_like().map_err(generation_error)?;
+
+        let pooled = (|| {
+            let hidden = self.bert.forward(&input_ids, &token_type_ids, Some(&attention_mask))?;
+            let mask = attention_mask.to_dtype(DTYPE)?.unsqueeze(2)?;
+            let mean = hidden.broadcast_mul(&mask)?.sum(1)?.broadcast_div(&mask.sum(1)?)?;
+            let norm = mean.sqr()?.sum_keepdim(1)?.sqrt()?;
+            mean.broadcast_div(&norm)?.to_vec2::<f32>()
+        })();
+        pooled.map_err(generation_error)
+    }
+}
+
+pub struct LocalEmbeddingService {
+    config: EmbeddingConfig,
+    model: Arc<SentenceModel>,
+}
+
+impl LocalEmbeddingService {
+    pub fn new(config: EmbeddingConfig) -> Result<Self, EmbeddingError> {
+        let dir = config.model_path.clone().ok_or_else(|| EmbeddingError::ConfigurationError {
+            message: "The local embedding provider needs embedding.model_path, the model's directory".to_string(),
+        })?;
+        let model = SentenceModel::load(Path::new(&dir), config.max_sequence_length)?;
+        if model.dimension != config.embedding_dimension {
+            return Err(EmbeddingError::ConfigurationError {
+                message: format!(
+                    "embedding.dimension is {} but the model in {dir} produces {} dimensions",
+                    config.embedding_dimension, model.dimension
+                ),
+            });
+        }
+        Ok(Self {
+            config,
+            model: Arc::new(model),
+        })
+    }
+
+    async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        let model = self.model.clone();
+        tokio::task::spawn_blocking(move || model.embed(texts))
+            .await
+            .map_err(|e| EmbeddingError::EmbeddingGenerationError { message: e.to_string() })?
+    }
+
+    fn to_embedding(&self, context_id: &str, text: &str, content_type: &str, vector: Vec<f32>) -> ContextEmbedding {
+        let mut embedding = ContextEmbedding::new(
+            context_id.to_string(),
+            vector,
+            self.config.model_name.clone(),
+            "1".to_string(),
+            format!("{:x}", md5::compute(text.as_bytes())),
+        );
+        embedding.metadata = EmbeddingMetadata {
+            content_type: content_type.to_string(),
+            content_length: text.len(),
+            tokenization_method: self.config.tokenization_method.clone(),
+            preprocessing_steps: vec!["wordpiece".to_string(), "mean_pooling".to_string()],
+            quality_score: if text.len() < 10 { 0.5 } else { 1.0 },
+            custom_fields: HashMap::new(),
+        };
+        embedding
+    }
+}
+
+#[async_trait]
+impl EmbeddingService for LocalEmbeddingService {
+    async fn generate_embedding(&self, text: &str, content_type: &str) -> Result<ContextEmbedding, EmbeddingError> {
+        let vector = self.embed(vec![text.to_string()]).await?.remove(0);
+        Ok(self.to_embedding("", text, content_type, vector))
+    }
+
+    async fn generate_embeddings_batch(&self, texts: Vec<(&str, &str, &str)>) -> Result<Vec<ContextEmbedding>, EmbeddingError> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for chunk in texts.chunks(self.max_batch_size()) {
+            let vectors = self.embed(chunk.iter().map(|(_, text, _)| text.to_string()).collect()).await?;
+            for ((context_id, text, content_type), vector) in chunk.iter().zip(vectors) {
+                embeddings.push(self.to_embedding(context_id, text, content_type, vector));
+            }
+        }
+        Ok(embeddings)
+    }
+
+    fn calculate_similarity(&self, embedding1: &ContextEmbedding, embedding2: &ContextEmbedding) -> f32 {
+        embedding1.cosine_similarity(embedding2)
+    }
+
+    async fn find_similar(&self, query: &VectorSearchQuery, embeddings: &[ContextEmbedding]) -> Result<Vec<VectorSearchResult>, EmbeddingError> {
+        let query_embedding = match &query.query_embedding {
+            Some(embedding) => embedding.clone(),
+            None => self.embed(vec![query.query_text.clone()]).await?.remove(0),
+        };
+        Ok(rank_by_similarity(&query_embedding, query, embeddings))
+    }
+
+    fn get_model_info(&self) -> ModelInfo {
+        ModelInfo {
+            model_name: self.config.model_name.clone(),
+            model_version: "1".to_string(),
+            embedding_dimension: self.config.embedding_dimension,
+            max_sequence_length: self.config.max_sequence_length,
+            model_type: ModelType::SentenceTransformer,
+        }
+    }
+
+    async fn update_config(&mut self, config: EmbeddingConfig) -> Result<(), EmbeddingError> {
+        *self = Self::new(config)?;
+        Ok(())
+    }
+
+    fn max_batch_size(&self) -> usize {
+        self.config.batch_size.max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candle_nn::VarMap;
+    use tokenizers::models::wordlevel::WordLevel;
+    use tokenizers::pre_tokenizers::whitespace::Whitespace;
+    use tokenizers::processors::bert::BertProcessing;
+
+    /// A two-layer BERT with random weights and a word-level vocabulary, saved like a
+    /// downloaded model
+    fn write_tiny_model(dir: &Path) {
+        let config = serde_json::json!({
+            "vocab_size": 8, "hidden_size": 16, "num_hidden_layers": 2, "num_attention_heads": 2,
+            "intermediate_size": 32, "hidden_act": "gelu", "hidden_dropout_prob": 0.0,
+            "max_position_embeddings": 32, "type_vocab_size": 2, "initializer_range": 0.02,
+            "layer_norm_eps": 1e-12, "pad_token_id": 0, "classifier_dropout": null, "model_type": "bert"
+        });
+        std::fs::write(dir.join("config.json"), config.to_string()).unwrap();
+        let varmap = VarMap::new();
+        let vb = VarBuilder::from_varmap(&varmap, DTYPE, &Device::Cpu);
+        BertModel::load(vb, &serde_json::from_value(config).unwrap()).unwrap();
+        varmap.save(dir.join("model.safetensors")).unwrap();
+
+        let vocab = ["[PAD]", "[UNK]", "[CLS]", "[SEP]", "cart", "checkout", "invoice", "pdf"]
+            .iter()
+            .enumerate()
+            .map(|(id, word)| (word.to_string(), id as u32))
+            .collect();
+        let model = WordLevel::builder().vocab(vocab).unk_token("[UNK]".to_string()).build().unwrap();
+        let mut tokenizer = Tokenizer::new(model);
+        tokenizer.with_pre_tokenizer(Some(Whitespace {}));
+        tokenizer.with_post_processor(Some(BertProcessing::new(("[SEP]".to_string(), 3), ("[CLS]".to_string(), 2))));
+        tokenizer.save(dir.join("tokenizer.json"), false).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_embeds_with_the_model_in_model_path() {
+        let dir = tempfile::tempdir().unwrap();
+        write_tiny_model(dir.path());
+        let config = EmbeddingConfig {
+            model_name: "tiny-bert".to_string(),
+            model_path: Some(dir.path().display().to_string()),
+            embedding_dimension: 16,
+            ..EmbeddingConfig::default()
+        };
+        let service = LocalEmbeddingService::new(config.clone()).unwrap();
+
+        let single = service.generate_embedding("checkout cart", "text").await.unwrap();
+        assert_eq!(single.embedding_vector.len(), 16);
+        assert_eq!(single.embedding_model, "tiny-bert");
+        let norm: f32 = single.embedding_vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-4);
+
+        // Padding a shorter text in a batch doesn't change its vector
+        let batch = service
+            .generate_embeddings_batch(vec![("a", "checkout cart", "text"), ("b", "invoice pdf pdf cart", "text")])
+            .await
+            .unwrap();
+        assert_eq!(batch[0].context_id, "a");
+        assert!(service.calculate_similarity(&single, &batch[0]) > 0.9999);
+        assert!(service.calculate_similarity(&single, &batch[1]) < 0.9999);
+
+        let wrong_dimension = EmbeddingConfig { embedding_dimension: 384, ..config };
+        assert!(matches!(
+            LocalEmbeddingService::new(wrong_dimension),
+            Err(EmbeddingError::ConfigurationError { .. })
+        ));
+    }
+}
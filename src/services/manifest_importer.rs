@@ -0,0 +1,422 @@
+use crate::infrastructure::DependencyRepository;
+use crate::models::constraint::{ComponentDependency, DependencyType};
+use crate::services::codebase_scanner::SKIPPED_DIRS;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+
+/// Package manifest format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManifestKind {
+    Cargo,
+    Pubspec,
+    PackageJson,
+}
+
+impl ManifestKind {
+    pub fn of(file_name: &str) -> Option<Self> {
+        match file_name {
+            "Cargo.toml" => Some(ManifestKind::Cargo),
+            "pubspec.yaml" => Some(ManifestKind::Pubspec),
+            "package.json" => Some(ManifestKind::PackageJson),
+            _ => None,
+        }
+    }
+
+    /// `source_type` of the dependency rows imported from a manifest of this kind
+    pub fn source_type(&self) -> &'static str {
+        match self {
+            ManifestKind::Cargo => "cargo_manifest",
+            ManifestKind::Pubspec => "pubspec",
+            ManifestKind::PackageJson => "package_json",
+        }
+    }
+
+    /// `target_type` of the external packages a manifest of this kind declares
+    pub fn target_type(&self) -> &'static str {
+        match self {
+            ManifestKind::Cargo => "crate",
+            ManifestKind::Pubspec => "dart_package",
+            ManifestKind::PackageJson => "npm_package",
+        }
+    }
+
+    pub fn from_source_type(source_type: &str) -> Option<Self> {
+        [ManifestKind::Cargo, ManifestKind::Pubspec, ManifestKind::PackageJson]
+            .into_iter()
+            .find(|kind| kind.source_type() == source_type)
+    }
+}
+
+/// Whether a dependency is needed at run time or only for development or building
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyScope {
+    Normal,
+    Dev,
+    Build,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExternalDependency {
+    /// Package name, after following Cargo `package = "..."` renames
+    pub name: String,
+    /// Version requirement; `None` for path, git, SDK and workspace dependencies
+    pub version: Option<String>,
+    pub scope: DependencyScope,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParsedManifest {
+    pub kind: ManifestKind,
+    /// Relative to the imported root, with `/` separators
+    pub path: String,
+    /// Declared package name, or the manifest's directory for a virtual Cargo workspace
+    pub package: String,
+    pub dependencies: Vec<ExternalDependency>,
+}
+
+impl ParsedManifest {
+    /// Directory of the manifest with a trailing `/`; empty at the root
+    pub fn directory(path: &str) -> &str {
+        path.rfind('/').map_or("", |index| &path[..=index])
+    }
+
+    /// The dependency rows recorded for this manifest
+    pub fn component_dependencies(&self, project_id: &str) -> Vec<ComponentDependency> {
+        self.dependencies
+            .iter()
+            .map(|dependency| {
+                let mut description = format!("{} depends on {}", self.package, dependency.name);
+                if let Some(version) = &dependency.version {
+                    description.push_str(&format!(" {}", version));
+                }
+                match dependency.scope {
+                    DependencyScope::Normal => {}
+                    DependencyScope::Dev => description.push_str(" for development"),
+                    DependencyScope::Build => description.push_str(" at build time"),
+                }
+                let mut row = ComponentDependency::new(
+                    project_id.to_string(),
+                    self.path.clone(),
+                    self.kind.source_type().to_string(),
+                    dependency.name.clone(),
+                    self.kind.target_type().to_string(),
+                    DependencyType::DependsOn,
+                    description,
+                );
+                row.criticality = match dependency.scope {
+                    DependencyScope::Normal => "medium".to_string(),
+                    DependencyScope::Dev | DependencyScope::Build => "low".to_string(),
+                };
+                row
+            })
+            .collect()
+    }
+}
+
+/// Reads the external dependencies declared in `Cargo.toml`, `pubspec.yaml` and `package.json`
+pub struct ManifestParser;
+
+impl ManifestParser {
+    pub fn parse(kind: ManifestKind, path: &str, content: &str) -> Result<ParsedManifest, String> {
+        let (package, dependencies) = match kind {
+            ManifestKind::Cargo => Self::parse_cargo(content)?,
+            ManifestKind::Pubspec => Self::parse_pubspec(content)?,
+            ManifestKind::PackageJson => Self::parse_package_json(content)?,
+        };
+        let package = package.unwrap_or_else(|| {
+            let directory = ParsedManifest::directory(path).trim_end_matches('/');
+            directory.rsplit('/').next().filter(|name| !name.is_empty()).unwrap_or(".").to_string()
+        });
+        Ok(ParsedManifest {
+            kind,
+            path: path.to_string(),
+            package,
+            dependencies,
+        })
+    }
+
+    fn parse_cargo(content: &str) -> Result<(Option<String>, Vec<ExternalDependency>), String> {
+        let manifest: toml::Table = content.parse().map_err(|e| format!("Invalid TOML: {}", e))?;
+        let package = manifest
+            .get("package")
+            .and_then(|package| package.get("name"))
+            .and_then(|name| name.as_str())
+            .map(str::to_string);
+
+        let mut tables = vec![&manifest];
+        if let Some(targets) = manifest.get("target").and_then(|targets| targets.as_table()) {
+            tables.extend(targets.values().filter_map(|target| target.as_table()));
+        }
+        let mut dependencies = Vec::new();
+        for table in tables {
+            for (section, scope) in [
+                ("dependencies", DependencyScope::Normal),
+                ("dev-dependencies", DependencyScope::Dev),
+                ("build-dependencies", DependencyScope::Build),
+            ] {
+                let Some(entries) = table.get(section).and_then(|entries| entries.as_table()) else {
+                    continue;
+                };
+                for (key, spec) in entries {
+                    // Path dependencies are crates of the same repository, not external ones
+                    if spec.get("path").is_some() {
+                        continue;
+                    }
+                    let name = spec.get("package").and_then(|name| name.as_str()).unwrap_or(key);
+                    let version = spec
+                        .as_str()
+                        .or_else(|| spec.get("version").and_then(|version| version.as_str()))
+                        .map(str::to_string);
+                    dependencies.push(ExternalDependency { name: name.to_string(), version, scope });
+                }
+            }
+        }
+        Ok((package, dependencies))
+    }
+
+    fn parse_pubspec(content: &str) -> Result<(Option<String>, Vec<ExternalDependency>), String> {
+        let manifest: serde_yaml::Value = serde_yaml::from_str(content).map_err(|e| format!("Invalid YAML: {}", e))?;
+        let package = manifest.get("name").and_then(|name| name.as_str()).map(str::to_string);
+
+        let mut dependencies = Vec::new();
+        for (section, scope) in [("dependencies", DependencyScope::Normal), ("dev_dependencies", DependencyScope::Dev)] {
+            let Some(entries) = manifest.get(section).and_then(|entries| entries.as_mapping()) else {
+                continue;
+            };
+            for (name, spec) in entries {
+                let Some(name) = name.as_str() else {
+                    continue;
+                };
+                if spec.get("path").is_some() {
+                    continue;
+                }
+                let version = match spec {
+                    serde_yaml::Value::String(version) => Some(version.clone()),
+                    _ => spec.get("version").and_then(|version| version.as_str()).map(str::to_string),
+                };
+                dependencies.push(ExternalDependency { name: name.to_string(), version, scope });
+            }
+        }
+        Ok((package, dependencies))
+    }
+
+    fn parse_package_json(content: &str) -> Result<(Option<String>, Vec<ExternalDependency>), String> {
+        let manifest: serde_json::Value = serde_json::from_str(content).map_err(|e| format!("Invalid JSON: {}", e))?;
+        let package = manifest.get("name").and_then(|name| name.as_str()).map(str::to_string);
+
+        let mut dependencies = Vec::new();
+        for (section, scope) in [
+            ("dependencies", DependencyScope::Normal),
+            ("peerDependencies", DependencyScope::Normal),
+            ("optionalDependencies", DependencyScope::Normal),
+            ("devDependencies", DependencyScope::Dev),
+        ] {
+            let Some(entries) = manifest.get(section).and_then(|entries| entries.as_object()) else {
+                continue;
+            };
+            for (name, version) in entries {
+                let version = version.as_str().map(str::to_string);
+                // Workspace and file references point into the same repository
+                if version.as_deref().is_some_and(|v| v.starts_with("file:") || v.starts_with("link:") || v.starts_with("workspace:")) {
+                    continue;
+                }
+                if dependencies.iter().any(|d: &ExternalDependency| &d.name == name) {
+                    continue;
+                }
+                dependencies.push(ExternalDependency { name: name.clone(), version, scope });
+            }
+        }
+        Ok((package, dependencies))
+    }
+}
+
+/// What an import found and changed
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestImportReport {
+    pub project_id: String,
+    pub root: String,
+    pub manifests: Vec<ParsedManifest>,
+    pub created: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+    /// Manifests that could not be read or parsed
+    pub errors: Vec<String>,
+    pub dry_run: bool,
+}
+
+/// Keeps the external dependencies recorded for a project in step with its package manifests
+#[async_trait]
+pub trait ManifestImportService: Send + Sync {
+    /// Records a dependency row per external package each manifest under `root` declares and
+    /// removes rows of those manifests that are no longer declared; with `dry_run` nothing is written
+    async fn import_manifests(&self, project_id: &str, root: &Path, dry_run: bool) -> Result<ManifestImportReport, McpError>;
+}
+
+pub struct DefaultManifestImportService {
+    dependencies: Arc<dyn DependencyRepository>,
+}
+
+impl DefaultManifestImportService {
+    pub fn new(dependencies: Arc<dyn DependencyRepository>) -> Self {
+        Self { dependencies }
+    }
+
+    /// Manifests under the root, relative and sorted, skipping hidden and build directories
+    async fn manifest_files(root: &Path) -> Result<Vec<(ManifestKind, String)>, McpError> {
+        let mut files = Vec::new();
+        let mut pending: Vec<PathBuf> = vec![root.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let mut entries = fs::read_dir(&dir)
+                .await
+                .map_err(|e| McpError::invalid_params(format!("Cannot read {}: {}", dir.display(), e), None))?;
+            while let Some(entry) = entries
+                .next_entry()
+                .await
+                .map_err(|e| McpError::internal_error(format!("Cannot read {}: {}", dir.display(), e), None))?
+            {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let Ok(file_type) = entry.file_type().await else {
+                    continue;
+                };
+                if file_type.is_dir() {
+                    if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str()) {
+                        pending.push(entry.path());
+                    }
+                } else if let Some(kind) = ManifestKind::of(&name) {
+                    let path = entry.path();
+                    let Ok(relative) = path.strip_prefix(root) else {
+                        continue;
+                    };
+                    let relative = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+                    files.push((kind, relative));
+                }
+            }
+        }
+        files.sort_by(|a, b| a.1.cmp(&b.1));
+        Ok(files)
+    }
+}
+
+#[async_trait]
+impl ManifestImportService for DefaultManifestImportService {
+    async fn import_manifests(&self, project_id: &str, root: &Path, dry_run: bool) -> Result<ManifestImportReport, McpError> {
+        let storage_error = |e: anyhow::Error| McpError::internal_error(format!("Dependency storage error: {}", e), None);
+        let mut report = ManifestImportReport {
+            project_id: project_id.to_string(),
+            root: root.display().to_string(),
+            manifests: Vec::new(),
+            created: 0,
+            removed: 0,
+            unchanged: 0,
+            errors: Vec::new(),
+            dry_run,
+        };
+
+        for (kind, path) in Self::manifest_files(root).await? {
+            let content = match fs::read_to_string(root.join(&path)).await {
+                Ok(content) => content,
+                Err(e) => {
+                    report.errors.push(format!("{}: {}", path, e));
+                    continue;
+                }
+            };
+            let manifest = match ManifestParser::parse(kind, &path, &content) {
+                Ok(manifest) => manifest,
+                Err(e) => {
+                    report.errors.push(format!("{}: {}", path, e));
+                    continue;
+                }
+            };
+
+            let stored: Vec<ComponentDependency> = self
+                .dependencies
+                .get_dependencies_of(project_id, &path)
+                .map_err(storage_error)?
+                .into_iter()
+                .filter(|row| row.source_type == kind.source_type())
+                .collect();
+            let declared = manifest.component_dependencies(project_id);
+            let key = |row: &ComponentDependency| (row.target_component.clone(), row.description.clone());
+            let declared_keys: HashSet<_> = declared.iter().map(key).collect();
+            let stored_keys: HashSet<_> = stored.iter().map(key).collect();
+
+            for row in stored.iter().filter(|row| !declared_keys.contains(&key(row))) {
+                if !dry_run {
+                    self.dependencies.delete_dependency(&row.id).map_err(storage_error)?;
+                }
+                report.removed += 1;
+            }
+            for row in &declared {
+                if stored_keys.contains(&key(row)) {
+                    report.unchanged += 1;
+                    continue;
+                }
+                if !dry_run {
+                    self.dependencies.create_dependency(row).map_err(storage_error)?;
+                }
+                report.created += 1;
+            }
+            report.manifests.push(manifest);
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifests() {
+        let cargo = r#"
+[package]
+name = "storage"
+
+[dependencies]
+rusqlite = { version = "0.31", features = ["bundled"] }
+serde = "1.0"
+shared = { path = "../shared" }
+json = { package = "serde_json", version = "1" }
+tokio = { workspace = true }
+
+[dev-dependencies]
+tempfile = "3"
+
+[target.'cfg(windows)'.dependencies]
+winapi = "0.3"
+"#;
+        let manifest = ManifestParser::parse(ManifestKind::Cargo, "crates/storage/Cargo.toml", cargo).unwrap();
+        assert_eq!(manifest.package, "storage");
+        let names: Vec<&str> = manifest.dependencies.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["serde_json", "rusqlite", "serde", "tokio", "tempfile", "winapi"]);
+        assert_eq!(manifest.dependencies[3].version, None);
+        assert_eq!(manifest.dependencies[4].scope, DependencyScope::Dev);
+
+        let rows = manifest.component_dependencies("p1");
+        assert_eq!(rows[1].source_component, "crates/storage/Cargo.toml");
+        assert_eq!(rows[1].target_type, "crate");
+        assert_eq!(rows[1].description, "storage depends on rusqlite 0.31");
+        assert_eq!(rows[4].description, "storage depends on tempfile 3 for development");
+        assert_eq!(rows[4].criticality, "low");
+
+        let pubspec = "name: app\ndependencies:\n  flutter:\n    sdk: flutter\n  sqflite: ^2.3.0\n  core:\n    path: ../core\ndev_dependencies:\n  mockito: ^5.0.0\n";
+        let manifest = ManifestParser::parse(ManifestKind::Pubspec, "app/pubspec.yaml", pubspec).unwrap();
+        assert_eq!(manifest.package, "app");
+        let deps: Vec<(&str, Option<&str>)> = manifest.dependencies.iter().map(|d| (d.name.as_str(), d.version.as_deref())).collect();
+        assert_eq!(deps, vec![("flutter", None), ("sqflite", Some("^2.3.0")), ("mockito", Some("^5.0.0"))]);
+
+        let package_json = r#"{"dependencies": {"@prisma/client": "^5.0.0", "ui-kit": "workspace:*"}, "devDependencies": {"jest": "^29.0.0"}}"#;
+        let manifest = ManifestParser::parse(ManifestKind::PackageJson, "web/package.json", package_json).unwrap();
+        assert_eq!(manifest.package, "web");
+        let names: Vec<&str> = manifest.dependencies.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["@prisma/client", "jest"]);
+        assert!(ManifestParser::parse(ManifestKind::PackageJson, "package.json", "{").is_err());
+    }
+}
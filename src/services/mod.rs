@@ -1,35 +1,78 @@
 // Service layer modules following SOLID principles
 
 pub mod advanced_query_service;
+pub mod analytics_export;
 pub mod analytics_helper;
+pub mod analytics_policy;
 pub mod analytics_service;
 pub mod architecture_validation_service;
+pub mod attachment_service;
+pub mod codebase_scanner;
+pub mod dependency_cycles;
+pub mod dependency_graph;
+pub mod fitness_functions;
+pub mod fix_suggestions;
+pub mod manifest_importer;
+pub mod sarif_export;
 pub mod context_crud_service;
+pub mod context_freshness;
+pub mod context_gaps;
+pub mod context_recommendations;
 pub mod context_intelligence_service;
 pub mod context_quality_service;
 pub mod context_query_service;
 pub mod context_relationship_engine;
+pub mod dashboard;
 pub mod development_phase_service;
+pub mod digest_generator;
+pub mod tag_classifier;
+pub mod embedding_providers;
+pub mod embedding_migration;
+pub mod embedding_queue;
 pub mod embedding_service;
 pub mod extended_context_crud_service;
 pub mod framework_service;
+pub mod glossary_service;
+#[cfg(feature = "local-model")]
+pub mod local_embedding;
 pub mod project_service;
 pub mod semantic_search_service;
 pub mod hybrid_search_service;
+pub mod report_scheduler;
+pub mod tool_audit_service;
+pub mod reranker;
+pub mod saved_search_service;
+pub mod search_feedback;
+pub mod shared_context;
 pub mod search_index_manager;
 pub mod specification_parser;
+pub mod specification_exporter;
+pub mod specification_template;
+pub mod ears_linter;
+pub mod adr_parser;
+pub mod gherkin_parser;
+pub mod openapi_parser;
 pub mod specification_service;
 pub mod specification_import_service;
+pub mod adr_import_service;
+pub mod openapi_import_service;
 pub mod specification_versioning_service;
 pub mod specification_context_linking_service;
 pub mod specification_analytics_service;
+pub mod traceability_service;
+pub mod unused_context;
+pub mod approval_workflow_service;
 pub mod vector_embedding_integration;
+pub mod websocket_auth;
 pub mod websocket_manager;
 pub mod websocket_server;
 pub mod websocket_types;
 pub mod change_broadcaster;
 pub mod change_detection_service;
+pub mod json_patch;
+pub mod text_crdt;
 pub mod sync_engine;
+pub mod sync_metrics;
 pub mod conflict_resolution_engine;
 pub mod conflict_resolution_ui;
 pub mod plugin_manager;
@@ -68,21 +111,36 @@ pub mod specification_import_integration_test;
 // Re-export service traits
 // Temporarily commented out to debug compilation issues
 // pub use advanced_query_service::AdvancedQueryConfig;
+pub use analytics_export::AnalyticsExporter;
 pub use analytics_helper::AnalyticsHelper;
-pub use analytics_service::{AnalyticsService, DefaultAnalyticsService, AnalyticsEvent, AnalyticsEventType, UsageStatistics, ProjectInsights};
+pub use analytics_policy::AnalyticsPolicy;
+pub use analytics_service::{AnalyticsService, DefaultAnalyticsService, AnalyticsEvent, AnalyticsEventType, EntityUseSource, UsageStatistics, ProjectInsights};
 pub use architecture_validation_service::ArchitectureValidationService;
+pub use dependency_cycles::{CycleDetector, CycleEdge, DependencyCycle};
+pub use dependency_graph::{DependencyGraph, GraphFormat};
+pub use fix_suggestions::FixSuggester;
+pub use sarif_export::SarifExporter;
+pub use codebase_scanner::{CodebaseScanReport, CodebaseScanService, DefaultCodebaseScanService, ScannedComponent};
+pub use manifest_importer::{DefaultManifestImportService, ManifestImportReport, ManifestImportService};
 pub use context_intelligence_service::{ContextIntelligenceService, DefaultContextIntelligenceService};
 pub use context_quality_service::{ContextQualityService, DefaultContextQualityService};
 pub use context_query_service::ContextQueryService;
 pub use context_relationship_engine::{ContextRelationshipEngine, DefaultContextRelationshipEngine};
 pub use development_phase_service::DevelopmentPhaseService;
+pub use digest_generator::{DigestGenerator, DigestGeneratorFactory};
+pub use tag_classifier::TagClassifier;
 pub use embedding_service::{EmbeddingService, EmbeddingServiceFactory};
 pub use framework_service::FrameworkService;
 pub use project_service::ProjectService;
 pub use semantic_search_service::SemanticSearchService;
 pub use hybrid_search_service::{HybridSearchService, HybridSearchServiceImpl};
-pub use search_index_manager::{SearchIndexManager, SearchIndexManagerImpl, IndexManagerConfig};
+pub use search_index_manager::{SearchIndexManager, SearchIndexManagerImpl, IndexManagerConfig, SearchIndexStatus, IndexDrift};
 pub use specification_parser::SpecificationParser;
+pub use specification_exporter::SpecificationExporter;
+pub use specification_template::{SpecTemplate, SpecTemplateGenerator, TemplateContext};
+pub use ears_linter::{EarsLinter, EarsPattern, SpecQualityReport};
+pub use adr_parser::{AdrParser, ParsedAdr};
+pub use gherkin_parser::{GherkinParser, ParsedFeature};
 pub use plugin_manager::{PluginManager, DefaultPluginManager};
 pub use plugin_discovery::{PluginDiscovery, DefaultPluginDiscovery, PluginLoader};
 pub use plugin_security::{PluginSecurity, DefaultPluginSecurity, ResourceMonitor, PermissionValidator};
@@ -94,16 +152,28 @@ pub use plugin_error_handler::{PluginErrorHandler, DefaultPluginErrorHandler, Pl
 pub use plugin_testing::{PluginTestFramework, DefaultPluginTestFramework, PluginTestCase, TestResult, TestSuiteResult, MockPluginBehavior};
 pub use plugins::{GitIntegrationPlugin, KiroIntegrationPlugin, IdeIntegrationPlugin};
 pub use specification_import_service::{SpecificationImportService, DefaultSpecificationImportService, SpecificationChange, ChangeType};
-pub use specification_versioning_service::{SpecificationVersioningService, SqliteSpecificationVersioningService, SpecificationVersion, VersionChangeType, VersionComparison, VersionDifference, DifferenceType};
+pub use adr_import_service::{AdrImportService, DefaultAdrImportService};
+pub use openapi_import_service::{DefaultOpenApiImportService, OpenApiImportService};
+pub use specification_versioning_service::{SpecificationVersioningService, SqliteSpecificationVersioningService, SpecificationVersion, VersionChangeType, VersionComparison, VersionDifference, DifferenceType, SquashedStep, VersionSquashReport, VersionSummary};
 pub use specification_service::{SpecificationService, DefaultSpecificationService};
 pub use specification_context_linking_service::{SpecificationContextLinkingService, DefaultSpecificationContextLinkingService};
 pub use specification_analytics_service::{SpecificationAnalyticsService, DefaultSpecificationAnalyticsService};
+pub use traceability_service::{DefaultTraceabilityService, TraceabilityService};
+pub use report_scheduler::{DefaultReportSchedulerService, ReportSchedulerService};
+pub use tool_audit_service::{DefaultToolAuditService, ToolAuditService};
+pub use unused_context::{UnusedContextReport, STALE_TAG};
+pub use context_freshness::{EntityFreshness, StaleContextReport};
+pub use context_gaps::ContextGapReport;
+pub use dashboard::{DashboardData, DashboardRenderer};
+pub use approval_workflow_service::{ApprovalWorkflowService, DefaultApprovalWorkflowService};
+pub use websocket_auth::{ClientPermissions, WebSocketAuth};
 pub use websocket_manager::WebSocketManager;
 pub use websocket_server::{WebSocketServer, WebSocketService, WebSocketConfig};
 pub use websocket_types::*;
 pub use change_broadcaster::{ChangeBroadcaster, ChangeEvent, BroadcastMetrics, QueuedChange};
 pub use change_detection_service::{ChangeDetectionService, ChangeEmitter};
 pub use sync_engine::{SyncEngine, SyncStream, SyncConflict, Resolution};
+pub use sync_metrics::SyncMetrics;
 pub use conflict_resolution_engine::{ConflictResolutionEngine, ConflictInfo, ConflictType, ManualResolutionRequest, ConflictResolutionResult};
 pub use conflict_resolution_ui::{ConflictResolutionUI, ConflictResolutionSession, StartResolutionRequest, StartResolutionResponse, UpdateUIStateRequest, UpdateUIStateResponse};
 // Note: ComponentService removed as it was identical to FrameworkService
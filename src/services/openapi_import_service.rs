@@ -0,0 +1,334 @@
+use crate::models::context::{BusinessRule, FeatureContext};
+use crate::models::framework::FrameworkComponent;
+use crate::repositories::{BusinessRuleRepository, FeatureContextRepository, FrameworkRepository};
+use crate::services::openapi_parser::{ApiDescription, EndpointGroup, OpenApiParser};
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::fs;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+/// Component type of the framework components created for endpoint groups
+pub const ENDPOINT_GROUP_COMPONENT_TYPE: &str = "api_endpoint_group";
+
+/// Service importing OpenAPI documents as project context: a feature context and a framework
+/// component per endpoint group, linked to the business rules about that group
+#[async_trait]
+pub trait OpenApiImportService: Send + Sync {
+    /// Import an OpenAPI 3 or Swagger 2 file (JSON or YAML); importing it again updates what the
+    /// previous import created
+    async fn import_openapi_file(&self, project_id: &str, file_path: &Path) -> Result<OpenApiImportReport, McpError>;
+
+    /// Import an already read document; `source` is recorded as the components' file path
+    async fn import_openapi(&self, project_id: &str, content: &str, source: Option<&str>) -> Result<OpenApiImportReport, McpError>;
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenApiImportReport {
+    pub api_title: String,
+    pub api_version: Option<String>,
+    pub groups: Vec<ImportedEndpointGroup>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedEndpointGroup {
+    pub name: String,
+    pub endpoints: usize,
+    /// False when an earlier import or a hand-written context with the same name was updated
+    pub created: bool,
+    pub feature_context: FeatureContext,
+    pub component: FrameworkComponent,
+    pub business_rule_ids: Vec<String>,
+}
+
+/// Default implementation of OpenApiImportService
+pub struct DefaultOpenApiImportService {
+    feature_context_repository: Arc<dyn FeatureContextRepository>,
+    framework_repository: Arc<dyn FrameworkRepository>,
+    business_rule_repository: Arc<dyn BusinessRuleRepository>,
+}
+
+impl DefaultOpenApiImportService {
+    pub fn new(
+        feature_context_repository: Arc<dyn FeatureContextRepository>,
+        framework_repository: Arc<dyn FrameworkRepository>,
+        business_rule_repository: Arc<dyn BusinessRuleRepository>,
+    ) -> Self {
+        Self {
+            feature_context_repository,
+            framework_repository,
+            business_rule_repository,
+        }
+    }
+
+    /// Business rules about a group: rules whose domain area is the group, or whose name or
+    /// description mentions the group (singular or plural) or one of its schemas
+    pub fn related_rules<'a>(group: &EndpointGroup, rules: &'a [BusinessRule]) -> Vec<&'a BusinessRule> {
+        let name = group.name.to_lowercase();
+        let mut terms: BTreeSet<String> = group.schema_names().iter().map(|s| s.to_lowercase()).collect();
+        terms.insert(name.trim_end_matches('s').to_string());
+        terms.insert(name.clone());
+
+        rules
+            .iter()
+            .filter(|rule| {
+                if rule.domain_area.as_deref().is_some_and(|area| area.eq_ignore_ascii_case(&name)) {
+                    return true;
+                }
+                let text = format!("{} {}", rule.rule_name, rule.description.as_deref().unwrap_or("")).to_lowercase();
+                text.split(|c: char| !c.is_alphanumeric()).any(|word| terms.contains(word))
+            })
+            .collect()
+    }
+
+    async fn import_group(
+        &self,
+        project_id: &str,
+        api: &ApiDescription,
+        group: &EndpointGroup,
+        source: Option<&str>,
+        rules: &[BusinessRule],
+    ) -> Result<ImportedEndpointGroup, McpError> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let business_rule_ids: Vec<String> = Self::related_rules(group, rules).into_iter().map(|r| r.id.clone()).collect();
+
+        let existing = self.feature_context_repository.get_by_feature_name(project_id, &group.name).await?;
+        let created = existing.is_none();
+        let feature_context = Self::feature_context(project_id, api, group, existing, &now);
+        let feature_context = if created {
+            self.feature_context_repository.create(&feature_context).await?
+        } else {
+            self.feature_context_repository.update(&feature_context).await?
+        };
+
+        let component_name = format!("{} API", group.name);
+        let existing = self
+            .framework_repository
+            .find_by_project_id(project_id)
+            .await?
+            .into_iter()
+            .find(|c| c.component_name == component_name && c.component_type == ENDPOINT_GROUP_COMPONENT_TYPE);
+        let component = FrameworkComponent {
+            id: existing.as_ref().map_or_else(|| Uuid::new_v4().to_string(), |c| c.id.clone()),
+            project_id: project_id.to_string(),
+            component_name,
+            component_type: ENDPOINT_GROUP_COMPONENT_TYPE.to_string(),
+            architecture_layer: "api".to_string(),
+            file_path: source.map(str::to_string),
+            // The schemas the endpoints exchange
+            dependencies: group.schema_names().into_iter().collect(),
+            metadata: Some(serde_json::json!({
+                "source": "openapi",
+                "api_title": api.title,
+                "api_version": api.version,
+                "servers": api.servers,
+                "feature_context_id": feature_context.id,
+                "business_rule_ids": business_rule_ids,
+                "endpoints": group.endpoints,
+            })),
+            created_at: existing.as_ref().and_then(|c| c.created_at.clone()).or_else(|| Some(now.clone())),
+            updated_at: Some(now),
+        };
+        let component = match existing {
+            Some(_) => self.framework_repository.update(&component).await?,
+            None => self.framework_repository.create(&component).await?,
+        };
+
+        Ok(ImportedEndpointGroup {
+            name: group.name.clone(),
+            endpoints: group.endpoints.len(),
+            created,
+            feature_context,
+            component,
+            business_rule_ids,
+        })
+    }
+
+    /// The API shape of a group; a hand-written purpose and personas are kept
+    fn feature_context(
+        project_id: &str,
+        api: &ApiDescription,
+        group: &EndpointGroup,
+        existing: Option<FeatureContext>,
+        now: &str,
+    ) -> FeatureContext {
+        let workflows: Vec<String> = group
+            .endpoints
+            .iter()
+            .map(|e| match &e.summary {
+                Some(summary) => format!("{} ({})", summary, e.signature()),
+                None => e.signature(),
+            })
+            .collect();
+        let integration_points: Vec<String> = group
+            .endpoints
+            .iter()
+            .map(|e| {
+                let mut shape = e.signature();
+                if let Some(body) = &e.request_body {
+                    shape.push_str(&format!(" body {}", body));
+                }
+                let responses: Vec<String> = e
+                    .responses
+                    .iter()
+                    .map(|r| match &r.schema {
+                        Some(schema) => format!("{} {}", r.status, schema),
+                        None => r.status.clone(),
+                    })
+                    .collect();
+                if !responses.is_empty() {
+                    shape.push_str(&format!(" -> {}", responses.join(", ")));
+                }
+                if e.deprecated {
+                    shape.push_str(" (deprecated)");
+                }
+                shape
+            })
+            .collect();
+        let edge_cases: Vec<String> = group
+            .endpoints
+            .iter()
+            .flat_map(|e| {
+                e.error_responses().map(move |r| match &r.description {
+                    Some(description) => format!("{} {}: {}", e.signature(), r.status, description),
+                    None => format!("{} {}", e.signature(), r.status),
+                })
+            })
+            .collect();
+
+        let to_json = |items: &[String]| serde_json::to_string(items).ok();
+        let purpose = group
+            .description
+            .clone()
+            .unwrap_or_else(|| format!("{} endpoints of {}", group.name, api.title));
+
+        match existing {
+            Some(existing) => FeatureContext {
+                business_purpose: group.description.clone().or(existing.business_purpose).or(Some(purpose)),
+                key_workflows: to_json(&workflows),
+                integration_points: to_json(&integration_points),
+                edge_cases: to_json(&edge_cases),
+                ..existing
+            },
+            None => FeatureContext {
+                id: Uuid::new_v4().to_string(),
+                project_id: project_id.to_string(),
+                feature_name: group.name.clone(),
+                business_purpose: Some(purpose),
+                user_personas: None,
+                key_workflows: to_json(&workflows),
+                integration_points: to_json(&integration_points),
+                edge_cases: to_json(&edge_cases),
+                constraints: None,
+                related_components: None,
+                status: None,
+                owner: None,
+                created_at: Some(now.to_string()),
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl OpenApiImportService for DefaultOpenApiImportService {
+    async fn import_openapi_file(&self, project_id: &str, file_path: &Path) -> Result<OpenApiImportReport, McpError> {
+        let content = fs::read_to_string(file_path).await
+            .map_err(|e| McpError::internal_error(format!("Failed to read file {}: {}", file_path.display(), e), None))?;
+        self.import_openapi(project_id, &content, Some(&file_path.to_string_lossy())).await
+    }
+
+    async fn import_openapi(&self, project_id: &str, content: &str, source: Option<&str>) -> Result<OpenApiImportReport, McpError> {
+        let api = OpenApiParser::parse(content)
+            .map_err(|e| McpError::invalid_params(format!("Failed to parse OpenAPI document: {}", e), None))?;
+        debug!("Importing {} endpoint groups of {}", api.groups.len(), api.title);
+
+        let rules = self.business_rule_repository.find_by_project_id(project_id).await?;
+        let mut groups = Vec::new();
+        for group in &api.groups {
+            groups.push(self.import_group(project_id, &api, group, source, &rules).await?);
+        }
+
+        info!("Imported {} endpoint groups of {} into project {}", groups.len(), api.title, project_id);
+        Ok(OpenApiImportReport {
+            api_title: api.title,
+            api_version: api.version,
+            groups,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::{SqliteBusinessRuleRepository, SqliteFeatureContextRepository, SqliteFrameworkRepository};
+    use std::sync::Mutex;
+
+    const ORDERS_API: &str = r##"{
+        "openapi": "3.0.0",
+        "info": {"title": "Shop", "version": "1"},
+        "tags": [{"name": "orders", "description": "Placing orders"}],
+        "paths": {
+            "/orders": {
+                "post": {
+                    "tags": ["orders"],
+                    "summary": "Place an order",
+                    "requestBody": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/NewOrder"}}}},
+                    "responses": {"201": {"description": "Placed"}, "409": {"description": "Out of stock"}}
+                }
+            },
+            "/invoices/{id}": {"get": {"responses": {"200": {"description": "An invoice"}}}}
+        }
+    }"##;
+
+    #[tokio::test]
+    async fn test_import_creates_then_updates_groups_linked_to_rules() {
+        let conn = crate::db::init::init_db(":memory:").unwrap();
+        conn.execute("INSERT INTO projects (id, name) VALUES ('p1', 'Shop')", []).unwrap();
+        conn.execute(
+            "INSERT INTO business_rules (id, project_id, rule_name, description, domain_area) VALUES
+             ('r1', 'p1', 'Order limit', 'An order holds at most 50 items', 'checkout'),
+             ('r2', 'p1', 'Refunds', 'Refunds need approval', 'orders'),
+             ('r3', 'p1', 'Retention', 'Keep invoices for ten years', NULL)",
+            [],
+        )
+        .unwrap();
+        let db = Arc::new(Mutex::new(conn));
+        let service = DefaultOpenApiImportService::new(
+            Arc::new(SqliteFeatureContextRepository::new(db.clone())),
+            Arc::new(SqliteFrameworkRepository::new(db.clone())),
+            Arc::new(SqliteBusinessRuleRepository::new(db.clone())),
+        );
+
+        let report = service.import_openapi("p1", ORDERS_API, Some("openapi.json")).await.unwrap();
+        assert_eq!(report.groups.len(), 2);
+        let invoices = &report.groups[0];
+        assert_eq!(invoices.name, "invoices");
+        assert_eq!(invoices.business_rule_ids, vec!["r3"]);
+
+        let orders = &report.groups[1];
+        assert!(orders.created);
+        assert_eq!(orders.business_rule_ids, vec!["r1", "r2"]);
+        assert_eq!(orders.feature_context.business_purpose.as_deref(), Some("Placing orders"));
+        assert_eq!(
+            orders.feature_context.integration_points.as_deref(),
+            Some(r#"["POST /orders body NewOrder -> 201, 409"]"#)
+        );
+        assert_eq!(
+            orders.feature_context.edge_cases.as_deref(),
+            Some(r#"["POST /orders 409: Out of stock"]"#)
+        );
+        assert_eq!(orders.component.component_name, "orders API");
+        assert_eq!(orders.component.dependencies, vec!["NewOrder"]);
+
+        let again = service.import_openapi("p1", ORDERS_API, Some("openapi.json")).await.unwrap();
+        assert!(!again.groups[1].created);
+        assert_eq!(again.groups[1].feature_context.id, orders.feature_context.id);
+        assert_eq!(again.groups[1].component.id, orders.component.id);
+        assert_eq!(service.framework_repository.find_by_project_id("p1").await.unwrap().len(), 2);
+        assert_eq!(service.feature_context_repository.list_by_project("p1").await.unwrap().len(), 2);
+    }
+}
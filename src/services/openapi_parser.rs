@@ -0,0 +1,403 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+const HTTP_METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// An API described by an OpenAPI 3 or Swagger 2 document, with its endpoints grouped
+/// the way feature contexts are: by their first tag, or by their first path segment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiDescription {
+    pub title: String,
+    pub version: Option<String>,
+    pub servers: Vec<String>,
+    pub groups: Vec<EndpointGroup>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EndpointGroup {
+    pub name: String,
+    /// Description of the tag the group is named after
+    pub description: Option<String>,
+    pub endpoints: Vec<Endpoint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Endpoint {
+    /// Upper case, e.g. `POST`
+    pub method: String,
+    pub path: String,
+    pub operation_id: Option<String>,
+    pub summary: Option<String>,
+    pub parameters: Vec<EndpointParameter>,
+    /// Schema of the request body: a schema name, `Name[]` for arrays, or a primitive type
+    pub request_body: Option<String>,
+    pub responses: Vec<EndpointResponse>,
+    pub deprecated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EndpointParameter {
+    pub name: String,
+    /// `path`, `query`, `header` or `cookie`
+    pub location: String,
+    pub required: bool,
+    pub schema: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EndpointResponse {
+    /// Status code or `default`
+    pub status: String,
+    pub description: Option<String>,
+    pub schema: Option<String>,
+}
+
+impl Endpoint {
+    /// `POST /orders`
+    pub fn signature(&self) -> String {
+        format!("{} {}", self.method, self.path)
+    }
+
+    /// Responses with a 4xx or 5xx status
+    pub fn error_responses(&self) -> impl Iterator<Item = &EndpointResponse> {
+        self.responses.iter().filter(|r| r.status.starts_with('4') || r.status.starts_with('5'))
+    }
+
+    /// Named schemas the endpoint sends or receives
+    pub fn schema_names(&self) -> BTreeSet<String> {
+        self.request_body
+            .iter()
+            .chain(self.responses.iter().filter_map(|r| r.schema.as_ref()))
+            .chain(self.parameters.iter().filter_map(|p| p.schema.as_ref()))
+            .map(|s| s.trim_end_matches("[]").to_string())
+            .filter(|s| s.chars().next().is_some_and(char::is_uppercase))
+            .collect()
+    }
+}
+
+impl EndpointGroup {
+    pub fn schema_names(&self) -> BTreeSet<String> {
+        self.endpoints.iter().flat_map(Endpoint::schema_names).collect()
+    }
+}
+
+/// Parser for OpenAPI 3.x and Swagger 2.0 documents in JSON or YAML
+pub struct OpenApiParser;
+
+impl OpenApiParser {
+    pub fn parse(content: &str) -> Result<ApiDescription> {
+        // JSON is valid YAML, but JSON errors are clearer for JSON documents
+        let document: Value = match serde_json::from_str(content) {
+            Ok(document) => document,
+            Err(_) => serde_yaml::from_str(content).map_err(|e| anyhow!("Not a JSON or YAML document: {}", e))?,
+        };
+        Self::from_value(&document)
+    }
+
+    pub fn from_value(document: &Value) -> Result<ApiDescription> {
+        if document.get("openapi").is_none() && document.get("swagger").is_none() {
+            return Err(anyhow!("Missing `openapi` or `swagger` version field"));
+        }
+        let paths = document
+            .get("paths")
+            .and_then(Value::as_object)
+            .ok_or_else(|| anyhow!("Missing `paths` object"))?;
+
+        let info = document.get("info");
+        let title = info
+            .and_then(|i| i.get("title"))
+            .and_then(Value::as_str)
+            .unwrap_or("API")
+            .to_string();
+        let version = info.and_then(|i| i.get("version")).and_then(Self::scalar);
+
+        let tag_descriptions: BTreeMap<String, String> = document
+            .get("tags")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|tag| {
+                Some((
+                    tag.get("name")?.as_str()?.to_string(),
+                    tag.get("description")?.as_str()?.to_string(),
+                ))
+            })
+            .collect();
+
+        // Paths come in sorted order, so groups are ordered by their first path
+        let mut groups: Vec<EndpointGroup> = Vec::new();
+        for (path, item) in paths {
+            let shared_parameters = item.get("parameters").and_then(Value::as_array);
+            for method in HTTP_METHODS {
+                let Some(operation) = item.get(*method) else { continue };
+                let endpoint = Self::endpoint(method, path, operation, shared_parameters);
+                let name = operation
+                    .get("tags")
+                    .and_then(|t| t.get(0))
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| Self::path_group(path));
+
+                match groups.iter_mut().find(|g| g.name == name) {
+                    Some(group) => group.endpoints.push(endpoint),
+                    None => groups.push(EndpointGroup {
+                        description: tag_descriptions.get(&name).cloned(),
+                        name,
+                        endpoints: vec![endpoint],
+                    }),
+                }
+            }
+        }
+
+        Ok(ApiDescription {
+            title,
+            version,
+            servers: Self::servers(document),
+            groups,
+        })
+    }
+
+    fn endpoint(method: &str, path: &str, operation: &Value, shared_parameters: Option<&Vec<Value>>) -> Endpoint {
+        let mut parameters = Vec::new();
+        let mut request_body = None;
+        // Operation parameters override path-level ones with the same name and location
+        let operation_parameters = operation.get("parameters").and_then(Value::as_array);
+        for parameter in operation_parameters.into_iter().flatten().chain(shared_parameters.into_iter().flatten()) {
+            let (Some(name), Some(location)) = (
+                parameter.get("name").and_then(Value::as_str),
+                parameter.get("in").and_then(Value::as_str),
+            ) else {
+                continue;
+            };
+            if location == "body" {
+                // Swagger 2 request bodies are parameters
+                request_body = request_body.or_else(|| parameter.get("schema").and_then(Self::schema_name));
+                continue;
+            }
+            if parameters.iter().any(|p: &EndpointParameter| p.name == name && p.location == location) {
+                continue;
+            }
+            parameters.push(EndpointParameter {
+                name: name.to_string(),
+                location: location.to_string(),
+                required: parameter.get("required").and_then(Value::as_bool).unwrap_or(location == "path"),
+                // Swagger 2 puts the type on the parameter itself
+                schema: parameter.get("schema").and_then(Self::schema_name).or_else(|| Self::schema_name(parameter)),
+            });
+        }
+
+        if let Some(body) = operation.get("requestBody") {
+            request_body = Self::content_schema(body).or(request_body);
+        }
+
+        let responses = operation
+            .get("responses")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .map(|(status, response)| EndpointResponse {
+                status: status.clone(),
+                description: response.get("description").and_then(Value::as_str).map(str::to_string),
+                schema: Self::content_schema(response).or_else(|| response.get("schema").and_then(Self::schema_name)),
+            })
+            .collect();
+
+        Endpoint {
+            method: method.to_uppercase(),
+            path: path.to_string(),
+            operation_id: operation.get("operationId").and_then(Value::as_str).map(str::to_string),
+            summary: operation
+                .get("summary")
+                .or_else(|| operation.get("description"))
+                .and_then(Value::as_str)
+                .map(|s| s.trim().to_string()),
+            parameters,
+            request_body,
+            responses,
+            deprecated: operation.get("deprecated").and_then(Value::as_bool).unwrap_or(false),
+        }
+    }
+
+    /// Schema of the first media type of an OpenAPI 3 request body or response
+    fn content_schema(value: &Value) -> Option<String> {
+        let content = value.get("content")?.as_object()?;
+        let media = content.get("application/json").or_else(|| content.values().next())?;
+        media.get("schema").and_then(Self::schema_name)
+    }
+
+    /// `#/components/schemas/Order` becomes `Order`, an array of them `Order[]`
+    fn schema_name(schema: &Value) -> Option<String> {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            return reference.rsplit('/').next().map(str::to_string);
+        }
+        match schema.get("type").and_then(Value::as_str)? {
+            "array" => schema
+                .get("items")
+                .and_then(Self::schema_name)
+                .map(|items| format!("{}[]", items)),
+            other => Some(other.to_string()),
+        }
+    }
+
+    /// First path segment that is not a parameter, `api` or a version such as `v2`
+    fn path_group(path: &str) -> String {
+        path.split('/')
+            .filter(|s| !s.is_empty() && !s.starts_with('{'))
+            .find(|s| {
+                let is_version = s.len() > 1 && s.starts_with('v') && s[1..].chars().all(|c| c.is_ascii_digit());
+                *s != "api" && !is_version
+            })
+            .unwrap_or("root")
+            .to_string()
+    }
+
+    fn servers(document: &Value) -> Vec<String> {
+        let servers: Vec<String> = document
+            .get("servers")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|s| s.get("url").and_then(Value::as_str).map(str::to_string))
+            .collect();
+        if !servers.is_empty() {
+            return servers;
+        }
+        // Swagger 2
+        match document.get("host").and_then(Value::as_str) {
+            Some(host) => {
+                let scheme = document
+                    .get("schemes")
+                    .and_then(|s| s.get(0))
+                    .and_then(Value::as_str)
+                    .unwrap_or("https");
+                let base_path = document.get("basePath").and_then(Value::as_str).unwrap_or("");
+                vec![format!("{}://{}{}", scheme, host, base_path)]
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn scalar(value: &Value) -> Option<String> {
+        match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PETSTORE: &str = r##"
+openapi: 3.0.3
+info:
+  title: Orders API
+  version: 1.2.0
+servers:
+  - url: https://api.example.com/v1
+tags:
+  - name: Orders
+    description: Placing and tracking orders
+paths:
+  /orders/{orderId}:
+    parameters:
+      - name: orderId
+        in: path
+        schema: { type: string }
+    get:
+      tags: [Orders]
+      operationId: getOrder
+      summary: Get an order
+      responses:
+        "200":
+          description: The order
+          content:
+            application/json:
+              schema: { $ref: "#/components/schemas/Order" }
+        "404":
+          description: No such order
+  /orders:
+    post:
+      tags: [Orders]
+      summary: Place an order
+      requestBody:
+        content:
+          application/json:
+            schema: { $ref: "#/components/schemas/NewOrder" }
+      responses:
+        "201": { description: Created }
+        "422": { description: Invalid order }
+  /api/v1/health:
+    get:
+      responses:
+        "200": { description: OK }
+"##;
+
+    #[test]
+    fn test_parse_openapi_3_groups_by_tag_and_path() {
+        let api = OpenApiParser::parse(PETSTORE).unwrap();
+        assert_eq!(api.title, "Orders API");
+        assert_eq!(api.version.as_deref(), Some("1.2.0"));
+        assert_eq!(api.servers, vec!["https://api.example.com/v1"]);
+        assert_eq!(api.groups.len(), 2);
+
+        assert_eq!(api.groups[0].name, "health");
+        let orders = &api.groups[1];
+        assert_eq!(orders.name, "Orders");
+        assert_eq!(orders.description.as_deref(), Some("Placing and tracking orders"));
+        assert_eq!(orders.endpoints.len(), 2);
+
+        let get = orders.endpoints.iter().find(|e| e.method == "GET").unwrap();
+        assert_eq!(get.signature(), "GET /orders/{orderId}");
+        assert_eq!(get.operation_id.as_deref(), Some("getOrder"));
+        assert_eq!(get.parameters[0].name, "orderId");
+        assert!(get.parameters[0].required);
+        assert_eq!(get.responses[0].schema.as_deref(), Some("Order"));
+        assert_eq!(get.error_responses().map(|r| r.status.as_str()).collect::<Vec<_>>(), vec!["404"]);
+
+        let post = orders.endpoints.iter().find(|e| e.method == "POST").unwrap();
+        assert_eq!(post.request_body.as_deref(), Some("NewOrder"));
+        assert_eq!(
+            orders.schema_names().into_iter().collect::<Vec<_>>(),
+            vec!["NewOrder".to_string(), "Order".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_swagger_2() {
+        let document = serde_json::json!({
+            "swagger": "2.0",
+            "info": {"title": "Legacy", "version": 2},
+            "host": "legacy.example.com",
+            "basePath": "/api",
+            "paths": {
+                "/customers": {
+                    "post": {
+                        "parameters": [
+                            {"name": "body", "in": "body", "schema": {"$ref": "#/definitions/Customer"}},
+                            {"name": "dryRun", "in": "query", "type": "boolean"}
+                        ],
+                        "responses": {"200": {"description": "Saved", "schema": {"type": "array", "items": {"$ref": "#/definitions/Customer"}}}}
+                    }
+                }
+            }
+        });
+        let api = OpenApiParser::from_value(&document).unwrap();
+        assert_eq!(api.version.as_deref(), Some("2"));
+        assert_eq!(api.servers, vec!["https://legacy.example.com/api"]);
+
+        let endpoint = &api.groups[0].endpoints[0];
+        assert_eq!(api.groups[0].name, "customers");
+        assert_eq!(endpoint.request_body.as_deref(), Some("Customer"));
+        assert_eq!(endpoint.parameters.len(), 1);
+        assert_eq!(endpoint.parameters[0].schema.as_deref(), Some("boolean"));
+        assert!(!endpoint.parameters[0].required);
+        assert_eq!(endpoint.responses[0].schema.as_deref(), Some("Customer[]"));
+
+        assert!(OpenApiParser::parse("title: not an api").is_err());
+    }
+}
@@ -122,7 +122,7 @@ impl PluginApi for DefaultPluginApi {
         self.check_permissions(instance_id, &[PluginPermission::ReadContext]).await?;
         
         // Execute query - using simplified parameters for now
-        let results = self.context_query_service.query_context(project_id, query, "general", &[]).await?;
+        let results = self.context_query_service.query_context(project_id, query, "general", &[], None).await?;
         
         // Convert results to JSON values
         let mut json_results = Vec::new();
@@ -3,6 +3,7 @@ use crate::models::plugin::{
     PluginInstance, PluginInstanceId, PluginMetadata, PluginResponse, PluginStatus,
     ResourceUsage, HealthStatus, PluginApiClient,
 };
+use crate::services::plugins::ArchitectureValidatorRegistry;
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use chrono::Utc;
@@ -55,6 +56,7 @@ pub struct DefaultPluginManager {
     instances: Arc<RwLock<HashMap<PluginInstanceId, PluginInstance>>>,
     plugin_data_dir: PathBuf,
     temp_dir: PathBuf,
+    validator_registry: Option<Arc<ArchitectureValidatorRegistry>>,
 }
 
 impl DefaultPluginManager {
@@ -65,8 +67,15 @@ impl DefaultPluginManager {
             instances: Arc::new(RwLock::new(HashMap::new())),
             plugin_data_dir,
             temp_dir,
+            validator_registry: None,
         }
     }
+
+    /// Register the architecture validators of started plugins, dropping them again when they stop
+    pub fn with_validator_registry(mut self, registry: Arc<ArchitectureValidatorRegistry>) -> Self {
+        self.validator_registry = Some(registry);
+        self
+    }
     
     /// Create plugin context for initialization
     fn create_plugin_context(&self, instance_id: PluginInstanceId, config: PluginConfiguration) -> PluginContext {
@@ -169,6 +178,10 @@ impl PluginManager for DefaultPluginManager {
             let mut instances = self.instances.write().await;
             instances.remove(&instance_id);
         }
+
+        if let Some(registry) = &self.validator_registry {
+            registry.unregister(&instance_id.to_string());
+        }
         
         Ok(())
     }
@@ -195,6 +208,9 @@ impl PluginManager for DefaultPluginManager {
             
             match plugin.initialize(context).await {
                 Ok(()) => {
+                    if let Some(registry) = &self.validator_registry {
+                        registry.register(&instance_id.to_string(), plugin.architecture_validators());
+                    }
                     drop(plugin);
                     drop(plugins);
                     drop(instances);
@@ -215,6 +231,9 @@ impl PluginManager for DefaultPluginManager {
     }
     
     async fn stop_plugin(&self, instance_id: PluginInstanceId) -> Result<()> {
+        if let Some(registry) = &self.validator_registry {
+            registry.unregister(&instance_id.to_string());
+        }
         self.update_plugin_status(instance_id, PluginStatus::Paused).await?;
         Ok(())
     }
@@ -3,6 +3,7 @@ use crate::models::plugin::{
     PluginInstanceId, PluginMetadata, PluginRegistryEntry, PluginResponse, PluginSearchFilters,
     ResourceUsage, PluginPermission, ResourceLimits,
 };
+use crate::services::plugins::ArchitectureValidatorRegistry;
 use crate::services::{
     PluginManager, PluginDiscovery, PluginSecurity, PluginConfigurationManager,
     DefaultPluginManager, DefaultPluginDiscovery, DefaultPluginSecurity, DefaultPluginConfigurationManager,
@@ -136,6 +137,15 @@ impl DefaultPluginService {
             temp_dir,
         }
     }
+
+    /// Let started plugins contribute architecture validators to `registry`
+    pub fn with_validator_registry(mut self, registry: Arc<ArchitectureValidatorRegistry>) -> Self {
+        self.plugin_manager = Arc::new(
+            DefaultPluginManager::new(self.plugin_data_dir.clone(), self.temp_dir.clone())
+                .with_validator_registry(registry),
+        );
+        self
+    }
     
     /// Create plugin directories if they don't exist
     async fn ensure_directories(&self) -> Result<()> {
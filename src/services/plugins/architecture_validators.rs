@@ -0,0 +1,40 @@
+use crate::models::plugin::ArchitectureValidator;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// Validators plugins contribute to architecture validation, kept under the plugin that
+/// registered them so they can be dropped when it stops
+#[derive(Default)]
+pub struct ArchitectureValidatorRegistry {
+    validators: RwLock<Vec<(String, Arc<dyn ArchitectureValidator>)>>,
+}
+
+impl ArchitectureValidatorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the validators registered by `owner`
+    pub fn register(&self, owner: &str, validators: Vec<Arc<dyn ArchitectureValidator>>) {
+        let mut registered = self.validators.write();
+        registered.retain(|(registered_by, _)| registered_by != owner);
+        registered.extend(validators.into_iter().map(|validator| (owner.to_string(), validator)));
+    }
+
+    /// Removes the validators registered by `owner` and returns how many there were
+    pub fn unregister(&self, owner: &str) -> usize {
+        let mut registered = self.validators.write();
+        let before = registered.len();
+        registered.retain(|(registered_by, _)| registered_by != owner);
+        before - registered.len()
+    }
+
+    pub fn validators(&self) -> Vec<Arc<dyn ArchitectureValidator>> {
+        self.validators.read().iter().map(|(_, validator)| validator.clone()).collect()
+    }
+
+    /// Names of the registered validators
+    pub fn names(&self) -> Vec<String> {
+        self.validators.read().iter().map(|(_, validator)| validator.name().to_string()).collect()
+    }
+}
@@ -0,0 +1,245 @@
+use crate::models::architecture::{ArchitectureFinding, ArchitectureRuleSet, Severity, ViolationRule};
+use crate::models::framework::FrameworkComponent;
+use crate::models::plugin::{
+    ArchitectureValidator, ContextContribution, ContextPlugin, HealthStatus, PluginContext, PluginEvent, PluginHealth,
+    PluginMetadata, PluginPermission, PluginResponse, ResourceUsage,
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Libraries the components of some layers may not depend on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DependencyGuard {
+    /// Layers the guard applies to, matched case-insensitively
+    pub layers: Vec<String>,
+    /// Crate, Dart package or npm package names, e.g. `rusqlite`, `sqflite` or `@prisma/client`
+    pub libraries: Vec<String>,
+    #[serde(default)]
+    pub severity: Option<Severity>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl DependencyGuard {
+    /// Presentation code must not talk to a database itself
+    pub fn presentation_storage() -> Self {
+        let libraries = [
+            "rusqlite", "sqlx", "diesel", "sea_orm", "sqflite", "drift", "hive", "isar", "typeorm", "@prisma/client",
+            "sequelize", "mongoose", "pg", "mysql2",
+        ];
+        Self {
+            layers: vec!["presentation".to_string()],
+            libraries: libraries.iter().map(|l| l.to_string()).collect(),
+            severity: Some(Severity::Error),
+            reason: Some("storage belongs behind the domain layer".to_string()),
+        }
+    }
+
+    /// Library a dependency comes from: `rusqlite::Connection` → `rusqlite`,
+    /// `package:sqflite/sqflite.dart` → `sqflite`, `@prisma/client/runtime` → `@prisma/client`
+    pub fn library_of(dependency: &str) -> &str {
+        if let Some(package) = dependency.strip_prefix("package:") {
+            return package.split('/').next().unwrap_or(package);
+        }
+        if dependency.starts_with('@') {
+            let end = dependency.match_indices('/').nth(1).map_or(dependency.len(), |(i, _)| i);
+            return &dependency[..end];
+        }
+        let dependency = dependency.trim_start_matches("::");
+        dependency.split("::").next().and_then(|first| first.split('/').next()).unwrap_or(dependency)
+    }
+}
+
+/// Validator checking components against dependency guards
+pub struct DependencyGuardValidator {
+    guards: Vec<DependencyGuard>,
+}
+
+impl DependencyGuardValidator {
+    pub fn new(guards: Vec<DependencyGuard>) -> Self {
+        Self { guards }
+    }
+}
+
+#[async_trait]
+impl ArchitectureValidator for DependencyGuardValidator {
+    fn name(&self) -> &str {
+        "dependency_guard"
+    }
+
+    async fn validate(
+        &self,
+        _rules: &ArchitectureRuleSet,
+        components: &[FrameworkComponent],
+    ) -> Result<Vec<ArchitectureFinding>> {
+        let mut findings = Vec::new();
+        for component in components {
+            for guard in &self.guards {
+                if !guard.layers.iter().any(|layer| layer.eq_ignore_ascii_case(&component.architecture_layer)) {
+                    continue;
+                }
+                for dep in &component.dependencies {
+                    let library = DependencyGuard::library_of(dep);
+                    if !guard.libraries.iter().any(|guarded| guarded == library) {
+                        continue;
+                    }
+                    let mut message = format!(
+                        "Dependency guard: {} ({}) depends on {}: {}",
+                        component.component_name, component.architecture_layer, library, dep
+                    );
+                    if let Some(reason) = &guard.reason {
+                        message.push_str(&format!(" ({})", reason));
+                    }
+                    findings.push(ArchitectureFinding {
+                        rule: ViolationRule::Plugin,
+                        severity: guard.severity.unwrap_or_else(|| ViolationRule::Plugin.default_severity()),
+                        component: component.component_name.clone(),
+                        message,
+                        validator: Some(self.name().to_string()),
+                        dependency: Some(dep.clone()),
+                        fixes: Vec::new(),
+                    });
+                }
+            }
+        }
+        Ok(findings)
+    }
+}
+
+/// Contributes a validator that keeps layers away from libraries that belong to other layers;
+/// the `guards` setting replaces the default guard, presentation code depending on database drivers
+pub struct DependencyGuardPlugin {
+    metadata: PluginMetadata,
+    guards: Vec<DependencyGuard>,
+}
+
+impl DependencyGuardPlugin {
+    pub fn new() -> Self {
+        let metadata = PluginMetadata {
+            id: Uuid::new_v4(),
+            name: "Dependency Guard".to_string(),
+            version: "1.0.0".to_string(),
+            description: "Reports components that depend on libraries their layer may not use".to_string(),
+            author: "Context Server".to_string(),
+            homepage: None,
+            repository: None,
+            license: "MIT".to_string(),
+            keywords: vec!["architecture".to_string(), "validation".to_string()],
+            dependencies: vec![],
+            permissions: vec![PluginPermission::ReadContext],
+            configuration_schema: Some(serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "guards": {
+                        "type": "array",
+                        "items": {"type": "object", "required": ["layers", "libraries"]}
+                    }
+                }
+            })),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        Self {
+            metadata,
+            guards: vec![DependencyGuard::presentation_storage()],
+        }
+    }
+
+    pub fn with_guards(mut self, guards: Vec<DependencyGuard>) -> Self {
+        self.guards = guards;
+        self
+    }
+}
+
+impl Default for DependencyGuardPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ContextPlugin for DependencyGuardPlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+
+    async fn initialize(&mut self, context: PluginContext) -> Result<()> {
+        if let Some(guards) = context.configuration.settings.get("guards") {
+            self.guards = serde_json::from_value(guards.clone())
+                .map_err(|e| anyhow!("Invalid dependency guards: {}", e))?;
+        }
+        Ok(())
+    }
+
+    async fn handle_event(&self, _event: PluginEvent) -> Result<PluginResponse> {
+        Ok(PluginResponse::EventIgnored)
+    }
+
+    async fn provide_context(&self, _query: &str, _project_id: &str) -> Result<Option<ContextContribution>> {
+        Ok(None)
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<PluginHealth> {
+        Ok(PluginHealth {
+            status: HealthStatus::Healthy,
+            message: Some(format!("{} dependency guards", self.guards.len())),
+            last_check: Utc::now(),
+            resource_usage: ResourceUsage::default(),
+        })
+    }
+
+    fn architecture_validators(&self) -> Vec<Arc<dyn ArchitectureValidator>> {
+        vec![Arc::new(DependencyGuardValidator::new(self.guards.clone()))]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(name: &str, layer: &str, dependencies: &[&str]) -> FrameworkComponent {
+        FrameworkComponent {
+            id: name.to_string(),
+            project_id: "p1".to_string(),
+            component_name: name.to_string(),
+            component_type: "widget".to_string(),
+            architecture_layer: layer.to_string(),
+            file_path: None,
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            metadata: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_presentation_may_not_use_database_drivers() {
+        assert_eq!(DependencyGuard::library_of("::rusqlite::Connection"), "rusqlite");
+        assert_eq!(DependencyGuard::library_of("package:sqflite/sqflite.dart"), "sqflite");
+        assert_eq!(DependencyGuard::library_of("@prisma/client/runtime"), "@prisma/client");
+        assert_eq!(DependencyGuard::library_of("pg"), "pg");
+
+        let validators = DependencyGuardPlugin::new().architecture_validators();
+        let rules = ArchitectureRuleSet::clean_architecture("p1");
+        let components = [
+            component("SettingsPage", "Presentation", &["rusqlite::Connection", "serde::Serialize"]),
+            component("UserStore", "data", &["rusqlite::Connection"]),
+        ];
+        let findings = validators[0].validate(&rules, &components).await.unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(
+            findings[0].message,
+            "Dependency guard: SettingsPage (Presentation) depends on rusqlite: rusqlite::Connection (storage belongs behind the domain layer)"
+        );
+    }
+}
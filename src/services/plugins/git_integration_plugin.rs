@@ -162,6 +162,12 @@ impl GitIntegrationPlugin {
         }
     }
 
+    /// Read the repository at `repository_path` without going through plugin initialization
+    pub fn with_repository_path(mut self, repository_path: impl Into<PathBuf>) -> Self {
+        self.repository_path = Some(repository_path.into());
+        self
+    }
+
     /// Execute a Git command
     async fn execute_git_command(&self, args: &[&str]) -> Result<String> {
         let repo_path = self.repository_path.as_ref()
@@ -196,13 +202,18 @@ impl GitIntegrationPlugin {
             "show", "--format=%H|%an|%ae|%at|%s", "--name-status", commit_hash
         ]).await?;
 
-        let lines: Vec<&str> = commit_info.lines().collect();
+        Self::parse_commit(&commit_info)
+    }
+
+    /// Parse a `%H|%an|%ae|%at|%s` header line followed by `--name-status` lines
+    fn parse_commit(commit_info: &str) -> Result<GitCommit> {
+        let lines: Vec<&str> = commit_info.trim_start().lines().collect();
         if lines.is_empty() {
             return Err(anyhow!("No commit information found"));
         }
 
         // Parse commit header
-        let header_parts: Vec<&str> = lines[0].split('|').collect();
+        let header_parts: Vec<&str> = lines[0].splitn(5, '|').collect();
         if header_parts.len() < 5 {
             return Err(anyhow!("Invalid commit format"));
         }
@@ -228,7 +239,8 @@ impl GitIntegrationPlugin {
                 };
 
                 files_changed.push(GitFileChange {
-                    path: parts[1].to_string(),
+                    // Renames list the old and the new path; keep the new one
+                    path: parts[parts.len() - 1].to_string(),
                     change_type,
                     additions: 0, // Would need additional git command to get this
                     deletions: 0, // Would need additional git command to get this
@@ -242,12 +254,27 @@ impl GitIntegrationPlugin {
             author: header_parts[1].to_string(),
             email: header_parts[2].to_string(),
             timestamp: DateTime::from_timestamp(timestamp, 0)
-                .unwrap_or_else(|| Utc::now()),
+                .unwrap_or_else(Utc::now),
             message: header_parts[4].to_string(),
             files_changed,
         })
     }
 
+    /// The latest `limit` commits of the checked out branch with the files they changed,
+    /// newest first
+    pub async fn recent_commits(&self, limit: usize) -> Result<Vec<GitCommit>> {
+        let limit = format!("--max-count={}", limit);
+        // Each commit starts with a record separator so commits can be split apart
+        let log = self.execute_git_command(&[
+            "log", &limit, "--format=%x1e%H|%an|%ae|%at|%s", "--name-status"
+        ]).await?;
+
+        log.split('\u{1e}')
+            .filter(|commit| !commit.trim().is_empty())
+            .map(Self::parse_commit)
+            .collect()
+    }
+
     /// Check for new commits
     async fn check_for_new_commits(&self) -> Result<Vec<GitCommit>> {
         let current_hash = self.get_current_commit_hash().await?;
@@ -1,7 +1,11 @@
+pub mod architecture_validators;
+pub mod dependency_guard_plugin;
 pub mod git_integration_plugin;
 pub mod kiro_integration_plugin;
 pub mod ide_integration_plugin;
 
+pub use architecture_validators::ArchitectureValidatorRegistry;
+pub use dependency_guard_plugin::{DependencyGuard, DependencyGuardPlugin, DependencyGuardValidator};
 pub use git_integration_plugin::GitIntegrationPlugin;
 pub use kiro_integration_plugin::KiroIntegrationPlugin;
-pub use ide_integration_plugin::IdeIntegrationPlugin;
\ No newline at end of file
+pub use ide_integration_plugin::IdeIntegrationPlugin;
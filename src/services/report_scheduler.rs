@@ -0,0 +1,343 @@
+use crate::config::{ConfigManager, ReportsConfig};
+use crate::models::scheduled_report::{ScheduledReport, ScheduledReportKind};
+use crate::repositories::{ProjectRepository, ScheduledReportRepository};
+use crate::services::analytics_service::AnalyticsService;
+use crate::services::specification_analytics_service::SpecificationAnalyticsService;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use rmcp::model::ErrorData as McpError;
+use std::sync::Arc;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Period covered by a project's first report of a kind when scheduling is off
+const DEFAULT_PERIOD_HOURS: i64 = 24;
+/// How long webhook and Slack deliveries may take
+const DELIVERY_TIMEOUT_SECS: u64 = 10;
+
+/// The usage report of a period with the project's quality assessment, as
+/// `generate_quality_report` returns it
+pub async fn quality_report(
+    analytics: &dyn AnalyticsService,
+    start_date: DateTime<Utc>,
+    end_date: DateTime<Utc>,
+    project_id: Option<&str>,
+) -> anyhow::Result<serde_json::Value> {
+    let mut report = analytics.generate_usage_report(start_date, end_date).await?;
+    if let Some(project_id) = project_id {
+        if let Ok(insights) = analytics.get_project_insights(project_id).await {
+            if let Some(report) = report.as_object_mut() {
+                report.insert(
+                    "quality_assessment".to_string(),
+                    serde_json::json!({
+                        "context_health_score": insights.context_health_score,
+                        "recommendations": insights.recommendations,
+                        "most_active_entity_types": insights.most_active_entity_types
+                    }),
+                );
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Generates quality and specification health reports for every project on the `[reports]`
+/// schedule and keeps them as `scheduled_report` entities
+#[async_trait]
+pub trait ReportSchedulerService: Send + Sync {
+    /// Generate the configured reports that are due, for every project
+    async fn run_due(&self) -> Result<Vec<ScheduledReport>, McpError>;
+    /// Generate reports of a project now, due or not; the configured kinds when `kinds` is empty
+    async fn run_project(&self, project_id: &str, kinds: &[ScheduledReportKind]) -> Result<Vec<ScheduledReport>, McpError>;
+    async fn get_report(&self, id: &str) -> Result<Option<ScheduledReport>, McpError>;
+    /// Newest first
+    async fn list_reports(&self, project_id: &str, kind: Option<ScheduledReportKind>) -> Result<Vec<ScheduledReport>, McpError>;
+    async fn delete_report(&self, id: &str) -> Result<bool, McpError>;
+}
+
+/// Default implementation of ReportSchedulerService
+pub struct DefaultReportSchedulerService {
+    projects: Arc<dyn ProjectRepository>,
+    reports: Arc<dyn ScheduledReportRepository>,
+    analytics: Arc<dyn AnalyticsService>,
+    specification_analytics: Arc<dyn SpecificationAnalyticsService>,
+    config: Arc<ConfigManager>,
+    client: reqwest::Client,
+}
+
+impl DefaultReportSchedulerService {
+    pub fn new(
+        projects: Arc<dyn ProjectRepository>,
+        reports: Arc<dyn ScheduledReportRepository>,
+        analytics: Arc<dyn AnalyticsService>,
+        specification_analytics: Arc<dyn SpecificationAnalyticsService>,
+        config: Arc<ConfigManager>,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(DELIVERY_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default();
+        Self {
+            projects,
+            reports,
+            analytics,
+            specification_analytics,
+            config,
+            client,
+        }
+    }
+
+    /// Checks every few minutes for due reports while `[reports] interval_hours` is set,
+    /// re-reading it on every check so reloads apply
+    pub fn spawn(service: Arc<dyn ReportSchedulerService>, config: Arc<ConfigManager>) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            debug!("No tokio runtime; report scheduler not started");
+            return;
+        };
+
+        runtime.spawn(async move {
+            let mut interval = tokio::time::interval_at(
+                tokio::time::Instant::now() + std::time::Duration::from_secs(60),
+                std::time::Duration::from_secs(300),
+            );
+            loop {
+                interval.tick().await;
+                if config.current().reports.interval_hours == 0 {
+                    continue;
+                }
+                match service.run_due().await {
+                    Ok(reports) if reports.is_empty() => {}
+                    Ok(reports) => debug!("Generated {} scheduled reports", reports.len()),
+                    Err(e) => warn!("Failed to generate scheduled reports: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Whether a new report should follow `latest` at `now`
+    pub fn is_due(latest: Option<&ScheduledReport>, interval_hours: u64, now: DateTime<Utc>) -> bool {
+        if interval_hours == 0 {
+            return false;
+        }
+        let Some(latest) = latest else {
+            return true;
+        };
+        match DateTime::parse_from_rfc3339(&latest.created_at) {
+            Ok(created_at) => now - created_at.with_timezone(&Utc) >= Duration::hours(interval_hours as i64),
+            Err(_) => true,
+        }
+    }
+
+    /// One line for chat notifications
+    pub fn summary(report: &ScheduledReport, project_name: &str) -> String {
+        let number = |pointer: &str| report.report.pointer(pointer).and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let count = |pointer: &str| report.report.pointer(pointer).and_then(|v| v.as_array()).map_or(0, Vec::len);
+        let period = format!(
+            "{} to {}",
+            report.period_start.get(..10).unwrap_or(&report.period_start),
+            report.period_end.get(..10).unwrap_or(&report.period_end)
+        );
+        match report.kind {
+            ScheduledReportKind::Quality => format!(
+                "Quality report for {} ({}): context health {:.0}/100, {} recommendations",
+                project_name,
+                period,
+                number("/quality_assessment/context_health_score"),
+                count("/quality_assessment/recommendations"),
+            ),
+            ScheduledReportKind::SpecificationHealth => format!(
+                "Specification health for {} ({}): score {:.1}, {} critical issues, {} recommendations",
+                project_name,
+                period,
+                number("/overall_health_score"),
+                count("/critical_issues"),
+                count("/recommendations"),
+            ),
+        }
+    }
+
+    async fn generate(
+        &self,
+        project_id: &str,
+        project_name: &str,
+        kind: ScheduledReportKind,
+        latest: Option<ScheduledReport>,
+        settings: &ReportsConfig,
+        webhook_targets: &[String],
+    ) -> Result<ScheduledReport, McpError> {
+        let now = Utc::now();
+        let hours = match settings.interval_hours {
+            0 => DEFAULT_PERIOD_HOURS,
+            hours => hours as i64,
+        };
+        // Each report picks up where the previous one of its kind ended
+        let period_start = latest
+            .and_then(|latest| DateTime::parse_from_rfc3339(&latest.period_end).ok())
+            .map(|end| end.with_timezone(&Utc))
+            .unwrap_or(now - Duration::hours(hours));
+
+        let content = match kind {
+            ScheduledReportKind::Quality => quality_report(self.analytics.as_ref(), period_start, now, Some(project_id))
+                .await
+                .map_err(|e| McpError::internal_error(format!("Quality report generation failed: {}", e), None))?,
+            ScheduledReportKind::SpecificationHealth => {
+                let report = self.specification_analytics.generate_health_report(project_id).await?;
+                serde_json::to_value(report)
+                    .map_err(|e| McpError::internal_error(format!("Serialization error: {}", e), None))?
+            }
+        };
+
+        let mut report = ScheduledReport {
+            id: Uuid::new_v4().to_string(),
+            project_id: project_id.to_string(),
+            kind,
+            period_start: period_start.to_rfc3339(),
+            period_end: now.to_rfc3339(),
+            report: content,
+            delivered_to: Vec::new(),
+            created_at: now.to_rfc3339(),
+        };
+        report.delivered_to = self.deliver(&report, project_name, settings, webhook_targets).await;
+
+        let report = self.reports.create(&report).await?;
+        if settings.keep > 0 {
+            self.reports.prune(project_id, kind, settings.keep).await?;
+        }
+        Ok(report)
+    }
+
+    /// The report to the webhook targets and its summary to Slack, as configured; returns the
+    /// targets that accepted it, with Slack as `slack`
+    async fn deliver(
+        &self,
+        report: &ScheduledReport,
+        project_name: &str,
+        settings: &ReportsConfig,
+        webhook_targets: &[String],
+    ) -> Vec<String> {
+        let mut deliveries: Vec<(String, reqwest::RequestBuilder)> = Vec::new();
+        if settings.notify_webhooks {
+            let body = serde_json::json!({"event": "scheduled_report", "report": report});
+            for target in webhook_targets {
+                deliveries.push((target.clone(), self.client.post(target).json(&body)));
+            }
+        }
+        if let Some(url) = settings.slack_webhook_url.as_deref().filter(|url| !url.is_empty()) {
+            let body = serde_json::json!({"text": Self::summary(report, project_name)});
+            deliveries.push(("slack".to_string(), self.client.post(url).json(&body)));
+        }
+
+        let mut delivered = Vec::new();
+        for (target, request) in deliveries {
+            match request.send().await.and_then(|response| response.error_for_status()) {
+                Ok(_) => delivered.push(target),
+                Err(e) => warn!("Failed to deliver scheduled report {} to {}: {}", report.id, target, e),
+            }
+        }
+        delivered
+    }
+}
+
+#[async_trait]
+impl ReportSchedulerService for DefaultReportSchedulerService {
+    async fn run_due(&self) -> Result<Vec<ScheduledReport>, McpError> {
+        let config = self.config.current();
+        let now = Utc::now();
+        let mut generated = Vec::new();
+        for project in self.projects.find_all().await? {
+            for &kind in &config.reports.kinds {
+                let latest = self.reports.find_latest(&project.id, kind).await?;
+                if !Self::is_due(latest.as_ref(), config.reports.interval_hours, now) {
+                    continue;
+                }
+                match self
+                    .generate(&project.id, &project.name, kind, latest, &config.reports, &config.webhooks.targets)
+                    .await
+                {
+                    Ok(report) => generated.push(report),
+                    Err(e) => warn!("Failed to generate {} report for project {}: {}", kind.as_str(), project.id, e),
+                }
+            }
+        }
+        Ok(generated)
+    }
+
+    async fn run_project(&self, project_id: &str, kinds: &[ScheduledReportKind]) -> Result<Vec<ScheduledReport>, McpError> {
+        let project = self
+            .projects
+            .find_by_id(project_id)
+            .await?
+            .ok_or_else(|| McpError::invalid_params(format!("Project not found: {}", project_id), None))?;
+        let config = self.config.current();
+        let kinds = if kinds.is_empty() { &config.reports.kinds[..] } else { kinds };
+
+        let mut generated = Vec::new();
+        for &kind in kinds {
+            let latest = self.reports.find_latest(project_id, kind).await?;
+            generated.push(
+                self.generate(project_id, &project.name, kind, latest, &config.reports, &config.webhooks.targets)
+                    .await?,
+            );
+        }
+        Ok(generated)
+    }
+
+    async fn get_report(&self, id: &str) -> Result<Option<ScheduledReport>, McpError> {
+        self.reports.find_by_id(id).await
+    }
+
+    async fn list_reports(&self, project_id: &str, kind: Option<ScheduledReportKind>) -> Result<Vec<ScheduledReport>, McpError> {
+        self.reports.find_by_project_id(project_id, kind).await
+    }
+
+    async fn delete_report(&self, id: &str) -> Result<bool, McpError> {
+        self.reports.delete(id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(kind: ScheduledReportKind, created_at: &str, content: serde_json::Value) -> ScheduledReport {
+        ScheduledReport {
+            id: "r1".to_string(),
+            project_id: "p1".to_string(),
+            kind,
+            period_start: "2024-05-01T00:00:00+00:00".to_string(),
+            period_end: created_at.to_string(),
+            report: content,
+            delivered_to: Vec::new(),
+            created_at: created_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_schedule_and_summary() {
+        let now = DateTime::parse_from_rfc3339("2024-05-02T12:00:00Z").unwrap().with_timezone(&Utc);
+        let quality = report(
+            ScheduledReportKind::Quality,
+            "2024-05-02T00:00:00+00:00",
+            serde_json::json!({"quality_assessment": {"context_health_score": 87.5, "recommendations": ["Add rules"]}}),
+        );
+        assert!(DefaultReportSchedulerService::is_due(None, 24, now));
+        assert!(!DefaultReportSchedulerService::is_due(Some(&quality), 24, now));
+        assert!(DefaultReportSchedulerService::is_due(Some(&quality), 12, now));
+        assert!(!DefaultReportSchedulerService::is_due(None, 0, now));
+
+        assert_eq!(
+            DefaultReportSchedulerService::summary(&quality, "Shop"),
+            "Quality report for Shop (2024-05-01 to 2024-05-02): context health 88/100, 1 recommendations"
+        );
+        let health = report(
+            ScheduledReportKind::SpecificationHealth,
+            "2024-05-02T00:00:00+00:00",
+            serde_json::json!({"overall_health_score": 0.75, "critical_issues": [], "recommendations": ["a", "b"]}),
+        );
+        assert_eq!(
+            DefaultReportSchedulerService::summary(&health, "Shop"),
+            "Specification health for Shop (2024-05-01 to 2024-05-02): score 0.8, 0 critical issues, 2 recommendations"
+        );
+        assert_eq!(ScheduledReportKind::from_name("specification_health"), Some(ScheduledReportKind::SpecificationHealth));
+    }
+}
@@ -0,0 +1,326 @@
+//! Optional second ranking stage for hybrid search
+//! A reranker reads the query together with each candidate, which is slower than
+//! comparing precomputed vectors but more precise for the few results agents keep
+
+use crate::config::RerankSettings;
+use crate::models::embedding::RetryPolicy;
+use crate::services::embedding_service::{retry_with_backoff, EmbeddingError};
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+const OPENAI_DEFAULT_ENDPOINT: &str = "https://api.openai.com/v1";
+
+/// Scores (query, candidate) pairs
+#[async_trait]
+pub trait Reranker: Send + Sync {
+    /// One relevance score per document, in input order; higher is more relevant
+    async fn rerank(&self, query: &str, documents: &[String]) -> Result<Vec<f32>, EmbeddingError>;
+
+    /// Shown in score breakdowns, e.g. `cross_encoder:bge-reranker-base`
+    fn name(&self) -> String;
+}
+
+#[derive(Deserialize)]
+struct RerankResponse {
+    results: Vec<RerankItem>,
+}
+
+#[derive(Deserialize)]
+struct RerankItem {
+    index: usize,
+    relevance_score: f32,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatMessage {
+    content: String,
+}
+
+/// How the backend is asked for scores
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RerankBackend {
+    /// A cross-encoder behind a `/rerank` endpoint (llama.cpp, vLLM, Infinity, Jina, Cohere)
+    CrossEncoder,
+    /// An OpenAI-compatible chat model asked to grade each candidate
+    Llm,
+}
+
+pub struct HttpReranker {
+    backend: RerankBackend,
+    client: reqwest::Client,
+    endpoint: String,
+    model: String,
+    api_key: Option<String>,
+    retry: RetryPolicy,
+}
+
+impl HttpReranker {
+    fn new(backend: RerankBackend, settings: &RerankSettings) -> Result<Self, EmbeddingError> {
+        let (endpoint, api_key) = match backend {
+            RerankBackend::CrossEncoder => (
+                settings.endpoint.clone().ok_or_else(|| EmbeddingError::ConfigurationError {
+                    message: "search.rerank.endpoint is required for the cross_encoder provider".to_string(),
+                })?,
+                settings.api_key.clone(),
+            ),
+            RerankBackend::Llm => (
+                settings
+                    .endpoint
+                    .clone()
+                    .unwrap_or_else(|| OPENAI_DEFAULT_ENDPOINT.to_string()),
+                settings
+                    .api_key
+                    .clone()
+                    .or_else(|| std::env::var("OPENAI_API_KEY").ok()),
+            ),
+        };
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(settings.timeout_secs))
+            .build()
+            .map_err(|e| EmbeddingError::ConfigurationError {
+                message: format!("Failed to create HTTP client: {e}"),
+            })?;
+
+        Ok(Self {
+            backend,
+            client,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            model: settings.model.clone(),
+            api_key,
+            retry: RetryPolicy {
+                max_retries: settings.max_retries,
+                ..RetryPolicy::default()
+            },
+        })
+    }
+
+    async fn post(&self, url: &str, body: &serde_json::Value) -> Result<reqwest::Response, EmbeddingError> {
+        let mut request = self.client.post(url).json(body);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await.map_err(|e| EmbeddingError::BackendError {
+            message: format!("{url}: {e}"),
+            retryable: e.is_timeout() || e.is_connect() || e.is_request(),
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let detail = response.text().await.unwrap_or_default();
+            return Err(EmbeddingError::BackendError {
+                message: format!("{url} returned {status}: {detail}"),
+                retryable: status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error(),
+            });
+        }
+        Ok(response)
+    }
+
+    async fn cross_encoder_scores(&self, query: &str, documents: &[String]) -> Result<Vec<f32>, EmbeddingError> {
+        let url = format!("{}/rerank", self.endpoint);
+        let body = serde_json::json!({"model": self.model, "query": query, "documents": documents});
+        let response = self.post(&url, &body).await?;
+        let results = response
+            .json::<RerankResponse>()
+            .await
+            .map_err(|e| EmbeddingError::BackendError {
+                message: format!("Unexpected response from {url}: {e}"),
+                retryable: false,
+            })?
+            .results;
+
+        let mut scores = vec![f32::NEG_INFINITY; documents.len()];
+        for item in results {
+            if let Some(score) = scores.get_mut(item.index) {
+                *score = item.relevance_score;
+            }
+        }
+        Ok(scores)
+    }
+
+    async fn llm_scores(&self, query: &str, documents: &[String]) -> Result<Vec<f32>, EmbeddingError> {
+        let url = format!("{}/chat/completions", self.endpoint);
+        let candidates: String = documents
+            .iter()
+            .enumerate()
+            .map(|(index, document)| format!("[{index}] {document}\n"))
+            .collect();
+        let prompt = format!(
+            "Grade how relevant each numbered candidate is to the query, from 0 (unrelated) to 10 (exactly what is needed).\n\
+             Reply with only a JSON array of {} numbers, one per candidate, in order.\n\nQuery: {query}\n\nCandidates:\n{candidates}",
+            documents.len()
+        );
+        let body = serde_json::json!({
+            "model": self.model,
+            "temperature": 0,
+            "messages": [{"role": "user", "content": prompt}],
+        });
+        let response = self.post(&url, &body).await?;
+        let reply = response
+            .json::<ChatResponse>()
+            .await
+            .map_err(|e| EmbeddingError::BackendError {
+                message: format!("Unexpected response from {url}: {e}"),
+                retryable: false,
+            })?
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content)
+            .unwrap_or_default();
+
+        let scores = parse_grades(&reply).ok_or_else(|| EmbeddingError::BackendError {
+            message: format!("{url} did not reply with a JSON array of grades: {reply}"),
+            retryable: false,
+        })?;
+        if scores.len() != documents.len() {
+            return Err(EmbeddingError::BackendError {
+                message: format!("{url} graded {} of {} candidates", scores.len(), documents.len()),
+                retryable: false,
+            });
+        }
+        Ok(scores.into_iter().map(|grade| grade / 10.0).collect())
+    }
+}
+
+/// The first JSON array of numbers in `reply`; models often wrap it in prose or a code fence
+fn parse_grades(reply: &str) -> Option<Vec<f32>> {
+    let start = reply.find('[')?;
+    let end = start + reply[start..].find(']')?;
+    serde_json::from_str(&reply[start..=end]).ok()
+}
+
+#[async_trait]
+impl Reranker for HttpReranker {
+    async fn rerank(&self, query: &str, documents: &[String]) -> Result<Vec<f32>, EmbeddingError> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+        debug!("Reranking {} candidates via {}", documents.len(), self.endpoint);
+        retry_with_backoff(&self.retry, || async {
+            match self.backend {
+                RerankBackend::CrossEncoder => self.cross_encoder_scores(query, documents).await,
+                RerankBackend::Llm => self.llm_scores(query, documents).await,
+            }
+        })
+        .await
+    }
+
+    fn name(&self) -> String {
+        let backend = match self.backend {
+            RerankBackend::CrossEncoder => "cross_encoder",
+            RerankBackend::Llm => "llm",
+        };
+        format!("{backend}:{}", self.model)
+    }
+}
+
+pub struct RerankerFactory;
+
+impl RerankerFactory {
+    /// Translate `[search.rerank]`; `None` when reranking is off
+    pub fn from_settings(settings: &RerankSettings) -> Result<Option<Arc<dyn Reranker>>, EmbeddingError> {
+        let backend = match settings.provider.to_lowercase().replace('-', "_").as_str() {
+            "none" | "" => return Ok(None),
+            "cross_encoder" => RerankBackend::CrossEncoder,
+            "llm" => RerankBackend::Llm,
+            other => {
+                return Err(EmbeddingError::ConfigurationError {
+                    message: format!(
+                        "Unknown search.rerank.provider \"{other}\"; expected \"none\", \"cross_encoder\" or \"llm\""
+                    ),
+                })
+            }
+        };
+        Ok(Some(Arc::new(HttpReranker::new(backend, settings)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    async fn serve_response(body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buffer = vec![0u8; 64 * 1024];
+            let _ = socket.read(&mut buffer).await;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        format!("http://{address}")
+    }
+
+    fn settings(provider: &str, endpoint: String) -> RerankSettings {
+        RerankSettings {
+            provider: provider.to_string(),
+            endpoint: Some(endpoint),
+            max_retries: 0,
+            ..RerankSettings::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cross_encoder_scores_in_input_order() {
+        let endpoint = serve_response(
+            r#"{"results":[{"index":1,"relevance_score":0.9},{"index":0,"relevance_score":0.2}]}"#,
+        )
+        .await;
+        let reranker = RerankerFactory::from_settings(&settings("cross_encoder", endpoint))
+            .unwrap()
+            .unwrap();
+
+        let scores = reranker
+            .rerank("token refresh", &["Logging format".to_string(), "Refresh tokens rotate".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(scores, vec![0.2, 0.9]);
+    }
+
+    #[tokio::test]
+    async fn test_llm_grades_are_parsed_and_scaled() {
+        let endpoint = serve_response(
+            r#"{"choices":[{"message":{"content":"```json\n[3, 10]\n```"}}]}"#,
+        )
+        .await;
+        let reranker = RerankerFactory::from_settings(&settings("llm", endpoint)).unwrap().unwrap();
+
+        let scores = reranker
+            .rerank("token refresh", &["a".to_string(), "b".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(scores, vec![0.3, 1.0]);
+    }
+
+    #[test]
+    fn test_factory_settings() {
+        assert!(RerankerFactory::from_settings(&RerankSettings::default()).unwrap().is_none());
+        assert!(RerankerFactory::from_settings(&RerankSettings {
+            provider: "cross_encoder".to_string(),
+            ..RerankSettings::default()
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::models::architecture::{ArchitectureFinding, Severity, ViolationRule};
+use crate::models::framework::FrameworkComponent;
+use crate::services::manifest_importer::ManifestKind;
+use serde_json::{json, Value};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Writes architecture findings as a SARIF 2.1.0 log for code scanning tools. Findings point
+/// at the file of the component they name; the others carry the component as a logical location.
+pub struct SarifExporter;
+
+impl SarifExporter {
+    /// `suppressed` findings, acknowledged in the baseline, are included as suppressed results
+    pub fn export(
+        findings: &[ArchitectureFinding],
+        suppressed: &[ArchitectureFinding],
+        components: &[FrameworkComponent],
+    ) -> Value {
+        let mut rules: Vec<(String, &ArchitectureFinding)> = Vec::new();
+        let mut results = Vec::new();
+        let all = findings.iter().map(|f| (f, false)).chain(suppressed.iter().map(|f| (f, true)));
+        for (finding, is_suppressed) in all {
+            let rule_id = Self::rule_id(finding);
+            let rule_index = match rules.iter().position(|(id, _)| *id == rule_id) {
+                Some(index) => index,
+                None => {
+                    rules.push((rule_id.clone(), finding));
+                    rules.len() - 1
+                }
+            };
+
+            let mut result = json!({
+                "ruleId": rule_id,
+                "ruleIndex": rule_index,
+                "level": Self::level(finding.severity),
+                "message": {"text": finding.message},
+            });
+            match Self::file_of(finding, components) {
+                Some(uri) => {
+                    result["locations"] = json!([{"physicalLocation": {"artifactLocation": {"uri": uri}}}]);
+                }
+                None => {
+                    result["locations"] = json!([{"logicalLocations": [{"name": finding.component, "kind": "module"}]}]);
+                }
+            }
+            if is_suppressed {
+                result["suppressions"] = json!([{
+                    "kind": "external",
+                    "justification": "Acknowledged in the architecture baseline",
+                }]);
+            }
+            results.push(result);
+        }
+
+        let rules: Vec<Value> = rules
+            .iter()
+            .map(|(id, finding)| {
+                json!({
+                    "id": id,
+                    "shortDescription": {"text": Self::describe(finding)},
+                    "defaultConfiguration": {"level": Self::level(finding.rule.default_severity())},
+                })
+            })
+            .collect();
+
+        json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                        "rules": rules,
+                    }
+                },
+                "results": results,
+            }]
+        })
+    }
+
+    /// Plugin findings get a rule per validator
+    fn rule_id(finding: &ArchitectureFinding) -> String {
+        let rule = serde_json::to_value(finding.rule)
+            .ok()
+            .and_then(|rule| rule.as_str().map(str::to_string))
+            .unwrap_or_default();
+        match (&finding.rule, &finding.validator) {
+            (ViolationRule::Plugin, Some(validator)) => format!("architecture/{}/{}", rule, validator),
+            _ => format!("architecture/{}", rule),
+        }
+    }
+
+    fn describe(finding: &ArchitectureFinding) -> String {
+        match finding.rule {
+            ViolationRule::LayerDependency => "Dependency on a layer the component's layer may not use".to_string(),
+            ViolationRule::ForbiddenImport => "Forbidden import or package dependency".to_string(),
+            ViolationRule::Naming => "Component name does not match the naming pattern of its layer or type".to_string(),
+            ViolationRule::UnknownLayer => "Component belongs to a layer the architecture rules do not define".to_string(),
+            ViolationRule::DependencyCycle => "Components depend on each other in a cycle".to_string(),
+            ViolationRule::Plugin => format!(
+                "Reported by the {} validator",
+                finding.validator.as_deref().unwrap_or("plugin")
+            ),
+            ViolationRule::Convention => "Name does not follow a project naming convention".to_string(),
+            ViolationRule::Fitness => "Architecture fitness function above its threshold".to_string(),
+        }
+    }
+
+    fn level(severity: Severity) -> &'static str {
+        match severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "note",
+        }
+    }
+
+    /// File of the component a finding names; manifest findings name the manifest itself
+    fn file_of(finding: &ArchitectureFinding, components: &[FrameworkComponent]) -> Option<String> {
+        let path = components
+            .iter()
+            .find(|component| component.component_name == finding.component)
+            .and_then(|component| component.file_path.clone())
+            .or_else(|| {
+                let file_name = finding.component.rsplit('/').next()?;
+                ManifestKind::of(file_name).map(|_| finding.component.clone())
+            })?;
+        Some(path.replace('\\', "/"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(rule: ViolationRule, severity: Severity, component: &str, message: &str) -> ArchitectureFinding {
+        ArchitectureFinding {
+            rule,
+            severity,
+            component: component.to_string(),
+            message: message.to_string(),
+            validator: None,
+            dependency: None,
+            fixes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_export_sarif() {
+        let components = vec![FrameworkComponent {
+            id: "c1".to_string(),
+            project_id: "p1".to_string(),
+            component_name: "LoginPage".to_string(),
+            component_type: "widget".to_string(),
+            architecture_layer: "presentation".to_string(),
+            file_path: Some("lib\\ui\\login_page.dart".to_string()),
+            dependencies: Vec::new(),
+            metadata: None,
+            created_at: None,
+            updated_at: None,
+        }];
+        let mut guard = finding(ViolationRule::Plugin, Severity::Error, "LoginPage", "Dependency guard: LoginPage uses sqflite");
+        guard.validator = Some("dependency_guard".to_string());
+        let findings = vec![
+            finding(ViolationRule::LayerDependency, Severity::Error, "LoginPage", "LoginPage imports data"),
+            finding(ViolationRule::Fitness, Severity::Warning, "presentation", "Fitness function size failed"),
+            guard,
+        ];
+        let suppressed = vec![finding(ViolationRule::ForbiddenImport, Severity::Info, "app/pubspec.yaml", "app declares http")];
+
+        let sarif = SarifExporter::export(&findings, &suppressed, &components);
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        let rule_ids: Vec<&str> = run["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|rule| rule["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            rule_ids,
+            vec![
+                "architecture/layer_dependency",
+                "architecture/fitness",
+                "architecture/plugin/dependency_guard",
+                "architecture/forbidden_import",
+            ]
+        );
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"], "lib/ui/login_page.dart");
+        assert_eq!(results[1]["level"], "warning");
+        assert_eq!(results[1]["locations"][0]["logicalLocations"][0]["name"], "presentation");
+        assert_eq!(results[3]["level"], "note");
+        assert_eq!(results[3]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"], "app/pubspec.yaml");
+        assert_eq!(results[3]["suppressions"][0]["kind"], "external");
+        assert!(results[0].get("suppressions").is_none());
+    }
+}
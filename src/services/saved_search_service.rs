@@ -0,0 +1,380 @@
+//! Named searches stored per project, so agents can re-run a recurring context pull by
+//! name and subscribe to the changes that could alter its results
+
+use crate::models::saved_search::SavedSearch;
+use crate::repositories::SavedSearchRepository;
+use crate::services::hybrid_search_service::{
+    FacetFilters, HybridSearchResult, HybridSearchService, FACET_FIELDS,
+};
+use crate::services::websocket_types::SyncFilters;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Service for Saved Search operations
+#[async_trait]
+pub trait SavedSearchService: Send + Sync {
+    async fn create_saved_search(
+        &self,
+        project_id: &str,
+        name: &str,
+        query: &str,
+        feature_area: Option<&str>,
+        filters: FacetFilters,
+        limit: Option<usize>,
+    ) -> Result<SavedSearch, McpError>;
+    async fn get_saved_search(&self, id: &str) -> Result<Option<SavedSearch>, McpError>;
+    async fn find_saved_search(&self, project_id: &str, name: &str) -> Result<Option<SavedSearch>, McpError>;
+    async fn list_saved_searches(&self, project_id: &str) -> Result<Vec<SavedSearch>, McpError>;
+    async fn update_saved_search(&self, search: &SavedSearch) -> Result<SavedSearch, McpError>;
+    async fn delete_saved_search(&self, id: &str) -> Result<bool, McpError>;
+    /// Run with the ranking configuration currently in effect, truncated to the search's limit
+    async fn run_saved_search(&self, search: &SavedSearch) -> Result<HybridSearchResult, McpError>;
+}
+
+/// WebSocket `Subscribe` filters for changes that can alter a saved search's results:
+/// writes to its project, narrowed to the entity types it filters on
+pub fn subscribe_filters(search: &SavedSearch) -> SyncFilters {
+    SyncFilters {
+        project_ids: Some(vec![search.project_id.clone()]),
+        entity_types: search
+            .filters
+            .get("entity_type")
+            .filter(|types| !types.is_empty())
+            .cloned(),
+        feature_areas: None,
+        change_types: None,
+    }
+}
+
+pub struct SavedSearchServiceImpl<R: SavedSearchRepository> {
+    repository: R,
+    hybrid_search: Arc<dyn HybridSearchService>,
+}
+
+impl<R: SavedSearchRepository> SavedSearchServiceImpl<R> {
+    pub fn new(repository: R, hybrid_search: Arc<dyn HybridSearchService>) -> Self {
+        Self {
+            repository,
+            hybrid_search,
+        }
+    }
+
+    fn validate(search: &SavedSearch) -> Result<(), McpError> {
+        if search.name.trim().is_empty() {
+            return Err(McpError::invalid_params("Saved search name must not be empty", None));
+        }
+        if search.query.trim().is_empty() {
+            return Err(McpError::invalid_params("Saved search query must not be empty", None));
+        }
+        if let Some(facet) = search
+            .filters
+            .keys()
+            .find(|facet| *facet != "entity_type" && !FACET_FIELDS.contains(&facet.as_str()))
+        {
+            return Err(McpError::invalid_params(
+                format!(
+                    "Unknown filter facet \"{facet}\"; expected entity_type or one of {}",
+                    FACET_FIELDS.join(", ")
+                ),
+                None,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Names identify searches within a project, so `run_saved_search` can look them up
+    async fn ensure_name_available(&self, search: &SavedSearch) -> Result<(), McpError> {
+        match self.repository.find_by_name(&search.project_id, &search.name).await? {
+            Some(existing) if existing.id != search.id => Err(McpError::invalid_params(
+                format!(
+                    "A saved search named \"{}\" already exists in project {}",
+                    search.name, search.project_id
+                ),
+                None,
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl<R: SavedSearchRepository> SavedSearchService for SavedSearchServiceImpl<R> {
+    async fn create_saved_search(
+        &self,
+        project_id: &str,
+        name: &str,
+        query: &str,
+        feature_area: Option<&str>,
+        filters: FacetFilters,
+        limit: Option<usize>,
+    ) -> Result<SavedSearch, McpError> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let search = SavedSearch {
+            id: Uuid::new_v4().to_string(),
+            project_id: project_id.to_string(),
+            name: name.trim().to_string(),
+            query: query.to_string(),
+            feature_area: feature_area.map(|s| s.to_string()),
+            filters,
+            limit,
+            created_at: Some(now.clone()),
+            updated_at: Some(now),
+        };
+        Self::validate(&search)?;
+        self.ensure_name_available(&search).await?;
+        self.repository.create(&search).await
+    }
+
+    async fn get_saved_search(&self, id: &str) -> Result<Option<SavedSearch>, McpError> {
+        self.repository.find_by_id(id).await
+    }
+
+    async fn find_saved_search(&self, project_id: &str, name: &str) -> Result<Option<SavedSearch>, McpError> {
+        self.repository.find_by_name(project_id, name.trim()).await
+    }
+
+    async fn list_saved_searches(&self, project_id: &str) -> Result<Vec<SavedSearch>, McpError> {
+        self.repository.find_by_project_id(project_id).await
+    }
+
+    async fn update_saved_search(&self, search: &SavedSearch) -> Result<SavedSearch, McpError> {
+        let mut search = search.clone();
+        search.name = search.name.trim().to_string();
+        search.updated_at = Some(chrono::Utc::now().to_rfc3339());
+        Self::validate(&search)?;
+        self.ensure_name_available(&search).await?;
+        self.repository.update(&search).await
+    }
+
+    async fn delete_saved_search(&self, id: &str) -> Result<bool, McpError> {
+        self.repository.delete(id).await
+    }
+
+    async fn run_saved_search(&self, search: &SavedSearch) -> Result<HybridSearchResult, McpError> {
+        let mut result = self
+            .hybrid_search
+            .faceted_search(
+                &search.project_id,
+                &search.query,
+                search.feature_area.as_deref(),
+                &search.filters,
+            )
+            .await
+            .map_err(|e| McpError::internal_error(format!("Search failed: {e}"), None))?;
+        if let Some(limit) = search.limit {
+            result.ranked_results.truncate(limit);
+        }
+        result.total_results = result.ranked_results.len();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::SqliteSavedSearchRepository;
+    use crate::models::embedding::VectorSearchQuery;
+    use crate::services::context_query_service::ContextQueryResult;
+    use crate::services::hybrid_search_service::{
+        FusionMethod, HybridSearchError, RankedSearchResult, ScoreBreakdown, SearchStrategy,
+    };
+    use crate::services::semantic_search_service::EnhancedSearchResult;
+    use parking_lot::Mutex;
+
+    /// Project, query, feature area and filters of a faceted search
+    type SearchCall = (String, String, Option<String>, FacetFilters);
+
+    /// Records the arguments of the last faceted search and returns three hits
+    #[derive(Default)]
+    struct RecordingSearch {
+        last_call: Mutex<Option<SearchCall>>,
+    }
+
+    #[async_trait]
+    impl HybridSearchService for RecordingSearch {
+        async fn hybrid_search(
+            &self,
+            project_id: &str,
+            query_text: &str,
+            feature_area: Option<&str>,
+            _task_type: Option<&str>,
+            _components: &[String],
+        ) -> Result<HybridSearchResult, HybridSearchError> {
+            self.faceted_search(project_id, query_text, feature_area, &FacetFilters::new()).await
+        }
+
+        async fn faceted_search(
+            &self,
+            project_id: &str,
+            query_text: &str,
+            feature_area: Option<&str>,
+            filters: &FacetFilters,
+        ) -> Result<HybridSearchResult, HybridSearchError> {
+            *self.last_call.lock() = Some((
+                project_id.to_string(),
+                query_text.to_string(),
+                feature_area.map(str::to_string),
+                filters.clone(),
+            ));
+            let ranked_results: Vec<RankedSearchResult> = (0..3)
+                .map(|i| RankedSearchResult {
+                    entity_id: format!("rule-{i}"),
+                    entity_type: "business_rule".to_string(),
+                    title: None,
+                    score: 1.0 - i as f32 * 0.1,
+                    breakdown: ScoreBreakdown {
+                        fusion: FusionMethod::WeightedSum,
+                        vector_score: None,
+                        vector_rank: None,
+                        vector_contribution: 0.0,
+                        keyword_score: None,
+                        keyword_rank: None,
+                        keyword_contribution: 0.0,
+                        entity_type_boost: 1.0,
+                        popularity_boost: 1.0,
+                        rerank_score: None,
+                    },
+                    project_id: Some(project_id.to_string()),
+                    facets: Default::default(),
+                    content: String::new(),
+                })
+                .collect();
+            Ok(HybridSearchResult {
+                semantic_results: Vec::new(),
+                traditional_results: ContextQueryResult {
+                    business_rules: Vec::new(),
+                    architectural_decisions: Vec::new(),
+                    performance_requirements: Vec::new(),
+                    security_policies: Vec::new(),
+                    project_conventions: Vec::new(),
+                    ..Default::default()
+                },
+                combined_score: 1.0,
+                search_strategy: SearchStrategy::Hybrid,
+                total_results: ranked_results.len(),
+                ranked_results,
+                facet_counts: Default::default(),
+                query_expansions: Vec::new(),
+            })
+        }
+
+        async fn cross_project_search(
+            &self,
+            _project_ids: &[String],
+            _query_text: &str,
+            _feature_area: Option<&str>,
+            _filters: &FacetFilters,
+        ) -> Result<HybridSearchResult, HybridSearchError> {
+            unimplemented!("saved searches are scoped to one project")
+        }
+
+        async fn semantic_search(
+            &self,
+            _query: &VectorSearchQuery,
+        ) -> Result<Vec<EnhancedSearchResult>, HybridSearchError> {
+            Ok(Vec::new())
+        }
+
+        async fn traditional_search(
+            &self,
+            _project_id: &str,
+            _feature_area: &str,
+            _task_type: &str,
+            _components: &[String],
+        ) -> Result<ContextQueryResult, HybridSearchError> {
+            unimplemented!("not used by saved searches")
+        }
+
+        async fn get_search_suggestions(
+            &self,
+            _partial_query: &str,
+            _project_id: Option<&str>,
+        ) -> Result<Vec<String>, HybridSearchError> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn service(search: Arc<RecordingSearch>) -> SavedSearchServiceImpl<SqliteSavedSearchRepository> {
+        let conn = crate::db::init::init_db(":memory:").unwrap();
+        conn.execute("INSERT INTO projects (id, name) VALUES ('p1', 'Payments')", [])
+            .unwrap();
+        let db = Arc::new(std::sync::Mutex::new(conn));
+        SavedSearchServiceImpl::new(SqliteSavedSearchRepository::new(db), search)
+    }
+
+    fn filters(entries: &[(&str, &[&str])]) -> FacetFilters {
+        entries
+            .iter()
+            .map(|(facet, values)| (facet.to_string(), values.iter().map(|v| v.to_string()).collect()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_saved_search_round_trip_and_unique_names() {
+        let service = service(Arc::new(RecordingSearch::default()));
+        let created = service
+            .create_saved_search(
+                "p1",
+                "payment rules",
+                "refund limits",
+                Some("billing"),
+                filters(&[("entity_type", &["business_rule"]), ("tags", &["payments"])]),
+                Some(2),
+            )
+            .await
+            .unwrap();
+
+        let found = service.find_saved_search("p1", "payment rules").await.unwrap().unwrap();
+        assert_eq!(found, created);
+        assert_eq!(service.list_saved_searches("p1").await.unwrap().len(), 1);
+
+        let duplicate = service
+            .create_saved_search("p1", "payment rules", "other", None, FacetFilters::new(), None)
+            .await;
+        assert!(duplicate.is_err());
+        let unknown_facet = service
+            .create_saved_search("p1", "by owner", "x", None, filters(&[("owner", &["me"])]), None)
+            .await;
+        assert!(unknown_facet.is_err());
+
+        let mut renamed = created.clone();
+        renamed.name = "refunds".to_string();
+        service.update_saved_search(&renamed).await.unwrap();
+        assert!(service.find_saved_search("p1", "payment rules").await.unwrap().is_none());
+        assert!(service.delete_saved_search(&created.id).await.unwrap());
+        assert!(service.get_saved_search(&created.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_run_uses_stored_query_and_limit() {
+        let search = Arc::new(RecordingSearch::default());
+        let service = service(search.clone());
+        let saved = service
+            .create_saved_search(
+                "p1",
+                "payment rules",
+                "refund limits",
+                Some("billing"),
+                filters(&[("entity_type", &["business_rule"])]),
+                Some(2),
+            )
+            .await
+            .unwrap();
+
+        let result = service.run_saved_search(&saved).await.unwrap();
+        assert_eq!(result.ranked_results.len(), 2);
+        assert_eq!(result.total_results, 2);
+        let (project_id, query, feature_area, applied) = search.last_call.lock().clone().unwrap();
+        assert_eq!(
+            (project_id.as_str(), query.as_str(), feature_area.as_deref()),
+            ("p1", "refund limits", Some("billing"))
+        );
+        assert_eq!(applied, saved.filters);
+
+        let subscription = subscribe_filters(&saved);
+        assert_eq!(subscription.project_ids, Some(vec!["p1".to_string()]));
+        assert_eq!(subscription.entity_types, Some(vec!["business_rule".to_string()]));
+    }
+}
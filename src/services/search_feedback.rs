@@ -0,0 +1,145 @@
+use crate::services::analytics_service::SearchClick;
+use crate::services::hybrid_search_service::RankedSearchResult;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How long after a search fetching one of its results counts as a click
+pub const CLICK_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+/// Clicks older than this many half-lives add less than 1/16 each and are not loaded
+pub const CLICK_HISTORY_HALF_LIVES: f32 = 4.0;
+
+/// Where an entity appeared in a recent search
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub query: String,
+    pub project_id: Option<String>,
+    pub entity_type: String,
+    /// 1-based position in the returned results
+    pub rank: usize,
+    returned_at: Instant,
+}
+
+/// Remembers which entities recent searches returned, so a later `get_entity`
+/// can be attributed to the search as a click
+#[derive(Debug, Default)]
+pub struct SearchClickTracker {
+    hits: Mutex<HashMap<String, SearchHit>>,
+}
+
+impl SearchClickTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Note the results returned for `query`; an entity returned again is attributed to
+    /// the latest search
+    pub fn record_results(&self, query: &str, results: &[RankedSearchResult]) {
+        let now = Instant::now();
+        let mut hits = self.hits.lock();
+        hits.retain(|_, hit| now.duration_since(hit.returned_at) < CLICK_WINDOW);
+        for (index, result) in results.iter().enumerate() {
+            hits.insert(
+                result.entity_id.clone(),
+                SearchHit {
+                    query: query.to_string(),
+                    project_id: result.project_id.clone(),
+                    entity_type: result.entity_type.clone(),
+                    rank: index + 1,
+                    returned_at: now,
+                },
+            );
+        }
+    }
+
+    /// The search that returned `entity_id` within `CLICK_WINDOW`, if any. Each search
+    /// result counts as one click at most, so the hit is forgotten once taken.
+    pub fn take_click(&self, entity_id: &str) -> Option<SearchHit> {
+        let hit = self.hits.lock().remove(entity_id)?;
+        (hit.returned_at.elapsed() < CLICK_WINDOW).then_some(hit)
+    }
+}
+
+/// Decayed click count per entity: each click counts 1 when it happens and half as
+/// much every `half_life_days` after
+pub fn click_popularity(clicks: &[SearchClick], now: DateTime<Utc>, half_life_days: f32) -> HashMap<String, f32> {
+    let mut popularity = HashMap::new();
+    if half_life_days <= 0.0 {
+        return popularity;
+    }
+    for click in clicks {
+        let age_days = (now - click.timestamp).num_seconds().max(0) as f32 / 86_400.0;
+        *popularity.entry(click.entity_id.clone()).or_insert(0.0) += 0.5f32.powf(age_days / half_life_days);
+    }
+    popularity
+}
+
+/// Score multiplier for a decayed click count: 1.0 without clicks, approaching
+/// `1 + max_boost` as clicks accumulate (half of it at one recent click)
+pub fn popularity_boost(popularity: f32, max_boost: f32) -> f32 {
+    1.0 + max_boost * popularity / (1.0 + popularity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::hybrid_search_service::{FusionMethod, ScoreBreakdown};
+
+    fn result(entity_id: &str) -> RankedSearchResult {
+        RankedSearchResult {
+            entity_id: entity_id.to_string(),
+            entity_type: "business_rule".to_string(),
+            title: None,
+            score: 1.0,
+            breakdown: ScoreBreakdown {
+                fusion: FusionMethod::WeightedSum,
+                vector_score: None,
+                vector_rank: None,
+                vector_contribution: 0.0,
+                keyword_score: None,
+                keyword_rank: None,
+                keyword_contribution: 0.0,
+                entity_type_boost: 1.0,
+                popularity_boost: 1.0,
+                rerank_score: None,
+            },
+            project_id: Some("p1".to_string()),
+            facets: Default::default(),
+            content: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_clicks_are_attributed_to_the_latest_search_once() {
+        let tracker = SearchClickTracker::new();
+        tracker.record_results("token refresh", &[result("rule-1"), result("rule-2")]);
+        tracker.record_results("refresh", &[result("rule-2")]);
+
+        let hit = tracker.take_click("rule-2").unwrap();
+        assert_eq!(hit.query, "refresh");
+        assert_eq!(hit.rank, 1);
+        assert_eq!(hit.project_id.as_deref(), Some("p1"));
+        assert!(tracker.take_click("rule-2").is_none());
+        assert_eq!(tracker.take_click("rule-1").unwrap().query, "token refresh");
+        assert!(tracker.take_click("never-returned").is_none());
+    }
+
+    #[test]
+    fn test_click_popularity_decays() {
+        let now = Utc::now();
+        let click = |entity_id: &str, days_ago: i64| SearchClick {
+            entity_id: entity_id.to_string(),
+            project_id: None,
+            timestamp: now - chrono::Duration::days(days_ago),
+        };
+        let popularity = click_popularity(&[click("a", 0), click("a", 14), click("b", 28)], now, 14.0);
+
+        assert!((popularity["a"] - 1.5).abs() < 1e-4);
+        assert!((popularity["b"] - 0.25).abs() < 1e-4);
+        assert_eq!(popularity_boost(0.0, 0.2), 1.0);
+        assert!((popularity_boost(1.0, 0.2) - 1.1).abs() < 1e-6);
+        assert!(popularity_boost(100.0, 0.2) < 1.2);
+    }
+}
@@ -1,12 +1,13 @@
 use crate::models::enhanced_context::EnhancedContextItem;
 use crate::models::embedding::{ContextEmbedding, VectorSearchQuery};
-use crate::repositories::embedding_repository::{EmbeddingRepository, EmbeddingRepositoryError};
+use crate::repositories::embedding_repository::{EmbeddingRepository, EmbeddingRepositoryError, IndexEntry};
 use crate::services::embedding_service::{EmbeddingService, EmbeddingError};
 use crate::services::semantic_search_service::{SemanticSearchService, SemanticSearchError, SearchIndexStats};
+use crate::services::vector_embedding_integration::parent_id;
 use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, error, info};
@@ -150,6 +151,39 @@ pub struct IndexPerformanceMetrics {
     pub disk_usage_mb: f64,
 }
 
+/// Ids listed per kind of drift in `IndexDrift`
+const DRIFT_SAMPLE: usize = 20;
+
+/// What the search index holds compared with the database, as reported by `search_index_status`
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchIndexStatus {
+    /// Entities with at least one stored vector
+    pub documents_indexed: usize,
+    pub documents_by_entity_type: BTreeMap<String, usize>,
+    /// Stored vectors, counting each chunk of a chunked entity
+    pub vectors_stored: usize,
+    pub chunked_documents: usize,
+    /// Storage taken by the vectors and their metadata
+    pub index_size_bytes: u64,
+    pub last_indexed_at: Option<DateTime<Utc>>,
+    /// Last `reindex_project` since the server started
+    pub last_rebuild_at: Option<DateTime<Utc>>,
+    pub drift: IndexDrift,
+}
+
+/// Differences between the entities that should be indexed and the index entries
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IndexDrift {
+    /// Entities in the database with text to index
+    pub expected: usize,
+    /// Entities without a stored vector
+    pub missing: usize,
+    /// Indexed ids without a matching entity, e.g. left behind by failed deletes
+    pub orphaned: usize,
+    pub missing_ids: Vec<String>,
+    pub orphaned_ids: Vec<String>,
+}
+
 /// Pending index operation
 #[derive(Debug, Clone)]
 pub struct PendingIndexOperation {
@@ -213,6 +247,17 @@ pub trait SearchIndexManager: Send + Sync {
     
     /// Get operation statistics
     async fn get_operation_stats(&self) -> Result<IndexOperationStats, IndexManagerError>;
+
+    /// Summarize the stored index, in one project when given, and compare it with
+    /// `expected`, the entities that should be indexed (id to entity type)
+    async fn get_index_status(
+        &self,
+        project_id: Option<&str>,
+        expected: &HashMap<String, String>,
+    ) -> Result<SearchIndexStatus, IndexManagerError>;
+
+    /// Note that a project's index was rebuilt, for `SearchIndexStatus::last_rebuild_at`
+    async fn record_rebuild(&self, project_id: &str);
 }
 
 /// Implementation of SearchIndexManager
@@ -224,6 +269,7 @@ pub struct SearchIndexManagerImpl {
     pending_operations: Arc<Mutex<Vec<PendingIndexOperation>>>,
     operation_stats: Arc<RwLock<IndexOperationStats>>,
     content_hashes: Arc<RwLock<HashMap<String, String>>>, // context_id -> content_hash
+    last_rebuilds: Arc<RwLock<HashMap<String, DateTime<Utc>>>>, // project_id -> rebuild time
 }
 
 impl SearchIndexManagerImpl {
@@ -241,6 +287,7 @@ impl SearchIndexManagerImpl {
             pending_operations: Arc::new(Mutex::new(Vec::new())),
             operation_stats: Arc::new(RwLock::new(IndexOperationStats::default())),
             content_hashes: Arc::new(RwLock::new(HashMap::new())),
+            last_rebuilds: Arc::new(RwLock::new(HashMap::new())),
         }
     }
     
@@ -664,6 +711,7 @@ impl SearchIndexManager for SearchIndexManagerImpl {
             let hash = self.calculate_content_hash(context);
             hashes.insert(context.id.clone(), hash);
         }
+        self.record_rebuild(project_id).await;
         
         info!("Successfully rebuilt index for project: {}", project_id);
         Ok(())
@@ -697,6 +745,70 @@ impl SearchIndexManager for SearchIndexManagerImpl {
         let stats = self.operation_stats.read().await;
         Ok(stats.clone())
     }
+
+    async fn get_index_status(
+        &self,
+        project_id: Option<&str>,
+        expected: &HashMap<String, String>,
+    ) -> Result<SearchIndexStatus, IndexManagerError> {
+        let entries: Vec<IndexEntry> = self.embedding_repository.list_index_entries(project_id).await?;
+
+        // Entity id -> (entity type, stored vectors)
+        let mut documents: HashMap<&str, (&str, usize)> = HashMap::new();
+        for entry in &entries {
+            let document = documents
+                .entry(parent_id(&entry.context_id))
+                .or_insert((entry.content_type.as_deref().unwrap_or("unknown"), 0));
+            document.1 += 1;
+        }
+        let mut documents_by_entity_type = BTreeMap::new();
+        for (entity_type, _) in documents.values() {
+            *documents_by_entity_type.entry(entity_type.to_string()).or_default() += 1;
+        }
+
+        let indexed: HashSet<&str> = documents.keys().copied().collect();
+        let mut missing_ids: Vec<String> = expected
+            .keys()
+            .filter(|id| !indexed.contains(id.as_str()))
+            .cloned()
+            .collect();
+        let mut orphaned_ids: Vec<String> = indexed
+            .iter()
+            .filter(|id| !expected.contains_key(**id))
+            .map(|id| id.to_string())
+            .collect();
+        missing_ids.sort();
+        orphaned_ids.sort();
+        let drift = IndexDrift {
+            expected: expected.len(),
+            missing: missing_ids.len(),
+            orphaned: orphaned_ids.len(),
+            missing_ids: missing_ids.into_iter().take(DRIFT_SAMPLE).collect(),
+            orphaned_ids: orphaned_ids.into_iter().take(DRIFT_SAMPLE).collect(),
+        };
+
+        let last_rebuilds = self.last_rebuilds.read().await;
+        let last_rebuild_at = match project_id {
+            Some(project_id) => last_rebuilds.get(project_id).copied(),
+            None => last_rebuilds.values().max().copied(),
+        };
+
+        Ok(SearchIndexStatus {
+            documents_indexed: documents.len(),
+            documents_by_entity_type,
+            vectors_stored: entries.len(),
+            chunked_documents: documents.values().filter(|(_, vectors)| *vectors > 1).count(),
+            index_size_bytes: entries.iter().map(|entry| entry.size_bytes).sum(),
+            last_indexed_at: entries.iter().filter_map(|entry| entry.created_at).max(),
+            last_rebuild_at,
+            drift,
+        })
+    }
+
+    async fn record_rebuild(&self, project_id: &str) {
+        let mut last_rebuilds = self.last_rebuilds.write().await;
+        last_rebuilds.insert(project_id.to_string(), Utc::now());
+    }
 }
 
 #[cfg(test)]
@@ -705,6 +817,7 @@ mod tests {
     use crate::models::enhanced_context::{ContextContent, ContextType};
     use crate::services::semantic_search_service::{SemanticSearchService, EnhancedSearchResult};
     use crate::models::embedding::VectorSearchResult;
+    use crate::repositories::embedding_repository::ModelEmbeddingCount;
     use std::collections::HashMap;
     
     // Mock implementations for testing
@@ -805,6 +918,26 @@ mod tests {
         async fn embedding_exists(&self, _context_id: &str) -> Result<bool, EmbeddingRepositoryError> {
             Ok(false)
         }
+
+        async fn get_embedding_for_model(&self, _context_id: &str, _model: &str, _dimension: usize) -> Result<Option<ContextEmbedding>, EmbeddingRepositoryError> {
+            Ok(None)
+        }
+
+        async fn count_embeddings_by_model(&self) -> Result<Vec<ModelEmbeddingCount>, EmbeddingRepositoryError> {
+            Ok(Vec::new())
+        }
+
+        async fn delete_embeddings_except(&self, _model: &str, _dimension: usize) -> Result<u64, EmbeddingRepositoryError> {
+            Ok(0)
+        }
+
+        async fn delete_chunks(&self, _entity_id: &str, _model: Option<&str>) -> Result<u64, EmbeddingRepositoryError> {
+            Ok(0)
+        }
+
+        async fn list_index_entries(&self, _project_id: Option<&str>) -> Result<Vec<IndexEntry>, EmbeddingRepositoryError> {
+            Ok(Vec::new())
+        }
     }
     
     struct MockEmbeddingService;
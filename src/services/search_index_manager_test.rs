@@ -1,6 +1,6 @@
 use crate::models::enhanced_context::{EnhancedContextItem, ContextContent, ContextType};
 use crate::models::embedding::{ContextEmbedding, VectorSearchQuery, VectorSearchResult};
-use crate::repositories::embedding_repository::{EmbeddingRepository, EmbeddingRepositoryError, EmbeddingStats};
+use crate::repositories::embedding_repository::{EmbeddingRepository, EmbeddingRepositoryError, EmbeddingStats, IndexEntry, ModelEmbeddingCount};
 use crate::services::embedding_service::{EmbeddingService, EmbeddingError};
 use crate::services::semantic_search_service::{SemanticSearchService, SemanticSearchError, SearchIndexStats, EnhancedSearchResult};
 use crate::services::search_index_manager::{
@@ -119,6 +119,41 @@ impl EmbeddingRepository for MockEmbeddingRepository {
         let responses = self.embedding_exists_responses.lock().await;
         Ok(responses.get(context_id).copied().unwrap_or(false))
     }
+
+    async fn get_embedding_for_model(&self, _context_id: &str, _model: &str, _dimension: usize) -> Result<Option<ContextEmbedding>, EmbeddingRepositoryError> {
+        Ok(None)
+    }
+
+    async fn count_embeddings_by_model(&self) -> Result<Vec<ModelEmbeddingCount>, EmbeddingRepositoryError> {
+        Ok(Vec::new())
+    }
+
+    async fn delete_embeddings_except(&self, _model: &str, _dimension: usize) -> Result<u64, EmbeddingRepositoryError> {
+        Ok(0)
+    }
+
+    async fn delete_chunks(&self, _entity_id: &str, _model: Option<&str>) -> Result<u64, EmbeddingRepositoryError> {
+        Ok(0)
+    }
+
+    async fn list_index_entries(&self, project_id: Option<&str>) -> Result<Vec<IndexEntry>, EmbeddingRepositoryError> {
+        let embeddings = self.embeddings.lock().await;
+        Ok(embeddings
+            .values()
+            .filter(|embedding| {
+                project_id.is_none_or(|id| embedding.metadata.custom_fields.get("project_id").and_then(|v| v.as_str()) == Some(id))
+            })
+            .map(|embedding| IndexEntry {
+                context_id: embedding.context_id.clone(),
+                project_id: embedding.metadata.custom_fields.get("project_id").and_then(|v| v.as_str()).map(str::to_string),
+                content_type: Some(embedding.metadata.content_type.clone()),
+                model: embedding.embedding_model.clone(),
+                dimension: embedding.embedding_vector.len(),
+                size_bytes: (embedding.embedding_vector.len() * 4) as u64,
+                created_at: Some(embedding.created_at),
+            })
+            .collect())
+    }
 }
 
 /// Mock semantic search service for testing
@@ -839,4 +874,53 @@ mod tests {
         let (_, update_count, _) = semantic_service.get_call_counts().await;
         assert_eq!(update_count, 1); // Only high quality context
     }
+    #[tokio::test]
+    async fn test_index_status_reports_contents_and_drift() {
+        let (manager, _, embedding_repo, _) = create_test_manager().await;
+
+        for (context_id, content_type) in [
+            ("rule-1", "business_rule"),
+            ("rule-1#chunk-1", "business_rule"),
+            ("decision-1", "architectural_decision"),
+            ("deleted-1", "business_rule"),
+        ] {
+            let mut embedding = ContextEmbedding::new(
+                context_id.to_string(),
+                vec![0.1; 4],
+                "test-model".to_string(),
+                "1.0".to_string(),
+                "hash".to_string(),
+            );
+            embedding.metadata.content_type = content_type.to_string();
+            embedding.metadata.custom_fields.insert("project_id".to_string(), serde_json::json!("test-project"));
+            embedding_repo.store_embedding(&embedding).await.unwrap();
+        }
+        manager.record_rebuild("test-project").await;
+
+        let expected: HashMap<String, String> = [
+            ("rule-1", "business_rule"),
+            ("decision-1", "architectural_decision"),
+            ("phase-1", "development_phase"),
+        ]
+        .into_iter()
+        .map(|(id, entity_type)| (id.to_string(), entity_type.to_string()))
+        .collect();
+        let status = manager.get_index_status(Some("test-project"), &expected).await.unwrap();
+
+        assert_eq!(status.documents_indexed, 3);
+        assert_eq!(status.vectors_stored, 4);
+        assert_eq!(status.chunked_documents, 1);
+        assert_eq!(status.documents_by_entity_type.get("business_rule"), Some(&2));
+        assert_eq!(status.documents_by_entity_type.get("architectural_decision"), Some(&1));
+        assert_eq!(status.index_size_bytes, 64);
+        assert!(status.last_indexed_at.is_some());
+        assert!(status.last_rebuild_at.is_some());
+        assert_eq!(status.drift.expected, 3);
+        assert_eq!(status.drift.missing_ids, vec!["phase-1".to_string()]);
+        assert_eq!(status.drift.orphaned_ids, vec!["deleted-1".to_string()]);
+
+        let other = manager.get_index_status(Some("other-project"), &HashMap::new()).await.unwrap();
+        assert_eq!(other.vectors_stored, 0);
+        assert!(other.last_rebuild_at.is_none());
+    }
 }
\ No newline at end of file
@@ -357,6 +357,7 @@ impl ContextQueryService for MockContextQueryService {
         _feature_area: &str,
         _task_type: &str,
         _components: &[String],
+        _token_budget: Option<usize>,
     ) -> Result<ContextQueryResult, McpError> {
         // Return empty results for simplicity in testing
         Ok(ContextQueryResult {
@@ -365,6 +366,7 @@ impl ContextQueryService for MockContextQueryService {
             performance_requirements: Vec::new(),
             security_policies: Vec::new(),
             project_conventions: Vec::new(),
+            ..Default::default()
         })
     }
 }
@@ -4,6 +4,7 @@ use crate::models::embedding::{
 use crate::models::enhanced_context::{EnhancedContextItem, ContextType};
 use crate::repositories::embedding_repository::{EmbeddingRepository, EmbeddingRepositoryError};
 use crate::services::embedding_service::{EmbeddingService, EmbeddingError};
+use crate::services::vector_embedding_integration::merge_chunk_hits;
 use async_trait::async_trait;
 use regex::Regex;
 use std::collections::HashMap;
@@ -467,7 +468,12 @@ impl SemanticSearchServiceImpl {
     /// Get or generate query embedding with caching
     async fn get_query_embedding(&self, query_text: &str) -> Result<Vec<f32>, SemanticSearchError> {
         if self.config.cache_query_embeddings {
-            let cache_key = format!("{:x}", md5::compute(query_text.as_bytes()));
+            // Keyed by model too, so vectors cached before a migration aren't reused after it
+            let cache_key = format!(
+                "{}:{:x}",
+                self.embedding_service.get_model_info().model_name,
+                md5::compute(query_text.as_bytes())
+            );
             
             // Check cache first
             {
@@ -659,6 +665,7 @@ impl SemanticSearchService for SemanticSearchServiceImpl {
         // Create enhanced query with embedding and filters based on intent
         let mut enhanced_query = query.clone();
         enhanced_query.query_embedding = Some(query_embedding);
+        enhanced_query.filters.embedding_model = Some(self.embedding_service.get_model_info().model_name);
         
         // Apply content type filters based on intent
         if !processed_query.content_type_hints.is_empty() {
@@ -681,9 +688,11 @@ impl SemanticSearchService for SemanticSearchServiceImpl {
             .and_then(|ids| ids.first())
             .map(|s| s.as_str());
         
-        let mut vector_results = self.embedding_repository
-            .find_similar_embeddings(&enhanced_query, project_filter)
-            .await?;
+        let mut vector_results = merge_chunk_hits(
+            self.embedding_repository
+                .find_similar_embeddings(&enhanced_query, project_filter)
+                .await?,
+        );
         
         let search_time = search_start.elapsed().as_millis() as u64;
         
@@ -735,29 +744,67 @@ impl SemanticSearchService for SemanticSearchServiceImpl {
     async fn find_similar_contexts(&self, context_id: &str, max_results: usize) -> Result<Vec<EnhancedSearchResult>, SemanticSearchError> {
         debug!("Finding similar contexts to: {}", context_id);
         
-        // Get the embedding for the source context
-        let source_embedding = self.embedding_repository
-            .get_embedding_by_context_id(context_id)
+        // Get the embedding for the source context, preferring the active model's while a
+        // migration keeps vectors from two models
+        let model = self.embedding_service.get_model_info();
+        let source_embedding = match self
+            .embedding_repository
+            .get_embedding_for_model(context_id, &model.model_name, model.embedding_dimension)
             .await?
-            .ok_or_else(|| SemanticSearchError::QueryProcessingError {
-                message: format!("No embedding found for context: {}", context_id)
-            })?;
+        {
+            Some(embedding) => embedding,
+            None => self.embedding_repository
+                .get_embedding_by_context_id(context_id)
+                .await?
+                .ok_or_else(|| SemanticSearchError::QueryProcessingError {
+                    message: format!("No embedding found for context: {}", context_id)
+                })?,
+        };
         
-        // Create a search query using the source embedding
+        // Compare the stored vector directly; `search` would embed the query text instead
         let query = VectorSearchQuery {
-            query_text: "similar_context_search".to_string(),
-            query_embedding: Some(source_embedding.embedding_vector),
+            query_text: String::new(),
+            query_embedding: Some(source_embedding.embedding_vector.clone()),
             similarity_threshold: 0.5, // Lower threshold for similarity search
-            max_results,
-            filters: Default::default(),
+            max_results: max_results + 1,
+            filters: crate::models::embedding::SearchFilters {
+                embedding_model: Some(source_embedding.embedding_model.clone()),
+                ..Default::default()
+            },
             ranking_method: crate::models::embedding::RankingMethod::CosineSimilarity,
         };
-        
-        let results = self.search(&query).await?;
-        
-        // Filter out the source context itself
-        let filtered_results: Vec<_> = results.into_iter()
-            .filter(|r| r.vector_result.context_id != context_id)
+        let project_id = source_embedding
+            .metadata
+            .custom_fields
+            .get("project_id")
+            .and_then(|v| v.as_str());
+
+        let results = self
+            .embedding_repository
+            .find_similar_embeddings(&query, project_id)
+            .await?;
+
+        // Filter out the source context itself, including its other chunks
+        let filtered_results: Vec<_> = merge_chunk_hits(results)
+            .into_iter()
+            .filter(|r| r.context_id != context_id)
+            .take(max_results)
+            .map(|vector_result| EnhancedSearchResult {
+                relevance_explanation: format!(
+                    "Cosine similarity to {}: {:.3}",
+                    context_id, vector_result.similarity_score
+                ),
+                context_item: None,
+                search_metadata: SearchMetadata {
+                    query_processing_time_ms: 0,
+                    embedding_generation_time_ms: 0,
+                    similarity_calculation_time_ms: 0,
+                    total_candidates_evaluated: 0,
+                    filters_applied: vec!["similarity_threshold".to_string()],
+                    ranking_method_used: query.ranking_method.as_str().to_string(),
+                },
+                vector_result,
+            })
             .collect();
         
         info!("Found {} similar contexts to {}", filtered_results.len(), context_id);
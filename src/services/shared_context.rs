@@ -0,0 +1,100 @@
+use crate::services::context_query_service::ContextQueryResult;
+use std::collections::HashSet;
+
+/// Entity types `promote_to_shared` copies to the shared organization project
+pub const PROMOTABLE_ENTITY_TYPES: &[&str] = &["business_rule", "project_convention"];
+
+/// Add the rules and conventions of the shared project's `query_context` result to a project's,
+/// ranked among the project's own items, except the copies of entities in `promoted_from_here`
+pub fn merge_shared(result: &mut ContextQueryResult, shared: ContextQueryResult, promoted_from_here: &HashSet<String>) {
+    let merged = |id: &String| !promoted_from_here.contains(id);
+    result
+        .business_rules
+        .extend(shared.business_rules.into_iter().filter(|rule| merged(&rule.id)));
+    result
+        .project_conventions
+        .extend(shared.project_conventions.into_iter().filter(|convention| merged(&convention.id)));
+    result.ranking.extend(
+        shared
+            .ranking
+            .into_iter()
+            .filter(|item| PROMOTABLE_ENTITY_TYPES.contains(&item.entity_type.as_str()) && merged(&item.id))
+            .map(|mut item| {
+                item.why_included = format!("shared organization context; {}", item.why_included);
+                item
+            }),
+    );
+    result.ranking.sort_by(|a, b| b.score.total_cmp(&a.score));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::context::{ArchitecturalDecision, BusinessRule};
+    use crate::services::context_query_service::RankedContextItem;
+
+    fn rule(id: &str, project_id: &str) -> BusinessRule {
+        BusinessRule {
+            id: id.to_string(),
+            project_id: project_id.to_string(),
+            rule_name: id.to_string(),
+            description: None,
+            domain_area: Some("auth".to_string()),
+            implementation_pattern: None,
+            constraints: None,
+            examples: None,
+            created_at: None,
+        }
+    }
+
+    fn ranked(id: &str, entity_type: &str, score: f32) -> RankedContextItem {
+        RankedContextItem {
+            id: id.to_string(),
+            entity_type: entity_type.to_string(),
+            title: id.to_string(),
+            score,
+            relevance: score,
+            freshness: 1.0,
+            quality: 1.0,
+            estimated_tokens: 10,
+            why_included: "filed under auth".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_merges_shared_rules_by_score_without_own_promotions() {
+        let mut result = ContextQueryResult {
+            business_rules: vec![rule("rule-local", "p1")],
+            ranking: vec![ranked("rule-local", "business_rule", 0.5)],
+            ..Default::default()
+        };
+        let shared = ContextQueryResult {
+            business_rules: vec![rule("rule-org", "org"), rule("rule-copy", "org")],
+            architectural_decisions: vec![ArchitecturalDecision {
+                id: "adr-org".to_string(),
+                project_id: "org".to_string(),
+                decision_title: "Org decision".to_string(),
+                context: None,
+                decision: None,
+                consequences: None,
+                alternatives_considered: None,
+                status: None,
+                created_at: None,
+            }],
+            ranking: vec![
+                ranked("rule-org", "business_rule", 0.9),
+                ranked("rule-copy", "business_rule", 0.8),
+                ranked("adr-org", "architectural_decision", 0.7),
+            ],
+            ..Default::default()
+        };
+
+        merge_shared(&mut result, shared, &HashSet::from(["rule-copy".to_string()]));
+        let rules: Vec<&str> = result.business_rules.iter().map(|rule| rule.id.as_str()).collect();
+        assert_eq!(rules, vec!["rule-local", "rule-org"]);
+        assert!(result.architectural_decisions.is_empty());
+        let order: Vec<&str> = result.ranking.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(order, vec!["rule-org", "rule-local"]);
+        assert!(result.ranking[0].why_included.starts_with("shared organization context; "));
+    }
+}
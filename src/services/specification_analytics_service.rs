@@ -4,9 +4,11 @@ use crate::models::specification::{
 };
 use crate::repositories::SpecificationRepository;
 use crate::services::analytics_service::{AnalyticsService, AnalyticsEvent, AnalyticsEventType};
+use crate::services::specification_parser::SpecificationParser;
+use crate::services::specification_versioning_service::SpecificationVersioningService;
 use anyhow::Result;
 use async_trait::async_trait;
-use chrono::{DateTime, Utc, Duration};
+use chrono::{DateTime, Datelike, NaiveDate, Utc, Duration};
 use rmcp::model::ErrorData as McpError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -81,6 +83,37 @@ pub struct DevelopmentVelocity {
     pub velocity_trend: VelocityTrend,
     pub bottlenecks: Vec<String>,
     pub productivity_score: f64,
+    /// Open and closed tasks at the end of each day of the period, for burndown and
+    /// cumulative-flow charts
+    #[serde(default)]
+    pub task_flow: Vec<TaskFlowPoint>,
+    /// Requirements whose tasks were all checked off, per week of the period
+    #[serde(default)]
+    pub requirements_completed_per_week: Vec<WeeklyCompletions>,
+}
+
+/// Task counts at the end of a day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskFlowPoint {
+    pub date: NaiveDate,
+    pub open: usize,
+    pub closed: usize,
+}
+
+/// Completions during the week starting on `week_start` (a Monday)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeeklyCompletions {
+    pub week_start: NaiveDate,
+    pub completed: usize,
+}
+
+/// Tasks of a task list as one version of it had them
+struct TaskSnapshot {
+    at: DateTime<Utc>,
+    open: usize,
+    closed: usize,
+    /// Requirement numbers every referencing task of which is done
+    completed_requirements: Vec<String>,
 }
 
 /// Velocity trend analysis
@@ -135,6 +168,7 @@ pub trait SpecificationAnalyticsService: Send + Sync {
 pub struct DefaultSpecificationAnalyticsService {
     specification_repository: Arc<dyn SpecificationRepository>,
     analytics_service: Arc<dyn AnalyticsService>,
+    versioning_service: Option<Arc<dyn SpecificationVersioningService>>,
 }
 
 impl DefaultSpecificationAnalyticsService {
@@ -145,7 +179,124 @@ impl DefaultSpecificationAnalyticsService {
         Self {
             specification_repository,
             analytics_service,
+            versioning_service: None,
+        }
+    }
+
+    /// Derive the velocity time series from stored specification versions; without it, or for
+    /// specifications that have no versions, only their current content is used
+    pub fn with_version_history(mut self, versioning_service: Arc<dyn SpecificationVersioningService>) -> Self {
+        self.versioning_service = Some(versioning_service);
+        self
+    }
+
+    /// Task checkboxes of each version of a specification, oldest first
+    async fn task_snapshots(&self, spec: &ProjectSpecification) -> Result<Vec<TaskSnapshot>, McpError> {
+        let mut versions = match &self.versioning_service {
+            Some(versioning) => versioning
+                .get_versions(&spec.id)
+                .await?
+                .into_iter()
+                .map(|version| (version.created_at, version.raw_content))
+                .collect(),
+            None => Vec::new(),
+        };
+        if versions.is_empty() {
+            versions.push((spec.updated_at, spec.content.raw_content.clone()));
+        }
+        versions.sort_by_key(|(at, _)| *at);
+
+        Ok(versions
+            .into_iter()
+            .filter_map(|(at, content)| Self::task_snapshot(at, &content, &spec.id))
+            .collect())
+    }
+
+    /// `None` for content without task checkboxes, such as a requirements document
+    fn task_snapshot(at: DateTime<Utc>, content: &str, spec_id: &str) -> Option<TaskSnapshot> {
+        let tasks = SpecificationParser::parse_tasks_from_markdown(content, spec_id.to_string()).ok()?;
+        if tasks.is_empty() {
+            return None;
+        }
+        let closed = tasks.iter().filter(|task| task.status == TaskStatus::Completed).count();
+
+        // `_Requirements: 1.2, 3_` refers to acceptance criterion 2 of requirement 1 and to requirement 3
+        let mut requirement_done: HashMap<String, bool> = HashMap::new();
+        for task in &tasks {
+            let Some(refs) = task.metadata.custom_fields.get("requirements").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            for reference in refs.split(',') {
+                let number = reference.trim().split('.').next().unwrap_or_default();
+                if number.is_empty() {
+                    continue;
+                }
+                let done = requirement_done.entry(number.to_string()).or_insert(true);
+                *done &= task.status == TaskStatus::Completed;
+            }
+        }
+        let mut completed_requirements: Vec<String> = requirement_done
+            .into_iter()
+            .filter(|(_, done)| *done)
+            .map(|(number, _)| number)
+            .collect();
+        completed_requirements.sort();
+
+        Some(TaskSnapshot {
+            at,
+            open: tasks.len() - closed,
+            closed,
+            completed_requirements,
+        })
+    }
+
+    /// Open and closed tasks at the end of each day from `start` to `end`, summing the latest
+    /// version of every task list as of that day
+    fn task_flow(snapshots: &[Vec<TaskSnapshot>], start: NaiveDate, end: NaiveDate) -> Vec<TaskFlowPoint> {
+        start
+            .iter_days()
+            .take_while(|date| *date <= end)
+            .map(|date| {
+                let mut point = TaskFlowPoint { date, open: 0, closed: 0 };
+                for snapshot in snapshots.iter().filter_map(|versions| {
+                    versions.iter().rev().find(|snapshot| snapshot.at.date_naive() <= date)
+                }) {
+                    point.open += snapshot.open;
+                    point.closed += snapshot.closed;
+                }
+                point
+            })
+            .collect()
+    }
+
+    /// Requirements completed per week from `start` to `end`; a requirement counts in the week of
+    /// the version in which its last task was checked off
+    fn requirements_completed_per_week(snapshots: &[Vec<TaskSnapshot>], start: NaiveDate, end: NaiveDate) -> Vec<WeeklyCompletions> {
+        let week_of = |date: NaiveDate| date - Duration::days(date.weekday().num_days_from_monday() as i64);
+        let mut weeks: Vec<WeeklyCompletions> = week_of(start)
+            .iter_weeks()
+            .take_while(|week_start| *week_start <= end)
+            .map(|week_start| WeeklyCompletions { week_start, completed: 0 })
+            .collect();
+
+        for versions in snapshots {
+            let mut previous: &[String] = &[];
+            for snapshot in versions {
+                let date = snapshot.at.date_naive();
+                let newly_completed = snapshot
+                    .completed_requirements
+                    .iter()
+                    .filter(|number| !previous.contains(number))
+                    .count();
+                if date >= start && date <= end {
+                    if let Some(week) = weeks.iter_mut().find(|week| week.week_start == week_of(date)) {
+                        week.completed += newly_completed;
+                    }
+                }
+                previous = &snapshot.completed_requirements;
+            }
         }
+        weeks
     }
 
     /// Calculate completion percentage for a requirement based on acceptance criteria
@@ -488,6 +639,14 @@ impl SpecificationAnalyticsService for DefaultSpecificationAnalyticsService {
 
         let velocity_trend = self.calculate_velocity_trend(tasks_completed, previous_tasks_completed);
 
+        let mut snapshots = Vec::new();
+        for spec in &specifications {
+            snapshots.push(self.task_snapshots(spec).await?);
+        }
+        let (start, today) = (cutoff_date.date_naive(), Utc::now().date_naive());
+        let task_flow = Self::task_flow(&snapshots, start, today);
+        let requirements_completed_per_week = Self::requirements_completed_per_week(&snapshots, start, today);
+
         // Calculate productivity score (0-100)
         let productivity_score = if days > 0 {
             let daily_task_rate = tasks_completed as f64 / days as f64;
@@ -507,6 +666,8 @@ impl SpecificationAnalyticsService for DefaultSpecificationAnalyticsService {
             velocity_trend,
             bottlenecks,
             productivity_score,
+            task_flow,
+            requirements_completed_per_week,
         })
     }
 
@@ -651,7 +812,10 @@ mod tests {
     use super::*;
     use crate::models::specification::{SpecContent, SpecFormat, AcceptanceCriterion, CriterionType, CriterionStatus};
     use crate::repositories::SpecificationRepository;
-    use crate::services::analytics_service::{AnalyticsService, UsageStatistics, ProjectInsights};
+    use crate::services::analytics_service::{
+        AnalyticsPruneReport, AnalyticsService, DailyUsage, EntityUseSource, EntityUses, UsageStatistics, ProjectInsights,
+        SearchClick,
+    };
     use async_trait::async_trait;
 
     // Mock repositories for testing
@@ -815,6 +979,18 @@ mod tests {
         async fn unlink_task_from_requirement(&self, _task_id: &str, _requirement_id: &str) -> Result<(), McpError> {
             unimplemented!()
         }
+
+        async fn find_requirement_ids_by_context(&self, _context_id: &str) -> Result<Vec<String>, McpError> {
+            unimplemented!()
+        }
+
+        async fn suggest_requirement_context_link(&self, _link: &crate::models::specification::RequirementContextLink) -> Result<(), McpError> {
+            unimplemented!()
+        }
+
+        async fn find_suggested_context_links(&self, _spec_id: &str) -> Result<Vec<crate::models::specification::RequirementContextLink>, McpError> {
+            unimplemented!()
+        }
     }
 
     struct MockAnalyticsService;
@@ -856,6 +1032,50 @@ mod tests {
         async fn generate_usage_report(&self, _start_date: DateTime<Utc>, _end_date: DateTime<Utc>) -> Result<serde_json::Value> {
             Ok(serde_json::json!({}))
         }
+
+        async fn get_search_clicks(&self, _since: DateTime<Utc>) -> Result<Vec<SearchClick>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_events(
+            &self,
+            _start_date: DateTime<Utc>,
+            _end_date: DateTime<Utc>,
+            _project_id: Option<&str>,
+            _event_types: &[AnalyticsEventType],
+        ) -> Result<Vec<AnalyticsEvent>> {
+            Ok(Vec::new())
+        }
+
+        async fn prune_events(&self, _retention_days: u32, _vacuum: bool) -> Result<AnalyticsPruneReport> {
+            Ok(AnalyticsPruneReport::default())
+        }
+
+        async fn roll_up_events(&self) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn record_entity_uses(
+            &self,
+            _project_id: &str,
+            _source: EntityUseSource,
+            _entities: &[(String, String)],
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_entity_uses(&self, _project_id: &str, _since: DateTime<Utc>) -> Result<HashMap<String, EntityUses>> {
+            Ok(HashMap::new())
+        }
+
+        async fn get_daily_usage(
+            &self,
+            _project_id: Option<&str>,
+            _start_date: DateTime<Utc>,
+            _end_date: DateTime<Utc>,
+        ) -> Result<Vec<DailyUsage>> {
+            Ok(Vec::new())
+        }
     }
 
     #[tokio::test]
@@ -913,6 +1133,46 @@ mod tests {
         assert!(velocity.productivity_score >= 0.0);
     }
 
+    #[test]
+    fn test_velocity_series_replay_task_versions() {
+        let at = |day: u32| DateTime::parse_from_rfc3339(&format!("2026-03-{:02}T12:00:00Z", day)).unwrap().with_timezone(&Utc);
+        let tasks = |first: &str, second: &str, third: &str| format!(
+            "- [{}] 1. Add cart model\n  - _Requirements: 1.1_\n\n- [{}] 2. Add checkout endpoint\n  - _Requirements: 1.2, 2_\n\n- [{}] 3. Send receipts\n  - _Requirements: 2.1_\n",
+            first, second, third
+        );
+        let versions: Vec<TaskSnapshot> = [
+            (at(2), tasks(" ", " ", " ")),
+            (at(4), tasks("x", " ", " ")),
+            (at(10), tasks("x", "x", " ")),
+            (at(11), tasks("x", "x", "x")),
+        ]
+        .into_iter()
+        .filter_map(|(at, content)| DefaultSpecificationAnalyticsService::task_snapshot(at, &content, "spec-1"))
+        .collect();
+        assert_eq!(versions[2].completed_requirements, vec!["1".to_string()]);
+        assert!(DefaultSpecificationAnalyticsService::task_snapshot(at(2), "# Requirements", "spec-2").is_none());
+
+        let start = NaiveDate::from_ymd_opt(2026, 3, 3).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 3, 12).unwrap();
+        let snapshots = vec![versions];
+        let flow = DefaultSpecificationAnalyticsService::task_flow(&snapshots, start, end);
+        assert_eq!(flow.len(), 10);
+        assert_eq!((flow[0].open, flow[0].closed), (3, 0));
+        assert_eq!((flow[1].open, flow[1].closed), (2, 1));
+        assert_eq!((flow[7].open, flow[7].closed), (1, 2));
+        assert_eq!((flow[9].open, flow[9].closed), (0, 3));
+
+        // 2026-03-02 and 2026-03-09 are Mondays
+        let weeks = DefaultSpecificationAnalyticsService::requirements_completed_per_week(&snapshots, start, end);
+        assert_eq!(
+            weeks,
+            vec![
+                WeeklyCompletions { week_start: NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(), completed: 0 },
+                WeeklyCompletions { week_start: NaiveDate::from_ymd_opt(2026, 3, 9).unwrap(), completed: 2 },
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_generate_health_report() {
         let spec_repo = Arc::new(MockSpecificationRepository::with_test_data());
@@ -1,7 +1,10 @@
 use crate::models::enhanced_context::{EnhancedContextItem, ContextType, RelationshipType, ContextRelationship};
-use crate::models::specification::{ProjectSpecification, Requirement, Task, RequirementId, TaskId, ContextId};
+use crate::models::specification::{
+    ContextLinkStatus, ProjectSpecification, Requirement, RequirementContextLink, Task, RequirementId, TaskId, ContextId,
+};
 use crate::repositories::{SpecificationRepository, EnhancedContextRepository};
 use crate::services::context_query_service::{ContextQueryService, ContextQueryResult};
+use crate::services::hybrid_search_service::{FacetFilters, HybridSearchService, RankedSearchResult};
 use async_trait::async_trait;
 use rmcp::model::ErrorData as McpError;
 use std::sync::Arc;
@@ -32,8 +35,42 @@ pub trait SpecificationContextLinkingService: Send + Sync {
     
     /// Track changes in specifications and update related context
     async fn track_specification_changes(&self, spec_id: &str, changes: SpecificationChanges) -> Result<(), McpError>;
+
+    /// Search a project's business rules, decisions and components for each requirement of an
+    /// imported specification and store the matches as suggested links. The specification's
+    /// own project is searched unless `context_project_id` names another, e.g. for Kiro specs
+    /// stored under their `.kiro/specs` directory name.
+    async fn suggest_links_for_specification(
+        &self,
+        spec_id: &str,
+        context_project_id: Option<&str>,
+    ) -> Result<Vec<RequirementContextLink>, McpError>;
+
+    /// Suggested links of a specification that were not accepted yet, best first
+    async fn get_suggested_links(&self, spec_id: &str) -> Result<Vec<RequirementContextLink>, McpError>;
+
+    /// Accept the listed `(requirement_id, context_id)` suggestions, or every suggestion when
+    /// none are listed, skipping those below `min_confidence`
+    async fn accept_suggested_links(
+        &self,
+        spec_id: &str,
+        links: &[(RequirementId, ContextId)],
+        min_confidence: Option<f64>,
+    ) -> Result<Vec<RequirementContextLink>, McpError>;
 }
 
+/// Entity types searched for link suggestions
+const SUGGESTED_CONTEXT_TYPES: &[&str] = &["business_rule", "architectural_decision", "framework_component"];
+
+/// Least confidence a search hit needs to be suggested
+const MIN_SUGGESTION_CONFIDENCE: f64 = 0.3;
+
+/// Most suggestions kept per requirement
+const MAX_SUGGESTIONS_PER_REQUIREMENT: usize = 5;
+
+/// User story filler that would match unrelated items or steer the search to semantic only
+const SUGGESTION_QUERY_NOISE: &[&str] = &["want", "like", "able", "requirement"];
+
 /// Result of linking context to specifications
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextLink {
@@ -326,6 +363,7 @@ pub struct DefaultSpecificationContextLinkingService {
     specification_repository: Arc<dyn SpecificationRepository>,
     enhanced_context_repository: Arc<dyn EnhancedContextRepository>,
     context_query_service: Arc<dyn ContextQueryService>,
+    hybrid_search: Option<Arc<dyn HybridSearchService>>,
 }
 
 impl DefaultSpecificationContextLinkingService {
@@ -338,6 +376,45 @@ impl DefaultSpecificationContextLinkingService {
             specification_repository,
             enhanced_context_repository,
             context_query_service,
+            hybrid_search: None,
+        }
+    }
+
+    /// Suggest links on import from hybrid search; without it nothing is suggested
+    pub fn with_hybrid_search(mut self, hybrid_search: Arc<dyn HybridSearchService>) -> Self {
+        self.hybrid_search = Some(hybrid_search);
+        self
+    }
+
+    /// Keywords of a requirement's story, or of its title when it has none
+    fn suggestion_query(&self, requirement: &Requirement) -> String {
+        let text = requirement.user_story.as_deref().unwrap_or(&requirement.description);
+        let text = if text.trim().is_empty() { requirement.title.as_str() } else { text };
+        let mut keywords: Vec<String> = Vec::new();
+        for keyword in self.extract_keywords_from_text(text) {
+            if !SUGGESTION_QUERY_NOISE.contains(&keyword.as_str()) && !keywords.contains(&keyword) {
+                keywords.push(keyword);
+            }
+        }
+        keywords.join(" ")
+    }
+
+    /// The reranker's relevance when there is one, otherwise the better of the vector
+    /// similarity and the share of query terms matched
+    fn suggestion_confidence(hit: &RankedSearchResult) -> f64 {
+        let breakdown = &hit.breakdown;
+        let confidence = breakdown.rerank_score.unwrap_or_else(|| {
+            breakdown.vector_score.unwrap_or(0.0).max(breakdown.keyword_score.unwrap_or(0.0))
+        });
+        f64::from(confidence).clamp(0.0, 1.0)
+    }
+
+    /// Link type of a requirement to a search hit; components implement requirements
+    fn suggested_link_type(&self, entity_type: &str, requirement: &Requirement) -> LinkType {
+        match entity_type {
+            "business_rule" => self.determine_link_type(&ContextType::BusinessRule, requirement),
+            "architectural_decision" => self.determine_link_type(&ContextType::ArchitecturalDecision, requirement),
+            _ => LinkType::Implements,
         }
     }
     
@@ -483,6 +560,7 @@ impl SpecificationContextLinkingService for DefaultSpecificationContextLinkingSe
             &requirement.title, // Use title as feature area
             "implementation", // Default task type
             &keywords,
+            None,
         ).await?;
         
         let mut links = Vec::new();
@@ -729,6 +807,89 @@ impl SpecificationContextLinkingService for DefaultSpecificationContextLinkingSe
         tracing::info!("Tracking specification changes for spec {}: {:?}", spec_id, changes);
         Ok(())
     }
+
+    async fn suggest_links_for_specification(
+        &self,
+        spec_id: &str,
+        context_project_id: Option<&str>,
+    ) -> Result<Vec<RequirementContextLink>, McpError> {
+        let Some(hybrid_search) = &self.hybrid_search else {
+            return Ok(Vec::new());
+        };
+        let spec = self.specification_repository.find_specification_by_id(spec_id).await?
+            .ok_or_else(|| McpError::resource_not_found(format!("Specification not found: {}", spec_id), None))?;
+        let project_id = context_project_id.unwrap_or(&spec.project_id);
+        let filters = FacetFilters::from([(
+            "entity_type".to_string(),
+            SUGGESTED_CONTEXT_TYPES.iter().map(|t| t.to_string()).collect(),
+        )]);
+
+        let mut suggested = Vec::new();
+        for requirement in self.specification_repository.find_requirements_by_spec(spec_id).await? {
+            let query = self.suggestion_query(&requirement);
+            if query.is_empty() {
+                continue;
+            }
+            let result = hybrid_search
+                .faceted_search(project_id, &query, None, &filters)
+                .await
+                .map_err(|e| McpError::internal_error(format!("Failed to search context for {}: {}", requirement.id, e), None))?;
+
+            let hits = result
+                .ranked_results
+                .iter()
+                .filter(|hit| !requirement.linked_context.contains(&hit.entity_id))
+                .map(|hit| (hit, Self::suggestion_confidence(hit)))
+                .filter(|(_, confidence)| *confidence >= MIN_SUGGESTION_CONFIDENCE)
+                .take(MAX_SUGGESTIONS_PER_REQUIREMENT);
+            for (hit, confidence) in hits {
+                let link = RequirementContextLink {
+                    requirement_id: requirement.id.clone(),
+                    context_id: hit.entity_id.clone(),
+                    context_type: Some(hit.entity_type.clone()),
+                    title: hit.title.clone(),
+                    link_type: Some(self.suggested_link_type(&hit.entity_type, &requirement).as_str().to_string()),
+                    confidence: Some(confidence),
+                    status: ContextLinkStatus::Suggested,
+                    created_at: Utc::now(),
+                };
+                self.specification_repository.suggest_requirement_context_link(&link).await?;
+                suggested.push(link);
+            }
+        }
+
+        tracing::debug!("Suggested {} context links for specification {}", suggested.len(), spec_id);
+        Ok(suggested)
+    }
+
+    async fn get_suggested_links(&self, spec_id: &str) -> Result<Vec<RequirementContextLink>, McpError> {
+        self.specification_repository.find_suggested_context_links(spec_id).await
+    }
+
+    async fn accept_suggested_links(
+        &self,
+        spec_id: &str,
+        links: &[(RequirementId, ContextId)],
+        min_confidence: Option<f64>,
+    ) -> Result<Vec<RequirementContextLink>, McpError> {
+        let mut accepted = Vec::new();
+        for mut link in self.specification_repository.find_suggested_context_links(spec_id).await? {
+            let listed = links.is_empty()
+                || links.iter().any(|(requirement_id, context_id)| {
+                    *requirement_id == link.requirement_id && *context_id == link.context_id
+                });
+            let confident = min_confidence.is_none_or(|min| link.confidence.unwrap_or(0.0) >= min);
+            if !listed || !confident {
+                continue;
+            }
+            self.specification_repository
+                .link_requirement_to_context(&link.requirement_id, &link.context_id)
+                .await?;
+            link.status = ContextLinkStatus::Accepted;
+            accepted.push(link);
+        }
+        Ok(accepted)
+    }
 }
 
 #[cfg(test)]
@@ -854,6 +1015,18 @@ mod tests {
         async fn unlink_task_from_requirement(&self, _task_id: &str, _requirement_id: &str) -> Result<(), McpError> {
             Ok(())
         }
+
+        async fn find_requirement_ids_by_context(&self, _context_id: &str) -> Result<Vec<String>, McpError> {
+            Ok(Vec::new())
+        }
+
+        async fn suggest_requirement_context_link(&self, _link: &crate::models::specification::RequirementContextLink) -> Result<(), McpError> {
+            Ok(())
+        }
+
+        async fn find_suggested_context_links(&self, _spec_id: &str) -> Result<Vec<crate::models::specification::RequirementContextLink>, McpError> {
+            Ok(Vec::new())
+        }
     }
     
     #[async_trait]
@@ -938,6 +1111,7 @@ mod tests {
             _feature_area: &str,
             _task_type: &str,
             _components: &[String],
+            _token_budget: Option<usize>,
         ) -> Result<ContextQueryResult, McpError> {
             use crate::models::context::{BusinessRule, ArchitecturalDecision, PerformanceRequirement};
             
@@ -982,6 +1156,7 @@ mod tests {
                 ],
                 security_policies: Vec::new(),
                 project_conventions: Vec::new(),
+                ..Default::default()
             })
         }
     }
@@ -1073,4 +1248,165 @@ mod tests {
         assert!(suggestion.relevance_score > 0.0);
         assert!(!suggestion.keywords_matched.is_empty());
     }
+    /// Hybrid search returning the same hits for every query
+    struct FixedHybridSearch(Vec<(&'static str, &'static str, f32)>);
+
+    #[async_trait]
+    impl HybridSearchService for FixedHybridSearch {
+        async fn hybrid_search(
+            &self,
+            project_id: &str,
+            query_text: &str,
+            _feature_area: Option<&str>,
+            _task_type: Option<&str>,
+            _components: &[String],
+        ) -> Result<crate::services::hybrid_search_service::HybridSearchResult, crate::services::hybrid_search_service::HybridSearchError> {
+            self.faceted_search(project_id, query_text, None, &FacetFilters::new()).await
+        }
+
+        async fn faceted_search(
+            &self,
+            _project_id: &str,
+            _query_text: &str,
+            _feature_area: Option<&str>,
+            filters: &FacetFilters,
+        ) -> Result<crate::services::hybrid_search_service::HybridSearchResult, crate::services::hybrid_search_service::HybridSearchError> {
+            use crate::services::hybrid_search_service::{FusionMethod, HybridSearchResult, ScoreBreakdown, SearchStrategy};
+
+            assert_eq!(filters["entity_type"], SUGGESTED_CONTEXT_TYPES);
+            let ranked_results = self.0.iter().map(|(id, entity_type, keyword_score)| RankedSearchResult {
+                entity_id: id.to_string(),
+                entity_type: entity_type.to_string(),
+                title: Some(format!("Title of {}", id)),
+                score: *keyword_score,
+                breakdown: ScoreBreakdown {
+                    fusion: FusionMethod::WeightedSum,
+                    vector_score: None,
+                    vector_rank: None,
+                    vector_contribution: 0.0,
+                    keyword_score: Some(*keyword_score),
+                    keyword_rank: None,
+                    keyword_contribution: *keyword_score,
+                    entity_type_boost: 1.0,
+                    popularity_boost: 1.0,
+                    rerank_score: None,
+                },
+                project_id: None,
+                facets: Default::default(),
+                content: String::new(),
+            }).collect::<Vec<_>>();
+            Ok(HybridSearchResult {
+                semantic_results: Vec::new(),
+                traditional_results: ContextQueryResult {
+                    business_rules: Vec::new(),
+                    architectural_decisions: Vec::new(),
+                    performance_requirements: Vec::new(),
+                    security_policies: Vec::new(),
+                    project_conventions: Vec::new(),
+                    ..Default::default()
+                },
+                combined_score: 0.0,
+                search_strategy: SearchStrategy::Hybrid,
+                total_results: ranked_results.len(),
+                ranked_results,
+                facet_counts: Default::default(),
+                query_expansions: Vec::new(),
+            })
+        }
+
+        async fn cross_project_search(
+            &self,
+            _project_ids: &[String],
+            query_text: &str,
+            _feature_area: Option<&str>,
+            filters: &FacetFilters,
+        ) -> Result<crate::services::hybrid_search_service::HybridSearchResult, crate::services::hybrid_search_service::HybridSearchError> {
+            self.faceted_search("", query_text, None, filters).await
+        }
+
+        async fn semantic_search(
+            &self,
+            _query: &crate::models::embedding::VectorSearchQuery,
+        ) -> Result<Vec<crate::services::semantic_search_service::EnhancedSearchResult>, crate::services::hybrid_search_service::HybridSearchError> {
+            Ok(Vec::new())
+        }
+
+        async fn traditional_search(
+            &self,
+            project_id: &str,
+            feature_area: &str,
+            task_type: &str,
+            components: &[String],
+        ) -> Result<ContextQueryResult, crate::services::hybrid_search_service::HybridSearchError> {
+            Ok(MockContextQueryService.query_context(project_id, feature_area, task_type, components, None).await.unwrap())
+        }
+
+        async fn get_search_suggestions(
+            &self,
+            _partial_query: &str,
+            _project_id: Option<&str>,
+        ) -> Result<Vec<String>, crate::services::hybrid_search_service::HybridSearchError> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_suggested_links_are_stored_and_accepted() {
+        use crate::infrastructure::SqliteSpecificationRepository;
+
+        let conn = crate::db::init::init_db(":memory:").unwrap();
+        let repository = SqliteSpecificationRepository::new(Arc::new(std::sync::Mutex::new(conn)));
+        repository.initialize_tables().unwrap();
+        let repository = Arc::new(repository);
+
+        let spec = ProjectSpecification::new(
+            "payments".to_string(),
+            SpecType::Requirements,
+            "Checkout".to_string(),
+            SpecContent::new(SpecFormat::Markdown, String::new()),
+        );
+        repository.create_specification(&spec).await.unwrap();
+        let mut requirement = Requirement::new(spec.id.clone(), "Requirement 1".to_string(), String::new());
+        requirement.user_story = Some("As a customer, I want to pay by card, so that I can check out".to_string());
+        repository.create_requirement(&requirement).await.unwrap();
+
+        let linking_service = DefaultSpecificationContextLinkingService::new(
+            repository.clone(),
+            Arc::new(MockEnhancedContextRepository),
+            Arc::new(MockContextQueryService),
+        )
+        .with_hybrid_search(Arc::new(FixedHybridSearch(vec![
+            ("br-card", "business_rule", 0.75),
+            ("comp-checkout", "framework_component", 0.5),
+            ("adr-unrelated", "architectural_decision", 0.1),
+        ])));
+
+        let suggested = linking_service.suggest_links_for_specification(&spec.id, None).await.unwrap();
+        assert_eq!(suggested.len(), 2);
+        assert_eq!(suggested[1].link_type.as_deref(), Some("implements"));
+        let stored = linking_service.get_suggested_links(&spec.id).await.unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].context_id, "br-card");
+        assert_eq!(stored[0].confidence, Some(0.75));
+        // Suggestions are not links until accepted
+        let stored_requirement = repository.find_requirement_by_id(&requirement.id).await.unwrap().unwrap();
+        assert!(stored_requirement.linked_context.is_empty());
+
+        let accepted = linking_service.accept_suggested_links(&spec.id, &[], Some(0.6)).await.unwrap();
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(accepted[0].status, ContextLinkStatus::Accepted);
+        let stored_requirement = repository.find_requirement_by_id(&requirement.id).await.unwrap().unwrap();
+        assert_eq!(stored_requirement.linked_context, vec!["br-card".to_string()]);
+
+        // Re-importing suggests only what is not linked yet
+        let suggested = linking_service.suggest_links_for_specification(&spec.id, None).await.unwrap();
+        assert_eq!(suggested.len(), 1);
+        assert_eq!(suggested[0].context_id, "comp-checkout");
+        let accepted = linking_service
+            .accept_suggested_links(&spec.id, &[(requirement.id.clone(), "comp-checkout".to_string())], None)
+            .await
+            .unwrap();
+        assert_eq!(accepted.len(), 1);
+        assert!(linking_service.get_suggested_links(&spec.id).await.unwrap().is_empty());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,151 @@
+use crate::models::specification::{ProjectSpecification, Requirement, SpecType, Task, TaskStatus};
+
+/// Renders stored specifications back to Kiro markdown, in the layout SpecificationParser reads
+pub struct SpecificationExporter;
+
+impl SpecificationExporter {
+    /// Markdown for a specification. Requirements and tasks documents are rebuilt from their
+    /// stored requirements and tasks; other documents have no structured parts and keep their
+    /// imported content.
+    pub fn render_markdown(spec: &ProjectSpecification, requirements: &[Requirement], tasks: &[Task]) -> String {
+        match spec.spec_type {
+            SpecType::Requirements => Self::render_requirements(spec, requirements),
+            SpecType::Tasks => Self::render_tasks(tasks),
+            _ => spec.content.raw_content.clone(),
+        }
+    }
+
+    /// `requirements.md`: an introduction, then each requirement with its user story and
+    /// numbered acceptance criteria
+    pub fn render_requirements(spec: &ProjectSpecification, requirements: &[Requirement]) -> String {
+        let mut out = String::from("# Requirements Document\n\n");
+
+        let introduction = spec
+            .content
+            .parsed_sections
+            .get("h2-introduction")
+            .or(spec.description.as_ref());
+        if let Some(introduction) = introduction {
+            out.push_str(&format!("## Introduction\n\n{}\n\n", introduction.trim()));
+        }
+
+        out.push_str("## Requirements\n");
+        for (index, requirement) in requirements.iter().enumerate() {
+            let number = index + 1;
+            // Titles are `Requirement N` when imported; others are kept after the number
+            if requirement.title.starts_with("Requirement ") {
+                out.push_str(&format!("\n### Requirement {}\n", number));
+            } else {
+                out.push_str(&format!("\n### Requirement {}: {}\n", number, requirement.title));
+            }
+
+            match &requirement.user_story {
+                Some(story) => out.push_str(&format!("\n**User Story:** {}\n", story)),
+                None if !requirement.description.trim().is_empty() => {
+                    out.push_str(&format!("\n{}\n", requirement.description.trim()))
+                }
+                None => {}
+            }
+
+            if !requirement.acceptance_criteria.is_empty() {
+                out.push_str("\n#### Acceptance Criteria\n\n");
+                for (index, criterion) in requirement.acceptance_criteria.iter().enumerate() {
+                    out.push_str(&format!("{}. {}\n", index + 1, criterion.description));
+                }
+            }
+        }
+        out
+    }
+
+    /// `tasks.md`: a checkbox list numbered by position, with subtasks indented under their parent
+    pub fn render_tasks(tasks: &[Task]) -> String {
+        let mut out = String::from("# Implementation Plan\n");
+        let is_root = |task: &Task| {
+            task.parent_task
+                .as_ref()
+                .is_none_or(|parent| !tasks.iter().any(|t| &t.id == parent))
+        };
+
+        for (index, task) in tasks.iter().filter(|t| is_root(t)).enumerate() {
+            out.push('\n');
+            Self::render_task(&mut out, tasks, task, &format!("{}.", index + 1), 0);
+        }
+        out
+    }
+
+    fn render_task(out: &mut String, tasks: &[Task], task: &Task, number: &str, depth: usize) {
+        let indent = "  ".repeat(depth);
+        out.push_str(&format!("{}- [{}] {} {}\n", indent, Self::checkbox(&task.status), number, task.title));
+
+        for line in task.description.lines().filter(|l| !l.trim().is_empty()) {
+            out.push_str(&format!("{}  - {}\n", indent, line.trim()));
+        }
+        if let Some(requirements) = task.metadata.custom_fields.get("requirements").and_then(|v| v.as_str()) {
+            out.push_str(&format!("{}  - _Requirements: {}_\n", indent, requirements.trim()));
+        }
+
+        let prefix = number.trim_end_matches('.');
+        for (index, subtask) in tasks.iter().filter(|t| t.parent_task.as_ref() == Some(&task.id)).enumerate() {
+            Self::render_task(out, tasks, subtask, &format!("{}.{}", prefix, index + 1), depth + 1);
+        }
+    }
+
+    /// `x` for completed tasks, `-` for tasks in progress
+    pub fn checkbox(status: &TaskStatus) -> char {
+        match status {
+            TaskStatus::Completed => 'x',
+            TaskStatus::InProgress => '-',
+            _ => ' ',
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::specification::{SpecContent, SpecFormat};
+    use crate::services::SpecificationParser;
+
+    #[test]
+    fn test_requirements_round_trip() {
+        let content = "# Requirements Document\n\n## Introduction\n\nSign-in for the portal.\n\n## Requirements\n\n\
+            ### Requirement 1\n\n**User Story:** As a user, I want to log in, so that I can see my orders.\n\n\
+            #### Acceptance Criteria\n\n1. WHEN credentials are valid THEN the system SHALL sign the user in\n\
+            2. WHEN credentials are invalid THEN the system SHALL show an error\n\n\
+            ### Requirement 2\n\n**User Story:** As an admin, I want to lock accounts.\n";
+        let spec = SpecificationParser::parse_specification("p1".to_string(), ".kiro/specs/login/requirements.md", content).unwrap();
+        let requirements = SpecificationParser::parse_requirements_from_markdown(content, spec.id.clone()).unwrap();
+
+        let exported = SpecificationExporter::render_markdown(&spec, &requirements, &[]);
+        assert_eq!(exported, content);
+
+        let reparsed = SpecificationParser::parse_requirements_from_markdown(&exported, spec.id.clone()).unwrap();
+        assert_eq!(reparsed.len(), 2);
+        assert_eq!(reparsed[0].acceptance_criteria.len(), 2);
+        assert_eq!(reparsed[1].user_story, requirements[1].user_story);
+    }
+
+    #[test]
+    fn test_tasks_round_trip_with_status() {
+        let content = "# Implementation Plan\n\n- [x] 1. Set up the project\n  - Create the crate\n  - _Requirements: 1.1_\n\n\
+            - [-] 2. Build sign-in\n  - [x] 2.1 Add the form\n  - [ ] 2.2 Add the session store\n    - Use cookies\n";
+        let mut tasks = SpecificationParser::parse_tasks_from_markdown(content, "spec-1".to_string()).unwrap();
+        assert_eq!(SpecificationExporter::render_tasks(&tasks), content);
+
+        // Status changes made after the import are written back
+        tasks[3].update_status(TaskStatus::Completed);
+        let exported = SpecificationExporter::render_tasks(&tasks);
+        assert!(exported.contains("  - [x] 2.2 Add the session store\n"));
+        let reparsed = SpecificationParser::parse_tasks_from_markdown(&exported, "spec-1".to_string()).unwrap();
+        assert_eq!(reparsed[3].status, TaskStatus::Completed);
+        assert_eq!(reparsed[1].subtasks.len(), 2);
+
+        let design = ProjectSpecification::new(
+            "p1".to_string(),
+            SpecType::Design,
+            "Design".to_string(),
+            SpecContent::new(SpecFormat::Markdown, "# Design\n".to_string()),
+        );
+        assert_eq!(SpecificationExporter::render_markdown(&design, &[], &tasks), "# Design\n");
+    }
+}
@@ -129,18 +129,7 @@ The system consists of:
         // Should have no validation issues for a properly structured file
         assert!(validation_issues.is_empty());
 
-        // Test versioning service
-        let version = container
-            .specification_versioning_service
-            .create_version(&imported_spec, "Initial import")
-            .await
-            .unwrap();
-
-        assert_eq!(version.spec_id, imported_spec.id);
-        assert_eq!(version.version_number, imported_spec.version);
-        assert_eq!(version.change_description, "Initial import");
-
-        // Test getting versions
+        // Importing the file kept its content as the first version
         let versions = container
             .specification_versioning_service
             .get_versions(&imported_spec.id)
@@ -148,7 +137,16 @@ The system consists of:
             .unwrap();
 
         assert_eq!(versions.len(), 1);
-        assert_eq!(versions[0].id, version.id);
+        assert_eq!(versions[0].spec_id, imported_spec.id);
+        assert_eq!(versions[0].version_number, imported_spec.version);
+        assert_eq!(versions[0].change_description, "Imported from file");
+
+        // The same content is not stored twice
+        assert!(container
+            .specification_versioning_service
+            .create_version(&imported_spec, "Initial import")
+            .await
+            .is_err());
 
         println!("✅ Specification import integration test passed!");
     }
@@ -1,14 +1,33 @@
-use crate::models::specification::{ProjectSpecification, SpecType};
+use crate::models::specification::{ProjectSpecification, Requirement, SpecContent, SpecFormat, SpecType};
 use crate::repositories::SpecificationRepository;
-use crate::services::{SpecificationParser, SpecificationService};
+use crate::services::conflict_resolution_engine::{ConflictInfo, ConflictResolutionEngine, ConflictType};
+use crate::services::websocket_types::{ChangeMetadata, ChangeType as ContextChangeType, ContextChange};
+use crate::services::gherkin_parser::GherkinParser;
+use crate::services::{
+    SpecificationContextLinkingService, SpecificationExporter, SpecificationParser, SpecificationService,
+    SpecificationVersioningService,
+};
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use parking_lot::Mutex;
 use rmcp::model::ErrorData as McpError;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use tokio::fs;
 use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// How long a file must stay unchanged before a monitor re-imports it
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Custom fields recording what a specification was last synced from, to tell which side changed since
+const SYNCED_FILE_HASH: &str = "synced_file_hash";
+const SYNCED_DB_HASH: &str = "synced_db_hash";
+const SYNCED_AT: &str = "synced_at";
 
 /// Service for importing and monitoring Kiro specification files
 #[async_trait]
@@ -16,10 +35,18 @@ pub trait SpecificationImportService: Send + Sync {
     /// Scan and import all specifications from the .kiro/specs directory
     async fn scan_and_import_specifications(&self, base_path: &Path) -> Result<Vec<ProjectSpecification>, McpError>;
     
+    /// Import every Gherkin `.feature` file under `base_path` into a project: each feature as a
+    /// specification whose scenarios are its requirements
+    async fn scan_and_import_features(&self, project_id: &str, base_path: &Path) -> Result<Vec<ProjectSpecification>, McpError>;
+
+    /// Import every markdown file under `base_path` into a project as a document without requirements
+    async fn scan_and_import_markdown(&self, project_id: &str, base_path: &Path) -> Result<Vec<ProjectSpecification>, McpError>;
+
     /// Import a single specification file
     async fn import_specification_file(&self, file_path: &Path) -> Result<ProjectSpecification, McpError>;
     
-    /// Start monitoring the .kiro/specs directory for changes
+    /// Start monitoring the .kiro/specs directory, re-importing files once they stop changing.
+    /// A file is not re-imported over a copy edited since its last import; that raises a conflict instead.
     async fn start_file_monitoring(&self, base_path: &Path) -> Result<(), McpError>;
     
     /// Stop file monitoring
@@ -56,6 +83,31 @@ pub enum ChangeType {
 pub struct DefaultSpecificationImportService {
     specification_service: Arc<dyn SpecificationService>,
     repository: Arc<dyn SpecificationRepository>,
+    monitor: SpecMonitor,
+    watchers: Mutex<HashMap<PathBuf, RecommendedWatcher>>,
+}
+
+/// What a monitor needs to sync changed files in the background
+#[derive(Clone)]
+struct SpecMonitor {
+    specification_service: Arc<dyn SpecificationService>,
+    conflict_engine: Option<Arc<tokio::sync::Mutex<ConflictResolutionEngine>>>,
+    link_suggester: Option<Arc<dyn SpecificationContextLinkingService>>,
+    versioning: Option<Arc<dyn SpecificationVersioningService>>,
+    debounce: Duration,
+}
+
+/// A document read from a file and the requirements found in it
+type ParsedDocument = (ProjectSpecification, Vec<Requirement>);
+
+/// What syncing a file did to its specification
+#[derive(Debug, Clone, PartialEq)]
+enum SyncOutcome {
+    Imported(String),
+    Updated(String),
+    Unchanged,
+    /// The stored copy was edited since the last import, so the file was not applied
+    Diverged { conflict_id: Option<String> },
 }
 
 impl DefaultSpecificationImportService {
@@ -64,11 +116,44 @@ impl DefaultSpecificationImportService {
         repository: Arc<dyn SpecificationRepository>,
     ) -> Self {
         Self {
+            monitor: SpecMonitor {
+                specification_service: specification_service.clone(),
+                conflict_engine: None,
+                link_suggester: None,
+                versioning: None,
+                debounce: DEFAULT_DEBOUNCE,
+            },
             specification_service,
             repository,
+            watchers: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Report files changed on both sides as conflicts; without an engine they are only logged
+    pub fn with_conflict_engine(mut self, engine: Arc<tokio::sync::Mutex<ConflictResolutionEngine>>) -> Self {
+        self.monitor.conflict_engine = Some(engine);
+        self
+    }
+
+    /// Propose links from imported requirements to the project's existing context
+    pub fn with_link_suggestions(mut self, linking_service: Arc<dyn SpecificationContextLinkingService>) -> Self {
+        self.monitor.link_suggester = Some(linking_service);
+        self
+    }
+
+    /// Keep a version of each imported or re-synced specification, which velocity analytics
+    /// replay for burndown and cumulative-flow series
+    pub fn with_version_history(mut self, versioning: Arc<dyn SpecificationVersioningService>) -> Self {
+        self.monitor.versioning = Some(versioning);
+        self
+    }
+
+    /// Wait for a file to stay unchanged this long before a monitor re-imports it
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.monitor.debounce = debounce;
+        self
+    }
+
     /// Extract project name from the .kiro/specs directory structure
     fn extract_project_name(file_path: &Path) -> Result<String> {
         let specs_dir = file_path
@@ -95,8 +180,11 @@ impl DefaultSpecificationImportService {
         }
     }
 
-    /// Scan directory recursively for Kiro specification files
-    fn scan_directory(dir_path: &Path) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Vec<PathBuf>>> + Send + '_>> {
+    /// Scan directory recursively for files `is_match` accepts
+    fn scan_directory(
+        dir_path: &Path,
+        is_match: fn(&Path) -> bool,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Vec<PathBuf>>> + Send + '_>> {
         Box::pin(async move {
             let mut spec_files = Vec::new();
             let mut entries = fs::read_dir(dir_path).await
@@ -109,9 +197,9 @@ impl DefaultSpecificationImportService {
                 
                 if path.is_dir() {
                     // Recursively scan subdirectories
-                    let mut sub_files = Self::scan_directory(&path).await?;
+                    let mut sub_files = Self::scan_directory(&path, is_match).await?;
                     spec_files.append(&mut sub_files);
-                } else if Self::is_kiro_spec_file(&path) {
+                } else if is_match(&path) {
                     spec_files.push(path);
                 }
             }
@@ -120,6 +208,119 @@ impl DefaultSpecificationImportService {
         })
     }
 
+    fn is_markdown_file(file_path: &Path) -> bool {
+        matches!(file_path.extension().and_then(|e| e.to_str()), Some("md" | "markdown"))
+    }
+
+    /// Import every file under `base_path` that `is_match` accepts, logging the ones that fail
+    async fn scan_and_import_documents(
+        &self,
+        project_id: &str,
+        base_path: &Path,
+        is_match: fn(&Path) -> bool,
+        parse: fn(&str, &Path, &str) -> Result<ParsedDocument>,
+    ) -> Result<Vec<ProjectSpecification>, McpError> {
+        info!("Scanning for documents in: {}", base_path.display());
+
+        let mut files = Self::scan_directory(base_path, is_match).await
+            .map_err(|e| McpError::internal_error(format!("Failed to scan directory: {}", e), None))?;
+        files.sort();
+
+        let mut imported = Vec::new();
+        for file_path in files {
+            let document = match fs::read_to_string(&file_path).await {
+                Ok(content) => parse(project_id, &file_path, &content),
+                Err(e) => Err(anyhow!("Failed to read file: {}", e)),
+            };
+            let stored = match document {
+                Ok((spec, requirements)) => self.store_document(spec, requirements).await,
+                Err(e) => Err(McpError::invalid_params(e.to_string(), None)),
+            };
+            match stored {
+                Ok(spec) => imported.push(spec),
+                Err(e) => warn!("Failed to import {}: {}", file_path.display(), e),
+            }
+        }
+
+        info!("Imported {} documents", imported.len());
+        Ok(imported)
+    }
+
+    /// A feature file as a specification and its scenarios as requirements
+    fn parse_feature(project_id: &str, file_path: &Path, content: &str) -> Result<ParsedDocument> {
+        let feature = GherkinParser::parse(content)?;
+        let mut spec = ProjectSpecification::new(
+            project_id.to_string(),
+            SpecType::Feature,
+            feature.name.clone(),
+            SpecContent::new(SpecFormat::Custom("gherkin".to_string()), content.to_string()),
+        );
+        spec.description = feature.description.clone();
+        spec.metadata.tags = feature.tags.clone();
+        spec.file_path = Some(file_path.to_string_lossy().to_string());
+
+        let requirements = feature.to_requirements(&spec.id);
+        Ok((spec, requirements))
+    }
+
+    /// A markdown file as a document titled by its first heading or its file name
+    fn parse_markdown_document(project_id: &str, file_path: &Path, content: &str) -> Result<ParsedDocument> {
+        let mut spec = SpecificationParser::parse_specification(project_id.to_string(), &file_path.to_string_lossy(), content)?;
+        spec.title = content
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("# "))
+            .map(|title| title.trim().to_string())
+            .or_else(|| file_path.file_stem().map(|stem| stem.to_string_lossy().to_string()))
+            .unwrap_or(spec.title);
+        Ok((spec, Vec::new()))
+    }
+
+    /// Store a document, replacing the one imported from the same file into the project
+    /// before while keeping its id
+    async fn store_document(
+        &self,
+        mut spec: ProjectSpecification,
+        requirements: Vec<Requirement>,
+    ) -> Result<ProjectSpecification, McpError> {
+        let existing = self
+            .repository
+            .find_specifications_by_project(&spec.project_id)
+            .await?
+            .into_iter()
+            .filter(|s| s.file_path == spec.file_path)
+            .max_by_key(|s| s.created_at);
+
+        match existing {
+            Some(mut existing) if existing.content.raw_content == spec.content.raw_content => {
+                existing.requirements = self.repository.find_requirements_by_spec(&existing.id).await?
+                    .into_iter()
+                    .map(|r| r.id)
+                    .collect();
+                return Ok(existing);
+            }
+            Some(existing) => {
+                for requirement in self.repository.find_requirements_by_spec(&existing.id).await? {
+                    self.repository.delete_requirement(&requirement.id).await?;
+                }
+                spec.id = existing.id;
+                spec.created_at = existing.created_at;
+                spec.version = existing.version + 1;
+                self.repository.update_specification(&spec).await?;
+            }
+            None => {
+                self.repository.create_specification(&spec).await?;
+            }
+        }
+
+        for mut requirement in requirements {
+            requirement.spec_id = spec.id.clone();
+            let stored = self.repository.create_requirement(&requirement).await?;
+            spec.requirements.push(stored.id);
+        }
+        self.monitor.imported(&spec, "Imported from file").await;
+        Ok(spec)
+    }
+
     /// Create a specification change record
     fn create_change_record(
         spec_id: String,
@@ -144,12 +345,248 @@ impl DefaultSpecificationImportService {
 
 }
 
+impl SpecMonitor {
+    /// Apply file system events as they settle: each changed file is synced once it has
+    /// seen no events for the debounce period
+    async fn run(self, mut events: tokio::sync::mpsc::UnboundedReceiver<Event>) {
+        let mut pending: HashMap<PathBuf, tokio::time::Instant> = HashMap::new();
+        loop {
+            let next_due = pending.values().min().copied();
+            tokio::select! {
+                event = events.recv() => {
+                    let Some(event) = event else { break };
+                    if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) {
+                        continue;
+                    }
+                    let due = tokio::time::Instant::now() + self.debounce;
+                    for path in event.paths.into_iter().filter(|p| DefaultSpecificationImportService::is_kiro_spec_file(p)) {
+                        pending.insert(path, due);
+                    }
+                }
+                _ = tokio::time::sleep_until(next_due.unwrap_or_else(tokio::time::Instant::now)), if next_due.is_some() => {
+                    let now = tokio::time::Instant::now();
+                    let settled: Vec<PathBuf> = pending
+                        .iter()
+                        .filter(|(_, due)| **due <= now)
+                        .map(|(path, _)| path.clone())
+                        .collect();
+                    for path in settled {
+                        pending.remove(&path);
+                        self.sync_changed_file(&path).await;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn sync_changed_file(&self, file_path: &Path) {
+        if !file_path.exists() {
+            debug!("Specification file {} was removed; keeping its imported copy", file_path.display());
+            return;
+        }
+        match self.sync_file(file_path).await {
+            Ok(SyncOutcome::Imported(id)) => info!("Imported specification {} from {}", id, file_path.display()),
+            Ok(SyncOutcome::Updated(id)) => info!("Re-imported specification {} from {}", id, file_path.display()),
+            Ok(SyncOutcome::Unchanged) => debug!("Specification file {} is unchanged", file_path.display()),
+            Ok(SyncOutcome::Diverged { conflict_id: Some(id) }) => warn!(
+                "{} changed while its imported copy was edited; not re-imported, see conflict {}",
+                file_path.display(),
+                id
+            ),
+            Ok(SyncOutcome::Diverged { conflict_id: None }) => warn!(
+                "{} changed while its imported copy was edited; not re-imported",
+                file_path.display()
+            ),
+            Err(e) => warn!("Failed to sync specification {}: {}", file_path.display(), e),
+        }
+    }
+
+    /// Bring the newest specification imported from a file up to date with it. A file whose
+    /// specification was edited since the last import is only applied once a conflict about
+    /// it has been resolved.
+    async fn sync_file(&self, file_path: &Path) -> Result<SyncOutcome, McpError> {
+        let project_name = DefaultSpecificationImportService::extract_project_name(file_path)
+            .map_err(|e| McpError::internal_error(format!("Failed to extract project name: {}", e), None))?;
+        let content = fs::read_to_string(file_path).await
+            .map_err(|e| McpError::internal_error(format!("Failed to read file {}: {}", file_path.display(), e), None))?;
+        let file_path_str = file_path.to_string_lossy().to_string();
+        let file_hash = Self::hash(&content);
+
+        let existing = self
+            .specification_service
+            .get_specifications_by_project(&project_name)
+            .await?
+            .into_iter()
+            .filter(|spec| spec.file_path.as_deref() == Some(file_path_str.as_str()))
+            .max_by_key(|spec| spec.created_at);
+        let Some(spec) = existing else {
+            let spec = self
+                .specification_service
+                .import_specification_from_file(project_name, &file_path_str, &content)
+                .await?;
+            let spec = self.mark_synced(spec, &file_hash).await?;
+            self.imported(&spec, "Imported from file").await;
+            return Ok(SyncOutcome::Imported(spec.id));
+        };
+
+        if Self::synced_field(&spec, SYNCED_FILE_HASH) == Some(file_hash.as_str()) {
+            return Ok(SyncOutcome::Unchanged);
+        }
+
+        let stored_markdown = self.render(&spec).await?;
+        let db_hash = Self::hash(&stored_markdown);
+        if db_hash == file_hash {
+            // The file was written from the stored copy, e.g. by export_specification
+            self.mark_synced(spec, &file_hash).await?;
+            return Ok(SyncOutcome::Unchanged);
+        }
+
+        // Specifications imported before syncs were recorded have nothing to compare against
+        let diverged = Self::synced_field(&spec, SYNCED_DB_HASH).is_some_and(|hash| hash != db_hash);
+        if diverged && !self.resolved_since_sync(&spec).await? {
+            let conflict_id = self.raise_conflict(&spec, &stored_markdown, &content).await?;
+            return Ok(SyncOutcome::Diverged { conflict_id });
+        }
+
+        let spec = self.specification_service.sync_specification_with_file(&spec.id, &content).await?;
+        let spec = self.mark_synced(spec, &file_hash).await?;
+        self.imported(&spec, "Synced from file").await;
+        Ok(SyncOutcome::Updated(spec.id))
+    }
+
+    /// Follow-up work on a specification read from a file
+    async fn imported(&self, spec: &ProjectSpecification, change_description: &str) {
+        self.record_version(spec, change_description).await;
+        self.suggest_links(&spec.id).await;
+    }
+
+    /// Keep the content as a version; content that is already a version is not stored again
+    async fn record_version(&self, spec: &ProjectSpecification, change_description: &str) {
+        let Some(versioning) = &self.versioning else {
+            return;
+        };
+        if let Err(e) = versioning.create_version(spec, change_description).await {
+            debug!("No new version of specification {}: {}", spec.id, e.message);
+        }
+    }
+
+    /// Suggest context links for a specification's requirements; the import stands even when
+    /// this fails
+    async fn suggest_links(&self, spec_id: &str) {
+        let Some(linker) = &self.link_suggester else {
+            return;
+        };
+        match linker.suggest_links_for_specification(spec_id, None).await {
+            Ok(links) if !links.is_empty() => info!("Suggested {} context links for specification {}", links.len(), spec_id),
+            Ok(_) => {}
+            Err(e) => warn!("Failed to suggest context links for specification {}: {}", spec_id, e),
+        }
+    }
+
+    /// Record the file a specification now matches and what its stored copy renders to
+    async fn mark_synced(&self, mut spec: ProjectSpecification, file_hash: &str) -> Result<ProjectSpecification, McpError> {
+        let db_hash = Self::hash(&self.render(&spec).await?);
+        let fields = &mut spec.metadata.custom_fields;
+        fields.insert(SYNCED_FILE_HASH.to_string(), serde_json::Value::String(file_hash.to_string()));
+        fields.insert(SYNCED_DB_HASH.to_string(), serde_json::Value::String(db_hash));
+        fields.insert(SYNCED_AT.to_string(), serde_json::Value::String(Utc::now().to_rfc3339()));
+        self.specification_service.update_specification(spec).await
+    }
+
+    /// The stored copy as markdown, which changes whenever its requirements or tasks are edited
+    async fn render(&self, spec: &ProjectSpecification) -> Result<String, McpError> {
+        let requirements = self.specification_service.get_requirements_by_spec(&spec.id).await?;
+        let tasks = self.specification_service.get_tasks_by_spec(&spec.id).await?;
+        Ok(SpecificationExporter::render_markdown(spec, &requirements, &tasks))
+    }
+
+    /// Whether the newest conflict about the specification was resolved after its last sync,
+    /// which lets the file replace the edited copy
+    async fn resolved_since_sync(&self, spec: &ProjectSpecification) -> Result<bool, McpError> {
+        let Some(engine) = &self.conflict_engine else {
+            return Ok(false);
+        };
+        let synced_at = Self::synced_field(spec, SYNCED_AT)
+            .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+            .map(|at| at.with_timezone(&Utc));
+        let newest = Self::newest_conflict(engine, spec).await?;
+        Ok(matches!(
+            (newest.and_then(|c| c.resolved_at), synced_at),
+            (Some(resolved_at), Some(synced_at)) if resolved_at > synced_at
+        ))
+    }
+
+    /// Report the divergence to the conflict engine with both versions, unless an unresolved
+    /// conflict about the specification is already waiting
+    async fn raise_conflict(&self, spec: &ProjectSpecification, stored: &str, file: &str) -> Result<Option<String>, McpError> {
+        let Some(engine) = &self.conflict_engine else {
+            return Ok(None);
+        };
+        if let Some(conflict) = Self::newest_conflict(engine, spec).await?.filter(|c| c.resolved_at.is_none()) {
+            return Ok(Some(conflict.conflict_id));
+        }
+
+        let change = |content: &str, timestamp: DateTime<Utc>| ContextChange {
+            change_id: Uuid::new_v4(),
+            change_type: ContextChangeType::Update,
+            entity_type: "specification".to_string(),
+            entity_id: spec.id.clone(),
+            project_id: spec.project_id.clone(),
+            feature_area: None,
+            delta: None,
+            full_entity: Some(serde_json::json!({
+                "id": spec.id,
+                "title": spec.title,
+                "file_path": spec.file_path,
+                "content": content
+            })),
+            metadata: ChangeMetadata {
+                user_id: None,
+                client_id: Uuid::nil(),
+                timestamp,
+                version: spec.version,
+                conflict_resolution: None,
+                sequence: None,
+            },
+        };
+
+        let conflict = engine
+            .lock()
+            .await
+            .record_conflict(ConflictType::ContentConflict, &change(stored, spec.updated_at), &change(file, Utc::now()))
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to record conflict: {}", e), None))?;
+        Ok(Some(conflict.conflict_id))
+    }
+
+    async fn newest_conflict(
+        engine: &tokio::sync::Mutex<ConflictResolutionEngine>,
+        spec: &ProjectSpecification,
+    ) -> Result<Option<ConflictInfo>, McpError> {
+        let conflicts = engine
+            .lock()
+            .await
+            .find_conflicts(&spec.project_id, None)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Failed to load conflicts: {}", e), None))?;
+        Ok(conflicts.into_iter().find(|c| c.entity_type == "specification" && c.entity_id == spec.id))
+    }
+
+    fn synced_field<'a>(spec: &'a ProjectSpecification, field: &str) -> Option<&'a str> {
+        spec.metadata.custom_fields.get(field).and_then(|v| v.as_str())
+    }
+
+    fn hash(content: &str) -> String {
+        format!("{:x}", md5::compute(content.as_bytes()))
+    }
+}
+
 #[async_trait]
 impl SpecificationImportService for DefaultSpecificationImportService {
     async fn scan_and_import_specifications(&self, base_path: &Path) -> Result<Vec<ProjectSpecification>, McpError> {
         info!("Scanning for Kiro specifications in: {}", base_path.display());
 
-        let spec_files = Self::scan_directory(base_path).await
+        let spec_files = Self::scan_directory(base_path, Self::is_kiro_spec_file).await
             .map_err(|e| McpError::internal_error(format!("Failed to scan directory: {}", e), None))?;
 
         let mut imported_specs = Vec::new();
@@ -170,6 +607,16 @@ impl SpecificationImportService for DefaultSpecificationImportService {
         Ok(imported_specs)
     }
 
+    async fn scan_and_import_features(&self, project_id: &str, base_path: &Path) -> Result<Vec<ProjectSpecification>, McpError> {
+        self.scan_and_import_documents(project_id, base_path, GherkinParser::is_feature_file, Self::parse_feature)
+            .await
+    }
+
+    async fn scan_and_import_markdown(&self, project_id: &str, base_path: &Path) -> Result<Vec<ProjectSpecification>, McpError> {
+        self.scan_and_import_documents(project_id, base_path, Self::is_markdown_file, Self::parse_markdown_document)
+            .await
+    }
+
     async fn import_specification_file(&self, file_path: &Path) -> Result<ProjectSpecification, McpError> {
         debug!("Importing specification file: {}", file_path.display());
 
@@ -186,8 +633,10 @@ impl SpecificationImportService for DefaultSpecificationImportService {
         let spec = self.specification_service
             .import_specification_from_file(project_name, &file_path_str, &content)
             .await?;
+        // Monitors compare later versions of the file and the stored copy against this import
+        let spec = self.monitor.mark_synced(spec, &SpecMonitor::hash(&content)).await?;
+        self.monitor.imported(&spec, "Imported from file").await;
 
-        // Record the change (simplified for now)
         debug!("Created specification {} from file {}", spec.id, file_path.display());
 
         Ok(spec)
@@ -196,15 +645,28 @@ impl SpecificationImportService for DefaultSpecificationImportService {
     async fn start_file_monitoring(&self, base_path: &Path) -> Result<(), McpError> {
         info!("Starting file system monitoring for: {}", base_path.display());
 
-        // For now, just return success - file monitoring would need to be implemented
-        // with proper lifetime management in a production system
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            if let Ok(event) = event {
+                let _ = tx.send(event);
+            }
+        })
+        .map_err(|e| McpError::internal_error(format!("Failed to create file watcher: {}", e), None))?;
+        watcher
+            .watch(base_path, RecursiveMode::Recursive)
+            .map_err(|e| McpError::internal_error(format!("Failed to watch {}: {}", base_path.display(), e), None))?;
+
+        // Replacing a monitor of the same directory drops its watcher, which ends its task
+        self.watchers.lock().insert(base_path.to_path_buf(), watcher);
+        tokio::spawn(self.monitor.clone().run(rx));
+
         info!("File system monitoring started successfully for: {}", base_path.display());
         Ok(())
     }
 
     async fn stop_file_monitoring(&self) -> Result<(), McpError> {
         info!("Stopping file system monitoring");
-        // The watcher will be dropped when the service is dropped
+        self.watchers.lock().clear();
         Ok(())
     }
 
@@ -456,6 +918,18 @@ mod tests {
         async fn unlink_task_from_requirement(&self, _task_id: &str, _requirement_id: &str) -> Result<(), McpError> {
             Ok(())
         }
+
+        async fn find_requirement_ids_by_context(&self, _context_id: &str) -> Result<Vec<String>, McpError> {
+            Ok(Vec::new())
+        }
+
+        async fn suggest_requirement_context_link(&self, _link: &crate::models::specification::RequirementContextLink) -> Result<(), McpError> {
+            Ok(())
+        }
+
+        async fn find_suggested_context_links(&self, _spec_id: &str) -> Result<Vec<crate::models::specification::RequirementContextLink>, McpError> {
+            Ok(Vec::new())
+        }
     }
 
     #[tokio::test]
@@ -503,6 +977,133 @@ mod tests {
         assert!(issues.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_sync_updates_in_place_and_raises_conflict_on_divergence() {
+        use crate::infrastructure::SqliteSpecificationRepository;
+        use crate::models::specification::TaskStatus;
+        use crate::services::conflict_resolution_engine::ManualResolutionRequest;
+        use crate::services::websocket_types::ConflictStrategy;
+        use crate::services::DefaultSpecificationService;
+
+        let db = Arc::new(std::sync::Mutex::new(crate::db::init::init_db(":memory:").unwrap()));
+        let repository = Arc::new(SqliteSpecificationRepository::new(db));
+        repository.initialize_tables().unwrap();
+        let engine = Arc::new(tokio::sync::Mutex::new(ConflictResolutionEngine::new()));
+        let service = DefaultSpecificationImportService::new(
+            Arc::new(DefaultSpecificationService::new(repository.clone())),
+            repository.clone(),
+        )
+        .with_conflict_engine(engine.clone());
+
+        let temp_dir = TempDir::new().unwrap();
+        let specs_dir = temp_dir.path().join(".kiro").join("specs").join("login");
+        fs::create_dir_all(&specs_dir).await.unwrap();
+        let tasks_file = specs_dir.join("tasks.md");
+        fs::write(&tasks_file, "# Implementation Plan\n\n- [ ] 1. Add the form\n").await.unwrap();
+
+        let SyncOutcome::Imported(spec_id) = service.monitor.sync_file(&tasks_file).await.unwrap() else {
+            panic!("expected an import");
+        };
+        assert_eq!(service.monitor.sync_file(&tasks_file).await.unwrap(), SyncOutcome::Unchanged);
+
+        fs::write(&tasks_file, "# Implementation Plan\n\n- [ ] 1. Add the form\n\n- [ ] 2. Add the session store\n")
+            .await
+            .unwrap();
+        assert_eq!(service.monitor.sync_file(&tasks_file).await.unwrap(), SyncOutcome::Updated(spec_id.clone()));
+        assert_eq!(repository.find_specifications_by_project("login").await.unwrap().len(), 1);
+        assert_eq!(repository.find_tasks_by_spec(&spec_id).await.unwrap().len(), 2);
+
+        // A task completed through the server, then the file edited on its own
+        let mut task = repository.find_tasks_by_spec(&spec_id).await.unwrap().remove(0);
+        task.update_status(TaskStatus::Completed);
+        repository.update_task(&task).await.unwrap();
+        fs::write(&tasks_file, "# Implementation Plan\n\n- [ ] 1. Add the login form\n\n- [ ] 2. Add the session store\n")
+            .await
+            .unwrap();
+
+        let SyncOutcome::Diverged { conflict_id: Some(conflict_id) } = service.monitor.sync_file(&tasks_file).await.unwrap() else {
+            panic!("expected a conflict");
+        };
+        let conflict = engine.lock().await.get_conflict_info(&conflict_id).cloned().unwrap();
+        assert_eq!(conflict.entity_id, spec_id);
+        assert_eq!(conflict.conflict_type, ConflictType::ContentConflict);
+        assert_eq!(conflict.conflicting_changes.len(), 2);
+        let stored = repository.find_tasks_by_spec(&spec_id).await.unwrap();
+        assert_eq!(stored[0].status, TaskStatus::Completed);
+        assert_eq!(stored[0].title, "Add the form");
+
+        // Saving again while the conflict is open reports the same conflict
+        assert_eq!(
+            service.monitor.sync_file(&tasks_file).await.unwrap(),
+            SyncOutcome::Diverged { conflict_id: Some(conflict_id.clone()) }
+        );
+
+        engine
+            .lock()
+            .await
+            .resolve_conflict_manually(ManualResolutionRequest {
+                conflict_id,
+                resolution_strategy: ConflictStrategy::ManualResolution,
+                resolved_entity: None,
+                resolution_notes: None,
+                resolved_by: "reviewer".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(service.monitor.sync_file(&tasks_file).await.unwrap(), SyncOutcome::Updated(spec_id.clone()));
+        assert_eq!(repository.find_tasks_by_spec(&spec_id).await.unwrap()[0].title, "Add the login form");
+    }
+
+    #[tokio::test]
+    async fn test_scan_features_and_markdown_updates_in_place() {
+        use crate::infrastructure::SqliteSpecificationRepository;
+        use crate::services::DefaultSpecificationService;
+
+        let db = Arc::new(std::sync::Mutex::new(crate::db::init::init_db(":memory:").unwrap()));
+        let repository = Arc::new(SqliteSpecificationRepository::new(db));
+        repository.initialize_tables().unwrap();
+        let service = DefaultSpecificationImportService::new(
+            Arc::new(DefaultSpecificationService::new(repository.clone())),
+            repository.clone(),
+        );
+
+        let temp_dir = TempDir::new().unwrap();
+        let features = temp_dir.path().join("billing").join("features");
+        fs::create_dir_all(&features).await.unwrap();
+        let invoices = features.join("invoices.feature");
+        fs::write(&invoices, "Feature: Invoices\n  Scenario: Issued\n    When the period ends\n    Then an invoice is issued\n")
+            .await
+            .unwrap();
+        fs::write(features.join("notes.txt"), "not a feature").await.unwrap();
+        fs::write(temp_dir.path().join("overview.md"), "Intro\n\n# Billing overview\n\nText.\n").await.unwrap();
+
+        let specs = service.scan_and_import_features("p1", &features).await.unwrap();
+        assert_eq!(specs.len(), 1);
+        assert_eq!(specs[0].spec_type, SpecType::Feature);
+        assert_eq!(specs[0].title, "Invoices");
+        let spec_id = specs[0].id.clone();
+        let requirements = repository.find_requirements_by_spec(&spec_id).await.unwrap();
+        assert_eq!(requirements[0].acceptance_criteria[0].description, "WHEN the period ends THEN an invoice is issued");
+
+        fs::write(
+            &invoices,
+            "Feature: Invoices\n  Scenario: Issued\n    When the period ends\n    Then an invoice is issued\n  Scenario: Voided\n    Then nothing is owed\n",
+        )
+        .await
+        .unwrap();
+        let rescanned = service.scan_and_import_features("p1", &features).await.unwrap();
+        assert_eq!(rescanned[0].id, spec_id);
+        assert_eq!(rescanned[0].version, 2);
+        assert_eq!(repository.find_specifications_by_project("p1").await.unwrap().len(), 1);
+        assert_eq!(repository.find_requirements_by_spec(&spec_id).await.unwrap().len(), 2);
+
+        // The markdown scan picks up every markdown file, including ones in subdirectories
+        let documents = service.scan_and_import_markdown("p1", temp_dir.path()).await.unwrap();
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0].title, "Billing overview");
+        assert_eq!(repository.find_specifications_by_project("p1").await.unwrap().len(), 2);
+    }
+
     #[test]
     fn test_extract_project_name() {
         let path = Path::new(".kiro/specs/test-project/requirements.md");
@@ -2,6 +2,7 @@ use crate::models::specification::{
     AcceptanceCriterion, CriterionType, ProjectSpecification, Requirement, RequirementStatus,
     SpecContent, SpecFormat, SpecType, Task, TaskStatus, TaskType,
 };
+use crate::services::ears_linter::{EarsLinter, SpecQualityReport};
 use anyhow::{anyhow, Result};
 use regex::Regex;
 use serde_json::Value;
@@ -409,6 +410,9 @@ impl SpecificationParser {
         match spec.spec_type {
             SpecType::Requirements => {
                 Self::validate_requirements_spec(&spec.content, &mut issues);
+                if let Some(report) = Self::criteria_quality(spec)? {
+                    issues.extend(report.flagged.iter().map(|lint| lint.describe()));
+                }
             }
             SpecType::Tasks => {
                 Self::validate_tasks_spec(&spec.content, &mut issues);
@@ -419,6 +423,15 @@ impl SpecificationParser {
         Ok(issues)
     }
 
+    /// EARS quality of the acceptance criteria of a markdown requirements specification
+    pub fn criteria_quality(spec: &ProjectSpecification) -> Result<Option<SpecQualityReport>> {
+        if spec.spec_type != SpecType::Requirements || spec.content.format != SpecFormat::Markdown {
+            return Ok(None);
+        }
+        let requirements = Self::parse_requirements_from_markdown(&spec.content.raw_content, spec.id.clone())?;
+        Ok(Some(EarsLinter::lint_requirements(&requirements)))
+    }
+
     /// Validate markdown specification
     fn validate_markdown_spec(content: &SpecContent, issues: &mut Vec<String>) {
         if !content.raw_content.contains('#') {
@@ -563,4 +576,24 @@ This section contains requirements.
         let issues = SpecificationParser::validate_specification(&spec).unwrap();
         assert!(issues.is_empty());
     }
+
+    #[test]
+    fn test_validate_flags_criteria_outside_ears() {
+        let content = "# Requirements\n\n### Requirement 1\n\n#### Acceptance Criteria\n\n1. WHEN a user signs in THEN the system SHALL show the dashboard\n2. The dashboard should load fast\n";
+        let spec = ProjectSpecification::new(
+            "test".to_string(),
+            SpecType::Requirements,
+            "Test Spec".to_string(),
+            SpecContent::new(SpecFormat::Markdown, content.to_string()),
+        );
+
+        let issues = SpecificationParser::validate_specification(&spec).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].starts_with("Requirement 1, criterion 2: does not follow an EARS pattern"));
+        assert!(issues[0].ends_with("Suggested rewrite: WHEN <trigger> THEN the dashboard SHALL load within <N> ms"));
+
+        let report = SpecificationParser::criteria_quality(&spec).unwrap().unwrap();
+        assert_eq!((report.criteria_count, report.passing), (2, 1));
+        assert_eq!(report.score, Some(50.0));
+    }
 }
\ No newline at end of file
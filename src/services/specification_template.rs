@@ -0,0 +1,332 @@
+use crate::models::context::{FeatureContext, ProjectConvention};
+use crate::models::development::DevelopmentPhase;
+use crate::models::framework::FrameworkComponent;
+use std::collections::BTreeMap;
+
+/// What a new feature's specification scaffold is pre-filled from
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext {
+    pub feature_name: String,
+    pub description: Option<String>,
+    /// Feature context with the same name, for personas, workflows and edge cases
+    pub feature_context: Option<FeatureContext>,
+    pub components: Vec<FrameworkComponent>,
+    pub conventions: Vec<ProjectConvention>,
+    pub phases: Vec<DevelopmentPhase>,
+}
+
+/// Kiro spec documents of one feature
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpecTemplate {
+    /// `.kiro/specs` directory name for the feature
+    pub directory: String,
+    pub requirements: String,
+    pub design: String,
+    pub tasks: String,
+}
+
+impl SpecTemplate {
+    /// File name and content of each document
+    pub fn documents(&self) -> [(&'static str, &str); 3] {
+        [
+            ("requirements.md", self.requirements.as_str()),
+            ("design.md", self.design.as_str()),
+            ("tasks.md", self.tasks.as_str()),
+        ]
+    }
+}
+
+/// Generates `requirements.md`, `design.md` and `tasks.md` scaffolds in the layout
+/// SpecificationParser reads. Parts the project's context cannot fill are left as `TODO`.
+pub struct SpecTemplateGenerator;
+
+impl SpecTemplateGenerator {
+    pub fn generate(context: &TemplateContext) -> SpecTemplate {
+        SpecTemplate {
+            directory: Self::directory_name(&context.feature_name),
+            requirements: Self::render_requirements(context),
+            design: Self::render_design(context),
+            tasks: Self::render_tasks(context),
+        }
+    }
+
+    /// Kebab-case directory name, e.g. `user-login` for "User Login"
+    pub fn directory_name(feature_name: &str) -> String {
+        feature_name
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    /// One requirement per key workflow of the feature context, or a single placeholder
+    pub fn render_requirements(context: &TemplateContext) -> String {
+        let feature = context.feature_context.as_ref();
+        let mut out = String::from("# Requirements Document\n\n## Introduction\n\n");
+        out.push_str(&Self::introduction(context));
+        out.push_str("\n\n## Requirements\n");
+
+        let persona = feature
+            .and_then(|f| json_list(f.user_personas.as_deref()).into_iter().next())
+            .unwrap_or_else(|| "user".to_string());
+        let purpose = feature
+            .and_then(|f| f.business_purpose.clone())
+            .map(|purpose| Self::lowercase_first(purpose.trim().trim_end_matches('.')))
+            .unwrap_or_else(|| "TODO: benefit".to_string());
+        let mut workflows: Vec<String> = feature
+            .map(|f| json_list(f.key_workflows.as_deref()))
+            .unwrap_or_default()
+            .iter()
+            .map(|workflow| Self::lowercase_first(workflow.trim().trim_end_matches('.')))
+            .collect();
+        if workflows.is_empty() {
+            workflows.push("TODO: capability".to_string());
+        }
+
+        for (index, workflow) in workflows.iter().enumerate() {
+            out.push_str(&format!(
+                "\n### Requirement {}\n\n**User Story:** As a {}, I want to {}, so that {}\n\n#### Acceptance Criteria\n\n",
+                index + 1,
+                persona,
+                workflow,
+                purpose
+            ));
+            out.push_str("1. WHEN TODO: trigger THEN the system SHALL TODO: response\n");
+        }
+        out
+    }
+
+    /// Overview, the project's layers and components, edge cases and conventions to follow
+    pub fn render_design(context: &TemplateContext) -> String {
+        let mut out = format!("# Design Document\n\n## Overview\n\n{}\n", Self::introduction(context));
+
+        out.push_str("\n## Architecture\n\n");
+        let mut layers: BTreeMap<&str, Vec<&FrameworkComponent>> = BTreeMap::new();
+        for component in &context.components {
+            layers.entry(component.architecture_layer.as_str()).or_default().push(component);
+        }
+        if layers.is_empty() {
+            out.push_str("TODO: describe where the feature fits in the architecture.\n");
+        } else {
+            out.push_str("Layers of the project, with components this feature may use or extend:\n\n");
+            for (layer, components) in &layers {
+                let names: Vec<&str> = components.iter().map(|c| c.component_name.as_str()).collect();
+                out.push_str(&format!("- **{}**: {}\n", layer, names.join(", ")));
+            }
+        }
+
+        out.push_str("\n## Components and Interfaces\n\n");
+        let integration_points = context
+            .feature_context
+            .as_ref()
+            .map(|f| json_list(f.integration_points.as_deref()))
+            .unwrap_or_default();
+        for point in &integration_points {
+            out.push_str(&format!("- Integrates with {}\n", point));
+        }
+        out.push_str("- TODO: new components, their type and layer\n");
+
+        out.push_str("\n## Data Models\n\nTODO: entities and their fields.\n");
+
+        out.push_str("\n## Error Handling\n\n");
+        let edge_cases = context
+            .feature_context
+            .as_ref()
+            .map(|f| json_list(f.edge_cases.as_deref()))
+            .unwrap_or_default();
+        for edge_case in &edge_cases {
+            out.push_str(&format!("- {}\n", edge_case));
+        }
+        out.push_str("- TODO: failure modes and how they are reported\n");
+
+        out.push_str("\n## Testing Strategy\n\nTODO: unit, integration and end-to-end coverage.\n");
+
+        if !context.conventions.is_empty() {
+            out.push_str("\n## Conventions\n\n");
+            for convention in &context.conventions {
+                let Some(rule) = convention.convention_rule.as_deref() else {
+                    continue;
+                };
+                match convention.convention_type.as_deref() {
+                    Some(kind) => out.push_str(&format!("- **{}**: {}\n", kind, rule)),
+                    None => out.push_str(&format!("- {}\n", rule)),
+                }
+            }
+        }
+        out
+    }
+
+    /// A task per development phase, in phase order, with its completion criteria as
+    /// details; a generic plan when the project has no phases
+    pub fn render_tasks(context: &TemplateContext) -> String {
+        let mut out = String::from("# Implementation Plan\n");
+
+        let mut phases: Vec<&DevelopmentPhase> = context.phases.iter().collect();
+        phases.sort_by_key(|phase| phase.phase_order);
+        let plan: Vec<(String, Vec<String>)> = if phases.is_empty() {
+            vec![
+                ("Set up the feature's structure".to_string(), Vec::new()),
+                (format!("Implement {}", context.feature_name), Vec::new()),
+                ("Write tests".to_string(), Vec::new()),
+            ]
+        } else {
+            phases
+                .iter()
+                .map(|phase| {
+                    let mut details: Vec<String> = phase.description.iter().cloned().collect();
+                    details.extend(phase.completion_criteria.iter().cloned());
+                    (format!("{}: {}", phase.phase_name, context.feature_name), details)
+                })
+                .collect()
+        };
+
+        for (index, (title, details)) in plan.iter().enumerate() {
+            out.push_str(&format!("\n- [ ] {}. {}\n", index + 1, title));
+            for detail in details.iter().filter(|d| !d.trim().is_empty()) {
+                out.push_str(&format!("  - {}\n", detail.trim()));
+            }
+            out.push_str("  - TODO: steps\n  - _Requirements: 1_\n");
+        }
+        out
+    }
+
+    fn introduction(context: &TemplateContext) -> String {
+        context
+            .description
+            .clone()
+            .or_else(|| context.feature_context.as_ref().and_then(|f| f.business_purpose.clone()))
+            .map(|text| text.trim().to_string())
+            .unwrap_or_else(|| format!("TODO: what {} does and why.", context.feature_name))
+    }
+
+    /// `Sign in` becomes `sign in` to continue a sentence; acronyms such as `API` are kept
+    fn lowercase_first(text: &str) -> String {
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(first), second) if !second.is_some_and(char::is_uppercase) => {
+                first.to_lowercase().chain(second).chain(chars).collect()
+            }
+            _ => text.to_string(),
+        }
+    }
+}
+
+/// Items of a JSON array column; a plain string is a single item
+pub(crate) fn json_list(value: Option<&str>) -> Vec<String> {
+    let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+        return Vec::new();
+    };
+    match serde_json::from_str::<Vec<serde_json::Value>>(value) {
+        Ok(items) => items
+            .into_iter()
+            .map(|item| match item {
+                serde_json::Value::String(text) => text,
+                other => other.to_string(),
+            })
+            .collect(),
+        Err(_) => vec![value.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::development::PhaseStatus;
+    use crate::services::SpecificationParser;
+
+    #[test]
+    fn test_template_is_filled_from_context_and_parses() {
+        let component = |name: &str, layer: &str| FrameworkComponent {
+            id: name.to_string(),
+            project_id: "p1".to_string(),
+            component_name: name.to_string(),
+            component_type: "service".to_string(),
+            architecture_layer: layer.to_string(),
+            file_path: None,
+            dependencies: Vec::new(),
+            metadata: None,
+            created_at: None,
+            updated_at: None,
+        };
+        let phase = |name: &str, order: i32| DevelopmentPhase {
+            id: name.to_string(),
+            project_id: "p1".to_string(),
+            phase_name: name.to_string(),
+            phase_order: order,
+            status: PhaseStatus::Pending,
+            description: None,
+            completion_criteria: vec![format!("{} reviewed", name)],
+            dependencies: Vec::new(),
+            started_at: None,
+            completed_at: None,
+            created_at: None,
+        };
+        let context = TemplateContext {
+            feature_name: "User Login".to_string(),
+            description: None,
+            feature_context: Some(FeatureContext {
+                id: "f1".to_string(),
+                project_id: "p1".to_string(),
+                feature_name: "User Login".to_string(),
+                business_purpose: Some("Customers reach their orders.".to_string()),
+                user_personas: Some(r#"["customer"]"#.to_string()),
+                key_workflows: Some(r#"["Sign in with email", "Reset a password"]"#.to_string()),
+                integration_points: None,
+                edge_cases: Some(r#"["Account locked"]"#.to_string()),
+                constraints: None,
+                related_components: None,
+                status: None,
+                owner: None,
+                created_at: None,
+            }),
+            components: vec![component("AuthService", "domain"), component("LoginScreen", "presentation")],
+            conventions: vec![ProjectConvention {
+                id: "c1".to_string(),
+                project_id: "p1".to_string(),
+                convention_type: Some("naming".to_string()),
+                convention_rule: Some("Services end in Service".to_string()),
+                pattern: None,
+                good_examples: None,
+                bad_examples: None,
+                rationale: None,
+                enforcement_level: None,
+                created_at: None,
+            }],
+            phases: vec![phase("Testing", 2), phase("Build", 1)],
+        };
+
+        let template = SpecTemplateGenerator::generate(&context);
+        assert_eq!(template.directory, "user-login");
+
+        let requirements = SpecificationParser::parse_requirements_from_markdown(&template.requirements, "s1".to_string()).unwrap();
+        assert_eq!(requirements.len(), 2);
+        assert_eq!(
+            requirements[1].user_story.as_deref(),
+            Some("As a customer, I want to reset a password, so that customers reach their orders")
+        );
+        assert_eq!(requirements[0].acceptance_criteria.len(), 1);
+
+        assert!(template.design.contains("- **domain**: AuthService\n- **presentation**: LoginScreen\n"));
+        assert!(template.design.contains("- Account locked\n"));
+        assert!(template.design.contains("- **naming**: Services end in Service\n"));
+
+        let tasks = SpecificationParser::parse_tasks_from_markdown(&template.tasks, "s1".to_string()).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].title, "Build: User Login");
+        assert!(template.tasks.contains("  - Testing reviewed\n"));
+    }
+
+    #[test]
+    fn test_template_without_context_uses_placeholders() {
+        let template = SpecTemplateGenerator::generate(&TemplateContext {
+            feature_name: "Exports".to_string(),
+            description: Some("CSV exports of invoices.".to_string()),
+            ..Default::default()
+        });
+        assert!(template.requirements.contains("## Introduction\n\nCSV exports of invoices.\n"));
+        assert!(template.requirements.contains("**User Story:** As a user, I want to TODO: capability, so that TODO: benefit"));
+        assert!(template.tasks.contains("- [ ] 2. Implement Exports\n"));
+        assert!(template.design.contains("TODO: describe where the feature fits"));
+    }
+}
@@ -1,7 +1,8 @@
+use crate::config::VersionRetentionSettings;
 use crate::models::specification::ProjectSpecification;
 use anyhow::Result;
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use rmcp::model::ErrorData as McpError;
 use rusqlite::{params, Connection, Row, OptionalExtension};
 use serde::{Deserialize, Serialize};
@@ -32,6 +33,10 @@ pub trait SpecificationVersioningService: Send + Sync {
     
     /// Delete old versions (keep only the latest N versions)
     async fn cleanup_old_versions(&self, spec_id: &str, keep_count: usize) -> Result<usize, McpError>;
+
+    /// Keep the milestone versions of a specification, fold the versions between them into the
+    /// next kept one and delete versions past the retention age
+    async fn squash_versions(&self, spec_id: &str, retention: &VersionRetentionSettings, dry_run: bool) -> Result<VersionSquashReport, McpError>;
 }
 
 /// Represents a version of a specification
@@ -103,6 +108,54 @@ pub struct VersionDifference {
     pub line_number: Option<usize>,
 }
 
+/// Metadata key of the steps a kept version stands for after squashing
+pub const SQUASHED_STEPS_KEY: &str = "squashed_steps";
+
+/// Metadata key that marks a version as a milestone, which is never squashed or pruned
+pub const MILESTONE_KEY: &str = "milestone";
+
+/// A version folded into a later one by squashing, with its changes to the version before it.
+/// A kept version lists the steps since the previous kept version, ending with itself, so the
+/// changes between any two kept versions can still be replayed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SquashedStep {
+    pub version_id: String,
+    pub version_number: u32,
+    pub created_at: DateTime<Utc>,
+    pub change_description: String,
+    pub differences: Vec<VersionDifference>,
+}
+
+/// A version as listed in a squash report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionSummary {
+    pub id: String,
+    pub version_number: u32,
+    pub created_at: DateTime<Utc>,
+    pub change_description: String,
+}
+
+impl From<&SpecificationVersion> for VersionSummary {
+    fn from(version: &SpecificationVersion) -> Self {
+        Self {
+            id: version.id.clone(),
+            version_number: version.version_number,
+            created_at: version.created_at,
+            change_description: version.change_description.clone(),
+        }
+    }
+}
+
+/// What squashing a specification's versions kept, folded and deleted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionSquashReport {
+    pub spec_id: String,
+    pub kept: Vec<VersionSummary>,
+    pub squashed: Vec<VersionSummary>,
+    pub pruned: Vec<VersionSummary>,
+    pub dry_run: bool,
+}
+
 /// Types of differences between versions
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum DifferenceType {
@@ -115,11 +168,22 @@ pub enum DifferenceType {
 /// SQLite implementation of SpecificationVersioningService
 pub struct SqliteSpecificationVersioningService {
     db: Arc<Mutex<Connection>>,
+    retention: VersionRetentionSettings,
 }
 
 impl SqliteSpecificationVersioningService {
     pub fn new(db: Arc<Mutex<Connection>>) -> Self {
-        Self { db }
+        Self {
+            db,
+            retention: VersionRetentionSettings::default(),
+        }
+    }
+
+    /// Squash each specification's versions by these settings whenever one is created, if
+    /// `auto_squash` is set
+    pub fn with_retention(mut self, retention: VersionRetentionSettings) -> Self {
+        self.retention = retention;
+        self
     }
 
     /// Initialize database tables for specification versioning
@@ -158,6 +222,135 @@ impl SqliteSpecificationVersioningService {
         Ok(())
     }
 
+    /// Versions tagged as milestones by hand
+    fn is_tagged_milestone(version: &SpecificationVersion) -> bool {
+        version.metadata.get(MILESTONE_KEY).and_then(|v| v.as_bool()).unwrap_or(false)
+    }
+
+    /// Versions kept by squashing: tagged milestones, creations, restores and merges, the oldest
+    /// version, the `keep_recent` newest, and the last version of every `milestone_interval_days`
+    fn milestones(versions: &[SpecificationVersion], retention: &VersionRetentionSettings) -> Vec<bool> {
+        let recent_from = versions.len().saturating_sub(retention.keep_recent.max(1));
+        let interval_secs = i64::from(retention.milestone_interval_days) * 86_400;
+        let period = |version: &SpecificationVersion| version.created_at.timestamp().div_euclid(interval_secs);
+
+        versions
+            .iter()
+            .enumerate()
+            .map(|(index, version)| {
+                index == 0
+                    || index >= recent_from
+                    || Self::is_tagged_milestone(version)
+                    || matches!(
+                        version.change_type,
+                        VersionChangeType::Created | VersionChangeType::Restored | VersionChangeType::Merged
+                    )
+                    || (interval_secs > 0 && period(version) != period(&versions[index + 1]))
+            })
+            .collect()
+    }
+
+    /// Steps that lead to a version: those it already stands for, or its own change
+    fn steps_to(version: &SpecificationVersion, previous: Option<&SpecificationVersion>) -> Vec<SquashedStep> {
+        let existing: Vec<SquashedStep> = version
+            .metadata
+            .get(SQUASHED_STEPS_KEY)
+            .and_then(|steps| serde_json::from_value(steps.clone()).ok())
+            .unwrap_or_default();
+        if !existing.is_empty() {
+            return existing;
+        }
+        vec![SquashedStep {
+            version_id: version.id.clone(),
+            version_number: version.version_number,
+            created_at: version.created_at,
+            change_description: version.change_description.clone(),
+            differences: Self::calculate_differences(previous.map_or("", |p| p.raw_content.as_str()), &version.raw_content),
+        }]
+    }
+
+    fn squash_locked(
+        db: &Connection,
+        spec_id: &str,
+        retention: &VersionRetentionSettings,
+        dry_run: bool,
+        now: DateTime<Utc>,
+    ) -> Result<VersionSquashReport, McpError> {
+        let db_error = |e: rusqlite::Error| McpError::internal_error(format!("Database error: {}", e), None);
+        let mut stmt = db.prepare(
+            r#"
+            SELECT id, spec_id, version_number, content_hash, raw_content, parsed_sections,
+                   change_description, change_type, created_at, created_by, file_path, metadata
+            FROM specification_versions WHERE spec_id = ? ORDER BY created_at ASC, version_number ASC
+            "#
+        ).map_err(db_error)?;
+        let versions = stmt
+            .query_map([spec_id], Self::row_to_version)
+            .map_err(db_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_error)?;
+
+        // Pruning drops versions past the age limit along with their changes
+        let cutoff = (retention.max_age_days > 0).then(|| now - Duration::days(i64::from(retention.max_age_days)));
+        let newest_id = versions.last().map(|version| version.id.clone());
+        let (pruned, remaining): (Vec<SpecificationVersion>, Vec<SpecificationVersion>) =
+            versions.into_iter().partition(|version| {
+                cutoff.is_some_and(|cutoff| version.created_at < cutoff)
+                    && Some(&version.id) != newest_id.as_ref()
+                    && !Self::is_tagged_milestone(version)
+            });
+
+        let milestones = Self::milestones(&remaining, retention);
+        let mut kept = Vec::new();
+        let mut squashed = Vec::new();
+        let mut updated: Vec<(String, HashMap<String, serde_json::Value>)> = Vec::new();
+        let mut pending: Vec<SquashedStep> = Vec::new();
+        for (index, version) in remaining.iter().enumerate() {
+            let steps = Self::steps_to(version, index.checked_sub(1).map(|i| &remaining[i]));
+            if !milestones[index] {
+                pending.extend(steps);
+                squashed.push(VersionSummary::from(version));
+                continue;
+            }
+            if !pending.is_empty() {
+                pending.extend(steps);
+                let mut metadata = version.metadata.clone();
+                metadata.insert(
+                    SQUASHED_STEPS_KEY.to_string(),
+                    serde_json::to_value(std::mem::take(&mut pending))
+                        .map_err(|e| McpError::internal_error(format!("Failed to serialize squashed steps: {}", e), None))?,
+                );
+                updated.push((version.id.clone(), metadata));
+            }
+            kept.push(VersionSummary::from(version));
+        }
+
+        let report = VersionSquashReport {
+            spec_id: spec_id.to_string(),
+            kept,
+            squashed,
+            pruned: pruned.iter().map(VersionSummary::from).collect(),
+            dry_run,
+        };
+        if dry_run || (report.squashed.is_empty() && report.pruned.is_empty()) {
+            return Ok(report);
+        }
+
+        let tx = db.unchecked_transaction().map_err(db_error)?;
+        for version in report.squashed.iter().chain(&report.pruned) {
+            tx.execute("DELETE FROM specification_versions WHERE id = ?", [&version.id]).map_err(db_error)?;
+        }
+        for (id, metadata) in &updated {
+            let metadata_json = serde_json::to_string(metadata)
+                .map_err(|e| McpError::internal_error(format!("Failed to serialize metadata: {}", e), None))?;
+            tx.execute("UPDATE specification_versions SET metadata = ? WHERE id = ?", params![metadata_json, id])
+                .map_err(db_error)?;
+        }
+        tx.commit().map_err(db_error)?;
+
+        Ok(report)
+    }
+
     /// Calculate content hash for change detection
     fn calculate_content_hash(content: &str) -> String {
         use std::collections::hash_map::DefaultHasher;
@@ -336,6 +529,13 @@ impl SpecificationVersioningService for SqliteSpecificationVersioningService {
             ],
         ).map_err(|e| McpError::internal_error(format!("Database error: {}", e), None))?;
 
+        if self.retention.auto_squash {
+            // The new version is the newest, so squashing never removes it
+            if let Err(e) = Self::squash_locked(&db, &spec.id, &self.retention, false, Utc::now()) {
+                tracing::warn!("Failed to squash versions of specification {}: {}", spec.id, e.message);
+            }
+        }
+
         Ok(version)
     }
 
@@ -435,6 +635,11 @@ impl SpecificationVersioningService for SqliteSpecificationVersioningService {
         Err(McpError::internal_error("restore_to_version not fully implemented".to_string(), None))
     }
 
+    async fn squash_versions(&self, spec_id: &str, retention: &VersionRetentionSettings, dry_run: bool) -> Result<VersionSquashReport, McpError> {
+        let db = self.db.lock().unwrap();
+        Self::squash_locked(&db, spec_id, retention, dry_run, Utc::now())
+    }
+
     async fn cleanup_old_versions(&self, spec_id: &str, keep_count: usize) -> Result<usize, McpError> {
         let db = self.db.lock().unwrap();
 
@@ -550,6 +755,51 @@ mod tests {
         assert!(comparison.similarity_score < 1.0);
     }
 
+    #[tokio::test]
+    async fn test_squash_keeps_milestones_with_their_steps() {
+        let db = Connection::open_in_memory().unwrap();
+        // The versions table references specifications, which this test does not store
+        db.execute_batch("PRAGMA foreign_keys = OFF").unwrap();
+        let service = SqliteSpecificationVersioningService::new(Arc::new(Mutex::new(db)));
+        service.initialize_tables().unwrap();
+
+        // Two edits on the first day, one two days later and two two days after that
+        let start = (Utc::now() - Duration::days(10)).date_naive().and_hms_opt(8, 0, 0).unwrap().and_utc();
+        let mut spec = create_test_spec();
+        let mut ids = Vec::new();
+        for (number, hours) in [0, 1, 2, 48, 96, 97].into_iter().enumerate() {
+            spec.version = number as u32 + 1;
+            spec.content.raw_content = format!("# Tasks\n\n- [x] 1. Done\n- [ ] {}. Next", number + 2);
+            let version = service.create_version(&spec, &format!("Edit {}", number + 1)).await.unwrap();
+            let created_at = (start + Duration::hours(hours)).to_rfc3339();
+            service.db.lock().unwrap()
+                .execute("UPDATE specification_versions SET created_at = ? WHERE id = ?", params![created_at, version.id])
+                .unwrap();
+            ids.push(version.id);
+        }
+
+        let retention = VersionRetentionSettings { auto_squash: false, keep_recent: 1, milestone_interval_days: 1, max_age_days: 0 };
+        let preview = service.squash_versions(&spec.id, &retention, true).await.unwrap();
+        assert_eq!(preview.squashed.iter().map(|v| v.id.as_str()).collect::<Vec<_>>(), vec![ids[1].as_str(), ids[4].as_str()]);
+        assert_eq!(service.get_versions(&spec.id).await.unwrap().len(), 6);
+
+        let report = service.squash_versions(&spec.id, &retention, false).await.unwrap();
+        assert_eq!(report.kept.len(), 4);
+        let day_one = service.get_version(&ids[2]).await.unwrap().unwrap();
+        let steps: Vec<SquashedStep> = serde_json::from_value(day_one.metadata[SQUASHED_STEPS_KEY].clone()).unwrap();
+        assert_eq!(steps.iter().map(|s| s.version_number).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(steps[0].differences[0].old_content.as_deref(), Some("- [ ] 2. Next"));
+        assert_eq!(steps[0].differences[0].new_content.as_deref(), Some("- [ ] 3. Next"));
+        assert!(service.compare_versions(&ids[0], &ids[2]).await.is_ok());
+
+        // Pruning the first day leaves the next milestone as the oldest version
+        let retention = VersionRetentionSettings { max_age_days: 9, ..retention };
+        let report = service.squash_versions(&spec.id, &retention, false).await.unwrap();
+        assert_eq!(report.pruned.iter().map(|v| v.id.as_str()).collect::<Vec<_>>(), vec![ids[0].as_str(), ids[2].as_str()]);
+        let versions = service.get_versions(&spec.id).await.unwrap();
+        assert_eq!(versions.iter().map(|v| v.id.as_str()).collect::<Vec<_>>(), vec![ids[5].as_str(), ids[3].as_str()]);
+    }
+
     #[test]
     fn test_calculate_content_hash() {
         let content1 = "Hello, world!";
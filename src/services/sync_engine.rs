@@ -4,7 +4,11 @@ use crate::services::websocket_manager::WebSocketManager;
 use crate::services::websocket_types::*;
 use crate::services::conflict_resolution_engine::{ConflictResolutionEngine, ConflictInfo, ConflictResolutionResult, ManualResolutionRequest};
 use crate::models::enhanced_context::EnhancedContextItem;
-use anyhow::Result;
+use crate::services::json_patch::{self, PatchOperation};
+use crate::services::text_crdt;
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{broadcast, Mutex};
 use tracing::{info, warn};
@@ -16,6 +20,8 @@ pub struct SyncEngine {
     websocket_manager: Arc<WebSocketManager>,
     change_detector: Arc<ChangeDetectionService>,
     conflict_resolver: Arc<Mutex<ConflictResolutionEngine>>,
+    /// String fields merged as replicated text when both sides edit them
+    text_fields: Vec<String>,
 }
 
 impl SyncEngine {
@@ -31,9 +37,35 @@ impl SyncEngine {
             websocket_manager,
             change_detector,
             conflict_resolver,
+            text_fields: Vec::new(),
         }
     }
 
+    /// Merge concurrent edits of the named string fields, such as `description`, as text
+    /// instead of reporting them as conflicts
+    pub fn with_text_fields<I: IntoIterator<Item = String>>(mut self, fields: I) -> Self {
+        self.text_fields = fields.into_iter().collect();
+        self
+    }
+
+    /// Apply operations a client queued while offline; see `apply_queued_operations`
+    pub async fn apply_operations(&self, client_id: ClientId, operations: Vec<QueuedOperation>) -> Vec<OperationResult> {
+        apply_queued_operations(
+            &self.change_broadcaster,
+            Some(&self.conflict_resolver),
+            &self.text_fields,
+            client_id,
+            operations,
+        )
+        .await
+    }
+
+    /// Merge two updates made from the same `base`, text fields included. Conflicts
+    /// left in the result are for the conflict resolution engine.
+    pub fn merge_concurrent_changes(&self, base: &Value, ours: &ContextChange, theirs: &ContextChange) -> Result<PatchMerge> {
+        merge_changes_with_text(base, ours, theirs, &self.text_fields)
+    }
+
     /// Start the sync engine with all background services
     pub async fn start(&self) -> Result<()> {
         info!("Starting sync engine");
@@ -126,12 +158,12 @@ impl SyncEngine {
     pub async fn handle_conflict(&self, conflict: SyncConflict) -> Result<Resolution> {
         warn!("Using legacy handle_conflict method - consider using the new conflict resolution methods");
         
-        // Convert SyncConflict to the new format and use default strategy
+        // Convert SyncConflict to the new format and resolve it as its policy says
         if let Some(first_change) = conflict.conflicting_changes.first() {
             let conflict_info = self.detect_and_handle_conflict(first_change, None, &conflict.conflicting_changes).await?;
             
             if let Some(info) = conflict_info {
-                let result = self.resolve_conflict(&info.conflict_id, ConflictStrategy::LastWriterWins, None).await?;
+                let result = self.conflict_resolver.lock().await.resolve_by_policy(&info.conflict_id, None).await?;
                 match result.strategy_used {
                     ConflictStrategy::LastWriterWins => Ok(Resolution::LastWriterWins),
                     ConflictStrategy::AutoMerge => Ok(Resolution::AutoMerge),
@@ -218,6 +250,283 @@ impl SyncStream {
     }
 }
 
+/// Patch an update carries, or the difference its full entity makes to `base`
+fn change_patch(base: &Value, change: &ContextChange) -> Result<Vec<PatchOperation>> {
+    if let Some(patch) = change.delta.as_ref().and_then(json_patch::from_delta) {
+        return Ok(patch);
+    }
+    change
+        .full_entity
+        .as_ref()
+        .map(|entity| json_patch::diff(base, entity))
+        .ok_or_else(|| anyhow!("Change {} has neither a patch nor the full entity", change.change_id))
+}
+
+/// Entity after `change`, given the entity as it was before (`None` if unknown). An
+/// update's patch is applied to `entity`; otherwise its full entity is taken as is.
+pub fn apply_change(entity: Option<&Value>, change: &ContextChange) -> Result<Option<Value>> {
+    if change.change_type == ChangeType::Delete {
+        return Ok(None);
+    }
+    let patch = change.delta.as_ref().and_then(json_patch::from_delta);
+    match (entity, patch) {
+        (Some(entity), Some(patch)) => json_patch::apply(entity, &patch).map(Some),
+        (None, Some(_)) if change.full_entity.is_none() => Err(anyhow!(
+            "Change {} is a patch for an entity that is not known",
+            change.change_id
+        )),
+        _ => Ok(change.full_entity.clone()),
+    }
+}
+
+/// Two concurrent updates of the same entity combined field by field
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchMerge {
+    /// `base` with every edit of `ours` and the edits of `theirs` that do not conflict
+    pub merged: Value,
+    /// Paths both sides changed differently, where `merged` keeps `ours`
+    pub conflicts: Vec<String>,
+}
+
+/// Merge two updates made from the same `base`. Edits to different fields both apply;
+/// edits to the same field are reported as conflicts rather than overwritten.
+pub fn merge_changes(base: &Value, ours: &ContextChange, theirs: &ContextChange) -> Result<PatchMerge> {
+    let our_patch = change_patch(base, ours)?;
+    let their_patch = change_patch(base, theirs)?;
+    let mut conflicts = json_patch::conflicting_paths(&our_patch, &their_patch);
+
+    let mut merged = json_patch::apply(base, &our_patch)?;
+    for operation in their_patch {
+        if conflicts.iter().any(|path| json_patch::paths_overlap(path, operation.path())) {
+            continue;
+        }
+        match json_patch::apply(&merged, std::slice::from_ref(&operation)) {
+            Ok(patched) => merged = patched,
+            // Array edits shifted by the other side no longer line up
+            Err(_) => conflicts.push(operation.path().to_string()),
+        }
+    }
+    Ok(PatchMerge { merged, conflicts })
+}
+
+/// `merge_changes`, except that fields named in `text_fields` that both sides changed
+/// are merged as text, keeping the insertions and deletions of both
+pub fn merge_changes_with_text(
+    base: &Value,
+    ours: &ContextChange,
+    theirs: &ContextChange,
+    text_fields: &[String],
+) -> Result<PatchMerge> {
+    let mut merge = merge_changes(base, ours, theirs)?;
+    if text_fields.is_empty() || merge.conflicts.is_empty() {
+        return Ok(merge);
+    }
+    let our_entity = json_patch::apply(base, &change_patch(base, ours)?)?;
+    let their_entity = json_patch::apply(base, &change_patch(base, theirs)?)?;
+
+    let mut conflicts = Vec::new();
+    for path in std::mem::take(&mut merge.conflicts) {
+        let text = merge_text_field(base, &our_entity, &their_entity, &path, text_fields);
+        match (text, merge.merged.pointer_mut(&path)) {
+            (Some(text), Some(target)) => *target = Value::String(text),
+            _ => conflicts.push(path),
+        }
+    }
+    merge.conflicts = conflicts;
+    Ok(merge)
+}
+
+/// The value at `path` merged as text, when the path ends in one of `text_fields` and
+/// holds a string on all three sides
+pub fn merge_text_field(base: &Value, ours: &Value, theirs: &Value, path: &str, text_fields: &[String]) -> Option<String> {
+    let field = path.rsplit('/').next()?;
+    if !text_fields.iter().any(|name| json_patch::escape_token(name) == field) {
+        return None;
+    }
+    let text = |entity: &Value| entity.pointer(path).and_then(Value::as_str).map(str::to_string);
+    Some(text_crdt::merge_text(&text(base)?, &text(ours)?, &text(theirs)?))
+}
+
+/// Apply operations a client queued while offline, in order, as changes by `client_id`.
+///
+/// An operation made from the entity as it still is applies as is. One made from an older
+/// version is rebased: merged onto the changes made since, field by field and as text for
+/// `text_fields`. When those changes overlap it, or it deletes or recreates an entity
+/// changed since, it is not applied but recorded as a conflict with `conflict_resolver`.
+/// Operations on the same entity are chained, each made on top of the one before, so
+/// only the first needs a `base_version`.
+pub async fn apply_queued_operations(
+    broadcaster: &ChangeBroadcaster,
+    conflict_resolver: Option<&Mutex<ConflictResolutionEngine>>,
+    text_fields: &[String],
+    client_id: ClientId,
+    operations: Vec<QueuedOperation>,
+) -> Vec<OperationResult> {
+    // The entity as the client had it after its previous operation
+    let mut client_views: HashMap<(String, String), Option<Value>> = HashMap::new();
+    let mut results = Vec::with_capacity(operations.len());
+
+    for operation in operations {
+        let change = &operation.change;
+        let key = (change.entity_type.clone(), change.entity_id.clone());
+        let base = match client_views.get(&key) {
+            Some(view) => Ok(view.clone()),
+            None => operation_base(broadcaster, &operation),
+        };
+
+        let result = match base {
+            Ok(base) => {
+                let outcome =
+                    apply_queued_operation(broadcaster, conflict_resolver, text_fields, client_id, &operation, &base).await;
+                match apply_change(base.as_ref(), change) {
+                    Ok(view) => {
+                        client_views.insert(key, view);
+                    }
+                    Err(_) => {
+                        client_views.remove(&key);
+                    }
+                }
+                outcome.unwrap_or_else(|e| OperationResult::rejected(&operation, e.to_string()))
+            }
+            Err(e) => OperationResult::rejected(&operation, e.to_string()),
+        };
+        results.push(result);
+    }
+    results
+}
+
+/// The entity as it was when the client made `operation`
+fn operation_base(broadcaster: &ChangeBroadcaster, operation: &QueuedOperation) -> Result<Option<Value>> {
+    let change = &operation.change;
+    let Some(base_version) = operation.base_version else {
+        return Ok(None);
+    };
+    match broadcaster.current_version(&change.entity_type, &change.entity_id) {
+        Some(current) if base_version < current => {
+            let base = broadcaster
+                .change_at_version(&change.entity_type, &change.entity_id, base_version)
+                .ok_or_else(|| anyhow!("Version {} of {} is no longer held; fetch the entity and edit it again", base_version, change.entity_id))?;
+            Ok(base.full_entity.filter(|_| base.change_type != ChangeType::Delete))
+        }
+        _ => Ok(broadcaster.last_entity(&change.entity_type, &change.entity_id)),
+    }
+}
+
+async fn apply_queued_operation(
+    broadcaster: &ChangeBroadcaster,
+    conflict_resolver: Option<&Mutex<ConflictResolutionEngine>>,
+    text_fields: &[String],
+    client_id: ClientId,
+    operation: &QueuedOperation,
+    base: &Option<Value>,
+) -> Result<OperationResult> {
+    let change = &operation.change;
+    let current = broadcaster.last_entity(&change.entity_type, &change.entity_id);
+
+    let (status, new_value) = if *base == current {
+        (OperationStatus::Applied, apply_change(current.as_ref(), change)?)
+    } else {
+        let merge = match (base, &current) {
+            (Some(base), Some(current)) if change.change_type != ChangeType::Delete => {
+                let mut server_change = change.clone();
+                server_change.delta = None;
+                server_change.full_entity = Some(current.clone());
+                Some(merge_changes_with_text(base, &server_change, change, text_fields)?)
+            }
+            _ => None,
+        };
+        match merge {
+            Some(merge) if merge.conflicts.is_empty() => (OperationStatus::Rebased, Some(merge.merged)),
+            merge => {
+                let paths = merge.map(|merge| merge.conflicts).unwrap_or_default();
+                return record_conflict(broadcaster, conflict_resolver, operation, paths).await;
+            }
+        }
+    };
+
+    let change_type = match (&current, &new_value) {
+        (_, None) => ChangeType::Delete,
+        (None, Some(_)) => ChangeType::Create,
+        (Some(_), Some(_)) => ChangeType::Update,
+    };
+    broadcaster
+        .broadcast_change(crate::services::change_broadcaster::ChangeEvent {
+            entity_type: change.entity_type.clone(),
+            entity_id: change.entity_id.clone(),
+            project_id: change.project_id.clone(),
+            change_type,
+            old_value: current,
+            new_value,
+            client_id,
+            feature_area: change.feature_area.clone(),
+        })
+        .await?;
+
+    Ok(OperationResult {
+        operation_id: operation.operation_id.clone(),
+        status,
+        version: broadcaster.current_version(&change.entity_type, &change.entity_id),
+        conflict_id: None,
+        message: None,
+    })
+}
+
+/// Report an operation that overlaps newer changes, recording it as a version conflict
+async fn record_conflict(
+    broadcaster: &ChangeBroadcaster,
+    conflict_resolver: Option<&Mutex<ConflictResolutionEngine>>,
+    operation: &QueuedOperation,
+    paths: Vec<String>,
+) -> Result<OperationResult> {
+    let change = &operation.change;
+    let current_version = broadcaster.current_version(&change.entity_type, &change.entity_id).unwrap_or(0);
+
+    let conflict_id = match conflict_resolver {
+        Some(conflict_resolver) => {
+            let mut stale = change.clone();
+            stale.metadata.version = operation.base_version.unwrap_or(0);
+            let current = EnhancedContextItem {
+                id: change.entity_id.clone(),
+                project_id: change.project_id.clone(),
+                version: current_version,
+                ..Default::default()
+            };
+            conflict_resolver
+                .lock()
+                .await
+                .detect_conflict(&stale, Some(&current), &[])
+                .await?
+                .map(|conflict| conflict.conflict_id)
+        }
+        None => None,
+    };
+
+    let message = if paths.is_empty() {
+        format!("{} changed since it was edited", change.entity_id)
+    } else {
+        format!("{} changed since it was edited, at {}", change.entity_id, paths.join(", "))
+    };
+    Ok(OperationResult {
+        operation_id: operation.operation_id.clone(),
+        status: OperationStatus::Conflict,
+        version: None,
+        conflict_id,
+        message: Some(message),
+    })
+}
+
+impl OperationResult {
+    fn rejected(operation: &QueuedOperation, message: String) -> Self {
+        Self {
+            operation_id: operation.operation_id.clone(),
+            status: OperationStatus::Rejected,
+            version: None,
+            conflict_id: None,
+            message: Some(message),
+        }
+    }
+}
+
 /// Sync conflict information (placeholder for task 3.3)
 #[derive(Debug, Clone)]
 pub struct SyncConflict {
@@ -251,13 +560,17 @@ impl ChangeBroadcasterExt for ChangeBroadcaster {
     async fn broadcast_change_from_context(&self, change: ContextChange) -> Result<()> {
         use crate::services::change_broadcaster::ChangeEvent;
 
+        // A patch-only update is applied to the entity as last broadcast
+        let old_value = self.last_entity(&change.entity_type, &change.entity_id);
+        let new_value = apply_change(old_value.as_ref(), &change)?;
+
         let change_event = ChangeEvent {
             entity_type: change.entity_type,
             entity_id: change.entity_id,
             project_id: change.project_id,
             change_type: change.change_type,
-            old_value: None, // Could extract from delta if needed
-            new_value: change.full_entity,
+            old_value,
+            new_value,
             client_id: change.metadata.client_id,
             feature_area: change.feature_area,
         };
@@ -333,6 +646,7 @@ mod tests {
                 timestamp: Utc::now(),
                 version: 1,
                 conflict_resolution: None,
+                sequence: None,
             },
         };
         
@@ -341,6 +655,136 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    fn update(delta: Option<serde_json::Value>, full_entity: Option<serde_json::Value>) -> ContextChange {
+        ContextChange {
+            change_id: Uuid::new_v4(),
+            change_type: ChangeType::Update,
+            entity_type: "business_rule".to_string(),
+            entity_id: "rule-1".to_string(),
+            project_id: "test-project".to_string(),
+            feature_area: None,
+            delta,
+            full_entity,
+            metadata: ChangeMetadata {
+                user_id: None,
+                client_id: Uuid::new_v4(),
+                timestamp: Utc::now(),
+                version: 2,
+                conflict_resolution: None,
+                sequence: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_apply_change_uses_patch_or_full_entity() {
+        let base = json!({"name": "Rule", "priority": "high"});
+        let patched = update(Some(json!([{"op": "replace", "path": "/priority", "value": "low"}])), None);
+        assert_eq!(
+            apply_change(Some(&base), &patched).unwrap(),
+            Some(json!({"name": "Rule", "priority": "low"}))
+        );
+        // Without the previous entity only a full entity will do
+        assert!(apply_change(None, &patched).is_err());
+        let full = update(None, Some(json!({"name": "Rule 2"})));
+        assert_eq!(apply_change(None, &full).unwrap(), Some(json!({"name": "Rule 2"})));
+    }
+
+    #[test]
+    fn test_merge_changes_by_field() {
+        let base = json!({"name": "Rule", "description": "Old", "priority": "high"});
+        let ours = update(Some(json!([{"op": "replace", "path": "/name", "value": "Rule A"}])), None);
+        let theirs = update(None, Some(json!({"name": "Rule", "description": "New", "priority": "high"})));
+        let merge = merge_changes(&base, &ours, &theirs).unwrap();
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(merge.merged, json!({"name": "Rule A", "description": "New", "priority": "high"}));
+
+        let theirs = update(None, Some(json!({"name": "Rule B", "description": "New", "priority": "high"})));
+        let merge = merge_changes(&base, &ours, &theirs).unwrap();
+        assert_eq!(merge.conflicts, vec!["/name".to_string()]);
+        assert_eq!(merge.merged, json!({"name": "Rule A", "description": "New", "priority": "high"}));
+    }
+
+    #[test]
+    fn test_text_fields_merge_as_text() {
+        let base = json!({"name": "Rule", "description": "Orders ship within two days."});
+        let ours = update(None, Some(json!({"name": "Rule A", "description": "All orders ship within two days."})));
+        let theirs = update(None, Some(json!({"name": "Rule B", "description": "Orders ship within three days."})));
+
+        let merge = merge_changes(&base, &ours, &theirs).unwrap();
+        assert_eq!(merge.conflicts.len(), 2);
+
+        let sync_engine = SyncEngine::new().with_text_fields(["description".to_string()]);
+        let merge = sync_engine.merge_concurrent_changes(&base, &ours, &theirs).unwrap();
+        assert_eq!(merge.merged["description"], "All orders ship within three days.");
+        // Structured fields are still left to the conflict resolution engine
+        assert_eq!(merge.conflicts, vec!["/name".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_queued_operations_apply_rebase_or_conflict() {
+        use crate::services::change_broadcaster::ChangeEvent;
+
+        let sync_engine = SyncEngine::new();
+        let broadcaster = sync_engine.get_broadcaster();
+        broadcaster
+            .broadcast_change(ChangeEvent {
+                entity_type: "business_rule".to_string(),
+                entity_id: "rule-1".to_string(),
+                project_id: "test-project".to_string(),
+                change_type: ChangeType::Create,
+                old_value: None,
+                new_value: Some(json!({"name": "Rule", "description": "Old"})),
+                client_id: Uuid::new_v4(),
+                feature_area: None,
+            })
+            .await
+            .unwrap();
+        let created = broadcaster.current_version("business_rule", "rule-1").unwrap();
+
+        let operation = |id: &str, base_version: Option<u32>, change: ContextChange| QueuedOperation {
+            operation_id: id.to_string(),
+            base_version,
+            change,
+        };
+        let laptop = Uuid::new_v4();
+
+        // Two edits queued offline against the created version, chained
+        let results = sync_engine
+            .apply_operations(
+                laptop,
+                vec![
+                    operation("rename", Some(created), update(Some(json!([{"op": "replace", "path": "/name", "value": "Rule A"}])), None)),
+                    operation("tag", None, update(Some(json!([{"op": "add", "path": "/tags", "value": ["billing"]}])), None)),
+                ],
+            )
+            .await;
+        assert_eq!(results[0].status, OperationStatus::Applied);
+        assert_eq!(results[1].status, OperationStatus::Applied);
+        assert_eq!(results[1].version, Some(created + 2));
+
+        // A second client, offline since the create, edited another field
+        let reworded = update(None, Some(json!({"name": "Rule", "description": "New"})));
+        let results = sync_engine
+            .apply_operations(Uuid::new_v4(), vec![operation("reword", Some(created), reworded)])
+            .await;
+        assert_eq!(results[0].status, OperationStatus::Rebased);
+        assert_eq!(
+            broadcaster.last_entity("business_rule", "rule-1"),
+            Some(json!({"name": "Rule A", "description": "New", "tags": ["billing"]}))
+        );
+
+        // and a third renamed it too
+        let renamed = update(None, Some(json!({"name": "Rule B", "description": "Old"})));
+        let results = sync_engine
+            .apply_operations(Uuid::new_v4(), vec![operation("rename", Some(created), renamed)])
+            .await;
+        assert_eq!(results[0].status, OperationStatus::Conflict);
+        let conflict_id = results[0].conflict_id.clone().unwrap();
+        assert!(sync_engine.get_conflict_info(&conflict_id).await.is_some());
+        assert_eq!(broadcaster.last_entity("business_rule", "rule-1").unwrap()["name"], "Rule A");
+    }
+
     #[tokio::test]
     async fn test_sync_status() {
         let sync_engine = SyncEngine::new();
@@ -0,0 +1,184 @@
+use crate::services::change_broadcaster::BroadcastMetrics;
+use crate::services::conflict_resolution_engine::ConflictMetrics;
+use crate::services::websocket_types::DeliveryStats;
+use serde::Serialize;
+use std::sync::atomic::Ordering;
+
+/// Changes waiting across broadcaster and client queues above which sync counts as degraded
+pub const DEGRADED_QUEUE_DEPTH: u64 = 100;
+
+/// Point-in-time view of realtime sync: broadcasting, delivery to clients and conflicts.
+/// Counters run from server start; queue depths and clients are current.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SyncMetrics {
+    /// "healthy", or "degraded" while more than `DEGRADED_QUEUE_DEPTH` changes are waiting
+    pub status: String,
+    /// Signs of trouble worth a look even while healthy
+    pub warnings: Vec<String>,
+    pub broadcast: BroadcastCounts,
+    pub delivery: DeliveryStats,
+    pub conflicts: ConflictCounts,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BroadcastCounts {
+    pub changes_broadcast: u64,
+    pub clients_notified: u64,
+    pub failed_deliveries: u64,
+    pub delta_calculations: u64,
+    /// Changes queued for clients by the broadcaster
+    pub queued_changes: u64,
+    pub batches_sent: u64,
+    pub average_batch_size: f64,
+    pub largest_batch: u64,
+    pub coalesced_changes: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ConflictCounts {
+    pub detected: u64,
+    pub resolved_last_writer_wins: u64,
+    pub resolved_auto_merge: u64,
+    pub resolved_reject: u64,
+    pub resolved_manually: u64,
+    pub escalated_to_manual: u64,
+}
+
+impl SyncMetrics {
+    pub fn collect(broadcast: &BroadcastMetrics, delivery: DeliveryStats, conflicts: &ConflictMetrics) -> Self {
+        let broadcast = BroadcastCounts {
+            changes_broadcast: broadcast.total_changes_broadcast.load(Ordering::Relaxed),
+            clients_notified: broadcast.total_clients_notified.load(Ordering::Relaxed),
+            failed_deliveries: broadcast.failed_deliveries.load(Ordering::Relaxed),
+            delta_calculations: broadcast.delta_calculations.load(Ordering::Relaxed),
+            queued_changes: broadcast.queue_size.load(Ordering::Relaxed),
+            batches_sent: broadcast.batches_sent.load(Ordering::Relaxed),
+            average_batch_size: broadcast.average_batch_size(),
+            largest_batch: broadcast.largest_batch.load(Ordering::Relaxed),
+            coalesced_changes: broadcast.coalesced_changes.load(Ordering::Relaxed),
+        };
+        let conflicts = ConflictCounts {
+            detected: conflicts.conflicts_detected.load(Ordering::Relaxed),
+            resolved_last_writer_wins: conflicts.resolved_last_writer_wins.load(Ordering::Relaxed),
+            resolved_auto_merge: conflicts.resolved_auto_merge.load(Ordering::Relaxed),
+            resolved_reject: conflicts.resolved_reject.load(Ordering::Relaxed),
+            resolved_manually: conflicts.resolved_manually.load(Ordering::Relaxed),
+            escalated_to_manual: conflicts.escalated_to_manual.load(Ordering::Relaxed),
+        };
+
+        let waiting = broadcast.queued_changes + (delivery.pending_messages + delivery.queued_messages) as u64;
+        let mut warnings = Vec::new();
+        if delivery.dropped_messages > 0 {
+            warnings.push(format!(
+                "{} messages dropped from full client send queues; raise [websocket] max_queue_depth or find the slow client",
+                delivery.dropped_messages
+            ));
+        }
+        if broadcast.failed_deliveries > 0 {
+            warnings.push(format!("{} change deliveries failed", broadcast.failed_deliveries));
+        }
+        if conflicts.escalated_to_manual > conflicts.resolved_manually {
+            warnings.push(format!(
+                "{} conflicts left for manual resolution, {} resolved manually",
+                conflicts.escalated_to_manual, conflicts.resolved_manually
+            ));
+        }
+
+        Self {
+            status: if waiting > DEGRADED_QUEUE_DEPTH { "degraded" } else { "healthy" }.to_string(),
+            warnings,
+            broadcast,
+            delivery,
+            conflicts,
+        }
+    }
+
+    /// Render in the Prometheus text exposition format, as `context_server_sync_*` series
+    pub fn to_prometheus(&self) -> String {
+        let b = &self.broadcast;
+        let d = &self.delivery;
+        let metrics: [(&str, &str, &str, String); 13] = [
+            ("sync_changes_broadcast_total", "counter", "Changes published to subscribers", b.changes_broadcast.to_string()),
+            ("sync_clients_notified_total", "counter", "Client notifications sent for changes", b.clients_notified.to_string()),
+            ("sync_failed_deliveries_total", "counter", "Changes that could not be delivered", b.failed_deliveries.to_string()),
+            ("sync_batches_sent_total", "counter", "Change batches published", b.batches_sent.to_string()),
+            ("sync_coalesced_changes_total", "counter", "Changes merged into a later change before publishing", b.coalesced_changes.to_string()),
+            ("sync_average_batch_size", "gauge", "Mean changes per published batch", b.average_batch_size.to_string()),
+            ("sync_queued_changes", "gauge", "Changes queued for clients by the broadcaster", b.queued_changes.to_string()),
+            ("sync_connected_clients", "gauge", "Connected WebSocket clients", d.connected_clients.to_string()),
+            ("sync_pending_messages", "gauge", "Messages held to retry after a failed send", d.pending_messages.to_string()),
+            ("sync_queued_messages", "gauge", "Messages in client send queues", d.queued_messages.to_string()),
+            ("sync_max_client_queue", "gauge", "Fullest send queue of a single client", d.max_client_queue.to_string()),
+            ("sync_dropped_messages_total", "counter", "Messages dropped from full client send queues", d.dropped_messages.to_string()),
+            ("sync_conflicts_detected_total", "counter", "Conflicts detected between concurrent changes", self.conflicts.detected.to_string()),
+        ];
+
+        let mut out = String::new();
+        for (name, kind, help, value) in metrics {
+            out.push_str(&format!(
+                "# HELP context_server_{name} {help}\n# TYPE context_server_{name} {kind}\ncontext_server_{name} {value}\n"
+            ));
+        }
+
+        let c = &self.conflicts;
+        out.push_str("# HELP context_server_sync_conflicts_resolved_total Conflicts resolved, by strategy\n");
+        out.push_str("# TYPE context_server_sync_conflicts_resolved_total counter\n");
+        for (strategy, value) in [
+            ("last_writer_wins", c.resolved_last_writer_wins),
+            ("auto_merge", c.resolved_auto_merge),
+            ("reject", c.resolved_reject),
+            ("manual", c.resolved_manually),
+        ] {
+            out.push_str(&format!("context_server_sync_conflicts_resolved_total{{strategy=\"{strategy}\"}} {value}\n"));
+        }
+        out.push_str(&format!(
+            "# HELP context_server_sync_conflicts_escalated_total Conflicts left for manual resolution\n\
+             # TYPE context_server_sync_conflicts_escalated_total counter\n\
+             context_server_sync_conflicts_escalated_total {}\n",
+            c.escalated_to_manual
+        ));
+        out.push_str(&format!(
+            "# HELP context_server_sync_degraded Whether more than {DEGRADED_QUEUE_DEPTH} changes are waiting\n\
+             # TYPE context_server_sync_degraded gauge\n\
+             context_server_sync_degraded {}\n",
+            u8::from(self.status == "degraded")
+        ));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_and_prometheus() {
+        let broadcast = BroadcastMetrics::default();
+        broadcast.total_changes_broadcast.store(7, Ordering::Relaxed);
+        broadcast.queue_size.store(90, Ordering::Relaxed);
+        let conflicts = ConflictMetrics::default();
+        conflicts.conflicts_detected.store(3, Ordering::Relaxed);
+        conflicts.resolved_auto_merge.store(2, Ordering::Relaxed);
+        conflicts.escalated_to_manual.store(1, Ordering::Relaxed);
+        let delivery = DeliveryStats {
+            connected_clients: 2,
+            queued_messages: 5,
+            max_client_queue: 5,
+            dropped_messages: 4,
+            ..Default::default()
+        };
+
+        let healthy = SyncMetrics::collect(&broadcast, delivery.clone(), &conflicts);
+        assert_eq!(healthy.status, "healthy");
+        assert_eq!(healthy.warnings.len(), 2);
+        assert!(healthy.warnings[0].starts_with("4 messages dropped"));
+
+        let degraded = SyncMetrics::collect(&broadcast, DeliveryStats { pending_messages: 6, ..delivery }, &conflicts);
+        assert_eq!(degraded.status, "degraded");
+        let text = degraded.to_prometheus();
+        assert!(text.contains("context_server_sync_changes_broadcast_total 7\n"));
+        assert!(text.contains("context_server_sync_conflicts_resolved_total{strategy=\"auto_merge\"} 2\n"));
+        assert!(text.contains("context_server_sync_degraded 1\n"));
+        assert_eq!(text.matches("# TYPE ").count(), 16);
+    }
+}
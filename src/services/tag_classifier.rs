@@ -0,0 +1,246 @@
+//! Classifies entities into domain area, layer and feature area tags from keywords in their
+//! text and, when an embedding model is available, how close their embedding is to each tag's
+
+use crate::config::TaggingSettings;
+use crate::services::embedding_service::EmbeddingService;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tracing::debug;
+
+pub const DOMAIN_AREA: &str = "domain_area";
+pub const LAYER: &str = "layer";
+pub const FEATURE_AREA: &str = "feature_area";
+
+/// Keywords of the layers every project knows; `[tagging] layers` adds to them
+const BUILTIN_LAYERS: &[(&str, &[&str])] = &[
+    ("presentation", &["ui", "view", "screen", "page", "widget", "frontend", "layout", "button", "form", "css"]),
+    ("api", &["api", "endpoint", "route", "rest", "graphql", "http", "request", "response", "controller", "handler"]),
+    ("domain", &["business", "domain", "policy", "entity", "validation", "workflow", "invariant", "aggregate"]),
+    ("data", &["database", "sql", "table", "query", "schema", "migration", "repository", "storage", "cache", "index"]),
+    ("infrastructure", &["deploy", "docker", "kubernetes", "server", "queue", "logging", "monitoring", "config", "network", "pipeline"]),
+];
+
+/// Embedding similarity below this adds no confidence; unrelated texts are rarely far apart
+const EMBEDDING_SIMILARITY_FLOOR: f32 = 0.5;
+
+/// Entity fields naming a tag outright, with the category they name
+const TAG_FIELDS: &[(&str, &str)] = &[
+    ("domain_area", DOMAIN_AREA),
+    ("component_area", DOMAIN_AREA),
+    ("architecture_layer", LAYER),
+    ("feature_name", FEATURE_AREA),
+];
+
+/// A tag an entity can be classified into
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagLabel {
+    pub name: String,
+    pub category: String,
+    pub keywords: Vec<String>,
+}
+
+/// A tag an entity was classified into
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagCandidate {
+    pub name: String,
+    pub category: String,
+    pub confidence: f64,
+    pub sources: Vec<String>,
+}
+
+pub struct TagClassifier {
+    settings: TaggingSettings,
+    embedding_service: Option<Arc<dyn EmbeddingService>>,
+}
+
+impl TagClassifier {
+    pub fn new(settings: TaggingSettings, embedding_service: Option<Arc<dyn EmbeddingService>>) -> Self {
+        let embedding_service = embedding_service.filter(|_| settings.use_embeddings);
+        Self { settings, embedding_service }
+    }
+
+    /// Built-in and configured layers, configured domain areas, and the domain and feature
+    /// areas a project already uses
+    pub fn labels(&self, domain_areas: &[String], feature_areas: &[String]) -> Vec<TagLabel> {
+        let mut keywords: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+        for (layer, words) in BUILTIN_LAYERS {
+            keywords
+                .entry((LAYER.to_string(), layer.to_string()))
+                .or_default()
+                .extend(words.iter().map(|word| word.to_string()));
+        }
+        for (category, configured) in [(LAYER, &self.settings.layers), (DOMAIN_AREA, &self.settings.domain_areas)] {
+            for (name, words) in configured {
+                keywords
+                    .entry((category.to_string(), tag_name(name)))
+                    .or_default()
+                    .extend(words.iter().map(|word| word.to_lowercase()));
+            }
+        }
+        for (category, names) in [(DOMAIN_AREA, domain_areas), (FEATURE_AREA, feature_areas)] {
+            for name in names.iter().filter(|name| !name.trim().is_empty()) {
+                keywords.entry((category.to_string(), tag_name(name))).or_default();
+            }
+        }
+
+        keywords
+            .into_iter()
+            .map(|((category, name), keywords)| TagLabel { name, category, keywords })
+            .collect()
+    }
+
+    /// The most confident tag of each category for `text`, if at least `min_confidence`
+    pub async fn classify(&self, text: &str, labels: &[TagLabel]) -> Vec<TagCandidate> {
+        let words = format!(" {} ", normalize(text));
+        let similarities = self.similarities(text, labels).await;
+
+        let mut best: BTreeMap<&str, TagCandidate> = BTreeMap::new();
+        for (index, label) in labels.iter().enumerate() {
+            let mut sources = Vec::new();
+            // The tag's own name counts as two keywords
+            let name_hits = if contains_phrase(&words, &label.name.replace('-', " ")) { 2 } else { 0 };
+            let hits = name_hits + label.keywords.iter().filter(|keyword| contains_phrase(&words, keyword)).count();
+            let keyword_confidence = 1.0 - 0.5f64.powi(hits as i32);
+            if hits > 0 {
+                sources.push("keyword".to_string());
+            }
+
+            let embedding_confidence = similarities
+                .get(index)
+                .map(|similarity| ((similarity - EMBEDDING_SIMILARITY_FLOOR) / (1.0 - EMBEDDING_SIMILARITY_FLOOR)).clamp(0.0, 1.0) as f64)
+                .unwrap_or(0.0);
+            if embedding_confidence > 0.0 {
+                sources.push("embedding".to_string());
+            }
+
+            let confidence = 1.0 - (1.0 - keyword_confidence) * (1.0 - embedding_confidence);
+            if confidence < self.settings.min_confidence {
+                continue;
+            }
+            let candidate = TagCandidate {
+                name: label.name.clone(),
+                category: label.category.clone(),
+                confidence: (confidence * 1000.0).round() / 1000.0,
+                sources,
+            };
+            if best.get(label.category.as_str()).is_none_or(|known| known.confidence < candidate.confidence) {
+                best.insert(label.category.as_str(), candidate);
+            }
+        }
+        best.into_values().collect()
+    }
+
+    /// Similarity of `text` to each label, or nothing without an embedding model
+    async fn similarities(&self, text: &str, labels: &[TagLabel]) -> Vec<f32> {
+        let Some(service) = &self.embedding_service else {
+            return Vec::new();
+        };
+        let texts: Vec<String> = labels
+            .iter()
+            .map(|label| format!("{}: {}", label.name.replace('-', " "), label.keywords.join(" ")))
+            .collect();
+        let entity = match service.generate_embedding(text, "text").await {
+            Ok(embedding) => embedding,
+            Err(e) => {
+                debug!("Classifying tags by keyword only: {}", e);
+                return Vec::new();
+            }
+        };
+        let mut similarities = Vec::with_capacity(labels.len());
+        for label_text in &texts {
+            match service.generate_embedding(label_text, "text").await {
+                Ok(embedding) => similarities.push(service.calculate_similarity(&entity, &embedding)),
+                Err(e) => {
+                    debug!("Classifying tags by keyword only: {}", e);
+                    return Vec::new();
+                }
+            }
+        }
+        similarities
+    }
+}
+
+/// Tags an entity names in its own fields (`domain_area`, `architecture_layer`, ...), which
+/// are certain
+pub fn field_tags(entity: &serde_json::Value) -> Vec<TagCandidate> {
+    TAG_FIELDS
+        .iter()
+        .filter_map(|(field, category)| {
+            let value = entity.get(*field)?.as_str()?.trim();
+            (!value.is_empty()).then(|| TagCandidate {
+                name: tag_name(value),
+                category: category.to_string(),
+                confidence: 1.0,
+                sources: vec!["field".to_string()],
+            })
+        })
+        .collect()
+}
+
+/// The text of an entity classification looks at: its string fields other than ids and timestamps
+pub fn entity_text(entity: &serde_json::Value) -> String {
+    let Some(fields) = entity.as_object() else {
+        return String::new();
+    };
+    fields
+        .iter()
+        .filter(|(name, _)| !(name.as_str() == "id" || name.ends_with("_id") || name.ends_with("_at")))
+        .filter_map(|(_, value)| value.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Tag names are lowercase with words joined by hyphens ("User Management" -> "user-management")
+pub fn tag_name(name: &str) -> String {
+    normalize(name).replace(' ', "-")
+}
+
+/// Lowercase words separated by single spaces
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether normalized `words` (padded with spaces) hold `phrase`, or its plural
+fn contains_phrase(words: &str, phrase: &str) -> bool {
+    let phrase = normalize(phrase);
+    !phrase.is_empty() && (words.contains(&format!(" {phrase} ")) || words.contains(&format!(" {phrase}s ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_keyword_classification() {
+        let mut settings = TaggingSettings::default();
+        settings.domain_areas.insert("Billing".to_string(), vec!["invoice".to_string(), "refund".to_string()]);
+        let classifier = TagClassifier::new(settings, None);
+        let labels = classifier.labels(&["payments".to_string()], &["Checkout Flow".to_string()]);
+        assert!(labels.iter().any(|label| label.name == "checkout-flow" && label.category == FEATURE_AREA));
+
+        let entity = serde_json::json!({
+            "id": "r1",
+            "project_id": "p1",
+            "rule_name": "Refunds need an invoice",
+            "description": "The refund endpoint rejects requests for invoices older than a year",
+            "created_at": "2026-01-01",
+        });
+        let text = entity_text(&entity);
+        assert!(!text.contains("p1"));
+        let candidates = classifier.classify(&text, &labels).await;
+        let billing = candidates.iter().find(|c| c.category == DOMAIN_AREA).unwrap();
+        assert_eq!((billing.name.as_str(), billing.confidence), ("billing", 0.75));
+        let layer = candidates.iter().find(|c| c.category == LAYER).unwrap();
+        assert_eq!(layer.name, "api");
+        assert!(layer.confidence < 0.8);
+        assert!(!candidates.iter().any(|c| c.category == FEATURE_AREA));
+
+        let fields = field_tags(&serde_json::json!({"domain_area": "User Management"}));
+        assert_eq!(fields[0].name, "user-management");
+        assert_eq!(fields[0].confidence, 1.0);
+    }
+}
@@ -0,0 +1,364 @@
+//! Replicated text (an RGA sequence CRDT) for text-heavy fields such as descriptions and
+//! specification content. Every character has a unique id and deleted characters stay as
+//! tombstones, so concurrent edits applied in any order converge on the same text.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Middle sections larger than this many character pairs are replaced as a whole
+/// instead of diffed character by character
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Identity of one inserted character: a Lamport counter, then the inserting site
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CharId {
+    pub counter: u64,
+    pub site: String,
+}
+
+impl Ord for CharId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.counter.cmp(&other.counter).then_with(|| self.site.cmp(&other.site))
+    }
+}
+
+impl PartialOrd for CharId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// An edit to a text document, to be applied by the other replicas
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum TextOperation {
+    /// `value` inserted right after `after`, or at the start
+    Insert {
+        id: CharId,
+        after: Option<CharId>,
+        value: char,
+    },
+    Delete { id: CharId },
+}
+
+#[derive(Debug, Clone)]
+struct Element {
+    id: CharId,
+    value: char,
+    deleted: bool,
+    /// Slot of the following character
+    next: Option<usize>,
+}
+
+/// One replica of a text; `site` must differ between replicas editing concurrently
+#[derive(Debug, Clone)]
+pub struct TextDocument {
+    site: String,
+    counter: u64,
+    /// Characters in the order they were integrated, linked in text order from `head`
+    elements: Vec<Element>,
+    head: Option<usize>,
+    slots: HashMap<CharId, usize>,
+}
+
+impl TextDocument {
+    pub fn new(site: impl Into<String>) -> Self {
+        Self {
+            site: site.into(),
+            counter: 0,
+            elements: Vec::new(),
+            head: None,
+            slots: HashMap::new(),
+        }
+    }
+
+    /// A document holding `text`, inserted by `site`
+    pub fn from_text(site: impl Into<String>, text: &str) -> Self {
+        let mut document = Self::new(site);
+        document.edit(text);
+        document
+    }
+
+    /// A replica of this document for another site
+    pub fn fork(&self, site: impl Into<String>) -> Self {
+        Self {
+            site: site.into(),
+            ..self.clone()
+        }
+    }
+
+    /// The visible text
+    pub fn text(&self) -> String {
+        self.iter().filter(|e| !e.deleted).map(|e| e.value).collect()
+    }
+
+    /// Every character, deleted ones included, in text order
+    fn iter(&self) -> impl Iterator<Item = &Element> {
+        std::iter::successors(self.head.map(|slot| &self.elements[slot]), |e| {
+            e.next.map(|slot| &self.elements[slot])
+        })
+    }
+
+    /// Change the text to `new_text`, returning the operations that make the change
+    pub fn edit(&mut self, new_text: &str) -> Vec<TextOperation> {
+        let visible: Vec<&Element> = self.iter().filter(|e| !e.deleted).collect();
+        let old: Vec<char> = visible.iter().map(|e| e.value).collect();
+        let visible: Vec<CharId> = visible.into_iter().map(|e| e.id.clone()).collect();
+        let new: Vec<char> = new_text.chars().collect();
+
+        let mut operations = Vec::new();
+        // Characters inserted after the last kept or deleted one, in order
+        let mut after: Option<CharId> = None;
+        let mut old_index = 0;
+        for edit in diff_chars(&old, &new) {
+            match edit {
+                CharEdit::Keep => {
+                    after = Some(visible[old_index].clone());
+                    old_index += 1;
+                }
+                CharEdit::Delete => {
+                    let id = visible[old_index].clone();
+                    after = Some(id.clone());
+                    operations.push(TextOperation::Delete { id });
+                    old_index += 1;
+                }
+                CharEdit::Insert(value) => {
+                    self.counter += 1;
+                    let id = CharId {
+                        counter: self.counter,
+                        site: self.site.clone(),
+                    };
+                    operations.push(TextOperation::Insert {
+                        id: id.clone(),
+                        after: after.clone(),
+                        value,
+                    });
+                    after = Some(id);
+                }
+            }
+        }
+
+        for operation in &operations {
+            // Local operations always integrate
+            let _ = self.apply(operation);
+        }
+        operations
+    }
+
+    /// Apply an operation from any replica. Operations already applied are ignored;
+    /// an operation referring to a character not seen yet is an error.
+    pub fn apply(&mut self, operation: &TextOperation) -> Result<()> {
+        match operation {
+            TextOperation::Insert { id, after, value } => {
+                if self.slots.contains_key(id) {
+                    return Ok(());
+                }
+                let mut previous = match after {
+                    Some(after) => Some(*self.slots.get(after).ok_or_else(|| {
+                        anyhow!("Insert after unknown character {}@{}", after.counter, after.site)
+                    })?),
+                    None => None,
+                };
+                // Concurrent inserts at the same place are ordered by id, newest first
+                loop {
+                    let next = match previous {
+                        Some(slot) => self.elements[slot].next,
+                        None => self.head,
+                    };
+                    match next {
+                        Some(slot) if self.elements[slot].id > *id => previous = Some(slot),
+                        _ => break,
+                    }
+                }
+                let slot = self.elements.len();
+                let next = match previous {
+                    Some(previous) => self.elements[previous].next.replace(slot),
+                    None => self.head.replace(slot),
+                };
+                self.elements.push(Element {
+                    id: id.clone(),
+                    value: *value,
+                    deleted: false,
+                    next,
+                });
+                self.slots.insert(id.clone(), slot);
+                self.counter = self.counter.max(id.counter);
+                Ok(())
+            }
+            TextOperation::Delete { id } => {
+                let slot = *self
+                    .slots
+                    .get(id)
+                    .ok_or_else(|| anyhow!("Delete of unknown character {}@{}", id.counter, id.site))?;
+                self.elements[slot].deleted = true;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Combine two concurrent edits of `base`, keeping every insertion and deletion of both.
+/// Insertions at the same position are ordered by site, so the sides get their sites in the
+/// order of their text and the result is the same whichever side is `ours`.
+pub fn merge_text(base: &str, ours: &str, theirs: &str) -> String {
+    let (first, second) = if ours <= theirs { (ours, theirs) } else { (theirs, ours) };
+    let document = TextDocument::from_text("base", base);
+    let mut first_document = document.fork("first");
+    first_document.edit(first);
+    let mut second_document = document.fork("second");
+    for operation in second_document.edit(second) {
+        // Both replicas share every character of `base`
+        let _ = first_document.apply(&operation);
+    }
+    first_document.text()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CharEdit {
+    Keep,
+    Delete,
+    Insert(char),
+}
+
+/// Edits turning `old` into `new`: a longest common subsequence between their common
+/// prefix and suffix
+fn diff_chars(old: &[char], new: &[char]) -> Vec<CharEdit> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut edits = vec![CharEdit::Keep; prefix];
+    if old_middle.len().saturating_mul(new_middle.len()) > MAX_DIFF_CELLS {
+        edits.extend(old_middle.iter().map(|_| CharEdit::Delete));
+        edits.extend(new_middle.iter().map(|&c| CharEdit::Insert(c)));
+    } else {
+        edits.extend(lcs_edits(old_middle, new_middle));
+    }
+    edits.extend(std::iter::repeat_n(CharEdit::Keep, suffix));
+    edits
+}
+
+fn lcs_edits(old: &[char], new: &[char]) -> Vec<CharEdit> {
+    let width = new.len() + 1;
+    // lengths[i * width + j]: longest common subsequence of old[i..] and new[j..]
+    let mut lengths = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::with_capacity(old.len() + new.len());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            edits.push(CharEdit::Keep);
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            edits.push(CharEdit::Delete);
+            i += 1;
+        } else {
+            edits.push(CharEdit::Insert(new[j]));
+            j += 1;
+        }
+    }
+    edits.extend(old[i..].iter().map(|_| CharEdit::Delete));
+    edits.extend(new[j..].iter().map(|&c| CharEdit::Insert(c)));
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concurrent_edits_converge() {
+        let base = TextDocument::from_text("base", "Retry failed payments.");
+        let mut ana = base.fork("ana");
+        let mut ben = base.fork("ben");
+
+        let from_ana = ana.edit("Retry failed card payments.");
+        let from_ben = ben.edit("Retry failed payments twice.");
+        for operation in &from_ben {
+            ana.apply(operation).unwrap();
+        }
+        for operation in from_ana.iter().chain(&from_ben) {
+            // Applying an operation twice changes nothing
+            ben.apply(operation).unwrap();
+        }
+
+        assert_eq!(ana.text(), "Retry failed card payments twice.");
+        assert_eq!(ana.text(), ben.text());
+    }
+
+    #[test]
+    fn test_merge_text_keeps_both_sides() {
+        let base = "Orders ship within two days.";
+        let ours = "All orders ship within two days.";
+        let theirs = "Orders ship within three days.";
+        assert_eq!(merge_text(base, ours, theirs), "All orders ship within three days.");
+        assert_eq!(merge_text(base, theirs, ours), merge_text(base, ours, theirs));
+
+        // Both deleting the same text deletes it once
+        assert_eq!(merge_text("a b c", "a c", "a c"), "a c");
+        assert_eq!(merge_text("", "x", ""), "x");
+    }
+
+    #[test]
+    fn test_merge_text_orders_inserts_at_the_same_position_the_same_way() {
+        let merged = merge_text("ab", "aXb", "aYb");
+        assert_eq!(merged, merge_text("ab", "aYb", "aXb"));
+        assert!(merged == "aXYb" || merged == "aYXb", "{merged}");
+        assert_eq!(merge_text("ab", "aXXb", "aYb"), merge_text("ab", "aYb", "aXXb"));
+    }
+
+    #[test]
+    fn test_merge_text_of_a_long_specification() {
+        let lines: Vec<String> = (0..2000)
+            .map(|i| format!("- Requirement {i}: checkout keeps the cart and the order total consistent.\n"))
+            .collect();
+        let base = lines.concat();
+        assert!(base.len() > 100_000);
+        let ours = base.replace("Requirement 10:", "Requirement 10 (revised):");
+        let theirs = base.replace("Requirement 1990: checkout", "Requirement 1990: guest checkout");
+
+        let started = std::time::Instant::now();
+        let merged = merge_text(&base, &ours, &theirs);
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        assert_eq!(merged, ours.replace("Requirement 1990: checkout", "Requirement 1990: guest checkout"));
+    }
+
+    #[test]
+    fn test_unknown_character_is_rejected() {
+        let mut document = TextDocument::from_text("a", "hi");
+        let stranger = CharId {
+            counter: 9,
+            site: "b".to_string(),
+        };
+        assert!(document.apply(&TextOperation::Delete { id: stranger.clone() }).is_err());
+        assert!(document
+            .apply(&TextOperation::Insert {
+                id: CharId {
+                    counter: 10,
+                    site: "b".to_string()
+                },
+                after: Some(stranger),
+                value: '!',
+            })
+            .is_err());
+        assert_eq!(document.text(), "hi");
+    }
+}
@@ -0,0 +1,140 @@
+use crate::config::ConfigManager;
+use crate::models::tool_call_audit::{ToolCallAudit, ToolCallAuditFilter};
+use crate::repositories::ToolCallAuditRepository;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use rmcp::model::ErrorData as McpError;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Hours between background pruning runs of the audit log
+const PRUNE_INTERVAL_HOURS: u64 = 24;
+
+/// Keeps the `[audit]` log of tool calls, which `[analytics]` settings don't affect
+#[async_trait]
+pub trait ToolAuditService: Send + Sync {
+    /// Record a call unless `[audit] enabled` is false
+    async fn record(&self, entry: ToolCallAudit) -> Result<(), McpError>;
+    /// Newest first
+    async fn find(&self, filter: &ToolCallAuditFilter) -> Result<Vec<ToolCallAudit>, McpError>;
+    /// Delete entries older than `[audit] retention_days`, returning how many went
+    async fn prune(&self) -> Result<usize, McpError>;
+}
+
+/// Default implementation of ToolAuditService
+pub struct DefaultToolAuditService {
+    repository: Arc<dyn ToolCallAuditRepository>,
+    config: Arc<ConfigManager>,
+}
+
+impl DefaultToolAuditService {
+    pub fn new(repository: Arc<dyn ToolCallAuditRepository>, config: Arc<ConfigManager>) -> Self {
+        Self { repository, config }
+    }
+
+    /// Prune the log at startup and then daily
+    pub fn spawn_pruning(service: Arc<dyn ToolAuditService>) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            debug!("No tokio runtime; audit log pruning not started");
+            return;
+        };
+
+        runtime.spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(PRUNE_INTERVAL_HOURS * 3600));
+            loop {
+                interval.tick().await;
+                match service.prune().await {
+                    Ok(0) => {}
+                    Ok(entries) => debug!("Pruned {} audit log entries", entries),
+                    Err(e) => warn!("Failed to prune the audit log: {}", e),
+                }
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl ToolAuditService for DefaultToolAuditService {
+    async fn record(&self, entry: ToolCallAudit) -> Result<(), McpError> {
+        if !self.config.current().audit.enabled {
+            return Ok(());
+        }
+        self.repository.record(&entry).await
+    }
+
+    async fn find(&self, filter: &ToolCallAuditFilter) -> Result<Vec<ToolCallAudit>, McpError> {
+        self.repository.find(filter).await
+    }
+
+    async fn prune(&self) -> Result<usize, McpError> {
+        let retention_days = self.config.current().audit.retention_days;
+        if retention_days == 0 {
+            return Ok(0);
+        }
+        self.repository
+            .prune(Utc::now() - Duration::days(retention_days as i64))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::infrastructure::SqliteToolCallAuditRepository;
+    use crate::models::tool_call_audit::ToolCallOutcome;
+    use std::sync::Mutex;
+
+    fn entry(tool: &str, caller: &str, outcome: ToolCallOutcome, days_ago: i64) -> ToolCallAudit {
+        ToolCallAudit {
+            id: uuid::Uuid::new_v4().to_string(),
+            tool: tool.to_string(),
+            caller: Some(caller.to_string()),
+            project_id: Some("p1".to_string()),
+            arguments_hash: ToolCallAudit::hash_arguments(None),
+            outcome,
+            error_code: None,
+            error_message: None,
+            duration_ms: 3,
+            created_at: ToolCallAudit::timestamp(Utc::now() - Duration::days(days_ago)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_filter_and_prune() {
+        let db = Arc::new(Mutex::new(crate::db::init::init_db(":memory:").unwrap()));
+        let config = Arc::new(ConfigManager::new(AppConfig::default(), None));
+        let service = DefaultToolAuditService::new(Arc::new(SqliteToolCallAuditRepository::new(db)), config.clone());
+
+        service.record(entry("query_context", "ide/1", ToolCallOutcome::Success, 400)).await.unwrap();
+        service.record(entry("create_entity", "ide/1", ToolCallOutcome::Error, 1)).await.unwrap();
+        service.record(entry("query_context", "bot/2", ToolCallOutcome::Success, 0)).await.unwrap();
+
+        let all = ToolCallAuditFilter { limit: 10, ..Default::default() };
+        let newest: Vec<String> = service.find(&all).await.unwrap().into_iter().map(|e| e.tool).collect();
+        assert_eq!(newest, vec!["query_context", "create_entity", "query_context"]);
+        let ide_errors = ToolCallAuditFilter {
+            caller: Some("ide/1".to_string()),
+            outcome: Some(ToolCallOutcome::Error),
+            ..all.clone()
+        };
+        assert_eq!(service.find(&ide_errors).await.unwrap().len(), 1);
+        let recent = ToolCallAuditFilter { since: Some(Utc::now() - Duration::days(2)), ..all.clone() };
+        assert_eq!(service.find(&recent).await.unwrap().len(), 2);
+
+        assert_eq!(service.prune().await.unwrap(), 1);
+
+        let mut disabled = AppConfig::default();
+        disabled.audit.enabled = false;
+        config.apply(disabled);
+        service.record(entry("get_entity", "ide/1", ToolCallOutcome::Success, 0)).await.unwrap();
+        assert_eq!(service.find(&all).await.unwrap().len(), 2);
+
+        let mut reordered = serde_json::Map::new();
+        reordered.insert("b".to_string(), serde_json::json!(1));
+        reordered.insert("a".to_string(), serde_json::json!({"y": 2, "x": 1}));
+        let ordered: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(r#"{"a": {"x": 1, "y": 2}, "b": 1}"#).unwrap();
+        assert_eq!(ToolCallAudit::hash_arguments(Some(&reordered)), ToolCallAudit::hash_arguments(Some(&ordered)));
+    }
+}
@@ -0,0 +1,478 @@
+use crate::models::framework::FrameworkComponent;
+use crate::models::specification::{Requirement, SpecType, Task, TaskStatus};
+use crate::models::traceability::{
+    CoverageAnalysis, CoverageGap, TraceabilityReport, TraceabilityRow, TraceabilitySummary, TracedCommit, TracedComponent, TracedTask,
+};
+use crate::repositories::{FrameworkRepository, TraceabilityReportRepository};
+use crate::services::plugins::git_integration_plugin::GitCommit;
+use crate::services::plugins::GitIntegrationPlugin;
+use crate::services::SpecificationService;
+use async_trait::async_trait;
+use rmcp::model::ErrorData as McpError;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Commits read from the repository when the caller sets no limit
+pub const DEFAULT_COMMIT_LIMIT: usize = 500;
+
+/// Service joining requirements, tasks, framework components and commits into traceability
+/// matrices, which are saved as reports
+#[async_trait]
+pub trait TraceabilityService: Send + Sync {
+    /// Trace the requirements imported under `spec_project` and save the result as a report of
+    /// `project_id`; commits are read from the git repository at `repository_path`
+    async fn generate_matrix(
+        &self,
+        project_id: &str,
+        spec_project: &str,
+        repository_path: &Path,
+        commit_limit: usize,
+    ) -> Result<TraceabilityReport, McpError>;
+
+    /// Requirements imported under `spec_project` that have no tasks or components, or that
+    /// name components `project_id` does not have. Nothing is saved and no commits are read.
+    async fn analyze_coverage(&self, project_id: &str, spec_project: &str) -> Result<CoverageAnalysis, McpError>;
+
+    async fn get_report(&self, id: &str) -> Result<Option<TraceabilityReport>, McpError>;
+    async fn list_reports(&self, project_id: &str) -> Result<Vec<TraceabilityReport>, McpError>;
+    async fn delete_report(&self, id: &str) -> Result<bool, McpError>;
+}
+
+/// Default implementation of TraceabilityService
+pub struct DefaultTraceabilityService {
+    specification_service: Arc<dyn SpecificationService>,
+    framework_repository: Arc<dyn FrameworkRepository>,
+    report_repository: Arc<dyn TraceabilityReportRepository>,
+}
+
+impl DefaultTraceabilityService {
+    pub fn new(
+        specification_service: Arc<dyn SpecificationService>,
+        framework_repository: Arc<dyn FrameworkRepository>,
+        report_repository: Arc<dyn TraceabilityReportRepository>,
+    ) -> Self {
+        Self {
+            specification_service,
+            framework_repository,
+            report_repository,
+        }
+    }
+
+    /// The matrix for already loaded data; `commits` is `None` when no repository could be read.
+    ///
+    /// A task traces to a requirement through a stored link or a `_Requirements: 2.1_`
+    /// reference to its number, inherited by subtasks without references of their own. A
+    /// component traces through a context link of the requirement or one of its tasks, or by
+    /// being named in a task. A commit traces when its message names a task or the requirement
+    /// id, or when it changes a traced component's file. Component names in backticks in the
+    /// requirement or its tasks that match no component are reported as missing.
+    pub fn build_report(
+        project_id: &str,
+        spec_project: &str,
+        requirements: &[(String, Requirement)],
+        tasks: &[Task],
+        components: &[FrameworkComponent],
+        commits: Option<&[GitCommit]>,
+    ) -> TraceabilityReport {
+        let references: HashMap<&str, HashSet<u32>> =
+            tasks.iter().map(|t| (t.id.as_str(), Self::referenced_numbers(t, tasks))).collect();
+        let mut traced_tasks: HashSet<&str> = HashSet::new();
+
+        let rows: Vec<TraceabilityRow> = requirements
+            .iter()
+            .map(|(spec_id, requirement)| {
+                let number = Self::requirement_number(requirement);
+                let row_tasks: Vec<&Task> = tasks
+                    .iter()
+                    .filter(|t| {
+                        requirement.linked_tasks.contains(&t.id)
+                            || t.linked_requirements.contains(&requirement.id)
+                            || number.is_some_and(|n| references[t.id.as_str()].contains(&n))
+                    })
+                    .collect();
+                traced_tasks.extend(row_tasks.iter().map(|t| t.id.as_str()));
+
+                let row_components: Vec<&FrameworkComponent> = components
+                    .iter()
+                    .filter(|c| {
+                        requirement.linked_context.contains(&c.id)
+                            || row_tasks.iter().any(|t| t.linked_context.contains(&c.id) || Self::names_component(t, c))
+                    })
+                    .collect();
+
+                let mut texts = vec![requirement.title.as_str(), requirement.description.as_str()];
+                texts.extend(requirement.user_story.as_deref());
+                texts.extend(requirement.acceptance_criteria.iter().map(|c| c.description.as_str()));
+                texts.extend(row_tasks.iter().flat_map(|t| [t.title.as_str(), t.description.as_str()]));
+                let mut missing_components: Vec<String> = texts
+                    .into_iter()
+                    .flat_map(Self::referenced_component_names)
+                    .filter(|name| !components.iter().any(|c| c.component_name.eq_ignore_ascii_case(name)))
+                    .collect();
+                missing_components.sort();
+                missing_components.dedup();
+
+                let row_commits: Vec<&GitCommit> = commits
+                    .unwrap_or_default()
+                    .iter()
+                    .filter(|commit| {
+                        let message = commit.message.to_lowercase();
+                        message.contains(&requirement.id)
+                            || row_tasks.iter().any(|t| message.contains(&t.title.to_lowercase()))
+                            || commit.files_changed.iter().any(|f| {
+                                row_components.iter().any(|c| c.file_path.as_deref().is_some_and(|p| Self::same_file(p, &f.path)))
+                            })
+                    })
+                    .collect();
+
+                let mut gaps = Vec::new();
+                if requirement.acceptance_criteria.is_empty() {
+                    gaps.push(CoverageGap::NoAcceptanceCriteria);
+                }
+                if row_tasks.is_empty() {
+                    gaps.push(CoverageGap::NoTasks);
+                } else if row_tasks.iter().any(|t| t.status != TaskStatus::Completed) {
+                    gaps.push(CoverageGap::TasksIncomplete);
+                }
+                if row_components.is_empty() {
+                    gaps.push(CoverageGap::NoComponents);
+                }
+                if !missing_components.is_empty() {
+                    gaps.push(CoverageGap::MissingComponents);
+                }
+                if commits.is_some() && row_commits.is_empty() {
+                    gaps.push(CoverageGap::NoCommits);
+                }
+
+                TraceabilityRow {
+                    spec_id: spec_id.clone(),
+                    requirement_id: requirement.id.clone(),
+                    requirement_title: requirement.title.clone(),
+                    user_story: requirement.user_story.clone(),
+                    acceptance_criteria: requirement.acceptance_criteria.len(),
+                    tasks: row_tasks.into_iter().map(Self::traced_task).collect(),
+                    components: row_components
+                        .into_iter()
+                        .map(|c| TracedComponent {
+                            id: c.id.clone(),
+                            name: c.component_name.clone(),
+                            file_path: c.file_path.clone(),
+                        })
+                        .collect(),
+                    missing_components,
+                    commits: row_commits
+                        .into_iter()
+                        .map(|c| TracedCommit {
+                            hash: c.hash.clone(),
+                            message: c.message.clone(),
+                            author: c.author.clone(),
+                            timestamp: c.timestamp.to_rfc3339(),
+                        })
+                        .collect(),
+                    gaps,
+                }
+            })
+            .collect();
+
+        // A parent whose subtasks carry the references is traced through them
+        let untraced_tasks = tasks
+            .iter()
+            .filter(|t| !traced_tasks.contains(t.id.as_str()))
+            .filter(|t| !tasks.iter().any(|child| child.parent_task.as_ref() == Some(&t.id) && traced_tasks.contains(child.id.as_str())))
+            .map(Self::traced_task)
+            .collect();
+
+        let count = |f: fn(&TraceabilityRow) -> bool| rows.iter().filter(|r| f(r)).count();
+        let fully_traced = count(|r| r.gaps.is_empty());
+        let summary = TraceabilitySummary {
+            requirements: rows.len(),
+            fully_traced,
+            with_tasks: count(|r| !r.tasks.is_empty()),
+            with_components: count(|r| !r.components.is_empty()),
+            with_commits: count(|r| !r.commits.is_empty()),
+            coverage: if rows.is_empty() { 0.0 } else { fully_traced as f64 / rows.len() as f64 },
+        };
+
+        TraceabilityReport {
+            id: Uuid::new_v4().to_string(),
+            project_id: project_id.to_string(),
+            spec_project: spec_project.to_string(),
+            rows,
+            untraced_tasks,
+            summary,
+            commits_scanned: commits.map(|c| c.len()),
+            created_at: Some(chrono::Utc::now().to_rfc3339()),
+        }
+    }
+
+    /// `Requirement 3` and `Requirement 3: Refunds` are requirement 3
+    fn requirement_number(requirement: &Requirement) -> Option<u32> {
+        let rest = requirement.title.strip_prefix("Requirement ")?;
+        let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+        digits.parse().ok()
+    }
+
+    /// Requirement numbers of a task's `_Requirements: 1.2, 3.1_` reference, or of its
+    /// nearest ancestor with one
+    fn referenced_numbers(task: &Task, tasks: &[Task]) -> HashSet<u32> {
+        let mut current = Some(task);
+        while let Some(task) = current {
+            if let Some(references) = task.metadata.custom_fields.get("requirements").and_then(|v| v.as_str()) {
+                return references
+                    .split(',')
+                    .filter_map(|r| r.trim().split('.').next()?.trim().parse().ok())
+                    .collect();
+            }
+            current = task.parent_task.as_ref().and_then(|parent| tasks.iter().find(|t| &t.id == parent));
+        }
+        HashSet::new()
+    }
+
+    /// Identifiers in backticks shaped like component names, e.g. `PaymentService`: starting
+    /// with a capital and holding a lower case letter and another capital
+    fn referenced_component_names(text: &str) -> Vec<String> {
+        text.split('`')
+            .skip(1)
+            .step_by(2)
+            .map(str::trim)
+            .filter(|name| {
+                let mut chars = name.chars();
+                chars.next().is_some_and(|c| c.is_ascii_uppercase())
+                    && name.chars().all(|c| c.is_ascii_alphanumeric())
+                    && name.chars().any(|c| c.is_ascii_lowercase())
+                    && chars.any(|c| c.is_ascii_uppercase())
+            })
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Newest specification per file of `spec_project`, split into requirements (with the id
+    /// of their specification) and tasks
+    async fn load_specifications(&self, spec_project: &str) -> Result<(Vec<(String, Requirement)>, Vec<Task>), McpError> {
+        // Importing a file again adds a specification; only the newest one per file counts
+        let mut seen_files = HashSet::new();
+        let mut requirements = Vec::new();
+        let mut tasks = Vec::new();
+        for spec in self.specification_service.get_specifications_by_project(spec_project).await? {
+            if let Some(file_path) = &spec.file_path {
+                if !seen_files.insert(file_path.clone()) {
+                    continue;
+                }
+            }
+            match spec.spec_type {
+                SpecType::Tasks => tasks.extend(self.specification_service.get_tasks_by_spec(&spec.id).await?),
+                _ => requirements.extend(
+                    self.specification_service
+                        .get_requirements_by_spec(&spec.id)
+                        .await?
+                        .into_iter()
+                        .map(|r| (spec.id.clone(), r)),
+                ),
+            }
+        }
+        Ok((requirements, tasks))
+    }
+
+    fn names_component(task: &Task, component: &FrameworkComponent) -> bool {
+        let name = component.component_name.to_lowercase();
+        if name.len() < 3 {
+            return false;
+        }
+        let text = format!("{} {}", task.title, task.description).to_lowercase();
+        text.contains(&name) || component.file_path.as_deref().is_some_and(|p| text.contains(&p.to_lowercase()))
+    }
+
+    /// Whether a changed path is the component's file or lies in its directory
+    fn same_file(component_path: &str, changed_path: &str) -> bool {
+        let component_path = component_path.trim_start_matches("./").trim_end_matches('/');
+        let changed_path = changed_path.trim_start_matches("./");
+        !component_path.is_empty()
+            && (changed_path == component_path
+                || changed_path.ends_with(&format!("/{}", component_path))
+                || changed_path.starts_with(&format!("{}/", component_path)))
+    }
+
+    fn traced_task(task: &Task) -> TracedTask {
+        TracedTask {
+            id: task.id.clone(),
+            title: task.title.clone(),
+            status: task.status.as_str().to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl TraceabilityService for DefaultTraceabilityService {
+    async fn generate_matrix(
+        &self,
+        project_id: &str,
+        spec_project: &str,
+        repository_path: &Path,
+        commit_limit: usize,
+    ) -> Result<TraceabilityReport, McpError> {
+        let (requirements, tasks) = self.load_specifications(spec_project).await?;
+        let components = self.framework_repository.find_by_project_id(project_id).await?;
+        let commits = match GitIntegrationPlugin::new()
+            .with_repository_path(repository_path)
+            .recent_commits(commit_limit)
+            .await
+        {
+            Ok(commits) => Some(commits),
+            Err(e) => {
+                warn!("Tracing without commits, {} could not be read: {}", repository_path.display(), e);
+                None
+            }
+        };
+
+        let report = Self::build_report(project_id, spec_project, &requirements, &tasks, &components, commits.as_deref());
+        info!(
+            "Traced {} of {} requirements of {} for project {}",
+            report.summary.fully_traced, report.summary.requirements, spec_project, project_id
+        );
+        self.report_repository.create(&report).await
+    }
+
+    async fn analyze_coverage(&self, project_id: &str, spec_project: &str) -> Result<CoverageAnalysis, McpError> {
+        let (requirements, tasks) = self.load_specifications(spec_project).await?;
+        let components = self.framework_repository.find_by_project_id(project_id).await?;
+        let report = Self::build_report(project_id, spec_project, &requirements, &tasks, &components, None);
+        Ok(CoverageAnalysis::from_report(&report))
+    }
+
+    async fn get_report(&self, id: &str) -> Result<Option<TraceabilityReport>, McpError> {
+        self.report_repository.find_by_id(id).await
+    }
+
+    async fn list_reports(&self, project_id: &str) -> Result<Vec<TraceabilityReport>, McpError> {
+        self.report_repository.find_by_project_id(project_id).await
+    }
+
+    async fn delete_report(&self, id: &str) -> Result<bool, McpError> {
+        self.report_repository.delete(id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::SqliteTraceabilityReportRepository;
+    use crate::repositories::TraceabilityReportRepository;
+    use crate::services::plugins::git_integration_plugin::{GitChangeType, GitFileChange};
+    use crate::services::SpecificationParser;
+
+    fn component(id: &str, name: &str, file_path: &str) -> FrameworkComponent {
+        FrameworkComponent {
+            id: id.to_string(),
+            project_id: "p1".to_string(),
+            component_name: name.to_string(),
+            component_type: "service".to_string(),
+            architecture_layer: "domain".to_string(),
+            file_path: Some(file_path.to_string()),
+            dependencies: Vec::new(),
+            metadata: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_matrix_traces_requirements_and_reports_gaps() {
+        let requirements = SpecificationParser::parse_requirements_from_markdown(
+            "### Requirement 1\n\n**User Story:** Sign in\n\n#### Acceptance Criteria\n\n1. WHEN valid THEN sign in\n\n\
+             ### Requirement 2\n\n**User Story:** Lock accounts\n",
+            "spec-r".to_string(),
+        )
+        .unwrap();
+        let requirements: Vec<(String, Requirement)> =
+            requirements.into_iter().map(|r| ("spec-r".to_string(), r)).collect();
+        let tasks = SpecificationParser::parse_tasks_from_markdown(
+            "- [x] 1. Build sign-in\n  - _Requirements: 1.1_\n  - [x] 1.1 Add the SessionStore\n\n- [ ] 2. Write the changelog\n",
+            "spec-t".to_string(),
+        )
+        .unwrap();
+        let components = vec![component("c1", "SessionStore", "src/session.rs"), component("c2", "Billing", "src/billing.rs")];
+        let commits = vec![GitCommit {
+            hash: "abc123def456".to_string(),
+            author: "dev".to_string(),
+            email: "dev@example.com".to_string(),
+            timestamp: chrono::Utc::now(),
+            message: "Store sessions in cookies".to_string(),
+            files_changed: vec![GitFileChange {
+                path: "src/session.rs".to_string(),
+                change_type: GitChangeType::Modified,
+                additions: 0,
+                deletions: 0,
+                diff: None,
+            }],
+        }];
+
+        let report = DefaultTraceabilityService::build_report("p1", "login", &requirements, &tasks, &components, Some(&commits));
+        let signed_in = &report.rows[0];
+        let titles: Vec<&str> = signed_in.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Build sign-in", "Add the SessionStore"]);
+        assert_eq!(signed_in.components[0].id, "c1");
+        assert_eq!(signed_in.commits[0].hash, "abc123def456");
+        assert!(signed_in.gaps.is_empty());
+
+        let locked = &report.rows[1];
+        assert_eq!(
+            locked.gaps,
+            vec![CoverageGap::NoAcceptanceCriteria, CoverageGap::NoTasks, CoverageGap::NoComponents, CoverageGap::NoCommits]
+        );
+        assert_eq!(report.untraced_tasks.len(), 1);
+        assert_eq!(report.untraced_tasks[0].title, "Write the changelog");
+        assert_eq!(report.summary.fully_traced, 1);
+        assert_eq!(report.summary.coverage, 0.5);
+        assert!(report.to_markdown().contains("| Requirement 2 |  |  |  | **no acceptance criteria**, **no tasks**"));
+
+        // Without a readable repository, missing commits are not a gap
+        let report = DefaultTraceabilityService::build_report("p1", "login", &requirements, &tasks, &components, None);
+        assert!(!report.rows[1].gaps.contains(&CoverageGap::NoCommits));
+        assert_eq!(report.commits_scanned, None);
+
+        let conn = crate::db::init::init_db(":memory:").unwrap();
+        conn.execute("INSERT INTO projects (id, name) VALUES ('p1', 'Portal')", []).unwrap();
+        let repository = SqliteTraceabilityReportRepository::new(Arc::new(std::sync::Mutex::new(conn)));
+        repository.create(&report).await.unwrap();
+        assert_eq!(repository.find_by_id(&report.id).await.unwrap(), Some(report.clone()));
+        assert_eq!(repository.find_by_project_id("p1").await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_coverage_lists_tasks_components_and_missing_names() {
+        let requirements = SpecificationParser::parse_requirements_from_markdown(
+            "### Requirement 1\n\n**User Story:** Pay through the `PaymentGateway`\n\n\
+             ### Requirement 2\n\n**User Story:** Refund orders\n",
+            "spec-r".to_string(),
+        )
+        .unwrap();
+        let requirements: Vec<(String, Requirement)> =
+            requirements.into_iter().map(|r| ("spec-r".to_string(), r)).collect();
+        let tasks = SpecificationParser::parse_tasks_from_markdown(
+            "- [ ] 1. Charge cards in the `CheckoutService` and `FraudCheck`, keeping `amount` in `cents`\n  - _Requirements: 1.1_\n",
+            "spec-t".to_string(),
+        )
+        .unwrap();
+        let components = vec![component("c1", "CheckoutService", "src/checkout.rs")];
+
+        let report = DefaultTraceabilityService::build_report("p1", "shop", &requirements, &tasks, &components, None);
+        assert_eq!(report.rows[0].missing_components, vec!["FraudCheck".to_string(), "PaymentGateway".to_string()]);
+        assert!(report.rows[0].gaps.contains(&CoverageGap::MissingComponents));
+
+        let analysis = CoverageAnalysis::from_report(&report);
+        assert_eq!(analysis.requirements, 2);
+        assert_eq!(analysis.covered, 0);
+        let titles = |items: &[crate::models::traceability::CoverageItem]| {
+            items.iter().map(|i| i.requirement_title.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(titles(&analysis.no_tasks), vec!["Requirement 2"]);
+        assert_eq!(titles(&analysis.no_components), vec!["Requirement 2"]);
+        assert_eq!(analysis.missing_components[0].component_names, vec!["FraudCheck", "PaymentGateway"]);
+        assert!(analysis
+            .to_markdown()
+            .contains("## Requirements naming missing components\n\n- Requirement 1: FraudCheck, PaymentGateway\n"));
+    }
+}
@@ -0,0 +1,145 @@
+use crate::services::analytics_service::EntityUses;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Name of the tag `get_unused_context` attaches to entities nobody used in its window
+pub const STALE_TAG: &str = "stale";
+
+/// Fields naming an entity, by entity type
+const TITLE_FIELDS: &[&str] = &["rule_name", "decision_title", "component_name", "phase_name", "name", "title", "component_area"];
+
+/// An entity with how often it was handed out in the report's window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContextUsage {
+    pub entity_type: String,
+    pub entity_id: String,
+    pub title: Option<String>,
+    pub created_at: Option<String>,
+    #[serde(flatten)]
+    pub uses: EntityUses,
+    pub total_uses: u64,
+}
+
+/// Entities of a project that `query_context`, search and `get_entity` did not return in the
+/// last `days`, and the ones returned least, as candidates for pruning
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnusedContextReport {
+    pub project_id: String,
+    pub days: u32,
+    pub since: DateTime<Utc>,
+    pub entities_checked: usize,
+    /// Unused entities created within the window, which are left out
+    pub too_new: usize,
+    /// Oldest first
+    pub never_used: Vec<ContextUsage>,
+    /// Used entities, fewest uses first
+    pub least_used: Vec<ContextUsage>,
+}
+
+impl UnusedContextReport {
+    /// `entities` are `(entity_type, entity)` pairs as the CRUD tools return them; `least_used`
+    /// holds at most `limit` entities
+    pub fn build(
+        project_id: &str,
+        days: u32,
+        now: DateTime<Utc>,
+        entities: &[(&str, serde_json::Value)],
+        uses: &HashMap<String, EntityUses>,
+        limit: usize,
+    ) -> Self {
+        let since = now - chrono::Duration::days(days as i64);
+        let mut report = Self {
+            project_id: project_id.to_string(),
+            days,
+            since,
+            entities_checked: 0,
+            too_new: 0,
+            never_used: Vec::new(),
+            least_used: Vec::new(),
+        };
+
+        for (entity_type, entity) in entities {
+            let Some(entity_id) = entity.get("id").and_then(|id| id.as_str()) else {
+                continue;
+            };
+            report.entities_checked += 1;
+            let entity_uses = uses.get(entity_id).cloned().unwrap_or_default();
+            let created_at = entity.get("created_at").and_then(|v| v.as_str()).map(str::to_string);
+            let usage = ContextUsage {
+                entity_type: entity_type.to_string(),
+                entity_id: entity_id.to_string(),
+                title: entity_title(entity),
+                total_uses: entity_uses.total(),
+                uses: entity_uses,
+                created_at,
+            };
+
+            if usage.total_uses > 0 {
+                report.least_used.push(usage);
+            } else if usage.created_at.as_deref().and_then(parse_timestamp).is_some_and(|at| at > since) {
+                report.too_new += 1;
+            } else {
+                report.never_used.push(usage);
+            }
+        }
+
+        report.never_used.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.entity_id.cmp(&b.entity_id)));
+        report.least_used.sort_by(|a, b| a.total_uses.cmp(&b.total_uses).then(a.entity_id.cmp(&b.entity_id)));
+        report.least_used.truncate(limit);
+        report
+    }
+}
+
+/// RFC 3339, or SQLite's `datetime('now')` format taken as UTC
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|at| at.with_timezone(&Utc))
+        .ok()
+        .or_else(|| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").ok().map(|at| at.and_utc()))
+}
+
+/// The field naming an entity, e.g. a rule's `rule_name`
+pub(crate) fn entity_title(entity: &serde_json::Value) -> Option<String> {
+    TITLE_FIELDS
+        .iter()
+        .find_map(|field| entity.get(*field).and_then(|v| v.as_str()))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_unused_context_report() {
+        let now = DateTime::parse_from_rfc3339("2024-06-30T12:00:00Z").unwrap().with_timezone(&Utc);
+        let entities = vec![
+            ("business_rule", json!({"id": "r1", "rule_name": "Refunds", "created_at": "2024-01-01T00:00:00Z"})),
+            ("business_rule", json!({"id": "r2", "rule_name": "Taxes", "created_at": "2024-06-29 08:00:00"})),
+            ("architectural_decision", json!({"id": "d1", "decision_title": "Use SQLite", "created_at": "2024-02-01 00:00:00"})),
+            ("framework_component", json!({"id": "c1", "component_name": "AuthService"})),
+            ("framework_component", json!({"id": "c2", "component_name": "UserRepository"})),
+        ];
+        let mut uses = HashMap::new();
+        uses.insert("c1".to_string(), EntityUses { queries: 3, searches: 1, reads: 0, last_used: Some("2024-06-30".to_string()) });
+        uses.insert("c2".to_string(), EntityUses { queries: 0, searches: 1, reads: 0, last_used: Some("2024-06-20".to_string()) });
+        uses.insert("d1".to_string(), EntityUses { last_used: Some("2024-03-01".to_string()), ..Default::default() });
+
+        let report = UnusedContextReport::build("p1", 30, now, &entities, &uses, 10);
+        assert_eq!(report.entities_checked, 5);
+        assert_eq!(report.too_new, 1);
+        let never: Vec<(&str, Option<&str>)> = report
+            .never_used
+            .iter()
+            .map(|usage| (usage.entity_id.as_str(), usage.uses.last_used.as_deref()))
+            .collect();
+        assert_eq!(never, vec![("r1", None), ("d1", Some("2024-03-01"))]);
+        assert_eq!(report.never_used[0].title.as_deref(), Some("Refunds"));
+        let least: Vec<(&str, u64)> = report.least_used.iter().map(|u| (u.entity_id.as_str(), u.total_uses)).collect();
+        assert_eq!(least, vec![("c2", 1), ("c1", 4)]);
+
+        assert_eq!(UnusedContextReport::build("p1", 30, now, &entities, &uses, 1).least_used.len(), 1);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::models::embedding::{EmbeddingConfig, VectorSearchQuery};
+use crate::config::EmbeddingSettings;
+use crate::models::embedding::{EmbeddingConfig, VectorSearchQuery, VectorSearchResult};
 use crate::models::enhanced_context::{
     EnhancedContextItem, ContextContent, ContextType, ContextMetadata,
 };
@@ -6,10 +7,141 @@ use crate::repositories::embedding_repository::SqliteEmbeddingRepository;
 use crate::services::embedding_service::EmbeddingServiceFactory;
 use crate::services::semantic_search_service::{SemanticSearchServiceImpl, SemanticSearchConfig, SemanticSearchService};
 use rusqlite::Connection;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing::{info, debug};
 
+/// Separates an entity id from the chunk number in the ids of its extra chunks
+const CHUNK_SEPARATOR: &str = "#chunk-";
+
+/// Splits long entity text (specifications, feature contexts) into overlapping chunks
+/// that fit the embedding model's input. The first chunk is indexed under the entity's
+/// own id and the rest under `chunk_id`, so entities that fit in one chunk are unaffected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContentChunker {
+    /// Characters per chunk; 0 disables chunking
+    chunk_size: usize,
+    /// Characters repeated at the start of the next chunk
+    overlap: usize,
+}
+
+impl Default for ContentChunker {
+    fn default() -> Self {
+        Self::from_settings(&EmbeddingSettings::default())
+    }
+}
+
+impl ContentChunker {
+    pub fn new(chunk_size: usize, overlap: usize) -> Self {
+        Self {
+            chunk_size,
+            // More overlap than half a chunk would re-embed most of the text twice
+            overlap: overlap.min(chunk_size / 2),
+        }
+    }
+
+    pub fn from_settings(settings: &EmbeddingSettings) -> Self {
+        Self::new(settings.chunk_size, settings.chunk_overlap)
+    }
+
+    /// `text` split into chunks, breaking at whitespace where possible; a single chunk
+    /// when it fits or chunking is disabled
+    pub fn chunk(&self, text: &str) -> Vec<String> {
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        if self.chunk_size == 0 || chars.len() <= self.chunk_size {
+            return vec![text.to_string()];
+        }
+        let byte_offset = |index: usize| chars.get(index).map_or(text.len(), |(offset, _)| *offset);
+
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        loop {
+            let mut end = (start + self.chunk_size).min(chars.len());
+            if end < chars.len() {
+                // Don't split a word unless the window's second half has no whitespace
+                if let Some(space) = (start + self.chunk_size / 2..end).rev().find(|&i| chars[i].1.is_whitespace()) {
+                    end = space + 1;
+                }
+            }
+            let chunk = text[byte_offset(start)..byte_offset(end)].trim();
+            if !chunk.is_empty() {
+                chunks.push(chunk.to_string());
+            }
+            if end >= chars.len() {
+                return chunks;
+            }
+            let mut next = end.saturating_sub(self.overlap).max(start + 1);
+            // Begin the overlap at a word boundary
+            while next < end && !chars[next - 1].1.is_whitespace() {
+                next += 1;
+            }
+            start = next;
+        }
+    }
+}
+
+/// Id under which chunk `index` of `entity_id` is stored; chunk 0 uses the entity id
+pub fn chunk_id(entity_id: &str, index: usize) -> String {
+    match index {
+        0 => entity_id.to_string(),
+        _ => format!("{entity_id}{CHUNK_SEPARATOR}{index}"),
+    }
+}
+
+/// Prefix shared by the ids of every extra chunk of `entity_id`
+pub fn chunk_id_prefix(entity_id: &str) -> String {
+    format!("{entity_id}{CHUNK_SEPARATOR}")
+}
+
+/// The entity a stored context id belongs to
+pub fn parent_id(context_id: &str) -> &str {
+    context_id
+        .rsplit_once(CHUNK_SEPARATOR)
+        .filter(|(_, index)| index.parse::<usize>().is_ok())
+        .map_or(context_id, |(parent, _)| parent)
+}
+
+/// Collapse chunk hits into one result per entity, scored by its best chunk, whose
+/// preview shows the matching passage; ranks are reassigned
+pub fn merge_chunk_hits(results: Vec<VectorSearchResult>) -> Vec<VectorSearchResult> {
+    let mut merged: Vec<VectorSearchResult> = Vec::with_capacity(results.len());
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut matching_chunks: Vec<usize> = Vec::new();
+    for mut result in results {
+        let parent = parent_id(&result.context_id).to_string();
+        match positions.get(&parent) {
+            Some(&position) => {
+                matching_chunks[position] += 1;
+                if result.similarity_score > merged[position].similarity_score {
+                    result.context_id = parent;
+                    merged[position] = result;
+                }
+            }
+            None => {
+                positions.insert(parent.clone(), merged.len());
+                matching_chunks.push(1);
+                result.context_id = parent;
+                merged.push(result);
+            }
+        }
+    }
+
+    for (result, chunks) in merged.iter_mut().zip(matching_chunks) {
+        if chunks > 1 {
+            result.metadata.match_explanation = format!(
+                "{} (best of {} matching chunks)",
+                result.metadata.match_explanation, chunks
+            );
+        }
+    }
+    merged.sort_by(|a, b| b.similarity_score.total_cmp(&a.similarity_score));
+    for (index, result) in merged.iter_mut().enumerate() {
+        result.rank = index + 1;
+    }
+    merged
+}
+
 /// Integration service that demonstrates the complete vector embedding workflow
 pub struct VectorEmbeddingIntegration {
     semantic_search_service: SemanticSearchServiceImpl,
@@ -284,6 +416,41 @@ mod tests {
             .expect("Workflow demonstration failed");
     }
     
+    #[test]
+    fn test_chunking_and_chunk_hit_merging() {
+        let text = "alpha beta gamma delta epsilon zeta eta theta iota kappa";
+        // Words shorter than the overlap are repeated; none are cut
+        assert_eq!(
+            ContentChunker::new(20, 6).chunk(text),
+            vec!["alpha beta gamma", "gamma delta epsilon", "zeta eta theta iota", "iota kappa"]
+        );
+        assert_eq!(ContentChunker::new(0, 0).chunk(text), vec![text.to_string()]);
+
+        assert_eq!(chunk_id("spec-1", 0), "spec-1");
+        assert_eq!(parent_id(&chunk_id("spec-1", 3)), "spec-1");
+        assert_eq!(parent_id("spec#chunk-notes"), "spec#chunk-notes");
+
+        let hit = |id: &str, score: f32| VectorSearchResult {
+            context_id: id.to_string(),
+            similarity_score: score,
+            distance: 1.0 - score,
+            rank: 0,
+            metadata: crate::models::embedding::ResultMetadata {
+                content_type: "specification".to_string(),
+                content_preview: id.to_string(),
+                match_explanation: "Cosine similarity".to_string(),
+                quality_indicators: Vec::new(),
+                facets: Default::default(),
+                project_id: None,
+            },
+        };
+        let merged = merge_chunk_hits(vec![hit("spec-1", 0.6), hit("rule-1", 0.7), hit("spec-1#chunk-2", 0.9)]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!((merged[0].context_id.as_str(), merged[0].rank), ("spec-1", 1));
+        assert_eq!(merged[0].metadata.content_preview, "spec-1#chunk-2");
+        assert!(merged[0].metadata.match_explanation.contains("2 matching chunks"));
+    }
+
     #[tokio::test]
     async fn test_query_suggestions() {
         let integration = VectorEmbeddingIntegration::new_for_testing()
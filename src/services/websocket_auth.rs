@@ -0,0 +1,196 @@
+use crate::config::AuthConfig;
+use crate::services::websocket_types::SyncFilters;
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use tokio_tungstenite::tungstenite::handshake::server::Request;
+
+/// What a WebSocket client may do, decided by the token it connected with
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientPermissions {
+    /// Projects the client may subscribe to; `None` for every project
+    projects: Option<HashSet<String>>,
+    /// Whether the client may publish changes
+    can_publish: bool,
+}
+
+impl ClientPermissions {
+    /// Subscribe to and publish on every project
+    pub fn full() -> Self {
+        Self {
+            projects: None,
+            can_publish: true,
+        }
+    }
+
+    /// Limited to `projects`, publishing only when `can_publish`
+    pub fn projects<I: IntoIterator<Item = String>>(projects: I, can_publish: bool) -> Self {
+        Self {
+            projects: Some(projects.into_iter().collect()),
+            can_publish,
+        }
+    }
+
+    pub fn can_subscribe(&self, project_id: &str) -> bool {
+        self.projects.as_ref().is_none_or(|projects| projects.contains(project_id))
+    }
+
+    pub fn can_publish(&self, project_id: &str) -> bool {
+        self.can_publish && self.can_subscribe(project_id)
+    }
+
+    /// `filters` limited to the client's projects. Subscribing to every project narrows to
+    /// the permitted ones; naming a project the client may not see is an error.
+    pub fn restrict(&self, mut filters: SyncFilters) -> Result<SyncFilters> {
+        let Some(allowed) = &self.projects else {
+            return Ok(filters);
+        };
+        match &filters.project_ids {
+            Some(project_ids) => {
+                if let Some(denied) = project_ids.iter().find(|id| !allowed.contains(*id)) {
+                    return Err(anyhow!("Not authorized for project {}", denied));
+                }
+            }
+            None => {
+                let mut project_ids: Vec<String> = allowed.iter().cloned().collect();
+                project_ids.sort();
+                filters.project_ids = Some(project_ids);
+            }
+        }
+        Ok(filters)
+    }
+}
+
+/// Token checks for the WebSocket handshake, built from `[auth]`
+#[derive(Debug, Clone, Default)]
+pub struct WebSocketAuth {
+    required: bool,
+    /// SHA-256 of each token, so every comparison takes the same time whatever the token
+    tokens: Vec<([u8; 32], ClientPermissions)>,
+}
+
+impl WebSocketAuth {
+    /// Accept every client with full permissions
+    pub fn open() -> Self {
+        Self::default()
+    }
+
+    pub fn from_config(config: &AuthConfig) -> Self {
+        let mut tokens: HashMap<String, ClientPermissions> = config
+            .project_tokens
+            .iter()
+            .map(|scoped| {
+                let permissions = ClientPermissions::projects(scoped.projects.iter().cloned(), !scoped.read_only);
+                (scoped.token.clone(), permissions)
+            })
+            .collect();
+        // A token listed in both places keeps full access
+        for token in &config.tokens {
+            tokens.insert(token.clone(), ClientPermissions::full());
+        }
+        Self {
+            required: config.required,
+            tokens: tokens
+                .into_iter()
+                .map(|(token, permissions)| (Sha256::digest(token.as_bytes()).into(), permissions))
+                .collect(),
+        }
+    }
+
+    /// Permissions for a client presenting `token`, or an error when it must be rejected.
+    /// Without `required`, clients without a token get full access, but a presented token
+    /// must still be valid and keeps its project limits.
+    pub fn authenticate(&self, token: Option<&str>) -> Result<ClientPermissions> {
+        match token {
+            Some(token) => {
+                let digest: [u8; 32] = Sha256::digest(token.as_bytes()).into();
+                // Compare with every token, without stopping early, so timing reveals nothing
+                let mut permissions = None;
+                for (candidate, candidate_permissions) in &self.tokens {
+                    let difference = candidate.iter().zip(&digest).fold(0u8, |acc, (a, b)| acc | (a ^ b));
+                    if difference == 0 {
+                        permissions = Some(candidate_permissions.clone());
+                    }
+                }
+                permissions.ok_or_else(|| anyhow!("Invalid API token"))
+            }
+            None if self.required => Err(anyhow!("An API token is required")),
+            None => Ok(ClientPermissions::full()),
+        }
+    }
+}
+
+/// Token of a WebSocket upgrade request, from `Authorization: Bearer <token>` or, for
+/// browsers that cannot set headers, a `token` query parameter
+pub fn request_token(request: &Request) -> Option<String> {
+    let bearer = request
+        .headers()
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string());
+    bearer.or_else(|| {
+        url::form_urlencoded::parse(request.uri().query()?.as_bytes())
+            .find(|(name, _)| name == "token")
+            .map(|(_, token)| token.into_owned())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProjectToken;
+
+    fn auth() -> WebSocketAuth {
+        WebSocketAuth::from_config(&AuthConfig {
+            required: true,
+            tokens: vec!["admin".to_string()],
+            project_tokens: vec![ProjectToken {
+                token: "ide".to_string(),
+                projects: vec!["p1".to_string()],
+                read_only: true,
+            }],
+        })
+    }
+
+    #[test]
+    fn test_tokens_grant_project_permissions() {
+        let auth = auth();
+        assert!(auth.authenticate(None).is_err());
+        assert!(auth.authenticate(Some("guess")).is_err());
+        assert_eq!(auth.authenticate(Some("admin")).unwrap(), ClientPermissions::full());
+
+        let ide = auth.authenticate(Some("ide")).unwrap();
+        assert!(ide.can_subscribe("p1"));
+        assert!(!ide.can_subscribe("p2"));
+        assert!(!ide.can_publish("p1"));
+
+        let narrowed = ide.restrict(SyncFilters::default()).unwrap();
+        assert_eq!(narrowed.project_ids, Some(vec!["p1".to_string()]));
+        let other = SyncFilters {
+            project_ids: Some(vec!["p1".to_string(), "p2".to_string()]),
+            ..SyncFilters::default()
+        };
+        assert!(ide.restrict(other).is_err());
+
+        assert_eq!(WebSocketAuth::open().authenticate(None).unwrap(), ClientPermissions::full());
+    }
+
+    #[test]
+    fn test_request_token_from_header_or_query() {
+        let request = Request::builder()
+            .uri("/sync")
+            .header("Authorization", "Bearer abc")
+            .body(())
+            .unwrap();
+        assert_eq!(request_token(&request).as_deref(), Some("abc"));
+
+        let request = Request::builder().uri("/sync?client=ide&token=xyz").body(()).unwrap();
+        assert_eq!(request_token(&request).as_deref(), Some("xyz"));
+        let request = Request::builder().uri("/sync?token=a%2Bb%2F%3D%3D").body(()).unwrap();
+        assert_eq!(request_token(&request).as_deref(), Some("a+b/=="));
+
+        let request = Request::builder().uri("/sync").body(()).unwrap();
+        assert!(request_token(&request).is_none());
+    }
+}
@@ -1,25 +1,48 @@
+use crate::config::SlowClientPolicy;
+use crate::services::change_broadcaster::{ChangeBroadcaster, ChangeEvent};
+use crate::services::conflict_resolution_engine::ConflictResolutionEngine;
+use crate::services::sync_engine::{apply_change, apply_queued_operations};
+use crate::services::websocket_auth::{request_token, ClientPermissions, WebSocketAuth};
+use crate::services::websocket_server::WebSocketConfig;
 use crate::services::websocket_types::*;
 use anyhow::{anyhow, Result};
 use chrono::Utc;
 use dashmap::DashMap;
 use futures_util::{SinkExt, StreamExt};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{broadcast, mpsc, Notify};
 use tokio::time::{interval, Duration};
-use tokio_tungstenite::{accept_async, tungstenite::Message};
+use tokio_tungstenite::accept_hdr_async;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::{http::StatusCode, Message};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+/// Most logged changes scanned for one `resume_from`; clients continue from `last_sequence`
+pub const REPLAY_LIMIT: usize = 500;
+
 /// WebSocket connection manager for real-time synchronization
 pub struct WebSocketManager {
     /// Active client connections
     pub connections: Arc<DashMap<ClientId, ClientConnection>>,
     /// Broadcast channel for sending changes to all clients
     change_broadcaster: broadcast::Sender<ContextChange>,
+    /// Source of changes made through the rest of the server; client subscriptions
+    /// are mirrored here so it knows which changes have listeners
+    broadcaster: Arc<ChangeBroadcaster>,
+    /// Tokens accepted during the handshake and the projects they grant
+    auth: WebSocketAuth,
     /// Message queue for reliable delivery
     pub message_queue: Arc<DashMap<ClientId, Vec<QueuedMessage>>>,
     /// Connection health monitoring
     pub health_monitor: Arc<DashMap<ClientId, ConnectionHealth>>,
+    /// Heartbeat and send queue limits
+    config: WebSocketConfig,
+    /// Records offline operations that overlap newer changes as conflicts
+    conflict_resolver: Option<Arc<tokio::sync::Mutex<ConflictResolutionEngine>>>,
 }
 
 /// Individual client connection
@@ -29,11 +52,76 @@ pub struct ClientConnection {
     pub project_id: String,
     pub client_info: ClientInfo,
     pub subscriptions: Vec<SyncFilters>,
-    pub message_sender: mpsc::UnboundedSender<WebSocketMessage>,
+    pub permissions: ClientPermissions,
+    /// Send updates as patches without the full entity
+    pub patches_only: bool,
+    /// Entities the client reported it is editing
+    pub editing: Vec<EditingActivity>,
+    pub message_sender: ClientSender,
     pub connected_at: chrono::DateTime<chrono::Utc>,
     pub last_activity: chrono::DateTime<chrono::Utc>,
 }
 
+/// Bounded queue of the messages waiting to be written to one client. When it is full,
+/// the `SlowClientPolicy` decides between dropping messages and disconnecting.
+#[derive(Clone)]
+pub struct ClientSender {
+    client_id: ClientId,
+    sender: mpsc::Sender<WebSocketMessage>,
+    policy: SlowClientPolicy,
+    dropped: Arc<AtomicU64>,
+    disconnect: Arc<Notify>,
+}
+
+impl ClientSender {
+    pub fn new(client_id: ClientId, sender: mpsc::Sender<WebSocketMessage>, policy: SlowClientPolicy) -> Self {
+        Self {
+            client_id,
+            sender,
+            policy,
+            dropped: Arc::new(AtomicU64::new(0)),
+            disconnect: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Queue `message` without waiting. A full queue is an error only when it disconnects
+    /// the client; dropped messages are counted instead.
+    pub fn send(&self, message: WebSocketMessage) -> Result<()> {
+        match self.sender.try_send(message) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => match self.policy {
+                SlowClientPolicy::Drop => {
+                    if self.dropped.fetch_add(1, Ordering::Relaxed) == 0 {
+                        warn!("Client {} is not keeping up; dropping messages", self.client_id);
+                    }
+                    Ok(())
+                }
+                SlowClientPolicy::Disconnect => {
+                    warn!("Client {} is not keeping up; disconnecting", self.client_id);
+                    self.disconnect();
+                    Err(anyhow!("Send queue of client {} is full", self.client_id))
+                }
+            },
+            Err(TrySendError::Closed(_)) => Err(anyhow!("Client {} is disconnected", self.client_id)),
+        }
+    }
+
+    /// Messages waiting to be written
+    pub fn queued(&self) -> usize {
+        self.sender.max_capacity() - self.sender.capacity()
+    }
+
+    /// Messages dropped because the queue was full
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Close the connection
+    pub fn disconnect(&self) {
+        self.disconnect.notify_one();
+    }
+}
+
 /// Queued message for reliable delivery
 #[derive(Debug, Clone)]
 pub struct QueuedMessage {
@@ -55,16 +143,43 @@ pub struct ConnectionHealth {
 impl WebSocketManager {
     /// Create a new WebSocket manager
     pub fn new() -> Self {
+        Self::with_broadcaster(Arc::new(ChangeBroadcaster::new()))
+    }
+
+    /// Create a WebSocket manager whose clients receive the changes published on `broadcaster`
+    pub fn with_broadcaster(broadcaster: Arc<ChangeBroadcaster>) -> Self {
         let (change_broadcaster, _) = broadcast::channel(1000);
-        
+
         Self {
             connections: Arc::new(DashMap::new()),
             change_broadcaster,
+            broadcaster,
+            auth: WebSocketAuth::open(),
             message_queue: Arc::new(DashMap::new()),
             health_monitor: Arc::new(DashMap::new()),
+            config: WebSocketConfig::default(),
+            conflict_resolver: None,
         }
     }
 
+    /// Use the heartbeat and send queue limits of `config`
+    pub fn with_config(mut self, config: WebSocketConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Require clients to connect with one of the tokens of `auth`
+    pub fn with_auth(mut self, auth: WebSocketAuth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Record submitted offline operations that overlap newer changes with `conflict_resolver`
+    pub fn with_conflict_resolver(mut self, conflict_resolver: Arc<tokio::sync::Mutex<ConflictResolutionEngine>>) -> Self {
+        self.conflict_resolver = Some(conflict_resolver);
+        self
+    }
+
     /// Start the WebSocket manager with health monitoring
     pub async fn start(&self) -> Result<()> {
         info!("Starting WebSocket manager");
@@ -78,23 +193,48 @@ impl WebSocketManager {
         Ok(())
     }
 
+    /// Broadcaster whose changes are delivered to subscribed clients
+    pub fn broadcaster(&self) -> Arc<ChangeBroadcaster> {
+        self.broadcaster.clone()
+    }
+
     /// Handle a new WebSocket connection
     pub async fn handle_connection(
         &self,
         stream: tokio::net::TcpStream,
     ) -> Result<()> {
-        let ws_stream = accept_async(stream).await?;
+        // Check the token before upgrading, so rejected clients get a plain HTTP 401
+        let mut permissions = None;
+        // The error response type is fixed by tungstenite
+        #[allow(clippy::result_large_err)]
+        let authorize = |request: &Request, response: Response| -> Result<Response, ErrorResponse> {
+            match self.auth.authenticate(request_token(request).as_deref()) {
+                Ok(granted) => {
+                    permissions = Some(granted);
+                    Ok(response)
+                }
+                Err(e) => {
+                    let mut rejection = ErrorResponse::new(Some(e.to_string()));
+                    *rejection.status_mut() = StatusCode::UNAUTHORIZED;
+                    Err(rejection)
+                }
+            }
+        };
+        let ws_stream = accept_hdr_async(stream, authorize).await?;
+        let permissions = permissions.ok_or_else(|| anyhow!("Handshake completed without authorization"))?;
         let (mut ws_sender, mut ws_receiver) = ws_stream.split();
-        
+
         let client_id = Uuid::new_v4();
         info!("New WebSocket connection: {}", client_id);
 
         // Create message channel for this client
-        let (message_sender, mut message_receiver) = mpsc::unbounded_channel();
+        let (sender, mut message_receiver) = mpsc::channel(self.config.message_queue_size);
+        let message_sender = ClientSender::new(client_id, sender, self.config.slow_client_policy);
+        let disconnect = message_sender.disconnect.clone();
 
         // Spawn task to handle outgoing messages
         let client_id_clone = client_id;
-        tokio::spawn(async move {
+        let writer = tokio::spawn(async move {
             while let Some(message) = message_receiver.recv().await {
                 let json_message = match serde_json::to_string(&message) {
                     Ok(json) => json,
@@ -113,15 +253,31 @@ impl WebSocketManager {
 
         // Handle incoming messages
         let connections = self.connections.clone();
-        let change_broadcaster = self.change_broadcaster.clone();
+        let broadcaster = self.broadcaster.clone();
         let message_queue = self.message_queue.clone();
         let health_monitor = self.health_monitor.clone();
+        let conflict_resolver = self.conflict_resolver.clone();
 
         tokio::spawn(async move {
             let mut authenticated = false;
             let mut client_connection: Option<ClientConnection> = None;
 
-            while let Some(msg) = ws_receiver.next().await {
+            loop {
+                let msg = tokio::select! {
+                    msg = ws_receiver.next() => match msg {
+                        Some(msg) => msg,
+                        None => break,
+                    },
+                    _ = disconnect.notified() => {
+                        info!("Disconnecting client {}", client_id);
+                        break;
+                    }
+                };
+                // Any frame shows the client is still there
+                if let Some(mut health) = health_monitor.get_mut(&client_id) {
+                    health.last_pong = Utc::now();
+                    health.missed_pings = 0;
+                }
                 match msg {
                     Ok(Message::Text(text)) => {
                         match serde_json::from_str::<WebSocketMessage>(&text) {
@@ -129,13 +285,15 @@ impl WebSocketManager {
                                 match Self::handle_message(
                                     client_id,
                                     ws_message,
+                                    &permissions,
                                     &mut authenticated,
                                     &mut client_connection,
                                     &connections,
                                     &message_sender,
-                                    &change_broadcaster,
+                                    &broadcaster,
                                     &message_queue,
                                     &health_monitor,
+                                    conflict_resolver.as_deref(),
                                 ).await {
                                     Ok(_) => {},
                                     Err(e) => {
@@ -173,9 +331,16 @@ impl WebSocketManager {
             }
 
             // Clean up connection
-            connections.remove(&client_id);
+            if let Some((_, connection)) = connections.remove(&client_id) {
+                Self::broadcast_presence(&connections, &connection, PresenceStatus::Left, None);
+            }
             message_queue.remove(&client_id);
             health_monitor.remove(&client_id);
+            if let Err(e) = broadcaster.unsubscribe(client_id).await {
+                warn!("Failed to unsubscribe client {}: {}", client_id, e);
+            }
+            // A writer stalled on a client that stopped reading would keep the socket open
+            writer.abort();
             info!("Cleaned up connection for client {}", client_id);
         });
 
@@ -183,27 +348,42 @@ impl WebSocketManager {
     }
 
     /// Handle individual WebSocket messages
+    #[allow(clippy::too_many_arguments)]
     async fn handle_message(
         client_id: ClientId,
         message: WebSocketMessage,
+        permissions: &ClientPermissions,
         authenticated: &mut bool,
         client_connection: &mut Option<ClientConnection>,
         connections: &Arc<DashMap<ClientId, ClientConnection>>,
-        message_sender: &mpsc::UnboundedSender<WebSocketMessage>,
-        _change_broadcaster: &broadcast::Sender<ContextChange>,
+        message_sender: &ClientSender,
+        broadcaster: &ChangeBroadcaster,
         message_queue: &Arc<DashMap<ClientId, Vec<QueuedMessage>>>,
         health_monitor: &Arc<DashMap<ClientId, ConnectionHealth>>,
+        conflict_resolver: Option<&tokio::sync::Mutex<ConflictResolutionEngine>>,
     ) -> Result<()> {
         match message {
             WebSocketMessage::Auth { token: _, project_id, client_info } => {
-                // Simple authentication - in production, validate token
+                // The token was checked during the handshake; here the project is
+                if !permissions.can_subscribe(&project_id) {
+                    message_sender.send(WebSocketMessage::AuthResponse {
+                        success: false,
+                        client_id,
+                        message: format!("Not authorized for project {}", project_id),
+                    })?;
+                    warn!("Client {} denied access to project {}", client_id, project_id);
+                    return Ok(());
+                }
                 *authenticated = true;
-                
+
                 let connection = ClientConnection {
                     client_id,
                     project_id: project_id.clone(),
                     client_info: client_info.clone(),
                     subscriptions: Vec::new(),
+                    permissions: permissions.clone(),
+                    patches_only: false,
+                    editing: Vec::new(),
                     message_sender: message_sender.clone(),
                     connected_at: Utc::now(),
                     last_activity: Utc::now(),
@@ -211,6 +391,9 @@ impl WebSocketManager {
 
                 connections.insert(client_id, connection);
                 *client_connection = connections.get(&client_id).map(|entry| entry.value().clone());
+                if let Some(connection) = client_connection.as_ref() {
+                    Self::broadcast_presence(connections, connection, PresenceStatus::Joined, None);
+                }
 
                 // Initialize health monitoring
                 health_monitor.insert(client_id, ConnectionHealth {
@@ -233,15 +416,28 @@ impl WebSocketManager {
                 info!("Client {} authenticated for project {}", client_id, project_id);
             }
 
-            WebSocketMessage::Subscribe { filters } => {
+            WebSocketMessage::Subscribe { filters, resume_from, patches_only } => {
                 if !*authenticated {
                     return Err(anyhow!("Client not authenticated"));
                 }
+                let filters = permissions.restrict(filters)?;
 
-                if let Some(mut connection) = connections.get_mut(&client_id) {
+                let subscriptions = connections.get_mut(&client_id).map(|mut connection| {
+                    connection.patches_only = patches_only;
                     connection.subscriptions.push(filters.clone());
                     connection.last_activity = Utc::now();
                     debug!("Client {} subscribed to filters: {:?}", client_id, filters);
+                    connection.subscriptions.clone()
+                });
+                if let Some(subscriptions) = subscriptions {
+                    broadcaster.subscribe(client_id, subscriptions).await?;
+                }
+
+                // Live delivery starts first, so a change made during the replay may arrive
+                // twice; clients drop sequences they have already seen
+                if let Some(after) = resume_from {
+                    Self::replay_changes(after, &filters, permissions, patches_only, broadcaster, message_sender)
+                        .await?;
                 }
             }
 
@@ -249,14 +445,89 @@ impl WebSocketManager {
                 if !*authenticated {
                     return Err(anyhow!("Client not authenticated"));
                 }
+                // Match the filters as they were narrowed on subscribe
+                let filters = permissions.restrict(filters)?;
 
-                if let Some(mut connection) = connections.get_mut(&client_id) {
-                    connection.subscriptions.retain(|sub| {
-                        // Simple comparison - in production, implement proper filter matching
-                        !std::ptr::eq(sub, &filters)
-                    });
+                let subscriptions = connections.get_mut(&client_id).map(|mut connection| {
+                    connection.subscriptions.retain(|sub| *sub != filters);
                     connection.last_activity = Utc::now();
                     debug!("Client {} unsubscribed from filters", client_id);
+                    connection.subscriptions.clone()
+                });
+                if let Some(subscriptions) = subscriptions {
+                    broadcaster.subscribe(client_id, subscriptions).await?;
+                }
+            }
+
+            WebSocketMessage::ContextChange { change, .. } => {
+                if !*authenticated {
+                    return Err(anyhow!("Client not authenticated"));
+                }
+                if !permissions.can_publish(&change.project_id) {
+                    return Err(anyhow!("Not authorized to publish to project {}", change.project_id));
+                }
+
+                // Relay to the other subscribers, attributed to this connection; a
+                // patch-only update applies to the entity as last broadcast
+                let old_value = broadcaster.last_entity(&change.entity_type, &change.entity_id);
+                let new_value = apply_change(old_value.as_ref(), &change)?;
+                broadcaster
+                    .broadcast_change(ChangeEvent {
+                        entity_type: change.entity_type,
+                        entity_id: change.entity_id,
+                        project_id: change.project_id,
+                        change_type: change.change_type,
+                        old_value,
+                        new_value,
+                        client_id,
+                        feature_area: change.feature_area,
+                    })
+                    .await?;
+            }
+
+            WebSocketMessage::SubmitOperations { operations } => {
+                if !*authenticated {
+                    return Err(anyhow!("Client not authenticated"));
+                }
+                if let Some(denied) = operations.iter().find(|op| !permissions.can_publish(&op.change.project_id)) {
+                    return Err(anyhow!("Not authorized to publish to project {}", denied.change.project_id));
+                }
+
+                let text_fields = match conflict_resolver {
+                    Some(conflict_resolver) => conflict_resolver.lock().await.config().text_fields.clone(),
+                    None => Vec::new(),
+                };
+                let results =
+                    apply_queued_operations(broadcaster, conflict_resolver, &text_fields, client_id, operations).await;
+                message_sender.send(WebSocketMessage::OperationResults { results })?;
+            }
+
+            WebSocketMessage::Activity { entity_type, entity_id, editing } => {
+                if !*authenticated {
+                    return Err(anyhow!("Client not authenticated"));
+                }
+                let entity = EntityRef { entity_type, entity_id };
+
+                // Only a change of state is announced, and only once the entry is released,
+                // since announcing walks all connections
+                let changed = connections.get_mut(&client_id).and_then(|mut connection| {
+                    connection.last_activity = Utc::now();
+                    let known = connection.editing.iter().position(|activity| activity.entity == entity);
+                    match (editing, known) {
+                        (true, None) => connection.editing.push(EditingActivity {
+                            entity: entity.clone(),
+                            since: Utc::now(),
+                        }),
+                        (false, Some(index)) => {
+                            connection.editing.remove(index);
+                        }
+                        _ => return None,
+                    }
+                    Some(connection.clone())
+                });
+                if let Some(connection) = changed {
+                    let status = if editing { PresenceStatus::Editing } else { PresenceStatus::StoppedEditing };
+                    Self::broadcast_presence(connections, &connection, status, Some(entity));
                 }
             }
 
@@ -295,36 +566,122 @@ impl WebSocketManager {
         Ok(())
     }
 
+    /// Tell the other clients of `connection`'s project about its presence
+    fn broadcast_presence(
+        connections: &DashMap<ClientId, ClientConnection>,
+        connection: &ClientConnection,
+        status: PresenceStatus,
+        entity: Option<EntityRef>,
+    ) {
+        let message = WebSocketMessage::Presence {
+            client_id: connection.client_id,
+            project_id: connection.project_id.clone(),
+            user_id: connection.client_info.user_id.clone(),
+            status,
+            entity,
+            timestamp: Utc::now(),
+        };
+        for other in connections.iter() {
+            if other.client_id != connection.client_id && other.project_id == connection.project_id {
+                // A closed channel means the other client is disconnecting itself
+                let _ = other.message_sender.send(message.clone());
+            }
+        }
+    }
+
+    /// Send the logged changes after sequence `after` that match `filters`, then a
+    /// `ReplayComplete` marking where the replay stopped
+    async fn replay_changes(
+        after: u64,
+        filters: &SyncFilters,
+        permissions: &ClientPermissions,
+        patches_only: bool,
+        broadcaster: &ChangeBroadcaster,
+        message_sender: &ClientSender,
+    ) -> Result<()> {
+        let mut changes = broadcaster.changes_since(after, REPLAY_LIMIT + 1).await?;
+        let more = changes.len() > REPLAY_LIMIT;
+        changes.truncate(REPLAY_LIMIT);
+
+        let mut last_sequence = after;
+        let mut replayed = 0;
+        for change in changes {
+            last_sequence = change.metadata.sequence.unwrap_or(last_sequence);
+            if filters.matches(&change) && permissions.can_subscribe(&change.project_id) {
+                message_sender.send(WebSocketMessage::ContextChange {
+                    message_id: Uuid::new_v4(),
+                    change: if patches_only { change.patch_only() } else { change },
+                    timestamp: Utc::now(),
+                })?;
+                replayed += 1;
+            }
+        }
+        message_sender.send(WebSocketMessage::ReplayComplete {
+            replayed,
+            last_sequence,
+            more,
+        })?;
+        Ok(())
+    }
+
     /// Broadcast a context change to all subscribed clients
     pub async fn broadcast_change(&self, change: ContextChange) -> Result<()> {
-        debug!("Broadcasting change: {:?}", change.change_id);
+        self.broadcast_changes(vec![change]).await
+    }
 
-        for connection in self.connections.iter() {
-            let client_id = *connection.key();
-            let client_connection = connection.value();
+    /// Send each client the changes of a batch it subscribed to, in one frame
+    pub async fn broadcast_changes(&self, changes: Vec<ContextChange>) -> Result<()> {
+        debug!("Broadcasting {} changes", changes.len());
 
-            // Check if client is subscribed to this change
-            let should_send = client_connection.subscriptions.iter()
-                .any(|filter| filter.matches(&change));
+        // Subscriptions are mirrored into the broadcaster, which decides who gets a change
+        let mut deliveries: HashMap<ClientId, Vec<ContextChange>> = HashMap::new();
+        for change in &changes {
+            for client_id in self.broadcaster.find_matching_clients(change).await {
+                let Some(connection) = self.connections.get(&client_id) else {
+                    continue;
+                };
+                let client_connection = connection.value();
+
+                if client_connection.permissions.can_subscribe(&change.project_id) {
+                    deliveries.entry(client_id).or_default().push(if client_connection.patches_only {
+                        change.patch_only()
+                    } else {
+                        change.clone()
+                    });
+                }
+            }
+        }
 
-            if should_send {
-                let message_id = Uuid::new_v4();
-                let message = WebSocketMessage::ContextChange {
+        for (client_id, mut client_changes) in deliveries {
+            let Some(connection) = self.connections.get(&client_id) else {
+                continue;
+            };
+            let message_id = Uuid::new_v4();
+            let message = if client_changes.len() == 1 {
+                WebSocketMessage::ContextChange {
                     message_id,
-                    change: change.clone(),
+                    change: client_changes.remove(0),
                     timestamp: Utc::now(),
-                };
-
-                // Try to send immediately
-                if client_connection.message_sender.send(message.clone()).is_err() {
-                    // If immediate send fails, queue the message
-                    self.queue_message(client_id, message_id, message).await;
                 }
+            } else {
+                WebSocketMessage::ContextChangeBatch {
+                    message_id,
+                    changes: client_changes,
+                    timestamp: Utc::now(),
+                }
+            };
+
+            // Try to send immediately
+            if connection.message_sender.send(message.clone()).is_err() {
+                // If immediate send fails, queue the message
+                self.queue_message(client_id, message_id, message).await;
             }
         }
 
         // Also send to broadcast channel for other components
-        let _ = self.change_broadcaster.send(change);
+        for change in changes {
+            let _ = self.change_broadcaster.send(change);
+        }
 
         Ok(())
     }
@@ -332,6 +689,10 @@ impl WebSocketManager {
     /// Queue a message for reliable delivery
     async fn queue_message(&self, client_id: ClientId, message_id: MessageId, message: WebSocketMessage) {
         if let Some(mut queue) = self.message_queue.get_mut(&client_id) {
+            // Bounded like the send queue; the oldest message goes first
+            if queue.len() >= self.config.message_queue_size {
+                queue.remove(0);
+            }
             queue.push(QueuedMessage {
                 message_id,
                 message,
@@ -347,7 +708,8 @@ impl WebSocketManager {
         let health = self.health_monitor.get(&client_id)?;
         let queue_size = self.message_queue.get(&client_id)
             .map(|queue| queue.len())
-            .unwrap_or(0);
+            .unwrap_or(0)
+            + connection.message_sender.queued();
 
         Some(ConnectionStatus {
             client_id,
@@ -356,19 +718,59 @@ impl WebSocketManager {
             project_id: connection.project_id.clone(),
             subscriptions: connection.subscriptions.clone(),
             message_queue_size: queue_size,
+            dropped_messages: connection.message_sender.dropped(),
             is_healthy: health.is_healthy,
         })
     }
 
+    /// Connected clients, optionally only those of one project, with what they are editing
+    pub fn active_clients(&self, project_id: Option<&str>) -> Vec<ActiveClient> {
+        let mut clients: Vec<ActiveClient> = self
+            .connections
+            .iter()
+            .filter(|entry| project_id.is_none_or(|project_id| entry.project_id == project_id))
+            .map(|entry| ActiveClient {
+                client_id: entry.client_id,
+                project_id: entry.project_id.clone(),
+                user_id: entry.client_info.user_id.clone(),
+                client_type: entry.client_info.client_type.clone(),
+                connected_at: entry.connected_at,
+                last_activity: entry.last_activity,
+                editing: entry.editing.clone(),
+            })
+            .collect();
+        clients.sort_by_key(|client| client.connected_at);
+        clients
+    }
+
+    /// Queue depths and dropped messages over all connected clients
+    pub fn delivery_stats(&self) -> DeliveryStats {
+        let mut stats = DeliveryStats {
+            pending_messages: self.message_queue.iter().map(|entry| entry.value().len()).sum(),
+            ..Default::default()
+        };
+        for connection in self.connections.iter() {
+            let queued = connection.message_sender.queued();
+            stats.connected_clients += 1;
+            stats.queued_messages += queued;
+            stats.max_client_queue = stats.max_client_queue.max(queued);
+            stats.dropped_messages += connection.message_sender.dropped();
+        }
+        stats
+    }
+
     /// Get sync status for a project
     pub async fn get_sync_status(&self, project_id: &str) -> SyncStatus {
         let connected_clients = self.connections.iter()
             .filter(|entry| entry.value().project_id == project_id)
             .count() as u32;
 
-        let pending_changes = self.message_queue.iter()
+        let pending_changes = (self.message_queue.iter()
             .map(|entry| entry.value().len())
-            .sum::<usize>() as u32;
+            .sum::<usize>()
+            + self.connections.iter()
+                .map(|entry| entry.value().message_sender.queued())
+                .sum::<usize>()) as u32;
 
         // Determine sync health based on connected clients and pending changes
         let sync_health = if connected_clients == 0 {
@@ -388,13 +790,17 @@ impl WebSocketManager {
         }
     }
 
-    /// Start health monitoring background task
+    /// Start health monitoring background task: ping every client each heartbeat interval
+    /// and disconnect those not heard from within the heartbeat timeout
     async fn start_health_monitoring(&self) {
         let connections = self.connections.clone();
         let health_monitor = self.health_monitor.clone();
+        let heartbeat_interval = self.config.heartbeat_interval;
+        let heartbeat_timeout = chrono::Duration::from_std(self.config.heartbeat_timeout)
+            .unwrap_or_else(|_| chrono::Duration::seconds(90));
 
         tokio::spawn(async move {
-            let mut interval = interval(Duration::from_secs(30)); // Check every 30 seconds
+            let mut interval = interval(heartbeat_interval);
 
             loop {
                 interval.tick().await;
@@ -407,14 +813,10 @@ impl WebSocketManager {
                     let client_id = *health_entry.key();
                     let health = health_entry.value_mut();
 
-                    // Check if client hasn't responded to ping in a while
-                    if now.signed_duration_since(health.last_pong).num_seconds() > 60 {
-                        health.missed_pings += 1;
-                        health.is_healthy = health.missed_pings < 3;
-
-                        if !health.is_healthy {
-                            unhealthy_clients.push(client_id);
-                        }
+                    if now.signed_duration_since(health.last_pong) > heartbeat_timeout {
+                        health.is_healthy = false;
+                        unhealthy_clients.push(client_id);
+                        continue;
                     }
 
                     // Send ping to check connectivity
@@ -422,15 +824,22 @@ impl WebSocketManager {
                         let ping = WebSocketMessage::Ping {
                             timestamp: now,
                         };
-                        let _ = connection.message_sender.send(ping);
+                        if connection.message_sender.send(ping).is_ok() {
+                            health.last_ping = now;
+                            health.missed_pings += 1;
+                        }
                     }
                 }
 
-                // Clean up unhealthy connections
+                // The connection task cleans up after itself once disconnected
                 for client_id in unhealthy_clients {
-                    warn!("Removing unhealthy client: {}", client_id);
-                    connections.remove(&client_id);
-                    health_monitor.remove(&client_id);
+                    warn!("Disconnecting unresponsive client: {}", client_id);
+                    match connections.get(&client_id) {
+                        Some(connection) => connection.message_sender.disconnect(),
+                        None => {
+                            health_monitor.remove(&client_id);
+                        }
+                    }
                 }
             }
         });
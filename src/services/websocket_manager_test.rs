@@ -35,6 +35,7 @@ async fn test_sync_filters_matching() {
             timestamp: Utc::now(),
             version: 1,
             conflict_resolution: None,
+            sequence: None,
         },
     };
 
@@ -101,6 +102,7 @@ async fn test_websocket_message_serialization() {
             user_agent: Some("test-agent".to_string()),
             client_type: ClientType::AIAgent,
             version: "1.0.0".to_string(),
+            user_id: None,
         },
     };
 
@@ -136,6 +138,7 @@ async fn test_websocket_message_serialization() {
             timestamp: Utc::now(),
             version: 2,
             conflict_resolution: None,
+            sequence: None,
         },
     };
 
@@ -206,6 +209,7 @@ async fn test_client_info_types() {
             user_agent: Some("test-agent".to_string()),
             client_type: client_type.clone(),
             version: "1.0.0".to_string(),
+            user_id: None,
         };
 
         // Test serialization
@@ -222,4 +226,151 @@ async fn test_client_info_types() {
             _ => panic!("Client type mismatch"),
         }
     }
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_presence_is_shared_within_a_project() {
+    use super::websocket_server::WebSocketServer;
+    use futures_util::{SinkExt, StreamExt};
+    use std::sync::Arc;
+    use tokio::time::{timeout, Duration};
+    use tokio_tungstenite::tungstenite::Message;
+
+    let manager = Arc::new(WebSocketManager::new());
+    let server = Arc::new(WebSocketServer::with_manager("127.0.0.1:0".parse().unwrap(), manager.clone()));
+    let listener = server.bind().await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let serving = server.clone();
+    tokio::spawn(async move { serving.serve(listener).await });
+
+    let auth = |user: &str| {
+        let message = WebSocketMessage::Auth {
+            token: None,
+            project_id: "p1".to_string(),
+            client_info: ClientInfo {
+                user_agent: None,
+                client_type: ClientType::IDE,
+                version: "1.0.0".to_string(),
+                user_id: Some(user.to_string()),
+            },
+        };
+        Message::Text(serde_json::to_string(&message).unwrap())
+    };
+    // Next presence event, skipping other messages
+    async fn next_presence<S>(stream: &mut S) -> (PresenceStatus, Option<String>, Option<EntityRef>)
+    where
+        S: futures_util::Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+    {
+        loop {
+            let message = timeout(Duration::from_secs(2), stream.next()).await.unwrap().unwrap().unwrap();
+            if let Ok(WebSocketMessage::Presence { status, user_id, entity, .. }) =
+                serde_json::from_str(message.to_text().unwrap())
+            {
+                return (status, user_id, entity);
+            }
+        }
+    }
+
+    let (mut ana, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+    ana.send(auth("ana")).await.unwrap();
+    // Authenticated before the second client joins
+    timeout(Duration::from_secs(2), ana.next()).await.unwrap();
+    let (mut ben, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+    ben.send(auth("ben")).await.unwrap();
+    assert_eq!(next_presence(&mut ana).await.0, PresenceStatus::Joined);
+
+    let activity = WebSocketMessage::Activity {
+        entity_type: "business_rule".to_string(),
+        entity_id: "rule-1".to_string(),
+        editing: true,
+    };
+    ben.send(Message::Text(serde_json::to_string(&activity).unwrap())).await.unwrap();
+    let (status, user_id, entity) = next_presence(&mut ana).await;
+    assert_eq!(status, PresenceStatus::Editing);
+    assert_eq!(user_id.as_deref(), Some("ben"));
+    assert_eq!(entity.unwrap().entity_id, "rule-1");
+
+    let clients = manager.active_clients(Some("p1"));
+    assert_eq!(clients.len(), 2);
+    assert_eq!(clients[1].user_id.as_deref(), Some("ben"));
+    assert_eq!(clients[1].editing[0].entity.entity_type, "business_rule");
+    assert!(manager.active_clients(Some("p2")).is_empty());
+
+    ben.close(None).await.unwrap();
+    assert_eq!(next_presence(&mut ana).await.0, PresenceStatus::Left);
+    assert_eq!(manager.active_clients(None).len(), 1);
+}
+
+#[tokio::test]
+async fn test_full_send_queue_drops_or_disconnects() {
+    use super::websocket_manager::ClientSender;
+    use crate::config::SlowClientPolicy;
+    use tokio::sync::mpsc;
+
+    let ping = || WebSocketMessage::Ping { timestamp: Utc::now() };
+
+    let (sender, _receiver) = mpsc::channel(2);
+    let dropping = ClientSender::new(Uuid::new_v4(), sender, SlowClientPolicy::Drop);
+    for _ in 0..3 {
+        dropping.send(ping()).unwrap();
+    }
+    assert_eq!(dropping.queued(), 2);
+    assert_eq!(dropping.dropped(), 1);
+
+    let (sender, receiver) = mpsc::channel(2);
+    let disconnecting = ClientSender::new(Uuid::new_v4(), sender, SlowClientPolicy::Disconnect);
+    disconnecting.send(ping()).unwrap();
+    disconnecting.send(ping()).unwrap();
+    assert!(disconnecting.send(ping()).is_err());
+    assert_eq!(disconnecting.dropped(), 0);
+
+    drop(receiver);
+    assert!(disconnecting.send(ping()).is_err());
+}
+
+#[tokio::test]
+async fn test_silent_clients_are_disconnected() {
+    use super::websocket_server::{WebSocketConfig, WebSocketServer};
+    use futures_util::{SinkExt, StreamExt};
+    use std::sync::Arc;
+    use tokio::time::{timeout, Duration};
+    use tokio_tungstenite::tungstenite::Message;
+
+    let config = WebSocketConfig {
+        heartbeat_interval: Duration::from_millis(50),
+        heartbeat_timeout: Duration::from_millis(200),
+        ..WebSocketConfig::default()
+    };
+    let manager = Arc::new(WebSocketManager::new().with_config(config));
+    let server = Arc::new(WebSocketServer::with_manager("127.0.0.1:0".parse().unwrap(), manager.clone()));
+    let listener = server.bind().await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let serving = server.clone();
+    tokio::spawn(async move { serving.serve(listener).await });
+
+    let (mut client, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+    let auth = WebSocketMessage::Auth {
+        token: None,
+        project_id: "p1".to_string(),
+        client_info: ClientInfo {
+            user_agent: None,
+            client_type: ClientType::CLI,
+            version: "1.0.0".to_string(),
+            user_id: None,
+        },
+    };
+    client.send(Message::Text(serde_json::to_string(&auth).unwrap())).await.unwrap();
+
+    // The client reads pings but never answers them, so the server hangs up
+    let mut pings = 0;
+    let closed = timeout(Duration::from_secs(3), async {
+        while let Some(Ok(message)) = client.next().await {
+            if message.to_text().unwrap_or_default().contains("\"Ping\"") {
+                pings += 1;
+            }
+        }
+    })
+    .await;
+    assert!(closed.is_ok(), "silent client should be disconnected");
+    assert!(pings > 0);
+    assert!(manager.active_clients(None).is_empty());
+}
@@ -1,10 +1,12 @@
+use crate::config::{SlowClientPolicy, WebSocketSettings};
 use crate::services::websocket_manager::WebSocketManager;
 use crate::services::websocket_types::*;
 use anyhow::Result;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::net::{TcpListener, TcpStream};
-use tracing::{error, info};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, info, warn};
 
 /// WebSocket server for real-time synchronization
 pub struct WebSocketServer {
@@ -21,6 +23,15 @@ impl WebSocketServer {
         }
     }
 
+    /// Create a WebSocket server around a configured manager, e.g. one delivering the
+    /// changes of the MCP server's change detection
+    pub fn with_manager(bind_address: SocketAddr, manager: Arc<WebSocketManager>) -> Self {
+        Self {
+            manager,
+            bind_address,
+        }
+    }
+
     /// Get a reference to the WebSocket manager
     pub fn manager(&self) -> Arc<WebSocketManager> {
         self.manager.clone()
@@ -28,14 +39,23 @@ impl WebSocketServer {
 
     /// Start the WebSocket server
     pub async fn start(&self) -> Result<()> {
+        let listener = self.bind().await?;
+        self.serve(listener).await
+    }
+
+    /// Bind the listening socket, so an unavailable address is reported before serving
+    pub async fn bind(&self) -> Result<TcpListener> {
         info!("Starting WebSocket server on {}", self.bind_address);
+        let listener = TcpListener::bind(self.bind_address).await?;
+        info!("WebSocket server listening on {}", listener.local_addr()?);
+        Ok(listener)
+    }
 
+    /// Accept connections on `listener` until the task is dropped
+    pub async fn serve(&self, listener: TcpListener) -> Result<()> {
         // Start the manager
         self.manager.start().await?;
-
-        // Create TCP listener
-        let listener = TcpListener::bind(self.bind_address).await?;
-        info!("WebSocket server listening on {}", self.bind_address);
+        self.forward_changes();
 
         // Accept connections
         loop {
@@ -57,6 +77,27 @@ impl WebSocketServer {
         }
     }
 
+    /// Relay changes from the manager's broadcaster to the subscribed clients
+    fn forward_changes(&self) {
+        let manager = self.manager.clone();
+        let mut batches = manager.broadcaster().subscribe_to_batches();
+        tokio::spawn(async move {
+            loop {
+                match batches.recv().await {
+                    Ok(changes) => {
+                        if let Err(e) = manager.broadcast_changes(changes).await {
+                            error!("Failed to deliver change to WebSocket clients: {}", e);
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("WebSocket clients missed {} batches of changes while delivery lagged", skipped);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
     /// Handle a new connection
     async fn handle_connection(
         manager: Arc<WebSocketManager>,
@@ -116,7 +157,11 @@ pub struct WebSocketConfig {
     pub bind_address: SocketAddr,
     pub max_connections: usize,
     pub heartbeat_interval: std::time::Duration,
+    /// Clients not heard from for this long are disconnected
+    pub heartbeat_timeout: std::time::Duration,
+    /// Depth of each client's send queue
     pub message_queue_size: usize,
+    pub slow_client_policy: SlowClientPolicy,
     pub enable_compression: bool,
 }
 
@@ -126,12 +171,29 @@ impl Default for WebSocketConfig {
             bind_address: "127.0.0.1:8080".parse().unwrap(),
             max_connections: 1000,
             heartbeat_interval: std::time::Duration::from_secs(30),
+            heartbeat_timeout: std::time::Duration::from_secs(90),
             message_queue_size: 1000,
+            slow_client_policy: SlowClientPolicy::Disconnect,
             enable_compression: true,
         }
     }
 }
 
+impl WebSocketConfig {
+    /// Limits from `[websocket]`; an unresolvable address is reported when binding instead
+    pub fn from_settings(settings: &WebSocketSettings) -> Self {
+        let defaults = Self::default();
+        Self {
+            bind_address: settings.bind_address().unwrap_or(defaults.bind_address),
+            heartbeat_interval: std::time::Duration::from_secs(settings.ping_interval_secs.max(1)),
+            heartbeat_timeout: std::time::Duration::from_secs(settings.pong_timeout_secs.max(1)),
+            message_queue_size: settings.max_queue_depth.max(1),
+            slow_client_policy: settings.slow_client_policy,
+            ..defaults
+        }
+    }
+}
+
 /// Helper functions for creating context changes
 pub mod change_helpers {
     use super::*;
@@ -162,6 +224,7 @@ pub mod change_helpers {
                 timestamp: Utc::now(),
                 version: 1,
                 conflict_resolution: None,
+                sequence: None,
             },
         }
     }
@@ -192,6 +255,7 @@ pub mod change_helpers {
                 timestamp: Utc::now(),
                 version,
                 conflict_resolution: None,
+                sequence: None,
             },
         }
     }
@@ -219,6 +283,7 @@ pub mod change_helpers {
                 timestamp: Utc::now(),
                 version: 1,
                 conflict_resolution: None,
+                sequence: None,
             },
         }
     }
@@ -249,6 +314,7 @@ pub mod change_helpers {
                 timestamp: Utc::now(),
                 version: 1,
                 conflict_resolution: None,
+                sequence: None,
             },
         }
     }
@@ -24,9 +24,15 @@ pub enum WebSocketMessage {
         client_id: ClientId,
         message: String,
     },
-    /// Subscribe to specific context changes
+    /// Subscribe to specific context changes, first replaying the stored changes after
+    /// sequence `resume_from` that match `filters`. With `patches_only`, updates that
+    /// carry a JSON Patch delta arrive without `full_entity` on this connection.
     Subscribe {
         filters: SyncFilters,
+        #[serde(default)]
+        resume_from: Option<u64>,
+        #[serde(default)]
+        patches_only: bool,
     },
     /// Unsubscribe from context changes
     Unsubscribe {
@@ -38,6 +44,44 @@ pub enum WebSocketMessage {
         change: ContextChange,
         timestamp: DateTime<Utc>,
     },
+    /// Several context changes in one frame, oldest first, when the server batches
+    /// changes made in quick succession
+    ContextChangeBatch {
+        message_id: MessageId,
+        changes: Vec<ContextChange>,
+        timestamp: DateTime<Utc>,
+    },
+    /// End of the changes replayed for `resume_from`; with `more`, subscribe again from
+    /// `last_sequence` for the rest
+    ReplayComplete {
+        replayed: usize,
+        last_sequence: u64,
+        more: bool,
+    },
+    /// The client started or stopped editing an entity
+    Activity {
+        entity_type: String,
+        entity_id: String,
+        editing: bool,
+    },
+    /// Another client of the same project connected, disconnected, or started or
+    /// stopped editing `entity`
+    Presence {
+        client_id: ClientId,
+        project_id: String,
+        user_id: Option<String>,
+        status: PresenceStatus,
+        entity: Option<EntityRef>,
+        timestamp: DateTime<Utc>,
+    },
+    /// Operations the client queued while offline, applied in order
+    SubmitOperations {
+        operations: Vec<QueuedOperation>,
+    },
+    /// What became of each submitted operation, in submission order
+    OperationResults {
+        results: Vec<OperationResult>,
+    },
     /// Acknowledgment of received message
     Ack {
         message_id: MessageId,
@@ -64,6 +108,9 @@ pub struct ClientInfo {
     pub user_agent: Option<String>,
     pub client_type: ClientType,
     pub version: String,
+    /// Who is using the client, as reported by the client itself, for presence
+    #[serde(default)]
+    pub user_id: Option<String>,
 }
 
 /// Type of client connecting
@@ -76,8 +123,8 @@ pub enum ClientType {
     Other(String),
 }
 
-/// Filters for subscribing to specific context changes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Filters for subscribing to specific context changes; unset fields match everything
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SyncFilters {
     pub project_ids: Option<Vec<String>>,
     pub entity_types: Option<Vec<String>>,
@@ -103,11 +150,24 @@ pub struct ContextChange {
     pub entity_id: String,
     pub project_id: String,
     pub feature_area: Option<String>,
+    /// For updates, an RFC 6902 JSON Patch from the entity's previous version
     pub delta: Option<serde_json::Value>,
     pub full_entity: Option<serde_json::Value>,
     pub metadata: ChangeMetadata,
 }
 
+impl ContextChange {
+    /// This change without `full_entity` when it is an update carrying a patch, for
+    /// clients that apply deltas to the entity they already hold
+    pub fn patch_only(&self) -> ContextChange {
+        let mut change = self.clone();
+        if change.change_type == ChangeType::Update && change.delta.as_ref().is_some_and(|delta| delta.is_array()) {
+            change.full_entity = None;
+        }
+        change
+    }
+}
+
 /// Metadata about the change
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChangeMetadata {
@@ -116,6 +176,9 @@ pub struct ChangeMetadata {
     pub timestamp: DateTime<Utc>,
     pub version: u32,
     pub conflict_resolution: Option<ConflictResolution>,
+    /// Position in the server's change log, for `resume_from` after a reconnect
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<u64>,
 }
 
 /// Conflict resolution information
@@ -144,9 +207,84 @@ pub struct ConnectionStatus {
     pub project_id: String,
     pub subscriptions: Vec<SyncFilters>,
     pub message_queue_size: usize,
+    /// Messages dropped because the client did not keep up
+    pub dropped_messages: u64,
     pub is_healthy: bool,
 }
 
+/// An entity by type and id
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityRef {
+    pub entity_type: String,
+    pub entity_id: String,
+}
+
+/// An edit a client made while offline, submitted with the version it was made from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedOperation {
+    /// Chosen by the client and echoed in the result
+    pub operation_id: String,
+    /// Version of the entity the edit was made from; `None` when the client had not seen
+    /// the entity, as for one it created
+    #[serde(default)]
+    pub base_version: Option<u32>,
+    pub change: ContextChange,
+}
+
+/// What became of a queued operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OperationStatus {
+    /// Applied as is, since the entity had not changed since its base version
+    Applied,
+    /// Merged onto the changes made since its base version
+    Rebased,
+    /// Overlaps changes made since its base version; recorded as a conflict to resolve
+    Conflict,
+    /// Not applied, for the reason in `message`
+    Rejected,
+}
+
+/// Outcome of one queued operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationResult {
+    pub operation_id: String,
+    pub status: OperationStatus,
+    /// Version of the entity after the operation, when it was applied
+    pub version: Option<u32>,
+    /// Conflict recorded for the operation
+    pub conflict_id: Option<String>,
+    pub message: Option<String>,
+}
+
+/// What a presence event reports about a client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PresenceStatus {
+    Joined,
+    Left,
+    Editing,
+    StoppedEditing,
+}
+
+/// An entity a client is editing, and since when
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditingActivity {
+    #[serde(flatten)]
+    pub entity: EntityRef,
+    pub since: DateTime<Utc>,
+}
+
+/// A connected client as listed by `get_active_clients`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveClient {
+    pub client_id: ClientId,
+    pub project_id: String,
+    pub user_id: Option<String>,
+    pub client_type: ClientType,
+    pub connected_at: DateTime<Utc>,
+    pub last_activity: DateTime<Utc>,
+    pub editing: Vec<EditingActivity>,
+}
+
 /// Sync status for a project
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncStatus {
@@ -157,6 +295,20 @@ pub struct SyncStatus {
     pub sync_health: SyncHealth,
 }
 
+/// Messages waiting for or lost by the connected clients, across projects
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeliveryStats {
+    pub connected_clients: usize,
+    /// Messages held to retry after a failed send
+    pub pending_messages: usize,
+    /// Messages in the clients' send queues
+    pub queued_messages: usize,
+    /// Fullest send queue of a single client
+    pub max_client_queue: usize,
+    /// Messages dropped from full send queues of clients still connected
+    pub dropped_messages: u64,
+}
+
 /// Health status of synchronization
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SyncHealth {
@@ -193,10 +345,10 @@ impl SyncFilters {
             }
         }
 
-        // Check feature area filter
+        // Check feature area filter; areas are free text, so ignore case
         if let Some(ref feature_areas) = self.feature_areas {
             if let Some(ref change_feature_area) = change.feature_area {
-                if !feature_areas.contains(change_feature_area) {
+                if !feature_areas.iter().any(|area| area.eq_ignore_ascii_case(change_feature_area)) {
                     return false;
                 }
             } else {
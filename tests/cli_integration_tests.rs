@@ -10,10 +10,13 @@ mod tests {
     use serde_json::Value;
 
     fn get_db_path() -> String {
-        format!(
-            "{}/.config/context-server-rs/context.db",
-            std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string())
-        )
+        let data_home = std::env::var("XDG_DATA_HOME").unwrap_or_else(|_| {
+            format!(
+                "{}/.local/share",
+                std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string())
+            )
+        });
+        format!("{}/context-server-rs/context.db", data_home)
     }
 
     fn run_command(args: &[&str]) -> Result<String, String> {
@@ -183,6 +186,30 @@ mod tests {
         println!("✓ Invalid entity type properly rejected");
     }
 
+    #[test]
+    fn test_completions_cover_entity_types_and_tools() {
+        let output = run_command(&["completions", "bash", "--aliases"])
+            .expect("Failed to generate completions");
+
+        assert!(output.contains("completions"));
+        assert!(output.contains("security_policy"));
+        assert!(output.contains("alias csq='context-server-rs query'"));
+        assert!(output.contains("query_context"));
+        assert!(output.contains("generate_dashboard"));
+
+        let fish = run_command(&["completions", "fish"]).expect("Failed to generate fish completions");
+        assert!(fish.contains("security_policy"));
+        assert!(fish.contains("query_context"));
+
+        println!("✓ Bash completions generated");
+    }
+
+    #[test]
+    fn test_call_rejects_unknown_tool() {
+        let output = run_command(&["call", "not_a_tool"]);
+        assert!(output.is_err(), "Expected error for an unknown tool");
+    }
+
     #[test]
     fn test_get_by_id() {
         // First, list to get an ID
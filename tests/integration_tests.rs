@@ -2,6 +2,10 @@ use tempfile::tempdir;
 
 use context_server_rs::container::AppContainer;
 use context_server_rs::db::init::init_db;
+use context_server_rs::models::api_contract::ApiContract;
+use context_server_rs::models::context::ProjectConvention;
+use context_server_rs::models::custom_entity::{CustomEntity, CustomEntityType};
+use context_server_rs::models::decision_link::{DecisionLink, DecisionLinkType};
 
 #[tokio::test]
 async fn test_database_initialization() {
@@ -375,6 +379,8 @@ async fn test_security_policy_crud_operations() {
     // Test security policy update
     let mut updated_policy = policy.clone();
     updated_policy.compliance_notes = Some("GDPR and CCPA compliant".to_string());
+    updated_policy.severity = Some("high".to_string());
+    updated_policy.classification = Some("confidential".to_string());
 
     let update_result = container
         .context_crud_service
@@ -385,6 +391,24 @@ async fn test_security_policy_crud_operations() {
         update_result.is_ok(),
         "Security policy update should succeed"
     );
+    let retrieved = container
+        .context_crud_service
+        .get_security_policy(&policy.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(retrieved.severity.as_deref(), Some("high"));
+    assert_eq!(retrieved.classification.as_deref(), Some("confidential"));
+    assert_eq!(retrieved.created_at, policy.created_at, "Updates should keep the creation time");
+
+    // Security policies are part of the project's context
+    let context = container
+        .context_query_service
+        .query_context(&project.id, "data_storage", "implement", &[], None)
+        .await
+        .unwrap();
+    assert_eq!(context.security_policies.len(), 1);
+    assert!(context.ranking.iter().any(|item| item.entity_type == "security_policy"));
 
     // Test security policy listing
     let policies = container
@@ -405,6 +429,178 @@ async fn test_security_policy_crud_operations() {
     assert!(deleted.unwrap(), "Security policy should be deleted");
 }
 
+#[tokio::test]
+async fn test_project_convention_crud_operations() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let db_path_str = db_path.to_str().unwrap();
+
+    init_db(db_path_str).unwrap();
+    let container = AppContainer::new(db_path_str).unwrap();
+    let project = container
+        .project_service
+        .create_project("Test Project", Some("A test project"), None)
+        .await
+        .unwrap();
+
+    let conventions = &container.project_convention_repository;
+    let convention = ProjectConvention {
+        id: "conv-commits".to_string(),
+        project_id: project.id.clone(),
+        convention_type: Some("git".to_string()),
+        convention_rule: Some("Commit subjects use the imperative mood".to_string()),
+        pattern: Some("^[A-Z][a-z]+ ".to_string()),
+        good_examples: Some(r#"["Add login form"]"#.to_string()),
+        bad_examples: Some(r#"["added login form"]"#.to_string()),
+        rationale: None,
+        enforcement_level: Some("recommended".to_string()),
+        created_at: Some("2026-01-01T00:00:00+00:00".to_string()),
+    };
+    conventions.create(&convention).await.unwrap();
+
+    let mut updated = convention.clone();
+    updated.enforcement_level = Some("required".to_string());
+    updated.created_at = None;
+    conventions.update(&updated).await.unwrap();
+    let retrieved = conventions.get_by_id(&convention.id).await.unwrap().unwrap();
+    assert_eq!(retrieved.pattern, convention.pattern);
+    assert_eq!(retrieved.enforcement_level.as_deref(), Some("required"));
+    assert_eq!(retrieved.created_at, convention.created_at, "Updates should keep the creation time");
+
+    // Conventions are part of the project's context whatever the feature area
+    let context = container
+        .context_query_service
+        .query_context(&project.id, "billing", "implement", &[], None)
+        .await
+        .unwrap();
+    assert_eq!(context.project_conventions.len(), 1);
+    assert!(context.ranking.iter().any(|item| item.entity_type == "project_convention"));
+
+    assert!(conventions.delete(&convention.id).await.unwrap());
+    assert!(conventions.list_by_project(&project.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_custom_entity_crud_operations() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let db_path_str = db_path.to_str().unwrap();
+
+    init_db(db_path_str).unwrap();
+    let container = AppContainer::new(db_path_str).unwrap();
+    let project = container
+        .project_service
+        .create_project("Test Project", Some("A test project"), None)
+        .await
+        .unwrap();
+
+    let repository = &container.custom_entity_repository;
+    let mut slo = CustomEntityType::new(
+        "slo",
+        serde_json::json!({"type": "object", "properties": {"service": {"type": "string"}}}),
+        vec!["service".to_string()],
+    );
+    slo.created_at = "2026-01-01T00:00:00+00:00".to_string();
+    repository.save_type(&slo).await.unwrap();
+    let mut redefined = slo.clone();
+    redefined.description = Some("Service level objectives".to_string());
+    redefined.created_at = "2026-02-01T00:00:00+00:00".to_string();
+    repository.save_type(&redefined).await.unwrap();
+    let stored = repository.get_type("slo").await.unwrap().unwrap();
+    assert_eq!(stored.description.as_deref(), Some("Service level objectives"));
+    assert_eq!(stored.created_at, slo.created_at, "Redefining a type should keep its creation time");
+
+    let entity = CustomEntity::new(&project.id, "slo", serde_json::json!({"service": "checkout", "objective": 99.9}));
+    repository.create(&entity).await.unwrap();
+    let other = CustomEntity::new(&project.id, "data_contract", serde_json::json!({"dataset": "orders"}));
+    repository.create(&other).await.unwrap();
+
+    let mut updated = repository.get_by_id(&entity.id).await.unwrap().unwrap();
+    assert_eq!(updated.fields, entity.fields);
+    updated.fields["objective"] = serde_json::json!(99.95);
+    repository.update(&updated).await.unwrap();
+
+    let slos = repository.list_by_project(&project.id, Some("slo")).await.unwrap();
+    assert_eq!(slos.len(), 1);
+    assert_eq!(slos[0].fields["objective"], 99.95);
+    assert_eq!(repository.list_by_project(&project.id, None).await.unwrap().len(), 2);
+    assert_eq!(repository.count_of_type("slo").await.unwrap(), 1);
+
+    assert!(repository.delete(&entity.id).await.unwrap());
+    assert_eq!(repository.count_of_type("slo").await.unwrap(), 0);
+    assert!(repository.delete_type("slo").await.unwrap());
+    assert!(repository.list_types().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_api_contract_crud_operations() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let db_path_str = db_path.to_str().unwrap();
+
+    init_db(db_path_str).unwrap();
+    let container = AppContainer::new(db_path_str).unwrap();
+    let repository = &container.api_contract_repository;
+
+    let mut contract = ApiContract::new("p1", "POST", "/orders");
+    contract.request_schema = Some(serde_json::json!({"type": "object", "required": ["items"]}));
+    contract.feature_context_ids = vec!["checkout".to_string()];
+    repository.create(&contract).await.unwrap();
+    repository.create(&ApiContract::new("p1", "GET", "/orders/{order_id}")).await.unwrap();
+    assert!(
+        repository.create(&ApiContract::new("p1", "POST", "/orders")).await.is_err(),
+        "A project should have one contract per method and path"
+    );
+
+    let mut stored = repository.get_by_id(&contract.id).await.unwrap().unwrap();
+    assert_eq!(stored, contract);
+    stored.request_schema = None;
+    stored.error_conventions = Some("422 with a problem+json body".to_string());
+    repository.update(&stored).await.unwrap();
+
+    let contracts = repository.list_by_project("p1").await.unwrap();
+    assert_eq!(contracts.len(), 2);
+    assert_eq!(contracts[0].signature(), "POST /orders");
+    assert_eq!(contracts[0].request_schema, None);
+    assert_eq!(contracts[0].feature_context_ids, vec!["checkout"]);
+
+    assert!(repository.delete(&contract.id).await.unwrap());
+    assert!(repository.get_by_id(&contract.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_decision_link_operations() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let db_path_str = db_path.to_str().unwrap();
+
+    init_db(db_path_str).unwrap();
+    let container = AppContainer::new(db_path_str).unwrap();
+    let repository = &container.decision_link_repository;
+
+    let supersedes = DecisionLink::new("p1", "adr-2", "adr-1", DecisionLinkType::Supersedes);
+    repository.save(&supersedes).await.unwrap();
+    repository.save(&supersedes).await.unwrap();
+    repository
+        .save(&DecisionLink::new("p1", "adr-3", "adr-2", DecisionLinkType::Amends))
+        .await
+        .unwrap();
+    repository
+        .save(&DecisionLink::new("p2", "adr-9", "adr-8", DecisionLinkType::RelatesTo))
+        .await
+        .unwrap();
+
+    let links = repository.find_by_project("p1").await.unwrap();
+    assert_eq!(links.len(), 2, "Saving a link twice should keep one");
+    assert_eq!(links[0].link_type, DecisionLinkType::Supersedes);
+
+    assert!(!repository.delete("adr-2", "adr-1", DecisionLinkType::Amends).await.unwrap());
+    assert!(repository.delete("adr-2", "adr-1", DecisionLinkType::Supersedes).await.unwrap());
+    assert_eq!(repository.delete_by_decision("adr-2").await.unwrap(), 1);
+    assert!(repository.find_by_project("p1").await.unwrap().is_empty());
+    assert_eq!(repository.find_by_project("p2").await.unwrap().len(), 1);
+}
+
 #[tokio::test]
 async fn test_feature_context_crud_operations() {
     let temp_dir = tempdir().unwrap();
@@ -448,6 +644,10 @@ async fn test_feature_context_crud_operations() {
     // Test feature context update
     let mut updated_ctx = feature_ctx.clone();
     updated_ctx.business_purpose = Some("Secure user authentication and session management".to_string());
+    updated_ctx.constraints = Some(r#"["Sessions expire after 30 minutes"]"#.to_string());
+    updated_ctx.related_components = Some(r#"["AuthService", "LoginScreen"]"#.to_string());
+    updated_ctx.status = Some("in_progress".to_string());
+    updated_ctx.owner = Some("identity-team".to_string());
 
     let update_result = container
         .context_crud_service
@@ -458,6 +658,19 @@ async fn test_feature_context_crud_operations() {
         update_result.is_ok(),
         "Feature context update should succeed"
     );
+    assert_eq!(feature_ctx.status.as_deref(), Some("planned"));
+
+    // The feature context named like the feature area comes with its context
+    let context = container
+        .context_query_service
+        .query_context(&project.id, "user-authentication", "implement", &[], None)
+        .await
+        .unwrap();
+    let feature = context.feature_context.expect("Feature context should be included");
+    assert_eq!(feature.description, updated_ctx.business_purpose);
+    assert_eq!(feature.constraints, vec!["Sessions expire after 30 minutes"]);
+    assert_eq!(feature.related_components, vec!["AuthService", "LoginScreen"]);
+    assert_eq!((feature.status.as_deref(), feature.owner.as_deref()), (Some("in_progress"), Some("identity-team")));
 
     // Test feature context listing
     let contexts = container
@@ -713,3 +926,32 @@ async fn test_combined_crud_workflow() {
     let deleted_project = container.project_service.delete_project(&project.id).await.unwrap();
     assert!(deleted_project, "Project should be deleted");
 }
+
+#[tokio::test]
+async fn test_query_cache_follows_config_reload() {
+    use context_server_rs::cache::CacheKeyBuilder;
+
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let db_path_str = db_path.to_str().unwrap();
+
+    init_db(db_path_str).unwrap();
+    let container = AppContainer::new(db_path_str).unwrap();
+
+    let key = CacheKeyBuilder::all_projects();
+    container.query_cache.set(key.clone(), serde_json::json!([]), None);
+    assert!(container.query_cache.get(&key).is_some());
+
+    // Disabling the namespace through a config reload takes effect immediately
+    let mut config = container.config_manager.current();
+    config.cache.policies.insert(
+        "projects".to_string(),
+        context_server_rs::config::CachePolicyConfig {
+            enabled: false,
+            ..Default::default()
+        },
+    );
+    container.config_manager.apply(config);
+
+    assert!(container.query_cache.get(&key).is_none());
+}